@@ -85,6 +85,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap()
             .naive_utc(),
         current_workspace_id: None,
+        asset_region: None,
+        is_service_account: false,
     };
 
     // 获取处理后的头像 URL
@@ -109,6 +111,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap()
             .naive_utc(),
         current_workspace_id: None,
+        asset_region: None,
+        is_service_account: false,
     };
 
     if let Some(processed_avatar_url) =
@@ -135,6 +139,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         description: Some("负责产品开发的团队".to_string()),
         icon_url: Some("team-icons/dev-team.png".to_string()),
         is_private: false,
+        parent_team_id: None,
+        estimate_scale: "fibonacci".to_string(),
+        assignment_mode: "none".to_string(),
+        default_assignee_id: None,
+        last_auto_assignee_id: None,
     };
 
     if let Some(processed_icon_url) = team.get_processed_icon_url(&asset_helper) {
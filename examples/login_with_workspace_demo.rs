@@ -9,7 +9,6 @@
 /// ```bash
 /// cargo run --example login_with_workspace_demo
 /// ```
-use reqwest;
 use serde::Serialize;
 use serde_json::Value;
 
@@ -34,7 +33,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let login_response = client
-        .post(&format!("{}/auth/login", base_url))
+        .post(format!("{}/auth/login", base_url))
         .json(&login_payload)
         .send()
         .await?;
@@ -10,7 +10,6 @@
 /// ```bash
 /// cargo run --example logout_demo
 /// ```
-use reqwest;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -20,6 +19,7 @@ struct LoginRequest {
     password: String,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 struct LoginResponse {
     access_token: String,
@@ -43,7 +43,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let login_response = client
-        .post(&format!("{}/auth/login", base_url))
+        .post(format!("{}/auth/login", base_url))
         .json(&login_payload)
         .send()
         .await?;
@@ -76,7 +76,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 步骤 2: 访问受保护资源（获取用户资料）
     println!("2. 使用 token 访问受保护资源...");
     let profile_response = client
-        .get(&format!("{}/auth/profile", base_url))
+        .get(format!("{}/auth/profile", base_url))
         .header("Authorization", format!("Bearer {}", access_token))
         .send()
         .await?;
@@ -95,7 +95,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 步骤 3: 用户登出
     println!("3. 执行登出操作...");
     let logout_response = client
-        .post(&format!("{}/auth/logout", base_url))
+        .post(format!("{}/auth/logout", base_url))
         .header("Authorization", format!("Bearer {}", access_token))
         .send()
         .await?;
@@ -118,7 +118,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 步骤 4: 验证登出后无法访问受保护资源
     println!("4. 验证登出后的状态...");
     let verify_response = client
-        .get(&format!("{}/auth/profile", base_url))
+        .get(format!("{}/auth/profile", base_url))
         .header("Authorization", format!("Bearer {}", access_token))
         .send()
         .await?;
@@ -26,6 +26,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap()
             .naive_utc(),
         current_workspace_id: None,
+        asset_region: None,
+        is_service_account: false,
     };
 
     println!("=== 性能测试：Avatar URL 处理 ===");
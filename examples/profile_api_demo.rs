@@ -31,6 +31,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap()
             .naive_utc(),
         current_workspace_id: Some(Uuid::new_v4()),
+        asset_region: None,
+        is_service_account: false,
     };
 
     // 模拟团队数据
@@ -61,6 +63,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         name: user.name,
         avatar_url: processed_avatar_url,
         current_workspace_id: user.current_workspace_id,
+        asset_region: None,
         workspaces: vec![workspace_info],
         teams: vec![team_info],
     };
@@ -129,6 +132,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap()
             .naive_utc(),
         current_workspace_id: None,
+        asset_region: None,
+        is_service_account: false,
     };
 
     println!("场景1 - 内部头像路径:");
@@ -153,6 +158,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap()
             .naive_utc(),
         current_workspace_id: None,
+        asset_region: None,
+        is_service_account: false,
     };
 
     println!("场景2 - 外部头像链接:");
@@ -177,6 +184,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap()
             .naive_utc(),
         current_workspace_id: None,
+        asset_region: None,
+        is_service_account: false,
     };
 
     println!("场景3 - 无头像:");
@@ -94,6 +94,7 @@ fn demonstrate_commands() -> Result<(), Box<dyn std::error::Error>> {
             description: Some("后端开发团队".to_string()),
             icon_url: Some("https://example.com/icon.png".to_string()),
             is_private: false,
+            parent_team_id: None,
         },
         request_id: Some("req-004".to_string()),
     };
@@ -107,6 +108,9 @@ fn demonstrate_commands() -> Result<(), Box<dyn std::error::Error>> {
             description: Some("全栈开发团队".to_string()),
             icon_url: None,
             is_private: None,
+            estimate_scale: None,
+            assignment_mode: None,
+            default_assignee_id: None,
         },
         request_id: Some("req-005".to_string()),
     };
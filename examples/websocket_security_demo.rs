@@ -26,13 +26,79 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         server_host: "localhost".to_string(),
         server_port: 8000,
         cors_origins: vec!["*".to_string()],
+        cors_allow_credentials: false,
+        cors_max_age_secs: 3600,
+        app_env: "development".to_string(),
+        security_headers_enabled: true,
+        hsts_max_age_secs: None,
+        hsts_include_subdomains: false,
+        referrer_policy: "strict-origin-when-cross-origin".to_string(),
+        content_security_policy: "default-src 'self'".to_string(),
         jwt_secret: "your-super-secret-jwt-key-for-signing-messages".to_string(),
         jwt_access_token_expires_in: 3600,
         jwt_refresh_token_expires_in: 604800,
         log_level: "info".to_string(),
         log_format: "json".to_string(),
         assets_url: "http://localhost:8000/assets".to_string(),
+        assets_region_urls: vec![],
+        assets_region_fallbacks: vec![],
+        assets_region_probe_secs: 30,
         bcrypt_cost: 4,
+        admin_emails: vec![],
+        metrics_token: None,
+        ws_metrics_sink: "memory".to_string(),
+        ws_metrics_retention_hours: 168,
+        ws_fanout: "none".to_string(),
+        ws_compression_threshold_bytes: 8 * 1024,
+        notification_batch_windows: vec![],
+        notification_email_windows: vec![],
+        email_reply_domain: None,
+        inbound_email_secret: None,
+        storage_endpoint: None,
+        storage_bucket: None,
+        storage_region: "us-east-1".to_string(),
+        storage_access_key: None,
+        storage_secret_key: None,
+        storage_path_style: true,
+        attachment_max_bytes: 25 * 1024 * 1024,
+        upload_session_max_bytes: 5 * 1024 * 1024 * 1024,
+        upload_session_ttl_secs: 86400,
+        rate_limit_enabled: true,
+        rate_limit_window_secs: 60,
+        rate_limit_per_user: 600,
+        rate_limit_per_ip: 300,
+        rate_limit_trust_proxy: false,
+        oauth_redirect_base_url: "http://localhost:8000".to_string(),
+        oauth_google_client_id: None,
+        oauth_google_client_secret: None,
+        oauth_github_client_id: None,
+        oauth_github_client_secret: None,
+        email_backend: "log".to_string(),
+        email_from: "Momentum <no-reply@localhost>".to_string(),
+        app_url: "http://localhost:3000".to_string(),
+        smtp_host: None,
+        smtp_port: 587,
+        smtp_username: None,
+        smtp_password: None,
+        smtp_implicit_tls: false,
+        email_domain_dns_url: "https://cloudflare-dns.com/dns-query".to_string(),
+        ses_region: "us-east-1".to_string(),
+        ses_access_key: None,
+        ses_secret_key: None,
+        push_vapid_subject: None,
+        push_vapid_public_key: None,
+        push_vapid_private_key: None,
+        apns_key_id: None,
+        apns_team_id: None,
+        apns_private_key: None,
+        apns_topic: None,
+        apns_sandbox: false,
+        fcm_project_id: None,
+        fcm_client_email: None,
+        fcm_private_key: None,
+        scheduler_jobs: String::new(),
+        telemetry_enabled: false,
+        telemetry_endpoint: None,
     };
 
     println!("🚀 WebSocket安全功能演示");
@@ -0,0 +1,4 @@
+-- Drop workspace_holidays table
+DROP INDEX IF EXISTS idx_workspace_holidays_holiday_date;
+DROP INDEX IF EXISTS idx_workspace_holidays_workspace_id;
+DROP TABLE IF EXISTS workspace_holidays;
@@ -0,0 +1,16 @@
+-- Create workspace_holidays table
+CREATE TABLE workspace_holidays (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    holiday_date DATE NOT NULL,
+    region VARCHAR(50), -- Region code of the imported holiday set, e.g. CN, US
+    source VARCHAR(20) NOT NULL DEFAULT 'manual', -- manual, ics
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(workspace_id, holiday_date, name)
+);
+
+-- Create indexes for performance
+CREATE INDEX idx_workspace_holidays_workspace_id ON workspace_holidays(workspace_id);
+CREATE INDEX idx_workspace_holidays_holiday_date ON workspace_holidays(holiday_date);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_notifications_email_pending;
+DROP INDEX IF EXISTS idx_notifications_coalesce;
+DROP INDEX IF EXISTS idx_notifications_recipient_created;
+DROP TABLE IF EXISTS notifications;
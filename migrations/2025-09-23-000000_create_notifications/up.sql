@@ -0,0 +1,25 @@
+-- Create notifications table
+-- Repeated events for the same recipient/entity within a batching window are
+-- coalesced into one row; event_count tracks how many were folded in.
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    recipient_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    event_type VARCHAR(50) NOT NULL, -- issue_updated, issue_assigned, comment_created, ...
+    entity_type VARCHAR(50) NOT NULL, -- issue, comment, invitation, ...
+    entity_id UUID NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    body TEXT,
+    event_count INTEGER NOT NULL DEFAULT 1,
+    read_at TIMESTAMPTZ,
+    email_pending BOOLEAN NOT NULL DEFAULT TRUE,
+    emailed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for performance
+CREATE INDEX idx_notifications_recipient_created ON notifications(recipient_id, created_at DESC);
+CREATE INDEX idx_notifications_coalesce ON notifications(recipient_id, event_type, entity_id) WHERE read_at IS NULL;
+CREATE INDEX idx_notifications_email_pending ON notifications(updated_at) WHERE email_pending;
@@ -0,0 +1,13 @@
+-- Create issue_views table
+-- One row per user/issue, bumped every time the user opens the issue.
+CREATE TABLE issue_views (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    first_viewed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_viewed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    view_count INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (user_id, issue_id)
+);
+
+-- Create indexes for performance
+CREATE INDEX idx_issue_views_issue_last_viewed ON issue_views(issue_id, last_viewed_at DESC);
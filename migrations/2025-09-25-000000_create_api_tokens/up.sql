@@ -0,0 +1,29 @@
+-- Create api_tokens table (personal access tokens)
+-- Only a SHA-256 hash of the token is stored; the plaintext is shown once on creation.
+CREATE TABLE api_tokens (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    token_prefix VARCHAR(16) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    tier VARCHAR(20) NOT NULL DEFAULT 'free', -- free, pro, unlimited
+    last_used_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Daily API usage rollup per client (personal access token or OAuth client) and endpoint
+CREATE TABLE api_usage_daily (
+    client_type VARCHAR(20) NOT NULL, -- personal_token, oauth_client
+    client_id UUID NOT NULL,
+    day DATE NOT NULL,
+    endpoint VARCHAR(255) NOT NULL, -- "GET /issues/:issue_id"
+    request_count BIGINT NOT NULL DEFAULT 0,
+    error_count BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (client_type, client_id, day, endpoint)
+);
+
+-- Create indexes for performance
+CREATE INDEX idx_api_tokens_user_id ON api_tokens(user_id);
+CREATE INDEX idx_api_usage_daily_client_day ON api_usage_daily(client_type, client_id, day DESC);
@@ -0,0 +1,58 @@
+-- OAuth2 provider: third-party apps registered by users
+-- Scopes and redirect URIs are stored space-separated, as in the OAuth wire format.
+CREATE TABLE oauth_apps (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    client_id VARCHAR(64) NOT NULL UNIQUE,
+    client_secret_hash VARCHAR(64) NOT NULL,
+    redirect_uris TEXT NOT NULL,
+    scopes TEXT NOT NULL, -- scopes the app may request
+    tier VARCHAR(20) NOT NULL DEFAULT 'free',
+    disabled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A user's consent for an app, with the scopes granted
+CREATE TABLE oauth_grants (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    app_id UUID NOT NULL REFERENCES oauth_apps(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    scopes TEXT NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (app_id, user_id)
+);
+
+-- Short-lived, single-use authorization codes (only hashes are stored)
+CREATE TABLE oauth_authorization_codes (
+    code_hash VARCHAR(64) PRIMARY KEY,
+    grant_id UUID NOT NULL REFERENCES oauth_grants(id) ON DELETE CASCADE,
+    redirect_uri TEXT NOT NULL,
+    scopes TEXT NOT NULL,
+    code_challenge VARCHAR(128), -- PKCE, S256 only
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Access/refresh token pairs issued to apps (only hashes are stored)
+CREATE TABLE oauth_access_tokens (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    grant_id UUID NOT NULL REFERENCES oauth_grants(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    refresh_token_hash VARCHAR(64) UNIQUE,
+    scopes TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    refresh_expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for performance
+CREATE INDEX idx_oauth_apps_owner_id ON oauth_apps(owner_id);
+CREATE INDEX idx_oauth_grants_user_id ON oauth_grants(user_id);
+CREATE INDEX idx_oauth_access_tokens_grant_id ON oauth_access_tokens(grant_id);
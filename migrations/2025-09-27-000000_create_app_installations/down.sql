@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS webhook_deliveries;
+DROP TABLE IF EXISTS app_installations;
+
+ALTER TABLE oauth_apps
+    DROP COLUMN IF EXISTS webhook_events,
+    DROP COLUMN IF EXISTS webhook_secret,
+    DROP COLUMN IF EXISTS webhook_url;
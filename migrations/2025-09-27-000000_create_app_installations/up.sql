@@ -0,0 +1,38 @@
+-- App marketplace: per-workspace installations of OAuth apps and outgoing webhooks
+ALTER TABLE oauth_apps
+    ADD COLUMN webhook_url TEXT,
+    ADD COLUMN webhook_secret VARCHAR(128),
+    ADD COLUMN webhook_events TEXT NOT NULL DEFAULT ''; -- space-separated event subscriptions
+
+CREATE TABLE app_installations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    app_id UUID NOT NULL REFERENCES oauth_apps(id) ON DELETE CASCADE,
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    installed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    scopes TEXT NOT NULL,
+    uninstalled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (app_id, workspace_id)
+);
+
+CREATE INDEX idx_app_installations_workspace ON app_installations(workspace_id) WHERE uninstalled_at IS NULL;
+
+-- Outbox of webhook deliveries; the worker sends due rows and retries with backoff
+CREATE TABLE webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    app_id UUID NOT NULL REFERENCES oauth_apps(id) ON DELETE CASCADE,
+    installation_id UUID REFERENCES app_installations(id) ON DELETE SET NULL,
+    event VARCHAR(64) NOT NULL,
+    payload TEXT NOT NULL, -- JSON body as sent
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, delivered, failed
+    attempts INTEGER NOT NULL DEFAULT 0,
+    response_status INTEGER,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX idx_webhook_deliveries_app ON webhook_deliveries(app_id, created_at DESC);
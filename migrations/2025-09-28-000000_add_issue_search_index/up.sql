@@ -0,0 +1,9 @@
+-- Full-text search over issue titles and descriptions.
+-- The 'simple' configuration does no stemming or stop-word removal, so it
+-- behaves the same for every language used in issue text. Titles weigh more
+-- than descriptions when ranking. Queries must repeat this expression exactly
+-- for the planner to use the index.
+CREATE INDEX idx_issues_search ON issues USING GIN (
+    (setweight(to_tsvector('simple', title), 'A')
+     || setweight(to_tsvector('simple', coalesce(description, '')), 'B'))
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS member_import_rows;
+DROP TABLE IF EXISTS member_imports;
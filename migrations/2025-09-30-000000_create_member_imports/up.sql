@@ -0,0 +1,25 @@
+-- Bulk workspace member imports from CSV; the worker applies pending rows
+CREATE TABLE member_imports (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    content_hash VARCHAR(64) NOT NULL, -- SHA-256 of the uploaded CSV; re-uploads return the same import
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, running, completed
+    total_rows INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    UNIQUE (workspace_id, content_hash)
+);
+
+CREATE TABLE member_import_rows (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    import_id UUID NOT NULL REFERENCES member_imports(id) ON DELETE CASCADE,
+    row_number INTEGER NOT NULL, -- 1-based, excluding the header
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(20) NOT NULL,
+    team_ids TEXT NOT NULL DEFAULT '', -- space-separated team ids resolved at upload
+    status VARCHAR(20) NOT NULL, -- invalid, pending, added, invited, skipped, failed
+    message TEXT,
+    UNIQUE (import_id, row_number)
+);
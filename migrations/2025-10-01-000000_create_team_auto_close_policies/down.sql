@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS issue_auto_close_warnings;
+DROP TABLE IF EXISTS team_auto_close_policies;
@@ -0,0 +1,19 @@
+-- Per-team policy that closes issues left inactive in the listed states
+CREATE TABLE team_auto_close_policies (
+    team_id UUID PRIMARY KEY REFERENCES teams(id) ON DELETE CASCADE,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    state_ids TEXT NOT NULL, -- space-separated workflow state ids watched for inactivity
+    inactive_days INTEGER NOT NULL CHECK (inactive_days > 0),
+    warning_days INTEGER NOT NULL CHECK (warning_days > 0 AND warning_days < inactive_days),
+    close_state_id UUID NOT NULL REFERENCES workflow_states(id) ON DELETE CASCADE,
+    exempt_label_id UUID REFERENCES labels(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- When the "will be closed" warning was sent; a warning older than the
+-- issue's last activity no longer counts
+CREATE TABLE issue_auto_close_warnings (
+    issue_id UUID PRIMARY KEY REFERENCES issues(id) ON DELETE CASCADE,
+    warned_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
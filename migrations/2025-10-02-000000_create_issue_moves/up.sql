@@ -0,0 +1,16 @@
+-- History of issues moved between teams; keeps the keys an issue had before
+CREATE TABLE issue_moves (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    from_team_id UUID REFERENCES teams(id) ON DELETE SET NULL,
+    to_team_id UUID REFERENCES teams(id) ON DELETE SET NULL,
+    from_key VARCHAR(32) NOT NULL,
+    to_key VARCHAR(32) NOT NULL,
+    from_state_id UUID,
+    to_state_id UUID,
+    moved_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    moved_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_moves_issue_id ON issue_moves(issue_id);
+CREATE INDEX idx_issue_moves_from_key ON issue_moves(from_key);
@@ -0,0 +1,15 @@
+-- Directed links between issues. `blocked_by` is stored as the inverse
+-- `blocks` row, so only blocks / duplicates / relates_to appear here.
+CREATE TABLE issue_relations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    related_issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    relation_type VARCHAR(32) NOT NULL,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT issue_relations_not_self CHECK (issue_id <> related_issue_id),
+    CONSTRAINT issue_relations_unique UNIQUE (issue_id, related_issue_id, relation_type)
+);
+
+CREATE INDEX idx_issue_relations_issue_id ON issue_relations(issue_id);
+CREATE INDEX idx_issue_relations_related_issue_id ON issue_relations(related_issue_id);
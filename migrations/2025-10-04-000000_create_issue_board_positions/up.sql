@@ -0,0 +1,9 @@
+-- Manual ordering of issues on a board column. `version` increases on every
+-- reorder so concurrent moves of the same issue can be detected.
+CREATE TABLE issue_board_positions (
+    issue_id UUID PRIMARY KEY REFERENCES issues(id) ON DELETE CASCADE,
+    position DOUBLE PRECISION NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
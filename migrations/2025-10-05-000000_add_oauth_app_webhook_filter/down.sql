@@ -0,0 +1 @@
+ALTER TABLE oauth_apps DROP COLUMN IF EXISTS webhook_filter;
@@ -0,0 +1,2 @@
+-- Optional filter expression evaluated before queueing a webhook delivery
+ALTER TABLE oauth_apps ADD COLUMN webhook_filter TEXT;
@@ -0,0 +1,11 @@
+ALTER TABLE oauth_apps ADD COLUMN webhook_secret VARCHAR(128);
+
+UPDATE oauth_apps a
+SET webhook_secret = (
+    SELECT k.secret FROM webhook_signing_keys k
+    WHERE k.app_id = a.id
+    ORDER BY k.created_at DESC
+    LIMIT 1
+);
+
+DROP TABLE IF EXISTS webhook_signing_keys;
@@ -0,0 +1,18 @@
+-- Webhook signing secrets; several can be active while a rotation is in its grace period
+CREATE TABLE webhook_signing_keys (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    app_id UUID NOT NULL REFERENCES oauth_apps(id) ON DELETE CASCADE,
+    key_id VARCHAR(32) NOT NULL UNIQUE, -- sent as kid= in the signature header
+    secret VARCHAR(128) NOT NULL,
+    expires_at TIMESTAMPTZ, -- NULL while the key is current
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_webhook_signing_keys_app ON webhook_signing_keys(app_id, created_at DESC);
+
+INSERT INTO webhook_signing_keys (app_id, key_id, secret)
+SELECT id, 'whk_' || substr(md5(id::text), 1, 16), webhook_secret
+FROM oauth_apps
+WHERE webhook_secret IS NOT NULL;
+
+ALTER TABLE oauth_apps DROP COLUMN webhook_secret;
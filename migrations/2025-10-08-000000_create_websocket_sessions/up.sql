@@ -0,0 +1,14 @@
+-- WebSocket connection sessions, used for workspace adoption analytics
+CREATE TABLE websocket_sessions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    connection_id VARCHAR(64) NOT NULL,
+    client VARCHAR(50) NOT NULL, -- web, desktop, mobile, or the client's own name
+    user_agent TEXT,
+    connected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    disconnected_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_websocket_sessions_workspace_connected ON websocket_sessions(workspace_id, connected_at);
+CREATE INDEX idx_websocket_sessions_user ON websocket_sessions(user_id);
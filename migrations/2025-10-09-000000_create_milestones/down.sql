@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS milestone_issues;
+DROP TABLE IF EXISTS milestones;
@@ -0,0 +1,10 @@
+DELETE FROM webhook_deliveries WHERE webhook_id IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_webhook_deliveries_webhook;
+
+ALTER TABLE webhook_deliveries
+    DROP CONSTRAINT IF EXISTS webhook_deliveries_one_target,
+    DROP COLUMN IF EXISTS webhook_id,
+    ALTER COLUMN app_id SET NOT NULL;
+
+DROP TABLE IF EXISTS webhooks;
@@ -0,0 +1,24 @@
+-- Workspace webhooks: URLs an admin registers to receive workspace events,
+-- signed with a per-webhook secret. Deliveries share the app webhook outbox.
+CREATE TABLE webhooks (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    secret VARCHAR(128) NOT NULL,
+    events TEXT NOT NULL DEFAULT '', -- space-separated event subscriptions
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_webhooks_workspace ON webhooks(workspace_id);
+
+-- A delivery goes either to an app or to a workspace webhook
+ALTER TABLE webhook_deliveries
+    ALTER COLUMN app_id DROP NOT NULL,
+    ADD COLUMN webhook_id UUID REFERENCES webhooks(id) ON DELETE CASCADE,
+    ADD CONSTRAINT webhook_deliveries_one_target CHECK (num_nonnulls(app_id, webhook_id) = 1);
+
+CREATE INDEX idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at DESC)
+    WHERE webhook_id IS NOT NULL;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS issue_links;
+DROP TABLE IF EXISTS github_integrations;
@@ -0,0 +1,29 @@
+-- Inbound GitHub integration: one per workspace. GitHub signs its webhook
+-- calls with `webhook_secret`; when a pull request merges, linked issues move
+-- to the state named `merge_state_name` in their workflow, if set.
+CREATE TABLE github_integrations (
+    workspace_id UUID PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+    webhook_secret VARCHAR(128) NOT NULL,
+    merge_state_name VARCHAR(255),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Commits and pull requests that mention an issue key
+CREATE TABLE issue_links (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    provider VARCHAR(20) NOT NULL, -- github
+    kind VARCHAR(20) NOT NULL, -- commit, pull_request
+    external_id VARCHAR(255) NOT NULL, -- commit sha, or owner/repo#number
+    url TEXT NOT NULL,
+    title TEXT NOT NULL,
+    state VARCHAR(20), -- open, closed, merged; NULL for commits
+    author VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (issue_id, provider, kind, external_id)
+);
+
+CREATE INDEX idx_issue_links_issue ON issue_links(issue_id, created_at DESC);
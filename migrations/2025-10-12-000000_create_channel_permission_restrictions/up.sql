@@ -0,0 +1,11 @@
+-- Permissions a workspace withholds from API callers. A row denies
+-- `permission` to requests authenticated through `channel` (api_token or
+-- oauth_app), whatever the caller's role; interactive sessions are unaffected.
+CREATE TABLE channel_permission_restrictions (
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    channel VARCHAR(20) NOT NULL,
+    permission VARCHAR(50) NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (workspace_id, channel, permission)
+);
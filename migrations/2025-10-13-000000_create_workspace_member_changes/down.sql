@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS record_workspace_member_change ON workspace_members;
+DROP FUNCTION IF EXISTS record_workspace_member_change();
+DROP TABLE IF EXISTS workspace_member_changes;
@@ -0,0 +1,40 @@
+-- Append-only log of membership changes, written by a trigger so every code
+-- path that adds, removes or re-roles a member is captured. Clients keep the
+-- highest `id` they have seen and ask for the changes after it.
+CREATE TABLE workspace_member_changes (
+    id BIGSERIAL PRIMARY KEY,
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    change VARCHAR(20) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_workspace_member_changes_workspace
+    ON workspace_member_changes (workspace_id, id);
+
+CREATE OR REPLACE FUNCTION record_workspace_member_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO workspace_member_changes (workspace_id, user_id, change)
+        VALUES (NEW.workspace_id, NEW.user_id, 'member_added');
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF OLD.role IS DISTINCT FROM NEW.role THEN
+            INSERT INTO workspace_member_changes (workspace_id, user_id, change)
+            VALUES (NEW.workspace_id, NEW.user_id, 'role_changed');
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- Members deleted along with their workspace need no tombstone
+        IF EXISTS (SELECT 1 FROM workspaces WHERE id = OLD.workspace_id) THEN
+            INSERT INTO workspace_member_changes (workspace_id, user_id, change)
+            VALUES (OLD.workspace_id, OLD.user_id, 'member_removed');
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_workspace_member_change
+AFTER INSERT OR UPDATE OR DELETE ON workspace_members
+FOR EACH ROW
+EXECUTE FUNCTION record_workspace_member_change();
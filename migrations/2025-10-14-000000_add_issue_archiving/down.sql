@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS audit_log;
+DROP INDEX IF EXISTS idx_issues_archive_batch;
+ALTER TABLE issues
+    DROP COLUMN IF EXISTS archive_batch_id,
+    DROP COLUMN IF EXISTS archived_at;
+DROP TABLE IF EXISTS issue_archive_batches;
@@ -0,0 +1,38 @@
+-- Bulk archive requests; the worker archives the issues matching `filter`
+-- and the batch can be undone for a while afterwards
+CREATE TABLE issue_archive_batches (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    filter TEXT NOT NULL, -- JSON-encoded IssueArchiveFilter
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, running, completed, undone
+    issue_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    undone_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_issue_archive_batches_workspace
+    ON issue_archive_batches (workspace_id, created_at DESC);
+
+-- Archived issues stay readable by id but drop out of lists, search and sync
+ALTER TABLE issues
+    ADD COLUMN archived_at TIMESTAMPTZ,
+    ADD COLUMN archive_batch_id UUID REFERENCES issue_archive_batches(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_issues_archive_batch
+    ON issues (archive_batch_id) WHERE archive_batch_id IS NOT NULL;
+
+-- Who did what to a workspace, for actions worth reviewing later
+CREATE TABLE audit_log (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(100) NOT NULL,
+    target_type VARCHAR(50) NOT NULL,
+    target_id UUID,
+    details TEXT, -- JSON
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_log_workspace ON audit_log (workspace_id, created_at DESC);
@@ -0,0 +1,6 @@
+-- Sub-teams: a team may sit below a parent team of the same workspace.
+-- Members of a parent team are members of every team below it, and a
+-- sub-team of a private team is private too.
+ALTER TABLE teams ADD COLUMN parent_team_id UUID REFERENCES teams(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_teams_parent_team_id ON teams(parent_team_id) WHERE parent_team_id IS NOT NULL;
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS cross_workspace_issue_relations;
+DROP INDEX IF EXISTS idx_workspaces_organization_id;
+ALTER TABLE workspaces DROP COLUMN IF EXISTS organization_id;
+DROP TABLE IF EXISTS organizations;
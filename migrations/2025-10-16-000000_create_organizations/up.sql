@@ -0,0 +1,34 @@
+-- Organizations group workspaces. When the organization allows it, issues
+-- can be related to issues in sibling workspaces.
+CREATE TABLE organizations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(255) NOT NULL,
+    allow_cross_workspace_links BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE workspaces
+    ADD COLUMN organization_id UUID REFERENCES organizations(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_workspaces_organization_id ON workspaces(organization_id)
+    WHERE organization_id IS NOT NULL;
+
+-- Relations between issues of two workspaces. Stored like issue_relations;
+-- `workspace_id` is the workspace the relation was created from.
+CREATE TABLE cross_workspace_issue_relations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    related_issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    relation_type VARCHAR(32) NOT NULL,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT cross_workspace_issue_relations_unique
+        UNIQUE (issue_id, related_issue_id, relation_type)
+);
+
+CREATE INDEX idx_cross_workspace_issue_relations_issue_id
+    ON cross_workspace_issue_relations(issue_id);
+CREATE INDEX idx_cross_workspace_issue_relations_related_issue_id
+    ON cross_workspace_issue_relations(related_issue_id);
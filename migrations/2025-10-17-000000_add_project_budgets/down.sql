@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS project_cost_entries;
+DROP TABLE IF EXISTS member_cost_rates;
+ALTER TABLE projects
+    DROP COLUMN IF EXISTS budget_alert_level,
+    DROP COLUMN IF EXISTS budget_alert_percent,
+    DROP COLUMN IF EXISTS budget_amount_cents,
+    DROP COLUMN IF EXISTS budget_currency;
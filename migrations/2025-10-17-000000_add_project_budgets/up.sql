@@ -0,0 +1,36 @@
+-- Optional project budgets and per-member hourly rates. Cost entries keep the
+-- rate they were logged at, so changing a rate never rewrites past spend.
+ALTER TABLE projects
+    ADD COLUMN budget_currency VARCHAR(3),
+    ADD COLUMN budget_amount_cents BIGINT CHECK (budget_amount_cents >= 0),
+    ADD COLUMN budget_alert_percent INTEGER NOT NULL DEFAULT 80
+        CHECK (budget_alert_percent BETWEEN 1 AND 100),
+    -- Highest budget alert already sent: ok, approaching or exceeded
+    ADD COLUMN budget_alert_level VARCHAR(16) NOT NULL DEFAULT 'ok';
+
+CREATE TABLE member_cost_rates (
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    hourly_rate_cents BIGINT NOT NULL CHECK (hourly_rate_cents >= 0),
+    currency VARCHAR(3) NOT NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (workspace_id, user_id)
+);
+
+CREATE TABLE project_cost_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    issue_id UUID REFERENCES issues(id) ON DELETE SET NULL,
+    minutes INTEGER NOT NULL CHECK (minutes > 0),
+    hourly_rate_cents BIGINT NOT NULL,
+    amount_cents BIGINT NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    spent_on DATE NOT NULL,
+    description TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_project_cost_entries_project ON project_cost_entries(project_id, spent_on);
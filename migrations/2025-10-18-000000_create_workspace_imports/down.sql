@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS import_external_ids;
+DROP TABLE IF EXISTS workspace_imports;
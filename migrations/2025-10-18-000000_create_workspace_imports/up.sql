@@ -0,0 +1,28 @@
+-- Workspace data imports from Linear or Jira exports; the worker applies them
+CREATE TABLE workspace_imports (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source VARCHAR(16) NOT NULL, -- linear, jira
+    content_hash VARCHAR(64) NOT NULL, -- SHA-256 of the export; re-uploads return the same import
+    payload TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, running, completed
+    total_records INTEGER NOT NULL,
+    result TEXT, -- JSON report of created records and conflicts
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    UNIQUE (workspace_id, content_hash)
+);
+
+-- Records created by imports, keyed by their id in the source system, so
+-- importing an overlapping export never creates duplicates
+CREATE TABLE import_external_ids (
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    source VARCHAR(16) NOT NULL,
+    record_type VARCHAR(16) NOT NULL, -- team, label, project, issue, comment
+    external_id VARCHAR(255) NOT NULL,
+    entity_id UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (workspace_id, source, record_type, external_id)
+);
@@ -0,0 +1,16 @@
+-- Links from issues to records in other systems (Zendesk tickets, Salesforce
+-- cases, Sentry issues, ...). An external record belongs to at most one issue
+-- per workspace, so integrations can look the issue up by its external id.
+CREATE TABLE external_references (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    source VARCHAR(32) NOT NULL,
+    external_id VARCHAR(255) NOT NULL,
+    url TEXT,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT external_references_unique UNIQUE (workspace_id, source, external_id)
+);
+
+CREATE INDEX idx_external_references_issue_id ON external_references(issue_id);
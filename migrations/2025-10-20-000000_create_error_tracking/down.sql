@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS error_groups;
+DROP TABLE IF EXISTS error_tracking_integrations;
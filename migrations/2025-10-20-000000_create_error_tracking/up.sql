@@ -0,0 +1,29 @@
+-- Inbound error tracking: one per workspace. Monitoring tools post error
+-- events with `ingest_key`; new errors become issues in `team_id`, filed as
+-- `created_by`.
+CREATE TABLE error_tracking_integrations (
+    workspace_id UUID PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    ingest_key VARCHAR(128) NOT NULL,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One tracked issue per distinct error, keyed by its fingerprint
+CREATE TABLE error_groups (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    fingerprint VARCHAR(64) NOT NULL, -- sha256 hex of the grouping key
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    level VARCHAR(20) NOT NULL, -- fatal, error, warning, info, debug
+    occurrences BIGINT NOT NULL DEFAULT 1,
+    reopened_count INTEGER NOT NULL DEFAULT 0,
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_event_id VARCHAR(255),
+    UNIQUE (workspace_id, fingerprint)
+);
+
+CREATE INDEX idx_error_groups_issue ON error_groups(issue_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS issue_form_submissions;
+DROP TABLE IF EXISTS issue_forms;
@@ -0,0 +1,31 @@
+-- Intake forms of a team. `fields` is the JSON list of form fields; each maps
+-- its answer onto the created issue. Public forms are served by the portal
+-- under `public_slug` and file issues as `created_by`.
+CREATE TABLE issue_forms (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    fields TEXT NOT NULL,
+    is_public BOOLEAN NOT NULL DEFAULT FALSE,
+    public_slug VARCHAR(64) UNIQUE,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_forms_team ON issue_forms(team_id, name);
+
+-- Answers behind issues created from a form
+CREATE TABLE issue_form_submissions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    form_id UUID NOT NULL REFERENCES issue_forms(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    submitted_by UUID REFERENCES users(id) ON DELETE SET NULL, -- NULL for portal submissions
+    submitter_email VARCHAR(255),
+    answers TEXT NOT NULL, -- JSON object of field key to answer
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_form_submissions_form ON issue_form_submissions(form_id, created_at DESC);
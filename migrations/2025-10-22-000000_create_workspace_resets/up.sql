@@ -0,0 +1,20 @@
+-- Sandbox resets of demo and trial workspaces: the worker wipes the
+-- workspace's content and reseeds it from a fixture profile
+CREATE TABLE workspace_resets (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    profile VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, running, completed, failed
+    result TEXT, -- JSON report of removed and seeded records
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_workspace_resets_workspace ON workspace_resets(workspace_id, created_at DESC);
+
+-- At most one reset of a workspace is waiting or running
+CREATE UNIQUE INDEX idx_workspace_resets_active ON workspace_resets(workspace_id)
+    WHERE status IN ('pending', 'running');
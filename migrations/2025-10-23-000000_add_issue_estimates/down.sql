@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS record_cycle_scope_change ON issues;
+DROP FUNCTION IF EXISTS record_cycle_scope_change();
+DROP TABLE IF EXISTS cycle_scope_changes;
+ALTER TABLE issues DROP COLUMN IF EXISTS estimate;
+ALTER TABLE teams DROP COLUMN IF EXISTS estimate_scale;
@@ -0,0 +1,58 @@
+-- Point estimates on issues, on a scale chosen per team
+ALTER TABLE teams ADD COLUMN estimate_scale VARCHAR(20) NOT NULL DEFAULT 'fibonacci'; -- fibonacci, tshirt, linear
+ALTER TABLE issues ADD COLUMN estimate INTEGER;
+
+-- Append-only log of changes to a cycle's scope, written by a trigger so
+-- every code path that moves issues in or out of a cycle, or re-estimates
+-- them, is captured. Cycle stats derive the committed points from it.
+CREATE TABLE cycle_scope_changes (
+    id BIGSERIAL PRIMARY KEY,
+    cycle_id UUID NOT NULL REFERENCES cycles(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL,
+    change VARCHAR(20) NOT NULL, -- added, removed, estimated
+    points_delta INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_cycle_scope_changes_cycle ON cycle_scope_changes (cycle_id, created_at);
+
+CREATE OR REPLACE FUNCTION record_cycle_scope_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        IF NEW.cycle_id IS NOT NULL THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'added', COALESCE(NEW.estimate, 0));
+        END IF;
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF OLD.cycle_id IS DISTINCT FROM NEW.cycle_id THEN
+            -- Issues unlinked because their cycle is being deleted need no entry
+            IF OLD.cycle_id IS NOT NULL
+                AND EXISTS (SELECT 1 FROM cycles WHERE id = OLD.cycle_id) THEN
+                INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+                VALUES (OLD.cycle_id, OLD.id, 'removed', -COALESCE(OLD.estimate, 0));
+            END IF;
+            IF NEW.cycle_id IS NOT NULL THEN
+                INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+                VALUES (NEW.cycle_id, NEW.id, 'added', COALESCE(NEW.estimate, 0));
+            END IF;
+        ELSIF NEW.cycle_id IS NOT NULL AND OLD.estimate IS DISTINCT FROM NEW.estimate THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'estimated',
+                    COALESCE(NEW.estimate, 0) - COALESCE(OLD.estimate, 0));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        IF OLD.cycle_id IS NOT NULL
+            AND EXISTS (SELECT 1 FROM cycles WHERE id = OLD.cycle_id) THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (OLD.cycle_id, OLD.id, 'removed', -COALESCE(OLD.estimate, 0));
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_cycle_scope_change
+AFTER INSERT OR UPDATE OR DELETE ON issues
+FOR EACH ROW
+EXECUTE FUNCTION record_cycle_scope_change();
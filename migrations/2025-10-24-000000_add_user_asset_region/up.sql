@@ -0,0 +1,2 @@
+-- Preferred asset CDN region; NULL leaves the choice to the request
+ALTER TABLE users ADD COLUMN asset_region VARCHAR(32);
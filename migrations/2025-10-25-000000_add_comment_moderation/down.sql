@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS comment_flags;
+DROP INDEX IF EXISTS idx_comments_author_created;
+ALTER TABLE comments DROP COLUMN IF EXISTS hidden_reason;
+ALTER TABLE comments DROP COLUMN IF EXISTS hidden_by;
+ALTER TABLE comments DROP COLUMN IF EXISTS hidden_at;
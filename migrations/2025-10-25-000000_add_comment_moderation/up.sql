@@ -0,0 +1,24 @@
+-- Hidden comments keep their content for audit but are left out of every
+-- regular listing; only moderators see them
+ALTER TABLE comments ADD COLUMN hidden_at TIMESTAMPTZ;
+ALTER TABLE comments ADD COLUMN hidden_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE comments ADD COLUMN hidden_reason TEXT;
+
+CREATE INDEX idx_comments_author_created ON comments(author_id, created_at DESC);
+
+-- Comments the spam heuristics found suspect, awaiting review
+CREATE TABLE comment_flags (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    comment_id UUID NOT NULL UNIQUE REFERENCES comments(id) ON DELETE CASCADE,
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    author_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reasons TEXT NOT NULL, -- comma separated heuristic names
+    score INTEGER NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'open', -- open, dismissed, actioned
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_comment_flags_workspace ON comment_flags(workspace_id, status, created_at DESC);
+CREATE INDEX idx_comment_flags_author ON comment_flags(author_id, created_at DESC);
@@ -0,0 +1,36 @@
+DELETE FROM cycle_scope_changes WHERE change IN ('completed', 'reopened');
+
+CREATE OR REPLACE FUNCTION record_cycle_scope_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        IF NEW.cycle_id IS NOT NULL THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'added', COALESCE(NEW.estimate, 0));
+        END IF;
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF OLD.cycle_id IS DISTINCT FROM NEW.cycle_id THEN
+            IF OLD.cycle_id IS NOT NULL
+                AND EXISTS (SELECT 1 FROM cycles WHERE id = OLD.cycle_id) THEN
+                INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+                VALUES (OLD.cycle_id, OLD.id, 'removed', -COALESCE(OLD.estimate, 0));
+            END IF;
+            IF NEW.cycle_id IS NOT NULL THEN
+                INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+                VALUES (NEW.cycle_id, NEW.id, 'added', COALESCE(NEW.estimate, 0));
+            END IF;
+        ELSIF NEW.cycle_id IS NOT NULL AND OLD.estimate IS DISTINCT FROM NEW.estimate THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'estimated',
+                    COALESCE(NEW.estimate, 0) - COALESCE(OLD.estimate, 0));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        IF OLD.cycle_id IS NOT NULL
+            AND EXISTS (SELECT 1 FROM cycles WHERE id = OLD.cycle_id) THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (OLD.cycle_id, OLD.id, 'removed', -COALESCE(OLD.estimate, 0));
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
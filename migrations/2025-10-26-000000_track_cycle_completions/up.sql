@@ -0,0 +1,84 @@
+-- Record when issues in a cycle are completed or reopened, so the cycle's
+-- progress over time (burndown) can be replayed from cycle_scope_changes
+-- alongside its scope. `points_delta` of these entries is the issue's
+-- estimate, negated for reopened issues.
+CREATE OR REPLACE FUNCTION record_cycle_scope_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    was_completed BOOLEAN := FALSE;
+    is_completed BOOLEAN := FALSE;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        IF NEW.cycle_id IS NOT NULL THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'added', COALESCE(NEW.estimate, 0));
+        END IF;
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF OLD.cycle_id IS DISTINCT FROM NEW.cycle_id THEN
+            -- Issues unlinked because their cycle is being deleted need no entry
+            IF OLD.cycle_id IS NOT NULL
+                AND EXISTS (SELECT 1 FROM cycles WHERE id = OLD.cycle_id) THEN
+                INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+                VALUES (OLD.cycle_id, OLD.id, 'removed', -COALESCE(OLD.estimate, 0));
+            END IF;
+            IF NEW.cycle_id IS NOT NULL THEN
+                INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+                VALUES (NEW.cycle_id, NEW.id, 'added', COALESCE(NEW.estimate, 0));
+            END IF;
+        ELSIF NEW.cycle_id IS NOT NULL AND OLD.estimate IS DISTINCT FROM NEW.estimate THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'estimated',
+                    COALESCE(NEW.estimate, 0) - COALESCE(OLD.estimate, 0));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        IF OLD.cycle_id IS NOT NULL
+            AND EXISTS (SELECT 1 FROM cycles WHERE id = OLD.cycle_id) THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (OLD.cycle_id, OLD.id, 'removed', -COALESCE(OLD.estimate, 0));
+        END IF;
+        RETURN NULL;
+    END IF;
+
+    -- Completion within the issue's (new) cycle; an issue that joins a cycle
+    -- already completed is recorded as completed right after being added
+    IF NEW.cycle_id IS NOT NULL THEN
+        SELECT category = 'completed' INTO is_completed
+        FROM workflow_states WHERE id = NEW.workflow_state_id;
+        IF TG_OP = 'UPDATE' AND OLD.cycle_id IS NOT DISTINCT FROM NEW.cycle_id THEN
+            SELECT category = 'completed' INTO was_completed
+            FROM workflow_states WHERE id = OLD.workflow_state_id;
+        END IF;
+        IF COALESCE(is_completed, FALSE) AND NOT COALESCE(was_completed, FALSE) THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'completed', COALESCE(NEW.estimate, 0));
+        ELSIF COALESCE(was_completed, FALSE) AND NOT COALESCE(is_completed, FALSE) THEN
+            INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta)
+            VALUES (NEW.cycle_id, NEW.id, 'reopened', -COALESCE(NEW.estimate, 0));
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Issues that joined their cycle before it was logged count as committed;
+-- those already completed count as completed when last updated, but not
+-- before they joined the cycle
+INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta, created_at)
+SELECT i.cycle_id, i.id, 'added', COALESCE(i.estimate, 0),
+       LEAST(c.created_at, c.start_date::timestamptz)
+FROM issues i
+JOIN cycles c ON c.id = i.cycle_id
+WHERE NOT EXISTS (
+    SELECT 1 FROM cycle_scope_changes sc
+    WHERE sc.cycle_id = i.cycle_id AND sc.issue_id = i.id AND sc.change = 'added'
+);
+
+INSERT INTO cycle_scope_changes (cycle_id, issue_id, change, points_delta, created_at)
+SELECT i.cycle_id, i.id, 'completed', COALESCE(i.estimate, 0),
+       GREATEST(i.updated_at, (
+           SELECT MAX(sc.created_at) FROM cycle_scope_changes sc
+           WHERE sc.cycle_id = i.cycle_id AND sc.issue_id = i.id AND sc.change = 'added'
+       ))
+FROM issues i
+JOIN workflow_states ws ON ws.id = i.workflow_state_id
+WHERE i.cycle_id IS NOT NULL AND ws.category = 'completed';
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS issue_label_rule_applications;
+DROP TABLE IF EXISTS issue_label_rules;
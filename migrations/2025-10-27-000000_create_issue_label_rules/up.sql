@@ -0,0 +1,37 @@
+-- Auto-labeling rules of a team, evaluated in `position` order against the
+-- title and description of every new issue. A `keyword` rule matches the
+-- pattern as case-insensitive text, a `regex` rule as a regular expression.
+-- A match adds `label_id` and, when the issue has no priority yet, sets
+-- `priority`.
+CREATE TABLE issue_label_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    match_type VARCHAR(16) NOT NULL,
+    pattern TEXT NOT NULL,
+    label_id UUID REFERENCES labels(id) ON DELETE SET NULL,
+    priority VARCHAR(16),
+    position INTEGER NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_label_rules_team ON issue_label_rules(team_id, position);
+
+-- Rules applied to an issue when it was created. The rule name is copied so
+-- the entry stays readable after the rule is deleted.
+CREATE TABLE issue_label_rule_applications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    rule_id UUID REFERENCES issue_label_rules(id) ON DELETE SET NULL,
+    rule_name VARCHAR(255) NOT NULL,
+    label_id UUID REFERENCES labels(id) ON DELETE SET NULL,
+    priority VARCHAR(16),
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_label_rule_applications_issue
+    ON issue_label_rule_applications(issue_id, applied_at);
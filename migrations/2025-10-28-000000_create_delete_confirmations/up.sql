@@ -0,0 +1,20 @@
+-- Two-step confirmation of destructive deletes. The first request stores a
+-- short-lived token with a summary of what would be deleted; repeating the
+-- delete with the token carries it out. Rows outlive the deleted workspace,
+-- so they keep the record of who requested and confirmed the deletion.
+CREATE TABLE delete_confirmations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL,
+    target_type VARCHAR(20) NOT NULL, -- workspace, team
+    target_id UUID NOT NULL,
+    target_name VARCHAR(255) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    impact TEXT NOT NULL, -- JSON DeleteImpact
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    confirmed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_delete_confirmations_target
+    ON delete_confirmations (target_type, target_id, created_at DESC);
@@ -0,0 +1,20 @@
+-- Create api_keys table (workspace API keys for machine access)
+-- A key acts for the admin who created it, limited to one workspace and its scopes.
+-- Only a SHA-256 hash of the key is stored; the plaintext is shown once on creation.
+CREATE TABLE api_keys (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    scopes TEXT NOT NULL, -- space-separated, same vocabulary as OAuth scopes
+    tier VARCHAR(20) NOT NULL DEFAULT 'free', -- free, pro, unlimited
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    last_used_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_api_keys_workspace_id ON api_keys(workspace_id);
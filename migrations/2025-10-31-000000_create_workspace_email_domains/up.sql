@@ -0,0 +1,22 @@
+-- Create workspace_email_domains table (custom sending domain per workspace)
+-- Invitation and digest emails use the domain only while it is verified: the
+-- ownership TXT record and the DKIM key must both be published in DNS.
+-- Otherwise the platform sender (EMAIL_FROM) is used.
+CREATE TABLE workspace_email_domains (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL UNIQUE REFERENCES workspaces(id) ON DELETE CASCADE,
+    domain VARCHAR(253) NOT NULL,
+    from_name VARCHAR(100),
+    from_local_part VARCHAR(64) NOT NULL DEFAULT 'notifications',
+    dkim_selector VARCHAR(63) NOT NULL DEFAULT 'momentum',
+    verification_token VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, verified, failed
+    last_error TEXT,
+    verified_at TIMESTAMPTZ,
+    last_checked_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_workspace_email_domains_status ON workspace_email_domains(status);
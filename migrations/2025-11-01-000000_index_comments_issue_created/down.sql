@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_comments_issue_created_id;
@@ -0,0 +1,3 @@
+-- Keyset pagination of an issue's comments walks (created_at, id) in either
+-- direction from a cursor or an anchor comment
+CREATE INDEX idx_comments_issue_created_id ON comments(issue_id, created_at, id);
@@ -0,0 +1,18 @@
+-- Workspace custom emoji, referenced as `:name:` in reactions and comment
+-- markdown; the images live in S3-compatible object storage
+CREATE TABLE custom_emojis (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    name VARCHAR(30) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    usage_count BIGINT NOT NULL DEFAULT 0,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    uploaded_at TIMESTAMPTZ,
+    CONSTRAINT custom_emojis_workspace_name_unique UNIQUE (workspace_id, name)
+);
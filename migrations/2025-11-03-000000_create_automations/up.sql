@@ -0,0 +1,38 @@
+-- "When X then Y" rules of a workspace. `conditions` is a JSON object that
+-- must match the issue, `actions` the JSON list of what to do to it. Rules
+-- without a team apply to every team's issues.
+CREATE TABLE automation_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    team_id UUID REFERENCES teams(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    trigger VARCHAR(32) NOT NULL,
+    conditions TEXT NOT NULL DEFAULT '{}',
+    actions TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_automation_rules_workspace_trigger ON automation_rules(workspace_id, trigger);
+
+-- One evaluation of a rule against an issue, queued with the change that
+-- triggered it and run by the worker. `event` is the JSON trigger event.
+CREATE TABLE automation_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rule_id UUID NOT NULL REFERENCES automation_rules(id) ON DELETE CASCADE,
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    event TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_automation_runs_due ON automation_runs(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX idx_automation_runs_rule_created ON automation_runs(rule_id, created_at DESC);
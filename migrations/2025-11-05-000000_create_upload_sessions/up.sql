@@ -0,0 +1,24 @@
+-- Resumable uploads of large attachments. Chunks are appended in order as
+-- parts of an S3 multipart upload; the last chunk completes it into an
+-- attachment. Sessions idle past expires_at are aborted by the scheduler.
+CREATE TABLE upload_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    uploader_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    filename VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    multipart_upload_id TEXT NOT NULL,
+    upload_offset BIGINT NOT NULL DEFAULT 0,
+    -- JSON array of {part_number, etag, size_bytes}
+    parts TEXT NOT NULL DEFAULT '[]',
+    attachment_id UUID REFERENCES attachments(id) ON DELETE SET NULL,
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_upload_sessions_expires_at ON upload_sessions(expires_at);
@@ -0,0 +1,23 @@
+-- Comments and issues reported by workspace members, reviewed by workspace
+-- admins. target_id has no foreign key so reports outlive removed content.
+CREATE TABLE content_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    reporter_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    target_type VARCHAR(20) NOT NULL, -- comment, issue
+    target_id UUID NOT NULL,
+    reason VARCHAR(30) NOT NULL,
+    details TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'open', -- open, dismissed, hidden, removed
+    resolution_note TEXT,
+    resolved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_content_reports_queue ON content_reports(workspace_id, status, created_at DESC);
+CREATE INDEX idx_content_reports_target ON content_reports(target_type, target_id);
+-- One open report per reporter and target
+CREATE UNIQUE INDEX idx_content_reports_open_reporter
+    ON content_reports(reporter_id, target_type, target_id)
+    WHERE status = 'open';
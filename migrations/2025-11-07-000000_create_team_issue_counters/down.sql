@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_issues_team_number;
+
+CREATE SEQUENCE issues_issue_number_seq OWNED BY issues.issue_number;
+SELECT setval('issues_issue_number_seq', GREATEST((SELECT MAX(issue_number) FROM issues), 1));
+ALTER TABLE issues ALTER COLUMN issue_number SET DEFAULT nextval('issues_issue_number_seq');
+
+DROP TABLE IF EXISTS team_issue_counters;
@@ -0,0 +1,23 @@
+-- Issue numbers count up per team (ENG-1, ENG-2, ...) instead of from one
+-- sequence shared by every team. Issuing a number locks the team's counter
+-- row until the transaction ends, so concurrent creates never share one.
+CREATE TABLE team_issue_counters (
+    team_id UUID PRIMARY KEY REFERENCES teams(id) ON DELETE CASCADE,
+    last_issue_number INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Existing issues keep their numbers, which are already in links and
+-- markdown references; each team continues from its highest one
+INSERT INTO team_issue_counters (team_id, last_issue_number)
+SELECT t.id, COALESCE(MAX(i.issue_number), 0)
+FROM teams t
+LEFT JOIN issues i ON i.team_id = t.id
+GROUP BY t.id;
+
+ALTER TABLE issues ALTER COLUMN issue_number DROP DEFAULT;
+DROP SEQUENCE IF EXISTS issues_issue_number_seq;
+
+-- The counter only covers inserts that go through it; the index makes the
+-- database reject a duplicate ENG-N key from any other path as well
+CREATE UNIQUE INDEX idx_issues_team_number ON issues(team_id, issue_number);
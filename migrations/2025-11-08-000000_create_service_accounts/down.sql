@@ -0,0 +1,3 @@
+ALTER TABLE api_tokens DROP COLUMN IF EXISTS scopes;
+DROP TABLE IF EXISTS service_accounts;
+ALTER TABLE users DROP COLUMN IF EXISTS is_service_account;
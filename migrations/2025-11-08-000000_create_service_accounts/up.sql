@@ -0,0 +1,19 @@
+-- Machine users for integrations. A service account is a user that belongs
+-- to one workspace, has no credentials and cannot sign in; it acts through
+-- the scoped API tokens workspace admins create for it.
+ALTER TABLE users ADD COLUMN is_service_account BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE service_accounts (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    description TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    disabled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_service_accounts_workspace ON service_accounts(workspace_id);
+
+-- Space-separated scopes; NULL leaves a personal token unrestricted
+ALTER TABLE api_tokens ADD COLUMN scopes TEXT;
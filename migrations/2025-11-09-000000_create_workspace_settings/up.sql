@@ -0,0 +1,17 @@
+-- Workspace-level options. A workspace without a row uses the defaults
+-- below; the row is created on the first update.
+CREATE TABLE workspace_settings (
+    workspace_id UUID PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+    -- Workflow new issues of its team are filed in when none is given
+    default_workflow_id UUID REFERENCES workflows(id) ON DELETE SET NULL,
+    week_start_day VARCHAR(10) NOT NULL DEFAULT 'monday', -- monday, sunday, saturday
+    -- Estimate scale new teams start with
+    estimate_scale VARCHAR(20) NOT NULL DEFAULT 'fibonacci',
+    -- Space-separated domains invitations may be sent to; empty allows any
+    allowed_email_domains TEXT NOT NULL DEFAULT '',
+    -- JSON object of the feature toggles that were changed from their default
+    features TEXT NOT NULL DEFAULT '{}',
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+-- Identity of this installation in anonymous usage reports and the last
+-- report it sent. Holds at most one row; the identity is random and not
+-- derived from any data of the installation.
+CREATE TABLE telemetry_state (
+    singleton BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (singleton),
+    instance_id UUID NOT NULL,
+    last_sent_at TIMESTAMPTZ,
+    -- JSON body of the last report the endpoint accepted
+    last_report TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
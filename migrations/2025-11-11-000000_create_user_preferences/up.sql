@@ -0,0 +1,15 @@
+-- Per-user display settings and which channels deliver each notification
+-- type. Users without a row get the defaults: every channel on.
+CREATE TABLE user_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    locale VARCHAR(20) NOT NULL DEFAULT 'en',
+    theme VARCHAR(10) NOT NULL DEFAULT 'system',
+    -- Space-separated `event_type:channel` pairs the user turned off
+    muted_channels TEXT NOT NULL DEFAULT '',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Notifications the recipient turned off in-app for are still recorded so
+-- they can be emailed or pushed, but are left out of their inbox
+ALTER TABLE notifications ADD COLUMN in_app BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,20 @@
+-- WebSocket commands that failed with a server-side error, kept with the
+-- original command so an admin can replay them once the cause is fixed
+CREATE TABLE websocket_dead_letters (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    command_type VARCHAR(64) NOT NULL,
+    request_id VARCHAR(255),
+    command TEXT NOT NULL, -- JSON command as received
+    error_code VARCHAR(50) NOT NULL,
+    error_message TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, replayed, rejected
+    attempts INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    replayed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    replayed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_websocket_dead_letters_status_created ON websocket_dead_letters(status, created_at);
+CREATE INDEX idx_websocket_dead_letters_workspace ON websocket_dead_letters(workspace_id);
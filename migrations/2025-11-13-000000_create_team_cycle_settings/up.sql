@@ -0,0 +1,16 @@
+-- Per-team cycle cadence used to create each team's next cycle automatically
+CREATE TABLE team_cycle_settings (
+    team_id UUID PRIMARY KEY REFERENCES teams(id) ON DELETE CASCADE,
+    auto_create BOOLEAN NOT NULL DEFAULT TRUE,
+    length_days INTEGER NOT NULL CHECK (length_days BETWEEN 1 AND 84),
+    -- ISO weekday cycles start on: 1 is Monday, 7 is Sunday
+    start_weekday INTEGER NOT NULL CHECK (start_weekday BETWEEN 1 AND 7),
+    -- Days left free between the end of one cycle and the start of the next
+    cooldown_days INTEGER NOT NULL DEFAULT 0 CHECK (cooldown_days BETWEEN 0 AND 28),
+    roll_over_unfinished BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Latest completed cycle whose unfinished issues were rolled over, so a
+    -- cycle is only rolled over once
+    last_rolled_over_cycle_id UUID REFERENCES cycles(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1 @@
+ALTER TABLE workspace_settings DROP COLUMN IF EXISTS archive_completed_after_days;
@@ -0,0 +1,5 @@
+-- Archive issues that have sat in a completed state, untouched, for this many
+-- days; NULL leaves completed issues alone
+ALTER TABLE workspace_settings
+    ADD COLUMN archive_completed_after_days INTEGER
+        CHECK (archive_completed_after_days BETWEEN 1 AND 3650);
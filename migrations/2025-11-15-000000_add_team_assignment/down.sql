@@ -0,0 +1,3 @@
+ALTER TABLE teams DROP COLUMN IF EXISTS last_auto_assignee_id;
+ALTER TABLE teams DROP COLUMN IF EXISTS default_assignee_id;
+ALTER TABLE teams DROP COLUMN IF EXISTS assignment_mode;
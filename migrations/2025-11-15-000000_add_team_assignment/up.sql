@@ -0,0 +1,5 @@
+-- How new issues created without an assignee are assigned
+ALTER TABLE teams ADD COLUMN assignment_mode VARCHAR(20) NOT NULL DEFAULT 'none'; -- none, default_assignee, round_robin
+ALTER TABLE teams ADD COLUMN default_assignee_id UUID REFERENCES users(id) ON DELETE SET NULL;
+-- Member the last round-robin assignment went to; the next goes to the member after them
+ALTER TABLE teams ADD COLUMN last_auto_assignee_id UUID REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE workspace_settings
+    DROP COLUMN IF EXISTS auto_join_role,
+    DROP COLUMN IF EXISTS auto_join_domains;
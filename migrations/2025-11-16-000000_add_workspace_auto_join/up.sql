@@ -0,0 +1,6 @@
+-- Users who register with an address at one of these domains join the
+-- workspace automatically with auto_join_role; space-separated, empty disables
+ALTER TABLE workspace_settings
+    ADD COLUMN auto_join_domains TEXT NOT NULL DEFAULT '',
+    ADD COLUMN auto_join_role workspace_user_role NOT NULL DEFAULT 'member'
+        CHECK (auto_join_role IN ('member', 'guest'));
@@ -0,0 +1 @@
+DROP TRIGGER IF EXISTS update_cycles_updated_at ON cycles;
@@ -0,0 +1,3 @@
+-- Keep cycles.updated_at current so list ETags see renames and status changes
+CREATE TRIGGER update_cycles_updated_at BEFORE UPDATE ON cycles
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,48 @@
+use clap::{Arg, ArgAction, Command};
+use rust_backend::{config::Config, db, services::integrity_service::IntegrityService};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = Command::new("integrity_check")
+        .about("Scan the database for orphaned rows and cross-tenant mismatches")
+        .arg(
+            Arg::new("fix")
+                .long("fix")
+                .action(ArgAction::SetTrue)
+                .help("Repair fixable problems, one transaction per check"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("Print the report as JSON"),
+        )
+        .get_matches();
+
+    let config = Config::from_env()?;
+    let pool = db::create_pool(&config.database())?;
+    let mut conn = pool.get()?;
+
+    let report = IntegrityService::run(&mut conn, matches.get_flag("fix"))?;
+
+    if matches.get_flag("json") {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("Integrity check at {}", report.checked_at);
+        for finding in &report.findings {
+            let status = if finding.count == 0 { "ok" } else { "FAIL" };
+            println!(
+                "[{:>4}] {:<34} {:>6} found {:>6} fixed  {}",
+                status, finding.check, finding.count, finding.fixed, finding.description
+            );
+        }
+        println!(
+            "Total: {} problem(s), {} fixed",
+            report.total_problems, report.total_fixed
+        );
+    }
+
+    if report.total_problems > report.total_fixed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
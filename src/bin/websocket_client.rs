@@ -245,12 +245,11 @@ async fn handle_interactive_mode(config: ClientConfig) -> Result<(), Box<dyn std
         tokio::select! {
             // Handle auto-ping messages
             ping_msg = ping_rx.recv() => {
-                if let Some(msg) = ping_msg {
-                    if let Err(e) = ws_sender.send(TungsteniteMessage::Text(msg)).await {
+                if let Some(msg) = ping_msg
+                    && let Err(e) = ws_sender.send(TungsteniteMessage::Text(msg)).await {
                         println!("❌ Failed to send auto-ping: {}", e);
                         break;
                     }
-                }
             },
             line = lines.next_line() => {
                 match line {
@@ -1,14 +1,452 @@
 use redis::AsyncCommands;
+use rust_backend::{
+    cache::{CacheStore, RedisCacheStore},
+    config::Config,
+    db::{self, models::email::EmailMessage, repositories::auth::AuthRepo},
+    services::auto_close_service::AutoCloseService,
+    services::automations_service::AutomationsService,
+    services::email_service::{EmailService, Mailer},
+    services::import_service::ImportService,
+    services::issue_archive_service::IssueArchiveService,
+    services::member_imports_service::MemberImportsService,
+    services::notifications_service::{
+        EmailReplySettings, NotificationBatchPolicy, NotificationsService,
+    },
+    services::push_service::{PushGateway, PushService},
+    services::sandbox_service::SandboxService,
+    services::webhook_service::WebhookService,
+    services::workspace_email_domains_service::{DohResolver, WorkspaceEmailDomainsService},
+};
+
+/// How often pending notification emails are rolled up into digests
+const DIGEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How often due webhook deliveries are sent
+const WEBHOOK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How often due push deliveries are sent
+const PUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How often stale push subscriptions and old push deliveries are removed
+const PUSH_CLEANUP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// How often team auto-close policies warn and close inactive issues
+const AUTO_CLOSE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// How often queued automation runs are evaluated
+const AUTOMATION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How often verified workspace email domains are looked for that are due
+/// for a DNS re-check
+const EMAIL_DOMAIN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Verified email domains are checked again once their last check is older than this
+const EMAIL_DOMAIN_RECHECK_HOURS: i64 = 24;
+
+/// Queued emails sent per loop iteration
+const EMAIL_BATCH: usize = 50;
+
+/// How often member imports, bulk archives and workspace resets whose queue
+/// task was lost are picked up
+const IMPORT_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
 
 #[tokio::main]
 async fn main() {
+    let config = Config::from_env().ok();
+    let reply_settings = config.as_ref().and_then(EmailReplySettings::from_config);
+    let db_pool = config.as_ref().and_then(|c| {
+        if let Ok(policy) = NotificationBatchPolicy::from_config(c) {
+            NotificationBatchPolicy::install(policy);
+        }
+        db::create_pool(&c.database()).ok()
+    });
+
     let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    let cache = RedisCacheStore::new(client.clone());
+    // Imports and member changes run here invalidate the API's cached lists
+    rust_backend::cache::typed::install(std::sync::Arc::new(cache.clone()));
+    let email = config.as_ref().and_then(|c| {
+        match c
+            .email()
+            .and_then(|email| EmailService::new(&email, client.clone()))
+        {
+            Ok(email) => Some(email),
+            Err(e) => {
+                eprintln!("Email sending disabled: {}", e);
+                None
+            }
+        }
+    });
+    let resolver = config.as_ref().and_then(|c| {
+        DohResolver::new(&c.email_domain_dns_url)
+            .map_err(|e| eprintln!("Email domain checks disabled: {}", e))
+            .ok()
+    });
+    let http = reqwest::Client::new();
+    let push = PushGateway::new(
+        config.as_ref().map(|c| c.push()).unwrap_or_default(),
+        http.clone(),
+    );
+    let mut last_digest = std::time::Instant::now();
+    let mut last_webhooks = std::time::Instant::now();
+    let mut last_import_sweep = std::time::Instant::now();
+    let mut last_auto_close = std::time::Instant::now();
+    let mut last_push = std::time::Instant::now();
+    let mut last_push_cleanup = std::time::Instant::now();
+    let mut last_email_domains = std::time::Instant::now();
+    let mut last_automations = std::time::Instant::now();
     loop {
         let mut conn = client.get_multiplexed_async_connection().await.unwrap();
         let task: String = conn.lpop("tasks", None).await.unwrap_or_default();
         if !task.is_empty() {
             println!("Processing task: {}", task);
+            if let (Some(pool), Some(import_id)) =
+                (&db_pool, MemberImportsService::parse_job(&task))
+            {
+                run_member_import(pool, import_id);
+            }
+            if let (Some(pool), Some(batch_id)) = (&db_pool, IssueArchiveService::parse_job(&task))
+            {
+                run_issue_archive(pool, batch_id);
+            }
+            if let (Some(pool), Some(import_id)) = (&db_pool, ImportService::parse_job(&task)) {
+                run_workspace_import(pool, import_id);
+            }
+            if let (Some(pool), Some(reset_id)) = (&db_pool, SandboxService::parse_job(&task)) {
+                run_workspace_reset(pool, reset_id);
+            }
+        }
+
+        if let Some(pool) = &db_pool
+            && last_import_sweep.elapsed() >= IMPORT_SWEEP_INTERVAL
+        {
+            last_import_sweep = std::time::Instant::now();
+            sweep_member_imports(pool);
+            sweep_issue_archives(pool);
+            sweep_workspace_imports(pool);
+            sweep_workspace_resets(pool);
+        }
+
+        if let Some(pool) = &db_pool
+            && last_digest.elapsed() >= DIGEST_INTERVAL
+        {
+            last_digest = std::time::Instant::now();
+            flush_notification_digests(pool, reply_settings.as_ref(), email.as_ref(), &cache).await;
         }
+
+        if let Some(email) = &email
+            && let Err(e) = email.process_queue(EMAIL_BATCH).await
+        {
+            eprintln!("Email queue failed: {}", e);
+        }
+
+        if let Some(pool) = &db_pool
+            && last_webhooks.elapsed() >= WEBHOOK_INTERVAL
+        {
+            last_webhooks = std::time::Instant::now();
+            if let Err(e) =
+                WebhookService::deliver_due(&db::DbExecutor::new(pool.clone()), &http).await
+            {
+                eprintln!("Webhook delivery failed: {}", e);
+            }
+        }
+
+        if let Some(pool) = &db_pool
+            && last_push.elapsed() >= PUSH_INTERVAL
+        {
+            last_push = std::time::Instant::now();
+            if let Err(e) = PushService::deliver_due(pool, &push).await {
+                eprintln!("Push delivery failed: {}", e);
+            }
+        }
+
+        if let Some(pool) = &db_pool
+            && last_push_cleanup.elapsed() >= PUSH_CLEANUP_INTERVAL
+        {
+            last_push_cleanup = std::time::Instant::now();
+            clean_up_push(pool);
+        }
+
+        if let (Some(pool), Some(resolver)) = (&db_pool, &resolver)
+            && last_email_domains.elapsed() >= EMAIL_DOMAIN_INTERVAL
+        {
+            last_email_domains = std::time::Instant::now();
+            recheck_email_domains(pool, resolver).await;
+        }
+
+        if let Some(pool) = &db_pool
+            && last_auto_close.elapsed() >= AUTO_CLOSE_INTERVAL
+        {
+            last_auto_close = std::time::Instant::now();
+            run_auto_close(pool);
+        }
+
+        if let Some(pool) = &db_pool
+            && last_automations.elapsed() >= AUTOMATION_INTERVAL
+        {
+            last_automations = std::time::Instant::now();
+            run_automations(pool);
+        }
+
         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
     }
 }
+
+async fn flush_notification_digests(
+    pool: &db::DbPool,
+    reply: Option<&EmailReplySettings>,
+    email: Option<&EmailService>,
+    cache: &dyn CacheStore,
+) {
+    let digests = {
+        let Ok(mut conn) = pool.get() else {
+            eprintln!("Notification digests: database connection failed");
+            return;
+        };
+        match NotificationsService::take_email_digests(&mut conn, chrono::Utc::now()) {
+            Ok(digests) => digests
+                .into_iter()
+                .filter_map(
+                    |digest| match AuthRepo::find_by_id(&mut conn, digest.recipient_id) {
+                        Ok(Some(user)) => Some((
+                            NotificationsService::render_digest_email(&digest, &user.email, reply),
+                            WorkspaceEmailDomainsService::digest_sender(&mut conn, &digest)
+                                .unwrap_or_default(),
+                            digest,
+                        )),
+                        _ => None,
+                    },
+                )
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                eprintln!("Notification digests failed: {}", e);
+                return;
+            }
+        }
+    };
+
+    for (digest, from, notifications) in digests {
+        match email {
+            Some(email) => {
+                let html =
+                    NotificationsService::render_digest_html(&notifications, reply, cache).await;
+                email
+                    .enqueue_quietly(EmailMessage {
+                        from,
+                        html: Some(html),
+                        ..EmailService::digest_email(digest)
+                    })
+                    .await
+            }
+            None => println!(
+                "Notification digest for {}: subject {:?}, reply-to {:?}",
+                digest.to, digest.subject, digest.reply_to
+            ),
+        }
+    }
+}
+
+fn run_member_import(pool: &db::DbPool, import_id: uuid::Uuid) {
+    let Ok(mut conn) = pool.get() else {
+        eprintln!("Member import {}: database connection failed", import_id);
+        return;
+    };
+    match MemberImportsService::run(&mut conn, import_id) {
+        Ok(Some(_)) => println!("Member import {} completed", import_id),
+        Ok(None) => {}
+        Err(e) => eprintln!("Member import {} failed: {}", import_id, e),
+    }
+}
+
+fn sweep_member_imports(pool: &db::DbPool) {
+    let stale = match pool.get() {
+        Ok(mut conn) => {
+            MemberImportsService::stale_pending(&mut conn, chrono::Duration::minutes(5))
+        }
+        Err(_) => return,
+    };
+    match stale {
+        Ok(ids) => {
+            for import_id in ids {
+                run_member_import(pool, import_id);
+            }
+        }
+        Err(e) => eprintln!("Member import sweep failed: {}", e),
+    }
+}
+
+fn run_workspace_import(pool: &db::DbPool, import_id: uuid::Uuid) {
+    let Ok(mut conn) = pool.get() else {
+        eprintln!("Workspace import {}: database connection failed", import_id);
+        return;
+    };
+    match ImportService::run(&mut conn, import_id) {
+        Ok(Some(report)) => println!(
+            "Workspace import {} completed with {} conflicts",
+            import_id,
+            report.conflicts.len()
+        ),
+        Ok(None) => {}
+        Err(e) => eprintln!("Workspace import {} failed: {}", import_id, e),
+    }
+}
+
+fn sweep_workspace_imports(pool: &db::DbPool) {
+    let stale = match pool.get() {
+        Ok(mut conn) => ImportService::stale_pending(&mut conn, chrono::Duration::minutes(5)),
+        Err(_) => return,
+    };
+    match stale {
+        Ok(ids) => {
+            for import_id in ids {
+                run_workspace_import(pool, import_id);
+            }
+        }
+        Err(e) => eprintln!("Workspace import sweep failed: {}", e),
+    }
+}
+
+fn run_workspace_reset(pool: &db::DbPool, reset_id: uuid::Uuid) {
+    let Ok(mut conn) = pool.get() else {
+        eprintln!("Workspace reset {}: database connection failed", reset_id);
+        return;
+    };
+    match SandboxService::run(&mut conn, reset_id) {
+        Ok(Some(report)) => println!(
+            "Workspace reset {} completed: {} issues removed, {} seeded",
+            reset_id, report.removed.issues, report.seeded.issues
+        ),
+        Ok(None) => {}
+        Err(e) => eprintln!("Workspace reset {} failed: {}", reset_id, e),
+    }
+}
+
+fn sweep_workspace_resets(pool: &db::DbPool) {
+    let stale = match pool.get() {
+        Ok(mut conn) => SandboxService::stale_pending(&mut conn, chrono::Duration::minutes(5)),
+        Err(_) => return,
+    };
+    match stale {
+        Ok(ids) => {
+            for reset_id in ids {
+                run_workspace_reset(pool, reset_id);
+            }
+        }
+        Err(e) => eprintln!("Workspace reset sweep failed: {}", e),
+    }
+}
+
+fn run_issue_archive(pool: &db::DbPool, batch_id: uuid::Uuid) {
+    let Ok(mut conn) = pool.get() else {
+        eprintln!("Issue archive {}: database connection failed", batch_id);
+        return;
+    };
+    match IssueArchiveService::run(&mut conn, batch_id) {
+        Ok(Some(batch)) => println!(
+            "Issue archive {} archived {} issue(s)",
+            batch_id, batch.issue_count
+        ),
+        Ok(None) => {}
+        Err(e) => eprintln!("Issue archive {} failed: {}", batch_id, e),
+    }
+}
+
+fn sweep_issue_archives(pool: &db::DbPool) {
+    let stale = match pool.get() {
+        Ok(mut conn) => IssueArchiveService::stale_pending(&mut conn, chrono::Duration::minutes(5)),
+        Err(_) => return,
+    };
+    match stale {
+        Ok(ids) => {
+            for batch_id in ids {
+                run_issue_archive(pool, batch_id);
+            }
+        }
+        Err(e) => eprintln!("Issue archive sweep failed: {}", e),
+    }
+}
+
+fn clean_up_push(pool: &db::DbPool) {
+    let Ok(mut conn) = pool.get() else {
+        eprintln!("Push cleanup: database connection failed");
+        return;
+    };
+    match PushService::cleanup(&mut conn, chrono::Utc::now()) {
+        Ok((subscriptions, deliveries)) => {
+            if subscriptions > 0 || deliveries > 0 {
+                println!(
+                    "Push cleanup: removed {} stale subscription(s), {} old deliveries",
+                    subscriptions, deliveries
+                );
+            }
+        }
+        Err(e) => eprintln!("Push cleanup failed: {}", e),
+    }
+}
+
+fn run_auto_close(pool: &db::DbPool) {
+    let Ok(mut conn) = pool.get() else {
+        eprintln!("Auto-close: database connection failed");
+        return;
+    };
+    match AutoCloseService::run_due(&mut conn, chrono::Utc::now()) {
+        Ok(summary) => {
+            if summary.warned > 0 || summary.closed > 0 {
+                println!(
+                    "Auto-close: warned {} issue(s), closed {} issue(s)",
+                    summary.warned, summary.closed
+                );
+            }
+        }
+        Err(e) => eprintln!("Auto-close failed: {}", e),
+    }
+}
+
+fn run_automations(pool: &db::DbPool) {
+    let Ok(mut conn) = pool.get() else {
+        eprintln!("Automations: database connection failed");
+        return;
+    };
+    match AutomationsService::run_due(&mut conn) {
+        Ok(count) => {
+            if count > 0 {
+                println!("Automations: evaluated {} run(s)", count);
+            }
+        }
+        Err(e) => eprintln!("Automations failed: {}", e),
+    }
+}
+
+/// Re-check verified email domains; a domain whose records are gone fails and
+/// its workspace's mail falls back to the platform sender
+async fn recheck_email_domains(pool: &db::DbPool, resolver: &DohResolver) {
+    let due = match pool.get() {
+        Ok(mut conn) => WorkspaceEmailDomainsService::due_for_recheck(
+            &mut conn,
+            chrono::Duration::hours(EMAIL_DOMAIN_RECHECK_HOURS),
+            100,
+        ),
+        Err(_) => {
+            eprintln!("Email domain checks: database connection failed");
+            return;
+        }
+    };
+    let due = match due {
+        Ok(due) => due,
+        Err(e) => {
+            eprintln!("Email domain checks failed: {}", e);
+            return;
+        }
+    };
+    for domain in due {
+        let check = WorkspaceEmailDomainsService::check(resolver, &domain).await;
+        let Ok(mut conn) = pool.get() else {
+            eprintln!("Email domain checks: database connection failed");
+            return;
+        };
+        if let Err(e) = WorkspaceEmailDomainsService::record_check(&mut conn, None, &domain, &check)
+        {
+            eprintln!("Email domain {} check failed: {}", domain.domain, e);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// 锁持有者记录键前缀
+const LOCK_KEY_PREFIX: &str = "lock:";
+/// 栅栏令牌计数器键前缀；计数器不过期，保证令牌在锁的整个生命周期内递增
+const FENCE_KEY_PREFIX: &str = "lock:fence:";
+/// 续期间隔不短于该值，避免过短的 TTL 导致频繁访问 Redis
+const MIN_RENEW_INTERVAL: Duration = Duration::from_millis(100);
+/// 等待锁时的重试间隔上限
+const MAX_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// 未被持有时写入持有者并递增栅栏令牌，返回令牌；已被持有时返回 0
+const ACQUIRE_SCRIPT: &str = r"
+if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+    return redis.call('INCR', KEYS[2])
+end
+return 0
+";
+
+/// 仅当仍由本持有者持有时延长过期时间
+const RENEW_SCRIPT: &str = r"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+";
+
+/// 仅当仍由本持有者持有时删除
+const RELEASE_SCRIPT: &str = r"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+";
+
+/// 单个锁名的竞争统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LockStats {
+    pub name: String,
+    /// 成功获取次数
+    pub acquired: u64,
+    /// 因已被其他实例持有而失败的尝试次数
+    pub contended: u64,
+    /// 等待后仍未获取到锁的次数
+    pub timed_out: u64,
+    pub renewed: u64,
+    /// 续期失败、锁在持有期间丢失的次数
+    pub lost: u64,
+    pub released: u64,
+}
+
+#[derive(Clone, Copy)]
+enum LockEvent {
+    Acquired,
+    Contended,
+    TimedOut,
+    Renewed,
+    Lost,
+    Released,
+}
+
+/// Redis 分布式锁
+///
+/// 用于多副本部署时需要互斥执行的任务（定时任务、汇总、导入等）。
+/// 获取成功时返回 [`LockGuard`]，持有期间在后台自动续期，释放或丢弃时删除。
+/// 每次获取都会得到一个递增的栅栏令牌，写入外部资源时携带该令牌，
+/// 可拒绝锁过期后仍在运行的旧持有者的写入。
+#[derive(Clone)]
+pub struct LockManager {
+    redis_client: redis::Client,
+    stats: Arc<RwLock<BTreeMap<String, LockStats>>>,
+}
+
+impl LockManager {
+    pub fn new(redis_client: redis::Client) -> Self {
+        Self {
+            redis_client,
+            stats: Arc::new(RwLock::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn lock_key(name: &str) -> String {
+        format!("{}{}", LOCK_KEY_PREFIX, name)
+    }
+
+    pub fn fence_key(name: &str) -> String {
+        format!("{}{}", FENCE_KEY_PREFIX, name)
+    }
+
+    /// 续期间隔：TTL 的三分之一，使一次续期失败后仍有时间重试
+    pub fn renew_interval(ttl: Duration) -> Duration {
+        (ttl / 3).max(MIN_RENEW_INTERVAL)
+    }
+
+    async fn get_connection(&self) -> Result<redis::aio::MultiplexedConnection, AppError> {
+        self.redis_client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get Redis connection: {}", e)))
+    }
+
+    fn record(&self, name: &str, event: LockEvent) {
+        let mut stats = self.stats.write().unwrap_or_else(|e| e.into_inner());
+        let entry = stats.entry(name.to_string()).or_insert_with(|| LockStats {
+            name: name.to_string(),
+            ..LockStats::default()
+        });
+        match event {
+            LockEvent::Acquired => entry.acquired += 1,
+            LockEvent::Contended => entry.contended += 1,
+            LockEvent::TimedOut => entry.timed_out += 1,
+            LockEvent::Renewed => entry.renewed += 1,
+            LockEvent::Lost => entry.lost += 1,
+            LockEvent::Released => entry.released += 1,
+        }
+    }
+
+    /// 各锁名的统计，按名称排序
+    pub fn stats(&self) -> Vec<LockStats> {
+        self.stats
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// 尝试获取锁，已被持有时立即返回 `None`
+    pub async fn try_acquire(
+        &self,
+        name: &str,
+        ttl: Duration,
+    ) -> Result<Option<LockGuard>, AppError> {
+        let owner = Uuid::new_v4().to_string();
+        let mut conn = self.get_connection().await?;
+        let fencing_token: u64 = redis::Script::new(ACQUIRE_SCRIPT)
+            .key(Self::lock_key(name))
+            .key(Self::fence_key(name))
+            .arg(&owner)
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to acquire lock {}: {}", name, e)))?;
+
+        if fencing_token == 0 {
+            self.record(name, LockEvent::Contended);
+            return Ok(None);
+        }
+        self.record(name, LockEvent::Acquired);
+        Ok(Some(LockGuard::start(
+            self.clone(),
+            name.to_string(),
+            owner,
+            fencing_token,
+            ttl,
+        )))
+    }
+
+    /// 获取锁，最多等待 `wait`；超时返回 `None`
+    pub async fn acquire(
+        &self,
+        name: &str,
+        ttl: Duration,
+        wait: Duration,
+    ) -> Result<Option<LockGuard>, AppError> {
+        let deadline = Instant::now() + wait;
+        let mut delay = MIN_RENEW_INTERVAL / 2;
+        loop {
+            if let Some(guard) = self.try_acquire(name, ttl).await? {
+                return Ok(Some(guard));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                self.record(name, LockEvent::TimedOut);
+                return Ok(None);
+            }
+            tokio::time::sleep(delay.min(deadline - now)).await;
+            delay = (delay * 2).min(MAX_RETRY_DELAY);
+        }
+    }
+
+    async fn renew(&self, name: &str, owner: &str, ttl: Duration) -> Result<bool, AppError> {
+        let mut conn = self.get_connection().await?;
+        let renewed: i64 = redis::Script::new(RENEW_SCRIPT)
+            .key(Self::lock_key(name))
+            .arg(owner)
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to renew lock {}: {}", name, e)))?;
+        Ok(renewed == 1)
+    }
+
+    async fn release(&self, name: &str, owner: &str) -> Result<bool, AppError> {
+        let mut conn = self.get_connection().await?;
+        let released: i64 = redis::Script::new(RELEASE_SCRIPT)
+            .key(Self::lock_key(name))
+            .arg(owner)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to release lock {}: {}", name, e)))?;
+        if released == 1 {
+            self.record(name, LockEvent::Released);
+        }
+        Ok(released == 1)
+    }
+}
+
+/// 已获取的锁
+///
+/// 持有期间后台任务每隔 [`LockManager::renew_interval`] 续期一次。
+/// 续期被拒绝或超过 TTL 未能续期时锁视为丢失，`is_held` 返回 false，
+/// 持有者应停止对受保护资源的写入。丢弃时在后台释放锁。
+pub struct LockGuard {
+    manager: LockManager,
+    name: String,
+    owner: String,
+    fencing_token: u64,
+    held: Arc<AtomicBool>,
+    renewal: Option<JoinHandle<()>>,
+}
+
+impl LockGuard {
+    fn start(
+        manager: LockManager,
+        name: String,
+        owner: String,
+        fencing_token: u64,
+        ttl: Duration,
+    ) -> Self {
+        let held = Arc::new(AtomicBool::new(true));
+        let renewal = tokio::spawn({
+            let manager = manager.clone();
+            let name = name.clone();
+            let owner = owner.clone();
+            let held = held.clone();
+            async move {
+                let interval = LockManager::renew_interval(ttl);
+                let mut renewed_at = Instant::now();
+                loop {
+                    tokio::time::sleep(interval).await;
+                    match manager.renew(&name, &owner, ttl).await {
+                        Ok(true) => {
+                            renewed_at = Instant::now();
+                            manager.record(&name, LockEvent::Renewed);
+                            continue;
+                        }
+                        Ok(false) => {
+                            tracing::warn!("Lock {} was taken over before renewal", name);
+                        }
+                        Err(e) if renewed_at.elapsed() < ttl => {
+                            tracing::warn!("Lock {} renewal failed, retrying: {}", name, e);
+                            continue;
+                        }
+                        Err(e) => {
+                            tracing::warn!("Lock {} expired while renewal failed: {}", name, e);
+                        }
+                    }
+                    held.store(false, Ordering::SeqCst);
+                    manager.record(&name, LockEvent::Lost);
+                    return;
+                }
+            }
+        });
+        Self {
+            manager,
+            name,
+            owner,
+            fencing_token,
+            held,
+            renewal: Some(renewal),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 本次获取的栅栏令牌，同一锁名下严格递增
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token
+    }
+
+    /// 锁是否仍由本持有者持有
+    pub fn is_held(&self) -> bool {
+        self.held.load(Ordering::SeqCst)
+    }
+
+    /// 释放锁；锁已丢失时返回 false
+    pub async fn release(mut self) -> Result<bool, AppError> {
+        if let Some(renewal) = self.renewal.take() {
+            renewal.abort();
+        }
+        self.held.store(false, Ordering::SeqCst);
+        self.manager.release(&self.name, &self.owner).await
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let Some(renewal) = self.renewal.take() else {
+            return;
+        };
+        renewal.abort();
+        if !self.held.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        let manager = self.manager.clone();
+        let name = std::mem::take(&mut self.name);
+        let owner = std::mem::take(&mut self.owner);
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                if let Err(e) = manager.release(&name, &owner).await {
+                    tracing::warn!("Failed to release lock {} on drop: {}", name, e);
+                }
+            });
+        }
+    }
+}
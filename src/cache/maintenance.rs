@@ -0,0 +1,239 @@
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::websocket::{DeliveryTarget, MessageType, WebSocketManager, WebSocketMessage};
+
+/// 全局维护模式键
+const GLOBAL_KEY: &str = "maintenance:global";
+/// 工作区维护模式键前缀
+const WORKSPACE_KEY_PREFIX: &str = "maintenance:workspace:";
+/// 维护模式变更通知频道，各副本据此通知本机的 WebSocket 客户端
+pub const EVENTS_CHANNEL: &str = "maintenance:events";
+/// 推送给 WebSocket 客户端的系统事件名
+pub const MAINTENANCE_EVENT: &str = "MaintenanceMode";
+
+/// 维护模式的开启记录
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct MaintenanceState {
+    /// 开启维护模式的管理员邮箱
+    pub enabled_by: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub started_at: DateTime<Utc>,
+}
+
+/// 维护模式的作用范围
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceScope {
+    Global,
+    Workspace,
+}
+
+/// 通过 Redis 频道广播的维护模式变更
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceEvent {
+    pub enabled: bool,
+    pub scope: MaintenanceScope,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl MaintenanceEvent {
+    /// 转换为推送给客户端的系统消息
+    pub fn to_ws_message(&self) -> WebSocketMessage {
+        WebSocketMessage {
+            id: Some(Uuid::new_v4().to_string()),
+            message_type: MessageType::SystemMessage,
+            data: serde_json::json!({
+                "event": MAINTENANCE_EVENT,
+                "enabled": self.enabled,
+                "scope": self.scope,
+                "workspace_id": self.workspace_id,
+                "message": self.message,
+            }),
+            timestamp: Some(Utc::now()),
+        }
+    }
+}
+
+/// 只读维护模式
+///
+/// 开关状态保存在 Redis 中，所有副本共享：可以对整个服务或单个工作区开启，
+/// 开启期间写请求被拒绝。每次变更还会发布到 `maintenance:events` 频道，
+/// 由各副本的监听任务通知本机连接的 WebSocket 客户端。
+#[derive(Clone)]
+pub struct MaintenanceMode {
+    redis_client: redis::Client,
+}
+
+impl MaintenanceMode {
+    pub fn new(redis_client: redis::Client) -> Self {
+        Self { redis_client }
+    }
+
+    async fn get_connection(&self) -> Result<redis::aio::MultiplexedConnection, AppError> {
+        self.redis_client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get Redis connection: {}", e)))
+    }
+
+    /// 作用范围对应的键；`None` 表示整个服务
+    pub fn key(workspace_id: Option<Uuid>) -> String {
+        match workspace_id {
+            Some(workspace_id) => format!("{}{}", WORKSPACE_KEY_PREFIX, workspace_id),
+            None => GLOBAL_KEY.to_string(),
+        }
+    }
+
+    /// 开启维护模式并通知所有副本
+    pub async fn enable(
+        &self,
+        workspace_id: Option<Uuid>,
+        state: &MaintenanceState,
+    ) -> Result<(), AppError> {
+        let value = serde_json::to_string(state).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize maintenance state: {}", e))
+        })?;
+        let mut conn = self.get_connection().await?;
+        let _: () = conn
+            .set(Self::key(workspace_id), value)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to enable maintenance: {}", e)))?;
+        self.publish(
+            &mut conn,
+            MaintenanceEvent {
+                enabled: true,
+                scope: Self::scope(workspace_id),
+                workspace_id,
+                message: state.message.clone(),
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    /// 关闭维护模式并通知所有副本
+    pub async fn disable(&self, workspace_id: Option<Uuid>) -> Result<(), AppError> {
+        let mut conn = self.get_connection().await?;
+        let _: () = conn
+            .del(Self::key(workspace_id))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to disable maintenance: {}", e)))?;
+        self.publish(
+            &mut conn,
+            MaintenanceEvent {
+                enabled: false,
+                scope: Self::scope(workspace_id),
+                workspace_id,
+                message: None,
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    /// 查询某个范围的维护状态，不考虑全局开关
+    pub async fn get(
+        &self,
+        workspace_id: Option<Uuid>,
+    ) -> Result<Option<MaintenanceState>, AppError> {
+        let mut conn = self.get_connection().await?;
+        let value: Option<String> = conn
+            .get(Self::key(workspace_id))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get maintenance state: {}", e)))?;
+        Ok(value.and_then(|value| Self::parse(&value)))
+    }
+
+    /// 请求是否处于维护模式：全局开关优先，其次是所在工作区
+    ///
+    /// Redis 不可用时放行并记录警告，避免缓存故障让整个服务变为只读。
+    pub async fn active_for(&self, workspace_id: Option<Uuid>) -> Option<MaintenanceState> {
+        let mut keys = vec![Self::key(None)];
+        keys.extend(workspace_id.map(|id| Self::key(Some(id))));
+        let result: Result<Vec<Option<String>>, AppError> = async {
+            let mut conn = self.get_connection().await?;
+            conn.mget(&keys)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to check maintenance: {}", e)))
+        }
+        .await;
+
+        match result {
+            Ok(values) => values.into_iter().flatten().find_map(|v| Self::parse(&v)),
+            Err(e) => {
+                tracing::warn!("Maintenance check skipped: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 订阅变更频道，将事件转发给本机的 WebSocket 客户端；
+    /// 全局事件广播给所有连接，工作区事件只发给该工作区的连接
+    pub async fn run_listener(self, ws_manager: WebSocketManager) {
+        loop {
+            if let Err(e) = self.listen(&ws_manager).await {
+                tracing::warn!("Maintenance listener disconnected: {}", e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+    }
+
+    async fn listen(&self, ws_manager: &WebSocketManager) -> redis::RedisResult<()> {
+        let mut pubsub = self.redis_client.get_async_pubsub().await?;
+        pubsub.subscribe(EVENTS_CHANNEL).await?;
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let payload: String = msg.get_payload()?;
+            let Ok(event) = serde_json::from_str::<MaintenanceEvent>(&payload) else {
+                tracing::warn!("Ignoring malformed maintenance event: {}", payload);
+                continue;
+            };
+            // 每个副本都订阅了该频道，只投递本机连接，避免再经跨实例广播重复发送
+            let target = match event.workspace_id {
+                Some(workspace_id) => DeliveryTarget::Workspace { workspace_id },
+                None => DeliveryTarget::All,
+            };
+            ws_manager
+                .deliver_local(&target, event.to_ws_message())
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn publish(&self, conn: &mut redis::aio::MultiplexedConnection, event: MaintenanceEvent) {
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!("Failed to serialize maintenance event: {}", e);
+                return;
+            }
+        };
+        let published: redis::RedisResult<i64> = conn.publish(EVENTS_CHANNEL, payload).await;
+        if let Err(e) = published {
+            tracing::warn!("Failed to publish maintenance event: {}", e);
+        }
+    }
+
+    fn scope(workspace_id: Option<Uuid>) -> MaintenanceScope {
+        match workspace_id {
+            Some(_) => MaintenanceScope::Workspace,
+            None => MaintenanceScope::Global,
+        }
+    }
+
+    fn parse(value: &str) -> Option<MaintenanceState> {
+        serde_json::from_str(value)
+            .inspect_err(|e| tracing::warn!("Ignoring malformed maintenance state: {}", e))
+            .ok()
+    }
+}
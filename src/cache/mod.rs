@@ -1,6 +1,16 @@
+pub mod locks;
+pub mod maintenance;
 pub mod redis;
+pub mod store;
+pub mod token_revocation;
+pub mod typed;
 pub mod user_cache;
 
+pub use locks::{LockGuard, LockManager, LockStats};
+pub use maintenance::{MaintenanceMode, MaintenanceState};
+pub use store::{CacheStore, MemoryCacheStore, RedisCacheStore};
+pub use token_revocation::TokenRevocationList;
+pub use typed::CacheEntry;
 pub use user_cache::{CacheConfig, CacheStats, UserCache};
 
 use crate::error::AppError;
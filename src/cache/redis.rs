@@ -16,6 +16,20 @@ pub async fn set_cache<T: Serialize>(client: &redis::Client, key: &str, value: &
     let _: () = conn.set_ex(key, json, ttl).await.unwrap();
 }
 
+/// 与 `set_cache` 相同，但失败时返回错误而不是 panic，适用于缓存可有可无的读路径
+pub async fn try_set_cache<T: Serialize>(
+    client: &redis::Client,
+    key: &str,
+    value: &T,
+    ttl: u64,
+) -> Result<(), redis::RedisError> {
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    let json = serde_json::to_string(value).map_err(|e| {
+        redis::RedisError::from((redis::ErrorKind::TypeError, "serialize", e.to_string()))
+    })?;
+    conn.set_ex(key, json, ttl).await
+}
+
 pub async fn get_user_current_workspace_id(client: &redis::Client, user_id: Uuid) -> Option<Uuid> {
     let key = format!("user:{}:current_workspace_id", user_id);
     let mut conn = client.get_multiplexed_async_connection().await.ok()?;
@@ -0,0 +1,149 @@
+//! 键值缓存的抽象
+//!
+//! 处理器通过 `AppState::cache` 读写缓存，而不是直接持有 `redis::Client`：
+//! 生产环境使用 [`RedisCacheStore`]，测试可换成进程内的 [`MemoryCacheStore`]，
+//! 不需要运行 Redis。
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use serde::{Serialize, de::DeserializeOwned};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::error::AppError;
+use crate::utils::clock;
+
+/// 带过期时间的字符串键值存储
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<String>, AppError>;
+
+    /// 写入并在 `ttl_secs` 秒后过期
+    async fn set_ex(&self, key: &str, value: String, ttl_secs: u64) -> Result<(), AppError>;
+
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Redis 实现
+#[derive(Clone)]
+pub struct RedisCacheStore {
+    client: redis::Client,
+}
+
+impl RedisCacheStore {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+
+    async fn connection(&self) -> Result<redis::aio::MultiplexedConnection, AppError> {
+        self.client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get Redis connection: {}", e)))
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisCacheStore {
+    async fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+        self.connection()
+            .await?
+            .get(key)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read cache: {}", e)))
+    }
+
+    async fn set_ex(&self, key: &str, value: String, ttl_secs: u64) -> Result<(), AppError> {
+        self.connection()
+            .await?
+            .set_ex(key, value, ttl_secs)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write cache: {}", e)))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.connection()
+            .await?
+            .del(key)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to delete cache key: {}", e)))
+    }
+}
+
+/// 进程内实现，过期时间按 [`clock::now`] 计算，冻结时钟后可以推进时间测试过期
+#[derive(Default)]
+pub struct MemoryCacheStore {
+    entries: Mutex<HashMap<String, (String, DateTime<Utc>)>>,
+}
+
+impl MemoryCacheStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 当前未过期的键数量
+    pub fn len(&self) -> usize {
+        let now = clock::now();
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .filter(|(_, expires_at)| *expires_at > now)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl CacheStore for MemoryCacheStore {
+    async fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+        let now = clock::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some((value, expires_at)) if *expires_at > now => Ok(Some(value.clone())),
+            Some(_) => {
+                entries.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set_ex(&self, key: &str, value: String, ttl_secs: u64) -> Result<(), AppError> {
+        let expires_at = clock::now() + chrono::Duration::seconds(ttl_secs as i64);
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), (value, expires_at));
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+        Ok(())
+    }
+}
+
+/// 读取并反序列化缓存值；未命中、读取失败或格式不符都返回 `None`
+pub async fn get_json<T: DeserializeOwned>(store: &dyn CacheStore, key: &str) -> Option<T> {
+    let value = store.get(key).await.ok()??;
+    serde_json::from_str(&value).ok()
+}
+
+/// 序列化后写入缓存
+pub async fn set_json<T: Serialize + Sync>(
+    store: &dyn CacheStore,
+    key: &str,
+    value: &T,
+    ttl_secs: u64,
+) -> Result<(), AppError> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize cache value: {}", e)))?;
+    store.set_ex(key, json, ttl_secs).await
+}
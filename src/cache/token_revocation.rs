@@ -0,0 +1,118 @@
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::auth::AuthConfig;
+
+/// 单个令牌的吊销记录键前缀（按 jti）
+const REVOKED_JTI_PREFIX: &str = "auth:revoked:jti:";
+/// 用户级吊销时间点键前缀；在该时间点之前签发的令牌全部失效
+const REVOKED_BEFORE_PREFIX: &str = "auth:revoked_before:";
+
+/// JWT 吊销列表
+///
+/// JWT 本身无状态，登出、修改密码或管理员强制下线后仍会在过期前保持有效。
+/// 这里在 Redis 中记录被吊销的 jti 和用户级吊销时间点，由 HTTP 认证中间件
+/// 和 WebSocket 认证在验签后查询。记录只需保留到对应令牌自然过期为止。
+#[derive(Clone)]
+pub struct TokenRevocationList {
+    redis_client: redis::Client,
+    /// 用户级吊销时间点的保留时间（秒），不短于任何令牌的有效期
+    cutoff_ttl: u64,
+}
+
+impl TokenRevocationList {
+    pub fn new(redis_client: redis::Client) -> Self {
+        let config = AuthConfig::default();
+        let cutoff_ttl = config
+            .jwt_expiration
+            .max(config.refresh_expiration)
+            .as_secs();
+        Self {
+            redis_client,
+            cutoff_ttl,
+        }
+    }
+
+    async fn get_connection(&self) -> Result<redis::aio::MultiplexedConnection, AppError> {
+        self.redis_client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get Redis connection: {}", e)))
+    }
+
+    pub fn now() -> u64 {
+        chrono::Utc::now().timestamp().max(0) as u64
+    }
+
+    /// 吊销单个令牌，记录保留到令牌过期
+    pub async fn revoke_token(&self, jti: &str, expires_at: u64) -> Result<(), AppError> {
+        let ttl = expires_at.saturating_sub(Self::now());
+        if ttl == 0 {
+            return Ok(());
+        }
+
+        let mut conn = self.get_connection().await?;
+        let key = format!("{}{}", REVOKED_JTI_PREFIX, jti);
+        let _: () = conn
+            .set_ex(&key, 1, ttl)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to revoke token: {}", e)))?;
+        Ok(())
+    }
+
+    /// 吊销用户此前签发的所有令牌；之后重新登录获得的令牌不受影响
+    pub async fn revoke_all_for_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        self.revoke_issued_before(user_id, Self::now()).await
+    }
+
+    /// 吊销用户在 `cutoff`（Unix 秒）之前签发的所有令牌
+    pub async fn revoke_issued_before(&self, user_id: Uuid, cutoff: u64) -> Result<(), AppError> {
+        let mut conn = self.get_connection().await?;
+        let key = format!("{}{}", REVOKED_BEFORE_PREFIX, user_id);
+        let _: () = conn
+            .set_ex(&key, cutoff, self.cutoff_ttl)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to revoke user tokens: {}", e)))?;
+        Ok(())
+    }
+
+    /// 令牌是否已被吊销
+    ///
+    /// Redis 不可用时放行并记录警告，避免缓存故障导致所有请求认证失败。
+    pub async fn is_revoked(&self, user_id: Uuid, jti: &str, issued_at: u64) -> bool {
+        let keys = [
+            format!("{}{}", REVOKED_JTI_PREFIX, jti),
+            format!("{}{}", REVOKED_BEFORE_PREFIX, user_id),
+        ];
+        let result: Result<(Option<u8>, Option<u64>), AppError> = async {
+            let mut conn = self.get_connection().await?;
+            conn.mget(&keys)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to check revocation: {}", e)))
+        }
+        .await;
+
+        match result {
+            Ok((revoked_jti, cutoff)) => {
+                revoked_jti.is_some() || Self::issued_before_cutoff(issued_at, cutoff)
+            }
+            // 放行意味着已吊销的令牌可能被接受，记录具体令牌便于事后排查
+            Err(e) => {
+                tracing::warn!(
+                    "Token revocation check failed, accepting token {} of user {} unchecked: {}",
+                    jti,
+                    user_id,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// 签发时间早于用户级吊销时间点的令牌视为已吊销。
+    /// `iat` 精度为秒，吊销同一秒内重新签发的令牌仍然有效。
+    pub fn issued_before_cutoff(issued_at: u64, cutoff: Option<u64>) -> bool {
+        cutoff.is_some_and(|cutoff| issued_at < cutoff)
+    }
+}
@@ -0,0 +1,172 @@
+//! 带版本失效的类型化缓存
+//!
+//! 每个 [`CacheEntry`] 描述一类缓存数据（如某工作区的标签列表）：值以 JSON 存在
+//! [`CacheStore`] 中，键里带有该范围（通常是工作区 ID）的当前版本。失效时只删除
+//! 版本键，下一次读取会生成新版本，旧版本下的值不再被读到并随 TTL 过期。
+//! 与直接删除数据键相比，修改提交前开始的读取即使在失效之后才写回旧数据，
+//! 也只会写到已经废弃的版本下。
+//!
+//! 修改数据的服务运行在同步的数据库事务里，拿不到 `AppState`；启动时用
+//! [`install`] 注册共享的缓存，服务通过 [`invalidate_after_commit`] 在事务提交后失效。
+use serde::{Serialize, de::DeserializeOwned};
+use std::fmt::Display;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::{Arc, OnceLock};
+
+use super::store::{CacheStore, get_json, set_json};
+use crate::db::after_commit;
+use crate::error::AppError;
+use crate::utils::clock;
+
+static STORE: OnceLock<Arc<dyn CacheStore>> = OnceLock::new();
+
+/// 注册读取和失效共用的缓存；之后的调用会被忽略
+pub fn install(store: Arc<dyn CacheStore>) {
+    let _ = STORE.set(store);
+}
+
+/// 已注册的缓存；未注册时（测试、一次性命令）读取直接访问数据库
+pub fn installed() -> Option<&'static dyn CacheStore> {
+    STORE.get().map(|store| store.as_ref())
+}
+
+/// 一类缓存数据：键前缀、值类型和过期时间
+pub struct CacheEntry<T> {
+    namespace: &'static str,
+    ttl_secs: u64,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> CacheEntry<T> {
+    pub const fn new(namespace: &'static str, ttl_secs: u64) -> Self {
+        Self {
+            namespace,
+            ttl_secs,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn ttl_secs(&self) -> u64 {
+        self.ttl_secs
+    }
+
+    fn version_key(&self, scope: impl Display) -> String {
+        format!("cache:{}:{}:version", self.namespace, scope)
+    }
+
+    fn value_key(&self, scope: impl Display, version: &str, variant: &str) -> String {
+        format!("cache:{}:{}:{}:{}", self.namespace, scope, version, variant)
+    }
+
+    /// 范围的当前版本，不存在时生成一个新版本
+    async fn version(
+        &self,
+        store: &dyn CacheStore,
+        scope: impl Display,
+    ) -> Result<String, AppError> {
+        let key = self.version_key(&scope);
+        if let Some(version) = store.get(&key).await? {
+            return Ok(version);
+        }
+        let version = clock::new_id().simple().to_string();
+        store.set_ex(&key, version.clone(), self.ttl_secs).await?;
+        Ok(version)
+    }
+
+    /// 让范围下所有变体的缓存值失效
+    pub async fn invalidate(
+        &self,
+        store: &dyn CacheStore,
+        scope: impl Display,
+    ) -> Result<(), AppError> {
+        store.delete(&self.version_key(scope)).await
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Sync> CacheEntry<T> {
+    /// 读取当前版本下的值；未命中或缓存不可用时返回 `None`
+    pub async fn get(
+        &self,
+        store: &dyn CacheStore,
+        scope: impl Display,
+        variant: &str,
+    ) -> Option<T> {
+        let version = self.version(store, &scope).await.ok()?;
+        get_json(store, &self.value_key(&scope, &version, variant)).await
+    }
+
+    /// 在当前版本下写入值
+    pub async fn set(
+        &self,
+        store: &dyn CacheStore,
+        scope: impl Display,
+        variant: &str,
+        value: &T,
+    ) -> Result<(), AppError> {
+        let version = self.version(store, &scope).await?;
+        set_json(
+            store,
+            &self.value_key(&scope, &version, variant),
+            value,
+            self.ttl_secs,
+        )
+        .await
+    }
+
+    /// 命中时返回缓存值，否则调用 `load` 并写回。写回先读取版本再加载，
+    /// 加载期间的失效会让写回的值落在旧版本下。缓存出错只记录日志，不影响结果。
+    pub async fn get_or_load<F, Fut>(
+        &self,
+        store: Option<&dyn CacheStore>,
+        scope: impl Display,
+        variant: &str,
+        load: F,
+    ) -> Result<T, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let Some(store) = store else {
+            return load().await;
+        };
+        let version = match self.version(store, &scope).await {
+            Ok(version) => version,
+            Err(e) => {
+                tracing::debug!("Skipping {} cache: {}", self.namespace, e);
+                return load().await;
+            }
+        };
+        let key = self.value_key(&scope, &version, variant);
+        if let Some(value) = get_json(store, &key).await {
+            return Ok(value);
+        }
+        let value = load().await?;
+        if let Err(e) = set_json(store, &key, &value, self.ttl_secs).await {
+            tracing::debug!("Failed to cache {}: {}", self.namespace, e);
+        }
+        Ok(value)
+    }
+}
+
+/// 当前事务提交后让 `entry` 在 `scope` 下失效；未注册缓存或不在运行时内时什么都不做
+pub fn invalidate_after_commit<T: 'static>(entry: &'static CacheEntry<T>, scope: uuid::Uuid) {
+    let Some(store) = STORE.get().cloned() else {
+        return;
+    };
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    after_commit(move || {
+        runtime.spawn(async move {
+            if let Err(e) = entry.invalidate(store.as_ref(), scope).await {
+                tracing::warn!(
+                    "Failed to invalidate {} cache of {}: {}",
+                    entry.namespace,
+                    scope,
+                    e
+                );
+            }
+        });
+    });
+}
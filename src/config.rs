@@ -1,7 +1,9 @@
+use crate::db::models::user_identity::LoginProvider;
 use crate::error::{AppError, AppResult};
 use serde::Deserialize;
+use std::collections::HashMap;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
 pub struct Config {
     pub database_url: String,
     #[serde(default = "default_max_connections")]
@@ -21,6 +23,31 @@ pub struct Config {
     pub server_port: u16,
     #[serde(default = "default_cors_origins")]
     pub cors_origins: Vec<String>,
+    /// Send `Access-Control-Allow-Credentials`; requires explicit origins
+    #[serde(default)]
+    pub cors_allow_credentials: bool,
+    /// How long browsers may cache preflight responses
+    #[serde(default = "default_cors_max_age_secs")]
+    pub cors_max_age_secs: u64,
+
+    /// Deployment environment: "development", "staging" or "production";
+    /// selects defaults such as HSTS
+    #[serde(default = "default_app_env")]
+    pub app_env: String,
+    /// Add HSTS, X-Content-Type-Options, Referrer-Policy and friends to responses
+    #[serde(default = "default_security_headers_enabled")]
+    pub security_headers_enabled: bool,
+    /// `Strict-Transport-Security` max-age; 0 omits the header. Defaults to a
+    /// year in production and off elsewhere
+    #[serde(default)]
+    pub hsts_max_age_secs: Option<u64>,
+    #[serde(default)]
+    pub hsts_include_subdomains: bool,
+    #[serde(default = "default_referrer_policy")]
+    pub referrer_policy: String,
+    /// Policy sent with HTML responses
+    #[serde(default = "default_content_security_policy")]
+    pub content_security_policy: String,
 
     #[serde(default = "default_jwt_secret")]
     pub jwt_secret: String,
@@ -36,9 +63,303 @@ pub struct Config {
 
     #[serde(default = "default_assets_url")]
     pub assets_url: String,
+    /// Per region asset base URLs, e.g. `eu=https://eu.cdn.example.com/assets`
+    #[serde(default)]
+    pub assets_region_urls: Vec<String>,
+    /// Regions to try when a region is unavailable, e.g. `ap=eu|us`
+    #[serde(default)]
+    pub assets_region_fallbacks: Vec<String>,
+    /// How often asset regions are probed for availability and latency
+    #[serde(default = "default_assets_region_probe_secs")]
+    pub assets_region_probe_secs: u64,
 
     #[serde(default = "default_bcrypt_cost")]
     pub bcrypt_cost: u32,
+
+    /// Emails of operators allowed to call `/admin/*` endpoints
+    #[serde(default)]
+    pub admin_emails: Vec<String>,
+    /// Bearer token scrapers must send to `/metrics`; unset leaves it open
+    #[serde(default)]
+    pub metrics_token: Option<String>,
+
+    /// Where WebSocket monitoring snapshots are stored: "redis" or "memory"
+    #[serde(default = "default_ws_metrics_sink")]
+    pub ws_metrics_sink: String,
+    #[serde(default = "default_ws_metrics_retention_hours")]
+    pub ws_metrics_retention_hours: u64,
+    /// How WebSocket broadcasts reach clients connected to other instances:
+    /// "redis" (pub/sub) or "none" for a single instance
+    #[serde(default = "default_ws_fanout")]
+    pub ws_fanout: String,
+    /// Outgoing WebSocket payloads at least this large are compressed for
+    /// clients that negotiated compression at the handshake
+    #[serde(default = "default_ws_compression_threshold_bytes")]
+    pub ws_compression_threshold_bytes: usize,
+
+    /// Per event type in-app coalescing windows, e.g. `issue_updated=600`
+    #[serde(default)]
+    pub notification_batch_windows: Vec<String>,
+    /// Per event type email digest quiet periods, e.g. `issue_updated=1800`
+    #[serde(default)]
+    pub notification_email_windows: Vec<String>,
+
+    /// Domain for per-issue reply addresses (`reply+<token>@domain`); unset disables reply-by-email
+    #[serde(default)]
+    pub email_reply_domain: Option<String>,
+    /// Shared secret the inbound email provider sends in `X-Inbound-Secret`
+    #[serde(default)]
+    pub inbound_email_secret: Option<String>,
+
+    /// S3-compatible endpoint for issue attachments, e.g. `https://s3.amazonaws.com`
+    /// or a MinIO URL; attachments are disabled unless endpoint, bucket and keys are set
+    #[serde(default)]
+    pub storage_endpoint: Option<String>,
+    #[serde(default)]
+    pub storage_bucket: Option<String>,
+    #[serde(default = "default_storage_region")]
+    pub storage_region: String,
+    #[serde(default)]
+    pub storage_access_key: Option<String>,
+    #[serde(default)]
+    pub storage_secret_key: Option<String>,
+    /// Address objects as `endpoint/bucket/key` (MinIO) instead of `bucket.endpoint/key`
+    #[serde(default = "default_storage_path_style")]
+    pub storage_path_style: bool,
+    #[serde(default = "default_attachment_max_bytes")]
+    pub attachment_max_bytes: i64,
+    /// Largest file accepted through resumable upload sessions
+    #[serde(default = "default_upload_session_max_bytes")]
+    pub upload_session_max_bytes: i64,
+    /// Upload sessions with no chunk appended for this long are aborted
+    #[serde(default = "default_upload_session_ttl_secs")]
+    pub upload_session_ttl_secs: u64,
+
+    /// Sliding-window limits for HTTP routes, counted in Redis
+    #[serde(default = "default_rate_limit_enabled")]
+    pub rate_limit_enabled: bool,
+    #[serde(default = "default_rate_limit_window_secs")]
+    pub rate_limit_window_secs: u64,
+    #[serde(default = "default_rate_limit_per_user")]
+    pub rate_limit_per_user: u32,
+    #[serde(default = "default_rate_limit_per_ip")]
+    pub rate_limit_per_ip: u32,
+    /// Take the client IP from `X-Forwarded-For` / `X-Real-IP`; only enable behind a trusted proxy
+    #[serde(default)]
+    pub rate_limit_trust_proxy: bool,
+
+    /// Public base URL of this API; login provider callbacks go to
+    /// `<base>/auth/oauth/<provider>/callback`
+    #[serde(default = "default_oauth_redirect_base_url")]
+    pub oauth_redirect_base_url: String,
+    /// Google and GitHub sign-in; a provider is enabled once both values are set
+    #[serde(default)]
+    pub oauth_google_client_id: Option<String>,
+    #[serde(default)]
+    pub oauth_google_client_secret: Option<String>,
+    #[serde(default)]
+    pub oauth_github_client_id: Option<String>,
+    #[serde(default)]
+    pub oauth_github_client_secret: Option<String>,
+
+    /// Outgoing email transport: "smtp", "ses", or "log" to only log messages
+    #[serde(default = "default_email_backend")]
+    pub email_backend: String,
+    /// Sender of outgoing email, e.g. `Momentum <no-reply@example.com>`
+    #[serde(default = "default_email_from")]
+    pub email_from: String,
+    /// Web app URL that links in emails point to
+    #[serde(default = "default_app_url")]
+    pub app_url: String,
+    #[serde(default)]
+    pub smtp_host: Option<String>,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default)]
+    pub smtp_username: Option<String>,
+    #[serde(default)]
+    pub smtp_password: Option<String>,
+    /// Connect with TLS from the start (usually port 465) instead of STARTTLS
+    #[serde(default)]
+    pub smtp_implicit_tls: bool,
+    /// DNS-over-HTTPS JSON endpoint used to verify workspace email domains
+    #[serde(default = "default_email_domain_dns_url")]
+    pub email_domain_dns_url: String,
+    #[serde(default = "default_storage_region")]
+    pub ses_region: String,
+    #[serde(default)]
+    pub ses_access_key: Option<String>,
+    #[serde(default)]
+    pub ses_secret_key: Option<String>,
+
+    /// Web Push (VAPID): contact URI, base64url public key and PEM (P-256) private key
+    #[serde(default)]
+    pub push_vapid_subject: Option<String>,
+    #[serde(default)]
+    pub push_vapid_public_key: Option<String>,
+    #[serde(default)]
+    pub push_vapid_private_key: Option<String>,
+    /// APNs token auth: key id, team id, PEM (.p8) key and the app's bundle id
+    #[serde(default)]
+    pub apns_key_id: Option<String>,
+    #[serde(default)]
+    pub apns_team_id: Option<String>,
+    #[serde(default)]
+    pub apns_private_key: Option<String>,
+    #[serde(default)]
+    pub apns_topic: Option<String>,
+    /// Send to the APNs development environment
+    #[serde(default)]
+    pub apns_sandbox: bool,
+    /// FCM HTTP v1: Firebase project and service account credentials
+    #[serde(default)]
+    pub fcm_project_id: Option<String>,
+    #[serde(default)]
+    pub fcm_client_email: Option<String>,
+    #[serde(default)]
+    pub fcm_private_key: Option<String>,
+
+    /// Schedule overrides of periodic jobs as `;`-separated `name=schedule`
+    /// pairs, e.g. `cycle_status=*/5 * * * *;ws_connection_cleanup=off`
+    #[serde(default)]
+    pub scheduler_jobs: String,
+
+    /// Send anonymous aggregate usage reports to `TELEMETRY_ENDPOINT`; off
+    /// unless set. `/admin/telemetry` shows exactly what would be sent
+    #[serde(default)]
+    pub telemetry_enabled: bool,
+    #[serde(default)]
+    pub telemetry_endpoint: Option<String>,
+}
+
+/// Placeholder printed instead of secrets
+const REDACTED: &str = "<redacted>";
+
+fn redacted(value: &Option<String>) -> Option<&'static str> {
+    value.as_ref().map(|_| REDACTED)
+}
+
+// Written by hand so secrets and credentials never end up in logs
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("database_url", &REDACTED)
+            .field("database_max_connections", &self.database_max_connections)
+            .field("database_min_connections", &self.database_min_connections)
+            .field(
+                "database_connection_timeout",
+                &self.database_connection_timeout,
+            )
+            .field("redis_url", &REDACTED)
+            .field("redis_pool_size", &self.redis_pool_size)
+            .field("server_host", &self.server_host)
+            .field("server_port", &self.server_port)
+            .field("cors_origins", &self.cors_origins)
+            .field("cors_allow_credentials", &self.cors_allow_credentials)
+            .field("cors_max_age_secs", &self.cors_max_age_secs)
+            .field("app_env", &self.app_env)
+            .field("security_headers_enabled", &self.security_headers_enabled)
+            .field("hsts_max_age_secs", &self.hsts_max_age_secs)
+            .field("hsts_include_subdomains", &self.hsts_include_subdomains)
+            .field("referrer_policy", &self.referrer_policy)
+            .field("content_security_policy", &self.content_security_policy)
+            .field("jwt_secret", &REDACTED)
+            .field(
+                "jwt_access_token_expires_in",
+                &self.jwt_access_token_expires_in,
+            )
+            .field(
+                "jwt_refresh_token_expires_in",
+                &self.jwt_refresh_token_expires_in,
+            )
+            .field("log_level", &self.log_level)
+            .field("log_format", &self.log_format)
+            .field("assets_url", &self.assets_url)
+            .field("assets_region_urls", &self.assets_region_urls)
+            .field("assets_region_fallbacks", &self.assets_region_fallbacks)
+            .field("assets_region_probe_secs", &self.assets_region_probe_secs)
+            .field("bcrypt_cost", &self.bcrypt_cost)
+            .field("admin_emails", &self.admin_emails)
+            .field("metrics_token", &redacted(&self.metrics_token))
+            .field("ws_metrics_sink", &self.ws_metrics_sink)
+            .field(
+                "ws_metrics_retention_hours",
+                &self.ws_metrics_retention_hours,
+            )
+            .field("ws_fanout", &self.ws_fanout)
+            .field(
+                "ws_compression_threshold_bytes",
+                &self.ws_compression_threshold_bytes,
+            )
+            .field(
+                "notification_batch_windows",
+                &self.notification_batch_windows,
+            )
+            .field(
+                "notification_email_windows",
+                &self.notification_email_windows,
+            )
+            .field("email_reply_domain", &self.email_reply_domain)
+            .field(
+                "inbound_email_secret",
+                &redacted(&self.inbound_email_secret),
+            )
+            .field("storage_endpoint", &self.storage_endpoint)
+            .field("storage_bucket", &self.storage_bucket)
+            .field("storage_region", &self.storage_region)
+            .field("storage_access_key", &self.storage_access_key)
+            .field("storage_secret_key", &redacted(&self.storage_secret_key))
+            .field("storage_path_style", &self.storage_path_style)
+            .field("attachment_max_bytes", &self.attachment_max_bytes)
+            .field("upload_session_max_bytes", &self.upload_session_max_bytes)
+            .field("upload_session_ttl_secs", &self.upload_session_ttl_secs)
+            .field("rate_limit_enabled", &self.rate_limit_enabled)
+            .field("rate_limit_window_secs", &self.rate_limit_window_secs)
+            .field("rate_limit_per_user", &self.rate_limit_per_user)
+            .field("rate_limit_per_ip", &self.rate_limit_per_ip)
+            .field("rate_limit_trust_proxy", &self.rate_limit_trust_proxy)
+            .field("oauth_redirect_base_url", &self.oauth_redirect_base_url)
+            .field("oauth_google_client_id", &self.oauth_google_client_id)
+            .field(
+                "oauth_google_client_secret",
+                &redacted(&self.oauth_google_client_secret),
+            )
+            .field("oauth_github_client_id", &self.oauth_github_client_id)
+            .field(
+                "oauth_github_client_secret",
+                &redacted(&self.oauth_github_client_secret),
+            )
+            .field("email_backend", &self.email_backend)
+            .field("email_from", &self.email_from)
+            .field("app_url", &self.app_url)
+            .field("smtp_host", &self.smtp_host)
+            .field("smtp_port", &self.smtp_port)
+            .field("smtp_username", &self.smtp_username)
+            .field("smtp_password", &redacted(&self.smtp_password))
+            .field("smtp_implicit_tls", &self.smtp_implicit_tls)
+            .field("email_domain_dns_url", &self.email_domain_dns_url)
+            .field("ses_region", &self.ses_region)
+            .field("ses_access_key", &self.ses_access_key)
+            .field("ses_secret_key", &redacted(&self.ses_secret_key))
+            .field("push_vapid_subject", &self.push_vapid_subject)
+            .field("push_vapid_public_key", &self.push_vapid_public_key)
+            .field(
+                "push_vapid_private_key",
+                &redacted(&self.push_vapid_private_key),
+            )
+            .field("apns_key_id", &self.apns_key_id)
+            .field("apns_team_id", &self.apns_team_id)
+            .field("apns_private_key", &redacted(&self.apns_private_key))
+            .field("apns_topic", &self.apns_topic)
+            .field("apns_sandbox", &self.apns_sandbox)
+            .field("fcm_project_id", &self.fcm_project_id)
+            .field("fcm_client_email", &self.fcm_client_email)
+            .field("fcm_private_key", &redacted(&self.fcm_private_key))
+            .field("scheduler_jobs", &self.scheduler_jobs)
+            .field("telemetry_enabled", &self.telemetry_enabled)
+            .field("telemetry_endpoint", &self.telemetry_endpoint)
+            .finish()
+    }
 }
 
 // 为了向后兼容，创建嵌套结构的访问器
@@ -63,6 +384,22 @@ pub struct ServerConfig {
     pub cors_origins: Vec<String>,
 }
 
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    pub origins: Vec<String>,
+    pub allow_credentials: bool,
+    pub max_age_secs: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct SecurityHeadersConfig {
+    pub enabled: bool,
+    pub hsts_max_age_secs: u64,
+    pub hsts_include_subdomains: bool,
+    pub referrer_policy: String,
+    pub content_security_policy: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct AuthConfig {
     pub jwt_secret: String,
@@ -76,9 +413,105 @@ pub struct LoggingConfig {
     pub format: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct AssetsConfig {
     pub base_url: String,
+    /// Region name and base URL, in order of configuration
+    pub regions: Vec<(String, String)>,
+    pub fallbacks: HashMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpRateLimitConfig {
+    pub enabled: bool,
+    pub window_secs: u64,
+    pub per_user: u32,
+    pub per_ip: u32,
+    pub trust_proxy: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct LoginProviderConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub implicit_tls: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct SesConfig {
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum EmailBackendConfig {
+    Log,
+    Smtp(SmtpConfig),
+    Ses(SesConfig),
+}
+
+#[derive(Clone, Debug)]
+pub struct EmailConfig {
+    pub from: String,
+    pub app_url: String,
+    pub backend: EmailBackendConfig,
+}
+
+#[derive(Clone, Debug)]
+pub struct WebPushConfig {
+    pub subject: String,
+    pub public_key: String,
+    pub private_key: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ApnsConfig {
+    pub key_id: String,
+    pub team_id: String,
+    pub private_key: String,
+    pub topic: String,
+    pub sandbox: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct FcmConfig {
+    pub project_id: String,
+    pub client_email: String,
+    pub private_key: String,
+}
+
+/// Push providers; each one is enabled once all its settings are present
+#[derive(Clone, Debug, Default)]
+pub struct PushConfig {
+    pub web: Option<WebPushConfig>,
+    pub apns: Option<ApnsConfig>,
+    pub fcm: Option<FcmConfig>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TelemetryConfig {
+    pub endpoint: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct StorageConfig {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+    pub path_style: bool,
+    pub max_attachment_bytes: i64,
 }
 
 // Default value functions
@@ -103,6 +536,23 @@ fn default_port() -> u16 {
 fn default_cors_origins() -> Vec<String> {
     vec!["*".to_string()]
 }
+fn default_cors_max_age_secs() -> u64 {
+    3600
+}
+fn default_app_env() -> String {
+    "development".to_string()
+}
+fn default_security_headers_enabled() -> bool {
+    true
+}
+fn default_referrer_policy() -> String {
+    "strict-origin-when-cross-origin".to_string()
+}
+fn default_content_security_policy() -> String {
+    "default-src 'self'; img-src 'self' data:; style-src 'self' 'unsafe-inline'; \
+     frame-ancestors 'none'; base-uri 'self'; form-action 'self'"
+        .to_string()
+}
 fn default_jwt_secret() -> String {
     "your-secret-key".to_string()
 }
@@ -121,9 +571,69 @@ fn default_log_format() -> String {
 fn default_assets_url() -> String {
     "http://localhost:8000/assets".to_string()
 }
+fn default_assets_region_probe_secs() -> u64 {
+    30
+}
 fn default_bcrypt_cost() -> u32 {
     4
 } // Further reduce cost for better performance, use 12+ for production
+fn default_ws_metrics_sink() -> String {
+    "redis".to_string()
+}
+fn default_ws_metrics_retention_hours() -> u64 {
+    168
+} // 7 days
+fn default_ws_fanout() -> String {
+    "redis".to_string()
+}
+fn default_ws_compression_threshold_bytes() -> usize {
+    8 * 1024
+}
+fn default_storage_region() -> String {
+    "us-east-1".to_string()
+}
+fn default_storage_path_style() -> bool {
+    true
+}
+fn default_attachment_max_bytes() -> i64 {
+    25 * 1024 * 1024
+} // 25 MiB
+fn default_upload_session_max_bytes() -> i64 {
+    5 * 1024 * 1024 * 1024
+} // 5 GiB
+fn default_upload_session_ttl_secs() -> u64 {
+    86400
+} // 1 day
+fn default_rate_limit_enabled() -> bool {
+    true
+}
+fn default_rate_limit_window_secs() -> u64 {
+    60
+}
+fn default_rate_limit_per_user() -> u32 {
+    600
+}
+fn default_rate_limit_per_ip() -> u32 {
+    300
+}
+fn default_oauth_redirect_base_url() -> String {
+    "http://localhost:8000".to_string()
+}
+fn default_email_backend() -> String {
+    "log".to_string()
+}
+fn default_email_from() -> String {
+    "Momentum <no-reply@localhost>".to_string()
+}
+fn default_app_url() -> String {
+    "http://localhost:3000".to_string()
+}
+fn default_email_domain_dns_url() -> String {
+    "https://cloudflare-dns.com/dns-query".to_string()
+}
+fn default_smtp_port() -> u16 {
+    587
+}
 
 impl Config {
     pub fn from_env() -> AppResult<Self> {
@@ -160,15 +670,100 @@ impl Config {
             ));
         }
 
+        crate::services::notifications_service::NotificationBatchPolicy::from_config(self)
+            .map_err(AppError::Config)?;
+
+        let regions = crate::utils::asset_url::parse_region_urls(&self.assets_region_urls)
+            .map_err(AppError::Config)?;
+        crate::utils::asset_url::parse_region_fallbacks(&self.assets_region_fallbacks, &regions)
+            .map_err(AppError::Config)?;
+        if !regions.is_empty() && self.assets_region_probe_secs == 0 {
+            return Err(AppError::Config(
+                "ASSETS_REGION_PROBE_SECS must be > 0".to_string(),
+            ));
+        }
+
         if self.jwt_access_token_expires_in == 0 {
             return Err(AppError::Config(
                 "JWT_ACCESS_TOKEN_EXPIRES_IN must be > 0".to_string(),
             ));
         }
 
+        if let Some(endpoint) = &self.storage_endpoint
+            && url::Url::parse(endpoint).is_err()
+        {
+            return Err(AppError::Config(
+                "STORAGE_ENDPOINT must be a valid URL".to_string(),
+            ));
+        }
+
+        if self.attachment_max_bytes <= 0 {
+            return Err(AppError::Config(
+                "ATTACHMENT_MAX_BYTES must be > 0".to_string(),
+            ));
+        }
+
+        if self.upload_session_max_bytes <= 0 || self.upload_session_ttl_secs == 0 {
+            return Err(AppError::Config(
+                "UPLOAD_SESSION_MAX_BYTES and UPLOAD_SESSION_TTL_SECS must be > 0".to_string(),
+            ));
+        }
+
+        if url::Url::parse(&self.oauth_redirect_base_url).is_err() {
+            return Err(AppError::Config(
+                "OAUTH_REDIRECT_BASE_URL must be a valid URL".to_string(),
+            ));
+        }
+
+        if self.rate_limit_enabled
+            && (self.rate_limit_window_secs == 0
+                || self.rate_limit_per_user == 0
+                || self.rate_limit_per_ip == 0)
+        {
+            return Err(AppError::Config(
+                "RATE_LIMIT_WINDOW_SECS, RATE_LIMIT_PER_USER and RATE_LIMIT_PER_IP must be > 0"
+                    .to_string(),
+            ));
+        }
+
+        if self.telemetry_enabled {
+            let endpoint = self.telemetry_endpoint.as_deref().unwrap_or_default();
+            if !matches!(url::Url::parse(endpoint), Ok(url) if ["http", "https"].contains(&url.scheme()))
+            {
+                return Err(AppError::Config(
+                    "TELEMETRY_ENABLED requires TELEMETRY_ENDPOINT to be an http(s) URL"
+                        .to_string(),
+                ));
+            }
+        }
+
+        if url::Url::parse(&self.app_url).is_err() {
+            return Err(AppError::Config("APP_URL must be a valid URL".to_string()));
+        }
+
+        self.email()?;
+
+        if !["development", "staging", "production"].contains(&self.app_env.as_str()) {
+            return Err(AppError::Config(
+                "APP_ENV must be development, staging or production".to_string(),
+            ));
+        }
+
+        if self.cors_allow_credentials && self.cors_origins.iter().any(|origin| origin == "*") {
+            return Err(AppError::Config(
+                "CORS_ALLOW_CREDENTIALS requires explicit CORS_ORIGINS, not *".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
+    pub fn is_admin(&self, email: &str) -> bool {
+        self.admin_emails
+            .iter()
+            .any(|admin| admin.eq_ignore_ascii_case(email))
+    }
+
     pub fn server_address(&self) -> String {
         format!("{}:{}", self.server_host, self.server_port)
     }
@@ -198,6 +793,29 @@ impl Config {
         }
     }
 
+    pub fn cors(&self) -> CorsConfig {
+        CorsConfig {
+            origins: self.cors_origins.clone(),
+            allow_credentials: self.cors_allow_credentials,
+            max_age_secs: self.cors_max_age_secs,
+        }
+    }
+
+    pub fn is_production(&self) -> bool {
+        self.app_env == "production"
+    }
+
+    pub fn security_headers(&self) -> SecurityHeadersConfig {
+        let default_hsts = if self.is_production() { 31_536_000 } else { 0 };
+        SecurityHeadersConfig {
+            enabled: self.security_headers_enabled,
+            hsts_max_age_secs: self.hsts_max_age_secs.unwrap_or(default_hsts),
+            hsts_include_subdomains: self.hsts_include_subdomains,
+            referrer_policy: self.referrer_policy.clone(),
+            content_security_policy: self.content_security_policy.clone(),
+        }
+    }
+
     pub fn auth(&self) -> AuthConfig {
         AuthConfig {
             jwt_secret: self.jwt_secret.clone(),
@@ -214,10 +832,156 @@ impl Config {
     }
 
     pub fn assets(&self) -> AssetsConfig {
+        // Entries were checked by `validate`
+        let regions = crate::utils::asset_url::parse_region_urls(&self.assets_region_urls)
+            .unwrap_or_default();
+        let fallbacks = crate::utils::asset_url::parse_region_fallbacks(
+            &self.assets_region_fallbacks,
+            &regions,
+        )
+        .unwrap_or_default();
         AssetsConfig {
             base_url: self.assets_url.clone(),
+            regions,
+            fallbacks,
+        }
+    }
+
+    pub fn rate_limit(&self) -> HttpRateLimitConfig {
+        HttpRateLimitConfig {
+            enabled: self.rate_limit_enabled,
+            window_secs: self.rate_limit_window_secs,
+            per_user: self.rate_limit_per_user,
+            per_ip: self.rate_limit_per_ip,
+            trust_proxy: self.rate_limit_trust_proxy,
+        }
+    }
+
+    /// Client credentials of a login provider; `None` unless configured
+    pub fn login_provider(&self, provider: LoginProvider) -> Option<LoginProviderConfig> {
+        let (client_id, client_secret) = match provider {
+            LoginProvider::Google => (
+                &self.oauth_google_client_id,
+                &self.oauth_google_client_secret,
+            ),
+            LoginProvider::Github => (
+                &self.oauth_github_client_id,
+                &self.oauth_github_client_secret,
+            ),
+        };
+        Some(LoginProviderConfig {
+            client_id: client_id.clone()?,
+            client_secret: client_secret.clone()?,
+            redirect_uri: format!(
+                "{}/auth/oauth/{}/callback",
+                self.oauth_redirect_base_url.trim_end_matches('/'),
+                provider.as_str()
+            ),
+        })
+    }
+
+    /// Outgoing email settings; fails when the chosen backend is missing credentials
+    pub fn email(&self) -> AppResult<EmailConfig> {
+        let backend = match self.email_backend.as_str() {
+            "log" => EmailBackendConfig::Log,
+            "smtp" => EmailBackendConfig::Smtp(SmtpConfig {
+                host: self.smtp_host.clone().ok_or_else(|| {
+                    AppError::Config("SMTP_HOST is required when EMAIL_BACKEND=smtp".to_string())
+                })?,
+                port: self.smtp_port,
+                username: self.smtp_username.clone(),
+                password: self.smtp_password.clone(),
+                implicit_tls: self.smtp_implicit_tls,
+            }),
+            "ses" => match (&self.ses_access_key, &self.ses_secret_key) {
+                (Some(access_key), Some(secret_key)) => EmailBackendConfig::Ses(SesConfig {
+                    region: self.ses_region.clone(),
+                    access_key: access_key.clone(),
+                    secret_key: secret_key.clone(),
+                }),
+                _ => {
+                    return Err(AppError::Config(
+                        "SES_ACCESS_KEY and SES_SECRET_KEY are required when EMAIL_BACKEND=ses"
+                            .to_string(),
+                    ));
+                }
+            },
+            other => {
+                return Err(AppError::Config(format!(
+                    "EMAIL_BACKEND must be smtp, ses or log, got {}",
+                    other
+                )));
+            }
+        };
+        Ok(EmailConfig {
+            from: self.email_from.clone(),
+            app_url: self.app_url.trim_end_matches('/').to_string(),
+            backend,
+        })
+    }
+
+    /// Attachment storage settings; `None` unless fully configured
+    pub fn storage(&self) -> Option<StorageConfig> {
+        Some(StorageConfig {
+            endpoint: self.storage_endpoint.clone()?,
+            bucket: self.storage_bucket.clone()?,
+            region: self.storage_region.clone(),
+            access_key: self.storage_access_key.clone()?,
+            secret_key: self.storage_secret_key.clone()?,
+            path_style: self.storage_path_style,
+            max_attachment_bytes: self.attachment_max_bytes,
+        })
+    }
+
+    /// Where usage reports go; `None` unless the operator opted in
+    pub fn telemetry(&self) -> Option<TelemetryConfig> {
+        if !self.telemetry_enabled {
+            return None;
+        }
+        Some(TelemetryConfig {
+            endpoint: self.telemetry_endpoint.clone()?,
+        })
+    }
+
+    /// Push notification providers
+    pub fn push(&self) -> PushConfig {
+        PushConfig {
+            web: self.web_push(),
+            apns: self.apns(),
+            fcm: self.fcm(),
         }
     }
+
+    fn web_push(&self) -> Option<WebPushConfig> {
+        Some(WebPushConfig {
+            subject: self.push_vapid_subject.clone()?,
+            public_key: self.push_vapid_public_key.clone()?,
+            private_key: pem(self.push_vapid_private_key.as_deref()?),
+        })
+    }
+
+    fn apns(&self) -> Option<ApnsConfig> {
+        Some(ApnsConfig {
+            key_id: self.apns_key_id.clone()?,
+            team_id: self.apns_team_id.clone()?,
+            private_key: pem(self.apns_private_key.as_deref()?),
+            topic: self.apns_topic.clone()?,
+            sandbox: self.apns_sandbox,
+        })
+    }
+
+    fn fcm(&self) -> Option<FcmConfig> {
+        Some(FcmConfig {
+            project_id: self.fcm_project_id.clone()?,
+            client_email: self.fcm_client_email.clone()?,
+            private_key: pem(self.fcm_private_key.as_deref()?),
+        })
+    }
+}
+
+/// PEM keys are often passed with literal `\n` in environment variables
+fn pem(key: &str) -> String {
+    key.replace("\\n", "\n")
 }
 
 // 为了向后兼容，保留旧的字段访问方式
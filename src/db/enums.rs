@@ -7,6 +7,7 @@ use diesel::sql_types::Text;
 use diesel::{AsExpression, FromSqlRow, Queryable};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
+use utoipa::ToSchema;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ProjectStatus {
@@ -52,7 +53,7 @@ impl Queryable<Text, Pg> for ProjectStatus {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum CycleStatus {
     Planned,
@@ -91,7 +92,7 @@ impl Queryable<Text, Pg> for CycleStatus {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum IssuePriority {
     None,
@@ -137,7 +138,9 @@ impl Queryable<Text, Pg> for IssuePriority {
 }
 
 /// Project priority enum, using the same values as IssuePriority
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, AsExpression, FromSqlRow, Default)]
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, AsExpression, FromSqlRow, Default, ToSchema,
+)]
 #[diesel(sql_type = Text)]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectPriority {
@@ -176,7 +179,9 @@ impl ToSql<Text, Pg> for ProjectPriority {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::expression::AsExpression)]
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, diesel::expression::AsExpression, ToSchema,
+)]
 #[diesel(sql_type = LabelLevelEnum)]
 pub enum LabelLevel {
     Project,
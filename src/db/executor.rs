@@ -0,0 +1,59 @@
+//! Off-runtime database access.
+//!
+//! Diesel and r2d2 are synchronous: waiting for a pooled connection and every
+//! query block the calling thread. Async code runs its database work through
+//! [`DbExecutor`], which moves it onto Tokio's blocking pool so executor
+//! threads stay free for requests and WebSocket connections.
+use diesel::PgConnection;
+
+use crate::db::{DbPool, TransactionManager};
+use crate::error::AppError;
+
+/// Runs units of database work on the blocking pool
+#[derive(Clone)]
+pub struct DbExecutor {
+    pool: DbPool,
+}
+
+impl DbExecutor {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// The underlying pool, for synchronous callers such as the worker
+    pub fn pool(&self) -> &DbPool {
+        &self.pool
+    }
+
+    /// Run `f` in a request transaction (see [`crate::db::with_txn`]) on a
+    /// blocking thread
+    pub async fn transaction<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, AppError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let pool = self.pool.clone();
+        offload(move || TransactionManager::new(&pool).run(f)).await
+    }
+
+    /// Run `f` without a transaction on a blocking thread; for reads
+    pub async fn read<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, AppError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let pool = self.pool.clone();
+        offload(move || TransactionManager::new(&pool).read(f)).await
+    }
+}
+
+async fn offload<T, F>(f: F) -> Result<T, AppError>
+where
+    F: FnOnce() -> Result<T, AppError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(|e| {
+        tracing::error!("Database task failed: {}", e);
+        AppError::Internal("Database task failed".to_string())
+    })?
+}
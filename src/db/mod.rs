@@ -1,12 +1,17 @@
 pub mod enums;
+pub mod executor;
 pub mod models;
 pub mod repositories;
+pub mod transaction;
 
 use crate::config::DatabaseConfig;
 use crate::error::{AppError, AppResult};
 use diesel::PgConnection;
 use diesel::r2d2::{self, ConnectionManager as DbConnectionManager};
 
+pub use executor::DbExecutor;
+pub use transaction::{TransactionManager, after_commit, with_txn};
+
 pub type DbPool = r2d2::Pool<DbConnectionManager<PgConnection>>;
 
 pub fn create_pool(config: &DatabaseConfig) -> AppResult<DbPool> {
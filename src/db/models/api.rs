@@ -1,7 +1,11 @@
 use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::error::ErrorCode;
+use crate::middleware::request_tracking::current_request_id;
 
 // 统一API响应结构
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub code: u16,
@@ -12,10 +16,21 @@ pub struct ApiResponse<T> {
     pub meta: Option<ResponseMeta>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub errors: Option<Vec<ErrorDetail>>,
+    /// 错误响应的稳定错误码，见 [`ErrorCode`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ErrorCode>,
+    /// 请求追踪中间件分配的请求 ID，与响应头 `x-request-id` 一致
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// 原样重试是否可能成功
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retryable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
     pub timestamp: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ResponseMeta {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_id: Option<String>,
@@ -27,7 +42,7 @@ pub struct ResponseMeta {
     pub execution_time_ms: Option<u64>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct Pagination {
     pub page: i64,
     pub per_page: i64,
@@ -36,7 +51,7 @@ pub struct Pagination {
     pub has_prev: bool,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ErrorDetail {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub field: Option<String>,
@@ -44,6 +59,10 @@ pub struct ErrorDetail {
     pub message: String,
 }
 
+/// 只带消息、没有 `data` 的响应（如 [`ApiResponse::ok`]）在 OpenAPI 文档中的数据类型
+#[derive(Serialize, ToSchema)]
+pub struct EmptyData {}
+
 // 便捷构造函数
 impl<T> ApiResponse<T> {
     pub fn success(data: T, message: &str) -> Self {
@@ -54,6 +73,10 @@ impl<T> ApiResponse<T> {
             data: Some(data),
             meta: None,
             errors: None,
+            error_code: None,
+            trace_id: None,
+            retryable: None,
+            retry_after_secs: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
@@ -66,6 +89,10 @@ impl<T> ApiResponse<T> {
             data: Some(data),
             meta: Some(meta),
             errors: None,
+            error_code: None,
+            trace_id: None,
+            retryable: None,
+            retry_after_secs: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
@@ -78,11 +105,17 @@ impl<T> ApiResponse<T> {
             data: Some(data),
             meta: None,
             errors: None,
+            error_code: None,
+            trace_id: None,
+            retryable: None,
+            retry_after_secs: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
 
+    /// 所有错误响应的构造入口：按状态码填入错误码和重试提示，并带上当前请求的 ID
     pub fn error(code: u16, message: &str, errors: Vec<ErrorDetail>) -> Self {
+        let error_code = ErrorCode::for_status(code);
         Self {
             success: false,
             code,
@@ -90,68 +123,65 @@ impl<T> ApiResponse<T> {
             data: None,
             meta: None,
             errors: Some(errors),
+            error_code: Some(error_code),
+            trace_id: current_request_id(),
+            retryable: Some(error_code.retryable()),
+            retry_after_secs: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
 
+    /// 用更精确的错误码替换按状态码推断的错误码
+    pub fn with_error_code(mut self, error_code: ErrorCode) -> Self {
+        self.error_code = Some(error_code);
+        self.retryable = Some(error_code.retryable());
+        self
+    }
+
+    pub fn with_retry_after(mut self, secs: u64) -> Self {
+        self.retryable = Some(true);
+        self.retry_after_secs = Some(secs);
+        self
+    }
+
     pub fn validation_error(errors: Vec<ErrorDetail>) -> Self {
-        Self {
-            success: false,
-            code: 400,
-            message: "Validation failed".to_string(),
-            data: None,
-            meta: None,
-            errors: Some(errors),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+        Self::error(400, "Validation failed", errors)
     }
 
     pub fn unauthorized(message: &str) -> Self {
-        Self {
-            success: false,
-            code: 401,
-            message: message.to_string(),
-            data: None,
-            meta: None,
-            errors: Some(vec![ErrorDetail {
+        Self::error(
+            401,
+            message,
+            vec![ErrorDetail {
                 field: None,
                 code: "UNAUTHORIZED".to_string(),
                 message: message.to_string(),
-            }]),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+            }],
+        )
     }
 
     pub fn forbidden(message: &str) -> Self {
-        Self {
-            success: false,
-            code: 403,
-            message: message.to_string(),
-            data: None,
-            meta: None,
-            errors: Some(vec![ErrorDetail {
+        Self::error(
+            403,
+            message,
+            vec![ErrorDetail {
                 field: None,
                 code: "FORBIDDEN".to_string(),
                 message: message.to_string(),
-            }]),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+            }],
+        )
     }
 
     pub fn not_found(message: &str) -> Self {
-        Self {
-            success: false,
-            code: 404,
-            message: message.to_string(),
-            data: None,
-            meta: None,
-            errors: Some(vec![ErrorDetail {
+        Self::error(
+            404,
+            message,
+            vec![ErrorDetail {
                 field: None,
                 code: "NOT_FOUND".to_string(),
                 message: message.to_string(),
-            }]),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+            }],
+        )
     }
 
     pub fn ok(message: &str) -> Self {
@@ -162,72 +192,60 @@ impl<T> ApiResponse<T> {
             data: None,
             meta: None,
             errors: None,
+            error_code: None,
+            trace_id: None,
+            retryable: None,
+            retry_after_secs: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
 
     pub fn not_implemented(message: &str) -> Self {
-        Self {
-            success: false,
-            code: 501,
-            message: message.to_string(),
-            data: None,
-            meta: None,
-            errors: Some(vec![ErrorDetail {
+        Self::error(
+            501,
+            message,
+            vec![ErrorDetail {
                 field: None,
                 code: "NOT_IMPLEMENTED".to_string(),
                 message: message.to_string(),
-            }]),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+            }],
+        )
     }
 
     pub fn conflict(message: &str, field: Option<String>, error_code: &str) -> Self {
-        Self {
-            success: false,
-            code: 409,
-            message: message.to_string(),
-            data: None,
-            meta: None,
-            errors: Some(vec![ErrorDetail {
+        Self::error(
+            409,
+            message,
+            vec![ErrorDetail {
                 field,
                 code: error_code.to_string(),
                 message: message.to_string(),
-            }]),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+            }],
+        )
     }
 
     pub fn bad_request(message: &str) -> Self {
-        Self {
-            success: false,
-            code: 400,
-            message: message.to_string(),
-            data: None,
-            meta: None,
-            errors: Some(vec![ErrorDetail {
+        Self::error(
+            400,
+            message,
+            vec![ErrorDetail {
                 field: None,
                 code: "BAD_REQUEST".to_string(),
                 message: message.to_string(),
-            }]),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+            }],
+        )
     }
 
     pub fn internal_error(message: &str) -> Self {
-        Self {
-            success: false,
-            code: 500,
-            message: message.to_string(),
-            data: None,
-            meta: None,
-            errors: Some(vec![ErrorDetail {
+        Self::error(
+            500,
+            message,
+            vec![ErrorDetail {
                 field: None,
                 code: "INTERNAL_ERROR".to_string(),
                 message: message.to_string(),
-            }]),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        }
+            }],
+        )
     }
 }
 
@@ -0,0 +1,137 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::oauth_app::oauth_scopes;
+
+/// API client kinds tracked in usage analytics
+pub mod api_client_types {
+    pub const PERSONAL_TOKEN: &str = "personal_token";
+    pub const OAUTH_CLIENT: &str = "oauth_client";
+    pub const WORKSPACE_KEY: &str = "api_key";
+}
+
+/// API tiers and their daily request quotas
+pub mod api_tiers {
+    pub const FREE: &str = "free";
+    pub const PRO: &str = "pro";
+    pub const UNLIMITED: &str = "unlimited";
+
+    /// Requests per UTC day; `None` means no limit
+    pub fn daily_request_limit(tier: &str) -> Option<i64> {
+        match tier {
+            FREE => Some(1_000),
+            PRO => Some(50_000),
+            UNLIMITED => None,
+            _ => Some(1_000),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::api_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    pub tier: String,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Space-separated scopes; `None` leaves the token unrestricted
+    #[serde(serialize_with = "serialize_scopes")]
+    pub scopes: Option<String>,
+}
+
+fn serialize_scopes<S>(scopes: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    scopes
+        .as_deref()
+        .map(oauth_scopes::parse)
+        .serialize(serializer)
+}
+
+impl ApiToken {
+    pub fn is_active(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires| expires > now)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::api_tokens)]
+pub struct NewApiToken {
+    pub user_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub scopes: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub expires_in_days: Option<i64>,
+    /// Limits the token to these scopes; required for service accounts
+    pub scopes: Option<Vec<String>>,
+}
+
+/// Returned once on creation; the plaintext token cannot be retrieved again
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct CreatedApiToken {
+    pub token: String,
+    #[serde(flatten)]
+    pub api_token: ApiToken,
+}
+
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::api_usage_daily)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiUsageDaily {
+    pub client_type: String,
+    pub client_id: Uuid,
+    pub day: chrono::NaiveDate,
+    pub endpoint: String,
+    pub request_count: i64,
+    pub error_count: i64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct EndpointUsage {
+    pub endpoint: String,
+    pub requests: i64,
+    pub errors: i64,
+    pub error_rate: f64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct DailyUsage {
+    pub day: chrono::NaiveDate,
+    pub requests: i64,
+    pub errors: i64,
+}
+
+/// Usage summary for one API client over a window of days
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ApiUsageReport {
+    pub client_type: String,
+    pub client_id: Uuid,
+    pub tier: String,
+    pub daily_limit: Option<i64>,
+    pub requests_today: i64,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub total_requests: i64,
+    pub total_errors: i64,
+    pub error_rate: f64,
+    pub endpoints: Vec<EndpointUsage>,
+    pub daily: Vec<DailyUsage>,
+}
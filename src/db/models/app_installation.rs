@@ -0,0 +1,278 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::oauth_app::oauth_scopes;
+
+/// Webhook event names. Installation lifecycle events are always delivered;
+/// the rest are opt-in per app and require the matching read scope.
+pub mod webhook_events {
+    use super::oauth_scopes;
+
+    pub const INSTALLATION_CREATED: &str = "installation.created";
+    pub const INSTALLATION_DELETED: &str = "installation.deleted";
+    pub const INSTALLATION_PERMISSIONS_CHANGED: &str = "installation.permissions_changed";
+
+    pub const ISSUE_CREATED: &str = "issue.created";
+    pub const ISSUE_UPDATED: &str = "issue.updated";
+    pub const ISSUE_DELETED: &str = "issue.deleted";
+    pub const ISSUE_MOVED: &str = "issue.moved";
+    pub const COMMENT_CREATED: &str = "comment.created";
+    pub const PROJECT_CREATED: &str = "project.created";
+    pub const PROJECT_UPDATED: &str = "project.updated";
+    pub const PROJECT_DELETED: &str = "project.deleted";
+    pub const SESSION_STARTED: &str = "session.started";
+    pub const SESSION_ENDED: &str = "session.ended";
+
+    /// Installation lifecycle events, always delivered
+    pub const LIFECYCLE: &[&str] = &[
+        INSTALLATION_CREATED,
+        INSTALLATION_DELETED,
+        INSTALLATION_PERMISSIONS_CHANGED,
+    ];
+
+    /// Events an app can subscribe to
+    pub const SUBSCRIBABLE: &[&str] = &[
+        ISSUE_CREATED,
+        ISSUE_UPDATED,
+        ISSUE_DELETED,
+        ISSUE_MOVED,
+        COMMENT_CREATED,
+        PROJECT_CREATED,
+        PROJECT_UPDATED,
+        PROJECT_DELETED,
+        SESSION_STARTED,
+        SESSION_ENDED,
+    ];
+
+    pub fn is_lifecycle(event: &str) -> bool {
+        event.starts_with("installation.")
+    }
+
+    pub fn is_subscribable(event: &str) -> bool {
+        SUBSCRIBABLE.contains(&event)
+    }
+
+    /// Scope an installation needs to receive the event
+    pub fn required_scope(event: &str) -> Option<&'static str> {
+        match event.split('.').next() {
+            Some("issue") => Some(oauth_scopes::READ_ISSUES),
+            Some("comment") => Some(oauth_scopes::READ_COMMENTS),
+            Some("project") => Some(oauth_scopes::READ_PROJECTS),
+            Some("session") => Some(oauth_scopes::READ_WORKSPACE),
+            _ => None,
+        }
+    }
+}
+
+pub mod webhook_delivery_status {
+    pub const PENDING: &str = "pending";
+    pub const DELIVERED: &str = "delivered";
+    pub const FAILED: &str = "failed";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::app_installations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AppInstallation {
+    pub id: Uuid,
+    pub app_id: Uuid,
+    pub workspace_id: Uuid,
+    pub installed_by: Option<Uuid>,
+    pub scopes: String,
+    pub uninstalled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AppInstallation {
+    pub fn scope_list(&self) -> Vec<String> {
+        oauth_scopes::parse(&self.scopes)
+    }
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    /// Set for app deliveries; workspace webhook deliveries set `webhook_id`
+    pub app_id: Option<Uuid>,
+    pub installation_id: Option<Uuid>,
+    pub event: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub webhook_id: Option<Uuid>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::webhook_deliveries)]
+pub struct NewWebhookDelivery {
+    pub app_id: Option<Uuid>,
+    pub webhook_id: Option<Uuid>,
+    pub installation_id: Option<Uuid>,
+    pub event: String,
+    pub payload: String,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Outcome of a delivery attempt
+#[derive(AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::webhook_deliveries, treat_none_as_null = true)]
+pub struct WebhookDeliveryAttempt {
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// JSON body POSTed to an app's webhook URL
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebhookEnvelope {
+    pub id: Uuid,
+    pub event: String,
+    pub workspace_id: Uuid,
+    pub installation_id: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub data: serde_json::Value,
+    /// Set on synthetic sample deliveries sent from the test endpoint
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub test: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct InstallAppRequest {
+    pub client_id: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct UpdateInstallationRequest {
+    pub scopes: Vec<String>,
+}
+
+/// An app installed in the current workspace
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct InstalledApp {
+    pub installation_id: Uuid,
+    pub app_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub scopes: Vec<String>,
+    pub installed_by: Option<Uuid>,
+    pub installed_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct UpdateAppWebhookRequest {
+    /// `None` disables webhook delivery
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Filter expression deliveries must match; `None` sends every event
+    #[serde(default)]
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+/// Webhook settings of an app; `secret` is only returned when it was (re)generated
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AppWebhookConfig {
+    pub webhook_url: Option<String>,
+    pub events: Vec<String>,
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// A secret deliveries are signed with. Keys without `expires_at` are
+/// current; rotated keys keep signing until their grace period ends.
+#[derive(Queryable, Selectable, Serialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::webhook_signing_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookSigningKey {
+    #[serde(skip)]
+    pub id: Uuid,
+    #[serde(skip)]
+    pub app_id: Uuid,
+    pub key_id: String,
+    #[serde(skip)]
+    pub secret: String,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::webhook_signing_keys)]
+pub struct NewWebhookSigningKey {
+    pub app_id: Uuid,
+    pub key_id: String,
+    pub secret: String,
+}
+
+/// Event types to send synthetic samples of; every event type when empty
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct TestWebhookRequest {
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct RotateSigningKeyRequest {
+    /// How long the previous keys keep signing; defaults to 24 hours
+    #[serde(default)]
+    pub grace_period_hours: Option<i64>,
+}
+
+/// Result of a rotation; the new secret is only returned here
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct RotatedSigningKey {
+    pub key: WebhookSigningKey,
+    pub secret: String,
+    pub active_keys: Vec<WebhookSigningKey>,
+}
+
+/// Sample payload and signature to check an integration's HMAC code against
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct VerifySignatureRequest {
+    /// Raw request body; a sample `ping` delivery is used when omitted
+    #[serde(default)]
+    pub payload: Option<String>,
+    /// Signature computed by the integration, either a full header value or
+    /// a single `sha256=<hex>`
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Sign with a caller-supplied secret instead of the app's keys
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct KeySignature {
+    pub key_id: Option<String>,
+    pub signature: String,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SignatureVerification {
+    pub payload: String,
+    /// Header value a real delivery of `payload` would carry
+    pub header: String,
+    pub signatures: Vec<KeySignature>,
+    /// `None` when no signature was supplied
+    pub valid: Option<bool>,
+    pub matched_key_id: Option<String>,
+}
@@ -0,0 +1,78 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Upload state of an attachment. Rows start `pending` when the upload URL is
+/// issued and become `uploaded` once the object is confirmed in storage.
+pub mod attachment_status {
+    pub const PENDING: &str = "pending";
+    pub const UPLOADED: &str = "uploaded";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::attachments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Attachment {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub workspace_id: Uuid,
+    pub uploader_id: Option<Uuid>,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Path below the `attachments/` prefix in the bucket
+    #[serde(skip)]
+    pub storage_key: String,
+    pub status: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub uploaded_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Attachment {
+    /// Object key in the bucket, matching `AssetUrlHelper::build_attachment_url`
+    pub fn object_key(&self) -> String {
+        format!("attachments/{}", self.storage_key)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::attachments)]
+pub struct NewAttachment {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub workspace_id: Uuid,
+    pub uploader_id: Option<Uuid>,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateAttachmentRequest {
+    pub filename: String,
+    pub content_type: String,
+    /// Declared size; checked against the stored object when the upload completes
+    pub size_bytes: i64,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AttachmentResponse {
+    #[serde(flatten)]
+    pub attachment: Attachment,
+    /// Public URL through the assets host
+    pub url: String,
+    /// Short-lived signed URL straight to storage
+    pub download_url: String,
+}
+
+/// Returned when an upload is started; the client sends the file body to
+/// `upload_url` and then calls the complete endpoint
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AttachmentUpload {
+    pub attachment: Attachment,
+    pub upload_url: String,
+    pub upload_method: &'static str,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
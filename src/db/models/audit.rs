@@ -0,0 +1,117 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Actions recorded in the audit log
+pub mod audit_actions {
+    pub const ISSUES_BULK_ARCHIVED: &str = "issues.bulk_archived";
+    pub const ISSUES_BULK_ARCHIVE_UNDONE: &str = "issues.bulk_archive_undone";
+    /// A completed issue was archived by the workspace's auto-archive policy
+    pub const ISSUE_AUTO_ARCHIVED: &str = "issue.auto_archived";
+    pub const COMMENTS_BULK_HIDDEN: &str = "comments.bulk_hidden";
+    pub const COMMENTS_BULK_DELETED: &str = "comments.bulk_deleted";
+    pub const COMMENTS_UNHIDDEN: &str = "comments.unhidden";
+    pub const CONTENT_REPORT_RESOLVED: &str = "content_report.resolved";
+    pub const WORKSPACE_DELETE_REQUESTED: &str = "workspace.delete_requested";
+    pub const WORKSPACE_DELETED: &str = "workspace.deleted";
+    pub const TEAM_DELETE_REQUESTED: &str = "team.delete_requested";
+    pub const TEAM_DELETED: &str = "team.deleted";
+    pub const API_KEY_CREATED: &str = "api_key.created";
+    pub const API_KEY_UPDATED: &str = "api_key.updated";
+    pub const API_KEY_REVOKED: &str = "api_key.revoked";
+    pub const SERVICE_ACCOUNT_CREATED: &str = "service_account.created";
+    pub const SERVICE_ACCOUNT_DISABLED: &str = "service_account.disabled";
+    pub const SERVICE_ACCOUNT_TOKEN_CREATED: &str = "service_account.token_created";
+    pub const SERVICE_ACCOUNT_TOKEN_REVOKED: &str = "service_account.token_revoked";
+    pub const EMAIL_DOMAIN_SET: &str = "email_domain.set";
+    pub const EMAIL_DOMAIN_VERIFIED: &str = "email_domain.verified";
+    pub const EMAIL_DOMAIN_REMOVED: &str = "email_domain.removed";
+    /// A new user joined the workspace through its auto-join email domains
+    pub const WORKSPACE_MEMBER_AUTO_JOINED: &str = "workspace_member.auto_joined";
+    pub const WORKSPACE_SETTINGS_UPDATED: &str = "workspace.settings_updated";
+    pub const TEAM_SETTINGS_UPDATED: &str = "team.settings_updated";
+    pub const WORKFLOW_CREATED: &str = "workflow.created";
+    pub const WORKFLOW_UPDATED: &str = "workflow.updated";
+    pub const WORKFLOW_DELETED: &str = "workflow.deleted";
+    pub const WORKFLOW_STATE_CREATED: &str = "workflow_state.created";
+    pub const WORKFLOW_STATE_UPDATED: &str = "workflow_state.updated";
+}
+
+/// Kinds of actor shown with audit entries
+pub mod actor_types {
+    pub const USER: &str = "user";
+    pub const SERVICE_ACCOUNT: &str = "service_account";
+}
+
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::audit_log)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    /// JSON-encoded details of the action
+    pub details: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::audit_log)]
+pub struct NewAuditEntry {
+    pub workspace_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    pub details: Option<String>,
+}
+
+/// Kinds of settings whose changes are kept in the audit log
+pub mod settings_targets {
+    pub const WORKSPACE: &str = "workspace";
+    pub const TEAM: &str = "team";
+    pub const WORKFLOW: &str = "workflow";
+    pub const WORKFLOW_STATE: &str = "workflow_state";
+
+    pub const ALL: [&str; 4] = [WORKSPACE, TEAM, WORKFLOW, WORKFLOW_STATE];
+}
+
+/// One field of a settings change; `from` is null for created settings and
+/// `to` is null for deleted ones
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct SettingChange {
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SettingsHistoryQuery {
+    /// One of [`settings_targets`]
+    pub target_type: Option<String>,
+    pub target_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only changes made before this time, for paging
+    pub before: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<i64>,
+}
+
+/// A settings change as served by `GET /settings/history`
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SettingsHistoryEntry {
+    pub id: Uuid,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    /// One of [`actor_types`]; `None` for changes made by the system
+    pub actor_type: Option<&'static str>,
+    pub changes: Vec<SettingChange>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
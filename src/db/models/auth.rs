@@ -5,11 +5,12 @@ use axum::http::StatusCode;
 use axum::http::request::Parts;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
 // User models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[diesel(table_name = crate::schema::users)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct User {
@@ -22,6 +23,12 @@ pub struct User {
     pub updated_at: chrono::NaiveDateTime,
     pub id: Uuid,
     pub current_workspace_id: Option<Uuid>,
+    /// Preferred asset CDN region; `None` uses the default assets URL
+    #[serde(default)]
+    pub asset_region: Option<String>,
+    /// Machine user of an integration; cannot sign in
+    #[serde(default)]
+    pub is_service_account: bool,
 }
 
 #[derive(Insertable)]
@@ -61,7 +68,7 @@ pub struct NewUserCredential {
 }
 
 // Authentication DTOs
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 pub struct AuthUser {
     pub id: Uuid,
     pub email: String,
@@ -89,7 +96,7 @@ where
     }
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct RegisterRequest {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
@@ -108,7 +115,7 @@ pub struct RegisterRequest {
     pub password: String,
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct LoginRequest {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
@@ -117,7 +124,16 @@ pub struct LoginRequest {
     pub password: String,
 }
 
-#[derive(Serialize)]
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, message = "Current password is required"))]
+    pub current_password: String,
+
+    #[validate(custom(function = "validate_password_strength"))]
+    pub new_password: String,
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct LoginResponse {
     pub access_token: String,
     pub refresh_token: String,
@@ -132,7 +148,7 @@ pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 pub struct UserBasicInfo {
     pub id: Uuid,
     pub name: String,
@@ -141,7 +157,7 @@ pub struct UserBasicInfo {
     pub avatar_url: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct UserProfile {
     pub id: Uuid,
     pub email: String,
@@ -149,6 +165,7 @@ pub struct UserProfile {
     pub name: String,
     pub avatar_url: Option<String>,
     pub current_workspace_id: Option<Uuid>,
+    pub asset_region: Option<String>,
     pub workspaces: Vec<super::workspace::WorkspaceInfo>,
     pub teams: Vec<super::team::TeamInfo>,
 }
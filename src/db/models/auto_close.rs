@@ -0,0 +1,100 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::team_auto_close_policies)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TeamAutoClosePolicy {
+    pub team_id: Uuid,
+    pub enabled: bool,
+    pub state_ids: String,
+    pub inactive_days: i32,
+    pub warning_days: i32,
+    pub close_state_id: Uuid,
+    pub exempt_label_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TeamAutoClosePolicy {
+    /// Watched workflow states; ids that no longer parse are ignored
+    pub fn state_id_list(&self) -> Vec<Uuid> {
+        self.state_ids
+            .split_whitespace()
+            .filter_map(|id| id.parse().ok())
+            .collect()
+    }
+}
+
+#[derive(Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::team_auto_close_policies)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewTeamAutoClosePolicy {
+    pub team_id: Uuid,
+    pub enabled: bool,
+    pub state_ids: String,
+    pub inactive_days: i32,
+    pub warning_days: i32,
+    pub close_state_id: Uuid,
+    pub exempt_label_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct UpsertAutoClosePolicyRequest {
+    pub enabled: Option<bool>,
+    /// Workflow states whose issues are closed after inactivity
+    pub state_ids: Vec<Uuid>,
+    pub inactive_days: i32,
+    /// Days before closing that the assignee (or creator) is warned
+    pub warning_days: i32,
+    /// Completed or canceled state the issues are moved to
+    pub close_state_id: Uuid,
+    /// Issues with this label are never auto-closed
+    pub exempt_label_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AutoClosePolicyResponse {
+    pub team_id: Uuid,
+    pub enabled: bool,
+    pub state_ids: Vec<Uuid>,
+    pub inactive_days: i32,
+    pub warning_days: i32,
+    pub close_state_id: Uuid,
+    pub exempt_label_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<TeamAutoClosePolicy> for AutoClosePolicyResponse {
+    fn from(policy: TeamAutoClosePolicy) -> Self {
+        Self {
+            team_id: policy.team_id,
+            enabled: policy.enabled,
+            state_ids: policy.state_id_list(),
+            inactive_days: policy.inactive_days,
+            warning_days: policy.warning_days,
+            close_state_id: policy.close_state_id,
+            exempt_label_id: policy.exempt_label_id,
+            created_at: policy.created_at,
+            updated_at: policy.updated_at,
+        }
+    }
+}
+
+/// What the scheduler does with an issue on a policy run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoCloseAction {
+    None,
+    Warn,
+    Close,
+}
+
+/// Issues acted on by one scheduler run
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AutoCloseSummary {
+    pub warned: usize,
+    pub closed: usize,
+}
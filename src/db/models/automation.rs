@@ -0,0 +1,232 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Changes that start a rule
+pub mod automation_triggers {
+    pub const ISSUE_CREATED: &str = "issue_created";
+    /// The issue moved to another workflow state
+    pub const ISSUE_STATE_CHANGED: &str = "issue_state_changed";
+    /// A label was added to an existing issue
+    pub const ISSUE_LABEL_ADDED: &str = "issue_label_added";
+    pub const ALL: [&str; 3] = [ISSUE_CREATED, ISSUE_STATE_CHANGED, ISSUE_LABEL_ADDED];
+}
+
+/// State of a queued rule evaluation
+pub mod automation_run_status {
+    pub const PENDING: &str = "pending";
+    /// Conditions matched and every action was applied
+    pub const SUCCEEDED: &str = "succeeded";
+    /// Conditions no longer matched, or the rule was disabled before it ran
+    pub const SKIPPED: &str = "skipped";
+    pub const FAILED: &str = "failed";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::automation_rules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AutomationRule {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    /// `None` applies the rule to issues of every team
+    pub team_id: Option<Uuid>,
+    pub name: String,
+    /// One of [`automation_triggers`]
+    pub trigger: String,
+    /// JSON [`AutomationConditions`]
+    #[serde(skip)]
+    pub conditions: String,
+    /// JSON list of [`AutomationAction`]s
+    #[serde(skip)]
+    pub actions: String,
+    pub enabled: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::automation_rules)]
+pub struct NewAutomationRule {
+    pub workspace_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub name: String,
+    pub trigger: String,
+    pub conditions: String,
+    pub actions: String,
+    pub enabled: bool,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::automation_rules)]
+pub struct UpdateAutomationRule {
+    pub team_id: Option<Option<Uuid>>,
+    pub name: Option<String>,
+    pub trigger: Option<String>,
+    pub conditions: Option<String>,
+    pub actions: Option<String>,
+    pub enabled: Option<bool>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// What the issue must look like for a rule's actions to run; every given
+/// condition must hold
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AutomationConditions {
+    /// Issue priority is one of these
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priorities: Vec<String>,
+    /// Issue has all of these labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub label_ids: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    /// `true` for issues with an assignee, `false` for unassigned ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assigned: Option<bool>,
+    /// Case-insensitive text in the title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_contains: Option<String>,
+    /// `issue_state_changed` only: the state the issue left
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_state_id: Option<Uuid>,
+    /// `issue_state_changed` only: the state the issue entered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_state_id: Option<Uuid>,
+    /// `issue_label_added` only: the label that was added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_label_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationAction {
+    /// Assign the issue; `None` unassigns it
+    SetAssignee {
+        assignee_id: Option<Uuid>,
+    },
+    AddLabel {
+        label_id: Uuid,
+    },
+    /// Move the issue into a cycle of its team
+    MoveToCycle {
+        cycle_id: Uuid,
+    },
+    /// Comment on the issue as the rule's creator
+    PostComment {
+        content: String,
+    },
+}
+
+/// The change a run was queued for
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(tag = "trigger", rename_all = "snake_case")]
+pub enum AutomationEvent {
+    IssueCreated,
+    IssueStateChanged {
+        from_state_id: Option<Uuid>,
+        to_state_id: Option<Uuid>,
+    },
+    IssueLabelAdded {
+        label_id: Uuid,
+    },
+}
+
+impl AutomationEvent {
+    /// The [`automation_triggers`] value of the event
+    pub fn trigger(&self) -> &'static str {
+        match self {
+            AutomationEvent::IssueCreated => automation_triggers::ISSUE_CREATED,
+            AutomationEvent::IssueStateChanged { .. } => automation_triggers::ISSUE_STATE_CHANGED,
+            AutomationEvent::IssueLabelAdded { .. } => automation_triggers::ISSUE_LABEL_ADDED,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::automation_runs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AutomationRun {
+    pub id: Uuid,
+    pub rule_id: Uuid,
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    /// User whose change queued the run
+    pub actor_id: Option<Uuid>,
+    /// JSON [`AutomationEvent`]
+    #[serde(skip)]
+    pub event: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::automation_runs)]
+pub struct NewAutomationRun {
+    pub rule_id: Uuid,
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub event: String,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::automation_runs)]
+#[diesel(treat_none_as_null = true)]
+pub struct AutomationRunOutcome {
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateAutomationRuleRequest {
+    pub name: String,
+    #[serde(default)]
+    pub team_id: Option<Uuid>,
+    pub trigger: String,
+    #[serde(default)]
+    pub conditions: AutomationConditions,
+    pub actions: Vec<AutomationAction>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateAutomationRuleRequest {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]
+    pub team_id: Option<Option<Uuid>>,
+    pub trigger: Option<String>,
+    pub conditions: Option<AutomationConditions>,
+    pub actions: Option<Vec<AutomationAction>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AutomationRuleResponse {
+    #[serde(flatten)]
+    pub rule: AutomationRule,
+    pub conditions: AutomationConditions,
+    pub actions: Vec<AutomationAction>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AutomationRunResponse {
+    #[serde(flatten)]
+    pub run: AutomationRun,
+    pub event: Option<AutomationEvent>,
+}
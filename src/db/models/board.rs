@@ -0,0 +1,33 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::models::issue::Issue;
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_board_positions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueBoardPosition {
+    pub issue_id: Uuid,
+    pub position: f64,
+    pub version: i32,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Drop an issue into a board column at `position`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReorderIssueRequest {
+    /// Target column; unchanged when omitted
+    pub workflow_state_id: Option<Uuid>,
+    pub position: f64,
+    /// Position version the client last saw; `0` when the issue had none.
+    /// A stale version is rejected instead of overwriting a newer move.
+    pub expected_version: Option<i32>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BoardReorderResult {
+    pub issue: Issue,
+    pub position: IssueBoardPosition,
+}
@@ -0,0 +1,42 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A permission the workspace withholds from one API channel
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::channel_permission_restrictions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChannelPermissionRestriction {
+    pub workspace_id: Uuid,
+    pub channel: String,
+    pub permission: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::channel_permission_restrictions)]
+pub struct NewChannelPermissionRestriction {
+    pub workspace_id: Uuid,
+    pub channel: String,
+    pub permission: String,
+    pub created_by: Option<Uuid>,
+}
+
+/// Replaces the permissions denied to a channel
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateChannelPolicyRequest {
+    #[serde(default)]
+    pub denied: Vec<String>,
+}
+
+/// What an API channel may not do in the workspace
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ChannelPolicy {
+    pub channel: String,
+    /// Denied to every API channel; cannot be lifted
+    pub always_denied: Vec<String>,
+    /// Denied by the workspace's admins
+    pub denied: Vec<String>,
+}
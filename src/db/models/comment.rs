@@ -1,9 +1,10 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 // Comment models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::comments)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Comment {
@@ -17,6 +18,14 @@ pub struct Comment {
     pub parent_comment_id: Option<Uuid>,
     pub is_edited: Option<bool>,
     pub is_deleted: Option<bool>,
+    /// Set when a moderator hid the comment; hidden comments are only
+    /// returned by the moderation endpoints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_by: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_reason: Option<String>,
 }
 
 #[derive(Insertable, Serialize, Deserialize)]
@@ -100,6 +109,67 @@ pub struct NewCommentReaction {
     pub reaction_type: String,
 }
 
+/// How many users reacted to a comment with one emoji
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, ToSchema)]
+pub struct ReactionSummary {
+    pub emoji: String,
+    pub count: i64,
+    /// Whether the requesting user is among them
+    pub reacted: bool,
+}
+
+/// Entry of an issue's comment list; replies are listed alongside their
+/// parent and point to it through `parent_comment_id`
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CommentListItem {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub reply_count: i64,
+    pub reactions: Vec<ReactionSummary>,
+}
+
+/// Display order of a comment page
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentOrder {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+/// Which page of an issue's comments to return. At most one of `around`,
+/// `before` and `after` may be set; with none, the page starts at the top of
+/// the chosen order.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CommentPageQuery {
+    #[serde(default)]
+    pub order: CommentOrder,
+    /// Comment to center the page on, e.g. the target of a notification link
+    pub around: Option<Uuid>,
+    /// Comments shown before this one in `order`
+    pub before: Option<Uuid>,
+    /// Comments shown after this one in `order`
+    pub after: Option<Uuid>,
+    pub limit: Option<i64>,
+    pub include_deleted: Option<bool>,
+}
+
+/// A window of an issue's comments in display order
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CommentPage {
+    pub comments: Vec<CommentListItem>,
+    pub order: CommentOrder,
+    /// Set for `around` queries; the anchor is included in `comments`
+    pub anchor_id: Option<Uuid>,
+    pub has_more_before: bool,
+    pub has_more_after: bool,
+    /// Pass as `before` to load the page above this one
+    pub before_cursor: Option<Uuid>,
+    /// Pass as `after` to load the page below this one
+    pub after_cursor: Option<Uuid>,
+}
+
 // API Response models
 #[derive(Serialize, Deserialize)]
 pub struct CommentWithDetails {
@@ -126,3 +196,101 @@ pub struct UpdateCommentRequest {
     pub content: Option<String>,
     pub content_type: Option<String>,
 }
+
+pub mod comment_flag_status {
+    pub const OPEN: &str = "open";
+    pub const DISMISSED: &str = "dismissed";
+    /// The comment was hidden or deleted by a moderator
+    pub const ACTIONED: &str = "actioned";
+    pub const ALL: &[&str] = &[OPEN, DISMISSED, ACTIONED];
+}
+
+/// Heuristics that can flag a comment as suspected spam
+pub mod spam_signals {
+    /// Many links in one comment
+    pub const LINKS: &str = "links";
+    /// The author posted the same text several times recently
+    pub const DUPLICATE: &str = "duplicate";
+    /// The author is posting comments unusually fast
+    pub const BURST: &str = "burst";
+    /// Mostly upper-case text
+    pub const SHOUTING: &str = "shouting";
+}
+
+/// A comment the spam heuristics found suspect
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::comment_flags)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CommentFlag {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub workspace_id: Uuid,
+    pub author_id: Uuid,
+    /// Comma separated [`spam_signals`]
+    pub reasons: String,
+    pub score: i32,
+    pub status: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::comment_flags)]
+pub struct NewCommentFlag {
+    pub comment_id: Uuid,
+    pub workspace_id: Uuid,
+    pub author_id: Uuid,
+    pub reasons: String,
+    pub score: i32,
+}
+
+/// Flagged comment with its content, for review
+#[derive(Serialize, Clone, ToSchema)]
+pub struct CommentFlagItem {
+    #[serde(flatten)]
+    pub flag: CommentFlag,
+    pub comment: Comment,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Keep the content for audit but remove it from regular responses
+    Hide,
+    /// Delete the comments with their replies, reactions and mentions
+    Delete,
+}
+
+/// Select comments of a workspace by author and/or content pattern
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct BulkModerateCommentsRequest {
+    pub workspace_id: Uuid,
+    pub author_id: Option<Uuid>,
+    /// Case-insensitive text the content must contain; `*` matches any run
+    /// of characters
+    pub pattern: Option<String>,
+    /// Only comments created at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub action: ModerationAction,
+    pub reason: Option<String>,
+    /// Report the matching comments without changing them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct BulkModerationResult {
+    pub matched: usize,
+    /// Comments changed; hidden comments that were already hidden are not
+    /// counted
+    pub affected: usize,
+    pub comment_ids: Vec<Uuid>,
+    pub dry_run: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct UnhideCommentsRequest {
+    pub workspace_id: Uuid,
+    pub comment_ids: Vec<Uuid>,
+}
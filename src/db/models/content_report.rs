@@ -0,0 +1,117 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Why a member reported content
+pub mod report_reasons {
+    pub const SPAM: &str = "spam";
+    pub const HARASSMENT: &str = "harassment";
+    pub const INAPPROPRIATE: &str = "inappropriate";
+    pub const OFF_TOPIC: &str = "off_topic";
+    /// Requires `details`
+    pub const OTHER: &str = "other";
+    pub const ALL: &[&str] = &[SPAM, HARASSMENT, INAPPROPRIATE, OFF_TOPIC, OTHER];
+}
+
+pub mod report_targets {
+    pub const COMMENT: &str = "comment";
+    pub const ISSUE: &str = "issue";
+    pub const ALL: &[&str] = &[COMMENT, ISSUE];
+}
+
+pub mod report_status {
+    pub const OPEN: &str = "open";
+    pub const DISMISSED: &str = "dismissed";
+    /// A comment was hidden or an issue archived
+    pub const HIDDEN: &str = "hidden";
+    /// The content was deleted
+    pub const REMOVED: &str = "removed";
+    pub const ALL: &[&str] = &[OPEN, DISMISSED, HIDDEN, REMOVED];
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::content_reports)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ContentReport {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub reporter_id: Option<Uuid>,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub reason: String,
+    pub details: Option<String>,
+    pub status: String,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::content_reports)]
+pub struct NewContentReport {
+    pub workspace_id: Uuid,
+    pub reporter_id: Option<Uuid>,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub reason: String,
+    pub details: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateReportRequest {
+    /// One of [`report_reasons`]
+    pub reason: String,
+    pub details: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReportQueueQuery {
+    /// Defaults to `open`
+    pub status: Option<String>,
+    pub target_type: Option<String>,
+}
+
+/// What a moderator does about reported content
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportResolution {
+    /// Keep the content as it is
+    Dismiss,
+    /// Hide a comment or archive an issue
+    Hide,
+    /// Delete the content
+    Remove,
+}
+
+impl ReportResolution {
+    pub fn status(self) -> &'static str {
+        match self {
+            ReportResolution::Dismiss => report_status::DISMISSED,
+            ReportResolution::Hide => report_status::HIDDEN,
+            ReportResolution::Remove => report_status::REMOVED,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct ResolveReportRequest {
+    pub action: ReportResolution,
+    /// Kept with the reports; not shown to reporters
+    pub note: Option<String>,
+}
+
+/// A report in the moderation queue with the content it is about
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ReportQueueItem {
+    #[serde(flatten)]
+    pub report: ContentReport,
+    /// Author of the comment or creator of the issue; `None` once removed
+    pub content_author_id: Option<Uuid>,
+    /// Start of the comment or the issue title; `None` once removed
+    pub content_excerpt: Option<String>,
+    /// Open reports on the same content, this one included
+    pub open_reports: i64,
+}
@@ -0,0 +1,97 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Upload state of a custom emoji. Rows start `pending` when the upload URL
+/// is issued and become `uploaded` once the image is confirmed in storage;
+/// only uploaded emoji can be used.
+pub mod custom_emoji_status {
+    pub const PENDING: &str = "pending";
+    pub const UPLOADED: &str = "uploaded";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::custom_emojis)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CustomEmoji {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Path below the `emojis/` prefix in the bucket
+    #[serde(skip)]
+    pub storage_key: String,
+    pub status: String,
+    pub created_by: Option<Uuid>,
+    /// Reactions plus comments that used the emoji
+    pub usage_count: i64,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub uploaded_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl CustomEmoji {
+    /// Object key in the bucket, matching `AssetUrlHelper::build_emoji_url`
+    pub fn object_key(&self) -> String {
+        format!("emojis/{}", self.storage_key)
+    }
+
+    /// How the emoji is written in reactions and markdown, e.g. `:party:`
+    pub fn shortcode(&self) -> String {
+        format!(":{}:", self.name)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::custom_emojis)]
+pub struct NewCustomEmoji {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateCustomEmojiRequest {
+    pub name: String,
+    pub content_type: String,
+    /// Declared size; checked against the stored image when the upload completes
+    pub size_bytes: i64,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct RenameCustomEmojiRequest {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CustomEmojiStatsQuery {
+    /// Only emoji not used in this many days (or never used)
+    pub unused_days: Option<i64>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct CustomEmojiResponse {
+    #[serde(flatten)]
+    pub emoji: CustomEmoji,
+    pub shortcode: String,
+    /// Public URL through the assets host, for rendering
+    pub url: String,
+}
+
+/// Returned when an emoji upload is started; the client sends the image to
+/// `upload_url` and then calls the complete endpoint
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct CustomEmojiUpload {
+    pub emoji: CustomEmoji,
+    pub upload_url: String,
+    pub upload_method: &'static str,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
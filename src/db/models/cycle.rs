@@ -1,10 +1,12 @@
 use crate::db::enums::CycleStatus;
+use crate::db::models::workflow::WorkflowStateCategory;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // Cycle models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::cycles)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Cycle {
@@ -30,3 +32,324 @@ pub struct NewCycle {
     pub description: Option<String>,
     pub goal: Option<String>,
 }
+
+/// Kinds of entries in `cycle_scope_changes`
+pub mod cycle_scope_change_kinds {
+    pub const ADDED: &str = "added";
+    pub const REMOVED: &str = "removed";
+    /// An issue in the cycle was re-estimated
+    pub const ESTIMATED: &str = "estimated";
+    /// An issue in the cycle moved into a completed state
+    pub const COMPLETED: &str = "completed";
+    /// A completed issue in the cycle moved out of its completed state
+    pub const REOPENED: &str = "reopened";
+    /// Kinds that change the cycle's scope; the others record progress
+    pub const SCOPE: &[&str] = &[ADDED, REMOVED, ESTIMATED];
+}
+
+/// A change to a cycle's scope, recorded by a database trigger
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::cycle_scope_changes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CycleScopeChange {
+    pub id: i64,
+    pub cycle_id: Uuid,
+    pub issue_id: Uuid,
+    /// One of [`cycle_scope_change_kinds`]
+    pub change: String,
+    pub points_delta: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Cycles whose status was moved along by their dates
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct CycleStatusUpdates {
+    pub activated: usize,
+    pub completed: usize,
+}
+
+/// Estimate and state of one issue in a cycle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CycleIssuePoints {
+    pub estimate: Option<i32>,
+    pub category: Option<WorkflowStateCategory>,
+}
+
+/// Point rollup of a cycle. Issues without an estimate count as zero points.
+#[derive(Serialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct CyclePoints {
+    /// Points in the cycle when it started
+    pub committed_points: i64,
+    pub completed_points: i64,
+    /// Points in the cycle now
+    pub total_points: i64,
+    /// Points added since the cycle started, by new issues or re-estimates
+    pub scope_added_points: i64,
+    /// Points removed since the cycle started
+    pub scope_removed_points: i64,
+    /// Net scope change since the cycle started
+    pub scope_change_points: i64,
+    pub issues_added: i64,
+    pub issues_removed: i64,
+    pub unestimated_issues: i64,
+}
+
+impl CyclePoints {
+    /// Roll up the cycle's current issues and the scope changes made since it
+    /// started; the committed points are what the current total was before
+    /// those changes.
+    pub fn compute(issues: &[CycleIssuePoints], changes_since_start: &[CycleScopeChange]) -> Self {
+        let mut points = CyclePoints::default();
+        for issue in issues {
+            let estimate = issue.estimate.unwrap_or(0) as i64;
+            points.total_points += estimate;
+            if issue.category == Some(WorkflowStateCategory::Completed) {
+                points.completed_points += estimate;
+            }
+            if issue.estimate.is_none() {
+                points.unestimated_issues += 1;
+            }
+        }
+        for change in changes_since_start
+            .iter()
+            .filter(|c| cycle_scope_change_kinds::SCOPE.contains(&c.change.as_str()))
+        {
+            let delta = change.points_delta as i64;
+            if delta > 0 {
+                points.scope_added_points += delta;
+            } else {
+                points.scope_removed_points -= delta;
+            }
+            match change.change.as_str() {
+                cycle_scope_change_kinds::ADDED => points.issues_added += 1,
+                cycle_scope_change_kinds::REMOVED => points.issues_removed += 1,
+                _ => {}
+            }
+        }
+        points.scope_change_points = points.scope_added_points - points.scope_removed_points;
+        points.committed_points = points.total_points - points.scope_change_points;
+        points
+    }
+}
+
+/// A cycle's progress as of the end of one day (UTC)
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct CycleBurndownDay {
+    pub date: chrono::NaiveDate,
+    /// Issues and points in the cycle
+    pub scope_issues: i64,
+    pub scope_points: i64,
+    pub completed_issues: i64,
+    pub completed_points: i64,
+    pub remaining_issues: i64,
+    pub remaining_points: i64,
+    /// Issues added and points added since the cycle started
+    pub scope_added_issues: i64,
+    pub scope_added_points: i64,
+    /// Remaining points on a straight line from the committed points at the
+    /// start to zero at the end date
+    pub ideal_points: f64,
+}
+
+/// Daily burndown of a cycle, from its start date up to today or its end
+/// date, whichever comes first
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct CycleBurndown {
+    pub cycle_id: Uuid,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    /// Issues and points in the cycle when it started
+    pub committed_issues: i64,
+    pub committed_points: i64,
+    pub days: Vec<CycleBurndownDay>,
+}
+
+/// State of one issue while replaying a cycle's log
+#[derive(Default)]
+struct ReplayedIssue {
+    in_cycle: bool,
+    points: i64,
+    completed: bool,
+}
+
+impl CycleBurndown {
+    /// Replay the cycle's full scope change log day by day
+    pub fn compute(
+        cycle_id: Uuid,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+        today: chrono::NaiveDate,
+        changes: &[CycleScopeChange],
+    ) -> Self {
+        let day_start =
+            |date: chrono::NaiveDate| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        let mut ordered: Vec<&CycleScopeChange> = changes.iter().collect();
+        ordered.sort_by_key(|c| (c.created_at, c.id));
+        let mut pending = ordered.into_iter().peekable();
+        let mut issues: std::collections::HashMap<Uuid, ReplayedIssue> =
+            std::collections::HashMap::new();
+
+        let start = day_start(start_date);
+        while let Some(change) = pending.next_if(|c| c.created_at < start) {
+            Self::apply(&mut issues, change);
+        }
+        let (committed_issues, committed_points, _, _) = Self::totals(&issues);
+
+        let length = (end_date - start_date).num_days();
+        let mut days = Vec::new();
+        let (mut added_issues, mut added_points) = (0, 0);
+        let mut date = start_date;
+        while date <= end_date.min(today) {
+            let Some(next) = date.succ_opt() else {
+                break;
+            };
+            let end_of_day = day_start(next);
+            while let Some(change) = pending.next_if(|c| c.created_at < end_of_day) {
+                if change.change == cycle_scope_change_kinds::ADDED {
+                    added_issues += 1;
+                }
+                if cycle_scope_change_kinds::SCOPE.contains(&change.change.as_str())
+                    && change.points_delta > 0
+                {
+                    added_points += change.points_delta as i64;
+                }
+                Self::apply(&mut issues, change);
+            }
+
+            let (scope_issues, scope_points, completed_issues, completed_points) =
+                Self::totals(&issues);
+            let elapsed = (date - start_date).num_days();
+            let ideal_points = if length > 0 {
+                committed_points as f64 * (length - elapsed) as f64 / length as f64
+            } else {
+                0.0
+            };
+            days.push(CycleBurndownDay {
+                date,
+                scope_issues,
+                scope_points,
+                completed_issues,
+                completed_points,
+                remaining_issues: scope_issues - completed_issues,
+                remaining_points: scope_points - completed_points,
+                scope_added_issues: added_issues,
+                scope_added_points: added_points,
+                ideal_points,
+            });
+            date = next;
+        }
+
+        CycleBurndown {
+            cycle_id,
+            start_date,
+            end_date,
+            committed_issues,
+            committed_points,
+            days,
+        }
+    }
+
+    fn apply(
+        issues: &mut std::collections::HashMap<Uuid, ReplayedIssue>,
+        change: &CycleScopeChange,
+    ) {
+        let issue = issues.entry(change.issue_id).or_default();
+        let delta = change.points_delta as i64;
+        match change.change.as_str() {
+            // A re-added issue starts over; if it is already completed the
+            // log records that right after
+            cycle_scope_change_kinds::ADDED => {
+                *issue = ReplayedIssue {
+                    in_cycle: true,
+                    points: delta,
+                    completed: false,
+                }
+            }
+            cycle_scope_change_kinds::REMOVED => issue.in_cycle = false,
+            cycle_scope_change_kinds::ESTIMATED => issue.points += delta,
+            cycle_scope_change_kinds::COMPLETED => issue.completed = true,
+            cycle_scope_change_kinds::REOPENED => issue.completed = false,
+            _ => {}
+        }
+    }
+
+    /// Issues and points in the cycle, then those completed
+    fn totals(issues: &std::collections::HashMap<Uuid, ReplayedIssue>) -> (i64, i64, i64, i64) {
+        let mut totals = (0, 0, 0, 0);
+        for issue in issues.values().filter(|i| i.in_cycle) {
+            totals.0 += 1;
+            totals.1 += issue.points;
+            if issue.completed {
+                totals.2 += 1;
+                totals.3 += issue.points;
+            }
+        }
+        totals
+    }
+}
+
+/// A team's cycle cadence. The scheduler keeps one planned cycle ahead for
+/// teams with `auto_create` set.
+#[derive(Queryable, Selectable, Serialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::team_cycle_settings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TeamCycleSettings {
+    pub team_id: Uuid,
+    pub auto_create: bool,
+    pub length_days: i32,
+    /// ISO weekday cycles start on: 1 is Monday, 7 is Sunday
+    pub start_weekday: i32,
+    /// Days left free between the end of one cycle and the start of the next
+    pub cooldown_days: i32,
+    /// Move issues not completed or canceled when a cycle ends into the next
+    pub roll_over_unfinished: bool,
+    pub last_rolled_over_cycle_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::team_cycle_settings)]
+pub struct NewTeamCycleSettings {
+    pub team_id: Uuid,
+    pub auto_create: bool,
+    pub length_days: i32,
+    pub start_weekday: i32,
+    pub cooldown_days: i32,
+    pub roll_over_unfinished: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct UpsertCycleSettingsRequest {
+    pub auto_create: Option<bool>,
+    pub length_days: i32,
+    /// ISO weekday cycles start on: 1 is Monday, 7 is Sunday
+    pub start_weekday: i32,
+    #[serde(default)]
+    pub cooldown_days: i32,
+    #[serde(default)]
+    pub roll_over_unfinished: bool,
+}
+
+/// The cycle the scheduler would create next for a team
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct NextCyclePreview {
+    pub team_id: Uuid,
+    pub name: String,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    /// The team's latest cycle, which the next one follows
+    pub previous_cycle_id: Option<Uuid>,
+    /// Whether the scheduler creates it on its own
+    pub auto_create: bool,
+    /// Unfinished issues in the active cycle that would roll into the next
+    /// one if the active cycle ended now; zero when rollover is off
+    pub roll_over_issues: i64,
+}
+
+/// Cycles created and issues rolled over by one scheduler run
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct CycleAutoCreateSummary {
+    pub created: usize,
+    pub rolled_over: usize,
+}
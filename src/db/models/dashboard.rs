@@ -0,0 +1,73 @@
+use crate::db::models::workflow::WorkflowStateCategory;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// An open issue assigned to the dashboard's user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct DashboardIssue {
+    pub id: Uuid,
+    pub issue_number: i32,
+    pub title: String,
+    pub priority: String,
+    pub team_id: Uuid,
+    pub state_category: Option<WorkflowStateCategory>,
+    pub cycle_id: Option<Uuid>,
+    /// Issues have no due date of their own; they are due when their cycle ends
+    pub cycle_end_date: Option<chrono::NaiveDate>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Issue and point totals of an active cycle, summed in the database
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct DashboardCycleRollup {
+    pub cycle_id: Uuid,
+    pub team_id: Uuid,
+    pub name: String,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    pub total_issues: i64,
+    pub completed_issues: i64,
+    /// Issues without an estimate count as zero points
+    pub total_points: i64,
+    pub completed_points: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct DashboardCycleProgress {
+    #[serde(flatten)]
+    pub rollup: DashboardCycleRollup,
+    /// Share of the cycle's issues that are completed, 0-100
+    pub completion_rate: f64,
+    pub days_remaining: i64,
+}
+
+/// A recent notification for the dashboard's user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct DashboardActivity {
+    pub notification_id: Uuid,
+    pub event_type: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub title: String,
+    pub read: bool,
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct DashboardSummary {
+    /// Open issues assigned to the user, soonest cycle end first
+    pub my_open_issues: Vec<DashboardIssue>,
+    /// Whether more open issues exist than are listed; no total is counted
+    pub has_more_open_issues: bool,
+    /// Open issues whose cycle ends between today and the end of the week
+    pub due_this_week: Vec<DashboardIssue>,
+    pub week_start: chrono::NaiveDate,
+    pub week_end: chrono::NaiveDate,
+    /// Active cycles of the teams the user belongs to
+    pub active_cycles: Vec<DashboardCycleProgress>,
+    pub recent_activity: Vec<DashboardActivity>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    /// Whether the summary was served from the cache
+    pub cached: bool,
+}
@@ -0,0 +1,69 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// What a delete confirmation can be for
+pub mod delete_targets {
+    pub const WORKSPACE: &str = "workspace";
+    pub const TEAM: &str = "team";
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::delete_confirmations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeleteConfirmationRecord {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub target_name: String,
+    pub token_hash: String,
+    /// JSON-encoded [`DeleteImpact`]
+    pub impact: String,
+    pub requested_by: Option<Uuid>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub confirmed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::delete_confirmations)]
+pub struct NewDeleteConfirmation {
+    pub workspace_id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub target_name: String,
+    pub token_hash: String,
+    pub impact: String,
+    pub requested_by: Option<Uuid>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// What a delete takes with it
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct DeleteImpact {
+    pub issues: i64,
+    pub members: i64,
+    pub teams: i64,
+    pub projects: i64,
+    pub cycles: i64,
+}
+
+/// Returned by the first delete call; repeating the delete with `token`
+/// before `expires_at` carries it out
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct DeleteConfirmation {
+    pub token: String,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub target_name: String,
+    pub impact: DeleteImpact,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteConfirmationQuery {
+    pub confirmation_token: Option<String>,
+}
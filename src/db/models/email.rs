@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::models::notification::DigestEmail;
+
+/// An outgoing email rendered from one of the templates
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    /// HTML alternative of `text`; plain-text only when `None`
+    pub html: Option<String>,
+    pub reply_to: Option<String>,
+    /// Sender for this message, e.g. the workspace's verified domain; the
+    /// platform sender when `None`
+    #[serde(default)]
+    pub from: Option<String>,
+}
+
+impl From<DigestEmail> for EmailMessage {
+    fn from(digest: DigestEmail) -> Self {
+        Self {
+            to: digest.to,
+            subject: digest.subject,
+            text: digest.text,
+            html: None,
+            reply_to: digest.reply_to,
+            from: None,
+        }
+    }
+}
+
+/// An email waiting in the send queue
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueuedEmail {
+    pub message: EmailMessage,
+    /// Failed send attempts so far
+    #[serde(default)]
+    pub attempts: u32,
+}
@@ -0,0 +1,176 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Error tracking settings of a workspace
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::error_tracking_integrations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ErrorTrackingIntegration {
+    pub workspace_id: Uuid,
+    /// Team new error issues are filed in
+    pub team_id: Uuid,
+    #[serde(skip_serializing)]
+    pub ingest_key: String,
+    /// Creator of the issues filed for new errors
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::error_tracking_integrations)]
+pub struct NewErrorTrackingIntegration {
+    pub workspace_id: Uuid,
+    pub team_id: Uuid,
+    pub ingest_key: String,
+    pub created_by: Uuid,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::error_tracking_integrations)]
+pub struct UpdateErrorTrackingIntegration {
+    pub team_id: Option<Uuid>,
+    pub ingest_key: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct UpdateErrorTrackingRequest {
+    pub team_id: Uuid,
+    #[serde(default)]
+    pub rotate_key: bool,
+    /// Service account new error issues are created by; defaults to the
+    /// user setting the integration up
+    pub creator_id: Option<Uuid>,
+}
+
+/// Integration settings; `ingest_key` is only returned when it was (re)generated
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ErrorTrackingConfig {
+    #[serde(flatten)]
+    pub integration: ErrorTrackingIntegration,
+    /// URL monitoring tools post error events to
+    pub ingest_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_key: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ErrorEventQuery {
+    pub workspace_id: Uuid,
+}
+
+pub mod error_levels {
+    pub const FATAL: &str = "fatal";
+    pub const ERROR: &str = "error";
+    pub const WARNING: &str = "warning";
+    pub const INFO: &str = "info";
+    pub const DEBUG: &str = "debug";
+}
+
+// Subset of the Sentry event payload the ingestion reads
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct ErrorEventFrame {
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub function: Option<String>,
+    #[serde(default)]
+    pub lineno: Option<i64>,
+    /// Whether the frame is in the application rather than a library
+    #[serde(default)]
+    pub in_app: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct ErrorEventStacktrace {
+    /// Oldest call first, as Sentry sends them
+    #[serde(default)]
+    pub frames: Vec<ErrorEventFrame>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct ErrorEventException {
+    #[serde(rename = "type", default)]
+    pub exception_type: Option<String>,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub stacktrace: Option<ErrorEventStacktrace>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct ErrorEventExceptions {
+    #[serde(default)]
+    pub values: Vec<ErrorEventException>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct ErrorEvent {
+    #[serde(default)]
+    pub event_id: Option<String>,
+    /// Grouping key parts set by the client; `{{ default }}` stands for the
+    /// key the server would have derived
+    #[serde(default)]
+    pub fingerprint: Option<Vec<String>>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub exception: Option<ErrorEventExceptions>,
+    #[serde(default)]
+    pub level: Option<String>,
+    /// Where the error happened, e.g. the transaction or function name
+    #[serde(default)]
+    pub culprit: Option<String>,
+    #[serde(default)]
+    pub environment: Option<String>,
+    #[serde(default)]
+    pub release: Option<String>,
+    #[serde(default)]
+    pub platform: Option<String>,
+}
+
+/// A distinct error and the issue tracking it
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::error_groups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ErrorGroup {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub fingerprint: String,
+    pub issue_id: Uuid,
+    pub title: String,
+    pub level: String,
+    pub occurrences: i64,
+    /// Times a recurrence reopened the resolved issue
+    pub reopened_count: i32,
+    pub first_seen_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub last_event_id: Option<String>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::error_groups)]
+pub struct NewErrorGroup {
+    pub workspace_id: Uuid,
+    pub fingerprint: String,
+    pub issue_id: Uuid,
+    pub title: String,
+    pub level: String,
+    pub last_event_id: Option<String>,
+}
+
+/// What an ingested event did
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ErrorIngestResult {
+    pub group: ErrorGroup,
+    /// Set when the event opened a new issue
+    pub created: bool,
+    /// Set when the event reopened a resolved issue
+    pub reopened: bool,
+}
@@ -0,0 +1,56 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::db::models::issue::IssueResponse;
+
+/// A link from an issue to a record in another system, e.g. a Zendesk
+/// ticket or a Sentry issue
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[diesel(table_name = crate::schema::external_references)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ExternalReference {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    /// Lower-case name of the other system, e.g. `zendesk`
+    pub source: String,
+    pub external_id: String,
+    pub url: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::external_references)]
+pub struct NewExternalReference {
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    pub source: String,
+    pub external_id: String,
+    pub url: Option<String>,
+    pub created_by: Uuid,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateExternalReferenceRequest {
+    pub source: String,
+    pub external_id: String,
+    pub url: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExternalReferenceQuery {
+    pub external_id: String,
+    /// Limit the lookup to one system
+    pub source: Option<String>,
+}
+
+/// An issue found by one of its external ids
+#[derive(Serialize, Clone, ToSchema)]
+pub struct ExternalReferenceMatch {
+    pub reference: ExternalReference,
+    pub issue: IssueResponse,
+}
@@ -0,0 +1,136 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// GitHub webhook settings of a workspace
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::github_integrations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GithubIntegration {
+    pub workspace_id: Uuid,
+    #[serde(skip_serializing)]
+    pub webhook_secret: String,
+    /// State linked issues move to when a pull request merges; `None` only links
+    pub merge_state_name: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::github_integrations)]
+pub struct NewGithubIntegration {
+    pub workspace_id: Uuid,
+    pub webhook_secret: String,
+    pub merge_state_name: Option<String>,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::github_integrations)]
+pub struct UpdateGithubIntegration {
+    pub webhook_secret: Option<String>,
+    pub merge_state_name: Option<Option<String>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateGithubIntegrationRequest {
+    /// Name of the workflow state merged pull requests move issues to;
+    /// `None` turns transitions off
+    #[serde(default)]
+    pub merge_state_name: Option<String>,
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+/// Integration settings; `webhook_secret` is only returned when it was (re)generated
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct GithubIntegrationConfig {
+    #[serde(flatten)]
+    pub integration: GithubIntegration,
+    /// Payload URL to enter in the GitHub webhook settings
+    pub webhook_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_secret: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GithubWebhookQuery {
+    pub workspace_id: Uuid,
+}
+
+// Subset of the GitHub webhook payloads the integration reads
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubRepository {
+    pub full_name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubCommitAuthor {
+    pub name: String,
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubCommit {
+    pub id: String,
+    pub message: String,
+    pub url: String,
+    pub author: GithubCommitAuthor,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubPushEvent {
+    pub repository: GithubRepository,
+    #[serde(default)]
+    pub commits: Vec<GithubCommit>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubUser {
+    pub login: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubBranch {
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubPullRequest {
+    pub number: i64,
+    pub title: String,
+    pub html_url: String,
+    /// `open` or `closed`
+    pub state: String,
+    #[serde(default)]
+    pub merged: bool,
+    pub user: GithubUser,
+    pub head: GithubBranch,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GithubPullRequestEvent {
+    pub action: String,
+    pub pull_request: GithubPullRequest,
+    pub repository: GithubRepository,
+}
+
+/// What a webhook call did
+#[derive(Serialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct GithubWebhookResult {
+    pub event: String,
+    /// Links created or refreshed
+    pub linked: usize,
+    /// Issues moved to the merge state
+    pub transitioned: usize,
+    /// Set for event types the integration does not handle
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub ignored: bool,
+}
@@ -0,0 +1,45 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Holiday models
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[diesel(table_name = crate::schema::workspace_holidays)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Holiday {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub holiday_date: chrono::NaiveDate,
+    pub region: Option<String>,
+    pub source: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::workspace_holidays)]
+pub struct NewHoliday {
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub holiday_date: chrono::NaiveDate,
+    pub region: Option<String>,
+    pub source: String,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::workspace_holidays)]
+pub struct UpdateHoliday {
+    pub name: Option<String>,
+    pub holiday_date: Option<chrono::NaiveDate>,
+    pub region: Option<Option<String>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct HolidayImportResult {
+    pub imported: usize,
+    pub skipped: usize,
+    pub holidays: Vec<Holiday>,
+}
@@ -0,0 +1,27 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Result of a single integrity check
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IntegrityFinding {
+    pub check: String,
+    pub description: String,
+    pub count: i64,
+    pub fixable: bool,
+    pub fixed: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IntegrityReport {
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+    pub fix_applied: bool,
+    pub total_problems: i64,
+    pub total_fixed: i64,
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.total_problems == 0
+    }
+}
@@ -1,6 +1,7 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::io::Write;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::db::models::workspace_member::WorkspaceMemberRole;
@@ -8,7 +9,14 @@ use crate::schema::invitations;
 
 // InvitationStatus 枚举定义
 #[derive(
-    Debug, Clone, PartialEq, Serialize, Deserialize, diesel::FromSqlRow, diesel::AsExpression,
+    Debug,
+    Clone,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    diesel::FromSqlRow,
+    diesel::AsExpression,
+    ToSchema,
 )]
 #[diesel(sql_type = crate::schema::sql_types::InvitationStatus)]
 pub enum InvitationStatus {
@@ -52,7 +60,7 @@ impl diesel::deserialize::FromSql<crate::schema::sql_types::InvitationStatus, di
 }
 
 // Invitation 模型定义
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[diesel(table_name = invitations)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Invitation {
@@ -81,3 +89,37 @@ pub struct NewInvitation {
 pub struct UpdateInvitation {
     pub status: Option<InvitationStatus>,
 }
+
+/// Outcome of one entry of a bulk invitation
+pub mod bulk_invite_status {
+    pub const INVITED: &str = "invited";
+    /// Already a member or already invited; nothing was sent
+    pub const SKIPPED: &str = "skipped";
+    pub const FAILED: &str = "failed";
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct BulkInviteEntry {
+    pub email: String,
+    /// Defaults to member
+    #[serde(default)]
+    pub role: Option<WorkspaceMemberRole>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct BulkInviteRequest {
+    pub invitations: Vec<BulkInviteEntry>,
+}
+
+/// Result of one entry, in request order
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct BulkInviteResult {
+    pub email: String,
+    /// One of [`bulk_invite_status`]
+    pub status: String,
+    pub invitation: Option<Invitation>,
+    /// Why the entry was skipped or failed; failures also carry the error
+    /// code
+    pub error_code: Option<String>,
+    pub message: Option<String>,
+}
@@ -1,10 +1,11 @@
 use crate::db::enums::IssuePriority;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // Issue models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::issues)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Issue {
@@ -25,6 +26,13 @@ pub struct Issue {
     pub team_id: Uuid,
     pub workflow_id: Option<Uuid>,
     pub workflow_state_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Bulk archive batch that archived the issue
+    #[serde(skip)]
+    pub archive_batch_id: Option<Uuid>,
+    /// Points on the team's estimate scale
+    pub estimate: Option<i32>,
 }
 
 #[derive(Insertable)]
@@ -42,6 +50,7 @@ pub struct NewIssue {
     pub team_id: Uuid,
     pub workflow_id: Option<Uuid>,
     pub workflow_state_id: Option<Uuid>,
+    pub estimate: Option<i32>,
 }
 
 // Issue update model
@@ -59,6 +68,7 @@ pub struct UpdateIssue {
     pub team_id: Option<Uuid>,
     pub workflow_id: Option<Option<Uuid>>,
     pub workflow_state_id: Option<Option<Uuid>>,
+    pub estimate: Option<Option<i32>>,
 }
 
 // Issue Label models (many-to-many relationship)
@@ -70,6 +80,21 @@ pub struct IssueLabel {
     pub label_id: Uuid,
 }
 
+/// Nullable issue fields to clear in an update
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IssueFieldClears {
+    pub description: bool,
+    pub project_id: bool,
+    pub assignee_id: bool,
+    pub cycle_id: bool,
+}
+
+impl IssueFieldClears {
+    pub fn any(&self) -> bool {
+        self.description || self.project_id || self.assignee_id || self.cycle_id
+    }
+}
+
 #[derive(Insertable)]
 #[diesel(table_name = crate::schema::issue_labels)]
 pub struct NewIssueLabel {
@@ -104,7 +129,7 @@ pub struct UpdateIssueRequest {
     pub is_changelog_candidate: Option<bool>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, ToSchema)]
 pub struct IssueResponse {
     pub id: Uuid,
     pub project_id: Option<Uuid>,
@@ -117,11 +142,14 @@ pub struct IssueResponse {
     pub description: Option<String>,
     #[serde(serialize_with = "serialize_priority")]
     pub priority: IssuePriority,
+    pub estimate: Option<i32>,
     pub is_changelog_candidate: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub team_id: Uuid,
     pub team_key: Option<String>,
+    /// Team key and issue number, e.g. `ENG-42`
+    pub identifier: Option<String>,
     pub workflow_id: Option<Uuid>,
     pub workflow_state_id: Option<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -129,10 +157,17 @@ pub struct IssueResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub team: Option<crate::db::models::team::TeamBasicInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(no_recursion)]
     pub parent_issue: Option<Box<IssueResponse>>, // boxed to avoid infinite size
     #[serde(default)]
+    #[schema(no_recursion)]
     pub child_issues: Vec<IssueResponse>,
     #[serde(default)]
+    pub relations: Vec<crate::db::models::issue_relation::IssueRelationResponse>,
+    /// Relations to issues of sibling workspaces, including backlinks
+    #[serde(default)]
+    pub external_relations: Vec<crate::db::models::issue_relation::CrossWorkspaceRelationResponse>,
+    #[serde(default)]
     pub workflow_states: Vec<crate::db::models::workflow::WorkflowStateResponse>,
     #[serde(default)]
     pub labels: Vec<crate::db::models::label::Label>,
@@ -140,6 +175,14 @@ pub struct IssueResponse {
     pub project: Option<crate::db::models::project::ProjectInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cycle: Option<crate::db::models::cycle::Cycle>,
+    /// When the requesting user last opened this issue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_viewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the issue changed since the requesting user last opened it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unread: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 fn serialize_priority<S>(priority: &IssuePriority, serializer: S) -> Result<S::Ok, S::Error>
@@ -156,6 +199,50 @@ where
     serializer.serialize_str(priority_str)
 }
 
+impl IssueResponse {
+    /// Fields a list request can select with `?fields=`
+    pub const FIELDS: &'static [&'static str] = &[
+        "id",
+        "project_id",
+        "cycle_id",
+        "creator_id",
+        "assignee_id",
+        "parent_issue_id",
+        "issue_number",
+        "title",
+        "description",
+        "priority",
+        "estimate",
+        "is_changelog_candidate",
+        "created_at",
+        "updated_at",
+        "team_id",
+        "team_key",
+        "identifier",
+        "workflow_id",
+        "workflow_state_id",
+        "assignee",
+        "team",
+        "parent_issue",
+        "child_issues",
+        "relations",
+        "external_relations",
+        "workflow_states",
+        "labels",
+        "project",
+        "cycle",
+        "last_viewed_at",
+        "unread",
+        "archived_at",
+    ];
+
+    /// Fill in the read receipt fields from the user's last view
+    pub fn set_last_viewed(&mut self, last_viewed_at: Option<chrono::DateTime<chrono::Utc>>) {
+        self.unread = Some(last_viewed_at.is_none_or(|viewed| self.updated_at > viewed));
+        self.last_viewed_at = last_viewed_at;
+    }
+}
+
 impl From<Issue> for IssueResponse {
     fn from(issue: Issue) -> Self {
         let priority = match issue.priority.as_str() {
@@ -178,21 +265,28 @@ impl From<Issue> for IssueResponse {
             title: issue.title,
             description: issue.description,
             priority,
+            estimate: issue.estimate,
             is_changelog_candidate: issue.is_changelog_candidate,
             created_at: issue.created_at,
             updated_at: issue.updated_at,
             team_id: issue.team_id,
             team_key: None, // Will be populated by the API handler
+            identifier: None,
             workflow_id: issue.workflow_id,
             workflow_state_id: issue.workflow_state_id,
             assignee: None,
             team: None, // Will be populated by the API handler
             parent_issue: None,
             child_issues: Vec::new(),
+            relations: Vec::new(),
+            external_relations: Vec::new(),
             workflow_states: Vec::new(),
             labels: Vec::new(), // Will be populated by the API handler
             project: None,      // Will be populated by the API handler
             cycle: None,        // Will be populated by the API handler
+            last_viewed_at: None,
+            unread: None,
+            archived_at: issue.archived_at,
         }
     }
 }
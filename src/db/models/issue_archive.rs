@@ -0,0 +1,106 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub mod issue_archive_status {
+    pub const PENDING: &str = "pending";
+    pub const RUNNING: &str = "running";
+    pub const COMPLETED: &str = "completed";
+    pub const UNDONE: &str = "undone";
+}
+
+/// Which issues a bulk archive applies to; every set field must match
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct IssueArchiveFilter {
+    pub team_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub cycle_id: Option<Uuid>,
+    pub assignee_id: Option<Uuid>,
+    pub workflow_state_id: Option<Uuid>,
+    pub priority: Option<String>,
+    /// Only issues not updated since this time
+    pub updated_before: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl IssueArchiveFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct BulkArchiveRequest {
+    pub filter: IssueArchiveFilter,
+    /// Only report what would be archived; pass `false` to archive
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct IssueArchiveSample {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub issue_number: i32,
+    pub title: String,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Issues archived by one run of the auto-archive policy
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct AutoArchiveSummary {
+    pub workspaces: usize,
+    pub archived: usize,
+}
+
+/// Result of a dry run
+#[derive(Serialize, Debug, Clone)]
+pub struct BulkArchivePreview {
+    pub count: i64,
+    /// Most recently updated matching issues
+    pub sample: Vec<IssueArchiveSample>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_archive_batches)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueArchiveBatch {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    /// JSON-encoded [`IssueArchiveFilter`]
+    pub filter: String,
+    pub status: String,
+    pub issue_count: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub undone_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_archive_batches)]
+pub struct NewIssueArchiveBatch {
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    pub filter: String,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct IssueArchiveBatchResponse {
+    pub id: Uuid,
+    pub requested_by: Uuid,
+    pub filter: IssueArchiveFilter,
+    pub status: String,
+    /// Issues archived; 0 until the batch has run
+    pub issue_count: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub undone_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The batch can be undone until then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undo_until: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -0,0 +1,182 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Kinds of form fields
+pub mod form_field_types {
+    pub const TEXT: &str = "text";
+    pub const TEXTAREA: &str = "textarea";
+    pub const NUMBER: &str = "number";
+    pub const EMAIL: &str = "email";
+    pub const CHECKBOX: &str = "checkbox";
+    pub const SELECT: &str = "select";
+    pub const MULTI_SELECT: &str = "multi_select";
+}
+
+/// Issue properties a field's answer can fill; other answers are listed in
+/// the issue description under the field's label
+pub mod form_field_targets {
+    pub const TITLE: &str = "title";
+    pub const DESCRIPTION: &str = "description";
+    pub const PRIORITY: &str = "priority";
+    pub const LABELS: &str = "labels";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::issue_forms)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueForm {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub team_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON list of [`FormField`]s
+    #[serde(skip)]
+    pub fields: String,
+    pub is_public: bool,
+    /// Portal address of the form; kept when the form is made private so the
+    /// link works again if it is republished
+    pub public_slug: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_forms)]
+pub struct NewIssueForm {
+    pub workspace_id: Uuid,
+    pub team_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub fields: String,
+    pub is_public: bool,
+    pub public_slug: Option<String>,
+    pub created_by: Uuid,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::issue_forms)]
+pub struct UpdateIssueForm {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub fields: Option<String>,
+    pub is_public: Option<bool>,
+    pub public_slug: Option<Option<String>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// One choice of a select field
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct FormFieldOption {
+    pub value: String,
+    pub label: String,
+    /// Label added to the issue when chosen; only for fields mapped to labels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct FormField {
+    /// Identifier of the answer in submissions, e.g. `steps_to_reproduce`
+    pub key: String,
+    pub label: String,
+    /// One of [`form_field_types`]
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<FormFieldOption>,
+    /// One of [`form_field_targets`]; `None` lists the answer in the description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maps_to: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateIssueFormRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub fields: Vec<FormField>,
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateIssueFormRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub fields: Option<Vec<FormField>>,
+    pub is_public: Option<bool>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct IssueFormResponse {
+    #[serde(flatten)]
+    pub form: IssueForm,
+    pub fields: Vec<FormField>,
+}
+
+/// What the portal shows of a public form
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct PublicIssueFormResponse {
+    pub name: String,
+    pub description: Option<String>,
+    pub fields: Vec<FormField>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct SubmitIssueFormRequest {
+    /// Answers by field key
+    #[serde(default)]
+    pub answers: BTreeMap<String, serde_json::Value>,
+    /// Contact address of a portal submitter
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+/// Issue properties read from a valid submission
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FormSubmissionIssue {
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<String>,
+    pub label_ids: Vec<Uuid>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_form_submissions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueFormSubmission {
+    pub id: Uuid,
+    pub form_id: Uuid,
+    pub issue_id: Uuid,
+    pub submitted_by: Option<Uuid>,
+    pub submitter_email: Option<String>,
+    #[serde(skip)]
+    pub answers: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_form_submissions)]
+pub struct NewIssueFormSubmission {
+    pub form_id: Uuid,
+    pub issue_id: Uuid,
+    pub submitted_by: Option<Uuid>,
+    pub submitter_email: Option<String>,
+    pub answers: String,
+}
+
+/// Acknowledgement returned to portal submitters
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct PortalSubmissionReceipt {
+    pub submission_id: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,130 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How a rule's pattern is matched against the issue title and description
+pub mod label_rule_match_types {
+    /// Case-insensitive text anywhere in the title or description
+    pub const KEYWORD: &str = "keyword";
+    /// Regular expression; `(?i)` makes it case-insensitive
+    pub const REGEX: &str = "regex";
+    pub const ALL: [&str; 2] = [KEYWORD, REGEX];
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::issue_label_rules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueLabelRule {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub team_id: Uuid,
+    pub name: String,
+    pub match_type: String,
+    pub pattern: String,
+    /// Label added on a match; cleared when the label is deleted
+    pub label_id: Option<Uuid>,
+    /// Priority set on a match when the issue was created without one
+    pub priority: Option<String>,
+    pub position: i32,
+    pub enabled: bool,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_label_rules)]
+pub struct NewIssueLabelRule {
+    pub workspace_id: Uuid,
+    pub team_id: Uuid,
+    pub name: String,
+    pub match_type: String,
+    pub pattern: String,
+    pub label_id: Option<Uuid>,
+    pub priority: Option<String>,
+    pub position: i32,
+    pub enabled: bool,
+    pub created_by: Uuid,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::issue_label_rules)]
+pub struct UpdateIssueLabelRule {
+    pub name: Option<String>,
+    pub match_type: Option<String>,
+    pub pattern: Option<String>,
+    pub label_id: Option<Option<Uuid>>,
+    pub priority: Option<Option<String>>,
+    pub position: Option<i32>,
+    pub enabled: Option<bool>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::issue_label_rule_applications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueLabelRuleApplication {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    /// Cleared when the rule is deleted; `rule_name` keeps what it was called
+    pub rule_id: Option<Uuid>,
+    pub rule_name: String,
+    pub label_id: Option<Uuid>,
+    pub priority: Option<String>,
+    pub applied_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_label_rule_applications)]
+pub struct NewIssueLabelRuleApplication {
+    pub issue_id: Uuid,
+    pub rule_id: Option<Uuid>,
+    pub rule_name: String,
+    pub label_id: Option<Uuid>,
+    pub priority: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateIssueLabelRuleRequest {
+    pub name: String,
+    pub match_type: String,
+    pub pattern: String,
+    #[serde(default)]
+    pub label_id: Option<Uuid>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    /// Defaults to after the team's last rule
+    #[serde(default)]
+    pub position: Option<i32>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateIssueLabelRuleRequest {
+    pub name: Option<String>,
+    pub match_type: Option<String>,
+    pub pattern: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]
+    pub label_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]
+    pub priority: Option<Option<String>>,
+    pub position: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// What the matching rules do to a new issue
+#[derive(Debug, Clone, Default)]
+pub struct LabelRuleOutcome {
+    /// Labels to add, in rule order and without duplicates
+    pub label_ids: Vec<Uuid>,
+    /// Priority of the first matching rule that sets one
+    pub priority: Option<String>,
+    /// Matching rules with what each of them contributed
+    pub applied: Vec<NewIssueLabelRuleApplication>,
+}
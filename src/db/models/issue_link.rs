@@ -0,0 +1,48 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Systems issue links come from
+pub mod link_providers {
+    pub const GITHUB: &str = "github";
+}
+
+/// What an issue link points at
+pub mod link_kinds {
+    pub const COMMIT: &str = "commit";
+    pub const PULL_REQUEST: &str = "pull_request";
+}
+
+/// A commit or pull request that mentions an issue
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::issue_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueLink {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub provider: String,
+    pub kind: String,
+    /// Commit sha, or `owner/repo#number` for pull requests
+    pub external_id: String,
+    pub url: String,
+    pub title: String,
+    /// `open`, `closed` or `merged` for pull requests; `None` for commits
+    pub state: Option<String>,
+    pub author: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_links)]
+pub struct NewIssueLink {
+    pub issue_id: Uuid,
+    pub provider: String,
+    pub kind: String,
+    pub external_id: String,
+    pub url: String,
+    pub title: String,
+    pub state: Option<String>,
+    pub author: Option<String>,
+}
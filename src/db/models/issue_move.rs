@@ -0,0 +1,53 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::db::models::issue::Issue;
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::issue_moves)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueMove {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub from_team_id: Option<Uuid>,
+    pub to_team_id: Option<Uuid>,
+    pub from_key: String,
+    pub to_key: String,
+    pub from_state_id: Option<Uuid>,
+    pub to_state_id: Option<Uuid>,
+    pub moved_by: Uuid,
+    pub moved_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_moves)]
+pub struct NewIssueMove {
+    pub issue_id: Uuid,
+    pub from_team_id: Option<Uuid>,
+    pub to_team_id: Option<Uuid>,
+    pub from_key: String,
+    pub to_key: String,
+    pub from_state_id: Option<Uuid>,
+    pub to_state_id: Option<Uuid>,
+    pub moved_by: Uuid,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct MoveIssueRequest {
+    pub team_id: Uuid,
+    /// Source workflow state id -> target team state id. States without an
+    /// entry map to the target state with the same name and category, or
+    /// else the first state of the same category.
+    #[serde(default)]
+    pub state_mapping: HashMap<Uuid, Uuid>,
+}
+
+#[derive(Serialize, Clone, ToSchema)]
+pub struct IssueMoveResult {
+    pub issue: Issue,
+    #[serde(rename = "move")]
+    pub issue_move: IssueMove,
+}
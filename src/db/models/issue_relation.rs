@@ -0,0 +1,183 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::db::models::issue::IssueResponse;
+
+/// Relation kinds as seen from one issue. Only `blocks`, `duplicates` and
+/// `relates_to` are stored; the passive forms are the same rows read from
+/// the other side.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueRelationType {
+    Blocks,
+    BlockedBy,
+    Duplicates,
+    DuplicatedBy,
+    RelatesTo,
+}
+
+impl IssueRelationType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueRelationType::Blocks => "blocks",
+            IssueRelationType::BlockedBy => "blocked_by",
+            IssueRelationType::Duplicates => "duplicates",
+            IssueRelationType::DuplicatedBy => "duplicated_by",
+            IssueRelationType::RelatesTo => "relates_to",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "blocks" => Some(IssueRelationType::Blocks),
+            "blocked_by" => Some(IssueRelationType::BlockedBy),
+            "duplicates" => Some(IssueRelationType::Duplicates),
+            "duplicated_by" => Some(IssueRelationType::DuplicatedBy),
+            "relates_to" => Some(IssueRelationType::RelatesTo),
+            _ => None,
+        }
+    }
+
+    /// The same relation read from the other issue
+    pub fn inverse(&self) -> Self {
+        match self {
+            IssueRelationType::Blocks => IssueRelationType::BlockedBy,
+            IssueRelationType::BlockedBy => IssueRelationType::Blocks,
+            IssueRelationType::Duplicates => IssueRelationType::DuplicatedBy,
+            IssueRelationType::DuplicatedBy => IssueRelationType::Duplicates,
+            IssueRelationType::RelatesTo => IssueRelationType::RelatesTo,
+        }
+    }
+
+    /// Whether the relation is stored with the issues swapped
+    pub fn is_passive(&self) -> bool {
+        matches!(
+            self,
+            IssueRelationType::BlockedBy | IssueRelationType::DuplicatedBy
+        )
+    }
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_relations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueRelation {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub related_issue_id: Uuid,
+    pub relation_type: String,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_relations)]
+pub struct NewIssueRelation {
+    pub issue_id: Uuid,
+    pub related_issue_id: Uuid,
+    pub relation_type: String,
+    pub created_by: Uuid,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateIssueRelationRequest {
+    pub related_issue_id: Uuid,
+    pub relation_type: IssueRelationType,
+}
+
+/// A relation from the point of view of one issue
+#[derive(Serialize, Clone, ToSchema)]
+pub struct IssueRelationResponse {
+    pub id: Uuid,
+    pub relation_type: IssueRelationType,
+    #[schema(no_recursion)]
+    pub related_issue: IssueResponse,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::cross_workspace_issue_relations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CrossWorkspaceIssueRelation {
+    pub id: Uuid,
+    /// Workspace the relation was created from
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    pub related_issue_id: Uuid,
+    pub relation_type: String,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::cross_workspace_issue_relations)]
+pub struct NewCrossWorkspaceIssueRelation {
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    pub related_issue_id: Uuid,
+    pub relation_type: String,
+    pub created_by: Uuid,
+}
+
+/// Relate an issue to an issue of a sibling workspace, referenced as
+/// `<workspace url key>/<issue key>`, e.g. `acme-mobile/ENG-42`
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateCrossWorkspaceRelationRequest {
+    pub reference: String,
+    pub relation_type: IssueRelationType,
+}
+
+/// Parsed `<workspace url key>/<team key>-<number>` reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalIssueRef {
+    pub workspace_url_key: String,
+    pub team_key: String,
+    pub issue_number: i32,
+}
+
+impl ExternalIssueRef {
+    pub fn parse(reference: &str) -> Option<Self> {
+        let (workspace, key) = reference.trim().split_once('/')?;
+        let (team, number) = key.rsplit_once('-')?;
+        if workspace.is_empty() || team.is_empty() {
+            return None;
+        }
+        let issue_number = number.parse::<i32>().ok().filter(|n| *n > 0)?;
+        Some(Self {
+            workspace_url_key: workspace.to_string(),
+            team_key: team.to_string(),
+            issue_number,
+        })
+    }
+}
+
+/// What a sibling workspace may see of an issue. Title and state are left
+/// out when the organization policy no longer allows read-through or the
+/// issue is in a private team.
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ExternalIssueSummary {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub workspace_url_key: String,
+    /// Issue key such as `ENG-42`
+    pub key: String,
+    pub title: Option<String>,
+    /// Name of the workflow state
+    pub state: Option<String>,
+    pub restricted: bool,
+}
+
+/// A cross-workspace relation from the point of view of one issue
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct CrossWorkspaceRelationResponse {
+    pub id: Uuid,
+    pub relation_type: IssueRelationType,
+    pub related_issue: ExternalIssueSummary,
+    /// Whether the relation was created from the other workspace
+    pub is_backlink: bool,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
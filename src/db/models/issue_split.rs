@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::db::models::issue::Issue;
+
+/// Where the new issues are taken from in the original description
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitSource {
+    /// Markdown task list items (`- [ ] ...`)
+    Checklist,
+    /// Markdown heading sections
+    Sections,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct SplitIssueRequest {
+    pub source: SplitSource,
+    /// Zero-based indexes of the checklist items or sections to split out
+    pub items: Vec<usize>,
+    #[serde(default)]
+    pub close_original: bool,
+    /// State to close the original in; defaults to the first completed state
+    pub close_state_id: Option<Uuid>,
+}
+
+/// One splittable part of a description
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SplitCandidate {
+    pub title: String,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Clone, ToSchema)]
+pub struct IssueSplitResult {
+    pub original: Issue,
+    pub created: Vec<Issue>,
+}
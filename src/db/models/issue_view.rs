@@ -0,0 +1,34 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::db::models::auth::UserBasicInfo;
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_views)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IssueView {
+    pub user_id: Uuid,
+    pub issue_id: Uuid,
+    pub first_viewed_at: chrono::DateTime<chrono::Utc>,
+    pub last_viewed_at: chrono::DateTime<chrono::Utc>,
+    pub view_count: i32,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::issue_views)]
+pub struct NewIssueView {
+    pub user_id: Uuid,
+    pub issue_id: Uuid,
+    pub last_viewed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A user who has opened an issue, as shown to workspace admins
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct IssueViewer {
+    pub user: UserBasicInfo,
+    pub first_viewed_at: chrono::DateTime<chrono::Utc>,
+    pub last_viewed_at: chrono::DateTime<chrono::Utc>,
+    pub view_count: i32,
+}
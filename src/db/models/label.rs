@@ -1,10 +1,11 @@
 use crate::db::enums::LabelLevel;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // Label models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::labels)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Label {
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Issue description or comment rendered for email and webhook payloads;
+/// cached by content, so it holds nothing specific to a workspace
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RenderedMarkdown {
+    /// Sanitized HTML
+    pub html: String,
+    /// Text outside code blocks and spans, one line per block
+    pub text: String,
+    /// `@username` mentions in order of first appearance
+    pub mentions: Vec<String>,
+    /// Code blocks in document order
+    pub code_blocks: Vec<CodeBlockInfo>,
+}
+
+/// A code block of rendered markdown. Blocks over the size cap are cut
+/// short in the HTML; the raw endpoints serve them in full by `index`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CodeBlockInfo {
+    pub index: usize,
+    /// Normalized language, e.g. `rust` for a `rs` fence
+    pub language: Option<String>,
+    /// Whether `language` was guessed from the code rather than the fence
+    pub language_detected: bool,
+    pub lines: usize,
+    pub bytes: usize,
+    pub truncated: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct MentionEntity {
+    pub user_id: Uuid,
+    pub username: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IssueReferenceEntity {
+    pub issue_id: Uuid,
+    /// Key as written with the team's casing, e.g. `ENG-42`
+    pub key: String,
+    pub team_id: Uuid,
+    pub issue_number: i32,
+}
+
+/// Mentions and issue references of a text that resolve to members and
+/// issues of the workspace
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct MarkdownEntities {
+    pub mentions: Vec<MentionEntity>,
+    pub issue_references: Vec<IssueReferenceEntity>,
+}
+
+/// Markdown of a webhook payload rendered for receivers that show it
+#[derive(Serialize, Debug, Clone)]
+pub struct RenderedBody {
+    pub html: String,
+    pub code_blocks: Vec<CodeBlockInfo>,
+    #[serde(flatten)]
+    pub entities: MarkdownEntities,
+}
+
+impl RenderedBody {
+    pub fn new(rendered: RenderedMarkdown, entities: MarkdownEntities) -> Self {
+        Self {
+            html: rendered.html,
+            code_blocks: rendered.code_blocks,
+            entities,
+        }
+    }
+}
+
+/// An issue or comment as sent to webhooks, with its markdown rendered
+#[derive(Serialize, Debug)]
+pub struct WithRenderedMarkdown<'a, T> {
+    #[serde(flatten)]
+    pub data: &'a T,
+    /// `None` when there is no markdown, e.g. an issue without a description
+    pub rendered: Option<RenderedBody>,
+}
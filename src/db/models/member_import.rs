@@ -0,0 +1,100 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub mod member_import_status {
+    pub const PENDING: &str = "pending";
+    pub const RUNNING: &str = "running";
+    pub const COMPLETED: &str = "completed";
+}
+
+/// Per-row outcome. `invalid` rows are rejected at upload; `pending` rows are
+/// applied by the worker and end up in one of the remaining states.
+pub mod member_import_row_status {
+    pub const INVALID: &str = "invalid";
+    pub const PENDING: &str = "pending";
+    pub const ADDED: &str = "added";
+    pub const INVITED: &str = "invited";
+    pub const SKIPPED: &str = "skipped";
+    pub const FAILED: &str = "failed";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::member_imports)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MemberImport {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    #[serde(skip)]
+    pub content_hash: String,
+    pub status: String,
+    pub total_rows: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::member_imports)]
+pub struct NewMemberImport {
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    pub content_hash: String,
+    pub total_rows: i32,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::member_import_rows)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MemberImportRow {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub import_id: Uuid,
+    pub row_number: i32,
+    pub email: String,
+    pub role: String,
+    /// Space-separated team ids
+    #[serde(skip)]
+    pub team_ids: String,
+    pub status: String,
+    pub message: Option<String>,
+}
+
+impl MemberImportRow {
+    pub fn team_id_list(&self) -> Vec<Uuid> {
+        self.team_ids
+            .split_whitespace()
+            .filter_map(|id| id.parse().ok())
+            .collect()
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::member_import_rows)]
+pub struct NewMemberImportRow {
+    pub import_id: Uuid,
+    pub row_number: i32,
+    pub email: String,
+    pub role: String,
+    pub team_ids: String,
+    pub status: String,
+    pub message: Option<String>,
+}
+
+/// A parsed CSV row before team names are resolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberImportLine {
+    pub row_number: i32,
+    pub email: String,
+    pub role: String,
+    pub teams: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct MemberImportResponse {
+    #[serde(flatten)]
+    pub import: MemberImport,
+    pub rows: Vec<MemberImportRow>,
+}
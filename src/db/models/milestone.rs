@@ -0,0 +1,81 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Milestone models
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::milestones)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Milestone {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub target_date: Option<chrono::NaiveDate>,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::milestones)]
+pub struct NewMilestone {
+    pub project_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub target_date: Option<chrono::NaiveDate>,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::milestones)]
+pub struct UpdateMilestone {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub target_date: Option<Option<chrono::NaiveDate>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// An issue of a milestone with what progress stats need to know about it
+#[derive(Queryable, Debug, Clone)]
+pub struct MilestoneIssueProgress {
+    pub issue_id: Uuid,
+    /// Category of the issue's workflow state; `None` when it has no state
+    pub category: Option<crate::db::models::workflow::WorkflowStateCategory>,
+    pub added_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// One day of a milestone's burnup chart
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct BurnupPoint {
+    pub day: chrono::NaiveDate,
+    /// Issues attached to the milestone by the end of the day
+    pub scope: i64,
+    /// Of those, issues completed by the end of the day
+    pub completed: i64,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct MilestoneStats {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub target_date: Option<chrono::NaiveDate>,
+    /// Attached issues, not counting canceled ones
+    pub total_issues: i64,
+    pub completed_issues: i64,
+    pub in_progress_issues: i64,
+    pub todo_issues: i64,
+    pub canceled_issues: i64,
+    pub completion_rate: f64,
+    pub days_remaining: Option<i32>,
+    /// Working days (weekdays minus workspace holidays) left until the target date
+    pub working_days_remaining: Option<i64>,
+    pub is_overdue: bool,
+    /// Daily scope and completed counts from the milestone's creation until
+    /// today, or the target date if that is earlier. An issue counts as
+    /// completed from its last update once it is in a completed state.
+    pub burnup: Vec<BurnupPoint>,
+}
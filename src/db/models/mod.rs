@@ -1,18 +1,68 @@
 // Sub-modules organized by functional domain
 pub mod api;
+pub mod api_key;
+pub mod api_token;
+pub mod app_installation;
+pub mod attachment;
+pub mod audit;
 pub mod auth;
+pub mod auto_close;
+pub mod automation;
+pub mod board;
+pub mod channel_permission;
 pub mod comment;
+pub mod content_report;
+pub mod custom_emoji;
 pub mod cycle;
+pub mod dashboard;
+pub mod delete_confirmation;
+pub mod email;
+pub mod error_tracking;
+pub mod external_reference;
+pub mod github_integration;
+pub mod holiday;
+pub mod integrity;
 pub mod invitation;
 pub mod issue;
+pub mod issue_archive;
+pub mod issue_form;
+pub mod issue_label_rule;
+pub mod issue_link;
+pub mod issue_move;
+pub mod issue_relation;
+pub mod issue_split;
+pub mod issue_view;
 pub mod label;
+pub mod markdown;
+pub mod member_import;
+pub mod milestone;
+pub mod notification;
+pub mod oauth_app;
+pub mod organization;
 pub mod project;
+pub mod project_cost;
 pub mod project_status; // Added project_status module
+pub mod push;
 pub mod roadmap;
+pub mod scheduled_job;
+pub mod search;
+pub mod service_account;
+pub mod sync;
 pub mod team;
+pub mod telemetry;
+pub mod upload_session;
+pub mod user_identity;
+pub mod user_preference;
+pub mod webhook;
+pub mod websocket_dead_letter;
+pub mod websocket_session;
 pub mod workflow; // Added workflow module
 pub mod workspace;
+pub mod workspace_email_domain;
+pub mod workspace_import;
 pub mod workspace_member;
+pub mod workspace_reset;
+pub mod workspace_settings;
 pub mod workspace_user;
 
 // Re-export all models to maintain compatibility with existing code
@@ -21,33 +71,176 @@ pub mod workspace_user;
 // API response structures
 pub use api::*;
 
+// API token and usage models
+pub use api_key::*;
+pub use api_token::*;
+
+// App installation and webhook models
+pub use app_installation::*;
+
+// Issue attachment models
+pub use attachment::*;
+
+// Audit log models
+pub use audit::*;
+
 // Authentication and user models
 pub use auth::*;
 
+// Team issue auto-close policy models
+pub use auto_close::*;
+
+// Automation rule ("when X then Y") models
+pub use automation::*;
+
+// Issue board ordering models
+pub use board::*;
+
+// API channel permission restriction models
+pub use channel_permission::*;
+
 // Comment models
 pub use comment::*;
 
+// Reported content models
+pub use content_report::*;
+
+// Workspace custom emoji models
+pub use custom_emoji::*;
+
 // Cycle models
 pub use cycle::*;
 
+// Dashboard summary models
+pub use dashboard::*;
+
+// Delete confirmation models
+pub use delete_confirmation::*;
+
+// Outgoing email models
+pub use email::*;
+
+// Error tracking integration models
+pub use error_tracking::*;
+
+// Issue external reference (Zendesk / Salesforce / Sentry ...) models
+pub use external_reference::*;
+
+// GitHub integration models
+pub use github_integration::*;
+
+// Holiday models
+pub use holiday::*;
+
+// Integrity check models
+pub use integrity::*;
+
 // Issue models
 pub use issue::*;
 
+// Bulk issue archive models
+pub use issue_archive::*;
+
+// Issue intake form models
+pub use issue_form::*;
+
+// Issue link (commit / pull request) models
+pub use issue_link::*;
+
+// Issue move (cross-team transfer) models
+pub use issue_move::*;
+
+// Issue relation (blocks / duplicates / relates-to) models
+pub use issue_relation::*;
+
+// Issue split models
+pub use issue_split::*;
+
+// Issue view (read receipt) models
+pub use issue_view::*;
+
 // Label models
 pub use label::*;
 
+// Rendered markdown and its entities
+pub use markdown::*;
+
+// Bulk member import models
+pub use member_import::*;
+
+// Project milestone models
+pub use milestone::*;
+
+// Notification models
+pub use notification::*;
+
+// OAuth provider (third-party app) models
+pub use oauth_app::*;
+
+// Organization (workspace group) models
+pub use organization::*;
+
 // Project models
 pub use project::*;
 
+// Project budget and cost tracking models
+pub use project_cost::*;
+
 // Roadmap models
+pub use push::*;
 pub use roadmap::*;
 
+// Scheduler job run models
+pub use scheduled_job::*;
+
+// Search models
+pub use search::*;
+
+// Integration service account models
+pub use service_account::*;
+
+// Offline sync snapshot models
+pub use sync::*;
+
 // Team models
 pub use team::*;
 
+// Anonymous usage telemetry models
+pub use telemetry::*;
+
+// Resumable attachment upload models
+pub use upload_session::*;
+
+// External login identity models
+pub use user_identity::*;
+
+// User settings and notification channel models
+pub use user_preference::*;
+
+// Workspace webhook models
+pub use webhook::*;
+
+// Failed WebSocket command models
+pub use websocket_dead_letter::*;
+
+// WebSocket session analytics models
+pub use websocket_session::*;
+
 // Workspace models
 pub use workspace::*;
 
+// Workspace custom email domain models
+pub use workspace_email_domain::*;
+
+// Workspace data import (Linear / Jira) models
+pub use workspace_import::*;
+
+// Workspace sandbox reset models
+pub use workspace_reset::*;
+
+// Workspace-level settings models
+pub use workspace_settings::*;
+
 // WorkspaceMember models
 pub use invitation::*;
 pub use workspace_member::*;
@@ -0,0 +1,99 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Notification event types
+pub mod notification_events {
+    pub const ISSUE_UPDATED: &str = "issue_updated";
+    pub const ISSUE_ASSIGNED: &str = "issue_assigned";
+    pub const COMMENT_CREATED: &str = "comment_created";
+    pub const MENTIONED: &str = "mentioned";
+    pub const INVITATION_RECEIVED: &str = "invitation_received";
+    pub const AUTO_CLOSE_WARNING: &str = "auto_close_warning";
+    pub const BUDGET_ALERT: &str = "budget_alert";
+    pub const REPORT_RESOLVED: &str = "report_resolved";
+
+    pub const ALL: &[&str] = &[
+        ISSUE_UPDATED,
+        ISSUE_ASSIGNED,
+        COMMENT_CREATED,
+        MENTIONED,
+        INVITATION_RECEIVED,
+        AUTO_CLOSE_WARNING,
+        BUDGET_ALERT,
+        REPORT_RESOLVED,
+    ];
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::notifications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Notification {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub recipient_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub event_type: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub title: String,
+    pub body: Option<String>,
+    pub event_count: i32,
+    pub read_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub email_pending: bool,
+    pub emailed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Shown in the recipient's inbox; `false` when they turned the in-app
+    /// channel off for this type
+    pub in_app: bool,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::notifications)]
+pub struct NewNotification {
+    pub workspace_id: Uuid,
+    pub recipient_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub event_type: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub title: String,
+    pub body: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationListQuery {
+    #[serde(default)]
+    pub unread_only: bool,
+    /// Only notifications last updated before this time, for paging
+    pub before: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct NotificationList {
+    pub notifications: Vec<Notification>,
+    pub unread_count: i64,
+}
+
+/// Notifications rolled up into a single email for one recipient
+#[derive(Serialize, Debug, Clone)]
+pub struct NotificationDigest {
+    pub recipient_id: Uuid,
+    pub total_events: i64,
+    pub notifications: Vec<Notification>,
+}
+
+/// A digest rendered as a plain-text email
+#[derive(Serialize, Debug, Clone)]
+pub struct DigestEmail {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    /// Set when every comment in the digest is on the same issue, so a plain
+    /// reply lands on that issue
+    pub reply_to: Option<String>,
+}
@@ -0,0 +1,282 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Scopes third-party apps can request. `write:x` implies `read:x`.
+pub mod oauth_scopes {
+    pub const READ_USER: &str = "read:user";
+    pub const READ_WORKSPACE: &str = "read:workspace";
+    pub const WRITE_WORKSPACE: &str = "write:workspace";
+    pub const READ_TEAMS: &str = "read:teams";
+    pub const WRITE_TEAMS: &str = "write:teams";
+    pub const READ_PROJECTS: &str = "read:projects";
+    pub const WRITE_PROJECTS: &str = "write:projects";
+    pub const READ_ISSUES: &str = "read:issues";
+    pub const WRITE_ISSUES: &str = "write:issues";
+    pub const READ_COMMENTS: &str = "read:comments";
+    pub const WRITE_COMMENTS: &str = "write:comments";
+
+    pub const ALL: &[&str] = &[
+        READ_USER,
+        READ_WORKSPACE,
+        WRITE_WORKSPACE,
+        READ_TEAMS,
+        WRITE_TEAMS,
+        READ_PROJECTS,
+        WRITE_PROJECTS,
+        READ_ISSUES,
+        WRITE_ISSUES,
+        READ_COMMENTS,
+        WRITE_COMMENTS,
+    ];
+
+    pub fn is_valid(scope: &str) -> bool {
+        ALL.contains(&scope)
+    }
+
+    /// Split a space-separated scope string
+    pub fn parse(scopes: &str) -> Vec<String> {
+        let mut parsed: Vec<String> = Vec::new();
+        for scope in scopes.split_whitespace() {
+            if !parsed.iter().any(|s| s == scope) {
+                parsed.push(scope.to_string());
+            }
+        }
+        parsed
+    }
+
+    pub fn join(scopes: &[String]) -> String {
+        scopes.join(" ")
+    }
+
+    /// Whether `granted` covers `required`
+    pub fn allows(granted: &[String], required: &str) -> bool {
+        granted.iter().any(|scope| {
+            scope == required
+                || required
+                    .strip_prefix("read:")
+                    .is_some_and(|resource| scope.strip_prefix("write:") == Some(resource))
+        })
+    }
+
+    /// Scope needed to call a route, given its method and matched path.
+    /// `None` means the route is not available to third-party apps.
+    pub fn required_for(method: &str, path: &str) -> Option<&'static str> {
+        let write = !matches!(method, "GET" | "HEAD" | "OPTIONS");
+        let pick = |read: &'static str, write_scope: &'static str| {
+            Some(if write { write_scope } else { read })
+        };
+
+        if path.contains("/comments") {
+            return pick(READ_COMMENTS, WRITE_COMMENTS);
+        }
+        let resource = path.trim_start_matches('/').split('/').next().unwrap_or("");
+        match resource {
+            "auth" if path == "/auth/profile" && !write => Some(READ_USER),
+            "workspaces" if path.starts_with("/workspaces/current/apps") => None,
+            "users" if path == "/users/preferences" => None,
+            "issues" | "search" => pick(READ_ISSUES, WRITE_ISSUES),
+            "projects" | "project-statuses" => pick(READ_PROJECTS, WRITE_PROJECTS),
+            "teams" | "user" => pick(READ_TEAMS, WRITE_TEAMS),
+            "workspaces" | "workspace-members" | "labels" | "cycles" | "workflows" | "holidays"
+            | "users" => pick(READ_WORKSPACE, WRITE_WORKSPACE),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::oauth_apps)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OAuthApp {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub client_id: String,
+    #[serde(skip)]
+    pub client_secret_hash: String,
+    pub redirect_uris: String,
+    pub scopes: String,
+    pub tier: String,
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub webhook_url: Option<String>,
+    pub webhook_events: String,
+    pub webhook_filter: Option<String>,
+}
+
+impl OAuthApp {
+    pub fn redirect_uri_list(&self) -> Vec<&str> {
+        self.redirect_uris.split_whitespace().collect()
+    }
+
+    pub fn webhook_event_list(&self) -> Vec<String> {
+        self.webhook_events
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::oauth_apps)]
+pub struct NewOAuthApp {
+    pub owner_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub client_id: String,
+    pub client_secret_hash: String,
+    pub redirect_uris: String,
+    pub scopes: String,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::oauth_grants)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OAuthGrant {
+    pub id: Uuid,
+    pub app_id: Uuid,
+    pub user_id: Uuid,
+    pub scopes: String,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::oauth_authorization_codes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OAuthAuthorizationCode {
+    pub code_hash: String,
+    pub grant_id: Uuid,
+    pub redirect_uri: String,
+    pub scopes: String,
+    pub code_challenge: Option<String>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::oauth_authorization_codes)]
+pub struct NewOAuthAuthorizationCode {
+    pub code_hash: String,
+    pub grant_id: Uuid,
+    pub redirect_uri: String,
+    pub scopes: String,
+    pub code_challenge: Option<String>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::oauth_access_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OAuthAccessToken {
+    pub id: Uuid,
+    pub grant_id: Uuid,
+    pub token_hash: String,
+    pub refresh_token_hash: Option<String>,
+    pub scopes: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub refresh_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::oauth_access_tokens)]
+pub struct NewOAuthAccessToken {
+    pub grant_id: Uuid,
+    pub token_hash: String,
+    pub refresh_token_hash: Option<String>,
+    pub scopes: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub refresh_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateOAuthAppRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+}
+
+/// Returned once on registration; the client secret cannot be retrieved again
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct CreatedOAuthApp {
+    pub client_secret: String,
+    #[serde(flatten)]
+    pub app: OAuthApp,
+}
+
+/// Parameters of an authorization request (`response_type=code`)
+#[derive(Deserialize, Debug, Clone, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct AuthorizeRequest {
+    pub response_type: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+}
+
+/// What the consent screen shows before the user approves
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AuthorizePreview {
+    pub app_id: Uuid,
+    pub app_name: String,
+    pub app_description: Option<String>,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    /// Scopes the user already granted to this app
+    pub previously_granted: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct AuthorizeDecision {
+    #[serde(flatten)]
+    pub request: AuthorizeRequest,
+    pub approve: bool,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AuthorizeRedirect {
+    pub redirect_to: String,
+}
+
+/// `POST /oauth/token` form body
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct TokenRequest {
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub refresh_token: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub code_verifier: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    pub expires_in: i64,
+    pub refresh_token: String,
+    pub scope: String,
+}
+
+/// An app the user has authorized, for the "connected apps" settings page
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct AuthorizedApp {
+    pub app_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub scopes: Vec<String>,
+    pub authorized_at: chrono::DateTime<chrono::Utc>,
+}
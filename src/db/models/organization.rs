@@ -0,0 +1,66 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A group of workspaces; its policy decides what sibling workspaces may
+/// see of each other
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::organizations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    /// Whether issues may be related to issues of sibling workspaces, which
+    /// exposes the key, title and state of the related issue
+    pub allow_cross_workspace_links: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::organizations)]
+pub struct NewOrganization {
+    pub name: String,
+    pub allow_cross_workspace_links: bool,
+}
+
+#[derive(AsChangeset, Debug, Clone, Default)]
+#[diesel(table_name = crate::schema::organizations)]
+pub struct OrganizationChanges {
+    pub name: Option<String>,
+    pub allow_cross_workspace_links: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+    #[serde(default)]
+    pub allow_cross_workspace_links: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct UpdateOrganizationRequest {
+    pub name: Option<String>,
+    pub allow_cross_workspace_links: Option<bool>,
+}
+
+/// Put a workspace into an organization; `None` takes it out
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct SetWorkspaceOrganizationRequest {
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct OrganizationWorkspace {
+    pub id: Uuid,
+    pub name: String,
+    pub url_key: String,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct OrganizationResponse {
+    #[serde(flatten)]
+    pub organization: Organization,
+    pub workspaces: Vec<OrganizationWorkspace>,
+}
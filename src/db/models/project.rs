@@ -1,10 +1,11 @@
 use crate::db::enums::{ProjectPriority, ProjectStatus};
 use crate::db::models::auth::UserBasicInfo;
-use crate::db::models::project_status::ProjectStatusInfo;
+use crate::db::models::project_status::{ProjectStatusCategory, ProjectStatusInfo};
 use chrono;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 // 为ProjectPriority实现FromStr trait
@@ -38,7 +39,7 @@ impl std::fmt::Display for ProjectPriority {
 }
 
 // Project models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::projects)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Project {
@@ -58,6 +59,14 @@ pub struct Project {
         deserialize_with = "deserialize_priority"
     )]
     pub priority: ProjectPriority,
+    /// ISO 4217 code; cost entries are logged in this currency
+    pub budget_currency: Option<String>,
+    /// Budget in minor units of `budget_currency`
+    pub budget_amount_cents: Option<i64>,
+    /// Share of the budget spent at which the owner is warned
+    pub budget_alert_percent: i32,
+    /// Highest budget alert already sent for the current spend
+    pub budget_alert_level: String,
 }
 
 #[derive(Insertable)]
@@ -98,7 +107,7 @@ pub struct CreateProjectRequest {
     pub priority: Option<ProjectPriority>,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, ToSchema)]
 pub struct ProjectInfo {
     pub id: Uuid,
     pub name: String,
@@ -113,10 +122,31 @@ pub struct ProjectInfo {
         deserialize_with = "deserialize_priority"
     )]
     pub priority: ProjectPriority,
+    pub budget_currency: Option<String>,
+    pub budget_amount_cents: Option<i64>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl ProjectInfo {
+    /// Fields a list request can select with `?fields=`
+    pub const FIELDS: &'static [&'static str] = &[
+        "id",
+        "name",
+        "project_key",
+        "description",
+        "status",
+        "available_statuses",
+        "owner",
+        "target_date",
+        "priority",
+        "budget_currency",
+        "budget_amount_cents",
+        "created_at",
+        "updated_at",
+    ];
+}
+
 fn serialize_priority<S>(priority: &ProjectPriority, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
@@ -180,3 +210,60 @@ pub struct ProjectListQuery {
     pub page: Option<i64>,
     pub per_page: Option<i64>,
 }
+
+/// Filters of the project status board; each list is comma-separated and
+/// matches any of its values
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectBoardQuery {
+    /// Status categories to show columns for, e.g. `planned,in_progress`
+    pub categories: Option<String>,
+    pub status_ids: Option<String>,
+    pub lead_ids: Option<String>,
+    pub search: Option<String>,
+}
+
+/// Whether a project's issues are done at the pace its target date needs
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectHealth {
+    OnTrack,
+    AtRisk,
+    /// Past the target date with issues still open
+    OffTrack,
+}
+
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct ProjectBoardCard {
+    pub id: Uuid,
+    pub name: String,
+    pub project_key: String,
+    pub status: ProjectStatusInfo,
+    /// The project owner
+    pub lead: UserBasicInfo,
+    #[serde(serialize_with = "serialize_priority")]
+    pub priority: ProjectPriority,
+    pub target_date: Option<chrono::NaiveDate>,
+    /// Unarchived issues, canceled ones excluded
+    pub total_issues: i64,
+    pub completed_issues: i64,
+    /// `None` for finished projects and those without a target date
+    pub health: Option<ProjectHealth>,
+    /// Latest change to the project or one of its issues
+    pub last_activity_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Projects whose status is in one category
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct ProjectBoardColumn {
+    pub category: ProjectStatusCategory,
+    pub statuses: Vec<ProjectStatusInfo>,
+    pub count: i64,
+    pub projects: Vec<ProjectBoardCard>,
+}
+
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct ProjectBoard {
+    pub columns: Vec<ProjectBoardColumn>,
+    pub total_count: i64,
+}
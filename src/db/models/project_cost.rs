@@ -0,0 +1,158 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Default share of the budget at which the project owner is warned
+pub const DEFAULT_BUDGET_ALERT_PERCENT: i32 = 80;
+
+/// Longest single cost entry, in minutes
+pub const MAX_COST_ENTRY_MINUTES: i32 = 24 * 60;
+
+/// Hourly rate of a workspace member, used to price their logged time
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::member_cost_rates)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MemberCostRate {
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub hourly_rate_cents: i64,
+    pub currency: String,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::schema::member_cost_rates)]
+pub struct NewMemberCostRate {
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub hourly_rate_cents: i64,
+    pub currency: String,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Time a member spent on a project, priced at their rate when logged
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::project_cost_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ProjectCostEntry {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub issue_id: Option<Uuid>,
+    pub minutes: i32,
+    pub hourly_rate_cents: i64,
+    pub amount_cents: i64,
+    pub currency: String,
+    pub spent_on: chrono::NaiveDate,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::project_cost_entries)]
+pub struct NewProjectCostEntry {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub issue_id: Option<Uuid>,
+    pub minutes: i32,
+    pub hourly_rate_cents: i64,
+    pub amount_cents: i64,
+    pub currency: String,
+    pub spent_on: chrono::NaiveDate,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+}
+
+/// Replaces the project's budget; leaving `currency` out removes it
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct SetProjectBudgetRequest {
+    pub currency: Option<String>,
+    pub amount_cents: Option<i64>,
+    pub alert_percent: Option<i32>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct SetMemberRateRequest {
+    pub hourly_rate_cents: i64,
+    pub currency: String,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateCostEntryRequest {
+    /// Whose time this is; defaults to the caller
+    pub user_id: Option<Uuid>,
+    pub issue_id: Option<Uuid>,
+    pub minutes: i32,
+    /// Defaults to today
+    pub spent_on: Option<chrono::NaiveDate>,
+    pub description: Option<String>,
+}
+
+/// How spend compares to the budget
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    NoBudget,
+    Ok,
+    Approaching,
+    Exceeded,
+}
+
+impl BudgetStatus {
+    /// Stored as the project's alert level; a project without a budget
+    /// has nothing to alert on
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetStatus::NoBudget | BudgetStatus::Ok => "ok",
+            BudgetStatus::Approaching => "approaching",
+            BudgetStatus::Exceeded => "exceeded",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "ok" => Some(BudgetStatus::Ok),
+            "approaching" => Some(BudgetStatus::Approaching),
+            "exceeded" => Some(BudgetStatus::Exceeded),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct MemberCost {
+    pub user_id: Uuid,
+    pub minutes: i64,
+    pub amount_cents: i64,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ProjectCostSummary {
+    pub project_id: Uuid,
+    pub currency: Option<String>,
+    pub budget_amount_cents: Option<i64>,
+    pub spent_cents: i64,
+    /// Negative once the budget is overspent
+    pub remaining_cents: Option<i64>,
+    pub percent_used: Option<f64>,
+    pub alert_percent: i32,
+    pub status: BudgetStatus,
+    pub total_minutes: i64,
+    /// Highest spend first
+    pub by_member: Vec<MemberCost>,
+}
+
+/// Sent to the project owner and subscribers when spend crosses the alert
+/// threshold or the budget
+#[derive(Serialize, Debug, Clone)]
+pub struct BudgetAlert {
+    pub project_id: Uuid,
+    pub status: BudgetStatus,
+    pub spent_cents: i64,
+    pub budget_amount_cents: i64,
+    pub currency: String,
+}
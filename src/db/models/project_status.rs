@@ -4,9 +4,10 @@ use diesel::prelude::*;
 use diesel::serialize::{self, Output, ToSql};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel::AsExpression, diesel::FromSqlRow)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel::AsExpression, diesel::FromSqlRow, ToSchema)]
 #[diesel(sql_type = diesel::sql_types::Text)]
 pub enum ProjectStatusCategory {
     Backlog,
@@ -17,6 +18,15 @@ pub enum ProjectStatusCategory {
 }
 
 impl ProjectStatusCategory {
+    /// Every category, in board column order
+    pub const ALL: [ProjectStatusCategory; 5] = [
+        ProjectStatusCategory::Backlog,
+        ProjectStatusCategory::Planned,
+        ProjectStatusCategory::InProgress,
+        ProjectStatusCategory::Completed,
+        ProjectStatusCategory::Canceled,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             ProjectStatusCategory::Backlog => "backlog",
@@ -136,7 +146,7 @@ pub struct CreateProjectStatusRequest {
     pub category: ProjectStatusCategory,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 pub struct ProjectStatusInfo {
     pub id: Uuid,
     pub name: String,
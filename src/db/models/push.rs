@@ -0,0 +1,156 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Push services a device can be registered with
+pub mod push_platforms {
+    /// Browser Web Push; the token is the subscription endpoint URL
+    pub const WEB: &str = "web";
+    /// Apple Push Notification service; the token is the hex device token
+    pub const APNS: &str = "apns";
+    /// Firebase Cloud Messaging; the token is the registration token
+    pub const FCM: &str = "fcm";
+
+    pub const ALL: &[&str] = &[WEB, APNS, FCM];
+
+    pub fn is_valid(platform: &str) -> bool {
+        ALL.contains(&platform)
+    }
+}
+
+pub mod push_delivery_status {
+    pub const PENDING: &str = "pending";
+    pub const DELIVERED: &str = "delivered";
+    pub const FAILED: &str = "failed";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::push_subscriptions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PushSubscription {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub platform: String,
+    #[serde(skip_serializing)]
+    pub token: String,
+    pub device_name: Option<String>,
+    pub last_delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::push_subscriptions)]
+pub struct NewPushSubscription {
+    pub user_id: Uuid,
+    pub platform: String,
+    pub token: String,
+    pub device_name: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct RegisterPushSubscriptionRequest {
+    pub platform: String,
+    pub token: String,
+    pub device_name: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::push_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PushPreferenceRecord {
+    pub user_id: Uuid,
+    pub enabled: bool,
+    /// Space-separated notification event types
+    pub muted_events: String,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Which notifications a user wants pushed to their devices
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct PushPreferences {
+    pub enabled: bool,
+    pub muted_events: Vec<String>,
+}
+
+impl Default for PushPreferences {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            muted_events: Vec::new(),
+        }
+    }
+}
+
+impl PushPreferences {
+    pub fn allows(&self, event_type: &str) -> bool {
+        self.enabled && !self.muted_events.iter().any(|e| e == event_type)
+    }
+}
+
+impl From<PushPreferenceRecord> for PushPreferences {
+    fn from(record: PushPreferenceRecord) -> Self {
+        Self {
+            enabled: record.enabled,
+            muted_events: record
+                .muted_events
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdatePushPreferencesRequest {
+    pub enabled: Option<bool>,
+    pub muted_events: Option<Vec<String>>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::push_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PushDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub notification_id: Uuid,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::push_deliveries)]
+pub struct NewPushDelivery {
+    pub subscription_id: Uuid,
+    pub notification_id: Uuid,
+    pub payload: String,
+}
+
+/// Outcome of a delivery attempt
+#[derive(AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::push_deliveries, treat_none_as_null = true)]
+pub struct PushDeliveryAttempt {
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// What is sent to a device: enough to show the notification and open it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PushPayload {
+    pub notification_id: Uuid,
+    pub workspace_id: Uuid,
+    pub event_type: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub title: String,
+    pub body: Option<String>,
+}
@@ -0,0 +1,60 @@
+use diesel::prelude::*;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Outcome of a scheduler job's last run
+pub mod scheduled_job_status {
+    pub const RUNNING: &str = "running";
+    pub const SUCCEEDED: &str = "succeeded";
+    pub const FAILED: &str = "failed";
+}
+
+/// Last run of a scheduler job as stored in the database
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::scheduled_jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ScheduledJobRecord {
+    pub name: String,
+    /// Schedule the job ran with, e.g. `*/15 * * * *` or `@every 5m`
+    pub schedule: String,
+    /// One of [`scheduled_job_status`]
+    pub last_status: Option<String>,
+    pub last_started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_duration_ms: Option<i64>,
+    /// Summary the job returned on success
+    pub last_result: Option<String>,
+    pub last_error: Option<String>,
+    pub run_count: i64,
+    pub failure_count: i64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How a run ended
+#[derive(Debug, Clone)]
+pub struct ScheduledJobOutcome {
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+    pub duration_ms: i64,
+    pub result: Result<String, String>,
+}
+
+/// A registered job with its next run and stored last run, served at
+/// `/admin/jobs`
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ScheduledJobStatus {
+    pub name: String,
+    pub schedule: String,
+    /// `false` when the schedule was overridden with `off`
+    pub enabled: bool,
+    /// Runs on one replica at a time
+    pub exclusive: bool,
+    pub next_run_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_status: Option<String>,
+    pub last_started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_duration_ms: Option<i64>,
+    pub last_result: Option<String>,
+    pub last_error: Option<String>,
+    pub run_count: i64,
+    pub failure_count: i64,
+}
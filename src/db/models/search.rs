@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::issue::IssueResponse;
+
+/// `GET /search/issues` query string
+#[derive(Deserialize, Debug, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IssueSearchParams {
+    pub q: String,
+    pub team_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub assignee_id: Option<Uuid>,
+    pub priority: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Structured filters applied alongside the full-text match
+#[derive(Debug, Clone, Default)]
+pub struct IssueSearchFilters {
+    pub team_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub assignee_id: Option<Uuid>,
+    pub priority: Option<String>,
+}
+
+/// One search result; higher `rank` means a better match
+#[derive(Serialize, Clone, ToSchema)]
+pub struct IssueSearchHit {
+    pub rank: f32,
+    #[serde(flatten)]
+    pub issue: IssueResponse,
+}
@@ -0,0 +1,56 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::workspace_member::WorkspaceMemberRole;
+
+/// Workspace-side record of a machine user; `user_id` is the user it acts as
+#[derive(Queryable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::service_accounts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ServiceAccountRecord {
+    pub user_id: Uuid,
+    pub workspace_id: Uuid,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::service_accounts)]
+pub struct NewServiceAccount {
+    pub user_id: Uuid,
+    pub workspace_id: Uuid,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateServiceAccountRequest {
+    pub name: String,
+    /// Derived from `name` when left out
+    pub username: Option<String>,
+    pub description: Option<String>,
+    /// Workspace role the account acts with; `member` by default and never
+    /// `owner`
+    pub role: Option<WorkspaceMemberRole>,
+}
+
+/// A service account as shown to workspace admins
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ServiceAccount {
+    /// Id of the account's user, as it appears in issues and the audit log
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub description: Option<String>,
+    pub role: Option<WorkspaceMemberRole>,
+    pub created_by: Option<Uuid>,
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::db::enums::{CycleStatus, LabelLevel};
+
+/// Version of the snapshot format; bumped on incompatible changes so clients
+/// know to discard their local copy and take a new snapshot
+pub const SYNC_SCHEMA_VERSION: i32 = 1;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncChangesQuery {
+    /// `cursor` of the previous snapshot or change set
+    pub since: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SyncIssue {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub cycle_id: Option<Uuid>,
+    pub assignee_id: Option<Uuid>,
+    pub parent_issue_id: Option<Uuid>,
+    pub issue_number: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: String,
+    pub workflow_state_id: Option<Uuid>,
+    pub label_ids: Vec<Uuid>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SyncCycle {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub name: String,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    pub status: CycleStatus,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SyncTeam {
+    pub id: Uuid,
+    pub name: String,
+    pub team_key: String,
+    pub icon_url: Option<String>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SyncLabel {
+    pub id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub level: LabelLevel,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+/// Everything an offline client keeps locally
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SyncSnapshot {
+    pub schema_version: i32,
+    pub workspace_id: Uuid,
+    /// Pass as `since` to fetch changes made after this snapshot
+    pub cursor: chrono::DateTime<chrono::Utc>,
+    /// Issues assigned to or created by the user, most recently updated first
+    pub issues: Vec<SyncIssue>,
+    /// More issues exist than a snapshot carries
+    pub issues_truncated: bool,
+    /// Cycles of the user's teams that are running today
+    pub cycles: Vec<SyncCycle>,
+    pub teams: Vec<SyncTeam>,
+    pub labels: Vec<SyncLabel>,
+}
+
+/// Ids of every record that is still part of the snapshot; the client drops
+/// local records missing from these lists
+#[derive(Serialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct SyncIds {
+    pub issues: Vec<Uuid>,
+    pub cycles: Vec<Uuid>,
+    pub teams: Vec<Uuid>,
+    pub labels: Vec<Uuid>,
+}
+
+/// Records changed since a cursor, plus what is left of the snapshot
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SyncChanges {
+    pub schema_version: i32,
+    pub workspace_id: Uuid,
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub cursor: chrono::DateTime<chrono::Utc>,
+    pub issues: Vec<SyncIssue>,
+    pub cycles: Vec<SyncCycle>,
+    pub teams: Vec<SyncTeam>,
+    pub labels: Vec<SyncLabel>,
+    pub present: SyncIds,
+}
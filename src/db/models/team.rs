@@ -1,9 +1,48 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::db::models::issue::Issue;
+
+/// Deepest allowed nesting; a top-level team is at depth 1
+pub const MAX_TEAM_DEPTH: usize = 5;
+
+/// Scales a team can estimate its issues on. Estimates are stored as points;
+/// t-shirt sizes XS, S, M, L and XL are 1, 2, 3, 5 and 8 points.
+pub mod estimate_scales {
+    pub const FIBONACCI: &str = "fibonacci";
+    pub const TSHIRT: &str = "tshirt";
+    pub const LINEAR: &str = "linear";
+
+    pub const ALL: [&str; 3] = [FIBONACCI, TSHIRT, LINEAR];
+
+    /// Points allowed on the scale, `None` for an unknown scale
+    pub fn points(scale: &str) -> Option<&'static [i32]> {
+        match scale {
+            FIBONACCI => Some(&[0, 1, 2, 3, 5, 8, 13, 21]),
+            TSHIRT => Some(&[1, 2, 3, 5, 8]),
+            LINEAR => Some(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
+            _ => None,
+        }
+    }
+}
+
+/// How the team assigns new issues created without an assignee
+pub mod assignment_modes {
+    /// Leave them unassigned
+    pub const NONE: &str = "none";
+    /// Assign the team's default assignee
+    pub const DEFAULT_ASSIGNEE: &str = "default_assignee";
+    /// Take turns among the team's members in the order they joined
+    pub const ROUND_ROBIN: &str = "round_robin";
+
+    pub const ALL: [&str; 3] = [NONE, DEFAULT_ASSIGNEE, ROUND_ROBIN];
+}
+
 // Team models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::teams)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Team {
@@ -16,6 +55,16 @@ pub struct Team {
     pub description: Option<String>,
     pub icon_url: Option<String>,
     pub is_private: bool,
+    pub parent_team_id: Option<Uuid>,
+    /// One of [`estimate_scales`]
+    pub estimate_scale: String,
+    /// One of [`assignment_modes`]
+    pub assignment_mode: String,
+    /// Assignee of new unassigned issues in the `default_assignee` mode
+    pub default_assignee_id: Option<Uuid>,
+    /// Where round-robin assignment left off
+    #[serde(skip)]
+    pub last_auto_assignee_id: Option<Uuid>,
 }
 
 #[derive(Insertable)]
@@ -27,6 +76,8 @@ pub struct NewTeam {
     pub description: Option<String>,
     pub icon_url: Option<String>,
     pub is_private: bool,
+    pub parent_team_id: Option<Uuid>,
+    pub estimate_scale: String,
 }
 
 // Team Member models
@@ -49,7 +100,7 @@ pub struct NewTeamMember {
 }
 
 // Team API DTOs
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct TeamInfo {
     pub id: Uuid,
     pub name: String,
@@ -60,7 +111,7 @@ pub struct TeamInfo {
     pub role: String,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, ToSchema)]
 pub struct TeamBasicInfo {
     pub id: Uuid,
     pub name: String,
@@ -148,7 +199,7 @@ pub struct TeamDetailResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct TeamMemberInfo {
     pub user: crate::db::models::auth::UserBasicInfo,
     pub role: String,
@@ -172,3 +223,163 @@ pub struct UpdateTeamRequest {
     pub icon_url: Option<String>,
     pub is_private: Option<bool>,
 }
+
+/// Parent links of every team in a workspace, for walking the sub-team tree.
+/// Walks stop at a team already visited, so a corrupt cycle cannot hang them.
+#[derive(Debug, Clone, Default)]
+pub struct TeamHierarchy {
+    parents: HashMap<Uuid, Uuid>,
+    children: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl TeamHierarchy {
+    pub fn new(links: impl IntoIterator<Item = (Uuid, Option<Uuid>)>) -> Self {
+        let mut hierarchy = Self::default();
+        for (team_id, parent_team_id) in links {
+            if let Some(parent_team_id) = parent_team_id {
+                hierarchy.parents.insert(team_id, parent_team_id);
+                hierarchy
+                    .children
+                    .entry(parent_team_id)
+                    .or_default()
+                    .push(team_id);
+            }
+        }
+        hierarchy
+    }
+
+    pub fn from_teams(teams: &[Team]) -> Self {
+        Self::new(teams.iter().map(|t| (t.id, t.parent_team_id)))
+    }
+
+    pub fn parent(&self, team_id: Uuid) -> Option<Uuid> {
+        self.parents.get(&team_id).copied()
+    }
+
+    pub fn children(&self, team_id: Uuid) -> &[Uuid] {
+        self.children
+            .get(&team_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Parent, grandparent and so on up to the top-level team
+    pub fn ancestors(&self, team_id: Uuid) -> Vec<Uuid> {
+        let mut seen = HashSet::from([team_id]);
+        let mut ancestors = Vec::new();
+        let mut current = team_id;
+        while let Some(parent) = self.parent(current) {
+            if !seen.insert(parent) {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+
+    /// Every team below `team_id`, breadth first
+    pub fn descendants(&self, team_id: Uuid) -> Vec<Uuid> {
+        let mut seen = HashSet::from([team_id]);
+        let mut descendants = Vec::new();
+        let mut queue = VecDeque::from([team_id]);
+        while let Some(current) = queue.pop_front() {
+            for child in self.children(current) {
+                if seen.insert(*child) {
+                    descendants.push(*child);
+                    queue.push_back(*child);
+                }
+            }
+        }
+        descendants
+    }
+
+    /// `team_id` followed by its descendants
+    pub fn subtree(&self, team_id: Uuid) -> Vec<Uuid> {
+        let mut subtree = vec![team_id];
+        subtree.extend(self.descendants(team_id));
+        subtree
+    }
+
+    /// Levels from the top-level team down to `team_id`, counting both
+    pub fn depth(&self, team_id: Uuid) -> usize {
+        self.ancestors(team_id).len() + 1
+    }
+
+    /// Levels in the subtree rooted at `team_id`, counting `team_id`
+    pub fn height(&self, team_id: Uuid) -> usize {
+        let mut seen = HashSet::from([team_id]);
+        self.height_from(team_id, &mut seen)
+    }
+
+    fn height_from(&self, team_id: Uuid, seen: &mut HashSet<Uuid>) -> usize {
+        let mut tallest = 0;
+        for child in self.children(team_id) {
+            if seen.insert(*child) {
+                tallest = tallest.max(self.height_from(*child, seen));
+            }
+        }
+        tallest + 1
+    }
+
+    /// Whether putting `team_id` under `parent_team_id` would make a team
+    /// its own ancestor
+    pub fn would_cycle(&self, team_id: Uuid, parent_team_id: Uuid) -> bool {
+        parent_team_id == team_id || self.ancestors(parent_team_id).contains(&team_id)
+    }
+}
+
+/// Payload for moving a team under another team; `None` makes it top-level
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct SetParentTeamRequest {
+    pub parent_team_id: Option<Uuid>,
+}
+
+/// A team in the sub-team tree with issue counts rolled up from below
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct TeamHierarchyNode {
+    pub id: Uuid,
+    pub name: String,
+    pub team_key: String,
+    pub parent_team_id: Option<Uuid>,
+    /// Private itself or below a private team
+    pub is_private: bool,
+    /// Unarchived issues owned by this team
+    pub issue_count: i64,
+    /// Issues of this team and every visible team below it
+    pub total_issue_count: i64,
+    #[schema(no_recursion)]
+    pub children: Vec<TeamHierarchyNode>,
+}
+
+/// Member of a team, directly or through a parent team
+#[derive(Serialize, ToSchema)]
+pub struct EffectiveTeamMember {
+    #[serde(flatten)]
+    pub member: TeamMemberInfo,
+    /// Ancestor team the membership comes from; `None` for direct members
+    pub inherited_from: Option<Uuid>,
+}
+
+/// Issues of a team and its sub-teams grouped by workflow state category,
+/// since every team has its own workflow states
+#[derive(Serialize, Clone, ToSchema)]
+pub struct TeamBoard {
+    pub team_id: Uuid,
+    /// Teams whose issues are on the board
+    pub team_ids: Vec<Uuid>,
+    pub columns: Vec<TeamBoardColumn>,
+}
+
+#[derive(Serialize, Clone, ToSchema)]
+pub struct TeamBoardColumn {
+    pub category: String,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TeamBoardQuery {
+    /// Include issues of sub-teams; defaults to true
+    pub include_sub_teams: Option<bool>,
+}
@@ -0,0 +1,75 @@
+use diesel::prelude::*;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Version of the report layout; bumped whenever a field is added or changes
+/// meaning so the receiving end can tell reports apart
+pub const TELEMETRY_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::telemetry_state)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TelemetryStateRecord {
+    pub singleton: bool,
+    /// Random identity of this installation, so reports of one installation
+    /// can be told apart from those of another
+    pub instance_id: Uuid,
+    pub last_sent_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// JSON body of the last report the endpoint accepted
+    pub last_report: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Anonymous usage report: aggregate counts only, never names, emails,
+/// content or ids of workspaces and users
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct TelemetryReport {
+    pub schema_version: u32,
+    pub instance_id: Uuid,
+    /// Server version, e.g. `0.1.0`
+    pub version: String,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    pub counts: UsageCounts,
+    pub feature_adoption: FeatureAdoption,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq, ToSchema)]
+pub struct UsageCounts {
+    pub workspaces: i64,
+    /// Active people; service accounts are left out
+    pub users: i64,
+    pub teams: i64,
+    pub projects: i64,
+    /// Issues that are not archived
+    pub issues: i64,
+    /// Open WebSocket connections on the replica that sent the report
+    pub websocket_connections: i64,
+}
+
+/// Number of workspaces using each feature
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq, ToSchema)]
+pub struct FeatureAdoption {
+    /// With an enabled automation rule
+    pub automations: i64,
+    pub issue_forms: i64,
+    /// With an active webhook
+    pub webhooks: i64,
+    pub github: i64,
+    pub error_tracking: i64,
+    /// With a service account that is not disabled
+    pub service_accounts: i64,
+    pub cycles: i64,
+}
+
+/// What this installation reports and where, served at `/admin/telemetry`
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct TelemetryOverview {
+    /// Whether reports are sent; off unless `TELEMETRY_ENABLED` is set
+    pub enabled: bool,
+    pub endpoint: Option<String>,
+    pub last_sent_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_report: Option<serde_json::Value>,
+    /// Exactly the body the next report would have if it were sent now
+    pub next_report: TelemetryReport,
+}
@@ -0,0 +1,82 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::db::models::attachment::AttachmentResponse;
+
+/// A resumable upload of a large attachment. Chunks are appended at
+/// `upload_offset` as parts of a multipart upload in storage; the chunk that
+/// reaches `size_bytes` completes the upload into `attachment_id`.
+#[derive(Queryable, Selectable, Serialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::upload_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UploadSession {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    pub uploader_id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Path below the `attachments/` prefix, kept by the attachment
+    #[serde(skip)]
+    pub storage_key: String,
+    #[serde(skip)]
+    pub multipart_upload_id: String,
+    pub upload_offset: i64,
+    /// JSON array of [`UploadedPart`]
+    #[serde(skip)]
+    pub parts: String,
+    pub attachment_id: Option<Uuid>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl UploadSession {
+    /// Object key in the bucket, the same as the completed attachment's
+    pub fn object_key(&self) -> String {
+        format!("attachments/{}", self.storage_key)
+    }
+
+    pub fn uploaded_parts(&self) -> Vec<UploadedPart> {
+        serde_json::from_str(&self.parts).unwrap_or_default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UploadedPart {
+    pub part_number: i32,
+    pub etag: String,
+    pub size_bytes: i64,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::upload_sessions)]
+pub struct NewUploadSession {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub issue_id: Uuid,
+    pub uploader_id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub multipart_upload_id: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Returned for every session request. Clients send the next chunk at
+/// `upload_offset`; every chunk but the last must be at least
+/// `chunk_min_bytes`.
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct UploadSessionResponse {
+    #[serde(flatten)]
+    pub session: UploadSession,
+    pub chunk_min_bytes: i64,
+    pub chunk_max_bytes: i64,
+    /// Set once the last chunk has been appended
+    pub attachment: Option<AttachmentResponse>,
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::notification::notification_events;
+
+/// Ways a notification reaches its recipient besides device push, which has
+/// its own preferences
+pub mod notification_channels {
+    /// The notification inbox and its unread count
+    pub const IN_APP: &str = "in_app";
+    /// The periodic email digest
+    pub const EMAIL: &str = "email";
+    /// Live delivery to the recipient's open WebSocket connections
+    pub const WEBSOCKET: &str = "websocket";
+
+    pub const ALL: [&str; 3] = [IN_APP, EMAIL, WEBSOCKET];
+}
+
+pub mod themes {
+    pub const LIGHT: &str = "light";
+    pub const DARK: &str = "dark";
+    /// Follow the operating system
+    pub const SYSTEM: &str = "system";
+
+    pub const ALL: [&str; 3] = [LIGHT, DARK, SYSTEM];
+}
+
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+pub const DEFAULT_LOCALE: &str = "en";
+pub const DEFAULT_THEME: &str = themes::SYSTEM;
+
+#[derive(Queryable, Selectable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::user_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserPreferenceRecord {
+    pub user_id: Uuid,
+    pub timezone: String,
+    pub locale: String,
+    pub theme: String,
+    /// Space-separated `event_type:channel` pairs that are turned off
+    pub muted_channels: String,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Channels one notification type is delivered through
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub struct NotificationChannels {
+    pub in_app: bool,
+    pub email: bool,
+    pub websocket: bool,
+}
+
+impl Default for NotificationChannels {
+    fn default() -> Self {
+        Self {
+            in_app: true,
+            email: true,
+            websocket: true,
+        }
+    }
+}
+
+impl NotificationChannels {
+    pub fn allows(&self, channel: &str) -> bool {
+        match channel {
+            notification_channels::IN_APP => self.in_app,
+            notification_channels::EMAIL => self.email,
+            notification_channels::WEBSOCKET => self.websocket,
+            _ => true,
+        }
+    }
+}
+
+/// A user's settings with the defaults filled in for anything never set
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct UserPreferences {
+    /// IANA time zone name, e.g. `Europe/Berlin`
+    pub timezone: String,
+    /// BCP 47 language tag, e.g. `en` or `pt-BR`
+    pub locale: String,
+    pub theme: String,
+    /// Channels of every notification type
+    pub notifications: BTreeMap<String, NotificationChannels>,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        Self {
+            timezone: DEFAULT_TIMEZONE.to_string(),
+            locale: DEFAULT_LOCALE.to_string(),
+            theme: DEFAULT_THEME.to_string(),
+            notifications: notification_events::ALL
+                .iter()
+                .map(|event| (event.to_string(), NotificationChannels::default()))
+                .collect(),
+        }
+    }
+}
+
+impl UserPreferences {
+    /// Channels of `event_type`; types added later are delivered everywhere
+    pub fn channels(&self, event_type: &str) -> NotificationChannels {
+        self.notifications
+            .get(event_type)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl From<UserPreferenceRecord> for UserPreferences {
+    fn from(record: UserPreferenceRecord) -> Self {
+        let mut notifications = Self::default().notifications;
+        for (event, channel) in record
+            .muted_channels
+            .split_whitespace()
+            .filter_map(|pair| pair.split_once(':'))
+        {
+            if let Some(channels) = notifications.get_mut(event) {
+                match channel {
+                    notification_channels::IN_APP => channels.in_app = false,
+                    notification_channels::EMAIL => channels.email = false,
+                    notification_channels::WEBSOCKET => channels.websocket = false,
+                    _ => {}
+                }
+            }
+        }
+        Self {
+            timezone: record.timezone,
+            locale: record.locale,
+            theme: record.theme,
+            notifications,
+        }
+    }
+}
+
+/// Channel switches of one notification type; channels left out keep their
+/// state
+#[derive(Deserialize, Debug, Clone, Copy, Default, ToSchema)]
+pub struct UpdateNotificationChannels {
+    pub in_app: Option<bool>,
+    pub email: Option<bool>,
+    pub websocket: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateUserPreferencesRequest {
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
+    pub theme: Option<String>,
+    /// By notification type; types left out keep their channels
+    pub notifications: Option<BTreeMap<String, UpdateNotificationChannels>>,
+}
@@ -0,0 +1,103 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A URL registered by a workspace admin to receive workspace events
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::webhooks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Webhook {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub url: String,
+    /// Only returned when the webhook is created or its secret rotated
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// Space-separated event subscriptions
+    #[serde(skip_serializing)]
+    pub events: String,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Webhook {
+    pub fn event_list(&self) -> Vec<String> {
+        self.events.split_whitespace().map(str::to_string).collect()
+    }
+
+    pub fn subscribes_to(&self, event: &str) -> bool {
+        self.events.split_whitespace().any(|e| e == event)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::webhooks)]
+pub struct NewWebhook {
+    pub workspace_id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub events: String,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = crate::schema::webhooks)]
+pub struct UpdateWebhook {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<String>,
+    pub is_active: Option<bool>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub events: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+/// A webhook as returned by the API; `secret` is only set when it was (re)generated
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct WebhookConfig {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub events: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+impl WebhookConfig {
+    pub fn new(webhook: Webhook, secret: Option<String>) -> Self {
+        Self {
+            events: webhook.event_list(),
+            webhook,
+            secret,
+        }
+    }
+}
+
+/// JSON body POSTed to a workspace webhook's URL
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WorkspaceWebhookEnvelope {
+    pub id: Uuid,
+    pub event: String,
+    pub workspace_id: Uuid,
+    pub webhook_id: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub data: serde_json::Value,
+    /// Set on synthetic sample deliveries sent from the test endpoint
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub test: bool,
+}
@@ -0,0 +1,69 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+pub mod dead_letter_status {
+    /// Waiting to be replayed
+    pub const PENDING: &str = "pending";
+    pub const REPLAYED: &str = "replayed";
+    /// The replay failed for a reason retrying will not fix, such as a
+    /// validation error or a permission the user no longer has
+    pub const REJECTED: &str = "rejected";
+    pub const ALL: &[&str] = &[PENDING, REPLAYED, REJECTED];
+}
+
+/// A WebSocket command that failed with a server-side error
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::websocket_dead_letters)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebSocketDeadLetter {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    /// The user who sent the command; replays run as this user
+    pub user_id: Uuid,
+    pub command_type: String,
+    pub request_id: Option<String>,
+    /// The command as received, as JSON
+    pub command: String,
+    /// Code of the latest failure: a [`crate::error::ErrorCode`] for errors
+    /// raised while running it, otherwise the command error code
+    pub error_code: String,
+    pub error_message: String,
+    pub status: String,
+    /// The original attempt plus every replay
+    pub attempts: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Admin who ran the latest replay, whatever its outcome
+    pub replayed_by: Option<Uuid>,
+    pub replayed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::websocket_dead_letters)]
+pub struct NewWebSocketDeadLetter {
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub command_type: String,
+    pub request_id: Option<String>,
+    pub command: String,
+    pub error_code: String,
+    pub error_message: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeadLetterQuery {
+    /// One of [`dead_letter_status`]; all statuses when omitted
+    pub status: Option<String>,
+    pub workspace_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+/// Outcome of replaying a dead letter
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct DeadLetterReplay {
+    pub dead_letter: WebSocketDeadLetter,
+    /// The command response, as a client would have received it
+    pub response: serde_json::Value,
+}
@@ -0,0 +1,93 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Client kinds inferred from the User-Agent when the client does not name itself
+pub mod session_clients {
+    pub const WEB: &str = "web";
+    pub const DESKTOP: &str = "desktop";
+    pub const MOBILE: &str = "mobile";
+    pub const UNKNOWN: &str = "unknown";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::websocket_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebSocketSession {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub connection_id: String,
+    pub client: String,
+    pub user_agent: Option<String>,
+    pub connected_at: chrono::DateTime<chrono::Utc>,
+    pub disconnected_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl WebSocketSession {
+    /// Seconds between connecting and `disconnected_at`, or `now` while open
+    pub fn duration_seconds(&self, now: chrono::DateTime<chrono::Utc>) -> i64 {
+        (self.disconnected_at.unwrap_or(now) - self.connected_at)
+            .num_seconds()
+            .max(0)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::websocket_sessions)]
+pub struct NewWebSocketSession {
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub connection_id: String,
+    pub client: String,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SessionAnalyticsQuery {
+    pub days: Option<i64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct ClientUsage {
+    pub client: String,
+    pub sessions: i64,
+    pub users: i64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct DailySessions {
+    pub day: chrono::NaiveDate,
+    pub sessions: i64,
+    pub active_users: i64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct UserSessionUsage {
+    pub user_id: Uuid,
+    pub sessions: i64,
+    pub total_duration_seconds: i64,
+    pub last_connected_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Realtime usage of a workspace over a window of days
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SessionAnalytics {
+    pub workspace_id: Uuid,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub total_sessions: i64,
+    pub unique_users: i64,
+    /// Sessions still open
+    pub active_sessions: i64,
+    pub total_duration_seconds: i64,
+    pub average_duration_seconds: f64,
+    /// Sessions started in each UTC hour of the day, index 0 is 00:00-00:59
+    pub sessions_by_hour: Vec<i64>,
+    pub clients: Vec<ClientUsage>,
+    pub daily: Vec<DailySessions>,
+    /// Users by total connected time, longest first
+    pub users: Vec<UserSessionUsage>,
+}
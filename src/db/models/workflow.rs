@@ -4,9 +4,10 @@ use diesel::prelude::*;
 use diesel::serialize::{self, Output, ToSql};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel::AsExpression, diesel::FromSqlRow)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel::AsExpression, diesel::FromSqlRow, ToSchema)]
 #[diesel(sql_type = diesel::sql_types::Text)]
 pub enum WorkflowStateCategory {
     Backlog,
@@ -98,7 +99,7 @@ impl FromSql<diesel::sql_types::Text, Pg> for WorkflowStateCategory {
 }
 
 // Workflow models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[diesel(table_name = crate::schema::workflows)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Workflow {
@@ -129,7 +130,7 @@ pub struct UpdateWorkflow {
 }
 
 // WorkflowState models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[diesel(table_name = crate::schema::workflow_states)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct WorkflowState {
@@ -258,7 +259,7 @@ pub struct WorkflowResponse {
     pub states: Vec<WorkflowStateResponse>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, ToSchema)]
 pub struct WorkflowStateResponse {
     pub id: Uuid,
     pub workflow_id: Uuid,
@@ -284,7 +285,7 @@ pub struct WorkflowTransitionResponse {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, ToSchema)]
 pub struct IssueTransitionResponse {
     pub id: Uuid,
     pub workflow_id: Uuid,
@@ -1,9 +1,10 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // Workspace models
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::workspaces)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Workspace {
@@ -13,6 +14,7 @@ pub struct Workspace {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub logo_url: Option<String>,
+    pub organization_id: Option<Uuid>,
 }
 
 impl Workspace {
@@ -36,7 +38,7 @@ pub struct NewWorkspace {
 }
 
 // Workspace API DTOs
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct WorkspaceInfo {
     pub id: Uuid,
     pub name: String,
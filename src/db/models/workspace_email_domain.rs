@@ -0,0 +1,114 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub mod email_domain_status {
+    pub const PENDING: &str = "pending";
+    pub const VERIFIED: &str = "verified";
+    pub const FAILED: &str = "failed";
+}
+
+/// A workspace's own sending domain. Mail is sent from it only while
+/// `status` is `verified`; otherwise the platform sender is used.
+#[derive(Queryable, Selectable, Serialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::workspace_email_domains)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WorkspaceEmailDomain {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub domain: String,
+    /// Display name of the sender; the address alone when `None`
+    pub from_name: Option<String>,
+    pub from_local_part: String,
+    pub dkim_selector: String,
+    /// Value the ownership TXT record must contain
+    pub verification_token: String,
+    pub status: String,
+    /// Why the last verification failed
+    pub last_error: Option<String>,
+    pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_checked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl WorkspaceEmailDomain {
+    pub fn is_verified(&self) -> bool {
+        self.status == email_domain_status::VERIFIED
+    }
+
+    pub fn from_address(&self) -> String {
+        format!("{}@{}", self.from_local_part, self.domain)
+    }
+
+    /// `From` header value, e.g. `Acme <notifications@mail.acme.com>`
+    pub fn sender(&self) -> String {
+        match &self.from_name {
+            Some(name) => format!("{} <{}>", name, self.from_address()),
+            None => self.from_address(),
+        }
+    }
+}
+
+#[derive(Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::workspace_email_domains)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewWorkspaceEmailDomain {
+    pub workspace_id: Uuid,
+    pub domain: String,
+    pub from_name: Option<String>,
+    pub from_local_part: String,
+    pub dkim_selector: String,
+    pub verification_token: String,
+    pub status: String,
+    pub last_error: Option<String>,
+    pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_checked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_by: Option<Uuid>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct SetEmailDomainRequest {
+    pub domain: String,
+    pub from_name: Option<String>,
+    /// Part of the sender address before `@`; defaults to `notifications`
+    pub from_local_part: Option<String>,
+    /// DKIM selector the provider signs with; defaults to `momentum`
+    pub dkim_selector: Option<String>,
+}
+
+/// A DNS record the workspace must publish for its domain to verify
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct EmailDomainDnsRecord {
+    pub record_type: String,
+    pub name: String,
+    /// Expected value, or the part of it that is checked
+    pub value: String,
+    pub purpose: String,
+}
+
+/// Outcome of checking a domain's DNS records
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct EmailDomainCheck {
+    pub ownership_verified: bool,
+    pub dkim_verified: bool,
+    pub error: Option<String>,
+}
+
+impl EmailDomainCheck {
+    pub fn passed(&self) -> bool {
+        self.ownership_verified && self.dkim_verified
+    }
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct EmailDomainResponse {
+    #[serde(flatten)]
+    pub domain: WorkspaceEmailDomain,
+    /// Sender used for the workspace's mail right now
+    pub effective_from: String,
+    pub dns_records: Vec<EmailDomainDnsRecord>,
+}
@@ -0,0 +1,187 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+pub mod workspace_import_status {
+    pub const PENDING: &str = "pending";
+    pub const RUNNING: &str = "running";
+    pub const COMPLETED: &str = "completed";
+}
+
+/// Systems an export can come from
+pub mod import_source {
+    pub const LINEAR: &str = "linear";
+    pub const JIRA: &str = "jira";
+}
+
+/// Kinds of records tracked in `import_external_ids`
+pub mod import_record_type {
+    pub const TEAM: &str = "team";
+    pub const LABEL: &str = "label";
+    pub const PROJECT: &str = "project";
+    pub const ISSUE: &str = "issue";
+    pub const COMMENT: &str = "comment";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::workspace_imports)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WorkspaceImport {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    pub source: String,
+    #[serde(skip)]
+    pub content_hash: String,
+    #[serde(skip)]
+    pub payload: String,
+    pub status: String,
+    pub total_records: i32,
+    /// JSON [`ImportReport`], set once the import completes
+    #[serde(skip)]
+    pub result: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::workspace_imports)]
+pub struct NewWorkspaceImport {
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    pub source: String,
+    pub content_hash: String,
+    pub payload: String,
+    pub total_records: i32,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::import_external_ids)]
+pub struct NewImportExternalId {
+    pub workspace_id: Uuid,
+    pub source: String,
+    pub record_type: String,
+    pub external_id: String,
+    pub entity_id: Uuid,
+}
+
+#[derive(Deserialize, Debug, Clone, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WorkspaceImportQuery {
+    /// `linear` or `jira`
+    pub source: String,
+}
+
+/// An export mapped to this workspace's concepts, independent of its source
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportBundle {
+    pub teams: Vec<ImportTeam>,
+    pub labels: Vec<ImportLabel>,
+    pub projects: Vec<ImportProject>,
+    pub issues: Vec<ImportIssue>,
+}
+
+impl ImportBundle {
+    pub fn record_count(&self) -> usize {
+        self.teams.len()
+            + self.labels.len()
+            + self.projects.len()
+            + self.issues.len()
+            + self.issues.iter().map(|i| i.comments.len()).sum::<usize>()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportTeam {
+    pub external_id: String,
+    pub key: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportLabel {
+    pub external_id: String,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportProject {
+    pub external_id: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportIssue {
+    pub external_id: String,
+    /// Key in the source system, e.g. `ENG-12`; used in conflict reports
+    pub identifier: String,
+    pub team_external_id: String,
+    pub project_external_id: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    /// One of `none`, `low`, `medium`, `high`, `urgent`
+    pub priority: String,
+    pub state_name: Option<String>,
+    /// Workflow state category the source state maps to
+    pub state_category: Option<String>,
+    pub assignee_email: Option<String>,
+    pub label_external_ids: Vec<String>,
+    pub comments: Vec<ImportComment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportComment {
+    pub external_id: String,
+    pub author_email: Option<String>,
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct ImportCounts {
+    pub teams: i32,
+    pub labels: i32,
+    pub projects: i32,
+    pub issues: i32,
+    pub comments: i32,
+}
+
+impl ImportCounts {
+    pub fn bump(&mut self, record_type: &str) {
+        match record_type {
+            import_record_type::TEAM => self.teams += 1,
+            import_record_type::LABEL => self.labels += 1,
+            import_record_type::PROJECT => self.projects += 1,
+            import_record_type::ISSUE => self.issues += 1,
+            import_record_type::COMMENT => self.comments += 1,
+            _ => {}
+        }
+    }
+}
+
+/// A record that was not imported exactly as exported
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct ImportConflict {
+    pub record_type: String,
+    pub external_id: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct ImportReport {
+    pub created: ImportCounts,
+    /// Records matched to existing ones, including those imported before
+    pub matched: ImportCounts,
+    pub conflicts: Vec<ImportConflict>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct WorkspaceImportResponse {
+    #[serde(flatten)]
+    pub import: WorkspaceImport,
+    pub report: Option<ImportReport>,
+}
@@ -1,13 +1,21 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::io::Write;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::schema::workspace_members;
 
 // WorkspaceMemberRole枚举定义
 #[derive(
-    Debug, Clone, PartialEq, Serialize, Deserialize, diesel::FromSqlRow, diesel::AsExpression,
+    Debug,
+    Clone,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    diesel::FromSqlRow,
+    diesel::AsExpression,
+    ToSchema,
 )]
 #[diesel(sql_type = crate::schema::sql_types::WorkspaceUserRole)]
 pub enum WorkspaceMemberRole {
@@ -51,7 +59,7 @@ impl diesel::deserialize::FromSql<crate::schema::sql_types::WorkspaceUserRole, d
 }
 
 // WorkspaceMember模型定义
-#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[diesel(table_name = workspace_members)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct WorkspaceMember {
@@ -75,3 +83,31 @@ pub struct NewWorkspaceMember {
 pub struct UpdateWorkspaceMember {
     pub role: Option<WorkspaceMemberRole>,
 }
+
+/// Kinds of membership change, as recorded in `workspace_member_changes` and
+/// sent as WebSocket event names
+pub mod member_change {
+    pub const MEMBER_ADDED: &str = "member_added";
+    pub const MEMBER_REMOVED: &str = "member_removed";
+    pub const ROLE_CHANGED: &str = "role_changed";
+}
+
+/// One row of the membership change log; `id` doubles as the sync cursor
+#[derive(Queryable, Selectable, Serialize, Clone, Debug)]
+#[diesel(table_name = crate::schema::workspace_member_changes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WorkspaceMemberChange {
+    pub id: i64,
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub change: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Payload of the membership WebSocket events
+#[derive(Serialize, Clone, Debug)]
+pub struct MemberChangeEvent {
+    pub user_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<WorkspaceMemberRole>,
+}
@@ -0,0 +1,70 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::workspace_import::{ImportConflict, ImportCounts};
+
+pub mod workspace_reset_status {
+    pub const PENDING: &str = "pending";
+    pub const RUNNING: &str = "running";
+    pub const COMPLETED: &str = "completed";
+    pub const FAILED: &str = "failed";
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[diesel(table_name = crate::schema::workspace_resets)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WorkspaceReset {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    /// Fixture profile the workspace is reseeded from
+    pub profile: String,
+    pub status: String,
+    /// JSON [`SandboxResetReport`], set once the reset completes
+    #[serde(skip)]
+    pub result: Option<String>,
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::workspace_resets)]
+pub struct NewWorkspaceReset {
+    pub workspace_id: Uuid,
+    pub requested_by: Uuid,
+    pub profile: String,
+}
+
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct WorkspaceResetRequest {
+    pub profile: String,
+}
+
+/// Content deleted by a reset; issues include those of the removed teams
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct SandboxRemovedCounts {
+    pub teams: i64,
+    pub projects: i64,
+    pub labels: i64,
+    pub roadmaps: i64,
+    pub issues: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct SandboxResetReport {
+    pub removed: SandboxRemovedCounts,
+    pub seeded: ImportCounts,
+    /// Fixture records that could not be seeded as written
+    pub conflicts: Vec<ImportConflict>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct WorkspaceResetResponse {
+    #[serde(flatten)]
+    pub reset: WorkspaceReset,
+    pub report: Option<SandboxResetReport>,
+}
@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use chrono::Weekday;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::workspace_member::WorkspaceMemberRole;
+
+pub mod week_start_days {
+    use chrono::Weekday;
+
+    pub const MONDAY: &str = "monday";
+    pub const SUNDAY: &str = "sunday";
+    pub const SATURDAY: &str = "saturday";
+
+    pub const ALL: [&str; 3] = [MONDAY, SUNDAY, SATURDAY];
+
+    pub fn weekday(day: &str) -> Option<Weekday> {
+        match day {
+            MONDAY => Some(Weekday::Mon),
+            SUNDAY => Some(Weekday::Sun),
+            SATURDAY => Some(Weekday::Sat),
+            _ => None,
+        }
+    }
+}
+
+/// Features a workspace can switch off; all are on by default
+pub mod workspace_features {
+    /// Automation rules run on issue changes
+    pub const AUTOMATIONS: &str = "automations";
+    /// Published issue forms accept submissions from outside the workspace
+    pub const PUBLIC_ISSUE_FORMS: &str = "public_issue_forms";
+    /// Members can report comments and issues to the moderation queue
+    pub const CONTENT_REPORTS: &str = "content_reports";
+
+    pub const ALL: [&str; 3] = [AUTOMATIONS, PUBLIC_ISSUE_FORMS, CONTENT_REPORTS];
+}
+
+pub const DEFAULT_WEEK_START_DAY: &str = week_start_days::MONDAY;
+/// Longest wait before completed issues are archived automatically
+pub const MAX_ARCHIVE_COMPLETED_AFTER_DAYS: i32 = 3650;
+
+pub const DEFAULT_ESTIMATE_SCALE: &str = super::team::estimate_scales::FIBONACCI;
+
+#[derive(Queryable, Selectable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::workspace_settings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WorkspaceSettingsRecord {
+    pub workspace_id: Uuid,
+    pub default_workflow_id: Option<Uuid>,
+    pub week_start_day: String,
+    pub estimate_scale: String,
+    /// Space-separated; empty allows any domain
+    pub allowed_email_domains: String,
+    /// JSON object of the toggles changed from their default
+    pub features: String,
+    /// Who changed the settings last; the settings history has every change
+    pub updated_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub archive_completed_after_days: Option<i32>,
+    /// Space-separated; empty disables auto-join
+    pub auto_join_domains: String,
+    pub auto_join_role: WorkspaceMemberRole,
+}
+
+/// Settings of a workspace with the defaults filled in for anything never
+/// set
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct WorkspaceSettings {
+    pub workspace_id: Uuid,
+    /// Workflow new issues of its team are filed in when the request names
+    /// none
+    pub default_workflow_id: Option<Uuid>,
+    pub week_start_day: String,
+    /// Estimate scale new teams start with
+    pub estimate_scale: String,
+    /// Domains invitations may be sent to; empty allows any
+    pub allowed_email_domains: Vec<String>,
+    /// Every known feature and whether it is on
+    pub features: BTreeMap<String, bool>,
+    /// Issues in a completed state and not updated for this many days are
+    /// archived automatically; `None` never archives them
+    pub archive_completed_after_days: Option<i32>,
+    /// Users who register with an address at one of these domains join the
+    /// workspace automatically; empty disables auto-join
+    pub auto_join_domains: Vec<String>,
+    /// Role auto-joined users get, member or guest
+    pub auto_join_role: WorkspaceMemberRole,
+    /// `None` while the workspace uses the defaults
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl WorkspaceSettings {
+    pub fn defaults(workspace_id: Uuid) -> Self {
+        Self {
+            workspace_id,
+            default_workflow_id: None,
+            week_start_day: DEFAULT_WEEK_START_DAY.to_string(),
+            estimate_scale: DEFAULT_ESTIMATE_SCALE.to_string(),
+            allowed_email_domains: Vec::new(),
+            features: workspace_features::ALL
+                .iter()
+                .map(|feature| (feature.to_string(), true))
+                .collect(),
+            archive_completed_after_days: None,
+            auto_join_domains: Vec::new(),
+            auto_join_role: WorkspaceMemberRole::Member,
+            updated_at: None,
+        }
+    }
+
+    pub fn week_starts_on(&self) -> Weekday {
+        week_start_days::weekday(&self.week_start_day).unwrap_or(Weekday::Mon)
+    }
+
+    pub fn feature_enabled(&self, feature: &str) -> bool {
+        self.features.get(feature).copied().unwrap_or(true)
+    }
+
+    /// Whether invitations may be sent to `email`
+    pub fn allows_email(&self, email: &str) -> bool {
+        self.allowed_email_domains.is_empty()
+            || self.allowed_email_domains.contains(&email_domain(email))
+    }
+
+    /// Whether a user registering with `email` joins the workspace
+    pub fn auto_joins_email(&self, email: &str) -> bool {
+        self.auto_join_domains.contains(&email_domain(email))
+    }
+}
+
+/// Lowercased domain of an address; empty when it has none
+pub fn email_domain(email: &str) -> String {
+    email
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.trim().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct UpdateWorkspaceSettingsRequest {
+    /// Pass null to stop filing issues in a default workflow
+    #[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]
+    pub default_workflow_id: Option<Option<Uuid>>,
+    pub week_start_day: Option<String>,
+    pub estimate_scale: Option<String>,
+    /// Replaces the list; an empty list allows any domain
+    pub allowed_email_domains: Option<Vec<String>>,
+    /// Toggles to change; features left out keep their state
+    pub features: Option<BTreeMap<String, bool>>,
+    /// Pass null to stop archiving completed issues automatically
+    #[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]
+    pub archive_completed_after_days: Option<Option<i32>>,
+    /// Replaces the list; an empty list disables auto-join
+    pub auto_join_domains: Option<Vec<String>>,
+    pub auto_join_role: Option<WorkspaceMemberRole>,
+}
@@ -0,0 +1,94 @@
+use diesel::prelude::*;
+
+use crate::db::models::api_key::{ApiKeyChanges, ApiKeyRecord, NewApiKey};
+
+pub struct ApiKeysRepo;
+
+impl ApiKeysRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewApiKey,
+    ) -> Result<ApiKeyRecord, diesel::result::Error> {
+        use crate::schema::api_keys::dsl as k;
+        diesel::insert_into(k::api_keys)
+            .values(new)
+            .returning(ApiKeyRecord::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Vec<ApiKeyRecord>, diesel::result::Error> {
+        use crate::schema::api_keys::dsl as k;
+        k::api_keys
+            .filter(k::workspace_id.eq(workspace))
+            .order(k::created_at.desc())
+            .select(ApiKeyRecord::as_select())
+            .load::<ApiKeyRecord>(conn)
+    }
+
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        key_id: uuid::Uuid,
+    ) -> Result<Option<ApiKeyRecord>, diesel::result::Error> {
+        use crate::schema::api_keys::dsl as k;
+        k::api_keys
+            .filter(k::id.eq(key_id))
+            .filter(k::workspace_id.eq(workspace))
+            .select(ApiKeyRecord::as_select())
+            .first::<ApiKeyRecord>(conn)
+            .optional()
+    }
+
+    pub fn find_by_hash(
+        conn: &mut PgConnection,
+        hash: &str,
+    ) -> Result<Option<ApiKeyRecord>, diesel::result::Error> {
+        use crate::schema::api_keys::dsl as k;
+        k::api_keys
+            .filter(k::key_hash.eq(hash))
+            .select(ApiKeyRecord::as_select())
+            .first::<ApiKeyRecord>(conn)
+            .optional()
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        key_id: uuid::Uuid,
+        changes: &ApiKeyChanges,
+    ) -> Result<ApiKeyRecord, diesel::result::Error> {
+        use crate::schema::api_keys::dsl as k;
+        diesel::update(k::api_keys.filter(k::id.eq(key_id)))
+            .set(changes)
+            .returning(ApiKeyRecord::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn revoke(
+        conn: &mut PgConnection,
+        key_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::api_keys::dsl as k;
+        diesel::update(
+            k::api_keys
+                .filter(k::id.eq(key_id))
+                .filter(k::revoked_at.is_null()),
+        )
+        .set((k::revoked_at.eq(Some(at)), k::updated_at.eq(at)))
+        .execute(conn)
+    }
+
+    pub fn touch_last_used(
+        conn: &mut PgConnection,
+        key_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::api_keys::dsl as k;
+        diesel::update(k::api_keys.filter(k::id.eq(key_id)))
+            .set(k::last_used_at.eq(Some(at)))
+            .execute(conn)
+    }
+}
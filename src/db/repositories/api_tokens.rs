@@ -0,0 +1,188 @@
+use diesel::prelude::*;
+
+use crate::db::models::api_token::{ApiToken, ApiUsageDaily, NewApiToken, api_client_types};
+
+pub struct ApiTokensRepo;
+
+impl ApiTokensRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewApiToken,
+    ) -> Result<ApiToken, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        diesel::insert_into(t::api_tokens)
+            .values(new)
+            .returning(ApiToken::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_by_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+    ) -> Result<Vec<ApiToken>, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        t::api_tokens
+            .filter(t::user_id.eq(user))
+            .order(t::created_at.desc())
+            .select(ApiToken::as_select())
+            .load::<ApiToken>(conn)
+    }
+
+    pub fn find_for_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+        token_id: uuid::Uuid,
+    ) -> Result<Option<ApiToken>, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        t::api_tokens
+            .filter(t::id.eq(token_id))
+            .filter(t::user_id.eq(user))
+            .select(ApiToken::as_select())
+            .first::<ApiToken>(conn)
+            .optional()
+    }
+
+    pub fn find_by_hash(
+        conn: &mut PgConnection,
+        hash: &str,
+    ) -> Result<Option<ApiToken>, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        t::api_tokens
+            .filter(t::token_hash.eq(hash))
+            .select(ApiToken::as_select())
+            .first::<ApiToken>(conn)
+            .optional()
+    }
+
+    pub fn revoke(
+        conn: &mut PgConnection,
+        token_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        diesel::update(
+            t::api_tokens
+                .filter(t::id.eq(token_id))
+                .filter(t::revoked_at.is_null()),
+        )
+        .set(t::revoked_at.eq(Some(at)))
+        .execute(conn)
+    }
+
+    /// Revoke every active token of a user
+    pub fn revoke_all_for_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        diesel::update(
+            t::api_tokens
+                .filter(t::user_id.eq(user))
+                .filter(t::revoked_at.is_null()),
+        )
+        .set(t::revoked_at.eq(Some(at)))
+        .execute(conn)
+    }
+
+    pub fn touch_last_used(
+        conn: &mut PgConnection,
+        token_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        diesel::update(t::api_tokens.filter(t::id.eq(token_id)))
+            .set(t::last_used_at.eq(Some(at)))
+            .execute(conn)
+    }
+}
+
+pub struct ApiUsageRepo;
+
+impl ApiUsageRepo {
+    /// Count one request against the client's daily rollup for an endpoint
+    pub fn record(
+        conn: &mut PgConnection,
+        client_type: &str,
+        client_id: uuid::Uuid,
+        day: chrono::NaiveDate,
+        endpoint: &str,
+        is_error: bool,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::api_usage_daily::dsl as u;
+        let errors = i64::from(is_error);
+        diesel::insert_into(u::api_usage_daily)
+            .values((
+                u::client_type.eq(client_type),
+                u::client_id.eq(client_id),
+                u::day.eq(day),
+                u::endpoint.eq(endpoint),
+                u::request_count.eq(1i64),
+                u::error_count.eq(errors),
+            ))
+            .on_conflict((u::client_type, u::client_id, u::day, u::endpoint))
+            .do_update()
+            .set((
+                u::request_count.eq(u::request_count + 1i64),
+                u::error_count.eq(u::error_count + errors),
+            ))
+            .execute(conn)
+    }
+
+    /// Total requests made by the client on a day, across endpoints
+    pub fn requests_on(
+        conn: &mut PgConnection,
+        client_type: &str,
+        client_id: uuid::Uuid,
+        day: chrono::NaiveDate,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::api_usage_daily::dsl as u;
+        // One row per endpoint, so summing client-side stays cheap
+        let counts = u::api_usage_daily
+            .filter(u::client_type.eq(client_type))
+            .filter(u::client_id.eq(client_id))
+            .filter(u::day.eq(day))
+            .select(u::request_count)
+            .load::<i64>(conn)?;
+        Ok(counts.into_iter().sum())
+    }
+
+    /// Total requests made on a day through any of the user's personal
+    /// tokens of the tier, revoked ones included
+    pub fn user_token_requests_on(
+        conn: &mut PgConnection,
+        user_id: uuid::Uuid,
+        tier: &str,
+        day: chrono::NaiveDate,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl as t;
+        use crate::schema::api_usage_daily::dsl as u;
+        let tokens = t::api_tokens
+            .filter(t::user_id.eq(user_id))
+            .filter(t::tier.eq(tier))
+            .select(t::id);
+        let counts = u::api_usage_daily
+            .filter(u::client_type.eq(api_client_types::PERSONAL_TOKEN))
+            .filter(u::client_id.eq_any(tokens))
+            .filter(u::day.eq(day))
+            .select(u::request_count)
+            .load::<i64>(conn)?;
+        Ok(counts.into_iter().sum())
+    }
+
+    pub fn list_since(
+        conn: &mut PgConnection,
+        client_type: &str,
+        client_id: uuid::Uuid,
+        since: chrono::NaiveDate,
+    ) -> Result<Vec<ApiUsageDaily>, diesel::result::Error> {
+        use crate::schema::api_usage_daily::dsl as u;
+        u::api_usage_daily
+            .filter(u::client_type.eq(client_type))
+            .filter(u::client_id.eq(client_id))
+            .filter(u::day.ge(since))
+            .order((u::day.asc(), u::endpoint.asc()))
+            .select(ApiUsageDaily::as_select())
+            .load::<ApiUsageDaily>(conn)
+    }
+}
@@ -0,0 +1,284 @@
+use diesel::prelude::*;
+
+use crate::db::models::app_installation::{
+    AppInstallation, NewWebhookDelivery, NewWebhookSigningKey, WebhookDelivery,
+    WebhookDeliveryAttempt, WebhookSigningKey, webhook_delivery_status,
+};
+use crate::db::models::oauth_app::OAuthApp;
+
+pub struct AppInstallationsRepo;
+
+impl AppInstallationsRepo {
+    /// Install an app in a workspace, or reinstall it with new scopes
+    pub fn upsert(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        ws_id: uuid::Uuid,
+        user: uuid::Uuid,
+        granted_scopes: &str,
+    ) -> Result<AppInstallation, diesel::result::Error> {
+        use crate::schema::app_installations::dsl as i;
+        let now = chrono::Utc::now();
+        diesel::insert_into(i::app_installations)
+            .values((
+                i::app_id.eq(app),
+                i::workspace_id.eq(ws_id),
+                i::installed_by.eq(Some(user)),
+                i::scopes.eq(granted_scopes),
+            ))
+            .on_conflict((i::app_id, i::workspace_id))
+            .do_update()
+            .set((
+                i::installed_by.eq(Some(user)),
+                i::scopes.eq(granted_scopes),
+                i::uninstalled_at.eq(None::<chrono::DateTime<chrono::Utc>>),
+                i::updated_at.eq(now),
+            ))
+            .returning(AppInstallation::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_for_app(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        ws_id: uuid::Uuid,
+    ) -> Result<Option<AppInstallation>, diesel::result::Error> {
+        use crate::schema::app_installations::dsl as i;
+        i::app_installations
+            .filter(i::app_id.eq(app))
+            .filter(i::workspace_id.eq(ws_id))
+            .select(AppInstallation::as_select())
+            .first::<AppInstallation>(conn)
+            .optional()
+    }
+
+    /// An active installation in the workspace, together with its app
+    pub fn find_active(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        installation_id: uuid::Uuid,
+    ) -> Result<Option<(AppInstallation, OAuthApp)>, diesel::result::Error> {
+        use crate::schema::app_installations::dsl as i;
+        use crate::schema::oauth_apps;
+        i::app_installations
+            .inner_join(oauth_apps::table)
+            .filter(i::id.eq(installation_id))
+            .filter(i::workspace_id.eq(ws_id))
+            .filter(i::uninstalled_at.is_null())
+            .select((AppInstallation::as_select(), OAuthApp::as_select()))
+            .first::<(AppInstallation, OAuthApp)>(conn)
+            .optional()
+    }
+
+    /// Active installations of enabled apps in a workspace
+    pub fn list_active_by_workspace(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<Vec<(AppInstallation, OAuthApp)>, diesel::result::Error> {
+        use crate::schema::app_installations::dsl as i;
+        use crate::schema::oauth_apps;
+        i::app_installations
+            .inner_join(oauth_apps::table)
+            .filter(i::workspace_id.eq(ws_id))
+            .filter(i::uninstalled_at.is_null())
+            .filter(oauth_apps::disabled_at.is_null())
+            .order(i::created_at.asc())
+            .select((AppInstallation::as_select(), OAuthApp::as_select()))
+            .load::<(AppInstallation, OAuthApp)>(conn)
+    }
+
+    pub fn update_scopes(
+        conn: &mut PgConnection,
+        installation_id: uuid::Uuid,
+        granted_scopes: &str,
+    ) -> Result<AppInstallation, diesel::result::Error> {
+        use crate::schema::app_installations::dsl as i;
+        diesel::update(i::app_installations.filter(i::id.eq(installation_id)))
+            .set((
+                i::scopes.eq(granted_scopes),
+                i::updated_at.eq(chrono::Utc::now()),
+            ))
+            .returning(AppInstallation::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn uninstall(
+        conn: &mut PgConnection,
+        installation_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::app_installations::dsl as i;
+        diesel::update(
+            i::app_installations
+                .filter(i::id.eq(installation_id))
+                .filter(i::uninstalled_at.is_null()),
+        )
+        .set((i::uninstalled_at.eq(Some(at)), i::updated_at.eq(at)))
+        .execute(conn)
+    }
+
+    /// Uninstall an app everywhere, e.g. when it is deleted
+    pub fn uninstall_all_for_app(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::app_installations::dsl as i;
+        diesel::update(
+            i::app_installations
+                .filter(i::app_id.eq(app))
+                .filter(i::uninstalled_at.is_null()),
+        )
+        .set((i::uninstalled_at.eq(Some(at)), i::updated_at.eq(at)))
+        .execute(conn)
+    }
+}
+
+pub struct WebhookDeliveriesRepo;
+
+impl WebhookDeliveriesRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewWebhookDelivery,
+    ) -> Result<WebhookDelivery, diesel::result::Error> {
+        use crate::schema::webhook_deliveries::dsl as d;
+        diesel::insert_into(d::webhook_deliveries)
+            .values(new)
+            .returning(WebhookDelivery::as_returning())
+            .get_result(conn)
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first
+    pub fn list_due(
+        conn: &mut PgConnection,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, diesel::result::Error> {
+        use crate::schema::webhook_deliveries::dsl as d;
+        d::webhook_deliveries
+            .filter(d::status.eq(webhook_delivery_status::PENDING))
+            .filter(d::next_attempt_at.le(now))
+            .order(d::next_attempt_at.asc())
+            .limit(limit)
+            .select(WebhookDelivery::as_select())
+            .load::<WebhookDelivery>(conn)
+    }
+
+    pub fn list_by_app(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, diesel::result::Error> {
+        use crate::schema::webhook_deliveries::dsl as d;
+        d::webhook_deliveries
+            .filter(d::app_id.eq(app))
+            .order(d::created_at.desc())
+            .limit(limit)
+            .select(WebhookDelivery::as_select())
+            .load::<WebhookDelivery>(conn)
+    }
+
+    pub fn list_by_webhook(
+        conn: &mut PgConnection,
+        webhook: uuid::Uuid,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, diesel::result::Error> {
+        use crate::schema::webhook_deliveries::dsl as d;
+        d::webhook_deliveries
+            .filter(d::webhook_id.eq(webhook))
+            .order(d::created_at.desc())
+            .limit(limit)
+            .select(WebhookDelivery::as_select())
+            .load::<WebhookDelivery>(conn)
+    }
+
+    pub fn find_for_app(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        delivery_id: uuid::Uuid,
+    ) -> Result<Option<WebhookDelivery>, diesel::result::Error> {
+        use crate::schema::webhook_deliveries::dsl as d;
+        d::webhook_deliveries
+            .filter(d::id.eq(delivery_id))
+            .filter(d::app_id.eq(app))
+            .select(WebhookDelivery::as_select())
+            .first::<WebhookDelivery>(conn)
+            .optional()
+    }
+
+    pub fn find_for_webhook(
+        conn: &mut PgConnection,
+        webhook: uuid::Uuid,
+        delivery_id: uuid::Uuid,
+    ) -> Result<Option<WebhookDelivery>, diesel::result::Error> {
+        use crate::schema::webhook_deliveries::dsl as d;
+        d::webhook_deliveries
+            .filter(d::id.eq(delivery_id))
+            .filter(d::webhook_id.eq(webhook))
+            .select(WebhookDelivery::as_select())
+            .first::<WebhookDelivery>(conn)
+            .optional()
+    }
+
+    /// Store the outcome of an attempt
+    pub fn record_attempt(
+        conn: &mut PgConnection,
+        delivery_id: uuid::Uuid,
+        attempt: &WebhookDeliveryAttempt,
+    ) -> Result<WebhookDelivery, diesel::result::Error> {
+        use crate::schema::webhook_deliveries::dsl as d;
+        diesel::update(d::webhook_deliveries.filter(d::id.eq(delivery_id)))
+            .set(attempt)
+            .returning(WebhookDelivery::as_returning())
+            .get_result(conn)
+    }
+}
+
+pub struct WebhookSigningKeysRepo;
+
+impl WebhookSigningKeysRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewWebhookSigningKey,
+    ) -> Result<WebhookSigningKey, diesel::result::Error> {
+        use crate::schema::webhook_signing_keys::dsl as k;
+        diesel::insert_into(k::webhook_signing_keys)
+            .values(new)
+            .returning(WebhookSigningKey::as_returning())
+            .get_result(conn)
+    }
+
+    /// Keys that still sign deliveries at `now`, newest first
+    pub fn list_active(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<WebhookSigningKey>, diesel::result::Error> {
+        use crate::schema::webhook_signing_keys::dsl as k;
+        k::webhook_signing_keys
+            .filter(k::app_id.eq(app))
+            .filter(k::expires_at.is_null().or(k::expires_at.gt(now)))
+            .order(k::created_at.desc())
+            .select(WebhookSigningKey::as_select())
+            .load::<WebhookSigningKey>(conn)
+    }
+
+    /// Retire every other active key of the app at `at`; keys already due to
+    /// expire earlier keep their deadline
+    pub fn expire_others(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        keep: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::webhook_signing_keys::dsl as k;
+        diesel::update(
+            k::webhook_signing_keys
+                .filter(k::app_id.eq(app))
+                .filter(k::id.ne(keep))
+                .filter(k::expires_at.is_null().or(k::expires_at.gt(at))),
+        )
+        .set(k::expires_at.eq(at))
+        .execute(conn)
+    }
+}
@@ -0,0 +1,73 @@
+use diesel::prelude::*;
+
+use crate::db::models::attachment::{Attachment, NewAttachment, attachment_status};
+
+pub struct AttachmentsRepo;
+
+impl AttachmentsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_attachment: &NewAttachment,
+    ) -> Result<Attachment, diesel::result::Error> {
+        diesel::insert_into(crate::schema::attachments::table)
+            .values(new_attachment)
+            .returning(Attachment::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_for_issue(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        issue: uuid::Uuid,
+        attachment_id: uuid::Uuid,
+    ) -> Result<Option<Attachment>, diesel::result::Error> {
+        use crate::schema::attachments::dsl as a;
+        a::attachments
+            .filter(a::id.eq(attachment_id))
+            .filter(a::issue_id.eq(issue))
+            .filter(a::workspace_id.eq(ws_id))
+            .select(Attachment::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Attachments whose upload has completed, oldest first
+    pub fn list_uploaded_by_issue(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        issue: uuid::Uuid,
+    ) -> Result<Vec<Attachment>, diesel::result::Error> {
+        use crate::schema::attachments::dsl as a;
+        a::attachments
+            .filter(a::issue_id.eq(issue))
+            .filter(a::workspace_id.eq(ws_id))
+            .filter(a::status.eq(attachment_status::UPLOADED))
+            .order(a::created_at.asc())
+            .select(Attachment::as_select())
+            .load(conn)
+    }
+
+    pub fn mark_uploaded(
+        conn: &mut PgConnection,
+        attachment_id: uuid::Uuid,
+        actual_size: i64,
+    ) -> Result<Attachment, diesel::result::Error> {
+        use crate::schema::attachments::dsl as a;
+        diesel::update(a::attachments.filter(a::id.eq(attachment_id)))
+            .set((
+                a::status.eq(attachment_status::UPLOADED),
+                a::size_bytes.eq(actual_size),
+                a::uploaded_at.eq(Some(chrono::Utc::now())),
+            ))
+            .returning(Attachment::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        attachment_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::attachments::dsl as a;
+        diesel::delete(a::attachments.filter(a::id.eq(attachment_id))).execute(conn)
+    }
+}
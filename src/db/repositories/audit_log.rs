@@ -0,0 +1,58 @@
+use diesel::prelude::*;
+
+use crate::db::models::audit::{AuditEntry, NewAuditEntry};
+
+pub struct AuditLogRepo;
+
+impl AuditLogRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        entry: &NewAuditEntry,
+    ) -> Result<AuditEntry, diesel::result::Error> {
+        diesel::insert_into(crate::schema::audit_log::table)
+            .values(entry)
+            .returning(AuditEntry::as_returning())
+            .get_result(conn)
+    }
+
+    /// Entries of a workspace matching `filter`, newest first
+    pub fn list(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        filter: &AuditLogFilter,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, diesel::result::Error> {
+        use crate::schema::audit_log::dsl as a;
+        let mut query = a::audit_log
+            .filter(a::workspace_id.eq(workspace))
+            .filter(a::target_type.eq_any(filter.target_types))
+            .into_boxed();
+        if let Some(target_id) = filter.target_id {
+            query = query.filter(a::target_id.eq(target_id));
+        }
+        if let Some(actor_id) = filter.actor_id {
+            query = query.filter(a::actor_id.eq(actor_id));
+        }
+        if let Some(since) = filter.since {
+            query = query.filter(a::created_at.ge(since));
+        }
+        if let Some(before) = filter.before {
+            query = query.filter(a::created_at.lt(before));
+        }
+        query
+            .order((a::created_at.desc(), a::id.desc()))
+            .limit(limit)
+            .select(AuditEntry::as_select())
+            .load(conn)
+    }
+}
+
+/// Filters for [`AuditLogRepo::list`]
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter<'a> {
+    pub target_types: &'a [&'a str],
+    pub target_id: Option<uuid::Uuid>,
+    pub actor_id: Option<uuid::Uuid>,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub before: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -35,6 +35,14 @@ impl AuthRepo {
         users.filter(id.eq(user_id)).first::<User>(conn).optional()
     }
 
+    pub fn list_by_ids(
+        conn: &mut PgConnection,
+        user_ids: &[uuid::Uuid],
+    ) -> Result<Vec<User>, diesel::result::Error> {
+        use crate::schema::users::dsl::*;
+        users.filter(id.eq_any(user_ids)).load::<User>(conn)
+    }
+
     pub fn exists_by_email(
         conn: &mut PgConnection,
         target_email: &str,
@@ -83,6 +91,20 @@ impl AuthRepo {
             .optional()
     }
 
+    pub fn update_credential_hash(
+        conn: &mut PgConnection,
+        credential_id: i32,
+        new_hash: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::user_credentials::dsl::*;
+        diesel::update(user_credentials.filter(id.eq(credential_id)))
+            .set((
+                credential_hash.eq(Some(new_hash)),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .execute(conn)
+    }
+
     pub fn update_user_fields(
         conn: &mut PgConnection,
         user_id: uuid::Uuid,
@@ -159,6 +181,17 @@ impl AuthRepo {
         users.filter(id.eq(user_id)).first::<User>(conn)
     }
 
+    pub fn set_asset_region(
+        conn: &mut PgConnection,
+        target_user_id: uuid::Uuid,
+        region: Option<&str>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::users::dsl::*;
+        diesel::update(users.filter(id.eq(target_user_id)))
+            .set(asset_region.eq(region))
+            .execute(conn)
+    }
+
     pub fn update_current_workspace(
         conn: &mut PgConnection,
         target_user_id: uuid::Uuid,
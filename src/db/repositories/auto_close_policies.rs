@@ -0,0 +1,145 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+
+use crate::db::models::auto_close::{NewTeamAutoClosePolicy, TeamAutoClosePolicy};
+use crate::db::models::issue::Issue;
+
+pub struct AutoClosePoliciesRepo;
+
+impl AutoClosePoliciesRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Option<TeamAutoClosePolicy>, diesel::result::Error> {
+        use crate::schema::team_auto_close_policies::dsl as p;
+        p::team_auto_close_policies
+            .filter(p::team_id.eq(team))
+            .select(TeamAutoClosePolicy::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn upsert(
+        conn: &mut PgConnection,
+        policy: &NewTeamAutoClosePolicy,
+    ) -> Result<TeamAutoClosePolicy, diesel::result::Error> {
+        use crate::schema::team_auto_close_policies::dsl as p;
+        diesel::insert_into(p::team_auto_close_policies)
+            .values(policy)
+            .on_conflict(p::team_id)
+            .do_update()
+            .set((policy, p::updated_at.eq(chrono::Utc::now())))
+            .returning(TeamAutoClosePolicy::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::team_auto_close_policies::dsl as p;
+        diesel::delete(p::team_auto_close_policies.filter(p::team_id.eq(team))).execute(conn)
+    }
+
+    /// Enabled policies with their team's workspace id
+    pub fn list_enabled(
+        conn: &mut PgConnection,
+    ) -> Result<Vec<(TeamAutoClosePolicy, uuid::Uuid)>, diesel::result::Error> {
+        use crate::schema::team_auto_close_policies::dsl as p;
+        use crate::schema::teams::dsl as t;
+        p::team_auto_close_policies
+            .inner_join(t::teams)
+            .filter(p::enabled.eq(true))
+            .select((TeamAutoClosePolicy::as_select(), t::workspace_id))
+            .load(conn)
+    }
+
+    /// Issues of the team in the watched states, excluding those carrying the
+    /// exempt label
+    pub fn list_candidates(
+        conn: &mut PgConnection,
+        policy: &TeamAutoClosePolicy,
+    ) -> Result<Vec<Issue>, diesel::result::Error> {
+        use crate::schema::issue_labels::dsl as il;
+        use crate::schema::issues::dsl as i;
+        let mut query = i::issues
+            .filter(i::team_id.eq(policy.team_id))
+            .filter(i::workflow_state_id.eq_any(policy.state_id_list()))
+            .filter(i::archived_at.is_null())
+            .select(Issue::as_select())
+            .into_boxed();
+        if let Some(label) = policy.exempt_label_id {
+            query = query.filter(diesel::dsl::not(diesel::dsl::exists(
+                il::issue_labels
+                    .filter(il::issue_id.eq(i::id))
+                    .filter(il::label_id.eq(label)),
+            )));
+        }
+        query.load(conn)
+    }
+
+    /// Latest non-deleted, visible comment time per issue
+    pub fn last_comment_at(
+        conn: &mut PgConnection,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<HashMap<uuid::Uuid, chrono::DateTime<chrono::Utc>>, diesel::result::Error> {
+        use crate::schema::comments::dsl as c;
+        let rows: Vec<(uuid::Uuid, Option<chrono::DateTime<chrono::Utc>>)> = c::comments
+            .filter(c::issue_id.eq_any(issue_ids))
+            .filter(c::is_deleted.is_distinct_from(true))
+            .filter(c::hidden_at.is_null())
+            .group_by(c::issue_id)
+            .select((c::issue_id, diesel::dsl::max(c::created_at)))
+            .load(conn)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(issue, at)| at.map(|at| (issue, at)))
+            .collect())
+    }
+
+    pub fn warnings_for(
+        conn: &mut PgConnection,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<HashMap<uuid::Uuid, chrono::DateTime<chrono::Utc>>, diesel::result::Error> {
+        use crate::schema::issue_auto_close_warnings::dsl as w;
+        let rows: Vec<(uuid::Uuid, chrono::DateTime<chrono::Utc>)> = w::issue_auto_close_warnings
+            .filter(w::issue_id.eq_any(issue_ids))
+            .select((w::issue_id, w::warned_at))
+            .load(conn)?;
+        Ok(rows.into_iter().collect())
+    }
+
+    pub fn record_warning(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), diesel::result::Error> {
+        use crate::schema::issue_auto_close_warnings::dsl as w;
+        diesel::insert_into(w::issue_auto_close_warnings)
+            .values((w::issue_id.eq(issue), w::warned_at.eq(at)))
+            .on_conflict(w::issue_id)
+            .do_update()
+            .set(w::warned_at.eq(at))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Move the issue to the close state and drop its warning
+    pub fn close_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+        state: uuid::Uuid,
+    ) -> Result<Issue, diesel::result::Error> {
+        use crate::schema::issue_auto_close_warnings::dsl as w;
+        use crate::schema::issues::dsl as i;
+        conn.transaction(|conn| {
+            let closed = diesel::update(i::issues.filter(i::id.eq(issue)))
+                .set(i::workflow_state_id.eq(state))
+                .returning(Issue::as_returning())
+                .get_result(conn)?;
+            diesel::delete(w::issue_auto_close_warnings.filter(w::issue_id.eq(issue)))
+                .execute(conn)?;
+            Ok(closed)
+        })
+    }
+}
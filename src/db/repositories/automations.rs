@@ -0,0 +1,153 @@
+use diesel::prelude::*;
+
+use crate::db::models::automation::{
+    AutomationRule, AutomationRun, AutomationRunOutcome, NewAutomationRule, NewAutomationRun,
+    UpdateAutomationRule, automation_run_status,
+};
+
+pub struct AutomationRulesRepo;
+
+impl AutomationRulesRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_rule: &NewAutomationRule,
+    ) -> Result<AutomationRule, diesel::result::Error> {
+        diesel::insert_into(crate::schema::automation_rules::table)
+            .values(new_rule)
+            .returning(AutomationRule::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Vec<AutomationRule>, diesel::result::Error> {
+        use crate::schema::automation_rules::dsl as r;
+        r::automation_rules
+            .filter(r::workspace_id.eq(workspace))
+            .select(AutomationRule::as_select())
+            .order((r::created_at.asc(), r::id.asc()))
+            .load(conn)
+    }
+
+    /// Enabled rules for `trigger` that cover issues of `team`, oldest first
+    pub fn list_enabled_for(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        team: uuid::Uuid,
+        trigger_name: &str,
+    ) -> Result<Vec<AutomationRule>, diesel::result::Error> {
+        use crate::schema::automation_rules::dsl as r;
+        r::automation_rules
+            .filter(r::workspace_id.eq(workspace))
+            .filter(r::trigger.eq(trigger_name))
+            .filter(r::enabled.eq(true))
+            .filter(r::team_id.is_null().or(r::team_id.eq(team)))
+            .select(AutomationRule::as_select())
+            .order((r::created_at.asc(), r::id.asc()))
+            .load(conn)
+    }
+
+    pub fn find(
+        conn: &mut PgConnection,
+        rule: uuid::Uuid,
+    ) -> Result<Option<AutomationRule>, diesel::result::Error> {
+        use crate::schema::automation_rules::dsl as r;
+        r::automation_rules
+            .filter(r::id.eq(rule))
+            .select(AutomationRule::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        rule: uuid::Uuid,
+    ) -> Result<Option<AutomationRule>, diesel::result::Error> {
+        use crate::schema::automation_rules::dsl as r;
+        r::automation_rules
+            .filter(r::id.eq(rule))
+            .filter(r::workspace_id.eq(workspace))
+            .select(AutomationRule::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        rule: uuid::Uuid,
+        changes: &UpdateAutomationRule,
+    ) -> Result<AutomationRule, diesel::result::Error> {
+        use crate::schema::automation_rules::dsl as r;
+        diesel::update(r::automation_rules.filter(r::id.eq(rule)))
+            .set(changes)
+            .returning(AutomationRule::as_returning())
+            .get_result(conn)
+    }
+
+    /// Delete a rule together with its runs
+    pub fn delete(
+        conn: &mut PgConnection,
+        rule: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::automation_rules::dsl as r;
+        diesel::delete(r::automation_rules.filter(r::id.eq(rule))).execute(conn)
+    }
+}
+
+pub struct AutomationRunsRepo;
+
+impl AutomationRunsRepo {
+    pub fn insert_many(
+        conn: &mut PgConnection,
+        runs: &[NewAutomationRun],
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::insert_into(crate::schema::automation_runs::table)
+            .values(runs)
+            .execute(conn)
+    }
+
+    /// Pending runs whose next attempt is due, oldest first
+    pub fn list_due(
+        conn: &mut PgConnection,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<AutomationRun>, diesel::result::Error> {
+        use crate::schema::automation_runs::dsl as a;
+        a::automation_runs
+            .filter(a::status.eq(automation_run_status::PENDING))
+            .filter(a::next_attempt_at.le(now))
+            .order((a::next_attempt_at.asc(), a::created_at.asc()))
+            .limit(limit)
+            .select(AutomationRun::as_select())
+            .load(conn)
+    }
+
+    /// Most recent runs of a rule first
+    pub fn list_by_rule(
+        conn: &mut PgConnection,
+        rule: uuid::Uuid,
+        limit: i64,
+    ) -> Result<Vec<AutomationRun>, diesel::result::Error> {
+        use crate::schema::automation_runs::dsl as a;
+        a::automation_runs
+            .filter(a::rule_id.eq(rule))
+            .order(a::created_at.desc())
+            .limit(limit)
+            .select(AutomationRun::as_select())
+            .load(conn)
+    }
+
+    pub fn record(
+        conn: &mut PgConnection,
+        run: uuid::Uuid,
+        outcome: &AutomationRunOutcome,
+    ) -> Result<AutomationRun, diesel::result::Error> {
+        use crate::schema::automation_runs::dsl as a;
+        diesel::update(a::automation_runs.filter(a::id.eq(run)))
+            .set(outcome)
+            .returning(AutomationRun::as_returning())
+            .get_result(conn)
+    }
+}
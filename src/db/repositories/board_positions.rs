@@ -0,0 +1,83 @@
+use diesel::prelude::*;
+
+use crate::db::models::board::IssueBoardPosition;
+
+pub struct BoardPositionsRepo;
+
+impl BoardPositionsRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+    ) -> Result<Option<IssueBoardPosition>, diesel::result::Error> {
+        use crate::schema::issue_board_positions::dsl as p;
+        p::issue_board_positions
+            .filter(p::issue_id.eq(issue))
+            .select(IssueBoardPosition::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn list(
+        conn: &mut PgConnection,
+        issues: &[uuid::Uuid],
+    ) -> Result<Vec<IssueBoardPosition>, diesel::result::Error> {
+        use crate::schema::issue_board_positions::dsl as p;
+        p::issue_board_positions
+            .filter(p::issue_id.eq_any(issues))
+            .select(IssueBoardPosition::as_select())
+            .load(conn)
+    }
+
+    /// Store the position and bump its version. With `expected_version` the
+    /// write only happens if the stored version still matches (`0` meaning
+    /// no position yet); `None` is returned when it does not.
+    pub fn apply(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+        position: f64,
+        expected_version: Option<i32>,
+        user: uuid::Uuid,
+    ) -> Result<Option<IssueBoardPosition>, diesel::result::Error> {
+        use crate::schema::issue_board_positions::dsl as p;
+        let now = chrono::Utc::now();
+        let insert = diesel::insert_into(p::issue_board_positions).values((
+            p::issue_id.eq(issue),
+            p::position.eq(position),
+            p::updated_by.eq(user),
+            p::updated_at.eq(now),
+        ));
+        match expected_version {
+            None => insert
+                .on_conflict(p::issue_id)
+                .do_update()
+                .set((
+                    p::position.eq(position),
+                    p::version.eq(p::version + 1),
+                    p::updated_by.eq(user),
+                    p::updated_at.eq(now),
+                ))
+                .returning(IssueBoardPosition::as_returning())
+                .get_result(conn)
+                .map(Some),
+            Some(0) => insert
+                .on_conflict_do_nothing()
+                .returning(IssueBoardPosition::as_returning())
+                .get_result(conn)
+                .optional(),
+            Some(version) => diesel::update(
+                p::issue_board_positions
+                    .filter(p::issue_id.eq(issue))
+                    .filter(p::version.eq(version)),
+            )
+            .set((
+                p::position.eq(position),
+                p::version.eq(p::version + 1),
+                p::updated_by.eq(user),
+                p::updated_at.eq(now),
+            ))
+            .returning(IssueBoardPosition::as_returning())
+            .get_result(conn)
+            .optional(),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use diesel::prelude::*;
+
+use crate::db::models::channel_permission::{
+    ChannelPermissionRestriction, NewChannelPermissionRestriction,
+};
+
+pub struct ChannelPermissionsRepo;
+
+impl ChannelPermissionsRepo {
+    pub fn list_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Vec<ChannelPermissionRestriction>, diesel::result::Error> {
+        use crate::schema::channel_permission_restrictions::dsl as r;
+        r::channel_permission_restrictions
+            .filter(r::workspace_id.eq(workspace))
+            .order((r::channel.asc(), r::permission.asc()))
+            .select(ChannelPermissionRestriction::as_select())
+            .load(conn)
+    }
+
+    pub fn is_denied(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        channel_name: &str,
+        permission_key: &str,
+    ) -> Result<bool, diesel::result::Error> {
+        use crate::schema::channel_permission_restrictions::dsl as r;
+        diesel::select(diesel::dsl::exists(
+            r::channel_permission_restrictions
+                .filter(r::workspace_id.eq(workspace))
+                .filter(r::channel.eq(channel_name))
+                .filter(r::permission.eq(permission_key)),
+        ))
+        .get_result(conn)
+    }
+
+    /// Replace every restriction of a channel in the workspace
+    pub fn replace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        channel_name: &str,
+        restrictions: &[NewChannelPermissionRestriction],
+    ) -> Result<Vec<ChannelPermissionRestriction>, diesel::result::Error> {
+        use crate::schema::channel_permission_restrictions::dsl as r;
+        conn.transaction(|conn| {
+            diesel::delete(
+                r::channel_permission_restrictions
+                    .filter(r::workspace_id.eq(workspace))
+                    .filter(r::channel.eq(channel_name)),
+            )
+            .execute(conn)?;
+            diesel::insert_into(r::channel_permission_restrictions)
+                .values(restrictions)
+                .returning(ChannelPermissionRestriction::as_returning())
+                .get_results(conn)
+        })
+    }
+}
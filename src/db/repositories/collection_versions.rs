@@ -0,0 +1,149 @@
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+
+/// Row count and latest change of a collection in a workspace; any insert,
+/// update or delete changes at least one of them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectionVersion {
+    pub count: i64,
+    pub last_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl CollectionVersion {
+    /// Version of a response built from both collections
+    pub fn combine(self, other: Self) -> Self {
+        Self {
+            count: self.count + other.count,
+            last_updated_at: self.last_updated_at.max(other.last_updated_at),
+        }
+    }
+}
+
+impl From<(i64, Option<chrono::DateTime<chrono::Utc>>)> for CollectionVersion {
+    fn from((count, last_updated_at): (i64, Option<chrono::DateTime<chrono::Utc>>)) -> Self {
+        Self {
+            count,
+            last_updated_at,
+        }
+    }
+}
+
+pub struct CollectionVersionsRepo;
+
+impl CollectionVersionsRepo {
+    pub fn issues(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::{issues, teams};
+        let workspace_teams = teams::table
+            .filter(teams::workspace_id.eq(ws_id))
+            .select(teams::id);
+        issues::table
+            .filter(issues::team_id.eq_any(workspace_teams))
+            .select((count_star(), diesel::dsl::max(issues::updated_at)))
+            .get_result::<(i64, Option<chrono::DateTime<chrono::Utc>>)>(conn)
+            .map(CollectionVersion::from)
+    }
+
+    pub fn cycles(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::{cycles, teams};
+        let workspace_teams = teams::table
+            .filter(teams::workspace_id.eq(ws_id))
+            .select(teams::id);
+        cycles::table
+            .filter(cycles::team_id.eq_any(workspace_teams))
+            .select((count_star(), diesel::dsl::max(cycles::updated_at)))
+            .get_result::<(i64, Option<chrono::DateTime<chrono::Utc>>)>(conn)
+            .map(CollectionVersion::from)
+    }
+
+    pub fn labels(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::labels;
+        labels::table
+            .filter(labels::workspace_id.eq(ws_id))
+            .select((count_star(), diesel::dsl::max(labels::updated_at)))
+            .get_result::<(i64, Option<chrono::DateTime<chrono::Utc>>)>(conn)
+            .map(CollectionVersion::from)
+    }
+
+    pub fn projects(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::projects;
+        projects::table
+            .filter(projects::workspace_id.eq(ws_id))
+            .select((count_star(), diesel::dsl::max(projects::updated_at)))
+            .get_result::<(i64, Option<chrono::DateTime<chrono::Utc>>)>(conn)
+            .map(CollectionVersion::from)
+    }
+
+    pub fn project_statuses(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::project_statuses;
+        project_statuses::table
+            .filter(project_statuses::workspace_id.eq(ws_id))
+            .select((count_star(), diesel::dsl::max(project_statuses::updated_at)))
+            .get_result::<(i64, Option<chrono::DateTime<chrono::Utc>>)>(conn)
+            .map(CollectionVersion::from)
+    }
+
+    pub fn teams(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::teams;
+        teams::table
+            .filter(teams::workspace_id.eq(ws_id))
+            .select((count_star(), diesel::dsl::max(teams::updated_at)))
+            .get_result::<(i64, Option<chrono::DateTime<chrono::Utc>>)>(conn)
+            .map(CollectionVersion::from)
+    }
+
+    /// States of the workflows of the workspace's teams
+    pub fn workflow_states(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::{teams, workflow_states, workflows};
+        let workspace_teams = teams::table
+            .filter(teams::workspace_id.eq(ws_id))
+            .select(teams::id);
+        let workspace_workflows = workflows::table
+            .filter(workflows::team_id.eq_any(workspace_teams))
+            .select(workflows::id);
+        workflow_states::table
+            .filter(workflow_states::workflow_id.eq_any(workspace_workflows))
+            .select((count_star(), diesel::dsl::max(workflow_states::updated_at)))
+            .get_result::<(i64, Option<chrono::DateTime<chrono::Utc>>)>(conn)
+            .map(CollectionVersion::from)
+    }
+
+    /// Users who are members of the workspace
+    pub fn members(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        use crate::schema::{users, workspace_members};
+        let member_ids = workspace_members::table
+            .filter(workspace_members::workspace_id.eq(ws_id))
+            .select(workspace_members::user_id);
+        let (count, last_updated_at) = users::table
+            .filter(users::id.eq_any(member_ids))
+            .select((count_star(), diesel::dsl::max(users::updated_at)))
+            .get_result::<(i64, Option<chrono::NaiveDateTime>)>(conn)?;
+        Ok(CollectionVersion {
+            count,
+            last_updated_at: last_updated_at.map(|at| at.and_utc()),
+        })
+    }
+}
@@ -0,0 +1,106 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::comment::{
+    Comment, CommentFlag, CommentFlagItem, NewCommentFlag, comment_flag_status,
+};
+
+pub struct CommentFlagsRepo;
+
+impl CommentFlagsRepo {
+    /// Flag a comment; a comment is flagged at most once. Returns the number
+    /// of rows inserted.
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_flag: &NewCommentFlag,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::comment_flags::dsl as f;
+        diesel::insert_into(f::comment_flags)
+            .values(new_flag)
+            .on_conflict(f::comment_id)
+            .do_nothing()
+            .execute(conn)
+    }
+
+    pub fn count_by_author_since(
+        conn: &mut PgConnection,
+        author: Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::comment_flags::dsl as f;
+        f::comment_flags
+            .filter(f::author_id.eq(author))
+            .filter(f::created_at.ge(since))
+            .count()
+            .get_result(conn)
+    }
+
+    /// Flags of the workspace with their comments, newest first
+    pub fn list(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<CommentFlagItem>, diesel::result::Error> {
+        use crate::schema::{comment_flags::dsl as f, comments::dsl as c};
+        let mut query = f::comment_flags
+            .inner_join(c::comments)
+            .filter(f::workspace_id.eq(ws_id))
+            .select((CommentFlag::as_select(), Comment::as_select()))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(f::status.eq(status.to_string()));
+        }
+        let rows: Vec<(CommentFlag, Comment)> =
+            query.order(f::created_at.desc()).limit(limit).load(conn)?;
+        Ok(rows
+            .into_iter()
+            .map(|(flag, comment)| CommentFlagItem { flag, comment })
+            .collect())
+    }
+
+    /// Resolve an open flag of the workspace; `None` if there is none
+    pub fn resolve(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        flag_id: Uuid,
+        status: &str,
+        moderator_id: Uuid,
+    ) -> Result<Option<CommentFlag>, diesel::result::Error> {
+        use crate::schema::comment_flags::dsl as f;
+        diesel::update(
+            f::comment_flags
+                .filter(f::id.eq(flag_id))
+                .filter(f::workspace_id.eq(ws_id))
+                .filter(f::status.eq(comment_flag_status::OPEN)),
+        )
+        .set((
+            f::status.eq(status),
+            f::resolved_by.eq(Some(moderator_id)),
+            f::resolved_at.eq(Some(chrono::Utc::now())),
+        ))
+        .returning(CommentFlag::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    /// Mark the open flags of the comments as actioned
+    pub fn resolve_for_comments(
+        conn: &mut PgConnection,
+        comment_ids: &[Uuid],
+        moderator_id: Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::comment_flags::dsl as f;
+        diesel::update(
+            f::comment_flags
+                .filter(f::comment_id.eq_any(comment_ids))
+                .filter(f::status.eq(comment_flag_status::OPEN)),
+        )
+        .set((
+            f::status.eq(comment_flag_status::ACTIONED),
+            f::resolved_by.eq(Some(moderator_id)),
+            f::resolved_at.eq(Some(chrono::Utc::now())),
+        ))
+        .execute(conn)
+    }
+}
@@ -1,6 +1,8 @@
 use diesel::prelude::*;
 
-use crate::db::models::comment::{Comment, NewComment};
+use crate::db::models::comment::{
+    Comment, CommentMention, CommentReaction, NewComment, NewCommentMention, NewCommentReaction,
+};
 
 pub struct CommentRepo;
 
@@ -22,7 +24,10 @@ impl CommentRepo {
         include_deleted: bool,
     ) -> Result<Vec<Comment>, diesel::result::Error> {
         use crate::schema::comments::dsl::*;
-        let mut query = comments.filter(issue_id.eq(target_issue_id)).into_boxed();
+        let mut query = comments
+            .filter(issue_id.eq(target_issue_id))
+            .filter(hidden_at.is_null())
+            .into_boxed();
 
         if !include_deleted {
             query = query.filter(is_deleted.is_null().or(is_deleted.eq(false)));
@@ -31,6 +36,41 @@ impl CommentRepo {
         query.order(created_at.desc()).load::<Comment>(conn)
     }
 
+    /// Visible comments of an issue walking away from `pivot` on the
+    /// `(created_at, id)` order, nearest first; from the very start (or end,
+    /// when `older`) without a pivot
+    pub fn list_page(
+        conn: &mut PgConnection,
+        target_issue_id: uuid::Uuid,
+        include_deleted: bool,
+        pivot: Option<(chrono::DateTime<chrono::Utc>, uuid::Uuid)>,
+        older: bool,
+        limit: i64,
+    ) -> Result<Vec<Comment>, diesel::result::Error> {
+        use crate::schema::comments::dsl::*;
+        let mut query = comments
+            .filter(issue_id.eq(target_issue_id))
+            .filter(hidden_at.is_null())
+            .into_boxed();
+
+        if !include_deleted {
+            query = query.filter(is_deleted.is_null().or(is_deleted.eq(false)));
+        }
+
+        query = match (pivot, older) {
+            (Some((at, pivot_id)), true) => query
+                .filter(created_at.lt(at).or(created_at.eq(at).and(id.lt(pivot_id))))
+                .order((created_at.desc(), id.desc())),
+            (Some((at, pivot_id)), false) => query
+                .filter(created_at.gt(at).or(created_at.eq(at).and(id.gt(pivot_id))))
+                .order((created_at.asc(), id.asc())),
+            (None, true) => query.order((created_at.desc(), id.desc())),
+            (None, false) => query.order((created_at.asc(), id.asc())),
+        };
+
+        query.limit(limit).load::<Comment>(conn)
+    }
+
     pub fn insert(
         conn: &mut PgConnection,
         new_comment: &NewComment,
@@ -40,6 +80,16 @@ impl CommentRepo {
             .get_result(conn)
     }
 
+    pub fn insert_mentions(
+        conn: &mut PgConnection,
+        mentions: &[NewCommentMention],
+    ) -> Result<Vec<CommentMention>, diesel::result::Error> {
+        diesel::insert_into(crate::schema::comment_mentions::table)
+            .values(mentions)
+            .returning(CommentMention::as_returning())
+            .get_results(conn)
+    }
+
     pub fn update_content(
         conn: &mut PgConnection,
         comment_id: uuid::Uuid,
@@ -69,6 +119,66 @@ impl CommentRepo {
         diesel::delete(comments.filter(id.eq(comment_id))).execute(conn)
     }
 
+    /// Number of live replies to each of the given comments
+    pub fn count_replies(
+        conn: &mut PgConnection,
+        parent_ids: &[uuid::Uuid],
+    ) -> Result<Vec<(uuid::Uuid, i64)>, diesel::result::Error> {
+        use crate::schema::comments::dsl::*;
+        comments
+            .filter(parent_comment_id.eq_any(parent_ids))
+            .filter(is_deleted.is_null().or(is_deleted.eq(false)))
+            .filter(hidden_at.is_null())
+            .group_by(parent_comment_id)
+            .select((
+                parent_comment_id.assume_not_null(),
+                diesel::dsl::count_star(),
+            ))
+            .load(conn)
+    }
+
+    pub fn list_reactions(
+        conn: &mut PgConnection,
+        comment_ids: &[uuid::Uuid],
+    ) -> Result<Vec<CommentReaction>, diesel::result::Error> {
+        use crate::schema::comment_reactions::dsl::*;
+        comment_reactions
+            .filter(comment_id.eq_any(comment_ids))
+            .order(created_at.asc())
+            .select(CommentReaction::as_select())
+            .load(conn)
+    }
+
+    /// Add a reaction; a user reacting twice with the same emoji is a no-op.
+    /// Returns the number of rows inserted.
+    pub fn insert_reaction(
+        conn: &mut PgConnection,
+        reaction: &NewCommentReaction,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::comment_reactions::dsl::*;
+        diesel::insert_into(comment_reactions)
+            .values(reaction)
+            .on_conflict((comment_id, user_id, reaction_type))
+            .do_nothing()
+            .execute(conn)
+    }
+
+    pub fn delete_reaction(
+        conn: &mut PgConnection,
+        target_comment_id: uuid::Uuid,
+        target_user_id: uuid::Uuid,
+        emoji: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::comment_reactions::dsl::*;
+        diesel::delete(
+            comment_reactions
+                .filter(comment_id.eq(target_comment_id))
+                .filter(user_id.eq(target_user_id))
+                .filter(reaction_type.eq(emoji)),
+        )
+        .execute(conn)
+    }
+
     pub fn find_by_id_with_issue(
         conn: &mut PgConnection,
         comment_id: uuid::Uuid,
@@ -80,4 +190,119 @@ impl CommentRepo {
             .first(conn)
             .optional()
     }
+
+    /// Ids of the workspace's comments matching a moderation filter, oldest
+    /// first. `content_like` is an ILIKE pattern.
+    pub fn find_for_moderation(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        author: Option<uuid::Uuid>,
+        content_like: Option<&str>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::{comments::dsl as c, issues::dsl as i, teams::dsl as t};
+        let mut query = c::comments
+            .inner_join(i::issues.inner_join(t::teams))
+            .filter(t::workspace_id.eq(ws_id))
+            .select(c::id)
+            .into_boxed();
+        if let Some(author) = author {
+            query = query.filter(c::author_id.eq(author));
+        }
+        if let Some(pattern) = content_like {
+            query = query.filter(c::content.ilike(pattern.to_string()));
+        }
+        if let Some(since) = since {
+            query = query.filter(c::created_at.ge(since));
+        }
+        query.order(c::created_at.asc()).load(conn)
+    }
+
+    /// Those of the given comments that are on issues of the workspace
+    pub fn filter_in_workspace(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        comment_ids: &[uuid::Uuid],
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::{comments::dsl as c, issues::dsl as i, teams::dsl as t};
+        c::comments
+            .inner_join(i::issues.inner_join(t::teams))
+            .filter(t::workspace_id.eq(ws_id))
+            .filter(c::id.eq_any(comment_ids))
+            .select(c::id)
+            .load(conn)
+    }
+
+    /// Hide the comments that are not hidden yet; returns those hidden
+    pub fn hide(
+        conn: &mut PgConnection,
+        comment_ids: &[uuid::Uuid],
+        moderator_id: uuid::Uuid,
+        reason: Option<&str>,
+    ) -> Result<Vec<Comment>, diesel::result::Error> {
+        use crate::schema::comments::dsl::*;
+        diesel::update(
+            comments
+                .filter(id.eq_any(comment_ids))
+                .filter(hidden_at.is_null()),
+        )
+        .set((
+            hidden_at.eq(Some(chrono::Utc::now())),
+            hidden_by.eq(Some(moderator_id)),
+            hidden_reason.eq(reason),
+        ))
+        .returning(Comment::as_returning())
+        .get_results(conn)
+    }
+
+    /// Restore hidden comments; returns those restored
+    pub fn unhide(
+        conn: &mut PgConnection,
+        comment_ids: &[uuid::Uuid],
+    ) -> Result<Vec<Comment>, diesel::result::Error> {
+        use crate::schema::comments::dsl::*;
+        diesel::update(
+            comments
+                .filter(id.eq_any(comment_ids))
+                .filter(hidden_at.is_not_null()),
+        )
+        .set((
+            hidden_at.eq(None::<chrono::DateTime<chrono::Utc>>),
+            hidden_by.eq(None::<uuid::Uuid>),
+            hidden_reason.eq(None::<String>),
+        ))
+        .returning(Comment::as_returning())
+        .get_results(conn)
+    }
+
+    /// Delete the comments; replies, mentions, reactions and flags go with them
+    pub fn delete_many(
+        conn: &mut PgConnection,
+        comment_ids: &[uuid::Uuid],
+    ) -> Result<Vec<Comment>, diesel::result::Error> {
+        use crate::schema::comments::dsl::*;
+        diesel::delete(comments.filter(id.eq_any(comment_ids)))
+            .returning(Comment::as_returning())
+            .get_results(conn)
+    }
+
+    /// Comments the author posted since `since`, and how many of them have
+    /// exactly the given content
+    pub fn author_activity_since(
+        conn: &mut PgConnection,
+        author: uuid::Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+        same_content: &str,
+    ) -> Result<(i64, i64), diesel::result::Error> {
+        use crate::schema::comments::dsl::*;
+        let recent = comments
+            .filter(author_id.eq(author))
+            .filter(created_at.ge(since));
+        let total = recent.count().get_result(conn)?;
+        let duplicates = recent
+            .filter(content.eq(same_content))
+            .count()
+            .get_result(conn)?;
+        Ok((total, duplicates))
+    }
 }
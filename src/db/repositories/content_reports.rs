@@ -0,0 +1,137 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::content_report::{ContentReport, NewContentReport, report_status};
+
+pub struct ContentReportsRepo;
+
+impl ContentReportsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_report: &NewContentReport,
+    ) -> Result<ContentReport, diesel::result::Error> {
+        diesel::insert_into(crate::schema::content_reports::table)
+            .values(new_report)
+            .returning(ContentReport::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn has_open_report(
+        conn: &mut PgConnection,
+        reporter: Uuid,
+        target_type: &str,
+        target_id: Uuid,
+    ) -> Result<bool, diesel::result::Error> {
+        use crate::schema::content_reports::dsl as r;
+        diesel::select(diesel::dsl::exists(
+            r::content_reports
+                .filter(r::reporter_id.eq(reporter))
+                .filter(r::target_type.eq(target_type))
+                .filter(r::target_id.eq(target_id))
+                .filter(r::status.eq(report_status::OPEN)),
+        ))
+        .get_result(conn)
+    }
+
+    pub fn find(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        report_id: Uuid,
+    ) -> Result<Option<ContentReport>, diesel::result::Error> {
+        use crate::schema::content_reports::dsl as r;
+        r::content_reports
+            .filter(r::id.eq(report_id))
+            .filter(r::workspace_id.eq(ws_id))
+            .select(ContentReport::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Reports of the workspace, oldest first so the queue is worked in order
+    pub fn list(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        status: &str,
+        target_type: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ContentReport>, diesel::result::Error> {
+        use crate::schema::content_reports::dsl as r;
+        let mut query = r::content_reports
+            .filter(r::workspace_id.eq(ws_id))
+            .filter(r::status.eq(status.to_string()))
+            .select(ContentReport::as_select())
+            .into_boxed();
+        if let Some(target_type) = target_type {
+            query = query.filter(r::target_type.eq(target_type.to_string()));
+        }
+        query.order(r::created_at.asc()).limit(limit).load(conn)
+    }
+
+    /// Open reports per target among `target_ids`
+    pub fn count_open_by_target(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        target_ids: &[Uuid],
+    ) -> Result<Vec<(String, Uuid, i64)>, diesel::result::Error> {
+        use crate::schema::content_reports::dsl as r;
+        r::content_reports
+            .filter(r::workspace_id.eq(ws_id))
+            .filter(r::target_id.eq_any(target_ids))
+            .filter(r::status.eq(report_status::OPEN))
+            .group_by((r::target_type, r::target_id))
+            .select((r::target_type, r::target_id, diesel::dsl::count_star()))
+            .load(conn)
+    }
+
+    /// Resolve every open report on the target; returns those resolved
+    pub fn resolve_open_for_target(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        target_type: &str,
+        target_id: Uuid,
+        status: &str,
+        moderator_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Vec<ContentReport>, diesel::result::Error> {
+        use crate::schema::content_reports::dsl as r;
+        diesel::update(
+            r::content_reports
+                .filter(r::workspace_id.eq(ws_id))
+                .filter(r::target_type.eq(target_type))
+                .filter(r::target_id.eq(target_id))
+                .filter(r::status.eq(report_status::OPEN)),
+        )
+        .set((
+            r::status.eq(status),
+            r::resolution_note.eq(note),
+            r::resolved_by.eq(Some(moderator_id)),
+            r::resolved_at.eq(Some(chrono::Utc::now())),
+        ))
+        .returning(ContentReport::as_returning())
+        .get_results(conn)
+    }
+
+    /// `(id, author, content)` of the comments that still exist
+    pub fn comment_summaries(
+        conn: &mut PgConnection,
+        comment_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Uuid, String)>, diesel::result::Error> {
+        use crate::schema::comments::dsl as c;
+        c::comments
+            .filter(c::id.eq_any(comment_ids))
+            .select((c::id, c::author_id, c::content))
+            .load(conn)
+    }
+
+    /// `(id, creator, title)` of the issues that still exist
+    pub fn issue_summaries(
+        conn: &mut PgConnection,
+        issue_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Uuid, String)>, diesel::result::Error> {
+        use crate::schema::issues::dsl as i;
+        i::issues
+            .filter(i::id.eq_any(issue_ids))
+            .select((i::id, i::creator_id, i::title))
+            .load(conn)
+    }
+}
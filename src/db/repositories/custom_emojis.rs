@@ -0,0 +1,164 @@
+use diesel::prelude::*;
+
+use crate::db::models::custom_emoji::{CustomEmoji, NewCustomEmoji, custom_emoji_status};
+
+pub struct CustomEmojisRepo;
+
+impl CustomEmojisRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_emoji: &NewCustomEmoji,
+    ) -> Result<CustomEmoji, diesel::result::Error> {
+        diesel::insert_into(crate::schema::custom_emojis::table)
+            .values(new_emoji)
+            .returning(CustomEmoji::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        emoji_id: uuid::Uuid,
+    ) -> Result<Option<CustomEmoji>, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        e::custom_emojis
+            .filter(e::id.eq(emoji_id))
+            .filter(e::workspace_id.eq(ws_id))
+            .select(CustomEmoji::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Any emoji holding `emoji_name`, pending uploads included
+    pub fn find_by_name(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        emoji_name: &str,
+    ) -> Result<Option<CustomEmoji>, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        e::custom_emojis
+            .filter(e::workspace_id.eq(ws_id))
+            .filter(e::name.eq(emoji_name))
+            .select(CustomEmoji::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Whether an uploaded emoji named `emoji_name` exists
+    pub fn exists_uploaded(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        emoji_name: &str,
+    ) -> Result<bool, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        diesel::select(diesel::dsl::exists(
+            e::custom_emojis
+                .filter(e::workspace_id.eq(ws_id))
+                .filter(e::name.eq(emoji_name))
+                .filter(e::status.eq(custom_emoji_status::UPLOADED)),
+        ))
+        .get_result(conn)
+    }
+
+    /// Uploaded emoji in name order
+    pub fn list_uploaded(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<Vec<CustomEmoji>, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        e::custom_emojis
+            .filter(e::workspace_id.eq(ws_id))
+            .filter(e::status.eq(custom_emoji_status::UPLOADED))
+            .order(e::name.asc())
+            .select(CustomEmoji::as_select())
+            .load(conn)
+    }
+
+    /// Uploaded emoji, least used first; with `unused_since`, only those
+    /// never used or last used before it
+    pub fn list_by_usage(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        unused_since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<CustomEmoji>, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        let mut query = e::custom_emojis
+            .filter(e::workspace_id.eq(ws_id))
+            .filter(e::status.eq(custom_emoji_status::UPLOADED))
+            .into_boxed();
+        if let Some(since) = unused_since {
+            query = query.filter(e::last_used_at.is_null().or(e::last_used_at.lt(since)));
+        }
+        query
+            .order((
+                e::usage_count.asc(),
+                e::last_used_at.asc().nulls_first(),
+                e::name.asc(),
+            ))
+            .select(CustomEmoji::as_select())
+            .load(conn)
+    }
+
+    pub fn mark_uploaded(
+        conn: &mut PgConnection,
+        emoji_id: uuid::Uuid,
+        actual_size: i64,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<CustomEmoji, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        diesel::update(e::custom_emojis.filter(e::id.eq(emoji_id)))
+            .set((
+                e::status.eq(custom_emoji_status::UPLOADED),
+                e::size_bytes.eq(actual_size),
+                e::uploaded_at.eq(Some(at)),
+                e::updated_at.eq(at),
+            ))
+            .returning(CustomEmoji::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn rename(
+        conn: &mut PgConnection,
+        emoji_id: uuid::Uuid,
+        new_name: &str,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<CustomEmoji, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        diesel::update(e::custom_emojis.filter(e::id.eq(emoji_id)))
+            .set((e::name.eq(new_name), e::updated_at.eq(at)))
+            .returning(CustomEmoji::as_returning())
+            .get_result(conn)
+    }
+
+    /// Count one use of each named uploaded emoji; returns how many matched
+    pub fn record_usage(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        names: &[String],
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        if names.is_empty() {
+            return Ok(0);
+        }
+        diesel::update(
+            e::custom_emojis
+                .filter(e::workspace_id.eq(ws_id))
+                .filter(e::name.eq_any(names))
+                .filter(e::status.eq(custom_emoji_status::UPLOADED)),
+        )
+        .set((
+            e::usage_count.eq(e::usage_count + 1),
+            e::last_used_at.eq(Some(at)),
+        ))
+        .execute(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        emoji_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::custom_emojis::dsl as e;
+        diesel::delete(e::custom_emojis.filter(e::id.eq(emoji_id))).execute(conn)
+    }
+}
@@ -0,0 +1,67 @@
+use diesel::prelude::*;
+
+use crate::db::models::cycle::{NewTeamCycleSettings, TeamCycleSettings};
+
+pub struct CycleSettingsRepo;
+
+impl CycleSettingsRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Option<TeamCycleSettings>, diesel::result::Error> {
+        use crate::schema::team_cycle_settings::dsl as s;
+        s::team_cycle_settings
+            .filter(s::team_id.eq(team))
+            .select(TeamCycleSettings::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn upsert(
+        conn: &mut PgConnection,
+        settings: &NewTeamCycleSettings,
+    ) -> Result<TeamCycleSettings, diesel::result::Error> {
+        use crate::schema::team_cycle_settings::dsl as s;
+        diesel::insert_into(s::team_cycle_settings)
+            .values(settings)
+            .on_conflict(s::team_id)
+            .do_update()
+            .set((settings, s::updated_at.eq(chrono::Utc::now())))
+            .returning(TeamCycleSettings::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::team_cycle_settings::dsl as s;
+        diesel::delete(s::team_cycle_settings.filter(s::team_id.eq(team))).execute(conn)
+    }
+
+    /// Settings of teams that create cycles automatically or roll issues
+    /// over, with the team's workspace id
+    pub fn list_scheduled(
+        conn: &mut PgConnection,
+    ) -> Result<Vec<(TeamCycleSettings, uuid::Uuid)>, diesel::result::Error> {
+        use crate::schema::team_cycle_settings::dsl as s;
+        use crate::schema::teams::dsl as t;
+        s::team_cycle_settings
+            .inner_join(t::teams)
+            .filter(s::auto_create.eq(true).or(s::roll_over_unfinished.eq(true)))
+            .select((TeamCycleSettings::as_select(), t::workspace_id))
+            .load(conn)
+    }
+
+    pub fn mark_rolled_over(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+        cycle: uuid::Uuid,
+    ) -> Result<(), diesel::result::Error> {
+        use crate::schema::team_cycle_settings::dsl as s;
+        diesel::update(s::team_cycle_settings.filter(s::team_id.eq(team)))
+            .set(s::last_rolled_over_cycle_id.eq(Some(cycle)))
+            .execute(conn)?;
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use diesel::prelude::*;
 
-use crate::db::models::cycle::{Cycle, NewCycle};
+use crate::db::models::cycle::{Cycle, CycleIssuePoints, CycleScopeChange, NewCycle};
 
 pub struct CyclesRepo;
 
@@ -102,4 +102,219 @@ impl CyclesRepo {
         // Return the updated cycle
         c::cycles.filter(c::id.eq(cycle_id)).first::<Cycle>(conn)
     }
+
+    /// Estimate and workflow state category of every issue in the cycle
+    pub fn issue_points(
+        conn: &mut PgConnection,
+        cycle_id: uuid::Uuid,
+    ) -> Result<Vec<CycleIssuePoints>, diesel::result::Error> {
+        use crate::schema::{issues, workflow_states};
+        let rows: Vec<(
+            Option<i32>,
+            Option<crate::db::models::workflow::WorkflowStateCategory>,
+        )> = issues::table
+            .left_join(
+                workflow_states::table
+                    .on(issues::workflow_state_id.eq(workflow_states::id.nullable())),
+            )
+            .filter(issues::cycle_id.eq(cycle_id))
+            .select((issues::estimate, workflow_states::category.nullable()))
+            .load(conn)?;
+        Ok(rows
+            .into_iter()
+            .map(|(estimate, category)| CycleIssuePoints { estimate, category })
+            .collect())
+    }
+
+    /// Scope changes of the cycle at or after `since`, oldest first
+    pub fn scope_changes_since(
+        conn: &mut PgConnection,
+        cycle_id: uuid::Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<CycleScopeChange>, diesel::result::Error> {
+        use crate::schema::cycle_scope_changes::dsl as sc;
+        sc::cycle_scope_changes
+            .filter(sc::cycle_id.eq(cycle_id))
+            .filter(sc::created_at.ge(since))
+            .order(sc::id.asc())
+            .select(CycleScopeChange::as_select())
+            .load(conn)
+    }
+
+    /// The cycle's whole scope change log, oldest first
+    pub fn scope_changes(
+        conn: &mut PgConnection,
+        cycle_id: uuid::Uuid,
+    ) -> Result<Vec<CycleScopeChange>, diesel::result::Error> {
+        use crate::schema::cycle_scope_changes::dsl as sc;
+        sc::cycle_scope_changes
+            .filter(sc::cycle_id.eq(cycle_id))
+            .order(sc::id.asc())
+            .select(CycleScopeChange::as_select())
+            .load(conn)
+    }
+
+    /// Mark planned cycles running on `today` active
+    pub fn activate_started(
+        conn: &mut PgConnection,
+        today: chrono::NaiveDate,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Cycle>, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        diesel::update(
+            c::cycles
+                .filter(c::status.eq("planned"))
+                .filter(c::start_date.le(today))
+                .filter(c::end_date.ge(today)),
+        )
+        .set((c::status.eq("active"), c::updated_at.eq(now)))
+        .returning(Cycle::as_returning())
+        .get_results(conn)
+    }
+
+    /// Mark cycles that ended before `today` completed
+    pub fn complete_ended(
+        conn: &mut PgConnection,
+        today: chrono::NaiveDate,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Cycle>, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        diesel::update(
+            c::cycles
+                .filter(c::status.ne("completed"))
+                .filter(c::end_date.lt(today)),
+        )
+        .set((c::status.eq("completed"), c::updated_at.eq(now)))
+        .returning(Cycle::as_returning())
+        .get_results(conn)
+    }
+
+    /// The team's cycle that ends last
+    pub fn latest_for_team(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Option<Cycle>, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        c::cycles
+            .filter(c::team_id.eq(team))
+            .order((c::end_date.desc(), c::created_at.desc()))
+            .select(Cycle::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn count_for_team(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        c::cycles
+            .filter(c::team_id.eq(team))
+            .count()
+            .get_result(conn)
+    }
+
+    /// The team's earliest cycle with `status`
+    pub fn first_with_status(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+        status: &str,
+    ) -> Result<Option<Cycle>, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        c::cycles
+            .filter(c::team_id.eq(team))
+            .filter(c::status.eq(status.to_string()))
+            .order(c::start_date.asc())
+            .select(Cycle::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// The team's most recently ended completed cycle
+    pub fn latest_completed(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Option<Cycle>, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        c::cycles
+            .filter(c::team_id.eq(team))
+            .filter(c::status.eq("completed"))
+            .order(c::end_date.desc())
+            .select(Cycle::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// The team's earliest cycle that is not completed and starts after
+    /// `date`
+    pub fn next_open_after(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+        date: chrono::NaiveDate,
+    ) -> Result<Option<Cycle>, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        c::cycles
+            .filter(c::team_id.eq(team))
+            .filter(c::status.ne("completed"))
+            .filter(c::start_date.gt(date))
+            .order(c::start_date.asc())
+            .select(Cycle::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Unarchived issues in the cycle whose state is not completed or
+    /// canceled
+    pub fn count_unfinished(
+        conn: &mut PgConnection,
+        cycle_id: uuid::Uuid,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::issues::dsl as i;
+        i::issues
+            .filter(i::cycle_id.eq(cycle_id))
+            .filter(i::archived_at.is_null())
+            .filter(i::workflow_state_id.is_null().or(diesel::dsl::not(
+                i::workflow_state_id.eq_any(Self::finished_states()),
+            )))
+            .count()
+            .get_result(conn)
+    }
+
+    /// Move the cycle's unfinished issues into `to_cycle`
+    pub fn move_unfinished(
+        conn: &mut PgConnection,
+        from_cycle: uuid::Uuid,
+        to_cycle: uuid::Uuid,
+    ) -> Result<Vec<crate::db::models::issue::Issue>, diesel::result::Error> {
+        use crate::db::models::issue::Issue;
+        use crate::schema::issues::dsl as i;
+        diesel::update(
+            i::issues
+                .filter(i::cycle_id.eq(from_cycle))
+                .filter(i::archived_at.is_null())
+                .filter(i::workflow_state_id.is_null().or(diesel::dsl::not(
+                    i::workflow_state_id.eq_any(Self::finished_states()),
+                ))),
+        )
+        .set(i::cycle_id.eq(Some(to_cycle)))
+        .returning(Issue::as_returning())
+        .get_results(conn)
+    }
+
+    /// Ids of completed and canceled workflow states
+    fn finished_states() -> crate::schema::workflow_states::BoxedQuery<
+        'static,
+        diesel::pg::Pg,
+        diesel::sql_types::Nullable<diesel::sql_types::Uuid>,
+    > {
+        use crate::db::models::workflow::WorkflowStateCategory;
+        use crate::schema::workflow_states::dsl as ws;
+        ws::workflow_states
+            .filter(ws::category.eq_any([
+                WorkflowStateCategory::Completed,
+                WorkflowStateCategory::Canceled,
+            ]))
+            .select(ws::id.nullable())
+            .into_boxed()
+    }
 }
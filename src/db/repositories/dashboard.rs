@@ -0,0 +1,209 @@
+use crate::db::models::dashboard::{DashboardActivity, DashboardCycleRollup, DashboardIssue};
+use crate::db::models::workflow::WorkflowStateCategory;
+use diesel::PgSortExpressionMethods;
+use diesel::prelude::*;
+
+/// One row of the second dashboard query: either an active cycle rollup or a
+/// recent notification, so both come back in a single round trip
+#[derive(QueryableByName)]
+struct DashboardRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    kind: String,
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    id: uuid::Uuid,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    title: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Uuid>)]
+    team_id: Option<uuid::Uuid>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Date>)]
+    start_date: Option<chrono::NaiveDate>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Date>)]
+    end_date: Option<chrono::NaiveDate>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    total_issues: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    completed_issues: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    total_points: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    completed_points: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    event_type: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    entity_type: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Uuid>)]
+    entity_id: Option<uuid::Uuid>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    is_read: bool,
+    #[diesel(sql_type = diesel::sql_types::Timestamptz)]
+    occurred_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Active cycle rollups of the user's teams followed by the user's latest
+/// notifications. Totals are summed per cycle rather than counted in
+/// separate queries.
+const CYCLES_AND_ACTIVITY_SQL: &str = "\
+    (SELECT 'cycle'::text AS kind, c.id, c.name::text AS title, c.team_id, \
+            c.start_date, c.end_date, \
+            COALESCE(SUM(CASE WHEN i.id IS NULL THEN 0 ELSE 1 END), 0)::bigint AS total_issues, \
+            COALESCE(SUM(CASE WHEN ws.category = 'completed' THEN 1 ELSE 0 END), 0)::bigint AS completed_issues, \
+            COALESCE(SUM(COALESCE(i.estimate, 0)), 0)::bigint AS total_points, \
+            COALESCE(SUM(CASE WHEN ws.category = 'completed' THEN COALESCE(i.estimate, 0) ELSE 0 END), 0)::bigint AS completed_points, \
+            NULL::text AS event_type, NULL::text AS entity_type, NULL::uuid AS entity_id, \
+            FALSE AS is_read, c.updated_at AS occurred_at \
+     FROM cycles c \
+     JOIN teams t ON t.id = c.team_id \
+     JOIN team_members tm ON tm.team_id = c.team_id AND tm.user_id = $2 \
+     LEFT JOIN issues i ON i.cycle_id = c.id \
+     LEFT JOIN workflow_states ws ON ws.id = i.workflow_state_id \
+     WHERE t.workspace_id = $1 AND c.status = 'active' \
+     GROUP BY c.id \
+     ORDER BY c.end_date ASC \
+     LIMIT $3) \
+    UNION ALL \
+    (SELECT 'activity'::text, n.id, n.title::text, NULL, NULL, NULL, 0, 0, 0, 0, \
+            n.event_type::text, n.entity_type::text, n.entity_id, \
+            n.read_at IS NOT NULL, n.updated_at \
+     FROM notifications n \
+     WHERE n.workspace_id = $1 AND n.recipient_id = $2 \
+     ORDER BY n.updated_at DESC \
+     LIMIT $4)";
+
+pub struct DashboardRepo;
+
+impl DashboardRepo {
+    /// Open issues assigned to the user, soonest cycle end first, then most
+    /// recently updated. Issues in completed or canceled states are left out.
+    pub fn open_issues_for_user(
+        conn: &mut PgConnection,
+        workspace_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+        limit: i64,
+    ) -> Result<Vec<DashboardIssue>, diesel::result::Error> {
+        use crate::schema::{cycles, issues, teams, workflow_states};
+        #[allow(clippy::type_complexity)]
+        let rows: Vec<(
+            uuid::Uuid,
+            i32,
+            String,
+            String,
+            uuid::Uuid,
+            Option<WorkflowStateCategory>,
+            Option<uuid::Uuid>,
+            Option<chrono::NaiveDate>,
+            chrono::DateTime<chrono::Utc>,
+        )> = issues::table
+            .inner_join(teams::table.on(teams::id.eq(issues::team_id)))
+            .left_join(
+                workflow_states::table
+                    .on(issues::workflow_state_id.eq(workflow_states::id.nullable())),
+            )
+            .left_join(cycles::table.on(issues::cycle_id.eq(cycles::id.nullable())))
+            .filter(teams::workspace_id.eq(workspace_id))
+            .filter(issues::assignee_id.eq(user_id))
+            .filter(issues::archived_at.is_null())
+            .filter(workflow_states::category.nullable().is_null().or(
+                workflow_states::category.nullable().ne_all(vec![
+                    WorkflowStateCategory::Completed.as_str(),
+                    WorkflowStateCategory::Canceled.as_str(),
+                ]),
+            ))
+            .order((
+                cycles::end_date.nullable().asc().nulls_last(),
+                issues::updated_at.desc(),
+            ))
+            .limit(limit)
+            .select((
+                issues::id,
+                issues::issue_number,
+                issues::title,
+                issues::priority,
+                issues::team_id,
+                workflow_states::category.nullable(),
+                issues::cycle_id,
+                cycles::end_date.nullable(),
+                issues::updated_at,
+            ))
+            .load(conn)?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    id,
+                    issue_number,
+                    title,
+                    priority,
+                    team_id,
+                    state_category,
+                    cycle_id,
+                    cycle_end_date,
+                    updated_at,
+                )| DashboardIssue {
+                    id,
+                    issue_number,
+                    title,
+                    priority,
+                    team_id,
+                    state_category,
+                    cycle_id,
+                    cycle_end_date,
+                    updated_at,
+                },
+            )
+            .collect())
+    }
+
+    /// Active cycle rollups of the user's teams and the user's latest
+    /// notifications, fetched together in one statement
+    pub fn cycles_and_activity(
+        conn: &mut PgConnection,
+        workspace_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+        cycle_limit: i64,
+        activity_limit: i64,
+    ) -> Result<(Vec<DashboardCycleRollup>, Vec<DashboardActivity>), diesel::result::Error> {
+        use diesel::sql_types::{BigInt, Uuid};
+        let rows: Vec<DashboardRow> = diesel::sql_query(CYCLES_AND_ACTIVITY_SQL)
+            .bind::<Uuid, _>(workspace_id)
+            .bind::<Uuid, _>(user_id)
+            .bind::<BigInt, _>(cycle_limit)
+            .bind::<BigInt, _>(activity_limit)
+            .load(conn)?;
+
+        let mut cycles = Vec::new();
+        let mut activity = Vec::new();
+        for row in rows {
+            match (row.kind.as_str(), row.team_id, row.start_date, row.end_date) {
+                ("cycle", Some(team_id), Some(start_date), Some(end_date)) => {
+                    cycles.push(DashboardCycleRollup {
+                        cycle_id: row.id,
+                        team_id,
+                        name: row.title,
+                        start_date,
+                        end_date,
+                        total_issues: row.total_issues,
+                        completed_issues: row.completed_issues,
+                        total_points: row.total_points,
+                        completed_points: row.completed_points,
+                    })
+                }
+                _ => {
+                    if let (Some(event_type), Some(entity_type), Some(entity_id)) =
+                        (row.event_type, row.entity_type, row.entity_id)
+                    {
+                        activity.push(DashboardActivity {
+                            notification_id: row.id,
+                            event_type,
+                            entity_type,
+                            entity_id,
+                            title: row.title,
+                            read: row.is_read,
+                            occurred_at: row.occurred_at,
+                        })
+                    }
+                }
+            }
+        }
+        Ok((cycles, activity))
+    }
+}
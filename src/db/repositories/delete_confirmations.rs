@@ -0,0 +1,103 @@
+use diesel::prelude::*;
+
+use crate::db::models::delete_confirmation::{
+    DeleteConfirmationRecord, DeleteImpact, NewDeleteConfirmation,
+};
+
+pub struct DeleteConfirmationsRepo;
+
+impl DeleteConfirmationsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        confirmation: &NewDeleteConfirmation,
+    ) -> Result<DeleteConfirmationRecord, diesel::result::Error> {
+        diesel::insert_into(crate::schema::delete_confirmations::table)
+            .values(confirmation)
+            .returning(DeleteConfirmationRecord::as_returning())
+            .get_result(conn)
+    }
+
+    /// The unconfirmed confirmation with this token, locked until the
+    /// transaction ends so it can only be used once
+    pub fn find_pending_for_update(
+        conn: &mut PgConnection,
+        hash: &str,
+    ) -> Result<Option<DeleteConfirmationRecord>, diesel::result::Error> {
+        use crate::schema::delete_confirmations::dsl as dc;
+        dc::delete_confirmations
+            .filter(dc::token_hash.eq(hash))
+            .filter(dc::confirmed_at.is_null())
+            .select(DeleteConfirmationRecord::as_select())
+            .for_update()
+            .first(conn)
+            .optional()
+    }
+
+    pub fn mark_confirmed(
+        conn: &mut PgConnection,
+        confirmation_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::delete_confirmations::dsl as dc;
+        diesel::update(dc::delete_confirmations.filter(dc::id.eq(confirmation_id)))
+            .set(dc::confirmed_at.eq(Some(at)))
+            .execute(conn)
+    }
+
+    pub fn workspace_impact(
+        conn: &mut PgConnection,
+        workspace_id: uuid::Uuid,
+    ) -> Result<DeleteImpact, diesel::result::Error> {
+        use crate::schema::{cycles, issues, projects, teams, workspace_members};
+        let team_ids = teams::table
+            .filter(teams::workspace_id.eq(workspace_id))
+            .select(teams::id);
+        Ok(DeleteImpact {
+            issues: issues::table
+                .filter(issues::team_id.eq_any(team_ids))
+                .count()
+                .get_result(conn)?,
+            members: workspace_members::table
+                .filter(workspace_members::workspace_id.eq(workspace_id))
+                .count()
+                .get_result(conn)?,
+            teams: teams::table
+                .filter(teams::workspace_id.eq(workspace_id))
+                .count()
+                .get_result(conn)?,
+            projects: projects::table
+                .filter(projects::workspace_id.eq(workspace_id))
+                .count()
+                .get_result(conn)?,
+            cycles: cycles::table
+                .filter(cycles::team_id.eq_any(team_ids))
+                .count()
+                .get_result(conn)?,
+        })
+    }
+
+    /// Sub-teams move up to the parent rather than being deleted, so they do
+    /// not count
+    pub fn team_impact(
+        conn: &mut PgConnection,
+        team_id: uuid::Uuid,
+    ) -> Result<DeleteImpact, diesel::result::Error> {
+        use crate::schema::{cycles, issues, team_members};
+        Ok(DeleteImpact {
+            issues: issues::table
+                .filter(issues::team_id.eq(team_id))
+                .count()
+                .get_result(conn)?,
+            members: team_members::table
+                .filter(team_members::team_id.eq(team_id))
+                .count()
+                .get_result(conn)?,
+            teams: 1,
+            projects: 0,
+            cycles: cycles::table
+                .filter(cycles::team_id.eq(team_id))
+                .count()
+                .get_result(conn)?,
+        })
+    }
+}
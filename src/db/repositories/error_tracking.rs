@@ -0,0 +1,130 @@
+use diesel::prelude::*;
+
+use crate::db::models::error_tracking::{
+    ErrorGroup, ErrorTrackingIntegration, NewErrorGroup, NewErrorTrackingIntegration,
+    UpdateErrorTrackingIntegration,
+};
+use crate::db::models::issue::Issue;
+
+pub struct ErrorTrackingRepo;
+
+impl ErrorTrackingRepo {
+    pub fn find_integration(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Option<ErrorTrackingIntegration>, diesel::result::Error> {
+        use crate::schema::error_tracking_integrations::dsl as e;
+        e::error_tracking_integrations
+            .filter(e::workspace_id.eq(workspace))
+            .select(ErrorTrackingIntegration::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Lock the integration row so events of one workspace are grouped one
+    /// at a time and a new error never gets two issues
+    pub fn lock_integration(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Option<ErrorTrackingIntegration>, diesel::result::Error> {
+        use crate::schema::error_tracking_integrations::dsl as e;
+        e::error_tracking_integrations
+            .filter(e::workspace_id.eq(workspace))
+            .select(ErrorTrackingIntegration::as_select())
+            .for_update()
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert_integration(
+        conn: &mut PgConnection,
+        new_integration: &NewErrorTrackingIntegration,
+    ) -> Result<ErrorTrackingIntegration, diesel::result::Error> {
+        diesel::insert_into(crate::schema::error_tracking_integrations::table)
+            .values(new_integration)
+            .returning(ErrorTrackingIntegration::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn update_integration(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        changes: &UpdateErrorTrackingIntegration,
+    ) -> Result<ErrorTrackingIntegration, diesel::result::Error> {
+        use crate::schema::error_tracking_integrations::dsl as e;
+        diesel::update(e::error_tracking_integrations.filter(e::workspace_id.eq(workspace)))
+            .set(changes)
+            .returning(ErrorTrackingIntegration::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete_integration(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::error_tracking_integrations::dsl as e;
+        diesel::delete(e::error_tracking_integrations.filter(e::workspace_id.eq(workspace)))
+            .execute(conn)
+    }
+
+    pub fn find_group(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        fingerprint: &str,
+    ) -> Result<Option<ErrorGroup>, diesel::result::Error> {
+        use crate::schema::error_groups::dsl as g;
+        g::error_groups
+            .filter(g::workspace_id.eq(workspace))
+            .filter(g::fingerprint.eq(fingerprint))
+            .select(ErrorGroup::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert_group(
+        conn: &mut PgConnection,
+        new_group: &NewErrorGroup,
+    ) -> Result<ErrorGroup, diesel::result::Error> {
+        diesel::insert_into(crate::schema::error_groups::table)
+            .values(new_group)
+            .returning(ErrorGroup::as_returning())
+            .get_result(conn)
+    }
+
+    /// Count one more occurrence of the error
+    pub fn record_occurrence(
+        conn: &mut PgConnection,
+        group: uuid::Uuid,
+        event_id: Option<&str>,
+        reopened: bool,
+    ) -> Result<ErrorGroup, diesel::result::Error> {
+        use crate::schema::error_groups::dsl as g;
+        diesel::update(g::error_groups.filter(g::id.eq(group)))
+            .set((
+                g::occurrences.eq(g::occurrences + 1),
+                g::reopened_count.eq(g::reopened_count + i32::from(reopened)),
+                g::last_seen_at.eq(chrono::Utc::now()),
+                g::last_event_id.eq(event_id),
+            ))
+            .returning(ErrorGroup::as_returning())
+            .get_result(conn)
+    }
+
+    /// Move the issue back to an open state, taking it out of the archive
+    pub fn reopen_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+        state: uuid::Uuid,
+    ) -> Result<Issue, diesel::result::Error> {
+        use crate::schema::issues::dsl as i;
+        diesel::update(i::issues.filter(i::id.eq(issue)))
+            .set((
+                i::workflow_state_id.eq(state),
+                i::archived_at.eq(None::<chrono::DateTime<chrono::Utc>>),
+                i::archive_batch_id.eq(None::<uuid::Uuid>),
+                i::updated_at.eq(chrono::Utc::now()),
+            ))
+            .returning(Issue::as_returning())
+            .get_result(conn)
+    }
+}
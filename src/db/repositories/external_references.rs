@@ -0,0 +1,75 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::external_reference::{ExternalReference, NewExternalReference};
+
+pub struct ExternalReferencesRepo;
+
+impl ExternalReferencesRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        reference: &NewExternalReference,
+    ) -> Result<ExternalReference, diesel::result::Error> {
+        diesel::insert_into(crate::schema::external_references::table)
+            .values(reference)
+            .returning(ExternalReference::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_in_issue(
+        conn: &mut PgConnection,
+        issue: Uuid,
+        reference: Uuid,
+    ) -> Result<Option<ExternalReference>, diesel::result::Error> {
+        use crate::schema::external_references::dsl as x;
+        x::external_references
+            .filter(x::id.eq(reference))
+            .filter(x::issue_id.eq(issue))
+            .select(ExternalReference::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        reference: Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::external_references::dsl as x;
+        diesel::delete(x::external_references.filter(x::id.eq(reference))).execute(conn)
+    }
+
+    /// References of an issue, oldest first
+    pub fn list_by_issue(
+        conn: &mut PgConnection,
+        issue: Uuid,
+    ) -> Result<Vec<ExternalReference>, diesel::result::Error> {
+        use crate::schema::external_references::dsl as x;
+        x::external_references
+            .filter(x::issue_id.eq(issue))
+            .order(x::created_at.asc())
+            .select(ExternalReference::as_select())
+            .load(conn)
+    }
+
+    /// References with this external id in the workspace; from one system
+    /// when `source` is given
+    pub fn find_by_external_id(
+        conn: &mut PgConnection,
+        ws: Uuid,
+        source: Option<&str>,
+        external_id: &str,
+    ) -> Result<Vec<ExternalReference>, diesel::result::Error> {
+        use crate::schema::external_references::dsl as x;
+        let mut query = x::external_references
+            .filter(x::workspace_id.eq(ws))
+            .filter(x::external_id.eq(external_id))
+            .into_boxed();
+        if let Some(source) = source {
+            query = query.filter(x::source.eq(source));
+        }
+        query
+            .order(x::source.asc())
+            .select(ExternalReference::as_select())
+            .load(conn)
+    }
+}
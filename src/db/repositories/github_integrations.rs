@@ -0,0 +1,51 @@
+use diesel::prelude::*;
+
+use crate::db::models::github_integration::{
+    GithubIntegration, NewGithubIntegration, UpdateGithubIntegration,
+};
+
+pub struct GithubIntegrationsRepo;
+
+impl GithubIntegrationsRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Option<GithubIntegration>, diesel::result::Error> {
+        use crate::schema::github_integrations::dsl as g;
+        g::github_integrations
+            .filter(g::workspace_id.eq(workspace))
+            .select(GithubIntegration::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_integration: &NewGithubIntegration,
+    ) -> Result<GithubIntegration, diesel::result::Error> {
+        diesel::insert_into(crate::schema::github_integrations::table)
+            .values(new_integration)
+            .returning(GithubIntegration::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        changes: &UpdateGithubIntegration,
+    ) -> Result<GithubIntegration, diesel::result::Error> {
+        use crate::schema::github_integrations::dsl as g;
+        diesel::update(g::github_integrations.filter(g::workspace_id.eq(workspace)))
+            .set(changes)
+            .returning(GithubIntegration::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::github_integrations::dsl as g;
+        diesel::delete(g::github_integrations.filter(g::workspace_id.eq(workspace))).execute(conn)
+    }
+}
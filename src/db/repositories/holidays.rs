@@ -0,0 +1,99 @@
+use diesel::prelude::*;
+
+use crate::db::models::holiday::{Holiday, NewHoliday, UpdateHoliday};
+
+pub struct HolidaysRepo;
+
+impl HolidaysRepo {
+    pub fn list_by_workspace(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        from: Option<chrono::NaiveDate>,
+        to: Option<chrono::NaiveDate>,
+    ) -> Result<Vec<Holiday>, diesel::result::Error> {
+        use crate::schema::workspace_holidays::dsl as h;
+        let mut query = h::workspace_holidays
+            .filter(h::workspace_id.eq(ws_id))
+            .into_boxed();
+        if let Some(from) = from {
+            query = query.filter(h::holiday_date.ge(from));
+        }
+        if let Some(to) = to {
+            query = query.filter(h::holiday_date.le(to));
+        }
+        query.order(h::holiday_date.asc()).load::<Holiday>(conn)
+    }
+
+    pub fn list_dates_between(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> Result<Vec<chrono::NaiveDate>, diesel::result::Error> {
+        use crate::schema::workspace_holidays::dsl as h;
+        h::workspace_holidays
+            .filter(h::workspace_id.eq(ws_id))
+            .filter(h::holiday_date.ge(from))
+            .filter(h::holiday_date.le(to))
+            .select(h::holiday_date)
+            .distinct()
+            .load::<chrono::NaiveDate>(conn)
+    }
+
+    pub fn exists_on_date(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        date: chrono::NaiveDate,
+        holiday_name: &str,
+    ) -> Result<bool, diesel::result::Error> {
+        use crate::schema::workspace_holidays::dsl as h;
+        diesel::select(diesel::dsl::exists(
+            h::workspace_holidays
+                .filter(h::workspace_id.eq(ws_id))
+                .filter(h::holiday_date.eq(date))
+                .filter(h::name.eq(holiday_name)),
+        ))
+        .get_result(conn)
+    }
+
+    pub fn find_by_id_in_workspace(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        holiday_id: uuid::Uuid,
+    ) -> Result<Option<Holiday>, diesel::result::Error> {
+        use crate::schema::workspace_holidays::dsl as h;
+        h::workspace_holidays
+            .filter(h::id.eq(holiday_id))
+            .filter(h::workspace_id.eq(ws_id))
+            .first::<Holiday>(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_holiday: &NewHoliday,
+    ) -> Result<Holiday, diesel::result::Error> {
+        diesel::insert_into(crate::schema::workspace_holidays::table)
+            .values(new_holiday)
+            .get_result(conn)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        holiday_id: uuid::Uuid,
+        changes: &UpdateHoliday,
+    ) -> Result<Holiday, diesel::result::Error> {
+        use crate::schema::workspace_holidays::dsl as h;
+        diesel::update(h::workspace_holidays.filter(h::id.eq(holiday_id)))
+            .set(changes)
+            .get_result(conn)
+    }
+
+    pub fn delete_by_id(
+        conn: &mut PgConnection,
+        holiday_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::workspace_holidays::dsl as h;
+        diesel::delete(h::workspace_holidays.filter(h::id.eq(holiday_id))).execute(conn)
+    }
+}
@@ -0,0 +1,255 @@
+use diesel::pg::Pg;
+use diesel::prelude::*;
+
+use crate::db::models::issue_archive::{
+    IssueArchiveBatch, IssueArchiveFilter, IssueArchiveSample, NewIssueArchiveBatch,
+    issue_archive_status,
+};
+use crate::schema::issues;
+
+pub struct IssueArchivesRepo;
+
+impl IssueArchivesRepo {
+    /// Unarchived issues of the workspace matching `filter`
+    fn matching<'a>(
+        ws_id: uuid::Uuid,
+        filter: &'a IssueArchiveFilter,
+    ) -> issues::BoxedQuery<'a, Pg> {
+        use crate::schema::teams;
+        let mut query = issues::table
+            .filter(
+                issues::team_id.eq_any(
+                    teams::table
+                        .filter(teams::workspace_id.eq(ws_id))
+                        .select(teams::id),
+                ),
+            )
+            .filter(issues::archived_at.is_null())
+            .into_boxed();
+        if let Some(team_id) = filter.team_id {
+            query = query.filter(issues::team_id.eq(team_id));
+        }
+        if let Some(project_id) = filter.project_id {
+            query = query.filter(issues::project_id.eq(project_id));
+        }
+        if let Some(cycle_id) = filter.cycle_id {
+            query = query.filter(issues::cycle_id.eq(cycle_id));
+        }
+        if let Some(assignee_id) = filter.assignee_id {
+            query = query.filter(issues::assignee_id.eq(assignee_id));
+        }
+        if let Some(state_id) = filter.workflow_state_id {
+            query = query.filter(issues::workflow_state_id.eq(state_id));
+        }
+        if let Some(priority) = &filter.priority {
+            query = query.filter(issues::priority.eq(priority));
+        }
+        if let Some(before) = filter.updated_before {
+            query = query.filter(issues::updated_at.lt(before));
+        }
+        query
+    }
+
+    pub fn count_matching(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        filter: &IssueArchiveFilter,
+    ) -> Result<i64, diesel::result::Error> {
+        Self::matching(ws_id, filter).count().get_result(conn)
+    }
+
+    /// Most recently updated matching issues
+    pub fn sample_matching(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        filter: &IssueArchiveFilter,
+        limit: i64,
+    ) -> Result<Vec<IssueArchiveSample>, diesel::result::Error> {
+        let rows: Vec<(
+            uuid::Uuid,
+            uuid::Uuid,
+            i32,
+            String,
+            chrono::DateTime<chrono::Utc>,
+        )> = Self::matching(ws_id, filter)
+            .select((
+                issues::id,
+                issues::team_id,
+                issues::issue_number,
+                issues::title,
+                issues::updated_at,
+            ))
+            .order((issues::updated_at.desc(), issues::id.asc()))
+            .limit(limit)
+            .load(conn)?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(id, team_id, issue_number, title, updated_at)| IssueArchiveSample {
+                    id,
+                    team_id,
+                    issue_number,
+                    title,
+                    updated_at,
+                },
+            )
+            .collect())
+    }
+
+    /// Archive every matching issue under `batch_id`; returns how many
+    pub fn archive_matching(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        filter: &IssueArchiveFilter,
+        batch_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        let ids: Vec<uuid::Uuid> = Self::matching(ws_id, filter)
+            .select(issues::id)
+            .load(conn)?;
+        diesel::update(issues::table.filter(issues::id.eq_any(&ids)))
+            .set((
+                issues::archived_at.eq(Some(chrono::Utc::now())),
+                issues::archive_batch_id.eq(Some(batch_id)),
+            ))
+            .execute(conn)
+    }
+
+    /// Archive the workspace's unarchived issues in a completed state that
+    /// were last updated before `updated_before`; returns their ids
+    pub fn archive_completed(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        updated_before: chrono::DateTime<chrono::Utc>,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::db::models::workflow::WorkflowStateCategory;
+        use crate::schema::{teams, workflow_states};
+        diesel::update(
+            issues::table
+                .filter(
+                    issues::team_id.eq_any(
+                        teams::table
+                            .filter(teams::workspace_id.eq(ws_id))
+                            .select(teams::id),
+                    ),
+                )
+                .filter(issues::archived_at.is_null())
+                .filter(issues::updated_at.lt(updated_before))
+                .filter(
+                    issues::workflow_state_id.eq_any(
+                        workflow_states::table
+                            .filter(workflow_states::category.eq(WorkflowStateCategory::Completed))
+                            .select(workflow_states::id.nullable()),
+                    ),
+                ),
+        )
+        .set(issues::archived_at.eq(Some(at)))
+        .returning(issues::id)
+        .get_results(conn)
+    }
+
+    /// Restore the issues a batch archived; returns how many
+    pub fn unarchive_batch(
+        conn: &mut PgConnection,
+        batch_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::update(issues::table.filter(issues::archive_batch_id.eq(batch_id)))
+            .set((
+                issues::archived_at.eq(None::<chrono::DateTime<chrono::Utc>>),
+                issues::archive_batch_id.eq(None::<uuid::Uuid>),
+            ))
+            .execute(conn)
+    }
+
+    pub fn insert_batch(
+        conn: &mut PgConnection,
+        new_batch: &NewIssueArchiveBatch,
+    ) -> Result<IssueArchiveBatch, diesel::result::Error> {
+        diesel::insert_into(crate::schema::issue_archive_batches::table)
+            .values(new_batch)
+            .returning(IssueArchiveBatch::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_batch_in_workspace(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        batch_id: uuid::Uuid,
+    ) -> Result<Option<IssueArchiveBatch>, diesel::result::Error> {
+        use crate::schema::issue_archive_batches::dsl as b;
+        b::issue_archive_batches
+            .filter(b::id.eq(batch_id))
+            .filter(b::workspace_id.eq(ws_id))
+            .select(IssueArchiveBatch::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Move a pending batch to running; `None` if another worker got it first
+    pub fn claim(
+        conn: &mut PgConnection,
+        batch_id: uuid::Uuid,
+    ) -> Result<Option<IssueArchiveBatch>, diesel::result::Error> {
+        use crate::schema::issue_archive_batches::dsl as b;
+        diesel::update(
+            b::issue_archive_batches
+                .filter(b::id.eq(batch_id))
+                .filter(b::status.eq(issue_archive_status::PENDING)),
+        )
+        .set(b::status.eq(issue_archive_status::RUNNING))
+        .returning(IssueArchiveBatch::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    pub fn complete(
+        conn: &mut PgConnection,
+        batch_id: uuid::Uuid,
+        count: i32,
+    ) -> Result<IssueArchiveBatch, diesel::result::Error> {
+        use crate::schema::issue_archive_batches::dsl as b;
+        diesel::update(b::issue_archive_batches.filter(b::id.eq(batch_id)))
+            .set((
+                b::status.eq(issue_archive_status::COMPLETED),
+                b::issue_count.eq(count),
+                b::completed_at.eq(Some(chrono::Utc::now())),
+            ))
+            .returning(IssueArchiveBatch::as_returning())
+            .get_result(conn)
+    }
+
+    /// Mark a completed batch undone; `None` if it was not completed
+    pub fn mark_undone(
+        conn: &mut PgConnection,
+        batch_id: uuid::Uuid,
+    ) -> Result<Option<IssueArchiveBatch>, diesel::result::Error> {
+        use crate::schema::issue_archive_batches::dsl as b;
+        diesel::update(
+            b::issue_archive_batches
+                .filter(b::id.eq(batch_id))
+                .filter(b::status.eq(issue_archive_status::COMPLETED)),
+        )
+        .set((
+            b::status.eq(issue_archive_status::UNDONE),
+            b::undone_at.eq(Some(chrono::Utc::now())),
+        ))
+        .returning(IssueArchiveBatch::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    /// Pending batches created before `before`, oldest first; picked up by
+    /// the worker in case their queue task was lost
+    pub fn list_stale_pending(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::issue_archive_batches::dsl as b;
+        b::issue_archive_batches
+            .filter(b::status.eq(issue_archive_status::PENDING))
+            .filter(b::created_at.lt(before))
+            .order(b::created_at.asc())
+            .select(b::id)
+            .load(conn)
+    }
+}
@@ -0,0 +1,103 @@
+use diesel::prelude::*;
+
+use crate::db::models::issue_form::{
+    IssueForm, IssueFormSubmission, NewIssueForm, NewIssueFormSubmission, UpdateIssueForm,
+};
+
+pub struct IssueFormsRepo;
+
+impl IssueFormsRepo {
+    pub fn list_by_team(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Vec<IssueForm>, diesel::result::Error> {
+        use crate::schema::issue_forms::dsl as f;
+        f::issue_forms
+            .filter(f::team_id.eq(team))
+            .select(IssueForm::as_select())
+            .order((f::name.asc(), f::created_at.asc()))
+            .load(conn)
+    }
+
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        form: uuid::Uuid,
+    ) -> Result<Option<IssueForm>, diesel::result::Error> {
+        use crate::schema::issue_forms::dsl as f;
+        f::issue_forms
+            .filter(f::id.eq(form))
+            .filter(f::workspace_id.eq(workspace))
+            .select(IssueForm::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Published form with this portal slug
+    pub fn find_public(
+        conn: &mut PgConnection,
+        slug: &str,
+    ) -> Result<Option<IssueForm>, diesel::result::Error> {
+        use crate::schema::issue_forms::dsl as f;
+        f::issue_forms
+            .filter(f::public_slug.eq(slug))
+            .filter(f::is_public.eq(true))
+            .select(IssueForm::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_form: &NewIssueForm,
+    ) -> Result<IssueForm, diesel::result::Error> {
+        diesel::insert_into(crate::schema::issue_forms::table)
+            .values(new_form)
+            .returning(IssueForm::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        form: uuid::Uuid,
+        changes: &UpdateIssueForm,
+    ) -> Result<IssueForm, diesel::result::Error> {
+        use crate::schema::issue_forms::dsl as f;
+        diesel::update(f::issue_forms.filter(f::id.eq(form)))
+            .set(changes)
+            .returning(IssueForm::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        form: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::issue_forms::dsl as f;
+        diesel::delete(f::issue_forms.filter(f::id.eq(form))).execute(conn)
+    }
+
+    pub fn insert_submission(
+        conn: &mut PgConnection,
+        submission: &NewIssueFormSubmission,
+    ) -> Result<IssueFormSubmission, diesel::result::Error> {
+        diesel::insert_into(crate::schema::issue_form_submissions::table)
+            .values(submission)
+            .returning(IssueFormSubmission::as_returning())
+            .get_result(conn)
+    }
+
+    /// Those of the labels that exist in the workspace
+    pub fn existing_labels(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        labels: &[uuid::Uuid],
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::labels::dsl as l;
+        l::labels
+            .filter(l::workspace_id.eq(workspace))
+            .filter(l::id.eq_any(labels))
+            .select(l::id)
+            .load(conn)
+    }
+}
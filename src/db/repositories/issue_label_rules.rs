@@ -0,0 +1,134 @@
+use diesel::prelude::*;
+
+use crate::db::models::issue_label_rule::{
+    IssueLabelRule, IssueLabelRuleApplication, NewIssueLabelRule, NewIssueLabelRuleApplication,
+    UpdateIssueLabelRule,
+};
+
+pub struct IssueLabelRulesRepo;
+
+impl IssueLabelRulesRepo {
+    pub fn list_by_team(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Vec<IssueLabelRule>, diesel::result::Error> {
+        use crate::schema::issue_label_rules::dsl as r;
+        r::issue_label_rules
+            .filter(r::team_id.eq(team))
+            .select(IssueLabelRule::as_select())
+            .order((r::position.asc(), r::created_at.asc()))
+            .load(conn)
+    }
+
+    /// Enabled rules of the team in evaluation order
+    pub fn list_enabled_by_team(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Vec<IssueLabelRule>, diesel::result::Error> {
+        use crate::schema::issue_label_rules::dsl as r;
+        r::issue_label_rules
+            .filter(r::team_id.eq(team))
+            .filter(r::enabled.eq(true))
+            .select(IssueLabelRule::as_select())
+            .order((r::position.asc(), r::created_at.asc()))
+            .load(conn)
+    }
+
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        rule: uuid::Uuid,
+    ) -> Result<Option<IssueLabelRule>, diesel::result::Error> {
+        use crate::schema::issue_label_rules::dsl as r;
+        r::issue_label_rules
+            .filter(r::id.eq(rule))
+            .filter(r::workspace_id.eq(workspace))
+            .select(IssueLabelRule::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Position after the team's last rule
+    pub fn next_position(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<i32, diesel::result::Error> {
+        use crate::schema::issue_label_rules::dsl as r;
+        let last: Option<i32> = r::issue_label_rules
+            .filter(r::team_id.eq(team))
+            .select(diesel::dsl::max(r::position))
+            .first(conn)?;
+        Ok(last.map_or(0, |position| position + 1))
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_rule: &NewIssueLabelRule,
+    ) -> Result<IssueLabelRule, diesel::result::Error> {
+        diesel::insert_into(crate::schema::issue_label_rules::table)
+            .values(new_rule)
+            .returning(IssueLabelRule::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        rule: uuid::Uuid,
+        changes: &UpdateIssueLabelRule,
+    ) -> Result<IssueLabelRule, diesel::result::Error> {
+        use crate::schema::issue_label_rules::dsl as r;
+        diesel::update(r::issue_label_rules.filter(r::id.eq(rule)))
+            .set(changes)
+            .returning(IssueLabelRule::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        rule: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::issue_label_rules::dsl as r;
+        diesel::delete(r::issue_label_rules.filter(r::id.eq(rule))).execute(conn)
+    }
+
+    pub fn insert_applications(
+        conn: &mut PgConnection,
+        applications: &[NewIssueLabelRuleApplication],
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::insert_into(crate::schema::issue_label_rule_applications::table)
+            .values(applications)
+            .execute(conn)
+    }
+
+    pub fn list_applications_by_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+    ) -> Result<Vec<IssueLabelRuleApplication>, diesel::result::Error> {
+        use crate::schema::issue_label_rule_applications::dsl as a;
+        a::issue_label_rule_applications
+            .filter(a::issue_id.eq(issue))
+            .order((a::applied_at.asc(), a::id.asc()))
+            .select(IssueLabelRuleApplication::as_select())
+            .load(conn)
+    }
+
+    /// Add labels to an issue, skipping those it already has
+    pub fn add_issue_labels(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+        label_ids: &[uuid::Uuid],
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::db::models::issue::NewIssueLabel;
+        let rows: Vec<NewIssueLabel> = label_ids
+            .iter()
+            .map(|&label_id| NewIssueLabel {
+                issue_id: issue,
+                label_id,
+            })
+            .collect();
+        diesel::insert_into(crate::schema::issue_labels::table)
+            .values(&rows)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+}
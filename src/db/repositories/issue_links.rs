@@ -0,0 +1,68 @@
+use diesel::prelude::*;
+
+use crate::db::models::issue::Issue;
+use crate::db::models::issue_link::{IssueLink, NewIssueLink};
+
+pub struct IssueLinksRepo;
+
+impl IssueLinksRepo {
+    /// Insert a link, or refresh its url, title, state and author if the
+    /// issue is already linked to the same commit or pull request
+    pub fn upsert(
+        conn: &mut PgConnection,
+        link: &NewIssueLink,
+    ) -> Result<IssueLink, diesel::result::Error> {
+        use crate::schema::issue_links::dsl as l;
+        diesel::insert_into(l::issue_links)
+            .values(link)
+            .on_conflict((l::issue_id, l::provider, l::kind, l::external_id))
+            .do_update()
+            .set((
+                l::url.eq(&link.url),
+                l::title.eq(&link.title),
+                l::state.eq(&link.state),
+                l::author.eq(&link.author),
+                l::updated_at.eq(chrono::Utc::now()),
+            ))
+            .returning(IssueLink::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_by_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+    ) -> Result<Vec<IssueLink>, diesel::result::Error> {
+        use crate::schema::issue_links::dsl as l;
+        l::issue_links
+            .filter(l::issue_id.eq(issue))
+            .order(l::created_at.desc())
+            .select(IssueLink::as_select())
+            .load(conn)
+    }
+
+    /// Id and key of every team in the workspace
+    pub fn team_keys(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Vec<(uuid::Uuid, String)>, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        t::teams
+            .filter(t::workspace_id.eq(workspace))
+            .select((t::id, t::team_key))
+            .load(conn)
+    }
+
+    pub fn find_issue(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+        number: i32,
+    ) -> Result<Option<Issue>, diesel::result::Error> {
+        use crate::schema::issues::dsl as i;
+        i::issues
+            .filter(i::team_id.eq(team))
+            .filter(i::issue_number.eq(number))
+            .select(Issue::as_select())
+            .first(conn)
+            .optional()
+    }
+}
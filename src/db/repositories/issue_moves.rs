@@ -0,0 +1,50 @@
+use diesel::prelude::*;
+
+use crate::db::models::issue_move::{IssueMove, NewIssueMove};
+
+pub struct IssueMovesRepo;
+
+impl IssueMovesRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_move: &NewIssueMove,
+    ) -> Result<IssueMove, diesel::result::Error> {
+        diesel::insert_into(crate::schema::issue_moves::table)
+            .values(new_move)
+            .returning(IssueMove::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_by_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+    ) -> Result<Vec<IssueMove>, diesel::result::Error> {
+        use crate::schema::issue_moves::dsl as m;
+        m::issue_moves
+            .filter(m::issue_id.eq(issue))
+            .order(m::moved_at.asc())
+            .select(IssueMove::as_select())
+            .load(conn)
+    }
+
+    /// Issue of the workspace most recently moved away from `key`
+    pub fn find_moved_from(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        key: &str,
+    ) -> Result<Option<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::issue_moves::dsl as m;
+        use crate::schema::issues::dsl as i;
+        use crate::schema::teams::dsl as t;
+        let workspace_issues = i::issues
+            .filter(i::team_id.eq_any(t::teams.filter(t::workspace_id.eq(workspace)).select(t::id)))
+            .select(i::id);
+        m::issue_moves
+            .filter(m::from_key.eq(key))
+            .filter(m::issue_id.eq_any(workspace_issues))
+            .order(m::moved_at.desc())
+            .select(m::issue_id)
+            .first(conn)
+            .optional()
+    }
+}
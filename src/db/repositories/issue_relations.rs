@@ -0,0 +1,93 @@
+use diesel::prelude::*;
+
+use crate::db::models::issue::Issue;
+use crate::db::models::issue_relation::{IssueRelation, NewIssueRelation};
+
+pub struct IssueRelationsRepo;
+
+impl IssueRelationsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        relation: &NewIssueRelation,
+    ) -> Result<IssueRelation, diesel::result::Error> {
+        diesel::insert_into(crate::schema::issue_relations::table)
+            .values(relation)
+            .returning(IssueRelation::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_by_id(
+        conn: &mut PgConnection,
+        relation: uuid::Uuid,
+    ) -> Result<Option<IssueRelation>, diesel::result::Error> {
+        use crate::schema::issue_relations::dsl as r;
+        r::issue_relations
+            .filter(r::id.eq(relation))
+            .select(IssueRelation::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        relation: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::issue_relations::dsl as r;
+        diesel::delete(r::issue_relations.filter(r::id.eq(relation))).execute(conn)
+    }
+
+    /// Whether a stored relation of this type links `from` to `to`
+    pub fn exists(
+        conn: &mut PgConnection,
+        from: uuid::Uuid,
+        to: uuid::Uuid,
+        relation_type: &str,
+    ) -> Result<bool, diesel::result::Error> {
+        use crate::schema::issue_relations::dsl as r;
+        diesel::select(diesel::dsl::exists(
+            r::issue_relations
+                .filter(r::issue_id.eq(from))
+                .filter(r::related_issue_id.eq(to))
+                .filter(r::relation_type.eq(relation_type)),
+        ))
+        .get_result(conn)
+    }
+
+    /// Relations on either side of the issue, each with the issue at the
+    /// other end
+    pub fn list_for_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+    ) -> Result<Vec<(IssueRelation, Issue)>, diesel::result::Error> {
+        use crate::schema::issue_relations::dsl as r;
+        use crate::schema::issues;
+        let mut outgoing: Vec<(IssueRelation, Issue)> = r::issue_relations
+            .inner_join(issues::table.on(issues::id.eq(r::related_issue_id)))
+            .filter(r::issue_id.eq(issue))
+            .select((IssueRelation::as_select(), Issue::as_select()))
+            .load(conn)?;
+        let incoming: Vec<(IssueRelation, Issue)> = r::issue_relations
+            .inner_join(issues::table.on(issues::id.eq(r::issue_id)))
+            .filter(r::related_issue_id.eq(issue))
+            .select((IssueRelation::as_select(), Issue::as_select()))
+            .load(conn)?;
+        outgoing.extend(incoming);
+        outgoing.sort_by_key(|(relation, _)| relation.created_at);
+        Ok(outgoing)
+    }
+
+    /// Stored edges of one type leaving any of the given issues, as
+    /// (issue, related issue)
+    pub fn edges_from(
+        conn: &mut PgConnection,
+        from: &[uuid::Uuid],
+        relation_type: &str,
+    ) -> Result<Vec<(uuid::Uuid, uuid::Uuid)>, diesel::result::Error> {
+        use crate::schema::issue_relations::dsl as r;
+        r::issue_relations
+            .filter(r::issue_id.eq_any(from))
+            .filter(r::relation_type.eq(relation_type))
+            .select((r::issue_id, r::related_issue_id))
+            .load(conn)
+    }
+}
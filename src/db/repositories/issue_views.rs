@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+
+use crate::db::models::auth::User;
+use crate::db::models::issue_view::{IssueView, NewIssueView};
+
+pub struct IssueViewsRepo;
+
+impl IssueViewsRepo {
+    /// Insert or bump the user's view of an issue
+    pub fn record(
+        conn: &mut PgConnection,
+        new: &NewIssueView,
+    ) -> Result<IssueView, diesel::result::Error> {
+        use crate::schema::issue_views::dsl as v;
+        diesel::insert_into(v::issue_views)
+            .values(new)
+            .on_conflict((v::user_id, v::issue_id))
+            .do_update()
+            .set((
+                v::last_viewed_at.eq(new.last_viewed_at),
+                v::view_count.eq(v::view_count + 1),
+            ))
+            .returning(IssueView::as_returning())
+            .get_result(conn)
+    }
+
+    /// Viewers of an issue, most recent first
+    pub fn list_by_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+    ) -> Result<Vec<(IssueView, User)>, diesel::result::Error> {
+        use crate::schema::issue_views::dsl as v;
+        use crate::schema::users;
+        v::issue_views
+            .inner_join(users::table)
+            .filter(v::issue_id.eq(issue))
+            .order(v::last_viewed_at.desc())
+            .select((IssueView::as_select(), users::all_columns))
+            .load::<(IssueView, User)>(conn)
+    }
+
+    /// When the user last viewed each of the given issues
+    pub fn last_viewed_map(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<HashMap<uuid::Uuid, chrono::DateTime<chrono::Utc>>, diesel::result::Error> {
+        use crate::schema::issue_views::dsl as v;
+        let rows = v::issue_views
+            .filter(v::user_id.eq(user))
+            .filter(v::issue_id.eq_any(issue_ids))
+            .select((v::issue_id, v::last_viewed_at))
+            .load::<(uuid::Uuid, chrono::DateTime<chrono::Utc>)>(conn)?;
+        Ok(rows.into_iter().collect())
+    }
+}
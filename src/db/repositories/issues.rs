@@ -1,6 +1,20 @@
 use diesel::prelude::*;
 
 use crate::db::models::issue::{Issue, NewIssue};
+use crate::db::models::search::IssueSearchFilters;
+
+/// Weighted document searched by full-text queries. Must match the expression
+/// of `idx_issues_search` or the index is not used.
+const ISSUE_SEARCH_VECTOR: &str = "(setweight(to_tsvector('simple', i.title), 'A') \
+     || setweight(to_tsvector('simple', coalesce(i.description, '')), 'B'))";
+
+#[derive(QueryableByName)]
+struct SearchRow {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    id: uuid::Uuid,
+    #[diesel(sql_type = diesel::sql_types::Float4)]
+    rank: f32,
+}
 
 pub struct IssueRepo;
 
@@ -16,12 +30,18 @@ impl IssueRepo {
             .optional()
     }
 
+    /// Archived issues are left out unless `include_archived` is set
     pub fn list_by_workspace(
         conn: &mut PgConnection,
         _workspace_id: uuid::Uuid,
+        include_archived: bool,
     ) -> Result<Vec<Issue>, diesel::result::Error> {
         use crate::schema::issues::dsl::*;
-        issues.order(created_at.desc()).load::<Issue>(conn)
+        let mut query = issues.into_boxed();
+        if !include_archived {
+            query = query.filter(archived_at.is_null());
+        }
+        query.order(created_at.desc()).load::<Issue>(conn)
     }
 
     pub fn list_by_team(
@@ -31,10 +51,39 @@ impl IssueRepo {
         use crate::schema::issues::dsl::*;
         issues
             .filter(team_id.eq(target_team_id))
+            .filter(archived_at.is_null())
             .order(created_at.desc())
             .load::<Issue>(conn)
     }
 
+    pub fn list_by_teams(
+        conn: &mut PgConnection,
+        team_ids: &[uuid::Uuid],
+    ) -> Result<Vec<Issue>, diesel::result::Error> {
+        use crate::schema::issues::dsl::*;
+        issues
+            .filter(team_id.eq_any(team_ids))
+            .filter(archived_at.is_null())
+            .order(created_at.desc())
+            .load::<Issue>(conn)
+    }
+
+    /// Unarchived issues of the team in one of `state_ids`, oldest first
+    pub fn list_in_states(
+        conn: &mut PgConnection,
+        target_team_id: uuid::Uuid,
+        state_ids: &[uuid::Uuid],
+    ) -> Result<Vec<Issue>, diesel::result::Error> {
+        use crate::schema::issues::dsl::*;
+        issues
+            .filter(team_id.eq(target_team_id))
+            .filter(workflow_state_id.eq_any(state_ids))
+            .filter(archived_at.is_null())
+            .order(created_at.asc())
+            .select(Issue::as_select())
+            .load::<Issue>(conn)
+    }
+
     pub fn list_by_project(
         conn: &mut PgConnection,
         target_project_id: uuid::Uuid,
@@ -42,6 +91,7 @@ impl IssueRepo {
         use crate::schema::issues::dsl::*;
         issues
             .filter(project_id.eq(target_project_id))
+            .filter(archived_at.is_null())
             .order(created_at.desc())
             .load::<Issue>(conn)
     }
@@ -53,6 +103,7 @@ impl IssueRepo {
         use crate::schema::issues::dsl::*;
         issues
             .filter(assignee_id.eq(target_assignee_id))
+            .filter(archived_at.is_null())
             .order(created_at.desc())
             .load::<Issue>(conn)
     }
@@ -66,16 +117,84 @@ impl IssueRepo {
         let pattern = format!("%{}%", search_term);
         issues
             .filter(title.like(pattern))
+            .filter(archived_at.is_null())
             .order(created_at.desc())
             .load::<Issue>(conn)
     }
 
+    /// Full-text match over title and description within a workspace, best
+    /// matches first. `query` uses web search syntax (quotes, `or`, `-word`).
+    pub fn search_full_text(
+        conn: &mut PgConnection,
+        workspace_id: uuid::Uuid,
+        query: &str,
+        filters: &IssueSearchFilters,
+        limit: i64,
+    ) -> Result<Vec<(uuid::Uuid, f32)>, diesel::result::Error> {
+        use diesel::sql_types::{BigInt, Nullable, Text, Uuid};
+        let sql = format!(
+            "SELECT i.id, ts_rank({vector}, q) AS rank \
+             FROM issues i \
+             JOIN teams t ON t.id = i.team_id, \
+                  websearch_to_tsquery('simple', $1) q \
+             WHERE t.workspace_id = $2 \
+               AND i.archived_at IS NULL \
+               AND {vector} @@ q \
+               AND ($3::uuid IS NULL OR i.team_id = $3) \
+               AND ($4::uuid IS NULL OR i.project_id = $4) \
+               AND ($5::uuid IS NULL OR i.assignee_id = $5) \
+               AND ($6::text IS NULL OR i.priority = $6) \
+             ORDER BY rank DESC, i.updated_at DESC \
+             LIMIT $7",
+            vector = ISSUE_SEARCH_VECTOR
+        );
+        let rows: Vec<SearchRow> = diesel::sql_query(sql)
+            .bind::<Text, _>(query)
+            .bind::<Uuid, _>(workspace_id)
+            .bind::<Nullable<Uuid>, _>(filters.team_id)
+            .bind::<Nullable<Uuid>, _>(filters.project_id)
+            .bind::<Nullable<Uuid>, _>(filters.assignee_id)
+            .bind::<Nullable<Text>, _>(filters.priority.as_deref())
+            .bind::<BigInt, _>(limit)
+            .load(conn)?;
+        Ok(rows.into_iter().map(|row| (row.id, row.rank)).collect())
+    }
+
+    pub fn list_by_ids(
+        conn: &mut PgConnection,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<Vec<Issue>, diesel::result::Error> {
+        use crate::schema::issues::dsl::*;
+        issues.filter(id.eq_any(issue_ids)).load::<Issue>(conn)
+    }
+
+    /// Insert an issue with the next number of its team
     pub fn insert(
         conn: &mut PgConnection,
         new_issue: &NewIssue,
     ) -> Result<Issue, diesel::result::Error> {
+        let number = Self::next_issue_number(conn, new_issue.team_id)?;
         diesel::insert_into(crate::schema::issues::table)
-            .values(new_issue)
+            .values((new_issue, crate::schema::issues::issue_number.eq(number)))
+            .get_result(conn)
+    }
+
+    /// Take the next issue number of a team. The counter row stays locked
+    /// until the transaction ends, so numbers are never handed out twice.
+    pub fn next_issue_number(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<i32, diesel::result::Error> {
+        use crate::schema::team_issue_counters::dsl as c;
+        diesel::insert_into(c::team_issue_counters)
+            .values((c::team_id.eq(team), c::last_issue_number.eq(1)))
+            .on_conflict(c::team_id)
+            .do_update()
+            .set((
+                c::last_issue_number.eq(c::last_issue_number + 1),
+                c::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(c::last_issue_number)
             .get_result(conn)
     }
 
@@ -157,6 +276,17 @@ impl IssueRepo {
         issues.filter(id.eq(issue_id)).first::<Issue>(conn)
     }
 
+    pub fn set_priority(
+        conn: &mut PgConnection,
+        issue_id: uuid::Uuid,
+        value: &str,
+    ) -> Result<Issue, diesel::result::Error> {
+        use crate::schema::issues::dsl as i;
+        diesel::update(i::issues.filter(i::id.eq(issue_id)))
+            .set((i::priority.eq(value), i::updated_at.eq(diesel::dsl::now)))
+            .get_result(conn)
+    }
+
     pub fn delete_by_id(
         conn: &mut PgConnection,
         issue_id: uuid::Uuid,
@@ -176,4 +306,31 @@ impl IssueRepo {
             .first::<Issue>(conn)
             .optional()
     }
+
+    /// Archive one issue outside any bulk batch; returns the rows changed
+    pub fn archive(
+        conn: &mut PgConnection,
+        issue_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::issues::dsl::*;
+        diesel::update(issues.filter(id.eq(issue_id)).filter(archived_at.is_null()))
+            .set(archived_at.eq(Some(chrono::Utc::now())))
+            .execute(conn)
+    }
+
+    /// Whether the issue belongs to a team in the workspace
+    pub fn exists_in_workspace(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        issue: uuid::Uuid,
+    ) -> Result<bool, diesel::result::Error> {
+        use crate::schema::{issues, teams};
+        diesel::select(diesel::dsl::exists(
+            issues::table
+                .inner_join(teams::table)
+                .filter(issues::id.eq(issue))
+                .filter(teams::workspace_id.eq(ws_id)),
+        ))
+        .get_result(conn)
+    }
 }
@@ -55,6 +55,32 @@ impl LabelRepo {
             .load::<Label>(conn)
     }
 
+    pub fn list_by_issue(
+        conn: &mut PgConnection,
+        issue: uuid::Uuid,
+    ) -> Result<Vec<Label>, diesel::result::Error> {
+        use crate::schema::{issue_labels, labels};
+        labels::table
+            .inner_join(issue_labels::table.on(issue_labels::label_id.eq(labels::id)))
+            .filter(issue_labels::issue_id.eq(issue))
+            .select(labels::all_columns)
+            .load::<Label>(conn)
+    }
+
+    /// Labels of each of the given issues, as `(issue_id, label)` pairs
+    pub fn list_by_issues(
+        conn: &mut PgConnection,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<Vec<(uuid::Uuid, Label)>, diesel::result::Error> {
+        use crate::schema::{issue_labels, labels};
+        labels::table
+            .inner_join(issue_labels::table.on(issue_labels::label_id.eq(labels::id)))
+            .filter(issue_labels::issue_id.eq_any(issue_ids))
+            .order(labels::name.asc())
+            .select((issue_labels::issue_id, labels::all_columns))
+            .load::<(uuid::Uuid, Label)>(conn)
+    }
+
     pub fn find_by_id_in_workspace(
         conn: &mut PgConnection,
         ws_id: uuid::Uuid,
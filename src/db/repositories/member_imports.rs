@@ -0,0 +1,148 @@
+use diesel::prelude::*;
+
+use crate::db::models::member_import::{
+    MemberImport, MemberImportRow, NewMemberImport, NewMemberImportRow, member_import_row_status,
+    member_import_status,
+};
+
+pub struct MemberImportsRepo;
+
+impl MemberImportsRepo {
+    pub fn find_by_hash(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        hash: &str,
+    ) -> Result<Option<MemberImport>, diesel::result::Error> {
+        use crate::schema::member_imports::dsl as mi;
+        mi::member_imports
+            .filter(mi::workspace_id.eq(ws_id))
+            .filter(mi::content_hash.eq(hash))
+            .select(MemberImport::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        import_id: uuid::Uuid,
+    ) -> Result<Option<MemberImport>, diesel::result::Error> {
+        use crate::schema::member_imports::dsl as mi;
+        mi::member_imports
+            .filter(mi::id.eq(import_id))
+            .filter(mi::workspace_id.eq(ws_id))
+            .select(MemberImport::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_import: &NewMemberImport,
+        rows: &[NewMemberImportRow],
+    ) -> Result<MemberImport, diesel::result::Error> {
+        conn.transaction(|conn| {
+            let import: MemberImport = diesel::insert_into(crate::schema::member_imports::table)
+                .values(new_import)
+                .returning(MemberImport::as_returning())
+                .get_result(conn)?;
+            let rows: Vec<NewMemberImportRow> = rows
+                .iter()
+                .cloned()
+                .map(|row| NewMemberImportRow {
+                    import_id: import.id,
+                    ..row
+                })
+                .collect();
+            diesel::insert_into(crate::schema::member_import_rows::table)
+                .values(&rows)
+                .execute(conn)?;
+            Ok(import)
+        })
+    }
+
+    pub fn list_rows(
+        conn: &mut PgConnection,
+        import_id: uuid::Uuid,
+    ) -> Result<Vec<MemberImportRow>, diesel::result::Error> {
+        use crate::schema::member_import_rows::dsl as r;
+        r::member_import_rows
+            .filter(r::import_id.eq(import_id))
+            .order(r::row_number.asc())
+            .select(MemberImportRow::as_select())
+            .load(conn)
+    }
+
+    pub fn list_pending_rows(
+        conn: &mut PgConnection,
+        import_id: uuid::Uuid,
+    ) -> Result<Vec<MemberImportRow>, diesel::result::Error> {
+        use crate::schema::member_import_rows::dsl as r;
+        r::member_import_rows
+            .filter(r::import_id.eq(import_id))
+            .filter(r::status.eq(member_import_row_status::PENDING))
+            .order(r::row_number.asc())
+            .select(MemberImportRow::as_select())
+            .load(conn)
+    }
+
+    /// Move a pending import to running; `None` if another worker got it first
+    pub fn claim(
+        conn: &mut PgConnection,
+        import_id: uuid::Uuid,
+    ) -> Result<Option<MemberImport>, diesel::result::Error> {
+        use crate::schema::member_imports::dsl as mi;
+        diesel::update(
+            mi::member_imports
+                .filter(mi::id.eq(import_id))
+                .filter(mi::status.eq(member_import_status::PENDING)),
+        )
+        .set((
+            mi::status.eq(member_import_status::RUNNING),
+            mi::started_at.eq(Some(chrono::Utc::now())),
+        ))
+        .returning(MemberImport::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    pub fn set_row_result(
+        conn: &mut PgConnection,
+        row_id: uuid::Uuid,
+        status: &str,
+        message: Option<String>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::member_import_rows::dsl as r;
+        diesel::update(r::member_import_rows.filter(r::id.eq(row_id)))
+            .set((r::status.eq(status), r::message.eq(message)))
+            .execute(conn)
+    }
+
+    pub fn complete(
+        conn: &mut PgConnection,
+        import_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::member_imports::dsl as mi;
+        diesel::update(mi::member_imports.filter(mi::id.eq(import_id)))
+            .set((
+                mi::status.eq(member_import_status::COMPLETED),
+                mi::completed_at.eq(Some(chrono::Utc::now())),
+            ))
+            .execute(conn)
+    }
+
+    /// Pending imports created before `before`, oldest first; picked up by
+    /// the worker in case their queue task was lost
+    pub fn list_stale_pending(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::member_imports::dsl as mi;
+        mi::member_imports
+            .filter(mi::status.eq(member_import_status::PENDING))
+            .filter(mi::created_at.lt(before))
+            .order(mi::created_at.asc())
+            .select(mi::id)
+            .load(conn)
+    }
+}
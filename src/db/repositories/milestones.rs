@@ -0,0 +1,162 @@
+use diesel::prelude::*;
+
+use crate::db::models::issue::Issue;
+use crate::db::models::milestone::{
+    Milestone, MilestoneIssueProgress, NewMilestone, UpdateMilestone,
+};
+
+pub struct MilestonesRepo;
+
+impl MilestonesRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_milestone: &NewMilestone,
+    ) -> Result<Milestone, diesel::result::Error> {
+        diesel::insert_into(crate::schema::milestones::table)
+            .values(new_milestone)
+            .returning(Milestone::as_returning())
+            .get_result(conn)
+    }
+
+    /// Milestones of a project, by target date with undated ones last
+    pub fn list_by_project(
+        conn: &mut PgConnection,
+        project: uuid::Uuid,
+    ) -> Result<Vec<Milestone>, diesel::result::Error> {
+        use crate::schema::milestones::dsl as m;
+        m::milestones
+            .filter(m::project_id.eq(project))
+            .order((m::target_date.asc().nulls_last(), m::created_at.asc()))
+            .select(Milestone::as_select())
+            .load(conn)
+    }
+
+    pub fn find_in_project(
+        conn: &mut PgConnection,
+        project: uuid::Uuid,
+        milestone_id: uuid::Uuid,
+    ) -> Result<Option<Milestone>, diesel::result::Error> {
+        use crate::schema::milestones::dsl as m;
+        m::milestones
+            .filter(m::id.eq(milestone_id))
+            .filter(m::project_id.eq(project))
+            .select(Milestone::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        milestone_id: uuid::Uuid,
+        changes: &UpdateMilestone,
+    ) -> Result<Milestone, diesel::result::Error> {
+        use crate::schema::milestones::dsl as m;
+        diesel::update(m::milestones.filter(m::id.eq(milestone_id)))
+            .set(changes)
+            .returning(Milestone::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete_by_id(
+        conn: &mut PgConnection,
+        milestone_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::milestones::dsl as m;
+        diesel::delete(m::milestones.filter(m::id.eq(milestone_id))).execute(conn)
+    }
+
+    pub fn list_issues(
+        conn: &mut PgConnection,
+        milestone: uuid::Uuid,
+    ) -> Result<Vec<Issue>, diesel::result::Error> {
+        use crate::schema::{issues, milestone_issues};
+        milestone_issues::table
+            .inner_join(issues::table)
+            .filter(milestone_issues::milestone_id.eq(milestone))
+            .order(issues::issue_number.asc())
+            .select(Issue::as_select())
+            .load(conn)
+    }
+
+    /// Attached issues with the state category and timestamps stats need
+    pub fn issue_progress(
+        conn: &mut PgConnection,
+        milestone: uuid::Uuid,
+    ) -> Result<Vec<MilestoneIssueProgress>, diesel::result::Error> {
+        use crate::schema::{issues, milestone_issues, workflow_states};
+        milestone_issues::table
+            .inner_join(issues::table.left_join(workflow_states::table))
+            .filter(milestone_issues::milestone_id.eq(milestone))
+            .select((
+                milestone_issues::issue_id,
+                workflow_states::category.nullable(),
+                milestone_issues::added_at,
+                issues::updated_at,
+            ))
+            .load(conn)
+    }
+
+    /// How many of `issue_ids` belong to the project
+    pub fn count_project_issues(
+        conn: &mut PgConnection,
+        project: uuid::Uuid,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::issues::dsl as i;
+        i::issues
+            .filter(i::id.eq_any(issue_ids))
+            .filter(i::project_id.eq(project))
+            .count()
+            .get_result(conn)
+    }
+
+    /// Attach issues, moving them out of any milestone they were in. Issues
+    /// already in this milestone keep their original `added_at`.
+    pub fn attach(
+        conn: &mut PgConnection,
+        milestone: uuid::Uuid,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::milestone_issues::dsl as mi;
+        let attached: Vec<uuid::Uuid> = mi::milestone_issues
+            .filter(mi::milestone_id.eq(milestone))
+            .filter(mi::issue_id.eq_any(issue_ids))
+            .select(mi::issue_id)
+            .load(conn)?;
+        let now = chrono::Utc::now();
+        let rows: Vec<_> = issue_ids
+            .iter()
+            .filter(|issue| !attached.contains(issue))
+            .map(|issue| {
+                (
+                    mi::issue_id.eq(*issue),
+                    mi::milestone_id.eq(milestone),
+                    mi::added_at.eq(now),
+                )
+            })
+            .collect();
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        diesel::insert_into(mi::milestone_issues)
+            .values(&rows)
+            .on_conflict(mi::issue_id)
+            .do_update()
+            .set((mi::milestone_id.eq(milestone), mi::added_at.eq(now)))
+            .execute(conn)
+    }
+
+    pub fn detach(
+        conn: &mut PgConnection,
+        milestone: uuid::Uuid,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::milestone_issues::dsl as mi;
+        diesel::delete(
+            mi::milestone_issues
+                .filter(mi::milestone_id.eq(milestone))
+                .filter(mi::issue_id.eq_any(issue_ids)),
+        )
+        .execute(conn)
+    }
+}
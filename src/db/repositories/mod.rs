@@ -1,11 +1,61 @@
+pub mod api_keys;
+pub mod api_tokens;
+pub mod app_installations;
+pub mod attachments;
+pub mod audit_log;
 pub mod auth;
+pub mod auto_close_policies;
+pub mod automations;
+pub mod board_positions;
+pub mod channel_permissions;
+pub mod collection_versions;
+pub mod comment_flags;
 pub mod comments;
+pub mod content_reports;
+pub mod cross_workspace_relations;
+pub mod custom_emojis;
+pub mod cycle_settings;
 pub mod cycles;
+pub mod dashboard;
+pub mod delete_confirmations;
+pub mod error_tracking;
+pub mod external_references;
+pub mod github_integrations;
+pub mod holidays;
 pub mod invitations;
+pub mod issue_archives;
+pub mod issue_forms;
+pub mod issue_label_rules;
+pub mod issue_links;
+pub mod issue_moves;
+pub mod issue_relations;
+pub mod issue_views;
 pub mod issues;
 pub mod labels;
+pub mod member_imports;
+pub mod milestones;
+pub mod notifications;
+pub mod oauth_apps;
+pub mod organizations;
+pub mod project_costs;
 pub mod project_statuses;
 pub mod projects;
+pub mod push;
+pub mod scheduled_jobs;
+pub mod service_accounts;
+pub mod sync;
+pub mod teams;
+pub mod telemetry;
+pub mod upload_sessions;
+pub mod user_identities;
+pub mod user_preferences;
+pub mod webhooks;
+pub mod websocket_dead_letters;
+pub mod websocket_sessions;
 pub mod workflows;
+pub mod workspace_email_domains;
+pub mod workspace_imports;
 pub mod workspace_members;
+pub mod workspace_resets;
+pub mod workspace_settings;
 pub mod workspaces;
@@ -0,0 +1,202 @@
+use diesel::prelude::*;
+
+use crate::db::models::notification::{NewNotification, Notification};
+
+pub struct NotificationsRepo;
+
+impl NotificationsRepo {
+    /// Latest unread notification for the same recipient/event/entity updated since `since`
+    pub fn find_coalescable(
+        conn: &mut PgConnection,
+        new: &NewNotification,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<Notification>, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        n::notifications
+            .filter(n::recipient_id.eq(new.recipient_id))
+            .filter(n::event_type.eq(&new.event_type))
+            .filter(n::entity_type.eq(&new.entity_type))
+            .filter(n::entity_id.eq(new.entity_id))
+            .filter(n::read_at.is_null())
+            .filter(n::updated_at.ge(since))
+            .order(n::updated_at.desc())
+            .select(Notification::as_select())
+            .first::<Notification>(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewNotification,
+        in_app: bool,
+        email_pending: bool,
+    ) -> Result<Notification, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        diesel::insert_into(n::notifications)
+            .values((
+                new,
+                n::in_app.eq(in_app),
+                n::email_pending.eq(email_pending),
+            ))
+            .returning(Notification::as_returning())
+            .get_result(conn)
+    }
+
+    /// Fold another event into an existing notification
+    pub fn bump(
+        conn: &mut PgConnection,
+        notification_id: uuid::Uuid,
+        new: &NewNotification,
+        email_pending: bool,
+    ) -> Result<Notification, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        diesel::update(n::notifications.filter(n::id.eq(notification_id)))
+            .set((
+                n::event_count.eq(n::event_count + 1),
+                n::actor_id.eq(new.actor_id),
+                n::title.eq(&new.title),
+                n::body.eq(&new.body),
+                n::email_pending.eq(email_pending),
+                n::updated_at.eq(chrono::Utc::now()),
+            ))
+            .returning(Notification::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_email_pending_before(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Notification>, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        n::notifications
+            .filter(n::email_pending.eq(true))
+            .filter(n::read_at.is_null())
+            .filter(n::updated_at.le(before))
+            .order((n::recipient_id.asc(), n::updated_at.asc()))
+            .select(Notification::as_select())
+            .load::<Notification>(conn)
+    }
+
+    /// Mark the recipient's unread notifications about an entity as read;
+    /// read notifications are no longer emailed
+    pub fn mark_read_for_entity(
+        conn: &mut PgConnection,
+        recipient: uuid::Uuid,
+        entity_type: &str,
+        entity_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        diesel::update(
+            n::notifications
+                .filter(n::recipient_id.eq(recipient))
+                .filter(n::entity_type.eq(entity_type))
+                .filter(n::entity_id.eq(entity_id))
+                .filter(n::read_at.is_null()),
+        )
+        .set((n::read_at.eq(Some(at)), n::email_pending.eq(false)))
+        .execute(conn)
+    }
+
+    pub fn mark_emailed(
+        conn: &mut PgConnection,
+        ids: &[uuid::Uuid],
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        diesel::update(n::notifications.filter(n::id.eq_any(ids)))
+            .set((n::email_pending.eq(false), n::emailed_at.eq(Some(at))))
+            .execute(conn)
+    }
+
+    /// Stop emailing notifications whose recipient turned email off
+    pub fn cancel_email(
+        conn: &mut PgConnection,
+        ids: &[uuid::Uuid],
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        diesel::update(n::notifications.filter(n::id.eq_any(ids)))
+            .set(n::email_pending.eq(false))
+            .execute(conn)
+    }
+
+    /// Recipient's inbox, most recently updated first
+    pub fn list_for_recipient(
+        conn: &mut PgConnection,
+        recipient: uuid::Uuid,
+        unread_only: bool,
+        before: Option<chrono::DateTime<chrono::Utc>>,
+        limit: i64,
+    ) -> Result<Vec<Notification>, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        let mut query = n::notifications
+            .filter(n::recipient_id.eq(recipient))
+            .filter(n::in_app.eq(true))
+            .into_boxed();
+        if unread_only {
+            query = query.filter(n::read_at.is_null());
+        }
+        if let Some(before) = before {
+            query = query.filter(n::updated_at.lt(before));
+        }
+        query
+            .order(n::updated_at.desc())
+            .limit(limit)
+            .select(Notification::as_select())
+            .load::<Notification>(conn)
+    }
+
+    pub fn count_unread(
+        conn: &mut PgConnection,
+        recipient: uuid::Uuid,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        n::notifications
+            .filter(n::recipient_id.eq(recipient))
+            .filter(n::in_app.eq(true))
+            .filter(n::read_at.is_null())
+            .count()
+            .get_result(conn)
+    }
+
+    pub fn find_for_recipient(
+        conn: &mut PgConnection,
+        recipient: uuid::Uuid,
+        notification_id: uuid::Uuid,
+    ) -> Result<Option<Notification>, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        n::notifications
+            .filter(n::id.eq(notification_id))
+            .filter(n::recipient_id.eq(recipient))
+            .select(Notification::as_select())
+            .first::<Notification>(conn)
+            .optional()
+    }
+
+    pub fn mark_read(
+        conn: &mut PgConnection,
+        notification_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Notification, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        diesel::update(n::notifications.filter(n::id.eq(notification_id)))
+            .set((n::read_at.eq(Some(at)), n::email_pending.eq(false)))
+            .returning(Notification::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn mark_all_read(
+        conn: &mut PgConnection,
+        recipient: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::notifications::dsl as n;
+        diesel::update(
+            n::notifications
+                .filter(n::recipient_id.eq(recipient))
+                .filter(n::read_at.is_null()),
+        )
+        .set((n::read_at.eq(Some(at)), n::email_pending.eq(false)))
+        .execute(conn)
+    }
+}
@@ -0,0 +1,275 @@
+use diesel::prelude::*;
+
+use crate::db::models::oauth_app::{
+    NewOAuthAccessToken, NewOAuthApp, NewOAuthAuthorizationCode, OAuthAccessToken, OAuthApp,
+    OAuthAuthorizationCode, OAuthGrant,
+};
+
+pub struct OAuthAppsRepo;
+
+impl OAuthAppsRepo {
+    pub fn insert_app(
+        conn: &mut PgConnection,
+        new: &NewOAuthApp,
+    ) -> Result<OAuthApp, diesel::result::Error> {
+        use crate::schema::oauth_apps::dsl as a;
+        diesel::insert_into(a::oauth_apps)
+            .values(new)
+            .returning(OAuthApp::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_apps_by_owner(
+        conn: &mut PgConnection,
+        owner: uuid::Uuid,
+    ) -> Result<Vec<OAuthApp>, diesel::result::Error> {
+        use crate::schema::oauth_apps::dsl as a;
+        a::oauth_apps
+            .filter(a::owner_id.eq(owner))
+            .filter(a::disabled_at.is_null())
+            .order(a::created_at.desc())
+            .select(OAuthApp::as_select())
+            .load::<OAuthApp>(conn)
+    }
+
+    pub fn find_app(
+        conn: &mut PgConnection,
+        app_id: uuid::Uuid,
+    ) -> Result<Option<OAuthApp>, diesel::result::Error> {
+        use crate::schema::oauth_apps::dsl as a;
+        a::oauth_apps
+            .filter(a::id.eq(app_id))
+            .select(OAuthApp::as_select())
+            .first::<OAuthApp>(conn)
+            .optional()
+    }
+
+    pub fn find_app_by_client_id(
+        conn: &mut PgConnection,
+        client: &str,
+    ) -> Result<Option<OAuthApp>, diesel::result::Error> {
+        use crate::schema::oauth_apps::dsl as a;
+        a::oauth_apps
+            .filter(a::client_id.eq(client))
+            .filter(a::disabled_at.is_null())
+            .select(OAuthApp::as_select())
+            .first::<OAuthApp>(conn)
+            .optional()
+    }
+
+    pub fn disable_app(
+        conn: &mut PgConnection,
+        app_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::oauth_apps::dsl as a;
+        diesel::update(a::oauth_apps.filter(a::id.eq(app_id)))
+            .set((a::disabled_at.eq(Some(at)), a::updated_at.eq(at)))
+            .execute(conn)
+    }
+
+    pub fn update_webhook(
+        conn: &mut PgConnection,
+        app_id: uuid::Uuid,
+        url: Option<&str>,
+        events: &str,
+        filter: Option<&str>,
+    ) -> Result<OAuthApp, diesel::result::Error> {
+        use crate::schema::oauth_apps::dsl as a;
+        diesel::update(a::oauth_apps.filter(a::id.eq(app_id)))
+            .set((
+                a::webhook_url.eq(url),
+                a::webhook_events.eq(events),
+                a::webhook_filter.eq(filter),
+                a::updated_at.eq(chrono::Utc::now()),
+            ))
+            .returning(OAuthApp::as_returning())
+            .get_result(conn)
+    }
+
+    /// Create or refresh the user's grant for an app with the given scopes
+    pub fn upsert_grant(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        user: uuid::Uuid,
+        granted_scopes: &str,
+    ) -> Result<OAuthGrant, diesel::result::Error> {
+        use crate::schema::oauth_grants::dsl as g;
+        let now = chrono::Utc::now();
+        diesel::insert_into(g::oauth_grants)
+            .values((
+                g::app_id.eq(app),
+                g::user_id.eq(user),
+                g::scopes.eq(granted_scopes),
+            ))
+            .on_conflict((g::app_id, g::user_id))
+            .do_update()
+            .set((
+                g::scopes.eq(granted_scopes),
+                g::revoked_at.eq(None::<chrono::DateTime<chrono::Utc>>),
+                g::updated_at.eq(now),
+            ))
+            .returning(OAuthGrant::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_grant(
+        conn: &mut PgConnection,
+        grant_id: uuid::Uuid,
+    ) -> Result<Option<OAuthGrant>, diesel::result::Error> {
+        use crate::schema::oauth_grants::dsl as g;
+        g::oauth_grants
+            .filter(g::id.eq(grant_id))
+            .select(OAuthGrant::as_select())
+            .first::<OAuthGrant>(conn)
+            .optional()
+    }
+
+    pub fn find_active_grant(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        user: uuid::Uuid,
+    ) -> Result<Option<OAuthGrant>, diesel::result::Error> {
+        use crate::schema::oauth_grants::dsl as g;
+        g::oauth_grants
+            .filter(g::app_id.eq(app))
+            .filter(g::user_id.eq(user))
+            .filter(g::revoked_at.is_null())
+            .select(OAuthGrant::as_select())
+            .first::<OAuthGrant>(conn)
+            .optional()
+    }
+
+    /// Active grants of a user together with their apps
+    pub fn list_grants_by_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+    ) -> Result<Vec<(OAuthGrant, OAuthApp)>, diesel::result::Error> {
+        use crate::schema::oauth_apps;
+        use crate::schema::oauth_grants::dsl as g;
+        g::oauth_grants
+            .inner_join(oauth_apps::table)
+            .filter(g::user_id.eq(user))
+            .filter(g::revoked_at.is_null())
+            .filter(oauth_apps::disabled_at.is_null())
+            .order(g::updated_at.desc())
+            .select((OAuthGrant::as_select(), OAuthApp::as_select()))
+            .load::<(OAuthGrant, OAuthApp)>(conn)
+    }
+
+    /// Revoke a grant and every token issued under it
+    pub fn revoke_grant(
+        conn: &mut PgConnection,
+        grant_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::oauth_access_tokens::dsl as t;
+        use crate::schema::oauth_grants::dsl as g;
+        diesel::update(
+            t::oauth_access_tokens
+                .filter(t::grant_id.eq(grant_id))
+                .filter(t::revoked_at.is_null()),
+        )
+        .set(t::revoked_at.eq(Some(at)))
+        .execute(conn)?;
+        diesel::update(g::oauth_grants.filter(g::id.eq(grant_id)))
+            .set((g::revoked_at.eq(Some(at)), g::updated_at.eq(at)))
+            .execute(conn)
+    }
+
+    /// Revoke every grant of an app, e.g. when the app is deleted
+    pub fn revoke_grants_for_app(
+        conn: &mut PgConnection,
+        app: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::oauth_grants::dsl as g;
+        let grant_ids = g::oauth_grants
+            .filter(g::app_id.eq(app))
+            .filter(g::revoked_at.is_null())
+            .select(g::id)
+            .load::<uuid::Uuid>(conn)?;
+        for grant_id in &grant_ids {
+            Self::revoke_grant(conn, *grant_id, at)?;
+        }
+        Ok(grant_ids.len())
+    }
+
+    pub fn insert_code(
+        conn: &mut PgConnection,
+        new: &NewOAuthAuthorizationCode,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::oauth_authorization_codes::dsl as c;
+        diesel::insert_into(c::oauth_authorization_codes)
+            .values(new)
+            .execute(conn)
+    }
+
+    /// Mark an unused code as used and return it; `None` if missing or already used
+    pub fn consume_code(
+        conn: &mut PgConnection,
+        hash: &str,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<OAuthAuthorizationCode>, diesel::result::Error> {
+        use crate::schema::oauth_authorization_codes::dsl as c;
+        diesel::update(
+            c::oauth_authorization_codes
+                .filter(c::code_hash.eq(hash))
+                .filter(c::used_at.is_null()),
+        )
+        .set(c::used_at.eq(Some(at)))
+        .returning(OAuthAuthorizationCode::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    pub fn insert_token(
+        conn: &mut PgConnection,
+        new: &NewOAuthAccessToken,
+    ) -> Result<OAuthAccessToken, diesel::result::Error> {
+        use crate::schema::oauth_access_tokens::dsl as t;
+        diesel::insert_into(t::oauth_access_tokens)
+            .values(new)
+            .returning(OAuthAccessToken::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_token_by_hash(
+        conn: &mut PgConnection,
+        hash: &str,
+    ) -> Result<Option<OAuthAccessToken>, diesel::result::Error> {
+        use crate::schema::oauth_access_tokens::dsl as t;
+        t::oauth_access_tokens
+            .filter(t::token_hash.eq(hash))
+            .select(OAuthAccessToken::as_select())
+            .first::<OAuthAccessToken>(conn)
+            .optional()
+    }
+
+    pub fn find_token_by_refresh_hash(
+        conn: &mut PgConnection,
+        hash: &str,
+    ) -> Result<Option<OAuthAccessToken>, diesel::result::Error> {
+        use crate::schema::oauth_access_tokens::dsl as t;
+        t::oauth_access_tokens
+            .filter(t::refresh_token_hash.eq(hash))
+            .select(OAuthAccessToken::as_select())
+            .first::<OAuthAccessToken>(conn)
+            .optional()
+    }
+
+    pub fn revoke_token(
+        conn: &mut PgConnection,
+        token_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::oauth_access_tokens::dsl as t;
+        diesel::update(
+            t::oauth_access_tokens
+                .filter(t::id.eq(token_id))
+                .filter(t::revoked_at.is_null()),
+        )
+        .set(t::revoked_at.eq(Some(at)))
+        .execute(conn)
+    }
+}
@@ -0,0 +1,90 @@
+use diesel::prelude::*;
+
+use crate::db::models::organization::{NewOrganization, Organization, OrganizationChanges};
+use crate::db::models::workspace::Workspace;
+
+pub struct OrganizationsRepo;
+
+impl OrganizationsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        organization: &NewOrganization,
+    ) -> Result<Organization, diesel::result::Error> {
+        diesel::insert_into(crate::schema::organizations::table)
+            .values(organization)
+            .returning(Organization::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_by_id(
+        conn: &mut PgConnection,
+        organization: uuid::Uuid,
+    ) -> Result<Option<Organization>, diesel::result::Error> {
+        use crate::schema::organizations::dsl as o;
+        o::organizations
+            .filter(o::id.eq(organization))
+            .select(Organization::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn list(conn: &mut PgConnection) -> Result<Vec<Organization>, diesel::result::Error> {
+        use crate::schema::organizations::dsl as o;
+        o::organizations
+            .order(o::name.asc())
+            .select(Organization::as_select())
+            .load(conn)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        organization: uuid::Uuid,
+        changes: &OrganizationChanges,
+    ) -> Result<Organization, diesel::result::Error> {
+        use crate::schema::organizations::dsl as o;
+        diesel::update(o::organizations.filter(o::id.eq(organization)))
+            .set((changes, o::updated_at.eq(chrono::Utc::now())))
+            .returning(Organization::as_returning())
+            .get_result(conn)
+    }
+
+    /// Organization the workspace belongs to, if any
+    pub fn find_for_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Option<Organization>, diesel::result::Error> {
+        use crate::schema::{organizations, workspaces};
+        workspaces::table
+            .inner_join(organizations::table)
+            .filter(workspaces::id.eq(workspace))
+            .select(Organization::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn list_workspaces(
+        conn: &mut PgConnection,
+        organization: uuid::Uuid,
+    ) -> Result<Vec<Workspace>, diesel::result::Error> {
+        use crate::schema::workspaces::dsl as w;
+        w::workspaces
+            .filter(w::organization_id.eq(organization))
+            .order(w::name.asc())
+            .select(Workspace::as_select())
+            .load(conn)
+    }
+
+    pub fn set_workspace_organization(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        organization: Option<uuid::Uuid>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::workspaces::dsl as w;
+        diesel::update(w::workspaces.filter(w::id.eq(workspace)))
+            .set((
+                w::organization_id.eq(organization),
+                w::updated_at.eq(chrono::Utc::now()),
+            ))
+            .execute(conn)
+    }
+}
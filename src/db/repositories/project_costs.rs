@@ -0,0 +1,161 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::project::Project;
+use crate::db::models::project_cost::{
+    MemberCostRate, NewMemberCostRate, NewProjectCostEntry, ProjectCostEntry,
+};
+
+pub struct ProjectCostsRepo;
+
+impl ProjectCostsRepo {
+    /// Insert or replace a member's rate
+    pub fn upsert_rate(
+        conn: &mut PgConnection,
+        rate: &NewMemberCostRate,
+    ) -> Result<MemberCostRate, diesel::result::Error> {
+        use crate::schema::member_cost_rates::dsl as r;
+        diesel::insert_into(r::member_cost_rates)
+            .values(rate)
+            .on_conflict((r::workspace_id, r::user_id))
+            .do_update()
+            .set(rate)
+            .returning(MemberCostRate::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_rate(
+        conn: &mut PgConnection,
+        workspace: Uuid,
+        user: Uuid,
+    ) -> Result<Option<MemberCostRate>, diesel::result::Error> {
+        use crate::schema::member_cost_rates::dsl as r;
+        r::member_cost_rates
+            .filter(r::workspace_id.eq(workspace))
+            .filter(r::user_id.eq(user))
+            .select(MemberCostRate::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn list_rates(
+        conn: &mut PgConnection,
+        workspace: Uuid,
+    ) -> Result<Vec<MemberCostRate>, diesel::result::Error> {
+        use crate::schema::member_cost_rates::dsl as r;
+        r::member_cost_rates
+            .filter(r::workspace_id.eq(workspace))
+            .order(r::updated_at.desc())
+            .select(MemberCostRate::as_select())
+            .load(conn)
+    }
+
+    pub fn delete_rate(
+        conn: &mut PgConnection,
+        workspace: Uuid,
+        user: Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::member_cost_rates::dsl as r;
+        diesel::delete(
+            r::member_cost_rates
+                .filter(r::workspace_id.eq(workspace))
+                .filter(r::user_id.eq(user)),
+        )
+        .execute(conn)
+    }
+
+    pub fn insert_entry(
+        conn: &mut PgConnection,
+        entry: &NewProjectCostEntry,
+    ) -> Result<ProjectCostEntry, diesel::result::Error> {
+        diesel::insert_into(crate::schema::project_cost_entries::table)
+            .values(entry)
+            .returning(ProjectCostEntry::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_entry(
+        conn: &mut PgConnection,
+        project: Uuid,
+        entry_id: Uuid,
+    ) -> Result<Option<ProjectCostEntry>, diesel::result::Error> {
+        use crate::schema::project_cost_entries::dsl as e;
+        e::project_cost_entries
+            .filter(e::id.eq(entry_id))
+            .filter(e::project_id.eq(project))
+            .select(ProjectCostEntry::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn delete_entry(
+        conn: &mut PgConnection,
+        entry_id: Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::project_cost_entries::dsl as e;
+        diesel::delete(e::project_cost_entries.filter(e::id.eq(entry_id))).execute(conn)
+    }
+
+    /// Entries of a project, newest first; only `user`'s when given
+    pub fn list_entries(
+        conn: &mut PgConnection,
+        project: Uuid,
+        user: Option<Uuid>,
+    ) -> Result<Vec<ProjectCostEntry>, diesel::result::Error> {
+        use crate::schema::project_cost_entries::dsl as e;
+        let mut query = e::project_cost_entries
+            .filter(e::project_id.eq(project))
+            .into_boxed();
+        if let Some(user) = user {
+            query = query.filter(e::user_id.eq(user));
+        }
+        query
+            .order((e::spent_on.desc(), e::created_at.desc()))
+            .select(ProjectCostEntry::as_select())
+            .load(conn)
+    }
+
+    /// Whether the project has entries in a currency other than `currency`
+    pub fn has_entries_in_other_currency(
+        conn: &mut PgConnection,
+        project: Uuid,
+        currency: &str,
+    ) -> Result<bool, diesel::result::Error> {
+        use crate::schema::project_cost_entries::dsl as e;
+        diesel::select(diesel::dsl::exists(
+            e::project_cost_entries
+                .filter(e::project_id.eq(project))
+                .filter(e::currency.ne(currency)),
+        ))
+        .get_result(conn)
+    }
+
+    pub fn set_budget(
+        conn: &mut PgConnection,
+        project_id: Uuid,
+        currency: Option<&str>,
+        amount_cents: Option<i64>,
+        alert_percent: i32,
+    ) -> Result<Project, diesel::result::Error> {
+        use crate::schema::projects::dsl as p;
+        diesel::update(p::projects.filter(p::id.eq(project_id)))
+            .set((
+                p::budget_currency.eq(currency),
+                p::budget_amount_cents.eq(amount_cents),
+                p::budget_alert_percent.eq(alert_percent),
+                p::updated_at.eq(chrono::Utc::now()),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn set_alert_level(
+        conn: &mut PgConnection,
+        project_id: Uuid,
+        level: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::projects::dsl as p;
+        diesel::update(p::projects.filter(p::id.eq(project_id)))
+            .set(p::budget_alert_level.eq(level))
+            .execute(conn)
+    }
+}
@@ -41,6 +41,47 @@ impl ProjectsRepo {
             .optional()
     }
 
+    pub fn list_by_ids_in_workspace(
+        conn: &mut PgConnection,
+        ws: uuid::Uuid,
+        project_ids: &[uuid::Uuid],
+    ) -> Result<Vec<Project>, diesel::result::Error> {
+        use crate::schema::projects::dsl::*;
+        projects
+            .filter(id.eq_any(project_ids))
+            .filter(workspace_id.eq(ws))
+            .load::<Project>(conn)
+    }
+
+    /// Case-insensitive name match, used when importing projects
+    pub fn find_by_name_in_workspace(
+        conn: &mut PgConnection,
+        ws: uuid::Uuid,
+        project_name: &str,
+    ) -> Result<Option<Project>, diesel::result::Error> {
+        use crate::schema::projects::dsl::*;
+        let pattern = project_name
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        projects
+            .filter(workspace_id.eq(ws))
+            .filter(name.ilike(pattern))
+            .first::<Project>(conn)
+            .optional()
+    }
+
+    pub fn list_keys(
+        conn: &mut PgConnection,
+        ws: uuid::Uuid,
+    ) -> Result<Vec<String>, diesel::result::Error> {
+        use crate::schema::projects::dsl::*;
+        projects
+            .filter(workspace_id.eq(ws))
+            .select(project_key)
+            .load(conn)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn update_fields(
         conn: &mut PgConnection,
@@ -105,4 +146,33 @@ impl ProjectsRepo {
         use crate::schema::projects::dsl::*;
         diesel::delete(projects.filter(id.eq(project_id))).execute(conn)
     }
+
+    /// Unarchived issue count and latest issue update of each of
+    /// `projects`, per workflow state
+    #[allow(clippy::type_complexity)]
+    pub fn issue_rollups(
+        conn: &mut PgConnection,
+        projects: &[uuid::Uuid],
+    ) -> Result<
+        Vec<(
+            Option<uuid::Uuid>,
+            Option<uuid::Uuid>,
+            i64,
+            Option<chrono::DateTime<chrono::Utc>>,
+        )>,
+        diesel::result::Error,
+    > {
+        use crate::schema::issues::dsl as i;
+        i::issues
+            .filter(i::project_id.eq_any(projects))
+            .filter(i::archived_at.is_null())
+            .group_by((i::project_id, i::workflow_state_id))
+            .select((
+                i::project_id,
+                i::workflow_state_id,
+                diesel::dsl::count_star(),
+                diesel::dsl::max(i::updated_at),
+            ))
+            .load(conn)
+    }
 }
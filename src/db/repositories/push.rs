@@ -0,0 +1,185 @@
+use diesel::prelude::*;
+
+use crate::db::models::push::{
+    NewPushDelivery, NewPushSubscription, PushDelivery, PushDeliveryAttempt, PushPreferenceRecord,
+    PushSubscription, push_delivery_status,
+};
+
+pub struct PushSubscriptionsRepo;
+
+impl PushSubscriptionsRepo {
+    /// Register a device token, moving it to `new.user_id` if another user
+    /// had registered the same device before
+    pub fn upsert(
+        conn: &mut PgConnection,
+        new: &NewPushSubscription,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PushSubscription, diesel::result::Error> {
+        use crate::schema::push_subscriptions::dsl as s;
+        diesel::insert_into(s::push_subscriptions)
+            .values(new)
+            .on_conflict((s::platform, s::token))
+            .do_update()
+            .set((
+                s::user_id.eq(new.user_id),
+                s::device_name.eq(&new.device_name),
+                s::updated_at.eq(now),
+            ))
+            .returning(PushSubscription::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_by_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+    ) -> Result<Vec<PushSubscription>, diesel::result::Error> {
+        use crate::schema::push_subscriptions::dsl as s;
+        s::push_subscriptions
+            .filter(s::user_id.eq(user))
+            .order(s::created_at.desc())
+            .select(PushSubscription::as_select())
+            .load(conn)
+    }
+
+    pub fn find(
+        conn: &mut PgConnection,
+        subscription_id: uuid::Uuid,
+    ) -> Result<Option<PushSubscription>, diesel::result::Error> {
+        use crate::schema::push_subscriptions::dsl as s;
+        s::push_subscriptions
+            .filter(s::id.eq(subscription_id))
+            .select(PushSubscription::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn delete_for_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+        subscription_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::push_subscriptions::dsl as s;
+        diesel::delete(
+            s::push_subscriptions
+                .filter(s::id.eq(subscription_id))
+                .filter(s::user_id.eq(user)),
+        )
+        .execute(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        subscription_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::push_subscriptions::dsl as s;
+        diesel::delete(s::push_subscriptions.filter(s::id.eq(subscription_id))).execute(conn)
+    }
+
+    /// Remove subscriptions not re-registered since `before`
+    pub fn delete_not_refreshed_since(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::push_subscriptions::dsl as s;
+        diesel::delete(s::push_subscriptions.filter(s::updated_at.lt(before))).execute(conn)
+    }
+
+    pub fn touch_delivered(
+        conn: &mut PgConnection,
+        subscription_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::push_subscriptions::dsl as s;
+        diesel::update(s::push_subscriptions.filter(s::id.eq(subscription_id)))
+            .set(s::last_delivered_at.eq(Some(at)))
+            .execute(conn)
+    }
+}
+
+pub struct PushPreferencesRepo;
+
+impl PushPreferencesRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+    ) -> Result<Option<PushPreferenceRecord>, diesel::result::Error> {
+        use crate::schema::push_preferences::dsl as p;
+        p::push_preferences
+            .filter(p::user_id.eq(user))
+            .select(PushPreferenceRecord::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn upsert(
+        conn: &mut PgConnection,
+        record: &PushPreferenceRecord,
+    ) -> Result<PushPreferenceRecord, diesel::result::Error> {
+        use crate::schema::push_preferences::dsl as p;
+        diesel::insert_into(p::push_preferences)
+            .values(record)
+            .on_conflict(p::user_id)
+            .do_update()
+            .set((
+                p::enabled.eq(record.enabled),
+                p::muted_events.eq(&record.muted_events),
+                p::updated_at.eq(record.updated_at),
+            ))
+            .returning(PushPreferenceRecord::as_returning())
+            .get_result(conn)
+    }
+}
+
+pub struct PushDeliveriesRepo;
+
+impl PushDeliveriesRepo {
+    pub fn insert_many(
+        conn: &mut PgConnection,
+        deliveries: &[NewPushDelivery],
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::insert_into(crate::schema::push_deliveries::table)
+            .values(deliveries)
+            .execute(conn)
+    }
+
+    pub fn list_due(
+        conn: &mut PgConnection,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<PushDelivery>, diesel::result::Error> {
+        use crate::schema::push_deliveries::dsl as d;
+        d::push_deliveries
+            .filter(d::status.eq(push_delivery_status::PENDING))
+            .filter(d::next_attempt_at.le(now))
+            .order(d::next_attempt_at.asc())
+            .limit(limit)
+            .select(PushDelivery::as_select())
+            .load(conn)
+    }
+
+    pub fn record_attempt(
+        conn: &mut PgConnection,
+        delivery_id: uuid::Uuid,
+        attempt: &PushDeliveryAttempt,
+    ) -> Result<PushDelivery, diesel::result::Error> {
+        use crate::schema::push_deliveries::dsl as d;
+        diesel::update(d::push_deliveries.filter(d::id.eq(delivery_id)))
+            .set(attempt)
+            .returning(PushDelivery::as_returning())
+            .get_result(conn)
+    }
+
+    /// Drop finished deliveries created before `before`
+    pub fn delete_finished_before(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::push_deliveries::dsl as d;
+        diesel::delete(
+            d::push_deliveries
+                .filter(d::status.ne(push_delivery_status::PENDING))
+                .filter(d::created_at.lt(before)),
+        )
+        .execute(conn)
+    }
+}
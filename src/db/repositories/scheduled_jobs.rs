@@ -0,0 +1,68 @@
+use diesel::prelude::*;
+
+use crate::db::models::scheduled_job::{
+    ScheduledJobOutcome, ScheduledJobRecord, scheduled_job_status,
+};
+
+pub struct ScheduledJobsRepo;
+
+impl ScheduledJobsRepo {
+    pub fn list(conn: &mut PgConnection) -> Result<Vec<ScheduledJobRecord>, diesel::result::Error> {
+        use crate::schema::scheduled_jobs::dsl as j;
+        j::scheduled_jobs
+            .order(j::name.asc())
+            .select(ScheduledJobRecord::as_select())
+            .load(conn)
+    }
+
+    /// Mark the job as running, creating its row on the first run
+    pub fn record_start(
+        conn: &mut PgConnection,
+        name: &str,
+        schedule: &str,
+        started_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::scheduled_jobs::dsl as j;
+        diesel::insert_into(j::scheduled_jobs)
+            .values((
+                j::name.eq(name),
+                j::schedule.eq(schedule),
+                j::last_status.eq(scheduled_job_status::RUNNING),
+                j::last_started_at.eq(started_at),
+                j::updated_at.eq(started_at),
+            ))
+            .on_conflict(j::name)
+            .do_update()
+            .set((
+                j::schedule.eq(schedule),
+                j::last_status.eq(scheduled_job_status::RUNNING),
+                j::last_started_at.eq(started_at),
+                j::updated_at.eq(started_at),
+            ))
+            .execute(conn)
+    }
+
+    pub fn record_finish(
+        conn: &mut PgConnection,
+        name: &str,
+        outcome: &ScheduledJobOutcome,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::scheduled_jobs::dsl as j;
+        let (status, result, error, failures) = match &outcome.result {
+            Ok(summary) => (scheduled_job_status::SUCCEEDED, Some(summary), None, 0),
+            Err(error) => (scheduled_job_status::FAILED, None, Some(error), 1),
+        };
+        diesel::update(j::scheduled_jobs.filter(j::name.eq(name)))
+            .set((
+                j::last_status.eq(status),
+                j::last_finished_at.eq(outcome.finished_at),
+                j::last_duration_ms.eq(outcome.duration_ms),
+                j::last_result.eq(result),
+                j::last_error.eq(error),
+                j::run_count.eq(j::run_count + 1),
+                j::failure_count.eq(j::failure_count + failures),
+                j::updated_at.eq(outcome.finished_at),
+            ))
+            .execute(conn)
+    }
+}
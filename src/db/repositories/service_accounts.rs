@@ -0,0 +1,102 @@
+use diesel::prelude::*;
+
+use crate::db::models::auth::User;
+use crate::db::models::service_account::{NewServiceAccount, ServiceAccountRecord};
+use crate::db::models::workspace_member::WorkspaceMemberRole;
+
+pub struct ServiceAccountsRepo;
+
+impl ServiceAccountsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewServiceAccount,
+    ) -> Result<ServiceAccountRecord, diesel::result::Error> {
+        diesel::insert_into(crate::schema::service_accounts::table)
+            .values(new)
+            .returning(ServiceAccountRecord::as_returning())
+            .get_result(conn)
+    }
+
+    /// Service account record of a user, if the user is one
+    pub fn find(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+    ) -> Result<Option<ServiceAccountRecord>, diesel::result::Error> {
+        use crate::schema::service_accounts::dsl as s;
+        s::service_accounts
+            .filter(s::user_id.eq(user))
+            .select(ServiceAccountRecord::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Accounts of a workspace with their users and current role, newest
+    /// first; the role is `None` once the account was removed as a member
+    #[allow(clippy::type_complexity)]
+    pub fn list_with_users(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        user: Option<uuid::Uuid>,
+    ) -> Result<Vec<(ServiceAccountRecord, User, Option<WorkspaceMemberRole>)>, diesel::result::Error>
+    {
+        use crate::schema::{service_accounts as s, users as u, workspace_members as m};
+        let mut query = s::table
+            .inner_join(u::table.on(u::id.eq(s::user_id)))
+            .left_join(
+                m::table.on(m::user_id
+                    .eq(s::user_id)
+                    .and(m::workspace_id.eq(s::workspace_id))),
+            )
+            .filter(s::workspace_id.eq(workspace))
+            .into_boxed();
+        if let Some(user) = user {
+            query = query.filter(s::user_id.eq(user));
+        }
+        query
+            .order(s::created_at.desc())
+            .select((
+                ServiceAccountRecord::as_select(),
+                User::as_select(),
+                m::role.nullable(),
+            ))
+            .load(conn)
+    }
+
+    /// Disable an account and deactivate its user so no token of it
+    /// authenticates any more
+    pub fn disable(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::service_accounts::dsl as s;
+        use crate::schema::users::dsl as u;
+        let disabled = diesel::update(
+            s::service_accounts
+                .filter(s::user_id.eq(user))
+                .filter(s::disabled_at.is_null()),
+        )
+        .set((s::disabled_at.eq(Some(at)), s::updated_at.eq(at)))
+        .execute(conn)?;
+        diesel::update(u::users.filter(u::id.eq(user)))
+            .set(u::is_active.eq(false))
+            .execute(conn)?;
+        Ok(disabled)
+    }
+
+    /// Those of `users` that are service accounts
+    pub fn filter_service_accounts(
+        conn: &mut PgConnection,
+        users: &[uuid::Uuid],
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::users::dsl as u;
+        if users.is_empty() {
+            return Ok(Vec::new());
+        }
+        u::users
+            .filter(u::id.eq_any(users))
+            .filter(u::is_service_account.eq(true))
+            .select(u::id)
+            .load(conn)
+    }
+}
@@ -0,0 +1,117 @@
+use diesel::prelude::*;
+
+use crate::db::models::cycle::Cycle;
+use crate::db::models::issue::Issue;
+use crate::db::models::label::Label;
+use crate::db::models::team::Team;
+
+/// Read-only queries behind the offline sync snapshot
+pub struct SyncRepo;
+
+impl SyncRepo {
+    /// Teams of the workspace the user belongs to, optionally only those
+    /// updated after `since`
+    pub fn teams(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        user: uuid::Uuid,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Team>, diesel::result::Error> {
+        use crate::schema::{team_members, teams};
+        let mut query = teams::table
+            .inner_join(team_members::table)
+            .filter(teams::workspace_id.eq(ws_id))
+            .filter(team_members::user_id.eq(user))
+            .select(Team::as_select())
+            .order(teams::name.asc())
+            .into_boxed();
+        if let Some(since) = since {
+            query = query.filter(teams::updated_at.gt(since));
+        }
+        query.load(conn)
+    }
+
+    /// Issues in the workspace assigned to or created by the user, most
+    /// recently updated first
+    pub fn my_issues(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        user: uuid::Uuid,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: i64,
+    ) -> Result<Vec<Issue>, diesel::result::Error> {
+        use crate::schema::{issues, teams};
+        let mut query = issues::table
+            .inner_join(teams::table)
+            .filter(teams::workspace_id.eq(ws_id))
+            .filter(issues::assignee_id.eq(user).or(issues::creator_id.eq(user)))
+            .filter(issues::archived_at.is_null())
+            .select(Issue::as_select())
+            .order((issues::updated_at.desc(), issues::id.asc()))
+            .limit(limit)
+            .into_boxed();
+        if let Some(since) = since {
+            query = query.filter(issues::updated_at.gt(since));
+        }
+        query.load(conn)
+    }
+
+    pub fn my_issue_ids(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        user: uuid::Uuid,
+        limit: i64,
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::{issues, teams};
+        issues::table
+            .inner_join(teams::table)
+            .filter(teams::workspace_id.eq(ws_id))
+            .filter(issues::assignee_id.eq(user).or(issues::creator_id.eq(user)))
+            .filter(issues::archived_at.is_null())
+            .order((issues::updated_at.desc(), issues::id.asc()))
+            .limit(limit)
+            .select(issues::id)
+            .load(conn)
+    }
+
+    /// `(issue_id, label_id)` pairs of the given issues
+    pub fn issue_labels(
+        conn: &mut PgConnection,
+        issue_ids: &[uuid::Uuid],
+    ) -> Result<Vec<(uuid::Uuid, uuid::Uuid)>, diesel::result::Error> {
+        use crate::schema::issue_labels::dsl as il;
+        il::issue_labels
+            .filter(il::issue_id.eq_any(issue_ids))
+            .select((il::issue_id, il::label_id))
+            .load(conn)
+    }
+
+    /// Cycles of the given teams running on `today`
+    pub fn active_cycles(
+        conn: &mut PgConnection,
+        team_ids: &[uuid::Uuid],
+        today: chrono::NaiveDate,
+    ) -> Result<Vec<Cycle>, diesel::result::Error> {
+        use crate::schema::cycles::dsl as c;
+        c::cycles
+            .filter(c::team_id.eq_any(team_ids))
+            .filter(c::start_date.le(today))
+            .filter(c::end_date.ge(today))
+            .filter(c::status.ne("completed"))
+            .select(Cycle::as_select())
+            .order(c::start_date.asc())
+            .load(conn)
+    }
+
+    pub fn labels(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<Vec<Label>, diesel::result::Error> {
+        use crate::schema::labels::dsl as l;
+        l::labels
+            .filter(l::workspace_id.eq(ws_id))
+            .select(Label::as_select())
+            .order(l::name.asc())
+            .load(conn)
+    }
+}
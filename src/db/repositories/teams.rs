@@ -0,0 +1,170 @@
+use diesel::prelude::*;
+
+use crate::db::models::auth::User;
+use crate::db::models::team::{Team, TeamMember};
+
+pub struct TeamsRepo;
+
+impl TeamsRepo {
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        team: uuid::Uuid,
+    ) -> Result<Option<Team>, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        t::teams
+            .filter(t::id.eq(team))
+            .filter(t::workspace_id.eq(workspace))
+            .select(Team::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn find_by_key(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        key: &str,
+    ) -> Result<Option<Team>, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        t::teams
+            .filter(t::workspace_id.eq(workspace))
+            .filter(t::team_key.eq(key))
+            .select(Team::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn list_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Vec<Team>, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        t::teams
+            .filter(t::workspace_id.eq(workspace))
+            .order(t::created_at.desc())
+            .select(Team::as_select())
+            .load(conn)
+    }
+
+    pub fn list_by_ids(
+        conn: &mut PgConnection,
+        teams: &[uuid::Uuid],
+    ) -> Result<Vec<Team>, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        t::teams
+            .filter(t::id.eq_any(teams))
+            .select(Team::as_select())
+            .load(conn)
+    }
+
+    pub fn set_parent(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+        parent: Option<uuid::Uuid>,
+    ) -> Result<Team, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        diesel::update(t::teams.filter(t::id.eq(team)))
+            .set((
+                t::parent_team_id.eq(parent),
+                t::updated_at.eq(chrono::Utc::now()),
+            ))
+            .returning(Team::as_returning())
+            .get_result(conn)
+    }
+
+    /// Move the sub-teams of `team` under `new_parent`
+    pub fn reparent_children(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+        new_parent: Option<uuid::Uuid>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        diesel::update(t::teams.filter(t::parent_team_id.eq(team)))
+            .set((
+                t::parent_team_id.eq(new_parent),
+                t::updated_at.eq(chrono::Utc::now()),
+            ))
+            .execute(conn)
+    }
+
+    /// Which of `teams` the user belongs to directly
+    pub fn member_team_ids(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+        teams: &[uuid::Uuid],
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::team_members::dsl as tm;
+        tm::team_members
+            .filter(tm::user_id.eq(user))
+            .filter(tm::team_id.eq_any(teams))
+            .select(tm::team_id)
+            .load(conn)
+    }
+
+    /// Ids of the team's direct members in the order they joined
+    pub fn member_ids(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::team_members::dsl as tm;
+        tm::team_members
+            .filter(tm::team_id.eq(team))
+            .order((tm::joined_at.asc(), tm::user_id.asc()))
+            .select(tm::user_id)
+            .load(conn)
+    }
+
+    /// The team, locked until the end of the transaction
+    pub fn lock(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Option<Team>, diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        t::teams
+            .filter(t::id.eq(team))
+            .select(Team::as_select())
+            .for_update()
+            .first(conn)
+            .optional()
+    }
+
+    pub fn set_last_auto_assignee(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+        user: uuid::Uuid,
+    ) -> Result<(), diesel::result::Error> {
+        use crate::schema::teams::dsl as t;
+        diesel::update(t::teams.filter(t::id.eq(team)))
+            .set(t::last_auto_assignee_id.eq(Some(user)))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Direct members of each of `teams`
+    pub fn list_members(
+        conn: &mut PgConnection,
+        teams: &[uuid::Uuid],
+    ) -> Result<Vec<(TeamMember, User)>, diesel::result::Error> {
+        use crate::schema::{team_members, users};
+        team_members::table
+            .filter(team_members::team_id.eq_any(teams))
+            .inner_join(users::table.on(users::id.eq(team_members::user_id)))
+            .order(team_members::joined_at.asc())
+            .select((TeamMember::as_select(), User::as_select()))
+            .load(conn)
+    }
+
+    /// Number of unarchived issues owned by each of `teams`
+    pub fn count_issues(
+        conn: &mut PgConnection,
+        teams: &[uuid::Uuid],
+    ) -> Result<Vec<(uuid::Uuid, i64)>, diesel::result::Error> {
+        use crate::schema::issues::dsl as i;
+        i::issues
+            .filter(i::team_id.eq_any(teams))
+            .filter(i::archived_at.is_null())
+            .group_by(i::team_id)
+            .select((i::team_id, diesel::dsl::count_star()))
+            .load(conn)
+    }
+}
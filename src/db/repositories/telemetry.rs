@@ -0,0 +1,90 @@
+use diesel::dsl::count;
+use diesel::prelude::*;
+
+use crate::db::models::telemetry::{FeatureAdoption, TelemetryStateRecord, UsageCounts};
+
+pub struct TelemetryRepo;
+
+impl TelemetryRepo {
+    /// The installation's telemetry state, created with `instance_id` on
+    /// first use
+    pub fn state(
+        conn: &mut PgConnection,
+        instance_id: uuid::Uuid,
+    ) -> Result<TelemetryStateRecord, diesel::result::Error> {
+        use crate::schema::telemetry_state::dsl as t;
+        diesel::insert_into(t::telemetry_state)
+            .values((t::singleton.eq(true), t::instance_id.eq(instance_id)))
+            .on_conflict(t::singleton)
+            .do_nothing()
+            .execute(conn)?;
+        t::telemetry_state
+            .select(TelemetryStateRecord::as_select())
+            .first(conn)
+    }
+
+    pub fn record_sent(
+        conn: &mut PgConnection,
+        sent_at: chrono::DateTime<chrono::Utc>,
+        report: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::telemetry_state::dsl as t;
+        diesel::update(t::telemetry_state)
+            .set((t::last_sent_at.eq(sent_at), t::last_report.eq(report)))
+            .execute(conn)
+    }
+
+    /// Instance-wide totals; the WebSocket count is left at zero
+    pub fn usage_counts(conn: &mut PgConnection) -> Result<UsageCounts, diesel::result::Error> {
+        use crate::schema::{issues, projects, teams, users, workspaces};
+        Ok(UsageCounts {
+            workspaces: workspaces::table.count().get_result(conn)?,
+            users: users::table
+                .filter(users::is_active.eq(true))
+                .filter(users::is_service_account.eq(false))
+                .count()
+                .get_result(conn)?,
+            teams: teams::table.count().get_result(conn)?,
+            projects: projects::table.count().get_result(conn)?,
+            issues: issues::table
+                .filter(issues::archived_at.is_null())
+                .count()
+                .get_result(conn)?,
+            websocket_connections: 0,
+        })
+    }
+
+    pub fn feature_adoption(
+        conn: &mut PgConnection,
+    ) -> Result<FeatureAdoption, diesel::result::Error> {
+        use crate::schema::{
+            automation_rules, cycles, error_tracking_integrations, github_integrations,
+            issue_forms, service_accounts, teams, webhooks,
+        };
+        Ok(FeatureAdoption {
+            automations: automation_rules::table
+                .filter(automation_rules::enabled.eq(true))
+                .select(count(automation_rules::workspace_id).aggregate_distinct())
+                .get_result(conn)?,
+            issue_forms: issue_forms::table
+                .select(count(issue_forms::workspace_id).aggregate_distinct())
+                .get_result(conn)?,
+            webhooks: webhooks::table
+                .filter(webhooks::is_active.eq(true))
+                .select(count(webhooks::workspace_id).aggregate_distinct())
+                .get_result(conn)?,
+            github: github_integrations::table.count().get_result(conn)?,
+            error_tracking: error_tracking_integrations::table
+                .count()
+                .get_result(conn)?,
+            service_accounts: service_accounts::table
+                .filter(service_accounts::disabled_at.is_null())
+                .select(count(service_accounts::workspace_id).aggregate_distinct())
+                .get_result(conn)?,
+            cycles: cycles::table
+                .inner_join(teams::table)
+                .select(count(teams::workspace_id).aggregate_distinct())
+                .get_result(conn)?,
+        })
+    }
+}
@@ -0,0 +1,107 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::upload_session::{NewUploadSession, UploadSession};
+
+pub struct UploadSessionsRepo;
+
+impl UploadSessionsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_session: &NewUploadSession,
+    ) -> Result<UploadSession, diesel::result::Error> {
+        diesel::insert_into(crate::schema::upload_sessions::table)
+            .values(new_session)
+            .returning(UploadSession::as_returning())
+            .get_result(conn)
+    }
+
+    /// A session of the uploader in the workspace
+    pub fn find_for_uploader(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        uploader: Uuid,
+        session_id: Uuid,
+    ) -> Result<Option<UploadSession>, diesel::result::Error> {
+        use crate::schema::upload_sessions::dsl as u;
+        u::upload_sessions
+            .filter(u::id.eq(session_id))
+            .filter(u::workspace_id.eq(ws_id))
+            .filter(u::uploader_id.eq(uploader))
+            .select(UploadSession::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Record an appended chunk and push back the session's expiry
+    pub fn record_chunk(
+        conn: &mut PgConnection,
+        session_id: Uuid,
+        upload_offset: i64,
+        parts: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<UploadSession, diesel::result::Error> {
+        use crate::schema::upload_sessions::dsl as u;
+        diesel::update(u::upload_sessions.filter(u::id.eq(session_id)))
+            .set((
+                u::upload_offset.eq(upload_offset),
+                u::parts.eq(parts),
+                u::expires_at.eq(expires_at),
+                u::updated_at.eq(chrono::Utc::now()),
+            ))
+            .returning(UploadSession::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn mark_completed(
+        conn: &mut PgConnection,
+        session_id: Uuid,
+        upload_offset: i64,
+        parts: &str,
+        attachment_id: Uuid,
+    ) -> Result<UploadSession, diesel::result::Error> {
+        use crate::schema::upload_sessions::dsl as u;
+        let now = chrono::Utc::now();
+        diesel::update(u::upload_sessions.filter(u::id.eq(session_id)))
+            .set((
+                u::upload_offset.eq(upload_offset),
+                u::parts.eq(parts),
+                u::attachment_id.eq(Some(attachment_id)),
+                u::completed_at.eq(Some(now)),
+                u::updated_at.eq(now),
+            ))
+            .returning(UploadSession::as_returning())
+            .get_result(conn)
+    }
+
+    /// Sessions past their expiry, oldest first
+    pub fn list_expired(
+        conn: &mut PgConnection,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<UploadSession>, diesel::result::Error> {
+        use crate::schema::upload_sessions::dsl as u;
+        u::upload_sessions
+            .filter(u::expires_at.le(now))
+            .order(u::expires_at.asc())
+            .limit(limit)
+            .select(UploadSession::as_select())
+            .load(conn)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        session_id: Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::upload_sessions::dsl as u;
+        diesel::delete(u::upload_sessions.filter(u::id.eq(session_id))).execute(conn)
+    }
+
+    pub fn delete_many(
+        conn: &mut PgConnection,
+        session_ids: &[Uuid],
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::upload_sessions::dsl as u;
+        diesel::delete(u::upload_sessions.filter(u::id.eq_any(session_ids))).execute(conn)
+    }
+}
@@ -0,0 +1,61 @@
+use diesel::prelude::*;
+
+use crate::db::models::user_identity::{NewUserIdentity, UserIdentity};
+
+pub struct UserIdentitiesRepo;
+
+impl UserIdentitiesRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        provider: &str,
+        provider_user_id: &str,
+    ) -> Result<Option<UserIdentity>, diesel::result::Error> {
+        use crate::schema::user_identities::dsl as ui;
+        ui::user_identities
+            .filter(ui::provider.eq(provider))
+            .filter(ui::provider_user_id.eq(provider_user_id))
+            .select(UserIdentity::as_select())
+            .first::<UserIdentity>(conn)
+            .optional()
+    }
+
+    pub fn find_for_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+        provider: &str,
+    ) -> Result<Option<UserIdentity>, diesel::result::Error> {
+        use crate::schema::user_identities::dsl as ui;
+        ui::user_identities
+            .filter(ui::user_id.eq(user))
+            .filter(ui::provider.eq(provider))
+            .select(UserIdentity::as_select())
+            .first::<UserIdentity>(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewUserIdentity,
+    ) -> Result<UserIdentity, diesel::result::Error> {
+        use crate::schema::user_identities::dsl as ui;
+        diesel::insert_into(ui::user_identities)
+            .values(new)
+            .returning(UserIdentity::as_returning())
+            .get_result(conn)
+    }
+
+    /// Record a sign-in and refresh the email the provider reports
+    pub fn touch_login(
+        conn: &mut PgConnection,
+        identity_id: uuid::Uuid,
+        email: Option<&str>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::user_identities::dsl as ui;
+        diesel::update(ui::user_identities.filter(ui::id.eq(identity_id)))
+            .set((
+                ui::email.eq(email),
+                ui::last_login_at.eq(chrono::Utc::now()),
+            ))
+            .execute(conn)
+    }
+}
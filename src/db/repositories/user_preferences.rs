@@ -0,0 +1,51 @@
+use diesel::prelude::*;
+
+use crate::db::models::user_preference::UserPreferenceRecord;
+
+pub struct UserPreferencesRepo;
+
+impl UserPreferencesRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+    ) -> Result<Option<UserPreferenceRecord>, diesel::result::Error> {
+        use crate::schema::user_preferences::dsl as p;
+        p::user_preferences
+            .filter(p::user_id.eq(user))
+            .select(UserPreferenceRecord::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Stored preferences of any of `users`; users without a row are left out
+    pub fn list_for_users(
+        conn: &mut PgConnection,
+        users: &[uuid::Uuid],
+    ) -> Result<Vec<UserPreferenceRecord>, diesel::result::Error> {
+        use crate::schema::user_preferences::dsl as p;
+        p::user_preferences
+            .filter(p::user_id.eq_any(users))
+            .select(UserPreferenceRecord::as_select())
+            .load(conn)
+    }
+
+    pub fn upsert(
+        conn: &mut PgConnection,
+        record: &UserPreferenceRecord,
+    ) -> Result<UserPreferenceRecord, diesel::result::Error> {
+        use crate::schema::user_preferences::dsl as p;
+        diesel::insert_into(p::user_preferences)
+            .values(record)
+            .on_conflict(p::user_id)
+            .do_update()
+            .set((
+                p::timezone.eq(&record.timezone),
+                p::locale.eq(&record.locale),
+                p::theme.eq(&record.theme),
+                p::muted_channels.eq(&record.muted_channels),
+                p::updated_at.eq(record.updated_at),
+            ))
+            .returning(UserPreferenceRecord::as_returning())
+            .get_result(conn)
+    }
+}
@@ -0,0 +1,88 @@
+use diesel::prelude::*;
+
+use crate::db::models::webhook::{NewWebhook, UpdateWebhook, Webhook};
+
+pub struct WebhooksRepo;
+
+impl WebhooksRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_webhook: &NewWebhook,
+    ) -> Result<Webhook, diesel::result::Error> {
+        diesel::insert_into(crate::schema::webhooks::table)
+            .values(new_webhook)
+            .returning(Webhook::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Vec<Webhook>, diesel::result::Error> {
+        use crate::schema::webhooks::dsl as w;
+        w::webhooks
+            .filter(w::workspace_id.eq(workspace))
+            .order(w::created_at.asc())
+            .select(Webhook::as_select())
+            .load(conn)
+    }
+
+    /// Active webhooks of the workspace, for fanning out an event
+    pub fn list_active_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Vec<Webhook>, diesel::result::Error> {
+        use crate::schema::webhooks::dsl as w;
+        w::webhooks
+            .filter(w::workspace_id.eq(workspace))
+            .filter(w::is_active.eq(true))
+            .select(Webhook::as_select())
+            .load(conn)
+    }
+
+    pub fn find(
+        conn: &mut PgConnection,
+        webhook_id: uuid::Uuid,
+    ) -> Result<Option<Webhook>, diesel::result::Error> {
+        use crate::schema::webhooks::dsl as w;
+        w::webhooks
+            .filter(w::id.eq(webhook_id))
+            .select(Webhook::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        webhook_id: uuid::Uuid,
+    ) -> Result<Option<Webhook>, diesel::result::Error> {
+        use crate::schema::webhooks::dsl as w;
+        w::webhooks
+            .filter(w::id.eq(webhook_id))
+            .filter(w::workspace_id.eq(workspace))
+            .select(Webhook::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        webhook_id: uuid::Uuid,
+        changes: &UpdateWebhook,
+    ) -> Result<Webhook, diesel::result::Error> {
+        use crate::schema::webhooks::dsl as w;
+        diesel::update(w::webhooks.filter(w::id.eq(webhook_id)))
+            .set(changes)
+            .returning(Webhook::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn delete_by_id(
+        conn: &mut PgConnection,
+        webhook_id: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::webhooks::dsl as w;
+        diesel::delete(w::webhooks.filter(w::id.eq(webhook_id))).execute(conn)
+    }
+}
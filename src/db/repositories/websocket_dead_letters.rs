@@ -0,0 +1,105 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::websocket_dead_letter::{
+    NewWebSocketDeadLetter, WebSocketDeadLetter, dead_letter_status,
+};
+
+pub struct WebSocketDeadLettersRepo;
+
+impl WebSocketDeadLettersRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewWebSocketDeadLetter,
+    ) -> Result<WebSocketDeadLetter, diesel::result::Error> {
+        use crate::schema::websocket_dead_letters::dsl as dl;
+        diesel::insert_into(dl::websocket_dead_letters)
+            .values(new)
+            .returning(WebSocketDeadLetter::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn find_by_id(
+        conn: &mut PgConnection,
+        dead_letter_id: Uuid,
+    ) -> Result<Option<WebSocketDeadLetter>, diesel::result::Error> {
+        use crate::schema::websocket_dead_letters::dsl as dl;
+        dl::websocket_dead_letters
+            .filter(dl::id.eq(dead_letter_id))
+            .select(WebSocketDeadLetter::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Newest first
+    pub fn list(
+        conn: &mut PgConnection,
+        status: Option<&str>,
+        workspace: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<WebSocketDeadLetter>, diesel::result::Error> {
+        use crate::schema::websocket_dead_letters::dsl as dl;
+        let mut query = dl::websocket_dead_letters
+            .select(WebSocketDeadLetter::as_select())
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(dl::status.eq(status.to_string()));
+        }
+        if let Some(workspace) = workspace {
+            query = query.filter(dl::workspace_id.eq(workspace));
+        }
+        query.order(dl::created_at.desc()).limit(limit).load(conn)
+    }
+
+    /// Count a replay attempt on a pending dead letter that has had exactly
+    /// `seen_attempts` attempts; `None` if another replay got there first
+    pub fn claim(
+        conn: &mut PgConnection,
+        dead_letter_id: Uuid,
+        seen_attempts: i32,
+    ) -> Result<Option<WebSocketDeadLetter>, diesel::result::Error> {
+        use crate::schema::websocket_dead_letters::dsl as dl;
+        diesel::update(
+            dl::websocket_dead_letters
+                .filter(dl::id.eq(dead_letter_id))
+                .filter(dl::status.eq(dead_letter_status::PENDING))
+                .filter(dl::attempts.eq(seen_attempts)),
+        )
+        .set(dl::attempts.eq(seen_attempts + 1))
+        .returning(WebSocketDeadLetter::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    /// Record the outcome of a replay; `error` is the new failure, if any
+    pub fn finish(
+        conn: &mut PgConnection,
+        dead_letter_id: Uuid,
+        status: &str,
+        error: Option<(String, String)>,
+        replayed_by: Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<WebSocketDeadLetter, diesel::result::Error> {
+        use crate::schema::websocket_dead_letters::dsl as dl;
+        let target = dl::websocket_dead_letters.filter(dl::id.eq(dead_letter_id));
+        let replay = (
+            dl::status.eq(status),
+            dl::replayed_by.eq(Some(replayed_by)),
+            dl::replayed_at.eq(Some(at)),
+        );
+        match error {
+            Some((code, message)) => diesel::update(target)
+                .set((
+                    replay,
+                    dl::error_code.eq(code),
+                    dl::error_message.eq(message),
+                ))
+                .returning(WebSocketDeadLetter::as_returning())
+                .get_result(conn),
+            None => diesel::update(target)
+                .set(replay)
+                .returning(WebSocketDeadLetter::as_returning())
+                .get_result(conn),
+        }
+    }
+}
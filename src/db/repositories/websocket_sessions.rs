@@ -0,0 +1,51 @@
+use diesel::prelude::*;
+
+use crate::db::models::websocket_session::{NewWebSocketSession, WebSocketSession};
+
+pub struct WebSocketSessionsRepo;
+
+impl WebSocketSessionsRepo {
+    pub fn insert(
+        conn: &mut PgConnection,
+        new: &NewWebSocketSession,
+    ) -> Result<WebSocketSession, diesel::result::Error> {
+        use crate::schema::websocket_sessions::dsl as ws;
+        diesel::insert_into(ws::websocket_sessions)
+            .values(new)
+            .returning(WebSocketSession::as_returning())
+            .get_result(conn)
+    }
+
+    /// Mark an open session as ended; `None` if it was already closed
+    pub fn close(
+        conn: &mut PgConnection,
+        session_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<WebSocketSession>, diesel::result::Error> {
+        use crate::schema::websocket_sessions::dsl as ws;
+        diesel::update(
+            ws::websocket_sessions
+                .filter(ws::id.eq(session_id))
+                .filter(ws::disconnected_at.is_null()),
+        )
+        .set(ws::disconnected_at.eq(at))
+        .returning(WebSocketSession::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    /// Sessions of the workspace that started at or after `since`
+    pub fn list_since(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<WebSocketSession>, diesel::result::Error> {
+        use crate::schema::websocket_sessions::dsl as ws;
+        ws::websocket_sessions
+            .filter(ws::workspace_id.eq(workspace))
+            .filter(ws::connected_at.ge(since))
+            .order(ws::connected_at.asc())
+            .select(WebSocketSession::as_select())
+            .load(conn)
+    }
+}
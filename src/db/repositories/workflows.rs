@@ -126,6 +126,18 @@ impl WorkflowsRepo {
             .load::<WorkflowState>(conn)
     }
 
+    /// States of all the given workflows in position order
+    pub fn list_states_by_workflows(
+        conn: &mut PgConnection,
+        workflow_ids: &[uuid::Uuid],
+    ) -> Result<Vec<WorkflowState>, diesel::result::Error> {
+        use crate::schema::workflow_states::dsl::*;
+        workflow_states
+            .filter(workflow_id.eq_any(workflow_ids))
+            .order(position.asc())
+            .load::<WorkflowState>(conn)
+    }
+
     pub fn list_team_default_states(
         conn: &mut PgConnection,
         team: uuid::Uuid,
@@ -140,6 +152,64 @@ impl WorkflowsRepo {
             .load::<WorkflowState>(conn)
     }
 
+    /// Default states of each of the given teams in position order, as
+    /// `(team_id, state)` pairs
+    pub fn list_team_default_states_for_teams(
+        conn: &mut PgConnection,
+        team_ids: &[uuid::Uuid],
+    ) -> Result<Vec<(uuid::Uuid, WorkflowState)>, diesel::result::Error> {
+        use crate::schema::{workflow_states, workflows};
+        workflow_states::table
+            .inner_join(workflows::table.on(workflow_states::workflow_id.eq(workflows::id)))
+            .filter(workflows::team_id.eq_any(team_ids))
+            .filter(workflow_states::is_default.eq(true))
+            .select((workflows::team_id, WorkflowState::as_select()))
+            .order(workflow_states::position.asc())
+            .load::<(uuid::Uuid, WorkflowState)>(conn)
+    }
+
+    pub fn list_states_by_ids(
+        conn: &mut PgConnection,
+        state_ids: &[uuid::Uuid],
+    ) -> Result<Vec<WorkflowState>, diesel::result::Error> {
+        use crate::schema::workflow_states::dsl::*;
+        workflow_states
+            .filter(id.eq_any(state_ids))
+            .select(WorkflowState::as_select())
+            .load::<WorkflowState>(conn)
+    }
+
+    /// States of every workflow owned by the team
+    pub fn list_states_by_team(
+        conn: &mut PgConnection,
+        team: uuid::Uuid,
+    ) -> Result<Vec<WorkflowState>, diesel::result::Error> {
+        use crate::schema::{workflow_states, workflows};
+        workflow_states::table
+            .inner_join(workflows::table.on(workflow_states::workflow_id.eq(workflows::id)))
+            .filter(workflows::team_id.eq(team))
+            .select(WorkflowState::as_select())
+            .order(workflow_states::position.asc())
+            .load::<WorkflowState>(conn)
+    }
+
+    /// Team owning the workflow of a state, when the state is in the workspace
+    pub fn find_state_team_in_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+        state_id: uuid::Uuid,
+    ) -> Result<Option<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::{teams, workflow_states, workflows};
+        workflow_states::table
+            .inner_join(workflows::table.on(workflow_states::workflow_id.eq(workflows::id)))
+            .inner_join(teams::table.on(workflows::team_id.eq(teams::id)))
+            .filter(workflow_states::id.eq(state_id))
+            .filter(teams::workspace_id.eq(workspace))
+            .select(teams::id)
+            .first(conn)
+            .optional()
+    }
+
     pub fn insert_team_default_state(
         conn: &mut PgConnection,
         _team_id: uuid::Uuid,
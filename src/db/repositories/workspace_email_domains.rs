@@ -0,0 +1,86 @@
+use diesel::prelude::*;
+
+use crate::db::models::workspace_email_domain::{
+    NewWorkspaceEmailDomain, WorkspaceEmailDomain, email_domain_status,
+};
+
+pub struct WorkspaceEmailDomainsRepo;
+
+impl WorkspaceEmailDomainsRepo {
+    pub fn find_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Option<WorkspaceEmailDomain>, diesel::result::Error> {
+        use crate::schema::workspace_email_domains::dsl as d;
+        d::workspace_email_domains
+            .filter(d::workspace_id.eq(workspace))
+            .select(WorkspaceEmailDomain::as_select())
+            .first::<WorkspaceEmailDomain>(conn)
+            .optional()
+    }
+
+    /// Insert the workspace's domain or replace the existing one
+    pub fn upsert(
+        conn: &mut PgConnection,
+        new: &NewWorkspaceEmailDomain,
+    ) -> Result<WorkspaceEmailDomain, diesel::result::Error> {
+        use crate::schema::workspace_email_domains::dsl as d;
+        diesel::insert_into(d::workspace_email_domains)
+            .values(new)
+            .on_conflict(d::workspace_id)
+            .do_update()
+            .set(new)
+            .returning(WorkspaceEmailDomain::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn record_check(
+        conn: &mut PgConnection,
+        domain_id: uuid::Uuid,
+        status: &str,
+        last_error: Option<&str>,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<WorkspaceEmailDomain, diesel::result::Error> {
+        use crate::schema::workspace_email_domains::dsl as d;
+        let verified_at = (status == email_domain_status::VERIFIED).then_some(at);
+        diesel::update(d::workspace_email_domains.filter(d::id.eq(domain_id)))
+            .set((
+                d::status.eq(status),
+                d::last_error.eq(last_error),
+                d::verified_at.eq(verified_at),
+                d::last_checked_at.eq(Some(at)),
+                d::updated_at.eq(at),
+            ))
+            .returning(WorkspaceEmailDomain::as_returning())
+            .get_result(conn)
+    }
+
+    /// Verified domains last checked before `before`, oldest first
+    pub fn list_verified_checked_before(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<WorkspaceEmailDomain>, diesel::result::Error> {
+        use crate::schema::workspace_email_domains::dsl as d;
+        d::workspace_email_domains
+            .filter(d::status.eq(email_domain_status::VERIFIED))
+            .filter(
+                d::last_checked_at
+                    .lt(before)
+                    .or(d::last_checked_at.is_null()),
+            )
+            .order(d::last_checked_at.asc().nulls_first())
+            .limit(limit)
+            .select(WorkspaceEmailDomain::as_select())
+            .load::<WorkspaceEmailDomain>(conn)
+    }
+
+    pub fn delete_by_workspace(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::workspace_email_domains::dsl as d;
+        diesel::delete(d::workspace_email_domains.filter(d::workspace_id.eq(workspace)))
+            .execute(conn)
+    }
+}
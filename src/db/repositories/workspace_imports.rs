@@ -0,0 +1,129 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::workspace_import::{
+    NewImportExternalId, NewWorkspaceImport, WorkspaceImport, workspace_import_status,
+};
+
+pub struct WorkspaceImportsRepo;
+
+impl WorkspaceImportsRepo {
+    pub fn find_by_hash(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        hash: &str,
+    ) -> Result<Option<WorkspaceImport>, diesel::result::Error> {
+        use crate::schema::workspace_imports::dsl as wi;
+        wi::workspace_imports
+            .filter(wi::workspace_id.eq(ws_id))
+            .filter(wi::content_hash.eq(hash))
+            .select(WorkspaceImport::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        import_id: Uuid,
+    ) -> Result<Option<WorkspaceImport>, diesel::result::Error> {
+        use crate::schema::workspace_imports::dsl as wi;
+        wi::workspace_imports
+            .filter(wi::id.eq(import_id))
+            .filter(wi::workspace_id.eq(ws_id))
+            .select(WorkspaceImport::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_import: &NewWorkspaceImport,
+    ) -> Result<WorkspaceImport, diesel::result::Error> {
+        diesel::insert_into(crate::schema::workspace_imports::table)
+            .values(new_import)
+            .returning(WorkspaceImport::as_returning())
+            .get_result(conn)
+    }
+
+    /// Move a pending import to running; `None` if another worker got it first
+    pub fn claim(
+        conn: &mut PgConnection,
+        import_id: Uuid,
+    ) -> Result<Option<WorkspaceImport>, diesel::result::Error> {
+        use crate::schema::workspace_imports::dsl as wi;
+        diesel::update(
+            wi::workspace_imports
+                .filter(wi::id.eq(import_id))
+                .filter(wi::status.eq(workspace_import_status::PENDING)),
+        )
+        .set((
+            wi::status.eq(workspace_import_status::RUNNING),
+            wi::started_at.eq(Some(chrono::Utc::now())),
+        ))
+        .returning(WorkspaceImport::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    /// Store the report; the payload is no longer needed and is dropped
+    pub fn complete(
+        conn: &mut PgConnection,
+        import_id: Uuid,
+        result: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::workspace_imports::dsl as wi;
+        diesel::update(wi::workspace_imports.filter(wi::id.eq(import_id)))
+            .set((
+                wi::status.eq(workspace_import_status::COMPLETED),
+                wi::result.eq(Some(result)),
+                wi::payload.eq(""),
+                wi::completed_at.eq(Some(chrono::Utc::now())),
+            ))
+            .execute(conn)
+    }
+
+    /// Pending imports created before `before`, oldest first; picked up by
+    /// the worker in case their queue task was lost
+    pub fn list_stale_pending(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Uuid>, diesel::result::Error> {
+        use crate::schema::workspace_imports::dsl as wi;
+        wi::workspace_imports
+            .filter(wi::status.eq(workspace_import_status::PENDING))
+            .filter(wi::created_at.lt(before))
+            .order(wi::created_at.asc())
+            .select(wi::id)
+            .load(conn)
+    }
+
+    /// Id of the record an earlier import created for `external_id`
+    pub fn find_external(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        source: &str,
+        record_type: &str,
+        external_id: &str,
+    ) -> Result<Option<Uuid>, diesel::result::Error> {
+        use crate::schema::import_external_ids::dsl as x;
+        x::import_external_ids
+            .filter(x::workspace_id.eq(ws_id))
+            .filter(x::source.eq(source))
+            .filter(x::record_type.eq(record_type))
+            .filter(x::external_id.eq(external_id))
+            .select(x::entity_id)
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert_external(
+        conn: &mut PgConnection,
+        mapping: &NewImportExternalId,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::insert_into(crate::schema::import_external_ids::table)
+            .values(mapping)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+}
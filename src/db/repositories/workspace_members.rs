@@ -1,6 +1,9 @@
 use diesel::prelude::*;
 
-use crate::db::models::workspace_member::{NewWorkspaceMember, WorkspaceMember};
+use crate::db::models::auth::User;
+use crate::db::models::workspace_member::{
+    NewWorkspaceMember, WorkspaceMember, WorkspaceMemberChange, WorkspaceMemberRole,
+};
 
 pub struct WorkspaceMembersRepo;
 
@@ -25,6 +28,18 @@ impl WorkspaceMembersRepo {
             .load::<WorkspaceMember>(conn)
     }
 
+    /// Workspaces the user belongs to
+    pub fn workspace_ids_for_user(
+        conn: &mut PgConnection,
+        user: uuid::Uuid,
+    ) -> Result<Vec<uuid::Uuid>, diesel::result::Error> {
+        use crate::schema::workspace_members::dsl::*;
+        workspace_members
+            .filter(user_id.eq(user))
+            .select(workspace_id)
+            .load(conn)
+    }
+
     pub fn find(
         conn: &mut PgConnection,
         ws_id: uuid::Uuid,
@@ -51,4 +66,133 @@ impl WorkspaceMembersRepo {
         )
         .execute(conn)
     }
+
+    /// Drop the user from every team of the workspace
+    pub fn delete_team_memberships(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        user: uuid::Uuid,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::{team_members, teams};
+        let workspace_teams = teams::table
+            .filter(teams::workspace_id.eq(ws_id))
+            .select(teams::id);
+        diesel::delete(
+            team_members::table
+                .filter(team_members::user_id.eq(user))
+                .filter(team_members::team_id.eq_any(workspace_teams)),
+        )
+        .execute(conn)
+    }
+
+    pub fn update_role(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        user: uuid::Uuid,
+        new_role: &WorkspaceMemberRole,
+    ) -> Result<WorkspaceMember, diesel::result::Error> {
+        use crate::schema::workspace_members::dsl::*;
+        diesel::update(
+            workspace_members
+                .filter(workspace_id.eq(ws_id))
+                .filter(user_id.eq(user)),
+        )
+        .set((role.eq(new_role), updated_at.eq(diesel::dsl::now)))
+        .get_result(conn)
+    }
+
+    pub fn count_with_role(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        member_role: &WorkspaceMemberRole,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::workspace_members::dsl::*;
+        workspace_members
+            .filter(workspace_id.eq(ws_id))
+            .filter(role.eq(member_role))
+            .count()
+            .get_result(conn)
+    }
+
+    /// Members with their users, ordered by user id and starting after
+    /// `after`, so pages stay stable while members join or leave
+    pub fn page_with_users(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        after: Option<uuid::Uuid>,
+        limit: i64,
+    ) -> Result<Vec<(WorkspaceMember, User)>, diesel::result::Error> {
+        use crate::schema::{users, workspace_members};
+        let mut query = workspace_members::table
+            .inner_join(users::table)
+            .filter(workspace_members::workspace_id.eq(ws_id))
+            .select((WorkspaceMember::as_select(), User::as_select()))
+            .order(workspace_members::user_id.asc())
+            .limit(limit)
+            .into_boxed();
+        if let Some(after) = after {
+            query = query.filter(workspace_members::user_id.gt(after));
+        }
+        query.load(conn)
+    }
+
+    pub fn find_with_users(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        user_ids: &[uuid::Uuid],
+    ) -> Result<Vec<(WorkspaceMember, User)>, diesel::result::Error> {
+        use crate::schema::{users, workspace_members};
+        workspace_members::table
+            .inner_join(users::table)
+            .filter(workspace_members::workspace_id.eq(ws_id))
+            .filter(workspace_members::user_id.eq_any(user_ids))
+            .select((WorkspaceMember::as_select(), User::as_select()))
+            .order(workspace_members::user_id.asc())
+            .load(conn)
+    }
+
+    /// Users of the workspace's members with one of `usernames`
+    pub fn find_users_by_usernames(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        usernames: &[String],
+    ) -> Result<Vec<User>, diesel::result::Error> {
+        use crate::schema::{users, workspace_members};
+        workspace_members::table
+            .inner_join(users::table)
+            .filter(workspace_members::workspace_id.eq(ws_id))
+            .filter(users::username.eq_any(usernames))
+            .select(User::as_select())
+            .load(conn)
+    }
+
+    /// Id of the newest membership change, 0 when there is none
+    pub fn latest_change_id(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::workspace_member_changes::dsl::*;
+        let latest: Option<i64> = workspace_member_changes
+            .filter(workspace_id.eq(ws_id))
+            .select(diesel::dsl::max(id))
+            .first(conn)?;
+        Ok(latest.unwrap_or(0))
+    }
+
+    /// Membership changes after `since`, oldest first
+    pub fn changes_since(
+        conn: &mut PgConnection,
+        ws_id: uuid::Uuid,
+        since: i64,
+        limit: i64,
+    ) -> Result<Vec<WorkspaceMemberChange>, diesel::result::Error> {
+        use crate::schema::workspace_member_changes::dsl::*;
+        workspace_member_changes
+            .filter(workspace_id.eq(ws_id))
+            .filter(id.gt(since))
+            .order(id.asc())
+            .limit(limit)
+            .select(WorkspaceMemberChange::as_select())
+            .load(conn)
+    }
 }
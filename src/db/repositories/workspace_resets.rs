@@ -0,0 +1,159 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::models::workspace_reset::{
+    NewWorkspaceReset, SandboxRemovedCounts, WorkspaceReset, workspace_reset_status,
+};
+
+pub struct WorkspaceResetsRepo;
+
+impl WorkspaceResetsRepo {
+    pub fn find_in_workspace(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+        reset_id: Uuid,
+    ) -> Result<Option<WorkspaceReset>, diesel::result::Error> {
+        use crate::schema::workspace_resets::dsl as wr;
+        wr::workspace_resets
+            .filter(wr::id.eq(reset_id))
+            .filter(wr::workspace_id.eq(ws_id))
+            .select(WorkspaceReset::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// The pending or running reset of the workspace, if any
+    pub fn find_active(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+    ) -> Result<Option<WorkspaceReset>, diesel::result::Error> {
+        use crate::schema::workspace_resets::dsl as wr;
+        wr::workspace_resets
+            .filter(wr::workspace_id.eq(ws_id))
+            .filter(wr::status.eq_any([
+                workspace_reset_status::PENDING,
+                workspace_reset_status::RUNNING,
+            ]))
+            .select(WorkspaceReset::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn insert(
+        conn: &mut PgConnection,
+        new_reset: &NewWorkspaceReset,
+    ) -> Result<WorkspaceReset, diesel::result::Error> {
+        diesel::insert_into(crate::schema::workspace_resets::table)
+            .values(new_reset)
+            .returning(WorkspaceReset::as_returning())
+            .get_result(conn)
+    }
+
+    /// Move a pending reset to running; `None` if another worker got it first
+    pub fn claim(
+        conn: &mut PgConnection,
+        reset_id: Uuid,
+    ) -> Result<Option<WorkspaceReset>, diesel::result::Error> {
+        use crate::schema::workspace_resets::dsl as wr;
+        diesel::update(
+            wr::workspace_resets
+                .filter(wr::id.eq(reset_id))
+                .filter(wr::status.eq(workspace_reset_status::PENDING)),
+        )
+        .set((
+            wr::status.eq(workspace_reset_status::RUNNING),
+            wr::started_at.eq(Some(chrono::Utc::now())),
+        ))
+        .returning(WorkspaceReset::as_returning())
+        .get_result(conn)
+        .optional()
+    }
+
+    pub fn complete(
+        conn: &mut PgConnection,
+        reset_id: Uuid,
+        result: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::workspace_resets::dsl as wr;
+        diesel::update(wr::workspace_resets.filter(wr::id.eq(reset_id)))
+            .set((
+                wr::status.eq(workspace_reset_status::COMPLETED),
+                wr::result.eq(Some(result)),
+                wr::completed_at.eq(Some(chrono::Utc::now())),
+            ))
+            .execute(conn)
+    }
+
+    pub fn fail(
+        conn: &mut PgConnection,
+        reset_id: Uuid,
+        error: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::workspace_resets::dsl as wr;
+        diesel::update(wr::workspace_resets.filter(wr::id.eq(reset_id)))
+            .set((
+                wr::status.eq(workspace_reset_status::FAILED),
+                wr::error.eq(Some(error)),
+                wr::completed_at.eq(Some(chrono::Utc::now())),
+            ))
+            .execute(conn)
+    }
+
+    /// Pending resets created before `before`, oldest first; picked up by
+    /// the worker in case their queue task was lost
+    pub fn list_stale_pending(
+        conn: &mut PgConnection,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Uuid>, diesel::result::Error> {
+        use crate::schema::workspace_resets::dsl as wr;
+        wr::workspace_resets
+            .filter(wr::status.eq(workspace_reset_status::PENDING))
+            .filter(wr::created_at.lt(before))
+            .order(wr::created_at.asc())
+            .select(wr::id)
+            .load(conn)
+    }
+
+    /// Delete the workspace's teams (with their issues, cycles and
+    /// workflows), projects, labels, roadmaps, intake forms and import
+    /// mappings. Members, settings, project statuses and integrations stay.
+    pub fn delete_content(
+        conn: &mut PgConnection,
+        ws_id: Uuid,
+    ) -> Result<SandboxRemovedCounts, diesel::result::Error> {
+        use crate::schema::{
+            import_external_ids, issue_forms, issues, labels, projects, roadmaps, teams,
+        };
+
+        let workspace_teams = teams::table
+            .filter(teams::workspace_id.eq(ws_id))
+            .select(teams::id);
+        let issues = issues::table
+            .filter(issues::team_id.eq_any(workspace_teams))
+            .count()
+            .get_result(conn)?;
+
+        diesel::delete(issue_forms::table.filter(issue_forms::workspace_id.eq(ws_id)))
+            .execute(conn)?;
+        diesel::delete(
+            import_external_ids::table.filter(import_external_ids::workspace_id.eq(ws_id)),
+        )
+        .execute(conn)?;
+        let teams = diesel::delete(teams::table.filter(teams::workspace_id.eq(ws_id)))
+            .execute(conn)? as i64;
+        let projects = diesel::delete(projects::table.filter(projects::workspace_id.eq(ws_id)))
+            .execute(conn)? as i64;
+        let labels = diesel::delete(labels::table.filter(labels::workspace_id.eq(ws_id)))
+            .execute(conn)? as i64;
+        let roadmaps = diesel::delete(roadmaps::table.filter(roadmaps::workspace_id.eq(ws_id)))
+            .execute(conn)? as i64;
+
+        Ok(SandboxRemovedCounts {
+            teams,
+            projects,
+            labels,
+            roadmaps,
+            issues,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use diesel::prelude::*;
+
+use crate::db::models::workspace_settings::WorkspaceSettingsRecord;
+
+pub struct WorkspaceSettingsRepo;
+
+impl WorkspaceSettingsRepo {
+    pub fn find(
+        conn: &mut PgConnection,
+        workspace: uuid::Uuid,
+    ) -> Result<Option<WorkspaceSettingsRecord>, diesel::result::Error> {
+        use crate::schema::workspace_settings::dsl as s;
+        s::workspace_settings
+            .filter(s::workspace_id.eq(workspace))
+            .select(WorkspaceSettingsRecord::as_select())
+            .first::<WorkspaceSettingsRecord>(conn)
+            .optional()
+    }
+
+    /// Workspaces that archive completed issues automatically, with the
+    /// number of days they wait
+    pub fn list_auto_archive(
+        conn: &mut PgConnection,
+    ) -> Result<Vec<(uuid::Uuid, i32)>, diesel::result::Error> {
+        use crate::schema::workspace_settings::dsl as s;
+        let rows: Vec<(uuid::Uuid, Option<i32>)> = s::workspace_settings
+            .filter(s::archive_completed_after_days.is_not_null())
+            .select((s::workspace_id, s::archive_completed_after_days))
+            .load(conn)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(workspace, days)| days.map(|days| (workspace, days)))
+            .collect())
+    }
+
+    /// Settings that may auto-join users of `domain`; callers match the
+    /// domain exactly, as this also finds domains that merely contain it
+    pub fn list_auto_join(
+        conn: &mut PgConnection,
+        domain: &str,
+    ) -> Result<Vec<WorkspaceSettingsRecord>, diesel::result::Error> {
+        use crate::schema::workspace_settings::dsl as s;
+        s::workspace_settings
+            .filter(s::auto_join_domains.like(format!("%{}%", domain)))
+            .select(WorkspaceSettingsRecord::as_select())
+            .load(conn)
+    }
+
+    /// Insert the workspace's settings or replace the existing ones
+    pub fn upsert(
+        conn: &mut PgConnection,
+        record: &WorkspaceSettingsRecord,
+    ) -> Result<WorkspaceSettingsRecord, diesel::result::Error> {
+        use crate::schema::workspace_settings::dsl as s;
+        diesel::insert_into(s::workspace_settings)
+            .values(record)
+            .on_conflict(s::workspace_id)
+            .do_update()
+            .set((
+                s::default_workflow_id.eq(&record.default_workflow_id),
+                s::week_start_day.eq(&record.week_start_day),
+                s::estimate_scale.eq(&record.estimate_scale),
+                s::allowed_email_domains.eq(&record.allowed_email_domains),
+                s::features.eq(&record.features),
+                s::archive_completed_after_days.eq(&record.archive_completed_after_days),
+                s::auto_join_domains.eq(&record.auto_join_domains),
+                s::auto_join_role.eq(&record.auto_join_role),
+                s::updated_by.eq(&record.updated_by),
+                s::updated_at.eq(&record.updated_at),
+            ))
+            .returning(WorkspaceSettingsRecord::as_returning())
+            .get_result(conn)
+    }
+}
@@ -33,6 +33,27 @@ impl WorkspacesRepo {
             .optional()
     }
 
+    pub fn find_by_url_key(
+        conn: &mut PgConnection,
+        url: &str,
+    ) -> Result<Option<Workspace>, diesel::result::Error> {
+        use crate::schema::workspaces::dsl::*;
+        workspaces
+            .filter(url_key.eq(url))
+            .first::<Workspace>(conn)
+            .optional()
+    }
+
+    pub fn list_by_ids(
+        conn: &mut PgConnection,
+        workspace_ids: &[uuid::Uuid],
+    ) -> Result<Vec<Workspace>, diesel::result::Error> {
+        use crate::schema::workspaces::dsl::*;
+        workspaces
+            .filter(id.eq_any(workspace_ids))
+            .load::<Workspace>(conn)
+    }
+
     pub fn update_fields(
         conn: &mut PgConnection,
         workspace_id: uuid::Uuid,
@@ -0,0 +1,170 @@
+//! Request-scoped transactions.
+//!
+//! A command or route runs all of its writes through [`with_txn`] (or
+//! [`TransactionManager::run`]) so they commit together or not at all: any
+//! `AppError` rolls the whole unit back. Transactions opened further down,
+//! including by services that call `conn.transaction` themselves, become
+//! savepoints of the outer one.
+//!
+//! Side effects that must not be seen before the data is, such as WebSocket
+//! broadcasts, are registered with [`after_commit`] and run once the
+//! outermost transaction commits; they are dropped if it rolls back.
+use std::cell::RefCell;
+
+use diesel::{Connection, PgConnection};
+
+use crate::db::DbPool;
+use crate::error::AppError;
+
+type Hook = Box<dyn FnOnce()>;
+
+thread_local! {
+    /// Hooks waiting for the outermost transaction on this thread; `None`
+    /// outside of one
+    static AFTER_COMMIT: RefCell<Option<Vec<Hook>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` in a transaction. The outermost call commits and then runs the
+/// hooks registered with [`after_commit`]; nested calls use a savepoint, so a
+/// failure inside them can be handled without aborting the outer transaction.
+pub fn with_txn<T, F>(conn: &mut PgConnection, f: F) -> Result<T, AppError>
+where
+    F: FnOnce(&mut PgConnection) -> Result<T, AppError>,
+{
+    let scope = Scope::enter();
+    let result = conn.transaction(f);
+    match &result {
+        Ok(_) => scope.commit(),
+        Err(_) => scope.roll_back(),
+    }
+    result
+}
+
+/// Run `hook` once the current request transaction commits, or right away
+/// when there is none
+pub fn after_commit(hook: impl FnOnce() + 'static) {
+    let hook: Hook = Box::new(hook);
+    let pending = AFTER_COMMIT.with(|hooks| match hooks.borrow_mut().as_mut() {
+        Some(hooks) => {
+            hooks.push(hook);
+            None
+        }
+        None => Some(hook),
+    });
+    if let Some(hook) = pending {
+        hook();
+    }
+}
+
+/// Whether the current thread is inside [`with_txn`]
+pub fn in_transaction() -> bool {
+    AFTER_COMMIT.with(|hooks| hooks.borrow().is_some())
+}
+
+/// Hook bookkeeping for one `with_txn` call. Dropping it without committing
+/// (an error or a panic) discards the hooks registered inside it.
+struct Scope {
+    outermost: bool,
+    /// Hooks registered before this scope began
+    mark: usize,
+    done: bool,
+}
+
+impl Scope {
+    fn enter() -> Self {
+        AFTER_COMMIT.with(|hooks| {
+            let mut hooks = hooks.borrow_mut();
+            match hooks.as_ref() {
+                Some(pending) => Scope {
+                    outermost: false,
+                    mark: pending.len(),
+                    done: false,
+                },
+                None => {
+                    *hooks = Some(Vec::new());
+                    Scope {
+                        outermost: true,
+                        mark: 0,
+                        done: false,
+                    }
+                }
+            }
+        })
+    }
+
+    fn commit(mut self) {
+        self.done = true;
+        if !self.outermost {
+            return;
+        }
+        let hooks = AFTER_COMMIT.with(|hooks| hooks.borrow_mut().take().unwrap_or_default());
+        for hook in hooks {
+            hook();
+        }
+    }
+
+    fn roll_back(mut self) {
+        self.done = true;
+        self.discard();
+    }
+
+    fn discard(&self) {
+        AFTER_COMMIT.with(|hooks| {
+            let mut hooks = hooks.borrow_mut();
+            if self.outermost {
+                *hooks = None;
+            } else if let Some(pending) = hooks.as_mut() {
+                pending.truncate(self.mark);
+            }
+        });
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if !self.done {
+            self.discard();
+        }
+    }
+}
+
+/// Hands out pooled connections and runs units of work in [`with_txn`]
+#[derive(Clone, Copy)]
+pub struct TransactionManager<'a> {
+    pool: &'a DbPool,
+}
+
+impl<'a> TransactionManager<'a> {
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Run `f` in a transaction on a pooled connection
+    pub fn run<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, AppError>,
+    {
+        let mut conn = self.connection()?;
+        with_txn(&mut conn, f)
+    }
+
+    /// Run `f` on a pooled connection without a transaction; for reads
+    pub fn read<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, AppError>,
+    {
+        let mut conn = self.connection()?;
+        f(&mut conn)
+    }
+
+    fn connection(
+        &self,
+    ) -> Result<
+        diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<PgConnection>>,
+        AppError,
+    > {
+        self.pool
+            .get()
+            .map_err(|_| AppError::Internal("Database connection failed".to_string()))
+    }
+}
@@ -1,6 +1,173 @@
 use crate::db::models::api::ApiResponse;
+use crate::websocket::error_mapper::WebSocketErrorCode;
 use axum::{Json, http::StatusCode, response::IntoResponse};
+use diesel::result::DatabaseErrorKind;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use utoipa::ToSchema;
+
+/// 稳定的机器可读错误码，出现在所有 API 错误响应的 `error_code` 字段中。
+/// 客户端和审计日志依赖这些字符串，已发布的错误码只能新增不能改名。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ValidationFailed,
+    Unauthorized,
+    InvalidToken,
+    Forbidden,
+    NotFound,
+    Conflict,
+    TransactionConflict,
+    RateLimited,
+    DatabaseError,
+    ConnectionError,
+    CacheError,
+    ConfigurationError,
+    PasswordProcessingError,
+    InternalError,
+    NotImplemented,
+    ServiceUnavailable,
+}
+
+impl ErrorCode {
+    /// 完整的错误码目录，按 HTTP 状态码排列
+    pub const ALL: [ErrorCode; 16] = [
+        ErrorCode::ValidationFailed,
+        ErrorCode::Unauthorized,
+        ErrorCode::InvalidToken,
+        ErrorCode::Forbidden,
+        ErrorCode::NotFound,
+        ErrorCode::Conflict,
+        ErrorCode::TransactionConflict,
+        ErrorCode::RateLimited,
+        ErrorCode::DatabaseError,
+        ErrorCode::ConnectionError,
+        ErrorCode::CacheError,
+        ErrorCode::ConfigurationError,
+        ErrorCode::PasswordProcessingError,
+        ErrorCode::InternalError,
+        ErrorCode::NotImplemented,
+        ErrorCode::ServiceUnavailable,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::ValidationFailed => "VALIDATION_FAILED",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::InvalidToken => "INVALID_TOKEN",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::TransactionConflict => "TRANSACTION_CONFLICT",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::DatabaseError => "DATABASE_ERROR",
+            ErrorCode::ConnectionError => "CONNECTION_ERROR",
+            ErrorCode::CacheError => "CACHE_ERROR",
+            ErrorCode::ConfigurationError => "CONFIGURATION_ERROR",
+            ErrorCode::PasswordProcessingError => "PASSWORD_PROCESSING_ERROR",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::NotImplemented => "NOT_IMPLEMENTED",
+            ErrorCode::ServiceUnavailable => "SERVICE_UNAVAILABLE",
+        }
+    }
+
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::ValidationFailed => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized | ErrorCode::InvalidToken => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Conflict | ErrorCode::TransactionConflict => StatusCode::CONFLICT,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::DatabaseError
+            | ErrorCode::ConnectionError
+            | ErrorCode::CacheError
+            | ErrorCode::ConfigurationError
+            | ErrorCode::PasswordProcessingError
+            | ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::NotImplemented => StatusCode::NOT_IMPLEMENTED,
+            ErrorCode::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    /// 通过 WebSocket 返回同一错误时使用的错误码
+    pub fn ws_code(self) -> WebSocketErrorCode {
+        match self {
+            ErrorCode::ValidationFailed => WebSocketErrorCode::ValidationFailed,
+            ErrorCode::Unauthorized => WebSocketErrorCode::AuthenticationFailed,
+            ErrorCode::InvalidToken => WebSocketErrorCode::TokenInvalid,
+            ErrorCode::Forbidden => WebSocketErrorCode::PermissionDenied,
+            ErrorCode::NotFound => WebSocketErrorCode::CommandNotFound,
+            ErrorCode::Conflict | ErrorCode::TransactionConflict => {
+                WebSocketErrorCode::CommandFailed
+            }
+            ErrorCode::RateLimited => WebSocketErrorCode::RateLimitExceeded,
+            ErrorCode::DatabaseError => WebSocketErrorCode::DatabaseError,
+            ErrorCode::ConnectionError | ErrorCode::ServiceUnavailable => {
+                WebSocketErrorCode::ServiceUnavailable
+            }
+            ErrorCode::CacheError
+            | ErrorCode::ConfigurationError
+            | ErrorCode::PasswordProcessingError
+            | ErrorCode::InternalError => WebSocketErrorCode::InternalError,
+            ErrorCode::NotImplemented => WebSocketErrorCode::CommandInvalid,
+        }
+    }
+
+    /// 原样重试是否可能成功（瞬时故障、限流、并发冲突）
+    pub fn retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::TransactionConflict
+                | ErrorCode::RateLimited
+                | ErrorCode::ConnectionError
+                | ErrorCode::CacheError
+                | ErrorCode::ServiceUnavailable
+        )
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorCode::ValidationFailed => "The request is malformed or fails validation",
+            ErrorCode::Unauthorized => "Authentication is missing or was rejected",
+            ErrorCode::InvalidToken => "The access token is malformed or expired",
+            ErrorCode::Forbidden => "The caller may not perform this action",
+            ErrorCode::NotFound => "The requested resource does not exist",
+            ErrorCode::Conflict => "The request conflicts with the current state",
+            ErrorCode::TransactionConflict => "A concurrent change aborted the transaction",
+            ErrorCode::RateLimited => "Too many requests or the quota is used up",
+            ErrorCode::DatabaseError => "The database rejected the operation",
+            ErrorCode::ConnectionError => "No database connection was available",
+            ErrorCode::CacheError => "The cache could not be reached",
+            ErrorCode::ConfigurationError => "The server is misconfigured",
+            ErrorCode::PasswordProcessingError => "The password could not be processed",
+            ErrorCode::InternalError => "An unexpected server error",
+            ErrorCode::NotImplemented => "The endpoint is not implemented",
+            ErrorCode::ServiceUnavailable => "The service is temporarily unavailable",
+        }
+    }
+
+    /// 只有 HTTP 状态码的错误响应（中间件直接构造的响应）对应的错误码
+    pub fn for_status(status: u16) -> Self {
+        match status {
+            401 => ErrorCode::Unauthorized,
+            403 => ErrorCode::Forbidden,
+            404 => ErrorCode::NotFound,
+            409 => ErrorCode::Conflict,
+            429 => ErrorCode::RateLimited,
+            501 => ErrorCode::NotImplemented,
+            503 => ErrorCode::ServiceUnavailable,
+            400..=499 => ErrorCode::ValidationFailed,
+            _ => ErrorCode::InternalError,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -16,6 +183,9 @@ pub enum AppError {
     #[error("Authentication error: {message}")]
     Auth { message: String },
 
+    #[error("Permission denied: {message}")]
+    Forbidden { message: String },
+
     #[error("Validation error: {message}")]
     Validation { message: String },
 
@@ -44,79 +214,32 @@ pub enum AppError {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
-        let (status, response) = match self {
-            AppError::Database(ref e) => {
-                tracing::error!("Database error: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    ApiResponse::<()>::internal_error("Database error"),
-                )
-            }
-            AppError::Pool(ref e) => {
-                tracing::error!("Connection pool error: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    ApiResponse::<()>::internal_error("Connection error"),
-                )
-            }
-            AppError::Redis(ref e) => {
-                tracing::error!("Redis error: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    ApiResponse::<()>::internal_error("Cache error"),
-                )
-            }
-            AppError::Auth { ref message } => (
-                StatusCode::UNAUTHORIZED,
-                ApiResponse::<()>::unauthorized(message),
-            ),
-            AppError::Validation { ref message } => (
-                StatusCode::BAD_REQUEST,
-                ApiResponse::<()>::bad_request(message),
-            ),
-            AppError::NotFound { ref resource } => (
-                StatusCode::NOT_FOUND,
-                ApiResponse::<()>::not_found(&format!("{} not found", resource)),
-            ),
-            AppError::Conflict {
-                ref message,
-                ref field,
-                ref code,
-            } => (
-                StatusCode::CONFLICT,
-                ApiResponse::<()>::conflict(message, field.clone(), code.as_deref().unwrap_or("")),
-            ),
-            AppError::Config(ref e) => {
-                tracing::error!("Configuration error: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    ApiResponse::<()>::internal_error("Configuration error"),
-                )
+        let code = self.code();
+        match code {
+            ErrorCode::TransactionConflict => tracing::warn!("{}", self),
+            _ if code.status().is_server_error() || matches!(self, AppError::Jwt(_)) => {
+                tracing::error!("{}", self)
             }
-            AppError::Jwt(ref e) => {
-                tracing::error!("JWT error: {}", e);
-                (
-                    StatusCode::UNAUTHORIZED,
-                    ApiResponse::<()>::unauthorized("Invalid token"),
-                )
-            }
-            AppError::Bcrypt(ref e) => {
-                tracing::error!("Bcrypt error: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    ApiResponse::<()>::internal_error("Password processing error"),
-                )
+            _ => {}
+        }
+
+        let message = self.public_message();
+        let response = match &self {
+            AppError::Auth { .. } | AppError::Jwt(_) => ApiResponse::<()>::unauthorized(&message),
+            AppError::Forbidden { .. } => ApiResponse::<()>::forbidden(&message),
+            AppError::Validation { .. } => ApiResponse::<()>::bad_request(&message),
+            AppError::NotFound { .. } => ApiResponse::<()>::not_found(&message),
+            AppError::Conflict { field, code, .. } => {
+                ApiResponse::<()>::conflict(&message, field.clone(), code.as_deref().unwrap_or(""))
             }
-            AppError::Internal(ref message) => {
-                tracing::error!("Internal error: {}", message);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    ApiResponse::<()>::internal_error(message),
-                )
+            _ if code == ErrorCode::TransactionConflict => {
+                ApiResponse::<()>::conflict(&message, None, code.as_str())
             }
-        };
+            _ => ApiResponse::<()>::internal_error(&message),
+        }
+        .with_error_code(code);
 
-        (status, Json(response)).into_response()
+        (code.status(), Json(response)).into_response()
     }
 }
 
@@ -130,6 +253,12 @@ impl AppError {
         }
     }
 
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::Forbidden {
+            message: message.into(),
+        }
+    }
+
     pub fn validation(message: impl Into<String>) -> Self {
         Self::Validation {
             message: message.into(),
@@ -158,3 +287,48 @@ impl AppError {
         Self::Internal(message.into())
     }
 }
+
+impl AppError {
+    /// 错误对应的错误码；HTTP 状态码和 WebSocket 错误码都由它决定
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Database(diesel::result::Error::DatabaseError(
+                DatabaseErrorKind::SerializationFailure,
+                _,
+            )) => ErrorCode::TransactionConflict,
+            AppError::Database(_) => ErrorCode::DatabaseError,
+            AppError::Pool(_) => ErrorCode::ConnectionError,
+            AppError::Redis(_) => ErrorCode::CacheError,
+            AppError::Auth { .. } => ErrorCode::Unauthorized,
+            AppError::Forbidden { .. } => ErrorCode::Forbidden,
+            AppError::Validation { .. } => ErrorCode::ValidationFailed,
+            AppError::NotFound { .. } => ErrorCode::NotFound,
+            AppError::Conflict { .. } => ErrorCode::Conflict,
+            AppError::Config(_) => ErrorCode::ConfigurationError,
+            AppError::Jwt(_) => ErrorCode::InvalidToken,
+            AppError::Bcrypt(_) => ErrorCode::PasswordProcessingError,
+            AppError::Internal(_) => ErrorCode::InternalError,
+        }
+    }
+
+    /// 返回给客户端的消息；基础设施错误的细节只写日志
+    pub fn public_message(&self) -> String {
+        match self {
+            AppError::Database(_) if self.code() == ErrorCode::TransactionConflict => {
+                "Transaction conflict, please retry".to_string()
+            }
+            AppError::Database(_) => "Database error".to_string(),
+            AppError::Pool(_) => "Connection error".to_string(),
+            AppError::Redis(_) => "Cache error".to_string(),
+            AppError::Auth { message }
+            | AppError::Forbidden { message }
+            | AppError::Validation { message }
+            | AppError::Conflict { message, .. }
+            | AppError::Internal(message) => message.clone(),
+            AppError::NotFound { resource } => format!("{} not found", resource),
+            AppError::Config(_) => "Configuration error".to_string(),
+            AppError::Jwt(_) => "Invalid token".to_string(),
+            AppError::Bcrypt(_) => "Password processing error".to_string(),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use async_graphql::dataloader::Loader;
+use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor,
+    db::models::auth::{User, UserBasicInfo},
+    db::models::comment::Comment,
+    db::models::issue::{Issue, IssueResponse},
+    db::models::project::Project,
+    db::models::team::Team,
+    error::AppError,
+    graphql::graphql_error,
+    utils::AssetUrlHelper,
+};
+
+/// Connection and workspace shared by the loaders of one request. Every
+/// batch is restricted to the caller's workspace.
+#[derive(Clone)]
+pub struct LoaderScope {
+    pub executor: DbExecutor,
+    pub workspace_id: Uuid,
+    pub asset_helper: AssetUrlHelper,
+}
+
+impl LoaderScope {
+    /// Run a batch query on the executor
+    async fn read<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut PgConnection) -> Result<T, AppError> + Send + 'static,
+    ) -> Result<T, async_graphql::Error> {
+        self.executor.read(f).await.map_err(graphql_error)
+    }
+}
+
+/// Workspace members by user id
+pub struct UserLoader(pub LoaderScope);
+
+impl Loader<Uuid> for UserLoader {
+    type Value = UserBasicInfo;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        use crate::schema::{users::dsl as u, workspace_members::dsl as wm};
+        let workspace_id = self.0.workspace_id;
+        let ids = keys.to_vec();
+        let users = self
+            .0
+            .read(move |conn| {
+                Ok(u::users
+                    .inner_join(wm::workspace_members.on(wm::user_id.eq(u::id)))
+                    .filter(wm::workspace_id.eq(workspace_id))
+                    .filter(u::id.eq_any(&ids))
+                    .select(User::as_select())
+                    .load::<User>(conn)?)
+            })
+            .await?;
+        Ok(users
+            .into_iter()
+            .map(|user| {
+                let avatar_url = user
+                    .avatar_url
+                    .as_ref()
+                    .map(|url| self.0.asset_helper.process_url(url));
+                let info = UserBasicInfo {
+                    id: user.id,
+                    name: user.name,
+                    username: user.username,
+                    email: user.email,
+                    avatar_url,
+                };
+                (info.id, info)
+            })
+            .collect())
+    }
+}
+
+/// Teams by id
+pub struct TeamLoader(pub LoaderScope);
+
+impl Loader<Uuid> for TeamLoader {
+    type Value = Team;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        use crate::schema::teams::dsl as t;
+        let workspace_id = self.0.workspace_id;
+        let ids = keys.to_vec();
+        let teams = self
+            .0
+            .read(move |conn| {
+                Ok(t::teams
+                    .filter(t::workspace_id.eq(workspace_id))
+                    .filter(t::id.eq_any(&ids))
+                    .select(Team::as_select())
+                    .load::<Team>(conn)?)
+            })
+            .await?;
+        Ok(teams.into_iter().map(|team| (team.id, team)).collect())
+    }
+}
+
+/// Projects by id
+pub struct ProjectLoader(pub LoaderScope);
+
+impl Loader<Uuid> for ProjectLoader {
+    type Value = Project;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        use crate::schema::projects::dsl as p;
+        let workspace_id = self.0.workspace_id;
+        let ids = keys.to_vec();
+        let projects = self
+            .0
+            .read(move |conn| {
+                Ok(p::projects
+                    .filter(p::workspace_id.eq(workspace_id))
+                    .filter(p::id.eq_any(&ids))
+                    .select(Project::as_select())
+                    .load::<Project>(conn)?)
+            })
+            .await?;
+        Ok(projects
+            .into_iter()
+            .map(|project| (project.id, project))
+            .collect())
+    }
+}
+
+/// Which parent a batch of issues is grouped by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IssueParent {
+    Team(Uuid),
+    Project(Uuid),
+}
+
+/// Issues of teams and projects, newest first
+pub struct IssuesByParentLoader(pub LoaderScope);
+
+impl Loader<IssueParent> for IssuesByParentLoader {
+    type Value = Vec<IssueResponse>;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        keys: &[IssueParent],
+    ) -> Result<HashMap<IssueParent, Self::Value>, Self::Error> {
+        use crate::schema::{issues::dsl as i, teams::dsl as t};
+        let team_ids: Vec<Uuid> = keys
+            .iter()
+            .filter_map(|key| match key {
+                IssueParent::Team(id) => Some(*id),
+                IssueParent::Project(_) => None,
+            })
+            .collect();
+        let project_ids: Vec<Uuid> = keys
+            .iter()
+            .filter_map(|key| match key {
+                IssueParent::Project(id) => Some(*id),
+                IssueParent::Team(_) => None,
+            })
+            .collect();
+
+        let workspace_id = self.0.workspace_id;
+        let issues = self
+            .0
+            .read(move |conn| {
+                Ok(i::issues
+                    .inner_join(t::teams)
+                    .filter(t::workspace_id.eq(workspace_id))
+                    .filter(i::archived_at.is_null())
+                    .filter(
+                        i::team_id
+                            .eq_any(&team_ids)
+                            .or(i::project_id.eq_any(&project_ids)),
+                    )
+                    .order(i::created_at.desc())
+                    .select(Issue::as_select())
+                    .load::<Issue>(conn)?)
+            })
+            .await?;
+
+        let mut grouped: HashMap<IssueParent, Self::Value> =
+            keys.iter().map(|key| (*key, Vec::new())).collect();
+        for issue in issues {
+            if let Some(list) = grouped.get_mut(&IssueParent::Team(issue.team_id)) {
+                list.push(IssueResponse::from(issue.clone()));
+            }
+            if let Some(list) = issue
+                .project_id
+                .and_then(|id| grouped.get_mut(&IssueParent::Project(id)))
+            {
+                list.push(IssueResponse::from(issue));
+            }
+        }
+        Ok(grouped)
+    }
+}
+
+/// Comments of issues, oldest first, without deleted or hidden ones
+pub struct CommentsByIssueLoader(pub LoaderScope);
+
+impl Loader<Uuid> for CommentsByIssueLoader {
+    type Value = Vec<Comment>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        use crate::schema::{comments::dsl as c, issues::dsl as i, teams::dsl as t};
+        let workspace_id = self.0.workspace_id;
+        let ids = keys.to_vec();
+        let comments = self
+            .0
+            .read(move |conn| {
+                Ok(c::comments
+                    .inner_join(i::issues.inner_join(t::teams))
+                    .filter(t::workspace_id.eq(workspace_id))
+                    .filter(c::issue_id.eq_any(&ids))
+                    .filter(c::is_deleted.is_null().or(c::is_deleted.eq(false)))
+                    .filter(c::hidden_at.is_null())
+                    .order(c::created_at.asc())
+                    .select(Comment::as_select())
+                    .load::<Comment>(conn)?)
+            })
+            .await?;
+
+        let mut grouped: HashMap<Uuid, Self::Value> =
+            keys.iter().map(|key| (*key, Vec::new())).collect();
+        for comment in comments {
+            grouped.entry(comment.issue_id).or_default().push(comment);
+        }
+        Ok(grouped)
+    }
+}
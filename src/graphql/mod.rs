@@ -0,0 +1,169 @@
+pub mod loaders;
+pub mod types;
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Result, Schema,
+    dataloader::DataLoader,
+};
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor, error::AppError, services::context::RequestContext,
+    services::issues_service::IssuesService, services::projects_service::ProjectsService,
+    services::teams_service::TeamsService, utils::AssetUrlHelper,
+};
+use loaders::{
+    CommentsByIssueLoader, IssuesByParentLoader, LoaderScope, ProjectLoader, TeamLoader, UserLoader,
+};
+use types::{IssueFilterInput, IssueNode, ProjectNode, TeamNode, UserNode};
+
+/// Deepest selection a query may nest, e.g. `issues { team { issues { assignee } } }`
+pub const MAX_QUERY_DEPTH: usize = 8;
+pub const MAX_QUERY_COMPLEXITY: usize = 500;
+/// Issues returned by `issues` when `first` is not given, and the most allowed
+pub const DEFAULT_ISSUE_PAGE: usize = 50;
+pub const MAX_ISSUE_PAGE: usize = 200;
+
+pub type GraphqlSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Read-only schema over the issue, project, team and comment services
+pub fn build_schema() -> GraphqlSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Caller and database executor for one GraphQL request
+pub struct GraphqlContext {
+    pub executor: DbExecutor,
+    pub ctx: RequestContext,
+    pub asset_helper: AssetUrlHelper,
+}
+
+impl GraphqlContext {
+    /// Attach the caller and fresh dataloaders to a request. Loaders are per
+    /// request so cached rows never leak across users or workspaces.
+    pub fn attach(self, request: async_graphql::Request) -> async_graphql::Request {
+        let scope = LoaderScope {
+            executor: self.executor.clone(),
+            workspace_id: self.ctx.workspace_id,
+            asset_helper: self.asset_helper.clone(),
+        };
+        request
+            .data(DataLoader::new(UserLoader(scope.clone()), tokio::spawn))
+            .data(DataLoader::new(TeamLoader(scope.clone()), tokio::spawn))
+            .data(DataLoader::new(ProjectLoader(scope.clone()), tokio::spawn))
+            .data(DataLoader::new(
+                IssuesByParentLoader(scope.clone()),
+                tokio::spawn,
+            ))
+            .data(DataLoader::new(CommentsByIssueLoader(scope), tokio::spawn))
+            .data(self)
+    }
+}
+
+/// GraphQL error carrying the same error code the REST API would return.
+/// Database and other internal errors are logged and reported generically.
+pub fn graphql_error(err: AppError) -> async_graphql::Error {
+    let (message, code) = match err {
+        AppError::Auth { message } => (message, "UNAUTHORIZED".to_string()),
+        AppError::Forbidden { message } => (message, "FORBIDDEN".to_string()),
+        AppError::Validation { message } => (message, "VALIDATION_ERROR".to_string()),
+        AppError::NotFound { resource } => {
+            (format!("{} not found", resource), "NOT_FOUND".to_string())
+        }
+        AppError::Conflict { message, code, .. } => {
+            (message, code.unwrap_or_else(|| "CONFLICT".to_string()))
+        }
+        other => {
+            tracing::error!("GraphQL resolver error: {}", other);
+            (
+                "Internal server error".to_string(),
+                "INTERNAL_ERROR".to_string(),
+            )
+        }
+    };
+    async_graphql::Error::new(message).extend_with(|_, e| e.set("code", code))
+}
+
+/// Run a service call on the executor for the current caller
+async fn with_conn<T: Send + 'static>(
+    ctx: &Context<'_>,
+    f: impl FnOnce(&mut diesel::PgConnection, &RequestContext, &AssetUrlHelper) -> Result<T, AppError>
+    + Send
+    + 'static,
+) -> Result<T> {
+    let data = ctx.data_unchecked::<GraphqlContext>();
+    let rc = data.ctx.clone();
+    let assets = data.asset_helper.clone();
+    data.executor
+        .read(move |conn| f(conn, &rc, &assets))
+        .await
+        .map_err(graphql_error)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The signed-in user
+    async fn me(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        let user_id = ctx.data_unchecked::<GraphqlContext>().ctx.user_id;
+        let loader = ctx.data_unchecked::<DataLoader<UserLoader>>();
+        Ok(loader.load_one(user_id).await?.map(UserNode))
+    }
+
+    async fn issue(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<IssueNode>> {
+        let issue = with_conn(ctx, move |conn, rc, _| {
+            match IssuesService::get_by_id(conn, rc, id) {
+                Ok(issue) => Ok(Some(issue)),
+                Err(AppError::NotFound { .. }) => Ok(None),
+                Err(e) => Err(e),
+            }
+        })
+        .await?;
+        Ok(issue.map(IssueNode))
+    }
+
+    /// Issues in the current workspace, newest first
+    async fn issues(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<IssueFilterInput>,
+        first: Option<i32>,
+    ) -> Result<Vec<IssueNode>> {
+        let limit = first
+            .map(|n| n.clamp(0, MAX_ISSUE_PAGE as i32) as usize)
+            .unwrap_or(DEFAULT_ISSUE_PAGE);
+        let filters = filter.unwrap_or_default().into();
+        let issues = with_conn(ctx, move |conn, rc, _| {
+            IssuesService::list(conn, rc, &filters)
+        })
+        .await?;
+        Ok(issues.into_iter().take(limit).map(IssueNode).collect())
+    }
+
+    async fn projects(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        owner_id: Option<Uuid>,
+    ) -> Result<Vec<ProjectNode>> {
+        let projects = with_conn(ctx, move |conn, rc, assets| {
+            ProjectsService::list_infos(conn, rc, assets, search, owner_id)
+        })
+        .await?;
+        Ok(projects.into_iter().map(ProjectNode::from).collect())
+    }
+
+    async fn teams(&self, ctx: &Context<'_>) -> Result<Vec<TeamNode>> {
+        let teams = with_conn(ctx, move |conn, rc, _| TeamsService::list(conn, rc)).await?;
+        Ok(teams.into_iter().map(TeamNode).collect())
+    }
+
+    async fn team(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<TeamNode>> {
+        let loader = ctx.data_unchecked::<DataLoader<TeamLoader>>();
+        Ok(loader.load_one(id).await?.map(TeamNode))
+    }
+}
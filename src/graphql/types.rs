@@ -0,0 +1,366 @@
+use async_graphql::{Context, Enum, InputObject, Object, Result, dataloader::DataLoader};
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::{
+    db::enums::{IssuePriority, ProjectPriority},
+    db::models::auth::UserBasicInfo,
+    db::models::comment::Comment,
+    db::models::issue::IssueResponse,
+    db::models::project::{Project, ProjectInfo},
+    db::models::team::Team,
+    graphql::loaders::{
+        CommentsByIssueLoader, IssueParent, IssuesByParentLoader, ProjectLoader, TeamLoader,
+        UserLoader,
+    },
+    services::issues_service::IssueFilters,
+};
+
+/// Priority shared by issues and projects
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Priority {
+    None,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl From<&IssuePriority> for Priority {
+    fn from(priority: &IssuePriority) -> Self {
+        match priority {
+            IssuePriority::None => Priority::None,
+            IssuePriority::Low => Priority::Low,
+            IssuePriority::Medium => Priority::Medium,
+            IssuePriority::High => Priority::High,
+            IssuePriority::Urgent => Priority::Urgent,
+        }
+    }
+}
+
+impl From<Priority> for IssuePriority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::None => IssuePriority::None,
+            Priority::Low => IssuePriority::Low,
+            Priority::Medium => IssuePriority::Medium,
+            Priority::High => IssuePriority::High,
+            Priority::Urgent => IssuePriority::Urgent,
+        }
+    }
+}
+
+impl From<&ProjectPriority> for Priority {
+    fn from(priority: &ProjectPriority) -> Self {
+        match priority {
+            ProjectPriority::None => Priority::None,
+            ProjectPriority::Low => Priority::Low,
+            ProjectPriority::Medium => Priority::Medium,
+            ProjectPriority::High => Priority::High,
+            ProjectPriority::Urgent => Priority::Urgent,
+        }
+    }
+}
+
+/// Filters for the `issues` query, matching `GET /issues`
+#[derive(InputObject, Default)]
+pub struct IssueFilterInput {
+    pub team_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub assignee_id: Option<Uuid>,
+    pub priority: Option<Priority>,
+    pub search: Option<String>,
+    /// Also list archived issues
+    pub include_archived: Option<bool>,
+}
+
+impl From<IssueFilterInput> for IssueFilters {
+    fn from(filter: IssueFilterInput) -> Self {
+        Self {
+            team_id: filter.team_id,
+            project_id: filter.project_id,
+            assignee_id: filter.assignee_id,
+            priority: filter.priority.map(Into::into),
+            search: filter.search,
+            include_archived: filter.include_archived.unwrap_or(false),
+        }
+    }
+}
+
+async fn load_user(ctx: &Context<'_>, id: Uuid) -> Result<Option<UserNode>> {
+    let loader = ctx.data_unchecked::<DataLoader<UserLoader>>();
+    Ok(loader.load_one(id).await?.map(UserNode))
+}
+
+pub struct UserNode(pub UserBasicInfo);
+
+#[Object(name = "User")]
+impl UserNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn username(&self) -> &str {
+        &self.0.username
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn avatar_url(&self) -> Option<&str> {
+        self.0.avatar_url.as_deref()
+    }
+}
+
+pub struct TeamNode(pub Team);
+
+#[Object(name = "Team")]
+impl TeamNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn team_key(&self) -> &str {
+        &self.0.team_key
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn icon_url(&self) -> Option<&str> {
+        self.0.icon_url.as_deref()
+    }
+
+    async fn is_private(&self) -> bool {
+        self.0.is_private
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn issues(&self, ctx: &Context<'_>) -> Result<Vec<IssueNode>> {
+        let loader = ctx.data_unchecked::<DataLoader<IssuesByParentLoader>>();
+        let issues = loader.load_one(IssueParent::Team(self.0.id)).await?;
+        Ok(issues
+            .unwrap_or_default()
+            .into_iter()
+            .map(IssueNode)
+            .collect())
+    }
+}
+
+pub struct ProjectNode {
+    pub id: Uuid,
+    pub name: String,
+    pub project_key: String,
+    pub description: Option<String>,
+    pub owner_id: Uuid,
+    pub target_date: Option<NaiveDate>,
+    pub priority: Priority,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Project> for ProjectNode {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id,
+            name: project.name,
+            project_key: project.project_key,
+            description: project.description,
+            owner_id: project.owner_id,
+            target_date: project.target_date,
+            priority: Priority::from(&project.priority),
+            created_at: project.created_at,
+            updated_at: project.updated_at,
+        }
+    }
+}
+
+impl From<ProjectInfo> for ProjectNode {
+    fn from(project: ProjectInfo) -> Self {
+        Self {
+            id: project.id,
+            name: project.name,
+            project_key: project.project_key,
+            description: project.description,
+            owner_id: project.owner.id,
+            target_date: project.target_date,
+            priority: Priority::from(&project.priority),
+            created_at: project.created_at,
+            updated_at: project.updated_at,
+        }
+    }
+}
+
+#[Object(name = "Project")]
+impl ProjectNode {
+    async fn id(&self) -> Uuid {
+        self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn project_key(&self) -> &str {
+        &self.project_key
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    async fn target_date(&self) -> Option<NaiveDate> {
+        self.target_date
+    }
+
+    async fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        load_user(ctx, self.owner_id).await
+    }
+
+    async fn issues(&self, ctx: &Context<'_>) -> Result<Vec<IssueNode>> {
+        let loader = ctx.data_unchecked::<DataLoader<IssuesByParentLoader>>();
+        let issues = loader.load_one(IssueParent::Project(self.id)).await?;
+        Ok(issues
+            .unwrap_or_default()
+            .into_iter()
+            .map(IssueNode)
+            .collect())
+    }
+}
+
+pub struct IssueNode(pub IssueResponse);
+
+#[Object(name = "Issue")]
+impl IssueNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn issue_number(&self) -> i32 {
+        self.0.issue_number
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn priority(&self) -> Priority {
+        Priority::from(&self.0.priority)
+    }
+
+    async fn workflow_state_id(&self) -> Option<Uuid> {
+        self.0.workflow_state_id
+    }
+
+    async fn parent_issue_id(&self) -> Option<Uuid> {
+        self.0.parent_issue_id
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn assignee(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        match self.0.assignee_id {
+            Some(id) => load_user(ctx, id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn creator(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        load_user(ctx, self.0.creator_id).await
+    }
+
+    async fn team(&self, ctx: &Context<'_>) -> Result<Option<TeamNode>> {
+        let loader = ctx.data_unchecked::<DataLoader<TeamLoader>>();
+        Ok(loader.load_one(self.0.team_id).await?.map(TeamNode))
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Option<ProjectNode>> {
+        let Some(project_id) = self.0.project_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<DataLoader<ProjectLoader>>();
+        Ok(loader.load_one(project_id).await?.map(ProjectNode::from))
+    }
+
+    async fn comments(&self, ctx: &Context<'_>) -> Result<Vec<CommentNode>> {
+        let loader = ctx.data_unchecked::<DataLoader<CommentsByIssueLoader>>();
+        let comments = loader.load_one(self.0.id).await?;
+        Ok(comments
+            .unwrap_or_default()
+            .into_iter()
+            .map(CommentNode)
+            .collect())
+    }
+}
+
+pub struct CommentNode(pub Comment);
+
+#[Object(name = "Comment")]
+impl CommentNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn content(&self) -> &str {
+        &self.0.content
+    }
+
+    async fn content_type(&self) -> Option<&str> {
+        self.0.content_type.as_deref()
+    }
+
+    async fn parent_comment_id(&self) -> Option<Uuid> {
+        self.0.parent_comment_id
+    }
+
+    async fn is_edited(&self) -> bool {
+        self.0.is_edited.unwrap_or(false)
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn author(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        load_user(ctx, self.0.author_id).await
+    }
+}
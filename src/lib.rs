@@ -2,41 +2,159 @@ pub mod cache;
 pub mod config;
 pub mod db;
 pub mod error;
+pub mod graphql;
+pub mod metrics;
 pub mod middleware;
 pub mod routes;
+pub mod scheduler;
 pub mod schema;
 pub mod services;
+pub mod supervisor;
+pub mod testing;
 pub mod utils;
 pub mod validation;
 pub mod websocket;
 
-use crate::config::Config;
-use crate::db::DbPool;
+use crate::cache::{
+    CacheStore, LockManager, MaintenanceMode, RedisCacheStore, TokenRevocationList,
+};
+use crate::config::{Config, EmailBackendConfig, EmailConfig};
+use crate::db::{DbExecutor, DbPool};
+use crate::graphql::GraphqlSchema;
+use crate::middleware::HttpRateLimiter;
 use crate::middleware::auth::{AuthConfig, AuthService};
-use crate::utils::AssetUrlHelper;
+use crate::scheduler::Scheduler;
+use crate::services::dashboard_service::{DashboardSource, PgDashboardSource};
+use crate::services::email_service::{EmailService, Mailer};
+use crate::supervisor::TaskSupervisor;
+use crate::utils::clock::{Clock, IdGenerator, RandomIds, SystemClock};
+use crate::utils::{AssetUrlHelper, ObjectStorage};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DbPool,
+    /// Runs database work off the async runtime; prefer it over `db` in handlers
+    pub executor: DbExecutor,
     pub redis: redis::Client,
+    /// Key-value cache for handlers; prefer it over `redis` for cached reads
+    pub cache: Arc<dyn CacheStore>,
     pub config: Arc<Config>,
     pub asset_helper: AssetUrlHelper,
     pub auth_service: AuthService,
+    pub token_revocations: TokenRevocationList,
+    /// Object storage for issue attachments; `None` when not configured
+    pub storage: Option<ObjectStorage>,
+    /// Named background tasks; their health backs `/readyz`
+    pub supervisor: TaskSupervisor,
+    /// Periodic jobs, run as supervised tasks once started
+    pub scheduler: Scheduler,
+    /// Redis-backed limits for HTTP routes
+    pub rate_limiter: HttpRateLimiter,
+    /// Read-only GraphQL schema served at `/graphql`
+    pub graphql: GraphqlSchema,
+    /// Redis locks for work that must run on one replica at a time
+    pub locks: LockManager,
+    /// Transactional email, sent from a Redis queue by the worker
+    pub email: Arc<dyn Mailer>,
+    /// Uncached dashboard summaries
+    pub dashboards: Arc<dyn DashboardSource>,
+    /// Read-only switch for the whole server or single workspaces
+    pub maintenance: MaintenanceMode,
+    /// Source of the current time for services and the WebSocket layer
+    pub clock: Arc<dyn Clock>,
+    /// Source of new ids for services and the WebSocket layer
+    pub ids: Arc<dyn IdGenerator>,
 }
 
 impl AppState {
     pub fn new(db: DbPool, redis: redis::Client, config: Config) -> Self {
         let asset_helper = AssetUrlHelper::new(&config.assets());
         let auth_service = AuthService::new(AuthConfig::default());
+        if let Ok(policy) =
+            services::notifications_service::NotificationBatchPolicy::from_config(&config)
+        {
+            services::notifications_service::NotificationBatchPolicy::install(policy);
+        }
+        let token_revocations = TokenRevocationList::new(redis.clone());
+        let rate_limiter = HttpRateLimiter::new(redis.clone(), config.rate_limit());
+        let locks = LockManager::new(redis.clone());
+        let maintenance = MaintenanceMode::new(redis.clone());
+        let email = config
+            .email()
+            .and_then(|email| EmailService::new(&email, redis.clone()))
+            .unwrap_or_else(|e| {
+                tracing::warn!("Email sending disabled, logging emails instead: {}", e);
+                let fallback = EmailConfig {
+                    from: config.email_from.clone(),
+                    app_url: config.app_url.clone(),
+                    backend: EmailBackendConfig::Log,
+                };
+                EmailService::new(&fallback, redis.clone()).expect("log transport always builds")
+            });
+        let storage = config
+            .storage()
+            .and_then(|storage| match ObjectStorage::new(&storage) {
+                Ok(storage) => Some(storage),
+                Err(e) => {
+                    tracing::warn!("Attachment storage disabled: {}", e);
+                    None
+                }
+            });
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+        let ids: Arc<dyn IdGenerator> = Arc::new(RandomIds);
+        utils::clock::install(clock.clone(), ids.clone());
+        let executor = DbExecutor::new(db.clone());
+        let scheduler = Scheduler::with_overrides(&config.scheduler_jobs);
         Self {
+            dashboards: Arc::new(PgDashboardSource::new(executor.clone())),
+            executor,
             db,
+            cache: Arc::new(RedisCacheStore::new(redis.clone())),
             redis,
             config: Arc::new(config),
             asset_helper,
             auth_service,
+            token_revocations,
+            storage,
+            supervisor: TaskSupervisor::new(),
+            scheduler,
+            rate_limiter,
+            graphql: graphql::build_schema(),
+            locks,
+            email: Arc::new(email),
+            maintenance,
+            clock,
+            ids,
         }
     }
+
+    /// Replace the clock and id generator, e.g. with a frozen clock and
+    /// sequential ids in tests; they are installed process-wide
+    pub fn with_time_providers(mut self, clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> Self {
+        utils::clock::install(clock.clone(), ids.clone());
+        self.clock = clock;
+        self.ids = ids;
+        self
+    }
+
+    /// Replace the cache, e.g. with [`cache::MemoryCacheStore`] in tests
+    pub fn with_cache(mut self, cache: Arc<dyn CacheStore>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Replace the mailer, e.g. with [`testing::RecordingMailer`] in tests
+    pub fn with_mailer(mut self, email: Arc<dyn Mailer>) -> Self {
+        self.email = email;
+        self
+    }
+
+    /// Replace where dashboard summaries are loaded from
+    pub fn with_dashboards(mut self, dashboards: Arc<dyn DashboardSource>) -> Self {
+        self.dashboards = dashboards;
+        self
+    }
 }
 
 pub fn init_tracing(config: &Config) {
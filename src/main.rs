@@ -1,8 +1,19 @@
 use axum::{Router, Server, middleware::from_fn};
-use rust_backend::middleware::{performance_monitoring_middleware, request_tracking_middleware};
+use rust_backend::middleware::{
+    SecurityHeaders, conditional_get_middleware, cors_layer, maintenance_middleware,
+    performance_monitoring_middleware, rate_limit_middleware, redaction_middleware,
+    request_tracking_middleware, security_headers_middleware,
+};
+use rust_backend::scheduler::{Schedule, ScheduledJob};
+use rust_backend::services::cycles_service::CyclesService;
+use rust_backend::services::issue_archive_service::IssueArchiveService;
+use rust_backend::services::telemetry_service::{
+    TELEMETRY_JOB, TelemetryReporter, TelemetryService,
+};
+use rust_backend::services::upload_sessions_service::UploadSessionsService;
 use rust_backend::{AppState, db, init_tracing, websocket};
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -26,33 +37,169 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Application state
     let state = Arc::new(AppState::new(db_pool, redis, config.clone()));
 
-    // CORS configuration
-    let cors = if config.cors_origins.contains(&"*".to_string()) {
-        CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers(Any)
-    } else {
-        let origins: Result<Vec<_>, _> = config
-            .cors_origins
-            .iter()
-            .map(|origin| origin.parse())
-            .collect();
-
-        CorsLayer::new()
-            .allow_origin(origins?)
-            .allow_methods(Any)
-            .allow_headers(Any)
-    };
-
-    // Create WebSocket state and start cleanup task
-    let ws_state = websocket::create_websocket_state(Arc::new(state.db.clone()), &config);
-    let ws_manager = ws_state.ws_manager.clone();
+    // CORS and security headers, both configured per environment
+    let cors = cors_layer(&config.cors())?;
+    let security_headers = Arc::new(SecurityHeaders::new(&config.security_headers())?);
 
-    // Start WebSocket cleanup task
-    tokio::spawn(async move {
-        websocket::start_connection_cleanup_task(ws_manager).await;
+    // Create WebSocket state; its listeners run under the supervisor and its
+    // cleanups as scheduled jobs
+    let ws_state = websocket::create_websocket_state(
+        Arc::new(state.db.clone()),
+        &config,
+        &state.supervisor,
+        &state.scheduler,
+    );
+    rust_backend::services::notifications_service::NotificationsService::install_push(
+        ws_state.ws_manager.clone(),
+    );
+    rust_backend::services::realtime_service::RealtimeService::install(ws_state.ws_manager.clone());
+    TelemetryService::install(ws_state.ws_manager.clone());
+    rust_backend::services::dead_letter_service::DeadLetterService::install(
+        ws_state.command_handler.clone(),
+    );
+    // Cached workspace reads and the services that invalidate them share the app cache
+    rust_backend::cache::typed::install(state.cache.clone());
+    // Forward maintenance toggles made on any replica to local WebSocket clients
+    state.supervisor.spawn("maintenance_listener", {
+        let maintenance = state.maintenance.clone();
+        let ws_manager = ws_state.ws_manager.clone();
+        move || maintenance.clone().run_listener(ws_manager.clone())
     });
+    // Keep asset region availability and latency current for URL resolution
+    if !state.asset_helper.region_names().is_empty() {
+        state.supervisor.spawn("asset_region_probe", {
+            let helper = state.asset_helper.clone();
+            let interval = std::time::Duration::from_secs(config.assets_region_probe_secs);
+            move || helper.clone().run_region_probe(interval)
+        });
+    }
+
+    // Move cycles between planned, active and completed as their dates pass
+    state.scheduler.register(
+        ScheduledJob::new(
+            "cycle_status",
+            "*/15 * * * *".parse().expect("valid cycle status schedule"),
+            {
+                let executor = state.executor.clone();
+                move || {
+                    let executor = executor.clone();
+                    async move {
+                        let updates = executor
+                            .transaction(CyclesService::auto_update_status)
+                            .await?;
+                        Ok(format!(
+                            "activated {}, completed {}",
+                            updates.activated, updates.completed
+                        ))
+                    }
+                }
+            },
+        )
+        .exclusive(),
+    );
+    // Keep a planned cycle ahead for teams with a cycle cadence and roll
+    // unfinished issues over when a cycle ends
+    state.scheduler.register(
+        ScheduledJob::new(
+            "cycle_auto_create",
+            "5 * * * *"
+                .parse()
+                .expect("valid cycle auto-create schedule"),
+            {
+                let executor = state.executor.clone();
+                move || {
+                    let executor = executor.clone();
+                    async move {
+                        let summary = executor
+                            .transaction(CyclesService::auto_create_next)
+                            .await?;
+                        Ok(format!(
+                            "created {}, rolled over {}",
+                            summary.created, summary.rolled_over
+                        ))
+                    }
+                }
+            },
+        )
+        .exclusive(),
+    );
+    // Archive completed issues once they pass their workspace's auto-archive age
+    state.scheduler.register(
+        ScheduledJob::new(
+            "issue_auto_archive",
+            "40 3 * * *"
+                .parse()
+                .expect("valid issue auto-archive schedule"),
+            {
+                let executor = state.executor.clone();
+                move || {
+                    let executor = executor.clone();
+                    async move {
+                        let summary = executor
+                            .transaction(IssueArchiveService::run_auto_archive)
+                            .await?;
+                        Ok(format!(
+                            "archived {} in {} workspace(s)",
+                            summary.archived, summary.workspaces
+                        ))
+                    }
+                }
+            },
+        )
+        .exclusive(),
+    );
+    // Abort resumable uploads nobody has appended to within their TTL
+    if let Some(storage) = state.storage.clone() {
+        state.scheduler.register(
+            ScheduledJob::new(
+                "upload_session_expiry",
+                Schedule::every(std::time::Duration::from_secs(15 * 60)),
+                {
+                    let executor = state.executor.clone();
+                    move || {
+                        let executor = executor.clone();
+                        let storage = storage.clone();
+                        async move {
+                            let expired =
+                                UploadSessionsService::expire_abandoned(&executor, &storage)
+                                    .await?;
+                            Ok(format!("expired {}", expired))
+                        }
+                    }
+                },
+            )
+            .exclusive(),
+        );
+    }
+    // Send anonymous usage reports once a day when the operator opted in
+    if let Some(telemetry) = config.telemetry() {
+        match TelemetryReporter::new(&telemetry) {
+            Ok(reporter) => {
+                let reporter = Arc::new(reporter);
+                state.scheduler.register(
+                    ScheduledJob::new(
+                        TELEMETRY_JOB,
+                        "0 4 * * *".parse().expect("valid telemetry schedule"),
+                        {
+                            let executor = state.executor.clone();
+                            move || {
+                                let executor = executor.clone();
+                                let reporter = reporter.clone();
+                                async move { reporter.send(&executor).await }
+                            }
+                        },
+                    )
+                    .exclusive(),
+                );
+            }
+            Err(e) => tracing::warn!("Usage telemetry disabled: {}", e),
+        }
+    }
+    state.scheduler.start(
+        &state.supervisor,
+        state.executor.clone(),
+        state.locks.clone(),
+    );
 
     // Create the auth routes that don't need authentication
     let auth_routes = Router::new()
@@ -64,21 +211,120 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             "/auth/login",
             axum::routing::post(rust_backend::routes::auth::login),
         )
-        .with_state(state.clone());
+        .route(
+            "/auth/oauth/:provider/authorize",
+            axum::routing::get(rust_backend::routes::auth::oauth_authorize),
+        )
+        .route(
+            "/auth/oauth/:provider/callback",
+            axum::routing::get(rust_backend::routes::auth::oauth_callback),
+        )
+        .route(
+            "/oauth/token",
+            axum::routing::post(rust_backend::routes::oauth::token),
+        )
+        .route(
+            "/oauth/revoke",
+            axum::routing::post(rust_backend::routes::oauth::revoke),
+        )
+        .route(
+            "/inbound/email",
+            axum::routing::post(rust_backend::routes::inbound::receive_email),
+        )
+        .route(
+            "/integrations/github/webhook",
+            axum::routing::post(rust_backend::routes::integrations::receive_github_webhook),
+        )
+        .route(
+            "/integrations/errors/events",
+            axum::routing::post(rust_backend::routes::integrations::receive_error_event),
+        )
+        .route(
+            "/portal/forms/:slug",
+            axum::routing::get(rust_backend::routes::issue_forms::get_portal_form),
+        )
+        .route(
+            "/portal/forms/:slug/submissions",
+            axum::routing::post(rust_backend::routes::issue_forms::submit_portal_form),
+        )
+        .route(
+            "/openapi.json",
+            axum::routing::get(rust_backend::routes::docs::openapi_json),
+        )
+        .route(
+            "/docs",
+            axum::routing::get(rust_backend::routes::docs::swagger_ui),
+        )
+        .route(
+            "/error-codes",
+            axum::routing::get(rust_backend::routes::error_codes::list_error_codes),
+        )
+        .route(
+            "/healthz",
+            axum::routing::get(rust_backend::routes::health::healthz),
+        )
+        .route(
+            "/readyz",
+            axum::routing::get(rust_backend::routes::health::readyz),
+        )
+        .with_state(state.clone())
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            maintenance_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ));
 
-    // Build router - apply auth middleware only to routes that need it
-    let protected_routes = rust_backend::routes::create_router(state.clone()).layer(
-        axum::middleware::from_fn_with_state(
-            Arc::new(state.db.clone()),
+    // Build router - apply auth middleware only to routes that need it.
+    // Rate limiting sits inside auth so it can count per user, and response
+    // redaction inside that so it knows who the caller is. The maintenance
+    // check also needs the caller's workspace. Conditional GETs answer 304
+    // innermost, after the caller has been counted.
+    let protected_routes = rust_backend::routes::create_router(state.clone())
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            conditional_get_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            redaction_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            maintenance_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
             rust_backend::middleware::auth::auth_middleware,
-        ),
-    );
+        ));
+
+    // Prometheus scrape endpoint; kept outside auth and rate limiting
+    let metrics_routes = Router::new()
+        .route(
+            "/metrics",
+            axum::routing::get(rust_backend::routes::health::metrics),
+        )
+        .with_state(rust_backend::routes::health::MetricsState {
+            app: state.clone(),
+            ws_monitor: ws_state.monitor.clone(),
+        });
 
     let app = Router::new()
         .merge(auth_routes)
         .merge(protected_routes)
+        .merge(metrics_routes)
         .merge(websocket::create_websocket_routes().with_state(ws_state))
         .layer(cors)
+        .layer(axum::middleware::from_fn_with_state(
+            security_headers,
+            security_headers_middleware,
+        ))
         .layer(from_fn(request_tracking_middleware))
         .layer(from_fn(performance_monitoring_middleware))
         .layer(from_fn(rust_backend::middleware::logger::logger));
@@ -88,7 +334,44 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Server running at http://{}", addr);
     tracing::info!("WebSocket endpoint available at ws://{}/ws", addr);
 
-    Server::bind(&addr).serve(app.into_make_service()).await?;
+    Server::bind(&addr)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    tracing::info!("Stopping background tasks");
+    if !state
+        .supervisor
+        .shutdown(std::time::Duration::from_secs(10))
+        .await
+    {
+        tracing::warn!("Background tasks did not stop within 10s");
+    }
 
     Ok(())
 }
+
+/// Resolves on Ctrl-C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("Shutdown signal received");
+}
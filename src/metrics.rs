@@ -0,0 +1,165 @@
+//! Prometheus metrics
+//!
+//! `performance_monitoring_middleware` records every HTTP request into the
+//! process-wide [`HttpMetrics`]; `/metrics` renders it together with
+//! WebSocket and database pool gauges in the Prometheus text format.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the request latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+static HTTP_METRICS: LazyLock<HttpMetrics> = LazyLock::new(HttpMetrics::default);
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Non-cumulative count per bucket; cumulated when rendered
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|le| seconds <= *le) {
+            self.buckets[bucket] += 1;
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+/// Latency histograms per method, route template and status code
+#[derive(Debug, Default)]
+pub struct HttpMetrics {
+    series: Mutex<BTreeMap<(String, String, u16), Histogram>>,
+}
+
+impl HttpMetrics {
+    pub fn global() -> &'static HttpMetrics {
+        &HTTP_METRICS
+    }
+
+    /// Record one request; `route` is the matched route template (e.g.
+    /// `/issues/:issue_id`) so path parameters don't explode cardinality
+    pub fn observe(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        self.series
+            .lock()
+            .unwrap()
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default()
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn render(&self, out: &mut PrometheusText) {
+        let name = "http_request_duration_seconds";
+        out.family(name, "histogram", "HTTP request latency by route");
+        for ((method, route, status), histogram) in self.series.lock().unwrap().iter() {
+            let status = status.to_string();
+            let labels = [
+                ("method", method.as_str()),
+                ("route", route.as_str()),
+                ("status", status.as_str()),
+            ];
+            let mut cumulative = 0;
+            for (le, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let le = le.to_string();
+                out.sample_with(
+                    &format!("{}_bucket", name),
+                    &labels,
+                    ("le", &le),
+                    cumulative as f64,
+                );
+            }
+            out.sample_with(
+                &format!("{}_bucket", name),
+                &labels,
+                ("le", "+Inf"),
+                histogram.count as f64,
+            );
+            out.sample(&format!("{}_sum", name), &labels, histogram.sum);
+            out.sample(&format!("{}_count", name), &labels, histogram.count as f64);
+        }
+    }
+}
+
+/// Builder for the Prometheus text exposition format
+#[derive(Debug, Default)]
+pub struct PrometheusText {
+    out: String,
+}
+
+impl PrometheusText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a metric family; `kind` is `counter`, `gauge` or `histogram`
+    pub fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.write_sample(name, labels.iter().copied(), value);
+    }
+
+    /// A sample with one extra label, such as a histogram bucket's `le`
+    pub fn sample_with(
+        &mut self,
+        name: &str,
+        labels: &[(&str, &str)],
+        extra: (&str, &str),
+        value: f64,
+    ) {
+        self.write_sample(
+            name,
+            labels.iter().copied().chain(std::iter::once(extra)),
+            value,
+        );
+    }
+
+    /// Shorthand for a family with a single unlabelled sample
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, "gauge", help);
+        self.sample(name, &[], value);
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, "counter", help);
+        self.sample(name, &[], value);
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    fn write_sample<'a>(
+        &mut self,
+        name: &str,
+        labels: impl Iterator<Item = (&'a str, &'a str)>,
+        value: f64,
+    ) {
+        let labels: Vec<String> = labels
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+            .collect();
+        if labels.is_empty() {
+            let _ = writeln!(self.out, "{} {}", name, value);
+        } else {
+            let _ = writeln!(self.out, "{}{{{}}} {}", name, labels.join(","), value);
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
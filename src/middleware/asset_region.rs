@@ -0,0 +1,48 @@
+use crate::middleware::auth::AuthUserInfo;
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use std::convert::Infallible;
+
+/// 客户端指定资源 CDN 区域的请求头
+pub const ASSET_REGION_HEADER: &str = "x-asset-region";
+
+/// 请求的资源区域提示
+///
+/// 优先使用 `X-Asset-Region` 请求头，其次是已登录用户的区域偏好；
+/// 都没有时为 `None`，资源 URL 使用默认地址。
+#[derive(Debug, Clone, Default)]
+pub struct AssetRegionHint(pub Option<String>);
+
+impl AssetRegionHint {
+    pub fn as_deref(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    fn from_parts(parts: &Parts) -> Self {
+        let header = parts
+            .headers
+            .get(ASSET_REGION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string);
+        let preference = || {
+            parts
+                .extensions
+                .get::<AuthUserInfo>()
+                .and_then(|info| info.asset_region.clone())
+        };
+        Self(header.or_else(preference))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AssetRegionHint
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_parts(parts))
+    }
+}
@@ -1,8 +1,17 @@
+use crate::AppState;
+use crate::db::models::api_token::{api_client_types, api_tiers};
+use crate::db::models::oauth_app::oauth_scopes;
 use crate::db::models::{ApiResponse, ErrorDetail, User};
-use crate::db::{DbPool, models::AuthUser};
+use crate::db::{DbExecutor, DbPool, models::AuthUser};
+use crate::error::AppError;
+use crate::services::api_keys_service::ApiKeysService;
+use crate::services::api_tokens_service::ApiTokensService;
+use crate::services::context::AuthChannel;
+use crate::services::oauth_service::OAuthService;
+use crate::services::service_accounts_service::ServiceAccountsService;
 use axum::{
     Json,
-    extract::{FromRequestParts, State},
+    extract::{FromRequestParts, MatchedPath, State},
     http::{Request, StatusCode, header::AUTHORIZATION},
     middleware::Next,
     response::{IntoResponse, Response},
@@ -156,7 +165,7 @@ impl AuthService {
 }
 
 pub async fn auth_middleware(
-    State(pool): State<Arc<DbPool>>,
+    State(state): State<Arc<AppState>>,
     mut request: Request<axum::body::Body>,
     next: Next<axum::body::Body>,
 ) -> Result<Response, Response> {
@@ -178,6 +187,14 @@ pub async fn auth_middleware(
         }
     };
 
+    // 个人访问令牌、OAuth 访问令牌和工作区 API key 走单独的认证和用量统计流程
+    if ApiTokensService::is_api_token(&token)
+        || OAuthService::is_access_token(&token)
+        || ApiKeysService::is_api_key(&token)
+    {
+        return api_client_auth(&state.db, &token, request, next).await;
+    }
+
     // 创建认证服务实例
     let auth_service: AuthService = AuthService::new(AuthConfig::default());
 
@@ -190,8 +207,18 @@ pub async fn auth_middleware(
         }
     };
 
+    // 检查token是否已被吊销（登出、修改密码或管理员强制下线）
+    if state
+        .token_revocations
+        .is_revoked(claims.sub, &claims.jti, claims.iat)
+        .await
+    {
+        let response = ApiResponse::<()>::unauthorized("Access token has been revoked");
+        return Err((StatusCode::UNAUTHORIZED, Json(response)).into_response());
+    }
+
     // 从数据库获取用户信息
-    let user = match get_user_by_id(&pool, claims.sub).await {
+    let user = match get_user_by_id(&state.db, claims.sub).await {
         Ok(user) => user,
         Err(_) => {
             let response = ApiResponse::<()>::unauthorized("User not found or inactive");
@@ -199,18 +226,16 @@ pub async fn auth_middleware(
         }
     };
 
+    // 服务账号不能登录，只能使用 API 令牌
+    if user.is_service_account {
+        let response =
+            ApiResponse::<()>::unauthorized("Service accounts must authenticate with an API token");
+        return Err((StatusCode::UNAUTHORIZED, Json(response)).into_response());
+    }
+
     // 检查用户是否有当前工作区
     if user.current_workspace_id.is_none() {
-        let response = ApiResponse::<()>::error(
-            400,
-            "No current workspace found",
-            vec![ErrorDetail {
-                field: None,
-                code: "NO_WORKSPACE".to_string(),
-                message: "No current workspace found for user".to_string(),
-            }],
-        );
-        return Err((StatusCode::BAD_REQUEST, Json(response)).into_response());
+        return Err(no_workspace_response());
     }
 
     // 检查token是否需要续期（距离过期还有15分钟时续期）
@@ -222,20 +247,14 @@ pub async fn auth_middleware(
     let time_until_expiry = claims.exp.saturating_sub(now);
     let should_refresh = time_until_expiry <= 15 * 60; // 15分钟
 
-    // 构建认证用户信息
-    let auth_user_info = AuthUserInfo {
-        user: AuthUser {
-            id: user.id,
-            email: user.email.clone(),
-            username: user.username.clone(),
-            name: user.name.clone(),
-            avatar_url: user.avatar_url.clone(),
-        },
-        current_workspace_id: user.current_workspace_id,
-    };
-
-    // 将用户信息添加到请求扩展中
-    request.extensions_mut().insert(auth_user_info);
+    // 将用户信息和当前token信息添加到请求扩展中
+    request
+        .extensions_mut()
+        .insert(auth_user_info(&user, AuthChannel::Session));
+    request.extensions_mut().insert(AccessTokenInfo {
+        jti: claims.jti.clone(),
+        expires_at: claims.exp,
+    });
 
     if should_refresh {
         // 生成新的access token
@@ -260,6 +279,206 @@ pub async fn auth_middleware(
     Ok(next.run(request).await)
 }
 
+/// 当前请求所用 JWT 的标识，登出时用于吊销该 token；API 客户端请求没有此信息
+#[derive(Debug, Clone)]
+pub struct AccessTokenInfo {
+    pub jti: String,
+    pub expires_at: u64,
+}
+
+/// 通过个人访问令牌、OAuth 应用或工作区 API key 调用 API 时的客户端信息
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    pub client_type: &'static str,
+    pub client_id: Uuid,
+    pub tier: String,
+    /// OAuth 应用、API key 或令牌的 scope；未限定 scope 的个人访问令牌为 `None`
+    pub scopes: Option<Vec<String>>,
+    /// API key 或服务账号所属的工作区，请求固定在该工作区内；其他客户端使用用户的当前工作区
+    pub workspace_id: Option<Uuid>,
+    /// 认证渠道，权限检查据此应用工作区对 API 调用的额外限制
+    pub channel: AuthChannel,
+}
+
+/// 解析个人访问令牌、OAuth 访问令牌或 API key，返回客户端信息和所代表的用户
+fn resolve_api_client(
+    conn: &mut diesel::PgConnection,
+    raw_token: &str,
+) -> Result<(ApiClient, Uuid), crate::error::AppError> {
+    if OAuthService::is_access_token(raw_token) {
+        let session = OAuthService::authenticate_access_token(conn, raw_token)?;
+        let client = ApiClient {
+            client_type: api_client_types::OAUTH_CLIENT,
+            client_id: session.app_id,
+            tier: session.tier,
+            scopes: Some(session.scopes),
+            workspace_id: None,
+            channel: AuthChannel::OauthApp,
+        };
+        return Ok((client, session.user_id));
+    }
+
+    // API key 代表创建它的管理员，只能在所属工作区内访问 scope 覆盖的路由
+    if ApiKeysService::is_api_key(raw_token) {
+        let key = ApiKeysService::authenticate(conn, raw_token)?;
+        let client = ApiClient {
+            client_type: api_client_types::WORKSPACE_KEY,
+            client_id: key.id,
+            tier: key.tier,
+            scopes: Some(oauth_scopes::parse(&key.scopes)),
+            workspace_id: Some(key.workspace_id),
+            channel: AuthChannel::ApiKey,
+        };
+        return Ok((client, key.created_by));
+    }
+
+    // 服务账号的令牌固定在其所属工作区内
+    let api_token = ApiTokensService::authenticate(conn, raw_token)?;
+    let client = ApiClient {
+        client_type: api_client_types::PERSONAL_TOKEN,
+        client_id: api_token.id,
+        tier: api_token.tier,
+        scopes: api_token.scopes.as_deref().map(oauth_scopes::parse),
+        workspace_id: ServiceAccountsService::workspace_of(conn, api_token.user_id)?,
+        channel: AuthChannel::ApiToken,
+    };
+    Ok((client, api_token.user_id))
+}
+
+/// API 客户端认证：校验令牌和 scope、检查配额，并在请求结束后记录用量
+async fn api_client_auth(
+    pool: &DbPool,
+    raw_token: &str,
+    mut request: Request<axum::body::Body>,
+    next: Next<axum::body::Body>,
+) -> Result<Response, Response> {
+    let matched_path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().as_str().to_string();
+
+    let raw_token = raw_token.to_string();
+    let (client, user_id, remaining) = DbExecutor::new(pool.clone())
+        .transaction(move |conn| {
+            let (client, user_id) = resolve_api_client(conn, &raw_token)?;
+            let remaining = ApiTokensService::quota_remaining(
+                conn,
+                client.client_type,
+                client.client_id,
+                user_id,
+                &client.tier,
+            )?;
+            Ok((client, user_id, remaining))
+        })
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    // OAuth 应用和 API key 只能访问已授权 scope 覆盖的路由
+    if let Some(scopes) = &client.scopes {
+        let required = oauth_scopes::required_for(&method, &matched_path);
+        if !required.is_some_and(|scope| oauth_scopes::allows(scopes, scope)) {
+            let response = ApiResponse::<()>::error(
+                403,
+                "Insufficient scope",
+                vec![ErrorDetail {
+                    field: None,
+                    code: "INSUFFICIENT_SCOPE".to_string(),
+                    message: match required {
+                        Some(scope) => format!("This endpoint requires the '{}' scope", scope),
+                        None => "This endpoint is not available to API clients".to_string(),
+                    },
+                }],
+            );
+            return Err((StatusCode::FORBIDDEN, Json(response)).into_response());
+        }
+    }
+
+    if remaining == Some(0) {
+        let response = ApiResponse::<()>::error(
+            429,
+            "API quota exceeded",
+            vec![ErrorDetail {
+                field: None,
+                code: "QUOTA_EXCEEDED".to_string(),
+                message: format!("Daily request quota for tier '{}' reached", client.tier),
+            }],
+        );
+        return Err((StatusCode::TOO_MANY_REQUESTS, Json(response)).into_response());
+    }
+
+    let user = match get_user_by_id(pool, user_id).await {
+        Ok(user) => user,
+        Err(_) => {
+            let response = ApiResponse::<()>::unauthorized("User not found or inactive");
+            return Err((StatusCode::UNAUTHORIZED, Json(response)).into_response());
+        }
+    };
+    let mut auth_info = auth_user_info(&user, client.channel);
+    if let Some(workspace_id) = client.workspace_id {
+        auth_info.current_workspace_id = Some(workspace_id);
+    }
+    if auth_info.current_workspace_id.is_none() {
+        return Err(no_workspace_response());
+    }
+
+    let endpoint = format!("{} {}", method, matched_path);
+    request.extensions_mut().insert(auth_info);
+    request.extensions_mut().insert(client.clone());
+
+    let mut response = next.run(request).await;
+
+    let status = response.status();
+    let is_error = status.is_client_error() || status.is_server_error();
+    let (client_type, client_id) = (client.client_type, client.client_id);
+    if let Err(e) = DbExecutor::new(pool.clone())
+        .transaction(move |conn| {
+            ApiTokensService::record_usage(conn, client_type, client_id, &endpoint, is_error)
+        })
+        .await
+    {
+        tracing::warn!("Failed to record API usage: {}", e);
+    }
+
+    if let Some(remaining) = remaining
+        && let Some(limit) = api_tiers::daily_request_limit(&client.tier)
+    {
+        let headers = response.headers_mut();
+        headers.insert("X-RateLimit-Limit", limit.into());
+        headers.insert("X-RateLimit-Remaining", (remaining - 1).max(0).into());
+    }
+    Ok(response)
+}
+
+fn no_workspace_response() -> Response {
+    let response = ApiResponse::<()>::error(
+        400,
+        "No current workspace found",
+        vec![ErrorDetail {
+            field: None,
+            code: "NO_WORKSPACE".to_string(),
+            message: "No current workspace found for user".to_string(),
+        }],
+    );
+    (StatusCode::BAD_REQUEST, Json(response)).into_response()
+}
+
+fn auth_user_info(user: &User, channel: AuthChannel) -> AuthUserInfo {
+    AuthUserInfo {
+        user: AuthUser {
+            id: user.id,
+            email: user.email.clone(),
+            username: user.username.clone(),
+            name: user.name.clone(),
+            avatar_url: user.avatar_url.clone(),
+        },
+        current_workspace_id: user.current_workspace_id,
+        channel,
+        asset_region: user.asset_region.clone(),
+    }
+}
+
 pub async fn optional_auth_middleware(
     State(pool): State<Arc<DbPool>>,
     mut request: Request<axum::body::Body>,
@@ -294,20 +513,20 @@ pub async fn optional_auth_middleware(
     Ok(next.run(request).await)
 }
 
-async fn get_user_by_id(
-    pool: &Arc<DbPool>,
-    user_id: uuid::Uuid,
-) -> Result<User, diesel::result::Error> {
+async fn get_user_by_id(pool: &DbPool, user_id: uuid::Uuid) -> Result<User, AppError> {
     use crate::schema::users::dsl::*;
     use diesel::prelude::*;
 
-    let mut conn = pool.get().expect("Failed to get DB connection");
-
-    users
-        .filter(id.eq(user_id))
-        .filter(is_active.eq(true))
-        .select(User::as_select())
-        .first(&mut conn)
+    DbExecutor::new(pool.clone())
+        .read(move |conn| {
+            users
+                .filter(id.eq(user_id))
+                .filter(is_active.eq(true))
+                .select(User::as_select())
+                .first(conn)
+                .map_err(AppError::from)
+        })
+        .await
 }
 
 // 提取器，用于从请求中获取当前用户
@@ -328,6 +547,10 @@ pub async fn extract_optional_user(
 pub struct AuthUserInfo {
     pub user: AuthUser,
     pub current_workspace_id: Option<Uuid>,
+    /// 会话登录还是 API 令牌调用
+    pub channel: AuthChannel,
+    /// 用户偏好的资源 CDN 区域
+    pub asset_region: Option<String>,
 }
 
 use axum::async_trait;
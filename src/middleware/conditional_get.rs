@@ -0,0 +1,201 @@
+use crate::AppState;
+use crate::db::repositories::collection_versions::{CollectionVersion, CollectionVersionsRepo};
+use crate::middleware::ASSET_REGION_HEADER;
+use crate::middleware::auth::AuthUserInfo;
+use axum::{
+    body::{self, Empty},
+    extract::State,
+    http::{
+        HeaderValue, Method, Request, StatusCode,
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH},
+    },
+    middleware::Next,
+    response::Response,
+};
+use diesel::PgConnection;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 支持条件请求的列表接口所读取的数据集合
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collection {
+    Issues,
+    Projects,
+    Labels,
+    Cycles,
+    ProjectStatuses,
+}
+
+impl Collection {
+    /// 路径对应的集合；只匹配列表接口本身，不含单个资源和子路径
+    pub fn for_path(path: &str) -> Option<Self> {
+        match path.trim_end_matches('/') {
+            "/issues" => Some(Self::Issues),
+            "/projects" => Some(Self::Projects),
+            "/labels" => Some(Self::Labels),
+            "/cycles" => Some(Self::Cycles),
+            "/project-statuses" => Some(Self::ProjectStatuses),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Issues => "issues",
+            Self::Projects => "projects",
+            Self::Labels => "labels",
+            Self::Cycles => "cycles",
+            Self::ProjectStatuses => "project_statuses",
+        }
+    }
+
+    /// 响应内容依赖的所有表的版本：议题列表内嵌标签、团队、项目（含状态和负责人）、
+    /// 周期、经办人和工作流状态，项目列表带状态
+    pub fn version(
+        self,
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+    ) -> Result<CollectionVersion, diesel::result::Error> {
+        Ok(match self {
+            Self::Issues => CollectionVersionsRepo::issues(conn, workspace_id)?
+                .combine(CollectionVersionsRepo::labels(conn, workspace_id)?)
+                .combine(CollectionVersionsRepo::teams(conn, workspace_id)?)
+                .combine(CollectionVersionsRepo::projects(conn, workspace_id)?)
+                .combine(CollectionVersionsRepo::project_statuses(
+                    conn,
+                    workspace_id,
+                )?)
+                .combine(CollectionVersionsRepo::cycles(conn, workspace_id)?)
+                .combine(CollectionVersionsRepo::members(conn, workspace_id)?)
+                .combine(CollectionVersionsRepo::workflow_states(conn, workspace_id)?),
+            Self::Projects => CollectionVersionsRepo::projects(conn, workspace_id)?.combine(
+                CollectionVersionsRepo::project_statuses(conn, workspace_id)?,
+            ),
+            Self::Labels => CollectionVersionsRepo::labels(conn, workspace_id)?,
+            Self::Cycles => CollectionVersionsRepo::cycles(conn, workspace_id)?,
+            Self::ProjectStatuses => CollectionVersionsRepo::project_statuses(conn, workspace_id)?,
+        })
+    }
+}
+
+/// 区分同一集合不同响应的请求信息：调用者（脱敏和可见范围因人而异）、
+/// 查询参数（过滤、分页、字段选择）和资源 URL 所用的区域
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EtagVariant<'a> {
+    pub user_id: Uuid,
+    pub workspace_id: Uuid,
+    pub query: Option<&'a str>,
+    pub asset_region: Option<&'a str>,
+}
+
+/// 由集合版本（行数与最大 `updated_at`）和请求信息计算弱 ETag
+pub fn weak_etag(
+    collection: Collection,
+    version: &CollectionVersion,
+    variant: &EtagVariant<'_>,
+) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        collection.name().to_string(),
+        version.count.to_string(),
+        version
+            .last_updated_at
+            .map(|at| at.timestamp_micros().to_string())
+            .unwrap_or_default(),
+        variant.user_id.to_string(),
+        variant.workspace_id.to_string(),
+        variant.query.unwrap_or_default().to_string(),
+        variant.asset_region.unwrap_or_default().to_string(),
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    format!("W/\"{}\"", &hex::encode(hasher.finalize())[..32])
+}
+
+/// `If-None-Match` 是否命中：`*` 或任一标签弱比较相等（忽略 `W/` 前缀）
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+/// 条件 GET 中间件
+/// 为列表接口计算弱 ETag，`If-None-Match` 命中时直接返回 304，不再执行查询和序列化；
+/// 否则在 200 响应上附带 ETag。版本在处理请求之前读取，期间发生的修改最多导致
+/// 客户端下次多取一次完整响应。需放在认证中间件之内。
+pub async fn conditional_get_middleware<B>(
+    State(state): State<Arc<AppState>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let Some(collection) = Collection::for_path(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let Some(AuthUserInfo {
+        user,
+        current_workspace_id: Some(workspace_id),
+        ..
+    }) = request.extensions().get::<AuthUserInfo>().cloned()
+    else {
+        return next.run(request).await;
+    };
+
+    let version = match state
+        .executor
+        .read(move |conn| Ok(collection.version(conn, workspace_id)?))
+        .await
+    {
+        Ok(version) => version,
+        Err(e) => {
+            tracing::debug!("Skipping ETag for {}: {}", collection.name(), e);
+            return next.run(request).await;
+        }
+    };
+    let etag = weak_etag(
+        collection,
+        &version,
+        &EtagVariant {
+            user_id: user.id,
+            workspace_id,
+            query: request.uri().query(),
+            asset_region: request
+                .headers()
+                .get(ASSET_REGION_HEADER)
+                .and_then(|value| value.to_str().ok()),
+        },
+    );
+    let Ok(etag_value) = HeaderValue::from_str(&etag) else {
+        return next.run(request).await;
+    };
+
+    let not_modified = request
+        .headers()
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| etag_matches(value, &etag));
+    if not_modified {
+        let mut response = Response::new(body::boxed(Empty::new()));
+        *response.status_mut() = StatusCode::NOT_MODIFIED;
+        response.headers_mut().insert(ETAG, etag_value);
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static("private, no-cache"));
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    if response.status() == StatusCode::OK {
+        response.headers_mut().insert(ETAG, etag_value);
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static("private, no-cache"));
+    }
+    response
+}
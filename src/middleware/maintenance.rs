@@ -0,0 +1,73 @@
+use crate::AppState;
+use crate::cache::MaintenanceState;
+use crate::db::models::{ApiResponse, ErrorDetail};
+use crate::middleware::auth::AuthUserInfo;
+use axum::{
+    Json,
+    extract::State,
+    http::{Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+/// 维护期间仍然放行的写请求路径前缀：
+/// 管理接口（用于关闭维护模式）、登录登出等会话操作，以及只读的 GraphQL
+const EXEMPT_PATH_PREFIXES: &[&str] = &[
+    "/admin/",
+    "/auth/login",
+    "/auth/logout",
+    "/auth/switch-workspace",
+    "/auth/oauth/",
+    "/oauth/token",
+    "/oauth/revoke",
+    "/graphql",
+];
+
+/// 请求是否会修改数据，需要受维护模式限制
+pub fn is_mutation(method: &Method, path: &str) -> bool {
+    let read_only = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    !read_only
+        && !EXEMPT_PATH_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+}
+
+fn maintenance_error(state: &MaintenanceState) -> Response {
+    let message = state
+        .message
+        .clone()
+        .unwrap_or_else(|| "The service is in read-only maintenance mode".to_string());
+    let response = ApiResponse::<()>::error(
+        503,
+        "Service under maintenance",
+        vec![ErrorDetail {
+            field: None,
+            code: "MAINTENANCE_MODE".to_string(),
+            message,
+        }],
+    );
+    (StatusCode::SERVICE_UNAVAILABLE, Json(response)).into_response()
+}
+
+/// 只读维护中间件
+/// 全局或当前工作区处于维护模式时拒绝写请求并返回 503；
+/// 需放在认证中间件之内才能识别请求所属的工作区，匿名请求只受全局开关影响
+pub async fn maintenance_middleware<B>(
+    State(state): State<Arc<AppState>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if !is_mutation(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let workspace_id = request
+        .extensions()
+        .get::<AuthUserInfo>()
+        .and_then(|auth_info| auth_info.current_workspace_id);
+    match state.maintenance.active_for(workspace_id).await {
+        Some(maintenance) => maintenance_error(&maintenance),
+        None => next.run(request).await,
+    }
+}
@@ -1,8 +1,20 @@
+pub mod asset_region;
 pub mod auth;
+pub mod conditional_get;
+pub mod maintenance;
+pub mod rate_limit;
+pub mod redaction;
 pub mod request_tracking;
+pub mod security_headers;
 
+pub use asset_region::{ASSET_REGION_HEADER, AssetRegionHint};
+pub use conditional_get::conditional_get_middleware;
+pub use maintenance::maintenance_middleware;
+pub use rate_limit::{HttpRateLimiter, rate_limit_middleware};
+pub use redaction::redaction_middleware;
 pub use request_tracking::{
     REQUEST_ID_HEADER, extract_request_id, performance_monitoring_middleware,
     request_tracking_middleware,
 };
+pub use security_headers::{SecurityHeaders, cors_layer, security_headers_middleware};
 pub mod logger;
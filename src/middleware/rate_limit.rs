@@ -0,0 +1,210 @@
+use crate::AppState;
+use crate::config::HttpRateLimitConfig;
+use crate::db::models::{ApiResponse, ErrorDetail};
+use crate::middleware::auth::AuthUserInfo;
+use axum::{
+    Json,
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, HeaderValue, Request, StatusCode, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Redis 计数键前缀
+const RATE_LIMIT_KEY_PREFIX: &str = "ratelimit:http:";
+
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "x-ratelimit-limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// 滑动窗口计数脚本：清理窗口外的请求，未超限时记录本次请求。
+/// 返回 {是否放行, 窗口内请求数, 最早一次请求的毫秒时间戳}
+const SLIDING_WINDOW_SCRIPT: &str = r"
+local key = KEYS[1]
+local now = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local limit = tonumber(ARGV[3])
+redis.call('ZREMRANGEBYSCORE', key, '-inf', now - window)
+local count = redis.call('ZCARD', key)
+if count >= limit then
+    local oldest = redis.call('ZRANGE', key, 0, 0, 'WITHSCORES')
+    return {0, count, tonumber(oldest[2])}
+end
+redis.call('ZADD', key, now, ARGV[4])
+redis.call('PEXPIRE', key, window)
+return {1, count + 1, 0}
+";
+
+/// 单个限流维度的检查结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// 被拒绝时距离窗口内最早请求过期的秒数
+    pub retry_after_secs: u64,
+}
+
+/// HTTP 接口限流器
+///
+/// 按用户和按 IP 分别计数，计数保存在 Redis 的有序集合中，多实例部署时共享。
+/// Redis 不可用时放行请求，避免限流组件故障导致整个 API 不可用。
+#[derive(Clone)]
+pub struct HttpRateLimiter {
+    redis_client: redis::Client,
+    config: HttpRateLimitConfig,
+}
+
+impl HttpRateLimiter {
+    pub fn new(redis_client: redis::Client, config: HttpRateLimitConfig) -> Self {
+        Self {
+            redis_client,
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &HttpRateLimitConfig {
+        &self.config
+    }
+
+    pub fn user_key(user_id: Uuid) -> String {
+        format!("{}user:{}", RATE_LIMIT_KEY_PREFIX, user_id)
+    }
+
+    pub fn ip_key(ip: IpAddr) -> String {
+        format!("{}ip:{}", RATE_LIMIT_KEY_PREFIX, ip)
+    }
+
+    /// 被拒绝的请求需要等待的秒数，至少 1 秒
+    pub fn retry_after_secs(oldest_ms: i64, now_ms: i64, window_secs: u64) -> u64 {
+        let wait_ms = (oldest_ms + window_secs as i64 * 1000 - now_ms).max(0) as u64;
+        wait_ms.div_ceil(1000).max(1)
+    }
+
+    /// 在 `key` 对应的窗口内记录一次请求
+    pub async fn check(
+        &self,
+        key: &str,
+        limit: u32,
+    ) -> Result<RateLimitDecision, redis::RedisError> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let window_ms = self.config.window_secs as i64 * 1000;
+        let (allowed, count, oldest_ms): (i64, i64, i64) =
+            redis::Script::new(SLIDING_WINDOW_SCRIPT)
+                .key(key)
+                .arg(now_ms)
+                .arg(window_ms)
+                .arg(limit)
+                .arg(Uuid::new_v4().to_string())
+                .invoke_async(&mut conn)
+                .await?;
+        let allowed = allowed == 1;
+        Ok(RateLimitDecision {
+            allowed,
+            limit,
+            remaining: limit.saturating_sub(count.max(0) as u32),
+            retry_after_secs: if allowed {
+                0
+            } else {
+                Self::retry_after_secs(oldest_ms, now_ms, self.config.window_secs)
+            },
+        })
+    }
+}
+
+/// 请求方 IP：信任代理时取 `X-Forwarded-For` 的第一个地址或 `X-Real-IP`，否则取连接地址
+pub fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>, trust_proxy: bool) -> Option<IpAddr> {
+    if trust_proxy {
+        let forwarded = headers
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .or_else(|| headers.get("x-real-ip").and_then(|v| v.to_str().ok()))
+            .and_then(|v| v.trim().parse::<IpAddr>().ok());
+        if forwarded.is_some() {
+            return forwarded;
+        }
+    }
+    peer
+}
+
+fn too_many_requests(decision: &RateLimitDecision) -> Response {
+    let response = ApiResponse::<()>::error(
+        429,
+        "Too many requests",
+        vec![ErrorDetail {
+            field: None,
+            code: "RATE_LIMITED".to_string(),
+            message: format!(
+                "Rate limit of {} requests exceeded; retry in {}s",
+                decision.limit, decision.retry_after_secs
+            ),
+        }],
+    )
+    .with_retry_after(decision.retry_after_secs);
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, Json(response)).into_response();
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(decision.retry_after_secs));
+    insert_limit_headers(&mut response, decision);
+    response
+}
+
+fn insert_limit_headers(response: &mut Response, decision: &RateLimitDecision) {
+    let headers = response.headers_mut();
+    headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(decision.limit));
+    headers.insert(
+        RATE_LIMIT_REMAINING_HEADER,
+        HeaderValue::from(decision.remaining),
+    );
+}
+
+/// HTTP 限流中间件
+/// 已认证的请求按用户计数（需放在认证中间件之内），匿名请求按 IP 计数，
+/// 避免同一出口 IP 后的多个用户互相挤占额度；超限时返回 429 和 `Retry-After`
+pub async fn rate_limit_middleware<B>(
+    State(state): State<Arc<AppState>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let limiter = &state.rate_limiter;
+    if !limiter.config().enabled {
+        return next.run(request).await;
+    }
+
+    let key = match request.extensions().get::<AuthUserInfo>() {
+        Some(auth_info) => Some((
+            HttpRateLimiter::user_key(auth_info.user.id),
+            limiter.config().per_user,
+        )),
+        None => {
+            let peer = request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip());
+            client_ip(request.headers(), peer, limiter.config().trust_proxy)
+                .map(|ip| (HttpRateLimiter::ip_key(ip), limiter.config().per_ip))
+        }
+    };
+    let Some((key, limit)) = key else {
+        return next.run(request).await;
+    };
+
+    let decision = match limiter.check(&key, limit).await {
+        Ok(decision) if !decision.allowed => return too_many_requests(&decision),
+        Ok(decision) => Some(decision),
+        Err(e) => {
+            tracing::warn!("Rate limit check failed, allowing request: {}", e);
+            None
+        }
+    };
+
+    let mut response = next.run(request).await;
+    if let Some(decision) = decision {
+        insert_limit_headers(&mut response, &decision);
+    }
+    response
+}
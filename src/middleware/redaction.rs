@@ -0,0 +1,90 @@
+use crate::AppState;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::redaction_service::RedactionService;
+use axum::{
+    body::{self, Bytes, Full, HttpBody},
+    extract::State,
+    http::{Request, header::CONTENT_LENGTH, header::CONTENT_TYPE},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+/// 按调用者在当前工作区的角色脱敏 JSON 响应中的敏感字段（邮箱、邀请令牌、账单信息等）
+///
+/// 需位于认证中间件之内。响应中不含敏感字段时不解析，也不查询角色。
+pub async fn redaction_middleware<B>(
+    State(state): State<Arc<AppState>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let auth_info = request.extensions().get::<AuthUserInfo>().cloned();
+    let response = next.run(request).await;
+
+    let Some(AuthUserInfo {
+        user,
+        current_workspace_id: Some(workspace_id),
+        channel,
+        ..
+    }) = auth_info
+    else {
+        return response;
+    };
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, mut response_body) = response.into_parts();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response_body.data().await {
+        match chunk {
+            Ok(chunk) => bytes.extend_from_slice(&chunk),
+            Err(e) => {
+                tracing::warn!("Failed to read response body for redaction: {}", e);
+                return Response::from_parts(parts, body::boxed(Full::from(bytes)));
+            }
+        }
+    }
+    if !RedactionService::may_contain_sensitive(&bytes) {
+        return Response::from_parts(parts, body::boxed(Full::from(bytes)));
+    }
+
+    let ctx = RequestContext {
+        user_id: user.id,
+        workspace_id,
+        idempotency_key: None,
+        channel,
+    };
+    let email = user.email.clone();
+    let viewer = state
+        .executor
+        .read(move |conn| RedactionService::viewer(conn, &ctx, Some(email)))
+        .await;
+    let redacted = viewer.ok().and_then(|viewer| {
+        let mut value: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+        RedactionService::redact_value(&mut value, &viewer);
+        serde_json::to_vec(&value).ok()
+    });
+    match redacted {
+        Some(redacted) => {
+            parts.headers.remove(CONTENT_LENGTH);
+            Response::from_parts(parts, body::boxed(Full::from(Bytes::from(redacted))))
+        }
+        None => {
+            // 无法确定角色时不返回可能包含敏感信息的原始响应
+            tracing::warn!("Failed to redact response for user {}", user.id);
+            parts.headers.remove(CONTENT_LENGTH);
+            parts.status = axum::http::StatusCode::INTERNAL_SERVER_ERROR;
+            let response =
+                crate::db::models::ApiResponse::<()>::internal_error("Failed to prepare response");
+            let body = serde_json::to_vec(&response).unwrap_or_default();
+            Response::from_parts(parts, body::boxed(Full::from(body)))
+        }
+    }
+}
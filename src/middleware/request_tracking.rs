@@ -1,4 +1,6 @@
+use crate::metrics::HttpMetrics;
 use axum::{
+    extract::MatchedPath,
     http::{HeaderMap, HeaderName, HeaderValue, Request},
     middleware::Next,
     response::Response,
@@ -10,6 +12,16 @@ use uuid::Uuid;
 /// 请求ID头部名称
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
 
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// 当前正在处理的请求的 ID，供错误响应写入 `trace_id`；
+/// 不在请求追踪中间件之内（后台任务、测试）时为 `None`
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
 /// 请求追踪中间件
 /// 为每个请求生成唯一ID，记录请求信息和响应时间
 pub async fn request_tracking_middleware<B>(mut request: Request<B>, next: Next<B>) -> Response {
@@ -42,7 +54,9 @@ pub async fn request_tracking_middleware<B>(mut request: Request<B>, next: Next<
     );
 
     // 处理请求
-    let mut response = next.run(request).await;
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .await;
 
     // 计算处理时间
     let duration = start_time.elapsed();
@@ -119,11 +133,16 @@ fn get_or_generate_request_id(headers: &HeaderMap) -> String {
 }
 
 /// 性能监控中间件
-/// 专门用于监控API性能指标
+/// 专门用于监控API性能指标，并按路由模板记录延迟直方图供 `/metrics` 导出
 pub async fn performance_monitoring_middleware<B>(request: Request<B>, next: Next<B>) -> Response {
     let start_time = Instant::now();
     let method = request.method().clone();
     let uri = request.uri().path().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
 
     // 处理请求
     let response = next.run(request).await;
@@ -132,6 +151,7 @@ pub async fn performance_monitoring_middleware<B>(request: Request<B>, next: Nex
     let duration = start_time.elapsed();
     let duration_ms = duration.as_millis();
     let status_code = response.status().as_u16();
+    HttpMetrics::global().observe(method.as_str(), &route, status_code, duration);
 
     // 记录性能指标
     info!(
@@ -0,0 +1,161 @@
+use crate::config::{CorsConfig, SecurityHeadersConfig};
+use crate::error::AppError;
+use axum::{
+    extract::State,
+    http::{
+        HeaderName, HeaderValue, Method, Request,
+        header::{
+            CONTENT_SECURITY_POLICY, CONTENT_TYPE, ETAG, REFERRER_POLICY, RETRY_AFTER,
+            STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+        },
+    },
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer};
+
+/// 浏览器端需要读取的响应头：续期 token、限流额度和请求 ID
+const EXPOSED_HEADERS: &[&str] = &[
+    "x-new-access-token",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-request-id",
+];
+
+/// 根据配置构建 CORS 层
+///
+/// 允许携带凭据时浏览器不接受通配的方法和请求头，改为回显预检请求中的值。
+pub fn cors_layer(config: &CorsConfig) -> Result<CorsLayer, AppError> {
+    let mut exposed: Vec<HeaderName> = EXPOSED_HEADERS
+        .iter()
+        .map(|name| HeaderName::from_static(name))
+        .collect();
+    exposed.extend([RETRY_AFTER, ETAG]);
+
+    let layer = CorsLayer::new()
+        .expose_headers(exposed)
+        .max_age(Duration::from_secs(config.max_age_secs));
+
+    if config.origins.iter().any(|origin| origin == "*") {
+        return Ok(layer
+            .allow_origin(Any)
+            .allow_methods(Any)
+            .allow_headers(Any));
+    }
+
+    let origins = config
+        .origins
+        .iter()
+        .map(|origin| {
+            HeaderValue::from_str(origin)
+                .map_err(|_| AppError::Config(format!("Invalid CORS origin: {}", origin)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let layer = layer.allow_origin(AllowOrigin::list(origins));
+    if config.allow_credentials {
+        Ok(layer
+            .allow_credentials(true)
+            .allow_methods(AllowMethods::mirror_request())
+            .allow_headers(AllowHeaders::mirror_request()))
+    } else {
+        Ok(layer
+            .allow_methods([
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+                Method::OPTIONS,
+            ])
+            .allow_headers(Any))
+    }
+}
+
+/// 预先解析好的安全响应头
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders {
+    enabled: bool,
+    /// 所有响应都带的头
+    common: Vec<(HeaderName, HeaderValue)>,
+    /// 只对 HTML 响应生效的内容安全策略
+    content_security_policy: HeaderValue,
+}
+
+impl SecurityHeaders {
+    pub fn new(config: &SecurityHeadersConfig) -> Result<Self, AppError> {
+        let parse = |name: &str, value: &str| {
+            HeaderValue::from_str(value)
+                .map_err(|_| AppError::Config(format!("{} is not a valid header value", name)))
+        };
+
+        let mut common = vec![
+            (X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+            (X_FRAME_OPTIONS, HeaderValue::from_static("DENY")),
+            (
+                REFERRER_POLICY,
+                parse("REFERRER_POLICY", &config.referrer_policy)?,
+            ),
+        ];
+        if let Some(hsts) = Self::hsts_value(config) {
+            common.push((STRICT_TRANSPORT_SECURITY, parse("HSTS", &hsts)?));
+        }
+
+        Ok(Self {
+            enabled: config.enabled,
+            common,
+            content_security_policy: parse(
+                "CONTENT_SECURITY_POLICY",
+                &config.content_security_policy,
+            )?,
+        })
+    }
+
+    /// `Strict-Transport-Security` 的值；max-age 为 0 时不发送
+    pub fn hsts_value(config: &SecurityHeadersConfig) -> Option<String> {
+        if config.hsts_max_age_secs == 0 {
+            return None;
+        }
+        let mut value = format!("max-age={}", config.hsts_max_age_secs);
+        if config.hsts_include_subdomains {
+            value.push_str("; includeSubDomains");
+        }
+        Some(value)
+    }
+
+    fn apply(&self, response: &mut Response) {
+        if !self.enabled {
+            return;
+        }
+        let is_html = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/html"));
+
+        // 处理函数自己设置的值优先
+        let headers = response.headers_mut();
+        for (name, value) in &self.common {
+            headers.entry(name).or_insert_with(|| value.clone());
+        }
+        if is_html {
+            headers
+                .entry(CONTENT_SECURITY_POLICY)
+                .or_insert_with(|| self.content_security_policy.clone());
+        }
+    }
+}
+
+/// 安全响应头中间件
+/// 为所有响应添加 HSTS、X-Content-Type-Options、X-Frame-Options 和 Referrer-Policy，
+/// HTML 响应额外添加 Content-Security-Policy
+pub async fn security_headers_middleware<B>(
+    State(headers): State<Arc<SecurityHeaders>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let mut response = next.run(request).await;
+    headers.apply(&mut response);
+    response
+}
@@ -0,0 +1,704 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::cache::MaintenanceState;
+use crate::db::models::*;
+use crate::db::repositories::scheduled_jobs::ScheduledJobsRepo;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::auth_service::AuthService;
+use crate::services::comment_moderation_service::CommentModerationService;
+use crate::services::dead_letter_service::DeadLetterService;
+use crate::services::integrity_service::IntegrityService;
+use crate::services::organizations_service::OrganizationsService;
+use crate::services::sandbox_service::SandboxService;
+use crate::services::telemetry_service::TelemetryService;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IntegrityCheckQuery {
+    #[serde(default)]
+    pub fix: bool,
+}
+
+// 运行数据完整性检查（可选修复）
+#[utoipa::path(
+    post,
+    path = "/admin/integrity-check",
+    tag = "admin",
+    params(IntegrityCheckQuery),
+    responses((status = 200, description = "Integrity check completed", body = ApiResponse<IntegrityReport>))
+)]
+pub async fn run_integrity_check(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(params): Query<IntegrityCheckQuery>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| IntegrityService::run(conn, params.fix))
+        .await;
+    match result {
+        Ok(report) => {
+            let response = ApiResponse::success(report, "Integrity check completed");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 强制用户下线：结束所有会话并吊销已签发的 token
+#[utoipa::path(
+    post,
+    path = "/admin/users/{user_id}/force-logout",
+    tag = "admin",
+    params(("user_id" = Uuid, Path)),
+    responses((status = 200, description = "User logged out from all sessions", body = ApiResponse<EmptyData>))
+)]
+pub async fn force_logout_user(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    if let Err(err) = state
+        .executor
+        .transaction(move |conn| AuthService::force_logout(conn, user_id))
+        .await
+    {
+        return err.into_response();
+    }
+    if let Err(err) = state.token_revocations.revoke_all_for_user(user_id).await {
+        return err.into_response();
+    }
+
+    tracing::info!(
+        admin = %auth_info.user.email,
+        %user_id,
+        "Admin forced user logout"
+    );
+    let response = ApiResponse::<()>::ok("User logged out from all sessions");
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MaintenanceQuery {
+    pub workspace_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SetMaintenanceRequest {
+    /// 为空时作用于整个服务
+    pub workspace_id: Option<Uuid>,
+    pub enabled: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MaintenanceStatus {
+    pub workspace_id: Option<Uuid>,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub state: Option<MaintenanceState>,
+}
+
+// 查询维护模式状态：不带 workspace_id 时查询全局开关
+#[utoipa::path(
+    get,
+    path = "/admin/maintenance",
+    tag = "admin",
+    params(MaintenanceQuery),
+    responses((status = 200, description = "Maintenance status retrieved", body = ApiResponse<MaintenanceStatus>))
+)]
+pub async fn get_maintenance(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(params): Query<MaintenanceQuery>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    match state.maintenance.get(params.workspace_id).await {
+        Ok(maintenance) => {
+            let status = MaintenanceStatus {
+                workspace_id: params.workspace_id,
+                enabled: maintenance.is_some(),
+                state: maintenance,
+            };
+            let response = ApiResponse::success(status, "Maintenance status retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 开启或关闭只读维护模式，所有副本和 WebSocket 客户端都会收到通知
+#[utoipa::path(
+    put,
+    path = "/admin/maintenance",
+    tag = "admin",
+    request_body = SetMaintenanceRequest,
+    responses((status = 200, description = "Maintenance mode updated", body = ApiResponse<MaintenanceStatus>))
+)]
+pub async fn set_maintenance(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<SetMaintenanceRequest>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let maintenance = payload.enabled.then(|| MaintenanceState {
+        enabled_by: auth_info.user.email.clone(),
+        message: payload
+            .message
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty()),
+        started_at: chrono::Utc::now(),
+    });
+    let result = match &maintenance {
+        Some(maintenance) => {
+            state
+                .maintenance
+                .enable(payload.workspace_id, maintenance)
+                .await
+        }
+        None => state.maintenance.disable(payload.workspace_id).await,
+    };
+    if let Err(err) = result {
+        return err.into_response();
+    }
+
+    tracing::info!(
+        admin = %auth_info.user.email,
+        workspace_id = ?payload.workspace_id,
+        enabled = payload.enabled,
+        "Admin changed maintenance mode"
+    );
+    let status = MaintenanceStatus {
+        workspace_id: payload.workspace_id,
+        enabled: payload.enabled,
+        state: maintenance,
+    };
+    let response = ApiResponse::success(status, "Maintenance mode updated");
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+// 查看定时任务的调度和最近一次运行结果
+#[utoipa::path(
+    get,
+    path = "/admin/jobs",
+    tag = "admin",
+    responses((status = 200, description = "Jobs retrieved", body = ApiResponse<Vec<ScheduledJobStatus>>))
+)]
+pub async fn get_jobs(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .read(|conn| Ok(ScheduledJobsRepo::list(conn)?))
+        .await;
+    match result {
+        Ok(records) => {
+            let response = ApiResponse::success(state.scheduler.status(records), "Jobs retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 使用情况遥测：是否开启、上报地址、上次上报内容，以及现在上报时会发送的完整内容
+#[utoipa::path(
+    get,
+    path = "/admin/telemetry",
+    tag = "admin",
+    responses((status = 200, description = "Telemetry retrieved", body = ApiResponse<TelemetryOverview>))
+)]
+pub async fn get_telemetry(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let config = state.config.telemetry();
+    match TelemetryService::overview(&state.executor, config.as_ref()).await {
+        Ok(overview) => {
+            let response = ApiResponse::success(overview, "Telemetry retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 列出所有组织
+#[utoipa::path(
+    get,
+    path = "/admin/organizations",
+    tag = "admin",
+    responses((status = 200, description = "Organizations retrieved", body = ApiResponse<Vec<Organization>>))
+)]
+pub async fn list_organizations(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state.executor.read(OrganizationsService::list).await;
+    match result {
+        Ok(organizations) => {
+            let response = ApiResponse::success(organizations, "Organizations retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 创建组织
+#[utoipa::path(
+    post,
+    path = "/admin/organizations",
+    tag = "admin",
+    request_body = CreateOrganizationRequest,
+    responses((status = 201, description = "Organization created", body = ApiResponse<Organization>))
+)]
+pub async fn create_organization(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateOrganizationRequest>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| OrganizationsService::create(conn, &payload))
+        .await;
+    match result {
+        Ok(organization) => {
+            let response = ApiResponse::created(organization, "Organization created");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取组织及其下的工作区
+#[utoipa::path(
+    get,
+    path = "/admin/organizations/{organization_id}",
+    tag = "admin",
+    params(("organization_id" = Uuid, Path)),
+    responses((status = 200, description = "Organization retrieved", body = ApiResponse<OrganizationResponse>))
+)]
+pub async fn get_organization(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(organization_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .read(move |conn| OrganizationsService::get(conn, organization_id))
+        .await;
+    match result {
+        Ok(organization) => {
+            let response = ApiResponse::success(organization, "Organization retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 更新组织名称或跨工作区关联策略
+#[utoipa::path(
+    put,
+    path = "/admin/organizations/{organization_id}",
+    tag = "admin",
+    params(("organization_id" = Uuid, Path)),
+    request_body = UpdateOrganizationRequest,
+    responses((status = 200, description = "Organization updated", body = ApiResponse<Organization>))
+)]
+pub async fn update_organization(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<UpdateOrganizationRequest>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| OrganizationsService::update(conn, organization_id, &payload))
+        .await;
+    match result {
+        Ok(organization) => {
+            tracing::info!(
+                admin = %auth_info.user.email,
+                %organization_id,
+                allow_cross_workspace_links = organization.allow_cross_workspace_links,
+                "Admin updated organization"
+            );
+            let response = ApiResponse::success(organization, "Organization updated");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 将工作区加入组织；organization_id 为空时移出组织
+#[utoipa::path(
+    put,
+    path = "/admin/workspaces/{workspace_id}/organization",
+    tag = "admin",
+    params(("workspace_id" = Uuid, Path)),
+    request_body = SetWorkspaceOrganizationRequest,
+    responses((status = 200, description = "Workspace organization updated", body = ApiResponse<EmptyData>))
+)]
+pub async fn set_workspace_organization(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(workspace_id): Path<Uuid>,
+    Json(payload): Json<SetWorkspaceOrganizationRequest>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            OrganizationsService::set_workspace_organization(
+                conn,
+                workspace_id,
+                payload.organization_id,
+            )
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Workspace organization updated");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 重置演示/试用工作区：清空全部内容后按指定的数据模板重新填充，由后台任务在事务中执行
+#[utoipa::path(
+    post,
+    path = "/admin/workspaces/{workspace_id}/reset",
+    tag = "admin",
+    params(("workspace_id" = Uuid, Path)),
+    request_body = WorkspaceResetRequest,
+    responses((status = 200, description = "Workspace reset queued", body = ApiResponse<WorkspaceResetResponse>))
+)]
+pub async fn reset_workspace(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(workspace_id): Path<Uuid>,
+    Json(payload): Json<WorkspaceResetRequest>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            SandboxService::request(conn, workspace_id, auth_info.user.id, &payload)
+        })
+        .await;
+    match result {
+        Ok(reset) => {
+            if let Err(e) = SandboxService::enqueue(&state.redis, reset.reset.id).await {
+                // 后台任务也会补跑长时间未执行的重置
+                tracing::warn!("Failed to queue workspace reset {}: {}", reset.reset.id, e);
+            }
+            let response = ApiResponse::success(reset, "Workspace reset queued");
+            (StatusCode::ACCEPTED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 查询工作区重置进度，完成后包含删除和重新填充的数量
+#[utoipa::path(
+    get,
+    path = "/admin/workspaces/{workspace_id}/resets/{reset_id}",
+    tag = "admin",
+    params(("workspace_id" = Uuid, Path), ("reset_id" = Uuid, Path)),
+    responses((status = 200, description = "Workspace reset retrieved successfully", body = ApiResponse<WorkspaceResetResponse>))
+)]
+pub async fn get_workspace_reset(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((workspace_id, reset_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .read(move |conn| SandboxService::get(conn, workspace_id, reset_id))
+        .await;
+    match result {
+        Ok(reset) => {
+            let response = ApiResponse::success(reset, "Workspace reset retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CommentFlagsQuery {
+    pub status: Option<String>,
+}
+
+// 按作者或内容批量隐藏/删除工作区评论，用于清理垃圾评论；dry_run 时只返回匹配结果
+#[utoipa::path(
+    post,
+    path = "/admin/comments/moderate",
+    tag = "admin",
+    request_body = BulkModerateCommentsRequest,
+    responses((status = 200, description = "Comments moderated", body = ApiResponse<BulkModerationResult>))
+)]
+pub async fn moderate_comments(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<BulkModerateCommentsRequest>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let admin_id = auth_info.user.id;
+    let request = payload.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| CommentModerationService::bulk_moderate(conn, admin_id, &request))
+        .await;
+    match result {
+        Ok(result) => {
+            if !result.dry_run {
+                tracing::info!(
+                    admin = %auth_info.user.email,
+                    workspace_id = %payload.workspace_id,
+                    action = ?payload.action,
+                    affected = result.affected,
+                    "Admin moderated comments"
+                );
+            }
+            let response = ApiResponse::success(result, "Comments moderated");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 恢复被隐藏的评论
+#[utoipa::path(
+    post,
+    path = "/admin/comments/unhide",
+    tag = "admin",
+    request_body = UnhideCommentsRequest,
+    responses((status = 200, description = "Comments restored", body = ApiResponse<Vec<Comment>>))
+)]
+pub async fn unhide_comments(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<UnhideCommentsRequest>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            CommentModerationService::unhide(conn, auth_info.user.id, &payload)
+        })
+        .await;
+    match result {
+        Ok(comments) => {
+            let response = ApiResponse::success(comments, "Comments restored");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 列出被垃圾评论规则标记的评论（含被隐藏的内容），可按状态筛选
+#[utoipa::path(
+    get,
+    path = "/admin/workspaces/{workspace_id}/comment-flags",
+    tag = "admin",
+    params(("workspace_id" = Uuid, Path), CommentFlagsQuery),
+    responses((status = 200, description = "Comment flags retrieved", body = ApiResponse<Vec<CommentFlagItem>>))
+)]
+pub async fn list_comment_flags(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(workspace_id): Path<Uuid>,
+    Query(params): Query<CommentFlagsQuery>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .read(move |conn| {
+            CommentModerationService::list_flags(conn, workspace_id, params.status.as_deref())
+        })
+        .await;
+    match result {
+        Ok(flags) => {
+            let response = ApiResponse::success(flags, "Comment flags retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 驳回标记：评论保持原样
+#[utoipa::path(
+    post,
+    path = "/admin/workspaces/{workspace_id}/comment-flags/{flag_id}/dismiss",
+    tag = "admin",
+    params(("workspace_id" = Uuid, Path), ("flag_id" = Uuid, Path)),
+    responses((status = 200, description = "Comment flag dismissed", body = ApiResponse<CommentFlag>))
+)]
+pub async fn dismiss_comment_flag(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((workspace_id, flag_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            CommentModerationService::dismiss_flag(conn, auth_info.user.id, workspace_id, flag_id)
+        })
+        .await;
+    match result {
+        Ok(flag) => {
+            let response = ApiResponse::success(flag, "Comment flag dismissed");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 列出因系统错误失败的 WebSocket 命令（死信），可按状态和工作区筛选
+#[utoipa::path(
+    get,
+    path = "/admin/ws/dead-letters",
+    tag = "admin",
+    params(DeadLetterQuery),
+    responses((status = 200, description = "Dead letters retrieved", body = ApiResponse<Vec<WebSocketDeadLetter>>))
+)]
+pub async fn list_ws_dead_letters(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(params): Query<DeadLetterQuery>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    let result = state
+        .executor
+        .read(move |conn| DeadLetterService::list(conn, &params))
+        .await;
+    match result {
+        Ok(dead_letters) => {
+            let response = ApiResponse::success(dead_letters, "Dead letters retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 修复问题后重放死信：以原发送者身份在原工作区重新执行命令，返回命令响应
+#[utoipa::path(
+    post,
+    path = "/admin/ws/dead-letters/{dead_letter_id}/replay",
+    tag = "admin",
+    params(("dead_letter_id" = Uuid, Path)),
+    responses((status = 200, description = "Dead letter replayed", body = ApiResponse<DeadLetterReplay>))
+)]
+pub async fn replay_ws_dead_letter(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(dead_letter_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !state.config.is_admin(&auth_info.user.email) {
+        let response = ApiResponse::<()>::forbidden("Admin access required");
+        return (StatusCode::FORBIDDEN, Json(response)).into_response();
+    }
+
+    match DeadLetterService::replay(&state.executor, dead_letter_id, auth_info.user.id).await {
+        Ok(replay) => {
+            let response = ApiResponse::success(replay, "Dead letter replayed");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
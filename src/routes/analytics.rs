@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::websocket_session::{SessionAnalytics, SessionAnalyticsQuery};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::session_analytics_service::SessionAnalyticsService;
+
+// 获取当前工作区的实时连接会话统计（仅管理员）
+#[utoipa::path(
+    get,
+    path = "/analytics/sessions",
+    tag = "analytics",
+    params(SessionAnalyticsQuery),
+    responses((status = 200, description = "Session analytics retrieved successfully", body = ApiResponse<SessionAnalytics>))
+)]
+pub async fn get_session_analytics(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SessionAnalyticsQuery>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let days = params.days.unwrap_or(30);
+    let result = state
+        .executor
+        .read(move |conn| SessionAnalyticsService::report(conn, &ctx, days))
+        .await;
+    match result {
+        Ok(report) => {
+            let response = ApiResponse::success(report, "Session analytics retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,230 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::api_key::{ApiKey, CreateApiKeyRequest, CreatedApiKey, UpdateApiKeyRequest};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::api_keys_service::ApiKeysService;
+use crate::services::context::RequestContext;
+
+/// 获取当前工作空间的 API key 列表（不含密钥明文），仅管理员可用
+#[utoipa::path(
+    get,
+    path = "/api-keys",
+    tag = "api-keys",
+    responses((status = 200, description = "API keys retrieved successfully", body = ApiResponse<Vec<ApiKey>>))
+)]
+pub async fn get_api_keys(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ApiKeysService::list(conn, &ctx))
+        .await;
+    match result {
+        Ok(keys) => {
+            let response = ApiResponse::success(keys, "API keys retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建 API key；密钥明文只在创建时返回一次
+#[utoipa::path(
+    post,
+    path = "/api-keys",
+    tag = "api-keys",
+    request_body = CreateApiKeyRequest,
+    responses((status = 201, description = "API key created successfully", body = ApiResponse<CreatedApiKey>))
+)]
+pub async fn create_api_key(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ApiKeysService::create(conn, &ctx, &payload))
+        .await;
+    match result {
+        Ok(key) => {
+            let response = ApiResponse::created(key, "API key created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取单个 API key
+#[utoipa::path(
+    get,
+    path = "/api-keys/{key_id}",
+    tag = "api-keys",
+    params(("key_id" = Uuid, Path)),
+    responses((status = 200, description = "API key retrieved successfully", body = ApiResponse<ApiKey>))
+)]
+pub async fn get_api_key(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(key_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ApiKeysService::get(conn, &ctx, key_id))
+        .await;
+    match result {
+        Ok(key) => {
+            let response = ApiResponse::success(key, "API key retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 修改 API key 的名称或 scope，密钥保持不变
+#[utoipa::path(
+    put,
+    path = "/api-keys/{key_id}",
+    tag = "api-keys",
+    params(("key_id" = Uuid, Path)),
+    request_body = UpdateApiKeyRequest,
+    responses((status = 200, description = "API key updated successfully", body = ApiResponse<ApiKey>))
+)]
+pub async fn update_api_key(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(key_id): Path<Uuid>,
+    Json(payload): Json<UpdateApiKeyRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ApiKeysService::update(conn, &ctx, key_id, &payload))
+        .await;
+    match result {
+        Ok(key) => {
+            let response = ApiResponse::success(key, "API key updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 吊销 API key，之后使用该 key 的请求都会被拒绝
+#[utoipa::path(
+    delete,
+    path = "/api-keys/{key_id}",
+    tag = "api-keys",
+    params(("key_id" = Uuid, Path)),
+    responses((status = 200, description = "API key revoked successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn revoke_api_key(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(key_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ApiKeysService::revoke(conn, &ctx, key_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("API key revoked successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
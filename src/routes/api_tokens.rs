@@ -0,0 +1,142 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, EmptyData};
+use crate::db::models::api_token::{
+    ApiToken, ApiUsageReport, CreateApiTokenRequest, CreatedApiToken,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::api_tokens_service::ApiTokensService;
+use crate::services::context::RequestContext;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ApiTokenUsageQuery {
+    pub days: Option<i64>,
+}
+
+fn user_context(auth_info: &AuthUserInfo) -> RequestContext {
+    RequestContext {
+        user_id: auth_info.user.id,
+        workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
+        idempotency_key: None,
+        channel: auth_info.channel,
+    }
+}
+
+// 创建个人访问令牌（明文只在创建时返回一次）
+#[utoipa::path(
+    post,
+    path = "/auth/tokens",
+    tag = "api-tokens",
+    request_body = CreateApiTokenRequest,
+    responses((status = 201, description = "API token created successfully", body = ApiResponse<CreatedApiToken>))
+)]
+pub async fn create_api_token(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateApiTokenRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ApiTokensService::create(conn, &user_context(&auth_info), &payload)
+        })
+        .await;
+    match result {
+        Ok(token) => {
+            let response = ApiResponse::created(token, "API token created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取当前用户的个人访问令牌列表
+#[utoipa::path(
+    get,
+    path = "/auth/tokens",
+    tag = "api-tokens",
+    responses((status = 200, description = "API tokens retrieved successfully", body = ApiResponse<Vec<ApiToken>>))
+)]
+pub async fn get_api_tokens(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| ApiTokensService::list(conn, &user_context(&auth_info)))
+        .await;
+    match result {
+        Ok(tokens) => {
+            let response = ApiResponse::success(tokens, "API tokens retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 吊销个人访问令牌
+#[utoipa::path(
+    delete,
+    path = "/auth/tokens/{token_id}",
+    tag = "api-tokens",
+    params(("token_id" = Uuid, Path)),
+    responses((status = 200, description = "API token revoked successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn revoke_api_token(
+    State(state): State<Arc<AppState>>,
+    Path(token_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ApiTokensService::revoke(conn, &user_context(&auth_info), token_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("API token revoked successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取个人访问令牌的用量统计
+#[utoipa::path(
+    get,
+    path = "/auth/tokens/{token_id}/usage",
+    tag = "api-tokens",
+    params(("token_id" = Uuid, Path), ApiTokenUsageQuery),
+    responses((status = 200, description = "API token usage retrieved successfully", body = ApiResponse<ApiUsageReport>))
+)]
+pub async fn get_api_token_usage(
+    State(state): State<Arc<AppState>>,
+    Path(token_id): Path<Uuid>,
+    Query(params): Query<ApiTokenUsageQuery>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let days = params.days.unwrap_or(30);
+    let result = state
+        .executor
+        .read(move |conn| ApiTokensService::usage(conn, &user_context(&auth_info), token_id, days))
+        .await;
+    match result {
+        Ok(report) => {
+            let response = ApiResponse::success(report, "API token usage retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
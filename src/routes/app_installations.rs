@@ -0,0 +1,197 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::AppState;
+use crate::db::models::app_installation::{InstallAppRequest, UpdateInstallationRequest};
+use crate::db::models::*;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::app_installations_service::AppInstallationsService;
+use crate::services::context::RequestContext;
+use crate::services::permission_service::{Permission, PermissionService};
+
+// 获取当前工作空间已安装的应用
+#[utoipa::path(
+    get,
+    path = "/workspaces/current/apps",
+    tag = "app-installations",
+    responses((status = 200, description = "Installed apps retrieved successfully", body = ApiResponse<Vec<InstalledApp>>))
+)]
+pub async fn get_installed_apps(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| AppInstallationsService::list(conn, &ctx))
+        .await;
+    match result {
+        Ok(apps) => {
+            let response = ApiResponse::success(apps, "Installed apps retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 在当前工作空间安装应用（已安装时更新其权限范围）
+#[utoipa::path(
+    post,
+    path = "/workspaces/current/apps",
+    tag = "app-installations",
+    request_body = InstallAppRequest,
+    responses((status = 201, description = "App installed successfully", body = ApiResponse<InstalledApp>))
+)]
+pub async fn install_app(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<InstallAppRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageApps)?;
+            AppInstallationsService::install(conn, &ctx, &payload)
+        })
+        .await;
+    match result {
+        Ok(app) => {
+            let response = ApiResponse::created(app, "App installed successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 修改已安装应用的权限范围
+#[utoipa::path(
+    put,
+    path = "/workspaces/current/apps/{installation_id}",
+    tag = "app-installations",
+    params(("installation_id" = Uuid, Path)),
+    request_body = UpdateInstallationRequest,
+    responses((status = 200, description = "App permissions updated successfully", body = ApiResponse<InstalledApp>))
+)]
+pub async fn update_installed_app(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(installation_id): Path<Uuid>,
+    Json(payload): Json<UpdateInstallationRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageApps)?;
+            AppInstallationsService::update_scopes(conn, &ctx, installation_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(app) => {
+            let response = ApiResponse::success(app, "App permissions updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 从当前工作空间卸载应用
+#[utoipa::path(
+    delete,
+    path = "/workspaces/current/apps/{installation_id}",
+    tag = "app-installations",
+    params(("installation_id" = Uuid, Path)),
+    responses((status = 200, description = "App uninstalled successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn uninstall_app(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(installation_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageApps)?;
+            AppInstallationsService::uninstall(conn, &ctx, installation_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("App uninstalled successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
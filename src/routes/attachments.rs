@@ -0,0 +1,244 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::attachment::{
+    AttachmentResponse, AttachmentUpload, CreateAttachmentRequest,
+};
+use crate::middleware::asset_region::AssetRegionHint;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::attachments_service::AttachmentsService;
+use crate::services::context::RequestContext;
+use crate::services::permission_service::{Permission, PermissionService};
+
+// 开始上传附件，返回预签名上传地址
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/attachments",
+    tag = "attachments",
+    params(("issue_id" = Uuid, Path)),
+    request_body = CreateAttachmentRequest,
+    responses((status = 201, description = "Attachment upload started", body = ApiResponse<AttachmentUpload>))
+)]
+pub async fn create_attachment(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(issue_id): Path<Uuid>,
+    Json(payload): Json<CreateAttachmentRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let storage = state.storage.clone();
+    let max_bytes = state.config.attachment_max_bytes;
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            AttachmentsService::create_upload(
+                conn,
+                &ctx,
+                storage.as_ref(),
+                max_bytes,
+                issue_id,
+                payload,
+            )
+        })
+        .await;
+    match result {
+        Ok(upload) => {
+            let response = ApiResponse::created(upload, "Attachment upload started");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 确认附件已上传到存储
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/attachments/{attachment_id}/complete",
+    tag = "attachments",
+    params(("issue_id" = Uuid, Path), ("attachment_id" = Uuid, Path)),
+    responses((status = 200, description = "Attachment uploaded successfully", body = ApiResponse<AttachmentResponse>))
+)]
+pub async fn complete_attachment(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Path((issue_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let permission_ctx = ctx.clone();
+    if let Err(err) = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &permission_ctx, Permission::UpdateIssue)
+        })
+        .await
+    {
+        return err.into_response();
+    }
+
+    // 存储检查不占用数据库连接，确认结果再单独写入
+    match AttachmentsService::complete_upload(
+        &state.executor,
+        &ctx,
+        state.storage.as_ref(),
+        &state.asset_helper.for_region(region.as_deref()),
+        state.config.attachment_max_bytes,
+        issue_id,
+        attachment_id,
+    )
+    .await
+    {
+        Ok(attachment) => {
+            let response = ApiResponse::success(attachment, "Attachment uploaded successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取问题的附件列表
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/attachments",
+    tag = "attachments",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Attachments retrieved successfully", body = ApiResponse<Vec<AttachmentResponse>>))
+)]
+pub async fn get_attachments(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Path(issue_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let storage = state.storage.clone();
+    let result = state
+        .executor
+        .read(move |conn| {
+            AttachmentsService::list(conn, &ctx, storage.as_ref(), &asset_helper, issue_id)
+        })
+        .await;
+    match result {
+        Ok(attachments) => {
+            let response = ApiResponse::success(attachments, "Attachments retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 删除附件
+#[utoipa::path(
+    delete,
+    path = "/issues/{issue_id}/attachments/{attachment_id}",
+    tag = "attachments",
+    params(("issue_id" = Uuid, Path), ("attachment_id" = Uuid, Path)),
+    responses((status = 200, description = "Attachment deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_attachment(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((issue_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let permission_ctx = ctx.clone();
+    if let Err(err) = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &permission_ctx, Permission::UpdateIssue)
+        })
+        .await
+    {
+        return err.into_response();
+    }
+
+    // 先提交删除记录，再删除存储对象
+    match AttachmentsService::delete(
+        &state.executor,
+        &ctx,
+        state.storage.as_ref(),
+        issue_id,
+        attachment_id,
+    )
+    .await
+    {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Attachment deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -1,17 +1,30 @@
-use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+};
 use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
     AppState,
+    cache::TokenRevocationList,
     db::models::{
-        api::ApiResponse,
-        auth::{LoginRequest, RegisterRequest},
+        api::{ApiResponse, EmptyData},
+        auth::{
+            ChangePasswordRequest, LoginRequest, LoginResponse, RegisterRequest, User, UserProfile,
+        },
+        user_identity::OAuthCallbackQuery,
     },
-    middleware::auth::AuthUserInfo,
+    error::AppError,
+    middleware::asset_region::AssetRegionHint,
+    middleware::auth::{AccessTokenInfo, AuthUserInfo},
     services::auth_service::AuthService,
     services::context::RequestContext,
+    services::oauth_login_service::OAuthLoginService,
     validation::ValidatedJson,
 };
 
@@ -21,27 +34,35 @@ pub struct UpdateProfileRequest {
     pub username: Option<String>,
     pub email: Option<String>,
     pub avatar_url: Option<String>,
+    /// 资源 CDN 区域偏好；空字符串清除偏好
+    pub asset_region: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct SwitchWorkspaceRequest {
     pub workspace_id: Uuid,
 }
 
 // 用户注册
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses((status = 201, description = "User registered successfully", body = ApiResponse<LoginResponse>)),
+    security(())
+)]
 pub async fn register(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     ValidatedJson(payload): ValidatedJson<RegisterRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
-    match AuthService::register(&mut conn, &payload, &state.asset_helper) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .transaction(move |conn| AuthService::register(conn, &payload, &asset_helper))
+        .await;
+    match result {
         Ok(login_response) => {
             let response = ApiResponse::created(login_response, "User registered successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -51,19 +72,116 @@ pub async fn register(
 }
 
 // 用户登录
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses((status = 200, description = "Login successful", body = ApiResponse<LoginResponse>)),
+    security(())
+)]
 pub async fn login(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     ValidatedJson(payload): ValidatedJson<LoginRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .transaction(move |conn| AuthService::login(conn, &payload, &asset_helper))
+        .await;
+    match result {
+        Ok(login_response) => {
+            let response = ApiResponse::success(login_response, "Login successful");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 跳转到第三方登录（Google / GitHub）授权页
+#[utoipa::path(
+    get,
+    path = "/auth/oauth/{provider}/authorize",
+    tag = "auth",
+    params(("provider" = String, Path)),
+    responses((status = 307, description = "Redirect to the provider's consent page")),
+    security(())
+)]
+pub async fn oauth_authorize(
+    State(state): State<Arc<AppState>>,
+    Path(provider): Path<String>,
+) -> impl IntoResponse {
+    let (provider, settings) = match OAuthLoginService::provider(&state.config, &provider) {
+        Ok(provider) => provider,
+        Err(err) => return err.into_response(),
+    };
+
+    match OAuthLoginService::begin(&state.redis, provider).await {
+        Ok(oauth_state) => Redirect::to(&OAuthLoginService::authorize_url(
+            provider,
+            &settings,
+            &oauth_state,
+        ))
+        .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// 第三方登录回调：校验 state，换取用户信息并签发与密码登录相同的令牌
+#[utoipa::path(
+    get,
+    path = "/auth/oauth/{provider}/callback",
+    tag = "auth",
+    params(("provider" = String, Path), OAuthCallbackQuery),
+    responses((status = 200, description = "Login successful", body = ApiResponse<LoginResponse>)),
+    security(())
+)]
+pub async fn oauth_callback(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    Path(provider): Path<String>,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> impl IntoResponse {
+    let (provider, settings) = match OAuthLoginService::provider(&state.config, &provider) {
+        Ok(provider) => provider,
+        Err(err) => return err.into_response(),
+    };
+    if let Some(error) = &query.error {
+        return AppError::auth(format!(
+            "Login was not completed: {}",
+            query.error_description.as_deref().unwrap_or(error)
+        ))
+        .into_response();
+    }
+    let (Some(code), Some(oauth_state)) = (&query.code, &query.state) else {
+        return AppError::validation("Missing code or state").into_response();
+    };
+
+    if let Err(err) = OAuthLoginService::take_state(&state.redis, provider, oauth_state).await {
+        return err.into_response();
+    }
+    let client = match reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+    {
+        Ok(client) => client,
         Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
+            let response = ApiResponse::<()>::internal_error("Failed to create HTTP client");
             return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
         }
     };
+    let profile = match OAuthLoginService::fetch_profile(&client, provider, &settings, code).await {
+        Ok(profile) => profile,
+        Err(err) => return err.into_response(),
+    };
 
-    match AuthService::login(&mut conn, &payload, &state.asset_helper) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| OAuthLoginService::login(conn, provider, &profile, &asset_helper))
+        .await;
+    match result {
         Ok(login_response) => {
             let response = ApiResponse::success(login_response, "Login successful");
             (StatusCode::OK, Json(response)).into_response()
@@ -73,25 +191,30 @@ pub async fn login(
 }
 
 // 获取用户资料
+#[utoipa::path(
+    get,
+    path = "/auth/profile",
+    tag = "auth",
+    responses((status = 200, description = "Profile retrieved successfully", body = ApiResponse<UserProfile>))
+)]
 pub async fn get_profile(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
-    match AuthService::get_profile(&mut conn, &ctx, &state.asset_helper) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| AuthService::get_profile(conn, &ctx, &asset_helper))
+        .await;
+    match result {
         Ok(profile) => {
             let response = ApiResponse::success(profile, "Profile retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -103,24 +226,23 @@ pub async fn get_profile(
 // 更新用户资料
 pub async fn update_profile(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Json(payload): Json<UpdateProfileRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
-    match AuthService::update_profile(&mut conn, &ctx, &payload, &state.asset_helper) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .transaction(move |conn| AuthService::update_profile(conn, &ctx, &payload, &asset_helper))
+        .await;
+    match result {
         Ok(profile) => {
             let response = ApiResponse::success(profile, "Profile updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -130,26 +252,30 @@ pub async fn update_profile(
 }
 
 // 切换工作空间
+#[utoipa::path(
+    post,
+    path = "/auth/switch-workspace",
+    tag = "auth",
+    request_body = SwitchWorkspaceRequest,
+    responses((status = 200, description = "Workspace switched successfully", body = ApiResponse<User>))
+)]
 pub async fn switch_workspace(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<SwitchWorkspaceRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
-    match AuthService::switch_workspace(&mut conn, &ctx, payload.workspace_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| AuthService::switch_workspace(conn, &ctx, payload.workspace_id))
+        .await;
+    match result {
         Ok(user) => {
             let response = ApiResponse::success(user, "Workspace switched successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -158,32 +284,93 @@ pub async fn switch_workspace(
     }
 }
 
-// 用户登出
-pub async fn logout(
+// 修改密码（其他会话和已签发的 token 立即失效，返回新的 token）
+#[utoipa::path(
+    post,
+    path = "/auth/change-password",
+    tag = "auth",
+    request_body = ChangePasswordRequest,
+    responses((status = 200, description = "Password changed successfully", body = ApiResponse<LoginResponse>))
+)]
+pub async fn change_password(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
+    ValidatedJson(payload): ValidatedJson<ChangePasswordRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
+    let ctx = RequestContext {
+        user_id: auth_info.user.id,
+        workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
+        idempotency_key: None,
+        channel: auth_info.channel,
     };
 
+    // 吊销时间点取修改前的时间，保证随后签发的新 token 不受影响
+    let revoked_before = TokenRevocationList::now();
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .transaction(move |conn| AuthService::change_password(conn, &ctx, &payload, &asset_helper))
+        .await;
+    match result {
+        Ok(login_response) => {
+            if let Err(err) = state
+                .token_revocations
+                .revoke_issued_before(auth_info.user.id, revoked_before)
+                .await
+            {
+                return err.into_response();
+            }
+            let response = ApiResponse::success(login_response, "Password changed successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 用户登出
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "auth",
+    responses((status = 200, description = "Logout successful", body = ApiResponse<EmptyData>))
+)]
+pub async fn logout(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    access_token: Option<Extension<AccessTokenInfo>>,
+) -> impl IntoResponse {
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
     // 使所有会话失效
-    if let Err(err) = AuthService::logout(&mut conn, &ctx) {
+    let user_id = ctx.user_id;
+    if let Err(err) = state
+        .executor
+        .transaction(move |conn| AuthService::logout(conn, &ctx))
+        .await
+    {
+        return err.into_response();
+    }
+
+    // 吊销当前 token 及此前签发的所有 token，使其立即失效
+    if let Some(Extension(token)) = &access_token
+        && let Err(err) = state
+            .token_revocations
+            .revoke_token(&token.jti, token.expires_at)
+            .await
+    {
+        return err.into_response();
+    }
+    if let Err(err) = state.token_revocations.revoke_all_for_user(user_id).await {
         return err.into_response();
     }
 
     // 清除 Redis 中的所有用户相关缓存
-    let user_id = ctx.user_id;
 
     // 清除用户缓存的各个键
     if let Ok(mut redis_conn) = state.redis.get_multiplexed_async_connection().await {
@@ -0,0 +1,276 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::automation::{
+    AutomationRuleResponse, AutomationRunResponse, CreateAutomationRuleRequest,
+    UpdateAutomationRuleRequest,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::automations_service::AutomationsService;
+use crate::services::context::RequestContext;
+
+/// 获取工作区的自动化规则列表
+#[utoipa::path(
+    get,
+    path = "/automations",
+    tag = "automations",
+    responses((status = 200, description = "Automations retrieved successfully", body = ApiResponse<Vec<AutomationRuleResponse>>))
+)]
+pub async fn get_automations(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| AutomationsService::list(conn, &ctx))
+        .await;
+    match result {
+        Ok(rules) => {
+            let response = ApiResponse::success(rules, "Automations retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建自动化规则：问题被创建、状态变化或添加标签时，条件满足则执行动作
+#[utoipa::path(
+    post,
+    path = "/automations",
+    tag = "automations",
+    request_body = CreateAutomationRuleRequest,
+    responses((status = 201, description = "Automation created successfully", body = ApiResponse<AutomationRuleResponse>))
+)]
+pub async fn create_automation(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateAutomationRuleRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| AutomationsService::create(conn, &ctx, &payload))
+        .await;
+    match result {
+        Ok(rule) => {
+            let response = ApiResponse::created(rule, "Automation created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取自动化规则详情
+#[utoipa::path(
+    get,
+    path = "/automations/{rule_id}",
+    tag = "automations",
+    params(("rule_id" = Uuid, Path)),
+    responses((status = 200, description = "Automation retrieved successfully", body = ApiResponse<AutomationRuleResponse>))
+)]
+pub async fn get_automation(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(rule_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| AutomationsService::get(conn, &ctx, rule_id))
+        .await;
+    match result {
+        Ok(rule) => {
+            let response = ApiResponse::success(rule, "Automation retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 更新自动化规则
+#[utoipa::path(
+    put,
+    path = "/automations/{rule_id}",
+    tag = "automations",
+    params(("rule_id" = Uuid, Path)),
+    request_body = UpdateAutomationRuleRequest,
+    responses((status = 200, description = "Automation updated successfully", body = ApiResponse<AutomationRuleResponse>))
+)]
+pub async fn update_automation(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(rule_id): Path<Uuid>,
+    Json(payload): Json<UpdateAutomationRuleRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| AutomationsService::update(conn, &ctx, rule_id, &payload))
+        .await;
+    match result {
+        Ok(rule) => {
+            let response = ApiResponse::success(rule, "Automation updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除自动化规则及其执行记录
+#[utoipa::path(
+    delete,
+    path = "/automations/{rule_id}",
+    tag = "automations",
+    params(("rule_id" = Uuid, Path)),
+    responses((status = 200, description = "Automation deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_automation(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(rule_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| AutomationsService::delete(conn, &ctx, rule_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Automation deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取自动化规则最近的执行记录
+#[utoipa::path(
+    get,
+    path = "/automations/{rule_id}/runs",
+    tag = "automations",
+    params(("rule_id" = Uuid, Path)),
+    responses((status = 200, description = "Automation runs retrieved successfully", body = ApiResponse<Vec<AutomationRunResponse>>))
+)]
+pub async fn get_automation_runs(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(rule_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| AutomationsService::runs(conn, &ctx, rule_id))
+        .await;
+    match result {
+        Ok(runs) => {
+            let response = ApiResponse::success(runs, "Automation runs retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
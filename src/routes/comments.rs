@@ -1,53 +1,69 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Json},
 };
 use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::AppState;
-use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::comment::{
+    Comment, CommentListItem, CommentPage, CommentPageQuery, ReactionSummary,
+};
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::comments_service::CommentsService;
 use crate::services::context::RequestContext;
+use crate::services::permission_service::{Permission, PermissionService};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CommentQueryParams {
     pub include_deleted: Option<bool>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateCommentRequest {
     pub content: String,
+    /// Comment being replied to; replies to a reply join its thread
+    pub parent_comment_id: Option<Uuid>,
+    /// Users to notify; must be members of the workspace
+    pub mentions: Option<Vec<Uuid>>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateCommentRequest {
     pub content: String,
 }
 
+#[derive(Deserialize, ToSchema)]
+pub struct ReactionRequest {
+    #[serde(alias = "reaction_type")]
+    pub emoji: String,
+}
+
 // 获取issue的评论列表
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/comments",
+    tag = "comments",
+    params(("issue_id" = Uuid, Path), CommentQueryParams),
+    responses((status = 200, description = "Comments retrieved successfully", body = ApiResponse<Vec<CommentListItem>>))
+)]
 pub async fn get_comments(
     State(state): State<Arc<AppState>>,
     Path(issue_id): Path<Uuid>,
     Query(params): Query<CommentQueryParams>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -61,7 +77,11 @@ pub async fn get_comments(
 
     let include_deleted = params.include_deleted.unwrap_or(false);
 
-    match CommentsService::list_by_issue(&mut conn, &ctx, issue_id, include_deleted) {
+    let result = state
+        .executor
+        .read(move |conn| CommentsService::list_by_issue(conn, &ctx, issue_id, include_deleted))
+        .await;
+    match result {
         Ok(comments) => {
             let response = ApiResponse::success(comments, "Comments retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -70,26 +90,71 @@ pub async fn get_comments(
     }
 }
 
-// 创建评论
-pub async fn create_comment(
+// 分页获取issue的评论，支持定位到某条评论（around）
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/comments/page",
+    tag = "comments",
+    params(("issue_id" = Uuid, Path), CommentPageQuery),
+    responses((status = 200, description = "Comments retrieved successfully", body = ApiResponse<CommentPage>))
+)]
+pub async fn get_comment_page(
     State(state): State<Arc<AppState>>,
     Path(issue_id): Path<Uuid>,
+    Query(query): Query<CommentPageQuery>,
     auth_info: AuthUserInfo,
-    Json(payload): Json<CreateCommentRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
+    let result = state
+        .executor
+        .read(move |conn| CommentsService::page_by_issue(conn, &ctx, issue_id, &query))
+        .await;
+    match result {
+        Ok(page) => {
+            let response = ApiResponse::success(page, "Comments retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 创建评论
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/comments",
+    tag = "comments",
+    params(("issue_id" = Uuid, Path)),
+    request_body = CreateCommentRequest,
+    responses((status = 201, description = "Comment created successfully", body = ApiResponse<Comment>))
+)]
+pub async fn create_comment(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateCommentRequest>,
+) -> impl IntoResponse {
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -101,7 +166,21 @@ pub async fn create_comment(
         }
     };
 
-    match CommentsService::create(&mut conn, &ctx, issue_id, payload.content) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::CreateComment)?;
+            CommentsService::create_with_mentions(
+                conn,
+                &ctx,
+                issue_id,
+                payload.content,
+                payload.parent_comment_id,
+                payload.mentions.as_deref().unwrap_or_default(),
+            )
+        })
+        .await;
+    match result {
         Ok(comment) => {
             let response = ApiResponse::created(comment, "Comment created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -111,25 +190,26 @@ pub async fn create_comment(
 }
 
 // 更新评论
+#[utoipa::path(
+    put,
+    path = "/comments/{comment_id}",
+    tag = "comments",
+    params(("comment_id" = Uuid, Path)),
+    request_body = UpdateCommentRequest,
+    responses((status = 200, description = "Comment updated successfully", body = ApiResponse<Comment>))
+)]
 pub async fn update_comment(
     State(state): State<Arc<AppState>>,
     Path(comment_id): Path<Uuid>,
     auth_info: AuthUserInfo,
     Json(payload): Json<UpdateCommentRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -141,7 +221,11 @@ pub async fn update_comment(
         }
     };
 
-    match CommentsService::update(&mut conn, &ctx, comment_id, payload.content) {
+    let result = state
+        .executor
+        .transaction(move |conn| CommentsService::update(conn, &ctx, comment_id, payload.content))
+        .await;
+    match result {
         Ok(comment) => {
             let response = ApiResponse::success(comment, "Comment updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -151,24 +235,24 @@ pub async fn update_comment(
 }
 
 // 删除评论
+#[utoipa::path(
+    delete,
+    path = "/comments/{comment_id}",
+    tag = "comments",
+    params(("comment_id" = Uuid, Path)),
+    responses((status = 200, description = "Comment deleted successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn delete_comment(
     State(state): State<Arc<AppState>>,
     Path(comment_id): Path<Uuid>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -180,7 +264,11 @@ pub async fn delete_comment(
         }
     };
 
-    match CommentsService::delete(&mut conn, &ctx, comment_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| CommentsService::delete(conn, &ctx, comment_id))
+        .await;
+    match result {
         Ok(()) => {
             let response = ApiResponse::<()>::ok("Comment deleted successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -190,24 +278,160 @@ pub async fn delete_comment(
 }
 
 // 获取单个评论
+#[utoipa::path(
+    get,
+    path = "/comments/{comment_id}",
+    tag = "comments",
+    params(("comment_id" = Uuid, Path)),
+    responses((status = 200, description = "Comment retrieved successfully", body = ApiResponse<Comment>))
+)]
 pub async fn get_comment(
     State(state): State<Arc<AppState>>,
     Path(comment_id): Path<Uuid>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| CommentsService::get_by_id(conn, &ctx, comment_id))
+        .await;
+    match result {
+        Ok(comment) => {
+            let response = ApiResponse::success(comment, "Comment retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取评论中代码块的完整原文（渲染结果中被截断的代码块）
+#[utoipa::path(
+    get,
+    path = "/comments/{comment_id}/code-blocks/{index}",
+    tag = "comments",
+    params(("comment_id" = Uuid, Path), ("index" = usize, Path)),
+    responses((status = 200, description = "Raw code block", body = String, content_type = "text/plain"))
+)]
+pub async fn get_comment_code_block(
+    State(state): State<Arc<AppState>>,
+    Path((comment_id, index)): Path<(Uuid, usize)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| CommentsService::code_block(conn, &ctx, comment_id, index))
+        .await;
+    match result {
+        Ok(code) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            code,
+        )
+            .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// 添加评论表情反应
+#[utoipa::path(
+    post,
+    path = "/comments/{comment_id}/reactions",
+    tag = "comments",
+    params(("comment_id" = Uuid, Path)),
+    request_body = ReactionRequest,
+    responses((status = 200, description = "Reaction added successfully", body = ApiResponse<Vec<ReactionSummary>>))
+)]
+pub async fn add_reaction(
+    State(state): State<Arc<AppState>>,
+    Path(comment_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<ReactionRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::CreateComment)?;
+            CommentsService::add_reaction(conn, &ctx, comment_id, payload.emoji)
+        })
+        .await;
+    match result {
+        Ok(reactions) => {
+            let response = ApiResponse::success(reactions, "Reaction added successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 移除评论表情反应
+#[utoipa::path(
+    delete,
+    path = "/comments/{comment_id}/reactions/{emoji}",
+    tag = "comments",
+    params(("comment_id" = Uuid, Path), ("emoji" = String, Path)),
+    responses((status = 200, description = "Reaction removed successfully", body = ApiResponse<Vec<ReactionSummary>>))
+)]
+pub async fn remove_reaction(
+    State(state): State<Arc<AppState>>,
+    Path((comment_id, emoji)): Path<(Uuid, String)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -219,9 +443,13 @@ pub async fn get_comment(
         }
     };
 
-    match CommentsService::get_by_id(&mut conn, &ctx, comment_id) {
-        Ok(comment) => {
-            let response = ApiResponse::success(comment, "Comment retrieved successfully");
+    let result = state
+        .executor
+        .transaction(move |conn| CommentsService::remove_reaction(conn, &ctx, comment_id, &emoji))
+        .await;
+    match result {
+        Ok(reactions) => {
+            let response = ApiResponse::success(reactions, "Reaction removed successfully");
             (StatusCode::OK, Json(response)).into_response()
         }
         Err(err) => err.into_response(),
@@ -0,0 +1,209 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::content_report::{
+    ContentReport, CreateReportRequest, ReportQueueItem, ReportQueueQuery, ResolveReportRequest,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::content_reports_service::ContentReportsService;
+use crate::services::context::RequestContext;
+
+/// 举报评论
+///
+/// 权限要求: 工作区成员（包括Guest）。reason 为 spam、harassment、inappropriate、
+/// off_topic 或 other（other 需要填写 details）；同一评论的未处理举报只能有一条
+#[utoipa::path(
+    post,
+    path = "/comments/{comment_id}/report",
+    tag = "content-reports",
+    params(("comment_id" = Uuid, Path)),
+    request_body = CreateReportRequest,
+    responses((status = 201, description = "Comment reported", body = ApiResponse<ContentReport>))
+)]
+pub async fn report_comment(
+    State(state): State<Arc<AppState>>,
+    Path(comment_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateReportRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ContentReportsService::report_comment(conn, &ctx, comment_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(report) => {
+            let response = ApiResponse::created(report, "Comment reported");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 举报问题
+///
+/// 权限要求: 工作区成员（包括Guest）。规则与举报评论相同
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/report",
+    tag = "content-reports",
+    params(("issue_id" = Uuid, Path)),
+    request_body = CreateReportRequest,
+    responses((status = 201, description = "Issue reported", body = ApiResponse<ContentReport>))
+)]
+pub async fn report_issue(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateReportRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ContentReportsService::report_issue(conn, &ctx, issue_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(report) => {
+            let response = ApiResponse::created(report, "Issue reported");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取当前工作区的举报审核队列，默认只返回未处理的举报（最早的在前）
+///
+/// 权限要求: 当前用户必须是工作区的Owner或Admin。可按 status 和 target_type 过滤
+#[utoipa::path(
+    get,
+    path = "/workspaces/current/reports",
+    tag = "content-reports",
+    params(ReportQueueQuery),
+    responses((status = 200, description = "Reports retrieved successfully", body = ApiResponse<Vec<ReportQueueItem>>))
+)]
+pub async fn get_report_queue(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(query): Query<ReportQueueQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ContentReportsService::queue(conn, &ctx, &query))
+        .await;
+    match result {
+        Ok(reports) => {
+            let response = ApiResponse::success(reports, "Reports retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 处理举报：dismiss 不做处理，hide 隐藏评论或归档问题，remove 删除内容
+///
+/// 权限要求: 当前用户必须是工作区的Owner或Admin。同一内容的所有未处理举报一并关闭，
+/// 举报人会收到处理结果的通知
+#[utoipa::path(
+    post,
+    path = "/workspaces/current/reports/{report_id}/resolve",
+    tag = "content-reports",
+    params(("report_id" = Uuid, Path)),
+    request_body = ResolveReportRequest,
+    responses((status = 200, description = "Reports resolved", body = ApiResponse<Vec<ContentReport>>))
+)]
+pub async fn resolve_report(
+    State(state): State<Arc<AppState>>,
+    Path(report_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<ResolveReportRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ContentReportsService::resolve(conn, &ctx, report_id, &payload))
+        .await;
+    match result {
+        Ok(reports) => {
+            let response = ApiResponse::success(reports, "Reports resolved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,291 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::custom_emoji::{
+    CreateCustomEmojiRequest, CustomEmojiResponse, CustomEmojiStatsQuery, CustomEmojiUpload,
+    RenameCustomEmojiRequest,
+};
+use crate::middleware::asset_region::AssetRegionHint;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::custom_emojis_service::CustomEmojisService;
+
+// 获取工作区的自定义表情
+#[utoipa::path(
+    get,
+    path = "/emojis",
+    tag = "custom-emojis",
+    responses((status = 200, description = "Emojis retrieved successfully", body = ApiResponse<Vec<CustomEmojiResponse>>))
+)]
+pub async fn get_emojis(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| CustomEmojisService::list(conn, &ctx, &asset_helper))
+        .await;
+    match result {
+        Ok(emojis) => {
+            let response = ApiResponse::success(emojis, "Emojis retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 开始上传自定义表情，返回预签名上传地址
+#[utoipa::path(
+    post,
+    path = "/emojis",
+    tag = "custom-emojis",
+    request_body = CreateCustomEmojiRequest,
+    responses((status = 201, description = "Emoji upload started", body = ApiResponse<CustomEmojiUpload>))
+)]
+pub async fn create_emoji(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateCustomEmojiRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let storage = state.storage.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            CustomEmojisService::create_upload(conn, &ctx, storage.as_ref(), payload)
+        })
+        .await;
+    match result {
+        Ok(upload) => {
+            let response = ApiResponse::created(upload, "Emoji upload started");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 确认表情图片已上传到存储
+#[utoipa::path(
+    post,
+    path = "/emojis/{emoji_id}/complete",
+    tag = "custom-emojis",
+    params(("emoji_id" = Uuid, Path)),
+    responses((status = 200, description = "Emoji uploaded successfully", body = ApiResponse<CustomEmojiResponse>))
+)]
+pub async fn complete_emoji(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Path(emoji_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    // 存储检查不占用数据库连接，确认结果再单独写入
+    match CustomEmojisService::complete_upload(
+        &state.executor,
+        &ctx,
+        state.storage.as_ref(),
+        &state.asset_helper.for_region(region.as_deref()),
+        emoji_id,
+    )
+    .await
+    {
+        Ok(emoji) => {
+            let response = ApiResponse::success(emoji, "Emoji uploaded successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 重命名自定义表情
+#[utoipa::path(
+    put,
+    path = "/emojis/{emoji_id}",
+    tag = "custom-emojis",
+    params(("emoji_id" = Uuid, Path)),
+    request_body = RenameCustomEmojiRequest,
+    responses((status = 200, description = "Emoji renamed successfully", body = ApiResponse<CustomEmojiResponse>))
+)]
+pub async fn rename_emoji(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Path(emoji_id): Path<Uuid>,
+    Json(payload): Json<RenameCustomEmojiRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            CustomEmojisService::rename(conn, &ctx, &asset_helper, emoji_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(emoji) => {
+            let response = ApiResponse::success(emoji, "Emoji renamed successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 删除自定义表情
+#[utoipa::path(
+    delete,
+    path = "/emojis/{emoji_id}",
+    tag = "custom-emojis",
+    params(("emoji_id" = Uuid, Path)),
+    responses((status = 200, description = "Emoji deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_emoji(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(emoji_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    // 先提交删除记录，再删除存储对象
+    match CustomEmojisService::delete(&state.executor, &ctx, state.storage.as_ref(), emoji_id).await
+    {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Emoji deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 自定义表情使用统计，使用最少的排在前面，便于清理
+#[utoipa::path(
+    get,
+    path = "/emojis/stats",
+    tag = "custom-emojis",
+    params(CustomEmojiStatsQuery),
+    responses((status = 200, description = "Emoji usage retrieved successfully", body = ApiResponse<Vec<CustomEmojiResponse>>))
+)]
+pub async fn get_emoji_stats(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Query(query): Query<CustomEmojiStatsQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| CustomEmojisService::stats(conn, &ctx, &asset_helper, &query))
+        .await;
+    match result {
+        Ok(stats) => {
+            let response = ApiResponse::success(stats, "Emoji usage retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -7,6 +7,7 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::db::enums::*;
@@ -14,9 +15,10 @@ use crate::db::models::*;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::context::RequestContext;
 use crate::services::cycles_service::CyclesService;
+use crate::services::permission_service::{Permission, PermissionService};
 
 // 请求体定义
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateCycleRequest {
     pub team_id: Uuid,
     pub name: String,
@@ -26,7 +28,7 @@ pub struct CreateCycleRequest {
     pub goal: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateCycleRequest {
     pub team_id: Option<Uuid>,
     pub name: Option<String>,
@@ -37,19 +39,20 @@ pub struct UpdateCycleRequest {
     pub goal: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct AssignIssuesToCycleRequest {
     pub issue_ids: Vec<Uuid>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CycleIssuesQuery {
     pub page: Option<i64>,
     pub limit: Option<i64>,
     pub status: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct CycleStats {
     pub id: Uuid,
     pub name: String,
@@ -62,7 +65,13 @@ pub struct CycleStats {
     pub todo_issues: i64,
     pub completion_rate: f64,
     pub days_remaining: i32,
+    /// Working days (weekdays minus workspace holidays) in the whole cycle
+    pub working_days_total: i64,
+    /// Working days left from today until the cycle ends
+    pub working_days_remaining: i64,
     pub is_overdue: bool,
+    /// Committed vs completed points and scope change since the cycle started
+    pub points: CyclePoints,
 }
 
 #[derive(Serialize)]
@@ -82,24 +91,24 @@ pub struct PaginatedIssues {
 }
 
 /// 创建 Cycle
+#[utoipa::path(
+    post,
+    path = "/cycles",
+    tag = "cycles",
+    request_body = CreateCycleRequest,
+    responses((status = 201, description = "Cycle created successfully", body = ApiResponse<Cycle>))
+)]
 pub async fn create_cycle(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<CreateCycleRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -111,7 +120,14 @@ pub async fn create_cycle(
         }
     };
 
-    match CyclesService::create(&mut conn, &ctx, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageCycles)?;
+            CyclesService::create(conn, &ctx, &payload)
+        })
+        .await;
+    match result {
         Ok(cycle) => {
             let response = ApiResponse::created(cycle, "Cycle created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -120,31 +136,32 @@ pub async fn create_cycle(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CycleQuery {
     pub team_id: Option<Uuid>,
     pub status: Option<String>,
 }
 
 /// 获取 cycles 列表
+#[utoipa::path(
+    get,
+    path = "/cycles",
+    tag = "cycles",
+    params(CycleQuery),
+    responses((status = 200, description = "Cycles retrieved successfully", body = ApiResponse<Vec<Cycle>>))
+)]
 pub async fn get_cycles(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Query(_params): Query<CycleQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -156,7 +173,11 @@ pub async fn get_cycles(
         }
     };
 
-    match CyclesService::list(&mut conn, &ctx) {
+    let result = state
+        .executor
+        .read(move |conn| CyclesService::list(conn, &ctx))
+        .await;
+    match result {
         Ok(cycles) => {
             let response = ApiResponse::success(cycles, "Cycles retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -166,24 +187,24 @@ pub async fn get_cycles(
 }
 
 /// 获取指定 cycle
+#[utoipa::path(
+    get,
+    path = "/cycles/{cycle_id}",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path)),
+    responses((status = 200, description = "Cycle retrieved successfully", body = ApiResponse<Cycle>))
+)]
 pub async fn get_cycle_by_id(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(cycle_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -195,7 +216,11 @@ pub async fn get_cycle_by_id(
         }
     };
 
-    match CyclesService::get_by_id(&mut conn, &ctx, cycle_id) {
+    let result = state
+        .executor
+        .read(move |conn| CyclesService::get_by_id(conn, &ctx, cycle_id))
+        .await;
+    match result {
         Ok(cycle) => {
             let response = ApiResponse::success(cycle, "Cycle retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -205,25 +230,26 @@ pub async fn get_cycle_by_id(
 }
 
 /// 更新指定 cycle
+#[utoipa::path(
+    put,
+    path = "/cycles/{cycle_id}",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path)),
+    request_body = UpdateCycleRequest,
+    responses((status = 200, description = "Cycle updated successfully", body = ApiResponse<Cycle>))
+)]
 pub async fn update_cycle(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(cycle_id): Path<Uuid>,
     Json(payload): Json<UpdateCycleRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -235,7 +261,14 @@ pub async fn update_cycle(
         }
     };
 
-    match CyclesService::update(&mut conn, &ctx, cycle_id, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageCycles)?;
+            CyclesService::update(conn, &ctx, cycle_id, &payload)
+        })
+        .await;
+    match result {
         Ok(cycle) => {
             let response = ApiResponse::success(cycle, "Cycle updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -245,24 +278,24 @@ pub async fn update_cycle(
 }
 
 /// 删除指定 cycle
+#[utoipa::path(
+    delete,
+    path = "/cycles/{cycle_id}",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path)),
+    responses((status = 200, description = "Cycle deleted successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn delete_cycle(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(cycle_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -274,7 +307,14 @@ pub async fn delete_cycle(
         }
     };
 
-    match CyclesService::delete(&mut conn, &ctx, cycle_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageCycles)?;
+            CyclesService::delete(conn, &ctx, cycle_id)
+        })
+        .await;
+    match result {
         Ok(()) => {
             let response = ApiResponse::<()>::ok("Cycle deleted successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -284,24 +324,72 @@ pub async fn delete_cycle(
 }
 
 /// 获取周期统计信息
+///
+/// `points` 中包含承诺点数（周期开始时的范围）、已完成点数，以及周期开始后的范围变化
+#[utoipa::path(
+    get,
+    path = "/cycles/{cycle_id}/stats",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path)),
+    responses((status = 200, description = "Cycle statistics retrieved successfully", body = ApiResponse<CycleStats>))
+)]
 pub async fn get_cycle_stats(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(cycle_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
+    let result = state
+        .executor
+        .read(move |conn| CyclesService::get_stats(conn, &ctx, cycle_id))
+        .await;
+    match result {
+        Ok(stats) => {
+            let response = ApiResponse::success(stats, "Cycle statistics retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取周期燃尽图数据
+///
+/// 按天（UTC）返回剩余、已完成和新增的 Issue 数与点数，以及理想燃尽线；
+/// 由周期的范围变更日志回放得出，只包含到今天为止的日期
+#[utoipa::path(
+    get,
+    path = "/cycles/{cycle_id}/burndown",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path)),
+    responses((status = 200, description = "Cycle burndown retrieved successfully", body = ApiResponse<CycleBurndown>))
+)]
+pub async fn get_cycle_burndown(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(cycle_id): Path<Uuid>,
+) -> impl IntoResponse {
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -313,9 +401,13 @@ pub async fn get_cycle_stats(
         }
     };
 
-    match CyclesService::get_stats(&mut conn, &ctx, cycle_id) {
-        Ok(stats) => {
-            let response = ApiResponse::success(stats, "Cycle statistics retrieved successfully");
+    let result = state
+        .executor
+        .read(move |conn| CyclesService::get_burndown(conn, &ctx, cycle_id))
+        .await;
+    match result {
+        Ok(burndown) => {
+            let response = ApiResponse::success(burndown, "Cycle burndown retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
         }
         Err(err) => err.into_response(),
@@ -323,25 +415,25 @@ pub async fn get_cycle_stats(
 }
 
 /// 获取周期内的 Issues 列表
+#[utoipa::path(
+    get,
+    path = "/cycles/{cycle_id}/issues",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path), CycleIssuesQuery),
+    responses((status = 200, description = "Cycle issues retrieved successfully", body = ApiResponse<Vec<Issue>>))
+)]
 pub async fn get_cycle_issues(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(cycle_id): Path<Uuid>,
     Query(query): Query<CycleIssuesQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -353,14 +445,13 @@ pub async fn get_cycle_issues(
         }
     };
 
-    match CyclesService::get_issues(
-        &mut conn,
-        &ctx,
-        cycle_id,
-        query.page,
-        query.limit,
-        query.status,
-    ) {
+    let result = state
+        .executor
+        .read(move |conn| {
+            CyclesService::get_issues(conn, &ctx, cycle_id, query.page, query.limit, query.status)
+        })
+        .await;
+    match result {
         Ok(issues) => {
             let response = ApiResponse::success(issues, "Cycle issues retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -370,25 +461,26 @@ pub async fn get_cycle_issues(
 }
 
 /// 将 Issues 分配到周期
+#[utoipa::path(
+    post,
+    path = "/cycles/{cycle_id}/issues",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path)),
+    request_body = AssignIssuesToCycleRequest,
+    responses((status = 200, description = "OK", body = ApiResponse<Option<String>>))
+)]
 pub async fn assign_issues_to_cycle(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(cycle_id): Path<Uuid>,
     Json(payload): Json<AssignIssuesToCycleRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -400,12 +492,20 @@ pub async fn assign_issues_to_cycle(
         }
     };
 
-    match CyclesService::assign_issues(&mut conn, &ctx, cycle_id, &payload.issue_ids) {
+    let issue_count = payload.issue_ids.len();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageCycles)?;
+            CyclesService::assign_issues(conn, &ctx, cycle_id, &payload.issue_ids)
+        })
+        .await;
+    match result {
         Ok(_count) => {
             let response = ApiResponse::success(
                 Some(format!(
                     "Successfully assigned {} issues to cycle",
-                    payload.issue_ids.len()
+                    issue_count
                 )),
                 "Issues assigned to cycle successfully",
             );
@@ -416,25 +516,26 @@ pub async fn assign_issues_to_cycle(
 }
 
 /// 从周期中移除 Issues
+#[utoipa::path(
+    delete,
+    path = "/cycles/{cycle_id}/issues",
+    tag = "cycles",
+    params(("cycle_id" = Uuid, Path)),
+    request_body = AssignIssuesToCycleRequest,
+    responses((status = 200, description = "OK", body = ApiResponse<Option<String>>))
+)]
 pub async fn remove_issues_from_cycle(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(cycle_id): Path<Uuid>,
     Json(payload): Json<AssignIssuesToCycleRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -446,12 +547,20 @@ pub async fn remove_issues_from_cycle(
         }
     };
 
-    match CyclesService::remove_issues(&mut conn, &ctx, cycle_id, &payload.issue_ids) {
+    let issue_count = payload.issue_ids.len();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageCycles)?;
+            CyclesService::remove_issues(conn, &ctx, cycle_id, &payload.issue_ids)
+        })
+        .await;
+    match result {
         Ok(_count) => {
             let response = ApiResponse::success(
                 Some(format!(
                     "Successfully removed {} issues from cycle",
-                    payload.issue_ids.len()
+                    issue_count
                 )),
                 "Issues removed from cycle successfully",
             );
@@ -461,21 +570,207 @@ pub async fn remove_issues_from_cycle(
     }
 }
 
-/// 自动更新周期状态
-pub async fn update_cycle_status_auto(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+/// 获取团队的周期节奏设置
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/cycle-settings",
+    tag = "cycles",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Cycle settings retrieved successfully", body = ApiResponse<TeamCycleSettings>))
+)]
+pub async fn get_cycle_settings(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
-    match CyclesService::auto_update_status(&mut conn) {
-        Ok(_counts) => {
+    let result = state
+        .executor
+        .read(move |conn| CyclesService::get_settings(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(settings) => {
+            let response = ApiResponse::success(settings, "Cycle settings retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建或更新团队的周期节奏设置（周期长度、开始的星期几、间隔天数、自动创建与未完成 Issue 顺延）
+#[utoipa::path(
+    put,
+    path = "/teams/{team_id}/cycle-settings",
+    tag = "cycles",
+    params(("team_id" = Uuid, Path)),
+    request_body = UpsertCycleSettingsRequest,
+    responses((status = 200, description = "Cycle settings saved successfully", body = ApiResponse<TeamCycleSettings>))
+)]
+pub async fn put_cycle_settings(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<UpsertCycleSettingsRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageCycles)?;
+            CyclesService::upsert_settings(conn, &ctx, team_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(settings) => {
+            let response = ApiResponse::success(settings, "Cycle settings saved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除团队的周期节奏设置，之后不再自动创建周期
+#[utoipa::path(
+    delete,
+    path = "/teams/{team_id}/cycle-settings",
+    tag = "cycles",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Cycle settings deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_cycle_settings(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageCycles)?;
+            CyclesService::delete_settings(conn, &ctx, team_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Cycle settings deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 预览团队按节奏设置将要创建的下一个周期
+///
+/// 下一个周期接在团队最晚结束的周期之后，间隔 cooldown_days 天后从最近的开始星期几起算，
+/// 且不早于今天；开启顺延时同时返回当前周期中会被顺延的未完成 Issue 数
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/cycles/next-preview",
+    tag = "cycles",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Next cycle preview retrieved successfully", body = ApiResponse<NextCyclePreview>))
+)]
+pub async fn get_next_cycle_preview(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| CyclesService::preview_next(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(preview) => {
+            let response =
+                ApiResponse::success(preview, "Next cycle preview retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 自动更新周期状态
+#[utoipa::path(
+    post,
+    path = "/cycles/auto-update-status",
+    tag = "cycles",
+    responses((status = 200, description = "Cycle statuses updated automatically", body = ApiResponse<Option<String>>))
+)]
+pub async fn update_cycle_status_auto(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(CyclesService::auto_update_status)
+        .await;
+    match result {
+        Ok(updates) => {
             let message = format!(
                 "Auto-updated {} cycles to active and {} cycles to completed",
-                0, 0
+                updates.activated, updates.completed
             );
             let response =
                 ApiResponse::success(Some(message), "Cycle statuses updated automatically");
@@ -0,0 +1,80 @@
+use crate::AppState;
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::cache::store::{get_json, set_json};
+use crate::db::models::*;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::dashboard_service::{CACHE_TTL_SECONDS, DashboardService, LATENCY_BUDGET};
+
+/// 获取当前用户的工作台摘要
+///
+/// 一次返回分配给我的未完成事项、本周到期事项、进行中周期的进度和最近动态。
+/// 摘要按用户在 Redis 中缓存 30 秒；未命中时最多访问数据库两次，且不做
+/// COUNT 查询。超过延迟预算（150ms）的请求会记录警告。
+#[utoipa::path(
+    get,
+    path = "/dashboard/summary",
+    tag = "dashboard",
+    responses((status = 200, description = "Dashboard summary retrieved successfully", body = ApiResponse<DashboardSummary>))
+)]
+pub async fn get_dashboard_summary(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let started = Instant::now();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let cache_key = DashboardService::cache_key(ctx.workspace_id, ctx.user_id);
+    if let Some(mut summary) = get_json::<DashboardSummary>(state.cache.as_ref(), &cache_key).await
+    {
+        summary.cached = true;
+        let response = ApiResponse::success(summary, "Dashboard summary retrieved successfully");
+        return (StatusCode::OK, Json(response)).into_response();
+    }
+
+    let today = state.clock.today();
+    let summary = match state.dashboards.load(&ctx, today).await {
+        Ok(summary) => summary,
+        Err(err) => return err.into_response(),
+    };
+
+    if let Err(e) = set_json(
+        state.cache.as_ref(),
+        &cache_key,
+        &summary,
+        CACHE_TTL_SECONDS,
+    )
+    .await
+    {
+        tracing::debug!("Failed to cache dashboard summary: {}", e);
+    }
+    let elapsed = started.elapsed();
+    if elapsed > LATENCY_BUDGET {
+        tracing::warn!(
+            "Dashboard summary took {:?}, over its {:?} budget",
+            elapsed,
+            LATENCY_BUDGET
+        );
+    }
+
+    let response = ApiResponse::success(summary, "Dashboard summary retrieved successfully");
+    (StatusCode::OK, Json(response)).into_response()
+}
@@ -0,0 +1,399 @@
+use axum::{
+    Json,
+    http::header,
+    response::{Html, IntoResponse},
+};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Ref, ResponseBuilder};
+use utoipa::{Modify, OpenApi};
+
+use super::{
+    admin, analytics, api_keys, api_tokens, app_installations, attachments, auth, automations,
+    comments, content_reports, custom_emojis, cycles, dashboard, error_codes, graphql, health,
+    holidays, inbound, integrations, invitations, issue_forms, issue_label_rules, issues, labels,
+    milestones, notifications, oauth, project_costs, project_statuses, projects, search,
+    service_accounts, settings, sync, teams, uploads, users, webhooks, workflows,
+    workspace_imports, workspace_members, workspaces,
+};
+
+/// REST 接口的 OpenAPI 描述，由各处理函数上的 `#[utoipa::path]` 在编译期生成
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Momentum API",
+        description = "Team collaboration backend. Responses use the `ApiResponse` envelope; \
+                       failed requests carry a stable `error_code` (see `GET /error-codes`)."
+    ),
+    paths(
+        auth::register,
+        auth::login,
+        auth::oauth_authorize,
+        auth::oauth_callback,
+        oauth::token,
+        oauth::revoke,
+        inbound::receive_email,
+        integrations::receive_github_webhook,
+        integrations::receive_error_event,
+        issue_forms::get_portal_form,
+        issue_forms::submit_portal_form,
+        error_codes::list_error_codes,
+        health::healthz,
+        health::readyz,
+        health::metrics,
+        labels::get_labels,
+        labels::create_label,
+        labels::update_label,
+        labels::delete_label,
+        holidays::get_holidays,
+        holidays::create_holiday,
+        holidays::import_holidays,
+        holidays::update_holiday,
+        holidays::delete_holiday,
+        automations::get_automations,
+        automations::create_automation,
+        automations::get_automation,
+        automations::update_automation,
+        automations::delete_automation,
+        automations::get_automation_runs,
+        admin::run_integrity_check,
+        admin::get_maintenance,
+        admin::set_maintenance,
+        admin::get_jobs,
+        admin::get_telemetry,
+        admin::force_logout_user,
+        admin::list_organizations,
+        admin::create_organization,
+        admin::get_organization,
+        admin::update_organization,
+        admin::set_workspace_organization,
+        admin::reset_workspace,
+        admin::get_workspace_reset,
+        admin::moderate_comments,
+        admin::unhide_comments,
+        admin::list_comment_flags,
+        admin::dismiss_comment_flag,
+        admin::list_ws_dead_letters,
+        admin::replay_ws_dead_letter,
+        auth::get_profile,
+        auth::logout,
+        auth::change_password,
+        auth::switch_workspace,
+        api_tokens::create_api_token,
+        api_tokens::get_api_tokens,
+        api_tokens::revoke_api_token,
+        api_tokens::get_api_token_usage,
+        api_keys::get_api_keys,
+        api_keys::create_api_key,
+        api_keys::get_api_key,
+        api_keys::update_api_key,
+        api_keys::revoke_api_key,
+        service_accounts::get_service_accounts,
+        service_accounts::create_service_account,
+        service_accounts::disable_service_account,
+        service_accounts::get_service_account_tokens,
+        service_accounts::create_service_account_token,
+        service_accounts::revoke_service_account_token,
+        oauth::create_oauth_app,
+        oauth::get_oauth_apps,
+        oauth::delete_oauth_app,
+        oauth::get_oauth_app_usage,
+        oauth::update_app_webhook,
+        oauth::get_webhook_signing_keys,
+        oauth::rotate_webhook_signing_key,
+        oauth::verify_webhook_signature,
+        oauth::get_webhook_deliveries,
+        oauth::test_webhook,
+        oauth::redeliver_webhook,
+        integrations::get_github_integration,
+        integrations::update_github_integration,
+        integrations::delete_github_integration,
+        integrations::get_error_tracking_integration,
+        integrations::update_error_tracking_integration,
+        integrations::delete_error_tracking_integration,
+        webhooks::get_webhooks,
+        webhooks::create_webhook,
+        webhooks::get_webhook_by_id,
+        webhooks::update_webhook,
+        webhooks::delete_webhook,
+        webhooks::get_webhook_deliveries,
+        webhooks::test_webhook,
+        webhooks::redeliver_webhook,
+        oauth::get_authorize,
+        oauth::post_authorize,
+        oauth::get_authorizations,
+        oauth::revoke_authorization,
+        workspaces::create_workspace,
+        workspaces::get_current_workspace,
+        workspaces::update_workspace,
+        workspaces::delete_workspace,
+        workspaces::get_api_access,
+        workspaces::update_api_access,
+        workspaces::get_workspace_settings,
+        workspaces::update_workspace_settings,
+        workspaces::get_email_domain,
+        workspaces::set_email_domain,
+        workspaces::delete_email_domain,
+        workspaces::verify_email_domain,
+        app_installations::get_installed_apps,
+        app_installations::install_app,
+        app_installations::update_installed_app,
+        app_installations::uninstall_app,
+        workspace_imports::import_workspace_data,
+        workspace_imports::get_workspace_import,
+        content_reports::get_report_queue,
+        content_reports::resolve_report,
+        workspace_members::get_current_workspace_members,
+        workspace_members::get_member_directory,
+        workspace_members::get_member_directory_changes,
+        workspace_members::update_member_role,
+        workspace_members::remove_member,
+        project_costs::get_member_rates,
+        project_costs::set_member_rate,
+        project_costs::delete_member_rate,
+        workspace_members::import_members,
+        workspace_members::get_member_import,
+        workspace_members::get_workspace_members_and_invitations,
+        workspace_members::get_workspace_members,
+        invitations::invite_member,
+        invitations::bulk_invite_members,
+        invitations::get_user_invitations,
+        invitations::get_invitation_by_id,
+        invitations::accept_invitation,
+        invitations::decline_invitation,
+        invitations::revoke_invitation,
+        issues::create_issue,
+        issues::get_issues,
+        issues::get_issue_by_key,
+        issues::bulk_archive_issues,
+        issues::get_issue_archive_batch,
+        issues::undo_issue_archive_batch,
+        issues::get_issue,
+        issues::update_issue,
+        issues::patch_issue,
+        issues::delete_issue,
+        issues::get_description_code_block,
+        content_reports::report_issue,
+        issues::get_issue_viewers,
+        issues::move_issue,
+        issues::get_issue_moves,
+        issues::split_issue,
+        issues::get_issue_links,
+        issues::create_issue_relation,
+        issues::delete_issue_relation,
+        issues::create_cross_workspace_relation,
+        issues::delete_cross_workspace_relation,
+        issues::get_issue_backlinks,
+        issues::get_external_references,
+        issues::create_external_reference,
+        issues::delete_external_reference,
+        issues::find_by_external_reference,
+        search::search_issues,
+        sync::get_snapshot,
+        sync::get_changes,
+        graphql::graphql,
+        analytics::get_session_analytics,
+        settings::get_settings_history,
+        dashboard::get_dashboard_summary,
+        notifications::get_notifications,
+        notifications::mark_all_notifications_read,
+        notifications::mark_notification_read,
+        notifications::get_push_subscriptions,
+        notifications::register_push_subscription,
+        notifications::unregister_push_subscription,
+        notifications::get_push_preferences,
+        notifications::update_push_preferences,
+        attachments::get_attachments,
+        attachments::create_attachment,
+        attachments::delete_attachment,
+        attachments::complete_attachment,
+        uploads::create_upload_session,
+        uploads::get_upload_session,
+        uploads::append_upload_chunk,
+        uploads::cancel_upload_session,
+        comments::get_comments,
+        comments::create_comment,
+        comments::get_comment_page,
+        custom_emojis::get_emojis,
+        custom_emojis::create_emoji,
+        custom_emojis::get_emoji_stats,
+        custom_emojis::rename_emoji,
+        custom_emojis::delete_emoji,
+        custom_emojis::complete_emoji,
+        comments::get_comment,
+        comments::update_comment,
+        comments::delete_comment,
+        comments::get_comment_code_block,
+        comments::add_reaction,
+        content_reports::report_comment,
+        comments::remove_reaction,
+        users::update_profile,
+        users::get_preferences,
+        users::update_preferences,
+        projects::get_projects,
+        projects::create_project,
+        projects::get_project_board,
+        projects::update_project,
+        projects::delete_project,
+        project_costs::set_project_budget,
+        project_costs::get_project_costs,
+        project_costs::get_cost_entries,
+        project_costs::create_cost_entry,
+        project_costs::delete_cost_entry,
+        milestones::get_milestones,
+        milestones::create_milestone,
+        milestones::get_milestone_by_id,
+        milestones::update_milestone,
+        milestones::delete_milestone,
+        milestones::get_milestone_stats,
+        milestones::get_milestone_issues,
+        milestones::assign_issues_to_milestone,
+        milestones::remove_issues_from_milestone,
+        cycles::create_cycle,
+        cycles::get_cycles,
+        cycles::get_cycle_by_id,
+        cycles::update_cycle,
+        cycles::delete_cycle,
+        cycles::get_cycle_stats,
+        cycles::get_cycle_burndown,
+        cycles::get_cycle_issues,
+        cycles::assign_issues_to_cycle,
+        cycles::remove_issues_from_cycle,
+        cycles::update_cycle_status_auto,
+        cycles::get_cycle_settings,
+        cycles::put_cycle_settings,
+        cycles::delete_cycle_settings,
+        cycles::get_next_cycle_preview,
+        project_statuses::create_project_status,
+        project_statuses::get_project_statuses,
+        project_statuses::get_project_status_by_id,
+        project_statuses::update_project_status,
+        project_statuses::delete_project_status,
+        workflows::get_workflows,
+        workflows::create_workflow,
+        workflows::get_team_default_workflow_states,
+        workflows::create_team_default_workflow_state,
+        workflows::update_team_default_workflow_state,
+        issue_forms::get_team_forms,
+        issue_forms::create_form,
+        issue_forms::get_form,
+        issue_forms::update_form,
+        issue_forms::delete_form,
+        issue_label_rules::get_team_label_rules,
+        issue_label_rules::create_label_rule,
+        issue_label_rules::get_label_rule,
+        issue_label_rules::update_label_rule,
+        issue_label_rules::delete_label_rule,
+        issue_label_rules::get_issue_label_rule_applications,
+        issue_forms::submit_form,
+        workflows::get_workflow_by_id,
+        workflows::update_workflow,
+        workflows::delete_workflow,
+        workflows::get_workflow_states,
+        workflows::create_workflow_state,
+        workflows::get_issue_transitions,
+        teams::create_team,
+        teams::get_teams,
+        teams::get_team,
+        teams::update_team,
+        teams::delete_team,
+        teams::add_team_member,
+        teams::get_team_members_list,
+        teams::update_team_member,
+        teams::remove_team_member,
+        teams::get_auto_close_policy,
+        teams::put_auto_close_policy,
+        teams::delete_auto_close_policy,
+        teams::set_parent_team,
+        teams::get_team_hierarchy,
+        teams::get_effective_team_members,
+        teams::get_team_board,
+        teams::get_team_triage,
+        teams::get_user_teams,
+    ),
+    modifiers(&BearerAuth, &ErrorResponses),
+    security(("bearer_auth" = []))
+)]
+pub struct ApiDoc;
+
+/// JWT、个人访问令牌、OAuth 访问令牌和 API key 都通过 `Authorization: Bearer` 传递
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// 所有接口的错误响应形状相同，统一作为 `default` 响应写入，而不是在每个处理函数上重复
+struct ErrorResponses;
+
+impl Modify for ErrorResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let error = ResponseBuilder::new()
+            .description("Error with a stable `error_code`, `trace_id` and retry hints")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ApiResponse_EmptyData")))
+                    .build(),
+            )
+            .build();
+        for item in openapi.paths.paths.values_mut() {
+            for operation in [
+                &mut item.get,
+                &mut item.post,
+                &mut item.put,
+                &mut item.patch,
+                &mut item.delete,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                operation
+                    .responses
+                    .responses
+                    .entry("default".to_string())
+                    .or_insert_with(|| error.clone().into());
+            }
+        }
+    }
+}
+
+// OpenAPI 文档（JSON），供客户端生成 SDK
+pub async fn openapi_json() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI 页面；脚本和样式来自 CDN，页面自带放宽的 CSP
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Momentum API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+  </script>
+</body>
+</html>
+"##;
+
+const SWAGGER_UI_CSP: &str = "default-src 'self'; script-src 'self' 'unsafe-inline' https://unpkg.com; \
+     style-src 'self' 'unsafe-inline' https://unpkg.com; img-src 'self' data:; \
+     frame-ancestors 'none'; base-uri 'self'";
+
+// Swagger UI
+pub async fn swagger_ui() -> impl IntoResponse {
+    (
+        [(header::CONTENT_SECURITY_POLICY, SWAGGER_UI_CSP)],
+        Html(SWAGGER_UI_HTML),
+    )
+}
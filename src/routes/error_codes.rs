@@ -0,0 +1,41 @@
+use axum::{Json, http::StatusCode, response::IntoResponse};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::db::models::*;
+use crate::error::ErrorCode;
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorCodeInfo {
+    pub code: ErrorCode,
+    pub status: u16,
+    pub retryable: bool,
+    pub description: &'static str,
+}
+
+// 错误码目录：所有可能出现在错误响应 `error_code` 字段中的值
+#[utoipa::path(
+    get,
+    path = "/error-codes",
+    tag = "error-codes",
+    responses((status = 200, description = "Error codes retrieved successfully", body = ApiResponse<Vec<ErrorCodeInfo>>)),
+    security(())
+)]
+pub async fn list_error_codes() -> impl IntoResponse {
+    let catalog: Vec<ErrorCodeInfo> = ErrorCode::ALL
+        .iter()
+        .map(|&code| ErrorCodeInfo {
+            code,
+            status: code.status().as_u16(),
+            retryable: code.retryable(),
+            description: code.description(),
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            catalog,
+            "Error codes retrieved successfully",
+        )),
+    )
+}
@@ -0,0 +1,48 @@
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::graphql::GraphqlContext;
+use crate::middleware::asset_region::AssetRegionHint;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use std::sync::Arc;
+
+// GraphQL 查询（只读，作用于当前工作区）
+#[utoipa::path(
+    post,
+    path = "/graphql",
+    tag = "graphql",
+    request_body(content = serde_json::Value, description = "GraphQL request (`query`, `variables`, `operationName`)"),
+    responses((status = 200, description = "GraphQL response", body = serde_json::Value))
+)]
+pub async fn graphql(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Json(request): Json<async_graphql::Request>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let request = GraphqlContext {
+        executor: state.executor.clone(),
+        ctx,
+        asset_helper: state.asset_helper.for_region(region.as_deref()),
+    }
+    .attach(request);
+    Json(state.graphql.execute(request).await).into_response()
+}
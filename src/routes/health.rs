@@ -0,0 +1,226 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::IntoResponse,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+use crate::cache::{LockStats, redis_health_check};
+use crate::db::models::*;
+use crate::db::pool_health_check;
+use crate::metrics::{HttpMetrics, PrometheusText};
+use crate::supervisor::{TaskHealth, TaskState};
+use crate::websocket::WebSocketMonitor;
+
+/// How long a dependency check may take before it counts as down
+const DEPENDENCY_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, ToSchema)]
+pub struct DependencyHealth {
+    pub name: &'static str,
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    /// Database and Redis reachability
+    pub dependencies: Vec<DependencyHealth>,
+    pub tasks: Vec<TaskHealth>,
+    /// Distributed lock contention since startup
+    pub locks: Vec<LockStats>,
+}
+
+/// State of the `/metrics` route, which also reports WebSocket connections
+#[derive(Clone)]
+pub struct MetricsState {
+    pub app: Arc<AppState>,
+    pub ws_monitor: WebSocketMonitor,
+}
+
+async fn check_dependency<F>(name: &'static str, check: F) -> DependencyHealth
+where
+    F: std::future::Future<Output = Result<(), String>>,
+{
+    let error = match tokio::time::timeout(DEPENDENCY_CHECK_TIMEOUT, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e),
+        Err(_) => Some(format!(
+            "timed out after {}s",
+            DEPENDENCY_CHECK_TIMEOUT.as_secs()
+        )),
+    };
+    DependencyHealth {
+        name,
+        healthy: error.is_none(),
+        error,
+    }
+}
+
+// 存活检查：进程能处理请求即返回 200，不检查依赖
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "health",
+    responses((status = 200, description = "Alive", body = ApiResponse<EmptyData>)),
+    security(())
+)]
+pub async fn healthz() -> impl IntoResponse {
+    let response = ApiResponse::<()>::ok("Alive");
+    (StatusCode::OK, Json(response))
+}
+
+// 就绪检查：数据库、Redis 可用且后台任务全部正常运行时返回 200，否则 503
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    responses(
+        (status = 200, description = "Ready", body = ApiResponse<ReadinessResponse>),
+        (status = 503, description = "A dependency is down", body = ApiResponse<ReadinessResponse>),
+    ),
+    security(())
+)]
+pub async fn readyz(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    // r2d2 blocks while waiting for a connection, so check off the runtime
+    let pool = state.db.clone();
+    let database = check_dependency("database", async move {
+        tokio::task::spawn_blocking(move || futures::executor::block_on(pool_health_check(&pool)))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())
+    });
+    let redis = check_dependency("redis", async {
+        match redis_health_check(&state.redis).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("unexpected PING reply".to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    });
+    let (database, redis) = tokio::join!(database, redis);
+    let dependencies = vec![database, redis];
+
+    let readiness = ReadinessResponse {
+        ready: state.supervisor.is_ready() && dependencies.iter().all(|d| d.healthy),
+        dependencies,
+        tasks: state.supervisor.health(),
+        locks: state.locks.stats(),
+    };
+
+    if readiness.ready {
+        let response = ApiResponse::success(readiness, "Ready");
+        (StatusCode::OK, Json(response)).into_response()
+    } else {
+        let mut response = ApiResponse::error(503, "Service is not ready", vec![]);
+        response.data = Some(readiness);
+        (StatusCode::SERVICE_UNAVAILABLE, Json(response)).into_response()
+    }
+}
+
+// Prometheus 指标：HTTP 延迟直方图、WebSocket 连接数、数据库连接池和后台任务状态；
+// 配置了 METRICS_TOKEN 时需要携带对应的 Bearer token
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    responses((status = 200, description = "Prometheus text exposition", body = String, content_type = "text/plain")),
+    security(())
+)]
+pub async fn metrics(State(metrics): State<MetricsState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Some(expected) = metrics.app.config.metrics_token.as_deref() {
+        let provided = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        if provided != Some(expected) {
+            let response = ApiResponse::<()>::unauthorized("Invalid metrics token");
+            return (StatusCode::UNAUTHORIZED, Json(response)).into_response();
+        }
+    }
+
+    let mut out = PrometheusText::new();
+    HttpMetrics::global().render(&mut out);
+
+    let ws = metrics.ws_monitor.get_performance_metrics().await;
+    out.gauge(
+        "websocket_connections_active",
+        "Open WebSocket connections on this replica",
+        ws.active_connections as f64,
+    );
+    out.counter(
+        "websocket_connections_total",
+        "WebSocket connections accepted",
+        ws.total_connections as f64,
+    );
+    out.counter(
+        "websocket_messages_sent_total",
+        "WebSocket messages sent",
+        ws.total_messages_sent as f64,
+    );
+    out.counter(
+        "websocket_messages_received_total",
+        "WebSocket messages received",
+        ws.total_messages_received as f64,
+    );
+
+    let pool = metrics.app.db.state();
+    out.gauge(
+        "db_pool_connections",
+        "Database connections held by the pool",
+        pool.connections as f64,
+    );
+    out.gauge(
+        "db_pool_idle_connections",
+        "Idle database connections in the pool",
+        pool.idle_connections as f64,
+    );
+    out.gauge(
+        "db_pool_max_connections",
+        "Configured database pool size",
+        metrics.app.db.max_size() as f64,
+    );
+
+    let tasks = metrics.app.supervisor.health();
+    out.family(
+        "background_task_up",
+        "gauge",
+        "1 while a background task is running or finished normally",
+    );
+    for task in &tasks {
+        let up = matches!(task.state, TaskState::Running | TaskState::Finished);
+        out.sample(
+            "background_task_up",
+            &[("task", &task.name)],
+            if up { 1.0 } else { 0.0 },
+        );
+    }
+    out.family(
+        "background_task_restarts_total",
+        "counter",
+        "Restarts of a background task after panics",
+    );
+    for task in &tasks {
+        out.sample(
+            "background_task_restarts_total",
+            &[("task", &task.name)],
+            task.restarts as f64,
+        );
+    }
+
+    (
+        StatusCode::OK,
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4; charset=utf-8"),
+        )],
+        out.finish(),
+    )
+        .into_response()
+}
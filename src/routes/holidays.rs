@@ -0,0 +1,275 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::db::models::*;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::holidays_service::HolidaysService;
+use crate::services::permission_service::{Permission, PermissionService};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HolidayQuery {
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct CreateHolidayRequest {
+    pub name: String,
+    pub holiday_date: chrono::NaiveDate,
+    pub region: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct UpdateHolidayRequest {
+    pub name: Option<String>,
+    pub holiday_date: Option<chrono::NaiveDate>,
+    pub region: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct ImportHolidaysRequest {
+    /// Raw ICS calendar content (e.g. an exported regional holiday calendar)
+    pub ics: String,
+    pub region: Option<String>,
+}
+
+// 获取节假日列表
+// 节假日目前只影响周期和里程碑的工作日统计，SLA 计时和截止日期提醒尚未接入
+#[utoipa::path(
+    get,
+    path = "/holidays",
+    tag = "holidays",
+    params(HolidayQuery),
+    responses((status = 200, description = "Holidays retrieved successfully", body = ApiResponse<Vec<Holiday>>))
+)]
+pub async fn get_holidays(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(params): Query<HolidayQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| HolidaysService::list(conn, &ctx, params.from, params.to))
+        .await;
+    match result {
+        Ok(holidays) => {
+            let response = ApiResponse::success(holidays, "Holidays retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 创建节假日
+#[utoipa::path(
+    post,
+    path = "/holidays",
+    tag = "holidays",
+    request_body = CreateHolidayRequest,
+    responses((status = 201, description = "Holiday created successfully", body = ApiResponse<Holiday>))
+)]
+pub async fn create_holiday(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateHolidayRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageHolidays)?;
+            HolidaysService::create(conn, &ctx, &payload)
+        })
+        .await;
+    match result {
+        Ok(holiday) => {
+            let response = ApiResponse::created(holiday, "Holiday created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 更新节假日
+#[utoipa::path(
+    put,
+    path = "/holidays/{holiday_id}",
+    tag = "holidays",
+    params(("holiday_id" = Uuid, Path)),
+    request_body = UpdateHolidayRequest,
+    responses((status = 200, description = "Holiday updated successfully", body = ApiResponse<Holiday>))
+)]
+pub async fn update_holiday(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(holiday_id): Path<Uuid>,
+    Json(payload): Json<UpdateHolidayRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageHolidays)?;
+            HolidaysService::update(conn, &ctx, holiday_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(holiday) => {
+            let response = ApiResponse::success(holiday, "Holiday updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 删除节假日
+#[utoipa::path(
+    delete,
+    path = "/holidays/{holiday_id}",
+    tag = "holidays",
+    params(("holiday_id" = Uuid, Path)),
+    responses((status = 200, description = "Holiday deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_holiday(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(holiday_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageHolidays)?;
+            HolidaysService::delete(conn, &ctx, holiday_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Holiday deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 从 ICS 导入节假日
+#[utoipa::path(
+    post,
+    path = "/holidays/import",
+    tag = "holidays",
+    request_body = ImportHolidaysRequest,
+    responses((status = 201, description = "Holidays imported successfully", body = ApiResponse<HolidayImportResult>))
+)]
+pub async fn import_holidays(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<ImportHolidaysRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageHolidays)?;
+            HolidaysService::import_ics(conn, &ctx, &payload)
+        })
+        .await;
+    match result {
+        Ok(result) => {
+            let response = ApiResponse::created(result, "Holidays imported successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
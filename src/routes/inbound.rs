@@ -0,0 +1,57 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use std::sync::Arc;
+
+use crate::db::models::*;
+use crate::services::inbound_email_service::{InboundEmailRequest, InboundEmailService};
+
+/// 邮件服务商回调时携带的共享密钥头
+pub const INBOUND_SECRET_HEADER: &str = "x-inbound-secret";
+
+// 接收邮件回复并写入为 issue 评论
+#[utoipa::path(
+    post,
+    path = "/inbound/email",
+    tag = "inbound",
+    request_body = InboundEmailRequest,
+    responses((status = 201, description = "Reply added as comment", body = ApiResponse<Comment>)),
+    security(())
+)]
+pub async fn receive_email(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<InboundEmailRequest>,
+) -> impl IntoResponse {
+    let Some(expected) = state.config.inbound_email_secret.as_deref() else {
+        let response = ApiResponse::<()>::not_found("Inbound email is not configured");
+        return (StatusCode::NOT_FOUND, Json(response)).into_response();
+    };
+    let provided = headers
+        .get(INBOUND_SECRET_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !InboundEmailService::secret_matches(provided, expected) {
+        let response = ApiResponse::<()>::unauthorized("Invalid inbound secret");
+        return (StatusCode::UNAUTHORIZED, Json(response)).into_response();
+    }
+
+    let config = state.config.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            InboundEmailService::ingest_reply(conn, &config.jwt_secret, &payload)
+        })
+        .await;
+    match result {
+        Ok(comment) => {
+            let response = ApiResponse::created(comment, "Reply added as comment");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
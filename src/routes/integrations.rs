@@ -0,0 +1,387 @@
+use crate::AppState;
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use std::sync::Arc;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::error_tracking::{
+    ErrorEvent, ErrorEventQuery, ErrorIngestResult, ErrorTrackingConfig, UpdateErrorTrackingRequest,
+};
+use crate::db::models::github_integration::{
+    GithubIntegrationConfig, GithubWebhookQuery, GithubWebhookResult,
+    UpdateGithubIntegrationRequest,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::error_tracking_service::{
+    ErrorTrackingService, INGEST_KEY_HEADER, SENTRY_AUTH_HEADER,
+};
+use crate::services::github_integration_service::{
+    GITHUB_EVENT_HEADER, GITHUB_SIGNATURE_HEADER, GithubIntegrationService,
+};
+
+/// 获取当前工作区的 GitHub 集成配置
+#[utoipa::path(
+    get,
+    path = "/integrations/github",
+    tag = "integrations",
+    responses((status = 200, description = "GitHub integration retrieved successfully", body = ApiResponse<GithubIntegrationConfig>))
+)]
+pub async fn get_github_integration(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let config = state.config.clone();
+    let result = state
+        .executor
+        .read(move |conn| {
+            GithubIntegrationService::get(conn, &ctx, &config.oauth_redirect_base_url)
+        })
+        .await;
+    match result {
+        Ok(config) => {
+            let response =
+                ApiResponse::success(config, "GitHub integration retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建或更新 GitHub 集成，Webhook 密钥仅在创建或轮换时返回
+#[utoipa::path(
+    put,
+    path = "/integrations/github",
+    tag = "integrations",
+    request_body = UpdateGithubIntegrationRequest,
+    responses((status = 200, description = "GitHub integration saved successfully", body = ApiResponse<GithubIntegrationConfig>))
+)]
+pub async fn update_github_integration(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<UpdateGithubIntegrationRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let config = state.config.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            GithubIntegrationService::configure(
+                conn,
+                &ctx,
+                &config.oauth_redirect_base_url,
+                &payload,
+            )
+        })
+        .await;
+    match result {
+        Ok(config) => {
+            let response = ApiResponse::success(config, "GitHub integration saved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除 GitHub 集成，已记录的关联保留
+#[utoipa::path(
+    delete,
+    path = "/integrations/github",
+    tag = "integrations",
+    responses((status = 200, description = "GitHub integration deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_github_integration(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| GithubIntegrationService::remove(conn, &ctx))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("GitHub integration deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 接收 GitHub Webhook：校验签名，按提交信息、PR 标题和分支名中的问题编号关联问题
+#[utoipa::path(
+    post,
+    path = "/integrations/github/webhook",
+    tag = "integrations",
+    params(GithubWebhookQuery),
+    request_body(content = serde_json::Value, description = "GitHub event payload, verified against `X-Hub-Signature-256`"),
+    responses((status = 200, description = "GitHub webhook processed", body = ApiResponse<GithubWebhookResult>)),
+    security(())
+)]
+pub async fn receive_github_webhook(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<GithubWebhookQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let event = headers
+        .get(GITHUB_EVENT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let signature = headers
+        .get(GITHUB_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            GithubIntegrationService::handle_webhook(
+                conn,
+                query.workspace_id,
+                &event,
+                signature.as_deref(),
+                &body,
+            )
+        })
+        .await;
+    match result {
+        Ok(result) => {
+            let response = ApiResponse::success(result, "GitHub webhook processed");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取当前工作区的错误追踪集成配置
+#[utoipa::path(
+    get,
+    path = "/integrations/errors",
+    tag = "integrations",
+    responses((status = 200, description = "Error tracking integration retrieved successfully", body = ApiResponse<ErrorTrackingConfig>))
+)]
+pub async fn get_error_tracking_integration(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let config = state.config.clone();
+    let result = state
+        .executor
+        .read(move |conn| ErrorTrackingService::get(conn, &ctx, &config.oauth_redirect_base_url))
+        .await;
+    match result {
+        Ok(config) => {
+            let response =
+                ApiResponse::success(config, "Error tracking integration retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建或更新错误追踪集成，接入密钥仅在创建或轮换时返回
+#[utoipa::path(
+    put,
+    path = "/integrations/errors",
+    tag = "integrations",
+    request_body = UpdateErrorTrackingRequest,
+    responses((status = 200, description = "Error tracking integration saved successfully", body = ApiResponse<ErrorTrackingConfig>))
+)]
+pub async fn update_error_tracking_integration(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<UpdateErrorTrackingRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let config = state.config.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ErrorTrackingService::configure(conn, &ctx, &config.oauth_redirect_base_url, &payload)
+        })
+        .await;
+    match result {
+        Ok(config) => {
+            let response =
+                ApiResponse::success(config, "Error tracking integration saved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除错误追踪集成，已创建的问题和错误分组保留
+#[utoipa::path(
+    delete,
+    path = "/integrations/errors",
+    tag = "integrations",
+    responses((status = 200, description = "Error tracking integration deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_error_tracking_integration(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ErrorTrackingService::remove(conn, &ctx))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Error tracking integration deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 接收监控工具上报的错误事件：按指纹归组到同一个问题，累计出现次数，已解决的错误再次出现时重新打开问题
+#[utoipa::path(
+    post,
+    path = "/integrations/errors/events",
+    tag = "integrations",
+    params(ErrorEventQuery),
+    request_body = ErrorEvent,
+    responses((status = 201, description = "Error event recorded as a new issue", body = ApiResponse<ErrorIngestResult>)),
+    security(())
+)]
+pub async fn receive_error_event(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ErrorEventQuery>,
+    headers: HeaderMap,
+    Json(event): Json<ErrorEvent>,
+) -> impl IntoResponse {
+    let ingest_key = headers
+        .get(INGEST_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(SENTRY_AUTH_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(ErrorTrackingService::sentry_key)
+        })
+        .map(str::to_string);
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ErrorTrackingService::ingest(conn, query.workspace_id, ingest_key.as_deref(), &event)
+        })
+        .await;
+    match result {
+        Ok(result) if result.created => {
+            let response = ApiResponse::created(result, "Error event recorded as a new issue");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Ok(result) => {
+            let response = ApiResponse::success(result, "Error event recorded");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -7,21 +7,24 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::db::models::*;
+use crate::middleware::asset_region::AssetRegionHint;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::context::RequestContext;
 use crate::services::invitations_service::InvitationsService;
+use crate::services::permission_service::{Permission, PermissionService};
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct InviteMemberRequest {
     pub emails: Vec<String>,
     #[serde(default)]
     pub role: Option<WorkspaceMemberRole>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct InvitationInfo {
     pub id: uuid::Uuid,
     pub email: String,
@@ -38,7 +41,8 @@ pub struct InvitationInfo {
     pub updated_at: chrono::NaiveDateTime,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct InvitationQuery {
     pub status: Option<String>,
     pub email: Option<String>,
@@ -47,24 +51,24 @@ pub struct InvitationQuery {
 /// 邀请用户加入当前工作区
 ///
 /// 权限要求: 当前用户必须是工作区的Owner或Admin
+#[utoipa::path(
+    post,
+    path = "/invitations",
+    tag = "invitations",
+    request_body = InviteMemberRequest,
+    responses((status = 201, description = "Members invited successfully", body = ApiResponse<Vec<Invitation>>))
+)]
 pub async fn invite_member(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<InviteMemberRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -76,8 +80,32 @@ pub async fn invite_member(
         }
     };
 
-    match InvitationsService::invite_members(&mut conn, &ctx, &payload) {
+    let email_ctx = ctx.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::InviteMembers)?;
+            InvitationsService::invite_members(conn, &ctx, &payload)
+        })
+        .await;
+    match result {
         Ok(result) => {
+            let invitations = result.clone();
+            let app_url = state.email.app_url().to_string();
+            let emails = state
+                .executor
+                .read(move |conn| {
+                    InvitationsService::invitation_emails(conn, &email_ctx, &invitations, &app_url)
+                })
+                .await;
+            match emails {
+                Ok(emails) => {
+                    for email in emails {
+                        state.email.enqueue_quietly(email).await;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to render invitation emails: {}", e),
+            }
             let response = ApiResponse::created(result, "Members invited successfully");
             (StatusCode::CREATED, Json(response)).into_response()
         }
@@ -85,25 +113,96 @@ pub async fn invite_member(
     }
 }
 
-/// 获取当前用户的邀请列表
-pub async fn get_user_invitations(
+/// 批量邀请用户加入当前工作区，每个邮箱可指定不同角色
+///
+/// 每条邀请单独处理并返回各自的结果；已是成员或已有待处理邀请的邮箱会被跳过
+///
+/// 权限要求: 当前用户必须是工作区的Owner或Admin
+#[utoipa::path(
+    post,
+    path = "/invitations/bulk",
+    tag = "invitations",
+    request_body = BulkInviteRequest,
+    responses((status = 200, description = "Bulk invitation processed", body = ApiResponse<Vec<BulkInviteResult>>))
+)]
+pub async fn bulk_invite_members(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
-    Query(params): Query<InvitationQuery>,
+    Json(payload): Json<BulkInviteRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
+    let email_ctx = ctx.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::InviteMembers)?;
+            InvitationsService::bulk_invite(conn, &ctx, &payload)
+        })
+        .await;
+    match result {
+        Ok(results) => {
+            let invitations: Vec<Invitation> = results
+                .iter()
+                .filter_map(|result| result.invitation.clone())
+                .collect();
+            let app_url = state.email.app_url().to_string();
+            let emails = state
+                .executor
+                .read(move |conn| {
+                    InvitationsService::invitation_emails(conn, &email_ctx, &invitations, &app_url)
+                })
+                .await;
+            match emails {
+                Ok(emails) => {
+                    for email in emails {
+                        state.email.enqueue_quietly(email).await;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to render invitation emails: {}", e),
+            }
+            let response = ApiResponse::success(results, "Bulk invitation processed");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取当前用户的邀请列表
+#[utoipa::path(
+    get,
+    path = "/invitations",
+    tag = "invitations",
+    params(InvitationQuery),
+    responses((status = 200, description = "User invitations retrieved successfully", body = ApiResponse<Vec<InvitationInfo>>))
+)]
+pub async fn get_user_invitations(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Query(params): Query<InvitationQuery>,
+) -> impl IntoResponse {
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -123,13 +222,20 @@ pub async fn get_user_invitations(
         _ => None,
     });
 
-    match InvitationsService::get_user_invitations(
-        &mut conn,
-        &ctx,
-        &state.asset_helper,
-        status_enum,
-        params.email,
-    ) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| {
+            InvitationsService::get_user_invitations(
+                conn,
+                &ctx,
+                &asset_helper,
+                status_enum,
+                params.email,
+            )
+        })
+        .await;
+    match result {
         Ok(invitations) => {
             let response =
                 ApiResponse::success(invitations, "User invitations retrieved successfully");
@@ -140,24 +246,25 @@ pub async fn get_user_invitations(
 }
 
 /// 获取特定邀请的详细信息
+#[utoipa::path(
+    get,
+    path = "/invitations/{invitation_id}",
+    tag = "invitations",
+    params(("invitation_id" = Uuid, Path)),
+    responses((status = 200, description = "Invitation retrieved successfully", body = ApiResponse<InvitationInfo>))
+)]
 pub async fn get_invitation_by_id(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Path(invitation_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -169,7 +276,12 @@ pub async fn get_invitation_by_id(
         }
     };
 
-    match InvitationsService::get_by_id(&mut conn, &ctx, &state.asset_helper, invitation_id) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| InvitationsService::get_by_id(conn, &ctx, &asset_helper, invitation_id))
+        .await;
+    match result {
         Ok(invitation) => {
             let response = ApiResponse::success(invitation, "Invitation retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -179,24 +291,24 @@ pub async fn get_invitation_by_id(
 }
 
 /// 接受邀请
+#[utoipa::path(
+    post,
+    path = "/invitations/{invitation_id}/accept",
+    tag = "invitations",
+    params(("invitation_id" = Uuid, Path)),
+    responses((status = 200, description = "Invitation accepted successfully", body = ApiResponse<Invitation>))
+)]
 pub async fn accept_invitation(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(invitation_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -208,7 +320,11 @@ pub async fn accept_invitation(
         }
     };
 
-    match InvitationsService::accept(&mut conn, &ctx, invitation_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| InvitationsService::accept(conn, &ctx, invitation_id))
+        .await;
+    match result {
         Ok(invitation) => {
             let response = ApiResponse::success(invitation, "Invitation accepted successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -218,24 +334,24 @@ pub async fn accept_invitation(
 }
 
 /// 拒绝邀请
+#[utoipa::path(
+    post,
+    path = "/invitations/{invitation_id}/decline",
+    tag = "invitations",
+    params(("invitation_id" = Uuid, Path)),
+    responses((status = 200, description = "Invitation declined successfully", body = ApiResponse<Invitation>))
+)]
 pub async fn decline_invitation(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(invitation_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -247,7 +363,11 @@ pub async fn decline_invitation(
         }
     };
 
-    match InvitationsService::decline(&mut conn, &ctx, invitation_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| InvitationsService::decline(conn, &ctx, invitation_id))
+        .await;
+    match result {
         Ok(invitation) => {
             let response = ApiResponse::success(invitation, "Invitation declined successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -259,24 +379,24 @@ pub async fn decline_invitation(
 /// 撤销邀请（由邀请人操作）
 ///
 /// 权限要求: 当前用户必须是邀请人或工作区的Owner/Admin
+#[utoipa::path(
+    post,
+    path = "/invitations/{invitation_id}/revoke",
+    tag = "invitations",
+    params(("invitation_id" = Uuid, Path)),
+    responses((status = 200, description = "Invitation revoked successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn revoke_invitation(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(invitation_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -288,7 +408,14 @@ pub async fn revoke_invitation(
         }
     };
 
-    match InvitationsService::revoke(&mut conn, &ctx, invitation_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::InviteMembers)?;
+            InvitationsService::revoke(conn, &ctx, invitation_id)
+        })
+        .await;
+    match result {
         Ok(invitation) => {
             let response = ApiResponse::success(invitation, "Invitation revoked successfully");
             (StatusCode::OK, Json(response)).into_response()
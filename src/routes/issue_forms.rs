@@ -0,0 +1,341 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::issue::Issue;
+use crate::db::models::issue_form::{
+    CreateIssueFormRequest, IssueFormResponse, PortalSubmissionReceipt, PublicIssueFormResponse,
+    SubmitIssueFormRequest, UpdateIssueFormRequest,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::issue_forms_service::IssueFormsService;
+
+/// 获取团队的问题收集表单列表
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/forms",
+    tag = "issue-forms",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue forms retrieved successfully", body = ApiResponse<Vec<IssueFormResponse>>))
+)]
+pub async fn get_team_forms(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssueFormsService::list(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(forms) => {
+            let response = ApiResponse::success(forms, "Issue forms retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 为团队创建问题收集表单，字段可映射到问题的标题、描述、优先级和标签
+#[utoipa::path(
+    post,
+    path = "/teams/{team_id}/forms",
+    tag = "issue-forms",
+    params(("team_id" = Uuid, Path)),
+    request_body = CreateIssueFormRequest,
+    responses((status = 201, description = "Issue form created successfully", body = ApiResponse<IssueFormResponse>))
+)]
+pub async fn create_form(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<CreateIssueFormRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueFormsService::create(conn, &ctx, team_id, &payload))
+        .await;
+    match result {
+        Ok(form) => {
+            let response = ApiResponse::created(form, "Issue form created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取问题收集表单详情
+#[utoipa::path(
+    get,
+    path = "/forms/{form_id}",
+    tag = "issue-forms",
+    params(("form_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue form retrieved successfully", body = ApiResponse<IssueFormResponse>))
+)]
+pub async fn get_form(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(form_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssueFormsService::get(conn, &ctx, form_id))
+        .await;
+    match result {
+        Ok(form) => {
+            let response = ApiResponse::success(form, "Issue form retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 更新问题收集表单，首次公开时生成门户链接
+#[utoipa::path(
+    put,
+    path = "/forms/{form_id}",
+    tag = "issue-forms",
+    params(("form_id" = Uuid, Path)),
+    request_body = UpdateIssueFormRequest,
+    responses((status = 200, description = "Issue form updated successfully", body = ApiResponse<IssueFormResponse>))
+)]
+pub async fn update_form(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(form_id): Path<Uuid>,
+    Json(payload): Json<UpdateIssueFormRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueFormsService::update(conn, &ctx, form_id, &payload))
+        .await;
+    match result {
+        Ok(form) => {
+            let response = ApiResponse::success(form, "Issue form updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除问题收集表单，已提交的问题保留
+#[utoipa::path(
+    delete,
+    path = "/forms/{form_id}",
+    tag = "issue-forms",
+    params(("form_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue form deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_form(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(form_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueFormsService::delete(conn, &ctx, form_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Issue form deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 通过表单创建问题，提交内容在服务端校验
+#[utoipa::path(
+    post,
+    path = "/forms/{form_id}/submissions",
+    tag = "issue-forms",
+    params(("form_id" = Uuid, Path)),
+    request_body = SubmitIssueFormRequest,
+    responses((status = 201, description = "Issue created from form", body = ApiResponse<Issue>))
+)]
+pub async fn submit_form(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(form_id): Path<Uuid>,
+    Json(payload): Json<SubmitIssueFormRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueFormsService::submit(conn, &ctx, form_id, &payload))
+        .await;
+    match result {
+        Ok(issue) => {
+            let response = ApiResponse::created(issue, "Issue created from form");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取公开表单（门户，无需登录）
+#[utoipa::path(
+    get,
+    path = "/portal/forms/{slug}",
+    tag = "issue-forms",
+    params(("slug" = String, Path)),
+    responses((status = 200, description = "Issue form retrieved successfully", body = ApiResponse<PublicIssueFormResponse>)),
+    security(())
+)]
+pub async fn get_portal_form(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| IssueFormsService::get_public(conn, &slug))
+        .await;
+    match result {
+        Ok(form) => {
+            let response = ApiResponse::success(form, "Issue form retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 通过门户提交公开表单（无需登录），需填写联系邮箱
+#[utoipa::path(
+    post,
+    path = "/portal/forms/{slug}/submissions",
+    tag = "issue-forms",
+    params(("slug" = String, Path)),
+    request_body = SubmitIssueFormRequest,
+    responses((status = 201, description = "Submission received", body = ApiResponse<PortalSubmissionReceipt>)),
+    security(())
+)]
+pub async fn submit_portal_form(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Json(payload): Json<SubmitIssueFormRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| IssueFormsService::submit_public(conn, &slug, &payload))
+        .await;
+    match result {
+        Ok(submission) => {
+            let receipt = PortalSubmissionReceipt {
+                submission_id: submission.id,
+                created_at: submission.created_at,
+            };
+            let response = ApiResponse::created(receipt, "Submission received");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
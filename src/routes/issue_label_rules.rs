@@ -0,0 +1,283 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::issue_label_rule::{
+    CreateIssueLabelRuleRequest, IssueLabelRule, IssueLabelRuleApplication,
+    UpdateIssueLabelRuleRequest,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::issue_label_rules_service::IssueLabelRulesService;
+
+/// 获取团队的自动标签规则列表，按执行顺序排列
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/label-rules",
+    tag = "issue-label-rules",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Label rules retrieved successfully", body = ApiResponse<Vec<IssueLabelRule>>))
+)]
+pub async fn get_team_label_rules(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssueLabelRulesService::list(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(rules) => {
+            let response = ApiResponse::success(rules, "Label rules retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 为团队创建自动标签规则，新建问题的标题或描述匹配时添加标签或设置优先级
+#[utoipa::path(
+    post,
+    path = "/teams/{team_id}/label-rules",
+    tag = "issue-label-rules",
+    params(("team_id" = Uuid, Path)),
+    request_body = CreateIssueLabelRuleRequest,
+    responses((status = 201, description = "Label rule created successfully", body = ApiResponse<IssueLabelRule>))
+)]
+pub async fn create_label_rule(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<CreateIssueLabelRuleRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueLabelRulesService::create(conn, &ctx, team_id, &payload))
+        .await;
+    match result {
+        Ok(rule) => {
+            let response = ApiResponse::created(rule, "Label rule created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取自动标签规则详情
+#[utoipa::path(
+    get,
+    path = "/label-rules/{rule_id}",
+    tag = "issue-label-rules",
+    params(("rule_id" = Uuid, Path)),
+    responses((status = 200, description = "Label rule retrieved successfully", body = ApiResponse<IssueLabelRule>))
+)]
+pub async fn get_label_rule(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(rule_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssueLabelRulesService::get(conn, &ctx, rule_id))
+        .await;
+    match result {
+        Ok(rule) => {
+            let response = ApiResponse::success(rule, "Label rule retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 更新自动标签规则
+#[utoipa::path(
+    put,
+    path = "/label-rules/{rule_id}",
+    tag = "issue-label-rules",
+    params(("rule_id" = Uuid, Path)),
+    request_body = UpdateIssueLabelRuleRequest,
+    responses((status = 200, description = "Label rule updated successfully", body = ApiResponse<IssueLabelRule>))
+)]
+pub async fn update_label_rule(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(rule_id): Path<Uuid>,
+    Json(payload): Json<UpdateIssueLabelRuleRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueLabelRulesService::update(conn, &ctx, rule_id, &payload))
+        .await;
+    match result {
+        Ok(rule) => {
+            let response = ApiResponse::success(rule, "Label rule updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除自动标签规则，已应用到问题上的标签和记录保留
+#[utoipa::path(
+    delete,
+    path = "/label-rules/{rule_id}",
+    tag = "issue-label-rules",
+    params(("rule_id" = Uuid, Path)),
+    responses((status = 200, description = "Label rule deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_label_rule(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(rule_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueLabelRulesService::delete(conn, &ctx, rule_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Label rule deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取问题创建时应用的自动标签规则记录
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/label-rule-applications",
+    tag = "issue-label-rules",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Label rule applications retrieved successfully", body = ApiResponse<Vec<IssueLabelRuleApplication>>))
+)]
+pub async fn get_issue_label_rule_applications(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(issue_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssueLabelRulesService::applications(conn, &ctx, issue_id))
+        .await;
+    match result {
+        Ok(applications) => {
+            let response = ApiResponse::success(
+                applications,
+                "Label rule applications retrieved successfully",
+            );
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -1,29 +1,59 @@
 use crate::AppState;
 use crate::db::enums::IssuePriority;
-use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::external_reference::{
+    CreateExternalReferenceRequest, ExternalReference, ExternalReferenceMatch,
+    ExternalReferenceQuery,
+};
+use crate::db::models::issue::{Issue, IssueResponse};
+use crate::db::models::issue_archive::{BulkArchiveRequest, IssueArchiveBatchResponse};
+use crate::db::models::issue_link::IssueLink;
+use crate::db::models::issue_move::{IssueMove, IssueMoveResult, MoveIssueRequest};
+use crate::db::models::issue_relation::{
+    CreateCrossWorkspaceRelationRequest, CreateIssueRelationRequest,
+    CrossWorkspaceRelationResponse, IssueRelationResponse,
+};
+use crate::db::models::issue_split::{IssueSplitResult, SplitIssueRequest};
+use crate::db::models::issue_view::IssueViewer;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::context::RequestContext;
+use crate::services::cross_workspace_relations_service::CrossWorkspaceRelationsService;
+use crate::services::external_references_service::ExternalReferencesService;
+use crate::services::field_selection::FieldSelection;
+use crate::services::github_integration_service::GithubIntegrationService;
+use crate::services::issue_archive_service::IssueArchiveService;
+use crate::services::issue_moves_service::IssueMovesService;
+use crate::services::issue_patch_service::IssuePatchService;
+use crate::services::issue_relations_service::IssueRelationsService;
+use crate::services::issue_split_service::IssueSplitService;
 use crate::services::issues_service::{IssueFilters, IssuesService};
+use crate::services::permission_service::{Permission, PermissionService};
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
 };
 use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct IssueQueryParams {
     pub team_id: Option<Uuid>,
     pub project_id: Option<Uuid>,
     pub assignee_id: Option<Uuid>,
     pub priority: Option<String>,
     pub search: Option<String>,
+    /// 逗号分隔的字段列表，只返回这些字段（`id` 总会返回）
+    pub fields: Option<String>,
+    /// 为 true 时同时返回已归档的 Issue（默认不返回）
+    pub include_archived: Option<bool>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateIssueRequest {
     pub title: String,
     pub description: Option<String>,
@@ -37,9 +67,12 @@ pub struct CreateIssueRequest {
     pub label_ids: Option<Vec<Uuid>>,
     pub cycle_id: Option<Uuid>,
     pub parent_issue_id: Option<Uuid>,
+    /// 估算点数，须为团队估算刻度中的值
+    #[serde(default)]
+    pub estimate: Option<i32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateIssueRequest {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -52,27 +85,30 @@ pub struct UpdateIssueRequest {
     pub workflow_state_id: Option<Uuid>,
     pub cycle_id: Option<Uuid>,
     pub label_ids: Option<Vec<Uuid>>,
+    /// 估算点数；传 null 清除估算
+    #[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]
+    pub estimate: Option<Option<i32>>,
 }
 
 // 获取问题列表
+#[utoipa::path(
+    get,
+    path = "/issues",
+    tag = "issues",
+    params(IssueQueryParams),
+    responses((status = 200, description = "Issues retrieved successfully", body = ApiResponse<serde_json::Value>))
+)]
 pub async fn get_issues(
     State(state): State<Arc<AppState>>,
     Query(params): Query<IssueQueryParams>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -84,6 +120,11 @@ pub async fn get_issues(
         }
     };
 
+    let fields = match FieldSelection::from_query(params.fields.as_deref(), IssueResponse::FIELDS) {
+        Ok(fields) => fields,
+        Err(err) => return err.into_response(),
+    };
+
     // Parse priority if provided
     let priority = if let Some(priority_str) = params.priority {
         match priority_str.as_str() {
@@ -111,9 +152,16 @@ pub async fn get_issues(
         assignee_id: params.assignee_id,
         priority,
         search: params.search,
+        include_archived: params.include_archived.unwrap_or(false),
     };
 
-    match IssuesService::list(&mut conn, &ctx, &filters) {
+    let result = state
+        .executor
+        .read(move |conn| {
+            IssuesService::list(conn, &ctx, &filters).and_then(|issues| fields.project(&issues))
+        })
+        .await;
+    match result {
         Ok(issues) => {
             let response = ApiResponse::success(issues, "Issues retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -123,24 +171,24 @@ pub async fn get_issues(
 }
 
 // 创建问题
+#[utoipa::path(
+    post,
+    path = "/issues",
+    tag = "issues",
+    request_body = CreateIssueRequest,
+    responses((status = 201, description = "Issue created successfully", body = ApiResponse<IssueResponse>))
+)]
 pub async fn create_issue(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<CreateIssueRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -152,7 +200,15 @@ pub async fn create_issue(
         }
     };
 
-    match IssuesService::create(&mut conn, &ctx, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::CreateIssue)?;
+            let issue = IssuesService::create(conn, &ctx, &payload)?;
+            IssuesService::get_by_id(conn, &ctx, issue.id)
+        })
+        .await;
+    match result {
         Ok(issue) => {
             let response = ApiResponse::created(issue, "Issue created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -162,25 +218,76 @@ pub async fn create_issue(
 }
 
 // 更新问题
+#[utoipa::path(
+    put,
+    path = "/issues/{issue_id}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    request_body = UpdateIssueRequest,
+    responses((status = 200, description = "Issue updated successfully", body = ApiResponse<Issue>))
+)]
 pub async fn update_issue(
     State(state): State<Arc<AppState>>,
     Path(issue_id): Path<Uuid>,
     auth_info: AuthUserInfo,
     Json(payload): Json<UpdateIssueRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            IssuesService::update(conn, &ctx, issue_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(issue) => {
+            let response = ApiResponse::success(issue, "Issue updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 以 JSON Patch（application/json-patch+json，操作数组）或 JSON Merge Patch
+// （application/merge-patch+json，对象）部分更新问题；只允许修改可编辑字段，label_ids 可按下标增删单个标签
+#[utoipa::path(
+    patch,
+    path = "/issues/{issue_id}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    request_body = serde_json::Value,
+    responses((status = 200, description = "Issue updated successfully", body = ApiResponse<IssueResponse>))
+)]
+pub async fn patch_issue(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    headers: HeaderMap,
+    Json(payload): Json<serde_json::Value>,
+) -> impl IntoResponse {
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -192,7 +299,22 @@ pub async fn update_issue(
         }
     };
 
-    match IssuesService::update(&mut conn, &ctx, issue_id, &payload) {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let document = match IssuePatchService::parse_document(content_type, payload) {
+        Ok(document) => document,
+        Err(err) => return err.into_response(),
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            IssuePatchService::apply(conn, &ctx, issue_id, &document)
+        })
+        .await;
+    match result {
         Ok(issue) => {
             let response = ApiResponse::success(issue, "Issue updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -202,24 +324,71 @@ pub async fn update_issue(
 }
 
 // 删除问题
+#[utoipa::path(
+    delete,
+    path = "/issues/{issue_id}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue deleted successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn delete_issue(
     State(state): State<Arc<AppState>>,
     Path(issue_id): Path<Uuid>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::DeleteIssue)?;
+            IssuesService::delete(conn, &ctx, issue_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Issue deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 按筛选条件批量归档问题
+// dry_run 默认为 true，只返回受影响的数量和样例；为 false 时创建归档批次并交给后台任务执行
+#[utoipa::path(
+    post,
+    path = "/issues/bulk-archive",
+    tag = "issues",
+    request_body = BulkArchiveRequest,
+    responses((status = 200, description = "Issues archived; with `dry_run` the body is a `BulkArchivePreview` instead", body = ApiResponse<IssueArchiveBatchResponse>))
+)]
+pub async fn bulk_archive_issues(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<BulkArchiveRequest>,
+) -> impl IntoResponse {
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -231,9 +400,117 @@ pub async fn delete_issue(
         }
     };
 
-    match IssuesService::delete(&mut conn, &ctx, issue_id) {
-        Ok(()) => {
-            let response = ApiResponse::<()>::ok("Issue deleted successfully");
+    if payload.dry_run {
+        let result = state
+            .executor
+            .transaction(move |conn| IssueArchiveService::preview(conn, &ctx, &payload.filter))
+            .await;
+        return match result {
+            Ok(preview) => {
+                let response = ApiResponse::success(preview, "Bulk archive preview");
+                (StatusCode::OK, Json(response)).into_response()
+            }
+            Err(err) => err.into_response(),
+        };
+    }
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueArchiveService::request(conn, &ctx, &payload.filter))
+        .await;
+    match result {
+        Ok(batch) => {
+            if let Err(e) = IssueArchiveService::enqueue(&state.redis, batch.id).await {
+                // The worker also sweeps stale pending batches
+                tracing::warn!("Failed to queue issue archive {}: {}", batch.id, e);
+            }
+            let response = ApiResponse::success(batch, "Bulk archive queued");
+            (StatusCode::ACCEPTED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 查询批量归档进度
+#[utoipa::path(
+    get,
+    path = "/issues/bulk-archive/{batch_id}",
+    tag = "issues",
+    params(("batch_id" = Uuid, Path)),
+    responses((status = 200, description = "Bulk archive retrieved successfully", body = ApiResponse<IssueArchiveBatchResponse>))
+)]
+pub async fn get_issue_archive_batch(
+    State(state): State<Arc<AppState>>,
+    Path(batch_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssueArchiveService::get(conn, &ctx, batch_id))
+        .await;
+    match result {
+        Ok(batch) => {
+            let response = ApiResponse::success(batch, "Bulk archive retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 撤销批量归档（完成后的撤销窗口内有效）
+#[utoipa::path(
+    post,
+    path = "/issues/bulk-archive/{batch_id}/undo",
+    tag = "issues",
+    params(("batch_id" = Uuid, Path)),
+    responses((status = 200, description = "Bulk archive undone", body = ApiResponse<IssueArchiveBatchResponse>))
+)]
+pub async fn undo_issue_archive_batch(
+    State(state): State<Arc<AppState>>,
+    Path(batch_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| IssueArchiveService::undo(conn, &ctx, batch_id))
+        .await;
+    match result {
+        Ok(batch) => {
+            let response = ApiResponse::success(batch, "Bulk archive undone");
             (StatusCode::OK, Json(response)).into_response()
         }
         Err(err) => err.into_response(),
@@ -241,24 +518,73 @@ pub async fn delete_issue(
 }
 
 // 获取单个问题
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue retrieved successfully", body = ApiResponse<IssueResponse>))
+)]
 pub async fn get_issue(
     State(state): State<Arc<AppState>>,
     Path(issue_id): Path<Uuid>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
+    let result = state
+        .executor
+        .read(move |conn| {
+            let issue = IssuesService::get_by_id(conn, &ctx, issue_id)?;
+            if let Err(e) = IssuesService::mark_viewed(conn, &ctx, issue_id) {
+                tracing::warn!("Failed to record issue view: {}", e);
+            }
+            Ok(issue)
+        })
+        .await;
+    match result {
+        Ok(issue) => {
+            let response = ApiResponse::success(issue, "Issue retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 按问题标识（如 ENG-123）获取问题，团队标识不区分大小写；问题移动到其他团队前的标识仍然有效
+#[utoipa::path(
+    get,
+    path = "/issues/by-key/{identifier}",
+    tag = "issues",
+    params(("identifier" = String, Path)),
+    responses((status = 200, description = "Issue retrieved successfully", body = ApiResponse<IssueResponse>))
+)]
+pub async fn get_issue_by_key(
+    State(state): State<Arc<AppState>>,
+    Path(identifier): Path<String>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -270,7 +596,17 @@ pub async fn get_issue(
         }
     };
 
-    match IssuesService::get_by_id(&mut conn, &ctx, issue_id) {
+    let result = state
+        .executor
+        .read(move |conn| {
+            let issue = IssuesService::get_by_key(conn, &ctx, &identifier)?;
+            if let Err(e) = IssuesService::mark_viewed(conn, &ctx, issue.id) {
+                tracing::warn!("Failed to record issue view: {}", e);
+            }
+            Ok(issue)
+        })
+        .await;
+    match result {
         Ok(issue) => {
             let response = ApiResponse::success(issue, "Issue retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -278,3 +614,687 @@ pub async fn get_issue(
         Err(err) => err.into_response(),
     }
 }
+
+// 获取问题描述中代码块的完整原文（渲染结果中被截断的代码块）
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/code-blocks/{index}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path), ("index" = usize, Path)),
+    responses((status = 200, description = "Raw code block", body = String, content_type = "text/plain"))
+)]
+pub async fn get_description_code_block(
+    State(state): State<Arc<AppState>>,
+    Path((issue_id, index)): Path<(Uuid, usize)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssuesService::description_code_block(conn, &ctx, issue_id, index))
+        .await;
+    match result {
+        Ok(code) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            code,
+        )
+            .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取查看过该问题的用户（仅工作区管理员）
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/viewers",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue viewers retrieved successfully", body = ApiResponse<Vec<IssueViewer>>))
+)]
+pub async fn get_issue_viewers(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssuesService::list_viewers(conn, &ctx, issue_id))
+        .await;
+    match result {
+        Ok(viewers) => {
+            let response = ApiResponse::success(viewers, "Issue viewers retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 将问题移动到另一个团队
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/move",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    request_body = MoveIssueRequest,
+    responses((status = 200, description = "Issue moved successfully", body = ApiResponse<IssueMoveResult>))
+)]
+pub async fn move_issue(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<MoveIssueRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            IssueMovesService::move_to_team(conn, &ctx, issue_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(result) => {
+            let response = ApiResponse::success(result, "Issue moved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取问题的跨团队移动记录
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/moves",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue moves retrieved successfully", body = ApiResponse<Vec<IssueMove>>))
+)]
+pub async fn get_issue_moves(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| IssueMovesService::history(conn, &ctx, issue_id))
+        .await;
+    match result {
+        Ok(moves) => {
+            let response = ApiResponse::success(moves, "Issue moves retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取与问题关联的提交和 Pull Request
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/links",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue links retrieved successfully", body = ApiResponse<Vec<IssueLink>>))
+)]
+pub async fn get_issue_links(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| GithubIntegrationService::list_issue_links(conn, &ctx, issue_id))
+        .await;
+    match result {
+        Ok(links) => {
+            let response = ApiResponse::success(links, "Issue links retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 将问题拆分为多个子问题
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/split",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    request_body = SplitIssueRequest,
+    responses((status = 201, description = "Issue split successfully", body = ApiResponse<IssueSplitResult>))
+)]
+pub async fn split_issue(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<SplitIssueRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::CreateIssue)?;
+            IssueSplitService::split(conn, &ctx, issue_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(result) => {
+            let response = ApiResponse::created(result, "Issue split successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 为问题添加关联（阻塞、重复、相关）
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/relations",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    request_body = CreateIssueRelationRequest,
+    responses((status = 201, description = "Issue relation created successfully", body = ApiResponse<IssueRelationResponse>))
+)]
+pub async fn create_issue_relation(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateIssueRelationRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            IssueRelationsService::create(conn, &ctx, issue_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(relation) => {
+            let response = ApiResponse::created(relation, "Issue relation created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 删除问题关联
+#[utoipa::path(
+    delete,
+    path = "/issues/{issue_id}/relations/{relation_id}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path), ("relation_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue relation deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_issue_relation(
+    State(state): State<Arc<AppState>>,
+    Path((issue_id, relation_id)): Path<(Uuid, Uuid)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            IssueRelationsService::delete(conn, &ctx, issue_id, relation_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Issue relation deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 关联同一组织内其他工作区的问题，按 "<工作区>/<问题编号>" 引用，需组织策略允许
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/external-relations",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    request_body = CreateCrossWorkspaceRelationRequest,
+    responses((status = 201, description = "Issue relation created successfully", body = ApiResponse<CrossWorkspaceRelationResponse>))
+)]
+pub async fn create_cross_workspace_relation(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateCrossWorkspaceRelationRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            CrossWorkspaceRelationsService::create(conn, &ctx, issue_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(relation) => {
+            let response = ApiResponse::created(relation, "Issue relation created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 删除跨工作区的问题关联
+#[utoipa::path(
+    delete,
+    path = "/issues/{issue_id}/external-relations/{relation_id}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path), ("relation_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue relation deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_cross_workspace_relation(
+    State(state): State<Arc<AppState>>,
+    Path((issue_id, relation_id)): Path<(Uuid, Uuid)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            CrossWorkspaceRelationsService::delete(conn, &ctx, issue_id, relation_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Issue relation deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取其他工作区对该问题的引用（仅显示编号、标题和状态）
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/backlinks",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Issue backlinks retrieved successfully", body = ApiResponse<Vec<CrossWorkspaceRelationResponse>>))
+)]
+pub async fn get_issue_backlinks(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| CrossWorkspaceRelationsService::backlinks(conn, &ctx, issue_id))
+        .await;
+    match result {
+        Ok(backlinks) => {
+            let response =
+                ApiResponse::success(backlinks, "Issue backlinks retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取问题关联的外部记录（Zendesk、Salesforce、Sentry 等）
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/external-references",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "External references retrieved successfully", body = ApiResponse<Vec<ExternalReference>>))
+)]
+pub async fn get_external_references(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ExternalReferencesService::list(conn, &ctx, issue_id))
+        .await;
+    match result {
+        Ok(references) => {
+            let response =
+                ApiResponse::success(references, "External references retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 为问题添加外部记录引用；同一来源的外部 ID 在工作区内只能关联一个问题
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/external-references",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path)),
+    request_body = CreateExternalReferenceRequest,
+    responses((status = 201, description = "External reference created successfully", body = ApiResponse<ExternalReference>))
+)]
+pub async fn create_external_reference(
+    State(state): State<Arc<AppState>>,
+    Path(issue_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateExternalReferenceRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            ExternalReferencesService::create(conn, &ctx, issue_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(reference) => {
+            let response =
+                ApiResponse::created(reference, "External reference created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 删除问题的外部记录引用
+#[utoipa::path(
+    delete,
+    path = "/issues/{issue_id}/external-references/{reference_id}",
+    tag = "issues",
+    params(("issue_id" = Uuid, Path), ("reference_id" = Uuid, Path)),
+    responses((status = 200, description = "External reference deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_external_reference(
+    State(state): State<Arc<AppState>>,
+    Path((issue_id, reference_id)): Path<(Uuid, Uuid)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            ExternalReferencesService::delete(conn, &ctx, issue_id, reference_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("External reference deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 按外部 ID（可选来源）查找关联的问题
+#[utoipa::path(
+    get,
+    path = "/external-references",
+    tag = "issues",
+    params(ExternalReferenceQuery),
+    responses((status = 200, description = "Issues retrieved successfully", body = ApiResponse<Vec<ExternalReferenceMatch>>))
+)]
+pub async fn find_by_external_reference(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(query): Query<ExternalReferenceQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ExternalReferencesService::search(conn, &ctx, &query))
+        .await;
+    match result {
+        Ok(matches) => {
+            let response = ApiResponse::success(matches, "Issues retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
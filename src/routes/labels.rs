@@ -8,6 +8,7 @@ use axum::{
 // use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::db::enums::LabelLevel;
@@ -16,21 +17,23 @@ use crate::middleware::auth::AuthUserInfo;
 // use crate::schema; // no longer needed in handlers after service extraction
 use crate::services::context::RequestContext;
 use crate::services::labels_service::LabelsService;
+use crate::services::permission_service::{Permission, PermissionService};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LabelQuery {
     pub name: Option<String>,
     pub level: Option<LabelLevel>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct CreateLabelRequest {
     pub name: String,
     pub color: String,
     pub level: LabelLevel,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct UpdateLabelRequest {
     pub name: Option<String>,
     pub color: Option<String>,
@@ -38,23 +41,24 @@ pub struct UpdateLabelRequest {
 }
 
 // 获取标签列表
+#[utoipa::path(
+    get,
+    path = "/labels",
+    tag = "labels",
+    params(LabelQuery),
+    responses((status = 200, description = "Labels retrieved successfully", body = ApiResponse<Vec<Label>>))
+)]
 pub async fn get_labels(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Query(params): Query<LabelQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -66,7 +70,8 @@ pub async fn get_labels(
         }
     };
 
-    match LabelsService::list(&mut conn, &ctx, params.name, params.level) {
+    let result = LabelsService::list_cached(&state.executor, &ctx, params.name, params.level).await;
+    match result {
         Ok(labels) => {
             let response = ApiResponse::success(labels, "Labels retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -76,23 +81,24 @@ pub async fn get_labels(
 }
 
 // 创建标签
+#[utoipa::path(
+    post,
+    path = "/labels",
+    tag = "labels",
+    request_body = CreateLabelRequest,
+    responses((status = 201, description = "Label created successfully", body = ApiResponse<Label>))
+)]
 pub async fn create_label(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<CreateLabelRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -104,7 +110,14 @@ pub async fn create_label(
         }
     };
 
-    match LabelsService::create(&mut conn, &ctx, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageLabels)?;
+            LabelsService::create(conn, &ctx, &payload)
+        })
+        .await;
+    match result {
         Ok(label) => {
             let response = ApiResponse::created(label, "Label created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -114,24 +127,26 @@ pub async fn create_label(
 }
 
 // 更新标签
+#[utoipa::path(
+    put,
+    path = "/labels/{label_id}",
+    tag = "labels",
+    params(("label_id" = Uuid, Path)),
+    request_body = UpdateLabelRequest,
+    responses((status = 200, description = "Label updated successfully", body = ApiResponse<Label>))
+)]
 pub async fn update_label(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(label_id): Path<Uuid>,
     Json(payload): Json<UpdateLabelRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -143,7 +158,14 @@ pub async fn update_label(
         }
     };
 
-    match LabelsService::update(&mut conn, &ctx, label_id, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageLabels)?;
+            LabelsService::update(conn, &ctx, label_id, &payload)
+        })
+        .await;
+    match result {
         Ok(label) => {
             let response = ApiResponse::success(label, "Label updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -153,23 +175,24 @@ pub async fn update_label(
 }
 
 // 删除标签
+#[utoipa::path(
+    delete,
+    path = "/labels/{label_id}",
+    tag = "labels",
+    params(("label_id" = Uuid, Path)),
+    responses((status = 200, description = "Label deleted successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn delete_label(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(label_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -181,7 +204,14 @@ pub async fn delete_label(
         }
     };
 
-    match LabelsService::delete(&mut conn, &ctx, label_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageLabels)?;
+            LabelsService::delete(conn, &ctx, label_id)
+        })
+        .await;
+    match result {
         Ok(()) => {
             let response = ApiResponse::<()>::ok("Label deleted successfully");
             (StatusCode::OK, Json(response)).into_response()
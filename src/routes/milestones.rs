@@ -0,0 +1,462 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::issue::Issue;
+use crate::db::models::milestone::{Milestone, MilestoneStats};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::milestones_service::MilestonesService;
+use crate::services::permission_service::{Permission, PermissionService};
+
+// 请求体定义
+#[derive(Deserialize, ToSchema)]
+pub struct CreateMilestoneRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub target_date: Option<chrono::NaiveDate>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateMilestoneRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub target_date: Option<chrono::NaiveDate>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct MilestoneIssuesRequest {
+    pub issue_ids: Vec<Uuid>,
+}
+
+/// 获取项目的里程碑列表
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/milestones",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Milestones retrieved successfully", body = ApiResponse<Vec<Milestone>>))
+)]
+pub async fn get_milestones(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(project_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| MilestonesService::list(conn, &ctx, project_id))
+        .await;
+    match result {
+        Ok(milestones) => {
+            let response = ApiResponse::success(milestones, "Milestones retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建里程碑
+#[utoipa::path(
+    post,
+    path = "/projects/{project_id}/milestones",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path)),
+    request_body = CreateMilestoneRequest,
+    responses((status = 201, description = "Milestone created successfully", body = ApiResponse<Milestone>))
+)]
+pub async fn create_milestone(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateMilestoneRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateProject)?;
+            MilestonesService::create(conn, &ctx, project_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(milestone) => {
+            let response = ApiResponse::created(milestone, "Milestone created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取指定里程碑
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/milestones/{milestone_id}",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path), ("milestone_id" = Uuid, Path)),
+    responses((status = 200, description = "Milestone retrieved successfully", body = ApiResponse<Milestone>))
+)]
+pub async fn get_milestone_by_id(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| MilestonesService::get_by_id(conn, &ctx, project_id, milestone_id))
+        .await;
+    match result {
+        Ok(milestone) => {
+            let response = ApiResponse::success(milestone, "Milestone retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 更新指定里程碑
+#[utoipa::path(
+    put,
+    path = "/projects/{project_id}/milestones/{milestone_id}",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path), ("milestone_id" = Uuid, Path)),
+    request_body = UpdateMilestoneRequest,
+    responses((status = 200, description = "Milestone updated successfully", body = ApiResponse<Milestone>))
+)]
+pub async fn update_milestone(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateMilestoneRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateProject)?;
+            MilestonesService::update(conn, &ctx, project_id, milestone_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(milestone) => {
+            let response = ApiResponse::success(milestone, "Milestone updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除指定里程碑
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}/milestones/{milestone_id}",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path), ("milestone_id" = Uuid, Path)),
+    responses((status = 200, description = "Milestone deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_milestone(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateProject)?;
+            MilestonesService::delete(conn, &ctx, project_id, milestone_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Milestone deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取里程碑进度统计（含燃起图数据）
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/milestones/{milestone_id}/stats",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path), ("milestone_id" = Uuid, Path)),
+    responses((status = 200, description = "Milestone statistics retrieved successfully", body = ApiResponse<MilestoneStats>))
+)]
+pub async fn get_milestone_stats(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| MilestonesService::get_stats(conn, &ctx, project_id, milestone_id))
+        .await;
+    match result {
+        Ok(stats) => {
+            let response =
+                ApiResponse::success(stats, "Milestone statistics retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取里程碑内的 Issues 列表
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/milestones/{milestone_id}/issues",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path), ("milestone_id" = Uuid, Path)),
+    responses((status = 200, description = "Milestone issues retrieved successfully", body = ApiResponse<Vec<Issue>>))
+)]
+pub async fn get_milestone_issues(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| MilestonesService::get_issues(conn, &ctx, project_id, milestone_id))
+        .await;
+    match result {
+        Ok(issues) => {
+            let response = ApiResponse::success(issues, "Milestone issues retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 将 Issues 关联到里程碑
+#[utoipa::path(
+    post,
+    path = "/projects/{project_id}/milestones/{milestone_id}/issues",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path), ("milestone_id" = Uuid, Path)),
+    request_body = MilestoneIssuesRequest,
+    responses((status = 200, description = "Issues assigned to milestone successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn assign_issues_to_milestone(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<MilestoneIssuesRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateProject)?;
+            MilestonesService::assign_issues(
+                conn,
+                &ctx,
+                project_id,
+                milestone_id,
+                &payload.issue_ids,
+            )
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Issues assigned to milestone successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 从里程碑中移除 Issues
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}/milestones/{milestone_id}/issues",
+    tag = "milestones",
+    params(("project_id" = Uuid, Path), ("milestone_id" = Uuid, Path)),
+    request_body = MilestoneIssuesRequest,
+    responses((status = 200, description = "Issues removed from milestone successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn remove_issues_from_milestone(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<MilestoneIssuesRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateProject)?;
+            MilestonesService::remove_issues(
+                conn,
+                &ctx,
+                project_id,
+                milestone_id,
+                &payload.issue_ids,
+            )
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Issues removed from milestone successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -1,21 +1,52 @@
+pub mod admin;
+pub mod analytics;
+pub mod api_keys;
+pub mod api_tokens;
+pub mod app_installations;
+pub mod attachments;
 pub mod auth;
+pub mod automations;
 pub mod comments;
+pub mod content_reports;
+pub mod custom_emojis;
 pub mod cycles;
+pub mod dashboard;
+pub mod docs;
+pub mod error_codes;
+pub mod graphql;
+pub mod health;
+pub mod holidays;
+pub mod inbound;
+pub mod integrations;
 pub mod invitations;
+pub mod issue_forms;
+pub mod issue_label_rules;
 pub mod issues;
 pub mod labels;
+pub mod milestones;
+pub mod notifications;
+pub mod oauth;
+pub mod project_costs;
 pub mod project_statuses;
 pub mod projects;
+pub mod search;
+pub mod service_accounts;
+pub mod settings;
+pub mod sync;
 pub mod teams;
+pub mod uploads;
 pub mod users;
+pub mod webhooks;
 pub mod workflows;
+pub mod workspace_imports;
 pub mod workspace_members;
 pub mod workspaces;
 
 use crate::AppState;
 use axum::{
     Router,
-    routing::{delete, get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
 };
 use std::sync::Arc;
 
@@ -26,9 +57,189 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/labels", post(labels::create_label))
         .route("/labels/:label_id", put(labels::update_label))
         .route("/labels/:label_id", delete(labels::delete_label))
+        .route("/holidays", get(holidays::get_holidays))
+        .route("/holidays", post(holidays::create_holiday))
+        .route("/holidays/import", post(holidays::import_holidays))
+        .route("/holidays/:holiday_id", put(holidays::update_holiday))
+        .route("/holidays/:holiday_id", delete(holidays::delete_holiday))
+        .route("/automations", get(automations::get_automations))
+        .route("/automations", post(automations::create_automation))
+        .route("/automations/:rule_id", get(automations::get_automation))
+        .route("/automations/:rule_id", put(automations::update_automation))
+        .route(
+            "/automations/:rule_id",
+            delete(automations::delete_automation),
+        )
+        .route(
+            "/automations/:rule_id/runs",
+            get(automations::get_automation_runs),
+        )
+        .route("/admin/integrity-check", post(admin::run_integrity_check))
+        .route("/admin/maintenance", get(admin::get_maintenance))
+        .route("/admin/maintenance", put(admin::set_maintenance))
+        .route("/admin/jobs", get(admin::get_jobs))
+        .route("/admin/telemetry", get(admin::get_telemetry))
+        .route(
+            "/admin/users/:user_id/force-logout",
+            post(admin::force_logout_user),
+        )
+        .route("/admin/organizations", get(admin::list_organizations))
+        .route("/admin/organizations", post(admin::create_organization))
+        .route(
+            "/admin/organizations/:organization_id",
+            get(admin::get_organization),
+        )
+        .route(
+            "/admin/organizations/:organization_id",
+            put(admin::update_organization),
+        )
+        .route(
+            "/admin/workspaces/:workspace_id/organization",
+            put(admin::set_workspace_organization),
+        )
+        .route(
+            "/admin/workspaces/:workspace_id/reset",
+            post(admin::reset_workspace),
+        )
+        .route(
+            "/admin/workspaces/:workspace_id/resets/:reset_id",
+            get(admin::get_workspace_reset),
+        )
+        .route("/admin/comments/moderate", post(admin::moderate_comments))
+        .route("/admin/comments/unhide", post(admin::unhide_comments))
+        .route(
+            "/admin/workspaces/:workspace_id/comment-flags",
+            get(admin::list_comment_flags),
+        )
+        .route(
+            "/admin/workspaces/:workspace_id/comment-flags/:flag_id/dismiss",
+            post(admin::dismiss_comment_flag),
+        )
+        .route("/admin/ws/dead-letters", get(admin::list_ws_dead_letters))
+        .route(
+            "/admin/ws/dead-letters/:dead_letter_id/replay",
+            post(admin::replay_ws_dead_letter),
+        )
         .route("/auth/profile", get(auth::get_profile))
         .route("/auth/logout", post(auth::logout))
+        .route("/auth/change-password", post(auth::change_password))
         .route("/auth/switch-workspace", post(auth::switch_workspace))
+        .route("/auth/tokens", post(api_tokens::create_api_token))
+        .route("/auth/tokens", get(api_tokens::get_api_tokens))
+        .route(
+            "/auth/tokens/:token_id",
+            delete(api_tokens::revoke_api_token),
+        )
+        .route(
+            "/auth/tokens/:token_id/usage",
+            get(api_tokens::get_api_token_usage),
+        )
+        .route("/api-keys", get(api_keys::get_api_keys))
+        .route("/api-keys", post(api_keys::create_api_key))
+        .route("/api-keys/:key_id", get(api_keys::get_api_key))
+        .route("/api-keys/:key_id", put(api_keys::update_api_key))
+        .route("/api-keys/:key_id", delete(api_keys::revoke_api_key))
+        .route(
+            "/service-accounts",
+            get(service_accounts::get_service_accounts),
+        )
+        .route(
+            "/service-accounts",
+            post(service_accounts::create_service_account),
+        )
+        .route(
+            "/service-accounts/:account_id",
+            delete(service_accounts::disable_service_account),
+        )
+        .route(
+            "/service-accounts/:account_id/tokens",
+            get(service_accounts::get_service_account_tokens),
+        )
+        .route(
+            "/service-accounts/:account_id/tokens",
+            post(service_accounts::create_service_account_token),
+        )
+        .route(
+            "/service-accounts/:account_id/tokens/:token_id",
+            delete(service_accounts::revoke_service_account_token),
+        )
+        .route("/oauth/apps", post(oauth::create_oauth_app))
+        .route("/oauth/apps", get(oauth::get_oauth_apps))
+        .route("/oauth/apps/:app_id", delete(oauth::delete_oauth_app))
+        .route("/oauth/apps/:app_id/usage", get(oauth::get_oauth_app_usage))
+        .route(
+            "/oauth/apps/:app_id/webhook",
+            put(oauth::update_app_webhook),
+        )
+        .route(
+            "/oauth/apps/:app_id/webhook/signing-keys",
+            get(oauth::get_webhook_signing_keys),
+        )
+        .route(
+            "/oauth/apps/:app_id/webhook/signing-keys/rotate",
+            post(oauth::rotate_webhook_signing_key),
+        )
+        .route(
+            "/oauth/apps/:app_id/webhook/verify-signature",
+            post(oauth::verify_webhook_signature),
+        )
+        .route(
+            "/oauth/apps/:app_id/deliveries",
+            get(oauth::get_webhook_deliveries),
+        )
+        .route(
+            "/oauth/apps/:app_id/webhook/test",
+            post(oauth::test_webhook),
+        )
+        .route(
+            "/oauth/apps/:app_id/webhook/redeliver/:delivery_id",
+            post(oauth::redeliver_webhook),
+        )
+        .route(
+            "/integrations/github",
+            get(integrations::get_github_integration),
+        )
+        .route(
+            "/integrations/github",
+            put(integrations::update_github_integration),
+        )
+        .route(
+            "/integrations/github",
+            delete(integrations::delete_github_integration),
+        )
+        .route(
+            "/integrations/errors",
+            get(integrations::get_error_tracking_integration),
+        )
+        .route(
+            "/integrations/errors",
+            put(integrations::update_error_tracking_integration),
+        )
+        .route(
+            "/integrations/errors",
+            delete(integrations::delete_error_tracking_integration),
+        )
+        .route("/webhooks", get(webhooks::get_webhooks))
+        .route("/webhooks", post(webhooks::create_webhook))
+        .route("/webhooks/:webhook_id", get(webhooks::get_webhook_by_id))
+        .route("/webhooks/:webhook_id", put(webhooks::update_webhook))
+        .route("/webhooks/:webhook_id", delete(webhooks::delete_webhook))
+        .route(
+            "/webhooks/:webhook_id/deliveries",
+            get(webhooks::get_webhook_deliveries),
+        )
+        .route("/webhooks/:webhook_id/test", post(webhooks::test_webhook))
+        .route(
+            "/webhooks/:webhook_id/redeliver/:delivery_id",
+            post(webhooks::redeliver_webhook),
+        )
+        .route("/oauth/authorize", get(oauth::get_authorize))
+        .route("/oauth/authorize", post(oauth::post_authorize))
+        .route("/oauth/authorizations", get(oauth::get_authorizations))
+        .route(
+            "/oauth/authorizations/:app_id",
+            delete(oauth::revoke_authorization),
+        )
         .route("/workspaces", post(workspaces::create_workspace))
         .route(
             "/workspaces/current",
@@ -42,10 +253,112 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/workspaces/:workspace_id",
             delete(workspaces::delete_workspace),
         )
+        .route(
+            "/workspaces/current/api-access",
+            get(workspaces::get_api_access),
+        )
+        .route(
+            "/workspaces/current/api-access/:channel",
+            put(workspaces::update_api_access),
+        )
+        .route(
+            "/workspaces/current/settings",
+            get(workspaces::get_workspace_settings),
+        )
+        .route(
+            "/workspaces/current/settings",
+            put(workspaces::update_workspace_settings),
+        )
+        .route(
+            "/workspaces/current/email-domain",
+            get(workspaces::get_email_domain),
+        )
+        .route(
+            "/workspaces/current/email-domain",
+            put(workspaces::set_email_domain),
+        )
+        .route(
+            "/workspaces/current/email-domain",
+            delete(workspaces::delete_email_domain),
+        )
+        .route(
+            "/workspaces/current/email-domain/verify",
+            post(workspaces::verify_email_domain),
+        )
+        .route(
+            "/workspaces/current/apps",
+            get(app_installations::get_installed_apps),
+        )
+        .route(
+            "/workspaces/current/apps",
+            post(app_installations::install_app),
+        )
+        .route(
+            "/workspaces/current/apps/:installation_id",
+            put(app_installations::update_installed_app),
+        )
+        .route(
+            "/workspaces/current/apps/:installation_id",
+            delete(app_installations::uninstall_app),
+        )
+        .route(
+            "/workspaces/current/import",
+            post(workspace_imports::import_workspace_data).layer(DefaultBodyLimit::max(
+                crate::services::import_service::MAX_IMPORT_BYTES,
+            )),
+        )
+        .route(
+            "/workspaces/current/import/:import_id",
+            get(workspace_imports::get_workspace_import),
+        )
+        .route(
+            "/workspaces/current/reports",
+            get(content_reports::get_report_queue),
+        )
+        .route(
+            "/workspaces/current/reports/:report_id/resolve",
+            post(content_reports::resolve_report),
+        )
         .route(
             "/workspace-members",
             get(workspace_members::get_current_workspace_members),
         )
+        .route(
+            "/workspace-members/directory",
+            get(workspace_members::get_member_directory),
+        )
+        .route(
+            "/workspace-members/changes",
+            get(workspace_members::get_member_directory_changes),
+        )
+        .route(
+            "/workspace-members/:user_id",
+            put(workspace_members::update_member_role),
+        )
+        .route(
+            "/workspace-members/:user_id",
+            delete(workspace_members::remove_member),
+        )
+        .route(
+            "/workspace-members/rates",
+            get(project_costs::get_member_rates),
+        )
+        .route(
+            "/workspace-members/:user_id/rate",
+            put(project_costs::set_member_rate),
+        )
+        .route(
+            "/workspace-members/:user_id/rate",
+            delete(project_costs::delete_member_rate),
+        )
+        .route(
+            "/workspace-members/import",
+            post(workspace_members::import_members),
+        )
+        .route(
+            "/workspace-members/import/:import_id",
+            get(workspace_members::get_member_import),
+        )
         .route(
             "/workspace-member-and-invitations",
             get(workspace_members::get_workspace_members_and_invitations),
@@ -56,6 +369,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         )
         .route("/invitations", post(invitations::invite_member))
         .route("/invitations", get(invitations::get_user_invitations))
+        .route("/invitations/bulk", post(invitations::bulk_invite_members))
         .route(
             "/invitations/:invitation_id",
             get(invitations::get_invitation_by_id),
@@ -74,25 +388,244 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         )
         .route("/issues", post(issues::create_issue))
         .route("/issues", get(issues::get_issues))
+        .route("/issues/by-key/:identifier", get(issues::get_issue_by_key))
+        .route("/issues/bulk-archive", post(issues::bulk_archive_issues))
+        .route(
+            "/issues/bulk-archive/:batch_id",
+            get(issues::get_issue_archive_batch),
+        )
+        .route(
+            "/issues/bulk-archive/:batch_id/undo",
+            post(issues::undo_issue_archive_batch),
+        )
         .route("/issues/:issue_id", get(issues::get_issue))
         .route("/issues/:issue_id", put(issues::update_issue))
+        .route("/issues/:issue_id", patch(issues::patch_issue))
         .route("/issues/:issue_id", delete(issues::delete_issue))
+        .route(
+            "/issues/:issue_id/code-blocks/:index",
+            get(issues::get_description_code_block),
+        )
+        .route(
+            "/issues/:issue_id/report",
+            post(content_reports::report_issue),
+        )
+        .route("/issues/:issue_id/viewers", get(issues::get_issue_viewers))
+        .route("/issues/:issue_id/move", post(issues::move_issue))
+        .route("/issues/:issue_id/moves", get(issues::get_issue_moves))
+        .route("/issues/:issue_id/split", post(issues::split_issue))
+        .route("/issues/:issue_id/links", get(issues::get_issue_links))
+        .route(
+            "/issues/:issue_id/relations",
+            post(issues::create_issue_relation),
+        )
+        .route(
+            "/issues/:issue_id/relations/:relation_id",
+            delete(issues::delete_issue_relation),
+        )
+        .route(
+            "/issues/:issue_id/external-relations",
+            post(issues::create_cross_workspace_relation),
+        )
+        .route(
+            "/issues/:issue_id/external-relations/:relation_id",
+            delete(issues::delete_cross_workspace_relation),
+        )
+        .route(
+            "/issues/:issue_id/backlinks",
+            get(issues::get_issue_backlinks),
+        )
+        .route(
+            "/issues/:issue_id/external-references",
+            get(issues::get_external_references),
+        )
+        .route(
+            "/issues/:issue_id/external-references",
+            post(issues::create_external_reference),
+        )
+        .route(
+            "/issues/:issue_id/external-references/:reference_id",
+            delete(issues::delete_external_reference),
+        )
+        .route(
+            "/external-references",
+            get(issues::find_by_external_reference),
+        )
+        .route("/search/issues", get(search::search_issues))
+        .route("/sync/snapshot", get(sync::get_snapshot))
+        .route("/sync/changes", get(sync::get_changes))
+        .route("/graphql", post(graphql::graphql))
+        .route("/analytics/sessions", get(analytics::get_session_analytics))
+        .route("/settings/history", get(settings::get_settings_history))
+        .route("/dashboard/summary", get(dashboard::get_dashboard_summary))
+        .route("/notifications", get(notifications::get_notifications))
+        .route(
+            "/notifications/read-all",
+            post(notifications::mark_all_notifications_read),
+        )
+        .route(
+            "/notifications/:notification_id/read",
+            post(notifications::mark_notification_read),
+        )
+        .route(
+            "/notifications/push/subscriptions",
+            get(notifications::get_push_subscriptions),
+        )
+        .route(
+            "/notifications/push/subscriptions",
+            post(notifications::register_push_subscription),
+        )
+        .route(
+            "/notifications/push/subscriptions/:subscription_id",
+            delete(notifications::unregister_push_subscription),
+        )
+        .route(
+            "/notifications/push/preferences",
+            get(notifications::get_push_preferences),
+        )
+        .route(
+            "/notifications/push/preferences",
+            put(notifications::update_push_preferences),
+        )
+        .route(
+            "/issues/:issue_id/attachments",
+            get(attachments::get_attachments),
+        )
+        .route(
+            "/issues/:issue_id/attachments",
+            post(attachments::create_attachment),
+        )
+        .route(
+            "/issues/:issue_id/attachments/:attachment_id",
+            delete(attachments::delete_attachment),
+        )
+        .route(
+            "/issues/:issue_id/attachments/:attachment_id/complete",
+            post(attachments::complete_attachment),
+        )
+        .route(
+            "/issues/:issue_id/uploads",
+            post(uploads::create_upload_session),
+        )
+        .route("/uploads/:upload_id", get(uploads::get_upload_session))
+        .route(
+            "/uploads/:upload_id",
+            patch(uploads::append_upload_chunk).layer(DefaultBodyLimit::max(
+                crate::services::upload_sessions_service::MAX_CHUNK_BYTES,
+            )),
+        )
+        .route(
+            "/uploads/:upload_id",
+            delete(uploads::cancel_upload_session),
+        )
         .route("/issues/:issue_id/comments", get(comments::get_comments))
         .route("/issues/:issue_id/comments", post(comments::create_comment))
+        .route(
+            "/issues/:issue_id/comments/page",
+            get(comments::get_comment_page),
+        )
+        .route("/emojis", get(custom_emojis::get_emojis))
+        .route("/emojis", post(custom_emojis::create_emoji))
+        .route("/emojis/stats", get(custom_emojis::get_emoji_stats))
+        .route("/emojis/:emoji_id", put(custom_emojis::rename_emoji))
+        .route("/emojis/:emoji_id", delete(custom_emojis::delete_emoji))
+        .route(
+            "/emojis/:emoji_id/complete",
+            post(custom_emojis::complete_emoji),
+        )
         .route("/comments/:comment_id", get(comments::get_comment))
         .route("/comments/:comment_id", put(comments::update_comment))
         .route("/comments/:comment_id", delete(comments::delete_comment))
+        .route(
+            "/comments/:comment_id/code-blocks/:index",
+            get(comments::get_comment_code_block),
+        )
+        .route(
+            "/comments/:comment_id/reactions",
+            post(comments::add_reaction),
+        )
+        .route(
+            "/comments/:comment_id/report",
+            post(content_reports::report_comment),
+        )
+        .route(
+            "/comments/:comment_id/reactions/:emoji",
+            delete(comments::remove_reaction),
+        )
         .route("/users/profile", put(users::update_profile))
+        .route("/users/preferences", get(users::get_preferences))
+        .route("/users/preferences", put(users::update_preferences))
         .route("/projects", get(projects::get_projects))
         .route("/projects", post(projects::create_project))
+        .route("/projects/board", get(projects::get_project_board))
         .route("/projects/:project_id", put(projects::update_project))
         .route("/projects/:project_id", delete(projects::delete_project))
+        .route(
+            "/projects/:project_id/budget",
+            put(project_costs::set_project_budget),
+        )
+        .route(
+            "/projects/:project_id/costs",
+            get(project_costs::get_project_costs),
+        )
+        .route(
+            "/projects/:project_id/cost-entries",
+            get(project_costs::get_cost_entries),
+        )
+        .route(
+            "/projects/:project_id/cost-entries",
+            post(project_costs::create_cost_entry),
+        )
+        .route(
+            "/projects/:project_id/cost-entries/:entry_id",
+            delete(project_costs::delete_cost_entry),
+        )
+        .route(
+            "/projects/:project_id/milestones",
+            get(milestones::get_milestones),
+        )
+        .route(
+            "/projects/:project_id/milestones",
+            post(milestones::create_milestone),
+        )
+        .route(
+            "/projects/:project_id/milestones/:milestone_id",
+            get(milestones::get_milestone_by_id),
+        )
+        .route(
+            "/projects/:project_id/milestones/:milestone_id",
+            put(milestones::update_milestone),
+        )
+        .route(
+            "/projects/:project_id/milestones/:milestone_id",
+            delete(milestones::delete_milestone),
+        )
+        .route(
+            "/projects/:project_id/milestones/:milestone_id/stats",
+            get(milestones::get_milestone_stats),
+        )
+        .route(
+            "/projects/:project_id/milestones/:milestone_id/issues",
+            get(milestones::get_milestone_issues),
+        )
+        .route(
+            "/projects/:project_id/milestones/:milestone_id/issues",
+            post(milestones::assign_issues_to_milestone),
+        )
+        .route(
+            "/projects/:project_id/milestones/:milestone_id/issues",
+            delete(milestones::remove_issues_from_milestone),
+        )
         .route("/cycles", post(cycles::create_cycle))
         .route("/cycles", get(cycles::get_cycles))
         .route("/cycles/:cycle_id", get(cycles::get_cycle_by_id))
         .route("/cycles/:cycle_id", put(cycles::update_cycle))
         .route("/cycles/:cycle_id", delete(cycles::delete_cycle))
         .route("/cycles/:cycle_id/stats", get(cycles::get_cycle_stats))
+        .route(
+            "/cycles/:cycle_id/burndown",
+            get(cycles::get_cycle_burndown),
+        )
         .route("/cycles/:cycle_id/issues", get(cycles::get_cycle_issues))
         .route(
             "/cycles/:cycle_id/issues",
@@ -106,6 +639,22 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/cycles/auto-update-status",
             post(cycles::update_cycle_status_auto),
         )
+        .route(
+            "/teams/:team_id/cycle-settings",
+            get(cycles::get_cycle_settings),
+        )
+        .route(
+            "/teams/:team_id/cycle-settings",
+            put(cycles::put_cycle_settings),
+        )
+        .route(
+            "/teams/:team_id/cycle-settings",
+            delete(cycles::delete_cycle_settings),
+        )
+        .route(
+            "/teams/:team_id/cycles/next-preview",
+            get(cycles::get_next_cycle_preview),
+        )
         .route(
             "/project-statuses",
             post(project_statuses::create_project_status),
@@ -143,6 +692,39 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/teams/:team_id/workflows/default/states/:state_id",
             put(workflows::update_team_default_workflow_state),
         )
+        .route("/teams/:team_id/forms", get(issue_forms::get_team_forms))
+        .route("/teams/:team_id/forms", post(issue_forms::create_form))
+        .route("/forms/:form_id", get(issue_forms::get_form))
+        .route("/forms/:form_id", put(issue_forms::update_form))
+        .route("/forms/:form_id", delete(issue_forms::delete_form))
+        .route(
+            "/teams/:team_id/label-rules",
+            get(issue_label_rules::get_team_label_rules),
+        )
+        .route(
+            "/teams/:team_id/label-rules",
+            post(issue_label_rules::create_label_rule),
+        )
+        .route(
+            "/label-rules/:rule_id",
+            get(issue_label_rules::get_label_rule),
+        )
+        .route(
+            "/label-rules/:rule_id",
+            put(issue_label_rules::update_label_rule),
+        )
+        .route(
+            "/label-rules/:rule_id",
+            delete(issue_label_rules::delete_label_rule),
+        )
+        .route(
+            "/issues/:issue_id/label-rule-applications",
+            get(issue_label_rules::get_issue_label_rule_applications),
+        )
+        .route(
+            "/forms/:form_id/submissions",
+            post(issue_forms::submit_form),
+        )
         .route(
             "/workflows/:workflow_id",
             get(workflows::get_workflow_by_id),
@@ -166,7 +748,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         )
         .with_state(state.clone());
 
-    // Create a router for routes that only need the database pool
+    // Create a router for routes that only need database access
     // Note: Auth routes are handled in main.rs to avoid middleware conflicts
     let db_routes = Router::new()
         .route("/teams", post(teams::create_team))
@@ -184,8 +766,28 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/teams/:team_id/members/:user_id",
             delete(teams::remove_team_member),
         )
+        .route(
+            "/teams/:team_id/auto-close-policy",
+            get(teams::get_auto_close_policy),
+        )
+        .route(
+            "/teams/:team_id/auto-close-policy",
+            put(teams::put_auto_close_policy),
+        )
+        .route(
+            "/teams/:team_id/auto-close-policy",
+            delete(teams::delete_auto_close_policy),
+        )
+        .route("/teams/:team_id/parent", put(teams::set_parent_team))
+        .route("/teams/:team_id/hierarchy", get(teams::get_team_hierarchy))
+        .route(
+            "/teams/:team_id/effective-members",
+            get(teams::get_effective_team_members),
+        )
+        .route("/teams/:team_id/board", get(teams::get_team_board))
+        .route("/teams/:team_id/triage", get(teams::get_team_triage))
         .route("/user/teams", get(teams::get_user_teams))
-        .with_state(Arc::new(state.db.clone()));
+        .with_state(state.executor.clone());
 
     // Merge the routers
     app_routes.merge(db_routes)
@@ -0,0 +1,225 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::*;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::notifications_service::NotificationsService;
+use crate::services::push_service::PushService;
+
+// 获取当前用户的通知列表
+#[utoipa::path(
+    get,
+    path = "/notifications",
+    tag = "notifications",
+    params(NotificationListQuery),
+    responses((status = 200, description = "Notifications retrieved successfully", body = ApiResponse<NotificationList>))
+)]
+pub async fn get_notifications(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(query): Query<NotificationListQuery>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| NotificationsService::list(conn, auth_info.user.id, &query))
+        .await;
+    match result {
+        Ok(list) => {
+            let response = ApiResponse::success(list, "Notifications retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 将单条通知标记为已读
+#[utoipa::path(
+    post,
+    path = "/notifications/{notification_id}/read",
+    tag = "notifications",
+    params(("notification_id" = Uuid, Path)),
+    responses((status = 200, description = "Notification marked as read", body = ApiResponse<Notification>))
+)]
+pub async fn mark_notification_read(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(notification_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            NotificationsService::mark_read(conn, auth_info.user.id, notification_id)
+        })
+        .await;
+    match result {
+        Ok(notification) => {
+            let response = ApiResponse::success(notification, "Notification marked as read");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 将所有通知标记为已读
+#[utoipa::path(
+    post,
+    path = "/notifications/read-all",
+    tag = "notifications",
+    responses((status = 200, description = "OK", body = ApiResponse<serde_json::Value>))
+)]
+pub async fn mark_all_notifications_read(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| NotificationsService::mark_all_read(conn, auth_info.user.id))
+        .await;
+    match result {
+        Ok(updated) => {
+            let response = ApiResponse::success(
+                serde_json::json!({ "updated": updated }),
+                "All notifications marked as read",
+            );
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取当前用户已注册推送的设备
+#[utoipa::path(
+    get,
+    path = "/notifications/push/subscriptions",
+    tag = "notifications",
+    responses((status = 200, description = "Push subscriptions retrieved successfully", body = ApiResponse<Vec<PushSubscription>>))
+)]
+pub async fn get_push_subscriptions(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| PushService::list(conn, auth_info.user.id))
+        .await;
+    match result {
+        Ok(subscriptions) => {
+            let response =
+                ApiResponse::success(subscriptions, "Push subscriptions retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 注册（或刷新）设备的推送令牌；客户端每次启动时调用，长期未刷新的令牌会被清理
+#[utoipa::path(
+    post,
+    path = "/notifications/push/subscriptions",
+    tag = "notifications",
+    request_body = RegisterPushSubscriptionRequest,
+    responses((status = 200, description = "Push subscription registered successfully", body = ApiResponse<PushSubscription>))
+)]
+pub async fn register_push_subscription(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<RegisterPushSubscriptionRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| PushService::register(conn, auth_info.user.id, &payload))
+        .await;
+    match result {
+        Ok(subscription) => {
+            let response =
+                ApiResponse::success(subscription, "Push subscription registered successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 注销设备的推送令牌
+#[utoipa::path(
+    delete,
+    path = "/notifications/push/subscriptions/{subscription_id}",
+    tag = "notifications",
+    params(("subscription_id" = Uuid, Path)),
+    responses((status = 200, description = "Push subscription removed successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn unregister_push_subscription(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(subscription_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| PushService::unregister(conn, auth_info.user.id, subscription_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Push subscription removed successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取当前用户的推送偏好
+#[utoipa::path(
+    get,
+    path = "/notifications/push/preferences",
+    tag = "notifications",
+    responses((status = 200, description = "Push preferences retrieved successfully", body = ApiResponse<PushPreferences>))
+)]
+pub async fn get_push_preferences(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| PushService::preferences(conn, auth_info.user.id))
+        .await;
+    match result {
+        Ok(preferences) => {
+            let response =
+                ApiResponse::success(preferences, "Push preferences retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 更新推送偏好：整体开关和不推送的通知类型
+#[utoipa::path(
+    put,
+    path = "/notifications/push/preferences",
+    tag = "notifications",
+    request_body = UpdatePushPreferencesRequest,
+    responses((status = 200, description = "Push preferences updated successfully", body = ApiResponse<PushPreferences>))
+)]
+pub async fn update_push_preferences(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<UpdatePushPreferencesRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| PushService::update_preferences(conn, auth_info.user.id, &payload))
+        .await;
+    match result {
+        Ok(preferences) => {
+            let response =
+                ApiResponse::success(preferences, "Push preferences updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,581 @@
+use axum::{
+    Form, Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header::AUTHORIZATION},
+    response::{IntoResponse, Response},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, EmptyData};
+use crate::db::models::api_token::ApiUsageReport;
+use crate::db::models::app_installation::{
+    AppWebhookConfig, RotateSigningKeyRequest, RotatedSigningKey, SignatureVerification,
+    TestWebhookRequest, UpdateAppWebhookRequest, VerifySignatureRequest, WebhookDelivery,
+    WebhookSigningKey,
+};
+use crate::db::models::oauth_app::{
+    AuthorizeDecision, AuthorizePreview, AuthorizeRedirect, AuthorizeRequest, AuthorizedApp,
+    CreateOAuthAppRequest, CreatedOAuthApp, OAuthApp, TokenRequest, TokenResponse,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::routes::api_tokens::ApiTokenUsageQuery;
+use crate::services::context::RequestContext;
+use crate::services::oauth_service::{OAuthError, OAuthService};
+use crate::services::webhook_service::WebhookService;
+
+#[derive(Deserialize, ToSchema)]
+pub struct RevokeTokenRequest {
+    pub token: String,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+fn user_context(auth_info: &AuthUserInfo) -> RequestContext {
+    RequestContext {
+        user_id: auth_info.user.id,
+        workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
+        idempotency_key: None,
+        channel: auth_info.channel,
+    }
+}
+
+/// 解析 HTTP Basic 形式的客户端凭据
+fn basic_client_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let encoded = headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded).ok()?).ok()?;
+    let (id, secret) = decoded.split_once(':')?;
+    Some((id.to_string(), secret.to_string()))
+}
+
+/// Token 端点按 RFC 6749 返回错误，而不是统一的 ApiResponse 结构，方便标准 OAuth 客户端解析
+fn oauth_error_response(err: OAuthError) -> Response {
+    let status = match err {
+        OAuthError::InvalidClient => StatusCode::UNAUTHORIZED,
+        OAuthError::Server(ref e) => {
+            tracing::error!("OAuth token endpoint error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+    let body = serde_json::json!({
+        "error": err.error_code(),
+        "error_description": err.description(),
+    });
+    (status, Json(body)).into_response()
+}
+
+// 注册第三方应用（client_secret 只在创建时返回一次）
+#[utoipa::path(
+    post,
+    path = "/oauth/apps",
+    tag = "oauth",
+    request_body = CreateOAuthAppRequest,
+    responses((status = 201, description = "OAuth app created successfully", body = ApiResponse<CreatedOAuthApp>))
+)]
+pub async fn create_oauth_app(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateOAuthAppRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            OAuthService::create_app(conn, &user_context(&auth_info), &payload)
+        })
+        .await;
+    match result {
+        Ok(app) => {
+            let response = ApiResponse::created(app, "OAuth app created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取当前用户注册的第三方应用
+#[utoipa::path(
+    get,
+    path = "/oauth/apps",
+    tag = "oauth",
+    responses((status = 200, description = "OAuth apps retrieved successfully", body = ApiResponse<Vec<OAuthApp>>))
+)]
+pub async fn get_oauth_apps(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| OAuthService::list_apps(conn, &user_context(&auth_info)))
+        .await;
+    match result {
+        Ok(apps) => {
+            let response = ApiResponse::success(apps, "OAuth apps retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 删除第三方应用，并吊销其所有授权
+#[utoipa::path(
+    delete,
+    path = "/oauth/apps/{app_id}",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    responses((status = 200, description = "OAuth app deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_oauth_app(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| OAuthService::delete_app(conn, &user_context(&auth_info), app_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("OAuth app deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取第三方应用的 API 用量统计
+#[utoipa::path(
+    get,
+    path = "/oauth/apps/{app_id}/usage",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path), ApiTokenUsageQuery),
+    responses((status = 200, description = "OAuth app usage retrieved successfully", body = ApiResponse<ApiUsageReport>))
+)]
+pub async fn get_oauth_app_usage(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    Query(params): Query<ApiTokenUsageQuery>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let days = params.days.unwrap_or(30);
+    let result = state
+        .executor
+        .read(move |conn| OAuthService::app_usage(conn, &user_context(&auth_info), app_id, days))
+        .await;
+    match result {
+        Ok(report) => {
+            let response = ApiResponse::success(report, "OAuth app usage retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 配置第三方应用的 Webhook 地址与订阅事件
+#[utoipa::path(
+    put,
+    path = "/oauth/apps/{app_id}/webhook",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    request_body = UpdateAppWebhookRequest,
+    responses((status = 200, description = "Webhook updated successfully", body = ApiResponse<AppWebhookConfig>))
+)]
+pub async fn update_app_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<UpdateAppWebhookRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            OAuthService::set_webhook(conn, &user_context(&auth_info), app_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(config) => {
+            let response = ApiResponse::success(config, "Webhook updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取第三方应用当前有效的 Webhook 签名密钥（不含密钥内容）
+#[utoipa::path(
+    get,
+    path = "/oauth/apps/{app_id}/webhook/signing-keys",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    responses((status = 200, description = "Signing keys retrieved successfully", body = ApiResponse<Vec<WebhookSigningKey>>))
+)]
+pub async fn get_webhook_signing_keys(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| OAuthService::list_signing_keys(conn, &user_context(&auth_info), app_id))
+        .await;
+    match result {
+        Ok(keys) => {
+            let response = ApiResponse::success(keys, "Signing keys retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 轮换 Webhook 签名密钥，旧密钥在宽限期内继续签名
+#[utoipa::path(
+    post,
+    path = "/oauth/apps/{app_id}/webhook/signing-keys/rotate",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    request_body = Option<RotateSigningKeyRequest>,
+    responses((status = 201, description = "Signing key rotated successfully", body = ApiResponse<RotatedSigningKey>))
+)]
+pub async fn rotate_webhook_signing_key(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    payload: Option<Json<RotateSigningKeyRequest>>,
+) -> impl IntoResponse {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            OAuthService::rotate_signing_key(conn, &user_context(&auth_info), app_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(rotated) => {
+            let response = ApiResponse::created(rotated, "Signing key rotated successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 用示例载荷计算签名，并校验集成方自行计算的签名
+#[utoipa::path(
+    post,
+    path = "/oauth/apps/{app_id}/webhook/verify-signature",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    request_body = VerifySignatureRequest,
+    responses((status = 200, description = "Signature computed successfully", body = ApiResponse<SignatureVerification>))
+)]
+pub async fn verify_webhook_signature(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<VerifySignatureRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            OAuthService::verify_signature(conn, &user_context(&auth_info), app_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(result) => {
+            let response = ApiResponse::success(result, "Signature computed successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取第三方应用最近的 Webhook 投递记录
+#[utoipa::path(
+    get,
+    path = "/oauth/apps/{app_id}/deliveries",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    responses((status = 200, description = "Webhook deliveries retrieved successfully", body = ApiResponse<Vec<WebhookDelivery>>))
+)]
+pub async fn get_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| OAuthService::list_deliveries(conn, &user_context(&auth_info), app_id))
+        .await;
+    match result {
+        Ok(deliveries) => {
+            let response =
+                ApiResponse::success(deliveries, "Webhook deliveries retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 立即发送刚入队的投递，返回记录了结果的投递；发送失败的投递按正常节奏重试
+pub(crate) async fn send_now(state: &AppState, deliveries: Vec<WebhookDelivery>) -> Response {
+    let client = match reqwest::Client::builder().build() {
+        Ok(client) => client,
+        Err(_) => {
+            let response = ApiResponse::<()>::internal_error("Failed to create HTTP client");
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+        }
+    };
+    let mut sent = Vec::with_capacity(deliveries.len());
+    for delivery in &deliveries {
+        match WebhookService::deliver(&state.executor, &client, delivery).await {
+            Ok(delivery) => sent.push(delivery),
+            Err(err) => return err.into_response(),
+        }
+    }
+    let response = ApiResponse::success(sent, "Webhook deliveries sent");
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+// 向应用的 Webhook 地址发送各事件类型的示例载荷（测试模式）
+#[utoipa::path(
+    post,
+    path = "/oauth/apps/{app_id}/webhook/test",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    request_body = Option<TestWebhookRequest>,
+    responses((status = 200, description = "Webhook deliveries sent", body = ApiResponse<Vec<WebhookDelivery>>))
+)]
+pub async fn test_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    payload: Option<Json<TestWebhookRequest>>,
+) -> impl IntoResponse {
+    let deliveries = {
+        let payload = payload.map(|Json(p)| p).unwrap_or_default();
+        let ctx = user_context(&auth_info);
+        let result = state
+            .executor
+            .transaction(move |conn| {
+                OAuthService::queue_test_deliveries(conn, &ctx, app_id, &payload)
+            })
+            .await;
+        match result {
+            Ok(deliveries) => deliveries,
+            Err(err) => return err.into_response(),
+        }
+    };
+    send_now(&state, deliveries).await
+}
+
+// 按投递记录重放应用 Webhook 的一次历史投递
+#[utoipa::path(
+    post,
+    path = "/oauth/apps/{app_id}/webhook/redeliver/{delivery_id}",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path), ("delivery_id" = Uuid, Path)),
+    responses((status = 200, description = "Webhook deliveries sent", body = ApiResponse<Vec<WebhookDelivery>>))
+)]
+pub async fn redeliver_webhook(
+    State(state): State<Arc<AppState>>,
+    Path((app_id, delivery_id)): Path<(Uuid, Uuid)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let delivery = {
+        let ctx = user_context(&auth_info);
+        let result = state
+            .executor
+            .transaction(move |conn| {
+                OAuthService::queue_redelivery(conn, &ctx, app_id, delivery_id)
+            })
+            .await;
+        match result {
+            Ok(delivery) => delivery,
+            Err(err) => return err.into_response(),
+        }
+    };
+    send_now(&state, vec![delivery]).await
+}
+
+// 校验授权请求，返回授权确认页需要展示的信息
+#[utoipa::path(
+    get,
+    path = "/oauth/authorize",
+    tag = "oauth",
+    params(AuthorizeRequest),
+    responses((status = 200, description = "Authorization request is valid", body = ApiResponse<AuthorizePreview>))
+)]
+pub async fn get_authorize(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AuthorizeRequest>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| OAuthService::authorize_preview(conn, &user_context(&auth_info), &params))
+        .await;
+    match result {
+        Ok(preview) => {
+            let response = ApiResponse::success(preview, "Authorization request is valid");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 用户同意或拒绝授权，返回跳转回第三方应用的地址
+#[utoipa::path(
+    post,
+    path = "/oauth/authorize",
+    tag = "oauth",
+    request_body = AuthorizeDecision,
+    responses((status = 200, description = "Authorization recorded", body = ApiResponse<AuthorizeRedirect>))
+)]
+pub async fn post_authorize(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<AuthorizeDecision>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| OAuthService::authorize(conn, &user_context(&auth_info), &payload))
+        .await;
+    match result {
+        Ok(redirect) => {
+            let response = ApiResponse::success(redirect, "Authorization recorded");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// OAuth token 端点（authorization_code / refresh_token）
+#[utoipa::path(
+    post,
+    path = "/oauth/token",
+    tag = "oauth",
+    request_body(content = TokenRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Access token issued", body = TokenResponse),
+        (status = 400, description = "OAuth error (`error`, `error_description`)", body = serde_json::Value),
+    ),
+    security(())
+)]
+pub async fn token(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Form(payload): Form<TokenRequest>,
+) -> Response {
+    // 令牌端点的错误需按 OAuth 规范返回，服务结果原样带出事务
+    let basic_auth = basic_client_credentials(&headers);
+    let result = state
+        .executor
+        .transaction(move |conn| Ok(OAuthService::exchange_token(conn, &payload, basic_auth)))
+        .await
+        .unwrap_or_else(|e| Err(OAuthError::Server(e)));
+    match result {
+        Ok(token) => {
+            let mut response = (StatusCode::OK, Json(token)).into_response();
+            response
+                .headers_mut()
+                .insert("Cache-Control", "no-store".parse().unwrap());
+            response
+        }
+        Err(err) => oauth_error_response(err),
+    }
+}
+
+// OAuth token 吊销端点（RFC 7009）
+#[utoipa::path(
+    post,
+    path = "/oauth/revoke",
+    tag = "oauth",
+    request_body(content = RevokeTokenRequest, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "Token revoked or unknown")),
+    security(())
+)]
+pub async fn revoke(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Form(payload): Form<RevokeTokenRequest>,
+) -> Response {
+    let (client_id, client_secret) = match basic_client_credentials(&headers) {
+        Some((id, secret)) => (Some(id), Some(secret)),
+        None => (payload.client_id.clone(), payload.client_secret.clone()),
+    };
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            Ok(OAuthService::revoke_token(
+                conn,
+                &payload.token,
+                client_id.as_deref(),
+                client_secret.as_deref(),
+            ))
+        })
+        .await
+        .unwrap_or_else(|e| Err(OAuthError::Server(e)));
+    match result {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(err) => oauth_error_response(err),
+    }
+}
+
+// 获取当前用户已授权的第三方应用
+#[utoipa::path(
+    get,
+    path = "/oauth/authorizations",
+    tag = "oauth",
+    responses((status = 200, description = "Authorized apps retrieved successfully", body = ApiResponse<Vec<AuthorizedApp>>))
+)]
+pub async fn get_authorizations(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| OAuthService::list_authorizations(conn, &user_context(&auth_info)))
+        .await;
+    match result {
+        Ok(apps) => {
+            let response = ApiResponse::success(apps, "Authorized apps retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 撤销对第三方应用的授权
+#[utoipa::path(
+    delete,
+    path = "/oauth/authorizations/{app_id}",
+    tag = "oauth",
+    params(("app_id" = Uuid, Path)),
+    responses((status = 200, description = "Authorization revoked successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn revoke_authorization(
+    State(state): State<Arc<AppState>>,
+    Path(app_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            OAuthService::revoke_authorization(conn, &user_context(&auth_info), app_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Authorization revoked successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,367 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::project::Project;
+use crate::db::models::project_cost::{
+    CreateCostEntryRequest, MemberCostRate, ProjectCostEntry, ProjectCostSummary,
+    SetMemberRateRequest, SetProjectBudgetRequest,
+};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::project_costs_service::ProjectCostsService;
+
+/// 设置项目预算（币种、金额和预警比例）；不传币种则清除预算
+#[utoipa::path(
+    put,
+    path = "/projects/{project_id}/budget",
+    tag = "project-costs",
+    params(("project_id" = Uuid, Path)),
+    request_body = SetProjectBudgetRequest,
+    responses((status = 200, description = "Project budget updated successfully", body = ApiResponse<Project>))
+)]
+pub async fn set_project_budget(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<SetProjectBudgetRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ProjectCostsService::set_budget(conn, &ctx, project_id, &payload))
+        .await;
+    match result {
+        Ok(project) => {
+            let response = ApiResponse::success(project, "Project budget updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取项目成本汇总：已花费、剩余预算、预警状态和按成员统计
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/costs",
+    tag = "project-costs",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Project costs retrieved successfully", body = ApiResponse<ProjectCostSummary>))
+)]
+pub async fn get_project_costs(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(project_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ProjectCostsService::summary(conn, &ctx, project_id))
+        .await;
+    match result {
+        Ok(summary) => {
+            let response = ApiResponse::success(summary, "Project costs retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取项目成本记录；非预算管理员只能看到自己的记录
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/cost-entries",
+    tag = "project-costs",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Cost entries retrieved successfully", body = ApiResponse<Vec<ProjectCostEntry>>))
+)]
+pub async fn get_cost_entries(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(project_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ProjectCostsService::list_entries(conn, &ctx, project_id))
+        .await;
+    match result {
+        Ok(entries) => {
+            let response = ApiResponse::success(entries, "Cost entries retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 记录工时成本，按成员当前时薪计价
+#[utoipa::path(
+    post,
+    path = "/projects/{project_id}/cost-entries",
+    tag = "project-costs",
+    params(("project_id" = Uuid, Path)),
+    request_body = CreateCostEntryRequest,
+    responses((status = 201, description = "Cost entry created successfully", body = ApiResponse<ProjectCostEntry>))
+)]
+pub async fn create_cost_entry(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateCostEntryRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ProjectCostsService::log_cost(conn, &ctx, project_id, &payload))
+        .await;
+    match result {
+        Ok(entry) => {
+            let response = ApiResponse::created(entry, "Cost entry created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除成本记录
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}/cost-entries/{entry_id}",
+    tag = "project-costs",
+    params(("project_id" = Uuid, Path), ("entry_id" = Uuid, Path)),
+    responses((status = 200, description = "Cost entry deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_cost_entry(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((project_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ProjectCostsService::delete_cost(conn, &ctx, project_id, entry_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Cost entry deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取工作区成员时薪列表
+#[utoipa::path(
+    get,
+    path = "/workspace-members/rates",
+    tag = "project-costs",
+    responses((status = 200, description = "Member rates retrieved successfully", body = ApiResponse<Vec<MemberCostRate>>))
+)]
+pub async fn get_member_rates(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ProjectCostsService::list_rates(conn, &ctx))
+        .await;
+    match result {
+        Ok(rates) => {
+            let response = ApiResponse::success(rates, "Member rates retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 设置成员时薪，只影响之后记录的成本
+#[utoipa::path(
+    put,
+    path = "/workspace-members/{user_id}/rate",
+    tag = "project-costs",
+    params(("user_id" = Uuid, Path)),
+    request_body = SetMemberRateRequest,
+    responses((status = 200, description = "Member rate updated successfully", body = ApiResponse<MemberCostRate>))
+)]
+pub async fn set_member_rate(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<SetMemberRateRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ProjectCostsService::set_rate(conn, &ctx, user_id, &payload))
+        .await;
+    match result {
+        Ok(rate) => {
+            let response = ApiResponse::success(rate, "Member rate updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除成员时薪
+#[utoipa::path(
+    delete,
+    path = "/workspace-members/{user_id}/rate",
+    tag = "project-costs",
+    params(("user_id" = Uuid, Path)),
+    responses((status = 200, description = "Member rate deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_member_rate(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ProjectCostsService::delete_rate(conn, &ctx, user_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Member rate deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -7,38 +7,42 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::db::models::project_status::ProjectStatusInfo;
 use crate::db::models::*;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::context::RequestContext;
+use crate::services::permission_service::{Permission, PermissionService};
 use crate::services::project_statuses_service::ProjectStatusesService;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ProjectStatusQuery {
     pub name: Option<String>,
     pub category: Option<String>,
 }
 
 /// Get all project statuses for a workspace
+#[utoipa::path(
+    get,
+    path = "/project-statuses",
+    tag = "project-statuses",
+    params(ProjectStatusQuery),
+    responses((status = 200, description = "Project statuses retrieved successfully", body = ApiResponse<Vec<ProjectStatusInfo>>))
+)]
 pub async fn get_project_statuses(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Query(_params): Query<ProjectStatusQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -50,7 +54,11 @@ pub async fn get_project_statuses(
         }
     };
 
-    match ProjectStatusesService::list(&mut conn, &ctx) {
+    let result = state
+        .executor
+        .read(move |conn| ProjectStatusesService::list(conn, &ctx))
+        .await;
+    match result {
         Ok(list) => {
             let response = ApiResponse::success(list, "Project statuses retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -59,7 +67,7 @@ pub async fn get_project_statuses(
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct CreateProjectStatusRequest {
     pub name: String,
     pub description: Option<String>,
@@ -68,24 +76,24 @@ pub struct CreateProjectStatusRequest {
 }
 
 /// Create a new project status
+#[utoipa::path(
+    post,
+    path = "/project-statuses",
+    tag = "project-statuses",
+    request_body = CreateProjectStatusRequest,
+    responses((status = 201, description = "Project status created successfully", body = ApiResponse<ProjectStatusInfo>))
+)]
 pub async fn create_project_status(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<CreateProjectStatusRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -113,7 +121,14 @@ pub async fn create_project_status(
         category: category_enum,
     };
 
-    match ProjectStatusesService::create(&mut conn, &ctx, &model_request) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageProjectStatuses)?;
+            ProjectStatusesService::create(conn, &ctx, &model_request)
+        })
+        .await;
+    match result {
         Ok(status) => {
             let response = ApiResponse::created(status, "Project status created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -123,24 +138,24 @@ pub async fn create_project_status(
 }
 
 /// Get a specific project status by ID
+#[utoipa::path(
+    get,
+    path = "/project-statuses/{status_id}",
+    tag = "project-statuses",
+    params(("status_id" = Uuid, Path)),
+    responses((status = 200, description = "Project status retrieved successfully", body = ApiResponse<ProjectStatusInfo>))
+)]
 pub async fn get_project_status_by_id(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(status_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -152,7 +167,11 @@ pub async fn get_project_status_by_id(
         }
     };
 
-    match ProjectStatusesService::get_by_id(&mut conn, &ctx, status_id) {
+    let result = state
+        .executor
+        .read(move |conn| ProjectStatusesService::get_by_id(conn, &ctx, status_id))
+        .await;
+    match result {
         Ok(status) => {
             let response = ApiResponse::success(status, "Project status retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -161,7 +180,7 @@ pub async fn get_project_status_by_id(
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct UpdateProjectStatusRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -170,25 +189,26 @@ pub struct UpdateProjectStatusRequest {
 }
 
 /// Update a project status
+#[utoipa::path(
+    put,
+    path = "/project-statuses/{status_id}",
+    tag = "project-statuses",
+    params(("status_id" = Uuid, Path)),
+    request_body = UpdateProjectStatusRequest,
+    responses((status = 200, description = "Project status updated successfully", body = ApiResponse<ProjectStatusInfo>))
+)]
 pub async fn update_project_status(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(status_id): Path<Uuid>,
     Json(payload): Json<UpdateProjectStatusRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -200,7 +220,14 @@ pub async fn update_project_status(
         }
     };
 
-    match ProjectStatusesService::update(&mut conn, &ctx, status_id, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageProjectStatuses)?;
+            ProjectStatusesService::update(conn, &ctx, status_id, &payload)
+        })
+        .await;
+    match result {
         Ok(status) => {
             let response = ApiResponse::success(status, "Project status updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -210,24 +237,24 @@ pub async fn update_project_status(
 }
 
 /// Delete a project status
+#[utoipa::path(
+    delete,
+    path = "/project-statuses/{status_id}",
+    tag = "project-statuses",
+    params(("status_id" = Uuid, Path)),
+    responses((status = 200, description = "Project status deleted successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn delete_project_status(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(status_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -239,7 +266,14 @@ pub async fn delete_project_status(
         }
     };
 
-    match ProjectStatusesService::delete(&mut conn, &ctx, status_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageProjectStatuses)?;
+            ProjectStatusesService::delete(conn, &ctx, status_id)
+        })
+        .await;
+    match result {
         Ok(()) => {
             let response = ApiResponse::<()>::ok("Project status deleted successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -7,20 +7,27 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::db::models::*;
+use crate::middleware::asset_region::AssetRegionHint;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::context::RequestContext;
+use crate::services::field_selection::FieldSelection;
+use crate::services::permission_service::{Permission, PermissionService};
 use crate::services::projects_service::ProjectsService;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ProjectQuery {
     pub search: Option<String>,
     pub owner_id: Option<Uuid>,
+    /// 逗号分隔的字段列表，只返回这些字段（`id` 总会返回）
+    pub fields: Option<String>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct CreateProjectRequest {
     pub name: String,
     pub project_key: String,
@@ -30,7 +37,7 @@ pub struct CreateProjectRequest {
     pub priority: Option<String>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct UpdateProjectRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -41,24 +48,24 @@ pub struct UpdateProjectRequest {
 }
 
 /// 创建项目
+#[utoipa::path(
+    post,
+    path = "/projects",
+    tag = "projects",
+    request_body = CreateProjectRequest,
+    responses((status = 201, description = "Project created successfully", body = ApiResponse<Project>))
+)]
 pub async fn create_project(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<CreateProjectRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -79,7 +86,14 @@ pub async fn create_project(
         priority: payload.priority.map(|p| p.parse().unwrap_or_default()),
         roadmap_id: None, // TODO: Add roadmap_id to route request
     };
-    match ProjectsService::create(&mut conn, &ctx, &create_req) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::CreateProject)?;
+            ProjectsService::create(conn, &ctx, &create_req)
+        })
+        .await;
+    match result {
         Ok(project) => {
             let response = ApiResponse::created(project, "Project created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -89,24 +103,25 @@ pub async fn create_project(
 }
 
 /// 获取项目列表
+#[utoipa::path(
+    get,
+    path = "/projects",
+    tag = "projects",
+    params(ProjectQuery),
+    responses((status = 200, description = "Projects retrieved successfully", body = ApiResponse<serde_json::Value>))
+)]
 pub async fn get_projects(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Query(params): Query<ProjectQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -118,13 +133,20 @@ pub async fn get_projects(
         }
     };
 
-    match ProjectsService::list_infos(
-        &mut conn,
-        &ctx,
-        &state.asset_helper,
-        params.search,
-        params.owner_id,
-    ) {
+    let fields = match FieldSelection::from_query(params.fields.as_deref(), ProjectInfo::FIELDS) {
+        Ok(fields) => fields,
+        Err(err) => return err.into_response(),
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| {
+            ProjectsService::list_infos(conn, &ctx, &asset_helper, params.search, params.owner_id)
+                .and_then(|projects| fields.project(&projects))
+        })
+        .await;
+    match result {
         Ok(project_list_response) => {
             let response =
                 ApiResponse::success(project_list_response, "Projects retrieved successfully");
@@ -134,26 +156,74 @@ pub async fn get_projects(
     }
 }
 
+/// 项目状态看板：按状态分类分列，附带负责人、问题进度、健康度和目标日期；
+/// 分类、状态和负责人筛选均支持逗号分隔的多选
+#[utoipa::path(
+    get,
+    path = "/projects/board",
+    tag = "projects",
+    params(ProjectBoardQuery),
+    responses((status = 200, description = "Project board retrieved successfully", body = ApiResponse<ProjectBoard>))
+)]
+pub async fn get_project_board(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Query(params): Query<ProjectBoardQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| ProjectsService::board(conn, &ctx, &asset_helper, &params))
+        .await;
+    match result {
+        Ok(board) => {
+            let response = ApiResponse::success(board, "Project board retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
 /// 更新项目
+#[utoipa::path(
+    put,
+    path = "/projects/{project_id}",
+    tag = "projects",
+    params(("project_id" = Uuid, Path)),
+    request_body = UpdateProjectRequest,
+    responses((status = 200, description = "Project updated successfully", body = ApiResponse<ProjectInfo>))
+)]
 pub async fn update_project(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Path(project_id): Path<Uuid>,
     Json(payload): Json<UpdateProjectRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -183,13 +253,16 @@ pub async fn update_project(
         priority: priority_enum,
     };
 
-    match ProjectsService::update(
-        &mut conn,
-        &ctx,
-        &state.asset_helper,
-        project_id,
-        &model_request,
-    ) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateProject)?;
+
+            ProjectsService::update(conn, &ctx, &asset_helper, project_id, &model_request)
+        })
+        .await;
+    match result {
         Ok(project) => {
             let response = ApiResponse::success(project, "Project updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -199,24 +272,24 @@ pub async fn update_project(
 }
 
 /// 删除项目
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}",
+    tag = "projects",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Project deleted successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn delete_project(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(project_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -228,7 +301,14 @@ pub async fn delete_project(
         }
     };
 
-    match ProjectsService::delete(&mut conn, &ctx, project_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::DeleteProject)?;
+            ProjectsService::delete(conn, &ctx, project_id)
+        })
+        .await;
+    match result {
         Ok(()) => {
             let response = ApiResponse::success((), "Project deleted successfully");
             (StatusCode::OK, Json(response)).into_response()
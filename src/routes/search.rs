@@ -0,0 +1,65 @@
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::search::{IssueSearchFilters, IssueSearchHit, IssueSearchParams};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::search_service::SearchService;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+
+// 全文搜索问题
+#[utoipa::path(
+    get,
+    path = "/search/issues",
+    tag = "search",
+    params(IssueSearchParams),
+    responses((status = 200, description = "Issues searched successfully", body = ApiResponse<Vec<IssueSearchHit>>))
+)]
+pub async fn search_issues(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<IssueSearchParams>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let filters = IssueSearchFilters {
+        team_id: params.team_id,
+        project_id: params.project_id,
+        assignee_id: params.assignee_id,
+        priority: params.priority,
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| {
+            SearchService::search_issues(conn, &ctx, &params.q, &filters, params.limit)
+        })
+        .await;
+    match result {
+        Ok(hits) => {
+            let response = ApiResponse::success(hits, "Issues searched successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,281 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::api_token::{ApiToken, CreateApiTokenRequest, CreatedApiToken};
+use crate::db::models::service_account::{CreateServiceAccountRequest, ServiceAccount};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::service_accounts_service::ServiceAccountsService;
+
+/// 获取当前工作空间的服务账号列表，仅管理员可用
+#[utoipa::path(
+    get,
+    path = "/service-accounts",
+    tag = "service-accounts",
+    responses((status = 200, description = "Service accounts retrieved successfully", body = ApiResponse<Vec<ServiceAccount>>))
+)]
+pub async fn get_service_accounts(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ServiceAccountsService::list(conn, &ctx))
+        .await;
+    match result {
+        Ok(accounts) => {
+            let response =
+                ApiResponse::success(accounts, "Service accounts retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建服务账号；服务账号不能登录，只能通过带 scope 的 API 令牌调用接口
+///
+/// 未指定 username 时根据名称生成，role 默认为 member（不能为 owner）
+#[utoipa::path(
+    post,
+    path = "/service-accounts",
+    tag = "service-accounts",
+    request_body = CreateServiceAccountRequest,
+    responses((status = 201, description = "Service account created successfully", body = ApiResponse<ServiceAccount>))
+)]
+pub async fn create_service_account(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateServiceAccountRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ServiceAccountsService::create(conn, &ctx, &payload))
+        .await;
+    match result {
+        Ok(account) => {
+            let response = ApiResponse::created(account, "Service account created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 停用服务账号：吊销它的所有令牌，之前的操作记录仍归属于该账号
+#[utoipa::path(
+    delete,
+    path = "/service-accounts/{account_id}",
+    tag = "service-accounts",
+    params(("account_id" = Uuid, Path)),
+    responses((status = 200, description = "Service account disabled successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn disable_service_account(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(account_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ServiceAccountsService::disable(conn, &ctx, account_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Service account disabled successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取服务账号的 API 令牌列表（不含令牌明文）
+#[utoipa::path(
+    get,
+    path = "/service-accounts/{account_id}/tokens",
+    tag = "service-accounts",
+    params(("account_id" = Uuid, Path)),
+    responses((status = 200, description = "API tokens retrieved successfully", body = ApiResponse<Vec<ApiToken>>))
+)]
+pub async fn get_service_account_tokens(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(account_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ServiceAccountsService::list_tokens(conn, &ctx, account_id))
+        .await;
+    match result {
+        Ok(tokens) => {
+            let response = ApiResponse::success(tokens, "API tokens retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 为服务账号创建 API 令牌；必须指定 scopes，令牌明文只在创建时返回一次
+#[utoipa::path(
+    post,
+    path = "/service-accounts/{account_id}/tokens",
+    tag = "service-accounts",
+    params(("account_id" = Uuid, Path)),
+    request_body = CreateApiTokenRequest,
+    responses((status = 201, description = "API token created successfully", body = ApiResponse<CreatedApiToken>))
+)]
+pub async fn create_service_account_token(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(account_id): Path<Uuid>,
+    Json(payload): Json<CreateApiTokenRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ServiceAccountsService::create_token(conn, &ctx, account_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(token) => {
+            let response = ApiResponse::created(token, "API token created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 吊销服务账号的 API 令牌
+#[utoipa::path(
+    delete,
+    path = "/service-accounts/{account_id}/tokens/{token_id}",
+    tag = "service-accounts",
+    params(("account_id" = Uuid, Path), ("token_id" = Uuid, Path)),
+    responses((status = 200, description = "API token revoked successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn revoke_service_account_token(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path((account_id, token_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            ServiceAccountsService::revoke_token(conn, &ctx, account_id, token_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("API token revoked successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
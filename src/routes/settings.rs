@@ -0,0 +1,56 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::audit::{SettingsHistoryEntry, SettingsHistoryQuery};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::settings_history_service::SettingsHistoryService;
+
+// 查询工作区、团队和工作流设置的变更记录，可按对象、操作人和时间筛选（仅管理员）
+#[utoipa::path(
+    get,
+    path = "/settings/history",
+    tag = "settings",
+    params(SettingsHistoryQuery),
+    responses((status = 200, description = "Settings history retrieved successfully", body = ApiResponse<Vec<SettingsHistoryEntry>>))
+)]
+pub async fn get_settings_history(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SettingsHistoryQuery>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| SettingsHistoryService::list(conn, &ctx, &params))
+        .await;
+    match result {
+        Ok(history) => {
+            let response = ApiResponse::success(history, "Settings history retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,97 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::sync::{SyncChanges, SyncChangesQuery, SyncSnapshot};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::sync_service::SyncService;
+
+// 获取离线同步快照（我的问题、进行中的周期、团队、标签）
+#[utoipa::path(
+    get,
+    path = "/sync/snapshot",
+    tag = "sync",
+    responses((status = 200, description = "Snapshot retrieved successfully", body = ApiResponse<SyncSnapshot>))
+)]
+pub async fn get_snapshot(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| SyncService::snapshot(conn, &ctx))
+        .await;
+    match result {
+        Ok(snapshot) => {
+            let response = ApiResponse::success(snapshot, "Snapshot retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取自上次同步以来的增量变更
+#[utoipa::path(
+    get,
+    path = "/sync/changes",
+    tag = "sync",
+    params(SyncChangesQuery),
+    responses((status = 200, description = "Changes retrieved successfully", body = ApiResponse<SyncChanges>))
+)]
+pub async fn get_changes(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SyncChangesQuery>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| SyncService::changes(conn, &ctx, params.since))
+        .await;
+    match result {
+        Ok(changes) => {
+            let response = ApiResponse::success(changes, "Changes retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
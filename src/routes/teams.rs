@@ -1,73 +1,89 @@
-use crate::db::models::{ApiResponse, ErrorDetail, TeamInfo, TeamMemberInfo};
-use crate::db::{DbPool, models::*};
+use crate::db::models::{ApiResponse, EmptyData, ErrorDetail, TeamInfo, TeamMemberInfo};
+use crate::db::{DbExecutor, models::*};
 use crate::middleware::auth::AuthUserInfo;
+use crate::services::auto_close_service::AutoCloseService;
 use crate::services::context::RequestContext;
+use crate::services::issues_service::IssuesService;
+use crate::services::permission_service::{Permission, PermissionService};
+use crate::services::team_hierarchy_service::TeamHierarchyService;
 use crate::services::{team_members_service::TeamMembersService, teams_service::TeamsService};
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
 };
 use diesel::prelude::*;
 use serde::Deserialize;
-use std::sync::Arc;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // 请求体定义
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateTeamRequest {
     pub name: String,
     pub team_key: String,
     pub description: Option<String>,
     pub icon_url: Option<String>,
     pub is_private: bool,
+    /// 上级团队；为空时创建顶级团队
+    #[serde(default)]
+    pub parent_team_id: Option<Uuid>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateTeamRequest {
     pub name: Option<String>,
     pub team_key: Option<String>,
     pub description: Option<String>,
     pub icon_url: Option<String>,
     pub is_private: Option<bool>,
+    /// 估算刻度：fibonacci、tshirt 或 linear；已有估算保留原点数
+    #[serde(default)]
+    pub estimate_scale: Option<String>,
+    /// 未指定负责人的新 Issue 的分配方式：none、default_assignee 或 round_robin（按加入顺序在成员间轮流分配）
+    #[serde(default)]
+    pub assignment_mode: Option<String>,
+    /// 默认负责人，须为团队成员；传 null 清除
+    #[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]
+    pub default_assignee_id: Option<Option<Uuid>>,
 }
 
-#[derive(Deserialize, Clone, Copy)]
+#[derive(Deserialize, Clone, Copy, ToSchema)]
 pub enum TeamRole {
     Admin,
     Member,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct AddTeamMemberRequest {
     pub user_id: Uuid,
     pub role: TeamRole,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateTeamMemberRequest {
     pub role: TeamRole,
 }
 
 /// 创建团队
+#[utoipa::path(
+    post,
+    path = "/teams",
+    tag = "teams",
+    request_body = CreateTeamRequest,
+    responses((status = 201, description = "Team created successfully", body = ApiResponse<Option<Team>>))
+)]
 pub async fn create_team(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Json(payload): Json<CreateTeamRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
     let req = CreateTeamRequest {
@@ -76,9 +92,16 @@ pub async fn create_team(
         description: payload.description,
         icon_url: payload.icon_url,
         is_private: payload.is_private,
+        parent_team_id: payload.parent_team_id,
     };
 
-    match TeamsService::create(&mut conn, &ctx, &req) {
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeams)?;
+            TeamsService::create(conn, &ctx, &req)
+        })
+        .await;
+    match result {
         Ok(team) => {
             let response = ApiResponse::success(Some(team), "Team created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -96,25 +119,28 @@ pub async fn create_team(
     }
 }
 
-/// 获取团队列表
+/// 获取团队列表（不含当前用户无权查看的私有团队）
+#[utoipa::path(
+    get,
+    path = "/teams",
+    tag = "teams",
+    responses((status = 200, description = "Teams retrieved successfully", body = ApiResponse<Option<Vec<Team>>>))
+)]
 pub async fn get_teams(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
-    match TeamsService::list(&mut conn, &ctx) {
+    let result = executor
+        .read(move |conn| TeamHierarchyService::list_visible(conn, &ctx))
+        .await;
+    match result {
         Ok(list) => {
             let response = ApiResponse::success(Some(list), "Teams retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -127,25 +153,29 @@ pub async fn get_teams(
 }
 
 /// 获取指定团队
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Team retrieved successfully", body = ApiResponse<Option<Team>>))
+)]
 pub async fn get_team(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
-    match TeamsService::get(&mut conn, &ctx, team_id) {
+    let result = executor
+        .read(move |conn| TeamHierarchyService::require_visible(conn, &ctx, team_id))
+        .await;
+    match result {
         Ok(team) => {
             let response = ApiResponse::success(Some(team), "Team retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -158,23 +188,25 @@ pub async fn get_team(
 }
 
 /// 更新团队
+#[utoipa::path(
+    put,
+    path = "/teams/{team_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    request_body = UpdateTeamRequest,
+    responses((status = 200, description = "Team updated successfully", body = ApiResponse<Option<Team>>))
+)]
 pub async fn update_team(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
     Json(payload): Json<UpdateTeamRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
     let req = UpdateTeamRequest {
@@ -183,9 +215,19 @@ pub async fn update_team(
         description: payload.description,
         icon_url: payload.icon_url,
         is_private: payload.is_private,
+        estimate_scale: payload.estimate_scale,
+        assignment_mode: payload.assignment_mode,
+        default_assignee_id: payload.default_assignee_id,
     };
 
-    match TeamsService::update(&mut conn, &ctx, team_id, &req) {
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeams)?;
+
+            TeamsService::update(conn, &ctx, team_id, &req)
+        })
+        .await;
+    match result {
         Ok(updated_team) => {
             let response = ApiResponse::success(Some(updated_team), "Team updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -208,25 +250,56 @@ pub async fn update_team(
 }
 
 /// 删除团队
+///
+/// 分两步：不带 `confirmation_token` 时返回 202 和短时有效的确认令牌，
+/// 以及将被删除的事项、成员、周期数量；带上令牌再次调用才真正删除。
+#[utoipa::path(
+    delete,
+    path = "/teams/{team_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), DeleteConfirmationQuery),
+    responses((status = 200, description = "Repeat the request with the confirmation token to delete the team", body = ApiResponse<DeleteConfirmation>))
+)]
 pub async fn delete_team(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
+    Query(query): Query<DeleteConfirmationQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap(),
         idempotency_key: None,
+        channel: auth_info.channel,
+    };
+
+    let Some(token) = query.confirmation_token else {
+        let result = executor
+            .transaction(move |conn| {
+                PermissionService::require(conn, &ctx, Permission::ManageTeams)?;
+                TeamsService::request_delete(conn, &ctx, team_id)
+            })
+            .await;
+        return match result {
+            Ok(confirmation) => {
+                let response = ApiResponse::success(
+                    confirmation,
+                    "Repeat the request with the confirmation token to delete the team",
+                );
+                (StatusCode::ACCEPTED, Json(response)).into_response()
+            }
+            Err(crate::error::AppError::NotFound { .. }) => {
+                let response = ApiResponse::<()>::not_found("Team not found");
+                (StatusCode::NOT_FOUND, Json(response)).into_response()
+            }
+            Err(err) => err.into_response(),
+        };
     };
 
-    match TeamsService::delete(&mut conn, &ctx, team_id) {
+    let result = executor
+        .transaction(move |conn| TeamsService::delete(conn, &ctx, team_id, &token))
+        .await;
+    match result {
         Ok(_) => {
             let response = ApiResponse::<()>::success((), "Team deleted successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -235,40 +308,45 @@ pub async fn delete_team(
             let response = ApiResponse::<()>::not_found("Team not found");
             (StatusCode::NOT_FOUND, Json(response)).into_response()
         }
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Failed to delete team");
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response()
-        }
+        Err(err) => err.into_response(),
     }
 }
 
 /// 添加团队成员
+#[utoipa::path(
+    post,
+    path = "/teams/{team_id}/members",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    request_body = AddTeamMemberRequest,
+    responses((status = 201, description = "Team member added successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn add_team_member(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
     Json(payload): Json<AddTeamMemberRequest>,
 ) -> impl IntoResponse {
     let current_workspace_id = auth_info.current_workspace_id.unwrap();
 
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: current_workspace_id,
         idempotency_key: None,
+        channel: auth_info.channel,
     };
+
     let role_str = match payload.role {
         TeamRole::Admin => "admin",
         TeamRole::Member => "member",
     };
-    match TeamMembersService::add(&mut conn, &ctx, team_id, payload.user_id, role_str) {
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeamMembers)?;
+            TeamMembersService::add(conn, &ctx, team_id, payload.user_id, role_str)
+        })
+        .await;
+    match result {
         Ok(_) => {
             let response = ApiResponse::<()>::success((), "Team member added successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -293,27 +371,29 @@ pub async fn add_team_member(
 }
 
 /// 获取团队成员列表
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/members",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Team members retrieved successfully", body = ApiResponse<Option<Vec<TeamMemberInfo>>>))
+)]
 pub async fn get_team_members_list(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
-    let members = match crate::services::team_members_service::TeamMembersService::list(
-        &mut conn, &ctx, team_id,
-    ) {
+    let result = executor
+        .read(move |conn| TeamMembersService::list(conn, &ctx, team_id))
+        .await;
+    let members = match result {
         Ok(results) => results,
         Err(crate::error::AppError::NotFound { .. }) => {
             let response = ApiResponse::<()>::not_found("Team not found");
@@ -345,32 +425,41 @@ pub async fn get_team_members_list(
 }
 
 /// 更新团队成员
+#[utoipa::path(
+    put,
+    path = "/teams/{team_id}/members/{user_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    request_body = UpdateTeamMemberRequest,
+    responses((status = 200, description = "Team member updated successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn update_team_member(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Path((team_id, member_user_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<UpdateTeamMemberRequest>,
 ) -> impl IntoResponse {
     let current_workspace_id = auth_info.current_workspace_id.unwrap();
 
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: current_workspace_id,
         idempotency_key: None,
+        channel: auth_info.channel,
     };
+
     let role_str = match payload.role {
         TeamRole::Admin => "admin",
         TeamRole::Member => "member",
     };
-    match TeamMembersService::update(&mut conn, &ctx, team_id, member_user_id, role_str) {
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeamMembers)?;
+
+            TeamMembersService::update(conn, &ctx, team_id, member_user_id, role_str)
+        })
+        .await;
+    match result {
         Ok(_) => {
             let response = ApiResponse::<()>::success((), "Team member updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -387,27 +476,34 @@ pub async fn update_team_member(
 }
 
 /// 移除团队成员
+#[utoipa::path(
+    delete,
+    path = "/teams/{team_id}/members/{user_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses((status = 200, description = "Team member removed successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn remove_team_member(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
     Path((team_id, member_user_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
     let current_workspace_id = auth_info.current_workspace_id.unwrap();
 
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: current_workspace_id,
         idempotency_key: None,
+        channel: auth_info.channel,
     };
-    match TeamMembersService::remove(&mut conn, &ctx, team_id, member_user_id) {
+
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeamMembers)?;
+            TeamMembersService::remove(conn, &ctx, team_id, member_user_id)
+        })
+        .await;
+    match result {
         Ok(_) => {
             let response = ApiResponse::<()>::success((), "Team member removed successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -424,31 +520,33 @@ pub async fn remove_team_member(
 }
 
 /// 获取用户所属的团队列表
+#[utoipa::path(
+    get,
+    path = "/user/teams",
+    tag = "teams",
+    responses((status = 200, description = "User teams retrieved successfully", body = ApiResponse<Option<Vec<TeamInfo>>>))
+)]
 pub async fn get_user_teams(
-    State(pool): State<Arc<DbPool>>,
+    State(executor): State<DbExecutor>,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
     let current_user_id = auth_info.user.id;
     let current_workspace_id = auth_info.current_workspace_id.unwrap();
 
-    let mut conn = match pool.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     use crate::schema::team_members::dsl::*;
     use crate::schema::teams::dsl as team_dsl;
 
-    let user_teams = match team_members
-        .filter(user_id.eq(current_user_id))
-        .inner_join(team_dsl::teams.on(team_dsl::id.eq(team_id)))
-        .filter(team_dsl::workspace_id.eq(current_workspace_id))
-        .select((TeamMember::as_select(), Team::as_select()))
-        .load::<(TeamMember, Team)>(&mut conn)
-    {
+    let result = executor
+        .read(move |conn| {
+            Ok(team_members
+                .filter(user_id.eq(current_user_id))
+                .inner_join(team_dsl::teams.on(team_dsl::id.eq(team_id)))
+                .filter(team_dsl::workspace_id.eq(current_workspace_id))
+                .select((TeamMember::as_select(), Team::as_select()))
+                .load::<(TeamMember, Team)>(conn)?)
+        })
+        .await;
+    let user_teams = match result {
         Ok(results) => results
             .into_iter()
             .map(|(member, team)| TeamInfo {
@@ -470,3 +568,355 @@ pub async fn get_user_teams(
     let response = ApiResponse::success(Some(user_teams), "User teams retrieved successfully");
     (StatusCode::OK, Json(response)).into_response()
 }
+
+/// 获取团队的 issue 自动关闭策略
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/auto-close-policy",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Auto-close policy retrieved successfully", body = ApiResponse<AutoClosePolicyResponse>))
+)]
+pub async fn get_auto_close_policy(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = executor
+        .read(move |conn| AutoCloseService::get(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(policy) => {
+            let response = ApiResponse::success(policy, "Auto-close policy retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建或更新团队的 issue 自动关闭策略
+#[utoipa::path(
+    put,
+    path = "/teams/{team_id}/auto-close-policy",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    request_body = UpsertAutoClosePolicyRequest,
+    responses((status = 200, description = "Auto-close policy saved successfully", body = ApiResponse<AutoClosePolicyResponse>))
+)]
+pub async fn put_auto_close_policy(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<UpsertAutoClosePolicyRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeams)?;
+            AutoCloseService::upsert(conn, &ctx, team_id, &payload)
+        })
+        .await;
+    match result {
+        Ok(policy) => {
+            let response = ApiResponse::success(policy, "Auto-close policy saved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除团队的 issue 自动关闭策略
+#[utoipa::path(
+    delete,
+    path = "/teams/{team_id}/auto-close-policy",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Auto-close policy deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_auto_close_policy(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeams)?;
+            AutoCloseService::delete(conn, &ctx, team_id)
+        })
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Auto-close policy deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 调整团队的上级团队；parent_team_id 为空时改为顶级团队
+#[utoipa::path(
+    put,
+    path = "/teams/{team_id}/parent",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    request_body = SetParentTeamRequest,
+    responses((status = 200, description = "Parent team updated successfully", body = ApiResponse<Team>))
+)]
+pub async fn set_parent_team(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<SetParentTeamRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageTeams)?;
+            TeamHierarchyService::set_parent(conn, &ctx, team_id, payload.parent_team_id)
+        })
+        .await;
+    match result {
+        Ok(team) => {
+            let response = ApiResponse::success(team, "Parent team updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取团队及其子团队的层级结构，issue 数量逐级向上汇总
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/hierarchy",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Team hierarchy retrieved successfully", body = ApiResponse<TeamHierarchyNode>))
+)]
+pub async fn get_team_hierarchy(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = executor
+        .read(move |conn| TeamHierarchyService::tree(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(tree) => {
+            let response = ApiResponse::success(tree, "Team hierarchy retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取团队的有效成员，包括从上级团队继承的成员
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/effective-members",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Effective team members retrieved successfully", body = ApiResponse<Vec<EffectiveTeamMember>>))
+)]
+pub async fn get_effective_team_members(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = executor
+        .read(move |conn| TeamHierarchyService::effective_members(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(members) => {
+            let response =
+                ApiResponse::success(members, "Effective team members retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取团队看板，默认汇总所有子团队的 issue，按工作流状态类别分列
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/board",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), TeamBoardQuery),
+    responses((status = 200, description = "Team board retrieved successfully", body = ApiResponse<TeamBoard>))
+)]
+pub async fn get_team_board(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+    Query(query): Query<TeamBoardQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let include_sub_teams = query.include_sub_teams.unwrap_or(true);
+    let result = executor
+        .read(move |conn| TeamHierarchyService::board(conn, &ctx, team_id, include_sub_teams))
+        .await;
+    match result {
+        Ok(board) => {
+            let response = ApiResponse::success(board, "Team board retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取团队的分诊队列：处于分诊（triage）状态、未归档的 issue，最早创建的在前
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/triage",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Triage queue retrieved successfully", body = ApiResponse<Vec<IssueResponse>>))
+)]
+pub async fn get_team_triage(
+    State(executor): State<DbExecutor>,
+    auth_info: AuthUserInfo,
+    Path(team_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = executor
+        .read(move |conn| IssuesService::list_triage(conn, &ctx, team_id))
+        .await;
+    match result {
+        Ok(issues) => {
+            let response = ApiResponse::success(issues, "Triage queue retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
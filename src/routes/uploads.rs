@@ -0,0 +1,264 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::AppState;
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::attachment::CreateAttachmentRequest;
+use crate::db::models::upload_session::UploadSessionResponse;
+use crate::middleware::asset_region::AssetRegionHint;
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::permission_service::{Permission, PermissionService};
+use crate::services::upload_sessions_service::UploadSessionsService;
+
+/// Offset the appended chunk starts at, as in the tus protocol
+pub const UPLOAD_OFFSET_HEADER: &str = "upload-offset";
+
+// 创建可续传的附件上传会话
+#[utoipa::path(
+    post,
+    path = "/issues/{issue_id}/uploads",
+    tag = "uploads",
+    params(("issue_id" = Uuid, Path)),
+    request_body = CreateAttachmentRequest,
+    responses((status = 201, description = "Upload session created", body = ApiResponse<UploadSessionResponse>))
+)]
+pub async fn create_upload_session(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(issue_id): Path<Uuid>,
+    Json(payload): Json<CreateAttachmentRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let permission_ctx = ctx.clone();
+    if let Err(err) = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &permission_ctx, Permission::UpdateIssue)
+        })
+        .await
+    {
+        return err.into_response();
+    }
+
+    // 存储调用不占用数据库连接，会话记录单独写入
+    match UploadSessionsService::create(
+        &state.executor,
+        &ctx,
+        state.storage.as_ref(),
+        state.config.upload_session_max_bytes,
+        state.config.upload_session_ttl_secs,
+        issue_id,
+        payload,
+    )
+    .await
+    {
+        Ok(session) => {
+            let response = ApiResponse::created(session, "Upload session created");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 获取上传会话的当前偏移量，用于断点续传
+#[utoipa::path(
+    get,
+    path = "/uploads/{upload_id}",
+    tag = "uploads",
+    params(("upload_id" = Uuid, Path)),
+    responses((status = 200, description = "Upload session retrieved successfully", body = ApiResponse<UploadSessionResponse>))
+)]
+pub async fn get_upload_session(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Path(upload_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let storage = state.storage.clone();
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateIssue)?;
+            UploadSessionsService::get(conn, &ctx, storage.as_ref(), &asset_helper, upload_id)
+        })
+        .await;
+    match result {
+        Ok(session) => {
+            let response = ApiResponse::success(session, "Upload session retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 在 Upload-Offset 处追加一个分片，最后一个分片完成上传并生成附件
+#[utoipa::path(
+    patch,
+    path = "/uploads/{upload_id}",
+    tag = "uploads",
+    params(("upload_id" = Uuid, Path)),
+    request_body(content = Vec<u8>, content_type = "application/octet-stream"),
+    responses((status = 200, description = "Chunk appended successfully", body = ApiResponse<UploadSessionResponse>))
+)]
+pub async fn append_upload_chunk(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Path(upload_id): Path<Uuid>,
+    headers: HeaderMap,
+    chunk: Bytes,
+) -> impl IntoResponse {
+    let Some(offset) = headers
+        .get(UPLOAD_OFFSET_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<i64>().ok())
+    else {
+        let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+            field: Some("Upload-Offset".to_string()),
+            code: "INVALID_UPLOAD_OFFSET".to_string(),
+            message: "Upload-Offset header must be a byte offset".to_string(),
+        }]);
+        return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+    };
+
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let permission_ctx = ctx.clone();
+    if let Err(err) = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &permission_ctx, Permission::UpdateIssue)
+        })
+        .await
+    {
+        return err.into_response();
+    }
+
+    match UploadSessionsService::append_chunk(
+        &state.executor,
+        &ctx,
+        state.storage.as_ref(),
+        &state.locks,
+        &state.asset_helper.for_region(region.as_deref()),
+        state.config.upload_session_ttl_secs,
+        upload_id,
+        offset,
+        chunk,
+    )
+    .await
+    {
+        Ok(session) => {
+            let response = ApiResponse::success(session, "Chunk appended successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 取消上传会话并丢弃已上传的分片
+#[utoipa::path(
+    delete,
+    path = "/uploads/{upload_id}",
+    tag = "uploads",
+    params(("upload_id" = Uuid, Path)),
+    responses((status = 200, description = "Upload session cancelled", body = ApiResponse<EmptyData>))
+)]
+pub async fn cancel_upload_session(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(upload_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let permission_ctx = ctx.clone();
+    if let Err(err) = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &permission_ctx, Permission::UpdateIssue)
+        })
+        .await
+    {
+        return err.into_response();
+    }
+
+    match UploadSessionsService::cancel(&state.executor, &ctx, state.storage.as_ref(), upload_id)
+        .await
+    {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Upload session cancelled");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -1,51 +1,66 @@
 use crate::AppState;
 use crate::db::models::api::ApiResponse;
+use crate::db::models::auth::UserProfile;
+use crate::db::models::user_preference::{UpdateUserPreferencesRequest, UserPreferences};
+use crate::middleware::asset_region::AssetRegionHint;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::auth_service::AuthService;
 use crate::services::context::RequestContext;
+use crate::services::user_preferences_service::UserPreferencesService;
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::ToSchema;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateProfileRequest {
     pub name: Option<String>,
     pub username: Option<String>,
     pub email: Option<String>,
     pub avatar_url: Option<String>,
+    pub asset_region: Option<String>,
 }
 
 // 更新用户资料
+#[utoipa::path(
+    put,
+    path = "/users/profile",
+    tag = "users",
+    request_body = UpdateProfileRequest,
+    responses((status = 200, description = "Profile updated successfully", body = ApiResponse<UserProfile>))
+)]
 pub async fn update_profile(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Json(payload): Json<UpdateProfileRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = RequestContext {
         user_id: auth_info.user.id,
         workspace_id: auth_info.current_workspace_id.unwrap_or_default(),
         idempotency_key: None,
+        channel: auth_info.channel,
     };
 
-    match AuthService::update_profile(
-        &mut conn,
-        &ctx,
-        &crate::routes::auth::UpdateProfileRequest {
-            name: payload.name.clone(),
-            username: payload.username.clone(),
-            email: payload.email.clone(),
-            avatar_url: payload.avatar_url.clone(),
-        },
-        &state.asset_helper,
-    ) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            AuthService::update_profile(
+                conn,
+                &ctx,
+                &crate::routes::auth::UpdateProfileRequest {
+                    name: payload.name.clone(),
+                    username: payload.username.clone(),
+                    email: payload.email.clone(),
+                    avatar_url: payload.avatar_url.clone(),
+                    asset_region: payload.asset_region.clone(),
+                },
+                &asset_helper,
+            )
+        })
+        .await;
+    match result {
         Ok(profile) => {
             let response = ApiResponse::success(profile, "Profile updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -53,3 +68,53 @@ pub async fn update_profile(
         Err(err) => err.into_response(),
     }
 }
+
+// 获取当前用户的偏好设置：时区、语言、主题和各类通知的接收渠道
+#[utoipa::path(
+    get,
+    path = "/users/preferences",
+    tag = "users",
+    responses((status = 200, description = "Preferences retrieved successfully", body = ApiResponse<UserPreferences>))
+)]
+pub async fn get_preferences(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .read(move |conn| UserPreferencesService::get(conn, auth_info.user.id))
+        .await;
+    match result {
+        Ok(preferences) => {
+            let response = ApiResponse::success(preferences, "Preferences retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+// 更新偏好设置：只修改请求中给出的字段，通知渠道按类型分别更新
+#[utoipa::path(
+    put,
+    path = "/users/preferences",
+    tag = "users",
+    request_body = UpdateUserPreferencesRequest,
+    responses((status = 200, description = "Preferences updated successfully", body = ApiResponse<UserPreferences>))
+)]
+pub async fn update_preferences(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<UpdateUserPreferencesRequest>,
+) -> impl IntoResponse {
+    let result = state
+        .executor
+        .transaction(move |conn| UserPreferencesService::update(conn, auth_info.user.id, &payload))
+        .await;
+    match result {
+        Ok(preferences) => {
+            let response = ApiResponse::success(preferences, "Preferences updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,369 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, EmptyData, ErrorDetail};
+use crate::db::models::app_installation::{TestWebhookRequest, WebhookDelivery};
+use crate::db::models::webhook::{CreateWebhookRequest, UpdateWebhookRequest, WebhookConfig};
+use crate::middleware::auth::AuthUserInfo;
+use crate::routes::oauth;
+use crate::services::context::RequestContext;
+use crate::services::webhooks_service::WebhooksService;
+
+/// 获取当前工作区的 Webhook 列表
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    responses((status = 200, description = "Webhooks retrieved successfully", body = ApiResponse<Vec<WebhookConfig>>))
+)]
+pub async fn get_webhooks(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| WebhooksService::list(conn, &ctx))
+        .await;
+    match result {
+        Ok(webhooks) => {
+            let response = ApiResponse::success(webhooks, "Webhooks retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 创建 Webhook，签名密钥仅在创建时返回
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses((status = 201, description = "Webhook created successfully", body = ApiResponse<WebhookConfig>))
+)]
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| WebhooksService::create(conn, &ctx, &payload))
+        .await;
+    match result {
+        Ok(webhook) => {
+            let response = ApiResponse::created(webhook, "Webhook created successfully");
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取指定 Webhook
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    responses((status = 200, description = "Webhook retrieved successfully", body = ApiResponse<WebhookConfig>))
+)]
+pub async fn get_webhook_by_id(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(webhook_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| WebhooksService::get_by_id(conn, &ctx, webhook_id))
+        .await;
+    match result {
+        Ok(webhook) => {
+            let response = ApiResponse::success(webhook, "Webhook retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 更新 Webhook（地址、订阅事件、启用状态或轮换密钥）
+#[utoipa::path(
+    put,
+    path = "/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    request_body = UpdateWebhookRequest,
+    responses((status = 200, description = "Webhook updated successfully", body = ApiResponse<WebhookConfig>))
+)]
+pub async fn update_webhook(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(webhook_id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| WebhooksService::update(conn, &ctx, webhook_id, &payload))
+        .await;
+    match result {
+        Ok(webhook) => {
+            let response = ApiResponse::success(webhook, "Webhook updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 删除 Webhook 及其投递记录
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    responses((status = 200, description = "Webhook deleted successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(webhook_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| WebhooksService::delete(conn, &ctx, webhook_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Webhook deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取 Webhook 的投递记录
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}/deliveries",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    responses((status = 200, description = "Webhook deliveries retrieved successfully", body = ApiResponse<Vec<WebhookDelivery>>))
+)]
+pub async fn get_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(webhook_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| WebhooksService::list_deliveries(conn, &ctx, webhook_id))
+        .await;
+    match result {
+        Ok(deliveries) => {
+            let response =
+                ApiResponse::success(deliveries, "Webhook deliveries retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 向 Webhook 发送各事件类型的示例载荷（测试模式）
+#[utoipa::path(
+    post,
+    path = "/webhooks/{webhook_id}/test",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    request_body = Option<TestWebhookRequest>,
+    responses((status = 200, description = "OK", body = ApiResponse<EmptyData>))
+)]
+pub async fn test_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(webhook_id): Path<Uuid>,
+    auth_info: AuthUserInfo,
+    payload: Option<Json<TestWebhookRequest>>,
+) -> impl IntoResponse {
+    let deliveries = {
+        let ctx = match auth_info.current_workspace_id {
+            Some(ws) => RequestContext {
+                user_id: auth_info.user.id,
+                workspace_id: ws,
+                idempotency_key: None,
+                channel: auth_info.channel,
+            },
+            None => {
+                let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                    field: None,
+                    code: "NO_WORKSPACE".to_string(),
+                    message: "No current workspace selected".to_string(),
+                }]);
+                return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+            }
+        };
+
+        let payload = payload.map(|Json(p)| p).unwrap_or_default();
+        let result = state
+            .executor
+            .transaction(move |conn| {
+                WebhooksService::queue_test_deliveries(conn, &ctx, webhook_id, &payload)
+            })
+            .await;
+        match result {
+            Ok(deliveries) => deliveries,
+            Err(err) => return err.into_response(),
+        }
+    };
+    oauth::send_now(&state, deliveries).await
+}
+
+/// 重放 Webhook 的一次历史投递
+#[utoipa::path(
+    post,
+    path = "/webhooks/{webhook_id}/redeliver/{delivery_id}",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path), ("delivery_id" = Uuid, Path)),
+    responses((status = 200, description = "OK", body = ApiResponse<EmptyData>))
+)]
+pub async fn redeliver_webhook(
+    State(state): State<Arc<AppState>>,
+    Path((webhook_id, delivery_id)): Path<(Uuid, Uuid)>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let delivery = {
+        let ctx = match auth_info.current_workspace_id {
+            Some(ws) => RequestContext {
+                user_id: auth_info.user.id,
+                workspace_id: ws,
+                idempotency_key: None,
+                channel: auth_info.channel,
+            },
+            None => {
+                let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                    field: None,
+                    code: "NO_WORKSPACE".to_string(),
+                    message: "No current workspace selected".to_string(),
+                }]);
+                return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+            }
+        };
+
+        let result = state
+            .executor
+            .transaction(move |conn| {
+                WebhooksService::queue_redelivery(conn, &ctx, webhook_id, delivery_id)
+            })
+            .await;
+        match result {
+            Ok(delivery) => delivery,
+            Err(err) => return err.into_response(),
+        }
+    };
+    oauth::send_now(&state, vec![delivery]).await
+}
@@ -7,39 +7,42 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::db::models::*;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::context::RequestContext;
+use crate::services::permission_service::{Permission, PermissionService};
 use crate::services::workflows_service::WorkflowsService;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct WorkflowQuery {
     pub name: Option<String>,
     pub is_default: Option<bool>,
 }
 
 /// Get all workflows for a team
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/workflows",
+    tag = "workflows",
+    params(("team_id" = Uuid, Path), WorkflowQuery),
+    responses((status = 200, description = "Workflows retrieved successfully", body = ApiResponse<Vec<Workflow>>))
+)]
 pub async fn get_workflows(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
     Query(_params): Query<WorkflowQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -51,7 +54,11 @@ pub async fn get_workflows(
         }
     };
 
-    match WorkflowsService::list(&mut conn, &ctx, team_id) {
+    let result = state
+        .executor
+        .read(move |conn| WorkflowsService::list(conn, &ctx, team_id))
+        .await;
+    match result {
         Ok(workflows) => {
             let response = ApiResponse::success(workflows, "Workflows retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -61,24 +68,24 @@ pub async fn get_workflows(
 }
 
 /// Get a workflow by ID
+#[utoipa::path(
+    get,
+    path = "/workflows/{workflow_id}",
+    tag = "workflows",
+    params(("workflow_id" = Uuid, Path)),
+    responses((status = 200, description = "Workflow retrieved successfully", body = ApiResponse<Workflow>))
+)]
 pub async fn get_workflow_by_id(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(workflow_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -90,7 +97,11 @@ pub async fn get_workflow_by_id(
         }
     };
 
-    match WorkflowsService::get_by_id(&mut conn, &ctx, workflow_id) {
+    let result = state
+        .executor
+        .read(move |conn| WorkflowsService::get_by_id(conn, &ctx, workflow_id))
+        .await;
+    match result {
         Ok(workflow) => {
             let response = ApiResponse::success(workflow, "Workflow retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -99,7 +110,7 @@ pub async fn get_workflow_by_id(
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct CreateWorkflowRequest {
     pub name: String,
     pub description: Option<String>,
@@ -107,25 +118,26 @@ pub struct CreateWorkflowRequest {
 }
 
 /// Create a new workflow
+#[utoipa::path(
+    post,
+    path = "/teams/{team_id}/workflows",
+    tag = "workflows",
+    params(("team_id" = Uuid, Path)),
+    request_body = CreateWorkflowRequest,
+    responses((status = 201, description = "Workflow created successfully", body = ApiResponse<Workflow>))
+)]
 pub async fn create_workflow(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
     Json(payload): Json<CreateWorkflowRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -137,14 +149,21 @@ pub async fn create_workflow(
         }
     };
 
-    match WorkflowsService::create_workflow(
-        &mut conn,
-        &ctx,
-        team_id,
-        &payload.name,
-        payload.description.clone(),
-        payload.is_default.unwrap_or(false),
-    ) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageWorkflows)?;
+            WorkflowsService::create_workflow(
+                conn,
+                &ctx,
+                team_id,
+                &payload.name,
+                payload.description.clone(),
+                payload.is_default.unwrap_or(false),
+            )
+        })
+        .await;
+    match result {
         Ok(workflow) => {
             let response = ApiResponse::created(workflow, "Workflow created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -153,7 +172,7 @@ pub async fn create_workflow(
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct UpdateWorkflowRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -161,25 +180,26 @@ pub struct UpdateWorkflowRequest {
 }
 
 /// Update a workflow
+#[utoipa::path(
+    put,
+    path = "/workflows/{workflow_id}",
+    tag = "workflows",
+    params(("workflow_id" = Uuid, Path)),
+    request_body = UpdateWorkflowRequest,
+    responses((status = 200, description = "Workflow updated successfully", body = ApiResponse<Workflow>))
+)]
 pub async fn update_workflow(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(workflow_id): Path<Uuid>,
     Json(payload): Json<UpdateWorkflowRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -191,7 +211,14 @@ pub async fn update_workflow(
         }
     };
 
-    match WorkflowsService::update(&mut conn, &ctx, workflow_id, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageWorkflows)?;
+            WorkflowsService::update(conn, &ctx, workflow_id, &payload)
+        })
+        .await;
+    match result {
         Ok(workflow) => {
             let response = ApiResponse::success(workflow, "Workflow updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -201,24 +228,24 @@ pub async fn update_workflow(
 }
 
 /// Delete a workflow
+#[utoipa::path(
+    delete,
+    path = "/workflows/{workflow_id}",
+    tag = "workflows",
+    params(("workflow_id" = Uuid, Path)),
+    responses((status = 200, description = "Workflow deleted successfully", body = ApiResponse<EmptyData>))
+)]
 pub async fn delete_workflow(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(workflow_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -230,7 +257,14 @@ pub async fn delete_workflow(
         }
     };
 
-    match WorkflowsService::delete(&mut conn, &ctx, workflow_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageWorkflows)?;
+            WorkflowsService::delete(conn, &ctx, workflow_id)
+        })
+        .await;
+    match result {
         Ok(()) => {
             let response = ApiResponse::<()>::ok("Workflow deleted successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -240,24 +274,24 @@ pub async fn delete_workflow(
 }
 
 /// Get workflow states for a workflow
+#[utoipa::path(
+    get,
+    path = "/workflows/{workflow_id}/states",
+    tag = "workflows",
+    params(("workflow_id" = Uuid, Path)),
+    responses((status = 200, description = "Workflow states retrieved successfully", body = ApiResponse<Vec<WorkflowState>>))
+)]
 pub async fn get_workflow_states(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(workflow_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -269,7 +303,7 @@ pub async fn get_workflow_states(
         }
     };
 
-    match WorkflowsService::get_states(&mut conn, &ctx, workflow_id) {
+    match WorkflowsService::get_states_cached(&state.executor, &ctx, workflow_id).await {
         Ok(states) => {
             let response = ApiResponse::success(states, "Workflow states retrieved successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -279,24 +313,24 @@ pub async fn get_workflow_states(
 }
 
 /// Get workflow states for a team's default workflow
+#[utoipa::path(
+    get,
+    path = "/teams/{team_id}/workflows/default/states",
+    tag = "workflows",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, description = "Default workflow states retrieved successfully", body = ApiResponse<Vec<WorkflowState>>))
+)]
 pub async fn get_team_default_workflow_states(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -308,7 +342,11 @@ pub async fn get_team_default_workflow_states(
         }
     };
 
-    match WorkflowsService::get_team_default_states(&mut conn, &ctx, team_id) {
+    let result = state
+        .executor
+        .read(move |conn| WorkflowsService::get_team_default_states(conn, &ctx, team_id))
+        .await;
+    match result {
         Ok(states) => {
             let response =
                 ApiResponse::success(states, "Default workflow states retrieved successfully");
@@ -318,7 +356,7 @@ pub async fn get_team_default_workflow_states(
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct CreateWorkflowStateRequest {
     pub name: String,
     pub description: Option<String>,
@@ -329,25 +367,26 @@ pub struct CreateWorkflowStateRequest {
 }
 
 /// Create a new workflow state
+#[utoipa::path(
+    post,
+    path = "/workflows/{workflow_id}/states",
+    tag = "workflows",
+    params(("workflow_id" = Uuid, Path)),
+    request_body = CreateWorkflowStateRequest,
+    responses((status = 201, description = "Workflow state created successfully", body = ApiResponse<WorkflowState>))
+)]
 pub async fn create_workflow_state(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(workflow_id): Path<Uuid>,
     Json(payload): Json<CreateWorkflowStateRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -378,7 +417,15 @@ pub async fn create_workflow_state(
         is_default: payload.is_default,
     };
 
-    match WorkflowsService::add_state(&mut conn, &ctx, workflow_id, &model_request) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageWorkflows)?;
+
+            WorkflowsService::add_state(conn, &ctx, workflow_id, &model_request)
+        })
+        .await;
+    match result {
         Ok(state) => {
             let response = ApiResponse::created(state, "Workflow state created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -388,25 +435,26 @@ pub async fn create_workflow_state(
 }
 
 /// Create a new workflow state for a team's default workflow
+#[utoipa::path(
+    post,
+    path = "/teams/{team_id}/workflows/default/states",
+    tag = "workflows",
+    params(("team_id" = Uuid, Path)),
+    request_body = CreateWorkflowStateRequest,
+    responses((status = 201, description = "Workflow state created successfully", body = ApiResponse<WorkflowState>))
+)]
 pub async fn create_team_default_workflow_state(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(team_id): Path<Uuid>,
     Json(payload): Json<CreateWorkflowStateRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -436,12 +484,15 @@ pub async fn create_team_default_workflow_state(
         position: payload.position,
     };
 
-    match WorkflowsService::create_team_default_state(
-        &mut conn,
-        &ctx,
-        team_id,
-        &team_default_request,
-    ) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageWorkflows)?;
+
+            WorkflowsService::create_team_default_state(conn, &ctx, team_id, &team_default_request)
+        })
+        .await;
+    match result {
         Ok(state) => {
             let response = ApiResponse::created(state, "Workflow state created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -450,7 +501,7 @@ pub async fn create_team_default_workflow_state(
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct UpdateWorkflowStateRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -461,25 +512,26 @@ pub struct UpdateWorkflowStateRequest {
 }
 
 /// Update a workflow state for a team's default workflow
+#[utoipa::path(
+    put,
+    path = "/teams/{team_id}/workflows/default/states/{state_id}",
+    tag = "workflows",
+    params(("team_id" = Uuid, Path), ("state_id" = Uuid, Path)),
+    request_body = UpdateWorkflowStateRequest,
+    responses((status = 200, description = "Workflow state updated successfully", body = ApiResponse<WorkflowState>))
+)]
 pub async fn update_team_default_workflow_state(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path((team_id, state_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<UpdateWorkflowStateRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -512,13 +564,21 @@ pub async fn update_team_default_workflow_state(
         position: payload.position,
     };
 
-    match WorkflowsService::update_team_default_state(
-        &mut conn,
-        &ctx,
-        team_id,
-        state_id,
-        &team_default_request,
-    ) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::ManageWorkflows)?;
+
+            WorkflowsService::update_team_default_state(
+                conn,
+                &ctx,
+                team_id,
+                state_id,
+                &team_default_request,
+            )
+        })
+        .await;
+    match result {
         Ok(state) => {
             let response = ApiResponse::success(state, "Workflow state updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -528,24 +588,24 @@ pub async fn update_team_default_workflow_state(
 }
 
 /// Get available workflow transitions for an issue
+#[utoipa::path(
+    get,
+    path = "/issues/{issue_id}/transitions",
+    tag = "workflows",
+    params(("issue_id" = Uuid, Path)),
+    responses((status = 200, description = "Available transitions retrieved successfully", body = ApiResponse<Vec<IssueTransitionResponse>>))
+)]
 pub async fn get_issue_transitions(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(issue_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -557,7 +617,11 @@ pub async fn get_issue_transitions(
         }
     };
 
-    match WorkflowsService::get_issue_transitions(&mut conn, &ctx, issue_id) {
+    let result = state
+        .executor
+        .read(move |conn| WorkflowsService::get_issue_transitions(conn, &ctx, issue_id))
+        .await;
+    match result {
         Ok(transitions) => {
             let response =
                 ApiResponse::success(transitions, "Available transitions retrieved successfully");
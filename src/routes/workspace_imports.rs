@@ -0,0 +1,124 @@
+use crate::AppState;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::models::api::{ApiResponse, ErrorDetail};
+use crate::db::models::workspace_import::{WorkspaceImportQuery, WorkspaceImportResponse};
+use crate::middleware::auth::AuthUserInfo;
+use crate::services::context::RequestContext;
+use crate::services::import_service::ImportService;
+use crate::services::permission_service::{Permission, PermissionService};
+
+/// 导入 Linear 或 Jira 的导出文件（团队、项目、标签、问题和评论），由后台任务执行
+///
+/// 权限要求: 当前用户必须是工作区的Owner或Admin。重复上传同一文件返回已有的导入记录；
+/// 之前导入过的记录不会重复创建，无法按原样导入的记录在结果的 conflicts 中列出
+#[utoipa::path(
+    post,
+    path = "/workspaces/current/import",
+    tag = "workspace-imports",
+    params(WorkspaceImportQuery),
+    responses((status = 200, description = "Workspace import queued", body = ApiResponse<WorkspaceImportResponse>))
+)]
+pub async fn import_workspace_data(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Query(query): Query<WorkspaceImportQuery>,
+    body: String,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateWorkspace)?;
+            ImportService::create(conn, &ctx, &query.source, &body)
+        })
+        .await;
+    match result {
+        Ok((import, true)) => {
+            if let Err(e) = ImportService::enqueue(&state.redis, import.import.id).await {
+                // The worker also sweeps stale pending imports
+                tracing::warn!(
+                    "Failed to queue workspace import {}: {}",
+                    import.import.id,
+                    e
+                );
+            }
+            let response = ApiResponse::success(import, "Workspace import queued");
+            (StatusCode::ACCEPTED, Json(response)).into_response()
+        }
+        Ok((import, false)) => {
+            let response = ApiResponse::success(import, "Workspace import already exists");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 查询工作区导入进度，完成后包含创建/匹配数量和冲突列表
+#[utoipa::path(
+    get,
+    path = "/workspaces/current/import/{import_id}",
+    tag = "workspace-imports",
+    params(("import_id" = Uuid, Path)),
+    responses((status = 200, description = "Workspace import retrieved successfully", body = ApiResponse<WorkspaceImportResponse>))
+)]
+pub async fn get_workspace_import(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(import_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateWorkspace)?;
+            ImportService::get(conn, &ctx, import_id)
+        })
+        .await;
+    match result {
+        Ok(import) => {
+            let response = ApiResponse::success(import, "Workspace import retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -7,17 +7,45 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::db::models::*;
+use crate::middleware::asset_region::AssetRegionHint;
 use crate::middleware::auth::AuthUserInfo;
 use crate::services::context::RequestContext;
+use crate::services::field_selection::FieldSelection;
+use crate::services::member_imports_service::MemberImportsService;
+use crate::services::permission_service::{Permission, PermissionService};
 use crate::services::workspace_members_service::WorkspaceMembersService;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct WorkspaceMemberQuery {
     pub role: Option<String>,
     pub user_id: Option<Uuid>,
+    /// 逗号分隔的字段列表，只返回这些字段（`id` 总会返回）
+    pub fields: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MemberDirectoryQuery {
+    /// `next_after` of the previous page
+    pub after: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MemberDirectoryChangesQuery {
+    /// `cursor` of the first directory page or of the previous change set
+    pub since: i64,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateMemberRoleRequest {
+    pub role: WorkspaceMemberRole,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -32,19 +60,12 @@ pub async fn invite_member_to_workspace(
     auth_info: AuthUserInfo,
     Json(payload): Json<InviteMemberRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -56,7 +77,11 @@ pub async fn invite_member_to_workspace(
         }
     };
 
-    match WorkspaceMembersService::invite_member(&mut conn, &ctx, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| WorkspaceMembersService::invite_member(conn, &ctx, &payload))
+        .await;
+    match result {
         Ok(invitation) => {
             let response = ApiResponse::created(invitation, "Member invited successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -71,19 +96,12 @@ pub async fn accept_invitation(
     auth_info: AuthUserInfo,
     Path(invitation_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -95,7 +113,13 @@ pub async fn accept_invitation(
         }
     };
 
-    match WorkspaceMembersService::accept_invitation(&mut conn, &ctx, invitation_id) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            WorkspaceMembersService::accept_invitation(conn, &ctx, invitation_id)
+        })
+        .await;
+    match result {
         Ok(invitation) => {
             let response = ApiResponse::success(invitation, "Invitation accepted successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -105,35 +129,34 @@ pub async fn accept_invitation(
 }
 
 /// 获取工作区成员列表
+#[utoipa::path(
+    get,
+    path = "/workspaces/{workspace_id}/members",
+    tag = "workspace-members",
+    params(("workspace_id" = Uuid, Path), WorkspaceMemberQuery),
+    responses((status = 200, description = "Workspace members retrieved successfully", body = ApiResponse<serde_json::Value>))
+)]
 pub async fn get_workspace_members(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Path(workspace_id): Path<Uuid>,
     Query(params): Query<WorkspaceMemberQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
+    if auth_info.current_workspace_id.is_none() {
+        let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+            field: None,
+            code: "NO_WORKSPACE".to_string(),
+            message: "No current workspace selected".to_string(),
+        }]);
+        return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+    }
 
-    let ctx = match auth_info.current_workspace_id {
-        Some(ws) => RequestContext {
-            user_id: auth_info.user.id,
-            workspace_id: ws,
-            idempotency_key: None,
-        },
-        None => {
-            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
-                field: None,
-                code: "NO_WORKSPACE".to_string(),
-                message: "No current workspace selected".to_string(),
-            }]);
-            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
-        }
-    };
+    let fields =
+        match FieldSelection::from_query(params.fields.as_deref(), WorkspaceMemberInfo::FIELDS) {
+            Ok(fields) => fields,
+            Err(err) => return err.into_response(),
+        };
 
     let role_enum = params.role.as_ref().and_then(|r| match r.as_str() {
         "owner" => Some(WorkspaceMemberRole::Owner),
@@ -142,14 +165,17 @@ pub async fn get_workspace_members(
         _ => None,
     });
 
-    match WorkspaceMembersService::get_workspace_members(
-        &mut conn,
-        &ctx,
-        &state.asset_helper,
+    match WorkspaceMembersService::get_workspace_members_cached(
+        &state.executor,
+        &state.asset_helper.for_region(region.as_deref()),
         workspace_id,
         role_enum,
         params.user_id,
-    ) {
+        None,
+    )
+    .await
+    .and_then(|members| fields.project(&members))
+    {
         Ok(members) => {
             let response =
                 ApiResponse::success(members, "Workspace members retrieved successfully");
@@ -160,24 +186,25 @@ pub async fn get_workspace_members(
 }
 
 /// 获取工作区成员和邀请列表
+#[utoipa::path(
+    get,
+    path = "/workspace-member-and-invitations",
+    tag = "workspace-members",
+    params(WorkspaceMemberQuery),
+    responses((status = 200, description = "Workspace members and invitations retrieved successfully", body = ApiResponse<MembersAndInvitations>))
+)]
 pub async fn get_workspace_members_and_invitations(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Query(params): Query<WorkspaceMemberQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -196,13 +223,20 @@ pub async fn get_workspace_members_and_invitations(
         _ => None,
     });
 
-    match WorkspaceMembersService::get_members_and_invitations(
-        &mut conn,
-        &ctx,
-        &state.asset_helper,
-        role_enum,
-        params.user_id,
-    ) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| {
+            WorkspaceMembersService::get_members_and_invitations(
+                conn,
+                &ctx,
+                &asset_helper,
+                role_enum,
+                params.user_id,
+            )
+        })
+        .await;
+    match result {
         Ok(result) => {
             let response = ApiResponse::success(
                 result,
@@ -215,24 +249,25 @@ pub async fn get_workspace_members_and_invitations(
 }
 
 /// 获取当前工作区成员列表
+#[utoipa::path(
+    get,
+    path = "/workspace-members",
+    tag = "workspace-members",
+    params(WorkspaceMemberQuery),
+    responses((status = 200, description = "Current workspace members and invitations retrieved successfully", body = ApiResponse<Vec<WorkspaceMemberInfo>>))
+)]
 pub async fn get_current_workspace_members(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
     Query(params): Query<WorkspaceMemberQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -251,13 +286,20 @@ pub async fn get_current_workspace_members(
         _ => None,
     });
 
-    match WorkspaceMembersService::get_current_workspace_members(
-        &mut conn,
-        &ctx,
-        &state.asset_helper,
-        role_enum,
-        params.user_id,
-    ) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| {
+            WorkspaceMembersService::get_current_workspace_members(
+                conn,
+                &ctx,
+                &asset_helper,
+                role_enum,
+                params.user_id,
+            )
+        })
+        .await;
+    match result {
         Ok(result) => {
             let response = ApiResponse::success(
                 result,
@@ -269,7 +311,201 @@ pub async fn get_current_workspace_members(
     }
 }
 
-#[derive(Deserialize, Serialize)]
+/// 分页获取成员目录，按用户 ID 排序
+///
+/// 首页返回的 `cursor` 用于翻页结束后通过 `/workspace-members/changes` 拉取增量
+#[utoipa::path(
+    get,
+    path = "/workspace-members/directory",
+    tag = "workspace-members",
+    params(MemberDirectoryQuery),
+    responses((status = 200, description = "Member directory retrieved successfully", body = ApiResponse<MemberDirectoryPage>))
+)]
+pub async fn get_member_directory(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Query(params): Query<MemberDirectoryQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| {
+            WorkspaceMembersService::directory_page(
+                conn,
+                &ctx,
+                &asset_helper,
+                params.after,
+                params.limit,
+            )
+        })
+        .await;
+    match result {
+        Ok(page) => {
+            let response = ApiResponse::success(page, "Member directory retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取自 `since` 以来的成员变更（加入、角色变更、移除）
+#[utoipa::path(
+    get,
+    path = "/workspace-members/changes",
+    tag = "workspace-members",
+    params(MemberDirectoryChangesQuery),
+    responses((status = 200, description = "Member changes retrieved successfully", body = ApiResponse<MemberDirectoryChanges>))
+)]
+pub async fn get_member_directory_changes(
+    State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
+    auth_info: AuthUserInfo,
+    Query(params): Query<MemberDirectoryChangesQuery>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| {
+            WorkspaceMembersService::directory_changes(conn, &ctx, &asset_helper, params.since)
+        })
+        .await;
+    match result {
+        Ok(changes) => {
+            let response = ApiResponse::success(changes, "Member changes retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 修改成员角色
+///
+/// 权限要求: Owner 或 Admin；授予或撤销 Owner 角色需要 Owner，且工作区至少保留一名 Owner
+#[utoipa::path(
+    put,
+    path = "/workspace-members/{user_id}",
+    tag = "workspace-members",
+    params(("user_id" = Uuid, Path)),
+    request_body = UpdateMemberRoleRequest,
+    responses((status = 200, description = "Member role updated successfully", body = ApiResponse<WorkspaceMember>))
+)]
+pub async fn update_member_role(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<UpdateMemberRoleRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            WorkspaceMembersService::update_role(conn, &ctx, user_id, payload.role)
+        })
+        .await;
+    match result {
+        Ok(member) => {
+            let response = ApiResponse::success(member, "Member role updated successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 将成员移出工作区及其所有团队
+#[utoipa::path(
+    delete,
+    path = "/workspace-members/{user_id}",
+    tag = "workspace-members",
+    params(("user_id" = Uuid, Path)),
+    responses((status = 200, description = "Member removed successfully", body = ApiResponse<EmptyData>))
+)]
+pub async fn remove_member(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| WorkspaceMembersService::remove(conn, &ctx, user_id))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::<()>::ok("Member removed successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct WorkspaceMemberInfo {
     pub id: uuid::Uuid,
     pub user_id: uuid::Uuid,
@@ -280,8 +516,144 @@ pub struct WorkspaceMemberInfo {
     pub updated_at: chrono::NaiveDateTime,
 }
 
-#[derive(Deserialize, Serialize)]
+impl WorkspaceMemberInfo {
+    /// Fields a list request can select with `?fields=`
+    pub const FIELDS: &'static [&'static str] = &[
+        "id",
+        "user_id",
+        "workspace_id",
+        "role",
+        "user",
+        "created_at",
+        "updated_at",
+    ];
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct MembersAndInvitations {
     pub members: Vec<WorkspaceMemberInfo>,
     pub invitations: Vec<crate::routes::invitations::InvitationInfo>,
 }
+
+#[derive(Serialize, ToSchema)]
+pub struct MemberDirectoryPage {
+    pub members: Vec<WorkspaceMemberInfo>,
+    /// Pass as `after` for the next page; absent on the last page
+    pub next_after: Option<Uuid>,
+    /// Change log position when the page was read
+    pub cursor: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MemberDirectoryChanges {
+    pub since: i64,
+    /// Pass as `since` next time
+    pub cursor: i64,
+    /// More changes are waiting; ask again with `cursor`
+    pub has_more: bool,
+    /// Current details of members who joined or changed role
+    pub members: Vec<WorkspaceMemberInfo>,
+    /// Users who left the workspace
+    pub removed: Vec<Uuid>,
+}
+
+/// 从 CSV 批量导入成员（email, role, teams），由后台任务执行
+///
+/// 权限要求: 当前用户必须是工作区的Owner或Admin。重复上传同一文件返回已有的导入记录
+#[utoipa::path(
+    post,
+    path = "/workspace-members/import",
+    tag = "workspace-members",
+    responses((status = 200, description = "Member import queued", body = ApiResponse<MemberImportResponse>))
+)]
+pub async fn import_members(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    body: String,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::InviteMembers)?;
+            MemberImportsService::create(conn, &ctx, &body)
+        })
+        .await;
+    match result {
+        Ok((import, true)) => {
+            if let Err(e) = MemberImportsService::enqueue(&state.redis, import.import.id).await {
+                // The worker also sweeps stale pending imports
+                tracing::warn!("Failed to queue member import {}: {}", import.import.id, e);
+            }
+            let response = ApiResponse::success(import, "Member import queued");
+            (StatusCode::ACCEPTED, Json(response)).into_response()
+        }
+        Ok((import, false)) => {
+            let response = ApiResponse::success(import, "Member import already exists");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 查询成员导入进度与逐行结果
+#[utoipa::path(
+    get,
+    path = "/workspace-members/import/{import_id}",
+    tag = "workspace-members",
+    params(("import_id" = Uuid, Path)),
+    responses((status = 200, description = "Member import retrieved successfully", body = ApiResponse<MemberImportResponse>))
+)]
+pub async fn get_member_import(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(import_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::InviteMembers)?;
+            MemberImportsService::get(conn, &ctx, import_id)
+        })
+        .await;
+    match result {
+        Ok(import) => {
+            let response = ApiResponse::success(import, "Member import retrieved successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
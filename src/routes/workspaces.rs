@@ -1,27 +1,33 @@
 use crate::AppState;
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::db::models::*;
+use crate::middleware::asset_region::AssetRegionHint;
 use crate::middleware::auth::AuthUserInfo;
+use crate::services::channel_permissions_service::ChannelPermissionsService;
 use crate::services::context::RequestContext;
+use crate::services::permission_service::{Permission, PermissionService};
+use crate::services::workspace_email_domains_service::{DohResolver, WorkspaceEmailDomainsService};
+use crate::services::workspace_settings_service::WorkspaceSettingsService;
 use crate::services::workspaces_service::WorkspacesService;
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct CreateWorkspaceRequest {
     pub name: String,
     pub url_key: String,
     pub logo_url: Option<String>,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct UpdateWorkspaceRequest {
     pub name: Option<String>,
     pub url_key: Option<String>,
@@ -29,24 +35,24 @@ pub struct UpdateWorkspaceRequest {
 }
 
 /// 创建工作空间
+#[utoipa::path(
+    post,
+    path = "/workspaces",
+    tag = "workspaces",
+    request_body = CreateWorkspaceRequest,
+    responses((status = 201, description = "Workspace created successfully", body = ApiResponse<Workspace>))
+)]
 pub async fn create_workspace(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Json(payload): Json<CreateWorkspaceRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let _ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -58,12 +64,18 @@ pub async fn create_workspace(
         }
     };
 
-    match WorkspacesService::create(
-        &mut conn,
-        &payload.name,
-        &payload.url_key,
-        payload.logo_url.clone(),
-    ) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            WorkspacesService::create(
+                conn,
+                &payload.name,
+                &payload.url_key,
+                payload.logo_url.clone(),
+            )
+        })
+        .await;
+    match result {
         Ok(workspace) => {
             let response = ApiResponse::created(workspace, "Workspace created successfully");
             (StatusCode::CREATED, Json(response)).into_response()
@@ -73,23 +85,23 @@ pub async fn create_workspace(
 }
 
 /// 获取当前工作空间
+#[utoipa::path(
+    get,
+    path = "/workspaces/current",
+    tag = "workspaces",
+    responses((status = 200, description = "Current workspace retrieved successfully", body = ApiResponse<WorkspaceInfo>))
+)]
 pub async fn get_current_workspace(
     State(state): State<Arc<AppState>>,
+    region: AssetRegionHint,
     auth_info: AuthUserInfo,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
     let _ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -101,7 +113,12 @@ pub async fn get_current_workspace(
         }
     };
 
-    match WorkspacesService::get_current(&mut conn, &_ctx, &state.asset_helper) {
+    let asset_helper = state.asset_helper.for_region(region.as_deref());
+    let result = state
+        .executor
+        .read(move |conn| WorkspacesService::get_current(conn, &_ctx, &asset_helper))
+        .await;
+    match result {
         Ok(workspace) => {
             let response =
                 ApiResponse::success(workspace, "Current workspace retrieved successfully");
@@ -112,25 +129,26 @@ pub async fn get_current_workspace(
 }
 
 /// 更新工作空间
+#[utoipa::path(
+    put,
+    path = "/workspaces/{workspace_id}",
+    tag = "workspaces",
+    params(("workspace_id" = Uuid, Path)),
+    request_body = UpdateWorkspaceRequest,
+    responses((status = 200, description = "Workspace updated successfully", body = ApiResponse<Workspace>))
+)]
 pub async fn update_workspace(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(workspace_id): Path<Uuid>,
     Json(payload): Json<UpdateWorkspaceRequest>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
-        }
-    };
-
-    let _ctx = match auth_info.current_workspace_id {
+    let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -142,7 +160,14 @@ pub async fn update_workspace(
         }
     };
 
-    match WorkspacesService::update(&mut conn, &_ctx, workspace_id, &payload) {
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            PermissionService::require(conn, &ctx, Permission::UpdateWorkspace)?;
+            WorkspacesService::update(conn, &ctx, workspace_id, &payload)
+        })
+        .await;
+    match result {
         Ok(workspace) => {
             let response = ApiResponse::success(workspace, "Workspace updated successfully");
             (StatusCode::OK, Json(response)).into_response()
@@ -152,24 +177,306 @@ pub async fn update_workspace(
 }
 
 /// 删除工作空间
+///
+/// 分两步：不带 `confirmation_token` 时返回 202 和短时有效的确认令牌，
+/// 以及将被删除的事项、成员、团队等数量；带上令牌再次调用才真正删除。
+#[utoipa::path(
+    delete,
+    path = "/workspaces/{workspace_id}",
+    tag = "workspaces",
+    params(("workspace_id" = Uuid, Path), DeleteConfirmationQuery),
+    responses((status = 200, description = "Repeat the request with the confirmation token to delete the workspace", body = ApiResponse<DeleteConfirmation>))
+)]
 pub async fn delete_workspace(
     State(state): State<Arc<AppState>>,
     auth_info: AuthUserInfo,
     Path(workspace_id): Path<Uuid>,
+    Query(query): Query<DeleteConfirmationQuery>,
 ) -> impl IntoResponse {
-    let mut conn = match state.db.get() {
-        Ok(conn) => conn,
-        Err(_) => {
-            let response = ApiResponse::<()>::internal_error("Database connection failed");
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response();
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
 
-    let _ctx = match auth_info.current_workspace_id {
+    let Some(token) = query.confirmation_token else {
+        let result = state
+            .executor
+            .transaction(move |conn| {
+                PermissionService::require(conn, &ctx, Permission::DeleteWorkspace)?;
+                WorkspacesService::request_delete(conn, &ctx, workspace_id)
+            })
+            .await;
+        return match result {
+            Ok(confirmation) => {
+                let response = ApiResponse::success(
+                    confirmation,
+                    "Repeat the request with the confirmation token to delete the workspace",
+                );
+                (StatusCode::ACCEPTED, Json(response)).into_response()
+            }
+            Err(err) => err.into_response(),
+        };
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| WorkspacesService::delete(conn, &ctx, workspace_id, &token))
+        .await;
+    match result {
+        Ok(()) => {
+            let response = ApiResponse::success((), "Workspace deleted successfully");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取当前工作空间对 API 令牌、OAuth 应用和 API key 的权限限制
+#[utoipa::path(
+    get,
+    path = "/workspaces/current/api-access",
+    tag = "workspaces",
+    responses((status = 200, description = "API access policy retrieved", body = ApiResponse<Vec<ChannelPolicy>>))
+)]
+pub async fn get_api_access(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| ChannelPermissionsService::list(conn, &ctx))
+        .await;
+    match result {
+        Ok(policies) => {
+            let response = ApiResponse::success(policies, "API access policy retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 设置某个 API 渠道（api_token / oauth_app / api_key）被禁止的权限，整体替换
+#[utoipa::path(
+    put,
+    path = "/workspaces/current/api-access/{channel}",
+    tag = "workspaces",
+    params(("channel" = String, Path)),
+    request_body = UpdateChannelPolicyRequest,
+    responses((status = 200, description = "API access policy updated", body = ApiResponse<ChannelPolicy>))
+)]
+pub async fn update_api_access(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Path(channel): Path<String>,
+    Json(payload): Json<UpdateChannelPolicyRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| ChannelPermissionsService::update(conn, &ctx, &channel, &payload))
+        .await;
+    match result {
+        Ok(policy) => {
+            let response = ApiResponse::success(policy, "API access policy updated");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取当前工作空间的设置（默认工作流、每周起始日、估算刻度、允许邀请的邮箱域名和功能开关），
+/// 未设置过的项返回默认值
+#[utoipa::path(
+    get,
+    path = "/workspaces/current/settings",
+    tag = "workspaces",
+    responses((status = 200, description = "Workspace settings retrieved", body = ApiResponse<WorkspaceSettings>))
+)]
+pub async fn get_workspace_settings(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .read(move |conn| WorkspaceSettingsService::get(conn, &ctx))
+        .await;
+    match result {
+        Ok(settings) => {
+            let response = ApiResponse::success(settings, "Workspace settings retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 更新当前工作空间的设置，只修改请求中给出的项；features 只需包含要切换的开关
+///
+/// 权限要求: 当前用户必须是工作区的Owner或Admin
+#[utoipa::path(
+    put,
+    path = "/workspaces/current/settings",
+    tag = "workspaces",
+    request_body = UpdateWorkspaceSettingsRequest,
+    responses((status = 200, description = "Workspace settings updated", body = ApiResponse<WorkspaceSettings>))
+)]
+pub async fn update_workspace_settings(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<UpdateWorkspaceSettingsRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| WorkspaceSettingsService::update(conn, &ctx, &payload))
+        .await;
+    match result {
+        Ok(settings) => {
+            let response = ApiResponse::success(settings, "Workspace settings updated");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 获取当前工作空间的自定义发件域名及需要发布的 DNS 记录
+#[utoipa::path(
+    get,
+    path = "/workspaces/current/email-domain",
+    tag = "workspaces",
+    responses((status = 200, description = "Email domain retrieved", body = ApiResponse<EmailDomainResponse>))
+)]
+pub async fn get_email_domain(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let email = state.email.clone();
+    let result = state
+        .executor
+        .read(move |conn| WorkspaceEmailDomainsService::get(conn, &ctx, email.platform_from()))
+        .await;
+    match result {
+        Ok(domain) => {
+            let response = ApiResponse::success(domain, "Email domain retrieved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 设置自定义发件域名（仅企业工作空间）；更换域名后需要重新验证
+#[utoipa::path(
+    put,
+    path = "/workspaces/current/email-domain",
+    tag = "workspaces",
+    request_body = SetEmailDomainRequest,
+    responses((status = 200, description = "Email domain saved", body = ApiResponse<EmailDomainResponse>))
+)]
+pub async fn set_email_domain(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+    Json(payload): Json<SetEmailDomainRequest>,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
         Some(ws) => RequestContext {
             user_id: auth_info.user.id,
             workspace_id: ws,
             idempotency_key: None,
+            channel: auth_info.channel,
         },
         None => {
             let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
@@ -181,9 +488,132 @@ pub async fn delete_workspace(
         }
     };
 
-    match WorkspacesService::delete(&mut conn, &_ctx, workspace_id) {
+    let email = state.email.clone();
+    let result = state
+        .executor
+        .transaction(move |conn| {
+            WorkspaceEmailDomainsService::set(conn, &ctx, &payload, email.platform_from())
+        })
+        .await;
+    match result {
+        Ok(domain) => {
+            let response = ApiResponse::success(domain, "Email domain saved");
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 查询 DNS 验证所有权 TXT 记录和 DKIM 公钥；验证失败时邮件改用平台域名发送
+#[utoipa::path(
+    post,
+    path = "/workspaces/current/email-domain/verify",
+    tag = "workspaces",
+    responses((status = 200, description = "OK", body = ApiResponse<serde_json::Value>))
+)]
+pub async fn verify_email_domain(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let resolver = match DohResolver::new(&state.config.email_domain_dns_url) {
+        Ok(resolver) => resolver,
+        Err(err) => return err.into_response(),
+    };
+    let lookup_ctx = ctx.clone();
+    let domain = match state
+        .executor
+        .read(move |conn| WorkspaceEmailDomainsService::for_verification(conn, &lookup_ctx))
+        .await
+    {
+        Ok(domain) => domain,
+        Err(err) => return err.into_response(),
+    };
+    let check = WorkspaceEmailDomainsService::check(&resolver, &domain).await;
+
+    let platform_from = state.email.platform_from().to_string();
+    let user_id = ctx.user_id;
+    match state
+        .executor
+        .transaction(move |conn| {
+            WorkspaceEmailDomainsService::record_check(conn, Some(user_id), &domain, &check).map(
+                |record| {
+                    (
+                        WorkspaceEmailDomainsService::to_response(record, &platform_from),
+                        check,
+                    )
+                },
+            )
+        })
+        .await
+    {
+        Ok((domain, check)) => {
+            let message = if check.passed() {
+                "Email domain verified"
+            } else {
+                "Email domain verification failed"
+            };
+            let response = ApiResponse::success(
+                serde_json::json!({ "domain": domain, "check": check }),
+                message,
+            );
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+/// 移除自定义发件域名，之后邮件使用平台域名发送
+#[utoipa::path(
+    delete,
+    path = "/workspaces/current/email-domain",
+    tag = "workspaces",
+    responses((status = 200, description = "Email domain removed", body = ApiResponse<EmptyData>))
+)]
+pub async fn delete_email_domain(
+    State(state): State<Arc<AppState>>,
+    auth_info: AuthUserInfo,
+) -> impl IntoResponse {
+    let ctx = match auth_info.current_workspace_id {
+        Some(ws) => RequestContext {
+            user_id: auth_info.user.id,
+            workspace_id: ws,
+            idempotency_key: None,
+            channel: auth_info.channel,
+        },
+        None => {
+            let response = ApiResponse::<()>::validation_error(vec![ErrorDetail {
+                field: None,
+                code: "NO_WORKSPACE".to_string(),
+                message: "No current workspace selected".to_string(),
+            }]);
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+
+    let result = state
+        .executor
+        .transaction(move |conn| WorkspaceEmailDomainsService::remove(conn, &ctx))
+        .await;
+    match result {
         Ok(()) => {
-            let response = ApiResponse::success((), "Workspace deleted successfully");
+            let response = ApiResponse::<()>::ok("Email domain removed");
             (StatusCode::OK, Json(response)).into_response()
         }
         Err(err) => err.into_response(),
@@ -0,0 +1,294 @@
+//! Named periodic jobs
+//!
+//! Periodic work registers here with a default [`Schedule`] instead of
+//! running its own `tokio::spawn` loop. `SCHEDULER_JOBS` can replace a job's
+//! schedule or turn it off. Once started, every job runs as a task of the
+//! [`TaskSupervisor`], so a job that panics is restarted like any other
+//! background task. The start and outcome of each run are stored in
+//! `scheduled_jobs` and served with the registry at `/admin/jobs`.
+
+pub mod schedule;
+
+pub use schedule::Schedule;
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::cache::LockManager;
+use crate::db::DbExecutor;
+use crate::db::models::scheduled_job::{
+    ScheduledJobOutcome, ScheduledJobRecord, ScheduledJobStatus,
+};
+use crate::db::repositories::scheduled_jobs::ScheduledJobsRepo;
+use crate::error::AppError;
+use crate::supervisor::TaskSupervisor;
+use crate::utils::clock;
+
+/// What a job run returns: a short summary on success
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<String, AppError>> + Send>>;
+
+type JobFn = Arc<dyn Fn() -> JobFuture + Send + Sync>;
+
+/// Lock lifetime of an exclusive job; renewed while the run lasts
+const EXCLUSIVE_LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// Schedule value that turns a job off in `SCHEDULER_JOBS`
+const OFF: &str = "off";
+
+#[derive(Clone)]
+pub struct ScheduledJob {
+    name: String,
+    schedule: Schedule,
+    exclusive: bool,
+    run: JobFn,
+}
+
+impl ScheduledJob {
+    pub fn new<F, Fut>(name: impl Into<String>, schedule: Schedule, run: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, AppError>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            schedule,
+            exclusive: false,
+            run: Arc::new(move || Box::pin(run())),
+        }
+    }
+
+    /// Run on one replica at a time; replicas that find the job's lock held
+    /// skip the run. For jobs over shared data rather than in-process state.
+    pub fn exclusive(mut self) -> Self {
+        self.exclusive = true;
+        self
+    }
+}
+
+struct JobEntry {
+    job: ScheduledJob,
+    enabled: bool,
+    next_run_at: Option<DateTime<Utc>>,
+}
+
+/// Registry of periodic jobs
+#[derive(Clone, Default)]
+pub struct Scheduler {
+    jobs: Arc<RwLock<BTreeMap<String, JobEntry>>>,
+    /// Schedules from configuration by job name; `None` turns the job off
+    overrides: Arc<HashMap<String, Option<Schedule>>>,
+    started: Arc<AtomicBool>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scheduler whose jobs take their schedule from `spec` when it names
+    /// them; invalid entries are logged and ignored
+    pub fn with_overrides(spec: &str) -> Self {
+        let overrides = match Self::parse_overrides(spec) {
+            Ok(overrides) => overrides,
+            Err(e) => {
+                tracing::warn!("Ignoring SCHEDULER_JOBS: {}", e);
+                HashMap::new()
+            }
+        };
+        Self {
+            overrides: Arc::new(overrides),
+            ..Self::default()
+        }
+    }
+
+    /// Parse `;`-separated `name=schedule` pairs, e.g.
+    /// `cycle_status=*/5 * * * *;ws_connection_cleanup=off`
+    pub fn parse_overrides(spec: &str) -> Result<HashMap<String, Option<Schedule>>, AppError> {
+        let mut overrides = HashMap::new();
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, schedule) = entry.split_once('=').ok_or_else(|| {
+                AppError::validation(format!("Expected name=schedule, got '{}'", entry))
+            })?;
+            let schedule = match schedule.trim() {
+                OFF => None,
+                schedule => Some(schedule.parse::<Schedule>()?),
+            };
+            overrides.insert(name.trim().to_string(), schedule);
+        }
+        Ok(overrides)
+    }
+
+    /// Add a job; it runs once [`Self::start`] is called. Names must be
+    /// unique, and jobs registered after the start are not run.
+    pub fn register(&self, mut job: ScheduledJob) {
+        if self.started.load(Ordering::SeqCst) {
+            tracing::warn!("Job {} registered after the scheduler started", job.name);
+            return;
+        }
+        let enabled = match self.overrides.get(&job.name) {
+            Some(Some(schedule)) => {
+                job.schedule = schedule.clone();
+                true
+            }
+            Some(None) => false,
+            None => true,
+        };
+        let mut jobs = self.jobs.write().unwrap();
+        if jobs.contains_key(&job.name) {
+            tracing::warn!("Job {} is already registered", job.name);
+            return;
+        }
+        jobs.insert(
+            job.name.clone(),
+            JobEntry {
+                job,
+                enabled,
+                next_run_at: None,
+            },
+        );
+    }
+
+    /// Start every enabled job as a supervised task named `job:<name>`
+    pub fn start(&self, supervisor: &TaskSupervisor, executor: DbExecutor, locks: LockManager) {
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let jobs: Vec<ScheduledJob> = self
+            .jobs
+            .read()
+            .unwrap()
+            .values()
+            .filter(|entry| entry.enabled)
+            .map(|entry| entry.job.clone())
+            .collect();
+        for job in jobs {
+            supervisor.spawn(format!("job:{}", job.name), {
+                let scheduler = self.clone();
+                let executor = executor.clone();
+                let locks = locks.clone();
+                move || {
+                    scheduler
+                        .clone()
+                        .run_loop(job.clone(), executor.clone(), locks.clone())
+                }
+            });
+        }
+    }
+
+    async fn run_loop(self, job: ScheduledJob, executor: DbExecutor, locks: LockManager) {
+        loop {
+            let now = clock::now();
+            let next = job.schedule.next_after(now);
+            self.set_next_run(&job.name, next);
+            let Some(next) = next else {
+                tracing::warn!("Job {} has no upcoming run for {}", job.name, job.schedule);
+                return;
+            };
+            tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+            self.run_once(&job, &executor, &locks).await;
+        }
+    }
+
+    async fn run_once(&self, job: &ScheduledJob, executor: &DbExecutor, locks: &LockManager) {
+        let guard = if job.exclusive {
+            match locks
+                .try_acquire(&format!("job:{}", job.name), EXCLUSIVE_LOCK_TTL)
+                .await
+            {
+                Ok(Some(guard)) => Some(guard),
+                Ok(None) => {
+                    tracing::debug!("Job {} is running on another replica", job.name);
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping job {}: {}", job.name, e);
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        let started_at = clock::now();
+        let started = Instant::now();
+        let (name, schedule) = (job.name.clone(), job.schedule.to_string());
+        if let Err(e) = executor
+            .transaction(move |conn| {
+                Ok(ScheduledJobsRepo::record_start(
+                    conn, &name, &schedule, started_at,
+                )?)
+            })
+            .await
+        {
+            tracing::warn!("Failed to record start of job {}: {}", job.name, e);
+        }
+
+        let result = (job.run)().await;
+        match &result {
+            Ok(summary) => tracing::debug!("Job {} finished: {}", job.name, summary),
+            Err(e) => tracing::error!("Job {} failed: {}", job.name, e),
+        }
+        let outcome = ScheduledJobOutcome {
+            finished_at: clock::now(),
+            duration_ms: started.elapsed().as_millis() as i64,
+            result: result.map_err(|e| e.to_string()),
+        };
+        let name = job.name.clone();
+        if let Err(e) = executor
+            .transaction(move |conn| Ok(ScheduledJobsRepo::record_finish(conn, &name, &outcome)?))
+            .await
+        {
+            tracing::warn!("Failed to record outcome of job {}: {}", job.name, e);
+        }
+
+        if let Some(guard) = guard
+            && let Err(e) = guard.release().await
+        {
+            tracing::warn!("Failed to release lock of job {}: {}", job.name, e);
+        }
+    }
+
+    fn set_next_run(&self, name: &str, next_run_at: Option<DateTime<Utc>>) {
+        if let Some(entry) = self.jobs.write().unwrap().get_mut(name) {
+            entry.next_run_at = next_run_at;
+        }
+    }
+
+    /// Registered jobs ordered by name, with their stored last run from
+    /// `records`
+    pub fn status(&self, records: Vec<ScheduledJobRecord>) -> Vec<ScheduledJobStatus> {
+        let mut records: HashMap<String, ScheduledJobRecord> = records
+            .into_iter()
+            .map(|record| (record.name.clone(), record))
+            .collect();
+        self.jobs
+            .read()
+            .unwrap()
+            .values()
+            .map(|entry| {
+                let record = records.remove(&entry.job.name);
+                ScheduledJobStatus {
+                    name: entry.job.name.clone(),
+                    schedule: entry.job.schedule.to_string(),
+                    enabled: entry.enabled,
+                    exclusive: entry.job.exclusive,
+                    next_run_at: entry.next_run_at,
+                    last_status: record.as_ref().and_then(|r| r.last_status.clone()),
+                    last_started_at: record.as_ref().and_then(|r| r.last_started_at),
+                    last_finished_at: record.as_ref().and_then(|r| r.last_finished_at),
+                    last_duration_ms: record.as_ref().and_then(|r| r.last_duration_ms),
+                    last_result: record.as_ref().and_then(|r| r.last_result.clone()),
+                    last_error: record.as_ref().and_then(|r| r.last_error.clone()),
+                    run_count: record.as_ref().map_or(0, |r| r.run_count),
+                    failure_count: record.as_ref().map_or(0, |r| r.failure_count),
+                }
+            })
+            .collect()
+    }
+}
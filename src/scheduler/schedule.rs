@@ -0,0 +1,217 @@
+//! When a scheduled job runs.
+//!
+//! Two forms are accepted: `@every <n>s|m|h` for a fixed interval, and a
+//! five-field cron expression (`minute hour day-of-month month day-of-week`,
+//! UTC) with `*`, numbers, ranges `a-b`, steps `*/n` or `a-b/n` and lists
+//! `a,b`. Day of week runs 0-6 from Sunday; 7 is Sunday as well. As in cron,
+//! a job whose day of month and day of week are both restricted runs on days
+//! matching either. `@hourly` and `@daily` are shorthands for `0 * * * *` and
+//! `0 0 * * *`.
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, TimeZone, Timelike, Utc};
+
+use crate::error::AppError;
+
+/// Days searched for the next cron match before giving up; covers leap days
+const MAX_SEARCH_DAYS: i64 = 366 * 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    kind: ScheduleKind,
+    /// The expression as written, shown in job status
+    source: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ScheduleKind {
+    Every(Duration),
+    Cron(CronExpr),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Whether the day fields were given as `*`
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl Schedule {
+    /// A fixed interval; must be at least a second
+    pub fn every(interval: Duration) -> Self {
+        let interval = interval.max(Duration::from_secs(1));
+        Self {
+            source: format!("@every {}s", interval.as_secs()),
+            kind: ScheduleKind::Every(interval),
+        }
+    }
+
+    /// First run time strictly after `after`; `None` when a cron expression
+    /// never matches, e.g. `0 0 31 2 *`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match &self.kind {
+            ScheduleKind::Every(interval) => {
+                Some(after + ChronoDuration::from_std(*interval).ok()?)
+            }
+            ScheduleKind::Cron(expr) => expr.next_after(after),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let invalid = |reason: &str| {
+            AppError::validation(format!("Invalid schedule '{}': {}", value, reason))
+        };
+        let kind = match value {
+            "@hourly" => ScheduleKind::Cron(CronExpr::parse("0 * * * *").map_err(invalid)?),
+            "@daily" => ScheduleKind::Cron(CronExpr::parse("0 0 * * *").map_err(invalid)?),
+            _ => match value.strip_prefix("@every") {
+                Some(interval) => {
+                    ScheduleKind::Every(parse_interval(interval.trim()).map_err(invalid)?)
+                }
+                None => ScheduleKind::Cron(CronExpr::parse(value).map_err(invalid)?),
+            },
+        };
+        Ok(Self {
+            kind,
+            source: value.to_string(),
+        })
+    }
+}
+
+fn parse_interval(value: &str) -> Result<Duration, &'static str> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or("interval needs a unit of s, m or h")?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| "interval needs a number")?;
+    let seconds = match unit {
+        "s" => amount,
+        "m" => amount.saturating_mul(60),
+        "h" => amount.saturating_mul(3600),
+        _ => return Err("interval unit must be s, m or h"),
+    };
+    if seconds == 0 {
+        return Err("interval must be positive");
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+impl CronExpr {
+    fn parse(value: &str) -> Result<Self, &'static str> {
+        let fields: Vec<&str> = value.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err("expected five fields");
+        };
+        let mut days_of_week = parse_field(day_of_week, 0, 7)?;
+        // 7 is another name for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days_of_month: parse_field(day_of_month, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            days_of_week,
+            any_day_of_month: day_of_month == "*",
+            any_day_of_week: day_of_week == "*",
+        })
+    }
+
+    fn day_matches(&self, at: DateTime<Utc>) -> bool {
+        let by_month = bit(self.days_of_month, at.day());
+        let by_week = bit(self.days_of_week, at.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => by_month || by_week,
+            _ => by_month && by_week,
+        }
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + ChronoDuration::minutes(1);
+        let limit = start + ChronoDuration::days(MAX_SEARCH_DAYS);
+        let mut at = start;
+        while at < limit {
+            if !bit(self.months, at.month()) {
+                let (year, month) = if at.month() == 12 {
+                    (at.year() + 1, 1)
+                } else {
+                    (at.year(), at.month() + 1)
+                };
+                at = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.day_matches(at) {
+                at = at.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?.and_utc();
+            } else if !bit(self.hours, at.hour()) {
+                at = at.with_minute(0)? + ChronoDuration::hours(1);
+            } else if !bit(self.minutes, at.minute()) {
+                at += ChronoDuration::minutes(1);
+            } else {
+                return Some(at);
+            }
+        }
+        None
+    }
+}
+
+fn bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Bit set of the values `field` allows within `min..=max`
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, &'static str> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or("step must be a positive number")?,
+            ),
+            None => (part, 1),
+        };
+        let (from, to) = if range == "*" {
+            (min, max)
+        } else if let Some((from, to)) = range.split_once('-') {
+            (parse_value(from, min, max)?, parse_value(to, min, max)?)
+        } else {
+            let value = parse_value(range, min, max)?;
+            // `5/15` runs from 5 to the end of the range
+            (value, if step > 1 { max } else { value })
+        };
+        if from > to {
+            return Err("range start is after its end");
+        }
+        for value in (from..=to).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, &'static str> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or("value out of range")
+}
@@ -14,6 +14,156 @@ pub mod sql_types {
     pub struct WorkspaceUserRole;
 }
 
+diesel::table! {
+    api_keys (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 16]
+        key_prefix -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        scopes -> Text,
+        #[max_length = 20]
+        tier -> Varchar,
+        created_by -> Uuid,
+        last_used_at -> Nullable<Timestamptz>,
+        expires_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    api_tokens (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 16]
+        token_prefix -> Varchar,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        #[max_length = 20]
+        tier -> Varchar,
+        last_used_at -> Nullable<Timestamptz>,
+        expires_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        scopes -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    api_usage_daily (client_type, client_id, day, endpoint) {
+        #[max_length = 20]
+        client_type -> Varchar,
+        client_id -> Uuid,
+        day -> Date,
+        #[max_length = 255]
+        endpoint -> Varchar,
+        request_count -> Int8,
+        error_count -> Int8,
+    }
+}
+
+diesel::table! {
+    app_installations (id) {
+        id -> Uuid,
+        app_id -> Uuid,
+        workspace_id -> Uuid,
+        installed_by -> Nullable<Uuid>,
+        scopes -> Text,
+        uninstalled_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    attachments (id) {
+        id -> Uuid,
+        issue_id -> Uuid,
+        workspace_id -> Uuid,
+        uploader_id -> Nullable<Uuid>,
+        #[max_length = 255]
+        filename -> Varchar,
+        #[max_length = 255]
+        content_type -> Varchar,
+        size_bytes -> Int8,
+        storage_key -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        created_at -> Timestamptz,
+        uploaded_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    audit_log (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        #[max_length = 100]
+        action -> Varchar,
+        #[max_length = 50]
+        target_type -> Varchar,
+        target_id -> Nullable<Uuid>,
+        details -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    automation_rules (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        team_id -> Nullable<Uuid>,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 32]
+        trigger -> Varchar,
+        conditions -> Text,
+        actions -> Text,
+        enabled -> Bool,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    automation_runs (id) {
+        id -> Uuid,
+        rule_id -> Uuid,
+        workspace_id -> Uuid,
+        issue_id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        event -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamptz,
+        finished_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    channel_permission_restrictions (workspace_id, channel, permission) {
+        workspace_id -> Uuid,
+        #[max_length = 20]
+        channel -> Varchar,
+        #[max_length = 50]
+        permission -> Varchar,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     comment_attachments (id) {
         id -> Uuid,
@@ -28,6 +178,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    comment_flags (id) {
+        id -> Uuid,
+        comment_id -> Uuid,
+        workspace_id -> Uuid,
+        author_id -> Uuid,
+        reasons -> Text,
+        score -> Int4,
+        #[max_length = 20]
+        status -> Varchar,
+        created_at -> Timestamptz,
+        resolved_by -> Nullable<Uuid>,
+        resolved_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     comment_mentions (id) {
         id -> Uuid,
@@ -61,6 +227,75 @@ diesel::table! {
         parent_comment_id -> Nullable<Uuid>,
         is_edited -> Nullable<Bool>,
         is_deleted -> Nullable<Bool>,
+        hidden_at -> Nullable<Timestamptz>,
+        hidden_by -> Nullable<Uuid>,
+        hidden_reason -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    content_reports (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        reporter_id -> Nullable<Uuid>,
+        #[max_length = 20]
+        target_type -> Varchar,
+        target_id -> Uuid,
+        #[max_length = 30]
+        reason -> Varchar,
+        details -> Nullable<Text>,
+        #[max_length = 20]
+        status -> Varchar,
+        resolution_note -> Nullable<Text>,
+        resolved_by -> Nullable<Uuid>,
+        resolved_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    cross_workspace_issue_relations (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        issue_id -> Uuid,
+        related_issue_id -> Uuid,
+        #[max_length = 32]
+        relation_type -> Varchar,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    custom_emojis (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        #[max_length = 30]
+        name -> Varchar,
+        #[max_length = 255]
+        content_type -> Varchar,
+        size_bytes -> Int8,
+        storage_key -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        created_by -> Nullable<Uuid>,
+        usage_count -> Int8,
+        last_used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        uploaded_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    cycle_scope_changes (id) {
+        id -> Int8,
+        cycle_id -> Uuid,
+        issue_id -> Uuid,
+        #[max_length = 20]
+        change -> Varchar,
+        points_delta -> Int4,
+        created_at -> Timestamptz,
     }
 }
 
@@ -80,6 +315,98 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    delete_confirmations (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        #[max_length = 20]
+        target_type -> Varchar,
+        target_id -> Uuid,
+        #[max_length = 255]
+        target_name -> Varchar,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        impact -> Text,
+        requested_by -> Nullable<Uuid>,
+        expires_at -> Timestamptz,
+        confirmed_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    error_groups (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        #[max_length = 64]
+        fingerprint -> Varchar,
+        issue_id -> Uuid,
+        title -> Text,
+        #[max_length = 20]
+        level -> Varchar,
+        occurrences -> Int8,
+        reopened_count -> Int4,
+        first_seen_at -> Timestamptz,
+        last_seen_at -> Timestamptz,
+        #[max_length = 255]
+        last_event_id -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    error_tracking_integrations (workspace_id) {
+        workspace_id -> Uuid,
+        team_id -> Uuid,
+        #[max_length = 128]
+        ingest_key -> Varchar,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    external_references (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        issue_id -> Uuid,
+        #[max_length = 32]
+        source -> Varchar,
+        #[max_length = 255]
+        external_id -> Varchar,
+        url -> Nullable<Text>,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    github_integrations (workspace_id) {
+        workspace_id -> Uuid,
+        #[max_length = 128]
+        webhook_secret -> Varchar,
+        #[max_length = 255]
+        merge_state_name -> Nullable<Varchar>,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    import_external_ids (workspace_id, source, record_type, external_id) {
+        workspace_id -> Uuid,
+        #[max_length = 16]
+        source -> Varchar,
+        #[max_length = 16]
+        record_type -> Varchar,
+        #[max_length = 255]
+        external_id -> Varchar,
+        entity_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::WorkspaceUserRole;
@@ -95,51 +422,369 @@ diesel::table! {
         invited_by -> Uuid,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
-        expires_at -> Timestamptz,
+        expires_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_auto_close_warnings (issue_id) {
+        issue_id -> Uuid,
+        warned_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_board_positions (issue_id) {
+        issue_id -> Uuid,
+        position -> Float8,
+        version -> Int4,
+        updated_by -> Nullable<Uuid>,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_form_submissions (id) {
+        id -> Uuid,
+        form_id -> Uuid,
+        issue_id -> Uuid,
+        submitted_by -> Nullable<Uuid>,
+        #[max_length = 255]
+        submitter_email -> Nullable<Varchar>,
+        answers -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_forms (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        team_id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        fields -> Text,
+        is_public -> Bool,
+        #[max_length = 64]
+        public_slug -> Nullable<Varchar>,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_label_rule_applications (id) {
+        id -> Uuid,
+        issue_id -> Uuid,
+        rule_id -> Nullable<Uuid>,
+        #[max_length = 255]
+        rule_name -> Varchar,
+        label_id -> Nullable<Uuid>,
+        #[max_length = 16]
+        priority -> Nullable<Varchar>,
+        applied_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_label_rules (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        team_id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 16]
+        match_type -> Varchar,
+        pattern -> Text,
+        label_id -> Nullable<Uuid>,
+        #[max_length = 16]
+        priority -> Nullable<Varchar>,
+        position -> Int4,
+        enabled -> Bool,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_labels (issue_id, label_id) {
+        issue_id -> Uuid,
+        label_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    issue_archive_batches (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        requested_by -> Uuid,
+        filter -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        issue_count -> Int4,
+        created_at -> Timestamptz,
+        completed_at -> Nullable<Timestamptz>,
+        undone_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    issue_links (id) {
+        id -> Uuid,
+        issue_id -> Uuid,
+        #[max_length = 20]
+        provider -> Varchar,
+        #[max_length = 20]
+        kind -> Varchar,
+        #[max_length = 255]
+        external_id -> Varchar,
+        url -> Text,
+        title -> Text,
+        #[max_length = 20]
+        state -> Nullable<Varchar>,
+        #[max_length = 255]
+        author -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_moves (id) {
+        id -> Uuid,
+        issue_id -> Uuid,
+        from_team_id -> Nullable<Uuid>,
+        to_team_id -> Nullable<Uuid>,
+        #[max_length = 32]
+        from_key -> Varchar,
+        #[max_length = 32]
+        to_key -> Varchar,
+        from_state_id -> Nullable<Uuid>,
+        to_state_id -> Nullable<Uuid>,
+        moved_by -> Uuid,
+        moved_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_relations (id) {
+        id -> Uuid,
+        issue_id -> Uuid,
+        related_issue_id -> Uuid,
+        #[max_length = 32]
+        relation_type -> Varchar,
+        created_by -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    issue_views (user_id, issue_id) {
+        user_id -> Uuid,
+        issue_id -> Uuid,
+        first_viewed_at -> Timestamptz,
+        last_viewed_at -> Timestamptz,
+        view_count -> Int4,
+    }
+}
+
+diesel::table! {
+    issues (id) {
+        id -> Uuid,
+        project_id -> Nullable<Uuid>,
+        cycle_id -> Nullable<Uuid>,
+        creator_id -> Uuid,
+        assignee_id -> Nullable<Uuid>,
+        parent_issue_id -> Nullable<Uuid>,
+        issue_number -> Int4,
+        #[max_length = 512]
+        title -> Varchar,
+        description -> Nullable<Text>,
+        priority -> Text,
+        is_changelog_candidate -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        team_id -> Uuid,
+        workflow_id -> Nullable<Uuid>,
+        workflow_state_id -> Nullable<Uuid>,
+        archived_at -> Nullable<Timestamptz>,
+        archive_batch_id -> Nullable<Uuid>,
+        estimate -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::LabelLevelEnum;
+
+    labels (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 7]
+        color -> Varchar,
+        level -> LabelLevelEnum,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    member_cost_rates (workspace_id, user_id) {
+        workspace_id -> Uuid,
+        user_id -> Uuid,
+        hourly_rate_cents -> Int8,
+        #[max_length = 3]
+        currency -> Varchar,
+        updated_by -> Nullable<Uuid>,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    member_import_rows (id) {
+        id -> Uuid,
+        import_id -> Uuid,
+        row_number -> Int4,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 20]
+        role -> Varchar,
+        team_ids -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        message -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    member_imports (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        requested_by -> Uuid,
+        #[max_length = 64]
+        content_hash -> Varchar,
+        #[max_length = 20]
+        status -> Varchar,
+        total_rows -> Int4,
+        created_at -> Timestamptz,
+        started_at -> Nullable<Timestamptz>,
+        completed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    milestone_issues (issue_id) {
+        issue_id -> Uuid,
+        milestone_id -> Uuid,
+        added_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    milestones (id) {
+        id -> Uuid,
+        project_id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        target_date -> Nullable<Date>,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        recipient_id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        #[max_length = 50]
+        event_type -> Varchar,
+        #[max_length = 50]
+        entity_type -> Varchar,
+        entity_id -> Uuid,
+        #[max_length = 255]
+        title -> Varchar,
+        body -> Nullable<Text>,
+        event_count -> Int4,
+        read_at -> Nullable<Timestamptz>,
+        email_pending -> Bool,
+        emailed_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        in_app -> Bool,
     }
 }
 
 diesel::table! {
-    issue_labels (issue_id, label_id) {
-        issue_id -> Uuid,
-        label_id -> Uuid,
+    oauth_access_tokens (id) {
+        id -> Uuid,
+        grant_id -> Uuid,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        #[max_length = 64]
+        refresh_token_hash -> Nullable<Varchar>,
+        scopes -> Text,
+        expires_at -> Timestamptz,
+        refresh_expires_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
     }
 }
 
 diesel::table! {
-    issues (id) {
+    oauth_apps (id) {
         id -> Uuid,
-        project_id -> Nullable<Uuid>,
-        cycle_id -> Nullable<Uuid>,
-        creator_id -> Uuid,
-        assignee_id -> Nullable<Uuid>,
-        parent_issue_id -> Nullable<Uuid>,
-        issue_number -> Int4,
-        #[max_length = 512]
-        title -> Varchar,
+        owner_id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
         description -> Nullable<Text>,
-        priority -> Text,
-        is_changelog_candidate -> Bool,
+        #[max_length = 64]
+        client_id -> Varchar,
+        #[max_length = 64]
+        client_secret_hash -> Varchar,
+        redirect_uris -> Text,
+        scopes -> Text,
+        #[max_length = 20]
+        tier -> Varchar,
+        disabled_at -> Nullable<Timestamptz>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
-        team_id -> Uuid,
-        workflow_id -> Nullable<Uuid>,
-        workflow_state_id -> Nullable<Uuid>,
+        webhook_url -> Nullable<Text>,
+        webhook_events -> Text,
+        webhook_filter -> Nullable<Text>,
     }
 }
 
 diesel::table! {
-    use diesel::sql_types::*;
-    use super::sql_types::LabelLevelEnum;
+    oauth_authorization_codes (code_hash) {
+        #[max_length = 64]
+        code_hash -> Varchar,
+        grant_id -> Uuid,
+        redirect_uri -> Text,
+        scopes -> Text,
+        #[max_length = 128]
+        code_challenge -> Nullable<Varchar>,
+        expires_at -> Timestamptz,
+        used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
 
-    labels (id) {
+diesel::table! {
+    oauth_grants (id) {
         id -> Uuid,
-        workspace_id -> Uuid,
-        #[max_length = 255]
-        name -> Varchar,
-        #[max_length = 7]
-        color -> Varchar,
-        level -> LabelLevelEnum,
+        app_id -> Uuid,
+        user_id -> Uuid,
+        scopes -> Text,
+        revoked_at -> Nullable<Timestamptz>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
     }
@@ -165,6 +810,35 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    organizations (id) {
+        id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        allow_cross_workspace_links -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    project_cost_entries (id) {
+        id -> Uuid,
+        project_id -> Uuid,
+        user_id -> Uuid,
+        issue_id -> Nullable<Uuid>,
+        minutes -> Int4,
+        hourly_rate_cents -> Int8,
+        amount_cents -> Int8,
+        #[max_length = 3]
+        currency -> Varchar,
+        spent_on -> Date,
+        description -> Nullable<Text>,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     project_statuses (id) {
         id -> Uuid,
@@ -197,6 +871,52 @@ diesel::table! {
         updated_at -> Timestamptz,
         project_status_id -> Uuid,
         priority -> Text,
+        #[max_length = 3]
+        budget_currency -> Nullable<Varchar>,
+        budget_amount_cents -> Nullable<Int8>,
+        budget_alert_percent -> Int4,
+        #[max_length = 16]
+        budget_alert_level -> Varchar,
+    }
+}
+
+diesel::table! {
+    push_deliveries (id) {
+        id -> Uuid,
+        subscription_id -> Uuid,
+        notification_id -> Uuid,
+        payload -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamptz,
+        delivered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    push_preferences (user_id) {
+        user_id -> Uuid,
+        enabled -> Bool,
+        muted_events -> Text,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    push_subscriptions (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 10]
+        platform -> Varchar,
+        token -> Text,
+        #[max_length = 100]
+        device_name -> Nullable<Varchar>,
+        last_delivered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -213,6 +933,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    scheduled_jobs (name) {
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 100]
+        schedule -> Varchar,
+        #[max_length = 20]
+        last_status -> Nullable<Varchar>,
+        last_started_at -> Nullable<Timestamptz>,
+        last_finished_at -> Nullable<Timestamptz>,
+        last_duration_ms -> Nullable<Int8>,
+        last_result -> Nullable<Text>,
+        last_error -> Nullable<Text>,
+        run_count -> Int8,
+        failure_count -> Int8,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    service_accounts (user_id) {
+        user_id -> Uuid,
+        workspace_id -> Uuid,
+        description -> Nullable<Text>,
+        created_by -> Nullable<Uuid>,
+        disabled_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    team_issue_counters (team_id) {
+        team_id -> Uuid,
+        last_issue_number -> Int4,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     team_members (user_id, team_id) {
         user_id -> Uuid,
@@ -223,6 +982,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    team_auto_close_policies (team_id) {
+        team_id -> Uuid,
+        enabled -> Bool,
+        state_ids -> Text,
+        inactive_days -> Int4,
+        warning_days -> Int4,
+        close_state_id -> Uuid,
+        exempt_label_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    team_cycle_settings (team_id) {
+        team_id -> Uuid,
+        auto_create -> Bool,
+        length_days -> Int4,
+        start_weekday -> Int4,
+        cooldown_days -> Int4,
+        roll_over_unfinished -> Bool,
+        last_rolled_over_cycle_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     teams (id) {
         id -> Uuid,
@@ -236,6 +1023,46 @@ diesel::table! {
         description -> Nullable<Text>,
         icon_url -> Nullable<Text>,
         is_private -> Bool,
+        parent_team_id -> Nullable<Uuid>,
+        #[max_length = 20]
+        estimate_scale -> Varchar,
+        #[max_length = 20]
+        assignment_mode -> Varchar,
+        default_assignee_id -> Nullable<Uuid>,
+        last_auto_assignee_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    telemetry_state (singleton) {
+        singleton -> Bool,
+        instance_id -> Uuid,
+        last_sent_at -> Nullable<Timestamptz>,
+        last_report -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    upload_sessions (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        issue_id -> Uuid,
+        uploader_id -> Uuid,
+        #[max_length = 255]
+        filename -> Varchar,
+        #[max_length = 255]
+        content_type -> Varchar,
+        size_bytes -> Int8,
+        storage_key -> Text,
+        multipart_upload_id -> Text,
+        upload_offset -> Int8,
+        parts -> Text,
+        attachment_id -> Nullable<Uuid>,
+        completed_at -> Nullable<Timestamptz>,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -256,6 +1083,35 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_identities (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 32]
+        provider -> Varchar,
+        #[max_length = 255]
+        provider_user_id -> Varchar,
+        #[max_length = 255]
+        email -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        last_login_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    user_preferences (user_id) {
+        user_id -> Uuid,
+        #[max_length = 64]
+        timezone -> Varchar,
+        #[max_length = 20]
+        locale -> Varchar,
+        #[max_length = 10]
+        theme -> Varchar,
+        muted_channels -> Text,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     user_sessions (id) {
         id -> Int4,
@@ -287,6 +1143,94 @@ diesel::table! {
         updated_at -> Timestamp,
         id -> Uuid,
         current_workspace_id -> Nullable<Uuid>,
+        #[max_length = 32]
+        asset_region -> Nullable<Varchar>,
+        is_service_account -> Bool,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Uuid,
+        app_id -> Nullable<Uuid>,
+        installation_id -> Nullable<Uuid>,
+        #[max_length = 64]
+        event -> Varchar,
+        payload -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        response_status -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamptz,
+        delivered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        webhook_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    webhook_signing_keys (id) {
+        id -> Uuid,
+        app_id -> Uuid,
+        #[max_length = 32]
+        key_id -> Varchar,
+        #[max_length = 128]
+        secret -> Varchar,
+        expires_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        url -> Text,
+        #[max_length = 128]
+        secret -> Varchar,
+        events -> Text,
+        is_active -> Bool,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    websocket_dead_letters (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 64]
+        command_type -> Varchar,
+        #[max_length = 255]
+        request_id -> Nullable<Varchar>,
+        command -> Text,
+        #[max_length = 50]
+        error_code -> Varchar,
+        error_message -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        created_at -> Timestamptz,
+        replayed_by -> Nullable<Uuid>,
+        replayed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    websocket_sessions (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 64]
+        connection_id -> Varchar,
+        #[max_length = 50]
+        client -> Varchar,
+        user_agent -> Nullable<Text>,
+        connected_at -> Timestamptz,
+        disconnected_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -334,6 +1278,78 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    workspace_email_domains (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        #[max_length = 253]
+        domain -> Varchar,
+        #[max_length = 100]
+        from_name -> Nullable<Varchar>,
+        #[max_length = 64]
+        from_local_part -> Varchar,
+        #[max_length = 63]
+        dkim_selector -> Varchar,
+        #[max_length = 64]
+        verification_token -> Varchar,
+        #[max_length = 20]
+        status -> Varchar,
+        last_error -> Nullable<Text>,
+        verified_at -> Nullable<Timestamptz>,
+        last_checked_at -> Nullable<Timestamptz>,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    workspace_holidays (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        holiday_date -> Date,
+        #[max_length = 50]
+        region -> Nullable<Varchar>,
+        #[max_length = 20]
+        source -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    workspace_imports (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        requested_by -> Uuid,
+        #[max_length = 16]
+        source -> Varchar,
+        #[max_length = 64]
+        content_hash -> Varchar,
+        payload -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        total_records -> Int4,
+        result -> Nullable<Text>,
+        created_at -> Timestamptz,
+        started_at -> Nullable<Timestamptz>,
+        completed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    workspace_member_changes (id) {
+        id -> Int8,
+        workspace_id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 20]
+        change -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::WorkspaceUserRole;
@@ -347,6 +1363,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    workspace_resets (id) {
+        id -> Uuid,
+        workspace_id -> Uuid,
+        requested_by -> Uuid,
+        #[max_length = 64]
+        profile -> Varchar,
+        #[max_length = 20]
+        status -> Varchar,
+        result -> Nullable<Text>,
+        error -> Nullable<Text>,
+        created_at -> Timestamptz,
+        started_at -> Nullable<Timestamptz>,
+        completed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WorkspaceUserRole;
+
+    workspace_settings (workspace_id) {
+        workspace_id -> Uuid,
+        default_workflow_id -> Nullable<Uuid>,
+        #[max_length = 10]
+        week_start_day -> Varchar,
+        #[max_length = 20]
+        estimate_scale -> Varchar,
+        allowed_email_domains -> Text,
+        features -> Text,
+        updated_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        archive_completed_after_days -> Nullable<Int4>,
+        auto_join_domains -> Text,
+        auto_join_role -> WorkspaceUserRole,
+    }
+}
+
 diesel::table! {
     workspaces (id) {
         id -> Uuid,
@@ -357,67 +1412,255 @@ diesel::table! {
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         logo_url -> Nullable<Text>,
+        organization_id -> Nullable<Uuid>,
     }
 }
 
+diesel::joinable!(api_keys -> users (created_by));
+diesel::joinable!(api_keys -> workspaces (workspace_id));
+diesel::joinable!(api_tokens -> users (user_id));
+diesel::joinable!(app_installations -> oauth_apps (app_id));
+diesel::joinable!(app_installations -> users (installed_by));
+diesel::joinable!(app_installations -> workspaces (workspace_id));
+diesel::joinable!(attachments -> issues (issue_id));
+diesel::joinable!(attachments -> users (uploader_id));
+diesel::joinable!(attachments -> workspaces (workspace_id));
+diesel::joinable!(audit_log -> users (actor_id));
+diesel::joinable!(audit_log -> workspaces (workspace_id));
+diesel::joinable!(automation_rules -> teams (team_id));
+diesel::joinable!(automation_rules -> users (created_by));
+diesel::joinable!(automation_rules -> workspaces (workspace_id));
+diesel::joinable!(automation_runs -> automation_rules (rule_id));
+diesel::joinable!(automation_runs -> issues (issue_id));
+diesel::joinable!(automation_runs -> users (actor_id));
+diesel::joinable!(automation_runs -> workspaces (workspace_id));
+diesel::joinable!(channel_permission_restrictions -> users (created_by));
+diesel::joinable!(channel_permission_restrictions -> workspaces (workspace_id));
 diesel::joinable!(comment_attachments -> comments (comment_id));
+diesel::joinable!(comment_flags -> comments (comment_id));
+diesel::joinable!(comment_flags -> workspaces (workspace_id));
 diesel::joinable!(comment_mentions -> comments (comment_id));
 diesel::joinable!(comment_mentions -> users (mentioned_user_id));
 diesel::joinable!(comment_reactions -> comments (comment_id));
 diesel::joinable!(comment_reactions -> users (user_id));
 diesel::joinable!(comments -> issues (issue_id));
 diesel::joinable!(comments -> users (author_id));
+diesel::joinable!(content_reports -> workspaces (workspace_id));
+diesel::joinable!(cross_workspace_issue_relations -> users (created_by));
+diesel::joinable!(cross_workspace_issue_relations -> workspaces (workspace_id));
+diesel::joinable!(custom_emojis -> users (created_by));
+diesel::joinable!(custom_emojis -> workspaces (workspace_id));
+diesel::joinable!(cycle_scope_changes -> cycles (cycle_id));
 diesel::joinable!(cycles -> teams (team_id));
+diesel::joinable!(delete_confirmations -> users (requested_by));
+diesel::joinable!(error_groups -> issues (issue_id));
+diesel::joinable!(error_groups -> workspaces (workspace_id));
+diesel::joinable!(error_tracking_integrations -> teams (team_id));
+diesel::joinable!(error_tracking_integrations -> users (created_by));
+diesel::joinable!(error_tracking_integrations -> workspaces (workspace_id));
+diesel::joinable!(external_references -> issues (issue_id));
+diesel::joinable!(external_references -> users (created_by));
+diesel::joinable!(external_references -> workspaces (workspace_id));
+diesel::joinable!(github_integrations -> users (created_by));
+diesel::joinable!(github_integrations -> workspaces (workspace_id));
+diesel::joinable!(import_external_ids -> workspaces (workspace_id));
 diesel::joinable!(invitations -> users (invited_by));
 diesel::joinable!(invitations -> workspaces (workspace_id));
+diesel::joinable!(issue_auto_close_warnings -> issues (issue_id));
+diesel::joinable!(issue_board_positions -> issues (issue_id));
+diesel::joinable!(issue_board_positions -> users (updated_by));
+diesel::joinable!(issue_form_submissions -> issue_forms (form_id));
+diesel::joinable!(issue_form_submissions -> issues (issue_id));
+diesel::joinable!(issue_form_submissions -> users (submitted_by));
+diesel::joinable!(issue_forms -> teams (team_id));
+diesel::joinable!(issue_forms -> users (created_by));
+diesel::joinable!(issue_forms -> workspaces (workspace_id));
+diesel::joinable!(issue_label_rule_applications -> issue_label_rules (rule_id));
+diesel::joinable!(issue_label_rule_applications -> issues (issue_id));
+diesel::joinable!(issue_label_rule_applications -> labels (label_id));
+diesel::joinable!(issue_label_rules -> labels (label_id));
+diesel::joinable!(issue_label_rules -> teams (team_id));
+diesel::joinable!(issue_label_rules -> users (created_by));
+diesel::joinable!(issue_label_rules -> workspaces (workspace_id));
 diesel::joinable!(issue_labels -> issues (issue_id));
 diesel::joinable!(issue_labels -> labels (label_id));
+diesel::joinable!(issue_archive_batches -> users (requested_by));
+diesel::joinable!(issue_archive_batches -> workspaces (workspace_id));
+diesel::joinable!(issue_links -> issues (issue_id));
+diesel::joinable!(issue_moves -> issues (issue_id));
+diesel::joinable!(issue_moves -> users (moved_by));
+diesel::joinable!(issue_relations -> users (created_by));
+diesel::joinable!(issue_views -> issues (issue_id));
+diesel::joinable!(issue_views -> users (user_id));
 diesel::joinable!(issues -> cycles (cycle_id));
+diesel::joinable!(issues -> issue_archive_batches (archive_batch_id));
 diesel::joinable!(issues -> projects (project_id));
 diesel::joinable!(issues -> teams (team_id));
 diesel::joinable!(issues -> workflow_states (workflow_state_id));
 diesel::joinable!(issues -> workflows (workflow_id));
 diesel::joinable!(labels -> workspaces (workspace_id));
+diesel::joinable!(member_cost_rates -> users (user_id));
+diesel::joinable!(member_cost_rates -> workspaces (workspace_id));
+diesel::joinable!(member_import_rows -> member_imports (import_id));
+diesel::joinable!(member_imports -> users (requested_by));
+diesel::joinable!(member_imports -> workspaces (workspace_id));
+diesel::joinable!(milestone_issues -> issues (issue_id));
+diesel::joinable!(milestone_issues -> milestones (milestone_id));
+diesel::joinable!(milestones -> projects (project_id));
+diesel::joinable!(milestones -> users (created_by));
+diesel::joinable!(notifications -> workspaces (workspace_id));
+diesel::joinable!(oauth_access_tokens -> oauth_grants (grant_id));
+diesel::joinable!(oauth_apps -> users (owner_id));
+diesel::joinable!(oauth_authorization_codes -> oauth_grants (grant_id));
+diesel::joinable!(oauth_grants -> oauth_apps (app_id));
+diesel::joinable!(oauth_grants -> users (user_id));
+diesel::joinable!(project_cost_entries -> issues (issue_id));
+diesel::joinable!(project_cost_entries -> projects (project_id));
+diesel::joinable!(project_cost_entries -> users (user_id));
 diesel::joinable!(project_statuses -> workspaces (workspace_id));
 diesel::joinable!(projects -> project_statuses (project_status_id));
 diesel::joinable!(projects -> roadmaps (roadmap_id));
 diesel::joinable!(projects -> users (owner_id));
 diesel::joinable!(projects -> workspaces (workspace_id));
+diesel::joinable!(push_deliveries -> notifications (notification_id));
+diesel::joinable!(push_deliveries -> push_subscriptions (subscription_id));
+diesel::joinable!(push_preferences -> users (user_id));
+diesel::joinable!(push_subscriptions -> users (user_id));
 diesel::joinable!(roadmaps -> workspaces (workspace_id));
+diesel::joinable!(service_accounts -> workspaces (workspace_id));
+diesel::joinable!(team_auto_close_policies -> labels (exempt_label_id));
+diesel::joinable!(team_auto_close_policies -> teams (team_id));
+diesel::joinable!(team_auto_close_policies -> workflow_states (close_state_id));
+diesel::joinable!(team_cycle_settings -> cycles (last_rolled_over_cycle_id));
+diesel::joinable!(team_cycle_settings -> teams (team_id));
+diesel::joinable!(team_issue_counters -> teams (team_id));
 diesel::joinable!(team_members -> teams (team_id));
 diesel::joinable!(team_members -> users (user_id));
 diesel::joinable!(teams -> workspaces (workspace_id));
+diesel::joinable!(upload_sessions -> attachments (attachment_id));
+diesel::joinable!(upload_sessions -> issues (issue_id));
+diesel::joinable!(upload_sessions -> users (uploader_id));
+diesel::joinable!(upload_sessions -> workspaces (workspace_id));
 diesel::joinable!(user_credentials -> users (user_id));
+diesel::joinable!(user_identities -> users (user_id));
+diesel::joinable!(user_preferences -> users (user_id));
 diesel::joinable!(user_sessions -> users (user_id));
 diesel::joinable!(users -> workspaces (current_workspace_id));
+diesel::joinable!(webhook_deliveries -> app_installations (installation_id));
+diesel::joinable!(webhook_deliveries -> oauth_apps (app_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhook_signing_keys -> oauth_apps (app_id));
+diesel::joinable!(webhooks -> users (created_by));
+diesel::joinable!(webhooks -> workspaces (workspace_id));
+diesel::joinable!(websocket_dead_letters -> workspaces (workspace_id));
+diesel::joinable!(websocket_sessions -> users (user_id));
+diesel::joinable!(websocket_sessions -> workspaces (workspace_id));
 diesel::joinable!(workflow_states -> workflows (workflow_id));
 diesel::joinable!(workflow_transitions -> workflows (workflow_id));
 diesel::joinable!(workflows -> teams (team_id));
+diesel::joinable!(workspace_email_domains -> users (created_by));
+diesel::joinable!(workspace_email_domains -> workspaces (workspace_id));
+diesel::joinable!(workspace_holidays -> workspaces (workspace_id));
+diesel::joinable!(workspace_imports -> users (requested_by));
+diesel::joinable!(workspace_imports -> workspaces (workspace_id));
+diesel::joinable!(workspace_member_changes -> workspaces (workspace_id));
 diesel::joinable!(workspace_members -> users (user_id));
 diesel::joinable!(workspace_members -> workspaces (workspace_id));
+diesel::joinable!(workspace_resets -> users (requested_by));
+diesel::joinable!(workspace_resets -> workspaces (workspace_id));
+diesel::joinable!(workspace_settings -> users (updated_by));
+diesel::joinable!(workspace_settings -> workflows (default_workflow_id));
+diesel::joinable!(workspace_settings -> workspaces (workspace_id));
+diesel::joinable!(workspaces -> organizations (organization_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
+    api_tokens,
+    api_usage_daily,
+    app_installations,
+    attachments,
+    audit_log,
+    automation_rules,
+    automation_runs,
+    channel_permission_restrictions,
     comment_attachments,
+    comment_flags,
     comment_mentions,
     comment_reactions,
     comments,
+    content_reports,
+    cross_workspace_issue_relations,
+    custom_emojis,
+    cycle_scope_changes,
     cycles,
+    delete_confirmations,
+    error_groups,
+    error_tracking_integrations,
+    external_references,
+    github_integrations,
+    import_external_ids,
     invitations,
+    issue_auto_close_warnings,
+    issue_board_positions,
+    issue_form_submissions,
+    issue_forms,
+    issue_label_rule_applications,
+    issue_label_rules,
     issue_labels,
+    issue_archive_batches,
+    issue_links,
+    issue_moves,
+    issue_relations,
+    issue_views,
     issues,
     labels,
+    member_cost_rates,
+    member_import_rows,
+    member_imports,
+    milestone_issues,
+    milestones,
+    notifications,
+    oauth_access_tokens,
+    oauth_apps,
+    oauth_authorization_codes,
+    oauth_grants,
     oauth_providers,
+    organizations,
+    project_cost_entries,
     project_statuses,
     projects,
+    push_deliveries,
+    push_preferences,
+    push_subscriptions,
     roadmaps,
+    scheduled_jobs,
+    service_accounts,
+    team_auto_close_policies,
+    team_cycle_settings,
+    team_issue_counters,
     team_members,
     teams,
+    telemetry_state,
+    upload_sessions,
     user_credentials,
+    user_identities,
+    user_preferences,
     user_sessions,
     users,
+    webhook_deliveries,
+    webhook_signing_keys,
+    webhooks,
+    websocket_dead_letters,
+    websocket_sessions,
     workflow_states,
     workflow_transitions,
     workflows,
+    workspace_email_domains,
+    workspace_holidays,
+    workspace_imports,
+    workspace_member_changes,
     workspace_members,
+    workspace_resets,
+    workspace_settings,
     workspaces,
 );
@@ -0,0 +1,231 @@
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    db::models::api_key::{
+        ApiKey, ApiKeyChanges, ApiKeyRecord, CreateApiKeyRequest, CreatedApiKey, NewApiKey,
+        UpdateApiKeyRequest,
+    },
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::oauth_app::oauth_scopes,
+    db::repositories::api_keys::ApiKeysRepo,
+    db::repositories::audit_log::AuditLogRepo,
+    error::AppError,
+    services::context::{AuthChannel, RequestContext},
+    services::permission_service::{Permission, PermissionService},
+    utils::clock,
+};
+
+/// Prefix that distinguishes workspace API keys from other bearer tokens
+pub const API_KEY_PREFIX: &str = "mk_";
+
+/// Workspace API keys: long-lived credentials for scripts and services. A key
+/// acts for the admin who created it, but only inside its workspace and only
+/// on routes its scopes cover.
+pub struct ApiKeysService;
+
+impl ApiKeysService {
+    pub fn is_api_key(token: &str) -> bool {
+        token.starts_with(API_KEY_PREFIX)
+    }
+
+    pub fn hash_key(raw: &str) -> String {
+        hex::encode(Sha256::digest(raw.as_bytes()))
+    }
+
+    fn generate_key() -> String {
+        format!(
+            "{}{}{}",
+            API_KEY_PREFIX,
+            clock::new_id().simple(),
+            clock::new_id().simple()
+        )
+    }
+
+    /// Scopes must be known and non-empty; duplicates are dropped
+    pub fn validate_scopes(scopes: &[String]) -> Result<Vec<String>, AppError> {
+        let scopes = oauth_scopes::parse(&scopes.join(" "));
+        if scopes.is_empty() {
+            return Err(AppError::validation("At least one scope is required"));
+        }
+        if let Some(unknown) = scopes.iter().find(|s| !oauth_scopes::is_valid(s)) {
+            return Err(AppError::validation(format!("Unknown scope: {}", unknown)));
+        }
+        Ok(scopes)
+    }
+
+    fn validate_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(AppError::validation(
+                "API key name must be between 1 and 100 characters",
+            ));
+        }
+        Ok(name.to_string())
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &CreateApiKeyRequest,
+    ) -> Result<CreatedApiKey, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        let name = Self::validate_name(&req.name)?;
+        let scopes = Self::validate_scopes(&req.scopes)?;
+        let expires_at = match req.expires_in_days {
+            Some(days) if !(1..=365).contains(&days) => {
+                return Err(AppError::validation(
+                    "expires_in_days must be between 1 and 365",
+                ));
+            }
+            Some(days) => Some(clock::now() + chrono::Duration::days(days)),
+            None => None,
+        };
+
+        let key = Self::generate_key();
+        let record = conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            let record = ApiKeysRepo::insert(
+                tx,
+                &NewApiKey {
+                    workspace_id: ctx.workspace_id,
+                    name,
+                    key_prefix: key[..API_KEY_PREFIX.len() + 8].to_string(),
+                    key_hash: Self::hash_key(&key),
+                    scopes: oauth_scopes::join(&scopes),
+                    created_by: ctx.user_id,
+                    expires_at,
+                },
+            )?;
+            Self::audit(tx, ctx, audit_actions::API_KEY_CREATED, &record)?;
+            Ok(record)
+        })?;
+        Ok(CreatedApiKey {
+            key,
+            api_key: record.into(),
+        })
+    }
+
+    pub fn list(conn: &mut PgConnection, ctx: &RequestContext) -> Result<Vec<ApiKey>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        Ok(ApiKeysRepo::list_by_workspace(conn, ctx.workspace_id)?
+            .into_iter()
+            .map(ApiKey::from)
+            .collect())
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        key_id: Uuid,
+    ) -> Result<ApiKey, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        Ok(Self::find(conn, ctx, key_id)?.into())
+    }
+
+    /// Rename a key or change its scopes; the secret stays the same
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        key_id: Uuid,
+        req: &UpdateApiKeyRequest,
+    ) -> Result<ApiKey, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        let existing = Self::find(conn, ctx, key_id)?;
+        if existing.revoked_at.is_some() {
+            return Err(AppError::conflict_with_code(
+                "API key has been revoked",
+                None,
+                "API_KEY_REVOKED",
+            ));
+        }
+        let changes = ApiKeyChanges {
+            name: req.name.as_deref().map(Self::validate_name).transpose()?,
+            scopes: req
+                .scopes
+                .as_deref()
+                .map(Self::validate_scopes)
+                .transpose()?
+                .map(|scopes| oauth_scopes::join(&scopes)),
+            updated_at: Some(clock::now()),
+        };
+        let record = conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            let record = ApiKeysRepo::update(tx, key_id, &changes)?;
+            Self::audit(tx, ctx, audit_actions::API_KEY_UPDATED, &record)?;
+            Ok(record)
+        })?;
+        Ok(record.into())
+    }
+
+    pub fn revoke(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        key_id: Uuid,
+    ) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        let record = Self::find(conn, ctx, key_id)?;
+        conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            if ApiKeysRepo::revoke(tx, key_id, clock::now())? > 0 {
+                Self::audit(tx, ctx, audit_actions::API_KEY_REVOKED, &record)?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Resolve a raw bearer key to an active key whose creator still belongs
+    /// to the key's workspace
+    pub fn authenticate(conn: &mut PgConnection, raw: &str) -> Result<ApiKeyRecord, AppError> {
+        let now = clock::now();
+        let key = ApiKeysRepo::find_by_hash(conn, &Self::hash_key(raw))?
+            .filter(|k| k.is_active(now))
+            .ok_or_else(|| AppError::auth("Invalid or expired API key"))?;
+        let ctx = RequestContext {
+            user_id: key.created_by,
+            workspace_id: key.workspace_id,
+            idempotency_key: None,
+            channel: AuthChannel::ApiKey,
+        };
+        if PermissionService::current_role(conn, &ctx)?.is_none() {
+            return Err(AppError::auth(
+                "The API key's owner is no longer a member of its workspace",
+            ));
+        }
+        ApiKeysRepo::touch_last_used(conn, key.id, now)?;
+        Ok(key)
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        key_id: Uuid,
+    ) -> Result<ApiKeyRecord, AppError> {
+        ApiKeysRepo::find_in_workspace(conn, ctx.workspace_id, key_id)?
+            .ok_or_else(|| AppError::not_found("api_key"))
+    }
+
+    fn audit(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        action: &str,
+        key: &ApiKeyRecord,
+    ) -> Result<(), diesel::result::Error> {
+        let details = serde_json::json!({
+            "name": key.name,
+            "key_prefix": key.key_prefix,
+            "scopes": oauth_scopes::parse(&key.scopes),
+        });
+        AuditLogRepo::insert(
+            conn,
+            &NewAuditEntry {
+                workspace_id: ctx.workspace_id,
+                actor_id: Some(ctx.user_id),
+                action: action.to_string(),
+                target_type: "api_key".to_string(),
+                target_id: Some(key.id),
+                details: Some(details.to_string()),
+            },
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    db::models::api_token::{
+        ApiToken, ApiUsageDaily, ApiUsageReport, CreateApiTokenRequest, CreatedApiToken,
+        DailyUsage, EndpointUsage, NewApiToken, api_client_types, api_tiers,
+    },
+    db::models::oauth_app::oauth_scopes,
+    db::repositories::api_tokens::{ApiTokensRepo, ApiUsageRepo},
+    error::AppError,
+    services::api_keys_service::ApiKeysService,
+    services::context::RequestContext,
+    utils::clock,
+};
+
+/// Prefix that distinguishes personal access tokens from JWTs in `Authorization`
+pub const API_TOKEN_PREFIX: &str = "mtm_";
+
+/// Longest usage window that can be requested, in days
+pub const MAX_USAGE_DAYS: i64 = 90;
+
+pub struct ApiTokensService;
+
+impl ApiTokensService {
+    pub fn is_api_token(raw: &str) -> bool {
+        raw.starts_with(API_TOKEN_PREFIX)
+    }
+
+    pub fn hash_token(raw: &str) -> String {
+        hex::encode(Sha256::digest(raw.as_bytes()))
+    }
+
+    fn generate_token() -> String {
+        format!(
+            "{}{}{}",
+            API_TOKEN_PREFIX,
+            clock::new_id().simple(),
+            clock::new_id().simple()
+        )
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &CreateApiTokenRequest,
+    ) -> Result<CreatedApiToken, AppError> {
+        let (name, expires_at) = Self::validate_request(req)?;
+
+        let scopes = req
+            .scopes
+            .as_deref()
+            .map(ApiKeysService::validate_scopes)
+            .transpose()?;
+        Self::issue(conn, ctx.user_id, name, expires_at, scopes)
+    }
+
+    /// Store a new token for `user_id` and return it with its plaintext
+    pub(crate) fn issue(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        name: &str,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        scopes: Option<Vec<String>>,
+    ) -> Result<CreatedApiToken, AppError> {
+        let token = Self::generate_token();
+        let api_token = ApiTokensRepo::insert(
+            conn,
+            &NewApiToken {
+                user_id,
+                name: name.to_string(),
+                token_prefix: token[..API_TOKEN_PREFIX.len() + 8].to_string(),
+                token_hash: Self::hash_token(&token),
+                expires_at,
+                scopes: scopes.map(|scopes| oauth_scopes::join(&scopes)),
+            },
+        )?;
+        Ok(CreatedApiToken { token, api_token })
+    }
+
+    /// Trimmed name and expiry of a token request
+    pub fn validate_request(
+        req: &CreateApiTokenRequest,
+    ) -> Result<(&str, Option<chrono::DateTime<chrono::Utc>>), AppError> {
+        let name = req.name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(AppError::validation(
+                "Token name must be between 1 and 100 characters",
+            ));
+        }
+        let expires_at = match req.expires_in_days {
+            Some(days) if !(1..=365).contains(&days) => {
+                return Err(AppError::validation(
+                    "expires_in_days must be between 1 and 365",
+                ));
+            }
+            Some(days) => Some(clock::now() + chrono::Duration::days(days)),
+            None => None,
+        };
+        Ok((name, expires_at))
+    }
+
+    pub fn list(conn: &mut PgConnection, ctx: &RequestContext) -> Result<Vec<ApiToken>, AppError> {
+        Ok(ApiTokensRepo::list_by_user(conn, ctx.user_id)?)
+    }
+
+    pub fn revoke(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        token_id: Uuid,
+    ) -> Result<(), AppError> {
+        ApiTokensRepo::find_for_user(conn, ctx.user_id, token_id)?
+            .ok_or_else(|| AppError::not_found("api_token"))?;
+        ApiTokensRepo::revoke(conn, token_id, clock::now())?;
+        Ok(())
+    }
+
+    /// Resolve a raw bearer token to an active API token
+    pub fn authenticate(conn: &mut PgConnection, raw: &str) -> Result<ApiToken, AppError> {
+        let now = clock::now();
+        let token = ApiTokensRepo::find_by_hash(conn, &Self::hash_token(raw))?
+            .filter(|t| t.is_active(now))
+            .ok_or_else(|| AppError::auth("Invalid or expired API token"))?;
+        ApiTokensRepo::touch_last_used(conn, token.id, now)?;
+        Ok(token)
+    }
+
+    /// Requests left today under the client's tier; `None` when unlimited.
+    /// Personal tokens share one quota per user and tier, so minting more
+    /// tokens does not multiply the limit
+    pub fn quota_remaining(
+        conn: &mut PgConnection,
+        client_type: &str,
+        client_id: Uuid,
+        user_id: Uuid,
+        tier: &str,
+    ) -> Result<Option<i64>, AppError> {
+        let Some(limit) = api_tiers::daily_request_limit(tier) else {
+            return Ok(None);
+        };
+        let today = clock::now().date_naive();
+        let used = if client_type == api_client_types::PERSONAL_TOKEN {
+            ApiUsageRepo::user_token_requests_on(conn, user_id, tier, today)?
+        } else {
+            ApiUsageRepo::requests_on(conn, client_type, client_id, today)?
+        };
+        Ok(Some((limit - used).max(0)))
+    }
+
+    pub fn record_usage(
+        conn: &mut PgConnection,
+        client_type: &str,
+        client_id: Uuid,
+        endpoint: &str,
+        is_error: bool,
+    ) -> Result<(), AppError> {
+        ApiUsageRepo::record(
+            conn,
+            client_type,
+            client_id,
+            clock::now().date_naive(),
+            endpoint,
+            is_error,
+        )?;
+        Ok(())
+    }
+
+    /// Usage of one of the current user's tokens over the last `days` days
+    pub fn usage(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        token_id: Uuid,
+        days: i64,
+    ) -> Result<ApiUsageReport, AppError> {
+        let token = ApiTokensRepo::find_for_user(conn, ctx.user_id, token_id)?
+            .ok_or_else(|| AppError::not_found("api_token"))?;
+
+        let to = clock::now().date_naive();
+        let from = to - chrono::Duration::days(days.clamp(1, MAX_USAGE_DAYS) - 1);
+        let rows =
+            ApiUsageRepo::list_since(conn, api_client_types::PERSONAL_TOKEN, token.id, from)?;
+        Ok(Self::summarize_usage(
+            api_client_types::PERSONAL_TOKEN,
+            token.id,
+            &token.tier,
+            from,
+            to,
+            &rows,
+        ))
+    }
+
+    /// Roll daily per-endpoint rows up into totals, per-endpoint and per-day
+    /// series; `to` is treated as today
+    pub fn summarize_usage(
+        client_type: &str,
+        client_id: Uuid,
+        tier: &str,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+        rows: &[ApiUsageDaily],
+    ) -> ApiUsageReport {
+        let mut endpoints: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+        let mut daily: BTreeMap<chrono::NaiveDate, (i64, i64)> = BTreeMap::new();
+        for row in rows {
+            let e = endpoints.entry(row.endpoint.as_str()).or_default();
+            e.0 += row.request_count;
+            e.1 += row.error_count;
+            let d = daily.entry(row.day).or_default();
+            d.0 += row.request_count;
+            d.1 += row.error_count;
+        }
+
+        let mut endpoints: Vec<EndpointUsage> = endpoints
+            .into_iter()
+            .map(|(endpoint, (requests, errors))| EndpointUsage {
+                endpoint: endpoint.to_string(),
+                requests,
+                errors,
+                error_rate: error_rate(requests, errors),
+            })
+            .collect();
+        endpoints.sort_by_key(|e| std::cmp::Reverse(e.requests));
+
+        let total_requests = endpoints.iter().map(|e| e.requests).sum();
+        let total_errors = endpoints.iter().map(|e| e.errors).sum();
+
+        ApiUsageReport {
+            client_type: client_type.to_string(),
+            client_id,
+            tier: tier.to_string(),
+            daily_limit: api_tiers::daily_request_limit(tier),
+            requests_today: daily.get(&to).map(|d| d.0).unwrap_or(0),
+            from,
+            to,
+            total_requests,
+            total_errors,
+            error_rate: error_rate(total_requests, total_errors),
+            endpoints,
+            daily: daily
+                .into_iter()
+                .map(|(day, (requests, errors))| DailyUsage {
+                    day,
+                    requests,
+                    errors,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn error_rate(requests: i64, errors: i64) -> f64 {
+    if requests == 0 {
+        0.0
+    } else {
+        errors as f64 / requests as f64
+    }
+}
@@ -0,0 +1,147 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::{
+        AppInstallation, InstallAppRequest, InstalledApp, UpdateInstallationRequest, webhook_events,
+    },
+    db::models::oauth_app::{OAuthApp, oauth_scopes},
+    db::repositories::app_installations::AppInstallationsRepo,
+    db::repositories::oauth_apps::OAuthAppsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::oauth_service::OAuthService,
+    services::webhook_service::WebhookService,
+    utils::clock,
+};
+
+pub struct AppInstallationsService;
+
+impl AppInstallationsService {
+    fn to_installed_app(installation: AppInstallation, app: OAuthApp) -> InstalledApp {
+        InstalledApp {
+            installation_id: installation.id,
+            app_id: app.id,
+            name: app.name,
+            description: app.description,
+            scopes: installation.scope_list(),
+            installed_by: installation.installed_by,
+            installed_at: installation.created_at,
+            updated_at: installation.updated_at,
+        }
+    }
+
+    fn normalize_scopes(requested: &[String], app: &OAuthApp) -> Result<String, AppError> {
+        let requested = oauth_scopes::parse(&requested.join(" "));
+        OAuthService::validate_scopes(&requested, &oauth_scopes::parse(&app.scopes))?;
+        Ok(oauth_scopes::join(&requested))
+    }
+
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<InstalledApp>, AppError> {
+        Ok(
+            AppInstallationsRepo::list_active_by_workspace(conn, ctx.workspace_id)?
+                .into_iter()
+                .map(|(installation, app)| Self::to_installed_app(installation, app))
+                .collect(),
+        )
+    }
+
+    /// Install an app in the current workspace. Installing an app that is already
+    /// installed updates its scopes instead.
+    pub fn install(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &InstallAppRequest,
+    ) -> Result<InstalledApp, AppError> {
+        let app = OAuthAppsRepo::find_app_by_client_id(conn, &req.client_id)?
+            .ok_or_else(|| AppError::not_found("oauth_app"))?;
+        let scopes = Self::normalize_scopes(&req.scopes, &app)?;
+
+        let existing = AppInstallationsRepo::find_for_app(conn, app.id, ctx.workspace_id)?
+            .filter(|installation| installation.uninstalled_at.is_none());
+        conn.transaction::<_, AppError, _>(|conn| {
+            let installation = match existing {
+                Some(installation) if installation.scopes == scopes => installation,
+                Some(installation) => {
+                    let updated =
+                        AppInstallationsRepo::update_scopes(conn, installation.id, &scopes)?;
+                    WebhookService::enqueue_lifecycle(
+                        conn,
+                        &app,
+                        &updated,
+                        webhook_events::INSTALLATION_PERMISSIONS_CHANGED,
+                    )?;
+                    updated
+                }
+                None => {
+                    let installed = AppInstallationsRepo::upsert(
+                        conn,
+                        app.id,
+                        ctx.workspace_id,
+                        ctx.user_id,
+                        &scopes,
+                    )?;
+                    WebhookService::enqueue_lifecycle(
+                        conn,
+                        &app,
+                        &installed,
+                        webhook_events::INSTALLATION_CREATED,
+                    )?;
+                    installed
+                }
+            };
+            Ok(Self::to_installed_app(installation, app.clone()))
+        })
+    }
+
+    /// Change the scopes an installed app holds in the current workspace
+    pub fn update_scopes(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        installation_id: Uuid,
+        req: &UpdateInstallationRequest,
+    ) -> Result<InstalledApp, AppError> {
+        let (installation, app) =
+            AppInstallationsRepo::find_active(conn, ctx.workspace_id, installation_id)?
+                .ok_or_else(|| AppError::not_found("app_installation"))?;
+        let scopes = Self::normalize_scopes(&req.scopes, &app)?;
+        if installation.scopes == scopes {
+            return Ok(Self::to_installed_app(installation, app));
+        }
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let updated = AppInstallationsRepo::update_scopes(conn, installation.id, &scopes)?;
+            WebhookService::enqueue_lifecycle(
+                conn,
+                &app,
+                &updated,
+                webhook_events::INSTALLATION_PERMISSIONS_CHANGED,
+            )?;
+            Ok(Self::to_installed_app(updated, app.clone()))
+        })
+    }
+
+    pub fn uninstall(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        installation_id: Uuid,
+    ) -> Result<(), AppError> {
+        let (installation, app) =
+            AppInstallationsRepo::find_active(conn, ctx.workspace_id, installation_id)?
+                .ok_or_else(|| AppError::not_found("app_installation"))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            WebhookService::enqueue_lifecycle(
+                conn,
+                &app,
+                &installation,
+                webhook_events::INSTALLATION_DELETED,
+            )?;
+            AppInstallationsRepo::uninstall(conn, installation.id, clock::now())?;
+            Ok(())
+        })
+    }
+}
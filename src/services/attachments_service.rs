@@ -0,0 +1,272 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor,
+    db::models::attachment::{
+        Attachment, AttachmentResponse, AttachmentUpload, CreateAttachmentRequest, NewAttachment,
+        attachment_status,
+    },
+    db::repositories::attachments::AttachmentsRepo,
+    db::repositories::issues::IssueRepo,
+    error::AppError,
+    services::context::RequestContext,
+    utils::clock,
+    utils::{AssetUrlHelper, ObjectStorage},
+};
+
+/// How long a presigned upload URL stays valid, in seconds
+pub const UPLOAD_URL_TTL_SECS: u64 = 15 * 60;
+
+/// How long a presigned download URL stays valid, in seconds
+pub const DOWNLOAD_URL_TTL_SECS: u64 = 60 * 60;
+
+/// Longest sanitized filename used in object keys
+const MAX_KEY_FILENAME_CHARS: usize = 100;
+
+pub struct AttachmentsService;
+
+impl AttachmentsService {
+    /// Filename safe to embed in an object key: the last path component with
+    /// anything outside `[A-Za-z0-9._-]` replaced by `_`
+    pub fn sanitize_filename(filename: &str) -> String {
+        let base = filename.rsplit(['/', '\\']).next().unwrap_or("");
+        let sanitized: String = base
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(MAX_KEY_FILENAME_CHARS)
+            .collect();
+        let sanitized = sanitized.trim_start_matches('.');
+        if sanitized.is_empty() {
+            "file".to_string()
+        } else {
+            sanitized.to_string()
+        }
+    }
+
+    pub(crate) fn require_storage(
+        storage: Option<&ObjectStorage>,
+    ) -> Result<&ObjectStorage, AppError> {
+        storage.ok_or_else(|| AppError::Config("Attachment storage is not configured".to_string()))
+    }
+
+    pub(crate) fn ensure_issue(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<(), AppError> {
+        if IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            Ok(())
+        } else {
+            Err(AppError::not_found("issue"))
+        }
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<Attachment, AppError> {
+        AttachmentsRepo::find_for_issue(conn, ctx.workspace_id, issue_id, attachment_id)?
+            .ok_or_else(|| AppError::not_found("attachment"))
+    }
+
+    pub(crate) fn to_response(
+        storage: &ObjectStorage,
+        asset_helper: &AssetUrlHelper,
+        attachment: Attachment,
+    ) -> AttachmentResponse {
+        AttachmentResponse {
+            url: asset_helper.build_attachment_url(&attachment.storage_key),
+            download_url: storage.presign("GET", &attachment.object_key(), DOWNLOAD_URL_TTL_SECS),
+            attachment,
+        }
+    }
+
+    /// Record a pending attachment and return a presigned URL the client
+    /// uploads the file body to
+    pub fn create_upload(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        max_bytes: i64,
+        issue_id: Uuid,
+        req: CreateAttachmentRequest,
+    ) -> Result<AttachmentUpload, AppError> {
+        let storage = Self::require_storage(storage)?;
+        let (filename, content_type) = Self::validate_upload(&req, max_bytes)?;
+        Self::ensure_issue(conn, ctx, issue_id)?;
+
+        let id = clock::new_id();
+        let new_attachment = NewAttachment {
+            id,
+            issue_id,
+            workspace_id: ctx.workspace_id,
+            uploader_id: Some(ctx.user_id),
+            storage_key: Self::storage_key(ctx.workspace_id, issue_id, id, &filename),
+            filename,
+            content_type,
+            size_bytes: req.size_bytes,
+        };
+        let attachment = AttachmentsRepo::insert(conn, &new_attachment)?;
+
+        Ok(AttachmentUpload {
+            upload_url: storage.presign("PUT", &attachment.object_key(), UPLOAD_URL_TTL_SECS),
+            upload_method: "PUT",
+            expires_at: clock::now() + chrono::Duration::seconds(UPLOAD_URL_TTL_SECS as i64),
+            attachment,
+        })
+    }
+
+    /// Trimmed filename and content type of an upload request, after checking
+    /// them and the declared size
+    pub(crate) fn validate_upload(
+        req: &CreateAttachmentRequest,
+        max_bytes: i64,
+    ) -> Result<(String, String), AppError> {
+        let filename = req.filename.trim();
+        if filename.is_empty() || filename.chars().count() > 255 {
+            return Err(AppError::validation(
+                "Filename must be between 1 and 255 characters",
+            ));
+        }
+        if filename.chars().any(char::is_control) {
+            return Err(AppError::validation(
+                "Filename must not contain control characters",
+            ));
+        }
+        let content_type = req.content_type.trim();
+        if content_type.len() > 255
+            || !content_type.contains('/')
+            || content_type
+                .chars()
+                .any(|c| c.is_whitespace() || c.is_control())
+        {
+            return Err(AppError::validation("Invalid content type"));
+        }
+        if req.size_bytes <= 0 || req.size_bytes > max_bytes {
+            return Err(AppError::validation(format!(
+                "Attachment size must be between 1 and {} bytes",
+                max_bytes
+            )));
+        }
+
+        Ok((filename.to_string(), content_type.to_string()))
+    }
+
+    /// `<workspace>/<issue>/<attachment>/<filename>`, unique per attachment
+    pub(crate) fn storage_key(
+        workspace_id: Uuid,
+        issue_id: Uuid,
+        id: Uuid,
+        filename: &str,
+    ) -> String {
+        format!(
+            "{}/{}/{}/{}",
+            workspace_id,
+            issue_id,
+            id,
+            Self::sanitize_filename(filename)
+        )
+    }
+
+    /// Confirm the object exists in storage and mark the attachment uploaded.
+    /// Oversized objects are removed and the attachment discarded. No
+    /// connection is held while storage is checked.
+    pub async fn complete_upload(
+        executor: &DbExecutor,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        asset_helper: &AssetUrlHelper,
+        max_bytes: i64,
+        issue_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<AttachmentResponse, AppError> {
+        let storage = Self::require_storage(storage)?;
+        let ctx_owned = ctx.clone();
+        let attachment = executor
+            .read(move |conn| Self::find(conn, &ctx_owned, issue_id, attachment_id))
+            .await?;
+        if attachment.status == attachment_status::UPLOADED {
+            return Ok(Self::to_response(storage, asset_helper, attachment));
+        }
+
+        let object_key = attachment.object_key();
+        let size = storage
+            .head(&object_key)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to check attachment: {}", e)))?
+            .ok_or_else(|| AppError::validation("Attachment has not been uploaded yet"))?;
+
+        let id = attachment.id;
+        if size > max_bytes {
+            executor
+                .transaction(move |conn| Ok(AttachmentsRepo::delete(conn, id)?))
+                .await?;
+            Self::delete_object_quietly(storage, &object_key).await;
+            return Err(AppError::validation(format!(
+                "Attachment exceeds the maximum size of {} bytes",
+                max_bytes
+            )));
+        }
+
+        let attachment = executor
+            .transaction(move |conn| Ok(AttachmentsRepo::mark_uploaded(conn, id, size)?))
+            .await?;
+        Ok(Self::to_response(storage, asset_helper, attachment))
+    }
+
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        asset_helper: &AssetUrlHelper,
+        issue_id: Uuid,
+    ) -> Result<Vec<AttachmentResponse>, AppError> {
+        let storage = Self::require_storage(storage)?;
+        Self::ensure_issue(conn, ctx, issue_id)?;
+
+        Ok(
+            AttachmentsRepo::list_uploaded_by_issue(conn, ctx.workspace_id, issue_id)?
+                .into_iter()
+                .map(|attachment| Self::to_response(storage, asset_helper, attachment))
+                .collect(),
+        )
+    }
+
+    /// Delete the attachment record, then its object once the delete has
+    /// committed. A failed object delete only leaves an orphan in the bucket,
+    /// so it is logged and not surfaced.
+    pub async fn delete(
+        executor: &DbExecutor,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        issue_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<(), AppError> {
+        let storage = Self::require_storage(storage)?;
+        let ctx = ctx.clone();
+        let attachment = executor
+            .transaction(move |conn| {
+                let attachment = Self::find(conn, &ctx, issue_id, attachment_id)?;
+                AttachmentsRepo::delete(conn, attachment.id)?;
+                Ok(attachment)
+            })
+            .await?;
+        Self::delete_object_quietly(storage, &attachment.object_key()).await;
+        Ok(())
+    }
+
+    async fn delete_object_quietly(storage: &ObjectStorage, object_key: &str) {
+        if let Err(e) = storage.delete(object_key).await {
+            tracing::warn!("Failed to delete attachment object {}: {}", object_key, e);
+        }
+    }
+}
@@ -1,19 +1,21 @@
 use bcrypt::{hash, verify};
-use chrono::Utc;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use crate::utils::AssetUrlHelper;
 use crate::{
+    cache::typed,
     db::models::auth::{
-        AuthUser, LoginRequest, LoginResponse, NewUser, NewUserCredential, RegisterRequest, User,
-        UserProfile,
+        AuthUser, ChangePasswordRequest, LoginRequest, LoginResponse, NewUser, NewUserCredential,
+        RegisterRequest, User, UserProfile,
     },
     db::models::{team::TeamInfo, workspace::WorkspaceInfo},
     db::repositories::auth::AuthRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
     error::AppError,
     middleware::auth::{AuthConfig, AuthService as JwtAuthService},
     services::context::RequestContext,
+    services::workspace_members_service::WORKSPACE_MEMBERS_CACHE,
     validation::auth::{
         UpdateProfileChanges, validate_login_request, validate_register_request,
         validate_update_profile,
@@ -48,9 +50,6 @@ impl AuthService {
             ));
         }
 
-        let _now = Utc::now().naive_utc();
-        let user_id = Uuid::new_v4();
-
         // Hash password
         let hashed_password = hash(&req.password, bcrypt::DEFAULT_COST)
             .map_err(|_| AppError::internal("Failed to hash password"))?;
@@ -66,7 +65,7 @@ impl AuthService {
 
         // Create credential
         let new_credential = NewUserCredential {
-            user_id,
+            user_id: user.id,
             credential_type: "password".to_string(),
             credential_hash: Some(hashed_password),
             oauth_provider_id: None,
@@ -75,6 +74,7 @@ impl AuthService {
         };
 
         AuthRepo::insert_credential(conn, &new_credential)?;
+        // 密码注册未验证邮箱，不按邮箱域名自动加入工作区
 
         // Generate JWT tokens
         let auth_config = AuthConfig::default();
@@ -139,6 +139,18 @@ impl AuthService {
             return Err(AppError::auth("Invalid email or password"));
         }
 
+        Self::issue_tokens(conn, &user, asset_helper)
+    }
+
+    /// Issue a fresh access/refresh token pair for a user
+    pub fn issue_tokens(
+        conn: &mut PgConnection,
+        user: &User,
+        asset_helper: &AssetUrlHelper,
+    ) -> Result<LoginResponse, AppError> {
+        if user.is_service_account {
+            return Err(AppError::auth("Service accounts cannot sign in"));
+        }
         // Generate JWT tokens using the proper JWT service
         let auth_config = AuthConfig::default();
         let jwt_service = JwtAuthService::new(auth_config);
@@ -204,6 +216,7 @@ impl AuthService {
             email: user.email,
             avatar_url: processed_avatar_url,
             current_workspace_id: user.current_workspace_id,
+            asset_region: user.asset_region,
             workspaces,
             teams,
         })
@@ -223,33 +236,49 @@ impl AuthService {
             avatar_url: changes.avatar_url.as_deref(),
         };
         validate_update_profile(&update_changes)?;
+        // An empty region clears the preference
+        let asset_region = changes
+            .asset_region
+            .as_deref()
+            .map(|region| region.trim().to_ascii_lowercase());
+        if let Some(region) = asset_region.as_deref()
+            && !region.is_empty()
+            && !asset_helper.has_region(region)
+        {
+            return Err(AppError::validation(format!(
+                "Unknown asset region '{}'; available regions: {}",
+                region,
+                asset_helper.region_names().join(", ")
+            )));
+        }
 
         // Check username uniqueness if username changes
-        if let Some(ref new_username) = changes.username {
-            if let Some(existing_user) = AuthRepo::find_by_username(conn, new_username)? {
-                if existing_user.id != ctx.user_id {
-                    return Err(AppError::conflict_with_code(
-                        "Username already exists",
-                        Some("username".to_string()),
-                        "USER_USERNAME_EXISTS",
-                    ));
-                }
-            }
+        if let Some(ref new_username) = changes.username
+            && let Some(existing_user) = AuthRepo::find_by_username(conn, new_username)?
+            && existing_user.id != ctx.user_id
+        {
+            return Err(AppError::conflict_with_code(
+                "Username already exists",
+                Some("username".to_string()),
+                "USER_USERNAME_EXISTS",
+            ));
         }
 
         // Check email uniqueness if email changes
-        if let Some(ref new_email) = changes.email {
-            if let Some(existing_user) = AuthRepo::find_by_email(conn, new_email)? {
-                if existing_user.id != ctx.user_id {
-                    return Err(AppError::conflict_with_code(
-                        "Email already exists",
-                        Some("email".to_string()),
-                        "USER_EMAIL_EXISTS",
-                    ));
-                }
-            }
+        if let Some(ref new_email) = changes.email
+            && let Some(existing_user) = AuthRepo::find_by_email(conn, new_email)?
+            && existing_user.id != ctx.user_id
+        {
+            return Err(AppError::conflict_with_code(
+                "Email already exists",
+                Some("email".to_string()),
+                "USER_EMAIL_EXISTS",
+            ));
         }
 
+        if let Some(region) = asset_region.as_deref() {
+            AuthRepo::set_asset_region(conn, ctx.user_id, Some(region).filter(|r| !r.is_empty()))?;
+        }
         let updated_user = AuthRepo::update_user_fields(
             conn,
             ctx.user_id,
@@ -261,6 +290,11 @@ impl AuthService {
             ),
         )?;
 
+        // Member lists show the user's name and avatar
+        for workspace_id in WorkspaceMembersRepo::workspace_ids_for_user(conn, ctx.user_id)? {
+            typed::invalidate_after_commit(&WORKSPACE_MEMBERS_CACHE, workspace_id);
+        }
+
         let processed_avatar_url = updated_user.get_processed_avatar_url(asset_helper);
         Ok(UserProfile {
             id: updated_user.id,
@@ -269,6 +303,7 @@ impl AuthService {
             email: updated_user.email,
             avatar_url: processed_avatar_url,
             current_workspace_id: updated_user.current_workspace_id,
+            asset_region: updated_user.asset_region,
             workspaces: vec![],
             teams: vec![],
         })
@@ -364,10 +399,59 @@ impl AuthService {
 
     /// Logout user - invalidate all active sessions
     pub fn logout(conn: &mut PgConnection, ctx: &RequestContext) -> Result<(), AppError> {
+        Self::end_sessions(conn, ctx.user_id)
+    }
+
+    /// Change the current user's password and end their other sessions.
+    /// Returns a fresh token pair so the caller stays signed in; outstanding
+    /// JWTs must be revoked by the caller.
+    pub fn change_password(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &ChangePasswordRequest,
+        asset_helper: &AssetUrlHelper,
+    ) -> Result<LoginResponse, AppError> {
+        let user =
+            AuthRepo::find_by_id(conn, ctx.user_id)?.ok_or_else(|| AppError::not_found("user"))?;
+        let credential = AuthRepo::find_credential_by_user_id(conn, user.id)?
+            .ok_or_else(|| AppError::validation("Account has no password to change"))?;
+        let current_hash = credential
+            .credential_hash
+            .as_deref()
+            .ok_or_else(|| AppError::validation("Account has no password to change"))?;
+
+        let is_valid = verify(&req.current_password, current_hash)
+            .map_err(|_| AppError::internal("Failed to verify password"))?;
+        if !is_valid {
+            return Err(AppError::auth("Current password is incorrect"));
+        }
+        if req.new_password == req.current_password {
+            return Err(AppError::validation(
+                "New password must differ from the current password",
+            ));
+        }
+
+        let new_hash = hash(&req.new_password, bcrypt::DEFAULT_COST)
+            .map_err(|_| AppError::internal("Failed to hash password"))?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            AuthRepo::update_credential_hash(conn, credential.id, &new_hash)?;
+            Self::end_sessions(conn, user.id)
+        })?;
+
+        Self::issue_tokens(conn, &user, asset_helper)
+    }
+
+    /// End every session of another user, e.g. for an admin force-logout
+    pub fn force_logout(conn: &mut PgConnection, target_user_id: Uuid) -> Result<(), AppError> {
+        AuthRepo::find_by_id(conn, target_user_id)?.ok_or_else(|| AppError::not_found("user"))?;
+        Self::end_sessions(conn, target_user_id)
+    }
+
+    fn end_sessions(conn: &mut PgConnection, target_user_id: Uuid) -> Result<(), AppError> {
         use crate::schema::user_sessions::dsl::*;
 
         // Set all active sessions for this user to inactive
-        diesel::update(user_sessions.filter(user_id.eq(ctx.user_id).and(is_active.eq(true))))
+        diesel::update(user_sessions.filter(user_id.eq(target_user_id).and(is_active.eq(true))))
             .set(is_active.eq(false))
             .execute(conn)
             .map_err(|_| AppError::internal("Failed to logout user"))?;
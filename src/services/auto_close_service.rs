@@ -0,0 +1,278 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::auto_close::{
+        AutoCloseAction, AutoClosePolicyResponse, AutoCloseSummary, NewTeamAutoClosePolicy,
+        TeamAutoClosePolicy, UpsertAutoClosePolicyRequest,
+    },
+    db::models::issue::Issue,
+    db::models::notification::{NewNotification, notification_events},
+    db::models::workflow::WorkflowStateCategory,
+    db::repositories::auto_close_policies::AutoClosePoliciesRepo,
+    db::repositories::labels::LabelRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::notifications_service::NotificationsService,
+    services::realtime_service::RealtimeService,
+    services::teams_service::TeamsService,
+    services::webhook_service::WebhookService,
+    websocket::{EntityAction, EntityKind, Topic},
+};
+
+/// Longest inactivity window a policy may use
+pub const MAX_INACTIVE_DAYS: i32 = 365;
+
+pub struct AutoCloseService;
+
+impl AutoCloseService {
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<AutoClosePolicyResponse, AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        let policy = AutoClosePoliciesRepo::find(conn, team_id)?
+            .ok_or_else(|| AppError::not_found("auto-close policy"))?;
+        Ok(policy.into())
+    }
+
+    pub fn upsert(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+        req: &UpsertAutoClosePolicyRequest,
+    ) -> Result<AutoClosePolicyResponse, AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        Self::validate_windows(req.inactive_days, req.warning_days)?;
+        if req.state_ids.is_empty() {
+            return Err(AppError::validation("At least one state is required"));
+        }
+        if req.state_ids.contains(&req.close_state_id) {
+            return Err(AppError::validation(
+                "The close state cannot also be a watched state",
+            ));
+        }
+
+        let team_states = WorkflowsRepo::list_states_by_team(conn, team_id)?;
+        if let Some(unknown) = req
+            .state_ids
+            .iter()
+            .find(|id| !team_states.iter().any(|s| s.id == **id))
+        {
+            return Err(AppError::validation(format!(
+                "State {} does not belong to this team",
+                unknown
+            )));
+        }
+        let close_state = team_states
+            .iter()
+            .find(|s| s.id == req.close_state_id)
+            .ok_or_else(|| AppError::validation("Close state does not belong to this team"))?;
+        if !matches!(
+            close_state.category,
+            WorkflowStateCategory::Completed | WorkflowStateCategory::Canceled
+        ) {
+            return Err(AppError::validation(
+                "Close state must be a completed or canceled state",
+            ));
+        }
+        if let Some(label_id) = req.exempt_label_id
+            && LabelRepo::find_by_id_in_workspace(conn, ctx.workspace_id, label_id)?.is_none()
+        {
+            return Err(AppError::not_found("label"));
+        }
+
+        let mut state_ids: Vec<String> = Vec::new();
+        for id in &req.state_ids {
+            let id = id.to_string();
+            if !state_ids.contains(&id) {
+                state_ids.push(id);
+            }
+        }
+
+        let policy = AutoClosePoliciesRepo::upsert(
+            conn,
+            &NewTeamAutoClosePolicy {
+                team_id,
+                enabled: req.enabled.unwrap_or(true),
+                state_ids: state_ids.join(" "),
+                inactive_days: req.inactive_days,
+                warning_days: req.warning_days,
+                close_state_id: req.close_state_id,
+                exempt_label_id: req.exempt_label_id,
+            },
+        )?;
+        Ok(policy.into())
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<(), AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        if AutoClosePoliciesRepo::delete(conn, team_id)? == 0 {
+            return Err(AppError::not_found("auto-close policy"));
+        }
+        Ok(())
+    }
+
+    /// The warning must leave at least a day and come before the close
+    pub fn validate_windows(inactive_days: i32, warning_days: i32) -> Result<(), AppError> {
+        if !(1..=MAX_INACTIVE_DAYS).contains(&inactive_days) {
+            return Err(AppError::validation(format!(
+                "inactive_days must be between 1 and {}",
+                MAX_INACTIVE_DAYS
+            )));
+        }
+        if warning_days < 1 || warning_days >= inactive_days {
+            return Err(AppError::validation(
+                "warning_days must be at least 1 and less than inactive_days",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Decide what to do with an issue. An issue is warned once it has been
+    /// inactive for `inactive_days - warning_days`, and closed only after it
+    /// is fully inactive and the warning has stood for `warning_days`. Any
+    /// activity after the warning voids it.
+    pub fn decide(
+        policy: &TeamAutoClosePolicy,
+        last_activity: DateTime<Utc>,
+        warned_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> AutoCloseAction {
+        let inactive = now - last_activity;
+        let warning = chrono::Duration::days(policy.warning_days as i64);
+        match warned_at.filter(|warned| *warned >= last_activity) {
+            Some(warned) => {
+                if inactive >= chrono::Duration::days(policy.inactive_days as i64)
+                    && now - warned >= warning
+                {
+                    AutoCloseAction::Close
+                } else {
+                    AutoCloseAction::None
+                }
+            }
+            None => {
+                let warn_after =
+                    chrono::Duration::days((policy.inactive_days - policy.warning_days) as i64);
+                if inactive >= warn_after {
+                    AutoCloseAction::Warn
+                } else {
+                    AutoCloseAction::None
+                }
+            }
+        }
+    }
+
+    /// Warn and close inactive issues for every enabled policy. A failing
+    /// policy is logged and skipped so the others still run.
+    pub fn run_due(
+        conn: &mut PgConnection,
+        now: DateTime<Utc>,
+    ) -> Result<AutoCloseSummary, AppError> {
+        let mut summary = AutoCloseSummary::default();
+        for (policy, workspace_id) in AutoClosePoliciesRepo::list_enabled(conn)? {
+            if let Err(e) = Self::run_policy(conn, &policy, workspace_id, now, &mut summary) {
+                tracing::warn!(
+                    "Auto-close policy for team {} failed: {}",
+                    policy.team_id,
+                    e
+                );
+            }
+        }
+        Ok(summary)
+    }
+
+    fn run_policy(
+        conn: &mut PgConnection,
+        policy: &TeamAutoClosePolicy,
+        workspace_id: Uuid,
+        now: DateTime<Utc>,
+        summary: &mut AutoCloseSummary,
+    ) -> Result<(), AppError> {
+        let issues = AutoClosePoliciesRepo::list_candidates(conn, policy)?;
+        if issues.is_empty() {
+            return Ok(());
+        }
+        let ids: Vec<Uuid> = issues.iter().map(|i| i.id).collect();
+        let last_comments = AutoClosePoliciesRepo::last_comment_at(conn, &ids)?;
+        let warnings = AutoClosePoliciesRepo::warnings_for(conn, &ids)?;
+
+        for issue in issues {
+            let last_activity = last_comments
+                .get(&issue.id)
+                .map_or(issue.updated_at, |at| (*at).max(issue.updated_at));
+            match Self::decide(policy, last_activity, warnings.get(&issue.id).copied(), now) {
+                AutoCloseAction::None => {}
+                AutoCloseAction::Warn => {
+                    Self::warn(conn, policy, workspace_id, &issue, now)?;
+                    summary.warned += 1;
+                }
+                AutoCloseAction::Close => {
+                    let closed =
+                        AutoClosePoliciesRepo::close_issue(conn, issue.id, policy.close_state_id)?;
+                    WebhookService::emit_quietly(
+                        conn,
+                        workspace_id,
+                        webhook_events::ISSUE_UPDATED,
+                        &closed,
+                    );
+                    RealtimeService::publish(
+                        workspace_id,
+                        Topic::Issue(closed.id),
+                        webhook_events::ISSUE_UPDATED,
+                        &closed,
+                    );
+                    RealtimeService::entity_changed(
+                        workspace_id,
+                        EntityKind::Issue,
+                        EntityAction::Updated,
+                        closed.id,
+                        &closed,
+                    );
+                    summary.closed += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn warn(
+        conn: &mut PgConnection,
+        policy: &TeamAutoClosePolicy,
+        workspace_id: Uuid,
+        issue: &Issue,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let recipient_id = issue.assignee_id.unwrap_or(issue.creator_id);
+        let notified = NotificationsService::notify(
+            conn,
+            NewNotification {
+                workspace_id,
+                recipient_id,
+                actor_id: None,
+                event_type: notification_events::AUTO_CLOSE_WARNING.to_string(),
+                entity_type: "issue".to_string(),
+                entity_id: issue.id,
+                title: issue.title.clone(),
+                body: Some(format!(
+                    "No activity for {} days; this issue will be closed in {} days unless it is updated",
+                    policy.inactive_days - policy.warning_days,
+                    policy.warning_days
+                )),
+            },
+        );
+        if let Err(e) = notified {
+            tracing::warn!("Failed to send auto-close warning for {}: {}", issue.id, e);
+        }
+        AutoClosePoliciesRepo::record_warning(conn, issue.id, now)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,651 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::automation::{
+        AutomationAction, AutomationConditions, AutomationEvent, AutomationRule,
+        AutomationRuleResponse, AutomationRun, AutomationRunOutcome, AutomationRunResponse,
+        CreateAutomationRuleRequest, NewAutomationRule, NewAutomationRun, UpdateAutomationRule,
+        UpdateAutomationRuleRequest, automation_run_status, automation_triggers,
+    },
+    db::models::issue::{Issue, UpdateIssue},
+    db::models::workspace_settings::workspace_features,
+    db::repositories::automations::{AutomationRulesRepo, AutomationRunsRepo},
+    db::repositories::cycles::CyclesRepo,
+    db::repositories::issue_label_rules::IssueLabelRulesRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::labels::LabelRepo,
+    db::repositories::projects::ProjectsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
+    db::with_txn,
+    error::AppError,
+    services::comments_service::CommentsService,
+    services::context::{AuthChannel, RequestContext},
+    services::issues_service::IssuesService,
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    services::teams_service::TeamsService,
+    services::webhook_service::WebhookService,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    utils::clock,
+    validation::comment::validate_create_comment,
+    websocket::{EntityAction, EntityKind, Topic},
+};
+
+const MAX_NAME_CHARS: usize = 255;
+const MAX_TITLE_FILTER_CHARS: usize = 200;
+
+/// Actions a single rule may run
+pub const MAX_ACTIONS: usize = 10;
+
+/// Attempts before a run is given up and marked failed
+pub const MAX_RUN_ATTEMPTS: i32 = 3;
+
+/// Runs evaluated per worker tick
+pub const RUN_BATCH_SIZE: i64 = 50;
+
+/// Runs listed in a rule's history
+pub const RUN_HISTORY_LIMIT: i64 = 100;
+
+const RETRY_BASE_SECS: i64 = 60;
+
+/// Workspace rules that react to issue changes. Changes made through the
+/// issue services queue a run for every enabled rule listening for them; the
+/// worker then checks each rule's conditions against the issue as it is by
+/// then and applies the actions. Changes made by actions do not queue runs
+/// themselves, so rules cannot trigger each other in a loop.
+pub struct AutomationsService;
+
+impl AutomationsService {
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<AutomationRuleResponse>, AppError> {
+        AutomationRulesRepo::list_by_workspace(conn, ctx.workspace_id)?
+            .into_iter()
+            .map(Self::to_response)
+            .collect()
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+    ) -> Result<AutomationRuleResponse, AppError> {
+        Self::to_response(Self::find(conn, ctx, rule_id)?)
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &CreateAutomationRuleRequest,
+    ) -> Result<AutomationRuleResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageAutomations)?;
+        let name = Self::validate_name(&req.name)?;
+        let trigger = Self::validate_trigger(&req.trigger)?;
+        if let Some(team_id) = req.team_id {
+            TeamsService::get(conn, ctx, team_id)?;
+        }
+        let conditions = Self::validate_conditions(conn, ctx, trigger, &req.conditions)?;
+        Self::validate_actions(conn, ctx, req.team_id, &req.actions)?;
+
+        let rule = AutomationRulesRepo::insert(
+            conn,
+            &NewAutomationRule {
+                workspace_id: ctx.workspace_id,
+                team_id: req.team_id,
+                name,
+                trigger: trigger.to_string(),
+                conditions: Self::encode(&conditions)?,
+                actions: Self::encode(&req.actions)?,
+                enabled: req.enabled,
+                created_by: Some(ctx.user_id),
+            },
+        )?;
+        Self::to_response(rule)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+        req: &UpdateAutomationRuleRequest,
+    ) -> Result<AutomationRuleResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageAutomations)?;
+        let rule = Self::find(conn, ctx, rule_id)?;
+
+        let mut changes = UpdateAutomationRule {
+            updated_at: Some(clock::now()),
+            enabled: req.enabled,
+            ..Default::default()
+        };
+        if let Some(name) = &req.name {
+            changes.name = Some(Self::validate_name(name)?);
+        }
+        if let Some(team_id) = req.team_id {
+            if let Some(team_id) = team_id {
+                TeamsService::get(conn, ctx, team_id)?;
+            }
+            changes.team_id = Some(team_id);
+        }
+        let trigger = match &req.trigger {
+            Some(trigger) => Self::validate_trigger(trigger)?,
+            None => Self::validate_trigger(&rule.trigger)?,
+        };
+        // Conditions depend on the trigger and actions on the team, so any
+        // of them changing checks the rule as it will be saved
+        if req.trigger.is_some() || req.conditions.is_some() {
+            let conditions = match &req.conditions {
+                Some(conditions) => conditions.clone(),
+                None => Self::decode_conditions(&rule)?,
+            };
+            let conditions = Self::validate_conditions(conn, ctx, trigger, &conditions)?;
+            changes.trigger = Some(trigger.to_string());
+            changes.conditions = Some(Self::encode(&conditions)?);
+        }
+        if req.team_id.is_some() || req.actions.is_some() {
+            let actions = match &req.actions {
+                Some(actions) => actions.clone(),
+                None => Self::decode_actions(&rule)?,
+            };
+            let team_id = changes.team_id.unwrap_or(rule.team_id);
+            Self::validate_actions(conn, ctx, team_id, &actions)?;
+            changes.actions = Some(Self::encode(&actions)?);
+        }
+
+        Self::to_response(AutomationRulesRepo::update(conn, rule.id, &changes)?)
+    }
+
+    /// Delete a rule and its run history; what it already did to issues stays
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+    ) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageAutomations)?;
+        let rule = Self::find(conn, ctx, rule_id)?;
+        AutomationRulesRepo::delete(conn, rule.id)?;
+        Ok(())
+    }
+
+    /// Recent runs of a rule, newest first
+    pub fn runs(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+    ) -> Result<Vec<AutomationRunResponse>, AppError> {
+        let rule = Self::find(conn, ctx, rule_id)?;
+        Ok(
+            AutomationRunsRepo::list_by_rule(conn, rule.id, RUN_HISTORY_LIMIT)?
+                .into_iter()
+                .map(|run| AutomationRunResponse {
+                    event: serde_json::from_str(&run.event).ok(),
+                    run,
+                })
+                .collect(),
+        )
+    }
+
+    /// Queue a run of every enabled rule of the issue's team listening for
+    /// one of `events`. Rules whose event conditions cannot match are left
+    /// out; the rest of their conditions are checked when the run executes.
+    pub fn enqueue(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue: &Issue,
+        events: &[AutomationEvent],
+    ) -> Result<usize, AppError> {
+        if !WorkspaceSettingsService::feature_enabled(
+            conn,
+            ctx.workspace_id,
+            workspace_features::AUTOMATIONS,
+        )? {
+            return Ok(0);
+        }
+        let mut runs = Vec::new();
+        for event in events {
+            let rules = AutomationRulesRepo::list_enabled_for(
+                conn,
+                ctx.workspace_id,
+                issue.team_id,
+                event.trigger(),
+            )?;
+            for rule in rules {
+                match Self::decode_conditions(&rule) {
+                    Ok(conditions) if Self::event_matches(&conditions, event) => {}
+                    Ok(_) => continue,
+                    Err(e) => {
+                        tracing::warn!("Skipping automation rule {}: {}", rule.id, e);
+                        continue;
+                    }
+                }
+                runs.push(NewAutomationRun {
+                    rule_id: rule.id,
+                    workspace_id: ctx.workspace_id,
+                    issue_id: issue.id,
+                    actor_id: Some(ctx.user_id),
+                    event: Self::encode(event)?,
+                });
+            }
+        }
+        if runs.is_empty() {
+            return Ok(0);
+        }
+        Ok(AutomationRunsRepo::insert_many(conn, &runs)?)
+    }
+
+    /// Like [`Self::enqueue`], but a failure only logs a warning so the
+    /// change that triggered it still goes through
+    pub fn enqueue_quietly(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue: &Issue,
+        events: &[AutomationEvent],
+    ) {
+        if events.is_empty() {
+            return;
+        }
+        // A savepoint keeps a failure here from aborting the caller's transaction
+        if let Err(e) = with_txn(conn, |conn| Self::enqueue(conn, ctx, issue, events)) {
+            tracing::warn!("Failed to queue automations for {}: {}", issue.id, e);
+        }
+    }
+
+    /// Whether the conditions about the triggering change itself hold
+    pub fn event_matches(conditions: &AutomationConditions, event: &AutomationEvent) -> bool {
+        match event {
+            AutomationEvent::IssueCreated => true,
+            AutomationEvent::IssueStateChanged {
+                from_state_id,
+                to_state_id,
+            } => {
+                conditions
+                    .from_state_id
+                    .is_none_or(|state| *from_state_id == Some(state))
+                    && conditions
+                        .to_state_id
+                        .is_none_or(|state| *to_state_id == Some(state))
+            }
+            AutomationEvent::IssueLabelAdded { label_id } => conditions
+                .added_label_id
+                .is_none_or(|added| added == *label_id),
+        }
+    }
+
+    /// Whether every condition holds for the issue with `label_ids` after
+    /// `event`
+    pub fn matches(
+        conditions: &AutomationConditions,
+        issue: &Issue,
+        label_ids: &[Uuid],
+        event: &AutomationEvent,
+    ) -> bool {
+        Self::event_matches(conditions, event)
+            && (conditions.priorities.is_empty() || conditions.priorities.contains(&issue.priority))
+            && conditions
+                .label_ids
+                .iter()
+                .all(|label_id| label_ids.contains(label_id))
+            && conditions
+                .project_id
+                .is_none_or(|project_id| issue.project_id == Some(project_id))
+            && conditions
+                .assigned
+                .is_none_or(|assigned| issue.assignee_id.is_some() == assigned)
+            && conditions
+                .title_contains
+                .as_deref()
+                .is_none_or(|text| issue.title.to_lowercase().contains(&text.to_lowercase()))
+    }
+
+    /// Execute every due run once and record the outcome
+    pub fn run_due(conn: &mut PgConnection) -> Result<usize, AppError> {
+        let due = AutomationRunsRepo::list_due(conn, clock::now(), RUN_BATCH_SIZE)?;
+        for run in &due {
+            Self::run(conn, run)?;
+        }
+        Ok(due.len())
+    }
+
+    /// Execute one run: skipped when the rule is gone, disabled or no longer
+    /// matches the issue, retried with backoff when an action fails
+    pub fn run(conn: &mut PgConnection, run: &AutomationRun) -> Result<AutomationRun, AppError> {
+        let result = with_txn(conn, |conn| Self::execute(conn, run));
+        let now = clock::now();
+        let attempts = run.attempts + 1;
+        let outcome = match result {
+            Ok(applied) => AutomationRunOutcome {
+                status: if applied {
+                    automation_run_status::SUCCEEDED.to_string()
+                } else {
+                    automation_run_status::SKIPPED.to_string()
+                },
+                attempts,
+                last_error: None,
+                next_attempt_at: now,
+                finished_at: Some(now),
+            },
+            Err(e) => {
+                tracing::warn!("Automation run {} failed: {}", run.id, e);
+                let give_up = attempts >= MAX_RUN_ATTEMPTS;
+                AutomationRunOutcome {
+                    status: if give_up {
+                        automation_run_status::FAILED.to_string()
+                    } else {
+                        automation_run_status::PENDING.to_string()
+                    },
+                    attempts,
+                    last_error: Some(e.to_string()),
+                    next_attempt_at: now + Self::retry_delay(attempts),
+                    finished_at: give_up.then_some(now),
+                }
+            }
+        };
+        Ok(AutomationRunsRepo::record(conn, run.id, &outcome)?)
+    }
+
+    pub fn retry_delay(attempts: i32) -> chrono::Duration {
+        chrono::Duration::seconds(RETRY_BASE_SECS << attempts.clamp(0, 10))
+    }
+
+    /// Apply the rule's actions when its conditions hold; `false` when the
+    /// run was skipped
+    fn execute(conn: &mut PgConnection, run: &AutomationRun) -> Result<bool, AppError> {
+        let Some(rule) = AutomationRulesRepo::find(conn, run.rule_id)?.filter(|rule| rule.enabled)
+        else {
+            return Ok(false);
+        };
+        let Some(issue) =
+            IssueRepo::find_by_id(conn, run.issue_id)?.filter(|issue| issue.archived_at.is_none())
+        else {
+            return Ok(false);
+        };
+        // The issue may have moved to a team the rule does not cover
+        if rule.team_id.is_some_and(|team_id| team_id != issue.team_id) {
+            return Ok(false);
+        }
+        let event: AutomationEvent = serde_json::from_str(&run.event)
+            .map_err(|e| AppError::internal(format!("Invalid automation event: {}", e)))?;
+        let conditions = Self::decode_conditions(&rule)?;
+        let label_ids: Vec<Uuid> = LabelRepo::list_by_issue(conn, issue.id)?
+            .into_iter()
+            .map(|label| label.id)
+            .collect();
+        if !Self::matches(&conditions, &issue, &label_ids, &event) {
+            return Ok(false);
+        }
+
+        let actions = Self::decode_actions(&rule)?;
+        Self::apply(conn, &rule, issue, &label_ids, &actions)?;
+        Ok(true)
+    }
+
+    fn apply(
+        conn: &mut PgConnection,
+        rule: &AutomationRule,
+        issue: Issue,
+        label_ids: &[Uuid],
+        actions: &[AutomationAction],
+    ) -> Result<(), AppError> {
+        let mut changes = UpdateIssue::default();
+        let mut new_labels: Vec<Uuid> = Vec::new();
+        for action in actions {
+            match action {
+                AutomationAction::SetAssignee { assignee_id } => {
+                    if let Some(assignee_id) = assignee_id
+                        && WorkspaceMembersRepo::find(conn, rule.workspace_id, *assignee_id)?
+                            .is_none()
+                    {
+                        return Err(AppError::validation(
+                            "Assignee is no longer a member of the workspace",
+                        ));
+                    }
+                    if issue.assignee_id != *assignee_id {
+                        changes.assignee_id = Some(*assignee_id);
+                    }
+                }
+                AutomationAction::AddLabel { label_id } => {
+                    if LabelRepo::find_by_id_in_workspace(conn, rule.workspace_id, *label_id)?
+                        .is_none()
+                    {
+                        return Err(AppError::validation("Label no longer exists"));
+                    }
+                    if !label_ids.contains(label_id) && !new_labels.contains(label_id) {
+                        new_labels.push(*label_id);
+                    }
+                }
+                AutomationAction::MoveToCycle { cycle_id } => {
+                    let cycle =
+                        CyclesRepo::find_by_id_in_workspace(conn, rule.workspace_id, *cycle_id)?
+                            .ok_or_else(|| AppError::validation("Cycle no longer exists"))?;
+                    if cycle.team_id != issue.team_id {
+                        return Err(AppError::validation(
+                            "Cycle belongs to a different team than the issue",
+                        ));
+                    }
+                    if issue.cycle_id != Some(cycle.id) {
+                        changes.cycle_id = Some(Some(cycle.id));
+                    }
+                }
+                AutomationAction::PostComment { content } => {
+                    let author_id = rule.created_by.ok_or_else(|| {
+                        AppError::validation("The rule's creator no longer exists to comment as")
+                    })?;
+                    let ctx = RequestContext {
+                        user_id: author_id,
+                        workspace_id: rule.workspace_id,
+                        idempotency_key: None,
+                        channel: AuthChannel::Session,
+                    };
+                    CommentsService::create(conn, &ctx, issue.id, content.clone())?;
+                }
+            }
+        }
+
+        if !new_labels.is_empty() {
+            IssueLabelRulesRepo::add_issue_labels(conn, issue.id, &new_labels)?;
+        }
+        let has_field_changes = changes.assignee_id.is_some() || changes.cycle_id.is_some();
+        let updated = if has_field_changes {
+            use crate::schema::issues::dsl as i;
+            diesel::update(i::issues.filter(i::id.eq(issue.id)))
+                .set(&changes)
+                .returning(Issue::as_returning())
+                .get_result(conn)?
+        } else {
+            issue
+        };
+        if has_field_changes || !new_labels.is_empty() {
+            WebhookService::emit_quietly(
+                conn,
+                rule.workspace_id,
+                webhook_events::ISSUE_UPDATED,
+                &updated,
+            );
+            RealtimeService::publish(
+                rule.workspace_id,
+                Topic::Issue(updated.id),
+                webhook_events::ISSUE_UPDATED,
+                &updated,
+            );
+            RealtimeService::entity_changed(
+                rule.workspace_id,
+                EntityKind::Issue,
+                EntityAction::Updated,
+                updated.id,
+                &updated,
+            );
+        }
+        Ok(())
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+    ) -> Result<AutomationRule, AppError> {
+        AutomationRulesRepo::find_in_workspace(conn, ctx.workspace_id, rule_id)?
+            .ok_or_else(|| AppError::not_found("automation_rule"))
+    }
+
+    fn to_response(rule: AutomationRule) -> Result<AutomationRuleResponse, AppError> {
+        Ok(AutomationRuleResponse {
+            conditions: Self::decode_conditions(&rule)?,
+            actions: Self::decode_actions(&rule)?,
+            rule,
+        })
+    }
+
+    fn encode<T: serde::Serialize + ?Sized>(value: &T) -> Result<String, AppError> {
+        serde_json::to_string(value)
+            .map_err(|e| AppError::internal(format!("Failed to encode automation: {}", e)))
+    }
+
+    fn decode_conditions(rule: &AutomationRule) -> Result<AutomationConditions, AppError> {
+        serde_json::from_str(&rule.conditions)
+            .map_err(|e| AppError::internal(format!("Invalid automation conditions: {}", e)))
+    }
+
+    fn decode_actions(rule: &AutomationRule) -> Result<Vec<AutomationAction>, AppError> {
+        serde_json::from_str(&rule.actions)
+            .map_err(|e| AppError::internal(format!("Invalid automation actions: {}", e)))
+    }
+
+    fn validate_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(AppError::validation(format!(
+                "Rule name must be 1-{} characters",
+                MAX_NAME_CHARS
+            )));
+        }
+        Ok(name.to_string())
+    }
+
+    fn validate_trigger(trigger: &str) -> Result<&'static str, AppError> {
+        automation_triggers::ALL
+            .into_iter()
+            .find(|known| *known == trigger)
+            .ok_or_else(|| {
+                AppError::validation(format!(
+                    "Invalid trigger '{}'; expected one of: {}",
+                    trigger,
+                    automation_triggers::ALL.join(", ")
+                ))
+            })
+    }
+
+    /// Check that the conditions fit the trigger and refer to the
+    /// workspace's records; returns them with the title filter trimmed
+    pub fn validate_conditions(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        trigger: &str,
+        conditions: &AutomationConditions,
+    ) -> Result<AutomationConditions, AppError> {
+        let mut conditions = conditions.clone();
+        if trigger != automation_triggers::ISSUE_STATE_CHANGED
+            && (conditions.from_state_id.is_some() || conditions.to_state_id.is_some())
+        {
+            return Err(AppError::validation(
+                "from_state_id and to_state_id need the issue_state_changed trigger",
+            ));
+        }
+        if trigger != automation_triggers::ISSUE_LABEL_ADDED && conditions.added_label_id.is_some()
+        {
+            return Err(AppError::validation(
+                "added_label_id needs the issue_label_added trigger",
+            ));
+        }
+        for priority in &conditions.priorities {
+            IssuesService::parse_priority(priority)?;
+        }
+        for label_id in conditions
+            .label_ids
+            .iter()
+            .chain(&conditions.added_label_id)
+        {
+            if LabelRepo::find_by_id_in_workspace(conn, ctx.workspace_id, *label_id)?.is_none() {
+                return Err(AppError::validation("Invalid label_id for workspace"));
+            }
+        }
+        if let Some(project_id) = conditions.project_id
+            && ProjectsRepo::find_by_id_in_workspace(conn, ctx.workspace_id, project_id)?.is_none()
+        {
+            return Err(AppError::validation("Invalid project_id for workspace"));
+        }
+        for state_id in conditions
+            .from_state_id
+            .iter()
+            .chain(&conditions.to_state_id)
+        {
+            if WorkflowsRepo::find_state_team_in_workspace(conn, ctx.workspace_id, *state_id)?
+                .is_none()
+            {
+                return Err(AppError::validation("Invalid workflow state for workspace"));
+            }
+        }
+        if let Some(text) = &conditions.title_contains {
+            let text = text.trim();
+            if text.is_empty() || text.chars().count() > MAX_TITLE_FILTER_CHARS {
+                return Err(AppError::validation(format!(
+                    "title_contains must be 1-{} characters",
+                    MAX_TITLE_FILTER_CHARS
+                )));
+            }
+            conditions.title_contains = Some(text.to_string());
+        }
+        Ok(conditions)
+    }
+
+    /// Check that the actions refer to the workspace's records; a cycle must
+    /// belong to the rule's team when it has one
+    pub fn validate_actions(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Option<Uuid>,
+        actions: &[AutomationAction],
+    ) -> Result<(), AppError> {
+        if actions.is_empty() || actions.len() > MAX_ACTIONS {
+            return Err(AppError::validation(format!(
+                "A rule needs 1-{} actions",
+                MAX_ACTIONS
+            )));
+        }
+        for action in actions {
+            match action {
+                AutomationAction::SetAssignee { assignee_id } => {
+                    if let Some(assignee_id) = assignee_id
+                        && WorkspaceMembersRepo::find(conn, ctx.workspace_id, *assignee_id)?
+                            .is_none()
+                    {
+                        return Err(AppError::validation(
+                            "Assignee must be a member of the workspace",
+                        ));
+                    }
+                }
+                AutomationAction::AddLabel { label_id } => {
+                    if LabelRepo::find_by_id_in_workspace(conn, ctx.workspace_id, *label_id)?
+                        .is_none()
+                    {
+                        return Err(AppError::validation("Invalid label_id for workspace"));
+                    }
+                }
+                AutomationAction::MoveToCycle { cycle_id } => {
+                    let cycle =
+                        CyclesRepo::find_by_id_in_workspace(conn, ctx.workspace_id, *cycle_id)?
+                            .ok_or_else(|| {
+                                AppError::validation("Invalid cycle_id for workspace")
+                            })?;
+                    if team_id.is_some_and(|team_id| team_id != cycle.team_id) {
+                        return Err(AppError::validation("Cycle must belong to the rule's team"));
+                    }
+                }
+                AutomationAction::PostComment { content } => validate_create_comment(content)?,
+            }
+        }
+        Ok(())
+    }
+}
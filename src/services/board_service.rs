@@ -0,0 +1,137 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::automation::AutomationEvent,
+    db::models::board::{BoardReorderResult, ReorderIssueRequest},
+    db::models::issue::Issue,
+    db::repositories::board_positions::BoardPositionsRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::automations_service::AutomationsService,
+    services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    services::webhook_service::WebhookService,
+    websocket::{IssueMoveLock, Topic},
+};
+
+/// Realtime events published to the issue's team board
+pub const ISSUE_LOCKED_FOR_MOVE: &str = "issue_locked_for_move";
+pub const ISSUE_MOVE_UNLOCKED: &str = "issue_move_unlocked";
+pub const ISSUE_REORDERED: &str = "issue_reordered";
+
+pub struct BoardService;
+
+impl BoardService {
+    /// Team whose board shows the issue, checked against the workspace
+    pub fn issue_team(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<Uuid, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let issue =
+            IssueRepo::find_by_id(conn, issue_id)?.ok_or_else(|| AppError::not_found("issue"))?;
+        Ok(issue.team_id)
+    }
+
+    /// Tell the team board who is dragging the issue
+    pub fn publish_lock(ctx: &RequestContext, team_id: Uuid, lock: &IssueMoveLock) {
+        let event = if lock.holders.is_empty() {
+            ISSUE_MOVE_UNLOCKED
+        } else {
+            ISSUE_LOCKED_FOR_MOVE
+        };
+        RealtimeService::publish(ctx.workspace_id, Topic::Team(team_id), event, lock);
+    }
+
+    /// Move the issue to a column and position. Concurrent moves are settled
+    /// by the position version: the first write wins and a move based on an
+    /// older version fails with `MOVE_CONFLICT`.
+    pub fn reorder(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        req: &ReorderIssueRequest,
+    ) -> Result<BoardReorderResult, AppError> {
+        if !req.position.is_finite() {
+            return Err(AppError::validation("position must be a finite number"));
+        }
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let issue =
+            IssueRepo::find_by_id(conn, issue_id)?.ok_or_else(|| AppError::not_found("issue"))?;
+        let state_change = match req.workflow_state_id {
+            Some(state_id) if issue.workflow_state_id != Some(state_id) => {
+                let state = WorkflowsRepo::list_states_by_team(conn, issue.team_id)?
+                    .into_iter()
+                    .find(|s| s.id == state_id)
+                    .ok_or_else(|| AppError::validation("State does not belong to this team"))?;
+                Some(state)
+            }
+            _ => None,
+        };
+        let from_state_id = issue.workflow_state_id;
+
+        let result = conn.transaction::<_, AppError, _>(|conn| {
+            let position = BoardPositionsRepo::apply(
+                conn,
+                issue.id,
+                req.position,
+                req.expected_version,
+                ctx.user_id,
+            )?
+            .ok_or_else(|| {
+                AppError::conflict_with_code(
+                    "The issue was moved by someone else; reload its position",
+                    Some("expected_version".to_string()),
+                    "MOVE_CONFLICT",
+                )
+            })?;
+            let issue = match &state_change {
+                Some(state) => {
+                    use crate::schema::issues::dsl as i;
+                    diesel::update(i::issues.filter(i::id.eq(issue.id)))
+                        .set((
+                            i::workflow_id.eq(state.workflow_id),
+                            i::workflow_state_id.eq(state.id),
+                        ))
+                        .returning(Issue::as_returning())
+                        .get_result(conn)?
+                }
+                None => issue,
+            };
+            Ok(BoardReorderResult { issue, position })
+        })?;
+
+        if state_change.is_some() {
+            WebhookService::emit_quietly(
+                conn,
+                ctx.workspace_id,
+                webhook_events::ISSUE_UPDATED,
+                &result.issue,
+            );
+            AutomationsService::enqueue_quietly(
+                conn,
+                ctx,
+                &result.issue,
+                &[AutomationEvent::IssueStateChanged {
+                    from_state_id,
+                    to_state_id: result.issue.workflow_state_id,
+                }],
+            );
+        }
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Team(result.issue.team_id),
+            ISSUE_REORDERED,
+            &result,
+        );
+        Ok(result)
+    }
+}
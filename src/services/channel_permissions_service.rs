@@ -0,0 +1,95 @@
+use diesel::prelude::*;
+
+use crate::{
+    db::models::channel_permission::{
+        ChannelPolicy, NewChannelPermissionRestriction, UpdateChannelPolicyRequest,
+    },
+    db::repositories::channel_permissions::ChannelPermissionsRepo,
+    error::AppError,
+    services::context::{AuthChannel, RequestContext},
+    services::permission_service::{Permission, PermissionService},
+};
+
+pub struct ChannelPermissionsService;
+
+impl ChannelPermissionsService {
+    /// Restrictions of every API channel; visible to all members so token
+    /// owners can tell why a call was refused
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<ChannelPolicy>, AppError> {
+        if PermissionService::current_role(conn, ctx)?.is_none() {
+            return Err(AppError::forbidden(
+                "You are not a member of this workspace",
+            ));
+        }
+        let restrictions = ChannelPermissionsRepo::list_by_workspace(conn, ctx.workspace_id)?;
+        Ok(AuthChannel::API
+            .into_iter()
+            .map(|channel| {
+                let denied = restrictions
+                    .iter()
+                    .filter(|r| r.channel == channel.as_str())
+                    .map(|r| r.permission.clone())
+                    .collect();
+                Self::policy(channel, denied)
+            })
+            .collect())
+    }
+
+    /// Replace the permissions denied to `channel` in the workspace
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        channel: &str,
+        req: &UpdateChannelPolicyRequest,
+    ) -> Result<ChannelPolicy, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        let channel = AuthChannel::parse(channel)
+            .filter(|channel| channel.is_api())
+            .ok_or_else(|| AppError::not_found("channel"))?;
+        let permissions = Self::restrictable(&req.denied)?;
+        let rows: Vec<NewChannelPermissionRestriction> = permissions
+            .iter()
+            .map(|permission| NewChannelPermissionRestriction {
+                workspace_id: ctx.workspace_id,
+                channel: channel.as_str().to_string(),
+                permission: permission.key().to_string(),
+                created_by: Some(ctx.user_id),
+            })
+            .collect();
+        let saved =
+            ChannelPermissionsRepo::replace(conn, ctx.workspace_id, channel.as_str(), &rows)?;
+        Ok(Self::policy(
+            channel,
+            saved.into_iter().map(|r| r.permission).collect(),
+        ))
+    }
+
+    /// Validate requested permission names, dropping duplicates and the ones
+    /// API channels never hold anyway
+    pub fn restrictable(requested: &[String]) -> Result<Vec<Permission>, AppError> {
+        let mut permissions = Vec::new();
+        for key in requested {
+            let permission = Permission::parse(key.trim())
+                .ok_or_else(|| AppError::validation(format!("Unknown permission: {}", key)))?;
+            if !permission.always_denied_to_api() && !permissions.contains(&permission) {
+                permissions.push(permission);
+            }
+        }
+        Ok(permissions)
+    }
+
+    fn policy(channel: AuthChannel, denied: Vec<String>) -> ChannelPolicy {
+        ChannelPolicy {
+            channel: channel.as_str().to_string(),
+            always_denied: Permission::ALL
+                .into_iter()
+                .filter(|permission| permission.always_denied_to_api())
+                .map(|permission| permission.key().to_string())
+                .collect(),
+            denied,
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::comment::{
+        BulkModerateCommentsRequest, BulkModerationResult, Comment, CommentFlag, CommentFlagItem,
+        ModerationAction, NewCommentFlag, UnhideCommentsRequest, comment_flag_status, spam_signals,
+    },
+    db::repositories::audit_log::AuditLogRepo,
+    db::repositories::comment_flags::CommentFlagsRepo,
+    db::repositories::comments::CommentRepo,
+    db::repositories::workspaces::WorkspacesRepo,
+    db::with_txn,
+    error::AppError,
+    services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    utils::clock,
+    websocket::{EntityAction, EntityKind},
+};
+
+/// Window the burst and duplicate heuristics look back over
+const SPAM_WINDOW_MINUTES: i64 = 10;
+/// Links in one comment from which it counts as link spam
+const LINK_THRESHOLD: usize = 4;
+/// Identical comments by one author within the window, this one included
+const DUPLICATE_THRESHOLD: i64 = 3;
+/// Comments by one author within the window, this one included
+const BURST_THRESHOLD: i64 = 10;
+/// Letters a comment needs before it can count as shouting
+const SHOUTING_MIN_LETTERS: usize = 30;
+/// Score from which a comment is flagged
+pub const FLAG_SCORE: i32 = 3;
+/// Flags raised per author and hour; past it a spam wave by one author
+/// stops adding to the review queue, where it is handled in bulk
+const MAX_FLAGS_PER_AUTHOR_PER_HOUR: i64 = 20;
+/// Flags returned per listing
+const FLAG_LIST_LIMIT: i64 = 200;
+
+/// What the author posted within the spam window, the new comment included
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuthorActivity {
+    pub recent_comments: i64,
+    pub recent_duplicates: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpamVerdict {
+    pub score: i32,
+    pub signals: Vec<&'static str>,
+}
+
+impl SpamVerdict {
+    pub fn is_suspect(&self) -> bool {
+        self.score >= FLAG_SCORE
+    }
+}
+
+/// Admin tools against comment spam: bulk hide or delete by author or
+/// content, and review of the comments the spam heuristics flag as they
+/// are posted.
+pub struct CommentModerationService;
+
+impl CommentModerationService {
+    /// Score a comment against the spam heuristics
+    pub fn evaluate(content: &str, activity: &AuthorActivity) -> SpamVerdict {
+        let mut verdict = SpamVerdict::default();
+        let mut signal = |signal: &'static str, score: i32| {
+            verdict.signals.push(signal);
+            verdict.score += score;
+        };
+
+        let links = content.matches("http://").count() + content.matches("https://").count();
+        if links >= LINK_THRESHOLD {
+            signal(spam_signals::LINKS, 2);
+        }
+        if activity.recent_duplicates >= DUPLICATE_THRESHOLD {
+            signal(spam_signals::DUPLICATE, 3);
+        }
+        if activity.recent_comments >= BURST_THRESHOLD {
+            signal(spam_signals::BURST, 2);
+        }
+        let letters: Vec<char> = content.chars().filter(|c| c.is_alphabetic()).collect();
+        let upper = letters.iter().filter(|c| c.is_uppercase()).count();
+        if letters.len() >= SHOUTING_MIN_LETTERS && upper * 5 >= letters.len() * 4 {
+            signal(spam_signals::SHOUTING, 1);
+        }
+        verdict
+    }
+
+    /// Run the spam heuristics on a new comment and flag it when suspect.
+    /// Returns the verdict, or `None` when the author already reached the
+    /// hourly flag limit.
+    pub fn screen(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment: &Comment,
+    ) -> Result<Option<SpamVerdict>, AppError> {
+        let now = clock::now();
+        let flagged = CommentFlagsRepo::count_by_author_since(
+            conn,
+            comment.author_id,
+            now - chrono::Duration::hours(1),
+        )?;
+        if flagged >= MAX_FLAGS_PER_AUTHOR_PER_HOUR {
+            return Ok(None);
+        }
+
+        let (recent_comments, recent_duplicates) = CommentRepo::author_activity_since(
+            conn,
+            comment.author_id,
+            now - chrono::Duration::minutes(SPAM_WINDOW_MINUTES),
+            &comment.content,
+        )?;
+        let verdict = Self::evaluate(
+            &comment.content,
+            &AuthorActivity {
+                recent_comments,
+                recent_duplicates,
+            },
+        );
+        if verdict.is_suspect() {
+            CommentFlagsRepo::insert(
+                conn,
+                &NewCommentFlag {
+                    comment_id: comment.id,
+                    workspace_id: ctx.workspace_id,
+                    author_id: comment.author_id,
+                    reasons: verdict.signals.join(","),
+                    score: verdict.score,
+                },
+            )?;
+            tracing::info!(
+                comment_id = %comment.id,
+                author_id = %comment.author_id,
+                score = verdict.score,
+                "Flagged comment as suspected spam"
+            );
+        }
+        Ok(Some(verdict))
+    }
+
+    /// [`Self::screen`] for comment creation, where screening must not fail
+    /// the request
+    pub fn screen_quietly(conn: &mut PgConnection, ctx: &RequestContext, comment: &Comment) {
+        // A savepoint keeps a failure here from aborting the caller's transaction
+        if let Err(e) = with_txn(conn, |conn| Self::screen(conn, ctx, comment)) {
+            tracing::warn!("Failed to screen comment {} for spam: {}", comment.id, e);
+        }
+    }
+
+    /// ILIKE pattern for a moderation pattern: the text may appear anywhere
+    /// and `*` matches any run of characters
+    pub fn pattern_to_like(pattern: &str) -> String {
+        let mut like = String::from("%");
+        for c in pattern.chars() {
+            match c {
+                '\\' | '%' | '_' => {
+                    like.push('\\');
+                    like.push(c);
+                }
+                '*' => like.push('%'),
+                _ => like.push(c),
+            }
+        }
+        like.push('%');
+        like
+    }
+
+    /// Hide or delete the workspace's comments matching the request
+    pub fn bulk_moderate(
+        conn: &mut PgConnection,
+        moderator_id: Uuid,
+        req: &BulkModerateCommentsRequest,
+    ) -> Result<BulkModerationResult, AppError> {
+        let pattern = req
+            .pattern
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+        if req.author_id.is_none() && pattern.is_none() {
+            return Err(AppError::validation(
+                "Either author_id or pattern is required",
+            ));
+        }
+        if pattern.is_some_and(|p| p.chars().all(|c| c == '*')) {
+            return Err(AppError::validation(
+                "Pattern must contain text besides wildcards",
+            ));
+        }
+        if WorkspacesRepo::find_by_id(conn, req.workspace_id)?.is_none() {
+            return Err(AppError::not_found("workspace"));
+        }
+
+        let like = pattern.map(Self::pattern_to_like);
+        let matched = CommentRepo::find_for_moderation(
+            conn,
+            req.workspace_id,
+            req.author_id,
+            like.as_deref(),
+            req.since,
+        )?;
+        if req.dry_run || matched.is_empty() {
+            return Ok(BulkModerationResult {
+                matched: matched.len(),
+                affected: 0,
+                comment_ids: matched,
+                dry_run: req.dry_run,
+            });
+        }
+
+        let reason = req
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty());
+        let changed = conn.transaction::<_, AppError, _>(|conn| {
+            let (changed, action) = match req.action {
+                ModerationAction::Hide => {
+                    let hidden = CommentRepo::hide(conn, &matched, moderator_id, reason)?;
+                    CommentFlagsRepo::resolve_for_comments(conn, &matched, moderator_id)?;
+                    (hidden, audit_actions::COMMENTS_BULK_HIDDEN)
+                }
+                ModerationAction::Delete => (
+                    CommentRepo::delete_many(conn, &matched)?,
+                    audit_actions::COMMENTS_BULK_DELETED,
+                ),
+            };
+            let changed_ids: Vec<Uuid> = changed.iter().map(|c| c.id).collect();
+            let details = serde_json::json!({
+                "author_id": req.author_id,
+                "pattern": pattern,
+                "since": req.since,
+                "reason": reason,
+                "comment_ids": changed_ids,
+            });
+            AuditLogRepo::insert(
+                conn,
+                &NewAuditEntry {
+                    workspace_id: req.workspace_id,
+                    actor_id: Some(moderator_id),
+                    action: action.to_string(),
+                    target_type: "comment".to_string(),
+                    target_id: None,
+                    details: Some(details.to_string()),
+                },
+            )?;
+            Ok(changed)
+        })?;
+
+        // Hidden comments disappear for clients just like deleted ones
+        for comment in &changed {
+            RealtimeService::entity_changed(
+                req.workspace_id,
+                EntityKind::Comment,
+                EntityAction::Deleted,
+                comment.id,
+                &(),
+            );
+        }
+        Ok(BulkModerationResult {
+            matched: matched.len(),
+            affected: changed.len(),
+            comment_ids: changed.into_iter().map(|c| c.id).collect(),
+            dry_run: false,
+        })
+    }
+
+    /// Restore hidden comments of the workspace; returns those restored
+    pub fn unhide(
+        conn: &mut PgConnection,
+        moderator_id: Uuid,
+        req: &UnhideCommentsRequest,
+    ) -> Result<Vec<Comment>, AppError> {
+        if req.comment_ids.is_empty() {
+            return Err(AppError::validation("comment_ids must not be empty"));
+        }
+        let in_workspace =
+            CommentRepo::filter_in_workspace(conn, req.workspace_id, &req.comment_ids)?;
+
+        let restored = conn.transaction::<_, AppError, _>(|conn| {
+            let restored = CommentRepo::unhide(conn, &in_workspace)?;
+            if !restored.is_empty() {
+                let restored_ids: Vec<Uuid> = restored.iter().map(|c| c.id).collect();
+                AuditLogRepo::insert(
+                    conn,
+                    &NewAuditEntry {
+                        workspace_id: req.workspace_id,
+                        actor_id: Some(moderator_id),
+                        action: audit_actions::COMMENTS_UNHIDDEN.to_string(),
+                        target_type: "comment".to_string(),
+                        target_id: None,
+                        details: Some(
+                            serde_json::json!({ "comment_ids": restored_ids }).to_string(),
+                        ),
+                    },
+                )?;
+            }
+            Ok(restored)
+        })?;
+
+        for comment in &restored {
+            RealtimeService::entity_changed(
+                req.workspace_id,
+                EntityKind::Comment,
+                EntityAction::Created,
+                comment.id,
+                comment,
+            );
+        }
+        Ok(restored)
+    }
+
+    /// Flagged comments of the workspace, newest first
+    pub fn list_flags(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        status: Option<&str>,
+    ) -> Result<Vec<CommentFlagItem>, AppError> {
+        if let Some(status) = status
+            && !comment_flag_status::ALL.contains(&status)
+        {
+            return Err(AppError::validation(format!(
+                "Invalid flag status '{}'; expected one of: {}",
+                status,
+                comment_flag_status::ALL.join(", ")
+            )));
+        }
+        Ok(CommentFlagsRepo::list(
+            conn,
+            workspace_id,
+            status,
+            FLAG_LIST_LIMIT,
+        )?)
+    }
+
+    /// Close an open flag without acting on the comment
+    pub fn dismiss_flag(
+        conn: &mut PgConnection,
+        moderator_id: Uuid,
+        workspace_id: Uuid,
+        flag_id: Uuid,
+    ) -> Result<CommentFlag, AppError> {
+        CommentFlagsRepo::resolve(
+            conn,
+            workspace_id,
+            flag_id,
+            comment_flag_status::DISMISSED,
+            moderator_id,
+        )?
+        .ok_or_else(|| AppError::not_found("open comment flag"))
+    }
+}
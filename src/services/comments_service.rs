@@ -1,26 +1,237 @@
-use chrono::Utc;
 use diesel::prelude::*;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::{
-    db::models::comment::{Comment, NewComment},
+    db::models::app_installation::webhook_events,
+    db::models::comment::{
+        Comment, CommentListItem, CommentOrder, CommentPage, CommentPageQuery, CommentReaction,
+        NewComment, NewCommentMention, NewCommentReaction, ReactionSummary,
+    },
+    db::models::notification::{NewNotification, notification_events},
     db::repositories::comments::CommentRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
     error::AppError,
+    services::comment_moderation_service::CommentModerationService,
     services::context::RequestContext,
-    validation::comment::{validate_create_comment, validate_update_comment},
+    services::custom_emojis_service::CustomEmojisService,
+    services::markdown_service::MarkdownService,
+    services::notifications_service::NotificationsService,
+    services::realtime_service::RealtimeService,
+    services::webhook_service::WebhookService,
+    utils::clock,
+    validation::comment::{validate_create_comment, validate_reaction, validate_update_comment},
+    websocket::{EntityAction, EntityKind, Topic},
 };
 
+/// How much of a comment is copied into its notification
+const COMMENT_EXCERPT_CHARS: usize = 500;
+
+/// Realtime event published to the issue when a comment's reactions change
+pub const COMMENT_REACTIONS_UPDATED: &str = "comment_reactions_updated";
+
+/// Comments per page when the caller does not ask for a limit
+pub const DEFAULT_COMMENT_PAGE_LIMIT: i64 = 50;
+
+/// Upper bound on comments per page
+pub const MAX_COMMENT_PAGE_LIMIT: i64 = 200;
+
 pub struct CommentsService;
 
 impl CommentsService {
+    /// Comments of an issue with their reply counts and reaction summaries
     pub fn list_by_issue(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         issue_id: Uuid,
         include_deleted: bool,
-    ) -> Result<Vec<Comment>, AppError> {
-        CommentRepo::list_by_issue(conn, issue_id, include_deleted)
+    ) -> Result<Vec<CommentListItem>, AppError> {
+        let comments = CommentRepo::list_by_issue(conn, issue_id, include_deleted)
+            .map_err(|e| AppError::internal(format!("Failed to list comments: {}", e)))?;
+        Self::with_details(conn, ctx, comments)
+    }
+
+    pub fn clamp_page_limit(limit: Option<i64>) -> i64 {
+        limit
+            .unwrap_or(DEFAULT_COMMENT_PAGE_LIMIT)
+            .clamp(1, MAX_COMMENT_PAGE_LIMIT)
+    }
+
+    /// How many of the comments fetched on each side of an anchor to show so
+    /// the window, anchor included, holds at most `limit` comments. The
+    /// anchor is centered when both sides have enough; otherwise the short
+    /// side's share goes to the other.
+    pub fn split_window(before: usize, after: usize, limit: usize) -> (usize, usize) {
+        let room = limit.saturating_sub(1);
+        let take_before = before.min(room / 2);
+        let take_after = after.min(room - take_before);
+        let take_before = before.min(room - take_after);
+        (take_before, take_after)
+    }
+
+    /// One page of an issue's comments in the requested order: the top of the
+    /// list, the comments before or after a cursor, or a window centered on
+    /// an anchor comment for deep links
+    pub fn page_by_issue(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        query: &CommentPageQuery,
+    ) -> Result<CommentPage, AppError> {
+        let positions = [query.around, query.before, query.after];
+        if positions.iter().flatten().count() > 1 {
+            return Err(AppError::validation(
+                "Only one of around, before and after may be given",
+            ));
+        }
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+
+        let include_deleted = query.include_deleted.unwrap_or(false);
+        let limit = Self::clamp_page_limit(query.limit);
+        // Display order walks the table newest first or oldest first; "after"
+        // is further down the page in that order
+        let after_is_older = query.order == CommentOrder::NewestFirst;
+        let list_page = |conn: &mut PgConnection,
+                         pivot: Option<&Comment>,
+                         older: bool,
+                         limit: i64|
+         -> Result<Vec<Comment>, AppError> {
+            CommentRepo::list_page(
+                conn,
+                issue_id,
+                include_deleted,
+                pivot.map(|comment| (comment.created_at, comment.id)),
+                older,
+                limit,
+            )
             .map_err(|e| AppError::internal(format!("Failed to list comments: {}", e)))
+        };
+
+        let (comments, has_more_before, has_more_after) = if let Some(anchor_id) = query.around {
+            let anchor = Self::find_position(conn, issue_id, anchor_id)?;
+            if anchor.is_deleted == Some(true) && !include_deleted {
+                return Err(AppError::not_found("comment"));
+            }
+            let mut before = list_page(conn, Some(&anchor), !after_is_older, limit)?;
+            let mut after = list_page(conn, Some(&anchor), after_is_older, limit)?;
+            let (take_before, take_after) =
+                Self::split_window(before.len(), after.len(), limit as usize);
+            let more_before = before.len() > take_before;
+            let more_after = after.len() > take_after;
+            before.truncate(take_before);
+            after.truncate(take_after);
+            before.reverse();
+            before.push(anchor);
+            before.append(&mut after);
+            (before, more_before, more_after)
+        } else if let Some(cursor_id) = query.before {
+            let cursor = Self::find_position(conn, issue_id, cursor_id)?;
+            let mut comments = list_page(conn, Some(&cursor), !after_is_older, limit + 1)?;
+            let more_before = comments.len() as i64 > limit;
+            comments.truncate(limit as usize);
+            comments.reverse();
+            (comments, more_before, true)
+        } else if let Some(cursor_id) = query.after {
+            let cursor = Self::find_position(conn, issue_id, cursor_id)?;
+            let mut comments = list_page(conn, Some(&cursor), after_is_older, limit + 1)?;
+            let more_after = comments.len() as i64 > limit;
+            comments.truncate(limit as usize);
+            (comments, true, more_after)
+        } else {
+            let mut comments = list_page(conn, None, after_is_older, limit + 1)?;
+            let more_after = comments.len() as i64 > limit;
+            comments.truncate(limit as usize);
+            (comments, false, more_after)
+        };
+
+        Ok(CommentPage {
+            before_cursor: comments.first().map(|comment| comment.id),
+            after_cursor: comments.last().map(|comment| comment.id),
+            comments: Self::with_details(conn, ctx, comments)?,
+            order: query.order,
+            anchor_id: query.around,
+            has_more_before,
+            has_more_after,
+        })
+    }
+
+    /// A visible comment of the issue that a page is positioned against
+    fn find_position(
+        conn: &mut PgConnection,
+        issue_id: Uuid,
+        comment_id: Uuid,
+    ) -> Result<Comment, AppError> {
+        CommentRepo::find_by_id(conn, comment_id)
+            .map_err(|e| AppError::internal(format!("Failed to find comment: {}", e)))?
+            .filter(|comment| comment.issue_id == issue_id && comment.hidden_at.is_none())
+            .ok_or_else(|| AppError::not_found("comment"))
+    }
+
+    /// Attach reply counts and reaction summaries, keeping the given order
+    fn with_details(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comments: Vec<Comment>,
+    ) -> Result<Vec<CommentListItem>, AppError> {
+        let comment_ids: Vec<Uuid> = comments.iter().map(|comment| comment.id).collect();
+
+        let reply_counts: HashMap<Uuid, i64> = CommentRepo::count_replies(conn, &comment_ids)
+            .map_err(|e| AppError::internal(format!("Failed to count replies: {}", e)))?
+            .into_iter()
+            .collect();
+        let mut reactions: HashMap<Uuid, Vec<CommentReaction>> = HashMap::new();
+        for reaction in CommentRepo::list_reactions(conn, &comment_ids)
+            .map_err(|e| AppError::internal(format!("Failed to list reactions: {}", e)))?
+        {
+            reactions
+                .entry(reaction.comment_id)
+                .or_default()
+                .push(reaction);
+        }
+
+        Ok(comments
+            .into_iter()
+            .map(|comment| CommentListItem {
+                reply_count: reply_counts.get(&comment.id).copied().unwrap_or(0),
+                reactions: Self::summarize_reactions(
+                    reactions.get(&comment.id).map_or(&[], Vec::as_slice),
+                    ctx.user_id,
+                ),
+                comment,
+            })
+            .collect())
+    }
+
+    /// Group reactions by emoji, most used first, then by first use
+    pub fn summarize_reactions(
+        reactions: &[CommentReaction],
+        user_id: Uuid,
+    ) -> Vec<ReactionSummary> {
+        let mut summaries: Vec<ReactionSummary> = Vec::new();
+        for reaction in reactions {
+            let index = match summaries
+                .iter()
+                .position(|summary| summary.emoji == reaction.reaction_type)
+            {
+                Some(index) => index,
+                None => {
+                    summaries.push(ReactionSummary {
+                        emoji: reaction.reaction_type.clone(),
+                        count: 0,
+                        reacted: false,
+                    });
+                    summaries.len() - 1
+                }
+            };
+            summaries[index].count += 1;
+            summaries[index].reacted |= reaction.user_id == user_id;
+        }
+        // Stable sort keeps first-use order among equal counts
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.count));
+        summaries
     }
 
     pub fn create(
@@ -28,20 +239,267 @@ impl CommentsService {
         ctx: &RequestContext,
         issue_id: Uuid,
         content: String,
+    ) -> Result<Comment, AppError> {
+        Self::create_with_mentions(conn, ctx, issue_id, content, None, &[])
+    }
+
+    /// Create a comment and notify the mentioned users. Mentions of users
+    /// outside the workspace, and of the author, are ignored. A reply to a
+    /// reply joins the thread of the top-level comment.
+    pub fn create_with_mentions(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        content: String,
+        parent_comment_id: Option<Uuid>,
+        mentions: &[Uuid],
     ) -> Result<Comment, AppError> {
         validate_create_comment(&content)?;
 
-        let _now = Utc::now().naive_utc();
+        let parent_comment_id = match parent_comment_id {
+            Some(parent_id) => Some(Self::thread_root(conn, issue_id, parent_id)?),
+            None => None,
+        };
+
+        let _now = clock::now().naive_utc();
         let new_comment = NewComment {
             issue_id,
             author_id: ctx.user_id,
             content,
             content_type: None,
-            parent_comment_id: None,
+            parent_comment_id,
         };
 
-        CommentRepo::insert(conn, &new_comment)
-            .map_err(|e| AppError::internal(format!("Failed to create comment: {}", e)))
+        let (comment, mentioned) = conn.transaction::<_, AppError, _>(|conn| {
+            let comment = CommentRepo::insert(conn, &new_comment)
+                .map_err(|e| AppError::internal(format!("Failed to create comment: {}", e)))?;
+            let mentioned = Self::record_mentions(conn, ctx, &comment, mentions)?;
+            Ok((comment, mentioned))
+        })?;
+
+        CommentModerationService::screen_quietly(conn, ctx, &comment);
+        CustomEmojisService::record_usage_quietly(
+            conn,
+            ctx,
+            &CustomEmojisService::shortcodes(&comment.content),
+        );
+        Self::notify_participants(conn, ctx, &comment, &mentioned);
+        let payload = MarkdownService::webhook_payload(
+            conn,
+            ctx.workspace_id,
+            &comment,
+            Some(&comment.content),
+        );
+        WebhookService::emit_quietly(
+            conn,
+            ctx.workspace_id,
+            webhook_events::COMMENT_CREATED,
+            &payload,
+        );
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Issue(comment.issue_id),
+            webhook_events::COMMENT_CREATED,
+            &comment,
+        );
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Comment,
+            EntityAction::Created,
+            comment.id,
+            &comment,
+        );
+        Ok(comment)
+    }
+
+    /// Top-level comment of the thread a reply to `parent_id` belongs to
+    fn thread_root(
+        conn: &mut PgConnection,
+        issue_id: Uuid,
+        parent_id: Uuid,
+    ) -> Result<Uuid, AppError> {
+        let parent = CommentRepo::find_by_id(conn, parent_id)
+            .map_err(|e| AppError::internal(format!("Failed to find comment: {}", e)))?
+            .filter(|parent| parent.is_deleted != Some(true) && parent.hidden_at.is_none())
+            .ok_or_else(|| AppError::not_found("parent comment"))?;
+        if parent.issue_id != issue_id {
+            return Err(AppError::validation(
+                "Parent comment belongs to a different issue",
+            ));
+        }
+        Ok(parent.parent_comment_id.unwrap_or(parent.id))
+    }
+
+    /// Store mentions of workspace members other than the author; returns
+    /// the mentioned user ids
+    fn record_mentions(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment: &Comment,
+        mentions: &[Uuid],
+    ) -> Result<Vec<Uuid>, AppError> {
+        let mut mentioned: Vec<Uuid> = Vec::new();
+        for user_id in mentions {
+            if *user_id == ctx.user_id || mentioned.contains(user_id) {
+                continue;
+            }
+            if WorkspaceMembersRepo::find(conn, ctx.workspace_id, *user_id)?.is_some() {
+                mentioned.push(*user_id);
+            }
+        }
+        if mentioned.is_empty() {
+            return Ok(mentioned);
+        }
+
+        let rows: Vec<NewCommentMention> = mentioned
+            .iter()
+            .map(|user_id| NewCommentMention {
+                comment_id: comment.id,
+                mentioned_user_id: *user_id,
+            })
+            .collect();
+        CommentRepo::insert_mentions(conn, &rows)
+            .map_err(|e| AppError::internal(format!("Failed to record mentions: {}", e)))?;
+        Ok(mentioned)
+    }
+
+    /// Notify mentioned users, then the issue's assignee and creator and the
+    /// author of the thread being replied to, unless they were already
+    /// notified of the mention
+    fn notify_participants(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment: &Comment,
+        mentioned: &[Uuid],
+    ) {
+        let issue = match IssueRepo::find_by_id(conn, comment.issue_id) {
+            Ok(Some(issue)) => issue,
+            _ => return,
+        };
+
+        let mut recipients: Vec<(Uuid, &str)> = mentioned
+            .iter()
+            .map(|user_id| (*user_id, notification_events::MENTIONED))
+            .collect();
+        let thread_author = comment
+            .parent_comment_id
+            .and_then(|parent_id| CommentRepo::find_by_id(conn, parent_id).ok().flatten())
+            .map(|parent| parent.author_id);
+        for participant in std::iter::once(issue.creator_id)
+            .chain(issue.assignee_id)
+            .chain(thread_author)
+        {
+            if !recipients
+                .iter()
+                .any(|(user_id, _)| *user_id == participant)
+            {
+                recipients.push((participant, notification_events::COMMENT_CREATED));
+            }
+        }
+
+        let excerpt: String = comment
+            .content
+            .chars()
+            .take(COMMENT_EXCERPT_CHARS)
+            .collect();
+        for (recipient_id, event_type) in recipients {
+            NotificationsService::notify_quietly(
+                conn,
+                NewNotification {
+                    workspace_id: ctx.workspace_id,
+                    recipient_id,
+                    actor_id: Some(ctx.user_id),
+                    event_type: event_type.to_string(),
+                    entity_type: "issue".to_string(),
+                    entity_id: issue.id,
+                    title: issue.title.clone(),
+                    body: Some(excerpt.clone()),
+                },
+            );
+        }
+    }
+
+    /// React to a comment; returns the comment's updated reaction summary
+    pub fn add_reaction(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment_id: Uuid,
+        emoji: String,
+    ) -> Result<Vec<ReactionSummary>, AppError> {
+        validate_reaction(&emoji)?;
+        let comment = Self::find_reactable(conn, ctx, comment_id)?;
+        CustomEmojisService::require_reaction_emoji(conn, ctx, &emoji)?;
+        let custom_name = CustomEmojisService::reaction_name(&emoji).map(str::to_string);
+
+        let inserted = CommentRepo::insert_reaction(
+            conn,
+            &NewCommentReaction {
+                comment_id,
+                user_id: ctx.user_id,
+                reaction_type: emoji,
+            },
+        )
+        .map_err(|e| AppError::internal(format!("Failed to add reaction: {}", e)))?;
+        if let Some(name) = custom_name.filter(|_| inserted > 0) {
+            CustomEmojisService::record_usage_quietly(conn, ctx, &[name]);
+        }
+        Self::reactions_changed(conn, ctx, &comment, inserted > 0)
+    }
+
+    /// Remove the caller's reaction; returns the updated reaction summary
+    pub fn remove_reaction(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment_id: Uuid,
+        emoji: &str,
+    ) -> Result<Vec<ReactionSummary>, AppError> {
+        let comment = Self::find_reactable(conn, ctx, comment_id)?;
+
+        let removed = CommentRepo::delete_reaction(conn, comment_id, ctx.user_id, emoji)
+            .map_err(|e| AppError::internal(format!("Failed to remove reaction: {}", e)))?;
+        Self::reactions_changed(conn, ctx, &comment, removed > 0)
+    }
+
+    /// A live comment on an issue in the caller's workspace
+    fn find_reactable(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment_id: Uuid,
+    ) -> Result<Comment, AppError> {
+        let comment = CommentRepo::find_by_id(conn, comment_id)
+            .map_err(|e| AppError::internal(format!("Failed to find comment: {}", e)))?
+            .filter(|comment| comment.is_deleted != Some(true) && comment.hidden_at.is_none())
+            .ok_or_else(|| AppError::not_found("comment"))?;
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, comment.issue_id)? {
+            return Err(AppError::not_found("comment"));
+        }
+        Ok(comment)
+    }
+
+    /// Summarize the comment's reactions and, when they changed, publish
+    /// the summary to the issue's subscribers
+    fn reactions_changed(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment: &Comment,
+        changed: bool,
+    ) -> Result<Vec<ReactionSummary>, AppError> {
+        let reactions = CommentRepo::list_reactions(conn, &[comment.id])
+            .map_err(|e| AppError::internal(format!("Failed to list reactions: {}", e)))?;
+        let summary = Self::summarize_reactions(&reactions, ctx.user_id);
+        if changed {
+            RealtimeService::publish(
+                ctx.workspace_id,
+                Topic::Issue(comment.issue_id),
+                COMMENT_REACTIONS_UPDATED,
+                &serde_json::json!({
+                    "comment_id": comment.id,
+                    // Per-viewer `reacted` flags are not meaningful to other users
+                    "reactions": Self::summarize_reactions(&reactions, Uuid::nil()),
+                }),
+            );
+        }
+        Ok(summary)
     }
 
     pub fn update(
@@ -55,14 +513,30 @@ impl CommentsService {
         // Check if comment exists and belongs to user
         let comment = CommentRepo::find_by_id(conn, comment_id)
             .map_err(|e| AppError::internal(format!("Failed to find comment: {}", e)))?
+            .filter(|comment| comment.hidden_at.is_none())
             .ok_or_else(|| AppError::not_found("comment"))?;
 
         if comment.author_id != ctx.user_id {
             return Err(AppError::auth("You can only edit your own comments"));
         }
 
-        CommentRepo::update_content(conn, comment_id, content)
-            .map_err(|e| AppError::internal(format!("Failed to update comment: {}", e)))
+        let updated = CommentRepo::update_content(conn, comment_id, content)
+            .map_err(|e| AppError::internal(format!("Failed to update comment: {}", e)))?;
+        // Only shortcodes the edit introduced count as new uses
+        let previous = CustomEmojisService::shortcodes(&comment.content);
+        let added: Vec<String> = CustomEmojisService::shortcodes(&updated.content)
+            .into_iter()
+            .filter(|name| !previous.contains(name))
+            .collect();
+        CustomEmojisService::record_usage_quietly(conn, ctx, &added);
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Comment,
+            EntityAction::Updated,
+            updated.id,
+            &updated,
+        );
+        Ok(updated)
     }
 
     pub fn delete(
@@ -73,6 +547,7 @@ impl CommentsService {
         // Check if comment exists and belongs to user
         let comment = CommentRepo::find_by_id(conn, comment_id)
             .map_err(|e| AppError::internal(format!("Failed to find comment: {}", e)))?
+            .filter(|comment| comment.hidden_at.is_none())
             .ok_or_else(|| AppError::not_found("comment"))?;
 
         if comment.author_id != ctx.user_id {
@@ -81,6 +556,13 @@ impl CommentsService {
 
         CommentRepo::soft_delete(conn, comment_id)
             .map_err(|e| AppError::internal(format!("Failed to delete comment: {}", e)))?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Comment,
+            EntityAction::Deleted,
+            comment_id,
+            &(),
+        );
 
         Ok(())
     }
@@ -92,6 +574,22 @@ impl CommentsService {
     ) -> Result<Comment, AppError> {
         CommentRepo::find_by_id(conn, comment_id)
             .map_err(|e| AppError::internal(format!("Failed to find comment: {}", e)))?
+            .filter(|comment| comment.hidden_at.is_none())
             .ok_or_else(|| AppError::not_found("comment"))
     }
+
+    /// Full text of a code block in a comment of the workspace
+    pub fn code_block(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment_id: Uuid,
+        index: usize,
+    ) -> Result<String, AppError> {
+        let comment = Self::get_by_id(conn, ctx, comment_id)?;
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, comment.issue_id)? {
+            return Err(AppError::not_found("comment"));
+        }
+        MarkdownService::code_block(&comment.content, index)
+            .ok_or_else(|| AppError::not_found("code block"))
+    }
 }
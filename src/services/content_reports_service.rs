@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::content_report::{
+        ContentReport, CreateReportRequest, NewContentReport, ReportQueueItem, ReportQueueQuery,
+        ReportResolution, ResolveReportRequest, report_reasons, report_status, report_targets,
+    },
+    db::models::notification::{NewNotification, notification_events},
+    db::models::workspace_settings::workspace_features,
+    db::repositories::audit_log::AuditLogRepo,
+    db::repositories::comment_flags::CommentFlagsRepo,
+    db::repositories::comments::CommentRepo,
+    db::repositories::content_reports::ContentReportsRepo,
+    db::repositories::issues::IssueRepo,
+    error::AppError,
+    services::comments_service::CommentsService,
+    services::context::RequestContext,
+    services::issues_service::IssuesService,
+    services::notifications_service::NotificationsService,
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    websocket::{EntityAction, EntityKind},
+};
+
+const MAX_DETAILS_CHARS: usize = 1000;
+const MAX_NOTE_CHARS: usize = 1000;
+/// Reports returned per queue listing
+const QUEUE_LIMIT: i64 = 200;
+/// Characters of a reported comment shown in the queue
+const EXCERPT_CHARS: usize = 200;
+
+/// Reports of comments and issues by workspace members, and their review by
+/// workspace admins. Resolving a report acts on the content and closes
+/// every open report on it; reporters are notified of the outcome but not
+/// of who resolved it.
+pub struct ContentReportsService;
+
+impl ContentReportsService {
+    pub fn report_comment(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        comment_id: Uuid,
+        req: &CreateReportRequest,
+    ) -> Result<ContentReport, AppError> {
+        PermissionService::require(conn, ctx, Permission::ReportContent)?;
+        let comment = CommentsService::get_by_id(conn, ctx, comment_id)?;
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, comment.issue_id)? {
+            return Err(AppError::not_found("comment"));
+        }
+        Self::create(conn, ctx, report_targets::COMMENT, comment_id, req)
+    }
+
+    pub fn report_issue(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        req: &CreateReportRequest,
+    ) -> Result<ContentReport, AppError> {
+        PermissionService::require(conn, ctx, Permission::ReportContent)?;
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        Self::create(conn, ctx, report_targets::ISSUE, issue_id, req)
+    }
+
+    /// Reason and trimmed details of a report; `other` needs details
+    pub fn validate_report(
+        req: &CreateReportRequest,
+    ) -> Result<(String, Option<String>), AppError> {
+        if !report_reasons::ALL.contains(&req.reason.as_str()) {
+            return Err(AppError::validation(format!(
+                "reason must be one of {}",
+                report_reasons::ALL.join(", ")
+            )));
+        }
+        let details = req
+            .details
+            .as_deref()
+            .map(str::trim)
+            .filter(|details| !details.is_empty());
+        if details.is_some_and(|details| details.chars().count() > MAX_DETAILS_CHARS) {
+            return Err(AppError::validation(format!(
+                "details must be at most {} characters",
+                MAX_DETAILS_CHARS
+            )));
+        }
+        if req.reason == report_reasons::OTHER && details.is_none() {
+            return Err(AppError::validation(
+                "details are required when the reason is other",
+            ));
+        }
+        Ok((req.reason.clone(), details.map(str::to_string)))
+    }
+
+    fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        target_type: &str,
+        target_id: Uuid,
+        req: &CreateReportRequest,
+    ) -> Result<ContentReport, AppError> {
+        if !WorkspaceSettingsService::feature_enabled(
+            conn,
+            ctx.workspace_id,
+            workspace_features::CONTENT_REPORTS,
+        )? {
+            return Err(AppError::forbidden(
+                "Content reporting is turned off for this workspace",
+            ));
+        }
+        let (reason, details) = Self::validate_report(req)?;
+        if ContentReportsRepo::has_open_report(conn, ctx.user_id, target_type, target_id)? {
+            return Err(AppError::conflict_with_code(
+                format!("You already reported this {}", target_type),
+                None,
+                "ALREADY_REPORTED",
+            ));
+        }
+        Ok(ContentReportsRepo::insert(
+            conn,
+            &NewContentReport {
+                workspace_id: ctx.workspace_id,
+                reporter_id: Some(ctx.user_id),
+                target_type: target_type.to_string(),
+                target_id,
+                reason,
+                details,
+            },
+        )?)
+    }
+
+    /// Reports awaiting review (or resolved ones, by `status`), oldest first,
+    /// with the content they are about
+    pub fn queue(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        query: &ReportQueueQuery,
+    ) -> Result<Vec<ReportQueueItem>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ModerateContent)?;
+        let status = query.status.as_deref().unwrap_or(report_status::OPEN);
+        if !report_status::ALL.contains(&status) {
+            return Err(AppError::validation(format!(
+                "status must be one of {}",
+                report_status::ALL.join(", ")
+            )));
+        }
+        let target_type = query.target_type.as_deref();
+        if target_type.is_some_and(|target_type| !report_targets::ALL.contains(&target_type)) {
+            return Err(AppError::validation(format!(
+                "target_type must be one of {}",
+                report_targets::ALL.join(", ")
+            )));
+        }
+
+        let reports =
+            ContentReportsRepo::list(conn, ctx.workspace_id, status, target_type, QUEUE_LIMIT)?;
+        let ids_of = |kind: &str| -> Vec<Uuid> {
+            reports
+                .iter()
+                .filter(|report| report.target_type == kind)
+                .map(|report| report.target_id)
+                .collect()
+        };
+        let (comment_ids, issue_ids) = (
+            ids_of(report_targets::COMMENT),
+            ids_of(report_targets::ISSUE),
+        );
+
+        let mut content: HashMap<(&str, Uuid), (Uuid, String)> = HashMap::new();
+        for (id, author_id, text) in ContentReportsRepo::comment_summaries(conn, &comment_ids)? {
+            let excerpt = text.chars().take(EXCERPT_CHARS).collect();
+            content.insert((report_targets::COMMENT, id), (author_id, excerpt));
+        }
+        for (id, creator_id, title) in ContentReportsRepo::issue_summaries(conn, &issue_ids)? {
+            content.insert((report_targets::ISSUE, id), (creator_id, title));
+        }
+        let target_ids: Vec<Uuid> = comment_ids.into_iter().chain(issue_ids).collect();
+        let open_reports: HashMap<(String, Uuid), i64> =
+            ContentReportsRepo::count_open_by_target(conn, ctx.workspace_id, &target_ids)?
+                .into_iter()
+                .map(|(target_type, target_id, count)| ((target_type, target_id), count))
+                .collect();
+
+        Ok(reports
+            .into_iter()
+            .map(|report| {
+                let summary = content.get(&(report.target_type.as_str(), report.target_id));
+                ReportQueueItem {
+                    content_author_id: summary.map(|(author_id, _)| *author_id),
+                    content_excerpt: summary.map(|(_, excerpt)| excerpt.clone()),
+                    open_reports: open_reports
+                        .get(&(report.target_type.clone(), report.target_id))
+                        .copied()
+                        .unwrap_or(0),
+                    report,
+                }
+            })
+            .collect())
+    }
+
+    /// Act on the reported content and resolve every open report on it;
+    /// returns the reports resolved
+    pub fn resolve(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        report_id: Uuid,
+        req: &ResolveReportRequest,
+    ) -> Result<Vec<ContentReport>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ModerateContent)?;
+        let note = req
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|note| !note.is_empty());
+        if note.is_some_and(|note| note.chars().count() > MAX_NOTE_CHARS) {
+            return Err(AppError::validation(format!(
+                "note must be at most {} characters",
+                MAX_NOTE_CHARS
+            )));
+        }
+        let report = ContentReportsRepo::find(conn, ctx.workspace_id, report_id)?
+            .ok_or_else(|| AppError::not_found("report"))?;
+        if report.status != report_status::OPEN {
+            return Err(AppError::conflict_with_code(
+                "Report is already resolved",
+                None,
+                "REPORT_RESOLVED",
+            ));
+        }
+
+        let title = Self::content_title(conn, &report)?;
+        match (report.target_type.as_str(), req.action) {
+            (_, ReportResolution::Dismiss) => {}
+            (report_targets::COMMENT, ReportResolution::Hide) => {
+                let hidden = CommentRepo::hide(conn, &[report.target_id], ctx.user_id, note)?;
+                CommentFlagsRepo::resolve_for_comments(conn, &[report.target_id], ctx.user_id)?;
+                if !hidden.is_empty() {
+                    Self::comment_gone(ctx, report.target_id);
+                }
+            }
+            (report_targets::COMMENT, ReportResolution::Remove) => {
+                if !CommentRepo::delete_many(conn, &[report.target_id])?.is_empty() {
+                    Self::comment_gone(ctx, report.target_id);
+                }
+            }
+            (_, ReportResolution::Hide) => {
+                IssueRepo::archive(conn, report.target_id)?;
+            }
+            (_, ReportResolution::Remove) => {
+                if IssueRepo::exists_in_workspace(conn, ctx.workspace_id, report.target_id)? {
+                    IssuesService::delete(conn, ctx, report.target_id)?;
+                }
+            }
+        }
+
+        let status = req.action.status();
+        let resolved = ContentReportsRepo::resolve_open_for_target(
+            conn,
+            ctx.workspace_id,
+            &report.target_type,
+            report.target_id,
+            status,
+            ctx.user_id,
+            note,
+        )?;
+        let report_ids: Vec<Uuid> = resolved.iter().map(|report| report.id).collect();
+        AuditLogRepo::insert(
+            conn,
+            &NewAuditEntry {
+                workspace_id: ctx.workspace_id,
+                actor_id: Some(ctx.user_id),
+                action: audit_actions::CONTENT_REPORT_RESOLVED.to_string(),
+                target_type: report.target_type.clone(),
+                target_id: Some(report.target_id),
+                details: Some(
+                    serde_json::json!({
+                        "status": status,
+                        "report_ids": report_ids,
+                        "note": note,
+                    })
+                    .to_string(),
+                ),
+            },
+        )?;
+
+        let body = Self::outcome(&report.target_type, req.action);
+        let mut reporters: Vec<Uuid> = resolved.iter().filter_map(|r| r.reporter_id).collect();
+        reporters.sort();
+        reporters.dedup();
+        for reporter_id in reporters {
+            NotificationsService::notify_quietly(
+                conn,
+                NewNotification {
+                    workspace_id: ctx.workspace_id,
+                    recipient_id: reporter_id,
+                    actor_id: None,
+                    event_type: notification_events::REPORT_RESOLVED.to_string(),
+                    entity_type: report.target_type.clone(),
+                    entity_id: report.target_id,
+                    title: title.clone(),
+                    body: Some(body.to_string()),
+                },
+            );
+        }
+        Ok(resolved)
+    }
+
+    /// What reporters are told about the outcome
+    pub fn outcome(target_type: &str, action: ReportResolution) -> &'static str {
+        match (target_type, action) {
+            (_, ReportResolution::Dismiss) => {
+                "A moderator reviewed your report and took no action."
+            }
+            (report_targets::COMMENT, ReportResolution::Hide) => {
+                "A moderator hid the reported comment."
+            }
+            (_, ReportResolution::Hide) => "A moderator archived the reported issue.",
+            (report_targets::COMMENT, ReportResolution::Remove) => {
+                "A moderator removed the reported comment."
+            }
+            (_, ReportResolution::Remove) => "A moderator removed the reported issue.",
+        }
+    }
+
+    /// Title of the issue the content is or belongs to, for the reporter's
+    /// notification
+    fn content_title(conn: &mut PgConnection, report: &ContentReport) -> Result<String, AppError> {
+        let issue_id = if report.target_type == report_targets::COMMENT {
+            CommentRepo::find_by_id_with_issue(conn, report.target_id)?
+                .map(|(_, issue_id)| issue_id)
+        } else {
+            Some(report.target_id)
+        };
+        let title = match issue_id {
+            Some(issue_id) => ContentReportsRepo::issue_summaries(conn, &[issue_id])?
+                .into_iter()
+                .next()
+                .map(|(_, _, title)| title),
+            None => None,
+        };
+        Ok(title.unwrap_or_else(|| format!("Reported {}", report.target_type)))
+    }
+
+    /// Hidden and removed comments disappear for clients alike
+    fn comment_gone(ctx: &RequestContext, comment_id: Uuid) {
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Comment,
+            EntityAction::Deleted,
+            comment_id,
+            &(),
+        );
+    }
+}
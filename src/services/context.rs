@@ -1,8 +1,59 @@
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// How the caller authenticated; API channels can be held to a narrower set
+/// of permissions than interactive sessions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthChannel {
+    /// Interactive login with a session JWT
+    #[default]
+    Session,
+    /// Personal access token
+    ApiToken,
+    /// OAuth app acting for a user
+    OauthApp,
+    /// Workspace API key acting for the admin who created it
+    ApiKey,
+}
+
+impl AuthChannel {
+    /// Channels that admins can restrict
+    pub const API: [AuthChannel; 3] = [
+        AuthChannel::ApiToken,
+        AuthChannel::OauthApp,
+        AuthChannel::ApiKey,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuthChannel::Session => "session",
+            AuthChannel::ApiToken => "api_token",
+            AuthChannel::OauthApp => "oauth_app",
+            AuthChannel::ApiKey => "api_key",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            AuthChannel::Session,
+            AuthChannel::ApiToken,
+            AuthChannel::OauthApp,
+            AuthChannel::ApiKey,
+        ]
+        .into_iter()
+        .find(|channel| channel.as_str() == value)
+    }
+
+    pub fn is_api(self) -> bool {
+        self != AuthChannel::Session
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RequestContext {
     pub user_id: Uuid,
     pub workspace_id: Uuid,
     pub idempotency_key: Option<String>,
+    pub channel: AuthChannel,
 }
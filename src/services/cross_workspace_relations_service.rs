@@ -0,0 +1,327 @@
+use diesel::prelude::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    db::models::issue::Issue,
+    db::models::issue_relation::{
+        CreateCrossWorkspaceRelationRequest, CrossWorkspaceIssueRelation,
+        CrossWorkspaceRelationResponse, ExternalIssueRef, ExternalIssueSummary, IssueRelationType,
+        NewCrossWorkspaceIssueRelation,
+    },
+    db::models::organization::Organization,
+    db::models::team::TeamHierarchy,
+    db::models::workspace::Workspace,
+    db::repositories::cross_workspace_relations::CrossWorkspaceRelationsRepo,
+    db::repositories::issue_links::IssueLinksRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::organizations::OrganizationsRepo,
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    db::repositories::workspaces::WorkspacesRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::issue_moves_service::IssueMovesService,
+    services::issue_relations_service::{
+        IssueRelationsService, RELATION_CREATED, RELATION_DELETED,
+    },
+    services::realtime_service::RealtimeService,
+    services::team_hierarchy_service::TeamHierarchyService,
+    websocket::Topic,
+};
+
+/// Relations between issues of sibling workspaces in an organization. The
+/// other side is only ever shown as its key, title and state, and only
+/// while the organization allows cross-workspace links.
+pub struct CrossWorkspaceRelationsService;
+
+impl CrossWorkspaceRelationsService {
+    /// Whether a workspace in `organization` may see the title and state of
+    /// an issue in `other`; issues of private teams are never shown
+    pub fn read_through_allowed(
+        organization: Option<&Organization>,
+        other: &Workspace,
+        issue_team_is_public: bool,
+    ) -> bool {
+        issue_team_is_public
+            && organization.is_some_and(|org| {
+                org.allow_cross_workspace_links && other.organization_id == Some(org.id)
+            })
+    }
+
+    pub fn summarize(
+        issue: &Issue,
+        team_key: &str,
+        workspace: &Workspace,
+        state: Option<&str>,
+        readable: bool,
+    ) -> ExternalIssueSummary {
+        ExternalIssueSummary {
+            id: issue.id,
+            workspace_id: workspace.id,
+            workspace_url_key: workspace.url_key.clone(),
+            key: IssueMovesService::issue_key(team_key, issue.issue_number),
+            title: readable.then(|| issue.title.clone()),
+            state: state.filter(|_| readable).map(str::to_string),
+            restricted: !readable,
+        }
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        req: &CreateCrossWorkspaceRelationRequest,
+    ) -> Result<CrossWorkspaceRelationResponse, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let reference = ExternalIssueRef::parse(&req.reference).ok_or_else(|| {
+            AppError::validation("Reference must look like <workspace>/<TEAM>-<number>")
+        })?;
+        let organization = OrganizationsRepo::find_for_workspace(conn, ctx.workspace_id)?
+            .filter(|org| org.allow_cross_workspace_links)
+            .ok_or_else(|| {
+                AppError::forbidden(
+                    "Your organization does not allow linking issues across workspaces",
+                )
+            })?;
+
+        let target_workspace = WorkspacesRepo::find_by_url_key(conn, &reference.workspace_url_key)?
+            .filter(|w| w.id != ctx.workspace_id && w.organization_id == Some(organization.id))
+            .ok_or_else(|| AppError::not_found("workspace"))?;
+        // Issues of private teams cannot be referenced from outside
+        let public_teams = Self::public_teams(conn, target_workspace.id)?;
+        let target_team = TeamsRepo::find_by_key(conn, target_workspace.id, &reference.team_key)?
+            .filter(|t| public_teams.contains(&t.id))
+            .ok_or_else(|| AppError::not_found("issue"))?;
+        let target = IssueLinksRepo::find_issue(conn, target_team.id, reference.issue_number)?
+            .filter(|i| i.archived_at.is_none())
+            .ok_or_else(|| AppError::not_found("issue"))?;
+
+        let (from, to, stored_type) =
+            IssueRelationsService::canonical(issue_id, target.id, req.relation_type);
+        let relation = conn.transaction::<_, AppError, _>(|conn| {
+            if CrossWorkspaceRelationsRepo::exists(conn, from, to, stored_type.as_str())?
+                || (matches!(
+                    stored_type,
+                    IssueRelationType::RelatesTo | IssueRelationType::Duplicates
+                ) && CrossWorkspaceRelationsRepo::exists(conn, to, from, stored_type.as_str())?)
+            {
+                return Err(AppError::conflict_with_code(
+                    "These issues are already related this way",
+                    Some("reference".to_string()),
+                    "RELATION_EXISTS",
+                ));
+            }
+            Ok(CrossWorkspaceRelationsRepo::insert(
+                conn,
+                &NewCrossWorkspaceIssueRelation {
+                    workspace_id: ctx.workspace_id,
+                    issue_id: from,
+                    related_issue_id: to,
+                    relation_type: stored_type.as_str().to_string(),
+                    created_by: ctx.user_id,
+                },
+            )?)
+        })?;
+
+        Self::publish(
+            ctx,
+            &relation,
+            issue_id,
+            target_workspace.id,
+            RELATION_CREATED,
+        );
+        let state = match target.workflow_state_id {
+            Some(state_id) => WorkflowsRepo::list_states_by_ids(conn, &[state_id])?
+                .into_iter()
+                .next()
+                .map(|s| s.name),
+            None => None,
+        };
+        let related_issue = Self::summarize(
+            &target,
+            &target_team.team_key,
+            &target_workspace,
+            state.as_deref(),
+            true,
+        );
+        Ok(Self::to_response(
+            ctx.workspace_id,
+            issue_id,
+            relation,
+            related_issue,
+        ))
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        relation_id: Uuid,
+    ) -> Result<(), AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let relation = CrossWorkspaceRelationsRepo::find_by_id(conn, relation_id)?
+            .filter(|r| r.issue_id == issue_id || r.related_issue_id == issue_id)
+            .ok_or_else(|| AppError::not_found("issue relation"))?;
+        let other_id = if relation.issue_id == issue_id {
+            relation.related_issue_id
+        } else {
+            relation.issue_id
+        };
+        let other_team = IssueRepo::find_by_id(conn, other_id)?.map(|i| i.team_id);
+        let other_workspace = match other_team {
+            Some(team_id) => TeamsRepo::list_by_ids(conn, &[team_id])?
+                .into_iter()
+                .next()
+                .map(|t| t.workspace_id),
+            None => None,
+        };
+
+        CrossWorkspaceRelationsRepo::delete(conn, relation.id)?;
+        if let Some(other_workspace) = other_workspace {
+            Self::publish(ctx, &relation, issue_id, other_workspace, RELATION_DELETED);
+        }
+        Ok(())
+    }
+
+    /// Cross-workspace relations of the issue as seen from it, oldest first
+    pub fn list_for_issue(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<Vec<CrossWorkspaceRelationResponse>, AppError> {
+        let rows = CrossWorkspaceRelationsRepo::list_for_issue(conn, issue_id)?;
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let team_ids: Vec<Uuid> = rows.iter().map(|(_, other)| other.team_id).collect();
+        let teams: HashMap<Uuid, _> = TeamsRepo::list_by_ids(conn, &team_ids)?
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+        let workspace_ids: Vec<Uuid> = teams
+            .values()
+            .map(|t| t.workspace_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let workspaces: HashMap<Uuid, Workspace> =
+            WorkspacesRepo::list_by_ids(conn, &workspace_ids)?
+                .into_iter()
+                .map(|w| (w.id, w))
+                .collect();
+        let mut public_teams = HashSet::new();
+        for workspace in &workspace_ids {
+            public_teams.extend(Self::public_teams(conn, *workspace)?);
+        }
+        let state_ids: Vec<Uuid> = rows
+            .iter()
+            .filter_map(|(_, other)| other.workflow_state_id)
+            .collect();
+        let states: HashMap<Uuid, String> = WorkflowsRepo::list_states_by_ids(conn, &state_ids)?
+            .into_iter()
+            .map(|s| (s.id, s.name))
+            .collect();
+        let organization = OrganizationsRepo::find_for_workspace(conn, workspace_id)?;
+
+        let mut relations = Vec::new();
+        for (relation, other) in rows {
+            let Some(team) = teams.get(&other.team_id) else {
+                continue;
+            };
+            let Some(workspace) = workspaces.get(&team.workspace_id) else {
+                continue;
+            };
+            let readable = Self::read_through_allowed(
+                organization.as_ref(),
+                workspace,
+                public_teams.contains(&team.id),
+            );
+            let state = other
+                .workflow_state_id
+                .and_then(|id| states.get(&id))
+                .map(String::as_str);
+            let related_issue = Self::summarize(&other, &team.team_key, workspace, state, readable);
+            relations.push(Self::to_response(
+                workspace_id,
+                issue_id,
+                relation,
+                related_issue,
+            ));
+        }
+        Ok(relations)
+    }
+
+    /// Relations to the issue created from sibling workspaces
+    pub fn backlinks(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<Vec<CrossWorkspaceRelationResponse>, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        Ok(Self::list_for_issue(conn, ctx.workspace_id, issue_id)?
+            .into_iter()
+            .filter(|r| r.is_backlink)
+            .collect())
+    }
+
+    fn public_teams(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+    ) -> Result<HashSet<Uuid>, AppError> {
+        let teams = TeamsRepo::list_by_workspace(conn, workspace_id)?;
+        let hierarchy = TeamHierarchy::from_teams(&teams);
+        Ok(TeamHierarchyService::visible_team_ids(
+            &teams,
+            &hierarchy,
+            &HashSet::new(),
+        ))
+    }
+
+    fn to_response(
+        viewer_workspace: Uuid,
+        viewer: Uuid,
+        relation: CrossWorkspaceIssueRelation,
+        related_issue: ExternalIssueSummary,
+    ) -> CrossWorkspaceRelationResponse {
+        let stored = IssueRelationType::parse(&relation.relation_type)
+            .unwrap_or(IssueRelationType::RelatesTo);
+        let relation_type = if relation.issue_id == viewer {
+            stored
+        } else {
+            stored.inverse()
+        };
+        CrossWorkspaceRelationResponse {
+            id: relation.id,
+            relation_type,
+            related_issue,
+            is_backlink: relation.workspace_id != viewer_workspace,
+            created_by: relation.created_by,
+            created_at: relation.created_at,
+        }
+    }
+
+    /// Tell both issues, each in its own workspace
+    fn publish(
+        ctx: &RequestContext,
+        relation: &CrossWorkspaceIssueRelation,
+        local_issue: Uuid,
+        other_workspace: Uuid,
+        event: &str,
+    ) {
+        let other_issue = if relation.issue_id == local_issue {
+            relation.related_issue_id
+        } else {
+            relation.issue_id
+        };
+        RealtimeService::publish(ctx.workspace_id, Topic::Issue(local_issue), event, relation);
+        RealtimeService::publish(other_workspace, Topic::Issue(other_issue), event, relation);
+    }
+}
@@ -0,0 +1,355 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor,
+    db::models::custom_emoji::{
+        CreateCustomEmojiRequest, CustomEmoji, CustomEmojiResponse, CustomEmojiStatsQuery,
+        CustomEmojiUpload, NewCustomEmoji, RenameCustomEmojiRequest, custom_emoji_status,
+    },
+    db::repositories::custom_emojis::CustomEmojisRepo,
+    error::AppError,
+    services::attachments_service::UPLOAD_URL_TTL_SECS,
+    services::context::RequestContext,
+    services::permission_service::{Permission, PermissionService},
+    utils::clock,
+    utils::{AssetUrlHelper, ObjectStorage},
+};
+
+/// Largest accepted emoji image, in bytes
+pub const MAX_EMOJI_BYTES: i64 = 256 * 1024;
+
+/// Image types accepted for emoji
+pub const EMOJI_CONTENT_TYPES: [&str; 4] = ["image/png", "image/gif", "image/jpeg", "image/webp"];
+
+const MIN_NAME_CHARS: usize = 2;
+
+/// Keeps `:name:` within the 32 characters a reaction may have
+const MAX_NAME_CHARS: usize = 30;
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '+')
+}
+
+fn is_valid_name(name: &str) -> bool {
+    (MIN_NAME_CHARS..=MAX_NAME_CHARS).contains(&name.len()) && name.chars().all(is_name_char)
+}
+
+pub struct CustomEmojisService;
+
+impl CustomEmojisService {
+    /// Lowercased name without surrounding colons, so `:Party:` and `party`
+    /// both register `party`
+    pub fn normalize_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        let name = name
+            .strip_prefix(':')
+            .and_then(|rest| rest.strip_suffix(':'))
+            .unwrap_or(name)
+            .to_ascii_lowercase();
+        if !is_valid_name(&name) {
+            return Err(AppError::validation(format!(
+                "Emoji name must be {} to {} characters of a-z, 0-9, _, - or +",
+                MIN_NAME_CHARS, MAX_NAME_CHARS
+            )));
+        }
+        Ok(name)
+    }
+
+    /// Name of the custom emoji a reaction refers to, when it is written as
+    /// a `:name:` shortcode rather than a Unicode emoji
+    pub fn reaction_name(emoji: &str) -> Option<&str> {
+        emoji
+            .strip_prefix(':')
+            .and_then(|rest| rest.strip_suffix(':'))
+            .filter(|name| is_valid_name(name))
+    }
+
+    /// Distinct `:name:` shortcodes in markdown, in order of first use. A
+    /// shortcode must not follow a letter or digit, so times like `10:30:45`
+    /// are not mistaken for one.
+    pub fn shortcodes(content: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut rest = content;
+        let mut prev: Option<char> = None;
+        while let Some(start) = rest.find(':') {
+            prev = rest[..start].chars().last().or(prev);
+            let after = &rest[start + 1..];
+            let end = after.find(|c: char| !is_name_char(c));
+            let matched = match end {
+                Some(end) if after[end..].starts_with(':') => {
+                    let name = &after[..end];
+                    (is_valid_name(name) && !prev.is_some_and(|c| c.is_alphanumeric()))
+                        .then_some((name, end))
+                }
+                _ => None,
+            };
+            match matched {
+                Some((name, end)) => {
+                    if !names.iter().any(|existing| existing == name) {
+                        names.push(name.to_string());
+                    }
+                    // Continue after the closing colon
+                    prev = Some(':');
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    prev = Some(':');
+                    rest = after;
+                }
+            }
+        }
+        names
+    }
+
+    /// Fail unless a `:name:` reaction names an uploaded emoji of the
+    /// workspace; Unicode reactions always pass
+    pub fn require_reaction_emoji(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        emoji: &str,
+    ) -> Result<(), AppError> {
+        match Self::reaction_name(emoji) {
+            Some(name) if !CustomEmojisRepo::exists_uploaded(conn, ctx.workspace_id, name)? => Err(
+                AppError::validation(format!("Unknown custom emoji {}", emoji)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Count one use of each named emoji. Usage only feeds the cleanup
+    /// stats, so failures are logged and not surfaced.
+    pub fn record_usage_quietly(conn: &mut PgConnection, ctx: &RequestContext, names: &[String]) {
+        if let Err(e) = CustomEmojisRepo::record_usage(conn, ctx.workspace_id, names, clock::now())
+        {
+            tracing::warn!("Failed to record custom emoji usage: {}", e);
+        }
+    }
+
+    fn require_storage(storage: Option<&ObjectStorage>) -> Result<&ObjectStorage, AppError> {
+        storage.ok_or_else(|| AppError::Config("Emoji storage is not configured".to_string()))
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        emoji_id: Uuid,
+    ) -> Result<CustomEmoji, AppError> {
+        CustomEmojisRepo::find(conn, ctx.workspace_id, emoji_id)?
+            .ok_or_else(|| AppError::not_found("emoji"))
+    }
+
+    fn ensure_name_free(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        name: &str,
+    ) -> Result<(), AppError> {
+        if CustomEmojisRepo::find_by_name(conn, ctx.workspace_id, name)?.is_some() {
+            return Err(AppError::conflict_with_code(
+                format!("An emoji named :{}: already exists", name),
+                Some("name".into()),
+                "EMOJI_NAME_EXISTS",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn to_response(asset_helper: &AssetUrlHelper, emoji: CustomEmoji) -> CustomEmojiResponse {
+        CustomEmojiResponse {
+            shortcode: emoji.shortcode(),
+            url: asset_helper.build_emoji_url(&emoji.storage_key),
+            emoji,
+        }
+    }
+
+    /// Record a pending emoji and return a presigned URL the client uploads
+    /// the image to
+    pub fn create_upload(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        req: CreateCustomEmojiRequest,
+    ) -> Result<CustomEmojiUpload, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageEmoji)?;
+        let storage = Self::require_storage(storage)?;
+
+        let name = Self::normalize_name(&req.name)?;
+        let content_type = req.content_type.trim().to_ascii_lowercase();
+        if !EMOJI_CONTENT_TYPES.contains(&content_type.as_str()) {
+            return Err(AppError::validation(format!(
+                "Emoji image must be one of {}",
+                EMOJI_CONTENT_TYPES.join(", ")
+            )));
+        }
+        if req.size_bytes <= 0 || req.size_bytes > MAX_EMOJI_BYTES {
+            return Err(AppError::validation(format!(
+                "Emoji image size must be between 1 and {} bytes",
+                MAX_EMOJI_BYTES
+            )));
+        }
+        Self::ensure_name_free(conn, ctx, &name)?;
+
+        let id = clock::new_id();
+        let extension = content_type.rsplit('/').next().unwrap_or("png");
+        let emoji = CustomEmojisRepo::insert(
+            conn,
+            &NewCustomEmoji {
+                id,
+                workspace_id: ctx.workspace_id,
+                storage_key: format!("{}/{}.{}", ctx.workspace_id, id, extension),
+                name,
+                content_type,
+                size_bytes: req.size_bytes,
+                created_by: Some(ctx.user_id),
+            },
+        )?;
+
+        Ok(CustomEmojiUpload {
+            upload_url: storage.presign("PUT", &emoji.object_key(), UPLOAD_URL_TTL_SECS),
+            upload_method: "PUT",
+            expires_at: clock::now() + chrono::Duration::seconds(UPLOAD_URL_TTL_SECS as i64),
+            emoji,
+        })
+    }
+
+    /// Confirm the image exists in storage and make the emoji usable.
+    /// Oversized images are removed and the emoji discarded. No connection
+    /// is held while storage is checked.
+    pub async fn complete_upload(
+        executor: &DbExecutor,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        asset_helper: &AssetUrlHelper,
+        emoji_id: Uuid,
+    ) -> Result<CustomEmojiResponse, AppError> {
+        let ctx = ctx.clone();
+        let emoji = executor
+            .read(move |conn| {
+                PermissionService::require(conn, &ctx, Permission::ManageEmoji)?;
+                Self::find(conn, &ctx, emoji_id)
+            })
+            .await?;
+        let storage = Self::require_storage(storage)?;
+        if emoji.status == custom_emoji_status::UPLOADED {
+            return Ok(Self::to_response(asset_helper, emoji));
+        }
+
+        let object_key = emoji.object_key();
+        let size = storage
+            .head(&object_key)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to check emoji image: {}", e)))?
+            .ok_or_else(|| AppError::validation("Emoji image has not been uploaded yet"))?;
+
+        let id = emoji.id;
+        if size > MAX_EMOJI_BYTES {
+            executor
+                .transaction(move |conn| Ok(CustomEmojisRepo::delete(conn, id)?))
+                .await?;
+            Self::delete_object_quietly(storage, &object_key).await;
+            return Err(AppError::validation(format!(
+                "Emoji image exceeds the maximum size of {} bytes",
+                MAX_EMOJI_BYTES
+            )));
+        }
+
+        let emoji = executor
+            .transaction(move |conn| {
+                Ok(CustomEmojisRepo::mark_uploaded(
+                    conn,
+                    id,
+                    size,
+                    clock::now(),
+                )?)
+            })
+            .await?;
+        Ok(Self::to_response(asset_helper, emoji))
+    }
+
+    /// Usable emoji of the workspace in name order, with the URLs clients
+    /// render `:name:` shortcodes with
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        asset_helper: &AssetUrlHelper,
+    ) -> Result<Vec<CustomEmojiResponse>, AppError> {
+        Ok(CustomEmojisRepo::list_uploaded(conn, ctx.workspace_id)?
+            .into_iter()
+            .map(|emoji| Self::to_response(asset_helper, emoji))
+            .collect())
+    }
+
+    /// Emoji by usage, least used first, to help admins clean up
+    pub fn stats(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        asset_helper: &AssetUrlHelper,
+        query: &CustomEmojiStatsQuery,
+    ) -> Result<Vec<CustomEmojiResponse>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageEmoji)?;
+        let unused_since = match query.unused_days {
+            Some(days) if days < 0 => {
+                return Err(AppError::validation("unused_days must not be negative"));
+            }
+            Some(days) => Some(clock::now() - chrono::Duration::days(days)),
+            None => None,
+        };
+        Ok(
+            CustomEmojisRepo::list_by_usage(conn, ctx.workspace_id, unused_since)?
+                .into_iter()
+                .map(|emoji| Self::to_response(asset_helper, emoji))
+                .collect(),
+        )
+    }
+
+    /// Rename an emoji. Reactions and comments keep the old shortcode, so
+    /// they stop resolving to the image.
+    pub fn rename(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        asset_helper: &AssetUrlHelper,
+        emoji_id: Uuid,
+        req: &RenameCustomEmojiRequest,
+    ) -> Result<CustomEmojiResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageEmoji)?;
+        let emoji = Self::find(conn, ctx, emoji_id)?;
+        let name = Self::normalize_name(&req.name)?;
+        if name == emoji.name {
+            return Ok(Self::to_response(asset_helper, emoji));
+        }
+        Self::ensure_name_free(conn, ctx, &name)?;
+
+        let emoji = CustomEmojisRepo::rename(conn, emoji.id, &name, clock::now())?;
+        Ok(Self::to_response(asset_helper, emoji))
+    }
+
+    /// Delete the emoji record, then its image once the delete has
+    /// committed. A failed object delete only leaves an orphan in the bucket,
+    /// so it is logged and not surfaced.
+    pub async fn delete(
+        executor: &DbExecutor,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        emoji_id: Uuid,
+    ) -> Result<(), AppError> {
+        let ctx = ctx.clone();
+        let emoji = executor
+            .transaction(move |conn| {
+                PermissionService::require(conn, &ctx, Permission::ManageEmoji)?;
+                let emoji = Self::find(conn, &ctx, emoji_id)?;
+                CustomEmojisRepo::delete(conn, emoji.id)?;
+                Ok(emoji)
+            })
+            .await?;
+        if let Some(storage) = storage {
+            Self::delete_object_quietly(storage, &emoji.object_key()).await;
+        }
+        Ok(())
+    }
+
+    async fn delete_object_quietly(storage: &ObjectStorage, object_key: &str) {
+        if let Err(e) = storage.delete(object_key).await {
+            tracing::warn!("Failed to delete emoji object {}: {}", object_key, e);
+        }
+    }
+}
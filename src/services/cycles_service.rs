@@ -1,12 +1,32 @@
+use chrono::Datelike;
 use diesel::prelude::*;
 
 use crate::{
-    db::models::cycle::{Cycle, NewCycle},
+    db::enums::CycleStatus,
+    db::models::app_installation::webhook_events,
+    db::models::cycle::{
+        Cycle, CycleAutoCreateSummary, CycleBurndown, CyclePoints, CycleStatusUpdates, NewCycle,
+        NewTeamCycleSettings, NextCyclePreview, TeamCycleSettings, UpsertCycleSettingsRequest,
+    },
+    db::models::workflow::WorkflowStateCategory,
+    db::repositories::cycle_settings::CycleSettingsRepo,
     db::repositories::cycles::CyclesRepo,
+    db::repositories::teams::TeamsRepo,
     error::AppError,
     services::context::RequestContext,
+    services::holidays_service::HolidaysService,
+    services::realtime_service::RealtimeService,
+    services::teams_service::TeamsService,
+    services::webhook_service::WebhookService,
+    utils::clock,
+    websocket::{EntityAction, EntityKind, Topic},
 };
 
+/// Longest cycle a team's cadence may use
+pub const MAX_CYCLE_LENGTH_DAYS: i32 = 84;
+/// Longest break a team's cadence may leave between cycles
+pub const MAX_COOLDOWN_DAYS: i32 = 28;
+
 pub struct CyclesService;
 
 impl CyclesService {
@@ -17,7 +37,7 @@ impl CyclesService {
 
     pub fn create(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         req: &crate::routes::cycles::CreateCycleRequest,
     ) -> Result<Cycle, AppError> {
         let new_cycle = NewCycle {
@@ -29,6 +49,13 @@ impl CyclesService {
             goal: req.goal.clone(),
         };
         let created = CyclesRepo::insert(conn, &new_cycle)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Cycle,
+            EntityAction::Created,
+            created.id,
+            &created,
+        );
         Ok(created)
     }
 
@@ -44,7 +71,7 @@ impl CyclesService {
 
     pub fn update(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         cycle_id: uuid::Uuid,
         req: &crate::routes::cycles::UpdateCycleRequest,
     ) -> Result<Cycle, AppError> {
@@ -62,47 +89,82 @@ impl CyclesService {
             req.end_date
                 .map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default()),
         )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Cycle,
+            EntityAction::Updated,
+            updated.id,
+            &updated,
+        );
         Ok(updated)
     }
 
     pub fn delete(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         cycle_id: uuid::Uuid,
     ) -> Result<(), AppError> {
         let _existing =
             CyclesRepo::find_by_id(conn, cycle_id)?.ok_or_else(|| AppError::not_found("cycle"))?;
 
         CyclesRepo::delete_by_id(conn, cycle_id)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Cycle,
+            EntityAction::Deleted,
+            cycle_id,
+            &(),
+        );
         Ok(())
     }
 
     pub fn get_stats(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         cycle_id: uuid::Uuid,
     ) -> Result<crate::routes::cycles::CycleStats, AppError> {
         let cycle =
             CyclesRepo::find_by_id(conn, cycle_id)?.ok_or_else(|| AppError::not_found("cycle"))?;
 
-        // Get issue counts by status - simplified for now
-        // use crate::schema::issues; // not used in simplified stats
-        let stats: Vec<(String, i64)> = Vec::new(); // Simplified for now
-
-        let mut total_issues = 0;
-        let mut completed_issues = 0;
-        let mut in_progress_issues = 0;
-        let mut planned_issues = 0;
-
-        for (status, count) in stats {
-            total_issues += count;
-            match status.as_str() {
-                "completed" => completed_issues = count,
-                "in_progress" => in_progress_issues = count,
-                "planned" => planned_issues = count,
-                _ => {}
-            }
-        }
+        let issues = CyclesRepo::issue_points(conn, cycle.id)?;
+        let total_issues = issues.len() as i64;
+        let count = |categories: &[WorkflowStateCategory]| {
+            issues
+                .iter()
+                .filter(|i| i.category.is_some_and(|c| categories.contains(&c)))
+                .count() as i64
+        };
+        let completed_issues = count(&[WorkflowStateCategory::Completed]);
+        let in_progress_issues = count(&[WorkflowStateCategory::Started]);
+        let planned_issues = count(&[
+            WorkflowStateCategory::Backlog,
+            WorkflowStateCategory::Unstarted,
+            WorkflowStateCategory::Triage,
+        ]);
+
+        let cycle_start = cycle
+            .start_date
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc();
+        let changes = CyclesRepo::scope_changes_since(conn, cycle.id, cycle_start)?;
+        let points = CyclePoints::compute(&issues, &changes);
+
+        let today = clock::now().date_naive();
+        let days_remaining = (cycle.end_date - today).num_days().max(0) as i32;
+        let working_days_total = HolidaysService::working_days_between(
+            conn,
+            ctx.workspace_id,
+            cycle.start_date,
+            cycle.end_date,
+        )?;
+        let working_days_remaining = HolidaysService::working_days_between(
+            conn,
+            ctx.workspace_id,
+            today.max(cycle.start_date),
+            cycle.end_date,
+        )?;
+        let is_overdue = today > cycle.end_date && cycle.status != CycleStatus::Completed;
 
         Ok(crate::routes::cycles::CycleStats {
             id: cycle.id,
@@ -119,11 +181,33 @@ impl CyclesService {
             } else {
                 0.0
             },
-            days_remaining: 0, // Calculate based on end_date
-            is_overdue: false, // Calculate based on end_date
+            days_remaining,
+            working_days_total,
+            working_days_remaining,
+            is_overdue,
+            points,
         })
     }
 
+    /// Daily remaining, completed and added issues and points of the cycle,
+    /// replayed from its scope change log
+    pub fn get_burndown(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        cycle_id: uuid::Uuid,
+    ) -> Result<CycleBurndown, AppError> {
+        let cycle = CyclesRepo::find_by_id_in_workspace(conn, ctx.workspace_id, cycle_id)?
+            .ok_or_else(|| AppError::not_found("cycle"))?;
+        let changes = CyclesRepo::scope_changes(conn, cycle.id)?;
+        Ok(CycleBurndown::compute(
+            cycle.id,
+            cycle.start_date,
+            cycle.end_date,
+            clock::now().date_naive(),
+            &changes,
+        ))
+    }
+
     pub fn get_issues(
         conn: &mut PgConnection,
         _ctx: &RequestContext,
@@ -138,6 +222,7 @@ impl CyclesService {
         use crate::schema::issues;
         let query = issues::table
             .filter(issues::cycle_id.eq(cycle_id))
+            .filter(issues::archived_at.is_null())
             .into_boxed();
 
         // Note: issues table might not have status field, simplified for now
@@ -190,22 +275,254 @@ impl CyclesService {
         Ok(())
     }
 
-    pub fn auto_update_status(_conn: &mut PgConnection) -> Result<(), AppError> {
-        use chrono::Utc;
+    /// Activate planned cycles once they start and complete cycles once
+    /// they end, by today's UTC date
+    pub fn auto_update_status(conn: &mut PgConnection) -> Result<CycleStatusUpdates, AppError> {
+        let now = clock::now();
+        let today = now.date_naive();
+        let activated = CyclesRepo::activate_started(conn, today, now)?;
+        let completed = CyclesRepo::complete_ended(conn, today, now)?;
+        let team_ids: Vec<uuid::Uuid> = activated
+            .iter()
+            .chain(&completed)
+            .map(|cycle| cycle.team_id)
+            .collect();
+        let workspaces: std::collections::HashMap<uuid::Uuid, uuid::Uuid> =
+            TeamsRepo::list_by_ids(conn, &team_ids)?
+                .into_iter()
+                .map(|team| (team.id, team.workspace_id))
+                .collect();
+        for cycle in activated.iter().chain(&completed) {
+            if let Some(&workspace_id) = workspaces.get(&cycle.team_id) {
+                RealtimeService::entity_changed(
+                    workspace_id,
+                    EntityKind::Cycle,
+                    EntityAction::Updated,
+                    cycle.id,
+                    cycle,
+                );
+            }
+        }
+        Ok(CycleStatusUpdates {
+            activated: activated.len(),
+            completed: completed.len(),
+        })
+    }
 
-        let _now = Utc::now().naive_utc();
+    pub fn get_settings(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: uuid::Uuid,
+    ) -> Result<TeamCycleSettings, AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        CycleSettingsRepo::find(conn, team_id)?.ok_or_else(|| AppError::not_found("cycle settings"))
+    }
 
-        // Update cycles that have started - simplified for now
-        // Note: cycles table might not have status field
-        // diesel::update(cycles::table.filter(cycles::start_date.le(now)))
-        //     .set(cycles::status.eq("active"))
-        //     .execute(conn)?;
+    pub fn upsert_settings(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: uuid::Uuid,
+        req: &UpsertCycleSettingsRequest,
+    ) -> Result<TeamCycleSettings, AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        Self::validate_cadence(req.length_days, req.start_weekday, req.cooldown_days)?;
+        let settings = CycleSettingsRepo::upsert(
+            conn,
+            &NewTeamCycleSettings {
+                team_id,
+                auto_create: req.auto_create.unwrap_or(true),
+                length_days: req.length_days,
+                start_weekday: req.start_weekday,
+                cooldown_days: req.cooldown_days,
+                roll_over_unfinished: req.roll_over_unfinished,
+            },
+        )?;
+        Ok(settings)
+    }
 
-        // Update cycles that have ended - simplified for now
-        // diesel::update(cycles::table.filter(cycles::end_date.le(now)))
-        //     .set(cycles::status.eq("completed"))
-        //     .execute(conn)?;
+    pub fn delete_settings(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: uuid::Uuid,
+    ) -> Result<(), AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        if CycleSettingsRepo::delete(conn, team_id)? == 0 {
+            return Err(AppError::not_found("cycle settings"));
+        }
+        Ok(())
+    }
 
+    pub fn validate_cadence(
+        length_days: i32,
+        start_weekday: i32,
+        cooldown_days: i32,
+    ) -> Result<(), AppError> {
+        if !(1..=MAX_CYCLE_LENGTH_DAYS).contains(&length_days) {
+            return Err(AppError::validation(format!(
+                "length_days must be between 1 and {}",
+                MAX_CYCLE_LENGTH_DAYS
+            )));
+        }
+        if !(1..=7).contains(&start_weekday) {
+            return Err(AppError::validation(
+                "start_weekday must be between 1 (Monday) and 7 (Sunday)",
+            ));
+        }
+        if !(0..=MAX_COOLDOWN_DAYS).contains(&cooldown_days) {
+            return Err(AppError::validation(format!(
+                "cooldown_days must be between 0 and {}",
+                MAX_COOLDOWN_DAYS
+            )));
+        }
         Ok(())
     }
+
+    /// Start and end date of the cycle following one that ends on
+    /// `previous_end`: the first start weekday after the cooldown, and never
+    /// before `today`, so a team that lapsed does not get cycles in the past
+    pub fn next_cycle_window(
+        settings: &TeamCycleSettings,
+        previous_end: Option<chrono::NaiveDate>,
+        today: chrono::NaiveDate,
+    ) -> (chrono::NaiveDate, chrono::NaiveDate) {
+        let earliest = previous_end
+            .map(|end| end + chrono::Duration::days(1 + settings.cooldown_days as i64))
+            .map_or(today, |date| date.max(today));
+        let offset =
+            (settings.start_weekday as i64 - 1 - earliest.weekday().num_days_from_monday() as i64)
+                .rem_euclid(7);
+        let start = earliest + chrono::Duration::days(offset);
+        let end = start + chrono::Duration::days(settings.length_days as i64 - 1);
+        (start, end)
+    }
+
+    /// The cycle that follows the team's latest one under its cadence
+    pub fn preview_next(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: uuid::Uuid,
+    ) -> Result<NextCyclePreview, AppError> {
+        let settings = Self::get_settings(conn, ctx, team_id)?;
+        let roll_over_issues = if settings.roll_over_unfinished {
+            match CyclesRepo::first_with_status(conn, team_id, "active")? {
+                Some(active) => CyclesRepo::count_unfinished(conn, active.id)?,
+                None => 0,
+            }
+        } else {
+            0
+        };
+        let (new_cycle, previous) = Self::next_cycle(conn, &settings, clock::now().date_naive())?;
+        Ok(NextCyclePreview {
+            team_id,
+            name: new_cycle.name,
+            start_date: new_cycle.start_date,
+            end_date: new_cycle.end_date,
+            previous_cycle_id: previous.map(|cycle| cycle.id),
+            auto_create: settings.auto_create,
+            roll_over_issues,
+        })
+    }
+
+    /// Keep one planned cycle ahead for teams that create cycles
+    /// automatically, and roll the unfinished issues of each newly completed
+    /// cycle into the team's next one. A failing team is logged and skipped
+    /// so the others still run.
+    pub fn auto_create_next(conn: &mut PgConnection) -> Result<CycleAutoCreateSummary, AppError> {
+        let today = clock::now().date_naive();
+        let mut summary = CycleAutoCreateSummary::default();
+        for (settings, workspace_id) in CycleSettingsRepo::list_scheduled(conn)? {
+            // Counted only once the team's transaction has committed
+            match conn.transaction(|conn| Self::run_cadence(conn, &settings, workspace_id, today)) {
+                Ok(team) => {
+                    summary.created += team.created;
+                    summary.rolled_over += team.rolled_over;
+                }
+                Err(e) => {
+                    tracing::warn!("Cycle cadence for team {} failed: {}", settings.team_id, e);
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    fn run_cadence(
+        conn: &mut PgConnection,
+        settings: &TeamCycleSettings,
+        workspace_id: uuid::Uuid,
+        today: chrono::NaiveDate,
+    ) -> Result<CycleAutoCreateSummary, AppError> {
+        let team_id = settings.team_id;
+        let mut summary = CycleAutoCreateSummary::default();
+        if settings.auto_create
+            && CyclesRepo::first_with_status(conn, team_id, "planned")?.is_none()
+        {
+            let (new_cycle, _) = Self::next_cycle(conn, settings, today)?;
+            let created = CyclesRepo::insert(conn, &new_cycle)?;
+            RealtimeService::entity_changed(
+                workspace_id,
+                EntityKind::Cycle,
+                EntityAction::Created,
+                created.id,
+                &created,
+            );
+            summary.created += 1;
+        }
+
+        if !settings.roll_over_unfinished {
+            return Ok(summary);
+        }
+        let Some(ended) = CyclesRepo::latest_completed(conn, team_id)? else {
+            return Ok(summary);
+        };
+        if settings.last_rolled_over_cycle_id == Some(ended.id) {
+            return Ok(summary);
+        }
+        // Without a following cycle yet, try again on the next run
+        let Some(target) = CyclesRepo::next_open_after(conn, team_id, ended.end_date)? else {
+            return Ok(summary);
+        };
+        let moved = CyclesRepo::move_unfinished(conn, ended.id, target.id)?;
+        for issue in &moved {
+            WebhookService::emit_quietly(conn, workspace_id, webhook_events::ISSUE_UPDATED, issue);
+            RealtimeService::publish(
+                workspace_id,
+                Topic::Issue(issue.id),
+                webhook_events::ISSUE_UPDATED,
+                issue,
+            );
+            RealtimeService::entity_changed(
+                workspace_id,
+                EntityKind::Issue,
+                EntityAction::Updated,
+                issue.id,
+                issue,
+            );
+        }
+        CycleSettingsRepo::mark_rolled_over(conn, team_id, ended.id)?;
+        summary.rolled_over = moved.len();
+        Ok(summary)
+    }
+
+    /// The cycle to create after the team's latest one, and that cycle
+    fn next_cycle(
+        conn: &mut PgConnection,
+        settings: &TeamCycleSettings,
+        today: chrono::NaiveDate,
+    ) -> Result<(NewCycle, Option<Cycle>), AppError> {
+        let previous = CyclesRepo::latest_for_team(conn, settings.team_id)?;
+        let number = CyclesRepo::count_for_team(conn, settings.team_id)? + 1;
+        let (start_date, end_date) =
+            Self::next_cycle_window(settings, previous.as_ref().map(|c| c.end_date), today);
+        Ok((
+            NewCycle {
+                team_id: settings.team_id,
+                name: format!("Cycle {}", number),
+                start_date,
+                end_date,
+                description: None,
+                goal: None,
+            },
+            previous,
+        ))
+    }
 }
@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Weekday};
+use diesel::prelude::*;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor,
+    db::models::dashboard::{
+        DashboardActivity, DashboardCycleProgress, DashboardCycleRollup, DashboardIssue,
+        DashboardSummary,
+    },
+    db::repositories::dashboard::DashboardRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    utils::clock,
+};
+
+/// Open issues listed on the dashboard; one more is fetched to tell whether
+/// the list is complete without counting them
+pub const OPEN_ISSUE_LIMIT: usize = 50;
+/// Active cycles listed on the dashboard
+pub const ACTIVE_CYCLE_LIMIT: usize = 10;
+/// Notifications listed as recent activity
+pub const ACTIVITY_LIMIT: usize = 10;
+/// How long an assembled summary is served from the cache
+pub const CACHE_TTL_SECONDS: u64 = 30;
+/// Latency budget of one summary request. A cache hit is a single Redis read;
+/// a miss costs at most two database round trips plus assembly, and requests
+/// over budget are logged.
+pub const LATENCY_BUDGET: Duration = Duration::from_millis(150);
+/// Share of [`LATENCY_BUDGET`] that assembling the fetched rows may take
+pub const ASSEMBLY_BUDGET: Duration = Duration::from_millis(5);
+
+/// Where the dashboard handler gets uncached summaries from; tests swap in a
+/// stub so the handler runs without a database
+#[async_trait]
+pub trait DashboardSource: Send + Sync {
+    async fn load(
+        &self,
+        ctx: &RequestContext,
+        today: NaiveDate,
+    ) -> Result<DashboardSummary, AppError>;
+}
+
+/// Loads summaries from Postgres with [`DashboardService::load`]
+pub struct PgDashboardSource {
+    executor: DbExecutor,
+}
+
+impl PgDashboardSource {
+    pub fn new(executor: DbExecutor) -> Self {
+        Self { executor }
+    }
+}
+
+#[async_trait]
+impl DashboardSource for PgDashboardSource {
+    async fn load(
+        &self,
+        ctx: &RequestContext,
+        today: NaiveDate,
+    ) -> Result<DashboardSummary, AppError> {
+        let ctx = ctx.clone();
+        self.executor
+            .read(move |conn| DashboardService::load(conn, &ctx, today))
+            .await
+    }
+}
+
+/// The "my work" dashboard: open issues, what is due this week, active cycle
+/// progress and recent activity in one response.
+pub struct DashboardService;
+
+impl DashboardService {
+    pub fn cache_key(workspace_id: Uuid, user_id: Uuid) -> String {
+        format!("dashboard:{}:{}:summary", workspace_id, user_id)
+    }
+
+    /// Build the summary from the database in two round trips
+    pub fn load(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        today: NaiveDate,
+    ) -> Result<DashboardSummary, AppError> {
+        let issues = DashboardRepo::open_issues_for_user(
+            conn,
+            ctx.workspace_id,
+            ctx.user_id,
+            OPEN_ISSUE_LIMIT as i64 + 1,
+        )?;
+        let (cycles, activity) = DashboardRepo::cycles_and_activity(
+            conn,
+            ctx.workspace_id,
+            ctx.user_id,
+            ACTIVE_CYCLE_LIMIT as i64,
+            ACTIVITY_LIMIT as i64,
+        )?;
+        let week_starts_on =
+            WorkspaceSettingsService::load(conn, ctx.workspace_id)?.week_starts_on();
+        Ok(Self::assemble(
+            issues,
+            cycles,
+            activity,
+            today,
+            week_starts_on,
+            clock::now(),
+        ))
+    }
+
+    /// Put the fetched rows together. `issues` may hold one row beyond
+    /// [`OPEN_ISSUE_LIMIT`], which only marks that more exist.
+    pub fn assemble(
+        mut issues: Vec<DashboardIssue>,
+        cycles: Vec<DashboardCycleRollup>,
+        mut activity: Vec<DashboardActivity>,
+        today: NaiveDate,
+        week_starts_on: Weekday,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> DashboardSummary {
+        let has_more_open_issues = issues.len() > OPEN_ISSUE_LIMIT;
+        issues.truncate(OPEN_ISSUE_LIMIT);
+        activity.truncate(ACTIVITY_LIMIT);
+
+        let (week_start, week_end) = Self::week_bounds(today, week_starts_on);
+        let due_this_week = issues
+            .iter()
+            .filter(|issue| {
+                issue
+                    .cycle_end_date
+                    .is_some_and(|end| end >= week_start && end <= week_end)
+            })
+            .cloned()
+            .collect();
+
+        let active_cycles = cycles
+            .into_iter()
+            .take(ACTIVE_CYCLE_LIMIT)
+            .map(|rollup| DashboardCycleProgress {
+                completion_rate: if rollup.total_issues > 0 {
+                    rollup.completed_issues as f64 / rollup.total_issues as f64 * 100.0
+                } else {
+                    0.0
+                },
+                days_remaining: (rollup.end_date - today).num_days().max(0),
+                rollup,
+            })
+            .collect();
+
+        DashboardSummary {
+            my_open_issues: issues,
+            has_more_open_issues,
+            due_this_week,
+            week_start,
+            week_end,
+            active_cycles,
+            recent_activity: activity,
+            generated_at: now,
+            cached: false,
+        }
+    }
+
+    /// First and last day of the week containing `today`, for weeks
+    /// starting on `week_starts_on`
+    pub fn week_bounds(today: NaiveDate, week_starts_on: Weekday) -> (NaiveDate, NaiveDate) {
+        let week = today.week(week_starts_on);
+        (week.first_day(), week.last_day())
+    }
+}
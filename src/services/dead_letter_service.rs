@@ -0,0 +1,198 @@
+use std::sync::OnceLock;
+
+use diesel::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor,
+    db::models::websocket_dead_letter::{
+        DeadLetterQuery, DeadLetterReplay, NewWebSocketDeadLetter, WebSocketDeadLetter,
+        dead_letter_status,
+    },
+    db::repositories::{auth::AuthRepo, websocket_dead_letters::WebSocketDeadLettersRepo},
+    error::{AppError, ErrorCode},
+    utils::clock,
+    websocket::{AuthenticatedUser, WebSocketCommand, WebSocketCommandHandler},
+};
+
+/// Dead letters listed when the request does not ask for a number
+pub const DEFAULT_LIST_LIMIT: i64 = 100;
+pub const MAX_LIST_LIMIT: i64 = 500;
+
+static HANDLER: OnceLock<WebSocketCommandHandler> = OnceLock::new();
+
+pub struct DeadLetterService;
+
+impl DeadLetterService {
+    /// Replay dead letters through the live command handler; later calls
+    /// are ignored
+    pub fn install(handler: WebSocketCommandHandler) {
+        let _ = HANDLER.set(handler);
+    }
+
+    /// Whether a command that failed with `error` should be kept for replay:
+    /// the server failed or hit a transient fault, so the same command may
+    /// succeed once that is fixed
+    pub fn is_dead_letter(error: &AppError) -> bool {
+        let code = error.code();
+        code.status().is_server_error() || code.retryable()
+    }
+
+    pub fn record(
+        conn: &mut PgConnection,
+        new: &NewWebSocketDeadLetter,
+    ) -> Result<WebSocketDeadLetter, AppError> {
+        let dead_letter = WebSocketDeadLettersRepo::insert(conn, new)?;
+        tracing::warn!(
+            "WebSocket {} command from user {} dead-lettered as {}: {}",
+            dead_letter.command_type,
+            dead_letter.user_id,
+            dead_letter.id,
+            dead_letter.error_message
+        );
+        Ok(dead_letter)
+    }
+
+    pub fn list(
+        conn: &mut PgConnection,
+        query: &DeadLetterQuery,
+    ) -> Result<Vec<WebSocketDeadLetter>, AppError> {
+        if let Some(status) = query.status.as_deref()
+            && !dead_letter_status::ALL.contains(&status)
+        {
+            return Err(AppError::validation(format!(
+                "Invalid dead letter status '{}'; expected one of: {}",
+                status,
+                dead_letter_status::ALL.join(", ")
+            )));
+        }
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_LIST_LIMIT)
+            .clamp(1, MAX_LIST_LIMIT);
+        Ok(WebSocketDeadLettersRepo::list(
+            conn,
+            query.status.as_deref(),
+            query.workspace_id,
+            limit,
+        )?)
+    }
+
+    /// Run a pending dead letter again as the user who sent it, in the
+    /// workspace it was sent to. It is marked replayed on success, stays
+    /// pending if it fails the same way again, and is rejected if it now
+    /// fails for a reason a retry will not fix.
+    pub async fn replay(
+        db: &DbExecutor,
+        dead_letter_id: Uuid,
+        admin_id: Uuid,
+    ) -> Result<DeadLetterReplay, AppError> {
+        let handler = HANDLER
+            .get()
+            .ok_or_else(|| AppError::internal("WebSocket command handler is not installed"))?;
+
+        let (dead_letter, user) = db
+            .read(move |conn| {
+                let dead_letter = WebSocketDeadLettersRepo::find_by_id(conn, dead_letter_id)?
+                    .ok_or_else(|| AppError::not_found("dead letter"))?;
+                let user = AuthRepo::find_by_id(conn, dead_letter.user_id)?
+                    .ok_or_else(|| AppError::not_found("user"))?;
+                Ok((dead_letter, user))
+            })
+            .await?;
+        if dead_letter.status != dead_letter_status::PENDING {
+            return Err(AppError::conflict_with_code(
+                format!("Dead letter is already {}", dead_letter.status),
+                None,
+                "DEAD_LETTER_NOT_PENDING",
+            ));
+        }
+
+        // Claim the attempt first so concurrent replays cannot run it twice
+        let seen_attempts = dead_letter.attempts;
+        db.transaction(move |conn| {
+            WebSocketDeadLettersRepo::claim(conn, dead_letter_id, seen_attempts)?.ok_or_else(|| {
+                AppError::conflict_with_code(
+                    "Dead letter is already being replayed",
+                    None,
+                    "DEAD_LETTER_NOT_PENDING",
+                )
+            })
+        })
+        .await?;
+
+        let (status, error, response) =
+            match serde_json::from_str::<WebSocketCommand>(&dead_letter.command) {
+                Ok(command) => {
+                    let user = AuthenticatedUser {
+                        user_id: user.id,
+                        username: user.username,
+                        email: user.email,
+                        name: user.name,
+                        avatar_url: user.avatar_url,
+                        current_workspace_id: Some(dead_letter.workspace_id),
+                    };
+                    let (response, failure) = handler.replay_command(command, &user).await;
+                    let (status, error) = Self::replay_outcome(&response, failure.as_ref());
+                    let response = serde_json::to_value(&response).unwrap_or_default();
+                    (status, error, response)
+                }
+                // The command format changed since it was recorded
+                Err(e) => {
+                    let message = format!("Failed to parse command: {}", e);
+                    let response = serde_json::json!({ "success": false, "error": message });
+                    (
+                        dead_letter_status::REJECTED,
+                        Some((ErrorCode::ValidationFailed.as_str().to_string(), message)),
+                        response,
+                    )
+                }
+            };
+
+        let dead_letter = db
+            .transaction(move |conn| {
+                Ok(WebSocketDeadLettersRepo::finish(
+                    conn,
+                    dead_letter_id,
+                    status,
+                    error,
+                    admin_id,
+                    clock::now(),
+                )?)
+            })
+            .await?;
+        Ok(DeadLetterReplay {
+            dead_letter,
+            response,
+        })
+    }
+
+    /// Status and error to record after a replay produced `response`,
+    /// failing with `failure` if it raised an error
+    pub fn replay_outcome(
+        response: &crate::websocket::WebSocketCommandResponse,
+        failure: Option<&AppError>,
+    ) -> (&'static str, Option<(String, String)>) {
+        match failure {
+            _ if response.success => (dead_letter_status::REPLAYED, None),
+            Some(error) => {
+                let status = if Self::is_dead_letter(error) {
+                    dead_letter_status::PENDING
+                } else {
+                    dead_letter_status::REJECTED
+                };
+                (
+                    status,
+                    Some((error.code().as_str().to_string(), error.to_string())),
+                )
+            }
+            None => (
+                dead_letter_status::REJECTED,
+                response
+                    .error
+                    .as_ref()
+                    .map(|error| (error.code.clone(), error.message.clone())),
+            ),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::delete_confirmation::{
+        DeleteConfirmation, DeleteConfirmationRecord, DeleteImpact, NewDeleteConfirmation,
+        delete_targets,
+    },
+    db::repositories::audit_log::AuditLogRepo,
+    db::repositories::delete_confirmations::DeleteConfirmationsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    utils::clock,
+};
+
+/// How long a confirmation token can be used
+pub const CONFIRMATION_TTL_MINUTES: i64 = 5;
+const TOKEN_PREFIX: &str = "del_";
+
+/// Two-step confirmation of destructive deletes: the first call hands out a
+/// short-lived token with a summary of what would go, the second one spends
+/// it. Both steps are audited.
+pub struct DeleteConfirmationsService;
+
+impl DeleteConfirmationsService {
+    pub fn hash_token(raw: &str) -> String {
+        hex::encode(Sha256::digest(raw.as_bytes()))
+    }
+
+    fn generate_token() -> String {
+        format!(
+            "{}{}{}",
+            TOKEN_PREFIX,
+            clock::new_id().simple(),
+            clock::new_id().simple()
+        )
+    }
+
+    /// Store a confirmation for deleting the target and return its token
+    pub fn request(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        target_type: &str,
+        target_id: Uuid,
+        target_name: &str,
+        impact: DeleteImpact,
+    ) -> Result<DeleteConfirmation, AppError> {
+        let token = Self::generate_token();
+        let impact_json = serde_json::to_string(&impact)
+            .map_err(|e| AppError::internal(format!("Failed to serialize impact: {}", e)))?;
+        let expires_at = clock::now() + chrono::Duration::minutes(CONFIRMATION_TTL_MINUTES);
+
+        conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            DeleteConfirmationsRepo::insert(
+                tx,
+                &NewDeleteConfirmation {
+                    workspace_id: ctx.workspace_id,
+                    target_type: target_type.to_string(),
+                    target_id,
+                    target_name: target_name.to_string(),
+                    token_hash: Self::hash_token(&token),
+                    impact: impact_json.clone(),
+                    requested_by: Some(ctx.user_id),
+                    expires_at,
+                },
+            )?;
+            AuditLogRepo::insert(
+                tx,
+                &NewAuditEntry {
+                    workspace_id: ctx.workspace_id,
+                    actor_id: Some(ctx.user_id),
+                    action: Self::audit_action(target_type, false).to_string(),
+                    target_type: target_type.to_string(),
+                    target_id: Some(target_id),
+                    details: Some(impact_json.clone()),
+                },
+            )?;
+            Ok(())
+        })?;
+
+        Ok(DeleteConfirmation {
+            token,
+            target_type: target_type.to_string(),
+            target_id,
+            target_name: target_name.to_string(),
+            impact,
+            expires_at,
+        })
+    }
+
+    /// Spend a token on deleting the target. Call this inside the delete's
+    /// transaction so the token stays usable if the delete fails.
+    pub fn confirm(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        target_type: &str,
+        target_id: Uuid,
+        token: &str,
+    ) -> Result<DeleteConfirmationRecord, AppError> {
+        let record = DeleteConfirmationsRepo::find_pending_for_update(
+            conn,
+            &Self::hash_token(token.trim()),
+        )?
+        .ok_or_else(|| AppError::validation("Invalid or already used confirmation token"))?;
+        let now = clock::now();
+        Self::check(&record, ctx, target_type, target_id, now)?;
+        DeleteConfirmationsRepo::mark_confirmed(conn, record.id, now)?;
+
+        // A deleted workspace takes its audit log with it; the confirmation
+        // row is what remains of the deletion
+        if target_type != delete_targets::WORKSPACE {
+            AuditLogRepo::insert(
+                conn,
+                &NewAuditEntry {
+                    workspace_id: ctx.workspace_id,
+                    actor_id: Some(ctx.user_id),
+                    action: Self::audit_action(target_type, true).to_string(),
+                    target_type: target_type.to_string(),
+                    target_id: Some(target_id),
+                    details: Some(record.impact.clone()),
+                },
+            )?;
+        }
+        tracing::info!(
+            "{} {} ({}) deleted by {} in workspace {}",
+            target_type,
+            target_id,
+            record.target_name,
+            ctx.user_id,
+            ctx.workspace_id
+        );
+        Ok(record)
+    }
+
+    /// Whether the confirmation may be spent by this user on this target
+    pub fn check(
+        record: &DeleteConfirmationRecord,
+        ctx: &RequestContext,
+        target_type: &str,
+        target_id: Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), AppError> {
+        if record.target_type != target_type
+            || record.target_id != target_id
+            || record.workspace_id != ctx.workspace_id
+        {
+            return Err(AppError::validation(
+                "The confirmation token was issued for a different target",
+            ));
+        }
+        if record.requested_by != Some(ctx.user_id) {
+            return Err(AppError::forbidden(
+                "The confirmation token was issued to another user",
+            ));
+        }
+        if record.expires_at <= now {
+            return Err(AppError::conflict_with_code(
+                "The confirmation token has expired; request a new one",
+                None,
+                "DELETE_CONFIRMATION_EXPIRED",
+            ));
+        }
+        Ok(())
+    }
+
+    fn audit_action(target_type: &str, confirmed: bool) -> &'static str {
+        match (target_type, confirmed) {
+            (delete_targets::WORKSPACE, false) => audit_actions::WORKSPACE_DELETE_REQUESTED,
+            (delete_targets::WORKSPACE, true) => audit_actions::WORKSPACE_DELETED,
+            (_, false) => audit_actions::TEAM_DELETE_REQUESTED,
+            (_, true) => audit_actions::TEAM_DELETED,
+        }
+    }
+}
@@ -0,0 +1,475 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, MultiPart, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use redis::AsyncCommands;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    config::{EmailBackendConfig, EmailConfig, SesConfig, SmtpConfig},
+    db::models::email::{EmailMessage, QueuedEmail},
+    db::models::notification::DigestEmail,
+    error::AppError,
+    utils::clock,
+    utils::object_storage::hmac_sha256,
+};
+
+/// Redis list the worker sends queued emails from
+pub const EMAIL_QUEUE_KEY: &str = "email:outbox";
+/// Sends of one email are retried this many times before it is dropped
+pub const MAX_SEND_ATTEMPTS: u32 = 5;
+const SES_SEND_PATH: &str = "/v2/email/outbound-emails";
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Delivers a rendered email
+#[async_trait]
+pub trait EmailTransport: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), AppError>;
+}
+
+/// Logs emails instead of sending them; the default until a provider is configured
+pub struct LogTransport;
+
+#[async_trait]
+impl EmailTransport for LogTransport {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), AppError> {
+        tracing::info!(
+            "Email from {} to {}: {:?} ({} chars)",
+            from,
+            message.to,
+            message.subject,
+            message.text.len()
+        );
+        Ok(())
+    }
+}
+
+/// SMTP relay with STARTTLS, or implicit TLS when configured
+pub struct SmtpTransport {
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpTransport {
+    pub fn new(config: &SmtpConfig) -> Result<Self, AppError> {
+        let builder = if config.implicit_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+        }
+        .map_err(|e| AppError::Config(format!("Invalid SMTP host: {}", e)))?
+        .port(config.port)
+        .timeout(Some(REQUEST_TIMEOUT));
+        let builder = match (&config.username, &config.password) {
+            (Some(username), Some(password)) => {
+                builder.credentials(Credentials::new(username.clone(), password.clone()))
+            }
+            _ => builder,
+        };
+        Ok(Self {
+            mailer: builder.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl EmailTransport for SmtpTransport {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), AppError> {
+        let mime = EmailService::build_mime(from, message)?;
+        self.mailer
+            .send(mime)
+            .await
+            .map_err(|e| AppError::internal(format!("SMTP send failed: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Amazon SES v2 `SendEmail`, signed with SigV4 like the attachment storage
+pub struct SesTransport {
+    config: SesConfig,
+    http: reqwest::Client,
+}
+
+impl SesTransport {
+    pub fn new(config: &SesConfig) -> Result<Self, AppError> {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::internal(format!("Failed to build SES client: {}", e)))?;
+        Ok(Self {
+            config: config.clone(),
+            http,
+        })
+    }
+
+    pub fn host(&self) -> String {
+        format!("email.{}.amazonaws.com", self.config.region)
+    }
+
+    /// `SendEmail` request body
+    pub fn request_body(from: &str, message: &EmailMessage) -> serde_json::Value {
+        let mut body = json!({ "Text": { "Data": message.text, "Charset": "UTF-8" } });
+        if let Some(html) = &message.html {
+            body["Html"] = json!({ "Data": html, "Charset": "UTF-8" });
+        }
+        json!({
+            "FromEmailAddress": from,
+            "Destination": { "ToAddresses": [message.to] },
+            "ReplyToAddresses": message.reply_to.iter().collect::<Vec<_>>(),
+            "Content": {
+                "Simple": {
+                    "Subject": { "Data": message.subject, "Charset": "UTF-8" },
+                    "Body": body,
+                }
+            }
+        })
+    }
+
+    /// `Authorization` header for a JSON POST to the send endpoint
+    pub fn authorization(&self, body: &[u8], now: DateTime<Utc>) -> String {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/ses/aws4_request", date, self.config.region);
+        let signed_headers = "content-type;host;x-amz-date";
+        let canonical_request = format!(
+            "POST\n{}\n\ncontent-type:application/json\nhost:{}\nx-amz-date:{}\n\n{}\n{}",
+            SES_SEND_PATH,
+            self.host(),
+            amz_date,
+            signed_headers,
+            hex::encode(Sha256::digest(body))
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = [
+            date.as_str(),
+            self.config.region.as_str(),
+            "ses",
+            "aws4_request",
+        ]
+        .iter()
+        .fold(
+            format!("AWS4{}", self.config.secret_key).into_bytes(),
+            |key, part| hmac_sha256(&key, part.as_bytes()),
+        );
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.config.access_key,
+            scope,
+            signed_headers,
+            hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()))
+        )
+    }
+}
+
+#[async_trait]
+impl EmailTransport for SesTransport {
+    fn name(&self) -> &'static str {
+        "ses"
+    }
+
+    async fn send(&self, from: &str, message: &EmailMessage) -> Result<(), AppError> {
+        let body = serde_json::to_vec(&Self::request_body(from, message))
+            .map_err(|e| AppError::internal(e.to_string()))?;
+        let now = clock::now();
+        let response = self
+            .http
+            .post(format!("https://{}{}", self.host(), SES_SEND_PATH))
+            .header("Content-Type", "application/json")
+            .header("X-Amz-Date", now.format("%Y%m%dT%H%M%SZ").to_string())
+            .header("Authorization", self.authorization(&body, now))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("SES request failed: {}", e)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let detail = response.text().await.unwrap_or_default();
+            return Err(AppError::internal(format!(
+                "SES returned HTTP {}: {}",
+                status, detail
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Queues outgoing email for handlers and services. [`EmailService`] is the
+/// real implementation; tests use a recording mailer instead.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Web app URL that links in emails point to
+    fn app_url(&self) -> &str;
+
+    /// Platform sender, used when a message has no `from` of its own
+    fn platform_from(&self) -> &str;
+
+    /// Queue an email for the worker to send
+    async fn enqueue(&self, message: EmailMessage) -> Result<(), AppError>;
+
+    /// Queue an email, logging instead of failing the caller
+    async fn enqueue_quietly(&self, message: EmailMessage) {
+        let to = message.to.clone();
+        if let Err(e) = self.enqueue(message).await {
+            tracing::warn!("Failed to queue email to {}: {}", to, e);
+        }
+    }
+}
+
+/// Renders transactional emails and sends them through the configured
+/// transport. Requests enqueue emails in Redis; the worker sends them and
+/// retries failures.
+#[derive(Clone)]
+pub struct EmailService {
+    from: String,
+    app_url: String,
+    transport: Arc<dyn EmailTransport>,
+    redis: redis::Client,
+}
+
+impl EmailService {
+    pub fn new(config: &EmailConfig, redis: redis::Client) -> Result<Self, AppError> {
+        let transport: Arc<dyn EmailTransport> = match &config.backend {
+            EmailBackendConfig::Log => Arc::new(LogTransport),
+            EmailBackendConfig::Smtp(smtp) => Arc::new(SmtpTransport::new(smtp)?),
+            EmailBackendConfig::Ses(ses) => Arc::new(SesTransport::new(ses)?),
+        };
+        Ok(Self::with_transport(config, transport, redis))
+    }
+
+    pub fn with_transport(
+        config: &EmailConfig,
+        transport: Arc<dyn EmailTransport>,
+        redis: redis::Client,
+    ) -> Self {
+        Self {
+            from: config.from.clone(),
+            app_url: config.app_url.clone(),
+            transport,
+            redis,
+        }
+    }
+
+    pub fn transport_name(&self) -> &'static str {
+        self.transport.name()
+    }
+
+    /// Send right away, bypassing the queue
+    pub async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        let from = message.from.as_deref().unwrap_or(&self.from);
+        self.transport.send(from, message).await
+    }
+
+    async fn push(&self, queued: &QueuedEmail) -> Result<(), AppError> {
+        let payload =
+            serde_json::to_string(queued).map_err(|e| AppError::internal(e.to_string()))?;
+        let mut conn = self
+            .redis
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to get Redis connection: {}", e)))?;
+        let _: () = conn
+            .rpush(EMAIL_QUEUE_KEY, payload)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to queue email: {}", e)))?;
+        Ok(())
+    }
+
+    /// Send up to `max` queued emails; failed ones go back to the end of the
+    /// queue until they run out of attempts. Returns how many were sent.
+    pub async fn process_queue(&self, max: usize) -> Result<usize, AppError> {
+        let mut conn = self
+            .redis
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to get Redis connection: {}", e)))?;
+        let mut sent = 0;
+        for _ in 0..max {
+            let payload: Option<String> = conn
+                .lpop(EMAIL_QUEUE_KEY, None)
+                .await
+                .map_err(|e| AppError::internal(format!("Failed to read email queue: {}", e)))?;
+            let Some(payload) = payload else {
+                break;
+            };
+            let mut queued: QueuedEmail = match serde_json::from_str(&payload) {
+                Ok(queued) => queued,
+                Err(e) => {
+                    tracing::error!("Dropping malformed queued email: {}", e);
+                    continue;
+                }
+            };
+            match self.send(&queued.message).await {
+                Ok(()) => sent += 1,
+                Err(e) => {
+                    queued.attempts += 1;
+                    if queued.attempts >= MAX_SEND_ATTEMPTS {
+                        tracing::error!(
+                            "Giving up on email to {} after {} attempts: {}",
+                            queued.message.to,
+                            queued.attempts,
+                            e
+                        );
+                    } else {
+                        tracing::warn!("Email to {} failed, will retry: {}", queued.message.to, e);
+                        self.push(&queued).await?;
+                    }
+                }
+            }
+        }
+        Ok(sent)
+    }
+
+    /// MIME message with a plain-text part and, when present, an HTML alternative
+    pub fn build_mime(from: &str, message: &EmailMessage) -> Result<Message, AppError> {
+        let parse = |address: &str, field: &str| {
+            address
+                .parse::<Mailbox>()
+                .map_err(|e| AppError::validation(format!("Invalid {} address: {}", field, e)))
+        };
+        let mut builder = Message::builder()
+            .from(parse(from, "from")?)
+            .to(parse(&message.to, "to")?)
+            .subject(&message.subject);
+        if let Some(reply_to) = &message.reply_to {
+            builder = builder.reply_to(parse(reply_to, "reply-to")?);
+        }
+        let built = match &message.html {
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(
+                message.text.clone(),
+                html.clone(),
+            )),
+            None => builder
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.text.clone()),
+        };
+        built.map_err(|e| AppError::internal(format!("Failed to build email: {}", e)))
+    }
+
+    /// Invitation to join a workspace, linking to the invitation page
+    pub fn invitation_email(
+        app_url: &str,
+        to: &str,
+        inviter_name: &str,
+        workspace_name: &str,
+        invitation_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> EmailMessage {
+        let link = format!("{}/invitations/{}", app_url, invitation_id);
+        let expires = expires_at.format("%Y-%m-%d");
+        EmailMessage {
+            to: to.to_string(),
+            subject: format!("{} invited you to {}", inviter_name, workspace_name),
+            text: format!(
+                "{} invited you to join the {} workspace.\n\nAccept the invitation: {}\n\nThis invitation expires on {}.\n",
+                inviter_name, workspace_name, link, expires
+            ),
+            html: Some(format!(
+                "<p>{} invited you to join the <strong>{}</strong> workspace.</p>\n<p><a href=\"{}\">Accept the invitation</a></p>\n<p>This invitation expires on {}.</p>\n",
+                escape_html(inviter_name),
+                escape_html(workspace_name),
+                escape_html(&link),
+                expires
+            )),
+            reply_to: None,
+            from: None,
+        }
+    }
+
+    /// Password reset link; `token` is appended to the reset page URL
+    pub fn password_reset_email(
+        app_url: &str,
+        to: &str,
+        name: &str,
+        token: &str,
+        valid_minutes: i64,
+    ) -> EmailMessage {
+        let link = format!("{}/reset-password?token={}", app_url, token);
+        EmailMessage {
+            to: to.to_string(),
+            subject: "Reset your password".to_string(),
+            text: format!(
+                "Hi {},\n\nReset your password: {}\n\nThe link is valid for {} minutes. If you did not ask for a reset, you can ignore this email.\n",
+                name, link, valid_minutes
+            ),
+            html: Some(format!(
+                "<p>Hi {},</p>\n<p><a href=\"{}\">Reset your password</a></p>\n<p>The link is valid for {} minutes. If you did not ask for a reset, you can ignore this email.</p>\n",
+                escape_html(name),
+                escape_html(&link),
+                valid_minutes
+            )),
+            reply_to: None,
+            from: None,
+        }
+    }
+
+    /// Mention and activity digest rendered by the notifications service,
+    /// with an HTML version of the same text
+    pub fn digest_email(digest: DigestEmail) -> EmailMessage {
+        let html = digest
+            .text
+            .split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .map(|block| format!("<p>{}</p>", escape_html(block).replace('\n', "<br>\n")))
+            .collect::<Vec<_>>()
+            .join("\n");
+        EmailMessage {
+            html: Some(html),
+            ..EmailMessage::from(digest)
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for EmailService {
+    fn app_url(&self) -> &str {
+        &self.app_url
+    }
+
+    fn platform_from(&self) -> &str {
+        &self.from
+    }
+
+    async fn enqueue(&self, message: EmailMessage) -> Result<(), AppError> {
+        self.push(&QueuedEmail {
+            message,
+            attempts: 0,
+        })
+        .await
+    }
+}
+
+/// Escape text for use in HTML element content and quoted attributes
+pub fn escape_html(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
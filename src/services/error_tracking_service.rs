@@ -0,0 +1,492 @@
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::error_tracking::{
+        ErrorEvent, ErrorEventFrame, ErrorIngestResult, ErrorTrackingConfig,
+        ErrorTrackingIntegration, NewErrorGroup, NewErrorTrackingIntegration,
+        UpdateErrorTrackingIntegration, UpdateErrorTrackingRequest, error_levels,
+    },
+    db::models::issue::{Issue, NewIssue},
+    db::models::workflow::{WorkflowState, WorkflowStateCategory},
+    db::repositories::error_tracking::ErrorTrackingRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::issue_label_rules_service::IssueLabelRulesService,
+    services::oauth_service::OAuthService,
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    services::service_accounts_service::ServiceAccountsService,
+    services::webhook_service::WebhookService,
+    utils::clock,
+    websocket::{EntityAction, EntityKind, Topic},
+};
+
+/// Header carrying the workspace's ingest key
+pub const INGEST_KEY_HEADER: &str = "x-ingest-key";
+/// Header Sentry SDKs authenticate with, e.g.
+/// `Sentry sentry_version=7, sentry_key=<key>`
+pub const SENTRY_AUTH_HEADER: &str = "x-sentry-auth";
+
+const INGEST_KEY_PREFIX: &str = "errk_";
+
+/// Fingerprint part replaced by the key the server derives
+const DEFAULT_FINGERPRINT: &str = "{{ default }}";
+
+/// Longest issue title; longer error messages are cut
+const MAX_TITLE_CHARS: usize = 255;
+
+/// Stack frames written to the issue description
+const MAX_DESCRIPTION_FRAMES: usize = 20;
+
+pub struct ErrorTrackingService;
+
+impl ErrorTrackingService {
+    /// URL monitoring tools should post the workspace's error events to
+    pub fn ingest_url(base_url: &str, workspace_id: Uuid) -> String {
+        format!(
+            "{}/integrations/errors/events?workspace_id={}",
+            base_url.trim_end_matches('/'),
+            workspace_id
+        )
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        base_url: &str,
+    ) -> Result<ErrorTrackingConfig, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageIntegrations)?;
+        let integration = ErrorTrackingRepo::find_integration(conn, ctx.workspace_id)?
+            .ok_or_else(|| AppError::not_found("error_tracking_integration"))?;
+        Ok(Self::config(integration, base_url, None))
+    }
+
+    /// Create the integration or change its team and issue creator. The ingest key is
+    /// generated on creation and only returned then and on rotation.
+    pub fn configure(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        base_url: &str,
+        req: &UpdateErrorTrackingRequest,
+    ) -> Result<ErrorTrackingConfig, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageIntegrations)?;
+        if TeamsRepo::find_in_workspace(conn, ctx.workspace_id, req.team_id)?.is_none() {
+            return Err(AppError::not_found("team"));
+        }
+        if let Some(creator_id) = req.creator_id {
+            ServiceAccountsService::require_active(conn, ctx, creator_id)?;
+        }
+
+        let (integration, key) = match ErrorTrackingRepo::find_integration(conn, ctx.workspace_id)?
+        {
+            None => {
+                let key = OAuthService::random_secret(INGEST_KEY_PREFIX);
+                let integration = ErrorTrackingRepo::insert_integration(
+                    conn,
+                    &NewErrorTrackingIntegration {
+                        workspace_id: ctx.workspace_id,
+                        team_id: req.team_id,
+                        ingest_key: key.clone(),
+                        created_by: req.creator_id.unwrap_or(ctx.user_id),
+                    },
+                )?;
+                (integration, Some(key))
+            }
+            Some(_) => {
+                let key = req
+                    .rotate_key
+                    .then(|| OAuthService::random_secret(INGEST_KEY_PREFIX));
+                let integration = ErrorTrackingRepo::update_integration(
+                    conn,
+                    ctx.workspace_id,
+                    &UpdateErrorTrackingIntegration {
+                        team_id: Some(req.team_id),
+                        ingest_key: key.clone(),
+                        created_by: req.creator_id,
+                        updated_at: Some(clock::now()),
+                    },
+                )?;
+                (integration, key)
+            }
+        };
+        Ok(Self::config(integration, base_url, key))
+    }
+
+    /// Remove the integration; issues already filed and their groups stay
+    pub fn remove(conn: &mut PgConnection, ctx: &RequestContext) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageIntegrations)?;
+        if ErrorTrackingRepo::delete_integration(conn, ctx.workspace_id)? == 0 {
+            return Err(AppError::not_found("error_tracking_integration"));
+        }
+        Ok(())
+    }
+
+    /// `sentry_key` of an `X-Sentry-Auth` header
+    pub fn sentry_key(header: &str) -> Option<&str> {
+        let header = header.trim();
+        let fields = header.strip_prefix("Sentry ").unwrap_or(header);
+        fields
+            .split(',')
+            .filter_map(|field| field.trim().split_once('='))
+            .find(|(name, _)| *name == "sentry_key")
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Known level of the event; unknown or missing levels count as errors
+    pub fn normalize_level(level: Option<&str>) -> &'static str {
+        match level.map(|l| l.trim().to_ascii_lowercase()).as_deref() {
+            Some("fatal" | "critical") => error_levels::FATAL,
+            Some("warning" | "warn") => error_levels::WARNING,
+            Some("info" | "log") => error_levels::INFO,
+            Some("debug") => error_levels::DEBUG,
+            _ => error_levels::ERROR,
+        }
+    }
+
+    /// Priority of the issue filed for an error of this level
+    pub fn priority_for_level(level: &str) -> &'static str {
+        match level {
+            error_levels::FATAL => "urgent",
+            error_levels::ERROR => "high",
+            error_levels::WARNING => "medium",
+            _ => "low",
+        }
+    }
+
+    /// Issue title for the event: `Type: message` of the raised exception,
+    /// or the first line of the log message
+    pub fn title(event: &ErrorEvent) -> Option<String> {
+        let title = match event.exception.as_ref().and_then(|e| e.values.last()) {
+            Some(exception) => {
+                let kind = exception.exception_type.as_deref().map(str::trim);
+                let value = exception
+                    .value
+                    .as_deref()
+                    .and_then(|v| v.lines().next())
+                    .map(str::trim);
+                match (kind, value) {
+                    (Some(kind), Some(value)) if !kind.is_empty() && !value.is_empty() => {
+                        format!("{}: {}", kind, value)
+                    }
+                    (Some(kind), _) if !kind.is_empty() => kind.to_string(),
+                    (_, Some(value)) => value.to_string(),
+                    _ => String::new(),
+                }
+            }
+            None => String::new(),
+        };
+        let title = if title.is_empty() {
+            event
+                .message
+                .as_deref()
+                .and_then(|m| m.lines().map(str::trim).find(|line| !line.is_empty()))?
+                .to_string()
+        } else {
+            title
+        };
+        Some(title.chars().take(MAX_TITLE_CHARS).collect())
+    }
+
+    /// Grouping key of the event, hashed. Without a client fingerprint,
+    /// exceptions group by type and the functions on their stack, so the same
+    /// error groups together across messages and line numbers; exceptions
+    /// without a stack group by type and message, and plain messages by text.
+    pub fn fingerprint(event: &ErrorEvent) -> Option<String> {
+        let default_key = Self::default_grouping_key(event);
+        let key = match &event.fingerprint {
+            Some(parts) if !parts.is_empty() => {
+                let mut resolved = Vec::with_capacity(parts.len());
+                for part in parts {
+                    if part == DEFAULT_FINGERPRINT {
+                        resolved.push(default_key.clone()?);
+                    } else {
+                        resolved.push(part.clone());
+                    }
+                }
+                resolved.join("\n")
+            }
+            _ => default_key?,
+        };
+        Some(hex::encode(Sha256::digest(key.as_bytes())))
+    }
+
+    fn default_grouping_key(event: &ErrorEvent) -> Option<String> {
+        if let Some(exception) = event.exception.as_ref().and_then(|e| e.values.last()) {
+            let kind = exception.exception_type.as_deref().unwrap_or_default();
+            let frames = Self::grouping_frames(exception.stacktrace.as_ref().map(|s| &s.frames));
+            if !frames.is_empty() {
+                let frames: Vec<String> = frames
+                    .iter()
+                    .map(|frame| {
+                        format!(
+                            "{}@{}",
+                            frame.function.as_deref().unwrap_or("?"),
+                            frame.filename.as_deref().unwrap_or("?")
+                        )
+                    })
+                    .collect();
+                return Some(format!("exception:{}\n{}", kind, frames.join("\n")));
+            }
+            let value = exception.value.as_deref().unwrap_or_default().trim();
+            if !kind.is_empty() || !value.is_empty() {
+                return Some(format!("exception:{}\n{}", kind, value));
+            }
+        }
+        event
+            .message
+            .as_deref()
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .map(|message| format!("message:{}", message))
+    }
+
+    /// In-app frames when the stack marks any, all frames otherwise
+    fn grouping_frames(frames: Option<&Vec<ErrorEventFrame>>) -> Vec<&ErrorEventFrame> {
+        let frames = frames.map(Vec::as_slice).unwrap_or_default();
+        let in_app: Vec<&ErrorEventFrame> = frames
+            .iter()
+            .filter(|frame| frame.in_app == Some(true))
+            .collect();
+        if in_app.is_empty() {
+            frames.iter().collect()
+        } else {
+            in_app
+        }
+    }
+
+    /// Workflow state new and reopened error issues go to: the default state
+    /// unless it is a closed one, else the first triage, unstarted or backlog
+    /// state
+    pub fn open_state(states: &[WorkflowState]) -> Option<&WorkflowState> {
+        let is_open = |state: &&WorkflowState| {
+            !matches!(
+                state.category,
+                WorkflowStateCategory::Completed | WorkflowStateCategory::Canceled
+            )
+        };
+        states
+            .iter()
+            .filter(is_open)
+            .find(|state| state.is_default)
+            .or_else(|| {
+                [
+                    WorkflowStateCategory::Triage,
+                    WorkflowStateCategory::Unstarted,
+                    WorkflowStateCategory::Backlog,
+                ]
+                .iter()
+                .find_map(|category| states.iter().find(|state| state.category == *category))
+            })
+    }
+
+    /// Whether the issue tracking an error was resolved: completed, canceled
+    /// or archived
+    pub fn is_resolved(issue: &Issue, states: &[WorkflowState]) -> bool {
+        issue.archived_at.is_some()
+            || issue.workflow_state_id.is_some_and(|state_id| {
+                states.iter().any(|state| {
+                    state.id == state_id
+                        && matches!(
+                            state.category,
+                            WorkflowStateCategory::Completed | WorkflowStateCategory::Canceled
+                        )
+                })
+            })
+    }
+
+    /// Record one error event for the workspace: open an issue for a new
+    /// error, or count another occurrence of a known one and reopen its issue
+    /// if it was resolved. A repeated delivery of the last event is not
+    /// counted again.
+    pub fn ingest(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        ingest_key: Option<&str>,
+        event: &ErrorEvent,
+    ) -> Result<ErrorIngestResult, AppError> {
+        let integration = ErrorTrackingRepo::find_integration(conn, workspace_id)?
+            .ok_or_else(|| AppError::not_found("error_tracking_integration"))?;
+        if ingest_key != Some(integration.ingest_key.as_str()) {
+            return Err(AppError::auth("Invalid ingest key"));
+        }
+        let fingerprint = Self::fingerprint(event)
+            .ok_or_else(|| AppError::validation("Event has no exception or message"))?;
+        let title = Self::title(event).unwrap_or_else(|| "Unknown error".to_string());
+        let level = Self::normalize_level(event.level.as_deref());
+        let event_id = event
+            .event_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty());
+
+        let (result, issue) = conn.transaction::<_, AppError, _>(|conn| {
+            let integration = ErrorTrackingRepo::lock_integration(conn, workspace_id)?
+                .ok_or_else(|| AppError::not_found("error_tracking_integration"))?;
+
+            let Some(group) = ErrorTrackingRepo::find_group(conn, workspace_id, &fingerprint)?
+            else {
+                let states = WorkflowsRepo::list_states_by_team(conn, integration.team_id)?;
+                let state = Self::open_state(&states);
+                let issue = IssueRepo::insert(
+                    conn,
+                    &NewIssue {
+                        project_id: None,
+                        cycle_id: None,
+                        creator_id: integration.created_by,
+                        assignee_id: None,
+                        parent_issue_id: None,
+                        title: title.clone(),
+                        description: Some(Self::description(event, level)),
+                        priority: Some(Self::priority_for_level(level).to_string()),
+                        is_changelog_candidate: Some(false),
+                        team_id: integration.team_id,
+                        workflow_id: state.map(|s| s.workflow_id),
+                        workflow_state_id: state.map(|s| s.id),
+                        estimate: None,
+                    },
+                )?;
+                let issue = IssueLabelRulesService::apply_quietly(conn, issue);
+                let group = ErrorTrackingRepo::insert_group(
+                    conn,
+                    &NewErrorGroup {
+                        workspace_id,
+                        fingerprint: fingerprint.clone(),
+                        issue_id: issue.id,
+                        title: title.clone(),
+                        level: level.to_string(),
+                        last_event_id: event_id.map(str::to_string),
+                    },
+                )?;
+                let result = ErrorIngestResult {
+                    group,
+                    created: true,
+                    reopened: false,
+                };
+                return Ok((result, Some(issue)));
+            };
+
+            if event_id.is_some() && group.last_event_id.as_deref() == event_id {
+                let result = ErrorIngestResult {
+                    group,
+                    created: false,
+                    reopened: false,
+                };
+                return Ok((result, None));
+            }
+
+            let issue = IssueRepo::find_by_id(conn, group.issue_id)?
+                .ok_or_else(|| AppError::not_found("issue"))?;
+            let states: Vec<WorkflowState> =
+                WorkflowsRepo::list_states_by_team(conn, issue.team_id)?
+                    .into_iter()
+                    .filter(|state| issue.workflow_id.is_none_or(|w| w == state.workflow_id))
+                    .collect();
+            let reopened = match Self::open_state(&states) {
+                Some(state) if Self::is_resolved(&issue, &states) => {
+                    Some(ErrorTrackingRepo::reopen_issue(conn, issue.id, state.id)?)
+                }
+                _ => None,
+            };
+            let group =
+                ErrorTrackingRepo::record_occurrence(conn, group.id, event_id, reopened.is_some())?;
+            let result = ErrorIngestResult {
+                group,
+                created: false,
+                reopened: reopened.is_some(),
+            };
+            Ok((result, reopened))
+        })?;
+
+        if let Some(issue) = issue {
+            let (event, action) = if result.created {
+                (webhook_events::ISSUE_CREATED, EntityAction::Created)
+            } else {
+                (webhook_events::ISSUE_UPDATED, EntityAction::Updated)
+            };
+            WebhookService::emit_quietly(conn, workspace_id, event, &issue);
+            RealtimeService::publish(workspace_id, Topic::Issue(issue.id), event, &issue);
+            RealtimeService::entity_changed(
+                workspace_id,
+                EntityKind::Issue,
+                action,
+                issue.id,
+                &issue,
+            );
+        }
+        Ok(result)
+    }
+
+    /// Issue description with what the first event said about the error
+    pub fn description(event: &ErrorEvent, level: &str) -> String {
+        let mut lines = vec![format!("Level: {}", level)];
+        for (label, value) in [
+            ("Culprit", &event.culprit),
+            ("Environment", &event.environment),
+            ("Release", &event.release),
+            ("Platform", &event.platform),
+            ("Event", &event.event_id),
+        ] {
+            if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                lines.push(format!("{}: {}", label, value));
+            }
+        }
+        if let Some(message) = event.message.as_deref().map(str::trim)
+            && !message.is_empty()
+        {
+            lines.push(String::new());
+            lines.push(message.to_string());
+        }
+
+        let frames = event
+            .exception
+            .as_ref()
+            .and_then(|e| e.values.last())
+            .and_then(|e| e.stacktrace.as_ref())
+            .map(|s| s.frames.as_slice())
+            .unwrap_or_default();
+        if !frames.is_empty() {
+            lines.push(String::new());
+            lines.push("Stack trace (most recent call first):".to_string());
+            lines.push("```".to_string());
+            for frame in frames.iter().rev().take(MAX_DESCRIPTION_FRAMES) {
+                let location = match (frame.filename.as_deref(), frame.lineno) {
+                    (Some(file), Some(line)) => format!("{}:{}", file, line),
+                    (Some(file), None) => file.to_string(),
+                    _ => "?".to_string(),
+                };
+                lines.push(format!(
+                    "  at {} ({})",
+                    frame.function.as_deref().unwrap_or("?"),
+                    location
+                ));
+            }
+            if frames.len() > MAX_DESCRIPTION_FRAMES {
+                lines.push(format!(
+                    "  ... {} more",
+                    frames.len() - MAX_DESCRIPTION_FRAMES
+                ));
+            }
+            lines.push("```".to_string());
+        }
+        lines.join("\n")
+    }
+
+    fn config(
+        integration: ErrorTrackingIntegration,
+        base_url: &str,
+        key: Option<String>,
+    ) -> ErrorTrackingConfig {
+        ErrorTrackingConfig {
+            ingest_url: Self::ingest_url(base_url, integration.workspace_id),
+            integration,
+            ingest_key: key,
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::external_reference::{
+        CreateExternalReferenceRequest, ExternalReference, ExternalReferenceMatch,
+        ExternalReferenceQuery, NewExternalReference,
+    },
+    db::repositories::external_references::ExternalReferencesRepo,
+    db::repositories::issues::IssueRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::issues_service::IssuesService,
+    services::realtime_service::RealtimeService,
+    services::webhook_service::WebhookService,
+    websocket::Topic,
+};
+
+/// Realtime events published to the issue
+pub const REFERENCE_ADDED: &str = "issue.external_reference_added";
+pub const REFERENCE_REMOVED: &str = "issue.external_reference_removed";
+
+/// Longest source name, e.g. `zendesk`
+pub const MAX_SOURCE_LENGTH: usize = 32;
+pub const MAX_EXTERNAL_ID_LENGTH: usize = 255;
+pub const MAX_URL_LENGTH: usize = 2048;
+
+/// Links between issues and records in other systems. An external record
+/// points at one issue per workspace, so integrations can find the issue
+/// again from their own id; issue webhooks carry the references as well.
+pub struct ExternalReferencesService;
+
+impl ExternalReferencesService {
+    /// Lower-case source name of letters, digits, `-` and `_`
+    pub fn normalize_source(source: &str) -> Result<String, AppError> {
+        let source = source.trim().to_ascii_lowercase();
+        if source.is_empty()
+            || source.len() > MAX_SOURCE_LENGTH
+            || !source
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(AppError::validation(format!(
+                "Source must be 1-{} letters, digits, '-' or '_'",
+                MAX_SOURCE_LENGTH
+            )));
+        }
+        Ok(source)
+    }
+
+    pub fn normalize_external_id(external_id: &str) -> Result<String, AppError> {
+        let external_id = external_id.trim();
+        if external_id.is_empty() || external_id.chars().count() > MAX_EXTERNAL_ID_LENGTH {
+            return Err(AppError::validation(format!(
+                "External id must be 1-{} characters",
+                MAX_EXTERNAL_ID_LENGTH
+            )));
+        }
+        Ok(external_id.to_string())
+    }
+
+    /// Links must be absolute http(s) URLs
+    pub fn validate_url(url: &str) -> Result<(), AppError> {
+        if url.len() > MAX_URL_LENGTH {
+            return Err(AppError::validation(format!(
+                "URL must be at most {} characters",
+                MAX_URL_LENGTH
+            )));
+        }
+        let parsed = url::Url::parse(url)
+            .map_err(|_| AppError::validation(format!("Invalid URL: {}", url)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(AppError::validation("URL must use http or https"));
+        }
+        Ok(())
+    }
+
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<Vec<ExternalReference>, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        Ok(ExternalReferencesRepo::list_by_issue(conn, issue_id)?)
+    }
+
+    /// Link the issue to an external record. Linking the same record again
+    /// returns the existing reference; a record already linked to another
+    /// issue is a conflict.
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        req: &CreateExternalReferenceRequest,
+    ) -> Result<ExternalReference, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let source = Self::normalize_source(&req.source)?;
+        let external_id = Self::normalize_external_id(&req.external_id)?;
+        let url = req
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| Self::validate_url(url).map(|_| url.to_string()))
+            .transpose()?;
+
+        let reference = conn.transaction::<_, AppError, _>(|conn| {
+            if let Some(existing) = ExternalReferencesRepo::find_by_external_id(
+                conn,
+                ctx.workspace_id,
+                Some(&source),
+                &external_id,
+            )?
+            .into_iter()
+            .next()
+            {
+                if existing.issue_id == issue_id {
+                    return Ok(None);
+                }
+                return Err(AppError::conflict_with_code(
+                    format!(
+                        "{} {} is already linked to another issue",
+                        source, external_id
+                    ),
+                    Some("external_id".to_string()),
+                    "EXTERNAL_REFERENCE_EXISTS",
+                ));
+            }
+            Ok(Some(ExternalReferencesRepo::insert(
+                conn,
+                &NewExternalReference {
+                    workspace_id: ctx.workspace_id,
+                    issue_id,
+                    source: source.clone(),
+                    external_id: external_id.clone(),
+                    url,
+                    created_by: ctx.user_id,
+                },
+            )?))
+        })?;
+
+        match reference {
+            Some(reference) => {
+                Self::changed(conn, ctx, &reference, REFERENCE_ADDED);
+                Ok(reference)
+            }
+            None => ExternalReferencesRepo::find_by_external_id(
+                conn,
+                ctx.workspace_id,
+                Some(&source),
+                &external_id,
+            )?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::not_found("external reference")),
+        }
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        reference_id: Uuid,
+    ) -> Result<(), AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let reference = ExternalReferencesRepo::find_in_issue(conn, issue_id, reference_id)?
+            .ok_or_else(|| AppError::not_found("external reference"))?;
+        ExternalReferencesRepo::delete(conn, reference.id)?;
+        Self::changed(conn, ctx, &reference, REFERENCE_REMOVED);
+        Ok(())
+    }
+
+    /// Issues linked to an external id, with the matching references
+    pub fn search(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        query: &ExternalReferenceQuery,
+    ) -> Result<Vec<ExternalReferenceMatch>, AppError> {
+        let external_id = Self::normalize_external_id(&query.external_id)?;
+        let source = query
+            .source
+            .as_deref()
+            .map(Self::normalize_source)
+            .transpose()?;
+        let references = ExternalReferencesRepo::find_by_external_id(
+            conn,
+            ctx.workspace_id,
+            source.as_deref(),
+            &external_id,
+        )?;
+        if references.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<Uuid> = references.iter().map(|r| r.issue_id).collect();
+        let issues = IssueRepo::list_by_ids(conn, &ids)?;
+        let responses = IssuesService::to_responses(conn, ctx, issues)?;
+        Ok(references
+            .into_iter()
+            .filter_map(|reference| {
+                let issue = responses
+                    .iter()
+                    .find(|issue| issue.id == reference.issue_id)?
+                    .clone();
+                Some(ExternalReferenceMatch { reference, issue })
+            })
+            .collect())
+    }
+
+    /// Tell realtime subscribers about the reference, and webhooks that the
+    /// issue changed; issue webhooks carry the current references
+    fn changed(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        reference: &ExternalReference,
+        event: &str,
+    ) {
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Issue(reference.issue_id),
+            event,
+            reference,
+        );
+        match IssueRepo::find_by_id(conn, reference.issue_id) {
+            Ok(Some(issue)) => WebhookService::emit_quietly(
+                conn,
+                ctx.workspace_id,
+                webhook_events::ISSUE_UPDATED,
+                &issue,
+            ),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Failed to load issue {} for webhooks: {}",
+                reference.issue_id,
+                e
+            ),
+        }
+    }
+}
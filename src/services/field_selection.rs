@@ -0,0 +1,78 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::AppError;
+
+/// Sparse fieldset of a list response: the top-level fields a client asked
+/// for with `?fields=` (or `fields` on a WebSocket query), `id` included.
+/// The default selection keeps every field.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSelection {
+    fields: Option<Vec<String>>,
+}
+
+impl FieldSelection {
+    /// Parse a comma-separated `fields` query parameter
+    pub fn from_query(raw: Option<&str>, allowed: &[&str]) -> Result<Self, AppError> {
+        match raw {
+            Some(raw) => Self::from_names(raw.split(','), allowed),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Check the requested names against the fields the DTO has; a request
+    /// naming no field at all keeps every field
+    pub fn from_names<'a>(
+        names: impl IntoIterator<Item = &'a str>,
+        allowed: &[&str],
+    ) -> Result<Self, AppError> {
+        let mut fields = vec!["id".to_string()];
+        let mut named = false;
+        for name in names
+            .into_iter()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            if !allowed.contains(&name) {
+                return Err(AppError::validation(format!(
+                    "Unknown field '{}'; expected some of: {}",
+                    name,
+                    allowed.join(", ")
+                )));
+            }
+            named = true;
+            if !fields.iter().any(|field| field == name) {
+                fields.push(name.to_string());
+            }
+        }
+        Ok(Self {
+            fields: named.then_some(fields),
+        })
+    }
+
+    pub fn is_all(&self) -> bool {
+        self.fields.is_none()
+    }
+
+    pub fn includes(&self, field: &str) -> bool {
+        self.fields
+            .as_ref()
+            .is_none_or(|fields| fields.iter().any(|f| f == field))
+    }
+
+    /// Serialize `items`, keeping only the selected fields of each
+    pub fn project<T: Serialize>(&self, items: &[T]) -> Result<Value, AppError> {
+        let mut value = serde_json::to_value(items)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize payload: {}", e)))?;
+        if !self.is_all()
+            && let Value::Array(items) = &mut value
+        {
+            for item in items {
+                if let Value::Object(map) = item {
+                    map.retain(|key, _| self.includes(key));
+                }
+            }
+        }
+        Ok(value)
+    }
+}
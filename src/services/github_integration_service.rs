@@ -0,0 +1,389 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::github_integration::{
+        GithubIntegration, GithubIntegrationConfig, GithubPullRequestEvent, GithubPushEvent,
+        GithubWebhookResult, NewGithubIntegration, UpdateGithubIntegration,
+        UpdateGithubIntegrationRequest,
+    },
+    db::models::issue::Issue,
+    db::models::issue_link::{IssueLink, NewIssueLink, link_kinds, link_providers},
+    db::repositories::github_integrations::GithubIntegrationsRepo,
+    db::repositories::issue_links::IssueLinksRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::oauth_service::{OAuthService, WEBHOOK_SECRET_PREFIX},
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    services::webhook_service::WebhookService,
+    utils::clock,
+    websocket::{EntityAction, EntityKind, Topic},
+};
+
+/// Header GitHub puts the `sha256=<hex hmac>` of the body in
+pub const GITHUB_SIGNATURE_HEADER: &str = "x-hub-signature-256";
+/// Header naming the GitHub event type
+pub const GITHUB_EVENT_HEADER: &str = "x-github-event";
+
+pub mod github_events {
+    pub const PING: &str = "ping";
+    pub const PUSH: &str = "push";
+    pub const PULL_REQUEST: &str = "pull_request";
+}
+
+pub struct GithubIntegrationService;
+
+impl GithubIntegrationService {
+    /// Payload URL GitHub should POST to for the workspace
+    pub fn webhook_url(base_url: &str, workspace_id: Uuid) -> String {
+        format!(
+            "{}/integrations/github/webhook?workspace_id={}",
+            base_url.trim_end_matches('/'),
+            workspace_id
+        )
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        base_url: &str,
+    ) -> Result<GithubIntegrationConfig, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageIntegrations)?;
+        let integration = GithubIntegrationsRepo::find(conn, ctx.workspace_id)?
+            .ok_or_else(|| AppError::not_found("github_integration"))?;
+        Ok(Self::config(integration, base_url, None))
+    }
+
+    /// Create the integration or change its merge state. The webhook secret
+    /// is generated on creation and only returned then and on rotation.
+    pub fn configure(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        base_url: &str,
+        req: &UpdateGithubIntegrationRequest,
+    ) -> Result<GithubIntegrationConfig, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageIntegrations)?;
+        let merge_state_name = req
+            .merge_state_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        if let Some(name) = &merge_state_name {
+            Self::ensure_state_exists(conn, ctx.workspace_id, name)?;
+        }
+
+        let (integration, secret) = match GithubIntegrationsRepo::find(conn, ctx.workspace_id)? {
+            None => {
+                let secret = OAuthService::random_secret(WEBHOOK_SECRET_PREFIX);
+                let integration = GithubIntegrationsRepo::insert(
+                    conn,
+                    &NewGithubIntegration {
+                        workspace_id: ctx.workspace_id,
+                        webhook_secret: secret.clone(),
+                        merge_state_name,
+                        created_by: Some(ctx.user_id),
+                    },
+                )?;
+                (integration, Some(secret))
+            }
+            Some(_) => {
+                let secret = req
+                    .rotate_secret
+                    .then(|| OAuthService::random_secret(WEBHOOK_SECRET_PREFIX));
+                let integration = GithubIntegrationsRepo::update(
+                    conn,
+                    ctx.workspace_id,
+                    &UpdateGithubIntegration {
+                        webhook_secret: secret.clone(),
+                        merge_state_name: Some(merge_state_name),
+                        updated_at: Some(clock::now()),
+                    },
+                )?;
+                (integration, secret)
+            }
+        };
+        Ok(Self::config(integration, base_url, secret))
+    }
+
+    pub fn remove(conn: &mut PgConnection, ctx: &RequestContext) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageIntegrations)?;
+        if GithubIntegrationsRepo::delete(conn, ctx.workspace_id)? == 0 {
+            return Err(AppError::not_found("github_integration"));
+        }
+        Ok(())
+    }
+
+    /// Commits and pull requests linked to the issue, newest first
+    pub fn list_issue_links(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueLink>, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        Ok(IssueLinksRepo::list_by_issue(conn, issue_id)?)
+    }
+
+    /// Issue keys of the given teams mentioned in `text`, e.g. `ENG-123`,
+    /// as (index into `team_keys`, issue number). Keys match case-insensitively
+    /// and must not be glued to a preceding letter or digit, so branch names
+    /// like `eng-123-fix-login` match but `BENG-123` does not match `ENG`.
+    pub fn find_issue_keys(text: &str, team_keys: &[&str]) -> Vec<(usize, i32)> {
+        let lower = text.to_ascii_lowercase();
+        let bytes = lower.as_bytes();
+        let mut found: Vec<(usize, i32)> = Vec::new();
+        for (team, key) in team_keys.iter().enumerate() {
+            let needle = format!("{}-", key.to_ascii_lowercase());
+            if needle.len() < 2 {
+                continue;
+            }
+            for (start, _) in lower.match_indices(&needle) {
+                if start > 0 && bytes[start - 1].is_ascii_alphanumeric() {
+                    continue;
+                }
+                let digits_start = start + needle.len();
+                let digits_end = bytes[digits_start..]
+                    .iter()
+                    .position(|b| !b.is_ascii_digit())
+                    .map_or(bytes.len(), |len| digits_start + len);
+                if let Ok(number) = lower[digits_start..digits_end].parse::<i32>()
+                    && !found.contains(&(team, number))
+                {
+                    found.push((team, number));
+                }
+            }
+        }
+        found
+    }
+
+    /// Verify and process one GitHub webhook call for the workspace
+    pub fn handle_webhook(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        event: &str,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<GithubWebhookResult, AppError> {
+        let integration = GithubIntegrationsRepo::find(conn, workspace_id)?
+            .ok_or_else(|| AppError::not_found("github_integration"))?;
+        let body = std::str::from_utf8(body)
+            .map_err(|_| AppError::validation("Webhook body is not valid UTF-8"))?;
+        if !signature.is_some_and(|signature| {
+            WebhookService::verify(&integration.webhook_secret, body, signature)
+        }) {
+            return Err(AppError::auth("Invalid GitHub webhook signature"));
+        }
+
+        let mut result = GithubWebhookResult {
+            event: event.to_string(),
+            ..Default::default()
+        };
+        match event {
+            github_events::PING => {}
+            github_events::PUSH => {
+                let push: GithubPushEvent = Self::parse(body)?;
+                result.linked = Self::link_commits(conn, workspace_id, &push)?;
+            }
+            github_events::PULL_REQUEST => {
+                let pull: GithubPullRequestEvent = Self::parse(body)?;
+                let (linked, transitioned) = Self::link_pull_request(conn, &integration, &pull)?;
+                result.linked = linked;
+                result.transitioned = transitioned;
+            }
+            _ => result.ignored = true,
+        }
+        Ok(result)
+    }
+
+    fn parse<T: serde::de::DeserializeOwned>(body: &str) -> Result<T, AppError> {
+        serde_json::from_str(body)
+            .map_err(|e| AppError::validation(format!("Invalid GitHub payload: {}", e)))
+    }
+
+    fn link_commits(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        push: &GithubPushEvent,
+    ) -> Result<usize, AppError> {
+        let teams = IssueLinksRepo::team_keys(conn, workspace_id)?;
+        let mut linked = 0;
+        for commit in &push.commits {
+            let title = commit
+                .message
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            for issue in Self::mentioned_issues(conn, &teams, &commit.message)? {
+                IssueLinksRepo::upsert(
+                    conn,
+                    &NewIssueLink {
+                        issue_id: issue.id,
+                        provider: link_providers::GITHUB.to_string(),
+                        kind: link_kinds::COMMIT.to_string(),
+                        external_id: commit.id.clone(),
+                        url: commit.url.clone(),
+                        title: title.clone(),
+                        state: None,
+                        author: Some(
+                            commit
+                                .author
+                                .username
+                                .clone()
+                                .unwrap_or_else(|| commit.author.name.clone()),
+                        ),
+                    },
+                )?;
+                linked += 1;
+            }
+        }
+        Ok(linked)
+    }
+
+    /// Link the issues a pull request's title or branch mentions, and move
+    /// them to the merge state when it has just been merged
+    fn link_pull_request(
+        conn: &mut PgConnection,
+        integration: &GithubIntegration,
+        event: &GithubPullRequestEvent,
+    ) -> Result<(usize, usize), AppError> {
+        let pull = &event.pull_request;
+        let teams = IssueLinksRepo::team_keys(conn, integration.workspace_id)?;
+        let text = format!("{}\n{}", pull.title, pull.head.ref_name);
+        let issues = Self::mentioned_issues(conn, &teams, &text)?;
+        let merged = event.action == "closed" && pull.merged;
+        let state = if pull.merged {
+            "merged"
+        } else {
+            pull.state.as_str()
+        };
+
+        let mut transitioned = 0;
+        for issue in &issues {
+            IssueLinksRepo::upsert(
+                conn,
+                &NewIssueLink {
+                    issue_id: issue.id,
+                    provider: link_providers::GITHUB.to_string(),
+                    kind: link_kinds::PULL_REQUEST.to_string(),
+                    external_id: format!("{}#{}", event.repository.full_name, pull.number),
+                    url: pull.html_url.clone(),
+                    title: pull.title.clone(),
+                    state: Some(state.to_string()),
+                    author: Some(pull.user.login.clone()),
+                },
+            )?;
+            if let (true, Some(name)) = (merged, integration.merge_state_name.as_deref())
+                && Self::transition(conn, integration.workspace_id, issue, name)?
+            {
+                transitioned += 1;
+            }
+        }
+        Ok((issues.len(), transitioned))
+    }
+
+    fn mentioned_issues(
+        conn: &mut PgConnection,
+        teams: &[(Uuid, String)],
+        text: &str,
+    ) -> Result<Vec<Issue>, AppError> {
+        let keys: Vec<&str> = teams.iter().map(|(_, key)| key.as_str()).collect();
+        let mut issues = Vec::new();
+        for (team, number) in Self::find_issue_keys(text, &keys) {
+            if let Some(issue) = IssueLinksRepo::find_issue(conn, teams[team].0, number)? {
+                issues.push(issue);
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Move the issue to the state named `state_name` in its workflow. Returns
+    /// false when it is already there or its team has no such state.
+    fn transition(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        issue: &Issue,
+        state_name: &str,
+    ) -> Result<bool, AppError> {
+        let Some(state) = WorkflowsRepo::list_states_by_team(conn, issue.team_id)?
+            .into_iter()
+            .find(|state| {
+                state.name.eq_ignore_ascii_case(state_name)
+                    && issue.workflow_id.is_none_or(|w| w == state.workflow_id)
+            })
+        else {
+            return Ok(false);
+        };
+        if issue.workflow_state_id == Some(state.id) {
+            return Ok(false);
+        }
+        let updated = IssueRepo::update_fields(
+            conn,
+            issue.id,
+            (
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(state.id),
+                None,
+            ),
+        )?;
+        WebhookService::emit_quietly(conn, workspace_id, webhook_events::ISSUE_UPDATED, &updated);
+        RealtimeService::publish(
+            workspace_id,
+            Topic::Issue(updated.id),
+            webhook_events::ISSUE_UPDATED,
+            &updated,
+        );
+        RealtimeService::entity_changed(
+            workspace_id,
+            EntityKind::Issue,
+            EntityAction::Updated,
+            updated.id,
+            &updated,
+        );
+        Ok(true)
+    }
+
+    /// Fail unless some team of the workspace has a state with this name
+    fn ensure_state_exists(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        name: &str,
+    ) -> Result<(), AppError> {
+        for (team_id, _) in IssueLinksRepo::team_keys(conn, workspace_id)? {
+            if WorkflowsRepo::list_states_by_team(conn, team_id)?
+                .iter()
+                .any(|state| state.name.eq_ignore_ascii_case(name))
+            {
+                return Ok(());
+            }
+        }
+        Err(AppError::validation(format!(
+            "No workflow in this workspace has a state named {}",
+            name
+        )))
+    }
+
+    fn config(
+        integration: GithubIntegration,
+        base_url: &str,
+        secret: Option<String>,
+    ) -> GithubIntegrationConfig {
+        GithubIntegrationConfig {
+            webhook_url: Self::webhook_url(base_url, integration.workspace_id),
+            integration,
+            webhook_secret: secret,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use diesel::prelude::*;
+
+use crate::{
+    db::models::holiday::{Holiday, HolidayImportResult, NewHoliday, UpdateHoliday},
+    db::repositories::holidays::HolidaysRepo,
+    error::AppError,
+    services::context::RequestContext,
+    utils::clock,
+    utils::ics::parse_ics_holidays,
+    validation::holiday::{
+        UpdateHolidayChanges, validate_create_holiday, validate_region, validate_update_holiday,
+    },
+};
+
+/// Workspace holiday calendar. Cycle working-day stats and milestone
+/// working-day countdowns skip these dates. SLA timers and due-date reminders
+/// are not covered: issues carry neither an SLA nor a due date in this schema,
+/// so those computations need their own change before they can consult it.
+pub struct HolidaysService;
+
+impl HolidaysService {
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<Holiday>, AppError> {
+        if let (Some(f), Some(t)) = (from, to)
+            && f > t
+        {
+            return Err(AppError::validation("from must not be after to"));
+        }
+        let list = HolidaysRepo::list_by_workspace(conn, ctx.workspace_id, from, to)?;
+        Ok(list)
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &crate::routes::holidays::CreateHolidayRequest,
+    ) -> Result<Holiday, AppError> {
+        validate_create_holiday(&req.name, &req.region)?;
+        let name = req.name.trim();
+
+        if HolidaysRepo::exists_on_date(conn, ctx.workspace_id, req.holiday_date, name)? {
+            return Err(AppError::conflict_with_code(
+                "Holiday already exists on this date",
+                Some("holiday_date".to_string()),
+                "HOLIDAY_EXISTS",
+            ));
+        }
+
+        let new_holiday = NewHoliday {
+            workspace_id: ctx.workspace_id,
+            name: name.to_string(),
+            holiday_date: req.holiday_date,
+            region: req.region.clone(),
+            source: "manual".to_string(),
+        };
+        let holiday = HolidaysRepo::insert(conn, &new_holiday)?;
+        Ok(holiday)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        holiday_id: uuid::Uuid,
+        req: &crate::routes::holidays::UpdateHolidayRequest,
+    ) -> Result<Holiday, AppError> {
+        let existing = HolidaysRepo::find_by_id_in_workspace(conn, ctx.workspace_id, holiday_id)?
+            .ok_or_else(|| AppError::not_found("holiday"))?;
+
+        validate_update_holiday(&UpdateHolidayChanges {
+            name: req.name.as_deref(),
+            date_present: req.holiday_date.is_some(),
+            region: req.region.as_deref(),
+        })?;
+
+        let name = req.name.as_deref().map(str::trim);
+        let new_name = name.unwrap_or(&existing.name);
+        let new_date = req.holiday_date.unwrap_or(existing.holiday_date);
+        if (new_name != existing.name || new_date != existing.holiday_date)
+            && HolidaysRepo::exists_on_date(conn, ctx.workspace_id, new_date, new_name)?
+        {
+            return Err(AppError::conflict_with_code(
+                "Holiday already exists on this date",
+                Some("holiday_date".to_string()),
+                "HOLIDAY_EXISTS",
+            ));
+        }
+
+        let changes = UpdateHoliday {
+            name: name.map(str::to_string),
+            holiday_date: req.holiday_date,
+            region: req.region.clone().map(Some),
+            updated_at: Some(clock::now()),
+        };
+        let updated = HolidaysRepo::update(conn, holiday_id, &changes)?;
+        Ok(updated)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        holiday_id: uuid::Uuid,
+    ) -> Result<(), AppError> {
+        if HolidaysRepo::find_by_id_in_workspace(conn, ctx.workspace_id, holiday_id)?.is_none() {
+            return Err(AppError::not_found("holiday"));
+        }
+        HolidaysRepo::delete_by_id(conn, holiday_id)?;
+        Ok(())
+    }
+
+    /// Import a regional holiday set from an ICS calendar. Entries that already exist
+    /// (same date and name) are skipped so re-importing the same file is harmless.
+    pub fn import_ics(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &crate::routes::holidays::ImportHolidaysRequest,
+    ) -> Result<HolidayImportResult, AppError> {
+        if let Some(ref region) = req.region {
+            validate_region(region)?;
+        }
+        let parsed = parse_ics_holidays(&req.ics).map_err(AppError::validation)?;
+        if parsed.is_empty() {
+            return Err(AppError::validation("ICS file contains no events"));
+        }
+
+        let workspace_id = ctx.workspace_id;
+        conn.transaction::<_, AppError, _>(|conn| {
+            let mut holidays = Vec::new();
+            let mut skipped = 0;
+            for entry in parsed {
+                let name = entry.name.trim();
+                if HolidaysRepo::exists_on_date(conn, workspace_id, entry.date, name)? {
+                    skipped += 1;
+                    continue;
+                }
+                let new_holiday = NewHoliday {
+                    workspace_id,
+                    name: name.to_string(),
+                    holiday_date: entry.date,
+                    region: req.region.clone(),
+                    source: "ics".to_string(),
+                };
+                holidays.push(HolidaysRepo::insert(conn, &new_holiday)?);
+            }
+            Ok(HolidayImportResult {
+                imported: holidays.len(),
+                skipped,
+                holidays,
+            })
+        })
+    }
+
+    /// Number of working days in `[start, end]`, loading the workspace holiday calendar.
+    pub fn working_days_between(
+        conn: &mut PgConnection,
+        workspace_id: uuid::Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<i64, AppError> {
+        if start > end {
+            return Ok(0);
+        }
+        let holidays: HashSet<NaiveDate> =
+            HolidaysRepo::list_dates_between(conn, workspace_id, start, end)?
+                .into_iter()
+                .collect();
+        Ok(Self::count_working_days(start, end, &holidays))
+    }
+
+    /// Count weekdays in the inclusive range `[start, end]` that are not holidays.
+    pub fn count_working_days(
+        start: NaiveDate,
+        end: NaiveDate,
+        holidays: &HashSet<NaiveDate>,
+    ) -> i64 {
+        if start > end {
+            return 0;
+        }
+        start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| Self::is_working_day(*d, holidays))
+            .count() as i64
+    }
+
+    pub fn is_working_day(date: NaiveDate, holidays: &HashSet<NaiveDate>) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !holidays.contains(&date)
+    }
+}
@@ -0,0 +1,1169 @@
+use diesel::prelude::*;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+use uuid::Uuid;
+
+use crate::{
+    db::enums::LabelLevel,
+    db::models::comment::NewComment,
+    db::models::issue::{NewIssue, NewIssueLabel},
+    db::models::project::CreateProjectRequest,
+    db::models::workflow::{WorkflowState, WorkflowStateCategory},
+    db::models::workspace_import::{
+        ImportBundle, ImportComment, ImportConflict, ImportIssue, ImportLabel, ImportProject,
+        ImportReport, ImportTeam, NewImportExternalId, NewWorkspaceImport, WorkspaceImport,
+        WorkspaceImportResponse, import_record_type, import_source,
+    },
+    db::repositories::auth::AuthRepo,
+    db::repositories::comments::CommentRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::labels::LabelRepo,
+    db::repositories::projects::ProjectsRepo,
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    db::repositories::workspace_imports::WorkspaceImportsRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
+    error::AppError,
+    services::context::{AuthChannel, RequestContext},
+    services::labels_service::LabelsService,
+    services::member_imports_service::JOB_QUEUE,
+    services::projects_service::ProjectsService,
+    services::teams_service::TeamsService,
+    utils::clock,
+    validation::issue::validate_create_issue,
+    validation::label::validate_create_label,
+};
+
+/// Job name prefix; the task is `workspace_import:<import id>`
+pub const JOB_PREFIX: &str = "workspace_import:";
+
+/// Most records (teams, labels, projects, issues and comments) in one export
+pub const MAX_IMPORT_RECORDS: usize = 20_000;
+
+/// Largest export accepted; well above the default request body limit
+pub const MAX_IMPORT_BYTES: usize = 50 * 1024 * 1024;
+
+/// Color for imported labels that have none, or one we can't use
+pub const DEFAULT_LABEL_COLOR: &str = "#95A2B3";
+
+/// Longest description kept; longer ones are truncated
+const MAX_DESCRIPTION_BYTES: usize = 10_000;
+
+/// Linear export: the teams, projects, labels and issues (with comments) of a
+/// workspace as returned by its GraphQL API
+#[derive(Deserialize)]
+struct LinearExport {
+    #[serde(default)]
+    teams: Vec<LinearTeam>,
+    #[serde(default)]
+    projects: Vec<LinearProject>,
+    #[serde(default)]
+    labels: Vec<LinearLabel>,
+    #[serde(default)]
+    issues: Vec<LinearIssue>,
+}
+
+#[derive(Deserialize)]
+struct LinearTeam {
+    id: String,
+    key: String,
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LinearProject {
+    id: String,
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LinearLabel {
+    id: String,
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LinearRef {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct LinearUser {
+    email: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LinearState {
+    name: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LinearIssue {
+    id: String,
+    identifier: Option<String>,
+    title: String,
+    description: Option<String>,
+    /// 0 none, 1 urgent, 2 high, 3 medium, 4 low
+    #[serde(default)]
+    priority: i32,
+    team: LinearRef,
+    project: Option<LinearRef>,
+    state: Option<LinearState>,
+    assignee: Option<LinearUser>,
+    #[serde(default)]
+    labels: Vec<LinearRef>,
+    #[serde(default)]
+    comments: Vec<LinearComment>,
+}
+
+#[derive(Deserialize)]
+struct LinearComment {
+    id: String,
+    body: String,
+    user: Option<LinearUser>,
+}
+
+/// Jira export: a REST search response with `comment` among the fields
+#[derive(Deserialize)]
+struct JiraExport {
+    issues: Vec<JiraIssue>,
+}
+
+#[derive(Deserialize)]
+struct JiraIssue {
+    id: String,
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraFields {
+    summary: String,
+    /// Plain text or an Atlassian document
+    description: Option<serde_json::Value>,
+    issuetype: Option<JiraNamed>,
+    project: JiraProject,
+    status: Option<JiraStatus>,
+    priority: Option<JiraNamed>,
+    #[serde(default)]
+    labels: Vec<String>,
+    assignee: Option<JiraUser>,
+    parent: Option<JiraParent>,
+    comment: Option<JiraComments>,
+}
+
+#[derive(Deserialize)]
+struct JiraNamed {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JiraProject {
+    id: String,
+    key: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraStatus {
+    name: String,
+    status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Deserialize)]
+struct JiraStatusCategory {
+    /// `new`, `indeterminate` or `done`
+    key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraUser {
+    email_address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JiraParent {
+    id: String,
+    fields: Option<JiraParentFields>,
+}
+
+#[derive(Deserialize)]
+struct JiraParentFields {
+    issuetype: Option<JiraNamed>,
+}
+
+#[derive(Deserialize)]
+struct JiraComments {
+    #[serde(default)]
+    comments: Vec<JiraComment>,
+}
+
+#[derive(Deserialize)]
+struct JiraComment {
+    id: String,
+    body: serde_json::Value,
+    author: Option<JiraUser>,
+}
+
+/// How one exported record ended up in the workspace
+struct Resolved {
+    id: Uuid,
+    created: bool,
+    /// Reported as a conflict: what differs from the export
+    note: Option<String>,
+}
+
+impl Resolved {
+    fn created(id: Uuid) -> Self {
+        Resolved {
+            id,
+            created: true,
+            note: None,
+        }
+    }
+
+    fn matched(id: Uuid, note: Option<String>) -> Self {
+        Resolved {
+            id,
+            created: false,
+            note,
+        }
+    }
+}
+
+/// Imports Linear and Jira exports. Each source is mapped into an
+/// [`ImportBundle`] first; applying a bundle is the same for every source.
+/// Records are remembered by their id in the source system, so importing an
+/// overlapping export only adds what is new.
+pub struct ImportService;
+
+impl ImportService {
+    pub fn parse(source: &str, content: &str) -> Result<ImportBundle, AppError> {
+        let bundle = match source {
+            import_source::LINEAR => Self::parse_linear(content)?,
+            import_source::JIRA => Self::parse_jira(content)?,
+            _ => {
+                return Err(AppError::validation(
+                    "Import source must be 'linear' or 'jira'",
+                ));
+            }
+        };
+        let records = bundle.record_count();
+        if records == 0 {
+            return Err(AppError::validation("The export contains no records"));
+        }
+        if records > MAX_IMPORT_RECORDS {
+            return Err(AppError::validation(format!(
+                "The export has {} records; at most {} can be imported at once",
+                records, MAX_IMPORT_RECORDS
+            )));
+        }
+        Ok(bundle)
+    }
+
+    pub fn parse_linear(content: &str) -> Result<ImportBundle, AppError> {
+        let export: LinearExport = serde_json::from_str(content)
+            .map_err(|e| AppError::validation(format!("Invalid Linear export: {}", e)))?;
+
+        let issues = export
+            .issues
+            .into_iter()
+            .map(|issue| {
+                let (state_name, state_category) = match issue.state {
+                    Some(state) => {
+                        let category = state
+                            .kind
+                            .filter(|kind| Self::is_state_category(kind))
+                            .map(|kind| kind.to_string());
+                        (Some(state.name), category)
+                    }
+                    None => (None, None),
+                };
+                ImportIssue {
+                    identifier: issue.identifier.unwrap_or_else(|| issue.id.clone()),
+                    external_id: issue.id,
+                    team_external_id: issue.team.id,
+                    project_external_id: issue.project.map(|p| p.id),
+                    title: issue.title,
+                    description: issue.description.filter(|d| !d.trim().is_empty()),
+                    priority: Self::linear_priority(issue.priority).to_string(),
+                    state_name,
+                    state_category,
+                    assignee_email: issue.assignee.and_then(|a| a.email),
+                    label_external_ids: issue.labels.into_iter().map(|l| l.id).collect(),
+                    comments: issue
+                        .comments
+                        .into_iter()
+                        .map(|c| ImportComment {
+                            external_id: c.id,
+                            author_email: c.user.and_then(|u| u.email),
+                            body: c.body,
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Ok(ImportBundle {
+            teams: export
+                .teams
+                .into_iter()
+                .map(|t| ImportTeam {
+                    external_id: t.id,
+                    key: t.key,
+                    name: t.name,
+                    description: t.description,
+                })
+                .collect(),
+            labels: export
+                .labels
+                .into_iter()
+                .map(|l| ImportLabel {
+                    external_id: l.id,
+                    name: l.name,
+                    color: l.color,
+                })
+                .collect(),
+            projects: export
+                .projects
+                .into_iter()
+                .map(|p| ImportProject {
+                    external_id: p.id,
+                    name: p.name,
+                    description: p.description,
+                })
+                .collect(),
+            issues,
+        })
+    }
+
+    /// Jira projects become teams and epics become projects; labels are
+    /// plain names in Jira, so the name is their external id
+    pub fn parse_jira(content: &str) -> Result<ImportBundle, AppError> {
+        let export: JiraExport = serde_json::from_str(content)
+            .map_err(|e| AppError::validation(format!("Invalid Jira export: {}", e)))?;
+        let is_epic = |issuetype: &Option<JiraNamed>| {
+            issuetype
+                .as_ref()
+                .is_some_and(|t| t.name.eq_ignore_ascii_case("epic"))
+        };
+        let epic_ids: HashSet<String> = export
+            .issues
+            .iter()
+            .filter(|i| is_epic(&i.fields.issuetype))
+            .map(|i| i.id.clone())
+            .collect();
+
+        let mut bundle = ImportBundle::default();
+        let mut labels_seen = HashSet::new();
+        for issue in export.issues {
+            let fields = issue.fields;
+            if !bundle
+                .teams
+                .iter()
+                .any(|t| t.external_id == fields.project.id)
+            {
+                bundle.teams.push(ImportTeam {
+                    external_id: fields.project.id.clone(),
+                    key: fields.project.key.clone(),
+                    name: fields.project.name.clone(),
+                    description: None,
+                });
+            }
+            let description = fields
+                .description
+                .as_ref()
+                .map(Self::jira_text)
+                .filter(|d| !d.trim().is_empty());
+
+            if is_epic(&fields.issuetype) {
+                bundle.projects.push(ImportProject {
+                    external_id: issue.id,
+                    name: fields.summary,
+                    description,
+                });
+                continue;
+            }
+
+            for label in &fields.labels {
+                if labels_seen.insert(label.clone()) {
+                    bundle.labels.push(ImportLabel {
+                        external_id: label.clone(),
+                        name: label.clone(),
+                        color: None,
+                    });
+                }
+            }
+            let project_external_id = fields.parent.and_then(|parent| {
+                let parent_is_epic = epic_ids.contains(&parent.id)
+                    || parent.fields.is_some_and(|f| is_epic(&f.issuetype));
+                parent_is_epic.then_some(parent.id)
+            });
+            let (state_name, state_category) = match fields.status {
+                Some(status) => {
+                    let category = status
+                        .status_category
+                        .and_then(|c| Self::jira_state_category(&c.key, &status.name));
+                    (Some(status.name), category.map(str::to_string))
+                }
+                None => (None, None),
+            };
+
+            bundle.issues.push(ImportIssue {
+                external_id: issue.id,
+                identifier: issue.key,
+                team_external_id: fields.project.id,
+                project_external_id,
+                title: fields.summary,
+                description,
+                priority: fields
+                    .priority
+                    .map(|p| Self::jira_priority(&p.name))
+                    .unwrap_or("none")
+                    .to_string(),
+                state_name,
+                state_category,
+                assignee_email: fields.assignee.and_then(|a| a.email_address),
+                label_external_ids: fields.labels,
+                comments: fields
+                    .comment
+                    .map(|c| c.comments)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|c| ImportComment {
+                        external_id: c.id,
+                        author_email: c.author.and_then(|a| a.email_address),
+                        body: Self::jira_text(&c.body),
+                    })
+                    .collect(),
+            });
+        }
+        Ok(bundle)
+    }
+
+    fn is_state_category(kind: &str) -> bool {
+        matches!(
+            kind,
+            "triage" | "backlog" | "unstarted" | "started" | "completed" | "canceled"
+        )
+    }
+
+    pub fn linear_priority(priority: i32) -> &'static str {
+        match priority {
+            1 => "urgent",
+            2 => "high",
+            3 => "medium",
+            4 => "low",
+            _ => "none",
+        }
+    }
+
+    pub fn jira_priority(name: &str) -> &'static str {
+        match name.to_ascii_lowercase().as_str() {
+            "highest" | "blocker" | "critical" => "urgent",
+            "high" | "major" => "high",
+            "medium" => "medium",
+            "low" | "lowest" | "minor" | "trivial" => "low",
+            _ => "none",
+        }
+    }
+
+    /// Jira only has three status categories; done statuses that read like
+    /// a rejection map to canceled
+    pub fn jira_state_category(category_key: &str, status_name: &str) -> Option<&'static str> {
+        match category_key {
+            "new" => Some("unstarted"),
+            "indeterminate" => Some("started"),
+            "done" => {
+                let name = status_name.to_ascii_lowercase();
+                if ["cancel", "won't", "wont", "declin", "reject", "duplicate"]
+                    .iter()
+                    .any(|word| name.contains(word))
+                {
+                    Some("canceled")
+                } else {
+                    Some("completed")
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Plain text of a Jira field: strings as they are, Atlassian documents
+    /// flattened with a line break after each paragraph
+    pub fn jira_text(value: &serde_json::Value) -> String {
+        fn walk(value: &serde_json::Value, out: &mut String) {
+            match value {
+                serde_json::Value::String(s) => out.push_str(s),
+                serde_json::Value::Object(map) => {
+                    if let Some(serde_json::Value::String(text)) = map.get("text") {
+                        out.push_str(text);
+                    }
+                    if map.get("type").and_then(|t| t.as_str()) == Some("hardBreak") {
+                        out.push('\n');
+                    }
+                    if let Some(content) = map.get("content") {
+                        walk(content, out);
+                    }
+                    if matches!(
+                        map.get("type").and_then(|t| t.as_str()),
+                        Some("paragraph" | "heading" | "listItem" | "codeBlock")
+                    ) && !out.ends_with('\n')
+                    {
+                        out.push('\n');
+                    }
+                }
+                serde_json::Value::Array(items) => {
+                    for item in items {
+                        walk(item, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let mut out = String::new();
+        walk(value, &mut out);
+        out.trim_end().to_string()
+    }
+
+    /// Upper-case key of at most ten letters and digits for a project name,
+    /// made unique against `taken` with a numeric suffix
+    pub fn project_key(name: &str, taken: &HashSet<String>) -> String {
+        let words: Vec<&str> = name
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let mut base: String = if words.len() > 1 {
+            words.iter().filter_map(|w| w.chars().next()).collect()
+        } else {
+            words.first().map(|w| w.to_string()).unwrap_or_default()
+        };
+        base = base
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .take(10)
+            .collect::<String>()
+            .to_ascii_uppercase();
+        if base.is_empty() {
+            base = "PRJ".to_string();
+        }
+        if !taken.contains(&base) {
+            return base;
+        }
+        (2..)
+            .map(|n: u32| {
+                let suffix = n.to_string();
+                let keep = 10 - suffix.len();
+                format!("{}{}", &base[..base.len().min(keep)], suffix)
+            })
+            .find(|key| !taken.contains(key))
+            .unwrap_or(base)
+    }
+
+    pub fn content_hash(source: &str, content: &str) -> String {
+        hex::encode(Sha256::digest(
+            format!("{}\n{}", source, content).as_bytes(),
+        ))
+    }
+
+    /// Validate the export and record an import. Uploading the same file
+    /// again returns the existing import; the flag is `true` only when a new
+    /// import was created and needs to be queued.
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        source: &str,
+        content: &str,
+    ) -> Result<(WorkspaceImportResponse, bool), AppError> {
+        let source = source.trim().to_ascii_lowercase();
+        let hash = Self::content_hash(&source, content);
+        if let Some(existing) = WorkspaceImportsRepo::find_by_hash(conn, ctx.workspace_id, &hash)? {
+            return Ok((Self::to_response(existing), false));
+        }
+
+        let bundle = Self::parse(&source, content)?;
+        let import = WorkspaceImportsRepo::insert(
+            conn,
+            &NewWorkspaceImport {
+                workspace_id: ctx.workspace_id,
+                requested_by: ctx.user_id,
+                source,
+                content_hash: hash,
+                payload: content.to_string(),
+                total_records: bundle.record_count() as i32,
+            },
+        )?;
+        Ok((Self::to_response(import), true))
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        import_id: Uuid,
+    ) -> Result<WorkspaceImportResponse, AppError> {
+        let import = WorkspaceImportsRepo::find_in_workspace(conn, ctx.workspace_id, import_id)?
+            .ok_or_else(|| AppError::not_found("workspace import"))?;
+        Ok(Self::to_response(import))
+    }
+
+    fn to_response(import: WorkspaceImport) -> WorkspaceImportResponse {
+        let report = import
+            .result
+            .as_deref()
+            .and_then(|r| serde_json::from_str(r).ok());
+        WorkspaceImportResponse { import, report }
+    }
+
+    /// Push the import onto the worker queue
+    pub async fn enqueue(redis: &redis::Client, import_id: Uuid) -> redis::RedisResult<()> {
+        use redis::AsyncCommands;
+        let mut conn = redis.get_multiplexed_async_connection().await?;
+        conn.rpush(JOB_QUEUE, format!("{}{}", JOB_PREFIX, import_id))
+            .await
+    }
+
+    /// Import id of a queued job, if the task is a workspace import
+    pub fn parse_job(task: &str) -> Option<Uuid> {
+        task.strip_prefix(JOB_PREFIX)?.parse().ok()
+    }
+
+    /// Pending imports older than `age`, for the worker to pick up
+    pub fn stale_pending(
+        conn: &mut PgConnection,
+        age: chrono::Duration,
+    ) -> Result<Vec<Uuid>, AppError> {
+        Ok(WorkspaceImportsRepo::list_stale_pending(
+            conn,
+            clock::now() - age,
+        )?)
+    }
+
+    /// Apply a pending import. Returns `None` when the import is not pending,
+    /// so duplicate jobs are harmless.
+    pub fn run(conn: &mut PgConnection, import_id: Uuid) -> Result<Option<ImportReport>, AppError> {
+        let Some(import) = WorkspaceImportsRepo::claim(conn, import_id)? else {
+            return Ok(None);
+        };
+        let ctx = RequestContext {
+            user_id: import.requested_by,
+            workspace_id: import.workspace_id,
+            idempotency_key: None,
+            channel: AuthChannel::Session,
+        };
+
+        let report = match Self::parse(&import.source, &import.payload) {
+            Ok(bundle) => Self::apply(conn, &ctx, &import.source, &bundle)?,
+            Err(err) => ImportReport {
+                conflicts: vec![ImportConflict {
+                    record_type: "export".to_string(),
+                    external_id: import.id.to_string(),
+                    message: err.to_string(),
+                }],
+                ..Default::default()
+            },
+        };
+        let result = serde_json::to_string(&report)
+            .map_err(|e| AppError::internal(format!("Failed to store import report: {}", e)))?;
+        WorkspaceImportsRepo::complete(conn, import.id, &result)?;
+        Ok(Some(report))
+    }
+
+    /// Create or match every record of the bundle: teams, labels and
+    /// projects first, then issues and their comments. Issues and comments
+    /// are written directly so an import doesn't notify every assignee.
+    pub fn apply(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        source: &str,
+        bundle: &ImportBundle,
+    ) -> Result<ImportReport, AppError> {
+        let mut report = ImportReport::default();
+
+        let mut teams = HashMap::new();
+        for team in &bundle.teams {
+            let resolved = Self::import_record(
+                conn,
+                ctx,
+                source,
+                import_record_type::TEAM,
+                &team.external_id,
+                &mut report,
+                |conn| Self::create_team(conn, ctx, team),
+            );
+            if let Some(id) = resolved {
+                teams.insert(team.external_id.clone(), id);
+            }
+        }
+
+        let mut labels = HashMap::new();
+        let mut existing_labels = LabelRepo::list_by_workspace(conn, ctx.workspace_id)?;
+        for label in &bundle.labels {
+            let resolved = Self::import_record(
+                conn,
+                ctx,
+                source,
+                import_record_type::LABEL,
+                &label.external_id,
+                &mut report,
+                |conn| {
+                    if let Some(existing) = existing_labels
+                        .iter()
+                        .find(|l| l.name.eq_ignore_ascii_case(label.name.trim()))
+                    {
+                        return Ok(Resolved::matched(existing.id, None));
+                    }
+                    let color = label
+                        .color
+                        .clone()
+                        .filter(|c| validate_create_label(&label.name, c).is_ok())
+                        .unwrap_or_else(|| DEFAULT_LABEL_COLOR.to_string());
+                    let created = LabelsService::create(
+                        conn,
+                        ctx,
+                        &crate::routes::labels::CreateLabelRequest {
+                            name: label.name.trim().to_string(),
+                            color,
+                            level: LabelLevel::Issue,
+                        },
+                    )?;
+                    let id = created.id;
+                    existing_labels.push(created);
+                    Ok(Resolved::created(id))
+                },
+            );
+            if let Some(id) = resolved {
+                labels.insert(label.external_id.clone(), id);
+            }
+        }
+
+        let mut projects = HashMap::new();
+        for project in &bundle.projects {
+            let resolved = Self::import_record(
+                conn,
+                ctx,
+                source,
+                import_record_type::PROJECT,
+                &project.external_id,
+                &mut report,
+                |conn| Self::create_project(conn, ctx, project),
+            );
+            if let Some(id) = resolved {
+                projects.insert(project.external_id.clone(), id);
+            }
+        }
+
+        let mut team_states: HashMap<Uuid, Vec<WorkflowState>> = HashMap::new();
+        for issue in &bundle.issues {
+            let Some(team_id) = Self::lookup(
+                conn,
+                ctx,
+                source,
+                import_record_type::TEAM,
+                &issue.team_external_id,
+                &teams,
+            )?
+            else {
+                report.conflicts.push(ImportConflict {
+                    record_type: import_record_type::ISSUE.to_string(),
+                    external_id: issue.external_id.clone(),
+                    message: format!("{}: its team was not imported", issue.identifier),
+                });
+                continue;
+            };
+            let project_id = match &issue.project_external_id {
+                Some(external_id) => Self::lookup(
+                    conn,
+                    ctx,
+                    source,
+                    import_record_type::PROJECT,
+                    external_id,
+                    &projects,
+                )?,
+                None => None,
+            };
+            let mut label_ids = Vec::new();
+            for external_id in &issue.label_external_ids {
+                if let Some(id) = Self::lookup(
+                    conn,
+                    ctx,
+                    source,
+                    import_record_type::LABEL,
+                    external_id,
+                    &labels,
+                )? && !label_ids.contains(&id)
+                {
+                    label_ids.push(id);
+                }
+            }
+            let states = match team_states.entry(team_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(WorkflowsRepo::list_states_by_team(conn, team_id)?)
+                }
+            };
+            let state = Self::match_state(
+                states,
+                issue.state_name.as_deref(),
+                issue.state_category.as_deref(),
+            )
+            .cloned();
+
+            let resolved = Self::import_record(
+                conn,
+                ctx,
+                source,
+                import_record_type::ISSUE,
+                &issue.external_id,
+                &mut report,
+                |conn| {
+                    Self::create_issue(
+                        conn,
+                        ctx,
+                        issue,
+                        team_id,
+                        project_id,
+                        state.as_ref(),
+                        &label_ids,
+                    )
+                },
+            );
+            let Some(issue_id) = resolved else {
+                continue;
+            };
+
+            for comment in &issue.comments {
+                Self::import_record(
+                    conn,
+                    ctx,
+                    source,
+                    import_record_type::COMMENT,
+                    &comment.external_id,
+                    &mut report,
+                    |conn| Self::create_comment(conn, ctx, issue_id, comment),
+                );
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// State for an imported issue: same name first, then the first state
+    /// of the same category
+    pub fn match_state<'a>(
+        states: &'a [WorkflowState],
+        name: Option<&str>,
+        category: Option<&str>,
+    ) -> Option<&'a WorkflowState> {
+        name.and_then(|name| {
+            states
+                .iter()
+                .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+        })
+        .or_else(|| {
+            let category = category.filter(|c| Self::is_state_category(c))?;
+            let category = WorkflowStateCategory::parse_from_string(category);
+            states.iter().find(|s| s.category == category)
+        })
+    }
+
+    /// Id of a record from this export or an earlier one
+    fn lookup(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        source: &str,
+        record_type: &str,
+        external_id: &str,
+        imported: &HashMap<String, Uuid>,
+    ) -> Result<Option<Uuid>, AppError> {
+        if let Some(id) = imported.get(external_id) {
+            return Ok(Some(*id));
+        }
+        Ok(WorkspaceImportsRepo::find_external(
+            conn,
+            ctx.workspace_id,
+            source,
+            record_type,
+            external_id,
+        )?)
+    }
+
+    /// Resolve one record, creating it unless an earlier import did. The
+    /// record and its external id are saved together; failures become
+    /// conflicts and leave nothing behind.
+    fn import_record<F>(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        source: &str,
+        record_type: &str,
+        external_id: &str,
+        report: &mut ImportReport,
+        create: F,
+    ) -> Option<Uuid>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<Resolved, AppError>,
+    {
+        let conflict = |message: String| ImportConflict {
+            record_type: record_type.to_string(),
+            external_id: external_id.to_string(),
+            message,
+        };
+        match WorkspaceImportsRepo::find_external(
+            conn,
+            ctx.workspace_id,
+            source,
+            record_type,
+            external_id,
+        ) {
+            Ok(Some(id)) => {
+                report.matched.bump(record_type);
+                return Some(id);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                report.conflicts.push(conflict(err.to_string()));
+                return None;
+            }
+        }
+
+        let result = conn.transaction::<_, AppError, _>(|conn| {
+            let resolved = create(conn)?;
+            WorkspaceImportsRepo::insert_external(
+                conn,
+                &NewImportExternalId {
+                    workspace_id: ctx.workspace_id,
+                    source: source.to_string(),
+                    record_type: record_type.to_string(),
+                    external_id: external_id.chars().take(255).collect(),
+                    entity_id: resolved.id,
+                },
+            )?;
+            Ok(resolved)
+        });
+        match result {
+            Ok(resolved) => {
+                if resolved.created {
+                    report.created.bump(record_type);
+                } else {
+                    report.matched.bump(record_type);
+                }
+                if let Some(note) = resolved.note {
+                    report.conflicts.push(conflict(note));
+                }
+                Some(resolved.id)
+            }
+            Err(err) => {
+                report.conflicts.push(conflict(match err {
+                    AppError::Validation { message } => message,
+                    other => other.to_string(),
+                }));
+                None
+            }
+        }
+    }
+
+    fn create_team(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team: &ImportTeam,
+    ) -> Result<Resolved, AppError> {
+        let key: String = team
+            .key
+            .trim()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        if let Some(existing) = TeamsRepo::find_by_key(conn, ctx.workspace_id, &key)? {
+            return Ok(Resolved::matched(
+                existing.id,
+                Some(format!(
+                    "A team with key {} already exists; its issues were added to it",
+                    key
+                )),
+            ));
+        }
+        let created = TeamsService::create(
+            conn,
+            ctx,
+            &crate::routes::teams::CreateTeamRequest {
+                name: team.name.trim().to_string(),
+                team_key: key,
+                description: team.description.clone(),
+                icon_url: None,
+                is_private: false,
+                parent_team_id: None,
+            },
+        )?;
+        Ok(Resolved::created(created.id))
+    }
+
+    fn create_project(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project: &ImportProject,
+    ) -> Result<Resolved, AppError> {
+        let name = project.name.trim();
+        if let Some(existing) =
+            ProjectsRepo::find_by_name_in_workspace(conn, ctx.workspace_id, name)?
+        {
+            return Ok(Resolved::matched(
+                existing.id,
+                Some(format!(
+                    "A project named '{}' already exists; its issues were added to it",
+                    name
+                )),
+            ));
+        }
+        let taken: HashSet<String> = ProjectsRepo::list_keys(conn, ctx.workspace_id)?
+            .into_iter()
+            .collect();
+        let created = ProjectsService::create(
+            conn,
+            ctx,
+            &CreateProjectRequest {
+                name: name.to_string(),
+                project_key: Self::project_key(name, &taken),
+                description: project.description.clone(),
+                roadmap_id: None,
+                target_date: None,
+                project_status_id: None,
+                priority: None,
+            },
+        )?;
+        Ok(Resolved::created(created.id))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_issue(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue: &ImportIssue,
+        team_id: Uuid,
+        project_id: Option<Uuid>,
+        state: Option<&WorkflowState>,
+        label_ids: &[Uuid],
+    ) -> Result<Resolved, AppError> {
+        let mut notes = Vec::new();
+        let mut description = issue.description.clone();
+        if let Some(text) = &mut description
+            && text.len() > MAX_DESCRIPTION_BYTES
+        {
+            let mut end = MAX_DESCRIPTION_BYTES;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            text.truncate(end);
+            notes.push("description was truncated".to_string());
+        }
+        let title = issue.title.trim().to_string();
+        validate_create_issue(&title, &description, &team_id)?;
+
+        let assignee_id = match &issue.assignee_email {
+            Some(email) => {
+                let member = match AuthRepo::find_by_email(conn, email)? {
+                    Some(user) => WorkspaceMembersRepo::find(conn, ctx.workspace_id, user.id)?
+                        .map(|_| user.id),
+                    None => None,
+                };
+                if member.is_none() {
+                    notes.push(format!(
+                        "assignee {} is not a workspace member; left unassigned",
+                        email
+                    ));
+                }
+                member
+            }
+            None => None,
+        };
+        if issue.state_name.is_some() && state.is_none() {
+            notes.push(format!(
+                "no matching workflow state for '{}'",
+                issue.state_name.as_deref().unwrap_or_default()
+            ));
+        }
+
+        let created = IssueRepo::insert(
+            conn,
+            &NewIssue {
+                project_id,
+                cycle_id: None,
+                creator_id: ctx.user_id,
+                assignee_id,
+                parent_issue_id: None,
+                title,
+                description,
+                priority: Some(issue.priority.clone()),
+                is_changelog_candidate: Some(false),
+                team_id,
+                workflow_id: state.map(|s| s.workflow_id),
+                workflow_state_id: state.map(|s| s.id),
+                estimate: None,
+            },
+        )?;
+        if !label_ids.is_empty() {
+            let rows: Vec<NewIssueLabel> = label_ids
+                .iter()
+                .map(|label_id| NewIssueLabel {
+                    issue_id: created.id,
+                    label_id: *label_id,
+                })
+                .collect();
+            diesel::insert_into(crate::schema::issue_labels::table)
+                .values(&rows)
+                .execute(conn)?;
+        }
+
+        let note =
+            (!notes.is_empty()).then(|| format!("{}: {}", issue.identifier, notes.join("; ")));
+        Ok(Resolved {
+            id: created.id,
+            created: true,
+            note,
+        })
+    }
+
+    /// Comments by people outside the workspace are posted as the importing
+    /// user, with the original author's address at the top
+    fn create_comment(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        comment: &ImportComment,
+    ) -> Result<Resolved, AppError> {
+        if comment.body.trim().is_empty() {
+            return Err(AppError::validation("Comment is empty"));
+        }
+        let author = match &comment.author_email {
+            Some(email) => match AuthRepo::find_by_email(conn, email)? {
+                Some(user) => {
+                    WorkspaceMembersRepo::find(conn, ctx.workspace_id, user.id)?.map(|_| user.id)
+                }
+                None => None,
+            },
+            None => None,
+        };
+        let content = match (author, &comment.author_email) {
+            (None, Some(email)) => format!("_Originally posted by {}_\n\n{}", email, comment.body),
+            _ => comment.body.clone(),
+        };
+        let created = CommentRepo::insert(
+            conn,
+            &NewComment {
+                issue_id,
+                author_id: author.unwrap_or(ctx.user_id),
+                content,
+                content_type: None,
+                parent_comment_id: None,
+            },
+        )?;
+        Ok(Resolved::created(created.id))
+    }
+}
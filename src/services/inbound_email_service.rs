@@ -0,0 +1,88 @@
+use diesel::prelude::*;
+use serde::Deserialize;
+use subtle::ConstantTimeEq;
+use utoipa::ToSchema;
+
+use crate::{
+    db::models::comment::Comment,
+    db::repositories::{
+        auth::AuthRepo, issues::IssueRepo, workspace_members::WorkspaceMembersRepo,
+    },
+    error::AppError,
+    services::{
+        comments_service::CommentsService,
+        context::{AuthChannel, RequestContext},
+    },
+    utils::email_reply,
+};
+
+/// Parsed inbound email as posted by the mail provider's webhook
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct InboundEmailRequest {
+    pub from: String,
+    pub to: String,
+    pub subject: Option<String>,
+    pub text: String,
+}
+
+pub struct InboundEmailService;
+
+impl InboundEmailService {
+    /// Compare the secret the provider sent with the configured one in
+    /// constant time, so response timing does not reveal how much matched
+    pub fn secret_matches(provided: &str, expected: &str) -> bool {
+        provided.as_bytes().ct_eq(expected.as_bytes()).into()
+    }
+
+    /// Turn a reply to a notification email into a comment on the issue.
+    ///
+    /// The reply token identifies the issue and the user it was sent to; the
+    /// sender must be that same user and still a member of the issue's workspace.
+    pub fn ingest_reply(
+        conn: &mut PgConnection,
+        secret: &str,
+        email: &InboundEmailRequest,
+    ) -> Result<Comment, AppError> {
+        let token = email_reply::extract_reply_token(&email.to)
+            .ok_or_else(|| AppError::validation("No reply address found in recipients"))?;
+        let (issue_id, user_id) = email_reply::verify_reply_token(&token, secret)
+            .ok_or_else(|| AppError::auth("Invalid reply address"))?;
+
+        let user =
+            AuthRepo::find_by_id(conn, user_id)?.ok_or_else(|| AppError::not_found("user"))?;
+        let sender = email_reply::extract_email_address(&email.from);
+        if !user.email.eq_ignore_ascii_case(&sender) {
+            return Err(AppError::auth("Sender does not match the reply address"));
+        }
+
+        let issue =
+            IssueRepo::find_by_id(conn, issue_id)?.ok_or_else(|| AppError::not_found("issue"))?;
+        let workspace_id = {
+            use crate::schema::teams::dsl as t;
+            t::teams
+                .filter(t::id.eq(issue.team_id))
+                .select(t::workspace_id)
+                .first::<uuid::Uuid>(conn)
+                .optional()?
+                .ok_or_else(|| AppError::not_found("team"))?
+        };
+        if WorkspaceMembersRepo::find(conn, workspace_id, user.id)?.is_none() {
+            return Err(AppError::auth(
+                "User is no longer a member of this workspace",
+            ));
+        }
+
+        let content = email_reply::strip_reply_text(&email.text);
+        if content.is_empty() {
+            return Err(AppError::validation("Reply has no content"));
+        }
+
+        let ctx = RequestContext {
+            user_id: user.id,
+            workspace_id,
+            idempotency_key: None,
+            channel: AuthChannel::Session,
+        };
+        CommentsService::create(conn, &ctx, issue.id, content)
+    }
+}
@@ -0,0 +1,218 @@
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+
+use crate::{
+    db::models::integrity::{IntegrityFinding, IntegrityReport},
+    error::AppError,
+    utils::clock,
+};
+
+/// A single data-integrity rule.
+///
+/// `count_sql` must return one row with a `count` BIGINT column. `fix_sql`,
+/// when present, repairs every offending row and is executed in its own
+/// transaction so a failing repair never leaves a check half-applied.
+pub struct IntegrityCheck {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub count_sql: &'static str,
+    pub fix_sql: Option<&'static str>,
+}
+
+pub const INTEGRITY_CHECKS: &[IntegrityCheck] = &[
+    IntegrityCheck {
+        name: "orphaned_issues",
+        description: "Issues whose team no longer exists",
+        count_sql: "SELECT COUNT(*) AS count FROM issues i \
+                    LEFT JOIN teams t ON t.id = i.team_id WHERE t.id IS NULL",
+        fix_sql: Some(
+            "DELETE FROM issues i WHERE NOT EXISTS (SELECT 1 FROM teams t WHERE t.id = i.team_id)",
+        ),
+    },
+    IntegrityCheck {
+        name: "orphaned_comments",
+        description: "Comments whose issue no longer exists",
+        count_sql: "SELECT COUNT(*) AS count FROM comments c \
+                    LEFT JOIN issues i ON i.id = c.issue_id WHERE i.id IS NULL",
+        fix_sql: Some(
+            "DELETE FROM comments c WHERE NOT EXISTS (SELECT 1 FROM issues i WHERE i.id = c.issue_id)",
+        ),
+    },
+    IntegrityCheck {
+        name: "issue_project_workspace_mismatch",
+        description: "Issues linked to a project from a different workspace than their team",
+        count_sql: "SELECT COUNT(*) AS count FROM issues i \
+                    JOIN teams t ON t.id = i.team_id \
+                    JOIN projects p ON p.id = i.project_id \
+                    WHERE p.workspace_id <> t.workspace_id",
+        fix_sql: Some(
+            "UPDATE issues i SET project_id = NULL FROM teams t, projects p \
+             WHERE t.id = i.team_id AND p.id = i.project_id AND p.workspace_id <> t.workspace_id",
+        ),
+    },
+    IntegrityCheck {
+        name: "issue_cycle_team_mismatch",
+        description: "Issues assigned to a cycle that belongs to another team",
+        count_sql: "SELECT COUNT(*) AS count FROM issues i \
+                    JOIN cycles c ON c.id = i.cycle_id WHERE c.team_id <> i.team_id",
+        fix_sql: Some(
+            "UPDATE issues i SET cycle_id = NULL FROM cycles c \
+             WHERE c.id = i.cycle_id AND c.team_id <> i.team_id",
+        ),
+    },
+    IntegrityCheck {
+        name: "issue_workflow_team_mismatch",
+        description: "Issues using a workflow that belongs to another team",
+        count_sql: "SELECT COUNT(*) AS count FROM issues i \
+                    JOIN workflows w ON w.id = i.workflow_id WHERE w.team_id <> i.team_id",
+        fix_sql: Some(
+            "UPDATE issues i SET workflow_id = NULL, workflow_state_id = NULL FROM workflows w \
+             WHERE w.id = i.workflow_id AND w.team_id <> i.team_id",
+        ),
+    },
+    IntegrityCheck {
+        name: "issue_state_workflow_mismatch",
+        description: "Issues whose workflow state is not part of the issue's workflow",
+        count_sql: "SELECT COUNT(*) AS count FROM issues i \
+                    JOIN workflow_states s ON s.id = i.workflow_state_id \
+                    WHERE i.workflow_id IS NULL OR s.workflow_id <> i.workflow_id",
+        fix_sql: Some(
+            "UPDATE issues i SET workflow_state_id = NULL FROM workflow_states s \
+             WHERE s.id = i.workflow_state_id \
+             AND (i.workflow_id IS NULL OR s.workflow_id <> i.workflow_id)",
+        ),
+    },
+    IntegrityCheck {
+        name: "issue_label_workspace_mismatch",
+        description: "Issue labels that belong to a different workspace than the issue",
+        count_sql: "SELECT COUNT(*) AS count FROM issue_labels il \
+                    JOIN issues i ON i.id = il.issue_id \
+                    JOIN teams t ON t.id = i.team_id \
+                    JOIN labels l ON l.id = il.label_id \
+                    WHERE l.workspace_id <> t.workspace_id",
+        fix_sql: Some(
+            "DELETE FROM issue_labels il USING issues i, teams t, labels l \
+             WHERE i.id = il.issue_id AND t.id = i.team_id AND l.id = il.label_id \
+             AND l.workspace_id <> t.workspace_id",
+        ),
+    },
+    IntegrityCheck {
+        name: "team_member_outside_workspace",
+        description: "Team members who are not members of the team's workspace",
+        count_sql: "SELECT COUNT(*) AS count FROM team_members tm \
+                    JOIN teams t ON t.id = tm.team_id \
+                    LEFT JOIN workspace_members wm \
+                      ON wm.workspace_id = t.workspace_id AND wm.user_id = tm.user_id \
+                    WHERE wm.user_id IS NULL",
+        fix_sql: Some(
+            "DELETE FROM team_members tm USING teams t WHERE t.id = tm.team_id \
+             AND NOT EXISTS (SELECT 1 FROM workspace_members wm \
+               WHERE wm.workspace_id = t.workspace_id AND wm.user_id = tm.user_id)",
+        ),
+    },
+    IntegrityCheck {
+        name: "stale_current_workspace",
+        description: "Users whose current workspace is one they are not a member of",
+        count_sql: "SELECT COUNT(*) AS count FROM users u \
+                    WHERE u.current_workspace_id IS NOT NULL \
+                    AND NOT EXISTS (SELECT 1 FROM workspace_members wm \
+                      WHERE wm.workspace_id = u.current_workspace_id AND wm.user_id = u.id)",
+        fix_sql: Some(
+            "UPDATE users u SET current_workspace_id = NULL \
+             WHERE u.current_workspace_id IS NOT NULL \
+             AND NOT EXISTS (SELECT 1 FROM workspace_members wm \
+               WHERE wm.workspace_id = u.current_workspace_id AND wm.user_id = u.id)",
+        ),
+    },
+    IntegrityCheck {
+        name: "team_issue_counter_drift",
+        description: "Teams whose issue counter is behind their highest issue number",
+        count_sql: "SELECT COUNT(*) AS count FROM ( \
+                      SELECT i.team_id FROM issues i \
+                      LEFT JOIN team_issue_counters c ON c.team_id = i.team_id \
+                      GROUP BY i.team_id \
+                      HAVING MAX(i.issue_number) > COALESCE(MAX(c.last_issue_number), 0)) d",
+        fix_sql: Some(
+            "INSERT INTO team_issue_counters (team_id, last_issue_number) \
+             SELECT team_id, MAX(issue_number) FROM issues GROUP BY team_id \
+             ON CONFLICT (team_id) DO UPDATE \
+             SET last_issue_number = GREATEST(team_issue_counters.last_issue_number, \
+               EXCLUDED.last_issue_number), updated_at = NOW()",
+        ),
+    },
+    IntegrityCheck {
+        name: "duplicate_issue_numbers",
+        description: "Issue numbers used more than once within the same team",
+        count_sql: "SELECT COALESCE(SUM(n - 1), 0)::BIGINT AS count FROM ( \
+                      SELECT COUNT(*) AS n FROM issues GROUP BY team_id, issue_number \
+                      HAVING COUNT(*) > 1) d",
+        fix_sql: None,
+    },
+];
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+pub struct IntegrityService;
+
+impl IntegrityService {
+    /// Run every integrity check, optionally repairing the problems found.
+    pub fn run(conn: &mut PgConnection, fix: bool) -> Result<IntegrityReport, AppError> {
+        let mut findings = Vec::with_capacity(INTEGRITY_CHECKS.len());
+        for check in INTEGRITY_CHECKS {
+            findings.push(Self::run_check(conn, check, fix)?);
+        }
+
+        let total_problems = findings.iter().map(|f| f.count).sum();
+        let total_fixed = findings.iter().map(|f| f.fixed).sum();
+        Ok(IntegrityReport {
+            checked_at: clock::now(),
+            fix_applied: fix,
+            total_problems,
+            total_fixed,
+            findings,
+        })
+    }
+
+    fn run_check(
+        conn: &mut PgConnection,
+        check: &IntegrityCheck,
+        fix: bool,
+    ) -> Result<IntegrityFinding, AppError> {
+        let count = Self::count(conn, check.count_sql)?;
+        let mut fixed = 0;
+
+        if fix
+            && count > 0
+            && let Some(fix_sql) = check.fix_sql
+        {
+            fixed = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::sql_query(fix_sql).execute(conn)?;
+                let remaining = Self::count(conn, check.count_sql)?;
+                Ok(count - remaining)
+            })?;
+            tracing::info!(
+                check = check.name,
+                count,
+                fixed,
+                "Integrity check repaired rows"
+            );
+        }
+
+        Ok(IntegrityFinding {
+            check: check.name.to_string(),
+            description: check.description.to_string(),
+            count,
+            fixable: check.fix_sql.is_some(),
+            fixed,
+        })
+    }
+
+    fn count(conn: &mut PgConnection, sql: &str) -> Result<i64, diesel::result::Error> {
+        let row: CountRow = diesel::sql_query(sql).get_result(conn)?;
+        Ok(row.count)
+    }
+}
@@ -1,13 +1,30 @@
 use diesel::prelude::*;
 
 use crate::{
-    db::models::invitation::{Invitation, InvitationStatus, NewInvitation},
+    db::models::email::EmailMessage,
+    db::models::invitation::{
+        BulkInviteRequest, BulkInviteResult, Invitation, InvitationStatus, NewInvitation,
+        bulk_invite_status,
+    },
+    db::models::notification::{NewNotification, notification_events},
+    db::models::workspace_member::{WorkspaceMemberRole, member_change},
+    db::repositories::auth::AuthRepo,
     db::repositories::invitations::InvitationsRepo,
     db::repositories::workspace_members::WorkspaceMembersRepo,
+    db::repositories::workspaces::WorkspacesRepo,
     error::AppError,
     services::context::RequestContext,
+    services::email_service::EmailService,
+    services::notifications_service::NotificationsService,
+    services::workspace_email_domains_service::WorkspaceEmailDomainsService,
+    services::workspace_members_service::WorkspaceMembersService,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    validation::invitation::validate_invite_email,
 };
 
+/// Most entries accepted in one bulk invitation
+pub const MAX_BULK_INVITES: usize = 100;
+
 pub struct InvitationsService;
 
 impl InvitationsService {
@@ -17,6 +34,13 @@ impl InvitationsService {
         email: &str,
         role: crate::db::models::workspace_member::WorkspaceMemberRole,
     ) -> Result<Invitation, AppError> {
+        let settings = WorkspaceSettingsService::load(conn, ctx.workspace_id)?;
+        if !settings.allows_email(email) {
+            return Err(AppError::validation(format!(
+                "Invitations can only be sent to addresses at {}",
+                settings.allowed_email_domains.join(", ")
+            )));
+        }
         if InvitationsRepo::pending_exists_for_email(conn, ctx.workspace_id, email)? {
             return Err(AppError::conflict_with_code(
                 "User already has a pending invitation to this workspace",
@@ -31,16 +55,71 @@ impl InvitationsService {
             invited_by: ctx.user_id,
         };
         let inv = InvitationsRepo::insert(conn, &new_inv)?;
+        Self::notify_invitee(conn, ctx, &inv);
         Ok(inv)
     }
 
+    /// Notify the invitee if they already have an account
+    fn notify_invitee(conn: &mut PgConnection, ctx: &RequestContext, inv: &Invitation) {
+        let invitee = match AuthRepo::find_by_email(conn, &inv.email) {
+            Ok(Some(user)) => user,
+            _ => return,
+        };
+        let title = match WorkspacesRepo::find_by_id(conn, inv.workspace_id) {
+            Ok(Some(workspace)) => format!("Join {}", workspace.name),
+            _ => "Workspace invitation".to_string(),
+        };
+        NotificationsService::notify_quietly(
+            conn,
+            NewNotification {
+                workspace_id: ctx.workspace_id,
+                recipient_id: invitee.id,
+                actor_id: Some(ctx.user_id),
+                event_type: notification_events::INVITATION_RECEIVED.to_string(),
+                entity_type: "invitation".to_string(),
+                entity_id: inv.id,
+                title,
+                body: None,
+            },
+        );
+    }
+
+    /// Invitation emails for newly created invitations, sent by the caller
+    /// from the workspace's verified email domain if it has one
+    pub fn invitation_emails(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        invitations: &[Invitation],
+        app_url: &str,
+    ) -> Result<Vec<EmailMessage>, AppError> {
+        let inviter =
+            AuthRepo::find_by_id(conn, ctx.user_id)?.ok_or_else(|| AppError::not_found("user"))?;
+        let workspace = WorkspacesRepo::find_by_id(conn, ctx.workspace_id)?
+            .ok_or_else(|| AppError::not_found("workspace"))?;
+        let from = WorkspaceEmailDomainsService::sender_for(conn, ctx.workspace_id)?;
+        Ok(invitations
+            .iter()
+            .map(|inv| EmailMessage {
+                from: from.clone(),
+                ..EmailService::invitation_email(
+                    app_url,
+                    &inv.email,
+                    &inviter.name,
+                    &workspace.name,
+                    inv.id,
+                    inv.expires_at,
+                )
+            })
+            .collect())
+    }
+
     pub fn accept(
         conn: &mut PgConnection,
         ctx: &RequestContext,
         invitation_id: uuid::Uuid,
     ) -> Result<Invitation, AppError> {
         // ensure invitation belongs to email of current user? callsites should check
-        let updated = conn.transaction::<Invitation, diesel::result::Error, _>(|tx| {
+        let (updated, member) = conn.transaction::<_, diesel::result::Error, _>(|tx| {
             let inv =
                 InvitationsRepo::update_status(tx, invitation_id, InvitationStatus::Accepted)?;
             // add workspace member
@@ -49,9 +128,10 @@ impl InvitationsService {
                 workspace_id: inv.workspace_id,
                 role: inv.role.clone(),
             };
-            let _ = WorkspaceMembersRepo::insert(tx, &new_member)?;
-            Ok(inv)
+            let member = WorkspaceMembersRepo::insert(tx, &new_member)?;
+            Ok((inv, member))
         })?;
+        WorkspaceMembersService::publish_change(&member, member_change::MEMBER_ADDED);
         Ok(updated)
     }
 
@@ -74,6 +154,88 @@ impl InvitationsService {
         Ok(invitations)
     }
 
+    /// Invite every entry on its own, so one bad address does not stop the
+    /// rest. Existing members, pending invitations and repeated addresses are
+    /// skipped rather than failed.
+    pub fn bulk_invite(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &BulkInviteRequest,
+    ) -> Result<Vec<BulkInviteResult>, AppError> {
+        if req.invitations.is_empty() {
+            return Err(AppError::validation("At least one invitation is required"));
+        }
+        if req.invitations.len() > MAX_BULK_INVITES {
+            return Err(AppError::validation(format!(
+                "At most {} invitations can be sent at once",
+                MAX_BULK_INVITES
+            )));
+        }
+
+        let mut seen: Vec<String> = Vec::new();
+        let mut results = Vec::with_capacity(req.invitations.len());
+        for entry in &req.invitations {
+            let email = entry.email.trim().to_string();
+            let key = email.to_lowercase();
+            if seen.contains(&key) {
+                results.push(Self::skipped(email, "Duplicate of an earlier entry"));
+                continue;
+            }
+            seen.push(key);
+
+            let role = entry.role.clone().unwrap_or(WorkspaceMemberRole::Member);
+            let result = conn.transaction::<_, AppError, _>(|conn| {
+                Self::invite_entry(conn, ctx, email.clone(), role)
+            });
+            results.push(result.unwrap_or_else(|err| BulkInviteResult {
+                email,
+                status: bulk_invite_status::FAILED.to_string(),
+                invitation: None,
+                error_code: Some(err.code().as_str().to_string()),
+                message: Some(err.to_string()),
+            }));
+        }
+        Ok(results)
+    }
+
+    fn invite_entry(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        email: String,
+        role: WorkspaceMemberRole,
+    ) -> Result<BulkInviteResult, AppError> {
+        validate_invite_email(&email)?;
+        if role == WorkspaceMemberRole::Owner {
+            return Err(AppError::validation("Owners cannot be invited in bulk"));
+        }
+        if let Some(user) = AuthRepo::find_by_email(conn, &email)?
+            && WorkspaceMembersRepo::find(conn, ctx.workspace_id, user.id)?.is_some()
+        {
+            return Ok(Self::skipped(email, "Already a member"));
+        }
+        if InvitationsRepo::pending_exists_for_email(conn, ctx.workspace_id, &email)? {
+            return Ok(Self::skipped(email, "Already invited"));
+        }
+        let invitation = Self::create(conn, ctx, &email, role)?;
+        Ok(BulkInviteResult {
+            email,
+            status: bulk_invite_status::INVITED.to_string(),
+            invitation: Some(invitation),
+            error_code: None,
+            message: None,
+        })
+    }
+
+    fn skipped(email: String, message: &str) -> BulkInviteResult {
+        BulkInviteResult {
+            email,
+            status: bulk_invite_status::SKIPPED.to_string(),
+            invitation: None,
+            error_code: None,
+            message: Some(message.to_string()),
+        }
+    }
+
     pub fn get_user_invitations(
         conn: &mut PgConnection,
         ctx: &RequestContext,
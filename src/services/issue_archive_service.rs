@@ -0,0 +1,285 @@
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::issue_archive::{
+        AutoArchiveSummary, BulkArchivePreview, IssueArchiveBatch, IssueArchiveBatchResponse,
+        IssueArchiveFilter, NewIssueArchiveBatch, issue_archive_status,
+    },
+    db::repositories::audit_log::AuditLogRepo,
+    db::repositories::issue_archives::IssueArchivesRepo,
+    db::repositories::workspace_settings::WorkspaceSettingsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::issues_service::IssuesService,
+    services::member_imports_service::JOB_QUEUE,
+    services::permission_service::{Permission, PermissionService},
+    utils::clock,
+};
+
+/// Job name prefix; the task is `issue_archive:<batch id>`
+pub const JOB_PREFIX: &str = "issue_archive:";
+
+/// Matching issues listed by a dry run
+pub const SAMPLE_SIZE: i64 = 10;
+
+/// How long after a batch completes it can still be undone
+pub const UNDO_WINDOW_HOURS: i64 = 24;
+
+pub struct IssueArchiveService;
+
+impl IssueArchiveService {
+    /// Count and sample the issues a bulk archive would affect
+    pub fn preview(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        filter: &IssueArchiveFilter,
+    ) -> Result<BulkArchivePreview, AppError> {
+        PermissionService::require(conn, ctx, Permission::BulkArchiveIssues)?;
+        Self::validate_filter(filter)?;
+        Ok(BulkArchivePreview {
+            count: IssueArchivesRepo::count_matching(conn, ctx.workspace_id, filter)?,
+            sample: IssueArchivesRepo::sample_matching(
+                conn,
+                ctx.workspace_id,
+                filter,
+                SAMPLE_SIZE,
+            )?,
+        })
+    }
+
+    /// Record a bulk archive for the worker to run. The filter is applied
+    /// when the batch runs, so the count may differ from the dry run.
+    pub fn request(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        filter: &IssueArchiveFilter,
+    ) -> Result<IssueArchiveBatchResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::BulkArchiveIssues)?;
+        Self::validate_filter(filter)?;
+        let filter_json = serde_json::to_string(filter)
+            .map_err(|e| AppError::internal(format!("Failed to serialize filter: {}", e)))?;
+
+        let batch = conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            let batch = IssueArchivesRepo::insert_batch(
+                tx,
+                &NewIssueArchiveBatch {
+                    workspace_id: ctx.workspace_id,
+                    requested_by: ctx.user_id,
+                    filter: filter_json.clone(),
+                },
+            )?;
+            AuditLogRepo::insert(
+                tx,
+                &NewAuditEntry {
+                    workspace_id: ctx.workspace_id,
+                    actor_id: Some(ctx.user_id),
+                    action: audit_actions::ISSUES_BULK_ARCHIVED.to_string(),
+                    target_type: "issue_archive_batch".to_string(),
+                    target_id: Some(batch.id),
+                    details: Some(filter_json.clone()),
+                },
+            )?;
+            Ok(batch)
+        })?;
+        Ok(Self::response(batch))
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        batch_id: Uuid,
+    ) -> Result<IssueArchiveBatchResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::BulkArchiveIssues)?;
+        let batch = IssueArchivesRepo::find_batch_in_workspace(conn, ctx.workspace_id, batch_id)?
+            .ok_or_else(|| AppError::not_found("issue_archive_batch"))?;
+        Ok(Self::response(batch))
+    }
+
+    /// Restore the issues of a completed batch within the undo window
+    pub fn undo(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        batch_id: Uuid,
+    ) -> Result<IssueArchiveBatchResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::BulkArchiveIssues)?;
+        let batch = IssueArchivesRepo::find_batch_in_workspace(conn, ctx.workspace_id, batch_id)?
+            .ok_or_else(|| AppError::not_found("issue_archive_batch"))?;
+        if batch.status != issue_archive_status::COMPLETED {
+            return Err(AppError::conflict_with_code(
+                format!(
+                    "Only completed batches can be undone, this one is {}",
+                    batch.status
+                ),
+                None,
+                "ARCHIVE_NOT_COMPLETED",
+            ));
+        }
+        if Self::undo_until(&batch).is_none_or(|until| clock::now() > until) {
+            return Err(AppError::conflict_with_code(
+                format!(
+                    "Bulk archives can only be undone within {} hours",
+                    UNDO_WINDOW_HOURS
+                ),
+                None,
+                "UNDO_WINDOW_EXPIRED",
+            ));
+        }
+
+        let batch = conn.transaction::<_, AppError, _>(|tx| {
+            let batch = IssueArchivesRepo::mark_undone(tx, batch_id)?.ok_or_else(|| {
+                AppError::conflict_with_code(
+                    "The batch was already undone",
+                    None,
+                    "ARCHIVE_NOT_COMPLETED",
+                )
+            })?;
+            let restored = IssueArchivesRepo::unarchive_batch(tx, batch_id)?;
+            AuditLogRepo::insert(
+                tx,
+                &NewAuditEntry {
+                    workspace_id: ctx.workspace_id,
+                    actor_id: Some(ctx.user_id),
+                    action: audit_actions::ISSUES_BULK_ARCHIVE_UNDONE.to_string(),
+                    target_type: "issue_archive_batch".to_string(),
+                    target_id: Some(batch_id),
+                    details: Some(serde_json::json!({ "restored": restored }).to_string()),
+                },
+            )?;
+            Ok(batch)
+        })?;
+        Ok(Self::response(batch))
+    }
+
+    /// Push the batch onto the worker queue
+    pub async fn enqueue(redis: &redis::Client, batch_id: Uuid) -> redis::RedisResult<()> {
+        use redis::AsyncCommands;
+        let mut conn = redis.get_multiplexed_async_connection().await?;
+        conn.rpush(JOB_QUEUE, format!("{}{}", JOB_PREFIX, batch_id))
+            .await
+    }
+
+    /// Batch id of a queued job, if the task is a bulk archive
+    pub fn parse_job(task: &str) -> Option<Uuid> {
+        task.strip_prefix(JOB_PREFIX)?.parse().ok()
+    }
+
+    /// Archive the issues matching a pending batch. Returns `None` when the
+    /// batch is not pending, so duplicate jobs are harmless.
+    pub fn run(
+        conn: &mut PgConnection,
+        batch_id: Uuid,
+    ) -> Result<Option<IssueArchiveBatch>, AppError> {
+        let Some(batch) = IssueArchivesRepo::claim(conn, batch_id)? else {
+            return Ok(None);
+        };
+        let filter: IssueArchiveFilter = serde_json::from_str(&batch.filter)
+            .map_err(|e| AppError::internal(format!("Invalid archive filter: {}", e)))?;
+        let batch = conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            let archived =
+                IssueArchivesRepo::archive_matching(tx, batch.workspace_id, &filter, batch.id)?;
+            IssueArchivesRepo::complete(tx, batch.id, archived as i32)
+        })?;
+        Ok(Some(batch))
+    }
+
+    /// Pending batches older than `age`, whose queue task was probably lost
+    pub fn stale_pending(conn: &mut PgConnection, age: Duration) -> Result<Vec<Uuid>, AppError> {
+        Ok(IssueArchivesRepo::list_stale_pending(
+            conn,
+            clock::now() - age,
+        )?)
+    }
+
+    /// Archive completed issues left untouched for longer than each
+    /// workspace's `archive_completed_after_days`, recording every issue in
+    /// the audit log. A failing workspace is logged and skipped so the
+    /// others still run.
+    pub fn run_auto_archive(conn: &mut PgConnection) -> Result<AutoArchiveSummary, AppError> {
+        let now = clock::now();
+        let mut summary = AutoArchiveSummary::default();
+        for (workspace_id, days) in WorkspaceSettingsRepo::list_auto_archive(conn)? {
+            let archived = conn.transaction::<_, diesel::result::Error, _>(|tx| {
+                let ids = IssueArchivesRepo::archive_completed(
+                    tx,
+                    workspace_id,
+                    Self::auto_archive_cutoff(now, days),
+                    now,
+                )?;
+                let details = serde_json::json!({ "archive_completed_after_days": days });
+                for id in &ids {
+                    AuditLogRepo::insert(
+                        tx,
+                        &NewAuditEntry {
+                            workspace_id,
+                            actor_id: None,
+                            action: audit_actions::ISSUE_AUTO_ARCHIVED.to_string(),
+                            target_type: "issue".to_string(),
+                            target_id: Some(*id),
+                            details: Some(details.to_string()),
+                        },
+                    )?;
+                }
+                Ok(ids.len())
+            });
+            match archived {
+                Ok(0) => {}
+                Ok(archived) => {
+                    summary.workspaces += 1;
+                    summary.archived += archived;
+                }
+                Err(e) => {
+                    tracing::warn!("Auto-archive for workspace {} failed: {}", workspace_id, e)
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Completed issues last updated before this are due for auto-archiving
+    pub fn auto_archive_cutoff(now: DateTime<Utc>, days: i32) -> DateTime<Utc> {
+        now - Duration::days(days as i64)
+    }
+
+    /// A bulk archive needs at least one condition, so an empty filter can't
+    /// archive the whole workspace
+    pub fn validate_filter(filter: &IssueArchiveFilter) -> Result<(), AppError> {
+        if filter.is_empty() {
+            return Err(AppError::validation(
+                "filter must have at least one condition",
+            ));
+        }
+        if let Some(priority) = &filter.priority {
+            IssuesService::parse_priority(priority)?;
+        }
+        Ok(())
+    }
+
+    /// End of the undo window; `None` until the batch has completed
+    pub fn undo_until(batch: &IssueArchiveBatch) -> Option<DateTime<Utc>> {
+        batch
+            .completed_at
+            .map(|completed| completed + Duration::hours(UNDO_WINDOW_HOURS))
+    }
+
+    fn response(batch: IssueArchiveBatch) -> IssueArchiveBatchResponse {
+        let undo_until = if batch.status == issue_archive_status::COMPLETED {
+            Self::undo_until(&batch)
+        } else {
+            None
+        };
+        IssueArchiveBatchResponse {
+            id: batch.id,
+            requested_by: batch.requested_by,
+            filter: serde_json::from_str(&batch.filter).unwrap_or_default(),
+            status: batch.status,
+            issue_count: batch.issue_count,
+            created_at: batch.created_at,
+            completed_at: batch.completed_at,
+            undone_at: batch.undone_at,
+            undo_until,
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Related rows shown next to each issue of a list response. Instead of
+//! looking up the team, states, assignee, labels and project per issue, the
+//! ids of a whole page are collected first and each kind is loaded with a
+//! single `= ANY(...)` query, then joined in memory.
+use std::collections::{BTreeSet, HashMap};
+
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::auth::{User, UserBasicInfo},
+    db::models::issue::{Issue, IssueResponse},
+    db::models::label::Label,
+    db::models::project::ProjectInfo,
+    db::models::project_status::ProjectStatusInfo,
+    db::models::team::TeamBasicInfo,
+    db::models::workflow::{WorkflowState, WorkflowStateResponse},
+    db::repositories::auth::AuthRepo,
+    db::repositories::issue_views::IssueViewsRepo,
+    db::repositories::labels::LabelRepo,
+    db::repositories::project_statuses::ProjectStatusRepo,
+    db::repositories::projects::ProjectsRepo,
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::issue_moves_service::IssueMovesService,
+};
+
+/// Distinct ids a page of issues refers to, by kind of related row
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EnrichmentKeys {
+    pub issue_ids: Vec<Uuid>,
+    pub team_ids: Vec<Uuid>,
+    pub workflow_ids: Vec<Uuid>,
+    /// Teams of issues without a workflow, which show the team's default
+    /// states
+    pub default_state_team_ids: Vec<Uuid>,
+    pub assignee_ids: Vec<Uuid>,
+    pub project_ids: Vec<Uuid>,
+}
+
+impl EnrichmentKeys {
+    pub fn collect(issues: &[Issue]) -> Self {
+        let distinct = |ids: &mut dyn Iterator<Item = Uuid>| -> Vec<Uuid> {
+            ids.collect::<BTreeSet<_>>().into_iter().collect()
+        };
+        Self {
+            issue_ids: distinct(&mut issues.iter().map(|issue| issue.id)),
+            team_ids: distinct(&mut issues.iter().map(|issue| issue.team_id)),
+            workflow_ids: distinct(&mut issues.iter().filter_map(|issue| issue.workflow_id)),
+            default_state_team_ids: distinct(
+                &mut issues
+                    .iter()
+                    .filter(|issue| issue.workflow_id.is_none())
+                    .map(|issue| issue.team_id),
+            ),
+            assignee_ids: distinct(&mut issues.iter().filter_map(|issue| issue.assignee_id)),
+            project_ids: distinct(&mut issues.iter().filter_map(|issue| issue.project_id)),
+        }
+    }
+
+    /// Most queries [`IssueEnrichment::load`] runs for these keys; it
+    /// doesn't grow with the number of issues
+    pub fn query_count(&self) -> usize {
+        if self.issue_ids.is_empty() {
+            return 0;
+        }
+        // Last views, teams and labels
+        let mut queries = 3;
+        queries += usize::from(!self.workflow_ids.is_empty());
+        queries += usize::from(!self.default_state_team_ids.is_empty());
+        // Projects and the workspace's project statuses
+        queries += 2 * usize::from(!self.project_ids.is_empty());
+        // Assignees and project owners together
+        queries += usize::from(!self.assignee_ids.is_empty() || !self.project_ids.is_empty());
+        queries
+    }
+}
+
+/// Related rows of a page of issues, keyed for the in-memory join
+#[derive(Default)]
+pub struct IssueEnrichment {
+    pub last_viewed: HashMap<Uuid, chrono::DateTime<chrono::Utc>>,
+    pub teams: HashMap<Uuid, TeamBasicInfo>,
+    /// By workflow id, in position order
+    pub workflow_states: HashMap<Uuid, Vec<WorkflowState>>,
+    /// By team id, in position order
+    pub default_states: HashMap<Uuid, Vec<WorkflowState>>,
+    pub users: HashMap<Uuid, UserBasicInfo>,
+    /// By issue id
+    pub labels: HashMap<Uuid, Vec<Label>>,
+    pub projects: HashMap<Uuid, ProjectInfo>,
+}
+
+impl IssueEnrichment {
+    /// Load everything `issues` refer to with at most
+    /// [`EnrichmentKeys::query_count`] queries
+    pub fn load(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issues: &[Issue],
+    ) -> Result<Self, AppError> {
+        let keys = EnrichmentKeys::collect(issues);
+        let mut enrichment = Self::default();
+        if keys.issue_ids.is_empty() {
+            return Ok(enrichment);
+        }
+
+        enrichment.last_viewed =
+            IssueViewsRepo::last_viewed_map(conn, ctx.user_id, &keys.issue_ids)?;
+        enrichment.teams = TeamsRepo::list_by_ids(conn, &keys.team_ids)?
+            .into_iter()
+            .map(|team| {
+                (
+                    team.id,
+                    TeamBasicInfo {
+                        id: team.id,
+                        name: team.name,
+                        team_key: team.team_key,
+                        description: team.description,
+                        icon_url: team.icon_url,
+                        is_private: team.is_private,
+                    },
+                )
+            })
+            .collect();
+        if !keys.workflow_ids.is_empty() {
+            for state in WorkflowsRepo::list_states_by_workflows(conn, &keys.workflow_ids)? {
+                enrichment
+                    .workflow_states
+                    .entry(state.workflow_id)
+                    .or_default()
+                    .push(state);
+            }
+        }
+        if !keys.default_state_team_ids.is_empty() {
+            for (team_id, state) in WorkflowsRepo::list_team_default_states_for_teams(
+                conn,
+                &keys.default_state_team_ids,
+            )? {
+                enrichment
+                    .default_states
+                    .entry(team_id)
+                    .or_default()
+                    .push(state);
+            }
+        }
+        for (issue_id, label) in LabelRepo::list_by_issues(conn, &keys.issue_ids)? {
+            enrichment.labels.entry(issue_id).or_default().push(label);
+        }
+
+        let projects = if keys.project_ids.is_empty() {
+            Vec::new()
+        } else {
+            ProjectsRepo::list_by_ids_in_workspace(conn, ctx.workspace_id, &keys.project_ids)?
+        };
+        let mut user_ids = keys.assignee_ids.clone();
+        user_ids.extend(projects.iter().map(|project| project.owner_id));
+        user_ids.sort();
+        user_ids.dedup();
+        if !user_ids.is_empty() {
+            enrichment.users = AuthRepo::list_by_ids(conn, &user_ids)?
+                .into_iter()
+                .map(|user: User| {
+                    (
+                        user.id,
+                        UserBasicInfo {
+                            id: user.id,
+                            name: user.name,
+                            username: user.username,
+                            email: user.email,
+                            avatar_url: user.avatar_url,
+                        },
+                    )
+                })
+                .collect();
+        }
+        if !projects.is_empty() {
+            let available_statuses: Vec<ProjectStatusInfo> =
+                ProjectStatusRepo::list_by_workspace(conn, ctx.workspace_id)?
+                    .into_iter()
+                    .map(ProjectStatusInfo::from)
+                    .collect();
+            for project in projects {
+                let status = available_statuses
+                    .iter()
+                    .find(|status| status.id == project.project_status_id);
+                let owner = enrichment.users.get(&project.owner_id);
+                let (Some(status), Some(owner)) = (status, owner) else {
+                    continue;
+                };
+                enrichment.projects.insert(
+                    project.id,
+                    ProjectInfo {
+                        id: project.id,
+                        name: project.name,
+                        project_key: project.project_key,
+                        description: project.description,
+                        status: status.clone(),
+                        available_statuses: available_statuses.clone(),
+                        owner: owner.clone(),
+                        target_date: project.target_date,
+                        priority: project.priority,
+                        budget_currency: project.budget_currency,
+                        budget_amount_cents: project.budget_amount_cents,
+                        created_at: project.created_at,
+                        updated_at: project.updated_at,
+                    },
+                );
+            }
+        }
+        Ok(enrichment)
+    }
+
+    /// Response of `issue` with its related rows filled in
+    pub fn apply(&self, issue: Issue) -> IssueResponse {
+        let id = issue.id;
+        let team_id = issue.team_id;
+        let workflow_id = issue.workflow_id;
+        let assignee_id = issue.assignee_id;
+        let project_id = issue.project_id;
+        let issue_number = issue.issue_number;
+
+        let mut resp = IssueResponse::from(issue);
+        resp.set_last_viewed(self.last_viewed.get(&id).copied());
+        if let Some(team) = self.teams.get(&team_id) {
+            resp.team_key = Some(team.team_key.clone());
+            resp.identifier = Some(IssueMovesService::issue_key(&team.team_key, issue_number));
+            resp.team = Some(team.clone());
+        }
+        let states = match workflow_id {
+            Some(workflow_id) => self.workflow_states.get(&workflow_id),
+            None => self.default_states.get(&team_id),
+        };
+        resp.workflow_states = states
+            .into_iter()
+            .flatten()
+            .cloned()
+            .map(WorkflowStateResponse::from)
+            .collect();
+        resp.assignee = assignee_id.and_then(|user_id| self.users.get(&user_id).cloned());
+        resp.labels = self.labels.get(&id).cloned().unwrap_or_default();
+        resp.project = project_id.and_then(|project_id| self.projects.get(&project_id).cloned());
+        resp
+    }
+}
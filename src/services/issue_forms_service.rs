@@ -0,0 +1,673 @@
+use diesel::prelude::*;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::issue::{Issue, NewIssue, NewIssueLabel},
+    db::models::issue_form::{
+        CreateIssueFormRequest, FormField, FormSubmissionIssue, IssueForm, IssueFormResponse,
+        IssueFormSubmission, NewIssueForm, NewIssueFormSubmission, PublicIssueFormResponse,
+        SubmitIssueFormRequest, UpdateIssueForm, UpdateIssueFormRequest, form_field_targets,
+        form_field_types,
+    },
+    db::models::workspace_settings::workspace_features,
+    db::repositories::issue_forms::IssueFormsRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::error_tracking_service::ErrorTrackingService,
+    services::issue_label_rules_service::IssueLabelRulesService,
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    services::teams_service::TeamsService,
+    services::webhook_service::WebhookService,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    utils::clock,
+    validation::issue::validate_create_issue,
+    websocket::{EntityAction, EntityKind, Topic},
+};
+
+const MAX_FIELDS: usize = 50;
+const MAX_KEY_CHARS: usize = 64;
+const MAX_TEXT_CHARS: usize = 255;
+const MAX_TEXTAREA_CHARS: usize = 5000;
+const PRIORITIES: [&str; 5] = ["none", "low", "medium", "high", "urgent"];
+
+pub struct IssueFormsService;
+
+impl IssueFormsService {
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<Vec<IssueFormResponse>, AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        IssueFormsRepo::list_by_team(conn, team_id)?
+            .into_iter()
+            .map(Self::response)
+            .collect()
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        form_id: Uuid,
+    ) -> Result<IssueFormResponse, AppError> {
+        Self::response(Self::find(conn, ctx, form_id)?)
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+        req: &CreateIssueFormRequest,
+    ) -> Result<IssueFormResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageTeams)?;
+        TeamsService::get(conn, ctx, team_id)?;
+        let name = Self::validate_name(&req.name)?;
+        Self::validate_fields(&req.fields)?;
+        Self::check_labels(conn, ctx.workspace_id, &req.fields)?;
+
+        let form = IssueFormsRepo::insert(
+            conn,
+            &NewIssueForm {
+                workspace_id: ctx.workspace_id,
+                team_id,
+                name,
+                description: Self::normalize_description(req.description.as_deref()),
+                fields: Self::encode_fields(&req.fields)?,
+                is_public: req.is_public,
+                public_slug: req.is_public.then(Self::new_slug),
+                created_by: ctx.user_id,
+            },
+        )?;
+        Self::response(form)
+    }
+
+    /// Change a form; publishing it for the first time gives it a portal slug
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        form_id: Uuid,
+        req: &UpdateIssueFormRequest,
+    ) -> Result<IssueFormResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageTeams)?;
+        let form = Self::find(conn, ctx, form_id)?;
+
+        let mut changes = UpdateIssueForm {
+            updated_at: Some(clock::now()),
+            ..Default::default()
+        };
+        if let Some(name) = &req.name {
+            changes.name = Some(Self::validate_name(name)?);
+        }
+        if let Some(description) = &req.description {
+            changes.description = Some(Self::normalize_description(Some(description)));
+        }
+        if let Some(fields) = &req.fields {
+            Self::validate_fields(fields)?;
+            Self::check_labels(conn, ctx.workspace_id, fields)?;
+            changes.fields = Some(Self::encode_fields(fields)?);
+        }
+        if let Some(is_public) = req.is_public {
+            changes.is_public = Some(is_public);
+            if is_public && form.public_slug.is_none() {
+                changes.public_slug = Some(Some(Self::new_slug()));
+            }
+        }
+
+        Self::response(IssueFormsRepo::update(conn, form.id, &changes)?)
+    }
+
+    /// Delete a form; issues filed through it stay
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        form_id: Uuid,
+    ) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageTeams)?;
+        let form = Self::find(conn, ctx, form_id)?;
+        IssueFormsRepo::delete(conn, form.id)?;
+        Ok(())
+    }
+
+    /// File an issue from a member's answers to the form
+    pub fn submit(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        form_id: Uuid,
+        req: &SubmitIssueFormRequest,
+    ) -> Result<Issue, AppError> {
+        PermissionService::require(conn, ctx, Permission::CreateIssue)?;
+        let form = Self::find(conn, ctx, form_id)?;
+        let (issue, _) = Self::file(conn, &form, req, Some(ctx.user_id), None)?;
+        Ok(issue)
+    }
+
+    /// Published form as the portal shows it
+    pub fn get_public(
+        conn: &mut PgConnection,
+        slug: &str,
+    ) -> Result<PublicIssueFormResponse, AppError> {
+        let form = Self::find_public(conn, slug)?;
+        Ok(PublicIssueFormResponse {
+            fields: Self::decode_fields(&form.fields)?,
+            name: form.name,
+            description: form.description,
+        })
+    }
+
+    /// File an issue from a portal submission. The issue is created on
+    /// behalf of the form's author and records the submitter's email.
+    pub fn submit_public(
+        conn: &mut PgConnection,
+        slug: &str,
+        req: &SubmitIssueFormRequest,
+    ) -> Result<IssueFormSubmission, AppError> {
+        let form = Self::find_public(conn, slug)?;
+        let email = req
+            .email
+            .as_deref()
+            .map(str::trim)
+            .filter(|email| !email.is_empty())
+            .ok_or_else(|| AppError::validation("Email is required"))?;
+        if !Self::is_email(email) || email.chars().count() > MAX_TEXT_CHARS {
+            return Err(AppError::validation("Invalid email format"));
+        }
+        let (_, submission) = Self::file(conn, &form, req, None, Some(email))?;
+        Ok(submission)
+    }
+
+    /// Check a form definition: unique keys, known types and targets,
+    /// options for select fields and exactly one required text field filling
+    /// the issue title
+    pub fn validate_fields(fields: &[FormField]) -> Result<(), AppError> {
+        if fields.len() > MAX_FIELDS {
+            return Err(AppError::validation(format!(
+                "A form has at most {} fields",
+                MAX_FIELDS
+            )));
+        }
+
+        let mut keys = HashSet::new();
+        let mut targets = HashSet::new();
+        for field in fields {
+            let key = field.key.as_str();
+            if key.is_empty()
+                || key.len() > MAX_KEY_CHARS
+                || !key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            {
+                return Err(AppError::validation(format!(
+                    "Field key '{}' must be 1-{} lowercase letters, digits or underscores",
+                    key, MAX_KEY_CHARS
+                )));
+            }
+            if !keys.insert(key) {
+                return Err(AppError::validation(format!(
+                    "Field key '{}' is used twice",
+                    key
+                )));
+            }
+            if field.label.trim().is_empty() {
+                return Err(AppError::validation(format!(
+                    "Field '{}' needs a label",
+                    key
+                )));
+            }
+
+            let field_type = field.field_type.as_str();
+            let is_select = matches!(
+                field_type,
+                form_field_types::SELECT | form_field_types::MULTI_SELECT
+            );
+            if !is_select
+                && !matches!(
+                    field_type,
+                    form_field_types::TEXT
+                        | form_field_types::TEXTAREA
+                        | form_field_types::NUMBER
+                        | form_field_types::EMAIL
+                        | form_field_types::CHECKBOX
+                )
+            {
+                return Err(AppError::validation(format!(
+                    "Field '{}' has unknown type '{}'",
+                    key, field_type
+                )));
+            }
+            if is_select {
+                if field.options.is_empty() {
+                    return Err(AppError::validation(format!(
+                        "Field '{}' needs at least one option",
+                        key
+                    )));
+                }
+                let mut values = HashSet::new();
+                if field
+                    .options
+                    .iter()
+                    .any(|option| option.value.is_empty() || !values.insert(&option.value))
+                {
+                    return Err(AppError::validation(format!(
+                        "Options of field '{}' need distinct, non-empty values",
+                        key
+                    )));
+                }
+            } else if !field.options.is_empty() {
+                return Err(AppError::validation(format!(
+                    "Field '{}' of type '{}' takes no options",
+                    key, field_type
+                )));
+            }
+
+            let Some(target) = field.maps_to.as_deref() else {
+                if field.options.iter().any(|option| option.label_id.is_some()) {
+                    return Err(AppError::validation(format!(
+                        "Only fields mapped to labels can set label_id on options (field '{}')",
+                        key
+                    )));
+                }
+                continue;
+            };
+            let type_fits = match target {
+                form_field_targets::TITLE => field_type == form_field_types::TEXT,
+                form_field_targets::DESCRIPTION => {
+                    matches!(
+                        field_type,
+                        form_field_types::TEXT | form_field_types::TEXTAREA
+                    )
+                }
+                form_field_targets::PRIORITY => {
+                    field_type == form_field_types::SELECT
+                        && field
+                            .options
+                            .iter()
+                            .all(|option| PRIORITIES.contains(&option.value.as_str()))
+                }
+                form_field_targets::LABELS => {
+                    is_select && field.options.iter().all(|option| option.label_id.is_some())
+                }
+                _ => {
+                    return Err(AppError::validation(format!(
+                        "Field '{}' maps to unknown target '{}'",
+                        key, target
+                    )));
+                }
+            };
+            if !type_fits {
+                return Err(AppError::validation(format!(
+                    "Field '{}' cannot map to {}: title takes a text field, description a text or textarea field, priority a select of priorities and labels a select whose options all have a label_id",
+                    key, target
+                )));
+            }
+            if target != form_field_targets::LABELS && !targets.insert(target) {
+                return Err(AppError::validation(format!(
+                    "Only one field can map to {}",
+                    target
+                )));
+            }
+            if target != form_field_targets::LABELS
+                && field.options.iter().any(|option| option.label_id.is_some())
+            {
+                return Err(AppError::validation(format!(
+                    "Only fields mapped to labels can set label_id on options (field '{}')",
+                    key
+                )));
+            }
+            if target == form_field_targets::TITLE && !field.required {
+                return Err(AppError::validation(format!(
+                    "Title field '{}' must be required",
+                    key
+                )));
+            }
+        }
+
+        if !targets.contains(form_field_targets::TITLE) {
+            return Err(AppError::validation(
+                "A form needs a required text field mapped to title",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check answers against the form and read the issue out of them. All
+    /// problems are reported together so the submitter can fix them at once.
+    pub fn validate_submission(
+        fields: &[FormField],
+        answers: &BTreeMap<String, Value>,
+    ) -> Result<FormSubmissionIssue, AppError> {
+        let mut errors = Vec::new();
+        for key in answers.keys() {
+            if !fields.iter().any(|field| &field.key == key) {
+                errors.push(format!("'{}' is not a field of this form", key));
+            }
+        }
+
+        let mut issue = FormSubmissionIssue::default();
+        let mut description = None;
+        let mut details = Vec::new();
+        for field in fields {
+            let answer = answers
+                .get(&field.key)
+                .filter(|value| !Self::is_blank(value));
+            let Some(answer) = answer else {
+                if field.required {
+                    errors.push(format!("{} is required", field.label));
+                }
+                continue;
+            };
+            let text = match Self::answer_text(field, answer) {
+                Ok(text) => text,
+                Err(problem) => {
+                    errors.push(format!("{} {}", field.label, problem));
+                    continue;
+                }
+            };
+
+            match field.maps_to.as_deref() {
+                Some(form_field_targets::TITLE) => issue.title = text,
+                Some(form_field_targets::DESCRIPTION) => description = Some(text),
+                Some(form_field_targets::PRIORITY) => issue.priority = Some(text),
+                Some(form_field_targets::LABELS) => {
+                    for value in Self::chosen_values(answer) {
+                        let label = field
+                            .options
+                            .iter()
+                            .find(|option| option.value == value)
+                            .and_then(|option| option.label_id);
+                        if let Some(label) = label
+                            && !issue.label_ids.contains(&label)
+                        {
+                            issue.label_ids.push(label);
+                        }
+                    }
+                }
+                _ => details.push(format!(
+                    "**{}**\n{}",
+                    field.label,
+                    Self::option_labels(field, &text)
+                )),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::validation(errors.join("; ")));
+        }
+        let sections: Vec<String> = description.into_iter().chain(details).collect();
+        issue.description = (!sections.is_empty()).then(|| sections.join("\n\n"));
+        Ok(issue)
+    }
+
+    fn file(
+        conn: &mut PgConnection,
+        form: &IssueForm,
+        req: &SubmitIssueFormRequest,
+        submitted_by: Option<Uuid>,
+        submitter_email: Option<&str>,
+    ) -> Result<(Issue, IssueFormSubmission), AppError> {
+        let fields = Self::decode_fields(&form.fields)?;
+        let mut values = Self::validate_submission(&fields, &req.answers)?;
+        if let Some(email) = submitter_email {
+            let contact = format!("Submitted through the portal by {}", email);
+            values.description = Some(match values.description {
+                Some(description) => format!("{}\n\n{}", description, contact),
+                None => contact,
+            });
+        }
+        validate_create_issue(&values.title, &values.description, &form.team_id)?;
+        let answers = serde_json::to_string(&req.answers)
+            .map_err(|e| AppError::internal(format!("Failed to encode answers: {}", e)))?;
+
+        let (issue, submission) = conn.transaction::<_, AppError, _>(|conn| {
+            let states = WorkflowsRepo::list_states_by_team(conn, form.team_id)?;
+            let state = ErrorTrackingService::open_state(&states);
+            let issue = IssueRepo::insert(
+                conn,
+                &NewIssue {
+                    project_id: None,
+                    cycle_id: None,
+                    creator_id: submitted_by.unwrap_or(form.created_by),
+                    assignee_id: None,
+                    parent_issue_id: None,
+                    title: values.title.trim().to_string(),
+                    description: values.description.clone(),
+                    priority: values.priority.clone(),
+                    is_changelog_candidate: Some(false),
+                    team_id: form.team_id,
+                    workflow_id: state.map(|s| s.workflow_id),
+                    workflow_state_id: state.map(|s| s.id),
+                    estimate: None,
+                },
+            )?;
+            if !values.label_ids.is_empty() {
+                // Labels deleted since the form was saved are skipped
+                let label_ids =
+                    IssueFormsRepo::existing_labels(conn, form.workspace_id, &values.label_ids)?;
+                let rows: Vec<NewIssueLabel> = label_ids
+                    .into_iter()
+                    .map(|label_id| NewIssueLabel {
+                        issue_id: issue.id,
+                        label_id,
+                    })
+                    .collect();
+                diesel::insert_into(crate::schema::issue_labels::table)
+                    .values(&rows)
+                    .execute(conn)?;
+            }
+            let issue = IssueLabelRulesService::apply_quietly(conn, issue);
+            let submission = IssueFormsRepo::insert_submission(
+                conn,
+                &NewIssueFormSubmission {
+                    form_id: form.id,
+                    issue_id: issue.id,
+                    submitted_by,
+                    submitter_email: submitter_email.map(str::to_string),
+                    answers,
+                },
+            )?;
+            Ok((issue, submission))
+        })?;
+
+        WebhookService::emit_quietly(
+            conn,
+            form.workspace_id,
+            webhook_events::ISSUE_CREATED,
+            &issue,
+        );
+        RealtimeService::publish(
+            form.workspace_id,
+            Topic::Issue(issue.id),
+            webhook_events::ISSUE_CREATED,
+            &issue,
+        );
+        RealtimeService::entity_changed(
+            form.workspace_id,
+            EntityKind::Issue,
+            EntityAction::Created,
+            issue.id,
+            &issue,
+        );
+        Ok((issue, submission))
+    }
+
+    /// Answer as text, or what is wrong with it
+    fn answer_text(field: &FormField, answer: &Value) -> Result<String, &'static str> {
+        match field.field_type.as_str() {
+            form_field_types::TEXT | form_field_types::TEXTAREA | form_field_types::EMAIL => {
+                let text = answer.as_str().ok_or("must be text")?.trim();
+                let limit = if field.field_type == form_field_types::TEXTAREA {
+                    MAX_TEXTAREA_CHARS
+                } else {
+                    MAX_TEXT_CHARS
+                };
+                if text.chars().count() > limit {
+                    return Err("is too long");
+                }
+                if field.field_type == form_field_types::EMAIL && !Self::is_email(text) {
+                    return Err("must be an email address");
+                }
+                Ok(text.to_string())
+            }
+            form_field_types::NUMBER => match answer {
+                Value::Number(number) => Ok(number.to_string()),
+                _ => Err("must be a number"),
+            },
+            form_field_types::CHECKBOX => match answer {
+                Value::Bool(checked) => Ok(if *checked { "Yes" } else { "No" }.to_string()),
+                _ => Err("must be true or false"),
+            },
+            form_field_types::SELECT => {
+                let value = answer.as_str().ok_or("must be one of the options")?;
+                if !field.options.iter().any(|option| option.value == value) {
+                    return Err("must be one of the options");
+                }
+                Ok(value.to_string())
+            }
+            _ => {
+                let values = answer.as_array().ok_or("must be a list of options")?;
+                let mut chosen = Vec::new();
+                for value in values {
+                    let value = value.as_str().ok_or("must be a list of options")?;
+                    if !field.options.iter().any(|option| option.value == value) {
+                        return Err("must only contain options of the field");
+                    }
+                    if !chosen.contains(&value) {
+                        chosen.push(value);
+                    }
+                }
+                Ok(chosen.join(", "))
+            }
+        }
+    }
+
+    /// Chosen option labels of a select answer, the answer itself otherwise
+    fn option_labels(field: &FormField, text: &str) -> String {
+        if field.options.is_empty() {
+            return text.to_string();
+        }
+        text.split(", ")
+            .map(|value| {
+                field
+                    .options
+                    .iter()
+                    .find(|option| option.value == value)
+                    .map_or(value, |option| option.label.as_str())
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn chosen_values(answer: &Value) -> Vec<&str> {
+        match answer {
+            Value::String(value) => vec![value.as_str()],
+            Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn is_blank(value: &Value) -> bool {
+        match value {
+            Value::Null => true,
+            Value::String(text) => text.trim().is_empty(),
+            Value::Array(values) => values.is_empty(),
+            _ => false,
+        }
+    }
+
+    fn is_email(text: &str) -> bool {
+        text.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty() && domain.contains('.') && !text.contains(char::is_whitespace)
+        })
+    }
+
+    fn check_labels(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        fields: &[FormField],
+    ) -> Result<(), AppError> {
+        let label_ids: Vec<Uuid> = fields
+            .iter()
+            .flat_map(|field| field.options.iter().filter_map(|option| option.label_id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if label_ids.is_empty() {
+            return Ok(());
+        }
+        let found = IssueFormsRepo::existing_labels(conn, workspace_id, &label_ids)?;
+        if found.len() != label_ids.len() {
+            return Err(AppError::validation("Invalid label_ids for workspace"));
+        }
+        Ok(())
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        form_id: Uuid,
+    ) -> Result<IssueForm, AppError> {
+        IssueFormsRepo::find_in_workspace(conn, ctx.workspace_id, form_id)?
+            .ok_or_else(|| AppError::not_found("issue_form"))
+    }
+
+    /// Published form by slug; forms of workspaces that turned public forms
+    /// off are not found
+    fn find_public(conn: &mut PgConnection, slug: &str) -> Result<IssueForm, AppError> {
+        let form = IssueFormsRepo::find_public(conn, slug)?
+            .ok_or_else(|| AppError::not_found("issue_form"))?;
+        if !WorkspaceSettingsService::feature_enabled(
+            conn,
+            form.workspace_id,
+            workspace_features::PUBLIC_ISSUE_FORMS,
+        )? {
+            return Err(AppError::not_found("issue_form"));
+        }
+        Ok(form)
+    }
+
+    fn validate_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::validation("Form name is required"));
+        }
+        if name.chars().count() > MAX_TEXT_CHARS {
+            return Err(AppError::validation(
+                "Form name is too long (max 255 characters)",
+            ));
+        }
+        Ok(name.to_string())
+    }
+
+    fn normalize_description(description: Option<&str>) -> Option<String> {
+        description
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .map(str::to_string)
+    }
+
+    fn new_slug() -> String {
+        clock::new_id().simple().to_string()
+    }
+
+    fn encode_fields(fields: &[FormField]) -> Result<String, AppError> {
+        serde_json::to_string(fields)
+            .map_err(|e| AppError::internal(format!("Failed to encode form fields: {}", e)))
+    }
+
+    fn decode_fields(fields: &str) -> Result<Vec<FormField>, AppError> {
+        serde_json::from_str(fields)
+            .map_err(|e| AppError::internal(format!("Failed to decode form fields: {}", e)))
+    }
+
+    fn response(form: IssueForm) -> Result<IssueFormResponse, AppError> {
+        Ok(IssueFormResponse {
+            fields: Self::decode_fields(&form.fields)?,
+            form,
+        })
+    }
+}
@@ -0,0 +1,370 @@
+use diesel::prelude::*;
+use regex::{Regex, RegexBuilder};
+use uuid::Uuid;
+
+use crate::{
+    db::models::issue::Issue,
+    db::models::issue_label_rule::{
+        CreateIssueLabelRuleRequest, IssueLabelRule, IssueLabelRuleApplication, LabelRuleOutcome,
+        NewIssueLabelRule, NewIssueLabelRuleApplication, UpdateIssueLabelRule,
+        UpdateIssueLabelRuleRequest, label_rule_match_types,
+    },
+    db::repositories::issue_label_rules::IssueLabelRulesRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::labels::LabelRepo,
+    db::with_txn,
+    error::AppError,
+    services::context::RequestContext,
+    services::issues_service::IssuesService,
+    services::permission_service::{Permission, PermissionService},
+    services::teams_service::TeamsService,
+    utils::clock,
+};
+
+const MAX_NAME_CHARS: usize = 255;
+const MAX_PATTERN_CHARS: usize = 500;
+/// Compiled size limit of a rule's regex, so a pattern cannot make issue
+/// creation expensive
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+/// Priority of issues created without one
+const NO_PRIORITY: &str = "none";
+
+/// A rule's pattern ready for matching
+enum RuleMatcher {
+    Keyword(String),
+    Regex(Regex),
+}
+
+impl RuleMatcher {
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            RuleMatcher::Keyword(keyword) => text.to_lowercase().contains(keyword),
+            RuleMatcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Team rules that label and prioritize new issues from patterns in their
+/// title and description, whichever channel the issue is created through.
+pub struct IssueLabelRulesService;
+
+impl IssueLabelRulesService {
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<Vec<IssueLabelRule>, AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        Ok(IssueLabelRulesRepo::list_by_team(conn, team_id)?)
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+    ) -> Result<IssueLabelRule, AppError> {
+        IssueLabelRulesRepo::find_in_workspace(conn, ctx.workspace_id, rule_id)?
+            .ok_or_else(|| AppError::not_found("label_rule"))
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+        req: &CreateIssueLabelRuleRequest,
+    ) -> Result<IssueLabelRule, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageTeams)?;
+        TeamsService::get(conn, ctx, team_id)?;
+        let name = Self::validate_name(&req.name)?;
+        let match_type = Self::validate_match_type(&req.match_type)?;
+        let pattern = Self::validate_pattern(match_type, &req.pattern)?;
+        let priority = req
+            .priority
+            .as_deref()
+            .map(Self::validate_priority)
+            .transpose()?;
+        if req.label_id.is_none() && priority.is_none() {
+            return Err(AppError::validation(
+                "A rule needs a label_id or a priority",
+            ));
+        }
+        if let Some(label_id) = req.label_id {
+            Self::check_label(conn, ctx.workspace_id, label_id)?;
+        }
+        let position = match req.position {
+            Some(position) => position,
+            None => IssueLabelRulesRepo::next_position(conn, team_id)?,
+        };
+
+        Ok(IssueLabelRulesRepo::insert(
+            conn,
+            &NewIssueLabelRule {
+                workspace_id: ctx.workspace_id,
+                team_id,
+                name,
+                match_type: match_type.to_string(),
+                pattern,
+                label_id: req.label_id,
+                priority,
+                position,
+                enabled: req.enabled,
+                created_by: ctx.user_id,
+            },
+        )?)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+        req: &UpdateIssueLabelRuleRequest,
+    ) -> Result<IssueLabelRule, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageTeams)?;
+        let rule = Self::get(conn, ctx, rule_id)?;
+
+        let mut changes = UpdateIssueLabelRule {
+            updated_at: Some(clock::now()),
+            ..Default::default()
+        };
+        if let Some(name) = &req.name {
+            changes.name = Some(Self::validate_name(name)?);
+        }
+        let match_type = match &req.match_type {
+            Some(match_type) => Self::validate_match_type(match_type)?,
+            None => rule.match_type.as_str(),
+        };
+        if req.match_type.is_some() || req.pattern.is_some() {
+            let pattern = req.pattern.as_deref().unwrap_or(&rule.pattern);
+            changes.pattern = Some(Self::validate_pattern(match_type, pattern)?);
+            changes.match_type = Some(match_type.to_string());
+        }
+        if let Some(label_id) = req.label_id {
+            if let Some(label_id) = label_id {
+                Self::check_label(conn, ctx.workspace_id, label_id)?;
+            }
+            changes.label_id = Some(label_id);
+        }
+        if let Some(priority) = &req.priority {
+            changes.priority = Some(
+                priority
+                    .as_deref()
+                    .map(Self::validate_priority)
+                    .transpose()?,
+            );
+        }
+        let label_id = changes.label_id.unwrap_or(rule.label_id);
+        let priority = changes.priority.as_ref().unwrap_or(&rule.priority);
+        if label_id.is_none() && priority.is_none() {
+            return Err(AppError::validation(
+                "A rule needs a label_id or a priority",
+            ));
+        }
+        changes.position = req.position;
+        changes.enabled = req.enabled;
+
+        Ok(IssueLabelRulesRepo::update(conn, rule.id, &changes)?)
+    }
+
+    /// Delete a rule; what it applied to issues stays, as does its entry in
+    /// their rule history
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        rule_id: Uuid,
+    ) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageTeams)?;
+        let rule = Self::get(conn, ctx, rule_id)?;
+        IssueLabelRulesRepo::delete(conn, rule.id)?;
+        Ok(())
+    }
+
+    /// Rules applied to an issue when it was created
+    pub fn applications(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueLabelRuleApplication>, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        Ok(IssueLabelRulesRepo::list_applications_by_issue(
+            conn, issue_id,
+        )?)
+    }
+
+    /// Match the rules, in order, against a new issue. Every matching rule
+    /// adds its label; the first one with a priority sets it unless the
+    /// issue already has one. Rules that would change nothing are left out.
+    pub fn evaluate(
+        issue: &Issue,
+        rules: &[IssueLabelRule],
+        existing_labels: &[Uuid],
+    ) -> LabelRuleOutcome {
+        let text = match &issue.description {
+            Some(description) => format!("{}\n{}", issue.title, description),
+            None => issue.title.clone(),
+        };
+        let mut outcome = LabelRuleOutcome::default();
+        let mut has_priority = issue.priority != NO_PRIORITY;
+
+        for rule in rules.iter().filter(|rule| rule.enabled) {
+            let matcher = match Self::matcher(&rule.match_type, &rule.pattern) {
+                Ok(matcher) => matcher,
+                Err(e) => {
+                    tracing::warn!("Skipping label rule {}: {}", rule.id, e);
+                    continue;
+                }
+            };
+            if !matcher.is_match(&text) {
+                continue;
+            }
+
+            let label_id = rule.label_id.filter(|label_id| {
+                !existing_labels.contains(label_id) && !outcome.label_ids.contains(label_id)
+            });
+            let priority = rule.priority.clone().filter(|_| !has_priority);
+            if label_id.is_none() && priority.is_none() {
+                continue;
+            }
+            if let Some(label_id) = label_id {
+                outcome.label_ids.push(label_id);
+            }
+            if priority.is_some() {
+                has_priority = true;
+                outcome.priority = priority.clone();
+            }
+            outcome.applied.push(NewIssueLabelRuleApplication {
+                issue_id: issue.id,
+                rule_id: Some(rule.id),
+                rule_name: rule.name.clone(),
+                label_id,
+                priority,
+            });
+        }
+        outcome
+    }
+
+    /// Run the team's rules on a newly created issue and record what they
+    /// applied. Returns the issue as it is afterwards.
+    pub fn apply(conn: &mut PgConnection, issue: Issue) -> Result<Issue, AppError> {
+        // Runs as a savepoint when the issue is created in a transaction, so
+        // a failure here leaves the creation intact
+        conn.transaction::<_, AppError, _>(|conn| {
+            let rules = IssueLabelRulesRepo::list_enabled_by_team(conn, issue.team_id)?;
+            if rules.is_empty() {
+                return Ok(issue);
+            }
+            let existing: Vec<Uuid> = LabelRepo::list_by_issue(conn, issue.id)?
+                .into_iter()
+                .map(|label| label.id)
+                .collect();
+            let outcome = Self::evaluate(&issue, &rules, &existing);
+            if outcome.applied.is_empty() {
+                return Ok(issue);
+            }
+
+            if !outcome.label_ids.is_empty() {
+                IssueLabelRulesRepo::add_issue_labels(conn, issue.id, &outcome.label_ids)?;
+            }
+            let issue = match &outcome.priority {
+                Some(priority) => IssueRepo::set_priority(conn, issue.id, priority)?,
+                None => issue,
+            };
+            IssueLabelRulesRepo::insert_applications(conn, &outcome.applied)?;
+            tracing::debug!(
+                issue_id = %issue.id,
+                rules = outcome.applied.len(),
+                "Applied label rules to new issue"
+            );
+            Ok(issue)
+        })
+    }
+
+    /// [`Self::apply`] for issue creation, where a failing rule must not
+    /// fail the request; the issue is kept as created
+    pub fn apply_quietly(conn: &mut PgConnection, issue: Issue) -> Issue {
+        let fallback = issue.clone();
+        // A savepoint keeps a failure here from aborting the caller's transaction
+        match with_txn(conn, |conn| Self::apply(conn, issue)) {
+            Ok(issue) => issue,
+            Err(e) => {
+                tracing::warn!("Failed to apply label rules to {}: {}", fallback.id, e);
+                fallback
+            }
+        }
+    }
+
+    fn matcher(match_type: &str, pattern: &str) -> Result<RuleMatcher, String> {
+        match match_type {
+            label_rule_match_types::KEYWORD => Ok(RuleMatcher::Keyword(pattern.to_lowercase())),
+            label_rule_match_types::REGEX => RegexBuilder::new(pattern)
+                .size_limit(REGEX_SIZE_LIMIT)
+                .build()
+                .map(RuleMatcher::Regex)
+                .map_err(|e| e.to_string()),
+            other => Err(format!("unknown match type '{}'", other)),
+        }
+    }
+
+    fn validate_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(AppError::validation(format!(
+                "Rule name must be 1-{} characters",
+                MAX_NAME_CHARS
+            )));
+        }
+        Ok(name.to_string())
+    }
+
+    fn validate_match_type(match_type: &str) -> Result<&'static str, AppError> {
+        label_rule_match_types::ALL
+            .into_iter()
+            .find(|known| *known == match_type)
+            .ok_or_else(|| {
+                AppError::validation(format!(
+                    "Invalid match_type '{}'; expected one of: {}",
+                    match_type,
+                    label_rule_match_types::ALL.join(", ")
+                ))
+            })
+    }
+
+    /// Check a pattern for its match type; keywords are trimmed
+    pub fn validate_pattern(match_type: &str, pattern: &str) -> Result<String, AppError> {
+        let pattern = if match_type == label_rule_match_types::KEYWORD {
+            pattern.trim()
+        } else {
+            pattern
+        };
+        if pattern.trim().is_empty() || pattern.chars().count() > MAX_PATTERN_CHARS {
+            return Err(AppError::validation(format!(
+                "Pattern must be 1-{} characters",
+                MAX_PATTERN_CHARS
+            )));
+        }
+        Self::matcher(match_type, pattern)
+            .map_err(|e| AppError::validation(format!("Invalid pattern: {}", e)))?;
+        Ok(pattern.to_string())
+    }
+
+    fn validate_priority(priority: &str) -> Result<String, AppError> {
+        IssuesService::parse_priority(priority)?;
+        if priority == NO_PRIORITY {
+            return Err(AppError::validation(
+                "A rule cannot set the priority to none",
+            ));
+        }
+        Ok(priority.to_string())
+    }
+
+    fn check_label(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        label_id: Uuid,
+    ) -> Result<(), AppError> {
+        LabelRepo::find_by_id_in_workspace(conn, workspace_id, label_id)?
+            .map(|_| ())
+            .ok_or_else(|| AppError::validation("Invalid label_id for workspace"))
+    }
+}
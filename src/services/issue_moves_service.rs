@@ -0,0 +1,193 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::issue::Issue,
+    db::models::issue_move::{IssueMove, IssueMoveResult, MoveIssueRequest, NewIssueMove},
+    db::models::workflow::{Workflow, WorkflowState},
+    db::repositories::issue_moves::IssueMovesRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    services::teams_service::TeamsService,
+    services::webhook_service::WebhookService,
+    websocket::{EntityAction, EntityKind, Topic},
+};
+
+pub struct IssueMovesService;
+
+impl IssueMovesService {
+    /// Human-readable key such as `ENG-42`
+    pub fn issue_key(team_key: &str, issue_number: i32) -> String {
+        format!("{}-{}", team_key, issue_number)
+    }
+
+    /// Team key and number of a key such as `ENG-42`; team keys may
+    /// themselves contain `-`
+    pub fn parse_issue_key(key: &str) -> Option<(&str, i32)> {
+        let (team_key, number) = key.trim().rsplit_once('-')?;
+        if team_key.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let number = number.parse::<i32>().ok().filter(|n| *n > 0)?;
+        Some((team_key, number))
+    }
+
+    /// Pick the target team state for `current`: an explicit mapping wins,
+    /// then a state with the same name and category, then the first state of
+    /// the same category (the default one if there is one).
+    pub fn remap_state(
+        current: &WorkflowState,
+        target_states: &[WorkflowState],
+        mapping: &HashMap<Uuid, Uuid>,
+    ) -> Option<Uuid> {
+        if let Some(mapped) = mapping.get(&current.id) {
+            return target_states.iter().find(|s| s.id == *mapped).map(|s| s.id);
+        }
+        let same_category = || {
+            target_states
+                .iter()
+                .filter(|s| s.category == current.category)
+        };
+        same_category()
+            .find(|s| s.name.eq_ignore_ascii_case(&current.name))
+            .or_else(|| same_category().find(|s| s.is_default))
+            .or_else(|| same_category().next())
+            .map(|s| s.id)
+    }
+
+    /// Move an issue to another team in one transaction: a new key from the
+    /// target team, the workflow state remapped, and the team-scoped cycle
+    /// cleared. Comments, attachments, labels and history stay on the issue.
+    pub fn move_to_team(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        req: &MoveIssueRequest,
+    ) -> Result<IssueMoveResult, AppError> {
+        let issue = IssueRepo::find_by_id_in_workspace(conn, ctx.workspace_id, issue_id)?
+            .ok_or_else(|| AppError::not_found("issue"))?;
+        if issue.team_id == req.team_id {
+            return Err(AppError::validation("Issue already belongs to this team"));
+        }
+        let from_team = TeamsService::get(conn, ctx, issue.team_id)?;
+        let to_team = TeamsService::get(conn, ctx, req.team_id)?;
+
+        let source_states = WorkflowsRepo::list_states_by_team(conn, from_team.id)?;
+        let target_states = WorkflowsRepo::list_states_by_team(conn, to_team.id)?;
+        if let Some(unknown) = req
+            .state_mapping
+            .values()
+            .find(|id| !target_states.iter().any(|s| s.id == **id))
+        {
+            return Err(AppError::validation(format!(
+                "State {} does not belong to team {}",
+                unknown, to_team.team_key
+            )));
+        }
+
+        let to_state_id = match issue
+            .workflow_state_id
+            .and_then(|id| source_states.iter().find(|s| s.id == id))
+        {
+            Some(current) => Some(
+                Self::remap_state(current, &target_states, &req.state_mapping).ok_or_else(
+                    || {
+                        AppError::validation(format!(
+                            "Team {} has no state matching '{}'; add it to state_mapping",
+                            to_team.team_key, current.name
+                        ))
+                    },
+                )?,
+            ),
+            None => None,
+        };
+        let to_workflow_id = match to_state_id {
+            Some(state) => target_states
+                .iter()
+                .find(|s| s.id == state)
+                .map(|s| s.workflow_id),
+            None => {
+                use crate::schema::workflows::dsl as w;
+                w::workflows
+                    .filter(w::team_id.eq(to_team.id))
+                    .filter(w::is_default.eq(true))
+                    .first::<Workflow>(conn)
+                    .optional()?
+                    .map(|wf| wf.id)
+            }
+        };
+
+        let result = conn.transaction::<_, AppError, _>(|conn| {
+            let issue_number = IssueRepo::next_issue_number(conn, to_team.id)?;
+            let moved: Issue = {
+                use crate::schema::issues::dsl as i;
+                diesel::update(i::issues.filter(i::id.eq(issue.id)))
+                    .set((
+                        i::team_id.eq(to_team.id),
+                        i::issue_number.eq(issue_number),
+                        i::workflow_id.eq(to_workflow_id),
+                        i::workflow_state_id.eq(to_state_id),
+                        i::cycle_id.eq(None::<Uuid>),
+                    ))
+                    .returning(Issue::as_returning())
+                    .get_result(conn)?
+            };
+            let issue_move: IssueMove = IssueMovesRepo::insert(
+                conn,
+                &NewIssueMove {
+                    issue_id: issue.id,
+                    from_team_id: Some(from_team.id),
+                    to_team_id: Some(to_team.id),
+                    from_key: Self::issue_key(&from_team.team_key, issue.issue_number),
+                    to_key: Self::issue_key(&to_team.team_key, issue_number),
+                    from_state_id: issue.workflow_state_id,
+                    to_state_id,
+                    moved_by: ctx.user_id,
+                },
+            )?;
+            let result = IssueMoveResult {
+                issue: moved,
+                issue_move,
+            };
+            WebhookService::emit(conn, ctx.workspace_id, webhook_events::ISSUE_MOVED, &result)?;
+            Ok(result)
+        })?;
+
+        for topic in [
+            Topic::Team(from_team.id),
+            Topic::Team(to_team.id),
+            Topic::Issue(issue.id),
+        ] {
+            RealtimeService::publish(
+                ctx.workspace_id,
+                topic,
+                webhook_events::ISSUE_MOVED,
+                &result,
+            );
+        }
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Issue,
+            EntityAction::Updated,
+            result.issue.id,
+            &result.issue,
+        );
+        Ok(result)
+    }
+
+    pub fn history(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueMove>, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        Ok(IssueMovesRepo::list_by_issue(conn, issue_id)?)
+    }
+}
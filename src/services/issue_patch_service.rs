@@ -0,0 +1,275 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::{
+    db::models::issue::{Issue, IssueFieldClears, IssueResponse},
+    db::repositories::issues::IssueRepo,
+    db::repositories::labels::LabelRepo,
+    error::AppError,
+    routes::issues::UpdateIssueRequest,
+    services::context::RequestContext,
+    services::issues_service::IssuesService,
+    utils::json_patch::{
+        self, JSON_PATCH_CONTENT_TYPE, MERGE_PATCH_CONTENT_TYPE, PatchDocument, PatchError,
+    },
+};
+
+/// Top-level issue fields a patch may touch
+pub const PATCHABLE_FIELDS: [&str; 11] = [
+    "title",
+    "description",
+    "priority",
+    "team_id",
+    "project_id",
+    "cycle_id",
+    "assignee_id",
+    "workflow_id",
+    "workflow_state_id",
+    "estimate",
+    "label_ids",
+];
+/// Field whose elements operations may address one by one
+const LIST_FIELD: &str = "label_ids";
+/// Operations accepted in one JSON Patch
+const MAX_OPERATIONS: usize = 100;
+
+/// The issue as a patch sees it: a flat document of its editable fields,
+/// with `null` for unset optional ones so `replace` can address them
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IssuePatchTarget {
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: String,
+    pub team_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub cycle_id: Option<Uuid>,
+    pub assignee_id: Option<Uuid>,
+    pub workflow_id: Option<Uuid>,
+    pub workflow_state_id: Option<Uuid>,
+    pub estimate: Option<i32>,
+    pub label_ids: Vec<Uuid>,
+}
+
+impl IssuePatchTarget {
+    pub fn new(issue: &Issue, label_ids: Vec<Uuid>) -> Self {
+        Self {
+            title: issue.title.clone(),
+            description: issue.description.clone(),
+            priority: issue.priority.clone(),
+            team_id: issue.team_id,
+            project_id: issue.project_id,
+            cycle_id: issue.cycle_id,
+            assignee_id: issue.assignee_id,
+            workflow_id: issue.workflow_id,
+            workflow_state_id: issue.workflow_state_id,
+            estimate: issue.estimate,
+            label_ids,
+        }
+    }
+}
+
+/// Partial issue updates expressed as JSON Patch (RFC 6902) or JSON Merge
+/// Patch (RFC 7396) documents, restricted to the editable issue fields and
+/// carried out through the regular issue update.
+pub struct IssuePatchService;
+
+impl IssuePatchService {
+    /// Read a request body as the patch kind its content type names; plain
+    /// JSON is a JSON Patch when it is an array and a merge patch otherwise
+    pub fn parse_document(
+        content_type: Option<&str>,
+        body: Value,
+    ) -> Result<PatchDocument, AppError> {
+        let media_type = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase());
+        let document = match media_type.as_deref() {
+            Some(JSON_PATCH_CONTENT_TYPE) if !body.is_array() => {
+                return Err(AppError::validation(
+                    "A JSON Patch must be an array of operations",
+                ));
+            }
+            Some(MERGE_PATCH_CONTENT_TYPE) if !body.is_object() => {
+                return Err(AppError::validation("A merge patch must be an object"));
+            }
+            _ => serde_json::from_value(body),
+        };
+        document.map_err(|e| AppError::validation(format!("Invalid patch document: {}", e)))
+    }
+
+    /// Reject patches touching anything but [`PATCHABLE_FIELDS`]; only
+    /// `label_ids` may be addressed below the top level, one element deep
+    pub fn validate_paths(document: &PatchDocument) -> Result<(), AppError> {
+        match document {
+            PatchDocument::JsonPatch(operations) => {
+                if operations.is_empty() {
+                    return Err(AppError::validation("The patch has no operations"));
+                }
+                if operations.len() > MAX_OPERATIONS {
+                    return Err(AppError::validation(format!(
+                        "A patch has at most {} operations",
+                        MAX_OPERATIONS
+                    )));
+                }
+                for operation in operations {
+                    Self::check_path(operation.path())?;
+                    if let Some(from) = operation.from() {
+                        Self::check_path(from)?;
+                    }
+                }
+            }
+            PatchDocument::MergePatch(fields) => {
+                if let Some(field) = fields
+                    .keys()
+                    .find(|field| !PATCHABLE_FIELDS.contains(&field.as_str()))
+                {
+                    return Err(Self::not_patchable(&format!("/{}", field)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a patch document to the target; the result is checked against
+    /// the field types
+    pub fn patch_target(
+        target: &IssuePatchTarget,
+        document: &PatchDocument,
+    ) -> Result<IssuePatchTarget, AppError> {
+        Self::validate_paths(document)?;
+        let mut value = serde_json::to_value(target)
+            .map_err(|e| AppError::internal(format!("Failed to encode issue: {}", e)))?;
+        match document {
+            PatchDocument::JsonPatch(operations) => {
+                json_patch::apply_patch(&mut value, operations).map_err(|e| match e {
+                    PatchError::TestFailed(message) => {
+                        AppError::conflict_with_code(message, None, "PATCH_TEST_FAILED")
+                    }
+                    PatchError::Invalid(message) => AppError::validation(message),
+                })?;
+            }
+            PatchDocument::MergePatch(fields) => {
+                json_patch::apply_merge_patch(&mut value, &Value::Object(fields.clone()));
+            }
+        }
+        serde_json::from_value(value)
+            .map_err(|e| AppError::validation(format!("Patched issue is invalid: {}", e)))
+    }
+
+    /// The update turning `before` into `after`
+    pub fn changes(
+        before: &IssuePatchTarget,
+        after: &IssuePatchTarget,
+    ) -> Result<(UpdateIssueRequest, IssueFieldClears), AppError> {
+        let clears = IssueFieldClears {
+            description: before.description.is_some() && after.description.is_none(),
+            project_id: before.project_id.is_some() && after.project_id.is_none(),
+            assignee_id: before.assignee_id.is_some() && after.assignee_id.is_none(),
+            cycle_id: before.cycle_id.is_some() && after.cycle_id.is_none(),
+        };
+
+        // Moving the issue to another team resets its workflow; otherwise
+        // the workflow can be changed but not removed
+        let team_changed = before.team_id != after.team_id;
+        for (field, before, after) in [
+            ("workflow_id", before.workflow_id, after.workflow_id),
+            (
+                "workflow_state_id",
+                before.workflow_state_id,
+                after.workflow_state_id,
+            ),
+        ] {
+            if before.is_some() && after.is_none() && !team_changed {
+                return Err(AppError::validation(format!("{} cannot be removed", field)));
+            }
+        }
+
+        let changed = |before: Option<Uuid>, after: Option<Uuid>| after.filter(|_| before != after);
+        let mut label_ids = after.label_ids.clone();
+        let mut seen = HashSet::new();
+        label_ids.retain(|label_id| seen.insert(*label_id));
+        let labels_changed = {
+            let mut before_sorted = before.label_ids.clone();
+            let mut after_sorted = label_ids.clone();
+            before_sorted.sort();
+            after_sorted.sort();
+            before_sorted != after_sorted
+        };
+
+        let req = UpdateIssueRequest {
+            title: (before.title != after.title).then(|| after.title.clone()),
+            description: after
+                .description
+                .clone()
+                .filter(|_| before.description != after.description),
+            project_id: changed(before.project_id, after.project_id),
+            team_id: team_changed.then_some(after.team_id),
+            priority: if before.priority != after.priority {
+                Some(IssuesService::parse_priority(&after.priority)?)
+            } else {
+                None
+            },
+            assignee_id: changed(before.assignee_id, after.assignee_id),
+            reporter_id: None,
+            workflow_id: changed(before.workflow_id, after.workflow_id),
+            workflow_state_id: changed(before.workflow_state_id, after.workflow_state_id),
+            cycle_id: changed(before.cycle_id, after.cycle_id),
+            label_ids: labels_changed.then_some(label_ids),
+            estimate: (before.estimate != after.estimate).then_some(after.estimate),
+        };
+        Ok((req, clears))
+    }
+
+    /// Patch an issue; a patch that changes nothing leaves it untouched
+    pub fn apply(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        document: &PatchDocument,
+    ) -> Result<IssueResponse, AppError> {
+        Self::validate_paths(document)?;
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let issue =
+            IssueRepo::find_by_id(conn, issue_id)?.ok_or_else(|| AppError::not_found("issue"))?;
+        let label_ids = LabelRepo::list_by_issue(conn, issue_id)?
+            .into_iter()
+            .map(|label| label.id)
+            .collect();
+
+        let before = IssuePatchTarget::new(&issue, label_ids);
+        let after = Self::patch_target(&before, document)?;
+        let (req, clears) = Self::changes(&before, &after)?;
+        if before != after {
+            IssuesService::update_with_clears(conn, ctx, issue_id, &req, &clears)?;
+        }
+        IssuesService::get_by_id(conn, ctx, issue_id)
+    }
+
+    fn check_path(path: &str) -> Result<(), AppError> {
+        let keys =
+            json_patch::parse_pointer(path).map_err(|e| AppError::validation(e.to_string()))?;
+        let allowed = match keys.as_slice() {
+            [field] => PATCHABLE_FIELDS.contains(&field.as_str()),
+            [field, _] => field == LIST_FIELD,
+            _ => false,
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(Self::not_patchable(path))
+        }
+    }
+
+    fn not_patchable(path: &str) -> AppError {
+        AppError::validation(format!(
+            "Path '{}' cannot be patched; patchable fields: {}",
+            path,
+            PATCHABLE_FIELDS.join(", ")
+        ))
+    }
+}
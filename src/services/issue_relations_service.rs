@@ -0,0 +1,191 @@
+use diesel::prelude::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    db::models::issue::IssueResponse,
+    db::models::issue_relation::{
+        CreateIssueRelationRequest, IssueRelation, IssueRelationResponse, IssueRelationType,
+        NewIssueRelation,
+    },
+    db::repositories::issue_relations::IssueRelationsRepo,
+    db::repositories::issues::IssueRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    websocket::Topic,
+};
+
+/// Realtime events published to both related issues
+pub const RELATION_CREATED: &str = "issue.relation_created";
+pub const RELATION_DELETED: &str = "issue.relation_deleted";
+
+pub struct IssueRelationsService;
+
+impl IssueRelationsService {
+    /// Stored form of a relation requested from `issue_id`: passive types are
+    /// flipped so only active rows are kept. Returns (from, to, type).
+    pub fn canonical(
+        issue_id: Uuid,
+        related_issue_id: Uuid,
+        relation_type: IssueRelationType,
+    ) -> (Uuid, Uuid, IssueRelationType) {
+        if relation_type.is_passive() {
+            (related_issue_id, issue_id, relation_type.inverse())
+        } else {
+            (issue_id, related_issue_id, relation_type)
+        }
+    }
+
+    /// Path of issues from `blocked` back to `blocker` along existing
+    /// `blocks` edges, if adding `blocker blocks blocked` would close a loop.
+    /// `successors` returns the (blocker, blocked) edges leaving a frontier.
+    pub fn find_blocking_cycle<E>(
+        blocker: Uuid,
+        blocked: Uuid,
+        mut successors: impl FnMut(&[Uuid]) -> Result<Vec<(Uuid, Uuid)>, E>,
+    ) -> Result<Option<Vec<Uuid>>, E> {
+        let mut came_from: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut seen: HashSet<Uuid> = HashSet::from([blocked]);
+        let mut frontier = vec![blocked];
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for (from, to) in successors(&frontier)? {
+                if !seen.insert(to) {
+                    continue;
+                }
+                came_from.insert(to, from);
+                if to == blocker {
+                    let mut path = vec![to];
+                    let mut current = to;
+                    while let Some(prev) = came_from.get(&current) {
+                        path.push(*prev);
+                        current = *prev;
+                    }
+                    path.reverse();
+                    return Ok(Some(path));
+                }
+                next.push(to);
+            }
+            frontier = next;
+        }
+        Ok(None)
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        req: &CreateIssueRelationRequest,
+    ) -> Result<IssueRelationResponse, AppError> {
+        if issue_id == req.related_issue_id {
+            return Err(AppError::validation("An issue cannot be related to itself"));
+        }
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, req.related_issue_id)? {
+            return Err(AppError::not_found("related issue"));
+        }
+        let related = IssueRepo::find_by_id(conn, req.related_issue_id)?
+            .ok_or_else(|| AppError::not_found("related issue"))?;
+
+        let (from, to, stored_type) =
+            Self::canonical(issue_id, req.related_issue_id, req.relation_type);
+        let relation = conn.transaction::<_, AppError, _>(|conn| {
+            if IssueRelationsRepo::exists(conn, from, to, stored_type.as_str())?
+                || (matches!(
+                    stored_type,
+                    IssueRelationType::RelatesTo | IssueRelationType::Duplicates
+                ) && IssueRelationsRepo::exists(conn, to, from, stored_type.as_str())?)
+            {
+                return Err(AppError::conflict_with_code(
+                    "These issues are already related this way",
+                    Some("related_issue_id".to_string()),
+                    "RELATION_EXISTS",
+                ));
+            }
+            if stored_type == IssueRelationType::Blocks
+                && let Some(path) = Self::find_blocking_cycle(from, to, |frontier| {
+                    IssueRelationsRepo::edges_from(
+                        conn,
+                        frontier,
+                        IssueRelationType::Blocks.as_str(),
+                    )
+                })?
+            {
+                return Err(AppError::validation(format!(
+                    "This relation would create a blocking cycle through {} issues",
+                    path.len()
+                )));
+            }
+            Ok(IssueRelationsRepo::insert(
+                conn,
+                &NewIssueRelation {
+                    issue_id: from,
+                    related_issue_id: to,
+                    relation_type: stored_type.as_str().to_string(),
+                    created_by: ctx.user_id,
+                },
+            )?)
+        })?;
+
+        Self::publish(ctx, &relation, RELATION_CREATED);
+        Ok(Self::to_response(issue_id, relation, related))
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        relation_id: Uuid,
+    ) -> Result<(), AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let relation = IssueRelationsRepo::find_by_id(conn, relation_id)?
+            .filter(|r| r.issue_id == issue_id || r.related_issue_id == issue_id)
+            .ok_or_else(|| AppError::not_found("issue relation"))?;
+        IssueRelationsRepo::delete(conn, relation.id)?;
+        Self::publish(ctx, &relation, RELATION_DELETED);
+        Ok(())
+    }
+
+    /// Relations of the issue as seen from it, oldest first
+    pub fn list_for_issue(
+        conn: &mut PgConnection,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueRelationResponse>, AppError> {
+        Ok(IssueRelationsRepo::list_for_issue(conn, issue_id)?
+            .into_iter()
+            .map(|(relation, other)| Self::to_response(issue_id, relation, other))
+            .collect())
+    }
+
+    fn to_response(
+        viewer: Uuid,
+        relation: IssueRelation,
+        other: crate::db::models::issue::Issue,
+    ) -> IssueRelationResponse {
+        let stored = IssueRelationType::parse(&relation.relation_type)
+            .unwrap_or(IssueRelationType::RelatesTo);
+        let relation_type = if relation.issue_id == viewer {
+            stored
+        } else {
+            stored.inverse()
+        };
+        IssueRelationResponse {
+            id: relation.id,
+            relation_type,
+            related_issue: IssueResponse::from(other),
+            created_by: relation.created_by,
+            created_at: relation.created_at,
+        }
+    }
+
+    fn publish(ctx: &RequestContext, relation: &IssueRelation, event: &str) {
+        for issue in [relation.issue_id, relation.related_issue_id] {
+            RealtimeService::publish(ctx.workspace_id, Topic::Issue(issue), event, relation);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::issue::{Issue, NewIssue},
+    db::models::issue_split::{IssueSplitResult, SplitCandidate, SplitIssueRequest, SplitSource},
+    db::models::workflow::{WorkflowState, WorkflowStateCategory},
+    db::repositories::issues::IssueRepo,
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    services::webhook_service::WebhookService,
+    validation::issue::validate_create_issue,
+    websocket::{EntityAction, EntityKind, Topic},
+};
+
+/// Most issues a single split may create
+pub const MAX_SPLIT_ITEMS: usize = 50;
+
+pub struct IssueSplitService;
+
+impl IssueSplitService {
+    /// Markdown task list items of a description, checked or not
+    pub fn checklist_items(description: &str) -> Vec<SplitCandidate> {
+        description
+            .lines()
+            .filter_map(|line| {
+                let rest = line.trim_start().strip_prefix(['-', '*', '+'])?;
+                let rest = rest.strip_prefix(' ')?.trim_start();
+                let text = ["[ ]", "[x]", "[X]"]
+                    .iter()
+                    .find_map(|mark| rest.strip_prefix(mark))?
+                    .trim();
+                (!text.is_empty()).then(|| SplitCandidate {
+                    title: text.to_string(),
+                    description: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Sections of a description split at its highest-level headings. Text
+    /// before the first heading is not a section.
+    pub fn description_sections(description: &str) -> Vec<SplitCandidate> {
+        let heading = |line: &str| {
+            let level = line.chars().take_while(|c| *c == '#').count();
+            let text = line[level..].strip_prefix(' ')?.trim();
+            ((1..=6).contains(&level) && !text.is_empty()).then_some((level, text.to_string()))
+        };
+        let Some(top) = description
+            .lines()
+            .filter_map(heading)
+            .map(|(l, _)| l)
+            .min()
+        else {
+            return Vec::new();
+        };
+
+        let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+        for line in description.lines() {
+            match heading(line) {
+                Some((level, title)) if level == top => sections.push((title, Vec::new())),
+                _ => {
+                    if let Some((_, body)) = sections.last_mut() {
+                        body.push(line);
+                    }
+                }
+            }
+        }
+        sections
+            .into_iter()
+            .map(|(title, body)| {
+                let body = body.join("\n").trim().to_string();
+                SplitCandidate {
+                    title,
+                    description: (!body.is_empty()).then_some(body),
+                }
+            })
+            .collect()
+    }
+
+    /// Candidates picked by `items`, in request order
+    pub fn select(
+        candidates: &[SplitCandidate],
+        items: &[usize],
+    ) -> Result<Vec<SplitCandidate>, AppError> {
+        if items.is_empty() {
+            return Err(AppError::validation("Select at least one item to split"));
+        }
+        if items.len() > MAX_SPLIT_ITEMS {
+            return Err(AppError::validation(format!(
+                "At most {} items can be split at once",
+                MAX_SPLIT_ITEMS
+            )));
+        }
+        let mut selected = Vec::with_capacity(items.len());
+        for (pos, index) in items.iter().enumerate() {
+            if items[..pos].contains(index) {
+                return Err(AppError::validation(format!(
+                    "Item {} selected twice",
+                    index
+                )));
+            }
+            let candidate = candidates.get(*index).ok_or_else(|| {
+                AppError::validation(format!(
+                    "Item {} does not exist; the description has {} items",
+                    index,
+                    candidates.len()
+                ))
+            })?;
+            selected.push(candidate.clone());
+        }
+        Ok(selected)
+    }
+
+    /// Create one sub-issue per selected item and optionally close the
+    /// original, all in one transaction
+    pub fn split(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        req: &SplitIssueRequest,
+    ) -> Result<IssueSplitResult, AppError> {
+        let original = IssueRepo::find_by_id_in_workspace(conn, ctx.workspace_id, issue_id)?
+            .ok_or_else(|| AppError::not_found("issue"))?;
+        // The issue lookup is not scoped, so check the team before writing
+        // anything into it
+        if TeamsRepo::find_in_workspace(conn, ctx.workspace_id, original.team_id)?.is_none() {
+            return Err(AppError::not_found("issue"));
+        }
+        let description = original.description.as_deref().unwrap_or_default();
+        let candidates = match req.source {
+            SplitSource::Checklist => Self::checklist_items(description),
+            SplitSource::Sections => Self::description_sections(description),
+        };
+        let selected = Self::select(&candidates, &req.items)?;
+        for candidate in &selected {
+            validate_create_issue(&candidate.title, &candidate.description, &original.team_id)?;
+        }
+
+        let states = match original.workflow_id {
+            Some(wf_id) => WorkflowsRepo::list_states_by_workflow(conn, wf_id)?,
+            None => WorkflowsRepo::list_team_default_states(conn, original.team_id)?,
+        };
+        let open_state_id = Self::open_state(&states).map(|s| (s.workflow_id, s.id));
+        let close_state_id = if req.close_original {
+            Some(Self::close_state(&states, req.close_state_id)?)
+        } else {
+            None
+        };
+
+        let result = conn.transaction::<_, AppError, _>(|conn| {
+            let mut created = Vec::with_capacity(selected.len());
+            for candidate in selected {
+                let issue = IssueRepo::insert(
+                    conn,
+                    &NewIssue {
+                        project_id: original.project_id,
+                        cycle_id: original.cycle_id,
+                        creator_id: ctx.user_id,
+                        assignee_id: original.assignee_id,
+                        parent_issue_id: Some(original.id),
+                        title: candidate.title,
+                        description: candidate.description,
+                        priority: Some(original.priority.clone()),
+                        is_changelog_candidate: Some(false),
+                        team_id: original.team_id,
+                        workflow_id: open_state_id.map_or(original.workflow_id, |(wf, _)| Some(wf)),
+                        workflow_state_id: open_state_id.map(|(_, state)| state),
+                        estimate: None,
+                    },
+                )?;
+                WebhookService::emit(
+                    conn,
+                    ctx.workspace_id,
+                    webhook_events::ISSUE_CREATED,
+                    &issue,
+                )?;
+                created.push(issue);
+            }
+            let original = match close_state_id {
+                Some(state) => {
+                    use crate::schema::issues::dsl as i;
+                    let closed: Issue = diesel::update(i::issues.filter(i::id.eq(original.id)))
+                        .set(i::workflow_state_id.eq(state))
+                        .returning(Issue::as_returning())
+                        .get_result(conn)?;
+                    WebhookService::emit(
+                        conn,
+                        ctx.workspace_id,
+                        webhook_events::ISSUE_UPDATED,
+                        &closed,
+                    )?;
+                    closed
+                }
+                None => original,
+            };
+            Ok(IssueSplitResult { original, created })
+        })?;
+
+        for issue in &result.created {
+            RealtimeService::publish(
+                ctx.workspace_id,
+                Topic::Issue(issue.id),
+                webhook_events::ISSUE_CREATED,
+                issue,
+            );
+            RealtimeService::entity_changed(
+                ctx.workspace_id,
+                EntityKind::Issue,
+                EntityAction::Created,
+                issue.id,
+                issue,
+            );
+        }
+        if close_state_id.is_some() {
+            RealtimeService::publish(
+                ctx.workspace_id,
+                Topic::Issue(result.original.id),
+                webhook_events::ISSUE_UPDATED,
+                &result.original,
+            );
+            RealtimeService::entity_changed(
+                ctx.workspace_id,
+                EntityKind::Issue,
+                EntityAction::Updated,
+                result.original.id,
+                &result.original,
+            );
+        }
+        Ok(result)
+    }
+
+    /// First not-yet-started state new sub-issues begin in
+    fn open_state(states: &[WorkflowState]) -> Option<&WorkflowState> {
+        [
+            WorkflowStateCategory::Unstarted,
+            WorkflowStateCategory::Backlog,
+        ]
+        .iter()
+        .find_map(|category| states.iter().find(|s| s.category == *category))
+    }
+
+    fn close_state(states: &[WorkflowState], requested: Option<Uuid>) -> Result<Uuid, AppError> {
+        match requested {
+            Some(id) => {
+                let state = states.iter().find(|s| s.id == id).ok_or_else(|| {
+                    AppError::validation("Close state does not belong to this workflow")
+                })?;
+                if !matches!(
+                    state.category,
+                    WorkflowStateCategory::Completed | WorkflowStateCategory::Canceled
+                ) {
+                    return Err(AppError::validation(
+                        "Close state must be a completed or canceled state",
+                    ));
+                }
+                Ok(state.id)
+            }
+            None => states
+                .iter()
+                .find(|s| s.category == WorkflowStateCategory::Completed)
+                .map(|s| s.id)
+                .ok_or_else(|| AppError::validation("The workflow has no completed state")),
+        }
+    }
+}
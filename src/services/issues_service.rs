@@ -1,17 +1,42 @@
-use chrono::Utc;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use crate::{
     db::enums::IssuePriority,
-    db::models::issue::{Issue, NewIssue},
+    db::models::app_installation::webhook_events,
+    db::models::automation::AutomationEvent,
+    db::models::issue::{Issue, IssueFieldClears, NewIssue},
+    db::models::issue_view::{IssueView, IssueViewer, NewIssueView},
+    db::models::notification::{NewNotification, notification_events},
+    db::models::search::IssueSearchFilters,
     db::models::team::{Team, TeamBasicInfo},
-    db::models::workflow::WorkflowStateResponse,
+    db::models::workflow::{WorkflowState, WorkflowStateCategory, WorkflowStateResponse},
+    db::repositories::issue_links::IssueLinksRepo,
+    db::repositories::issue_moves::IssueMovesRepo,
+    db::repositories::issue_views::IssueViewsRepo,
     db::repositories::issues::IssueRepo,
+    db::repositories::labels::LabelRepo,
+    db::repositories::notifications::NotificationsRepo,
     db::repositories::workflows::WorkflowsRepo,
     error::AppError,
+    services::automations_service::AutomationsService,
     services::context::RequestContext,
-    validation::issue::{validate_create_issue, validate_update_issue},
+    services::cross_workspace_relations_service::CrossWorkspaceRelationsService,
+    services::issue_enrichment::IssueEnrichment,
+    services::issue_label_rules_service::IssueLabelRulesService,
+    services::issue_moves_service::IssueMovesService,
+    services::issue_relations_service::IssueRelationsService,
+    services::markdown_service::MarkdownService,
+    services::notifications_service::NotificationsService,
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    services::search_service::SearchService,
+    services::teams_service::TeamsService,
+    services::webhook_service::WebhookService,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    utils::clock,
+    validation::issue::{validate_create_issue, validate_estimate, validate_update_issue},
+    websocket::{EntityAction, EntityKind, Topic},
 };
 
 pub struct IssuesService;
@@ -27,7 +52,7 @@ impl IssuesService {
         }
     }
 
-    fn parse_priority(priority_str: &str) -> Result<IssuePriority, AppError> {
+    pub(crate) fn parse_priority(priority_str: &str) -> Result<IssuePriority, AppError> {
         match priority_str {
             "none" => Ok(IssuePriority::None),
             "low" => Ok(IssuePriority::Low),
@@ -42,7 +67,8 @@ impl IssuesService {
         ctx: &RequestContext,
         filters: &IssueFilters,
     ) -> Result<Vec<crate::db::models::issue::IssueResponse>, AppError> {
-        let mut query = IssueRepo::list_by_workspace(conn, ctx.workspace_id)?;
+        let mut query =
+            IssueRepo::list_by_workspace(conn, ctx.workspace_id, filters.include_archived)?;
 
         // Apply filters
         if let Some(team_id) = filters.team_id {
@@ -66,48 +92,22 @@ impl IssuesService {
             query.retain(|issue| issue.title.to_lowercase().contains(&search.to_lowercase()));
         }
 
-        // Enrich with workflow states and map to response
-        let mut responses = Vec::with_capacity(query.len());
-        for issue in query {
-            let mut resp = crate::db::models::issue::IssueResponse::from(issue.clone());
-            // Populate team info (and team_key)
-            {
-                use crate::schema::teams::dsl as t;
-                if let Some(team) = t::teams
-                    .filter(t::id.eq(issue.team_id))
-                    .first::<Team>(conn)
-                    .optional()
-                    .map_err(|e| AppError::internal(format!("Failed to load team: {}", e)))?
-                {
-                    resp.team_key = Some(team.team_key.clone());
-                    resp.team = Some(TeamBasicInfo {
-                        id: team.id,
-                        name: team.name,
-                        team_key: team.team_key,
-                        description: team.description,
-                        icon_url: team.icon_url,
-                        is_private: team.is_private,
-                    });
-                }
-            }
-            // Determine states source
-            let states = if let Some(wf_id) = issue.workflow_id {
-                WorkflowsRepo::list_states_by_workflow(conn, wf_id).map_err(|e| {
-                    AppError::internal(format!("Failed to load workflow states: {}", e))
-                })?
-            } else {
-                WorkflowsRepo::list_team_default_states(conn, issue.team_id).map_err(|e| {
-                    AppError::internal(format!("Failed to load team default states: {}", e))
-                })?
-            };
-            resp.workflow_states = states
-                .into_iter()
-                .map(WorkflowStateResponse::from)
-                .collect();
-            responses.push(resp);
-        }
+        Self::to_responses(conn, ctx, query)
+    }
 
-        Ok(responses)
+    /// Map issues to responses enriched with team info, workflow states,
+    /// assignee, labels, project and the caller's last view, keeping their
+    /// order. Related rows are loaded in one batch per kind.
+    pub(crate) fn to_responses(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issues: Vec<Issue>,
+    ) -> Result<Vec<crate::db::models::issue::IssueResponse>, AppError> {
+        let enrichment = IssueEnrichment::load(conn, ctx, &issues)?;
+        Ok(issues
+            .into_iter()
+            .map(|issue| enrichment.apply(issue))
+            .collect())
     }
 
     pub fn create(
@@ -116,25 +116,110 @@ impl IssuesService {
         req: &crate::routes::issues::CreateIssueRequest,
     ) -> Result<Issue, AppError> {
         validate_create_issue(&req.title, &req.description, &req.team_id)?;
+        if let Some(estimate) = req.estimate {
+            Self::validate_team_estimate(conn, req.team_id, estimate)?;
+        }
+        let (mut workflow_id, mut workflow_state_id) =
+            if req.workflow_id.is_none() && req.workflow_state_id.is_none() {
+                WorkspaceSettingsService::default_workflow(conn, ctx.workspace_id, req.team_id)?
+            } else {
+                (req.workflow_id, req.workflow_state_id)
+            };
+        // Issues that end up without a state wait in the team's triage queue
+        if workflow_state_id.is_none() {
+            let states = WorkflowsRepo::list_states_by_team(conn, req.team_id)?;
+            if let Some(triage) = Self::triage_state(&states, workflow_id) {
+                workflow_id = Some(triage.workflow_id);
+                workflow_state_id = Some(triage.id);
+            }
+        }
+        let assignee_id = match req.assignee_id {
+            Some(assignee_id) => Some(assignee_id),
+            None => TeamsService::auto_assignee(conn, req.team_id)?,
+        };
 
-        let _now = Utc::now().naive_utc();
+        let _now = clock::now().naive_utc();
         let new_issue = NewIssue {
             project_id: req.project_id,
             cycle_id: req.cycle_id,
             creator_id: ctx.user_id,
-            assignee_id: req.assignee_id,
+            assignee_id,
             parent_issue_id: req.parent_issue_id,
             title: req.title.clone(),
             description: req.description.clone(),
             priority: req.priority.as_ref().map(Self::priority_to_string),
             is_changelog_candidate: Some(false),
             team_id: req.team_id,
-            workflow_id: req.workflow_id,
-            workflow_state_id: req.workflow_state_id,
+            workflow_id,
+            workflow_state_id,
+            estimate: req.estimate,
         };
 
-        IssueRepo::insert(conn, &new_issue)
-            .map_err(|e| AppError::internal(format!("Failed to create issue: {}", e)))
+        let issue = IssueRepo::insert(conn, &new_issue)
+            .map_err(|e| AppError::internal(format!("Failed to create issue: {}", e)))?;
+        let issue = IssueLabelRulesService::apply_quietly(conn, issue);
+        if let Some(assignee_id) = issue.assignee_id {
+            Self::notify_assignment(conn, ctx, &issue, assignee_id);
+        }
+        AutomationsService::enqueue_quietly(conn, ctx, &issue, &[AutomationEvent::IssueCreated]);
+        let payload = MarkdownService::webhook_payload(
+            conn,
+            ctx.workspace_id,
+            &issue,
+            issue.description.as_deref(),
+        );
+        WebhookService::emit_quietly(
+            conn,
+            ctx.workspace_id,
+            webhook_events::ISSUE_CREATED,
+            &payload,
+        );
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Issue(issue.id),
+            webhook_events::ISSUE_CREATED,
+            &issue,
+        );
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Issue,
+            EntityAction::Created,
+            issue.id,
+            &issue,
+        );
+        Ok(issue)
+    }
+
+    /// Triage state a new issue without a state is filed in: one in
+    /// `workflow_id` when the issue has a workflow, otherwise the team's
+    /// first, preferring default states
+    pub fn triage_state(
+        states: &[WorkflowState],
+        workflow_id: Option<Uuid>,
+    ) -> Option<&WorkflowState> {
+        let mut triage: Vec<&WorkflowState> = states
+            .iter()
+            .filter(|s| s.category == WorkflowStateCategory::Triage)
+            .filter(|s| workflow_id.is_none_or(|workflow_id| s.workflow_id == workflow_id))
+            .collect();
+        triage.sort_by_key(|s| (!s.is_default, s.position));
+        triage.first().copied()
+    }
+
+    /// Unarchived issues of the team waiting in a triage state, oldest first
+    pub fn list_triage(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<Vec<crate::db::models::issue::IssueResponse>, AppError> {
+        TeamsService::get(conn, ctx, team_id)?;
+        let state_ids: Vec<Uuid> = WorkflowsRepo::list_states_by_team(conn, team_id)?
+            .into_iter()
+            .filter(|s| s.category == WorkflowStateCategory::Triage)
+            .map(|s| s.id)
+            .collect();
+        let issues = IssueRepo::list_in_states(conn, team_id, &state_ids)?;
+        Self::to_responses(conn, ctx, issues)
     }
 
     pub fn update(
@@ -142,6 +227,18 @@ impl IssuesService {
         ctx: &RequestContext,
         issue_id: Uuid,
         changes: &crate::routes::issues::UpdateIssueRequest,
+    ) -> Result<Issue, AppError> {
+        Self::update_with_clears(conn, ctx, issue_id, changes, &IssueFieldClears::default())
+    }
+
+    /// [`Self::update`] that can also clear nullable fields, which
+    /// `UpdateIssueRequest` can only set
+    pub fn update_with_clears(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        changes: &crate::routes::issues::UpdateIssueRequest,
+        clears: &IssueFieldClears,
     ) -> Result<Issue, AppError> {
         // Only validate title/description when provided
         if changes.title.is_some() || changes.description.is_some() {
@@ -176,6 +273,25 @@ impl IssuesService {
         if let Some(pr) = &changes.priority {
             cs.priority = Some(Self::priority_to_string(pr));
         }
+        if let Some(estimate) = changes.estimate {
+            if let Some(points) = estimate {
+                let team_id = changes.team_id.unwrap_or(existing.team_id);
+                Self::validate_team_estimate(conn, team_id, points)?;
+            }
+            cs.estimate = Some(estimate);
+        }
+        if clears.description {
+            cs.description = Some(None);
+        }
+        if clears.project_id {
+            cs.project_id = Some(None);
+        }
+        if clears.assignee_id {
+            cs.assignee_id = Some(None);
+        }
+        if clears.cycle_id {
+            cs.cycle_id = Some(None);
+        }
 
         // Handle workflow/workflow_state validation and setting
         use crate::schema::{workflow_states as ws, workflows as w};
@@ -319,6 +435,7 @@ impl IssuesService {
         }
 
         // Handle labels replacement if provided
+        let mut added_label_ids: Vec<Uuid> = Vec::new();
         if let Some(ref label_ids) = changes.label_ids {
             use crate::schema::{issue_labels as il, labels as l};
             use diesel::prelude::*;
@@ -336,6 +453,16 @@ impl IssuesService {
                 }
             }
 
+            let previous: Vec<Uuid> = LabelRepo::list_by_issue(conn, issue_id)?
+                .into_iter()
+                .map(|label| label.id)
+                .collect();
+            for label_id in label_ids {
+                if !previous.contains(label_id) && !added_label_ids.contains(label_id) {
+                    added_label_ids.push(*label_id);
+                }
+            }
+
             // Replace issue labels
             diesel::delete(il::dsl::issue_labels.filter(il::dsl::issue_id.eq(issue_id)))
                 .execute(conn)
@@ -367,21 +494,127 @@ impl IssuesService {
             || changes.cycle_id.is_some()
             || changes.priority.is_some()
             || changes.workflow_id.is_some()
-            || changes.workflow_state_id.is_some();
+            || changes.workflow_state_id.is_some()
+            || changes.estimate.is_some()
+            || clears.any();
 
-        if has_field_changes {
+        let updated = if has_field_changes {
             use crate::schema::issues::dsl as i;
-            let updated = diesel::update(i::issues.filter(i::id.eq(issue_id)))
+            diesel::update(i::issues.filter(i::id.eq(issue_id)))
                 .set(&cs)
                 .get_result::<Issue>(conn)
-                .map_err(|e| AppError::internal(format!("Failed to update issue: {}", e)))?;
-            Ok(updated)
+                .map_err(|e| AppError::internal(format!("Failed to update issue: {}", e)))?
         } else {
             // No changes to issue table; return current row
-            let current = IssueRepo::find_by_id_in_workspace(conn, ctx.workspace_id, issue_id)?
-                .ok_or_else(|| AppError::not_found("issue"))?;
-            Ok(current)
+            IssueRepo::find_by_id_in_workspace(conn, ctx.workspace_id, issue_id)?
+                .ok_or_else(|| AppError::not_found("issue"))?
+        };
+
+        let reassigned_to = changes
+            .assignee_id
+            .filter(|assignee_id| existing.assignee_id != Some(*assignee_id));
+        if let Some(assignee_id) = reassigned_to {
+            Self::notify_assignment(conn, ctx, &updated, assignee_id);
+        }
+        if has_field_changes || changes.label_ids.is_some() {
+            if reassigned_to.is_none() {
+                Self::notify_assignee(conn, ctx, &updated);
+            }
+            WebhookService::emit_quietly(
+                conn,
+                ctx.workspace_id,
+                webhook_events::ISSUE_UPDATED,
+                &updated,
+            );
+            RealtimeService::publish(
+                ctx.workspace_id,
+                Topic::Issue(updated.id),
+                webhook_events::ISSUE_UPDATED,
+                &updated,
+            );
+            RealtimeService::entity_changed(
+                ctx.workspace_id,
+                EntityKind::Issue,
+                EntityAction::Updated,
+                updated.id,
+                &updated,
+            );
         }
+
+        let mut events: Vec<AutomationEvent> = added_label_ids
+            .into_iter()
+            .map(|label_id| AutomationEvent::IssueLabelAdded { label_id })
+            .collect();
+        if updated.workflow_state_id != existing.workflow_state_id {
+            events.insert(
+                0,
+                AutomationEvent::IssueStateChanged {
+                    from_state_id: existing.workflow_state_id,
+                    to_state_id: updated.workflow_state_id,
+                },
+            );
+        }
+        AutomationsService::enqueue_quietly(conn, ctx, &updated, &events);
+
+        Ok(updated)
+    }
+
+    /// Check the estimate against the scale of the issue's team
+    fn validate_team_estimate(
+        conn: &mut PgConnection,
+        team_id: Uuid,
+        estimate: i32,
+    ) -> Result<(), AppError> {
+        use crate::schema::teams::dsl as t;
+        let scale: String = t::teams
+            .filter(t::id.eq(team_id))
+            .select(t::estimate_scale)
+            .first(conn)
+            .optional()?
+            .ok_or_else(|| AppError::validation("Invalid team_id for workspace"))?;
+        validate_estimate(&scale, estimate)
+    }
+
+    /// Tell the assignee their issue changed; repeated updates are coalesced
+    fn notify_assignee(conn: &mut PgConnection, ctx: &RequestContext, issue: &Issue) {
+        let Some(assignee_id) = issue.assignee_id else {
+            return;
+        };
+        NotificationsService::notify_quietly(
+            conn,
+            NewNotification {
+                workspace_id: ctx.workspace_id,
+                recipient_id: assignee_id,
+                actor_id: Some(ctx.user_id),
+                event_type: notification_events::ISSUE_UPDATED.to_string(),
+                entity_type: "issue".to_string(),
+                entity_id: issue.id,
+                title: issue.title.clone(),
+                body: None,
+            },
+        );
+    }
+
+    /// Tell a user they were assigned the issue
+    fn notify_assignment(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue: &Issue,
+        assignee_id: Uuid,
+    ) {
+        NotificationsService::notify_quietly(
+            conn,
+            NewNotification {
+                workspace_id: ctx.workspace_id,
+                recipient_id: assignee_id,
+                actor_id: Some(ctx.user_id),
+                event_type: notification_events::ISSUE_ASSIGNED.to_string(),
+                entity_type: "issue".to_string(),
+                entity_id: issue.id,
+                title: issue.title.clone(),
+                body: None,
+            },
+        );
     }
 
     pub fn delete(
@@ -397,10 +630,48 @@ impl IssuesService {
 
         IssueRepo::delete_by_id(conn, issue_id)
             .map_err(|e| AppError::internal(format!("Failed to delete issue: {}", e)))?;
+        WebhookService::emit_quietly(
+            conn,
+            ctx.workspace_id,
+            webhook_events::ISSUE_DELETED,
+            &serde_json::json!({ "id": issue_id }),
+        );
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Issue(issue_id),
+            webhook_events::ISSUE_DELETED,
+            &serde_json::json!({ "id": issue_id }),
+        );
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Issue,
+            EntityAction::Deleted,
+            issue_id,
+            &(),
+        );
 
         Ok(())
     }
 
+    /// Full text of a code block in an issue's description
+    pub fn description_code_block(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+        index: usize,
+    ) -> Result<String, AppError> {
+        if !IssueRepo::exists_in_workspace(conn, ctx.workspace_id, issue_id)? {
+            return Err(AppError::not_found("issue"));
+        }
+        let issue = IssueRepo::find_by_id_in_workspace(conn, ctx.workspace_id, issue_id)?
+            .ok_or_else(|| AppError::not_found("issue"))?;
+        issue
+            .description
+            .as_deref()
+            .and_then(|description| MarkdownService::code_block(description, index))
+            .ok_or_else(|| AppError::not_found("code block"))
+    }
+
     pub fn get_by_id(
         conn: &mut PgConnection,
         ctx: &RequestContext,
@@ -410,6 +681,8 @@ impl IssuesService {
             .ok_or_else(|| AppError::not_found("issue"))?;
 
         let mut resp = crate::db::models::issue::IssueResponse::from(issue.clone());
+        let last_viewed = IssueViewsRepo::last_viewed_map(conn, ctx.user_id, &[issue.id])?;
+        resp.set_last_viewed(last_viewed.get(&issue.id).copied());
 
         // team info + team_key
         {
@@ -421,6 +694,10 @@ impl IssuesService {
                 .map_err(|e| AppError::internal(format!("Failed to load team: {}", e)))?
             {
                 resp.team_key = Some(team.team_key.clone());
+                resp.identifier = Some(IssueMovesService::issue_key(
+                    &team.team_key,
+                    issue.issue_number,
+                ));
                 resp.team = Some(TeamBasicInfo {
                     id: team.id,
                     name: team.name,
@@ -492,6 +769,8 @@ impl IssuesService {
                     },
                     target_date: project.target_date,
                     priority: project.priority,
+                    budget_currency: project.budget_currency,
+                    budget_amount_cents: project.budget_amount_cents,
                     created_at: project.created_at,
                     updated_at: project.updated_at,
                 });
@@ -518,12 +797,12 @@ impl IssuesService {
         }
 
         // parent issue
-        if let Some(pid) = issue.parent_issue_id {
-            if let Some(parent) = IssueRepo::find_by_id(conn, pid)? {
-                resp.parent_issue = Some(Box::new(crate::db::models::issue::IssueResponse::from(
-                    parent,
-                )));
-            }
+        if let Some(pid) = issue.parent_issue_id
+            && let Some(parent) = IssueRepo::find_by_id(conn, pid)?
+        {
+            resp.parent_issue = Some(Box::new(crate::db::models::issue::IssueResponse::from(
+                parent,
+            )));
         }
 
         // child issues
@@ -540,6 +819,11 @@ impl IssuesService {
                 .collect();
         }
 
+        // relations
+        resp.relations = IssueRelationsService::list_for_issue(conn, issue.id)?;
+        resp.external_relations =
+            CrossWorkspaceRelationsService::list_for_issue(conn, ctx.workspace_id, issue.id)?;
+
         // workflow states
         let states = if let Some(wf_id) = issue.workflow_id {
             WorkflowsRepo::list_states_by_workflow(conn, wf_id)
@@ -582,6 +866,95 @@ impl IssuesService {
         Ok(resp)
     }
 
+    /// Issue by key such as `ENG-42`, matching the team key in any case.
+    /// Keys an issue had before it moved to another team still find it.
+    pub fn get_by_key(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        key: &str,
+    ) -> Result<crate::db::models::issue::IssueResponse, AppError> {
+        let (team_key, number) = IssueMovesService::parse_issue_key(key)
+            .ok_or_else(|| AppError::validation("Issue key must look like ENG-42"))?;
+        let team = IssueLinksRepo::team_keys(conn, ctx.workspace_id)?
+            .into_iter()
+            .find(|(_, key)| key.eq_ignore_ascii_case(team_key));
+        if let Some((team_id, _)) = &team
+            && let Some(issue) = IssueLinksRepo::find_issue(conn, *team_id, number)?
+        {
+            return Self::get_by_id(conn, ctx, issue.id);
+        }
+        let former_key = match &team {
+            Some((_, key)) => IssueMovesService::issue_key(key, number),
+            None => key.trim().to_string(),
+        };
+        match IssueMovesRepo::find_moved_from(conn, ctx.workspace_id, &former_key)? {
+            Some(issue_id) => Self::get_by_id(conn, ctx, issue_id),
+            None => Err(AppError::not_found("issue")),
+        }
+    }
+
+    /// Record that the current user opened the issue and mark their
+    /// notifications about it as read, so they are not emailed as well.
+    pub fn mark_viewed(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<IssueView, AppError> {
+        let now = clock::now();
+        conn.transaction::<_, AppError, _>(|conn| {
+            let view = IssueViewsRepo::record(
+                conn,
+                &NewIssueView {
+                    user_id: ctx.user_id,
+                    issue_id,
+                    last_viewed_at: now,
+                },
+            )?;
+            NotificationsRepo::mark_read_for_entity(conn, ctx.user_id, "issue", issue_id, now)?;
+            Ok(view)
+        })
+    }
+
+    /// Users who have opened the issue; restricted to workspace owners and admins
+    pub fn list_viewers(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueViewer>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ViewIssueViewers)?;
+
+        let in_workspace = {
+            use crate::schema::{issues::dsl as i, teams::dsl as t};
+            i::issues
+                .inner_join(t::teams.on(t::id.eq(i::team_id)))
+                .filter(i::id.eq(issue_id))
+                .filter(t::workspace_id.eq(ctx.workspace_id))
+                .select(i::id)
+                .first::<Uuid>(conn)
+                .optional()?
+        };
+        if in_workspace.is_none() {
+            return Err(AppError::not_found("issue"));
+        }
+
+        let viewers = IssueViewsRepo::list_by_issue(conn, issue_id)?
+            .into_iter()
+            .map(|(view, user)| IssueViewer {
+                user: crate::db::models::auth::UserBasicInfo {
+                    id: user.id,
+                    name: user.name,
+                    username: user.username,
+                    email: user.email,
+                    avatar_url: user.avatar_url,
+                },
+                first_viewed_at: view.first_viewed_at,
+                last_viewed_at: view.last_viewed_at,
+                view_count: view.view_count,
+            })
+            .collect();
+        Ok(viewers)
+    }
+
     // WebSocket command handlers
     pub fn create_from_ws_command(
         conn: &mut PgConnection,
@@ -611,6 +984,7 @@ impl IssuesService {
             label_ids: cmd.label_ids.clone(),
             cycle_id: cmd.cycle_id,
             parent_issue_id: cmd.parent_issue_id,
+            estimate: cmd.estimate,
         };
 
         let issue = Self::create(conn, ctx, &req)?;
@@ -641,6 +1015,7 @@ impl IssuesService {
             workflow_state_id: cmd.workflow_state_id,
             cycle_id: cmd.cycle_id,
             label_ids: cmd.label_ids.clone(),
+            estimate: cmd.estimate,
         };
 
         Self::update(conn, ctx, issue_id, &req)?;
@@ -652,6 +1027,18 @@ impl IssuesService {
         ctx: &RequestContext,
         filters: &crate::websocket::commands::IssueFilters,
     ) -> Result<Vec<crate::db::models::issue::IssueResponse>, AppError> {
+        if let Some(search) = &filters.search {
+            let search_filters = IssueSearchFilters {
+                team_id: filters.team_id,
+                project_id: filters.project_id,
+                assignee_id: filters.assignee_id,
+                priority: filters.priority.clone(),
+            };
+            let hits =
+                SearchService::search_issues(conn, ctx, search, &search_filters, filters.limit)?;
+            return Ok(hits.into_iter().map(|hit| hit.issue).collect());
+        }
+
         let priority_enum = if let Some(ref priority_str) = filters.priority {
             Some(Self::parse_priority(priority_str)?)
         } else {
@@ -664,6 +1051,7 @@ impl IssuesService {
             assignee_id: filters.assignee_id,
             priority: priority_enum,
             search: filters.search.clone(),
+            include_archived: filters.include_archived,
         };
 
         Self::list(conn, ctx, &service_filters)
@@ -677,4 +1065,6 @@ pub struct IssueFilters {
     pub assignee_id: Option<Uuid>,
     pub priority: Option<IssuePriority>,
     pub search: Option<String>,
+    /// Also list archived issues
+    pub include_archived: bool,
 }
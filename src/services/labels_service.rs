@@ -1,16 +1,51 @@
 use diesel::prelude::*;
 
 use crate::{
+    cache::typed::{self, CacheEntry},
+    db::DbExecutor,
     db::models::label::{Label, NewLabel},
     db::repositories::labels::LabelRepo,
     error::AppError,
     services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    utils::clock,
     validation::label::validate_create_label,
+    websocket::{EntityAction, EntityKind},
 };
 
+/// All labels of a workspace, newest first
+pub static LABELS_CACHE: CacheEntry<Vec<Label>> = CacheEntry::new("labels", 600);
+
 pub struct LabelsService;
 
 impl LabelsService {
+    /// [`Self::list`] served from the installed cache. The workspace's labels
+    /// are cached as a whole and the level filter applied to them; name
+    /// searches go to the database.
+    pub async fn list_cached(
+        db: &DbExecutor,
+        ctx: &RequestContext,
+        name_filter: Option<String>,
+        level_filter: Option<crate::db::enums::LabelLevel>,
+    ) -> Result<Vec<Label>, AppError> {
+        if name_filter.is_some() {
+            let ctx = ctx.clone();
+            return db
+                .read(move |conn| Self::list(conn, &ctx, name_filter, level_filter))
+                .await;
+        }
+        let mut labels = LABELS_CACHE
+            .get_or_load(typed::installed(), ctx.workspace_id, "all", || {
+                let ctx = ctx.clone();
+                db.read(move |conn| Self::list(conn, &ctx, None, None))
+            })
+            .await?;
+        if let Some(level) = level_filter {
+            labels.retain(|label| label.level == level);
+        }
+        Ok(labels)
+    }
+
     pub fn list(
         conn: &mut PgConnection,
         ctx: &RequestContext,
@@ -48,7 +83,7 @@ impl LabelsService {
             ));
         }
 
-        let now = chrono::Utc::now().naive_utc();
+        let now = clock::now().naive_utc();
         let new_label = NewLabel {
             workspace_id: ctx.workspace_id,
             name: req.name.clone(),
@@ -59,6 +94,14 @@ impl LabelsService {
         };
 
         let label = LabelRepo::insert(conn, &new_label)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Label,
+            EntityAction::Created,
+            label.id,
+            &label,
+        );
+        typed::invalidate_after_commit(&LABELS_CACHE, ctx.workspace_id);
         Ok(label)
     }
 
@@ -82,14 +125,14 @@ impl LabelsService {
         crate::validation::label::validate_update_label(&update_changes)?;
 
         // enforce name uniqueness if name changes
-        if let Some(ref new_name) = changes.name {
-            if LabelRepo::exists_by_name_excluding_id(conn, ctx.workspace_id, new_name, label_id)? {
-                return Err(AppError::conflict_with_code(
-                    "Label already exists",
-                    Some("name".to_string()),
-                    "LABEL_EXISTS",
-                ));
-            }
+        if let Some(ref new_name) = changes.name
+            && LabelRepo::exists_by_name_excluding_id(conn, ctx.workspace_id, new_name, label_id)?
+        {
+            return Err(AppError::conflict_with_code(
+                "Label already exists",
+                Some("name".to_string()),
+                "LABEL_EXISTS",
+            ));
         }
 
         let updated = LabelRepo::update_fields(
@@ -101,6 +144,14 @@ impl LabelsService {
                 changes.level.clone(),
             ),
         )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Label,
+            EntityAction::Updated,
+            updated.id,
+            &updated,
+        );
+        typed::invalidate_after_commit(&LABELS_CACHE, ctx.workspace_id);
         Ok(updated)
     }
 
@@ -115,6 +166,14 @@ impl LabelsService {
             return Err(AppError::not_found("label"));
         }
         LabelRepo::delete_by_id(conn, label_id)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Label,
+            EntityAction::Deleted,
+            label_id,
+            &(),
+        );
+        typed::invalidate_after_commit(&LABELS_CACHE, ctx.workspace_id);
         Ok(())
     }
 }
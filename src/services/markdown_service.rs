@@ -0,0 +1,506 @@
+use std::sync::LazyLock;
+
+use diesel::prelude::*;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd, html};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    cache::CacheStore,
+    db::models::markdown::{
+        CodeBlockInfo, IssueReferenceEntity, MarkdownEntities, MentionEntity, RenderedBody,
+        RenderedMarkdown, WithRenderedMarkdown,
+    },
+    db::repositories::issue_links::IssueLinksRepo,
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
+    error::AppError,
+    services::github_integration_service::GithubIntegrationService,
+    services::issue_moves_service::IssueMovesService,
+};
+
+/// Bump when rendering changes so cached output from the old renderer is
+/// not served
+const CACHE_KEY_PREFIX: &str = "markdown:v2:";
+const CACHE_TTL_SECS: u64 = 24 * 3600;
+
+/// Code blocks past either limit are cut short in the HTML, so a pasted log
+/// does not bloat emails and webhook payloads
+pub const MAX_CODE_BLOCK_LINES: usize = 200;
+pub const MAX_CODE_BLOCK_BYTES: usize = 32 * 1024;
+
+/// Languages code blocks are labelled with, and other fence names for them
+const LANGUAGES: &[(&str, &[&str])] = &[
+    ("c", &["h"]),
+    ("cpp", &["c++", "cc", "cxx", "hpp"]),
+    ("csharp", &["cs", "c#"]),
+    ("css", &[]),
+    ("diff", &["patch"]),
+    ("dockerfile", &["docker"]),
+    ("go", &["golang"]),
+    ("graphql", &["gql"]),
+    ("html", &["htm"]),
+    ("java", &[]),
+    ("javascript", &["js", "jsx", "mjs", "node"]),
+    ("json", &["jsonc"]),
+    ("kotlin", &["kt"]),
+    ("log", &[]),
+    ("markdown", &["md"]),
+    ("php", &[]),
+    ("python", &["py", "python3"]),
+    ("ruby", &["rb"]),
+    ("rust", &["rs"]),
+    ("shell", &["sh", "bash", "zsh", "console", "shell-session"]),
+    ("sql", &["postgres", "postgresql", "psql"]),
+    ("swift", &[]),
+    ("text", &["txt", "plain", "plaintext"]),
+    ("toml", &[]),
+    ("typescript", &["ts", "tsx"]),
+    ("xml", &["svg"]),
+    ("yaml", &["yml"]),
+];
+
+/// Snippets whose presence suggests a language for unlabelled code; earlier
+/// entries win ties
+const LANGUAGE_HINTS: &[(&str, &[&str])] = &[
+    (
+        "rust",
+        &[
+            "fn ",
+            "let mut ",
+            "impl ",
+            "pub fn ",
+            "use std::",
+            "println!(",
+            "#[derive(",
+        ],
+    ),
+    ("go", &["func ", "package ", " := ", "fmt.", "err != nil"]),
+    (
+        "python",
+        &["def ", "import ", "self.", "elif ", "print(", "__init__"],
+    ),
+    (
+        "typescript",
+        &[
+            "interface ",
+            ": string",
+            ": number",
+            ": boolean",
+            "export type ",
+        ],
+    ),
+    (
+        "javascript",
+        &[
+            "function ",
+            "const ",
+            " => ",
+            "console.log(",
+            "require(",
+            "module.exports",
+        ],
+    ),
+    (
+        "sql",
+        &[
+            "SELECT ",
+            " FROM ",
+            " WHERE ",
+            "INSERT INTO ",
+            "CREATE TABLE ",
+            " JOIN ",
+        ],
+    ),
+    (
+        "shell",
+        &["$ ", "sudo ", "echo ", "apt-get ", "curl ", "| grep "],
+    ),
+];
+
+/// Levels that start a log line, bare or in brackets
+const LOG_LEVELS: [&str; 7] = [
+    "TRACE", "DEBUG", "INFO", "WARN", "WARNING", "ERROR", "FATAL",
+];
+
+static LANGUAGE_CLASSES: LazyLock<Vec<String>> = LazyLock::new(|| {
+    LANGUAGES
+        .iter()
+        .map(|(language, _)| format!("language-{}", language))
+        .collect()
+});
+
+/// Ammonia's defaults plus the classes of labelled code blocks and the
+/// truncation marker
+static SANITIZER: LazyLock<ammonia::Builder<'static>> = LazyLock::new(|| {
+    let mut sanitizer = ammonia::Builder::default();
+    sanitizer
+        .add_allowed_classes("code", LANGUAGE_CLASSES.iter())
+        .add_allowed_classes("p", ["code-truncated"]);
+    sanitizer
+});
+
+/// Longest username a mention can name, as in `users.username`
+const MAX_USERNAME_LEN: usize = 100;
+
+/// Server-side rendering of issue descriptions and comments for email and
+/// webhook payloads
+pub struct MarkdownService;
+
+impl MarkdownService {
+    /// Render markdown to sanitized HTML along with its plain text, mentions
+    /// and code blocks. Raw HTML in the source is kept only where it is safe,
+    /// and links get `rel="noopener noreferrer"`. Code blocks are labelled
+    /// with their language and cut short past the size cap.
+    pub fn render(markdown: &str) -> RenderedMarkdown {
+        let options = Self::options();
+        let mut events = Vec::new();
+        let mut code_blocks = Vec::new();
+        let mut parser = Parser::new_ext(markdown, options);
+        while let Some(event) = parser.next() {
+            let Event::Start(Tag::CodeBlock(kind)) = event else {
+                events.push(event);
+                continue;
+            };
+            let code = Self::read_code_block(&mut parser);
+            let (block, shown) = Self::code_block_info(code_blocks.len(), &kind, &code);
+            events.push(Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(
+                block.language.clone().unwrap_or_default().into(),
+            ))));
+            events.push(Event::Text(code[..shown].to_string().into()));
+            events.push(Event::End(TagEnd::CodeBlock));
+            if block.truncated {
+                events.push(Event::Html(
+                    format!(
+                        "<p class=\"code-truncated\">Truncated: {} of {} lines shown</p>\n",
+                        code[..shown].lines().count(),
+                        block.lines
+                    )
+                    .into(),
+                ));
+            }
+            code_blocks.push(block);
+        }
+
+        let mut unsafe_html = String::new();
+        html::push_html(&mut unsafe_html, events.into_iter());
+        let text = Self::plain_text(markdown, options);
+        RenderedMarkdown {
+            html: SANITIZER.clean(&unsafe_html).to_string(),
+            mentions: Self::mentions(&text),
+            text,
+            code_blocks,
+        }
+    }
+
+    /// Full text of the `index`th code block, for blocks truncated in the
+    /// rendered HTML
+    pub fn code_block(markdown: &str, index: usize) -> Option<String> {
+        let mut parser = Parser::new_ext(markdown, Self::options());
+        let mut current = 0;
+        while let Some(event) = parser.next() {
+            if let Event::Start(Tag::CodeBlock(_)) = event {
+                let code = Self::read_code_block(&mut parser);
+                if current == index {
+                    return Some(code);
+                }
+                current += 1;
+            }
+        }
+        None
+    }
+
+    /// Language named by a code fence's info string, e.g. `rust` for
+    /// `rs,ignore`
+    pub fn fence_language(info: &str) -> Option<&'static str> {
+        let name = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()?
+            .to_ascii_lowercase();
+        LANGUAGES
+            .iter()
+            .find(|(language, aliases)| *language == name || aliases.contains(&name.as_str()))
+            .map(|(language, _)| *language)
+    }
+
+    /// Best guess at the language of unlabelled code; `None` unless the code
+    /// is recognizably one of a few common languages or a log
+    pub fn detect_language(code: &str) -> Option<&'static str> {
+        let trimmed = code.trim_start();
+        if let Some(shebang) = trimmed.strip_prefix("#!") {
+            let interpreter = shebang.lines().next().unwrap_or_default();
+            return Some(if interpreter.contains("python") {
+                "python"
+            } else if interpreter.contains("node") {
+                "javascript"
+            } else {
+                "shell"
+            });
+        }
+        if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+        {
+            return Some("json");
+        }
+        if trimmed.starts_with("diff --git")
+            || (trimmed.starts_with("--- ") && code.contains("\n+++ "))
+        {
+            return Some("diff");
+        }
+        let head = trimmed
+            .get(..trimmed.len().min(16))
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if head.starts_with("<?xml") {
+            return Some("xml");
+        }
+        if head.starts_with("<!doctype html") || head.starts_with("<html") {
+            return Some("html");
+        }
+
+        let lines: Vec<&str> = code
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        if !lines.is_empty()
+            && lines.iter().filter(|line| Self::is_log_line(line)).count() * 2 >= lines.len()
+        {
+            return Some("log");
+        }
+
+        // Reversed so that max_by_key, which keeps the last maximum, prefers
+        // earlier entries
+        LANGUAGE_HINTS
+            .iter()
+            .rev()
+            .map(|(language, hints)| {
+                (
+                    *language,
+                    hints.iter().filter(|hint| code.contains(*hint)).count(),
+                )
+            })
+            .max_by_key(|(_, score)| *score)
+            .filter(|(_, score)| *score >= 2)
+            .map(|(language, _)| language)
+    }
+
+    /// [`Self::render`] through the cache, keyed by a hash of the markdown.
+    /// Cache errors are logged and the markdown is rendered directly.
+    pub async fn render_cached(cache: &dyn CacheStore, markdown: &str) -> RenderedMarkdown {
+        let key = Self::cache_key(markdown);
+        match cache.get(&key).await {
+            Ok(Some(cached)) => match serde_json::from_str(&cached) {
+                Ok(rendered) => return rendered,
+                Err(e) => tracing::warn!("Ignoring unreadable cached markdown {}: {}", key, e),
+            },
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to read cached markdown: {}", e),
+        }
+
+        let rendered = Self::render(markdown);
+        match serde_json::to_string(&rendered) {
+            Ok(value) => {
+                if let Err(e) = cache.set_ex(&key, value, CACHE_TTL_SECS).await {
+                    tracing::warn!("Failed to cache rendered markdown: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize rendered markdown: {}", e),
+        }
+        rendered
+    }
+
+    pub fn cache_key(markdown: &str) -> String {
+        format!(
+            "{}{}",
+            CACHE_KEY_PREFIX,
+            hex::encode(Sha256::digest(markdown.as_bytes()))
+        )
+    }
+
+    /// Mentions of workspace members and references to issues of the
+    /// workspace's teams (`ENG-42`); names and keys that match nothing are
+    /// left out
+    pub fn entities(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        rendered: &RenderedMarkdown,
+    ) -> Result<MarkdownEntities, AppError> {
+        let mut entities = MarkdownEntities::default();
+
+        if !rendered.mentions.is_empty() {
+            let users = WorkspaceMembersRepo::find_users_by_usernames(
+                conn,
+                workspace_id,
+                &rendered.mentions,
+            )?;
+            entities.mentions = rendered
+                .mentions
+                .iter()
+                .filter_map(|username| users.iter().find(|user| &user.username == username))
+                .map(|user| MentionEntity {
+                    user_id: user.id,
+                    username: user.username.clone(),
+                })
+                .collect();
+        }
+
+        let teams = TeamsRepo::list_by_workspace(conn, workspace_id)?;
+        let keys: Vec<&str> = teams.iter().map(|team| team.team_key.as_str()).collect();
+        for (team, number) in GithubIntegrationService::find_issue_keys(&rendered.text, &keys) {
+            let team = &teams[team];
+            if let Some(issue) = IssueLinksRepo::find_issue(conn, team.id, number)? {
+                entities.issue_references.push(IssueReferenceEntity {
+                    issue_id: issue.id,
+                    key: IssueMovesService::issue_key(&team.team_key, number),
+                    team_id: team.id,
+                    issue_number: number,
+                });
+            }
+        }
+        Ok(entities)
+    }
+
+    /// `data` with `markdown` rendered for a webhook payload. Entities that
+    /// fail to load are logged and left out rather than failing the event.
+    pub fn webhook_payload<'a, T>(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        data: &'a T,
+        markdown: Option<&str>,
+    ) -> WithRenderedMarkdown<'a, T> {
+        let rendered = markdown
+            .filter(|markdown| !markdown.trim().is_empty())
+            .map(|markdown| {
+                let rendered = Self::render(markdown);
+                let entities = Self::entities(conn, workspace_id, &rendered).unwrap_or_else(|e| {
+                    tracing::warn!("Failed to resolve markdown entities: {}", e);
+                    MarkdownEntities::default()
+                });
+                RenderedBody::new(rendered, entities)
+            });
+        WithRenderedMarkdown { data, rendered }
+    }
+
+    fn options() -> Options {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options
+    }
+
+    /// Text of the code block whose start tag was just read, consuming
+    /// events through its end tag
+    fn read_code_block(parser: &mut Parser) -> String {
+        let mut code = String::new();
+        for event in parser.by_ref() {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => break,
+                _ => {}
+            }
+        }
+        code
+    }
+
+    /// Metadata of a code block and how many of its bytes to show
+    fn code_block_info(index: usize, kind: &CodeBlockKind, code: &str) -> (CodeBlockInfo, usize) {
+        let fence_language = match kind {
+            CodeBlockKind::Fenced(info) => Self::fence_language(info),
+            CodeBlockKind::Indented => None,
+        };
+        let (language, language_detected) = match fence_language {
+            Some(language) => (Some(language), false),
+            None => {
+                let detected = Self::detect_language(code);
+                (detected, detected.is_some())
+            }
+        };
+
+        let mut shown = code.len();
+        if let Some((newline, _)) = code.match_indices('\n').nth(MAX_CODE_BLOCK_LINES - 1) {
+            shown = newline + 1;
+        }
+        if shown > MAX_CODE_BLOCK_BYTES {
+            shown = MAX_CODE_BLOCK_BYTES;
+            while !code.is_char_boundary(shown) {
+                shown -= 1;
+            }
+            if let Some(newline) = code[..shown].rfind('\n') {
+                shown = newline + 1;
+            }
+        }
+
+        let block = CodeBlockInfo {
+            index,
+            language: language.map(str::to_string),
+            language_detected,
+            lines: code.lines().count(),
+            bytes: code.len(),
+            truncated: shown < code.len(),
+        };
+        (block, shown)
+    }
+
+    /// Whether a line starts with a timestamp or a log level
+    fn is_log_line(line: &str) -> bool {
+        let line = line.trim_start().trim_start_matches('[');
+        let bytes = line.as_bytes();
+        let is_date = bytes.len() >= 10
+            && bytes[..10].iter().enumerate().all(|(i, b)| match i {
+                4 | 7 => *b == b'-',
+                _ => b.is_ascii_digit(),
+            });
+        is_date
+            || LOG_LEVELS.iter().any(|level| {
+                line.strip_prefix(level)
+                    .is_some_and(|rest| rest.starts_with([' ', ':', ']']))
+            })
+    }
+
+    /// Text of the document without code, so mentions and keys in code
+    /// samples are not picked up
+    fn plain_text(markdown: &str, options: Options) -> String {
+        let mut text = String::new();
+        let mut in_code_block = false;
+        for event in Parser::new_ext(markdown, options) {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => {
+                    in_code_block = false;
+                    text.push('\n');
+                }
+                Event::Text(value) if !in_code_block => text.push_str(&value),
+                Event::Code(_) => text.push(' '),
+                Event::SoftBreak | Event::HardBreak => text.push('\n'),
+                Event::End(
+                    TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell,
+                ) => text.push('\n'),
+                _ => {}
+            }
+        }
+        text.trim_end().to_string()
+    }
+
+    /// `@username` mentions, without duplicates. The `@` must not follow a
+    /// name character, so email addresses are not mentions.
+    fn mentions(text: &str) -> Vec<String> {
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-');
+        let mut mentions: Vec<String> = Vec::new();
+        let mut previous: Option<char> = None;
+        for (at, c) in text.char_indices() {
+            if c == '@' && !previous.is_some_and(is_name_char) {
+                let rest = &text[at + 1..];
+                let end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+                let username = rest[..end].trim_end_matches(['.', '-']);
+                if !username.is_empty()
+                    && username.len() <= MAX_USERNAME_LEN
+                    && !mentions.iter().any(|known| known == username)
+                {
+                    mentions.push(username.to_string());
+                }
+            }
+            previous = Some(c);
+        }
+        mentions
+    }
+}
@@ -0,0 +1,356 @@
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    db::models::member_import::{
+        MemberImport, MemberImportLine, MemberImportResponse, MemberImportRow, NewMemberImport,
+        NewMemberImportRow, member_import_row_status,
+    },
+    db::models::team::Team,
+    db::models::workspace_member::{NewWorkspaceMember, WorkspaceMemberRole, member_change},
+    db::repositories::auth::AuthRepo,
+    db::repositories::invitations::InvitationsRepo,
+    db::repositories::member_imports::MemberImportsRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
+    error::AppError,
+    services::context::{AuthChannel, RequestContext},
+    services::invitations_service::InvitationsService,
+    services::team_members_service::TeamMembersService,
+    services::workspace_members_service::WorkspaceMembersService,
+    utils::clock,
+    utils::csv::parse_csv,
+    validation::invitation::validate_invite_email,
+};
+
+/// Redis list the worker pops jobs from
+pub const JOB_QUEUE: &str = "tasks";
+
+/// Job name prefix; the task is `member_import:<import id>`
+pub const JOB_PREFIX: &str = "member_import:";
+
+/// Most data rows accepted in one upload
+pub const MAX_IMPORT_ROWS: usize = 1000;
+
+pub struct MemberImportsService;
+
+impl MemberImportsService {
+    /// Parse the CSV into rows. The header must name an `email` column;
+    /// `role` and `teams` are optional, and teams are separated by `;` or `|`.
+    pub fn parse_lines(content: &str) -> Result<Vec<MemberImportLine>, AppError> {
+        let mut records = parse_csv(content)
+            .map_err(AppError::validation)?
+            .into_iter();
+        let header = records
+            .next()
+            .ok_or_else(|| AppError::validation("CSV is empty"))?;
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+        };
+        let email_col =
+            column("email").ok_or_else(|| AppError::validation("CSV must have an email column"))?;
+        let role_col = column("role");
+        let teams_col = column("teams");
+
+        let lines: Vec<MemberImportLine> = records
+            .enumerate()
+            .map(|(i, record)| {
+                let field = |col: Option<usize>| {
+                    col.and_then(|c| record.get(c))
+                        .map(|v| v.trim().to_string())
+                        .unwrap_or_default()
+                };
+                MemberImportLine {
+                    row_number: i as i32 + 1,
+                    email: field(Some(email_col)),
+                    role: field(role_col).to_ascii_lowercase(),
+                    teams: field(teams_col)
+                        .split([';', '|'])
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                }
+            })
+            .collect();
+
+        if lines.is_empty() {
+            return Err(AppError::validation("CSV has no rows"));
+        }
+        if lines.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::validation(format!(
+                "CSV has {} rows; at most {} can be imported at once",
+                lines.len(),
+                MAX_IMPORT_ROWS
+            )));
+        }
+        Ok(lines)
+    }
+
+    /// Roles that can be granted by import; owners are never imported
+    pub fn parse_role(role: &str) -> Option<WorkspaceMemberRole> {
+        match role {
+            "" | "member" => Some(WorkspaceMemberRole::Member),
+            "admin" => Some(WorkspaceMemberRole::Admin),
+            "guest" => Some(WorkspaceMemberRole::Guest),
+            _ => None,
+        }
+    }
+
+    fn role_name(role: &WorkspaceMemberRole) -> &'static str {
+        match role {
+            WorkspaceMemberRole::Owner => "owner",
+            WorkspaceMemberRole::Admin => "admin",
+            WorkspaceMemberRole::Member => "member",
+            WorkspaceMemberRole::Guest => "guest",
+        }
+    }
+
+    /// Validate each line against the workspace's teams. Returns the row to
+    /// store; invalid rows carry the reason and are never applied.
+    pub fn validate_lines(lines: &[MemberImportLine], teams: &[Team]) -> Vec<NewMemberImportRow> {
+        let mut seen: Vec<(String, i32)> = Vec::new();
+        lines
+            .iter()
+            .map(|line| {
+                let mut row = NewMemberImportRow {
+                    import_id: Uuid::nil(),
+                    row_number: line.row_number,
+                    email: line.email.chars().take(255).collect(),
+                    role: line.role.chars().take(20).collect(),
+                    team_ids: String::new(),
+                    status: member_import_row_status::INVALID.to_string(),
+                    message: None,
+                };
+
+                if let Err(err) = validate_invite_email(&line.email) {
+                    row.message = Some(match err {
+                        AppError::Validation { message } => message,
+                        other => other.to_string(),
+                    });
+                    return row;
+                }
+                let key = line.email.to_lowercase();
+                if let Some((_, first)) = seen.iter().find(|(email, _)| *email == key) {
+                    row.message = Some(format!("Duplicate of row {}", first));
+                    return row;
+                }
+                seen.push((key, line.row_number));
+
+                let Some(role) = Self::parse_role(&line.role) else {
+                    row.message = Some(format!("Invalid role '{}'", line.role));
+                    return row;
+                };
+                row.role = Self::role_name(&role).to_string();
+
+                let mut team_ids: Vec<String> = Vec::new();
+                for name in &line.teams {
+                    let team = teams.iter().find(|t| {
+                        t.team_key.eq_ignore_ascii_case(name) || t.name.eq_ignore_ascii_case(name)
+                    });
+                    match team {
+                        Some(team) => {
+                            let id = team.id.to_string();
+                            if !team_ids.contains(&id) {
+                                team_ids.push(id);
+                            }
+                        }
+                        None => {
+                            row.message = Some(format!("Unknown team '{}'", name));
+                            return row;
+                        }
+                    }
+                }
+
+                row.team_ids = team_ids.join(" ");
+                row.status = member_import_row_status::PENDING.to_string();
+                row
+            })
+            .collect()
+    }
+
+    pub fn content_hash(content: &str) -> String {
+        hex::encode(Sha256::digest(content.as_bytes()))
+    }
+
+    /// Validate the CSV and record an import. Uploading the same file again
+    /// returns the existing import instead of creating another; the flag is
+    /// `true` only when a new import was created and needs to be queued.
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        content: &str,
+    ) -> Result<(MemberImportResponse, bool), AppError> {
+        let hash = Self::content_hash(content);
+        if let Some(existing) = MemberImportsRepo::find_by_hash(conn, ctx.workspace_id, &hash)? {
+            return Ok((Self::with_rows(conn, existing)?, false));
+        }
+
+        let lines = Self::parse_lines(content)?;
+        let teams = {
+            use crate::schema::teams::dsl as t;
+            t::teams
+                .filter(t::workspace_id.eq(ctx.workspace_id))
+                .select(Team::as_select())
+                .load::<Team>(conn)?
+        };
+        let rows = Self::validate_lines(&lines, &teams);
+
+        let import = MemberImportsRepo::insert(
+            conn,
+            &NewMemberImport {
+                workspace_id: ctx.workspace_id,
+                requested_by: ctx.user_id,
+                content_hash: hash,
+                total_rows: rows.len() as i32,
+            },
+            &rows,
+        )?;
+        Ok((Self::with_rows(conn, import)?, true))
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        import_id: Uuid,
+    ) -> Result<MemberImportResponse, AppError> {
+        let import = MemberImportsRepo::find_in_workspace(conn, ctx.workspace_id, import_id)?
+            .ok_or_else(|| AppError::not_found("member import"))?;
+        Self::with_rows(conn, import)
+    }
+
+    fn with_rows(
+        conn: &mut PgConnection,
+        import: MemberImport,
+    ) -> Result<MemberImportResponse, AppError> {
+        let rows = MemberImportsRepo::list_rows(conn, import.id)?;
+        Ok(MemberImportResponse { import, rows })
+    }
+
+    /// Push the import onto the worker queue
+    pub async fn enqueue(redis: &redis::Client, import_id: Uuid) -> redis::RedisResult<()> {
+        use redis::AsyncCommands;
+        let mut conn = redis.get_multiplexed_async_connection().await?;
+        conn.rpush(JOB_QUEUE, format!("{}{}", JOB_PREFIX, import_id))
+            .await
+    }
+
+    /// Import id of a queued job, if the task is a member import
+    pub fn parse_job(task: &str) -> Option<Uuid> {
+        task.strip_prefix(JOB_PREFIX)?.parse().ok()
+    }
+
+    /// Apply the pending rows of an import. Returns `None` when the import is
+    /// not pending, so duplicate jobs are harmless.
+    pub fn run(conn: &mut PgConnection, import_id: Uuid) -> Result<Option<MemberImport>, AppError> {
+        let Some(import) = MemberImportsRepo::claim(conn, import_id)? else {
+            return Ok(None);
+        };
+        let ctx = RequestContext {
+            user_id: import.requested_by,
+            workspace_id: import.workspace_id,
+            idempotency_key: None,
+            channel: AuthChannel::Session,
+        };
+
+        for row in MemberImportsRepo::list_pending_rows(conn, import.id)? {
+            let (status, message) = match conn
+                .transaction::<_, AppError, _>(|conn| Self::apply_row(conn, &ctx, &row))
+            {
+                Ok(result) => result,
+                Err(err) => (member_import_row_status::FAILED, err.to_string()),
+            };
+            MemberImportsRepo::set_row_result(conn, row.id, status, Some(message))?;
+        }
+
+        MemberImportsRepo::complete(conn, import.id)?;
+        Ok(Some(import))
+    }
+
+    /// Add an existing user (and their teams) or invite a new one. Rows that
+    /// are already satisfied are skipped, so re-running an import is safe.
+    fn apply_row(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        row: &MemberImportRow,
+    ) -> Result<(&'static str, String), AppError> {
+        let role = Self::parse_role(&row.role)
+            .ok_or_else(|| AppError::validation(format!("Invalid role '{}'", row.role)))?;
+
+        let Some(user) = AuthRepo::find_by_email(conn, &row.email)? else {
+            if InvitationsRepo::pending_exists_for_email(conn, ctx.workspace_id, &row.email)? {
+                return Ok((
+                    member_import_row_status::SKIPPED,
+                    "Already invited".to_string(),
+                ));
+            }
+            InvitationsService::create(conn, ctx, &row.email, role)?;
+            let mut message = "Invitation sent".to_string();
+            if !row.team_ids.is_empty() {
+                message.push_str("; teams are not applied until the user joins");
+            }
+            return Ok((member_import_row_status::INVITED, message));
+        };
+
+        let added = if WorkspaceMembersRepo::find(conn, ctx.workspace_id, user.id)?.is_none() {
+            let member = WorkspaceMembersRepo::insert(
+                conn,
+                &NewWorkspaceMember {
+                    user_id: user.id,
+                    workspace_id: ctx.workspace_id,
+                    role,
+                },
+            )?;
+            WorkspaceMembersService::publish_change(&member, member_change::MEMBER_ADDED);
+            true
+        } else {
+            false
+        };
+
+        let mut joined = 0;
+        for team_id in row.team_id_list() {
+            let in_team = {
+                use crate::schema::team_members::dsl as tm;
+                diesel::select(diesel::dsl::exists(
+                    tm::team_members
+                        .filter(tm::team_id.eq(team_id))
+                        .filter(tm::user_id.eq(user.id)),
+                ))
+                .get_result::<bool>(conn)?
+            };
+            if !in_team {
+                TeamMembersService::add(conn, ctx, team_id, user.id, "member")?;
+                joined += 1;
+            }
+        }
+
+        Ok(match (added, joined) {
+            (true, 0) => (member_import_row_status::ADDED, "Added".to_string()),
+            (true, n) => (
+                member_import_row_status::ADDED,
+                format!("Added to the workspace and {} team(s)", n),
+            ),
+            (false, 0) => (
+                member_import_row_status::SKIPPED,
+                "Already a member".to_string(),
+            ),
+            (false, n) => (
+                member_import_row_status::ADDED,
+                format!("Already a member; added to {} team(s)", n),
+            ),
+        })
+    }
+
+    /// Pending imports older than `age` whose queue task may have been lost
+    pub fn stale_pending(
+        conn: &mut PgConnection,
+        age: chrono::Duration,
+    ) -> Result<Vec<Uuid>, AppError> {
+        Ok(MemberImportsRepo::list_stale_pending(
+            conn,
+            clock::now() - age,
+        )?)
+    }
+}
@@ -0,0 +1,278 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::issue::Issue,
+    db::models::milestone::{
+        BurnupPoint, Milestone, MilestoneIssueProgress, MilestoneStats, NewMilestone,
+        UpdateMilestone,
+    },
+    db::models::workflow::WorkflowStateCategory,
+    db::repositories::milestones::MilestonesRepo,
+    db::repositories::projects::ProjectsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::holidays_service::HolidaysService,
+    services::realtime_service::RealtimeService,
+    utils::clock,
+    validation::milestone::validate_milestone_name,
+    websocket::{EntityAction, EntityKind},
+};
+
+pub struct MilestonesService;
+
+impl MilestonesService {
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+    ) -> Result<Vec<Milestone>, AppError> {
+        Self::ensure_project(conn, ctx, project_id)?;
+        Ok(MilestonesRepo::list_by_project(conn, project_id)?)
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        req: &crate::routes::milestones::CreateMilestoneRequest,
+    ) -> Result<Milestone, AppError> {
+        Self::ensure_project(conn, ctx, project_id)?;
+        validate_milestone_name(&req.name)?;
+        let new_milestone = NewMilestone {
+            project_id,
+            name: req.name.trim().to_string(),
+            description: req.description.clone(),
+            target_date: req.target_date,
+            created_by: Some(ctx.user_id),
+        };
+        let created = MilestonesRepo::insert(conn, &new_milestone)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Milestone,
+            EntityAction::Created,
+            created.id,
+            &created,
+        );
+        Ok(created)
+    }
+
+    pub fn get_by_id(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        milestone_id: Uuid,
+    ) -> Result<Milestone, AppError> {
+        Self::ensure_project(conn, ctx, project_id)?;
+        MilestonesRepo::find_in_project(conn, project_id, milestone_id)?
+            .ok_or_else(|| AppError::not_found("milestone"))
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        milestone_id: Uuid,
+        req: &crate::routes::milestones::UpdateMilestoneRequest,
+    ) -> Result<Milestone, AppError> {
+        Self::get_by_id(conn, ctx, project_id, milestone_id)?;
+        if let Some(name) = &req.name {
+            validate_milestone_name(name)?;
+        }
+        let changes = UpdateMilestone {
+            name: req.name.as_deref().map(|name| name.trim().to_string()),
+            description: req.description.clone().map(Some),
+            target_date: req.target_date.map(Some),
+            updated_at: Some(clock::now()),
+        };
+        let updated = MilestonesRepo::update(conn, milestone_id, &changes)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Milestone,
+            EntityAction::Updated,
+            updated.id,
+            &updated,
+        );
+        Ok(updated)
+    }
+
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        milestone_id: Uuid,
+    ) -> Result<(), AppError> {
+        Self::get_by_id(conn, ctx, project_id, milestone_id)?;
+        MilestonesRepo::delete_by_id(conn, milestone_id)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Milestone,
+            EntityAction::Deleted,
+            milestone_id,
+            &(),
+        );
+        Ok(())
+    }
+
+    pub fn get_issues(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        milestone_id: Uuid,
+    ) -> Result<Vec<Issue>, AppError> {
+        Self::get_by_id(conn, ctx, project_id, milestone_id)?;
+        Ok(MilestonesRepo::list_issues(conn, milestone_id)?)
+    }
+
+    /// Attach issues of the project to the milestone. An issue can only be
+    /// in one milestone, so attaching moves it out of its previous one.
+    pub fn assign_issues(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        milestone_id: Uuid,
+        issue_ids: &[Uuid],
+    ) -> Result<(), AppError> {
+        Self::get_by_id(conn, ctx, project_id, milestone_id)?;
+        if issue_ids.is_empty() {
+            return Err(AppError::validation("issue_ids must not be empty"));
+        }
+        let mut unique = issue_ids.to_vec();
+        unique.sort();
+        unique.dedup();
+        if MilestonesRepo::count_project_issues(conn, project_id, &unique)? != unique.len() as i64 {
+            return Err(AppError::validation(
+                "All issues must belong to the milestone's project",
+            ));
+        }
+        MilestonesRepo::attach(conn, milestone_id, &unique)?;
+        Ok(())
+    }
+
+    pub fn remove_issues(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        milestone_id: Uuid,
+        issue_ids: &[Uuid],
+    ) -> Result<(), AppError> {
+        Self::get_by_id(conn, ctx, project_id, milestone_id)?;
+        MilestonesRepo::detach(conn, milestone_id, issue_ids)?;
+        Ok(())
+    }
+
+    pub fn get_stats(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        milestone_id: Uuid,
+    ) -> Result<MilestoneStats, AppError> {
+        let milestone = Self::get_by_id(conn, ctx, project_id, milestone_id)?;
+        let issues = MilestonesRepo::issue_progress(conn, milestone_id)?;
+        let today = clock::now().date_naive();
+        let mut stats = Self::summarize(&milestone, today, &issues);
+        if let Some(target_date) = milestone.target_date {
+            stats.working_days_remaining = Some(if today > target_date {
+                0
+            } else {
+                HolidaysService::working_days_between(conn, ctx.workspace_id, today, target_date)?
+            });
+        }
+        Ok(stats)
+    }
+
+    /// Progress counts and burnup series as of `today`; working days are
+    /// left for the caller to fill in
+    pub fn summarize(
+        milestone: &Milestone,
+        today: NaiveDate,
+        issues: &[MilestoneIssueProgress],
+    ) -> MilestoneStats {
+        let mut completed_issues = 0;
+        let mut in_progress_issues = 0;
+        let mut todo_issues = 0;
+        let mut canceled_issues = 0;
+        // (added on, completed on) of every issue still in scope
+        let mut scope: Vec<(NaiveDate, Option<NaiveDate>)> = Vec::new();
+        for issue in issues {
+            let added_on = issue.added_at.date_naive();
+            let completed_on = match issue.category {
+                Some(WorkflowStateCategory::Canceled) => {
+                    canceled_issues += 1;
+                    continue;
+                }
+                Some(WorkflowStateCategory::Completed) => {
+                    completed_issues += 1;
+                    Some(issue.updated_at.date_naive().max(added_on))
+                }
+                Some(WorkflowStateCategory::Started) => {
+                    in_progress_issues += 1;
+                    None
+                }
+                _ => {
+                    todo_issues += 1;
+                    None
+                }
+            };
+            scope.push((added_on, completed_on));
+        }
+        let total_issues = scope.len() as i64;
+
+        let from = scope
+            .iter()
+            .map(|(added_on, _)| *added_on)
+            .fold(milestone.created_at.date_naive(), NaiveDate::min);
+        let to = milestone
+            .target_date
+            .map_or(today, |target| target.min(today))
+            .max(from);
+        let burnup = from
+            .iter_days()
+            .take_while(|day| *day <= to)
+            .map(|day| BurnupPoint {
+                day,
+                scope: scope.iter().filter(|(added, _)| *added <= day).count() as i64,
+                completed: scope
+                    .iter()
+                    .filter(|(_, completed)| completed.is_some_and(|c| c <= day))
+                    .count() as i64,
+            })
+            .collect();
+
+        MilestoneStats {
+            id: milestone.id,
+            project_id: milestone.project_id,
+            name: milestone.name.clone(),
+            target_date: milestone.target_date,
+            total_issues,
+            completed_issues,
+            in_progress_issues,
+            todo_issues,
+            canceled_issues,
+            completion_rate: if total_issues > 0 {
+                (completed_issues as f64) / (total_issues as f64)
+            } else {
+                0.0
+            },
+            days_remaining: milestone
+                .target_date
+                .map(|target| (target - today).num_days().max(0) as i32),
+            working_days_remaining: None,
+            is_overdue: milestone
+                .target_date
+                .is_some_and(|target| today > target && completed_issues < total_issues),
+            burnup,
+        }
+    }
+
+    fn ensure_project(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+    ) -> Result<(), AppError> {
+        ProjectsRepo::find_by_id_in_workspace(conn, ctx.workspace_id, project_id)?
+            .ok_or_else(|| AppError::not_found("project"))?;
+        Ok(())
+    }
+}
@@ -1,16 +1,74 @@
+pub mod api_keys_service;
+pub mod api_tokens_service;
+pub mod app_installations_service;
+pub mod attachments_service;
 pub mod auth_service;
+pub mod auto_close_service;
+pub mod automations_service;
+pub mod board_service;
+pub mod channel_permissions_service;
+pub mod comment_moderation_service;
 pub mod comments_service;
+pub mod content_reports_service;
 pub mod context;
+pub mod cross_workspace_relations_service;
+pub mod custom_emojis_service;
 pub mod cycles_service;
+pub mod dashboard_service;
+pub mod dead_letter_service;
+pub mod delete_confirmations_service;
+pub mod email_service;
+pub mod error_tracking_service;
+pub mod external_references_service;
+pub mod field_selection;
+pub mod github_integration_service;
+pub mod holidays_service;
+pub mod import_service;
+pub mod inbound_email_service;
+pub mod integrity_service;
 pub mod invitations_service;
+pub mod issue_archive_service;
+pub mod issue_enrichment;
+pub mod issue_forms_service;
+pub mod issue_label_rules_service;
+pub mod issue_moves_service;
+pub mod issue_patch_service;
+pub mod issue_relations_service;
+pub mod issue_split_service;
 pub mod issues_service;
 pub mod labels_service;
+pub mod markdown_service;
+pub mod member_imports_service;
+pub mod milestones_service;
+pub mod notifications_service;
+pub mod oauth_login_service;
+pub mod oauth_service;
+pub mod organizations_service;
+pub mod permission_service;
+pub mod project_costs_service;
 pub mod project_statuses_service;
 pub mod projects_service;
+pub mod push_service;
+pub mod realtime_service;
+pub mod redaction_service;
+pub mod sandbox_service;
+pub mod search_service;
+pub mod service_accounts_service;
+pub mod session_analytics_service;
+pub mod settings_history_service;
+pub mod sync_service;
+pub mod team_hierarchy_service;
 pub mod team_members_service;
 pub mod teams_service;
+pub mod telemetry_service;
+pub mod upload_sessions_service;
+pub mod user_preferences_service;
+pub mod webhook_service;
+pub mod webhooks_service;
 pub mod workflows_service;
+pub mod workspace_email_domains_service;
 pub mod workspace_members_service;
+pub mod workspace_settings_service;
 pub mod workspaces_service;
 
 pub use auth_service::AuthService;
@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use diesel::prelude::*;
+
+use crate::{
+    cache::CacheStore,
+    db::models::notification::{
+        DigestEmail, NewNotification, Notification, NotificationDigest, NotificationList,
+        NotificationListQuery, notification_events,
+    },
+    db::repositories::notifications::NotificationsRepo,
+    db::{after_commit, with_txn},
+    error::AppError,
+    services::email_service::escape_html,
+    services::markdown_service::MarkdownService,
+    services::push_service::PushService,
+    services::user_preferences_service::UserPreferencesService,
+    utils::clock,
+    utils::email_reply,
+    websocket::{MessageType, WebSocketManager, WebSocketMessage},
+};
+
+/// Default and maximum page size for `GET /notifications`
+const DEFAULT_LIST_LIMIT: i64 = 50;
+const MAX_LIST_LIMIT: i64 = 100;
+
+/// Settings for per-issue reply addresses in notification emails
+#[derive(Debug, Clone)]
+pub struct EmailReplySettings {
+    pub domain: String,
+    pub secret: String,
+}
+
+impl EmailReplySettings {
+    /// Enabled when `EMAIL_REPLY_DOMAIN` is set; tokens are signed with the JWT secret
+    pub fn from_config(config: &crate::config::Config) -> Option<Self> {
+        let domain = config.email_reply_domain.as_ref()?.trim();
+        if domain.is_empty() {
+            return None;
+        }
+        Some(Self {
+            domain: domain.to_string(),
+            secret: config.jwt_secret.clone(),
+        })
+    }
+}
+
+/// How long repeated events are coalesced, per event type and channel.
+///
+/// In-app: an event joins the recipient's unread notification for the same
+/// entity if that notification was touched within the window.
+/// Email: a pending notification is only emailed once it has been quiet for
+/// the window, so bursts of updates go out as a single digest entry.
+#[derive(Debug, Clone)]
+pub struct NotificationBatchPolicy {
+    pub in_app_default: Duration,
+    pub email_default: Duration,
+    pub in_app: HashMap<String, Duration>,
+    pub email: HashMap<String, Duration>,
+}
+
+impl Default for NotificationBatchPolicy {
+    fn default() -> Self {
+        Self {
+            in_app_default: Duration::from_secs(300),
+            email_default: Duration::from_secs(900),
+            in_app: HashMap::new(),
+            email: HashMap::new(),
+        }
+    }
+}
+
+static BATCH_POLICY: OnceLock<NotificationBatchPolicy> = OnceLock::new();
+
+impl NotificationBatchPolicy {
+    /// Build the policy from `NOTIFICATION_BATCH_WINDOWS` / `NOTIFICATION_EMAIL_WINDOWS`
+    pub fn from_config(config: &crate::config::Config) -> Result<Self, String> {
+        Ok(Self {
+            in_app: Self::parse_windows(&config.notification_batch_windows)?,
+            email: Self::parse_windows(&config.notification_email_windows)?,
+            ..Self::default()
+        })
+    }
+
+    /// Parse `event_type=seconds` entries
+    pub fn parse_windows(entries: &[String]) -> Result<HashMap<String, Duration>, String> {
+        let mut windows = HashMap::new();
+        for entry in entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let (event_type, secs) = entry.split_once('=').ok_or_else(|| {
+                format!("Invalid batch window '{}', expected type=seconds", entry)
+            })?;
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid batch window seconds in '{}'", entry))?;
+            windows.insert(event_type.trim().to_string(), Duration::from_secs(secs));
+        }
+        Ok(windows)
+    }
+
+    pub fn in_app_window(&self, event_type: &str) -> Duration {
+        self.in_app
+            .get(event_type)
+            .copied()
+            .unwrap_or(self.in_app_default)
+    }
+
+    pub fn email_window(&self, event_type: &str) -> Duration {
+        self.email
+            .get(event_type)
+            .copied()
+            .unwrap_or(self.email_default)
+    }
+
+    /// Install the process-wide policy; later calls are ignored
+    pub fn install(policy: NotificationBatchPolicy) {
+        let _ = BATCH_POLICY.set(policy);
+    }
+
+    pub fn current() -> &'static NotificationBatchPolicy {
+        BATCH_POLICY.get_or_init(NotificationBatchPolicy::default)
+    }
+}
+
+static PUSH: OnceLock<WebSocketManager> = OnceLock::new();
+
+pub struct NotificationsService;
+
+impl NotificationsService {
+    /// Push new and coalesced notifications to the recipient's open
+    /// WebSocket connections once they are committed; later calls are ignored
+    pub fn install_push(ws_manager: WebSocketManager) {
+        let _ = PUSH.set(ws_manager);
+    }
+
+    fn push(notification: &Notification) {
+        let Some(ws_manager) = PUSH.get() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let data = match serde_json::to_value(notification) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::warn!("Failed to serialize notification for push: {}", e);
+                return;
+            }
+        };
+        let message = WebSocketMessage {
+            id: Some(clock::new_id().to_string()),
+            message_type: MessageType::Notification,
+            data,
+            timestamp: Some(clock::now()),
+        };
+        let ws_manager = ws_manager.clone();
+        let recipient_id = notification.recipient_id;
+        after_commit(move || {
+            runtime.spawn(async move {
+                ws_manager.send_to_user(recipient_id, message).await;
+            });
+        });
+    }
+
+    /// Record an event for a recipient, coalescing it into a recent unread
+    /// notification for the same entity when within the in-app window. The
+    /// recipient's channel preferences decide whether it shows in their
+    /// inbox, is emailed and is pushed over WebSocket.
+    pub fn notify(conn: &mut PgConnection, new: NewNotification) -> Result<Notification, AppError> {
+        if new.actor_id == Some(new.recipient_id) {
+            return Err(AppError::validation(
+                "Cannot notify the actor of their own event",
+            ));
+        }
+        let channels =
+            UserPreferencesService::get(conn, new.recipient_id)?.channels(&new.event_type);
+
+        let policy = NotificationBatchPolicy::current();
+        let window = chrono::Duration::from_std(policy.in_app_window(&new.event_type))
+            .map_err(|e| AppError::internal(e.to_string()))?;
+        let since = clock::now() - window;
+
+        // Only new notifications reach devices; coalesced events would
+        // otherwise buzz the recipient once per update
+        let notification = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            match NotificationsRepo::find_coalescable(conn, &new, since)? {
+                Some(existing) => NotificationsRepo::bump(conn, existing.id, &new, channels.email),
+                None => {
+                    let notification =
+                        NotificationsRepo::insert(conn, &new, channels.in_app, channels.email)?;
+                    PushService::enqueue(conn, &notification)?;
+                    Ok(notification)
+                }
+            }
+        })?;
+        if channels.websocket {
+            Self::push(&notification);
+        }
+        Ok(notification)
+    }
+
+    /// Same as [`notify`](Self::notify) but never fails the caller; used from
+    /// mutation paths where the notification is a side effect.
+    pub fn notify_quietly(conn: &mut PgConnection, new: NewNotification) {
+        if new.actor_id == Some(new.recipient_id) {
+            return;
+        }
+        // A savepoint keeps a failure here from aborting the caller's transaction
+        if let Err(e) = with_txn(conn, |conn| Self::notify(conn, new)) {
+            tracing::warn!("Failed to record notification: {}", e);
+        }
+    }
+
+    /// The user's notifications, newest first, with their unread total
+    pub fn list(
+        conn: &mut PgConnection,
+        user_id: uuid::Uuid,
+        query: &NotificationListQuery,
+    ) -> Result<NotificationList, AppError> {
+        let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+        if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+            return Err(AppError::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_LIST_LIMIT
+            )));
+        }
+
+        let notifications = NotificationsRepo::list_for_recipient(
+            conn,
+            user_id,
+            query.unread_only,
+            query.before,
+            limit,
+        )?;
+        let unread_count = NotificationsRepo::count_unread(conn, user_id)?;
+        Ok(NotificationList {
+            notifications,
+            unread_count,
+        })
+    }
+
+    /// Mark one of the user's notifications as read; already-read
+    /// notifications keep their original `read_at`
+    pub fn mark_read(
+        conn: &mut PgConnection,
+        user_id: uuid::Uuid,
+        notification_id: uuid::Uuid,
+    ) -> Result<Notification, AppError> {
+        let notification = NotificationsRepo::find_for_recipient(conn, user_id, notification_id)?
+            .ok_or_else(|| AppError::not_found("notification"))?;
+        if notification.read_at.is_some() {
+            return Ok(notification);
+        }
+        Ok(NotificationsRepo::mark_read(
+            conn,
+            notification.id,
+            clock::now(),
+        )?)
+    }
+
+    /// Mark all of the user's notifications as read; returns how many changed
+    pub fn mark_all_read(conn: &mut PgConnection, user_id: uuid::Uuid) -> Result<usize, AppError> {
+        Ok(NotificationsRepo::mark_all_read(
+            conn,
+            user_id,
+            clock::now(),
+        )?)
+    }
+
+    /// Collect notifications whose email window has elapsed, grouped into one
+    /// digest per recipient, and mark them as emailed.
+    pub fn take_email_digests(
+        conn: &mut PgConnection,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<NotificationDigest>, AppError> {
+        let policy = NotificationBatchPolicy::current();
+        let shortest = policy
+            .email
+            .values()
+            .copied()
+            .chain(std::iter::once(policy.email_default))
+            .min()
+            .unwrap_or_default();
+        let shortest =
+            chrono::Duration::from_std(shortest).map_err(|e| AppError::internal(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let candidates = NotificationsRepo::list_email_pending_before(conn, now - shortest)?;
+            let due: Vec<Notification> = candidates
+                .into_iter()
+                .filter(|n| {
+                    chrono::Duration::from_std(policy.email_window(&n.event_type))
+                        .map(|w| n.updated_at <= now - w)
+                        .unwrap_or(true)
+                })
+                .collect();
+
+            // Recipients may have turned email off since the notification
+            // was recorded
+            let mut recipients: Vec<uuid::Uuid> = due.iter().map(|n| n.recipient_id).collect();
+            recipients.dedup();
+            let preferences = UserPreferencesService::get_many(conn, &recipients)?;
+            let (due, muted): (Vec<Notification>, Vec<Notification>) =
+                due.into_iter().partition(|n| {
+                    preferences
+                        .get(&n.recipient_id)
+                        .is_none_or(|p| p.channels(&n.event_type).email)
+                });
+
+            let ids: Vec<uuid::Uuid> = due.iter().map(|n| n.id).collect();
+            if !ids.is_empty() {
+                NotificationsRepo::mark_emailed(conn, &ids, now)?;
+            }
+            let muted: Vec<uuid::Uuid> = muted.iter().map(|n| n.id).collect();
+            if !muted.is_empty() {
+                NotificationsRepo::cancel_email(conn, &muted)?;
+            }
+            Ok(Self::group_digests(due))
+        })
+    }
+
+    /// Group notifications by recipient, preserving order of first appearance
+    pub fn group_digests(notifications: Vec<Notification>) -> Vec<NotificationDigest> {
+        let mut digests: Vec<NotificationDigest> = Vec::new();
+        for notification in notifications {
+            let total = notification.event_count as i64;
+            match digests
+                .iter_mut()
+                .find(|d| d.recipient_id == notification.recipient_id)
+            {
+                Some(digest) => {
+                    digest.total_events += total;
+                    digest.notifications.push(notification);
+                }
+                None => digests.push(NotificationDigest {
+                    recipient_id: notification.recipient_id,
+                    total_events: total,
+                    notifications: vec![notification],
+                }),
+            }
+        }
+        digests
+    }
+
+    /// Render a digest as a plain-text email. Comment notifications carry a
+    /// reply address that turns the reply into a comment on the issue.
+    pub fn render_digest_email(
+        digest: &NotificationDigest,
+        to: &str,
+        reply: Option<&EmailReplySettings>,
+    ) -> DigestEmail {
+        let subject = match digest.notifications.as_slice() {
+            [single] => single.title.clone(),
+            _ => format!("{} new notifications", digest.total_events),
+        };
+
+        let mut reply_addresses: Vec<String> = Vec::new();
+        let mut text = String::new();
+        for notification in &digest.notifications {
+            text.push_str(&Self::digest_heading(notification));
+            text.push('\n');
+            if let Some(body) = &notification.body {
+                text.push_str(body);
+                text.push('\n');
+            }
+
+            if let Some(address) = Self::digest_reply_address(digest, notification, reply) {
+                text.push_str(&format!("Reply by email: {}\n", address));
+                if !reply_addresses.contains(&address) {
+                    reply_addresses.push(address);
+                }
+            }
+            text.push('\n');
+        }
+
+        DigestEmail {
+            to: to.to_string(),
+            subject,
+            text,
+            reply_to: match reply_addresses.as_slice() {
+                [only] => Some(only.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// HTML version of [`Self::render_digest_email`], with notification
+    /// bodies rendered from markdown through `cache`
+    pub async fn render_digest_html(
+        digest: &NotificationDigest,
+        reply: Option<&EmailReplySettings>,
+        cache: &dyn CacheStore,
+    ) -> String {
+        let mut html = String::new();
+        for notification in &digest.notifications {
+            html.push_str(&format!(
+                "<p><strong>{}</strong></p>\n",
+                escape_html(&Self::digest_heading(notification))
+            ));
+            if let Some(body) = &notification.body {
+                html.push_str(&MarkdownService::render_cached(cache, body).await.html);
+                html.push('\n');
+            }
+            if let Some(address) = Self::digest_reply_address(digest, notification, reply) {
+                let address = escape_html(&address);
+                html.push_str(&format!(
+                    "<p>Reply by email: <a href=\"mailto:{}\">{}</a></p>\n",
+                    address, address
+                ));
+            }
+        }
+        html
+    }
+
+    /// What happened and to what, e.g. `Updated "Fix login" (3 events)`
+    fn digest_heading(notification: &Notification) -> String {
+        let heading = match notification.event_type.as_str() {
+            notification_events::COMMENT_CREATED => "New comment on",
+            notification_events::ISSUE_UPDATED => "Updated",
+            notification_events::ISSUE_ASSIGNED => "Assigned to you:",
+            notification_events::MENTIONED => "You were mentioned on",
+            notification_events::INVITATION_RECEIVED => "Invitation:",
+            notification_events::AUTO_CLOSE_WARNING => "Closing soon for inactivity:",
+            notification_events::BUDGET_ALERT => "Budget alert:",
+            notification_events::REPORT_RESOLVED => "Your report was reviewed:",
+            _ => "Activity on",
+        };
+        let mut line = format!("{} \"{}\"", heading, notification.title);
+        if notification.event_count > 1 {
+            line.push_str(&format!(" ({} events)", notification.event_count));
+        }
+        line
+    }
+
+    /// Address whose replies become comments on the issue, for comment
+    /// notifications when reply-by-email is configured
+    fn digest_reply_address(
+        digest: &NotificationDigest,
+        notification: &Notification,
+        reply: Option<&EmailReplySettings>,
+    ) -> Option<String> {
+        let reply = reply?;
+        if notification.event_type != notification_events::COMMENT_CREATED
+            || notification.entity_type != "issue"
+        {
+            return None;
+        }
+        let token =
+            email_reply::reply_token(notification.entity_id, digest.recipient_id, &reply.secret);
+        Some(email_reply::reply_address(&token, &reply.domain))
+    }
+}
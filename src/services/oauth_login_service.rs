@@ -0,0 +1,330 @@
+use diesel::prelude::*;
+use serde_json::Value;
+
+use crate::{
+    config::{Config, LoginProviderConfig},
+    db::models::auth::{LoginResponse, NewUser, User},
+    db::models::user_identity::{ExternalProfile, LoginProvider, NewUserIdentity},
+    db::repositories::auth::AuthRepo,
+    db::repositories::user_identities::UserIdentitiesRepo,
+    error::AppError,
+    services::auth_service::AuthService,
+    services::workspace_members_service::WorkspaceMembersService,
+    utils::AssetUrlHelper,
+    utils::clock,
+};
+
+/// How long an authorize request may take before its state expires
+pub const OAUTH_STATE_TTL_SECS: u64 = 600;
+const OAUTH_STATE_PREFIX: &str = "auth:oauth_state:";
+const USER_AGENT: &str = "momentum-backend";
+const MAX_USERNAME_LENGTH: usize = 30;
+
+const GOOGLE_AUTHORIZE_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const GOOGLE_USERINFO_URL: &str = "https://openidconnect.googleapis.com/v1/userinfo";
+const GITHUB_AUTHORIZE_URL: &str = "https://github.com/login/oauth/authorize";
+const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const GITHUB_USER_URL: &str = "https://api.github.com/user";
+const GITHUB_EMAILS_URL: &str = "https://api.github.com/user/emails";
+
+/// Sign-in with Google (OIDC) and GitHub (OAuth2). External identities are
+/// linked to local users, who then get the same tokens as password login.
+pub struct OAuthLoginService;
+
+impl OAuthLoginService {
+    /// Provider named in the URL and its client settings
+    pub fn provider(
+        config: &Config,
+        name: &str,
+    ) -> Result<(LoginProvider, LoginProviderConfig), AppError> {
+        let provider = LoginProvider::parse(name)
+            .ok_or_else(|| AppError::validation(format!("Unsupported login provider: {}", name)))?;
+        let settings = config.login_provider(provider).ok_or_else(|| {
+            AppError::validation(format!("Login with {} is not configured", name))
+        })?;
+        Ok((provider, settings))
+    }
+
+    /// Provider consent page the user is sent to
+    pub fn authorize_url(
+        provider: LoginProvider,
+        settings: &LoginProviderConfig,
+        state: &str,
+    ) -> String {
+        let (base, scope) = match provider {
+            LoginProvider::Google => (GOOGLE_AUTHORIZE_URL, "openid email profile"),
+            LoginProvider::Github => (GITHUB_AUTHORIZE_URL, "read:user user:email"),
+        };
+        let mut url = url::Url::parse(base).expect("provider URLs are valid");
+        url.query_pairs_mut()
+            .append_pair("client_id", &settings.client_id)
+            .append_pair("redirect_uri", &settings.redirect_uri)
+            .append_pair("response_type", "code")
+            .append_pair("scope", scope)
+            .append_pair("state", state);
+        url.into()
+    }
+
+    async fn redis_connection(
+        redis: &redis::Client,
+    ) -> Result<redis::aio::MultiplexedConnection, AppError> {
+        redis
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to get Redis connection: {}", e)))
+    }
+
+    /// Create a one-time state value that ties the callback to this request
+    pub async fn begin(redis: &redis::Client, provider: LoginProvider) -> Result<String, AppError> {
+        let state = format!("{}{}", clock::new_id().simple(), clock::new_id().simple());
+        let mut conn = Self::redis_connection(redis).await?;
+        let _: () = redis::cmd("SET")
+            .arg(format!("{}{}", OAUTH_STATE_PREFIX, state))
+            .arg(provider.as_str())
+            .arg("EX")
+            .arg(OAUTH_STATE_TTL_SECS)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to store OAuth state: {}", e)))?;
+        Ok(state)
+    }
+
+    /// Consume the state from the callback; it must have been issued for the same provider
+    pub async fn take_state(
+        redis: &redis::Client,
+        provider: LoginProvider,
+        state: &str,
+    ) -> Result<(), AppError> {
+        let mut conn = Self::redis_connection(redis).await?;
+        let stored: Option<String> = redis::cmd("GETDEL")
+            .arg(format!("{}{}", OAUTH_STATE_PREFIX, state))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to read OAuth state: {}", e)))?;
+        if stored.as_deref() != Some(provider.as_str()) {
+            return Err(AppError::auth("Invalid or expired OAuth state"));
+        }
+        Ok(())
+    }
+
+    /// Exchange the authorization code and load the user's profile
+    pub async fn fetch_profile(
+        client: &reqwest::Client,
+        provider: LoginProvider,
+        settings: &LoginProviderConfig,
+        code: &str,
+    ) -> Result<ExternalProfile, AppError> {
+        let token_url = match provider {
+            LoginProvider::Google => GOOGLE_TOKEN_URL,
+            LoginProvider::Github => GITHUB_TOKEN_URL,
+        };
+        let token: Value = client
+            .post(token_url)
+            .header("Accept", "application/json")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("client_id", settings.client_id.as_str()),
+                ("client_secret", settings.client_secret.as_str()),
+                ("redirect_uri", settings.redirect_uri.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("Token exchange failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::internal(format!("Invalid token response: {}", e)))?;
+        let access_token = token["access_token"]
+            .as_str()
+            .ok_or_else(|| AppError::auth("The authorization code was rejected by the provider"))?;
+
+        let profile = match provider {
+            LoginProvider::Google => {
+                let info = Self::get_json(client, GOOGLE_USERINFO_URL, access_token).await?;
+                Self::google_profile(&info)
+            }
+            LoginProvider::Github => {
+                let user = Self::get_json(client, GITHUB_USER_URL, access_token).await?;
+                let emails = Self::get_json(client, GITHUB_EMAILS_URL, access_token)
+                    .await
+                    .unwrap_or(Value::Null);
+                Self::github_profile(&user, &emails)
+            }
+        };
+        profile.ok_or_else(|| AppError::internal("Unexpected profile response from provider"))
+    }
+
+    async fn get_json(
+        client: &reqwest::Client,
+        url: &str,
+        access_token: &str,
+    ) -> Result<Value, AppError> {
+        client
+            .get(url)
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .header("User-Agent", USER_AGENT)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::internal(format!("Profile request failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::internal(format!("Invalid profile response: {}", e)))
+    }
+
+    /// Profile from Google's OIDC userinfo response
+    pub fn google_profile(info: &Value) -> Option<ExternalProfile> {
+        Some(ExternalProfile {
+            provider_user_id: info["sub"].as_str()?.to_string(),
+            email: info["email"].as_str().map(str::to_string),
+            email_verified: info["email_verified"].as_bool().unwrap_or(false),
+            name: info["name"].as_str().map(str::to_string),
+            username_hint: None,
+            avatar_url: info["picture"].as_str().map(str::to_string),
+        })
+    }
+
+    /// Profile from GitHub's `/user` and `/user/emails`; prefers the primary
+    /// verified address. The public profile email is not treated as verified.
+    pub fn github_profile(user: &Value, emails: &Value) -> Option<ExternalProfile> {
+        let verified: Vec<&Value> = emails
+            .as_array()
+            .map(|emails| {
+                emails
+                    .iter()
+                    .filter(|e| e["verified"].as_bool() == Some(true))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let verified_email = verified
+            .iter()
+            .find(|e| e["primary"].as_bool() == Some(true))
+            .or_else(|| verified.first())
+            .and_then(|e| e["email"].as_str());
+        Some(ExternalProfile {
+            provider_user_id: user["id"].as_i64()?.to_string(),
+            email: verified_email
+                .or_else(|| user["email"].as_str())
+                .map(str::to_string),
+            email_verified: verified_email.is_some(),
+            name: user["name"].as_str().map(str::to_string),
+            username_hint: user["login"].as_str().map(str::to_string),
+            avatar_url: user["avatar_url"].as_str().map(str::to_string),
+        })
+    }
+
+    /// Username derived from the provider login or the email's local part,
+    /// reduced to letters, digits and underscores
+    pub fn base_username(profile: &ExternalProfile) -> String {
+        let source = profile
+            .username_hint
+            .as_deref()
+            .or_else(|| profile.email.as_deref().and_then(|e| e.split('@').next()))
+            .unwrap_or("");
+        let mut username: String = source
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(MAX_USERNAME_LENGTH)
+            .collect::<String>()
+            .to_lowercase();
+        if username.trim_matches('_').len() < 3 {
+            username = format!("{}user", username);
+        }
+        username
+    }
+
+    pub(crate) fn unique_username(conn: &mut PgConnection, base: &str) -> Result<String, AppError> {
+        if !AuthRepo::exists_by_username(conn, base)? {
+            return Ok(base.to_string());
+        }
+        for suffix in 2..100 {
+            let candidate = format!("{}{}", base, suffix);
+            if !AuthRepo::exists_by_username(conn, &candidate)? {
+                return Ok(candidate);
+            }
+        }
+        Ok(format!(
+            "{}_{}",
+            base,
+            &clock::new_id().simple().to_string()[..8]
+        ))
+    }
+
+    /// Sign in with an external profile: use the linked user, link a user
+    /// with the same verified email, or create a new user
+    pub fn login(
+        conn: &mut PgConnection,
+        provider: LoginProvider,
+        profile: &ExternalProfile,
+        asset_helper: &AssetUrlHelper,
+    ) -> Result<LoginResponse, AppError> {
+        let user = conn.transaction::<User, AppError, _>(|conn| {
+            if let Some(identity) =
+                UserIdentitiesRepo::find(conn, provider.as_str(), &profile.provider_user_id)?
+            {
+                UserIdentitiesRepo::touch_login(conn, identity.id, profile.email.as_deref())?;
+                return AuthRepo::find_by_id(conn, identity.user_id)?
+                    .ok_or_else(|| AppError::not_found("user"));
+            }
+
+            let email = profile
+                .email
+                .as_deref()
+                .filter(|_| profile.email_verified)
+                .ok_or_else(|| {
+                    AppError::validation(format!(
+                        "Your {} account has no verified email address",
+                        provider.as_str()
+                    ))
+                })?;
+            let user = match AuthRepo::find_by_email(conn, email)? {
+                Some(user) => {
+                    if UserIdentitiesRepo::find_for_user(conn, user.id, provider.as_str())?
+                        .is_some()
+                    {
+                        return Err(AppError::conflict_with_code(
+                            format!(
+                                "This account is already linked to another {} account",
+                                provider.as_str()
+                            ),
+                            None,
+                            "IDENTITY_CONFLICT",
+                        ));
+                    }
+                    // The provider has now verified the email of an existing user
+                    WorkspaceMembersService::auto_join(conn, user)?
+                }
+                None => {
+                    let username = Self::unique_username(conn, &Self::base_username(profile))?;
+                    let user = AuthRepo::insert_user(
+                        conn,
+                        &NewUser {
+                            email: email.to_string(),
+                            name: profile.name.clone().unwrap_or_else(|| username.clone()),
+                            username,
+                            avatar_url: profile.avatar_url.clone(),
+                        },
+                    )?;
+                    WorkspaceMembersService::auto_join(conn, user)?
+                }
+            };
+            UserIdentitiesRepo::insert(
+                conn,
+                &NewUserIdentity {
+                    user_id: user.id,
+                    provider: provider.as_str().to_string(),
+                    provider_user_id: profile.provider_user_id.clone(),
+                    email: Some(email.to_string()),
+                },
+            )?;
+            Ok(user)
+        })?;
+
+        if !user.is_active {
+            return Err(AppError::auth("User account is disabled"));
+        }
+        AuthService::issue_tokens(conn, &user, asset_helper)
+    }
+}
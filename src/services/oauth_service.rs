@@ -0,0 +1,798 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Duration;
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    db::models::api_token::{ApiUsageReport, api_client_types},
+    db::models::app_installation::{
+        AppWebhookConfig, KeySignature, NewWebhookSigningKey, RotateSigningKeyRequest,
+        RotatedSigningKey, SignatureVerification, TestWebhookRequest, UpdateAppWebhookRequest,
+        VerifySignatureRequest, WebhookDelivery, WebhookEnvelope, WebhookSigningKey,
+        webhook_events,
+    },
+    db::models::oauth_app::{
+        AuthorizeDecision, AuthorizePreview, AuthorizeRedirect, AuthorizeRequest, AuthorizedApp,
+        CreateOAuthAppRequest, CreatedOAuthApp, NewOAuthAccessToken, NewOAuthApp,
+        NewOAuthAuthorizationCode, OAuthApp, OAuthGrant, TokenRequest, TokenResponse, oauth_scopes,
+    },
+    db::repositories::api_tokens::ApiUsageRepo,
+    db::repositories::app_installations::{
+        AppInstallationsRepo, WebhookDeliveriesRepo, WebhookSigningKeysRepo,
+    },
+    db::repositories::oauth_apps::OAuthAppsRepo,
+    error::AppError,
+    services::api_tokens_service::{ApiTokensService, MAX_USAGE_DAYS},
+    services::context::RequestContext,
+    services::webhook_service::WebhookService,
+    utils::clock,
+    utils::webhook_filter::WebhookFilter,
+};
+
+/// Prefix of access tokens issued to third-party apps
+pub const OAUTH_ACCESS_TOKEN_PREFIX: &str = "mto_";
+const OAUTH_REFRESH_TOKEN_PREFIX: &str = "mtr_";
+const OAUTH_CLIENT_ID_PREFIX: &str = "mtc_";
+const OAUTH_CLIENT_SECRET_PREFIX: &str = "mts_";
+pub(crate) const WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+const WEBHOOK_KEY_ID_PREFIX: &str = "whk_";
+
+/// How long rotated signing keys keep signing by default, and at most
+pub const DEFAULT_KEY_GRACE_HOURS: i64 = 24;
+pub const MAX_KEY_GRACE_HOURS: i64 = 168;
+
+const ACCESS_TOKEN_TTL_SECS: i64 = 3600;
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+const AUTHORIZATION_CODE_TTL_SECS: i64 = 600;
+
+/// Token endpoint errors, rendered in the RFC 6749 §5.2 format
+#[derive(Debug)]
+pub enum OAuthError {
+    InvalidRequest(String),
+    InvalidClient,
+    InvalidGrant(String),
+    UnsupportedGrantType,
+    Server(AppError),
+}
+
+impl OAuthError {
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            OAuthError::InvalidRequest(_) => "invalid_request",
+            OAuthError::InvalidClient => "invalid_client",
+            OAuthError::InvalidGrant(_) => "invalid_grant",
+            OAuthError::UnsupportedGrantType => "unsupported_grant_type",
+            OAuthError::Server(_) => "server_error",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            OAuthError::InvalidRequest(msg) | OAuthError::InvalidGrant(msg) => msg.clone(),
+            OAuthError::InvalidClient => "Client authentication failed".to_string(),
+            OAuthError::UnsupportedGrantType => "Unsupported grant_type".to_string(),
+            OAuthError::Server(_) => "Internal server error".to_string(),
+        }
+    }
+}
+
+impl From<diesel::result::Error> for OAuthError {
+    fn from(e: diesel::result::Error) -> Self {
+        OAuthError::Server(AppError::from(e))
+    }
+}
+
+/// Who an OAuth access token acts for
+#[derive(Debug, Clone)]
+pub struct OAuthSession {
+    pub user_id: Uuid,
+    pub app_id: Uuid,
+    pub tier: String,
+    pub scopes: Vec<String>,
+}
+
+pub struct OAuthService;
+
+impl OAuthService {
+    pub fn is_access_token(raw: &str) -> bool {
+        raw.starts_with(OAUTH_ACCESS_TOKEN_PREFIX)
+    }
+
+    pub(crate) fn random_secret(prefix: &str) -> String {
+        format!(
+            "{}{}{}",
+            prefix,
+            clock::new_id().simple(),
+            clock::new_id().simple()
+        )
+    }
+
+    /// Redirect URIs must be absolute https URLs; plain http is only allowed for loopback
+    pub fn validate_redirect_uri(uri: &str) -> Result<(), AppError> {
+        let parsed = url::Url::parse(uri)
+            .map_err(|_| AppError::validation(format!("Invalid redirect URI: {}", uri)))?;
+        let loopback = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        let secure = parsed.scheme() == "https" || (parsed.scheme() == "http" && loopback);
+        if !secure || parsed.fragment().is_some() {
+            return Err(AppError::validation(format!(
+                "Redirect URI must use https (or http on localhost) and have no fragment: {}",
+                uri
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn validate_scopes(requested: &[String], allowed: &[String]) -> Result<(), AppError> {
+        if requested.is_empty() {
+            return Err(AppError::validation("At least one scope is required"));
+        }
+        for scope in requested {
+            if !oauth_scopes::is_valid(scope) {
+                return Err(AppError::validation(format!("Unknown scope: {}", scope)));
+            }
+            if !allowed.contains(scope) {
+                return Err(AppError::validation(format!(
+                    "Scope not allowed for this app: {}",
+                    scope
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // App registration and management
+
+    pub fn create_app(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &CreateOAuthAppRequest,
+    ) -> Result<CreatedOAuthApp, AppError> {
+        let name = req.name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(AppError::validation(
+                "App name must be between 1 and 100 characters",
+            ));
+        }
+        if req.redirect_uris.is_empty() {
+            return Err(AppError::validation(
+                "At least one redirect URI is required",
+            ));
+        }
+        for uri in &req.redirect_uris {
+            Self::validate_redirect_uri(uri)?;
+        }
+        let all: Vec<String> = oauth_scopes::ALL.iter().map(|s| s.to_string()).collect();
+        Self::validate_scopes(&req.scopes, &all)?;
+
+        let client_secret = Self::random_secret(OAUTH_CLIENT_SECRET_PREFIX);
+        let app = OAuthAppsRepo::insert_app(
+            conn,
+            &NewOAuthApp {
+                owner_id: ctx.user_id,
+                name: name.to_string(),
+                description: req.description.clone(),
+                client_id: format!("{}{}", OAUTH_CLIENT_ID_PREFIX, clock::new_id().simple()),
+                client_secret_hash: ApiTokensService::hash_token(&client_secret),
+                redirect_uris: req.redirect_uris.join(" "),
+                scopes: oauth_scopes::join(&req.scopes),
+            },
+        )?;
+        Ok(CreatedOAuthApp { client_secret, app })
+    }
+
+    pub fn list_apps(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<OAuthApp>, AppError> {
+        Ok(OAuthAppsRepo::list_apps_by_owner(conn, ctx.user_id)?)
+    }
+
+    /// Disable an app owned by the current user and revoke everything issued to it
+    pub fn delete_app(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+    ) -> Result<(), AppError> {
+        let app = OAuthAppsRepo::find_app(conn, app_id)?
+            .filter(|app| app.owner_id == ctx.user_id && app.disabled_at.is_none())
+            .ok_or_else(|| AppError::not_found("oauth_app"))?;
+
+        let now = clock::now();
+        conn.transaction::<_, AppError, _>(|conn| {
+            OAuthAppsRepo::revoke_grants_for_app(conn, app.id, now)?;
+            AppInstallationsRepo::uninstall_all_for_app(conn, app.id, now)?;
+            OAuthAppsRepo::disable_app(conn, app.id, now)?;
+            Ok(())
+        })
+    }
+
+    /// API usage of an app owned by the current user over the last `days` days
+    pub fn app_usage(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+        days: i64,
+    ) -> Result<ApiUsageReport, AppError> {
+        let app = OAuthAppsRepo::find_app(conn, app_id)?
+            .filter(|app| app.owner_id == ctx.user_id)
+            .ok_or_else(|| AppError::not_found("oauth_app"))?;
+
+        let to = clock::now().date_naive();
+        let from = to - chrono::Duration::days(days.clamp(1, MAX_USAGE_DAYS) - 1);
+        let rows = ApiUsageRepo::list_since(conn, api_client_types::OAUTH_CLIENT, app.id, from)?;
+        Ok(ApiTokensService::summarize_usage(
+            api_client_types::OAUTH_CLIENT,
+            app.id,
+            &app.tier,
+            from,
+            to,
+            &rows,
+        ))
+    }
+
+    fn find_owned_app(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+    ) -> Result<OAuthApp, AppError> {
+        OAuthAppsRepo::find_app(conn, app_id)?
+            .filter(|app| app.owner_id == ctx.user_id && app.disabled_at.is_none())
+            .ok_or_else(|| AppError::not_found("oauth_app"))
+    }
+
+    /// Configure where and which events an app's webhooks are sent.
+    /// A signing key is generated on first configuration; `rotate_secret`
+    /// replaces the current keys immediately.
+    pub fn set_webhook(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+        req: &UpdateAppWebhookRequest,
+    ) -> Result<AppWebhookConfig, AppError> {
+        let app = Self::find_owned_app(conn, ctx, app_id)?;
+
+        if let Some(url) = &req.webhook_url {
+            WebhookService::validate_url(url)?;
+        }
+        let events = WebhookService::normalize_events(&req.events)?;
+
+        let filter = req
+            .filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty());
+        if let Some(filter) = filter {
+            WebhookFilter::parse(filter)
+                .map_err(|e| AppError::validation(format!("Invalid webhook filter: {}", e)))?;
+        }
+
+        let has_key = !WebhookSigningKeysRepo::list_active(conn, app.id, clock::now())?.is_empty();
+        let (updated, new_key) = conn.transaction::<_, AppError, _>(|conn| {
+            let updated = OAuthAppsRepo::update_webhook(
+                conn,
+                app.id,
+                req.webhook_url.as_deref(),
+                &events.join(" "),
+                filter,
+            )?;
+            let new_key = if req.webhook_url.is_some() && (req.rotate_secret || !has_key) {
+                Some(Self::issue_signing_key(conn, app.id, Duration::zero())?)
+            } else {
+                None
+            };
+            Ok((updated, new_key))
+        })?;
+        let (key_id, secret) = match new_key {
+            Some((key, secret)) => (Some(key.key_id), Some(secret)),
+            None => (None, None),
+        };
+        Ok(AppWebhookConfig {
+            webhook_url: updated.webhook_url.clone(),
+            events: updated.webhook_event_list(),
+            filter: updated.webhook_filter.clone(),
+            key_id,
+            secret,
+        })
+    }
+
+    /// Create a signing key and retire the app's other keys after `grace`
+    fn issue_signing_key(
+        conn: &mut PgConnection,
+        app_id: Uuid,
+        grace: Duration,
+    ) -> Result<(WebhookSigningKey, String), AppError> {
+        let secret = Self::random_secret(WEBHOOK_SECRET_PREFIX);
+        let key = WebhookSigningKeysRepo::insert(
+            conn,
+            &NewWebhookSigningKey {
+                app_id,
+                key_id: format!(
+                    "{}{}",
+                    WEBHOOK_KEY_ID_PREFIX,
+                    &clock::new_id().simple().to_string()[..16]
+                ),
+                secret: secret.clone(),
+            },
+        )?;
+        WebhookSigningKeysRepo::expire_others(conn, app_id, key.id, clock::now() + grace)?;
+        Ok((key, secret))
+    }
+
+    /// Signing keys of an app that still sign deliveries, newest first
+    pub fn list_signing_keys(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+    ) -> Result<Vec<WebhookSigningKey>, AppError> {
+        let app = Self::find_owned_app(conn, ctx, app_id)?;
+        Ok(WebhookSigningKeysRepo::list_active(
+            conn,
+            app.id,
+            clock::now(),
+        )?)
+    }
+
+    /// Issue a new signing key. Deliveries carry a signature for every active
+    /// key, so the previous keys stay valid for the grace period while
+    /// receivers switch over.
+    pub fn rotate_signing_key(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+        req: &RotateSigningKeyRequest,
+    ) -> Result<RotatedSigningKey, AppError> {
+        let app = Self::find_owned_app(conn, ctx, app_id)?;
+        let grace_hours = req.grace_period_hours.unwrap_or(DEFAULT_KEY_GRACE_HOURS);
+        if !(0..=MAX_KEY_GRACE_HOURS).contains(&grace_hours) {
+            return Err(AppError::validation(format!(
+                "grace_period_hours must be between 0 and {}",
+                MAX_KEY_GRACE_HOURS
+            )));
+        }
+
+        let (key, secret) = conn.transaction::<_, AppError, _>(|conn| {
+            Self::issue_signing_key(conn, app.id, Duration::hours(grace_hours))
+        })?;
+        let active_keys = WebhookSigningKeysRepo::list_active(conn, app.id, clock::now())?;
+        Ok(RotatedSigningKey {
+            key,
+            secret,
+            active_keys,
+        })
+    }
+
+    /// Sign a payload the way deliveries are signed and, when the caller
+    /// supplies its own signature, check it. Lets integrators test their
+    /// HMAC verification without waiting for a real event.
+    pub fn verify_signature(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+        req: &VerifySignatureRequest,
+    ) -> Result<SignatureVerification, AppError> {
+        let app = Self::find_owned_app(conn, ctx, app_id)?;
+        let payload = match &req.payload {
+            Some(payload) => payload.clone(),
+            None => serde_json::to_string(&WebhookEnvelope {
+                id: clock::new_id(),
+                event: "ping".to_string(),
+                workspace_id: Uuid::nil(),
+                installation_id: Uuid::nil(),
+                created_at: clock::now(),
+                data: serde_json::json!({ "app_id": app.id }),
+                test: true,
+            })
+            .map_err(|e| AppError::internal(e.to_string()))?,
+        };
+
+        let stored = WebhookSigningKeysRepo::list_active(conn, app.id, clock::now())?;
+        let keys: Vec<(Option<&str>, &str)> = match &req.secret {
+            Some(secret) => vec![(None, secret.as_str())],
+            None => stored
+                .iter()
+                .map(|k| (Some(k.key_id.as_str()), k.secret.as_str()))
+                .collect(),
+        };
+        if keys.is_empty() {
+            return Err(AppError::validation(
+                "The app has no signing key; configure its webhook first",
+            ));
+        }
+
+        let (valid, matched_key_id) = match &req.signature {
+            Some(signature) => match WebhookService::verify_header(&keys, &payload, signature) {
+                Some(key_id) => (Some(true), key_id.map(str::to_string)),
+                None => (Some(false), None),
+            },
+            None => (None, None),
+        };
+        Ok(SignatureVerification {
+            header: WebhookService::signature_header(&keys, &payload),
+            signatures: keys
+                .iter()
+                .map(|(key_id, secret)| KeySignature {
+                    key_id: key_id.map(str::to_string),
+                    signature: WebhookService::sign(secret, &payload),
+                })
+                .collect(),
+            valid,
+            matched_key_id,
+            payload,
+        })
+    }
+
+    /// Most recent webhook deliveries of an app owned by the current user
+    pub fn list_deliveries(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let app = Self::find_owned_app(conn, ctx, app_id)?;
+        Ok(WebhookDeliveriesRepo::list_by_app(conn, app.id, 100)?)
+    }
+
+    /// Queue a synthetic sample delivery for each requested event type
+    /// (every type by default) so integrators can exercise their endpoint
+    pub fn queue_test_deliveries(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+        req: &TestWebhookRequest,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let app = Self::find_owned_app(conn, ctx, app_id)?;
+        if app.webhook_url.is_none() {
+            return Err(AppError::validation("Configure a webhook URL first"));
+        }
+        let events: Vec<&str> = if req.events.is_empty() {
+            webhook_events::LIFECYCLE
+                .iter()
+                .chain(webhook_events::SUBSCRIBABLE)
+                .copied()
+                .collect()
+        } else {
+            req.events.iter().map(String::as_str).collect()
+        };
+        conn.transaction::<_, AppError, _>(|conn| {
+            events
+                .iter()
+                .map(|event| WebhookService::enqueue_test(conn, &app, event))
+                .collect()
+        })
+    }
+
+    /// Queue a replay of a past delivery from the app's delivery log
+    pub fn queue_redelivery(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+        delivery_id: Uuid,
+    ) -> Result<WebhookDelivery, AppError> {
+        let app = Self::find_owned_app(conn, ctx, app_id)?;
+        if app.webhook_url.is_none() {
+            return Err(AppError::validation("Configure a webhook URL first"));
+        }
+        let original = WebhookDeliveriesRepo::find_for_app(conn, app.id, delivery_id)?
+            .ok_or_else(|| AppError::not_found("webhook_delivery"))?;
+        WebhookService::enqueue_redelivery(conn, &original)
+    }
+
+    // Authorization (consent) flow
+
+    fn check_authorize_request(
+        conn: &mut PgConnection,
+        req: &AuthorizeRequest,
+    ) -> Result<(OAuthApp, Vec<String>), AppError> {
+        if req.response_type != "code" {
+            return Err(AppError::validation("Only response_type=code is supported"));
+        }
+        let app = OAuthAppsRepo::find_app_by_client_id(conn, &req.client_id)?
+            .ok_or_else(|| AppError::not_found("oauth_app"))?;
+        if !app.redirect_uri_list().contains(&req.redirect_uri.as_str()) {
+            return Err(AppError::validation(
+                "redirect_uri is not registered for this app",
+            ));
+        }
+        match (&req.code_challenge, req.code_challenge_method.as_deref()) {
+            (Some(_), None | Some("S256")) | (None, None) => {}
+            _ => {
+                return Err(AppError::validation(
+                    "Only the S256 code_challenge_method is supported",
+                ));
+            }
+        }
+
+        let requested = oauth_scopes::parse(&req.scope);
+        Self::validate_scopes(&requested, &oauth_scopes::parse(&app.scopes))?;
+        Ok((app, requested))
+    }
+
+    /// Validate an authorization request and describe it for the consent screen
+    pub fn authorize_preview(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &AuthorizeRequest,
+    ) -> Result<AuthorizePreview, AppError> {
+        let (app, scopes) = Self::check_authorize_request(conn, req)?;
+        let previously_granted = OAuthAppsRepo::find_active_grant(conn, app.id, ctx.user_id)?
+            .map(|grant| oauth_scopes::parse(&grant.scopes))
+            .unwrap_or_default();
+
+        Ok(AuthorizePreview {
+            app_id: app.id,
+            app_name: app.name,
+            app_description: app.description,
+            redirect_uri: req.redirect_uri.clone(),
+            scopes,
+            previously_granted,
+        })
+    }
+
+    /// Record the user's decision and build the redirect back to the app
+    pub fn authorize(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        decision: &AuthorizeDecision,
+    ) -> Result<AuthorizeRedirect, AppError> {
+        let req = &decision.request;
+        let (app, requested) = Self::check_authorize_request(conn, req)?;
+
+        let mut redirect = url::Url::parse(&req.redirect_uri)
+            .map_err(|_| AppError::validation("Invalid redirect URI"))?;
+        if !decision.approve {
+            redirect
+                .query_pairs_mut()
+                .append_pair("error", "access_denied");
+            if let Some(state) = &req.state {
+                redirect.query_pairs_mut().append_pair("state", state);
+            }
+            return Ok(AuthorizeRedirect {
+                redirect_to: redirect.to_string(),
+            });
+        }
+
+        let code = Self::random_secret("");
+        conn.transaction::<_, AppError, _>(|conn| {
+            // Keep scopes granted earlier so approving a narrower request does not drop them
+            let mut granted = OAuthAppsRepo::find_active_grant(conn, app.id, ctx.user_id)?
+                .map(|grant| oauth_scopes::parse(&grant.scopes))
+                .unwrap_or_default();
+            for scope in &requested {
+                if !granted.contains(scope) {
+                    granted.push(scope.clone());
+                }
+            }
+            let grant = OAuthAppsRepo::upsert_grant(
+                conn,
+                app.id,
+                ctx.user_id,
+                &oauth_scopes::join(&granted),
+            )?;
+
+            OAuthAppsRepo::insert_code(
+                conn,
+                &NewOAuthAuthorizationCode {
+                    code_hash: ApiTokensService::hash_token(&code),
+                    grant_id: grant.id,
+                    redirect_uri: req.redirect_uri.clone(),
+                    scopes: oauth_scopes::join(&requested),
+                    code_challenge: req.code_challenge.clone(),
+                    expires_at: clock::now()
+                        + chrono::Duration::seconds(AUTHORIZATION_CODE_TTL_SECS),
+                },
+            )?;
+            Ok(())
+        })?;
+
+        redirect.query_pairs_mut().append_pair("code", &code);
+        if let Some(state) = &req.state {
+            redirect.query_pairs_mut().append_pair("state", state);
+        }
+        Ok(AuthorizeRedirect {
+            redirect_to: redirect.to_string(),
+        })
+    }
+
+    // Token endpoint
+
+    fn authenticate_client(
+        conn: &mut PgConnection,
+        client_id: Option<&str>,
+        client_secret: Option<&str>,
+    ) -> Result<OAuthApp, OAuthError> {
+        let (Some(client_id), Some(client_secret)) = (client_id, client_secret) else {
+            return Err(OAuthError::InvalidClient);
+        };
+        OAuthAppsRepo::find_app_by_client_id(conn, client_id)?
+            .filter(|app| app.client_secret_hash == ApiTokensService::hash_token(client_secret))
+            .ok_or(OAuthError::InvalidClient)
+    }
+
+    /// PKCE S256: BASE64URL(SHA256(verifier)) must equal the stored challenge
+    pub fn verify_code_challenge(challenge: &str, verifier: &str) -> bool {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())) == challenge
+    }
+
+    fn active_grant(
+        conn: &mut PgConnection,
+        grant_id: Uuid,
+        app: &OAuthApp,
+    ) -> Result<OAuthGrant, OAuthError> {
+        OAuthAppsRepo::find_grant(conn, grant_id)?
+            .filter(|grant| grant.app_id == app.id && grant.revoked_at.is_none())
+            .ok_or_else(|| OAuthError::InvalidGrant("Authorization has been revoked".to_string()))
+    }
+
+    fn issue_token(
+        conn: &mut PgConnection,
+        grant: &OAuthGrant,
+        scopes: &str,
+    ) -> Result<TokenResponse, OAuthError> {
+        let now = clock::now();
+        let access_token = Self::random_secret(OAUTH_ACCESS_TOKEN_PREFIX);
+        let refresh_token = Self::random_secret(OAUTH_REFRESH_TOKEN_PREFIX);
+        OAuthAppsRepo::insert_token(
+            conn,
+            &NewOAuthAccessToken {
+                grant_id: grant.id,
+                token_hash: ApiTokensService::hash_token(&access_token),
+                refresh_token_hash: Some(ApiTokensService::hash_token(&refresh_token)),
+                scopes: scopes.to_string(),
+                expires_at: now + chrono::Duration::seconds(ACCESS_TOKEN_TTL_SECS),
+                refresh_expires_at: Some(now + chrono::Duration::days(REFRESH_TOKEN_TTL_DAYS)),
+            },
+        )?;
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer",
+            expires_in: ACCESS_TOKEN_TTL_SECS,
+            refresh_token,
+            scope: scopes.to_string(),
+        })
+    }
+
+    /// Handle `POST /oauth/token` for the authorization_code and refresh_token grants.
+    /// `basic_auth` carries client credentials sent via HTTP Basic, if any.
+    pub fn exchange_token(
+        conn: &mut PgConnection,
+        req: &TokenRequest,
+        basic_auth: Option<(String, String)>,
+    ) -> Result<TokenResponse, OAuthError> {
+        let (client_id, client_secret) = match &basic_auth {
+            Some((id, secret)) => (Some(id.as_str()), Some(secret.as_str())),
+            None => (req.client_id.as_deref(), req.client_secret.as_deref()),
+        };
+        let app = Self::authenticate_client(conn, client_id, client_secret)?;
+        let now = clock::now();
+
+        conn.transaction::<_, OAuthError, _>(|conn| match req.grant_type.as_str() {
+            "authorization_code" => {
+                let code = req
+                    .code
+                    .as_deref()
+                    .ok_or_else(|| OAuthError::InvalidRequest("code is required".to_string()))?;
+                let code =
+                    OAuthAppsRepo::consume_code(conn, &ApiTokensService::hash_token(code), now)?
+                        .filter(|c| c.expires_at > now)
+                        .ok_or_else(|| {
+                            OAuthError::InvalidGrant("Invalid or expired code".to_string())
+                        })?;
+                if req.redirect_uri.as_deref() != Some(code.redirect_uri.as_str()) {
+                    return Err(OAuthError::InvalidGrant(
+                        "redirect_uri does not match the authorization request".to_string(),
+                    ));
+                }
+                if let Some(challenge) = &code.code_challenge {
+                    let verified = req
+                        .code_verifier
+                        .as_deref()
+                        .is_some_and(|verifier| Self::verify_code_challenge(challenge, verifier));
+                    if !verified {
+                        return Err(OAuthError::InvalidGrant(
+                            "code_verifier does not match".to_string(),
+                        ));
+                    }
+                }
+                let grant = Self::active_grant(conn, code.grant_id, &app)?;
+                Self::issue_token(conn, &grant, &code.scopes)
+            }
+            "refresh_token" => {
+                let refresh = req.refresh_token.as_deref().ok_or_else(|| {
+                    OAuthError::InvalidRequest("refresh_token is required".to_string())
+                })?;
+                let previous = OAuthAppsRepo::find_token_by_refresh_hash(
+                    conn,
+                    &ApiTokensService::hash_token(refresh),
+                )?
+                .filter(|t| t.revoked_at.is_none() && t.refresh_expires_at.is_some_and(|e| e > now))
+                .ok_or_else(|| {
+                    OAuthError::InvalidGrant("Invalid or expired refresh token".to_string())
+                })?;
+                let grant = Self::active_grant(conn, previous.grant_id, &app)?;
+
+                // Refresh tokens rotate: the old pair stops working
+                OAuthAppsRepo::revoke_token(conn, previous.id, now)?;
+                Self::issue_token(conn, &grant, &previous.scopes)
+            }
+            _ => Err(OAuthError::UnsupportedGrantType),
+        })
+    }
+
+    /// Handle `POST /oauth/revoke` (RFC 7009); unknown tokens are not an error
+    pub fn revoke_token(
+        conn: &mut PgConnection,
+        token: &str,
+        client_id: Option<&str>,
+        client_secret: Option<&str>,
+    ) -> Result<(), OAuthError> {
+        let app = Self::authenticate_client(conn, client_id, client_secret)?;
+        let hash = ApiTokensService::hash_token(token);
+        let found = match OAuthAppsRepo::find_token_by_hash(conn, &hash)? {
+            Some(t) => Some(t),
+            None => OAuthAppsRepo::find_token_by_refresh_hash(conn, &hash)?,
+        };
+        if let Some(found) = found
+            && OAuthAppsRepo::find_grant(conn, found.grant_id)?.is_some_and(|g| g.app_id == app.id)
+        {
+            OAuthAppsRepo::revoke_token(conn, found.id, clock::now())?;
+        }
+        Ok(())
+    }
+
+    /// Resolve a bearer access token issued to an app
+    pub fn authenticate_access_token(
+        conn: &mut PgConnection,
+        raw: &str,
+    ) -> Result<OAuthSession, AppError> {
+        let now = clock::now();
+        let invalid = || AppError::auth("Invalid or expired access token");
+        let token = OAuthAppsRepo::find_token_by_hash(conn, &ApiTokensService::hash_token(raw))?
+            .filter(|t| t.revoked_at.is_none() && t.expires_at > now)
+            .ok_or_else(invalid)?;
+        let grant = OAuthAppsRepo::find_grant(conn, token.grant_id)?
+            .filter(|g| g.revoked_at.is_none())
+            .ok_or_else(invalid)?;
+        let app = OAuthAppsRepo::find_app(conn, grant.app_id)?
+            .filter(|a| a.disabled_at.is_none())
+            .ok_or_else(invalid)?;
+
+        Ok(OAuthSession {
+            user_id: grant.user_id,
+            app_id: app.id,
+            tier: app.tier,
+            scopes: oauth_scopes::parse(&token.scopes),
+        })
+    }
+
+    // User-facing connected apps
+
+    pub fn list_authorizations(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<AuthorizedApp>, AppError> {
+        Ok(OAuthAppsRepo::list_grants_by_user(conn, ctx.user_id)?
+            .into_iter()
+            .map(|(grant, app)| AuthorizedApp {
+                app_id: app.id,
+                name: app.name,
+                description: app.description,
+                scopes: oauth_scopes::parse(&grant.scopes),
+                authorized_at: grant.created_at,
+            })
+            .collect())
+    }
+
+    /// Revoke the current user's authorization of an app and all its tokens
+    pub fn revoke_authorization(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        app_id: Uuid,
+    ) -> Result<(), AppError> {
+        let grant = OAuthAppsRepo::find_active_grant(conn, app_id, ctx.user_id)?
+            .ok_or_else(|| AppError::not_found("oauth_authorization"))?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            OAuthAppsRepo::revoke_grant(conn, grant.id, clock::now())?;
+            Ok(())
+        })
+    }
+}
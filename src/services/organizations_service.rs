@@ -0,0 +1,108 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::organization::{
+        CreateOrganizationRequest, NewOrganization, Organization, OrganizationChanges,
+        OrganizationResponse, OrganizationWorkspace, UpdateOrganizationRequest,
+    },
+    db::repositories::organizations::OrganizationsRepo,
+    db::repositories::workspaces::WorkspacesRepo,
+    error::AppError,
+};
+
+/// Organizations are managed by server admins; callers check that first
+pub struct OrganizationsService;
+
+impl OrganizationsService {
+    pub fn validate_name(name: &str) -> Result<(), AppError> {
+        if name.trim().is_empty() {
+            return Err(AppError::validation("Organization name is required"));
+        }
+        Ok(())
+    }
+
+    pub fn list(conn: &mut PgConnection) -> Result<Vec<Organization>, AppError> {
+        Ok(OrganizationsRepo::list(conn)?)
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        organization_id: Uuid,
+    ) -> Result<OrganizationResponse, AppError> {
+        let organization = OrganizationsRepo::find_by_id(conn, organization_id)?
+            .ok_or_else(|| AppError::not_found("organization"))?;
+        Self::to_response(conn, organization)
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        req: &CreateOrganizationRequest,
+    ) -> Result<Organization, AppError> {
+        Self::validate_name(&req.name)?;
+        Ok(OrganizationsRepo::insert(
+            conn,
+            &NewOrganization {
+                name: req.name.trim().to_string(),
+                allow_cross_workspace_links: req.allow_cross_workspace_links,
+            },
+        )?)
+    }
+
+    pub fn update(
+        conn: &mut PgConnection,
+        organization_id: Uuid,
+        req: &UpdateOrganizationRequest,
+    ) -> Result<Organization, AppError> {
+        if let Some(name) = &req.name {
+            Self::validate_name(name)?;
+        }
+        let organization = OrganizationsRepo::find_by_id(conn, organization_id)?
+            .ok_or_else(|| AppError::not_found("organization"))?;
+        if req.name.is_none() && req.allow_cross_workspace_links.is_none() {
+            return Ok(organization);
+        }
+        Ok(OrganizationsRepo::update(
+            conn,
+            organization_id,
+            &OrganizationChanges {
+                name: req.name.as_ref().map(|n| n.trim().to_string()),
+                allow_cross_workspace_links: req.allow_cross_workspace_links,
+            },
+        )?)
+    }
+
+    /// Move a workspace into an organization, or out of any with `None`
+    pub fn set_workspace_organization(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        WorkspacesRepo::find_by_id(conn, workspace_id)?
+            .ok_or_else(|| AppError::not_found("workspace"))?;
+        if let Some(organization_id) = organization_id {
+            OrganizationsRepo::find_by_id(conn, organization_id)?
+                .ok_or_else(|| AppError::not_found("organization"))?;
+        }
+        OrganizationsRepo::set_workspace_organization(conn, workspace_id, organization_id)?;
+        Ok(())
+    }
+
+    fn to_response(
+        conn: &mut PgConnection,
+        organization: Organization,
+    ) -> Result<OrganizationResponse, AppError> {
+        let workspaces = OrganizationsRepo::list_workspaces(conn, organization.id)?
+            .into_iter()
+            .map(|w| OrganizationWorkspace {
+                id: w.id,
+                name: w.name,
+                url_key: w.url_key,
+            })
+            .collect();
+        Ok(OrganizationResponse {
+            organization,
+            workspaces,
+        })
+    }
+}
@@ -0,0 +1,282 @@
+use diesel::prelude::*;
+
+use crate::{
+    db::models::workspace_member::WorkspaceMemberRole,
+    db::repositories::channel_permissions::ChannelPermissionsRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo, error::AppError,
+    services::context::RequestContext,
+};
+
+/// Workspace-scoped operations gated by the caller's workspace role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    UpdateWorkspace,
+    DeleteWorkspace,
+    InviteMembers,
+    ManageMembers,
+    ManageApps,
+    ManageWebhooks,
+    ManageIntegrations,
+    ManageApiAccess,
+    ManageTeams,
+    ManageTeamMembers,
+    ManageWorkflows,
+    ManageProjectStatuses,
+    ManageHolidays,
+    ManageLabels,
+    ManageCycles,
+    ManageBudgets,
+    ManageEmoji,
+    ManageAutomations,
+    CreateProject,
+    UpdateProject,
+    DeleteProject,
+    CreateIssue,
+    UpdateIssue,
+    DeleteIssue,
+    BulkArchiveIssues,
+    CreateComment,
+    ViewIssueViewers,
+    ViewAnalytics,
+    ViewAuditLog,
+    ReportContent,
+    ModerateContent,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 31] = [
+        Permission::UpdateWorkspace,
+        Permission::DeleteWorkspace,
+        Permission::InviteMembers,
+        Permission::ManageMembers,
+        Permission::ManageApps,
+        Permission::ManageWebhooks,
+        Permission::ManageIntegrations,
+        Permission::ManageApiAccess,
+        Permission::ManageTeams,
+        Permission::ManageTeamMembers,
+        Permission::ManageWorkflows,
+        Permission::ManageProjectStatuses,
+        Permission::ManageHolidays,
+        Permission::ManageLabels,
+        Permission::ManageCycles,
+        Permission::ManageBudgets,
+        Permission::ManageEmoji,
+        Permission::ManageAutomations,
+        Permission::CreateProject,
+        Permission::UpdateProject,
+        Permission::DeleteProject,
+        Permission::CreateIssue,
+        Permission::UpdateIssue,
+        Permission::DeleteIssue,
+        Permission::BulkArchiveIssues,
+        Permission::CreateComment,
+        Permission::ViewIssueViewers,
+        Permission::ViewAnalytics,
+        Permission::ViewAuditLog,
+        Permission::ReportContent,
+        Permission::ModerateContent,
+    ];
+
+    /// Least privileged role that holds this permission; roles are ordered
+    /// guest < member < admin < owner
+    pub fn minimum_role(self) -> WorkspaceMemberRole {
+        match self {
+            Permission::DeleteWorkspace => WorkspaceMemberRole::Owner,
+            Permission::UpdateWorkspace
+            | Permission::InviteMembers
+            | Permission::ManageMembers
+            | Permission::ManageApps
+            | Permission::ManageWebhooks
+            | Permission::ManageIntegrations
+            | Permission::ManageApiAccess
+            | Permission::ManageTeams
+            | Permission::ManageTeamMembers
+            | Permission::ManageWorkflows
+            | Permission::ManageProjectStatuses
+            | Permission::ManageHolidays
+            | Permission::ManageBudgets
+            | Permission::ManageEmoji
+            | Permission::ManageAutomations
+            | Permission::DeleteProject
+            | Permission::BulkArchiveIssues
+            | Permission::ViewIssueViewers
+            | Permission::ViewAnalytics
+            | Permission::ViewAuditLog
+            | Permission::ModerateContent => WorkspaceMemberRole::Admin,
+            Permission::ManageLabels
+            | Permission::ManageCycles
+            | Permission::CreateProject
+            | Permission::UpdateProject
+            | Permission::CreateIssue
+            | Permission::UpdateIssue
+            | Permission::DeleteIssue => WorkspaceMemberRole::Member,
+            Permission::CreateComment | Permission::ReportContent => WorkspaceMemberRole::Guest,
+        }
+    }
+
+    /// Human-readable action, used in error messages
+    pub fn action(self) -> &'static str {
+        match self {
+            Permission::UpdateWorkspace => "update the workspace",
+            Permission::DeleteWorkspace => "delete the workspace",
+            Permission::InviteMembers => "invite workspace members",
+            Permission::ManageMembers => "change member roles or remove members",
+            Permission::ManageApps => "install or remove apps",
+            Permission::ManageWebhooks => "manage webhooks",
+            Permission::ManageIntegrations => "manage integrations",
+            Permission::ManageApiAccess => "manage API access restrictions",
+            Permission::ManageTeams => "manage teams",
+            Permission::ManageTeamMembers => "manage team members",
+            Permission::ManageWorkflows => "manage workflows",
+            Permission::ManageProjectStatuses => "manage project statuses",
+            Permission::ManageHolidays => "manage holidays",
+            Permission::ManageLabels => "manage labels",
+            Permission::ManageCycles => "manage cycles",
+            Permission::ManageBudgets => "manage project budgets and member rates",
+            Permission::ManageEmoji => "manage custom emoji",
+            Permission::ManageAutomations => "manage automations",
+            Permission::CreateProject => "create projects",
+            Permission::UpdateProject => "update projects",
+            Permission::DeleteProject => "delete projects",
+            Permission::CreateIssue => "create issues",
+            Permission::UpdateIssue => "update issues",
+            Permission::DeleteIssue => "delete issues",
+            Permission::BulkArchiveIssues => "bulk archive issues",
+            Permission::CreateComment => "comment on issues",
+            Permission::ViewIssueViewers => "view issue viewers",
+            Permission::ViewAnalytics => "view workspace analytics",
+            Permission::ViewAuditLog => "view the settings history",
+            Permission::ReportContent => "report content",
+            Permission::ModerateContent => "review reported content",
+        }
+    }
+
+    /// Stable name used when storing channel restrictions
+    pub fn key(self) -> &'static str {
+        match self {
+            Permission::UpdateWorkspace => "update_workspace",
+            Permission::DeleteWorkspace => "delete_workspace",
+            Permission::InviteMembers => "invite_members",
+            Permission::ManageMembers => "manage_members",
+            Permission::ManageApps => "manage_apps",
+            Permission::ManageWebhooks => "manage_webhooks",
+            Permission::ManageIntegrations => "manage_integrations",
+            Permission::ManageApiAccess => "manage_api_access",
+            Permission::ManageTeams => "manage_teams",
+            Permission::ManageTeamMembers => "manage_team_members",
+            Permission::ManageWorkflows => "manage_workflows",
+            Permission::ManageProjectStatuses => "manage_project_statuses",
+            Permission::ManageHolidays => "manage_holidays",
+            Permission::ManageLabels => "manage_labels",
+            Permission::ManageCycles => "manage_cycles",
+            Permission::ManageBudgets => "manage_budgets",
+            Permission::ManageEmoji => "manage_emoji",
+            Permission::ManageAutomations => "manage_automations",
+            Permission::CreateProject => "create_project",
+            Permission::UpdateProject => "update_project",
+            Permission::DeleteProject => "delete_project",
+            Permission::CreateIssue => "create_issue",
+            Permission::UpdateIssue => "update_issue",
+            Permission::DeleteIssue => "delete_issue",
+            Permission::BulkArchiveIssues => "bulk_archive_issues",
+            Permission::CreateComment => "create_comment",
+            Permission::ViewIssueViewers => "view_issue_viewers",
+            Permission::ViewAnalytics => "view_analytics",
+            Permission::ViewAuditLog => "view_audit_log",
+            Permission::ReportContent => "report_content",
+            Permission::ModerateContent => "moderate_content",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|permission| permission.key() == key)
+    }
+
+    /// Never granted to API channels, whatever the workspace allows: a token
+    /// must not be able to delete the workspace or lift its own restrictions
+    pub fn always_denied_to_api(self) -> bool {
+        matches!(
+            self,
+            Permission::DeleteWorkspace | Permission::ManageApiAccess
+        )
+    }
+}
+
+fn rank(role: &WorkspaceMemberRole) -> u8 {
+    match role {
+        WorkspaceMemberRole::Guest => 0,
+        WorkspaceMemberRole::Member => 1,
+        WorkspaceMemberRole::Admin => 2,
+        WorkspaceMemberRole::Owner => 3,
+    }
+}
+
+pub struct PermissionService;
+
+impl PermissionService {
+    pub fn role_allows(role: &WorkspaceMemberRole, permission: Permission) -> bool {
+        Self::role_meets(role, &permission.minimum_role())
+    }
+
+    /// Whether `role` is `minimum` or more privileged
+    pub fn role_meets(role: &WorkspaceMemberRole, minimum: &WorkspaceMemberRole) -> bool {
+        rank(role) >= rank(minimum)
+    }
+
+    /// The caller's role in the current workspace; `None` when not a member
+    pub fn current_role(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Option<WorkspaceMemberRole>, AppError> {
+        Ok(WorkspaceMembersRepo::find(conn, ctx.workspace_id, ctx.user_id)?.map(|m| m.role))
+    }
+
+    /// Whether the channel the caller authenticated through may use
+    /// `permission`; interactive sessions are never restricted
+    pub fn channel_allows(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        permission: Permission,
+    ) -> Result<bool, AppError> {
+        if !ctx.channel.is_api() {
+            return Ok(true);
+        }
+        if permission.always_denied_to_api() {
+            return Ok(false);
+        }
+        let denied = ChannelPermissionsRepo::is_denied(
+            conn,
+            ctx.workspace_id,
+            ctx.channel.as_str(),
+            permission.key(),
+        )?;
+        Ok(!denied)
+    }
+
+    /// Fail with `AppError::Forbidden` unless the caller's role grants
+    /// `permission` and the workspace allows it over the caller's channel
+    pub fn require(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        permission: Permission,
+    ) -> Result<WorkspaceMemberRole, AppError> {
+        let role = Self::current_role(conn, ctx)?
+            .ok_or_else(|| AppError::forbidden("You are not a member of this workspace"))?;
+        if !Self::role_allows(&role, permission) {
+            return Err(AppError::forbidden(format!(
+                "Your role does not allow you to {}",
+                permission.action()
+            )));
+        }
+        if !Self::channel_allows(conn, ctx, permission)? {
+            return Err(AppError::forbidden(format!(
+                "API access is not allowed to {}; sign in to do this",
+                permission.action()
+            )));
+        }
+        Ok(role)
+    }
+}
@@ -0,0 +1,391 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    db::models::notification::{NewNotification, notification_events},
+    db::models::project::Project,
+    db::models::project_cost::{
+        BudgetAlert, BudgetStatus, CreateCostEntryRequest, DEFAULT_BUDGET_ALERT_PERCENT,
+        MAX_COST_ENTRY_MINUTES, MemberCost, MemberCostRate, NewMemberCostRate, NewProjectCostEntry,
+        ProjectCostEntry, ProjectCostSummary, SetMemberRateRequest, SetProjectBudgetRequest,
+    },
+    db::repositories::issues::IssueRepo,
+    db::repositories::project_costs::ProjectCostsRepo,
+    db::repositories::projects::ProjectsRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::notifications_service::NotificationsService,
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    utils::clock,
+    websocket::Topic,
+};
+
+/// Realtime event sent when a project's spend crosses an alert threshold
+pub const BUDGET_ALERT: &str = "project.budget_alert";
+
+/// Project budgets and the cost of time logged against them. Budgets and
+/// rates are managed by admins; members log and see their own time.
+pub struct ProjectCostsService;
+
+impl ProjectCostsService {
+    /// Upper-case ISO 4217 code, e.g. `usd` becomes `USD`
+    pub fn normalize_currency(code: &str) -> Result<String, AppError> {
+        let code = code.trim().to_ascii_uppercase();
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(AppError::validation(
+                "Currency must be a three-letter ISO 4217 code",
+            ));
+        }
+        Ok(code)
+    }
+
+    /// Cost of `minutes` at an hourly rate, rounded to the nearest cent
+    pub fn cost_cents(minutes: i32, hourly_rate_cents: i64) -> i64 {
+        (i64::from(minutes) * hourly_rate_cents + 30) / 60
+    }
+
+    pub fn budget_status(
+        spent_cents: i64,
+        budget_cents: Option<i64>,
+        alert_percent: i32,
+    ) -> BudgetStatus {
+        let Some(budget) = budget_cents else {
+            return BudgetStatus::NoBudget;
+        };
+        if spent_cents > budget {
+            BudgetStatus::Exceeded
+        } else if budget > 0 && spent_cents * 100 >= budget * i64::from(alert_percent) {
+            BudgetStatus::Approaching
+        } else {
+            BudgetStatus::Ok
+        }
+    }
+
+    /// Totals for a project from its cost entries
+    pub fn summarize(project: &Project, entries: &[ProjectCostEntry]) -> ProjectCostSummary {
+        let mut by_member: HashMap<Uuid, MemberCost> = HashMap::new();
+        for entry in entries {
+            let member = by_member.entry(entry.user_id).or_insert(MemberCost {
+                user_id: entry.user_id,
+                minutes: 0,
+                amount_cents: 0,
+            });
+            member.minutes += i64::from(entry.minutes);
+            member.amount_cents += entry.amount_cents;
+        }
+        let mut by_member: Vec<MemberCost> = by_member.into_values().collect();
+        by_member.sort_by(|a, b| {
+            b.amount_cents
+                .cmp(&a.amount_cents)
+                .then(a.user_id.cmp(&b.user_id))
+        });
+
+        let spent_cents = by_member.iter().map(|m| m.amount_cents).sum();
+        let total_minutes = by_member.iter().map(|m| m.minutes).sum();
+        let budget = project.budget_amount_cents;
+        ProjectCostSummary {
+            project_id: project.id,
+            currency: project.budget_currency.clone(),
+            budget_amount_cents: budget,
+            spent_cents,
+            remaining_cents: budget.map(|b| b - spent_cents),
+            percent_used: budget
+                .filter(|b| *b > 0)
+                .map(|b| spent_cents as f64 * 100.0 / b as f64),
+            alert_percent: project.budget_alert_percent,
+            status: Self::budget_status(spent_cents, budget, project.budget_alert_percent),
+            total_minutes,
+            by_member,
+        }
+    }
+
+    pub fn set_budget(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        req: &SetProjectBudgetRequest,
+    ) -> Result<Project, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageBudgets)?;
+        let project = Self::find_project(conn, ctx, project_id)?;
+        let currency = req
+            .currency
+            .as_deref()
+            .map(Self::normalize_currency)
+            .transpose()?;
+        if req.amount_cents.is_some() && currency.is_none() {
+            return Err(AppError::validation("A budget amount needs a currency"));
+        }
+        if req.amount_cents.is_some_and(|amount| amount < 0) {
+            return Err(AppError::validation("Budget amount cannot be negative"));
+        }
+        let alert_percent = req.alert_percent.unwrap_or(DEFAULT_BUDGET_ALERT_PERCENT);
+        if !(1..=100).contains(&alert_percent) {
+            return Err(AppError::validation(
+                "Alert percent must be between 1 and 100",
+            ));
+        }
+        // Spend is summed without conversion, so the currency is fixed once
+        // costs are logged
+        if let Some(currency) = &currency
+            && project.budget_currency.as_ref() != Some(currency)
+            && ProjectCostsRepo::has_entries_in_other_currency(conn, project.id, currency)?
+        {
+            return Err(AppError::conflict_with_code(
+                "Costs have already been logged in another currency",
+                Some("currency".to_string()),
+                "BUDGET_CURRENCY_IN_USE",
+            ));
+        }
+        if currency.is_none() && !ProjectCostsRepo::list_entries(conn, project.id, None)?.is_empty()
+        {
+            return Err(AppError::conflict_with_code(
+                "Cannot remove the budget currency of a project with logged costs",
+                Some("currency".to_string()),
+                "BUDGET_CURRENCY_IN_USE",
+            ));
+        }
+
+        let updated = ProjectCostsRepo::set_budget(
+            conn,
+            project.id,
+            currency.as_deref(),
+            req.amount_cents,
+            alert_percent,
+        )?;
+        Self::check_alert(conn, ctx, &updated)?;
+        Ok(updated)
+    }
+
+    pub fn summary(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+    ) -> Result<ProjectCostSummary, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageBudgets)?;
+        let project = Self::find_project(conn, ctx, project_id)?;
+        let entries = ProjectCostsRepo::list_entries(conn, project.id, None)?;
+        Ok(Self::summarize(&project, &entries))
+    }
+
+    /// All entries for budget managers, otherwise only the caller's own
+    pub fn list_entries(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectCostEntry>, AppError> {
+        let project = Self::find_project(conn, ctx, project_id)?;
+        let user = if Self::can_manage(conn, ctx)? {
+            None
+        } else {
+            Some(ctx.user_id)
+        };
+        Ok(ProjectCostsRepo::list_entries(conn, project.id, user)?)
+    }
+
+    pub fn log_cost(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        req: &CreateCostEntryRequest,
+    ) -> Result<ProjectCostEntry, AppError> {
+        PermissionService::require(conn, ctx, Permission::UpdateProject)?;
+        let project = Self::find_project(conn, ctx, project_id)?;
+        let user_id = req.user_id.unwrap_or(ctx.user_id);
+        if user_id != ctx.user_id {
+            PermissionService::require(conn, ctx, Permission::ManageBudgets)?;
+        }
+        if !(1..=MAX_COST_ENTRY_MINUTES).contains(&req.minutes) {
+            return Err(AppError::validation(format!(
+                "Minutes must be between 1 and {}",
+                MAX_COST_ENTRY_MINUTES
+            )));
+        }
+        let Some(currency) = project.budget_currency.clone() else {
+            return Err(AppError::validation(
+                "Set a budget currency on the project before logging costs",
+            ));
+        };
+        if let Some(issue_id) = req.issue_id {
+            IssueRepo::find_by_id(conn, issue_id)?
+                .filter(|issue| issue.project_id == Some(project.id))
+                .ok_or_else(|| AppError::validation("Issue must belong to the project"))?;
+        }
+        let rate = ProjectCostsRepo::find_rate(conn, ctx.workspace_id, user_id)?
+            .ok_or_else(|| AppError::validation("No hourly rate is set for this member"))?;
+        if rate.currency != currency {
+            return Err(AppError::validation(format!(
+                "The member's rate is in {} but the project budget is in {}",
+                rate.currency, currency
+            )));
+        }
+
+        let entry = ProjectCostsRepo::insert_entry(
+            conn,
+            &NewProjectCostEntry {
+                project_id: project.id,
+                user_id,
+                issue_id: req.issue_id,
+                minutes: req.minutes,
+                hourly_rate_cents: rate.hourly_rate_cents,
+                amount_cents: Self::cost_cents(req.minutes, rate.hourly_rate_cents),
+                currency,
+                spent_on: req.spent_on.unwrap_or_else(|| clock::now().date_naive()),
+                description: req.description.clone(),
+                created_by: Some(ctx.user_id),
+            },
+        )?;
+        Self::check_alert(conn, ctx, &project)?;
+        Ok(entry)
+    }
+
+    /// Members may remove their own entries; budget managers any
+    pub fn delete_cost(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+        entry_id: Uuid,
+    ) -> Result<(), AppError> {
+        let project = Self::find_project(conn, ctx, project_id)?;
+        let entry = ProjectCostsRepo::find_entry(conn, project.id, entry_id)?
+            .ok_or_else(|| AppError::not_found("cost entry"))?;
+        if entry.user_id != ctx.user_id {
+            PermissionService::require(conn, ctx, Permission::ManageBudgets)?;
+        }
+        ProjectCostsRepo::delete_entry(conn, entry.id)?;
+        Self::check_alert(conn, ctx, &project)?;
+        Ok(())
+    }
+
+    pub fn list_rates(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<MemberCostRate>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageBudgets)?;
+        Ok(ProjectCostsRepo::list_rates(conn, ctx.workspace_id)?)
+    }
+
+    /// Applies to time logged from now on; existing entries keep their rate
+    pub fn set_rate(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        user_id: Uuid,
+        req: &SetMemberRateRequest,
+    ) -> Result<MemberCostRate, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageBudgets)?;
+        if WorkspaceMembersRepo::find(conn, ctx.workspace_id, user_id)?.is_none() {
+            return Err(AppError::not_found("workspace member"));
+        }
+        if req.hourly_rate_cents < 0 {
+            return Err(AppError::validation("Hourly rate cannot be negative"));
+        }
+        Ok(ProjectCostsRepo::upsert_rate(
+            conn,
+            &NewMemberCostRate {
+                workspace_id: ctx.workspace_id,
+                user_id,
+                hourly_rate_cents: req.hourly_rate_cents,
+                currency: Self::normalize_currency(&req.currency)?,
+                updated_by: Some(ctx.user_id),
+                updated_at: clock::now(),
+            },
+        )?)
+    }
+
+    pub fn delete_rate(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageBudgets)?;
+        if ProjectCostsRepo::delete_rate(conn, ctx.workspace_id, user_id)? == 0 {
+            return Err(AppError::not_found("member rate"));
+        }
+        Ok(())
+    }
+
+    fn find_project(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project_id: Uuid,
+    ) -> Result<Project, AppError> {
+        ProjectsRepo::find_by_id_in_workspace(conn, ctx.workspace_id, project_id)?
+            .ok_or_else(|| AppError::not_found("project"))
+    }
+
+    fn can_manage(conn: &mut PgConnection, ctx: &RequestContext) -> Result<bool, AppError> {
+        Ok(PermissionService::current_role(conn, ctx)?
+            .is_some_and(|role| PermissionService::role_allows(&role, Permission::ManageBudgets)))
+    }
+
+    /// Alert the owner once when spend first reaches the alert threshold and
+    /// again when it passes the budget. Dropping back below (an entry was
+    /// removed or the budget raised) re-arms the alert.
+    fn check_alert(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        project: &Project,
+    ) -> Result<(), AppError> {
+        let entries = ProjectCostsRepo::list_entries(conn, project.id, None)?;
+        let summary = Self::summarize(project, &entries);
+        let sent = BudgetStatus::parse(&project.budget_alert_level).unwrap_or(BudgetStatus::Ok);
+        let current = summary.status.max(BudgetStatus::Ok);
+        if current == sent {
+            return Ok(());
+        }
+        ProjectCostsRepo::set_alert_level(conn, project.id, current.as_str())?;
+        if current < sent {
+            return Ok(());
+        }
+
+        let (Some(budget_amount_cents), Some(currency)) =
+            (summary.budget_amount_cents, summary.currency.clone())
+        else {
+            return Ok(());
+        };
+        let alert = BudgetAlert {
+            project_id: project.id,
+            status: current,
+            spent_cents: summary.spent_cents,
+            budget_amount_cents,
+            currency,
+        };
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Project(project.id),
+            BUDGET_ALERT,
+            &alert,
+        );
+        let body = match current {
+            BudgetStatus::Exceeded => "Spend has passed the project budget",
+            _ => "Spend is approaching the project budget",
+        };
+        NotificationsService::notify_quietly(
+            conn,
+            NewNotification {
+                workspace_id: ctx.workspace_id,
+                recipient_id: project.owner_id,
+                actor_id: Some(ctx.user_id),
+                event_type: notification_events::BUDGET_ALERT.to_string(),
+                entity_type: "project".to_string(),
+                entity_id: project.id,
+                title: project.name.clone(),
+                body: Some(format!(
+                    "{}: {} of {} {} spent",
+                    body,
+                    format_cents(alert.spent_cents),
+                    format_cents(alert.budget_amount_cents),
+                    alert.currency
+                )),
+            },
+        );
+        Ok(())
+    }
+}
+
+fn format_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+}
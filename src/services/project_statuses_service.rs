@@ -5,9 +5,11 @@ use crate::{
     db::repositories::project_statuses::ProjectStatusRepo,
     error::AppError,
     services::context::RequestContext,
+    services::realtime_service::RealtimeService,
     validation::project_status::{
         UpdateProjectStatusChanges, validate_create_project_status, validate_update_project_status,
     },
+    websocket::{EntityAction, EntityKind},
 };
 
 pub struct ProjectStatusesService;
@@ -54,7 +56,7 @@ impl ProjectStatusesService {
             workspace_id: ctx.workspace_id,
         };
         let created = ProjectStatusRepo::insert(conn, &new_status)?;
-        Ok(ProjectStatusInfo {
+        let info = ProjectStatusInfo {
             id: created.id,
             name: created.name,
             description: created.description,
@@ -62,7 +64,15 @@ impl ProjectStatusesService {
             category: created.category,
             created_at: created.created_at,
             updated_at: created.updated_at,
-        })
+        };
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::ProjectStatus,
+            EntityAction::Created,
+            info.id,
+            &info,
+        );
+        Ok(info)
     }
 
     pub fn get_by_id(
@@ -119,19 +129,19 @@ impl ProjectStatusesService {
             }),
         };
         validate_update_project_status(&changes)?;
-        if let Some(name) = &req.name {
-            if ProjectStatusRepo::exists_by_name_excluding_id(
+        if let Some(name) = &req.name
+            && ProjectStatusRepo::exists_by_name_excluding_id(
                 conn,
                 ctx.workspace_id,
                 name,
                 status_id,
-            )? {
-                return Err(AppError::conflict_with_code(
-                    "Project status already exists",
-                    Some("name".into()),
-                    "PROJECT_STATUS_EXISTS",
-                ));
-            }
+            )?
+        {
+            return Err(AppError::conflict_with_code(
+                "Project status already exists",
+                Some("name".into()),
+                "PROJECT_STATUS_EXISTS",
+            ));
         }
         let updated = ProjectStatusRepo::update_fields(
             conn,
@@ -158,7 +168,7 @@ impl ProjectStatusesService {
                 _ => None,
             }),
         )?;
-        Ok(ProjectStatusInfo {
+        let info = ProjectStatusInfo {
             id: updated.id,
             name: updated.name,
             description: updated.description,
@@ -166,7 +176,15 @@ impl ProjectStatusesService {
             category: updated.category,
             created_at: updated.created_at,
             updated_at: updated.updated_at,
-        })
+        };
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::ProjectStatus,
+            EntityAction::Updated,
+            info.id,
+            &info,
+        );
+        Ok(info)
     }
 
     pub fn delete(
@@ -180,6 +198,13 @@ impl ProjectStatusesService {
             return Err(AppError::not_found("project_status"));
         }
         ProjectStatusRepo::delete_by_id(conn, status_id)?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::ProjectStatus,
+            EntityAction::Deleted,
+            status_id,
+            &(),
+        );
         Ok(())
     }
 }
@@ -1,13 +1,32 @@
+use chrono::NaiveDate;
 use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
 
 use crate::{
-    db::models::project::{NewProject, Project, ProjectInfo},
+    db::models::app_installation::webhook_events,
+    db::models::auth::{User, UserBasicInfo},
+    db::models::project::{
+        NewProject, Project, ProjectBoard, ProjectBoardCard, ProjectBoardColumn, ProjectBoardQuery,
+        ProjectHealth, ProjectInfo,
+    },
+    db::models::project_status::{ProjectStatus, ProjectStatusCategory, ProjectStatusInfo},
+    db::models::workflow::WorkflowStateCategory,
     db::repositories::projects::ProjectsRepo,
+    db::repositories::workflows::WorkflowsRepo,
     error::AppError,
     services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    services::webhook_service::WebhookService,
+    utils::clock,
     validation::project::validate_create_project,
+    websocket::{EntityAction, EntityKind, Topic},
 };
 
+/// How far issue progress may trail the time elapsed towards the target date
+/// before a project counts as at risk
+const HEALTH_SLACK: f64 = 0.25;
+
 pub struct ProjectsService;
 
 impl ProjectsService {
@@ -60,6 +79,25 @@ impl ProjectsService {
         };
 
         let created = ProjectsRepo::insert(conn, &new_project)?;
+        WebhookService::emit_quietly(
+            conn,
+            ctx.workspace_id,
+            webhook_events::PROJECT_CREATED,
+            &created,
+        );
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Project(created.id),
+            webhook_events::PROJECT_CREATED,
+            &created,
+        );
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Project,
+            EntityAction::Created,
+            created.id,
+            &created,
+        );
         Ok(created)
     }
 
@@ -154,6 +192,8 @@ impl ProjectsService {
                 owner: owner_basic,
                 target_date: project.target_date,
                 priority: project.priority,
+                budget_currency: project.budget_currency,
+                budget_amount_cents: project.budget_amount_cents,
                 created_at: project.created_at,
                 updated_at: project.updated_at,
             });
@@ -247,7 +287,7 @@ impl ProjectsService {
                 )
                 .collect();
 
-        Ok(crate::db::models::project::ProjectInfo {
+        let info = crate::db::models::project::ProjectInfo {
             id: updated.id,
             name: updated.name,
             project_key: updated.project_key,
@@ -257,9 +297,31 @@ impl ProjectsService {
             owner: owner_basic,
             target_date: updated.target_date,
             priority: updated.priority,
+            budget_currency: updated.budget_currency,
+            budget_amount_cents: updated.budget_amount_cents,
             created_at: updated.created_at,
             updated_at: updated.updated_at,
-        })
+        };
+        WebhookService::emit_quietly(
+            conn,
+            ctx.workspace_id,
+            webhook_events::PROJECT_UPDATED,
+            &info,
+        );
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Project(info.id),
+            webhook_events::PROJECT_UPDATED,
+            &info,
+        );
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Project,
+            EntityAction::Updated,
+            info.id,
+            &info,
+        );
+        Ok(info)
     }
 
     pub fn delete(
@@ -274,6 +336,268 @@ impl ProjectsService {
         }
 
         ProjectsRepo::delete_by_id(conn, project_id)?;
+        WebhookService::emit_quietly(
+            conn,
+            ctx.workspace_id,
+            webhook_events::PROJECT_DELETED,
+            &serde_json::json!({ "id": project_id }),
+        );
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Project(project_id),
+            webhook_events::PROJECT_DELETED,
+            &serde_json::json!({ "id": project_id }),
+        );
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Project,
+            EntityAction::Deleted,
+            project_id,
+            &(),
+        );
         Ok(())
     }
+
+    /// Projects grouped into one column per status category, in
+    /// [`ProjectStatusCategory::ALL`] order, with lead, issue progress and
+    /// health rolled up so the overview needs a single request
+    pub fn board(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        asset_helper: &crate::utils::AssetUrlHelper,
+        query: &ProjectBoardQuery,
+    ) -> Result<ProjectBoard, AppError> {
+        let categories = match query.categories.as_deref() {
+            Some(categories) => Self::parse_board_categories(categories)?,
+            None => ProjectStatusCategory::ALL.to_vec(),
+        };
+        let status_ids = Self::parse_board_ids(query.status_ids.as_deref(), "status_ids")?;
+        let lead_ids = Self::parse_board_ids(query.lead_ids.as_deref(), "lead_ids")?;
+
+        let statuses = {
+            use crate::schema::project_statuses::dsl as s;
+            s::project_statuses
+                .filter(s::workspace_id.eq(ctx.workspace_id))
+                .order((s::created_at.asc(), s::id.asc()))
+                .select(ProjectStatus::as_select())
+                .load::<ProjectStatus>(conn)?
+        };
+        let shown_status_ids: Vec<Uuid> = statuses
+            .iter()
+            .filter(|status| categories.contains(&status.category))
+            .filter(|status| status_ids.is_empty() || status_ids.contains(&status.id))
+            .map(|status| status.id)
+            .collect();
+
+        let projects = {
+            use crate::schema::projects::dsl as p;
+            let mut projects = p::projects
+                .filter(p::workspace_id.eq(ctx.workspace_id))
+                .filter(p::project_status_id.eq_any(&shown_status_ids))
+                .into_boxed();
+            if !lead_ids.is_empty() {
+                projects = projects.filter(p::owner_id.eq_any(&lead_ids));
+            }
+            if let Some(search) = query.search.as_deref().map(str::trim)
+                && !search.is_empty()
+            {
+                projects = projects.filter(p::name.ilike(format!("%{}%", search)));
+            }
+            projects
+                .order((p::target_date.asc().nulls_last(), p::name.asc()))
+                .select(Project::as_select())
+                .load::<Project>(conn)?
+        };
+
+        let owner_ids: Vec<Uuid> = projects.iter().map(|project| project.owner_id).collect();
+        let owners: HashMap<Uuid, User> = {
+            use crate::schema::users::dsl as u;
+            u::users
+                .filter(u::id.eq_any(&owner_ids))
+                .select(User::as_select())
+                .load::<User>(conn)?
+                .into_iter()
+                .map(|user| (user.id, user))
+                .collect()
+        };
+
+        // (total, completed, latest issue update) per project
+        let project_ids: Vec<Uuid> = projects.iter().map(|project| project.id).collect();
+        let rollups = ProjectsRepo::issue_rollups(conn, &project_ids)?;
+        let state_ids: Vec<Uuid> = rollups.iter().filter_map(|row| row.1).collect();
+        let state_categories: HashMap<Uuid, WorkflowStateCategory> =
+            WorkflowsRepo::list_states_by_ids(conn, &state_ids)?
+                .into_iter()
+                .map(|state| (state.id, state.category))
+                .collect();
+        let mut progress: HashMap<Uuid, (i64, i64, Option<chrono::DateTime<chrono::Utc>>)> =
+            HashMap::new();
+        for (project_id, state_id, count, last_update) in rollups {
+            let Some(project_id) = project_id else {
+                continue;
+            };
+            let entry = progress.entry(project_id).or_default();
+            entry.2 = entry.2.max(last_update);
+            match state_id.and_then(|state_id| state_categories.get(&state_id)) {
+                Some(WorkflowStateCategory::Canceled) => {}
+                Some(WorkflowStateCategory::Completed) => {
+                    entry.0 += count;
+                    entry.1 += count;
+                }
+                _ => entry.0 += count,
+            }
+        }
+
+        let today = clock::now().date_naive();
+        let status_infos: HashMap<Uuid, ProjectStatusInfo> = statuses
+            .iter()
+            .map(|status| (status.id, ProjectStatusInfo::from(status.clone())))
+            .collect();
+        let mut cards: Vec<ProjectBoardCard> = Vec::with_capacity(projects.len());
+        for project in projects {
+            let status = status_infos
+                .get(&project.project_status_id)
+                .cloned()
+                .ok_or_else(|| AppError::internal("Project status not found"))?;
+            let owner = owners
+                .get(&project.owner_id)
+                .ok_or_else(|| AppError::internal("Failed to retrieve project owner"))?;
+            let (total_issues, completed_issues, last_issue_update) =
+                progress.get(&project.id).copied().unwrap_or_default();
+            cards.push(ProjectBoardCard {
+                health: Self::project_health(
+                    status.category,
+                    project.created_at.date_naive(),
+                    project.target_date,
+                    today,
+                    total_issues,
+                    completed_issues,
+                ),
+                id: project.id,
+                name: project.name,
+                project_key: project.project_key,
+                status,
+                lead: UserBasicInfo {
+                    id: owner.id,
+                    name: owner.name.clone(),
+                    username: owner.username.clone(),
+                    email: owner.email.clone(),
+                    avatar_url: owner
+                        .avatar_url
+                        .as_ref()
+                        .map(|url| asset_helper.process_url(url)),
+                },
+                priority: project.priority,
+                target_date: project.target_date,
+                total_issues,
+                completed_issues,
+                last_activity_at: last_issue_update.map_or(project.updated_at, |updated| {
+                    updated.max(project.updated_at)
+                }),
+            });
+        }
+
+        let total_count = cards.len() as i64;
+        let columns = categories
+            .into_iter()
+            .map(|category| {
+                let projects: Vec<ProjectBoardCard> = cards
+                    .iter()
+                    .filter(|card| card.status.category == category)
+                    .cloned()
+                    .collect();
+                ProjectBoardColumn {
+                    category,
+                    statuses: statuses
+                        .iter()
+                        .filter(|status| status.category == category)
+                        .map(|status| status_infos[&status.id].clone())
+                        .collect(),
+                    count: projects.len() as i64,
+                    projects,
+                }
+            })
+            .collect();
+        Ok(ProjectBoard {
+            columns,
+            total_count,
+        })
+    }
+
+    /// Board categories from a comma-separated list, in column order and
+    /// without repeats
+    pub fn parse_board_categories(value: &str) -> Result<Vec<ProjectStatusCategory>, AppError> {
+        let mut wanted = Vec::new();
+        for name in value
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let category = ProjectStatusCategory::ALL
+                .into_iter()
+                .find(|category| category.as_str() == name)
+                .ok_or_else(|| {
+                    AppError::validation(format!("Unknown project status category '{}'", name))
+                })?;
+            wanted.push(category);
+        }
+        Ok(ProjectStatusCategory::ALL
+            .into_iter()
+            .filter(|category| wanted.contains(category))
+            .collect())
+    }
+
+    fn parse_board_ids(value: Option<&str>, field: &str) -> Result<Vec<Uuid>, AppError> {
+        value
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                Uuid::parse_str(id)
+                    .map_err(|_| AppError::validation(format!("Invalid id '{}' in {}", id, field)))
+            })
+            .collect()
+    }
+
+    /// Health of an open project with a target date: off track once the
+    /// date has passed with issues still open, at risk while the share of
+    /// completed issues trails the share of time elapsed since creation by
+    /// more than [`HEALTH_SLACK`]
+    pub fn project_health(
+        category: ProjectStatusCategory,
+        created_on: NaiveDate,
+        target_date: Option<NaiveDate>,
+        today: NaiveDate,
+        total_issues: i64,
+        completed_issues: i64,
+    ) -> Option<ProjectHealth> {
+        if matches!(
+            category,
+            ProjectStatusCategory::Completed | ProjectStatusCategory::Canceled
+        ) {
+            return None;
+        }
+        let target_date = target_date?;
+        let all_done = total_issues > 0 && completed_issues >= total_issues;
+        if today > target_date {
+            return Some(if all_done {
+                ProjectHealth::OnTrack
+            } else {
+                ProjectHealth::OffTrack
+            });
+        }
+        let span = (target_date - created_on).num_days().max(1) as f64;
+        let elapsed = ((today - created_on).num_days().max(0) as f64 / span).min(1.0);
+        let done = if total_issues > 0 {
+            completed_issues as f64 / total_issues as f64
+        } else {
+            0.0
+        };
+        Some(if done + HEALTH_SLACK < elapsed {
+            ProjectHealth::AtRisk
+        } else {
+            ProjectHealth::OnTrack
+        })
+    }
 }
@@ -0,0 +1,633 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    config::{ApnsConfig, FcmConfig, PushConfig, WebPushConfig},
+    db::models::notification::{Notification, notification_events},
+    db::models::push::{
+        NewPushDelivery, NewPushSubscription, PushDelivery, PushDeliveryAttempt, PushPayload,
+        PushPreferenceRecord, PushPreferences, PushSubscription, RegisterPushSubscriptionRequest,
+        UpdatePushPreferencesRequest, push_delivery_status, push_platforms,
+    },
+    db::repositories::push::{PushDeliveriesRepo, PushPreferencesRepo, PushSubscriptionsRepo},
+    error::AppError,
+    utils::clock,
+};
+
+/// Attempts before a delivery is given up
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 5;
+
+/// Deliveries sent per `deliver_due` call
+pub const DELIVERY_BATCH_SIZE: i64 = 100;
+
+/// Subscriptions the client has not re-registered for this long are dropped
+pub const STALE_SUBSCRIPTION_DAYS: i64 = 90;
+
+/// Delivered and failed rows are kept this long for debugging
+pub const DELIVERY_RETENTION_DAYS: i64 = 7;
+
+const RETRY_BASE_SECS: i64 = 15;
+const DELIVERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// How long push services hold a message for an offline device
+const PUSH_TTL_SECS: i64 = 24 * 3600;
+const MAX_TOKEN_LEN: usize = 4096;
+
+const APNS_HOST: &str = "https://api.push.apple.com";
+const APNS_SANDBOX_HOST: &str = "https://api.sandbox.push.apple.com";
+/// APNs rejects provider tokens older than an hour and refreshes more often than every 20 minutes
+const APNS_TOKEN_LIFETIME_MINUTES: i64 = 40;
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+
+/// How a push service answered a delivery
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushOutcome {
+    Delivered,
+    /// The device token is no longer valid; the subscription should be removed
+    Gone,
+    /// Temporary failure, worth retrying later
+    Retry(String),
+    /// Permanent failure for this message
+    Rejected(String),
+}
+
+/// Push subscriptions per user and device, and delivery of new notifications
+/// to them. Deliveries are queued with the notification and sent by the worker.
+pub struct PushService;
+
+impl PushService {
+    /// Register (or refresh) the caller's device. Clients should call this on
+    /// every app start so the subscription is not pruned as stale.
+    pub fn register(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        req: &RegisterPushSubscriptionRequest,
+    ) -> Result<PushSubscription, AppError> {
+        let new = Self::validate_registration(user_id, req)?;
+        Ok(PushSubscriptionsRepo::upsert(conn, &new, clock::now())?)
+    }
+
+    pub fn validate_registration(
+        user_id: Uuid,
+        req: &RegisterPushSubscriptionRequest,
+    ) -> Result<NewPushSubscription, AppError> {
+        let platform = req.platform.trim();
+        if !push_platforms::is_valid(platform) {
+            return Err(AppError::validation(format!(
+                "platform must be one of: {}",
+                push_platforms::ALL.join(", ")
+            )));
+        }
+        let token = req.token.trim();
+        if token.is_empty() || token.len() > MAX_TOKEN_LEN {
+            return Err(AppError::validation(
+                "token must be between 1 and 4096 characters",
+            ));
+        }
+        let valid = match platform {
+            push_platforms::WEB => token.starts_with("https://"),
+            push_platforms::APNS => token.chars().all(|c| c.is_ascii_hexdigit()),
+            _ => !token.chars().any(char::is_whitespace),
+        };
+        if !valid {
+            return Err(AppError::validation(format!(
+                "token is not a valid {} token",
+                platform
+            )));
+        }
+        let device_name = req
+            .device_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| name.chars().take(100).collect());
+        Ok(NewPushSubscription {
+            user_id,
+            platform: platform.to_string(),
+            token: token.to_string(),
+            device_name,
+        })
+    }
+
+    pub fn list(conn: &mut PgConnection, user_id: Uuid) -> Result<Vec<PushSubscription>, AppError> {
+        Ok(PushSubscriptionsRepo::list_by_user(conn, user_id)?)
+    }
+
+    pub fn unregister(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        subscription_id: Uuid,
+    ) -> Result<(), AppError> {
+        if PushSubscriptionsRepo::delete_for_user(conn, user_id, subscription_id)? == 0 {
+            return Err(AppError::not_found("push_subscription"));
+        }
+        Ok(())
+    }
+
+    pub fn preferences(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<PushPreferences, AppError> {
+        Ok(PushPreferencesRepo::find(conn, user_id)?
+            .map(PushPreferences::from)
+            .unwrap_or_default())
+    }
+
+    pub fn update_preferences(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        req: &UpdatePushPreferencesRequest,
+    ) -> Result<PushPreferences, AppError> {
+        let current = Self::preferences(conn, user_id)?;
+        let muted_events = match &req.muted_events {
+            Some(events) => Self::normalize_muted_events(events)?,
+            None => current.muted_events,
+        };
+        let record = PushPreferencesRepo::upsert(
+            conn,
+            &PushPreferenceRecord {
+                user_id,
+                enabled: req.enabled.unwrap_or(current.enabled),
+                muted_events: muted_events.join(" "),
+                updated_at: clock::now(),
+            },
+        )?;
+        Ok(record.into())
+    }
+
+    /// Known notification event types, without duplicates
+    pub fn normalize_muted_events(events: &[String]) -> Result<Vec<String>, AppError> {
+        let mut muted: Vec<String> = Vec::new();
+        for event in events.iter().map(|e| e.trim()) {
+            if !notification_events::ALL.contains(&event) {
+                return Err(AppError::validation(format!(
+                    "Unknown notification event: {}",
+                    event
+                )));
+            }
+            if !muted.iter().any(|m| m == event) {
+                muted.push(event.to_string());
+            }
+        }
+        Ok(muted)
+    }
+
+    /// Queue a new notification for each of the recipient's devices, unless
+    /// the recipient turned push off for it. Call inside the transaction that
+    /// creates the notification.
+    pub fn enqueue(
+        conn: &mut PgConnection,
+        notification: &Notification,
+    ) -> Result<usize, diesel::result::Error> {
+        let preferences = PushPreferencesRepo::find(conn, notification.recipient_id)?
+            .map(PushPreferences::from)
+            .unwrap_or_default();
+        if !preferences.allows(&notification.event_type) {
+            return Ok(0);
+        }
+        let subscriptions = PushSubscriptionsRepo::list_by_user(conn, notification.recipient_id)?;
+        if subscriptions.is_empty() {
+            return Ok(0);
+        }
+        let payload = serde_json::to_string(&Self::payload(notification))
+            .map_err(|e| diesel::result::Error::SerializationError(Box::new(e)))?;
+        let deliveries: Vec<NewPushDelivery> = subscriptions
+            .iter()
+            .map(|subscription| NewPushDelivery {
+                subscription_id: subscription.id,
+                notification_id: notification.id,
+                payload: payload.clone(),
+            })
+            .collect();
+        PushDeliveriesRepo::insert_many(conn, &deliveries)
+    }
+
+    pub fn payload(notification: &Notification) -> PushPayload {
+        PushPayload {
+            notification_id: notification.id,
+            workspace_id: notification.workspace_id,
+            event_type: notification.event_type.clone(),
+            entity_type: notification.entity_type.clone(),
+            entity_id: notification.entity_id,
+            title: notification.title.clone(),
+            body: notification.body.clone(),
+        }
+    }
+
+    /// Delay before retrying after `attempts` failed attempts (15s, 30s, 1m, ...)
+    pub fn retry_delay(attempts: i32) -> chrono::Duration {
+        let exponent = (attempts.max(1) - 1).min(10) as u32;
+        chrono::Duration::seconds(RETRY_BASE_SECS * 2_i64.pow(exponent))
+    }
+
+    /// Map a push service response to an outcome
+    pub fn classify(platform: &str, status: u16, body: &str) -> PushOutcome {
+        if (200..300).contains(&status) {
+            return PushOutcome::Delivered;
+        }
+        let error = || {
+            format!(
+                "HTTP {}: {}",
+                status,
+                body.chars().take(200).collect::<String>()
+            )
+        };
+        let gone = match platform {
+            push_platforms::APNS => {
+                status == 410
+                    || (status == 400
+                        && (body.contains("BadDeviceToken")
+                            || body.contains("DeviceTokenNotForTopic")))
+            }
+            push_platforms::FCM => status == 404 || body.contains("UNREGISTERED"),
+            _ => status == 404 || status == 410,
+        };
+        if gone {
+            return PushOutcome::Gone;
+        }
+        // Expired provider credentials are refreshed before the next attempt
+        let auth_expired = match platform {
+            push_platforms::APNS => status == 403 && body.contains("ExpiredProviderToken"),
+            push_platforms::FCM => status == 401,
+            _ => false,
+        };
+        if auth_expired || status == 429 || status >= 500 {
+            PushOutcome::Retry(error())
+        } else {
+            PushOutcome::Rejected(error())
+        }
+    }
+
+    /// Send every due delivery once and record the outcome
+    pub async fn deliver_due(
+        pool: &crate::db::DbPool,
+        gateway: &PushGateway,
+    ) -> Result<usize, AppError> {
+        let due = {
+            let mut conn = pool.get()?;
+            PushDeliveriesRepo::list_due(&mut conn, clock::now(), DELIVERY_BATCH_SIZE)?
+        };
+
+        let mut sent = 0;
+        for delivery in &due {
+            // A subscription removed earlier in the batch took its deliveries with it
+            let subscription = {
+                let mut conn = pool.get()?;
+                PushSubscriptionsRepo::find(&mut conn, delivery.subscription_id)?
+            };
+            let Some(subscription) = subscription else {
+                continue;
+            };
+            let outcome = match serde_json::from_str::<PushPayload>(&delivery.payload) {
+                Ok(payload) => gateway.send(&subscription, &payload).await,
+                Err(e) => PushOutcome::Rejected(format!("Invalid payload: {}", e)),
+            };
+            let mut conn = pool.get()?;
+            Self::record_outcome(&mut conn, delivery, outcome)?;
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    /// Mark a delivery delivered, schedule a retry or give up; a gone device
+    /// loses its subscription. Returns `None` when the delivery went with it.
+    pub fn record_outcome(
+        conn: &mut PgConnection,
+        delivery: &PushDelivery,
+        outcome: PushOutcome,
+    ) -> Result<Option<PushDelivery>, AppError> {
+        let now = clock::now();
+        let attempts = delivery.attempts + 1;
+        let attempt = match outcome {
+            PushOutcome::Delivered => {
+                PushSubscriptionsRepo::touch_delivered(conn, delivery.subscription_id, now)?;
+                PushDeliveryAttempt {
+                    status: push_delivery_status::DELIVERED.to_string(),
+                    attempts,
+                    last_error: None,
+                    next_attempt_at: now,
+                    delivered_at: Some(now),
+                }
+            }
+            PushOutcome::Gone => {
+                PushSubscriptionsRepo::delete(conn, delivery.subscription_id)?;
+                tracing::info!(
+                    "Removed push subscription {} rejected as no longer valid",
+                    delivery.subscription_id
+                );
+                return Ok(None);
+            }
+            PushOutcome::Retry(error) => PushDeliveryAttempt {
+                status: if attempts >= MAX_DELIVERY_ATTEMPTS {
+                    push_delivery_status::FAILED.to_string()
+                } else {
+                    push_delivery_status::PENDING.to_string()
+                },
+                attempts,
+                last_error: Some(error),
+                next_attempt_at: now + Self::retry_delay(attempts),
+                delivered_at: None,
+            },
+            PushOutcome::Rejected(error) => PushDeliveryAttempt {
+                status: push_delivery_status::FAILED.to_string(),
+                attempts,
+                last_error: Some(error),
+                next_attempt_at: now,
+                delivered_at: None,
+            },
+        };
+        Ok(Some(PushDeliveriesRepo::record_attempt(
+            conn,
+            delivery.id,
+            &attempt,
+        )?))
+    }
+
+    /// Drop subscriptions that stopped re-registering and old finished
+    /// deliveries; returns how many of each were removed
+    pub fn cleanup(
+        conn: &mut PgConnection,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(usize, usize), AppError> {
+        let subscriptions = PushSubscriptionsRepo::delete_not_refreshed_since(
+            conn,
+            now - chrono::Duration::days(STALE_SUBSCRIPTION_DAYS),
+        )?;
+        let deliveries = PushDeliveriesRepo::delete_finished_before(
+            conn,
+            now - chrono::Duration::days(DELIVERY_RETENTION_DAYS),
+        )?;
+        Ok((subscriptions, deliveries))
+    }
+}
+
+/// A provider token and when it should be replaced
+type CachedToken = Mutex<Option<(String, chrono::DateTime<chrono::Utc>)>>;
+
+/// Sends payloads to Web Push, APNs and FCM with the configured credentials
+pub struct PushGateway {
+    http: reqwest::Client,
+    config: PushConfig,
+    apns_token: CachedToken,
+    fcm_token: CachedToken,
+}
+
+#[derive(Deserialize)]
+struct GoogleTokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+impl PushGateway {
+    pub fn new(config: PushConfig, http: reqwest::Client) -> Self {
+        Self {
+            http,
+            config,
+            apns_token: Mutex::new(None),
+            fcm_token: Mutex::new(None),
+        }
+    }
+
+    pub async fn send(
+        &self,
+        subscription: &PushSubscription,
+        payload: &PushPayload,
+    ) -> PushOutcome {
+        let platform = subscription.platform.as_str();
+        let result = match platform {
+            push_platforms::WEB => match &self.config.web {
+                Some(web) => self.send_web(web, &subscription.token).await,
+                None => return PushOutcome::Rejected("Web Push is not configured".to_string()),
+            },
+            push_platforms::APNS => match &self.config.apns {
+                Some(apns) => self.send_apns(apns, &subscription.token, payload).await,
+                None => return PushOutcome::Rejected("APNs is not configured".to_string()),
+            },
+            push_platforms::FCM => match &self.config.fcm {
+                Some(fcm) => self.send_fcm(fcm, &subscription.token, payload).await,
+                None => return PushOutcome::Rejected("FCM is not configured".to_string()),
+            },
+            other => return PushOutcome::Rejected(format!("Unknown platform {}", other)),
+        };
+        let (status, body) = match result {
+            Ok(response) => response,
+            Err(e) => return PushOutcome::Retry(e),
+        };
+        if status == 401 || status == 403 {
+            let cache = match platform {
+                push_platforms::APNS => Some(&self.apns_token),
+                push_platforms::FCM => Some(&self.fcm_token),
+                _ => None,
+            };
+            if let Some(cache) = cache {
+                *cache.lock().await = None;
+            }
+        }
+        PushService::classify(platform, status, &body)
+    }
+
+    /// Web Push without a payload: encrypting one needs the subscription's
+    /// keys, so the service worker fetches `/notifications` when woken instead
+    async fn send_web(
+        &self,
+        config: &WebPushConfig,
+        endpoint: &str,
+    ) -> Result<(u16, String), String> {
+        let origin = reqwest::Url::parse(endpoint)
+            .map_err(|e| format!("Invalid endpoint: {}", e))?
+            .origin()
+            .ascii_serialization();
+        let now = clock::now().timestamp();
+        let claims = serde_json::json!({
+            "aud": origin,
+            "exp": now + 12 * 3600,
+            "sub": config.subject,
+        });
+        let key = EncodingKey::from_ec_pem(config.private_key.as_bytes())
+            .map_err(|e| format!("Invalid VAPID key: {}", e))?;
+        let jwt = encode(&Header::new(Algorithm::ES256), &claims, &key)
+            .map_err(|e| format!("Failed to sign VAPID token: {}", e))?;
+
+        let response = self
+            .http
+            .post(endpoint)
+            .timeout(DELIVERY_TIMEOUT)
+            .header("TTL", PUSH_TTL_SECS.to_string())
+            .header("Urgency", "normal")
+            .header(
+                "Authorization",
+                format!("vapid t={}, k={}", jwt, config.public_key),
+            )
+            .header("Content-Length", "0")
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Self::read(response).await)
+    }
+
+    async fn send_apns(
+        &self,
+        config: &ApnsConfig,
+        device_token: &str,
+        payload: &PushPayload,
+    ) -> Result<(u16, String), String> {
+        let token = self
+            .cached(&self.apns_token, || async {
+                let now = clock::now();
+                let mut header = Header::new(Algorithm::ES256);
+                header.kid = Some(config.key_id.clone());
+                let claims = serde_json::json!({ "iss": config.team_id, "iat": now.timestamp() });
+                let key = EncodingKey::from_ec_pem(config.private_key.as_bytes())
+                    .map_err(|e| format!("Invalid APNs key: {}", e))?;
+                let jwt = encode(&header, &claims, &key)
+                    .map_err(|e| format!("Failed to sign APNs token: {}", e))?;
+                Ok((
+                    jwt,
+                    now + chrono::Duration::minutes(APNS_TOKEN_LIFETIME_MINUTES),
+                ))
+            })
+            .await?;
+
+        let host = if config.sandbox {
+            APNS_SANDBOX_HOST
+        } else {
+            APNS_HOST
+        };
+        let body = serde_json::json!({
+            "aps": {
+                "alert": { "title": payload.title, "body": payload.body },
+                "sound": "default",
+                "thread-id": payload.entity_id,
+            },
+            "momentum": payload,
+        });
+        let response = self
+            .http
+            .post(format!("{}/3/device/{}", host, device_token))
+            .timeout(DELIVERY_TIMEOUT)
+            .bearer_auth(token)
+            .header("apns-topic", &config.topic)
+            .header("apns-push-type", "alert")
+            .header(
+                "apns-expiration",
+                (clock::now().timestamp() + PUSH_TTL_SECS).to_string(),
+            )
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Self::read(response).await)
+    }
+
+    async fn send_fcm(
+        &self,
+        config: &FcmConfig,
+        registration_token: &str,
+        payload: &PushPayload,
+    ) -> Result<(u16, String), String> {
+        let token = self
+            .cached(&self.fcm_token, || self.fcm_access_token(config))
+            .await?;
+
+        // FCM data values must be strings
+        let data: HashMap<&str, String> = HashMap::from([
+            ("notification_id", payload.notification_id.to_string()),
+            ("workspace_id", payload.workspace_id.to_string()),
+            ("event_type", payload.event_type.clone()),
+            ("entity_type", payload.entity_type.clone()),
+            ("entity_id", payload.entity_id.to_string()),
+        ]);
+        let body = serde_json::json!({
+            "message": {
+                "token": registration_token,
+                "notification": { "title": payload.title, "body": payload.body },
+                "data": data,
+                "android": { "ttl": format!("{}s", PUSH_TTL_SECS) },
+            }
+        });
+        let response = self
+            .http
+            .post(format!(
+                "https://fcm.googleapis.com/v1/projects/{}/messages:send",
+                config.project_id
+            ))
+            .timeout(DELIVERY_TIMEOUT)
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Self::read(response).await)
+    }
+
+    /// Exchange a signed service account assertion for an OAuth access token
+    async fn fcm_access_token(
+        &self,
+        config: &FcmConfig,
+    ) -> Result<(String, chrono::DateTime<chrono::Utc>), String> {
+        let now = clock::now();
+        let claims = serde_json::json!({
+            "iss": config.client_email,
+            "scope": FCM_SCOPE,
+            "aud": GOOGLE_TOKEN_URL,
+            "iat": now.timestamp(),
+            "exp": now.timestamp() + 3600,
+        });
+        let key = EncodingKey::from_rsa_pem(config.private_key.as_bytes())
+            .map_err(|e| format!("Invalid FCM service account key: {}", e))?;
+        let assertion = encode(&Header::new(Algorithm::RS256), &claims, &key)
+            .map_err(|e| format!("Failed to sign FCM assertion: {}", e))?;
+
+        let response = self
+            .http
+            .post(GOOGLE_TOKEN_URL)
+            .timeout(DELIVERY_TIMEOUT)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "FCM token exchange failed: HTTP {}",
+                response.status()
+            ));
+        }
+        let token: GoogleTokenResponse = response.json().await.map_err(|e| e.to_string())?;
+        // Refresh a minute early so a token never expires mid-request
+        Ok((
+            token.access_token,
+            now + chrono::Duration::seconds(token.expires_in - 60),
+        ))
+    }
+
+    /// The cached token, or a fresh one from `issue` once it is due for replacement
+    async fn cached<F, Fut>(&self, cache: &CachedToken, issue: F) -> Result<String, String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<(String, chrono::DateTime<chrono::Utc>), String>>,
+    {
+        let mut cached = cache.lock().await;
+        if let Some((token, refresh_at)) = cached.as_ref()
+            && *refresh_at > clock::now()
+        {
+            return Ok(token.clone());
+        }
+        let (token, refresh_at) = issue().await?;
+        *cached = Some((token.clone(), refresh_at));
+        Ok(token)
+    }
+
+    async fn read(response: reqwest::Response) -> (u16, String) {
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        (status, body)
+    }
+}
@@ -0,0 +1,89 @@
+use crate::db::after_commit;
+use crate::utils::clock;
+use serde::Serialize;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+use crate::websocket::{
+    EntityAction, EntityChanged, EntityKind, MessageType, Topic, WebSocketManager, WebSocketMessage,
+};
+
+static MANAGER: OnceLock<WebSocketManager> = OnceLock::new();
+
+pub struct RealtimeService;
+
+impl RealtimeService {
+    /// Publish topic events to subscribed WebSocket connections; later calls
+    /// are ignored
+    pub fn install(ws_manager: WebSocketManager) {
+        let _ = MANAGER.set(ws_manager);
+    }
+
+    /// Send `event` to connections in the workspace subscribed to `topic`,
+    /// once the current transaction commits. Does nothing when no manager is
+    /// installed or outside a runtime.
+    pub fn publish<T: Serialize>(workspace_id: Uuid, topic: Topic, event: &str, data: &T) {
+        let Some(ws_manager) = MANAGER.get() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let data = match serde_json::to_value(data) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::warn!("Failed to serialize {} for {}: {}", event, topic, e);
+                return;
+            }
+        };
+        let message = WebSocketMessage {
+            id: Some(clock::new_id().to_string()),
+            message_type: MessageType::TopicEvent,
+            data: serde_json::json!({
+                "topic": topic.key(),
+                "event": event,
+                "data": data,
+            }),
+            timestamp: Some(clock::now()),
+        };
+        let ws_manager = ws_manager.clone();
+        after_commit(move || {
+            runtime.spawn(async move {
+                ws_manager.publish(workspace_id, &topic, message).await;
+            });
+        });
+    }
+
+    /// Tell every connection in the workspace that an entity was created,
+    /// updated or deleted, so clients can patch their caches. `payload` is the
+    /// entity after the change and is left out for deletions. Sent once the
+    /// current transaction commits; does nothing when no manager is installed
+    /// or outside a runtime.
+    pub fn entity_changed<T: Serialize>(
+        workspace_id: Uuid,
+        entity: EntityKind,
+        action: EntityAction,
+        id: Uuid,
+        payload: &T,
+    ) {
+        let Some(ws_manager) = MANAGER.get() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let change = match EntityChanged::new(entity, action, id, payload) {
+            Ok(change) => change,
+            Err(e) => {
+                tracing::warn!("Failed to serialize {:?} {}: {}", entity, id, e);
+                return;
+            }
+        };
+        let ws_manager = ws_manager.clone();
+        after_commit(move || {
+            runtime.spawn(async move {
+                ws_manager.entity_changed(workspace_id, &change).await;
+            });
+        });
+    }
+}
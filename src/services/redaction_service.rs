@@ -0,0 +1,175 @@
+use diesel::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    db::models::workspace_member::WorkspaceMemberRole, error::AppError,
+    services::context::RequestContext, services::permission_service::PermissionService,
+};
+
+/// How a sensitive field is hidden from callers below its minimum role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mask {
+    /// Keep the domain and the first character, e.g. `j***@example.com`
+    Email,
+    /// Replace the value with `null`
+    Remove,
+}
+
+/// A JSON field that only callers with at least `minimum_role` may see
+#[derive(Debug, Clone)]
+pub struct SensitiveField {
+    pub name: &'static str,
+    pub minimum_role: WorkspaceMemberRole,
+    pub mask: Mask,
+}
+
+/// Fields redacted wherever they appear in a payload, matched by key
+pub const SENSITIVE_FIELDS: &[SensitiveField] = &[
+    SensitiveField {
+        name: "email",
+        minimum_role: WorkspaceMemberRole::Member,
+        mask: Mask::Email,
+    },
+    SensitiveField {
+        name: "invitation_token",
+        minimum_role: WorkspaceMemberRole::Admin,
+        mask: Mask::Remove,
+    },
+    SensitiveField {
+        name: "invite_link",
+        minimum_role: WorkspaceMemberRole::Admin,
+        mask: Mask::Remove,
+    },
+    SensitiveField {
+        name: "billing_email",
+        minimum_role: WorkspaceMemberRole::Owner,
+        mask: Mask::Email,
+    },
+    SensitiveField {
+        name: "billing_address",
+        minimum_role: WorkspaceMemberRole::Owner,
+        mask: Mask::Remove,
+    },
+    SensitiveField {
+        name: "tax_id",
+        minimum_role: WorkspaceMemberRole::Owner,
+        mask: Mask::Remove,
+    },
+    SensitiveField {
+        name: "card_last4",
+        minimum_role: WorkspaceMemberRole::Owner,
+        mask: Mask::Remove,
+    },
+];
+
+/// Who a payload is being serialized for
+#[derive(Debug, Clone)]
+pub struct Viewer {
+    pub user_id: Uuid,
+    /// The viewer's own email, never redacted
+    pub email: Option<String>,
+    /// Role in the workspace the payload belongs to; `None` when not a
+    /// member, which hides every sensitive field
+    pub role: Option<WorkspaceMemberRole>,
+}
+
+impl Viewer {
+    fn can_see(&self, field: &SensitiveField) -> bool {
+        self.role
+            .as_ref()
+            .is_some_and(|role| PermissionService::role_meets(role, &field.minimum_role))
+    }
+}
+
+pub struct RedactionService;
+
+impl RedactionService {
+    /// The caller as a viewer of the current workspace's data
+    pub fn viewer(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        email: Option<String>,
+    ) -> Result<Viewer, AppError> {
+        Ok(Viewer {
+            user_id: ctx.user_id,
+            email,
+            role: PermissionService::current_role(conn, ctx)?,
+        })
+    }
+
+    /// Whether serialized `bytes` might contain a sensitive field, to skip
+    /// parsing payloads that cannot need redaction
+    pub fn may_contain_sensitive(bytes: &[u8]) -> bool {
+        SENSITIVE_FIELDS.iter().any(|field| {
+            let key = format!("\"{}\"", field.name);
+            bytes
+                .windows(key.len())
+                .any(|window| window == key.as_bytes())
+        })
+    }
+
+    /// Serialize `data` with the fields `viewer` may not see masked; used for
+    /// exports and anything else sent outside REST and WebSocket responses
+    pub fn redact<T: Serialize>(data: &T, viewer: &Viewer) -> Result<Value, AppError> {
+        let mut value = serde_json::to_value(data)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize payload: {}", e)))?;
+        Self::redact_value(&mut value, viewer);
+        Ok(value)
+    }
+
+    /// Mask sensitive fields in place. Objects describing the viewer (an
+    /// `id` or `user_id` equal to theirs) and the viewer's own email are
+    /// left alone.
+    pub fn redact_value(value: &mut Value, viewer: &Viewer) {
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    Self::redact_value(item, viewer);
+                }
+            }
+            Value::Object(map) => {
+                let describes_viewer = ["id", "user_id"].iter().any(|key| {
+                    map.get(*key)
+                        .and_then(Value::as_str)
+                        .and_then(|id| Uuid::parse_str(id).ok())
+                        == Some(viewer.user_id)
+                });
+                for (key, field_value) in map.iter_mut() {
+                    match SENSITIVE_FIELDS.iter().find(|field| field.name == key) {
+                        Some(field) if !field_value.is_null() => {
+                            if describes_viewer
+                                || viewer.can_see(field)
+                                || (field.mask == Mask::Email
+                                    && field_value.as_str().is_some()
+                                    && field_value.as_str() == viewer.email.as_deref())
+                            {
+                                continue;
+                            }
+                            *field_value = match (field.mask, field_value.as_str()) {
+                                (Mask::Email, Some(email)) => {
+                                    Value::String(Self::mask_email(email))
+                                }
+                                _ => Value::Null,
+                            };
+                        }
+                        _ => Self::redact_value(field_value, viewer),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `jane@example.com` becomes `j***@example.com`
+    pub fn mask_email(email: &str) -> String {
+        match email.split_once('@') {
+            Some((local, domain)) => {
+                let first: String = local.chars().take(1).collect();
+                format!("{}***@{}", first, domain)
+            }
+            None => "***".to_string(),
+        }
+    }
+}
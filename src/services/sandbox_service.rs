@@ -0,0 +1,187 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::workspace_import::ImportBundle,
+    db::models::workspace_member::WorkspaceMemberRole,
+    db::models::workspace_reset::{
+        NewWorkspaceReset, SandboxResetReport, WorkspaceReset, WorkspaceResetRequest,
+        WorkspaceResetResponse,
+    },
+    db::repositories::workspace_members::WorkspaceMembersRepo,
+    db::repositories::workspace_resets::WorkspaceResetsRepo,
+    db::repositories::workspaces::WorkspacesRepo,
+    error::AppError,
+    services::context::{AuthChannel, RequestContext},
+    services::import_service::ImportService,
+    services::member_imports_service::JOB_QUEUE,
+    utils::clock,
+};
+
+/// Job name prefix; the task is `workspace_reset:<reset id>`
+pub const JOB_PREFIX: &str = "workspace_reset:";
+
+/// Source recorded for seeded records in `import_external_ids`
+pub const SANDBOX_SOURCE: &str = "sandbox";
+
+/// Fixture profiles by name, in the Linear export format
+pub const PROFILES: &[(&str, &str)] = &[
+    ("demo", include_str!("../../fixtures/sandbox/demo.json")),
+    (
+        "starter",
+        include_str!("../../fixtures/sandbox/starter.json"),
+    ),
+];
+
+/// Resets demo and trial workspaces: everything the workspace's members
+/// created is deleted and replaced with the records of a fixture profile.
+/// The wipe and the reseed run in one transaction, so a failed reset leaves
+/// the workspace as it was.
+pub struct SandboxService;
+
+impl SandboxService {
+    pub fn profile_names() -> Vec<&'static str> {
+        PROFILES.iter().map(|(name, _)| *name).collect()
+    }
+
+    pub fn load_profile(name: &str) -> Result<ImportBundle, AppError> {
+        let (_, content) = PROFILES
+            .iter()
+            .find(|(profile, _)| *profile == name)
+            .ok_or_else(|| {
+                AppError::validation(format!(
+                    "Unknown sandbox profile '{}'; available profiles: {}",
+                    name,
+                    Self::profile_names().join(", ")
+                ))
+            })?;
+        ImportService::parse_linear(content)
+    }
+
+    /// Record a reset of the workspace; at most one can be pending or running
+    pub fn request(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        requested_by: Uuid,
+        req: &WorkspaceResetRequest,
+    ) -> Result<WorkspaceResetResponse, AppError> {
+        let profile = req.profile.trim().to_ascii_lowercase();
+        Self::load_profile(&profile)?;
+        if WorkspacesRepo::find_by_id(conn, workspace_id)?.is_none() {
+            return Err(AppError::not_found("workspace"));
+        }
+        if WorkspaceResetsRepo::find_active(conn, workspace_id)?.is_some() {
+            return Err(AppError::conflict_with_code(
+                "A reset of this workspace is already in progress",
+                None,
+                "RESET_IN_PROGRESS",
+            ));
+        }
+
+        let reset = WorkspaceResetsRepo::insert(
+            conn,
+            &NewWorkspaceReset {
+                workspace_id,
+                requested_by,
+                profile,
+            },
+        )?;
+        Ok(Self::to_response(reset))
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        reset_id: Uuid,
+    ) -> Result<WorkspaceResetResponse, AppError> {
+        let reset = WorkspaceResetsRepo::find_in_workspace(conn, workspace_id, reset_id)?
+            .ok_or_else(|| AppError::not_found("workspace reset"))?;
+        Ok(Self::to_response(reset))
+    }
+
+    fn to_response(reset: WorkspaceReset) -> WorkspaceResetResponse {
+        let report = reset
+            .result
+            .as_deref()
+            .and_then(|r| serde_json::from_str(r).ok());
+        WorkspaceResetResponse { reset, report }
+    }
+
+    /// Push the reset onto the worker queue
+    pub async fn enqueue(redis: &redis::Client, reset_id: Uuid) -> redis::RedisResult<()> {
+        use redis::AsyncCommands;
+        let mut conn = redis.get_multiplexed_async_connection().await?;
+        conn.rpush(JOB_QUEUE, format!("{}{}", JOB_PREFIX, reset_id))
+            .await
+    }
+
+    /// Reset id of a queued job, if the task is a workspace reset
+    pub fn parse_job(task: &str) -> Option<Uuid> {
+        task.strip_prefix(JOB_PREFIX)?.parse().ok()
+    }
+
+    /// Pending resets older than `age`, for the worker to pick up
+    pub fn stale_pending(
+        conn: &mut PgConnection,
+        age: chrono::Duration,
+    ) -> Result<Vec<Uuid>, AppError> {
+        Ok(WorkspaceResetsRepo::list_stale_pending(
+            conn,
+            clock::now() - age,
+        )?)
+    }
+
+    /// Run a pending reset. Returns `None` when the reset is not pending, so
+    /// duplicate jobs are harmless. A failed reset is rolled back and marked
+    /// failed with the error.
+    pub fn run(
+        conn: &mut PgConnection,
+        reset_id: Uuid,
+    ) -> Result<Option<SandboxResetReport>, AppError> {
+        let Some(reset) = WorkspaceResetsRepo::claim(conn, reset_id)? else {
+            return Ok(None);
+        };
+
+        match Self::reseed(conn, &reset) {
+            Ok(report) => Ok(Some(report)),
+            Err(err) => {
+                WorkspaceResetsRepo::fail(conn, reset.id, &err.to_string())?;
+                Err(err)
+            }
+        }
+    }
+
+    fn reseed(
+        conn: &mut PgConnection,
+        reset: &WorkspaceReset,
+    ) -> Result<SandboxResetReport, AppError> {
+        let bundle = Self::load_profile(&reset.profile)?;
+        // Seeded records are created by the workspace's first owner; the
+        // requesting admin need not be a member
+        let owner = WorkspaceMembersRepo::list_by_workspace(conn, reset.workspace_id)?
+            .into_iter()
+            .filter(|m| m.role == WorkspaceMemberRole::Owner)
+            .min_by_key(|m| m.created_at)
+            .ok_or_else(|| AppError::validation("Workspace has no owner to seed content as"))?;
+        let ctx = RequestContext {
+            user_id: owner.user_id,
+            workspace_id: reset.workspace_id,
+            idempotency_key: None,
+            channel: AuthChannel::Session,
+        };
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let removed = WorkspaceResetsRepo::delete_content(conn, reset.workspace_id)?;
+            let imported = ImportService::apply(conn, &ctx, SANDBOX_SOURCE, &bundle)?;
+            let report = SandboxResetReport {
+                removed,
+                seeded: imported.created,
+                conflicts: imported.conflicts,
+            };
+            let result = serde_json::to_string(&report)
+                .map_err(|e| AppError::internal(format!("Failed to store reset report: {}", e)))?;
+            WorkspaceResetsRepo::complete(conn, reset.id, &result)?;
+            Ok(report)
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+
+use crate::{
+    db::models::search::{IssueSearchFilters, IssueSearchHit},
+    db::repositories::issues::IssueRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::issues_service::IssuesService,
+};
+
+/// Results returned when the caller does not ask for a limit
+pub const DEFAULT_SEARCH_LIMIT: i64 = 20;
+
+/// Upper bound on results per search
+pub const MAX_SEARCH_LIMIT: i64 = 100;
+
+/// Longest accepted query, in characters
+pub const MAX_QUERY_LENGTH: usize = 256;
+
+pub struct SearchService;
+
+impl SearchService {
+    /// Trimmed query, rejecting empty or oversized input
+    pub fn normalize_query(query: &str) -> Result<&str, AppError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(AppError::validation("Search query must not be empty"));
+        }
+        if query.chars().count() > MAX_QUERY_LENGTH {
+            return Err(AppError::validation(format!(
+                "Search query must be at most {} characters",
+                MAX_QUERY_LENGTH
+            )));
+        }
+        Ok(query)
+    }
+
+    pub fn clamp_limit(limit: Option<i64>) -> i64 {
+        limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT)
+    }
+
+    /// Full-text search over issue titles and descriptions in the current
+    /// workspace, ranked by relevance with title matches weighted highest
+    pub fn search_issues(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        query: &str,
+        filters: &IssueSearchFilters,
+        limit: Option<i64>,
+    ) -> Result<Vec<IssueSearchHit>, AppError> {
+        let query = Self::normalize_query(query)?;
+        if let Some(priority) = &filters.priority {
+            IssuesService::parse_priority(priority)?;
+        }
+
+        let ranked = IssueRepo::search_full_text(
+            conn,
+            ctx.workspace_id,
+            query,
+            filters,
+            Self::clamp_limit(limit),
+        )?;
+        if ranked.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<uuid::Uuid> = ranked.iter().map(|(id, _)| *id).collect();
+        let mut by_id: HashMap<uuid::Uuid, _> = IssueRepo::list_by_ids(conn, &ids)?
+            .into_iter()
+            .map(|issue| (issue.id, issue))
+            .collect();
+        let issues = ids.iter().filter_map(|id| by_id.remove(id)).collect();
+
+        let ranks: HashMap<uuid::Uuid, f32> = ranked.into_iter().collect();
+        Ok(IssuesService::to_responses(conn, ctx, issues)?
+            .into_iter()
+            .map(|issue| IssueSearchHit {
+                rank: ranks.get(&issue.id).copied().unwrap_or_default(),
+                issue,
+            })
+            .collect())
+    }
+}
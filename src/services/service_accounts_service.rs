@@ -0,0 +1,355 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::api_token::{ApiToken, CreateApiTokenRequest, CreatedApiToken},
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::auth::{NewUser, User},
+    db::models::service_account::{
+        CreateServiceAccountRequest, NewServiceAccount, ServiceAccount, ServiceAccountRecord,
+    },
+    db::models::workspace_member::{
+        NewWorkspaceMember, WorkspaceMemberRole, member_change::MEMBER_ADDED,
+    },
+    db::repositories::api_tokens::ApiTokensRepo,
+    db::repositories::audit_log::AuditLogRepo,
+    db::repositories::auth::AuthRepo,
+    db::repositories::service_accounts::ServiceAccountsRepo,
+    db::repositories::workspace_members::WorkspaceMembersRepo,
+    error::AppError,
+    services::api_keys_service::ApiKeysService,
+    services::api_tokens_service::ApiTokensService,
+    services::context::RequestContext,
+    services::oauth_login_service::OAuthLoginService,
+    services::permission_service::{Permission, PermissionService},
+    services::workspace_members_service::WorkspaceMembersService,
+    utils::clock,
+};
+
+/// Domain of the placeholder emails service accounts are created with; the
+/// `.invalid` TLD can never receive mail or match a verified login email
+pub const SERVICE_ACCOUNT_EMAIL_DOMAIN: &str = "service-accounts.invalid";
+
+const MAX_USERNAME_CHARS: usize = 30;
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+/// Machine users of a workspace for integrations and automation pipelines.
+/// They have no credentials, cannot sign in and act only through scoped API
+/// tokens, with the workspace role they were given. What they do is
+/// attributed to their own user rather than to the admin who set them up.
+pub struct ServiceAccountsService;
+
+impl ServiceAccountsService {
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<ServiceAccount>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        Ok(
+            ServiceAccountsRepo::list_with_users(conn, ctx.workspace_id, None)?
+                .into_iter()
+                .map(|(record, user, role)| Self::to_response(record, user, role))
+                .collect(),
+        )
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &CreateServiceAccountRequest,
+    ) -> Result<ServiceAccount, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        let name = req.name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(AppError::validation(
+                "Service account name must be between 1 and 100 characters",
+            ));
+        }
+        let description = req
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty());
+        if description.is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_CHARS) {
+            return Err(AppError::validation(format!(
+                "description must be at most {} characters",
+                MAX_DESCRIPTION_CHARS
+            )));
+        }
+        let role = req.role.clone().unwrap_or(WorkspaceMemberRole::Member);
+        if role == WorkspaceMemberRole::Owner {
+            return Err(AppError::validation(
+                "A service account cannot own a workspace",
+            ));
+        }
+        let username = match req.username.as_deref() {
+            Some(username) => {
+                let username = Self::validate_username(username)?;
+                if AuthRepo::exists_by_username(conn, &username)? {
+                    return Err(AppError::conflict_with_code(
+                        "Username is already taken",
+                        Some("username".to_string()),
+                        "USERNAME_TAKEN",
+                    ));
+                }
+                username
+            }
+            None => OAuthLoginService::unique_username(conn, &Self::base_username(name))?,
+        };
+
+        let user = AuthRepo::insert_user(
+            conn,
+            &NewUser {
+                email: format!("{}@{}", username, SERVICE_ACCOUNT_EMAIL_DOMAIN),
+                username,
+                name: name.to_string(),
+                avatar_url: None,
+            },
+        )?;
+        let user = {
+            use crate::schema::users::dsl as u;
+            diesel::update(u::users.filter(u::id.eq(user.id)))
+                .set((
+                    u::is_service_account.eq(true),
+                    u::current_workspace_id.eq(Some(ctx.workspace_id)),
+                ))
+                .returning(User::as_returning())
+                .get_result(conn)?
+        };
+        let record = ServiceAccountsRepo::insert(
+            conn,
+            &NewServiceAccount {
+                user_id: user.id,
+                workspace_id: ctx.workspace_id,
+                description: description.map(str::to_string),
+                created_by: Some(ctx.user_id),
+            },
+        )?;
+        let member = WorkspaceMembersRepo::insert(
+            conn,
+            &NewWorkspaceMember {
+                user_id: user.id,
+                workspace_id: ctx.workspace_id,
+                role,
+            },
+        )?;
+        WorkspaceMembersService::publish_change(&member, MEMBER_ADDED);
+        Self::audit(
+            conn,
+            ctx,
+            audit_actions::SERVICE_ACCOUNT_CREATED,
+            user.id,
+            serde_json::json!({
+                "name": user.name,
+                "username": user.username,
+                "role": member.role,
+            }),
+        )?;
+        Ok(Self::to_response(record, user, Some(member.role)))
+    }
+
+    /// Disable an account for good: its tokens are revoked and its user
+    /// deactivated, while what it did stays attributed to it
+    pub fn disable(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        account_id: Uuid,
+    ) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        Self::find(conn, ctx, account_id)?;
+        let now = clock::now();
+        if ServiceAccountsRepo::disable(conn, account_id, now)? > 0 {
+            let revoked = ApiTokensRepo::revoke_all_for_user(conn, account_id, now)?;
+            Self::audit(
+                conn,
+                ctx,
+                audit_actions::SERVICE_ACCOUNT_DISABLED,
+                account_id,
+                serde_json::json!({ "revoked_tokens": revoked }),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn list_tokens(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        account_id: Uuid,
+    ) -> Result<Vec<ApiToken>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        Self::find(conn, ctx, account_id)?;
+        Ok(ApiTokensRepo::list_by_user(conn, account_id)?)
+    }
+
+    /// Issue an API token for the account; unlike personal tokens it must
+    /// name its scopes
+    pub fn create_token(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        account_id: Uuid,
+        req: &CreateApiTokenRequest,
+    ) -> Result<CreatedApiToken, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        Self::require_active(conn, ctx, account_id)?;
+        let (name, expires_at) = ApiTokensService::validate_request(req)?;
+        let scopes = ApiKeysService::validate_scopes(req.scopes.as_deref().unwrap_or_default())?;
+        let created =
+            ApiTokensService::issue(conn, account_id, name, expires_at, Some(scopes.clone()))?;
+        Self::audit(
+            conn,
+            ctx,
+            audit_actions::SERVICE_ACCOUNT_TOKEN_CREATED,
+            account_id,
+            serde_json::json!({
+                "token_id": created.api_token.id,
+                "token_prefix": created.api_token.token_prefix,
+                "scopes": scopes,
+            }),
+        )?;
+        Ok(created)
+    }
+
+    pub fn revoke_token(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        account_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageApiAccess)?;
+        Self::find(conn, ctx, account_id)?;
+        let token = ApiTokensRepo::find_for_user(conn, account_id, token_id)?
+            .ok_or_else(|| AppError::not_found("api_token"))?;
+        if ApiTokensRepo::revoke(conn, token_id, clock::now())? > 0 {
+            Self::audit(
+                conn,
+                ctx,
+                audit_actions::SERVICE_ACCOUNT_TOKEN_REVOKED,
+                account_id,
+                serde_json::json!({
+                    "token_id": token.id,
+                    "token_prefix": token.token_prefix,
+                }),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Workspace a service account's tokens are pinned to; `None` for
+    /// regular users
+    pub fn workspace_of(conn: &mut PgConnection, user_id: Uuid) -> Result<Option<Uuid>, AppError> {
+        match ServiceAccountsRepo::find(conn, user_id)? {
+            Some(account) if account.disabled_at.is_some() => {
+                Err(AppError::auth("Service account is disabled"))
+            }
+            Some(account) => Ok(Some(account.workspace_id)),
+            None => Ok(None),
+        }
+    }
+
+    /// Check that `user_id` is an active service account of the workspace,
+    /// e.g. before making it the creator of an integration's issues
+    pub fn require_active(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        let account = Self::find(conn, ctx, user_id)?;
+        if account.disabled_at.is_some() {
+            return Err(AppError::conflict_with_code(
+                "Service account is disabled",
+                None,
+                "SERVICE_ACCOUNT_DISABLED",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Usernames are 3 to 30 letters, digits and underscores
+    pub fn validate_username(username: &str) -> Result<String, AppError> {
+        let username = username.trim();
+        let length = username.chars().count();
+        if !(3..=MAX_USERNAME_CHARS).contains(&length)
+            || !username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(AppError::validation(format!(
+                "username must be 3 to {} letters, digits or underscores",
+                MAX_USERNAME_CHARS
+            )));
+        }
+        Ok(username.to_lowercase())
+    }
+
+    /// Username derived from the account name, ending in `bot`
+    pub fn base_username(name: &str) -> String {
+        let words: Vec<String> = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        let mut username: String = words
+            .join("_")
+            .chars()
+            .take(MAX_USERNAME_CHARS - 4)
+            .collect();
+        username.truncate(username.trim_end_matches('_').len());
+        if !username.ends_with("bot") {
+            if !username.is_empty() {
+                username.push('_');
+            }
+            username.push_str("bot");
+        }
+        username
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        account_id: Uuid,
+    ) -> Result<ServiceAccountRecord, AppError> {
+        ServiceAccountsRepo::find(conn, account_id)?
+            .filter(|account| account.workspace_id == ctx.workspace_id)
+            .ok_or_else(|| AppError::not_found("service_account"))
+    }
+
+    fn to_response(
+        record: ServiceAccountRecord,
+        user: User,
+        role: Option<WorkspaceMemberRole>,
+    ) -> ServiceAccount {
+        ServiceAccount {
+            id: record.user_id,
+            workspace_id: record.workspace_id,
+            name: user.name,
+            username: user.username,
+            avatar_url: user.avatar_url,
+            description: record.description,
+            role,
+            created_by: record.created_by,
+            disabled_at: record.disabled_at,
+            created_at: record.created_at,
+        }
+    }
+
+    fn audit(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        action: &str,
+        account_id: Uuid,
+        details: serde_json::Value,
+    ) -> Result<(), AppError> {
+        AuditLogRepo::insert(
+            conn,
+            &NewAuditEntry {
+                workspace_id: ctx.workspace_id,
+                actor_id: Some(ctx.user_id),
+                action: action.to_string(),
+                target_type: "service_account".to_string(),
+                target_id: Some(account_id),
+                details: Some(details.to_string()),
+            },
+        )?;
+        Ok(())
+    }
+}
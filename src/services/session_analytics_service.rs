@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::webhook_events,
+    db::models::websocket_session::{
+        ClientUsage, DailySessions, NewWebSocketSession, SessionAnalytics, UserSessionUsage,
+        WebSocketSession, session_clients,
+    },
+    db::repositories::websocket_sessions::WebSocketSessionsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::permission_service::{Permission, PermissionService},
+    services::webhook_service::WebhookService,
+    utils::clock,
+};
+
+/// Longest window that can be requested, in days
+pub const MAX_SESSION_DAYS: i64 = 90;
+/// Open sessions older than this are assumed to have been cut off by a
+/// server restart; they count as ended after this long
+pub const MAX_OPEN_SESSION_HOURS: i64 = 24;
+/// Longest client name kept from the `client` query parameter
+const MAX_CLIENT_NAME_CHARS: usize = 50;
+
+pub struct SessionAnalyticsService;
+
+impl SessionAnalyticsService {
+    /// Name of the connecting client: the one it declared, if usable,
+    /// otherwise a kind inferred from its User-Agent
+    pub fn client_name(declared: Option<&str>, user_agent: Option<&str>) -> String {
+        if let Some(name) = declared.map(str::trim).filter(|name| {
+            !name.is_empty()
+                && name.chars().count() <= MAX_CLIENT_NAME_CHARS
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        }) {
+            return name.to_ascii_lowercase();
+        }
+
+        let kind = match user_agent {
+            Some(ua) if ua.contains("Electron") => session_clients::DESKTOP,
+            Some(ua)
+                if ["Android", "iPhone", "iPad", "Mobile"]
+                    .iter()
+                    .any(|marker| ua.contains(marker)) =>
+            {
+                session_clients::MOBILE
+            }
+            Some(ua) if ua.starts_with("Mozilla/") => session_clients::WEB,
+            _ => session_clients::UNKNOWN,
+        };
+        kind.to_string()
+    }
+
+    /// Record a new connection and emit `session.started`
+    pub fn start(
+        conn: &mut PgConnection,
+        new: NewWebSocketSession,
+    ) -> Result<WebSocketSession, AppError> {
+        let session = WebSocketSessionsRepo::insert(conn, &new)?;
+        WebhookService::emit_quietly(
+            conn,
+            session.workspace_id,
+            webhook_events::SESSION_STARTED,
+            &Self::event_data(&session, None),
+        );
+        Ok(session)
+    }
+
+    /// Close the session and emit `session.ended` with its duration
+    pub fn end(
+        conn: &mut PgConnection,
+        session_id: Uuid,
+    ) -> Result<Option<WebSocketSession>, AppError> {
+        let now = clock::now();
+        let Some(session) = WebSocketSessionsRepo::close(conn, session_id, now)? else {
+            return Ok(None);
+        };
+        WebhookService::emit_quietly(
+            conn,
+            session.workspace_id,
+            webhook_events::SESSION_ENDED,
+            &Self::event_data(&session, Some(session.duration_seconds(now))),
+        );
+        Ok(Some(session))
+    }
+
+    /// Webhook data for a session event
+    pub fn event_data(
+        session: &WebSocketSession,
+        duration_seconds: Option<i64>,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "session_id": session.id,
+            "user_id": session.user_id,
+            "client": session.client,
+            "user_agent": session.user_agent,
+            "connected_at": session.connected_at,
+            "disconnected_at": session.disconnected_at,
+            "duration_seconds": duration_seconds,
+        })
+    }
+
+    /// Realtime usage of the current workspace over the last `days` days
+    pub fn report(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        days: i64,
+    ) -> Result<SessionAnalytics, AppError> {
+        PermissionService::require(conn, ctx, Permission::ViewAnalytics)?;
+        if !(1..=MAX_SESSION_DAYS).contains(&days) {
+            return Err(AppError::validation(format!(
+                "days must be between 1 and {}",
+                MAX_SESSION_DAYS
+            )));
+        }
+        let now = clock::now();
+        let to = now.date_naive();
+        let from = to - Duration::days(days - 1);
+        let since = from.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        let sessions = WebSocketSessionsRepo::list_since(conn, ctx.workspace_id, since)?;
+        Ok(Self::summarize(ctx.workspace_id, from, to, now, &sessions))
+    }
+
+    /// Roll sessions up into totals and per-client, per-hour, per-day and
+    /// per-user series
+    pub fn summarize(
+        workspace_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        now: DateTime<Utc>,
+        sessions: &[WebSocketSession],
+    ) -> SessionAnalytics {
+        let stale_before = now - Duration::hours(MAX_OPEN_SESSION_HOURS);
+        let mut active_sessions = 0;
+        let mut total_duration_seconds = 0;
+        let mut sessions_by_hour = vec![0; 24];
+        let mut clients: BTreeMap<&str, (i64, HashSet<Uuid>)> = BTreeMap::new();
+        let mut daily: BTreeMap<NaiveDate, (i64, HashSet<Uuid>)> = BTreeMap::new();
+        let mut users: HashMap<Uuid, UserSessionUsage> = HashMap::new();
+
+        for session in sessions {
+            let duration = if session.disconnected_at.is_none() {
+                if session.connected_at >= stale_before {
+                    active_sessions += 1;
+                    session.duration_seconds(now)
+                } else {
+                    MAX_OPEN_SESSION_HOURS * 3600
+                }
+            } else {
+                session.duration_seconds(now)
+            };
+            total_duration_seconds += duration;
+            sessions_by_hour[session.connected_at.hour() as usize] += 1;
+
+            let client = clients.entry(session.client.as_str()).or_default();
+            client.0 += 1;
+            client.1.insert(session.user_id);
+
+            let day = daily.entry(session.connected_at.date_naive()).or_default();
+            day.0 += 1;
+            day.1.insert(session.user_id);
+
+            let user = users
+                .entry(session.user_id)
+                .or_insert_with(|| UserSessionUsage {
+                    user_id: session.user_id,
+                    sessions: 0,
+                    total_duration_seconds: 0,
+                    last_connected_at: session.connected_at,
+                });
+            user.sessions += 1;
+            user.total_duration_seconds += duration;
+            user.last_connected_at = user.last_connected_at.max(session.connected_at);
+        }
+
+        let total_sessions = sessions.len() as i64;
+        let mut clients: Vec<ClientUsage> = clients
+            .into_iter()
+            .map(|(client, (sessions, users))| ClientUsage {
+                client: client.to_string(),
+                sessions,
+                users: users.len() as i64,
+            })
+            .collect();
+        clients.sort_by_key(|c| std::cmp::Reverse(c.sessions));
+        let mut users: Vec<UserSessionUsage> = users.into_values().collect();
+        users.sort_by(|a, b| {
+            b.total_duration_seconds
+                .cmp(&a.total_duration_seconds)
+                .then(a.user_id.cmp(&b.user_id))
+        });
+
+        SessionAnalytics {
+            workspace_id,
+            from,
+            to,
+            total_sessions,
+            unique_users: users.len() as i64,
+            active_sessions,
+            total_duration_seconds,
+            average_duration_seconds: if total_sessions == 0 {
+                0.0
+            } else {
+                total_duration_seconds as f64 / total_sessions as f64
+            },
+            sessions_by_hour,
+            clients,
+            daily: daily
+                .into_iter()
+                .map(|(day, (sessions, users))| DailySessions {
+                    day,
+                    sessions,
+                    active_users: users.len() as i64,
+                })
+                .collect(),
+            users,
+        }
+    }
+}
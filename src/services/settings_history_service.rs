@@ -0,0 +1,169 @@
+use std::collections::BTreeSet;
+
+use diesel::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{
+        AuditEntry, NewAuditEntry, SettingChange, SettingsHistoryEntry, SettingsHistoryQuery,
+        actor_types, settings_targets,
+    },
+    db::repositories::audit_log::{AuditLogFilter, AuditLogRepo},
+    db::repositories::service_accounts::ServiceAccountsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::permission_service::{Permission, PermissionService},
+};
+
+/// Default and maximum page size for `GET /settings/history`
+const DEFAULT_LIST_LIMIT: i64 = 50;
+const MAX_LIST_LIMIT: i64 = 100;
+
+/// Fields that change on every write or never change, left out of diffs
+const UNTRACKED_FIELDS: [&str; 3] = ["id", "created_at", "updated_at"];
+
+/// Changes to workspace, team and workflow settings, kept in the audit log
+/// as field-level diffs
+pub struct SettingsHistoryService;
+
+impl SettingsHistoryService {
+    /// Record who changed which fields of a setting; `before` is `None` for
+    /// created settings and `after` for deleted ones. Writes that change
+    /// nothing are not recorded.
+    pub fn record<T: Serialize>(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        action: &str,
+        target_type: &str,
+        target_id: Uuid,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Result<(), AppError> {
+        let to_value = |value: Option<&T>| {
+            value
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(|e| AppError::internal(format!("Failed to serialize settings: {}", e)))
+        };
+        let changes = Self::diff(to_value(before)?.as_ref(), to_value(after)?.as_ref());
+        if changes.is_empty() {
+            return Ok(());
+        }
+        AuditLogRepo::insert(
+            conn,
+            &NewAuditEntry {
+                workspace_id: ctx.workspace_id,
+                actor_id: Some(ctx.user_id),
+                action: action.to_string(),
+                target_type: target_type.to_string(),
+                target_id: Some(target_id),
+                details: Some(serde_json::json!({ "changes": changes }).to_string()),
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Fields whose values differ between two serialized settings, ordered
+    /// by field name
+    pub fn diff(before: Option<&Value>, after: Option<&Value>) -> Vec<SettingChange> {
+        let field = |value: Option<&Value>, name: &str| {
+            value
+                .and_then(|value| value.get(name))
+                .cloned()
+                .unwrap_or(Value::Null)
+        };
+        let names: BTreeSet<&String> = [before, after]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object)
+            .flat_map(|object| object.keys())
+            .filter(|name| !UNTRACKED_FIELDS.contains(&name.as_str()))
+            .collect();
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let (from, to) = (field(before, name), field(after, name));
+                (from != to).then(|| SettingChange {
+                    field: name.clone(),
+                    from,
+                    to,
+                })
+            })
+            .collect()
+    }
+
+    /// Settings changes in the workspace, newest first
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        query: &SettingsHistoryQuery,
+    ) -> Result<Vec<SettingsHistoryEntry>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ViewAuditLog)?;
+        let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+        if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+            return Err(AppError::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_LIST_LIMIT
+            )));
+        }
+        let target_types: Vec<&str> = match query.target_type.as_deref() {
+            None => settings_targets::ALL.to_vec(),
+            Some(target_type) => match settings_targets::ALL
+                .into_iter()
+                .find(|known| *known == target_type)
+            {
+                Some(target_type) => vec![target_type],
+                None => {
+                    return Err(AppError::validation(format!(
+                        "target_type must be one of {}",
+                        settings_targets::ALL.join(", ")
+                    )));
+                }
+            },
+        };
+
+        let filter = AuditLogFilter {
+            target_types: &target_types,
+            target_id: query.target_id,
+            actor_id: query.actor_id,
+            since: query.since,
+            before: query.before,
+        };
+        let entries = AuditLogRepo::list(conn, ctx.workspace_id, &filter, limit)?;
+        let mut actors: Vec<Uuid> = entries.iter().filter_map(|e| e.actor_id).collect();
+        actors.sort();
+        actors.dedup();
+        let service_accounts = ServiceAccountsRepo::filter_service_accounts(conn, &actors)?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| Self::to_history_entry(entry, &service_accounts))
+            .collect())
+    }
+
+    fn to_history_entry(entry: AuditEntry, service_accounts: &[Uuid]) -> SettingsHistoryEntry {
+        let changes = entry
+            .details
+            .as_deref()
+            .and_then(|details| serde_json::from_str::<Value>(details).ok())
+            .and_then(|mut details| serde_json::from_value(details.get_mut("changes")?.take()).ok())
+            .unwrap_or_default();
+        SettingsHistoryEntry {
+            id: entry.id,
+            action: entry.action,
+            target_type: entry.target_type,
+            target_id: entry.target_id,
+            actor_id: entry.actor_id,
+            actor_type: entry.actor_id.map(|actor| {
+                if service_accounts.contains(&actor) {
+                    actor_types::SERVICE_ACCOUNT
+                } else {
+                    actor_types::USER
+                }
+            }),
+            changes,
+            created_at: entry.created_at,
+        }
+    }
+}
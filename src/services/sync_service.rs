@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::cycle::Cycle,
+    db::models::issue::Issue,
+    db::models::label::Label,
+    db::models::sync::{
+        SYNC_SCHEMA_VERSION, SyncChanges, SyncCycle, SyncIds, SyncIssue, SyncLabel, SyncSnapshot,
+        SyncTeam,
+    },
+    db::models::team::Team,
+    db::repositories::sync::SyncRepo,
+    error::AppError,
+    services::context::RequestContext,
+    utils::clock,
+};
+
+/// Most issues a snapshot carries
+pub const MAX_SNAPSHOT_ISSUES: i64 = 1000;
+/// Oldest cursor changes are served for; older clients take a new snapshot
+pub const MAX_CHANGES_AGE_DAYS: i64 = 30;
+
+pub struct SyncService;
+
+impl SyncService {
+    pub fn snapshot(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<SyncSnapshot, AppError> {
+        // Taken before reading so anything written meanwhile is sent again
+        // with the next change set
+        let cursor = clock::now();
+        let teams = SyncRepo::teams(conn, ctx.workspace_id, ctx.user_id, None)?;
+        let team_ids: Vec<Uuid> = teams.iter().map(|t| t.id).collect();
+        let mut issues = SyncRepo::my_issues(
+            conn,
+            ctx.workspace_id,
+            ctx.user_id,
+            None,
+            MAX_SNAPSHOT_ISSUES + 1,
+        )?;
+        let issues_truncated = issues.len() as i64 > MAX_SNAPSHOT_ISSUES;
+        issues.truncate(MAX_SNAPSHOT_ISSUES as usize);
+        let cycles = SyncRepo::active_cycles(conn, &team_ids, cursor.date_naive())?;
+        let labels = SyncRepo::labels(conn, ctx.workspace_id)?;
+
+        Ok(SyncSnapshot {
+            schema_version: SYNC_SCHEMA_VERSION,
+            workspace_id: ctx.workspace_id,
+            cursor,
+            issues: Self::with_labels(conn, issues)?,
+            issues_truncated,
+            cycles: cycles.into_iter().map(Self::cycle).collect(),
+            teams: teams.into_iter().map(Self::team).collect(),
+            labels: labels.into_iter().map(Self::label).collect(),
+        })
+    }
+
+    pub fn changes(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        since: DateTime<Utc>,
+    ) -> Result<SyncChanges, AppError> {
+        let cursor = clock::now();
+        Self::check_since(since, cursor)?;
+
+        let teams = SyncRepo::teams(conn, ctx.workspace_id, ctx.user_id, None)?;
+        let team_ids: Vec<Uuid> = teams.iter().map(|t| t.id).collect();
+        let issues = SyncRepo::my_issues(
+            conn,
+            ctx.workspace_id,
+            ctx.user_id,
+            Some(since),
+            MAX_SNAPSHOT_ISSUES,
+        )?;
+        let issue_ids =
+            SyncRepo::my_issue_ids(conn, ctx.workspace_id, ctx.user_id, MAX_SNAPSHOT_ISSUES)?;
+        let cycles = SyncRepo::active_cycles(conn, &team_ids, cursor.date_naive())?;
+        let labels = SyncRepo::labels(conn, ctx.workspace_id)?;
+
+        let present = SyncIds {
+            issues: issue_ids,
+            cycles: cycles.iter().map(|c| c.id).collect(),
+            teams: team_ids,
+            labels: labels.iter().map(|l| l.id).collect(),
+        };
+        Ok(SyncChanges {
+            schema_version: SYNC_SCHEMA_VERSION,
+            workspace_id: ctx.workspace_id,
+            since,
+            cursor,
+            issues: Self::with_labels(conn, issues)?,
+            cycles: Self::changed_cycles(cycles, since),
+            teams: teams
+                .into_iter()
+                .filter(|t| t.updated_at > since)
+                .map(Self::team)
+                .collect(),
+            labels: labels
+                .into_iter()
+                .filter(|l| l.updated_at > since.naive_utc())
+                .map(Self::label)
+                .collect(),
+            present,
+        })
+    }
+
+    /// Reject cursors from the future or too old to serve changes for
+    pub fn check_since(since: DateTime<Utc>, now: DateTime<Utc>) -> Result<(), AppError> {
+        if since > now {
+            return Err(AppError::validation("since must not be in the future"));
+        }
+        if now - since > Duration::days(MAX_CHANGES_AGE_DAYS) {
+            return Err(AppError::conflict_with_code(
+                format!(
+                    "Changes are only kept for {} days, take a new snapshot",
+                    MAX_CHANGES_AGE_DAYS
+                ),
+                Some("since".to_string()),
+                "SYNC_SNAPSHOT_REQUIRED",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Active cycles the client may not have: updated since the cursor, or
+    /// started running since then
+    pub fn changed_cycles(cycles: Vec<Cycle>, since: DateTime<Utc>) -> Vec<SyncCycle> {
+        cycles
+            .into_iter()
+            .filter(|c| c.updated_at > since || c.start_date >= since.date_naive())
+            .map(Self::cycle)
+            .collect()
+    }
+
+    /// Compact issues with their label ids from `(issue_id, label_id)` pairs
+    pub fn attach_labels(issues: Vec<Issue>, issue_labels: &[(Uuid, Uuid)]) -> Vec<SyncIssue> {
+        let mut labels_by_issue: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (issue_id, label_id) in issue_labels {
+            labels_by_issue
+                .entry(*issue_id)
+                .or_default()
+                .push(*label_id);
+        }
+        issues
+            .into_iter()
+            .map(|issue| {
+                let mut label_ids = labels_by_issue.remove(&issue.id).unwrap_or_default();
+                label_ids.sort();
+                SyncIssue {
+                    id: issue.id,
+                    team_id: issue.team_id,
+                    project_id: issue.project_id,
+                    cycle_id: issue.cycle_id,
+                    assignee_id: issue.assignee_id,
+                    parent_issue_id: issue.parent_issue_id,
+                    issue_number: issue.issue_number,
+                    title: issue.title,
+                    description: issue.description,
+                    priority: issue.priority,
+                    workflow_state_id: issue.workflow_state_id,
+                    label_ids,
+                    updated_at: issue.updated_at,
+                }
+            })
+            .collect()
+    }
+
+    fn with_labels(
+        conn: &mut PgConnection,
+        issues: Vec<Issue>,
+    ) -> Result<Vec<SyncIssue>, AppError> {
+        let ids: Vec<Uuid> = issues.iter().map(|i| i.id).collect();
+        let issue_labels = if ids.is_empty() {
+            Vec::new()
+        } else {
+            SyncRepo::issue_labels(conn, &ids)?
+        };
+        Ok(Self::attach_labels(issues, &issue_labels))
+    }
+
+    fn cycle(cycle: Cycle) -> SyncCycle {
+        SyncCycle {
+            id: cycle.id,
+            team_id: cycle.team_id,
+            name: cycle.name,
+            start_date: cycle.start_date,
+            end_date: cycle.end_date,
+            status: cycle.status,
+            updated_at: cycle.updated_at,
+        }
+    }
+
+    fn team(team: Team) -> SyncTeam {
+        SyncTeam {
+            id: team.id,
+            name: team.name,
+            team_key: team.team_key,
+            icon_url: team.icon_url,
+            updated_at: team.updated_at,
+        }
+    }
+
+    fn label(label: Label) -> SyncLabel {
+        SyncLabel {
+            id: label.id,
+            name: label.name,
+            color: label.color,
+            level: label.level,
+            updated_at: label.updated_at,
+        }
+    }
+}
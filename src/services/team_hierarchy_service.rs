@@ -0,0 +1,419 @@
+use diesel::prelude::*;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{audit_actions, settings_targets},
+    db::models::auth::UserBasicInfo,
+    db::models::issue::Issue,
+    db::models::team::{
+        EffectiveTeamMember, MAX_TEAM_DEPTH, Team, TeamBoard, TeamBoardColumn, TeamHierarchy,
+        TeamHierarchyNode, TeamMemberInfo,
+    },
+    db::models::workflow::WorkflowStateCategory,
+    db::repositories::board_positions::BoardPositionsRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::permission_service::{Permission, PermissionService},
+    services::settings_history_service::SettingsHistoryService,
+};
+
+/// Board columns in display order
+pub const BOARD_CATEGORIES: [WorkflowStateCategory; 6] = [
+    WorkflowStateCategory::Triage,
+    WorkflowStateCategory::Backlog,
+    WorkflowStateCategory::Unstarted,
+    WorkflowStateCategory::Started,
+    WorkflowStateCategory::Completed,
+    WorkflowStateCategory::Canceled,
+];
+
+/// Teams of a workspace together with their parent links
+struct WorkspaceTeams {
+    teams: HashMap<Uuid, Team>,
+    hierarchy: TeamHierarchy,
+}
+
+impl WorkspaceTeams {
+    fn load(conn: &mut PgConnection, workspace_id: Uuid) -> Result<Self, AppError> {
+        let teams = TeamsRepo::list_by_workspace(conn, workspace_id)?;
+        let hierarchy = TeamHierarchy::from_teams(&teams);
+        Ok(Self {
+            teams: teams.into_iter().map(|t| (t.id, t)).collect(),
+            hierarchy,
+        })
+    }
+}
+
+/// Sub-teams. Members of a team are members of every team below it, and
+/// a team below a private team is private as well.
+pub struct TeamHierarchyService;
+
+impl TeamHierarchyService {
+    /// Check that a new team can be created under `parent_team_id`
+    pub fn validate_new_parent(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        parent_team_id: Uuid,
+    ) -> Result<(), AppError> {
+        let workspace = WorkspaceTeams::load(conn, ctx.workspace_id)?;
+        if !workspace.teams.contains_key(&parent_team_id) {
+            return Err(AppError::validation(
+                "Parent team does not belong to this workspace",
+            ));
+        }
+        if workspace.hierarchy.depth(parent_team_id) + 1 > MAX_TEAM_DEPTH {
+            return Err(Self::too_deep());
+        }
+        Ok(())
+    }
+
+    /// Check that `team_id` can move under `parent_team_id` without making
+    /// a cycle or nesting deeper than `MAX_TEAM_DEPTH`
+    pub fn validate_parent(
+        hierarchy: &TeamHierarchy,
+        team_id: Uuid,
+        parent_team_id: Uuid,
+    ) -> Result<(), AppError> {
+        if hierarchy.would_cycle(team_id, parent_team_id) {
+            return Err(AppError::validation(
+                "A team cannot be placed under itself or one of its sub-teams",
+            ));
+        }
+        if hierarchy.depth(parent_team_id) + hierarchy.height(team_id) > MAX_TEAM_DEPTH {
+            return Err(Self::too_deep());
+        }
+        Ok(())
+    }
+
+    /// Move a team under another team, or to the top level with `None`
+    pub fn set_parent(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+        parent_team_id: Option<Uuid>,
+    ) -> Result<Team, AppError> {
+        let workspace = WorkspaceTeams::load(conn, ctx.workspace_id)?;
+        let Some(existing) = workspace.teams.get(&team_id) else {
+            return Err(AppError::not_found("team"));
+        };
+        if let Some(parent_team_id) = parent_team_id {
+            if !workspace.teams.contains_key(&parent_team_id) {
+                return Err(AppError::validation(
+                    "Parent team does not belong to this workspace",
+                ));
+            }
+            Self::validate_parent(&workspace.hierarchy, team_id, parent_team_id)?;
+        }
+        let team = TeamsRepo::set_parent(conn, team_id, parent_team_id)?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::TEAM_SETTINGS_UPDATED,
+            settings_targets::TEAM,
+            team.id,
+            Some(existing),
+            Some(&team),
+        )?;
+        Ok(team)
+    }
+
+    /// Teams the user may see given the teams they belong to directly:
+    /// every team that is not private through itself or an ancestor, plus
+    /// private teams they belong to directly or through an ancestor
+    pub fn visible_team_ids(
+        teams: &[Team],
+        hierarchy: &TeamHierarchy,
+        direct_memberships: &HashSet<Uuid>,
+    ) -> HashSet<Uuid> {
+        let private: HashSet<Uuid> = teams
+            .iter()
+            .filter(|t| t.is_private)
+            .map(|t| t.id)
+            .collect();
+        teams
+            .iter()
+            .map(|t| t.id)
+            .filter(|id| {
+                let mut lineage = hierarchy.ancestors(*id);
+                lineage.push(*id);
+                !lineage.iter().any(|t| private.contains(t))
+                    || lineage.iter().any(|t| direct_memberships.contains(t))
+            })
+            .collect()
+    }
+
+    /// Teams of the workspace the caller may see; workspace admins see all
+    pub fn list_visible(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<Team>, AppError> {
+        let teams = TeamsRepo::list_by_workspace(conn, ctx.workspace_id)?;
+        let visible = Self::visible_ids(conn, ctx, &teams)?;
+        Ok(teams
+            .into_iter()
+            .filter(|t| visible.contains(&t.id))
+            .collect())
+    }
+
+    /// The team, unless the caller may not see it
+    pub fn require_visible(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<Team, AppError> {
+        Self::list_visible(conn, ctx)?
+            .into_iter()
+            .find(|t| t.id == team_id)
+            .ok_or_else(|| AppError::not_found("team"))
+    }
+
+    /// The sub-team tree below a team with issue counts rolled up; sub-teams
+    /// the caller may not see are left out along with their issues
+    pub fn tree(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<TeamHierarchyNode, AppError> {
+        let (workspace, visible) = Self::load_visible(conn, ctx, team_id)?;
+        let subtree: Vec<Uuid> = workspace
+            .hierarchy
+            .subtree(team_id)
+            .into_iter()
+            .filter(|id| visible.contains(id))
+            .collect();
+        let counts: HashMap<Uuid, i64> = TeamsRepo::count_issues(conn, &subtree)?
+            .into_iter()
+            .collect();
+        Ok(Self::build_node(
+            team_id,
+            &workspace.teams,
+            &workspace.hierarchy,
+            &visible,
+            &counts,
+        ))
+    }
+
+    /// Build the tree below `team_id` from already loaded teams and per-team
+    /// issue counts
+    pub fn build_node(
+        team_id: Uuid,
+        teams: &HashMap<Uuid, Team>,
+        hierarchy: &TeamHierarchy,
+        visible: &HashSet<Uuid>,
+        issue_counts: &HashMap<Uuid, i64>,
+    ) -> TeamHierarchyNode {
+        let mut seen = HashSet::from([team_id]);
+        Self::build_node_from(team_id, teams, hierarchy, visible, issue_counts, &mut seen)
+    }
+
+    fn build_node_from(
+        team_id: Uuid,
+        teams: &HashMap<Uuid, Team>,
+        hierarchy: &TeamHierarchy,
+        visible: &HashSet<Uuid>,
+        issue_counts: &HashMap<Uuid, i64>,
+        seen: &mut HashSet<Uuid>,
+    ) -> TeamHierarchyNode {
+        let mut children = Vec::new();
+        for child in hierarchy.children(team_id) {
+            if teams.contains_key(child) && visible.contains(child) && seen.insert(*child) {
+                children.push(Self::build_node_from(
+                    *child,
+                    teams,
+                    hierarchy,
+                    visible,
+                    issue_counts,
+                    seen,
+                ));
+            }
+        }
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let issue_count = issue_counts.get(&team_id).copied().unwrap_or(0);
+        let team = &teams[&team_id];
+        let mut lineage = hierarchy.ancestors(team_id);
+        lineage.push(team_id);
+        TeamHierarchyNode {
+            id: team.id,
+            name: team.name.clone(),
+            team_key: team.team_key.clone(),
+            parent_team_id: team.parent_team_id,
+            is_private: lineage
+                .iter()
+                .any(|id| teams.get(id).is_some_and(|t| t.is_private)),
+            issue_count,
+            total_issue_count: issue_count
+                + children.iter().map(|c| c.total_issue_count).sum::<i64>(),
+            children,
+        }
+    }
+
+    /// Direct members of the team followed by members inherited from its
+    /// ancestors, nearest first; a user appears once, at the nearest team
+    pub fn effective_members(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<Vec<EffectiveTeamMember>, AppError> {
+        let (workspace, _) = Self::load_visible(conn, ctx, team_id)?;
+        let mut lineage = vec![team_id];
+        lineage.extend(workspace.hierarchy.ancestors(team_id));
+
+        let mut by_team: HashMap<Uuid, Vec<_>> = HashMap::new();
+        for (member, user) in TeamsRepo::list_members(conn, &lineage)? {
+            by_team
+                .entry(member.team_id)
+                .or_default()
+                .push((member, user));
+        }
+
+        let mut seen = HashSet::new();
+        let mut members = Vec::new();
+        for source in lineage {
+            for (member, user) in by_team.remove(&source).unwrap_or_default() {
+                if !seen.insert(user.id) {
+                    continue;
+                }
+                members.push(EffectiveTeamMember {
+                    member: TeamMemberInfo {
+                        user: UserBasicInfo {
+                            id: user.id,
+                            name: user.name,
+                            username: user.username,
+                            email: user.email,
+                            avatar_url: user.avatar_url,
+                        },
+                        role: member.role,
+                        joined_at: member.joined_at,
+                    },
+                    inherited_from: (source != team_id).then_some(source),
+                });
+            }
+        }
+        Ok(members)
+    }
+
+    /// Issues of the team, and of its visible sub-teams unless
+    /// `include_sub_teams` is false, grouped by state category
+    pub fn board(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+        include_sub_teams: bool,
+    ) -> Result<TeamBoard, AppError> {
+        let (workspace, visible) = Self::load_visible(conn, ctx, team_id)?;
+        let team_ids: Vec<Uuid> = if include_sub_teams {
+            workspace
+                .hierarchy
+                .subtree(team_id)
+                .into_iter()
+                .filter(|id| visible.contains(id))
+                .collect()
+        } else {
+            vec![team_id]
+        };
+
+        let issues = IssueRepo::list_by_teams(conn, &team_ids)?;
+        let mut categories = HashMap::new();
+        for id in &team_ids {
+            for state in WorkflowsRepo::list_states_by_team(conn, *id)? {
+                categories.insert(state.id, state.category);
+            }
+        }
+        let issue_ids: Vec<Uuid> = issues.iter().map(|i| i.id).collect();
+        let positions: HashMap<Uuid, f64> = BoardPositionsRepo::list(conn, &issue_ids)?
+            .into_iter()
+            .map(|p| (p.issue_id, p.position))
+            .collect();
+
+        Ok(TeamBoard {
+            team_id,
+            team_ids,
+            columns: Self::board_columns(issues, &categories, &positions),
+        })
+    }
+
+    /// Group issues into one column per state category. Issues without a
+    /// known state go to the backlog. Within a column issues are ordered by
+    /// board position; unpositioned issues follow, newest first.
+    pub fn board_columns(
+        issues: Vec<Issue>,
+        categories: &HashMap<Uuid, WorkflowStateCategory>,
+        positions: &HashMap<Uuid, f64>,
+    ) -> Vec<TeamBoardColumn> {
+        let mut columns: Vec<TeamBoardColumn> = BOARD_CATEGORIES
+            .iter()
+            .map(|c| TeamBoardColumn {
+                category: c.as_str().to_string(),
+                issues: Vec::new(),
+            })
+            .collect();
+        for issue in issues {
+            let category = issue
+                .workflow_state_id
+                .and_then(|id| categories.get(&id).copied())
+                .unwrap_or(WorkflowStateCategory::Backlog);
+            let column = BOARD_CATEGORIES
+                .iter()
+                .position(|c| *c == category)
+                .unwrap_or(1);
+            columns[column].issues.push(issue);
+        }
+        for column in &mut columns {
+            column
+                .issues
+                .sort_by(|a, b| match (positions.get(&a.id), positions.get(&b.id)) {
+                    (Some(x), Some(y)) => x.total_cmp(y),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => b.created_at.cmp(&a.created_at),
+                });
+        }
+        columns
+    }
+
+    /// Teams of the workspace and the ones the caller may see, failing
+    /// unless `team_id` is among them
+    fn load_visible(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<(WorkspaceTeams, HashSet<Uuid>), AppError> {
+        let workspace = WorkspaceTeams::load(conn, ctx.workspace_id)?;
+        let teams: Vec<Team> = workspace.teams.values().cloned().collect();
+        let visible = Self::visible_ids(conn, ctx, &teams)?;
+        if !visible.contains(&team_id) {
+            return Err(AppError::not_found("team"));
+        }
+        Ok((workspace, visible))
+    }
+
+    fn visible_ids(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        teams: &[Team],
+    ) -> Result<HashSet<Uuid>, AppError> {
+        let is_admin = PermissionService::current_role(conn, ctx)?
+            .is_some_and(|role| PermissionService::role_allows(&role, Permission::ManageTeams));
+        if is_admin {
+            return Ok(teams.iter().map(|t| t.id).collect());
+        }
+        let team_ids: Vec<Uuid> = teams.iter().map(|t| t.id).collect();
+        let memberships: HashSet<Uuid> = TeamsRepo::member_team_ids(conn, ctx.user_id, &team_ids)?
+            .into_iter()
+            .collect();
+        let hierarchy = TeamHierarchy::from_teams(teams);
+        Ok(Self::visible_team_ids(teams, &hierarchy, &memberships))
+    }
+
+    fn too_deep() -> AppError {
+        AppError::validation(format!(
+            "Teams can be nested at most {} levels deep",
+            MAX_TEAM_DEPTH
+        ))
+    }
+}
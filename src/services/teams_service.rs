@@ -2,10 +2,20 @@ use diesel::prelude::*;
 use uuid::Uuid;
 
 use crate::{
-    db::models::team::{NewTeam, Team},
+    db::models::audit::{audit_actions, settings_targets},
+    db::models::delete_confirmation::{DeleteConfirmation, delete_targets},
+    db::models::team::{NewTeam, Team, assignment_modes, estimate_scales},
+    db::repositories::delete_confirmations::DeleteConfirmationsRepo,
+    db::repositories::teams::TeamsRepo,
     error::AppError,
     schema,
     services::context::RequestContext,
+    services::delete_confirmations_service::DeleteConfirmationsService,
+    services::realtime_service::RealtimeService,
+    services::settings_history_service::SettingsHistoryService,
+    services::team_hierarchy_service::TeamHierarchyService,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    websocket::{EntityAction, EntityKind},
 };
 
 pub struct TeamsService;
@@ -32,6 +42,88 @@ impl TeamsService {
         }
         Ok(())
     }
+
+    pub fn validate_estimate_scale(scale: &str) -> Result<(), AppError> {
+        if estimate_scales::points(scale).is_none() {
+            return Err(AppError::validation(format!(
+                "Estimate scale must be one of: {}",
+                estimate_scales::ALL.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// The mode must be known, the default assignee a member of the team,
+    /// and the `default_assignee` mode needs one
+    pub fn validate_assignment(
+        conn: &mut diesel::PgConnection,
+        team_id: Uuid,
+        mode: &str,
+        default_assignee_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        if !assignment_modes::ALL.contains(&mode) {
+            return Err(AppError::validation(format!(
+                "Assignment mode must be one of: {}",
+                assignment_modes::ALL.join(", ")
+            )));
+        }
+        if let Some(user_id) = default_assignee_id {
+            if TeamsRepo::member_team_ids(conn, user_id, &[team_id])?.is_empty() {
+                return Err(AppError::validation(
+                    "The default assignee must be a member of the team",
+                ));
+            }
+        } else if mode == assignment_modes::DEFAULT_ASSIGNEE {
+            return Err(AppError::validation(
+                "The default_assignee mode needs a default_assignee_id",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Who a new issue of the team created without an assignee goes to
+    /// under the team's assignment mode. Round-robin locks the team row so
+    /// concurrent creates take consecutive turns.
+    pub fn auto_assignee(
+        conn: &mut diesel::PgConnection,
+        team_id: Uuid,
+    ) -> Result<Option<Uuid>, AppError> {
+        let Some(team) = TeamsRepo::list_by_ids(conn, &[team_id])?.pop() else {
+            return Ok(None);
+        };
+        match team.assignment_mode.as_str() {
+            assignment_modes::DEFAULT_ASSIGNEE => {
+                let Some(user_id) = team.default_assignee_id else {
+                    return Ok(None);
+                };
+                // The assignee may have left the team since it was set
+                let member = !TeamsRepo::member_team_ids(conn, user_id, &[team_id])?.is_empty();
+                Ok(member.then_some(user_id))
+            }
+            assignment_modes::ROUND_ROBIN => {
+                let Some(team) = TeamsRepo::lock(conn, team_id)? else {
+                    return Ok(None);
+                };
+                let members = TeamsRepo::member_ids(conn, team_id)?;
+                let next = Self::next_round_robin(&members, team.last_auto_assignee_id);
+                if let Some(user_id) = next {
+                    TeamsRepo::set_last_auto_assignee(conn, team_id, user_id)?;
+                }
+                Ok(next)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The member after `last` in `members`, wrapping around; the first
+    /// member when `last` is unset or has left the team
+    pub fn next_round_robin(members: &[Uuid], last: Option<Uuid>) -> Option<Uuid> {
+        let next = last
+            .and_then(|last| members.iter().position(|m| *m == last))
+            .map_or(0, |index| index + 1);
+        members.get(next % members.len().max(1)).copied()
+    }
+
     pub fn get(
         conn: &mut diesel::PgConnection,
         ctx: &RequestContext,
@@ -55,9 +147,13 @@ impl TeamsService {
     ) -> Result<Team, AppError> {
         Self::validate_name(&req.name)?;
         Self::validate_team_key(&req.team_key)?;
+        if let Some(parent_team_id) = req.parent_team_id {
+            TeamHierarchyService::validate_new_parent(conn, ctx, parent_team_id)?;
+        }
 
         let user_id = ctx.user_id;
         let current_workspace_id = ctx.workspace_id;
+        let estimate_scale = WorkspaceSettingsService::load(conn, ctx.workspace_id)?.estimate_scale;
 
         let result = conn.transaction::<Team, diesel::result::Error, _>(|conn| {
             let new_team = NewTeam {
@@ -66,7 +162,9 @@ impl TeamsService {
                 description: req.description.clone(),
                 icon_url: req.icon_url.clone(),
                 is_private: req.is_private,
+                parent_team_id: req.parent_team_id,
                 workspace_id: current_workspace_id,
+                estimate_scale,
             };
 
             let team: Team = diesel::insert_into(schema::teams::table)
@@ -87,7 +185,16 @@ impl TeamsService {
         });
 
         match result {
-            Ok(team) => Ok(team),
+            Ok(team) => {
+                RealtimeService::entity_changed(
+                    ctx.workspace_id,
+                    EntityKind::Team,
+                    EntityAction::Created,
+                    team.id,
+                    &team,
+                );
+                Ok(team)
+            }
             Err(e) => {
                 if e.to_string().contains("team_key") {
                     Err(AppError::validation(
@@ -123,9 +230,23 @@ impl TeamsService {
             && req.description.is_none()
             && req.icon_url.is_none()
             && req.is_private.is_none()
+            && req.estimate_scale.is_none()
+            && req.assignment_mode.is_none()
+            && req.default_assignee_id.is_none()
         {
             return Ok(existing_team);
         }
+        if let Some(scale) = &req.estimate_scale {
+            Self::validate_estimate_scale(scale)?;
+        }
+        let assignment_mode_val = req
+            .assignment_mode
+            .as_ref()
+            .unwrap_or(&existing_team.assignment_mode);
+        let default_assignee_val = req
+            .default_assignee_id
+            .unwrap_or(existing_team.default_assignee_id);
+        Self::validate_assignment(conn, team_id, assignment_mode_val, default_assignee_val)?;
 
         let team_name = req.name.as_ref().unwrap_or(&existing_team.name);
         let team_key_val = req.team_key.as_ref().unwrap_or(&existing_team.team_key);
@@ -135,6 +256,10 @@ impl TeamsService {
             .or(existing_team.description.as_ref());
         let icon_url_val = req.icon_url.as_ref().or(existing_team.icon_url.as_ref());
         let is_private_val = req.is_private.unwrap_or(existing_team.is_private);
+        let estimate_scale_val = req
+            .estimate_scale
+            .as_ref()
+            .unwrap_or(&existing_team.estimate_scale);
 
         let updated = diesel::update(t::teams.filter(t::id.eq(team_id)))
             .set((
@@ -143,11 +268,32 @@ impl TeamsService {
                 t::description.eq(description_val),
                 t::icon_url.eq(icon_url_val),
                 t::is_private.eq(is_private_val),
+                t::estimate_scale.eq(estimate_scale_val),
+                t::assignment_mode.eq(assignment_mode_val),
+                t::default_assignee_id.eq(default_assignee_val),
             ))
             .get_result::<Team>(conn);
 
         match updated {
-            Ok(team) => Ok(team),
+            Ok(team) => {
+                SettingsHistoryService::record(
+                    conn,
+                    ctx,
+                    audit_actions::TEAM_SETTINGS_UPDATED,
+                    settings_targets::TEAM,
+                    team.id,
+                    Some(&existing_team),
+                    Some(&team),
+                )?;
+                RealtimeService::entity_changed(
+                    ctx.workspace_id,
+                    EntityKind::Team,
+                    EntityAction::Updated,
+                    team.id,
+                    &team,
+                );
+                Ok(team)
+            }
             Err(e) => {
                 if e.to_string().contains("team_key") {
                     Err(AppError::validation(
@@ -160,27 +306,79 @@ impl TeamsService {
         }
     }
 
+    /// First step of deleting a team: a confirmation token and what the
+    /// delete would take with it
+    pub fn request_delete(
+        conn: &mut diesel::PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<DeleteConfirmation, AppError> {
+        let team = Self::find_in_workspace(conn, ctx, team_id)?;
+        let impact = DeleteConfirmationsRepo::team_impact(conn, team_id)?;
+        DeleteConfirmationsService::request(
+            conn,
+            ctx,
+            delete_targets::TEAM,
+            team_id,
+            &team.name,
+            impact,
+        )
+    }
+
+    /// Delete the team with a token from [`Self::request_delete`]
     pub fn delete(
         conn: &mut diesel::PgConnection,
         ctx: &RequestContext,
         team_id: Uuid,
+        confirmation_token: &str,
     ) -> Result<(), AppError> {
         use crate::schema::teams::dsl as t;
 
-        match t::teams
+        let team = Self::find_in_workspace(conn, ctx, team_id)?;
+
+        // Sub-teams move up to the deleted team's parent
+        let result = conn.transaction::<_, AppError, _>(|conn| {
+            DeleteConfirmationsService::confirm(
+                conn,
+                ctx,
+                delete_targets::TEAM,
+                team_id,
+                confirmation_token,
+            )?;
+            TeamsRepo::reparent_children(conn, team_id, team.parent_team_id)?;
+            diesel::delete(t::teams.filter(t::id.eq(team_id))).execute(conn)?;
+            Ok(())
+        });
+        match result {
+            Ok(()) => {
+                RealtimeService::entity_changed(
+                    ctx.workspace_id,
+                    EntityKind::Team,
+                    EntityAction::Deleted,
+                    team_id,
+                    &(),
+                );
+                Ok(())
+            }
+            Err(AppError::Database(_)) => {
+                Err(AppError::Internal("Failed to delete team".to_string()))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn find_in_workspace(
+        conn: &mut diesel::PgConnection,
+        ctx: &RequestContext,
+        team_id: Uuid,
+    ) -> Result<Team, AppError> {
+        use crate::schema::teams::dsl as t;
+        t::teams
             .filter(t::id.eq(team_id))
             .filter(t::workspace_id.eq(ctx.workspace_id))
             .select(Team::as_select())
             .first::<Team>(conn)
-        {
-            Ok(_) => (),
-            Err(_) => return Err(AppError::not_found("Team not found")),
-        }
-
-        match diesel::delete(t::teams.filter(t::id.eq(team_id))).execute(conn) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(AppError::Internal("Failed to delete team".to_string())),
-        }
+            .map_err(|_| AppError::not_found("Team not found"))
     }
 
     pub fn list(
@@ -0,0 +1,137 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use diesel::prelude::*;
+
+use crate::config::TelemetryConfig;
+use crate::db::DbExecutor;
+use crate::db::models::telemetry::{TELEMETRY_SCHEMA_VERSION, TelemetryOverview, TelemetryReport};
+use crate::db::repositories::telemetry::TelemetryRepo;
+use crate::error::AppError;
+use crate::utils::clock;
+use crate::websocket::WebSocketManager;
+
+/// Name of the scheduler job that sends reports
+pub const TELEMETRY_JOB: &str = "usage_telemetry";
+
+const TELEMETRY_TIMEOUT: Duration = Duration::from_secs(10);
+
+static MANAGER: OnceLock<WebSocketManager> = OnceLock::new();
+
+/// Anonymous, opt-in usage reports: instance-wide counts and how many
+/// workspaces use each feature, sent to the endpoint the operator configured
+pub struct TelemetryService;
+
+impl TelemetryService {
+    /// Count open WebSocket connections in reports; later calls are ignored
+    pub fn install(ws_manager: WebSocketManager) {
+        let _ = MANAGER.set(ws_manager);
+    }
+
+    /// Report of the current usage with the given WebSocket connection count
+    pub fn collect(
+        conn: &mut PgConnection,
+        websocket_connections: i64,
+    ) -> Result<TelemetryReport, AppError> {
+        let state = TelemetryRepo::state(conn, clock::new_id())?;
+        let mut counts = TelemetryRepo::usage_counts(conn)?;
+        counts.websocket_connections = websocket_connections;
+        Ok(TelemetryReport {
+            schema_version: TELEMETRY_SCHEMA_VERSION,
+            instance_id: state.instance_id,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: clock::now(),
+            counts,
+            feature_adoption: TelemetryRepo::feature_adoption(conn)?,
+        })
+    }
+
+    /// The report as it would be sent now
+    pub async fn next_report(executor: &DbExecutor) -> Result<TelemetryReport, AppError> {
+        let websocket_connections = Self::websocket_connections().await;
+        executor
+            .transaction(move |conn| Self::collect(conn, websocket_connections))
+            .await
+    }
+
+    /// Whether and where reports are sent, the last one sent and the next
+    pub async fn overview(
+        executor: &DbExecutor,
+        config: Option<&TelemetryConfig>,
+    ) -> Result<TelemetryOverview, AppError> {
+        let websocket_connections = Self::websocket_connections().await;
+        let (state, next_report) = executor
+            .transaction(move |conn| {
+                let next_report = Self::collect(conn, websocket_connections)?;
+                let state = TelemetryRepo::state(conn, next_report.instance_id)?;
+                Ok((state, next_report))
+            })
+            .await?;
+        Ok(TelemetryOverview {
+            enabled: config.is_some(),
+            endpoint: config.map(|config| config.endpoint.clone()),
+            last_sent_at: state.last_sent_at,
+            last_report: state
+                .last_report
+                .as_deref()
+                .and_then(|report| serde_json::from_str(report).ok()),
+            next_report,
+        })
+    }
+
+    async fn websocket_connections() -> i64 {
+        match MANAGER.get() {
+            Some(ws_manager) => ws_manager.get_connection_count().await as i64,
+            None => 0,
+        }
+    }
+}
+
+/// Sends reports to the configured endpoint
+pub struct TelemetryReporter {
+    endpoint: String,
+    http: reqwest::Client,
+}
+
+impl TelemetryReporter {
+    pub fn new(config: &TelemetryConfig) -> Result<Self, AppError> {
+        let http = reqwest::Client::builder()
+            .timeout(TELEMETRY_TIMEOUT)
+            .user_agent(concat!("momentum-telemetry/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| AppError::internal(format!("Failed to build telemetry client: {}", e)))?;
+        Ok(Self {
+            endpoint: config.endpoint.clone(),
+            http,
+        })
+    }
+
+    /// Send the current report and remember it once the endpoint accepts it
+    pub async fn send(&self, executor: &DbExecutor) -> Result<String, AppError> {
+        let report = TelemetryService::next_report(executor).await?;
+        let body = serde_json::to_string(&report)
+            .map_err(|e| AppError::internal(format!("Failed to encode usage report: {}", e)))?;
+        let response = self
+            .http
+            .post(&self.endpoint)
+            .header("Content-Type", "application/json")
+            .body(body.clone())
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("Sending usage report failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::internal(format!(
+                "Usage report endpoint returned HTTP {}",
+                response.status()
+            )));
+        }
+        let sent_at = report.generated_at;
+        executor
+            .transaction(move |conn| Ok(TelemetryRepo::record_sent(conn, sent_at, &body)?))
+            .await?;
+        Ok(format!(
+            "sent counts of {} workspaces",
+            report.counts.workspaces
+        ))
+    }
+}
@@ -0,0 +1,393 @@
+use std::time::Duration;
+
+use axum::body::Bytes;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    cache::LockManager,
+    db::DbExecutor,
+    db::models::attachment::{AttachmentResponse, CreateAttachmentRequest, NewAttachment},
+    db::models::upload_session::{
+        NewUploadSession, UploadSession, UploadSessionResponse, UploadedPart,
+    },
+    db::repositories::attachments::AttachmentsRepo,
+    db::repositories::upload_sessions::UploadSessionsRepo,
+    error::AppError,
+    services::attachments_service::AttachmentsService,
+    services::context::RequestContext,
+    utils::clock,
+    utils::{AssetUrlHelper, ObjectStorage},
+};
+
+/// Smallest chunk other than the last; storage rejects smaller multipart parts
+pub const MIN_CHUNK_BYTES: i64 = 5 * 1024 * 1024;
+
+/// Largest chunk accepted in one request, also the route's body limit
+pub const MAX_CHUNK_BYTES: usize = 64 * 1024 * 1024;
+
+/// Most parts storage accepts in one multipart upload
+const MAX_PARTS: i32 = 10_000;
+
+/// Held while a chunk is appended so a session takes one chunk at a time;
+/// renewed while the part uploads
+const APPEND_LOCK_TTL: Duration = Duration::from_secs(30);
+
+/// Sessions expired per scheduler run
+const EXPIRE_BATCH_SIZE: i64 = 100;
+
+/// Resumable uploads of large attachments. The client creates a session,
+/// appends chunks in order at the session's offset, and resumes after a
+/// failure by reading the offset back; the chunk that reaches the declared
+/// size turns the session into an uploaded attachment.
+pub struct UploadSessionsService;
+
+impl UploadSessionsService {
+    /// Start a multipart upload in storage and record a session for it. No
+    /// connection is held while storage is called.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        executor: &DbExecutor,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        max_bytes: i64,
+        ttl_secs: u64,
+        issue_id: Uuid,
+        req: CreateAttachmentRequest,
+    ) -> Result<UploadSessionResponse, AppError> {
+        let storage = AttachmentsService::require_storage(storage)?;
+        let (filename, content_type) = AttachmentsService::validate_upload(&req, max_bytes)?;
+        let check_ctx = ctx.clone();
+        executor
+            .read(move |conn| AttachmentsService::ensure_issue(conn, &check_ctx, issue_id))
+            .await?;
+
+        // The session id becomes the attachment id, so the key is final
+        let id = clock::new_id();
+        let storage_key =
+            AttachmentsService::storage_key(ctx.workspace_id, issue_id, id, &filename);
+        let object_key = format!("attachments/{}", storage_key);
+        let multipart_upload_id = storage
+            .create_multipart(&object_key, &content_type)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to start upload: {}", e)))?;
+
+        let new_session = NewUploadSession {
+            id,
+            workspace_id: ctx.workspace_id,
+            issue_id,
+            uploader_id: ctx.user_id,
+            filename,
+            content_type,
+            size_bytes: req.size_bytes,
+            storage_key,
+            multipart_upload_id: multipart_upload_id.clone(),
+            expires_at: Self::expires_at(ttl_secs),
+        };
+        let session = match executor
+            .transaction(move |conn| Ok(UploadSessionsRepo::insert(conn, &new_session)?))
+            .await
+        {
+            Ok(session) => session,
+            Err(e) => {
+                // Without a session nothing would ever abort the upload
+                if let Err(abort) = storage
+                    .abort_multipart(&object_key, &multipart_upload_id)
+                    .await
+                {
+                    tracing::warn!("Failed to abort upload {}: {}", id, abort);
+                }
+                return Err(e);
+            }
+        };
+        Ok(Self::to_response(session, None))
+    }
+
+    /// The session with its current offset, and the attachment once complete
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        asset_helper: &AssetUrlHelper,
+        upload_id: Uuid,
+    ) -> Result<UploadSessionResponse, AppError> {
+        let storage = AttachmentsService::require_storage(storage)?;
+        let session = Self::find(conn, ctx, upload_id)?;
+        let attachment = Self::attachment(conn, storage, asset_helper, &session)?;
+        Ok(Self::to_response(session, attachment))
+    }
+
+    /// Append `chunk` at `offset`, which must be the session's current
+    /// offset. The chunk that reaches the declared size completes the
+    /// upload and creates the attachment.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn append_chunk(
+        executor: &DbExecutor,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        locks: &LockManager,
+        asset_helper: &AssetUrlHelper,
+        ttl_secs: u64,
+        upload_id: Uuid,
+        offset: i64,
+        chunk: Bytes,
+    ) -> Result<UploadSessionResponse, AppError> {
+        let storage = AttachmentsService::require_storage(storage)?;
+        let Some(guard) = locks
+            .try_acquire(&format!("upload:{}", upload_id), APPEND_LOCK_TTL)
+            .await?
+        else {
+            return Err(AppError::conflict_with_code(
+                "Another chunk is being appended to this upload",
+                None,
+                "UPLOAD_BUSY",
+            ));
+        };
+
+        let find_ctx = ctx.clone();
+        let session = executor
+            .read(move |conn| Self::find(conn, &find_ctx, upload_id))
+            .await?;
+        if session.completed_at.is_some() {
+            return Err(AppError::conflict_with_code(
+                "Upload is already complete",
+                None,
+                "UPLOAD_COMPLETE",
+            ));
+        }
+        if offset != session.upload_offset {
+            return Err(AppError::conflict_with_code(
+                format!("Upload offset is {}", session.upload_offset),
+                Some("Upload-Offset".to_string()),
+                "UPLOAD_OFFSET_MISMATCH",
+            ));
+        }
+        let mut parts = session.uploaded_parts();
+        let part_number = parts.len() as i32 + 1;
+        let chunk_len = chunk.len() as i64;
+        Self::check_chunk(session.size_bytes, offset, chunk_len, part_number)?;
+
+        let object_key = session.object_key();
+        let etag = storage
+            .upload_part(
+                &object_key,
+                &session.multipart_upload_id,
+                part_number,
+                chunk,
+            )
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to upload chunk: {}", e)))?;
+        parts.push(UploadedPart {
+            part_number,
+            etag,
+            size_bytes: chunk_len,
+        });
+        let upload_offset = offset + chunk_len;
+        let parts_json = serde_json::to_string(&parts)
+            .map_err(|e| AppError::internal(format!("Failed to serialize parts: {}", e)))?;
+
+        let response = if upload_offset < session.size_bytes {
+            let expires_at = Self::expires_at(ttl_secs);
+            let session = executor
+                .transaction(move |conn| {
+                    Ok(UploadSessionsRepo::record_chunk(
+                        conn,
+                        session.id,
+                        upload_offset,
+                        &parts_json,
+                        expires_at,
+                    )?)
+                })
+                .await?;
+            Self::to_response(session, None)
+        } else {
+            let completed: Vec<(i32, &str)> = parts
+                .iter()
+                .map(|part| (part.part_number, part.etag.as_str()))
+                .collect();
+            storage
+                .complete_multipart(&object_key, &session.multipart_upload_id, &completed)
+                .await
+                .map_err(|e| AppError::internal(format!("Failed to complete upload: {}", e)))?;
+
+            let (session, attachment) = executor
+                .transaction(move |conn| {
+                    let attachment = AttachmentsRepo::insert(
+                        conn,
+                        &NewAttachment {
+                            id: session.id,
+                            issue_id: session.issue_id,
+                            workspace_id: session.workspace_id,
+                            uploader_id: Some(session.uploader_id),
+                            filename: session.filename.clone(),
+                            content_type: session.content_type.clone(),
+                            size_bytes: session.size_bytes,
+                            storage_key: session.storage_key.clone(),
+                        },
+                    )?;
+                    let attachment =
+                        AttachmentsRepo::mark_uploaded(conn, attachment.id, session.size_bytes)?;
+                    let session = UploadSessionsRepo::mark_completed(
+                        conn,
+                        session.id,
+                        upload_offset,
+                        &parts_json,
+                        attachment.id,
+                    )?;
+                    Ok((session, attachment))
+                })
+                .await?;
+            let attachment = AttachmentsService::to_response(storage, asset_helper, attachment);
+            Self::to_response(session, Some(attachment))
+        };
+
+        if let Err(e) = guard.release().await {
+            tracing::warn!("Failed to release upload lock {}: {}", upload_id, e);
+        }
+        Ok(response)
+    }
+
+    /// Abort an unfinished upload and discard its parts; a completed
+    /// session is only forgotten, its attachment stays
+    pub async fn cancel(
+        executor: &DbExecutor,
+        ctx: &RequestContext,
+        storage: Option<&ObjectStorage>,
+        upload_id: Uuid,
+    ) -> Result<(), AppError> {
+        let storage = AttachmentsService::require_storage(storage)?;
+        let ctx = ctx.clone();
+        let session = executor
+            .read(move |conn| Self::find(conn, &ctx, upload_id))
+            .await?;
+        if session.completed_at.is_none() {
+            storage
+                .abort_multipart(&session.object_key(), &session.multipart_upload_id)
+                .await
+                .map_err(|e| AppError::internal(format!("Failed to abort upload: {}", e)))?;
+        }
+        executor
+            .transaction(move |conn| Ok(UploadSessionsRepo::delete(conn, session.id)?))
+            .await?;
+        Ok(())
+    }
+
+    /// Abort expired sessions in storage and delete them. Sessions whose
+    /// abort fails are kept for the next run.
+    pub async fn expire_abandoned(
+        executor: &DbExecutor,
+        storage: &ObjectStorage,
+    ) -> Result<usize, AppError> {
+        let now = clock::now();
+        let expired = executor
+            .read(move |conn| {
+                Ok(UploadSessionsRepo::list_expired(
+                    conn,
+                    now,
+                    EXPIRE_BATCH_SIZE,
+                )?)
+            })
+            .await?;
+
+        let mut ids = Vec::with_capacity(expired.len());
+        for session in expired {
+            if session.completed_at.is_none()
+                && let Err(e) = storage
+                    .abort_multipart(&session.object_key(), &session.multipart_upload_id)
+                    .await
+            {
+                tracing::warn!("Failed to abort expired upload {}: {}", session.id, e);
+                continue;
+            }
+            ids.push(session.id);
+        }
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        executor
+            .transaction(move |conn| Ok(UploadSessionsRepo::delete_many(conn, &ids)?))
+            .await
+    }
+
+    /// Check a chunk of `chunk_len` bytes at `offset` against the declared
+    /// size and the storage limits on parts
+    pub fn check_chunk(
+        size_bytes: i64,
+        offset: i64,
+        chunk_len: i64,
+        part_number: i32,
+    ) -> Result<(), AppError> {
+        if chunk_len == 0 {
+            return Err(AppError::validation("Chunk must not be empty"));
+        }
+        if chunk_len > MAX_CHUNK_BYTES as i64 {
+            return Err(AppError::validation(format!(
+                "Chunk must be at most {} bytes",
+                MAX_CHUNK_BYTES
+            )));
+        }
+        if offset + chunk_len > size_bytes {
+            return Err(AppError::validation(format!(
+                "Chunk ends past the declared size of {} bytes",
+                size_bytes
+            )));
+        }
+        let is_last = offset + chunk_len == size_bytes;
+        if !is_last && chunk_len < MIN_CHUNK_BYTES {
+            return Err(AppError::validation(format!(
+                "Every chunk but the last must be at least {} bytes",
+                MIN_CHUNK_BYTES
+            )));
+        }
+        if part_number > MAX_PARTS || (!is_last && part_number == MAX_PARTS) {
+            return Err(AppError::validation(format!(
+                "Upload cannot have more than {} chunks",
+                MAX_PARTS
+            )));
+        }
+        Ok(())
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        upload_id: Uuid,
+    ) -> Result<UploadSession, AppError> {
+        UploadSessionsRepo::find_for_uploader(conn, ctx.workspace_id, ctx.user_id, upload_id)?
+            .ok_or_else(|| AppError::not_found("upload"))
+    }
+
+    fn attachment(
+        conn: &mut PgConnection,
+        storage: &ObjectStorage,
+        asset_helper: &AssetUrlHelper,
+        session: &UploadSession,
+    ) -> Result<Option<AttachmentResponse>, AppError> {
+        let Some(attachment_id) = session.attachment_id else {
+            return Ok(None);
+        };
+        Ok(AttachmentsRepo::find_for_issue(
+            conn,
+            session.workspace_id,
+            session.issue_id,
+            attachment_id,
+        )?
+        .map(|attachment| AttachmentsService::to_response(storage, asset_helper, attachment)))
+    }
+
+    fn expires_at(ttl_secs: u64) -> chrono::DateTime<chrono::Utc> {
+        clock::now() + chrono::Duration::seconds(ttl_secs as i64)
+    }
+
+    fn to_response(
+        session: UploadSession,
+        attachment: Option<AttachmentResponse>,
+    ) -> UploadSessionResponse {
+        UploadSessionResponse {
+            session,
+            chunk_min_bytes: MIN_CHUNK_BYTES,
+            chunk_max_bytes: MAX_CHUNK_BYTES as i64,
+            attachment,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, HashMap};
+
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::notification::notification_events,
+    db::models::user_preference::{
+        DEFAULT_TIMEZONE, NotificationChannels, UpdateUserPreferencesRequest, UserPreferenceRecord,
+        UserPreferences, notification_channels, themes,
+    },
+    db::repositories::user_preferences::UserPreferencesRepo,
+    error::AppError,
+    utils::clock,
+};
+
+const MAX_TIMEZONE_LEN: usize = 64;
+const MAX_LOCALE_LEN: usize = 20;
+
+/// Per-user display settings and notification channels. The notification
+/// subsystem reads the channels before recording, emailing or pushing a
+/// notification over WebSocket.
+pub struct UserPreferencesService;
+
+impl UserPreferencesService {
+    pub fn get(conn: &mut PgConnection, user_id: Uuid) -> Result<UserPreferences, AppError> {
+        Ok(UserPreferencesRepo::find(conn, user_id)?
+            .map(UserPreferences::from)
+            .unwrap_or_default())
+    }
+
+    /// Preferences of each of `user_ids`, with defaults for users who never
+    /// changed theirs
+    pub fn get_many(
+        conn: &mut PgConnection,
+        user_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, UserPreferences>, AppError> {
+        let mut preferences: HashMap<Uuid, UserPreferences> =
+            UserPreferencesRepo::list_for_users(conn, user_ids)?
+                .into_iter()
+                .map(|record| (record.user_id, record.into()))
+                .collect();
+        for user_id in user_ids {
+            preferences.entry(*user_id).or_default();
+        }
+        Ok(preferences)
+    }
+
+    /// Change the given settings; the others keep their value
+    pub fn update(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        req: &UpdateUserPreferencesRequest,
+    ) -> Result<UserPreferences, AppError> {
+        let mut preferences = Self::get(conn, user_id)?;
+        if let Some(timezone) = &req.timezone {
+            preferences.timezone = Self::validate_timezone(timezone)?;
+        }
+        if let Some(locale) = &req.locale {
+            preferences.locale = Self::validate_locale(locale)?;
+        }
+        if let Some(theme) = &req.theme {
+            let theme = theme.trim().to_ascii_lowercase();
+            if !themes::ALL.contains(&theme.as_str()) {
+                return Err(AppError::validation(format!(
+                    "Theme must be one of: {}",
+                    themes::ALL.join(", ")
+                )));
+            }
+            preferences.theme = theme;
+        }
+        if let Some(notifications) = &req.notifications {
+            for (event, update) in notifications {
+                let channels = preferences
+                    .notifications
+                    .get_mut(event.as_str())
+                    .ok_or_else(|| {
+                        AppError::validation(format!("Unknown notification event: {}", event))
+                    })?;
+                channels.in_app = update.in_app.unwrap_or(channels.in_app);
+                channels.email = update.email.unwrap_or(channels.email);
+                channels.websocket = update.websocket.unwrap_or(channels.websocket);
+            }
+        }
+
+        let record = UserPreferencesRepo::upsert(
+            conn,
+            &UserPreferenceRecord {
+                user_id,
+                timezone: preferences.timezone,
+                locale: preferences.locale,
+                theme: preferences.theme,
+                muted_channels: Self::encode_muted_channels(&preferences.notifications),
+                updated_at: clock::now(),
+            },
+        )?;
+        Ok(record.into())
+    }
+
+    /// An IANA zone name such as `America/Argentina/Buenos_Aires` or
+    /// `Etc/GMT+5`, or `UTC`. Only the shape is checked; the server has no
+    /// zone database.
+    pub fn validate_timezone(timezone: &str) -> Result<String, AppError> {
+        let timezone = timezone.trim();
+        if timezone.eq_ignore_ascii_case(DEFAULT_TIMEZONE) {
+            return Ok(DEFAULT_TIMEZONE.to_string());
+        }
+        let parts: Vec<&str> = timezone.split('/').collect();
+        let valid = timezone.len() <= MAX_TIMEZONE_LEN
+            && (2..=3).contains(&parts.len())
+            && parts[0].starts_with(|c: char| c.is_ascii_uppercase())
+            && parts.iter().all(|part| {
+                !part.is_empty()
+                    && part
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "_+-".contains(c))
+            });
+        if !valid {
+            return Err(AppError::validation(format!(
+                "Invalid time zone: {}; expected a name such as Europe/Berlin",
+                timezone
+            )));
+        }
+        Ok(timezone.to_string())
+    }
+
+    /// A language tag such as `en`, `pt-BR` or `zh-Hans-CN`; `_` is accepted
+    /// for `-` and the language is lowercased
+    pub fn validate_locale(locale: &str) -> Result<String, AppError> {
+        let locale = locale.trim().replace('_', "-");
+        let mut subtags = locale.split('-');
+        let language = subtags.next().unwrap_or_default().to_ascii_lowercase();
+        let valid = locale.len() <= MAX_LOCALE_LEN
+            && (2..=3).contains(&language.len())
+            && language.chars().all(|c| c.is_ascii_alphabetic())
+            && subtags.clone().all(|subtag| {
+                (2..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+            });
+        if !valid {
+            return Err(AppError::validation(format!(
+                "Invalid locale: {}; expected a language tag such as en or pt-BR",
+                locale
+            )));
+        }
+        Ok(std::iter::once(language.as_str())
+            .chain(subtags)
+            .collect::<Vec<_>>()
+            .join("-"))
+    }
+
+    /// Only turned-off channels are stored, so notification types added
+    /// later are delivered everywhere
+    fn encode_muted_channels(notifications: &BTreeMap<String, NotificationChannels>) -> String {
+        notifications
+            .iter()
+            .filter(|(event, _)| notification_events::ALL.contains(&event.as_str()))
+            .flat_map(|(event, channels)| {
+                notification_channels::ALL
+                    .iter()
+                    .filter(|channel| !channels.allows(channel))
+                    .map(move |channel| format!("{}:{}", event, channel))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,767 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor,
+    db::enums::ProjectPriority,
+    db::models::app_installation::{
+        AppInstallation, KeySignature, NewWebhookDelivery, WebhookDelivery, WebhookDeliveryAttempt,
+        WebhookEnvelope, webhook_delivery_status, webhook_events,
+    },
+    db::models::comment::Comment,
+    db::models::external_reference::ExternalReference,
+    db::models::issue::Issue,
+    db::models::issue_move::{IssueMove, IssueMoveResult},
+    db::models::markdown::{MarkdownEntities, RenderedBody, WithRenderedMarkdown},
+    db::models::oauth_app::{OAuthApp, oauth_scopes},
+    db::models::project::Project,
+    db::models::webhook::{Webhook, WorkspaceWebhookEnvelope},
+    db::models::websocket_session::{WebSocketSession, session_clients},
+    db::repositories::app_installations::{
+        AppInstallationsRepo, WebhookDeliveriesRepo, WebhookSigningKeysRepo,
+    },
+    db::repositories::external_references::ExternalReferencesRepo,
+    db::repositories::issues::IssueRepo,
+    db::repositories::labels::LabelRepo,
+    db::repositories::oauth_apps::OAuthAppsRepo,
+    db::repositories::webhooks::WebhooksRepo,
+    db::with_txn,
+    error::AppError,
+    services::markdown_service::MarkdownService,
+    services::session_analytics_service::SessionAnalyticsService,
+    utils::clock,
+    utils::webhook_filter::{FilterInput, WebhookFilter},
+};
+
+/// Attempts before a delivery is given up and marked failed
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 6;
+
+/// Deliveries sent per worker tick
+pub const DELIVERY_BATCH_SIZE: i64 = 50;
+
+const RETRY_BASE_SECS: i64 = 30;
+/// Deliveries sent right away from a request are hidden from the worker for
+/// this long, so it does not send them a second time
+const SEND_NOW_LEASE_SECS: i64 = 60;
+const DELIVERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+pub const SIGNATURE_HEADER: &str = "X-Momentum-Signature";
+pub const EVENT_HEADER: &str = "X-Momentum-Event";
+pub const DELIVERY_HEADER: &str = "X-Momentum-Delivery";
+
+pub struct WebhookService;
+
+impl WebhookService {
+    /// Whether an installation receives `event`: lifecycle events always go out,
+    /// other events need a subscription and a scope that covers them
+    pub fn should_deliver(subscribed: &[String], scopes: &[String], event: &str) -> bool {
+        if webhook_events::is_lifecycle(event) {
+            return true;
+        }
+        subscribed.iter().any(|e| e == event)
+            && webhook_events::required_scope(event)
+                .is_some_and(|required| oauth_scopes::allows(scopes, required))
+    }
+
+    /// Webhook URLs must be absolute https URLs; plain http is only allowed for loopback
+    pub fn validate_url(url: &str) -> Result<(), AppError> {
+        let parsed = url::Url::parse(url)
+            .map_err(|_| AppError::validation(format!("Invalid webhook URL: {}", url)))?;
+        let loopback = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        if !(parsed.scheme() == "https" || (parsed.scheme() == "http" && loopback)) {
+            return Err(AppError::validation(
+                "Webhook URL must use https (or http on localhost)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Subscribable events with duplicates removed, in the order given
+    pub fn normalize_events(requested: &[String]) -> Result<Vec<String>, AppError> {
+        let mut events: Vec<String> = Vec::new();
+        for event in requested {
+            if !webhook_events::is_subscribable(event) {
+                return Err(AppError::validation(format!(
+                    "Unknown webhook event: {}",
+                    event
+                )));
+            }
+            if !events.contains(event) {
+                events.push(event.clone());
+            }
+        }
+        Ok(events)
+    }
+
+    /// Delay before retrying after `attempts` failed attempts (30s, 1m, 2m, ...)
+    pub fn retry_delay(attempts: i32) -> chrono::Duration {
+        let exponent = (attempts.max(1) - 1).min(10) as u32;
+        chrono::Duration::seconds(RETRY_BASE_SECS * 2_i64.pow(exponent))
+    }
+
+    /// `sha256=<hex hmac>` of the request body, keyed by the app's webhook secret
+    pub fn sign(secret: &str, body: &str) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key size");
+        mac.update(body.as_bytes());
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Signature header value with one `kid=<key id>;sha256=<hex>` entry per
+    /// active key, so receivers keep verifying while a rotation is in progress.
+    /// Entries without a key id are written as a bare `sha256=<hex>`.
+    pub fn signature_header(keys: &[(Option<&str>, &str)], body: &str) -> String {
+        keys.iter()
+            .map(|(key_id, secret)| match key_id {
+                Some(key_id) => format!("kid={};{}", key_id, Self::sign(secret, body)),
+                None => Self::sign(secret, body),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Split a signature header (or a single `sha256=<hex>`) into its entries
+    pub fn parse_signature_header(header: &str) -> Vec<KeySignature> {
+        header
+            .split(',')
+            .filter_map(|entry| {
+                let mut key_id = None;
+                let mut signature = None;
+                for part in entry.split(';').map(str::trim) {
+                    if let Some(kid) = part.strip_prefix("kid=") {
+                        key_id = Some(kid.to_string());
+                    } else if part.starts_with("sha256=") {
+                        signature = Some(part.to_string());
+                    }
+                }
+                Some(KeySignature {
+                    key_id,
+                    signature: signature?,
+                })
+            })
+            .collect()
+    }
+
+    /// Constant-time check of a `sha256=<hex>` signature against `secret`
+    pub fn verify(secret: &str, body: &str, signature: &str) -> bool {
+        let Some(bytes) = signature
+            .trim()
+            .strip_prefix("sha256=")
+            .and_then(|hex_mac| hex::decode(hex_mac).ok())
+        else {
+            return false;
+        };
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key size");
+        mac.update(body.as_bytes());
+        mac.verify_slice(&bytes).is_ok()
+    }
+
+    /// Key that produced any entry of `header`. An entry naming a key id is
+    /// only checked against that key. Returns `None` when nothing matches.
+    pub fn verify_header<'a>(
+        keys: &[(Option<&'a str>, &str)],
+        body: &str,
+        header: &str,
+    ) -> Option<Option<&'a str>> {
+        Self::parse_signature_header(header)
+            .iter()
+            .find_map(|entry| {
+                keys.iter()
+                    .filter(|(key_id, _)| {
+                        entry.key_id.is_none() || entry.key_id.as_deref() == *key_id
+                    })
+                    .find(|(_, secret)| Self::verify(secret, body, &entry.signature))
+                    .map(|(key_id, _)| *key_id)
+            })
+    }
+
+    /// Synthetic data for an event type, shaped like the real payload
+    pub fn sample_data(event: &str, app_id: Uuid) -> Option<serde_json::Value> {
+        let now = clock::now();
+        let issue = Issue {
+            id: clock::new_id(),
+            project_id: None,
+            cycle_id: None,
+            creator_id: Uuid::nil(),
+            assignee_id: None,
+            parent_issue_id: None,
+            issue_number: 1,
+            title: "Sample issue".to_string(),
+            description: Some("Sent from the webhook test endpoint".to_string()),
+            priority: "medium".to_string(),
+            is_changelog_candidate: false,
+            created_at: now,
+            updated_at: now,
+            team_id: Uuid::nil(),
+            workflow_id: None,
+            workflow_state_id: None,
+            archived_at: None,
+            archive_batch_id: None,
+            estimate: Some(3),
+        };
+        let project = Project {
+            id: clock::new_id(),
+            workspace_id: Uuid::nil(),
+            roadmap_id: None,
+            owner_id: Uuid::nil(),
+            name: "Sample project".to_string(),
+            project_key: "SMP".to_string(),
+            description: None,
+            target_date: None,
+            created_at: now,
+            updated_at: now,
+            project_status_id: Uuid::nil(),
+            priority: ProjectPriority::Medium,
+            budget_currency: None,
+            budget_amount_cents: None,
+            budget_alert_percent: 80,
+            budget_alert_level: "ok".to_string(),
+        };
+        let data = match event {
+            webhook_events::INSTALLATION_CREATED
+            | webhook_events::INSTALLATION_DELETED
+            | webhook_events::INSTALLATION_PERMISSIONS_CHANGED => serde_json::json!({
+                "app_id": app_id,
+                "scopes": [oauth_scopes::READ_ISSUES],
+                "actor_id": Uuid::nil(),
+            }),
+            webhook_events::ISSUE_CREATED | webhook_events::ISSUE_UPDATED => {
+                let mut data = serde_json::to_value(&issue).ok()?;
+                data["external_references"] = serde_json::to_value([ExternalReference {
+                    id: clock::new_id(),
+                    workspace_id: Uuid::nil(),
+                    issue_id: issue.id,
+                    source: "zendesk".to_string(),
+                    external_id: "4711".to_string(),
+                    url: Some("https://example.zendesk.com/agent/tickets/4711".to_string()),
+                    created_by: Uuid::nil(),
+                    created_at: now,
+                }])
+                .ok()?;
+                if event == webhook_events::ISSUE_CREATED {
+                    let rendered =
+                        MarkdownService::render(issue.description.as_deref().unwrap_or_default());
+                    data["rendered"] = serde_json::to_value(RenderedBody::new(
+                        rendered,
+                        MarkdownEntities::default(),
+                    ))
+                    .ok()?;
+                }
+                data
+            }
+            webhook_events::ISSUE_DELETED => serde_json::json!({ "id": issue.id }),
+            webhook_events::ISSUE_MOVED => serde_json::to_value(IssueMoveResult {
+                issue_move: IssueMove {
+                    id: clock::new_id(),
+                    issue_id: issue.id,
+                    from_team_id: Some(Uuid::nil()),
+                    to_team_id: Some(Uuid::nil()),
+                    from_key: "ENG-1".to_string(),
+                    to_key: "OPS-1".to_string(),
+                    from_state_id: None,
+                    to_state_id: None,
+                    moved_by: Uuid::nil(),
+                    moved_at: now,
+                },
+                issue,
+            })
+            .ok()?,
+            webhook_events::COMMENT_CREATED => {
+                let comment = Comment {
+                    id: clock::new_id(),
+                    issue_id: issue.id,
+                    author_id: Uuid::nil(),
+                    content: "Sample comment".to_string(),
+                    created_at: now,
+                    updated_at: now,
+                    content_type: Some("markdown".to_string()),
+                    parent_comment_id: None,
+                    is_edited: Some(false),
+                    is_deleted: Some(false),
+                    hidden_at: None,
+                    hidden_by: None,
+                    hidden_reason: None,
+                };
+                let rendered = MarkdownService::render(&comment.content);
+                serde_json::to_value(WithRenderedMarkdown {
+                    data: &comment,
+                    rendered: Some(RenderedBody::new(rendered, MarkdownEntities::default())),
+                })
+                .ok()?
+            }
+            webhook_events::PROJECT_CREATED | webhook_events::PROJECT_UPDATED => {
+                serde_json::to_value(&project).ok()?
+            }
+            webhook_events::PROJECT_DELETED => serde_json::json!({ "id": project.id }),
+            webhook_events::SESSION_STARTED | webhook_events::SESSION_ENDED => {
+                let ended = event == webhook_events::SESSION_ENDED;
+                let session = WebSocketSession {
+                    id: clock::new_id(),
+                    workspace_id: Uuid::nil(),
+                    user_id: Uuid::nil(),
+                    connection_id: clock::new_id().to_string(),
+                    client: session_clients::WEB.to_string(),
+                    user_agent: Some("Mozilla/5.0".to_string()),
+                    connected_at: now - chrono::Duration::minutes(30),
+                    disconnected_at: ended.then_some(now),
+                };
+                SessionAnalyticsService::event_data(&session, ended.then_some(30 * 60))
+            }
+            _ => return None,
+        };
+        Some(data)
+    }
+
+    /// Queue a synthetic sample of `event` for the app, marked as a test
+    pub fn enqueue_test(
+        conn: &mut PgConnection,
+        app: &OAuthApp,
+        event: &str,
+    ) -> Result<WebhookDelivery, AppError> {
+        let data = Self::sample_data(event, app.id)
+            .ok_or_else(|| AppError::validation(format!("Unknown webhook event: {}", event)))?;
+        let envelope = WebhookEnvelope {
+            id: clock::new_id(),
+            event: event.to_string(),
+            workspace_id: Uuid::nil(),
+            installation_id: Uuid::nil(),
+            created_at: clock::now(),
+            data,
+            test: true,
+        };
+        let payload = serde_json::to_string(&envelope)
+            .map_err(|e| AppError::internal(format!("Failed to encode webhook: {}", e)))?;
+        Ok(WebhookDeliveriesRepo::insert(
+            conn,
+            &NewWebhookDelivery {
+                app_id: Some(app.id),
+                webhook_id: None,
+                installation_id: None,
+                event: event.to_string(),
+                payload,
+                next_attempt_at: Self::send_now_lease(),
+            },
+        )?)
+    }
+
+    /// Queue a copy of a past delivery with the payload exactly as it was sent
+    pub fn enqueue_redelivery(
+        conn: &mut PgConnection,
+        original: &WebhookDelivery,
+    ) -> Result<WebhookDelivery, AppError> {
+        Ok(WebhookDeliveriesRepo::insert(
+            conn,
+            &NewWebhookDelivery {
+                app_id: original.app_id,
+                webhook_id: original.webhook_id,
+                installation_id: original.installation_id,
+                event: original.event.clone(),
+                payload: original.payload.clone(),
+                next_attempt_at: Self::send_now_lease(),
+            },
+        )?)
+    }
+
+    /// First retry time for deliveries the caller sends itself
+    fn send_now_lease() -> chrono::DateTime<Utc> {
+        clock::now() + chrono::Duration::seconds(SEND_NOW_LEASE_SECS)
+    }
+
+    fn enqueue(
+        conn: &mut PgConnection,
+        installation: &AppInstallation,
+        event: &str,
+        data: serde_json::Value,
+    ) -> Result<WebhookDelivery, AppError> {
+        let envelope = WebhookEnvelope {
+            id: clock::new_id(),
+            event: event.to_string(),
+            workspace_id: installation.workspace_id,
+            installation_id: installation.id,
+            created_at: clock::now(),
+            data,
+            test: false,
+        };
+        let payload = serde_json::to_string(&envelope)
+            .map_err(|e| AppError::internal(format!("Failed to encode webhook: {}", e)))?;
+        Ok(WebhookDeliveriesRepo::insert(
+            conn,
+            &NewWebhookDelivery {
+                app_id: Some(installation.app_id),
+                webhook_id: None,
+                installation_id: Some(installation.id),
+                event: event.to_string(),
+                payload,
+                next_attempt_at: clock::now(),
+            },
+        )?)
+    }
+
+    /// Queue `event` for a workspace webhook; test deliveries are sent by the
+    /// caller right away
+    pub fn enqueue_for_webhook(
+        conn: &mut PgConnection,
+        webhook: &Webhook,
+        event: &str,
+        data: serde_json::Value,
+        test: bool,
+    ) -> Result<WebhookDelivery, AppError> {
+        let envelope = WorkspaceWebhookEnvelope {
+            id: clock::new_id(),
+            event: event.to_string(),
+            workspace_id: webhook.workspace_id,
+            webhook_id: webhook.id,
+            created_at: clock::now(),
+            data,
+            test,
+        };
+        let payload = serde_json::to_string(&envelope)
+            .map_err(|e| AppError::internal(format!("Failed to encode webhook: {}", e)))?;
+        Ok(WebhookDeliveriesRepo::insert(
+            conn,
+            &NewWebhookDelivery {
+                app_id: None,
+                webhook_id: Some(webhook.id),
+                installation_id: None,
+                event: event.to_string(),
+                payload,
+                next_attempt_at: if test {
+                    Self::send_now_lease()
+                } else {
+                    clock::now()
+                },
+            },
+        )?)
+    }
+
+    /// Queue an installation lifecycle event for the app, if it has a webhook URL
+    pub fn enqueue_lifecycle(
+        conn: &mut PgConnection,
+        app: &OAuthApp,
+        installation: &AppInstallation,
+        event: &str,
+    ) -> Result<(), AppError> {
+        if app.webhook_url.is_none() {
+            return Ok(());
+        }
+        let data = serde_json::json!({
+            "app_id": app.id,
+            "scopes": installation.scope_list(),
+            "actor_id": installation.installed_by,
+        });
+        Self::enqueue(conn, installation, event, data)?;
+        Ok(())
+    }
+
+    /// Fan a workspace event out to every installed app subscribed to it
+    /// whose webhook filter, if any, matches the event, and to every active
+    /// workspace webhook subscribed to it
+    pub fn emit<T: Serialize>(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        event: &str,
+        data: &T,
+    ) -> Result<usize, AppError> {
+        let targets: Vec<(AppInstallation, Option<WebhookFilter>)> =
+            AppInstallationsRepo::list_active_by_workspace(conn, workspace_id)?
+                .into_iter()
+                .filter(|(installation, app)| {
+                    app.webhook_url.is_some()
+                        && Self::should_deliver(
+                            &app.webhook_event_list(),
+                            &installation.scope_list(),
+                            event,
+                        )
+                })
+                .map(|(installation, app)| (installation, Self::app_filter(&app)))
+                .collect();
+        let webhooks: Vec<Webhook> = WebhooksRepo::list_active_by_workspace(conn, workspace_id)?
+            .into_iter()
+            .filter(|webhook| webhook.subscribes_to(event))
+            .collect();
+        if targets.is_empty() && webhooks.is_empty() {
+            return Ok(0);
+        }
+
+        let mut data = serde_json::to_value(data)
+            .map_err(|e| AppError::internal(format!("Failed to encode webhook: {}", e)))?;
+        Self::attach_external_references(conn, event, &mut data)?;
+        let input = if targets.iter().any(|(_, filter)| filter.is_some()) {
+            Some(Self::filter_input(conn, event, &data)?)
+        } else {
+            None
+        };
+        let mut queued = 0;
+        for (installation, filter) in &targets {
+            if let (Some(filter), Some(input)) = (filter, &input)
+                && !filter.matches(input)
+            {
+                continue;
+            }
+            Self::enqueue(conn, installation, event, data.clone())?;
+            queued += 1;
+        }
+        for webhook in &webhooks {
+            Self::enqueue_for_webhook(conn, webhook, event, data.clone(), false)?;
+            queued += 1;
+        }
+        Ok(queued)
+    }
+
+    /// Issue payloads carry the issue's external references, so integrations
+    /// can match the issue to their own records
+    fn attach_external_references(
+        conn: &mut PgConnection,
+        event: &str,
+        data: &mut serde_json::Value,
+    ) -> Result<(), AppError> {
+        let issue = match event {
+            webhook_events::ISSUE_CREATED | webhook_events::ISSUE_UPDATED => Some(data),
+            webhook_events::ISSUE_MOVED => data.get_mut("issue"),
+            _ => None,
+        };
+        let Some(issue) = issue else {
+            return Ok(());
+        };
+        let Some(issue_id) = issue
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| Uuid::parse_str(id).ok())
+        else {
+            return Ok(());
+        };
+        let references = ExternalReferencesRepo::list_by_issue(conn, issue_id)?;
+        if let Some(object) = issue.as_object_mut() {
+            object.insert(
+                "external_references".to_string(),
+                serde_json::to_value(references)
+                    .map_err(|e| AppError::internal(format!("Failed to encode webhook: {}", e)))?,
+            );
+        }
+        Ok(())
+    }
+
+    /// Parsed filter of an app. Filters are validated when saved, so one that
+    /// no longer parses is logged and ignored rather than dropping events.
+    fn app_filter(app: &OAuthApp) -> Option<WebhookFilter> {
+        let source = app.webhook_filter.as_deref()?;
+        match WebhookFilter::parse(source) {
+            Ok(filter) => Some(filter),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid webhook filter of app {}: {}", app.id, e);
+                None
+            }
+        }
+    }
+
+    /// Team, labels and priority of the issue an event is about, for filters
+    pub fn filter_input(
+        conn: &mut PgConnection,
+        event: &str,
+        data: &serde_json::Value,
+    ) -> Result<FilterInput, AppError> {
+        let mut input = FilterInput {
+            event: event.to_string(),
+            payload: data.clone(),
+            ..Default::default()
+        };
+        let issue_id = match event.split('.').next() {
+            Some("issue") => data.pointer("/issue/id").or_else(|| data.get("id")),
+            Some("comment") => data.get("issue_id"),
+            _ => None,
+        }
+        .and_then(|id| id.as_str())
+        .and_then(|id| Uuid::parse_str(id).ok());
+        let mut team_id = data
+            .get("team_id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| Uuid::parse_str(id).ok());
+
+        if let Some(issue) = issue_id
+            .map(|id| IssueRepo::find_by_id(conn, id))
+            .transpose()?
+            .flatten()
+        {
+            team_id = Some(issue.team_id);
+            input.priority = Some(issue.priority);
+            for label in LabelRepo::list_by_issue(conn, issue.id)? {
+                input.labels.push(label.id.to_string());
+                input.labels.push(label.name);
+            }
+        }
+        if let Some(team_id) = team_id {
+            use crate::schema::teams::dsl as t;
+            input.teams.push(team_id.to_string());
+            if let Some(key) = t::teams
+                .filter(t::id.eq(team_id))
+                .select(t::team_key)
+                .first::<String>(conn)
+                .optional()?
+            {
+                input.teams.push(key);
+            }
+        }
+        Ok(input)
+    }
+
+    /// Like `emit`, but a failure only logs a warning so it never fails the caller
+    pub fn emit_quietly<T: Serialize>(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        event: &str,
+        data: &T,
+    ) {
+        // A savepoint keeps a failure here from aborting the caller's transaction
+        if let Err(e) = with_txn(conn, |conn| Self::emit(conn, workspace_id, event, data)) {
+            tracing::warn!("Failed to queue {} webhooks: {}", event, e);
+        }
+    }
+
+    /// Send every due delivery once and record the outcome
+    pub async fn deliver_due(
+        executor: &DbExecutor,
+        client: &reqwest::Client,
+    ) -> Result<usize, AppError> {
+        let due = executor
+            .read(|conn| {
+                Ok(WebhookDeliveriesRepo::list_due(
+                    conn,
+                    clock::now(),
+                    DELIVERY_BATCH_SIZE,
+                )?)
+            })
+            .await?;
+
+        for delivery in &due {
+            Self::deliver(executor, client, delivery).await?;
+        }
+        Ok(due.len())
+    }
+
+    /// Send one delivery to its app's or workspace webhook's current URL and
+    /// record the outcome
+    pub async fn deliver(
+        executor: &DbExecutor,
+        client: &reqwest::Client,
+        delivery: &WebhookDelivery,
+    ) -> Result<WebhookDelivery, AppError> {
+        let pending = delivery.clone();
+        let target = executor
+            .read(move |conn| Self::target(conn, &pending))
+            .await?;
+        // The HTTP call runs outside any transaction
+        let outcome = match target {
+            Some((url, signature)) => Self::send(client, &url, &signature, delivery).await,
+            None => Err("Webhook is no longer configured".to_string()),
+        };
+
+        let delivery = delivery.clone();
+        executor
+            .transaction(move |conn| Self::record_outcome(conn, &delivery, outcome))
+            .await
+    }
+
+    /// URL and signature header for a delivery; `None` when its app or
+    /// webhook no longer takes deliveries
+    fn target(
+        conn: &mut PgConnection,
+        delivery: &WebhookDelivery,
+    ) -> Result<Option<(String, String)>, AppError> {
+        if let Some(webhook_id) = delivery.webhook_id {
+            return Ok(WebhooksRepo::find(conn, webhook_id)?
+                .filter(|webhook| webhook.is_active)
+                .map(|webhook| {
+                    let signature =
+                        Self::signature_header(&[(None, &webhook.secret)], &delivery.payload);
+                    (webhook.url, signature)
+                }));
+        }
+        let Some(app_id) = delivery.app_id else {
+            return Ok(None);
+        };
+        let app = OAuthAppsRepo::find_app(conn, app_id)?;
+        let keys = WebhookSigningKeysRepo::list_active(conn, app_id, clock::now())?;
+        let Some(url) = app
+            .filter(|app| app.disabled_at.is_none() && !keys.is_empty())
+            .and_then(|app| app.webhook_url)
+        else {
+            return Ok(None);
+        };
+        let keys: Vec<(Option<&str>, &str)> = keys
+            .iter()
+            .map(|k| (Some(k.key_id.as_str()), k.secret.as_str()))
+            .collect();
+        Ok(Some((
+            url,
+            Self::signature_header(&keys, &delivery.payload),
+        )))
+    }
+
+    async fn send(
+        client: &reqwest::Client,
+        url: &str,
+        signature: &str,
+        delivery: &WebhookDelivery,
+    ) -> Result<u16, String> {
+        let response = client
+            .post(url)
+            .timeout(DELIVERY_TIMEOUT)
+            .header("Content-Type", "application/json")
+            .header(EVENT_HEADER, &delivery.event)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(response.status().as_u16())
+    }
+
+    /// Mark a delivery delivered on 2xx; otherwise schedule a retry or give up
+    pub fn record_outcome(
+        conn: &mut PgConnection,
+        delivery: &WebhookDelivery,
+        outcome: Result<u16, String>,
+    ) -> Result<WebhookDelivery, AppError> {
+        let now = clock::now();
+        let attempts = delivery.attempts + 1;
+        let (status, error) = match outcome {
+            Ok(code) if (200..300).contains(&code) => (Some(code as i32), None),
+            Ok(code) => (Some(code as i32), Some(format!("HTTP {}", code))),
+            Err(e) => (None, Some(e)),
+        };
+
+        let attempt = match error {
+            None => WebhookDeliveryAttempt {
+                status: webhook_delivery_status::DELIVERED.to_string(),
+                attempts,
+                response_status: status,
+                last_error: None,
+                next_attempt_at: now,
+                delivered_at: Some(now),
+            },
+            Some(error) => WebhookDeliveryAttempt {
+                status: if attempts >= MAX_DELIVERY_ATTEMPTS {
+                    webhook_delivery_status::FAILED.to_string()
+                } else {
+                    webhook_delivery_status::PENDING.to_string()
+                },
+                attempts,
+                response_status: status,
+                last_error: Some(error),
+                next_attempt_at: now + Self::retry_delay(attempts),
+                delivered_at: None,
+            },
+        };
+        Ok(WebhookDeliveriesRepo::record_attempt(
+            conn,
+            delivery.id,
+            &attempt,
+        )?)
+    }
+}
@@ -0,0 +1,190 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::app_installation::{TestWebhookRequest, WebhookDelivery, webhook_events},
+    db::models::webhook::{
+        CreateWebhookRequest, NewWebhook, UpdateWebhook, UpdateWebhookRequest, Webhook,
+        WebhookConfig,
+    },
+    db::repositories::app_installations::WebhookDeliveriesRepo,
+    db::repositories::webhooks::WebhooksRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::oauth_service::{OAuthService, WEBHOOK_SECRET_PREFIX},
+    services::permission_service::{Permission, PermissionService},
+    services::webhook_service::WebhookService,
+    utils::clock,
+};
+
+/// Most recent deliveries returned in a webhook's delivery log
+const DELIVERY_LOG_LIMIT: i64 = 100;
+
+pub struct WebhooksService;
+
+impl WebhooksService {
+    pub fn list(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<Vec<WebhookConfig>, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageWebhooks)?;
+        Ok(WebhooksRepo::list_by_workspace(conn, ctx.workspace_id)?
+            .into_iter()
+            .map(|webhook| WebhookConfig::new(webhook, None))
+            .collect())
+    }
+
+    /// Register a webhook; its signing secret is only returned here and on rotation
+    pub fn create(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &CreateWebhookRequest,
+    ) -> Result<WebhookConfig, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageWebhooks)?;
+        WebhookService::validate_url(&req.url)?;
+        let events = Self::subscriptions(&req.events)?;
+        let secret = OAuthService::random_secret(WEBHOOK_SECRET_PREFIX);
+        let webhook = WebhooksRepo::insert(
+            conn,
+            &NewWebhook {
+                workspace_id: ctx.workspace_id,
+                url: req.url.clone(),
+                secret: secret.clone(),
+                events,
+                created_by: Some(ctx.user_id),
+            },
+        )?;
+        Ok(WebhookConfig::new(webhook, Some(secret)))
+    }
+
+    pub fn get_by_id(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        webhook_id: Uuid,
+    ) -> Result<WebhookConfig, AppError> {
+        Ok(WebhookConfig::new(Self::find(conn, ctx, webhook_id)?, None))
+    }
+
+    /// Change the URL, subscriptions or active flag; `rotate_secret` replaces
+    /// the signing secret immediately
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        webhook_id: Uuid,
+        req: &UpdateWebhookRequest,
+    ) -> Result<WebhookConfig, AppError> {
+        let webhook = Self::find(conn, ctx, webhook_id)?;
+        if let Some(url) = &req.url {
+            WebhookService::validate_url(url)?;
+        }
+        let events = req.events.as_deref().map(Self::subscriptions).transpose()?;
+        let secret = req
+            .rotate_secret
+            .then(|| OAuthService::random_secret(WEBHOOK_SECRET_PREFIX));
+        let changes = UpdateWebhook {
+            url: req.url.clone(),
+            secret: secret.clone(),
+            events,
+            is_active: req.is_active,
+            updated_at: Some(clock::now()),
+        };
+        let updated = WebhooksRepo::update(conn, webhook.id, &changes)?;
+        Ok(WebhookConfig::new(updated, secret))
+    }
+
+    /// Delete a webhook together with its delivery log
+    pub fn delete(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        webhook_id: Uuid,
+    ) -> Result<(), AppError> {
+        let webhook = Self::find(conn, ctx, webhook_id)?;
+        WebhooksRepo::delete_by_id(conn, webhook.id)?;
+        Ok(())
+    }
+
+    /// Latest deliveries of the webhook, newest first
+    pub fn list_deliveries(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        webhook_id: Uuid,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let webhook = Self::find(conn, ctx, webhook_id)?;
+        Ok(WebhookDeliveriesRepo::list_by_webhook(
+            conn,
+            webhook.id,
+            DELIVERY_LOG_LIMIT,
+        )?)
+    }
+
+    /// Queue a synthetic sample delivery for each requested event type
+    /// (every subscribable type by default), sent even if the webhook is not
+    /// subscribed to it
+    pub fn queue_test_deliveries(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        webhook_id: Uuid,
+        req: &TestWebhookRequest,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let webhook = Self::find(conn, ctx, webhook_id)?;
+        if !webhook.is_active {
+            return Err(AppError::validation("Webhook is disabled"));
+        }
+        let events: Vec<&str> = if req.events.is_empty() {
+            webhook_events::SUBSCRIBABLE.to_vec()
+        } else {
+            req.events.iter().map(String::as_str).collect()
+        };
+        conn.transaction::<_, AppError, _>(|conn| {
+            events
+                .iter()
+                .map(|event| {
+                    let data = webhook_events::is_subscribable(event)
+                        .then(|| WebhookService::sample_data(event, Uuid::nil()))
+                        .flatten()
+                        .ok_or_else(|| {
+                            AppError::validation(format!("Unknown webhook event: {}", event))
+                        })?;
+                    WebhookService::enqueue_for_webhook(conn, &webhook, event, data, true)
+                })
+                .collect()
+        })
+    }
+
+    /// Queue a replay of a past delivery from the webhook's delivery log
+    pub fn queue_redelivery(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        webhook_id: Uuid,
+        delivery_id: Uuid,
+    ) -> Result<WebhookDelivery, AppError> {
+        let webhook = Self::find(conn, ctx, webhook_id)?;
+        if !webhook.is_active {
+            return Err(AppError::validation("Webhook is disabled"));
+        }
+        let original = WebhookDeliveriesRepo::find_for_webhook(conn, webhook.id, delivery_id)?
+            .ok_or_else(|| AppError::not_found("webhook_delivery"))?;
+        WebhookService::enqueue_redelivery(conn, &original)
+    }
+
+    /// Space-separated subscriptions; at least one event is required
+    pub fn subscriptions(requested: &[String]) -> Result<String, AppError> {
+        let events = WebhookService::normalize_events(requested)?;
+        if events.is_empty() {
+            return Err(AppError::validation(
+                "Subscribe the webhook to at least one event",
+            ));
+        }
+        Ok(events.join(" "))
+    }
+
+    fn find(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        webhook_id: Uuid,
+    ) -> Result<Webhook, AppError> {
+        PermissionService::require(conn, ctx, Permission::ManageWebhooks)?;
+        WebhooksRepo::find_in_workspace(conn, ctx.workspace_id, webhook_id)?
+            .ok_or_else(|| AppError::not_found("webhook"))
+    }
+}
@@ -1,13 +1,24 @@
 use diesel::prelude::*;
 
 use crate::{
+    cache::typed::{self, CacheEntry},
+    db::DbExecutor,
+    db::models::audit::{audit_actions, settings_targets},
     db::models::workflow::{NewWorkflow, NewWorkflowState, Workflow, WorkflowState},
     db::repositories::workflows::WorkflowsRepo,
     error::AppError,
     services::context::RequestContext,
+    services::realtime_service::RealtimeService,
+    services::settings_history_service::SettingsHistoryService,
+    utils::clock,
     validation::workflow::{validate_create_state, validate_create_workflow},
+    websocket::{EntityAction, EntityKind},
 };
 
+/// States of a workflow in position order
+pub static WORKFLOW_STATES_CACHE: CacheEntry<Vec<WorkflowState>> =
+    CacheEntry::new("workflow_states", 600);
+
 pub struct WorkflowsService;
 
 impl WorkflowsService {
@@ -22,7 +33,7 @@ impl WorkflowsService {
 
     pub fn create_workflow(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         team_id: uuid::Uuid,
         name: &str,
         description: Option<String>,
@@ -36,12 +47,28 @@ impl WorkflowsService {
             is_default,
         };
         let wf = WorkflowsRepo::insert_workflow(conn, &new_wf)?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKFLOW_CREATED,
+            settings_targets::WORKFLOW,
+            wf.id,
+            None,
+            Some(&wf),
+        )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Workflow,
+            EntityAction::Created,
+            wf.id,
+            &wf,
+        );
         Ok(wf)
     }
 
     pub fn add_state(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         workflow_id: uuid::Uuid,
         req: &crate::db::models::workflow::CreateWorkflowStateRequest,
     ) -> Result<WorkflowState, AppError> {
@@ -56,6 +83,23 @@ impl WorkflowsService {
             is_default: req.is_default.unwrap_or(false),
         };
         let st = WorkflowsRepo::insert_state(conn, &new_state)?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKFLOW_STATE_CREATED,
+            settings_targets::WORKFLOW_STATE,
+            st.id,
+            None,
+            Some(&st),
+        )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::WorkflowState,
+            EntityAction::Created,
+            st.id,
+            &st,
+        );
+        typed::invalidate_after_commit(&WORKFLOW_STATES_CACHE, st.workflow_id);
         Ok(st)
     }
 
@@ -80,11 +124,11 @@ impl WorkflowsService {
 
     pub fn update(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         workflow_id: uuid::Uuid,
         req: &crate::routes::workflows::UpdateWorkflowRequest,
     ) -> Result<Workflow, AppError> {
-        let _existing = WorkflowsRepo::find_by_id(conn, workflow_id)?
+        let existing = WorkflowsRepo::find_by_id(conn, workflow_id)?
             .ok_or_else(|| AppError::not_found("workflow"))?;
 
         let updated = WorkflowsRepo::update_fields(
@@ -94,18 +138,51 @@ impl WorkflowsService {
             req.description.as_deref(),
             req.is_default,
         )?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKFLOW_UPDATED,
+            settings_targets::WORKFLOW,
+            updated.id,
+            Some(&existing),
+            Some(&updated),
+        )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Workflow,
+            EntityAction::Updated,
+            updated.id,
+            &updated,
+        );
         Ok(updated)
     }
 
     pub fn delete(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         workflow_id: uuid::Uuid,
     ) -> Result<(), AppError> {
-        let _existing = WorkflowsRepo::find_by_id(conn, workflow_id)?
+        let existing = WorkflowsRepo::find_by_id(conn, workflow_id)?
             .ok_or_else(|| AppError::not_found("workflow"))?;
 
         WorkflowsRepo::delete_by_id(conn, workflow_id)?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKFLOW_DELETED,
+            settings_targets::WORKFLOW,
+            workflow_id,
+            Some(&existing),
+            None,
+        )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::Workflow,
+            EntityAction::Deleted,
+            workflow_id,
+            &(),
+        );
+        typed::invalidate_after_commit(&WORKFLOW_STATES_CACHE, workflow_id);
         Ok(())
     }
 
@@ -121,6 +198,20 @@ impl WorkflowsService {
         Ok(states)
     }
 
+    /// [`Self::get_states`] served from the installed cache
+    pub async fn get_states_cached(
+        db: &DbExecutor,
+        ctx: &RequestContext,
+        workflow_id: uuid::Uuid,
+    ) -> Result<Vec<WorkflowState>, AppError> {
+        WORKFLOW_STATES_CACHE
+            .get_or_load(typed::installed(), workflow_id, "all", || {
+                let ctx = ctx.clone();
+                db.read(move |conn| Self::get_states(conn, &ctx, workflow_id))
+            })
+            .await
+    }
+
     pub fn get_team_default_states(
         conn: &mut PgConnection,
         _ctx: &RequestContext,
@@ -132,13 +223,13 @@ impl WorkflowsService {
 
     pub fn create_team_default_state(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         team_id: uuid::Uuid,
         req: &crate::routes::workflows::CreateTeamDefaultStateRequest,
     ) -> Result<WorkflowState, AppError> {
         validate_create_state(&req.name, req.position)?;
         let new_state = NewWorkflowState {
-            workflow_id: uuid::Uuid::new_v4(), // This will be handled by the repo
+            workflow_id: clock::new_id(), // This will be handled by the repo
             name: req.name.clone(),
             description: req.description.clone(),
             color: req.color.clone(),
@@ -147,17 +238,34 @@ impl WorkflowsService {
             is_default: true,
         };
         let state = WorkflowsRepo::insert_team_default_state(conn, team_id, &new_state)?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKFLOW_STATE_CREATED,
+            settings_targets::WORKFLOW_STATE,
+            state.id,
+            None,
+            Some(&state),
+        )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::WorkflowState,
+            EntityAction::Created,
+            state.id,
+            &state,
+        );
+        typed::invalidate_after_commit(&WORKFLOW_STATES_CACHE, state.workflow_id);
         Ok(state)
     }
 
     pub fn update_team_default_state(
         conn: &mut PgConnection,
-        _ctx: &RequestContext,
+        ctx: &RequestContext,
         team_id: uuid::Uuid,
         state_id: uuid::Uuid,
         req: &crate::routes::workflows::UpdateTeamDefaultStateRequest,
     ) -> Result<WorkflowState, AppError> {
-        let _existing = WorkflowsRepo::find_team_default_state_by_id(conn, team_id, state_id)?
+        let existing = WorkflowsRepo::find_team_default_state_by_id(conn, team_id, state_id)?
             .ok_or_else(|| AppError::not_found("team_default_state"))?;
 
         let updated = WorkflowsRepo::update_team_default_state_fields(
@@ -169,6 +277,23 @@ impl WorkflowsService {
             req.category.as_ref(),
             req.position,
         )?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKFLOW_STATE_UPDATED,
+            settings_targets::WORKFLOW_STATE,
+            updated.id,
+            Some(&existing),
+            Some(&updated),
+        )?;
+        RealtimeService::entity_changed(
+            ctx.workspace_id,
+            EntityKind::WorkflowState,
+            EntityAction::Updated,
+            updated.id,
+            &updated,
+        );
+        typed::invalidate_after_commit(&WORKFLOW_STATES_CACHE, updated.workflow_id);
         Ok(updated)
     }
 
@@ -207,13 +332,13 @@ impl WorkflowsService {
             };
 
             result.push(crate::db::models::workflow::IssueTransitionResponse {
-                id: uuid::Uuid::new_v4(),
+                id: clock::new_id(),
                 workflow_id: state.workflow_id,
                 from_state_id: issue.workflow_state_id,
                 to_state_id: state.id,
                 name: None,
                 description: None,
-                created_at: chrono::Utc::now(),
+                created_at: clock::now(),
                 from_state,
                 to_state: crate::db::models::workflow::WorkflowStateResponse::from(state),
             });
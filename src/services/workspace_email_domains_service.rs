@@ -0,0 +1,499 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::notification::NotificationDigest,
+    db::models::workspace_email_domain::{
+        EmailDomainCheck, EmailDomainDnsRecord, EmailDomainResponse, NewWorkspaceEmailDomain,
+        SetEmailDomainRequest, WorkspaceEmailDomain, email_domain_status,
+    },
+    db::repositories::audit_log::AuditLogRepo,
+    db::repositories::workspace_email_domains::WorkspaceEmailDomainsRepo,
+    db::repositories::workspaces::WorkspacesRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::permission_service::{Permission, PermissionService},
+    utils::clock,
+};
+
+/// Subdomain of the sending domain that holds the ownership TXT record
+pub const VERIFICATION_RECORD_PREFIX: &str = "_momentum-verification";
+/// Prefix of the ownership TXT record value; the domain's token follows it
+pub const VERIFICATION_VALUE_PREFIX: &str = "momentum-verification=";
+const DEFAULT_LOCAL_PART: &str = "notifications";
+const DEFAULT_DKIM_SELECTOR: &str = "momentum";
+const DNS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+/// DNS record type number of TXT records
+const TXT_RECORD_TYPE: u64 = 16;
+
+/// Looks up TXT records for domain verification
+#[async_trait]
+pub trait DnsResolver: Send + Sync {
+    /// TXT record values of `name`, with multi-string records joined; empty
+    /// when the name has none
+    async fn txt_records(&self, name: &str) -> Result<Vec<String>, AppError>;
+}
+
+/// Resolves through a DNS-over-HTTPS JSON endpoint such as Cloudflare's or
+/// Google's, so verification does not depend on the host's resolver
+pub struct DohResolver {
+    url: String,
+    http: reqwest::Client,
+}
+
+impl DohResolver {
+    pub fn new(url: &str) -> Result<Self, AppError> {
+        let http = reqwest::Client::builder()
+            .timeout(DNS_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::internal(format!("Failed to build DNS client: {}", e)))?;
+        Ok(Self {
+            url: url.to_string(),
+            http,
+        })
+    }
+
+    /// TXT values from a `application/dns-json` response body
+    pub fn parse_txt_answers(body: &serde_json::Value) -> Vec<String> {
+        body["Answer"]
+            .as_array()
+            .map(|answers| {
+                answers
+                    .iter()
+                    .filter(|a| a["type"].as_u64() == Some(TXT_RECORD_TYPE))
+                    .filter_map(|a| a["data"].as_str())
+                    .map(join_txt_strings)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl DnsResolver for DohResolver {
+    async fn txt_records(&self, name: &str) -> Result<Vec<String>, AppError> {
+        let response = self
+            .http
+            .get(&self.url)
+            .query(&[("name", name), ("type", "TXT")])
+            .header("Accept", "application/dns-json")
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("DNS lookup failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::internal(format!(
+                "DNS lookup returned HTTP {}",
+                response.status()
+            )));
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::internal(format!("Invalid DNS response: {}", e)))?;
+        Ok(Self::parse_txt_answers(&body))
+    }
+}
+
+/// `"v=DKIM1; k=rsa; " "p=MIIB..."` -> `v=DKIM1; k=rsa; p=MIIB...`
+fn join_txt_strings(data: &str) -> String {
+    let data = data.trim();
+    if !data.starts_with('"') {
+        return data.to_string();
+    }
+    data.split('"')
+        .skip(1)
+        .step_by(2)
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Custom sending domains for enterprise workspaces, i.e. those that belong
+/// to an organization. Invitation and digest emails are sent from the domain
+/// while it is verified and from the platform sender otherwise.
+pub struct WorkspaceEmailDomainsService;
+
+impl WorkspaceEmailDomainsService {
+    /// Lowercased domain without a trailing dot; needs at least two labels
+    pub fn validate_domain(domain: &str) -> Result<String, AppError> {
+        let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+        let labels: Vec<&str> = domain.split('.').collect();
+        if domain.len() > 253 || labels.len() < 2 || !labels.iter().all(|l| is_dns_label(l)) {
+            return Err(AppError::validation(format!(
+                "Invalid email domain: {}",
+                domain
+            )));
+        }
+        Ok(domain)
+    }
+
+    fn validate_local_part(local_part: &str) -> Result<String, AppError> {
+        let local_part = local_part.trim().to_ascii_lowercase();
+        let valid = !local_part.is_empty()
+            && local_part.len() <= 64
+            && !local_part.starts_with('.')
+            && !local_part.ends_with('.')
+            && !local_part.contains("..")
+            && local_part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "._+-".contains(c));
+        if !valid {
+            return Err(AppError::validation(format!(
+                "Invalid sender address local part: {}",
+                local_part
+            )));
+        }
+        Ok(local_part)
+    }
+
+    fn validate_from_name(name: &str) -> Result<Option<String>, AppError> {
+        let name = name.trim();
+        if name.chars().count() > 100 || name.chars().any(|c| c.is_control() || "<>\"".contains(c))
+        {
+            return Err(AppError::validation(
+                "Sender name must be at most 100 characters without <, > or quotes",
+            ));
+        }
+        Ok((!name.is_empty()).then(|| name.to_string()))
+    }
+
+    pub fn ownership_record_name(domain: &str) -> String {
+        format!("{}.{}", VERIFICATION_RECORD_PREFIX, domain)
+    }
+
+    pub fn dkim_record_name(selector: &str, domain: &str) -> String {
+        format!("{}._domainkey.{}", selector, domain)
+    }
+
+    /// Records the workspace has to publish before the domain verifies
+    pub fn dns_records(domain: &WorkspaceEmailDomain) -> Vec<EmailDomainDnsRecord> {
+        vec![
+            EmailDomainDnsRecord {
+                record_type: "TXT".to_string(),
+                name: Self::ownership_record_name(&domain.domain),
+                value: format!("{}{}", VERIFICATION_VALUE_PREFIX, domain.verification_token),
+                purpose: "ownership".to_string(),
+            },
+            EmailDomainDnsRecord {
+                record_type: "TXT".to_string(),
+                name: Self::dkim_record_name(&domain.dkim_selector, &domain.domain),
+                value: "v=DKIM1; k=rsa; p=<public key from your email provider>".to_string(),
+                purpose: "dkim".to_string(),
+            },
+        ]
+    }
+
+    /// Compare the published TXT records against what the domain expects:
+    /// the ownership token, and a DKIM record with a non-empty public key
+    pub fn evaluate(
+        domain: &WorkspaceEmailDomain,
+        ownership_records: &[String],
+        dkim_records: &[String],
+    ) -> EmailDomainCheck {
+        let expected = format!("{}{}", VERIFICATION_VALUE_PREFIX, domain.verification_token);
+        let ownership_verified = ownership_records.iter().any(|r| r.trim() == expected);
+        let dkim_verified = dkim_records.iter().any(|r| is_dkim_key(r));
+        let error = match (ownership_verified, dkim_verified) {
+            (true, true) => None,
+            (false, _) => Some(format!(
+                "TXT record {} does not contain {}",
+                Self::ownership_record_name(&domain.domain),
+                expected
+            )),
+            (true, false) => Some(format!(
+                "No DKIM public key published at {}",
+                Self::dkim_record_name(&domain.dkim_selector, &domain.domain)
+            )),
+        };
+        EmailDomainCheck {
+            ownership_verified,
+            dkim_verified,
+            error,
+        }
+    }
+
+    /// Look up the domain's records; lookup failures count as a failed check
+    pub async fn check(
+        resolver: &dyn DnsResolver,
+        domain: &WorkspaceEmailDomain,
+    ) -> EmailDomainCheck {
+        let ownership_name = Self::ownership_record_name(&domain.domain);
+        let dkim_name = Self::dkim_record_name(&domain.dkim_selector, &domain.domain);
+        let (ownership, dkim) = futures::join!(
+            resolver.txt_records(&ownership_name),
+            resolver.txt_records(&dkim_name)
+        );
+        match (ownership, dkim) {
+            (Ok(ownership), Ok(dkim)) => Self::evaluate(domain, &ownership, &dkim),
+            (Err(e), _) | (_, Err(e)) => EmailDomainCheck {
+                ownership_verified: false,
+                dkim_verified: false,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Configure the workspace's sending domain. Changing it starts over with
+    /// a new token and sends from the platform domain until verified.
+    pub fn set(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &SetEmailDomainRequest,
+        platform_from: &str,
+    ) -> Result<EmailDomainResponse, AppError> {
+        Self::require_enterprise(conn, ctx)?;
+        let domain = Self::validate_domain(&req.domain)?;
+        let from_local_part = Self::validate_local_part(
+            req.from_local_part.as_deref().unwrap_or(DEFAULT_LOCAL_PART),
+        )?;
+        let dkim_selector = req
+            .dkim_selector
+            .as_deref()
+            .unwrap_or(DEFAULT_DKIM_SELECTOR)
+            .trim()
+            .to_ascii_lowercase();
+        if !is_dns_label(&dkim_selector) {
+            return Err(AppError::validation(format!(
+                "Invalid DKIM selector: {}",
+                dkim_selector
+            )));
+        }
+        let from_name = req
+            .from_name
+            .as_deref()
+            .map(Self::validate_from_name)
+            .transpose()?
+            .flatten();
+
+        let existing = WorkspaceEmailDomainsRepo::find_by_workspace(conn, ctx.workspace_id)?;
+        let unchanged = existing
+            .as_ref()
+            .is_some_and(|e| e.domain == domain && e.dkim_selector == dkim_selector);
+        let new = match existing.filter(|_| unchanged) {
+            // Only the sender changed; the DNS records stay valid
+            Some(existing) => NewWorkspaceEmailDomain {
+                workspace_id: ctx.workspace_id,
+                domain,
+                from_name,
+                from_local_part,
+                dkim_selector,
+                verification_token: existing.verification_token,
+                status: existing.status,
+                last_error: existing.last_error,
+                verified_at: existing.verified_at,
+                last_checked_at: existing.last_checked_at,
+                created_by: existing.created_by,
+                updated_at: clock::now(),
+            },
+            None => NewWorkspaceEmailDomain {
+                workspace_id: ctx.workspace_id,
+                domain,
+                from_name,
+                from_local_part,
+                dkim_selector,
+                verification_token: clock::new_id().simple().to_string(),
+                status: email_domain_status::PENDING.to_string(),
+                last_error: None,
+                verified_at: None,
+                last_checked_at: None,
+                created_by: Some(ctx.user_id),
+                updated_at: clock::now(),
+            },
+        };
+        let record = conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            let record = WorkspaceEmailDomainsRepo::upsert(tx, &new)?;
+            Self::audit(tx, ctx, audit_actions::EMAIL_DOMAIN_SET, &record)?;
+            Ok(record)
+        })?;
+        Ok(Self::to_response(record, platform_from))
+    }
+
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        platform_from: &str,
+    ) -> Result<EmailDomainResponse, AppError> {
+        PermissionService::require(conn, ctx, Permission::UpdateWorkspace)?;
+        let record = WorkspaceEmailDomainsRepo::find_by_workspace(conn, ctx.workspace_id)?
+            .ok_or_else(|| AppError::not_found("email_domain"))?;
+        Ok(Self::to_response(record, platform_from))
+    }
+
+    /// The domain to verify, after checking the caller may manage it
+    pub fn for_verification(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<WorkspaceEmailDomain, AppError> {
+        Self::require_enterprise(conn, ctx)?;
+        WorkspaceEmailDomainsRepo::find_by_workspace(conn, ctx.workspace_id)?
+            .ok_or_else(|| AppError::not_found("email_domain"))
+    }
+
+    /// Store the result of a DNS check. A failed check moves the domain to
+    /// `failed`, so mail falls back to the platform sender until it passes.
+    pub fn record_check(
+        conn: &mut PgConnection,
+        actor_id: Option<Uuid>,
+        domain: &WorkspaceEmailDomain,
+        check: &EmailDomainCheck,
+    ) -> Result<WorkspaceEmailDomain, AppError> {
+        let status = if check.passed() {
+            email_domain_status::VERIFIED
+        } else {
+            email_domain_status::FAILED
+        };
+        let now = clock::now();
+        Ok(conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            let record = WorkspaceEmailDomainsRepo::record_check(
+                tx,
+                domain.id,
+                status,
+                check.error.as_deref(),
+                now,
+            )?;
+            if record.is_verified() && !domain.is_verified() {
+                AuditLogRepo::insert(
+                    tx,
+                    &Self::audit_entry(actor_id, audit_actions::EMAIL_DOMAIN_VERIFIED, &record),
+                )?;
+            } else if !record.is_verified() && domain.is_verified() {
+                tracing::warn!(
+                    "Email domain {} of workspace {} failed verification: {}",
+                    record.domain,
+                    record.workspace_id,
+                    check.error.as_deref().unwrap_or_default()
+                );
+            }
+            Ok(record)
+        })?)
+    }
+
+    pub fn remove(conn: &mut PgConnection, ctx: &RequestContext) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::UpdateWorkspace)?;
+        let record = WorkspaceEmailDomainsRepo::find_by_workspace(conn, ctx.workspace_id)?
+            .ok_or_else(|| AppError::not_found("email_domain"))?;
+        conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            WorkspaceEmailDomainsRepo::delete_by_workspace(tx, ctx.workspace_id)?;
+            Self::audit(tx, ctx, audit_actions::EMAIL_DOMAIN_REMOVED, &record)
+        })?;
+        Ok(())
+    }
+
+    /// Sender for the workspace's mail; `None` means the platform sender
+    pub fn sender_for(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+    ) -> Result<Option<String>, AppError> {
+        Ok(
+            WorkspaceEmailDomainsRepo::find_by_workspace(conn, workspace_id)?
+                .filter(WorkspaceEmailDomain::is_verified)
+                .map(|d| d.sender()),
+        )
+    }
+
+    /// Sender for a notification digest: the workspace's domain when every
+    /// notification in it comes from that one workspace
+    pub fn digest_sender(
+        conn: &mut PgConnection,
+        digest: &NotificationDigest,
+    ) -> Result<Option<String>, AppError> {
+        let mut workspaces = digest.notifications.iter().map(|n| n.workspace_id);
+        match workspaces.next() {
+            Some(first) if workspaces.all(|ws| ws == first) => Self::sender_for(conn, first),
+            _ => Ok(None),
+        }
+    }
+
+    /// Verified domains due for another check; their DNS records may have
+    /// been removed since they verified
+    pub fn due_for_recheck(
+        conn: &mut PgConnection,
+        interval: chrono::Duration,
+        limit: i64,
+    ) -> Result<Vec<WorkspaceEmailDomain>, AppError> {
+        Ok(WorkspaceEmailDomainsRepo::list_verified_checked_before(
+            conn,
+            clock::now() - interval,
+            limit,
+        )?)
+    }
+
+    pub fn to_response(record: WorkspaceEmailDomain, platform_from: &str) -> EmailDomainResponse {
+        let effective_from = if record.is_verified() {
+            record.sender()
+        } else {
+            platform_from.to_string()
+        };
+        EmailDomainResponse {
+            dns_records: Self::dns_records(&record),
+            effective_from,
+            domain: record,
+        }
+    }
+
+    /// Workspace admins of workspaces that belong to an organization
+    fn require_enterprise(conn: &mut PgConnection, ctx: &RequestContext) -> Result<(), AppError> {
+        PermissionService::require(conn, ctx, Permission::UpdateWorkspace)?;
+        let workspace = WorkspacesRepo::find_by_id(conn, ctx.workspace_id)?
+            .ok_or_else(|| AppError::not_found("workspace"))?;
+        if workspace.organization_id.is_none() {
+            return Err(AppError::forbidden(
+                "Custom email domains are available to enterprise workspaces only",
+            ));
+        }
+        Ok(())
+    }
+
+    fn audit(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        action: &str,
+        domain: &WorkspaceEmailDomain,
+    ) -> Result<(), diesel::result::Error> {
+        AuditLogRepo::insert(conn, &Self::audit_entry(Some(ctx.user_id), action, domain))?;
+        Ok(())
+    }
+
+    fn audit_entry(
+        actor_id: Option<Uuid>,
+        action: &str,
+        domain: &WorkspaceEmailDomain,
+    ) -> NewAuditEntry {
+        let details = serde_json::json!({
+            "domain": domain.domain,
+            "from": domain.sender(),
+            "dkim_selector": domain.dkim_selector,
+        });
+        NewAuditEntry {
+            workspace_id: domain.workspace_id,
+            actor_id,
+            action: action.to_string(),
+            target_type: "email_domain".to_string(),
+            target_id: Some(domain.id),
+            details: Some(details.to_string()),
+        }
+    }
+}
+
+fn is_dns_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// A DKIM key record with a public key; `p=` empty means a revoked key
+fn is_dkim_key(record: &str) -> bool {
+    let tags: Vec<(&str, &str)> = record
+        .split(';')
+        .filter_map(|tag| tag.split_once('='))
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .collect();
+    let version_ok = tags
+        .iter()
+        .find(|(k, _)| *k == "v")
+        .is_none_or(|(_, v)| *v == "DKIM1");
+    version_ok && tags.iter().any(|(k, v)| *k == "p" && !v.is_empty())
+}
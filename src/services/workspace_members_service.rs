@@ -1,12 +1,38 @@
 use diesel::prelude::*;
 
 use crate::{
-    db::models::workspace_member::{NewWorkspaceMember, WorkspaceMember},
+    cache::typed::{self, CacheEntry},
+    db::DbExecutor,
+    db::models::audit::{NewAuditEntry, audit_actions},
+    db::models::auth::{User, UserBasicInfo},
+    db::models::workspace_member::{
+        MemberChangeEvent, NewWorkspaceMember, WorkspaceMember, WorkspaceMemberChange,
+        WorkspaceMemberRole, member_change,
+    },
+    db::repositories::audit_log::AuditLogRepo,
+    db::repositories::auth::AuthRepo,
     db::repositories::workspace_members::WorkspaceMembersRepo,
     error::AppError,
+    routes::workspace_members::{MemberDirectoryChanges, MemberDirectoryPage, WorkspaceMemberInfo},
     services::context::RequestContext,
+    services::permission_service::{Permission, PermissionService},
+    services::realtime_service::RealtimeService,
+    services::workspace_settings_service::WorkspaceSettingsService,
+    utils::AssetUrlHelper,
+    websocket::Topic,
 };
 
+/// Page size of the member directory when the client doesn't ask for one
+pub const DEFAULT_DIRECTORY_PAGE_SIZE: i64 = 200;
+pub const MAX_DIRECTORY_PAGE_SIZE: i64 = 500;
+/// Most change log rows folded into one delta response
+pub const MAX_DIRECTORY_CHANGES: i64 = 1000;
+
+/// All members of a workspace with their users, newest first. Avatar URLs
+/// depend on the asset region, which is the variant.
+pub static WORKSPACE_MEMBERS_CACHE: CacheEntry<Vec<WorkspaceMemberInfo>> =
+    CacheEntry::new("workspace_members", 300);
+
 pub struct WorkspaceMembersService;
 
 impl WorkspaceMembersService {
@@ -37,9 +63,54 @@ impl WorkspaceMembersService {
             role,
         };
         let member = WorkspaceMembersRepo::insert(conn, &new_member)?;
+        Self::publish_change(&member, member_change::MEMBER_ADDED);
         Ok(member)
     }
 
+    /// Add a user to every workspace that auto-joins their email domain.
+    /// Only call this once the user has proven they own the email, e.g. via
+    /// a provider's verified email. Returns the user, with the first
+    /// workspace joined as their current one if they had none.
+    pub fn auto_join(conn: &mut PgConnection, user: User) -> Result<User, AppError> {
+        let mut joined = Vec::new();
+        for (workspace_id, role) in
+            WorkspaceSettingsService::auto_join_workspaces(conn, &user.email)?
+        {
+            if WorkspaceMembersRepo::find(conn, workspace_id, user.id)?.is_some() {
+                continue;
+            }
+            let member = WorkspaceMembersRepo::insert(
+                conn,
+                &NewWorkspaceMember {
+                    user_id: user.id,
+                    workspace_id,
+                    role,
+                },
+            )?;
+            AuditLogRepo::insert(
+                conn,
+                &NewAuditEntry {
+                    workspace_id,
+                    actor_id: Some(user.id),
+                    action: audit_actions::WORKSPACE_MEMBER_AUTO_JOINED.to_string(),
+                    target_type: "workspace_member".to_string(),
+                    target_id: Some(user.id),
+                    details: Some(
+                        serde_json::json!({ "email": user.email, "role": member.role }).to_string(),
+                    ),
+                },
+            )?;
+            Self::publish_change(&member, member_change::MEMBER_ADDED);
+            joined.push(workspace_id);
+        }
+        match joined.first() {
+            Some(&workspace_id) if user.current_workspace_id.is_none() => Ok(
+                AuthRepo::update_current_workspace(conn, user.id, workspace_id)?,
+            ),
+            _ => Ok(user),
+        }
+    }
+
     pub fn invite_member(
         conn: &mut PgConnection,
         ctx: &RequestContext,
@@ -66,6 +137,7 @@ impl WorkspaceMembersService {
                 role: req.role.clone(),
             };
             let member = WorkspaceMembersRepo::insert(conn, &new_member)?;
+            Self::publish_change(&member, member_change::MEMBER_ADDED);
             Ok(member)
         } else {
             // User doesn't exist, create invitation
@@ -126,15 +198,53 @@ impl WorkspaceMembersService {
         user_id: Option<uuid::Uuid>,
         search: Option<String>,
     ) -> Result<Vec<crate::routes::workspace_members::WorkspaceMemberInfo>, AppError> {
-        let mut members = WorkspaceMembersRepo::list_by_workspace(conn, workspace_id)?;
+        let members = Self::load_workspace_members(conn, asset_helper, workspace_id)?;
+        Ok(Self::filter_members(
+            members,
+            role,
+            user_id,
+            search.as_deref(),
+        ))
+    }
 
-        // Apply filters
-        if let Some(role_filter) = role {
-            members.retain(|member| member.role == role_filter);
-        }
-        if let Some(user_filter) = user_id {
-            members.retain(|member| member.user_id == user_filter);
-        }
+    /// [`Self::get_workspace_members_with_search`] served from the installed
+    /// cache; the whole member list is cached and filtered in memory
+    pub async fn get_workspace_members_cached(
+        db: &DbExecutor,
+        asset_helper: &AssetUrlHelper,
+        workspace_id: uuid::Uuid,
+        role: Option<WorkspaceMemberRole>,
+        user_id: Option<uuid::Uuid>,
+        search: Option<String>,
+    ) -> Result<Vec<WorkspaceMemberInfo>, AppError> {
+        let members = WORKSPACE_MEMBERS_CACHE
+            .get_or_load(
+                typed::installed(),
+                workspace_id,
+                asset_helper.base_url(),
+                || {
+                    let asset_helper = asset_helper.clone();
+                    db.read(move |conn| {
+                        Self::load_workspace_members(conn, &asset_helper, workspace_id)
+                    })
+                },
+            )
+            .await?;
+        Ok(Self::filter_members(
+            members,
+            role,
+            user_id,
+            search.as_deref(),
+        ))
+    }
+
+    /// Every member of the workspace with their user
+    fn load_workspace_members(
+        conn: &mut PgConnection,
+        asset_helper: &AssetUrlHelper,
+        workspace_id: uuid::Uuid,
+    ) -> Result<Vec<WorkspaceMemberInfo>, AppError> {
+        let members = WorkspaceMembersRepo::list_by_workspace(conn, workspace_id)?;
 
         let mut member_infos = Vec::new();
         for member in members {
@@ -145,18 +255,6 @@ impl WorkspaceMembersService {
                 .optional()?
                 .ok_or_else(|| AppError::internal("Failed to retrieve user"))?;
 
-            // Apply search filter
-            if let Some(ref search_term) = search {
-                let search_lower = search_term.to_lowercase();
-                let name_match = user.name.to_lowercase().contains(&search_lower);
-                let username_match = user.username.to_lowercase().contains(&search_lower);
-                let email_match = user.email.to_lowercase().contains(&search_lower);
-
-                if !name_match && !username_match && !email_match {
-                    continue;
-                }
-            }
-
             let processed_avatar_url = user
                 .avatar_url
                 .as_ref()
@@ -169,7 +267,7 @@ impl WorkspaceMembersService {
                 avatar_url: processed_avatar_url,
             };
 
-            member_infos.push(crate::routes::workspace_members::WorkspaceMemberInfo {
+            member_infos.push(WorkspaceMemberInfo {
                 id: member.user_id,
                 user_id: member.user_id,
                 workspace_id: member.workspace_id,
@@ -183,6 +281,31 @@ impl WorkspaceMembersService {
         Ok(member_infos)
     }
 
+    /// Members with `role`, the member `user_id`, and members whose name,
+    /// username or email contains `search`, ignoring case
+    pub fn filter_members(
+        mut members: Vec<WorkspaceMemberInfo>,
+        role: Option<WorkspaceMemberRole>,
+        user_id: Option<uuid::Uuid>,
+        search: Option<&str>,
+    ) -> Vec<WorkspaceMemberInfo> {
+        if let Some(role_filter) = role {
+            members.retain(|member| member.role == role_filter);
+        }
+        if let Some(user_filter) = user_id {
+            members.retain(|member| member.user_id == user_filter);
+        }
+        if let Some(search_term) = search {
+            let search_lower = search_term.to_lowercase();
+            members.retain(|member| {
+                member.user.name.to_lowercase().contains(&search_lower)
+                    || member.user.username.to_lowercase().contains(&search_lower)
+                    || member.user.email.to_lowercase().contains(&search_lower)
+            });
+        }
+        members
+    }
+
     pub fn get_members_and_invitations(
         conn: &mut PgConnection,
         ctx: &RequestContext,
@@ -237,4 +360,228 @@ impl WorkspaceMembersService {
             search,
         )
     }
+
+    /// One page of the member directory. `cursor` is the membership change
+    /// log position taken before reading; clients keep the one from the first
+    /// page and pass it to [`Self::directory_changes`] once they have paged
+    /// through, so nothing that changed meanwhile is missed.
+    pub fn directory_page(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        asset_helper: &AssetUrlHelper,
+        after: Option<uuid::Uuid>,
+        limit: Option<i64>,
+    ) -> Result<MemberDirectoryPage, AppError> {
+        Self::require_member(conn, ctx)?;
+        let limit = Self::page_size(limit);
+        let cursor = WorkspaceMembersRepo::latest_change_id(conn, ctx.workspace_id)?;
+        let mut rows =
+            WorkspaceMembersRepo::page_with_users(conn, ctx.workspace_id, after, limit + 1)?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+
+        let members: Vec<WorkspaceMemberInfo> = rows
+            .into_iter()
+            .map(|(member, user)| Self::member_info(member, user, asset_helper))
+            .collect();
+        let next_after = if has_more {
+            members.last().map(|m| m.user_id)
+        } else {
+            None
+        };
+        Ok(MemberDirectoryPage {
+            members,
+            next_after,
+            cursor,
+        })
+    }
+
+    /// Members added, re-roled or removed since `since`: current details of
+    /// everyone still in the workspace and the ids of those who left
+    pub fn directory_changes(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        asset_helper: &AssetUrlHelper,
+        since: i64,
+    ) -> Result<MemberDirectoryChanges, AppError> {
+        Self::require_member(conn, ctx)?;
+        if since < 0 {
+            return Err(AppError::validation("since must not be negative"));
+        }
+        let mut changes = WorkspaceMembersRepo::changes_since(
+            conn,
+            ctx.workspace_id,
+            since,
+            MAX_DIRECTORY_CHANGES + 1,
+        )?;
+        let has_more = changes.len() as i64 > MAX_DIRECTORY_CHANGES;
+        changes.truncate(MAX_DIRECTORY_CHANGES as usize);
+        let cursor = changes.last().map_or(since, |c| c.id);
+
+        let touched = Self::touched_users(&changes);
+        let rows = WorkspaceMembersRepo::find_with_users(conn, ctx.workspace_id, &touched)?;
+        let removed = touched
+            .iter()
+            .filter(|user_id| !rows.iter().any(|(member, _)| member.user_id == **user_id))
+            .copied()
+            .collect();
+        Ok(MemberDirectoryChanges {
+            since,
+            cursor,
+            has_more,
+            members: rows
+                .into_iter()
+                .map(|(member, user)| Self::member_info(member, user, asset_helper))
+                .collect(),
+            removed,
+        })
+    }
+
+    /// Change a member's role. Only owners may grant or take away ownership,
+    /// and the last owner can't be demoted.
+    pub fn update_role(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        user_id: uuid::Uuid,
+        role: WorkspaceMemberRole,
+    ) -> Result<WorkspaceMember, AppError> {
+        let caller_role = PermissionService::require(conn, ctx, Permission::ManageMembers)?;
+        let member = WorkspaceMembersRepo::find(conn, ctx.workspace_id, user_id)?
+            .ok_or_else(|| AppError::not_found("workspace_member"))?;
+        if member.role == role {
+            return Ok(member);
+        }
+        Self::check_owner_change(conn, ctx, &caller_role, &member, Some(&role))?;
+
+        let member = WorkspaceMembersRepo::update_role(conn, ctx.workspace_id, user_id, &role)?;
+        Self::publish_change(&member, member_change::ROLE_CHANGED);
+        Ok(member)
+    }
+
+    /// Remove a member from the workspace and its teams
+    pub fn remove(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        user_id: uuid::Uuid,
+    ) -> Result<(), AppError> {
+        let caller_role = PermissionService::require(conn, ctx, Permission::ManageMembers)?;
+        let member = WorkspaceMembersRepo::find(conn, ctx.workspace_id, user_id)?
+            .ok_or_else(|| AppError::not_found("workspace_member"))?;
+        Self::check_owner_change(conn, ctx, &caller_role, &member, None)?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|tx| {
+            WorkspaceMembersRepo::delete_team_memberships(tx, ctx.workspace_id, user_id)?;
+            WorkspaceMembersRepo::delete(tx, ctx.workspace_id, user_id)
+        })?;
+        RealtimeService::publish(
+            ctx.workspace_id,
+            Topic::Workspace,
+            member_change::MEMBER_REMOVED,
+            &MemberChangeEvent {
+                user_id,
+                role: None,
+            },
+        );
+        typed::invalidate_after_commit(&WORKSPACE_MEMBERS_CACHE, ctx.workspace_id);
+        Ok(())
+    }
+
+    /// Tell subscribed clients about a member who joined or changed role and
+    /// drop the workspace's cached member list
+    pub fn publish_change(member: &WorkspaceMember, event: &str) {
+        RealtimeService::publish(
+            member.workspace_id,
+            Topic::Workspace,
+            event,
+            &MemberChangeEvent {
+                user_id: member.user_id,
+                role: Some(member.role.clone()),
+            },
+        );
+        typed::invalidate_after_commit(&WORKSPACE_MEMBERS_CACHE, member.workspace_id);
+    }
+
+    /// Clamp a requested page size to `1..=MAX_DIRECTORY_PAGE_SIZE`
+    pub fn page_size(limit: Option<i64>) -> i64 {
+        limit
+            .unwrap_or(DEFAULT_DIRECTORY_PAGE_SIZE)
+            .clamp(1, MAX_DIRECTORY_PAGE_SIZE)
+    }
+
+    /// Users named in `changes`, each once, in the order first seen
+    pub fn touched_users(changes: &[WorkspaceMemberChange]) -> Vec<uuid::Uuid> {
+        let mut users = Vec::new();
+        for change in changes {
+            if !users.contains(&change.user_id) {
+                users.push(change.user_id);
+            }
+        }
+        users
+    }
+
+    /// Ownership may only be granted or taken away by an owner, and the
+    /// workspace must keep at least one owner. `new_role` is `None` when the
+    /// member is being removed.
+    fn check_owner_change(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        caller_role: &WorkspaceMemberRole,
+        member: &WorkspaceMember,
+        new_role: Option<&WorkspaceMemberRole>,
+    ) -> Result<(), AppError> {
+        let was_owner = member.role == WorkspaceMemberRole::Owner;
+        let becomes_owner = new_role == Some(&WorkspaceMemberRole::Owner);
+        if (was_owner || becomes_owner) && *caller_role != WorkspaceMemberRole::Owner {
+            return Err(AppError::forbidden(
+                "Only workspace owners can grant or revoke ownership",
+            ));
+        }
+        if was_owner
+            && !becomes_owner
+            && WorkspaceMembersRepo::count_with_role(
+                conn,
+                ctx.workspace_id,
+                &WorkspaceMemberRole::Owner,
+            )? <= 1
+        {
+            return Err(AppError::conflict_with_code(
+                "The workspace must keep at least one owner",
+                None,
+                "LAST_OWNER",
+            ));
+        }
+        Ok(())
+    }
+
+    fn require_member(conn: &mut PgConnection, ctx: &RequestContext) -> Result<(), AppError> {
+        PermissionService::current_role(conn, ctx)?
+            .map(|_| ())
+            .ok_or_else(|| AppError::forbidden("You are not a member of this workspace"))
+    }
+
+    fn member_info(
+        member: WorkspaceMember,
+        user: User,
+        asset_helper: &AssetUrlHelper,
+    ) -> WorkspaceMemberInfo {
+        let avatar_url = user
+            .avatar_url
+            .as_ref()
+            .map(|url| asset_helper.process_url(url));
+        WorkspaceMemberInfo {
+            id: member.user_id,
+            user_id: member.user_id,
+            workspace_id: member.workspace_id,
+            user: UserBasicInfo {
+                id: user.id,
+                name: user.name,
+                username: user.username,
+                email: user.email,
+                avatar_url,
+            },
+            role: member.role,
+            created_at: member.created_at.naive_utc(),
+            updated_at: member.updated_at.naive_utc(),
+        }
+    }
 }
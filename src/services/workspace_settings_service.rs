@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    db::models::audit::{audit_actions, settings_targets},
+    db::models::workspace_member::WorkspaceMemberRole,
+    db::models::workspace_settings::{
+        MAX_ARCHIVE_COMPLETED_AFTER_DAYS, UpdateWorkspaceSettingsRequest, WorkspaceSettings,
+        WorkspaceSettingsRecord, email_domain, week_start_days, workspace_features,
+    },
+    db::repositories::teams::TeamsRepo,
+    db::repositories::workflows::WorkflowsRepo,
+    db::repositories::workspace_settings::WorkspaceSettingsRepo,
+    error::AppError,
+    services::context::RequestContext,
+    services::error_tracking_service::ErrorTrackingService,
+    services::permission_service::{Permission, PermissionService},
+    services::settings_history_service::SettingsHistoryService,
+    services::teams_service::TeamsService,
+    services::workspace_email_domains_service::WorkspaceEmailDomainsService,
+    utils::clock,
+};
+
+const MAX_ALLOWED_EMAIL_DOMAINS: usize = 50;
+
+/// Workspace-level options other services read in place of their built-in
+/// defaults. Workspaces that never changed a setting have no row and get
+/// [`WorkspaceSettings::defaults`].
+pub struct WorkspaceSettingsService;
+
+impl WorkspaceSettingsService {
+    pub fn get(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+    ) -> Result<WorkspaceSettings, AppError> {
+        Self::load(conn, ctx.workspace_id)
+    }
+
+    /// Change the given settings; the others keep their value
+    pub fn update(
+        conn: &mut PgConnection,
+        ctx: &RequestContext,
+        req: &UpdateWorkspaceSettingsRequest,
+    ) -> Result<WorkspaceSettings, AppError> {
+        PermissionService::require(conn, ctx, Permission::UpdateWorkspace)?;
+        let before = Self::load(conn, ctx.workspace_id)?;
+        let mut after = before.clone();
+
+        if let Some(default_workflow_id) = req.default_workflow_id {
+            if let Some(workflow_id) = default_workflow_id {
+                let workflow = WorkflowsRepo::find_by_id(conn, workflow_id)?
+                    .ok_or_else(|| AppError::not_found("workflow"))?;
+                if TeamsRepo::find_in_workspace(conn, ctx.workspace_id, workflow.team_id)?.is_none()
+                {
+                    return Err(AppError::not_found("workflow"));
+                }
+            }
+            after.default_workflow_id = default_workflow_id;
+        }
+        if let Some(day) = &req.week_start_day {
+            let day = day.trim().to_ascii_lowercase();
+            if week_start_days::weekday(&day).is_none() {
+                return Err(AppError::validation(format!(
+                    "Week start day must be one of: {}",
+                    week_start_days::ALL.join(", ")
+                )));
+            }
+            after.week_start_day = day;
+        }
+        if let Some(scale) = &req.estimate_scale {
+            TeamsService::validate_estimate_scale(scale)?;
+            after.estimate_scale = scale.clone();
+        }
+        if let Some(domains) = &req.allowed_email_domains {
+            after.allowed_email_domains = Self::validate_email_domains(domains)?;
+        }
+        if let Some(features) = &req.features {
+            for (feature, enabled) in features {
+                if !workspace_features::ALL.contains(&feature.as_str()) {
+                    return Err(AppError::validation(format!(
+                        "Unknown feature '{}'; expected one of: {}",
+                        feature,
+                        workspace_features::ALL.join(", ")
+                    )));
+                }
+                after.features.insert(feature.clone(), *enabled);
+            }
+        }
+
+        if let Some(days) = req.archive_completed_after_days {
+            if let Some(days) = days
+                && !(1..=MAX_ARCHIVE_COMPLETED_AFTER_DAYS).contains(&days)
+            {
+                return Err(AppError::validation(format!(
+                    "archive_completed_after_days must be between 1 and {}",
+                    MAX_ARCHIVE_COMPLETED_AFTER_DAYS
+                )));
+            }
+            after.archive_completed_after_days = days;
+        }
+        if let Some(domains) = &req.auto_join_domains {
+            after.auto_join_domains = Self::validate_email_domains(domains)?;
+        }
+        if let Some(role) = &req.auto_join_role {
+            if !matches!(
+                role,
+                WorkspaceMemberRole::Member | WorkspaceMemberRole::Guest
+            ) {
+                return Err(AppError::validation(
+                    "Auto-joined users can only be members or guests",
+                ));
+            }
+            after.auto_join_role = role.clone();
+        }
+        // Invitations could not reach these users, so they must not get in
+        // through auto-join either
+        if !after.allowed_email_domains.is_empty()
+            && let Some(domain) = after
+                .auto_join_domains
+                .iter()
+                .find(|domain| !after.allowed_email_domains.contains(domain))
+        {
+            return Err(AppError::validation(format!(
+                "Auto-join domain {} is not one of the allowed email domains",
+                domain
+            )));
+        }
+
+        let now = clock::now();
+        let record = WorkspaceSettingsRepo::upsert(
+            conn,
+            &WorkspaceSettingsRecord {
+                workspace_id: ctx.workspace_id,
+                default_workflow_id: after.default_workflow_id,
+                week_start_day: after.week_start_day.clone(),
+                estimate_scale: after.estimate_scale.clone(),
+                allowed_email_domains: after.allowed_email_domains.join(" "),
+                features: Self::encode_features(&after.features)?,
+                updated_by: Some(ctx.user_id),
+                created_at: now,
+                updated_at: now,
+                archive_completed_after_days: after.archive_completed_after_days,
+                auto_join_domains: after.auto_join_domains.join(" "),
+                auto_join_role: after.auto_join_role.clone(),
+            },
+        )?;
+        let after = Self::from_record(record);
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKSPACE_SETTINGS_UPDATED,
+            settings_targets::WORKSPACE,
+            ctx.workspace_id,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    }
+
+    /// Settings of a workspace, with defaults for anything never set
+    pub fn load(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+    ) -> Result<WorkspaceSettings, AppError> {
+        Ok(match WorkspaceSettingsRepo::find(conn, workspace_id)? {
+            Some(record) => Self::from_record(record),
+            None => WorkspaceSettings::defaults(workspace_id),
+        })
+    }
+
+    pub fn feature_enabled(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        feature: &str,
+    ) -> Result<bool, AppError> {
+        Ok(Self::load(conn, workspace_id)?.feature_enabled(feature))
+    }
+
+    /// Workflow and state for a new issue of `team_id` that names neither:
+    /// the workspace's default workflow when it belongs to the team and its
+    /// open default state, else none
+    pub fn default_workflow(
+        conn: &mut PgConnection,
+        workspace_id: Uuid,
+        team_id: Uuid,
+    ) -> Result<(Option<Uuid>, Option<Uuid>), AppError> {
+        let Some(workflow_id) = Self::load(conn, workspace_id)?.default_workflow_id else {
+            return Ok((None, None));
+        };
+        match WorkflowsRepo::find_by_id(conn, workflow_id)? {
+            Some(workflow) if workflow.team_id == team_id => {
+                let states = WorkflowsRepo::list_states_by_workflow(conn, workflow.id)?;
+                let state_id = ErrorTrackingService::open_state(&states).map(|state| state.id);
+                Ok((Some(workflow.id), state_id))
+            }
+            _ => Ok((None, None)),
+        }
+    }
+
+    /// Workspaces a user registering with `email` joins automatically, with
+    /// the role each grants
+    pub fn auto_join_workspaces(
+        conn: &mut PgConnection,
+        email: &str,
+    ) -> Result<Vec<(Uuid, WorkspaceMemberRole)>, AppError> {
+        let domain = email_domain(email);
+        if domain.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(WorkspaceSettingsRepo::list_auto_join(conn, &domain)?
+            .into_iter()
+            .map(Self::from_record)
+            .filter(|settings| settings.auto_joins_email(email))
+            .map(|settings| (settings.workspace_id, settings.auto_join_role))
+            .collect())
+    }
+
+    /// Lowercased domains without a leading `@`, deduplicated in order
+    pub fn validate_email_domains(domains: &[String]) -> Result<Vec<String>, AppError> {
+        let mut validated: Vec<String> = Vec::new();
+        for domain in domains {
+            let domain = WorkspaceEmailDomainsService::validate_domain(
+                domain.trim().trim_start_matches('@'),
+            )?;
+            if !validated.contains(&domain) {
+                validated.push(domain);
+            }
+        }
+        if validated.len() > MAX_ALLOWED_EMAIL_DOMAINS {
+            return Err(AppError::validation(format!(
+                "At most {} email domains can be allowed",
+                MAX_ALLOWED_EMAIL_DOMAINS
+            )));
+        }
+        Ok(validated)
+    }
+
+    pub fn from_record(record: WorkspaceSettingsRecord) -> WorkspaceSettings {
+        let mut settings = WorkspaceSettings::defaults(record.workspace_id);
+        match serde_json::from_str::<BTreeMap<String, bool>>(&record.features) {
+            Ok(features) => {
+                for (feature, enabled) in features {
+                    if let Some(state) = settings.features.get_mut(&feature) {
+                        *state = enabled;
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(
+                "Ignoring unreadable feature toggles of workspace {}: {}",
+                record.workspace_id,
+                e
+            ),
+        }
+        WorkspaceSettings {
+            default_workflow_id: record.default_workflow_id,
+            week_start_day: record.week_start_day,
+            estimate_scale: record.estimate_scale,
+            allowed_email_domains: record
+                .allowed_email_domains
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            archive_completed_after_days: record.archive_completed_after_days,
+            auto_join_domains: record
+                .auto_join_domains
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            auto_join_role: record.auto_join_role,
+            updated_at: Some(record.updated_at),
+            ..settings
+        }
+    }
+
+    /// Only toggles that differ from their default are stored, so features
+    /// added later start out on
+    fn encode_features(features: &BTreeMap<String, bool>) -> Result<String, AppError> {
+        let changed: BTreeMap<&String, &bool> =
+            features.iter().filter(|(_, enabled)| !**enabled).collect();
+        serde_json::to_string(&changed)
+            .map_err(|e| AppError::internal(format!("Failed to encode feature toggles: {}", e)))
+    }
+}
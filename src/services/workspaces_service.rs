@@ -1,9 +1,14 @@
 use diesel::prelude::*;
 
 use crate::{
+    db::models::audit::{audit_actions, settings_targets},
+    db::models::delete_confirmation::{DeleteConfirmation, delete_targets},
     db::models::workspace::{NewWorkspace, Workspace},
+    db::repositories::delete_confirmations::DeleteConfirmationsRepo,
     db::repositories::workspaces::WorkspacesRepo,
     error::AppError,
+    services::delete_confirmations_service::DeleteConfirmationsService,
+    services::settings_history_service::SettingsHistoryService,
 };
 
 pub struct WorkspacesService;
@@ -73,14 +78,63 @@ impl WorkspacesService {
             req.url_key.as_deref(),
             req.logo_url.as_deref(),
         )?;
+        SettingsHistoryService::record(
+            conn,
+            ctx,
+            audit_actions::WORKSPACE_SETTINGS_UPDATED,
+            settings_targets::WORKSPACE,
+            updated.id,
+            Some(&existing),
+            Some(&updated),
+        )?;
         Ok(updated)
     }
 
+    /// First step of deleting a workspace: a confirmation token and what
+    /// the delete would take with it
+    pub fn request_delete(
+        conn: &mut PgConnection,
+        ctx: &crate::services::context::RequestContext,
+        workspace_id: uuid::Uuid,
+    ) -> Result<DeleteConfirmation, AppError> {
+        let existing = Self::deletable(conn, ctx, workspace_id)?;
+        let impact = DeleteConfirmationsRepo::workspace_impact(conn, workspace_id)?;
+        DeleteConfirmationsService::request(
+            conn,
+            ctx,
+            delete_targets::WORKSPACE,
+            workspace_id,
+            &existing.name,
+            impact,
+        )
+    }
+
+    /// Delete the workspace with a token from [`Self::request_delete`]
     pub fn delete(
         conn: &mut PgConnection,
         ctx: &crate::services::context::RequestContext,
         workspace_id: uuid::Uuid,
+        confirmation_token: &str,
     ) -> Result<(), AppError> {
+        Self::deletable(conn, ctx, workspace_id)?;
+        conn.transaction::<_, AppError, _>(|conn| {
+            DeleteConfirmationsService::confirm(
+                conn,
+                ctx,
+                delete_targets::WORKSPACE,
+                workspace_id,
+                confirmation_token,
+            )?;
+            WorkspacesRepo::delete_by_id(conn, workspace_id)?;
+            Ok(())
+        })
+    }
+
+    fn deletable(
+        conn: &mut PgConnection,
+        ctx: &crate::services::context::RequestContext,
+        workspace_id: uuid::Uuid,
+    ) -> Result<Workspace, AppError> {
         let existing = WorkspacesRepo::find_by_id(conn, workspace_id)?
             .ok_or_else(|| AppError::not_found("workspace"))?;
 
@@ -88,8 +142,6 @@ impl WorkspacesService {
         if existing.id != ctx.workspace_id {
             return Err(AppError::auth("Cannot delete this workspace"));
         }
-
-        WorkspacesRepo::delete_by_id(conn, workspace_id)?;
-        Ok(())
+        Ok(existing)
     }
 }
@@ -0,0 +1,245 @@
+//! Supervised background tasks
+//!
+//! Long-running loops register here by name instead of being spawned
+//! fire-and-forget. A task that panics is restarted with exponential backoff,
+//! its state is reported by `/readyz`, and every task is stopped on shutdown.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    /// Panicked and waiting to be restarted
+    Backoff,
+    /// Returned on its own; not restarted
+    Finished,
+    /// Stopped by shutdown
+    Stopped,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TaskHealth {
+    pub name: String,
+    pub state: TaskState,
+    pub restarts: u32,
+    pub last_started_at: DateTime<Utc>,
+    pub last_panic: Option<String>,
+    pub last_panic_at: Option<DateTime<Utc>>,
+}
+
+/// Restart delays: `initial`, doubling per consecutive panic up to `max`
+#[derive(Debug, Clone)]
+pub struct BackoffPolicy {
+    pub initial: Duration,
+    pub max: Duration,
+    /// A run at least this long resets the delay back to `initial`
+    pub reset_after: Duration,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            reset_after: Duration::from_secs(300),
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before restarting after `failures` consecutive panics (1-based)
+    pub fn delay(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+#[derive(Clone)]
+pub struct TaskSupervisor {
+    tasks: Arc<RwLock<BTreeMap<String, TaskHealth>>>,
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    shutdown: Arc<watch::Sender<bool>>,
+    backoff: BackoffPolicy,
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskSupervisor {
+    pub fn new() -> Self {
+        Self::with_backoff(BackoffPolicy::default())
+    }
+
+    pub fn with_backoff(backoff: BackoffPolicy) -> Self {
+        let (shutdown, _) = watch::channel(false);
+        Self {
+            tasks: Arc::new(RwLock::new(BTreeMap::new())),
+            handles: Arc::new(Mutex::new(Vec::new())),
+            shutdown: Arc::new(shutdown),
+            backoff,
+        }
+    }
+
+    /// Register and start a named task. `task` is called again to build a
+    /// fresh future each time the previous run panics. Names must be unique;
+    /// a duplicate is logged and ignored.
+    pub fn spawn<F, Fut>(&self, name: impl Into<String>, task: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        {
+            let mut tasks = self.tasks.write().unwrap();
+            if tasks.contains_key(&name) {
+                tracing::warn!("Background task {} is already registered", name);
+                return;
+            }
+            tasks.insert(
+                name.clone(),
+                TaskHealth {
+                    name: name.clone(),
+                    state: TaskState::Running,
+                    restarts: 0,
+                    last_started_at: Utc::now(),
+                    last_panic: None,
+                    last_panic_at: None,
+                },
+            );
+        }
+
+        let supervisor = self.clone();
+        let handle = tokio::spawn(async move { supervisor.supervise(name, task).await });
+        self.handles.lock().unwrap().push(handle);
+    }
+
+    async fn supervise<F, Fut>(self, name: String, task: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut shutdown = self.shutdown.subscribe();
+        let mut failures = 0u32;
+
+        loop {
+            if *shutdown.borrow() {
+                self.set_state(&name, TaskState::Stopped);
+                return;
+            }
+
+            let started = Instant::now();
+            self.update(&name, |health| {
+                health.state = TaskState::Running;
+                health.last_started_at = Utc::now();
+            });
+
+            let mut handle = tokio::spawn(task());
+            let result = tokio::select! {
+                result = &mut handle => result,
+                _ = shutdown.changed() => {
+                    handle.abort();
+                    let _ = handle.await;
+                    self.set_state(&name, TaskState::Stopped);
+                    return;
+                }
+            };
+
+            let error = match result {
+                Ok(()) => {
+                    tracing::info!("Background task {} finished", name);
+                    self.set_state(&name, TaskState::Finished);
+                    return;
+                }
+                Err(error) if error.is_panic() => error,
+                Err(_) => {
+                    self.set_state(&name, TaskState::Stopped);
+                    return;
+                }
+            };
+
+            let message = panic_message(error.into_panic());
+            if started.elapsed() >= self.backoff.reset_after {
+                failures = 0;
+            }
+            failures += 1;
+            let delay = self.backoff.delay(failures);
+            tracing::error!(
+                "Background task {} panicked: {}; restarting in {:?}",
+                name,
+                message,
+                delay
+            );
+            self.update(&name, |health| {
+                health.state = TaskState::Backoff;
+                health.restarts += 1;
+                health.last_panic = Some(message);
+                health.last_panic_at = Some(Utc::now());
+            });
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.changed() => {
+                    self.set_state(&name, TaskState::Stopped);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut TaskHealth)) {
+        if let Some(health) = self.tasks.write().unwrap().get_mut(name) {
+            f(health);
+        }
+    }
+
+    fn set_state(&self, name: &str, state: TaskState) {
+        self.update(name, |health| health.state = state);
+    }
+
+    /// Health of every registered task, ordered by name
+    pub fn health(&self) -> Vec<TaskHealth> {
+        self.tasks.read().unwrap().values().cloned().collect()
+    }
+
+    /// Ready while no task is backing off after a panic or stopped
+    pub fn is_ready(&self) -> bool {
+        self.tasks
+            .read()
+            .unwrap()
+            .values()
+            .all(|health| matches!(health.state, TaskState::Running | TaskState::Finished))
+    }
+
+    /// Signal every task to stop and wait up to `timeout` for them to exit.
+    /// Returns whether all tasks stopped in time.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutdown.send_replace(true);
+        let handles: Vec<_> = self.handles.lock().unwrap().drain(..).collect();
+        tokio::time::timeout(timeout, futures::future::join_all(handles))
+            .await
+            .is_ok()
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
@@ -0,0 +1,123 @@
+//! Test doubles for the services wired through [`AppState`].
+//!
+//! [`test_state`] builds an `AppState` that never connects to anything: the
+//! database pool is created lazily, the cache lives in memory and emails are
+//! recorded instead of queued. Handlers that only go through `cache`,
+//! `email`, `dashboards` and the clock can be called directly in unit tests.
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use std::sync::{Arc, Mutex};
+
+use crate::AppState;
+use crate::cache::MemoryCacheStore;
+use crate::config::Config;
+use crate::db::models::dashboard::DashboardSummary;
+use crate::db::models::email::EmailMessage;
+use crate::error::AppError;
+use crate::services::context::RequestContext;
+use crate::services::dashboard_service::DashboardSource;
+use crate::services::email_service::Mailer;
+
+/// Configuration from defaults plus placeholder database and Redis URLs
+pub fn test_config() -> Config {
+    envy::from_iter::<_, Config>([
+        (
+            "DATABASE_URL".to_string(),
+            "postgres://localhost/momentum_test".to_string(),
+        ),
+        ("REDIS_URL".to_string(), "redis://127.0.0.1/".to_string()),
+    ])
+    .expect("defaults cover every other setting")
+}
+
+/// `AppState` with a lazily connecting pool, an in-memory cache and a
+/// [`RecordingMailer`]; replace further services with the `with_*` methods
+pub fn test_state() -> AppState {
+    let config = test_config();
+    let db = Pool::builder()
+        .min_idle(Some(0))
+        .build_unchecked(ConnectionManager::<PgConnection>::new(&config.database_url));
+    let redis = redis::Client::open(config.redis_url.as_str()).expect("valid Redis URL");
+    AppState::new(db, redis, config)
+        .with_cache(Arc::new(MemoryCacheStore::new()))
+        .with_mailer(Arc::new(RecordingMailer::new()))
+}
+
+/// Keeps queued emails in memory so tests can inspect them
+#[derive(Default)]
+pub struct RecordingMailer {
+    app_url: String,
+    from: String,
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+impl RecordingMailer {
+    pub fn new() -> Self {
+        Self {
+            app_url: "http://localhost:3000".to_string(),
+            from: "Momentum <no-reply@localhost>".to_string(),
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Emails queued so far, oldest first
+    pub fn sent(&self) -> Vec<EmailMessage> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    fn app_url(&self) -> &str {
+        &self.app_url
+    }
+
+    fn platform_from(&self) -> &str {
+        &self.from
+    }
+
+    async fn enqueue(&self, message: EmailMessage) -> Result<(), AppError> {
+        self.sent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(message);
+        Ok(())
+    }
+}
+
+/// Serves a fixed summary and counts how often it was asked for
+pub struct StubDashboardSource {
+    summary: DashboardSummary,
+    loads: Mutex<Vec<(RequestContext, NaiveDate)>>,
+}
+
+impl StubDashboardSource {
+    pub fn new(summary: DashboardSummary) -> Self {
+        Self {
+            summary,
+            loads: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Contexts and dates of the loads so far
+    pub fn loads(&self) -> Vec<(RequestContext, NaiveDate)> {
+        self.loads.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[async_trait]
+impl DashboardSource for StubDashboardSource {
+    async fn load(
+        &self,
+        ctx: &RequestContext,
+        today: NaiveDate,
+    ) -> Result<DashboardSummary, AppError> {
+        self.loads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((ctx.clone(), today));
+        Ok(self.summary.clone())
+    }
+}
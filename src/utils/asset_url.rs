@@ -1,17 +1,111 @@
 use crate::config::AssetsConfig;
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 尚未探测过的区域延迟，排在已探测区域之后
+const UNPROBED_LATENCY_MS: u64 = u64::MAX;
+
+/// 区域资源入口及其最近一次探测结果
+#[derive(Debug)]
+struct RegionEndpoint {
+    name: String,
+    base_url: String,
+    available: AtomicBool,
+    latency_ms: AtomicU64,
+}
+
+/// 所有区域共享的入口表；各请求的 helper 克隆共用同一份探测状态
+#[derive(Debug, Default)]
+struct RegionTable {
+    endpoints: Vec<RegionEndpoint>,
+    fallbacks: HashMap<String, Vec<String>>,
+}
 
 /// 通用的资源 URL 处理工具
 #[derive(Clone, Debug)]
 pub struct AssetUrlHelper {
     base_url: String,
     base_url_with_slash: String,
+    regions: Arc<RegionTable>,
+}
+
+/// 解析 `region=url` 形式的区域入口配置，保持配置顺序
+pub fn parse_region_urls(entries: &[String]) -> Result<Vec<(String, String)>, String> {
+    let mut regions: Vec<(String, String)> = Vec::new();
+    for entry in entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+        let (name, url) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid asset region '{}', expected region=url", entry))?;
+        let name = name.trim().to_ascii_lowercase();
+        let url = url.trim();
+        if name.is_empty() {
+            return Err(format!("Asset region name is empty in '{}'", entry));
+        }
+        if url::Url::parse(url).is_err() {
+            return Err(format!("Invalid asset region URL in '{}'", entry));
+        }
+        if regions.iter().any(|(existing, _)| *existing == name) {
+            return Err(format!("Asset region '{}' is configured twice", name));
+        }
+        regions.push((name, url.to_string()));
+    }
+    Ok(regions)
+}
+
+/// 解析 `region=a|b` 形式的回退链配置；链中的区域必须已配置入口
+pub fn parse_region_fallbacks(
+    entries: &[String],
+    regions: &[(String, String)],
+) -> Result<HashMap<String, Vec<String>>, String> {
+    let known = |name: &str| regions.iter().any(|(region, _)| region == name);
+    let mut fallbacks = HashMap::new();
+    for entry in entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+        let (name, chain) = entry.split_once('=').ok_or_else(|| {
+            format!(
+                "Invalid asset region fallback '{}', expected region=a|b",
+                entry
+            )
+        })?;
+        let name = name.trim().to_ascii_lowercase();
+        let chain: Vec<String> = chain
+            .split('|')
+            .map(|r| r.trim().to_ascii_lowercase())
+            .filter(|r| !r.is_empty())
+            .collect();
+        if let Some(unknown) = chain.iter().find(|r| !known(r)) {
+            return Err(format!(
+                "Asset region fallback '{}' refers to unknown region '{}'",
+                entry, unknown
+            ));
+        }
+        fallbacks.insert(name, chain);
+    }
+    Ok(fallbacks)
 }
 
 impl AssetUrlHelper {
     /// 创建新的 AssetUrlHelper 实例
     pub fn new(assets_config: &AssetsConfig) -> Self {
-        let base_url = assets_config.base_url.clone();
+        let regions = RegionTable {
+            endpoints: assets_config
+                .regions
+                .iter()
+                .map(|(name, base_url)| RegionEndpoint {
+                    name: name.clone(),
+                    base_url: base_url.clone(),
+                    available: AtomicBool::new(true),
+                    latency_ms: AtomicU64::new(UNPROBED_LATENCY_MS),
+                })
+                .collect(),
+            fallbacks: assets_config.fallbacks.clone(),
+        };
+        Self::with_base(assets_config.base_url.clone(), Arc::new(regions))
+    }
+
+    fn with_base(base_url: String, regions: Arc<RegionTable>) -> Self {
         let base_url_with_slash = if base_url.ends_with('/') {
             base_url.clone()
         } else {
@@ -21,6 +115,133 @@ impl AssetUrlHelper {
         Self {
             base_url,
             base_url_with_slash,
+            regions,
+        }
+    }
+
+    /// 资源 URL 的前缀，区分不同区域生成的 URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 已配置的区域名，按配置顺序
+    pub fn region_names(&self) -> Vec<&str> {
+        self.regions
+            .endpoints
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect()
+    }
+
+    pub fn has_region(&self, region: &str) -> bool {
+        self.endpoint(region).is_some()
+    }
+
+    fn endpoint(&self, region: &str) -> Option<&RegionEndpoint> {
+        let region = region.trim();
+        self.regions
+            .endpoints
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(region))
+    }
+
+    /// 为区域提示选择实际使用的区域
+    ///
+    /// 依次尝试：提示的区域、其配置的回退链、其余可用区域中延迟最低的。
+    /// 没有提示、提示的区域未配置或所有区域都不可用时返回 `None`，使用默认地址。
+    pub fn resolve_region(&self, hint: Option<&str>) -> Option<&str> {
+        let requested = self.endpoint(hint?)?;
+        if requested.available.load(Ordering::Relaxed) {
+            return Some(&requested.name);
+        }
+
+        let chain = self
+            .regions
+            .fallbacks
+            .get(&requested.name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if let Some(fallback) = chain
+            .iter()
+            .filter_map(|name| self.endpoint(name))
+            .find(|e| e.available.load(Ordering::Relaxed))
+        {
+            return Some(&fallback.name);
+        }
+
+        self.regions
+            .endpoints
+            .iter()
+            .filter(|e| e.available.load(Ordering::Relaxed))
+            .min_by_key(|e| e.latency_ms.load(Ordering::Relaxed))
+            .map(|e| e.name.as_str())
+    }
+
+    /// 返回按区域提示解析后的 helper；解析不到区域时即默认地址的 helper
+    pub fn for_region(&self, hint: Option<&str>) -> AssetUrlHelper {
+        match self
+            .resolve_region(hint)
+            .and_then(|name| self.endpoint(name))
+        {
+            Some(endpoint) => Self::with_base(endpoint.base_url.clone(), self.regions.clone()),
+            None => self.clone(),
+        }
+    }
+
+    /// 记录区域的探测结果；延迟为 `None` 时保留上一次的值
+    pub fn report_region(&self, region: &str, available: bool, latency: Option<Duration>) {
+        let Some(endpoint) = self.endpoint(region) else {
+            return;
+        };
+        endpoint.available.store(available, Ordering::Relaxed);
+        if let Some(latency) = latency {
+            endpoint
+                .latency_ms
+                .store(latency.as_millis() as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// 对每个区域入口发送 HEAD 请求，更新可用性与延迟；5xx 或请求失败视为不可用
+    pub async fn probe_regions(&self, client: &reqwest::Client, timeout: Duration) {
+        for endpoint in &self.regions.endpoints {
+            let started = Instant::now();
+            let result = client
+                .head(&endpoint.base_url)
+                .timeout(timeout)
+                .send()
+                .await;
+            match result {
+                Ok(response) if !response.status().is_server_error() => {
+                    self.report_region(&endpoint.name, true, Some(started.elapsed()));
+                }
+                Ok(response) => {
+                    tracing::warn!(
+                        "Asset region {} answered {}; marking it unavailable",
+                        endpoint.name,
+                        response.status()
+                    );
+                    self.report_region(&endpoint.name, false, None);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Asset region {} is unreachable: {}; marking it unavailable",
+                        endpoint.name,
+                        e
+                    );
+                    self.report_region(&endpoint.name, false, None);
+                }
+            }
+        }
+    }
+
+    /// 定期探测所有区域，由后台任务运行
+    pub async fn run_region_probe(self, interval: Duration) {
+        let client = reqwest::Client::new();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            self.probe_regions(&client, interval.min(Duration::from_secs(5)))
+                .await;
         }
     }
 
@@ -103,6 +324,21 @@ impl AssetUrlHelper {
         self.build_url(&format!("attachments/{}", filename))
     }
 
+    /// 构建自定义表情 URL
+    ///
+    /// # 参数
+    /// * `filename` - 表情图片文件名
+    ///
+    /// # 示例
+    /// ```ignore
+    /// let helper = AssetUrlHelper::new(&assets_config);
+    /// let emoji_url = helper.build_emoji_url("ws/party.png");
+    /// // 返回: "http://localhost:8000/assets/emojis/ws/party.png"
+    /// ```ignore
+    pub fn build_emoji_url(&self, filename: &str) -> String {
+        self.build_url(&format!("emojis/{}", filename))
+    }
+
     /// 检查 URL 是否为外部链接（不是基于当前 assets_url 的）
     ///
     /// # 参数
@@ -164,10 +400,26 @@ mod tests {
     fn create_test_helper() -> AssetUrlHelper {
         let assets_config = AssetsConfig {
             base_url: "http://localhost:8000/assets".to_string(),
+            ..Default::default()
         };
         AssetUrlHelper::new(&assets_config)
     }
 
+    fn create_regional_helper() -> AssetUrlHelper {
+        let regions = parse_region_urls(&[
+            "us=https://us.cdn.example.com/assets".to_string(),
+            "eu=https://eu.cdn.example.com/assets".to_string(),
+            "ap=https://ap.cdn.example.com/assets".to_string(),
+        ])
+        .unwrap();
+        let fallbacks = parse_region_fallbacks(&["ap=eu|us".to_string()], &regions).unwrap();
+        AssetUrlHelper::new(&AssetsConfig {
+            base_url: "http://localhost:8000/assets".to_string(),
+            regions,
+            fallbacks,
+        })
+    }
+
     #[test]
     fn test_build_url() {
         let helper = create_test_helper();
@@ -229,4 +481,82 @@ mod tests {
             "http://localhost:8000/assets/avatars/user123.jpg"
         );
     }
+
+    #[test]
+    fn test_region_hint_selects_endpoint() {
+        let helper = create_regional_helper();
+
+        assert_eq!(
+            helper.for_region(Some("EU")).build_avatar_url("u.jpg"),
+            "https://eu.cdn.example.com/assets/avatars/u.jpg"
+        );
+        // 无提示或未知区域使用默认地址
+        assert_eq!(
+            helper.for_region(None).build_avatar_url("u.jpg"),
+            "http://localhost:8000/assets/avatars/u.jpg"
+        );
+        assert_eq!(helper.resolve_region(Some("mars")), None);
+    }
+
+    #[test]
+    fn test_unavailable_region_follows_fallback_chain() {
+        let helper = create_regional_helper();
+
+        helper.report_region("ap", false, None);
+        assert_eq!(helper.resolve_region(Some("ap")), Some("eu"));
+
+        helper.report_region("eu", false, None);
+        assert_eq!(helper.resolve_region(Some("ap")), Some("us"));
+
+        // 回退链耗尽时选择延迟最低的可用区域
+        helper.report_region("us", false, None);
+        helper.report_region("eu", true, Some(Duration::from_millis(80)));
+        assert_eq!(helper.resolve_region(Some("ap")), Some("eu"));
+
+        helper.report_region("eu", false, None);
+        assert_eq!(helper.resolve_region(Some("ap")), None);
+    }
+
+    #[test]
+    fn test_unavailable_region_without_chain_prefers_lowest_latency() {
+        let helper = create_regional_helper();
+
+        helper.report_region("us", false, Some(Duration::from_millis(10)));
+        helper.report_region("eu", true, Some(Duration::from_millis(120)));
+        helper.report_region("ap", true, Some(Duration::from_millis(40)));
+        assert_eq!(helper.resolve_region(Some("us")), Some("ap"));
+    }
+
+    #[test]
+    fn test_region_helper_keeps_absolute_urls() {
+        let helper = create_regional_helper();
+
+        // 已存储为其他区域的完整 URL 原样返回
+        assert_eq!(
+            helper
+                .for_region(Some("us"))
+                .process_url("https://eu.cdn.example.com/assets/avatars/u.jpg"),
+            "https://eu.cdn.example.com/assets/avatars/u.jpg"
+        );
+        assert_eq!(
+            helper.for_region(Some("us")).process_url("avatars/u.jpg"),
+            "https://us.cdn.example.com/assets/avatars/u.jpg"
+        );
+    }
+
+    #[test]
+    fn test_parse_region_config_errors() {
+        assert!(parse_region_urls(&["eu".to_string()]).is_err());
+        assert!(parse_region_urls(&["eu=not a url".to_string()]).is_err());
+        assert!(
+            parse_region_urls(&[
+                "eu=https://a.example.com".to_string(),
+                "EU=https://b.example.com".to_string(),
+            ])
+            .is_err()
+        );
+
+        let regions = parse_region_urls(&["eu=https://a.example.com".to_string()]).unwrap();
+        assert!(parse_region_fallbacks(&["ap=eu|us".to_string()], &regions).is_err());
+    }
 }
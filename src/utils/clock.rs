@@ -0,0 +1,160 @@
+//! 时间与 ID 的提供者
+//!
+//! 服务层和 WebSocket 层通过 [`now`] 与 [`new_id`] 获取当前时间和新 ID，
+//! 而不是直接调用 `Utc::now()` / `Uuid::new_v4()`：
+//!
+//! - 进程级提供者由 `AppState` 安装，默认为系统时钟和随机 UUID
+//! - 测试可用 [`freeze`] 在当前线程内替换为固定时钟和顺序 ID，
+//!   guard 释放后恢复，不影响并行运行的其他测试
+use chrono::{DateTime, Utc};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use uuid::Uuid;
+
+/// 当前时间的来源
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// 当前 UTC 日期
+    fn today(&self) -> chrono::NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+/// 新 ID 的来源
+pub trait IdGenerator: Send + Sync {
+    fn new_id(&self) -> Uuid;
+}
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 随机 UUID（v4）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIds;
+
+impl IdGenerator for RandomIds {
+    fn new_id(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// 只在显式设置或推进时才变化的时钟
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 依次递增的 ID：`seed` 占高 64 位，计数占低 64 位，
+/// 同一 seed 总是生成同一串 ID
+#[derive(Debug)]
+pub struct SequentialIds {
+    seed: u64,
+    next: AtomicU64,
+}
+
+impl SequentialIds {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            next: AtomicU64::new(1),
+        }
+    }
+}
+
+impl IdGenerator for SequentialIds {
+    fn new_id(&self) -> Uuid {
+        let n = self.next.fetch_add(1, Ordering::Relaxed);
+        Uuid::from_u128(((self.seed as u128) << 64) | n as u128)
+    }
+}
+
+/// 一组提供者
+struct Providers {
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+static PROVIDERS: RwLock<Option<Providers>> = RwLock::new(None);
+
+thread_local! {
+    static FROZEN: RefCell<Option<Providers>> = const { RefCell::new(None) };
+}
+
+/// 安装进程级提供者，替换之前安装的
+pub fn install(clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) {
+    let mut providers = PROVIDERS.write().unwrap_or_else(|e| e.into_inner());
+    *providers = Some(Providers { clock, ids });
+}
+
+/// 当前时间：优先使用当前线程冻结的时钟，其次是进程级提供者
+pub fn now() -> DateTime<Utc> {
+    if let Some(now) = FROZEN.with(|frozen| frozen.borrow().as_ref().map(|p| p.clock.now())) {
+        return now;
+    }
+    match PROVIDERS.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(providers) => providers.clock.now(),
+        None => Utc::now(),
+    }
+}
+
+/// 新 ID，来源的优先级与 [`now`] 相同
+pub fn new_id() -> Uuid {
+    if let Some(id) = FROZEN.with(|frozen| frozen.borrow().as_ref().map(|p| p.ids.new_id())) {
+        return id;
+    }
+    match PROVIDERS.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(providers) => providers.ids.new_id(),
+        None => Uuid::new_v4(),
+    }
+}
+
+/// 在当前线程内使用给定的时钟和 ID 来源，直到返回的 guard 被释放
+#[must_use = "the providers are restored when the guard is dropped"]
+pub fn freeze(clock: Arc<dyn Clock>, ids: Arc<dyn IdGenerator>) -> FrozenGuard {
+    let previous = FROZEN.with(|frozen| frozen.borrow_mut().replace(Providers { clock, ids }));
+    FrozenGuard { previous }
+}
+
+/// [`freeze`] 的 guard，释放时恢复之前的提供者
+pub struct FrozenGuard {
+    previous: Option<Providers>,
+}
+
+impl Drop for FrozenGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        FROZEN.with(|frozen| *frozen.borrow_mut() = previous);
+    }
+}
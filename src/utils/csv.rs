@@ -0,0 +1,60 @@
+/// 解析 CSV (RFC 4180) 文本，返回逐行的字段列表
+///
+/// - 字段可用双引号包裹，引号内可包含逗号、换行，`""` 表示一个引号
+/// - 支持 `\n` 与 `\r\n` 换行，忽略完全空白的行
+/// - 去掉 UTF-8 BOM
+pub fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '"' => return Err(format!("Unexpected quote on line {}", line)),
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                push_record(&mut records, std::mem::take(&mut record));
+                line += 1;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("Unterminated quoted field on line {}", line));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        push_record(&mut records, record);
+    }
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+    if record.iter().any(|f| !f.trim().is_empty()) {
+        records.push(record);
+    }
+}
@@ -0,0 +1,101 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// 回复令牌中保留的签名字节数
+const SIGNATURE_LEN: usize = 10;
+
+/// 生成 issue 回复令牌：issue_id + user_id + 截断的 HMAC-SHA256 签名
+pub fn reply_token(issue_id: Uuid, user_id: Uuid, secret: &str) -> String {
+    let mut bytes = Vec::with_capacity(32 + SIGNATURE_LEN);
+    bytes.extend_from_slice(issue_id.as_bytes());
+    bytes.extend_from_slice(user_id.as_bytes());
+    let signature = sign(&bytes, secret);
+    bytes.extend_from_slice(&signature[..SIGNATURE_LEN]);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 校验回复令牌，返回 `(issue_id, user_id)`
+pub fn verify_reply_token(token: &str, secret: &str) -> Option<(Uuid, Uuid)> {
+    let bytes = URL_SAFE_NO_PAD.decode(token).ok()?;
+    if bytes.len() != 32 + SIGNATURE_LEN {
+        return None;
+    }
+    let (payload, signature) = bytes.split_at(32);
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(payload);
+    mac.verify_truncated_left(signature).ok()?;
+
+    let issue_id = Uuid::from_slice(&payload[..16]).ok()?;
+    let user_id = Uuid::from_slice(&payload[16..]).ok()?;
+    Some((issue_id, user_id))
+}
+
+/// 组装回复地址，如 `reply+<token>@reply.example.com`
+pub fn reply_address(token: &str, domain: &str) -> String {
+    format!("reply+{}@{}", token, domain)
+}
+
+/// 从收件人列表中找出回复令牌
+pub fn extract_reply_token(recipients: &str) -> Option<String> {
+    recipients
+        .split(',')
+        .map(extract_email_address)
+        .find_map(|address| {
+            let (local, _) = address.split_once('@')?;
+            let token = local.strip_prefix("reply+")?;
+            (!token.is_empty()).then(|| token.to_string())
+        })
+}
+
+/// 从 `Name <user@example.com>` 形式的头部中取出邮箱地址
+pub fn extract_email_address(header: &str) -> String {
+    let header = header.trim();
+    let address = match (header.rfind('<'), header.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &header[start + 1..end],
+        _ => header,
+    };
+    address.trim().to_string()
+}
+
+/// 去掉回复邮件中的引用原文和签名，只保留新写的内容
+pub fn strip_reply_text(body: &str) -> String {
+    let mut kept: Vec<&str> = Vec::new();
+    for line in body.lines() {
+        let line = line.trim_end_matches('\r');
+        let trimmed = line.trim();
+
+        // 签名分隔符
+        if line.trim_end() == "--" {
+            break;
+        }
+        if is_quote_header(trimmed, kept.iter().any(|l| !l.trim().is_empty())) {
+            break;
+        }
+        if trimmed.starts_with('>') {
+            continue;
+        }
+        kept.push(line);
+    }
+
+    kept.join("\n").trim().to_string()
+}
+
+fn is_quote_header(line: &str, has_content: bool) -> bool {
+    (line.starts_with("On ") && line.ends_with("wrote:"))
+        || (line.starts_with('在') && (line.ends_with("写道：") || line.ends_with("写道:")))
+        || line.starts_with("-----Original Message-----")
+        || line.starts_with("________________________________")
+        || line.starts_with("Sent from my ")
+        || line.starts_with("Get Outlook for ")
+        || (has_content && line.starts_with("From: "))
+}
+
+fn sign(payload: &[u8], secret: &str) -> Vec<u8> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(payload);
+    mac.finalize().into_bytes().to_vec()
+}
@@ -0,0 +1,116 @@
+use chrono::NaiveDate;
+
+/// 从 ICS 日历中解析出的单个节假日
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcsHoliday {
+    pub name: String,
+    pub date: NaiveDate,
+}
+
+/// 单个事件最多展开的天数，避免异常数据生成海量记录
+const MAX_EVENT_SPAN_DAYS: i64 = 31;
+
+/// 解析 ICS (RFC 5545) 文本中的 VEVENT，返回全天节假日列表
+///
+/// 只关心 `DTSTART` / `DTEND` / `SUMMARY` 三个属性：
+/// - 支持 `DTSTART;VALUE=DATE:20250101` 与 `DTSTART:20250101T000000Z` 两种格式
+/// - `DTEND` 按规范为开区间，多天事件会被展开为逐日记录
+/// - 支持折行（以空格或制表符开头的续行）
+pub fn parse_ics_holidays(content: &str) -> Result<Vec<IcsHoliday>, String> {
+    let lines = unfold_lines(content);
+    if !lines
+        .iter()
+        .any(|l| l.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("Missing BEGIN:VCALENDAR".to_string());
+    }
+
+    let mut holidays = Vec::new();
+    let mut in_event = false;
+    let mut summary: Option<String> = None;
+    let mut start: Option<NaiveDate> = None;
+    let mut end: Option<NaiveDate> = None;
+
+    for line in lines {
+        if line.eq_ignore_ascii_case("BEGIN:VEVENT") {
+            in_event = true;
+            summary = None;
+            start = None;
+            end = None;
+            continue;
+        }
+        if line.eq_ignore_ascii_case("END:VEVENT") {
+            in_event = false;
+            let (Some(name), Some(first_day)) = (summary.take(), start.take()) else {
+                return Err("VEVENT is missing SUMMARY or DTSTART".to_string());
+            };
+            let span = end
+                .take()
+                .map(|last| (last - first_day).num_days())
+                .unwrap_or(1)
+                .clamp(1, MAX_EVENT_SPAN_DAYS);
+            for offset in 0..span {
+                holidays.push(IcsHoliday {
+                    name: name.clone(),
+                    date: first_day + chrono::Duration::days(offset),
+                });
+            }
+            continue;
+        }
+        if !in_event {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let property = key
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        match property.as_str() {
+            "SUMMARY" => summary = Some(unescape_text(value.trim())),
+            "DTSTART" => start = Some(parse_ics_date(value)?),
+            "DTEND" => end = Some(parse_ics_date(value)?),
+            _ => {}
+        }
+    }
+
+    Ok(holidays)
+}
+
+/// 合并 RFC 5545 的折行
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if (raw.starts_with(' ') || raw.starts_with('\t'))
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(&raw[1..]);
+            continue;
+        }
+        let trimmed = raw.trim();
+        if !trimmed.is_empty() {
+            lines.push(trimmed.to_string());
+        }
+    }
+    lines
+}
+
+fn parse_ics_date(value: &str) -> Result<NaiveDate, String> {
+    let value = value.trim();
+    let date_part = value.get(..8).unwrap_or(value);
+    NaiveDate::parse_from_str(date_part, "%Y%m%d")
+        .map_err(|_| format!("Invalid ICS date value: {}", value))
+}
+
+fn unescape_text(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
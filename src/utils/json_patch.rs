@@ -0,0 +1,296 @@
+//! JSON Patch（RFC 6902）与 JSON Merge Patch（RFC 7396）
+//!
+//! - JSON Patch 是操作数组，支持 `add`、`remove`、`replace`、`move`、
+//!   `copy`、`test`，路径为 JSON Pointer（RFC 6901，`~1` 表示 `/`，
+//!   `~0` 表示 `~`）；任一操作失败时文档保持不变
+//! - Merge Patch 是对象：值为 `null` 的键被删除，对象递归合并，其余值
+//!   （包括数组）整体替换
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// JSON Patch 的 Content-Type
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+/// JSON Merge Patch 的 Content-Type
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+
+/// 单个 JSON Patch 操作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+impl PatchOperation {
+    /// 操作的目标路径
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. }
+            | PatchOperation::Remove { path }
+            | PatchOperation::Replace { path, .. }
+            | PatchOperation::Move { path, .. }
+            | PatchOperation::Copy { path, .. }
+            | PatchOperation::Test { path, .. } => path,
+        }
+    }
+
+    /// `move` 与 `copy` 的来源路径
+    pub fn from(&self) -> Option<&str> {
+        match self {
+            PatchOperation::Move { from, .. } | PatchOperation::Copy { from, .. } => Some(from),
+            _ => None,
+        }
+    }
+}
+
+/// 补丁文档：操作数组为 JSON Patch，对象为 Merge Patch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PatchDocument {
+    JsonPatch(Vec<PatchOperation>),
+    MergePatch(serde_json::Map<String, Value>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    /// 补丁或路径不合法，或路径在文档中不存在
+    Invalid(String),
+    /// `test` 操作的值与文档不符
+    TestFailed(String),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::Invalid(message) | PatchError::TestFailed(message) => f.write_str(message),
+        }
+    }
+}
+
+/// 解析 JSON Pointer 为各层键
+pub fn parse_pointer(pointer: &str) -> Result<Vec<String>, PatchError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(PatchError::Invalid(format!(
+            "Path '{}' must start with '/'",
+            pointer
+        )));
+    };
+    rest.split('/')
+        .map(|token| {
+            let mut key = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    key.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('0') => key.push('~'),
+                    Some('1') => key.push('/'),
+                    _ => {
+                        return Err(PatchError::Invalid(format!(
+                            "Path '{}' has an invalid '~' escape",
+                            pointer
+                        )));
+                    }
+                }
+            }
+            Ok(key)
+        })
+        .collect()
+}
+
+/// 依次应用操作；失败时 `doc` 不变
+pub fn apply_patch(doc: &mut Value, operations: &[PatchOperation]) -> Result<(), PatchError> {
+    let mut patched = doc.clone();
+    for operation in operations {
+        apply_operation(&mut patched, operation)?;
+    }
+    *doc = patched;
+    Ok(())
+}
+
+/// 按 RFC 7396 合并补丁
+pub fn apply_merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                apply_merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+fn apply_operation(doc: &mut Value, operation: &PatchOperation) -> Result<(), PatchError> {
+    match operation {
+        PatchOperation::Add { path, value } => add(doc, path, value.clone()),
+        PatchOperation::Remove { path } => remove(doc, path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            let target = pointer_mut(doc, path)?;
+            *target = value.clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(PatchError::Invalid(format!(
+                    "Cannot move '{}' into its own child '{}'",
+                    from, path
+                )));
+            }
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = pointer_mut(doc, from)?.clone();
+            add(doc, path, value)
+        }
+        PatchOperation::Test { path, value } => {
+            if pointer_mut(doc, path)? == value {
+                Ok(())
+            } else {
+                Err(PatchError::TestFailed(format!(
+                    "Test failed: value at '{}' differs",
+                    path
+                )))
+            }
+        }
+    }
+}
+
+fn pointer_mut<'a>(doc: &'a mut Value, pointer: &str) -> Result<&'a mut Value, PatchError> {
+    let mut current = doc;
+    for key in parse_pointer(pointer)? {
+        current = match current {
+            Value::Object(map) => map.get_mut(&key),
+            Value::Array(items) => {
+                array_index(&key, items.len(), false)?.and_then(|index| items.get_mut(index))
+            }
+            _ => None,
+        }
+        .ok_or_else(|| PatchError::Invalid(format!("Path '{}' does not exist", pointer)))?;
+    }
+    Ok(current)
+}
+
+/// 拆出父级路径与最后一层键
+fn split_parent(pointer: &str) -> Result<(String, String), PatchError> {
+    let keys = parse_pointer(pointer)?;
+    let Some(last) = keys.last().cloned() else {
+        return Err(PatchError::Invalid(
+            "The document root cannot be changed".to_string(),
+        ));
+    };
+    let parent_len = pointer.rfind('/').unwrap_or(0);
+    Ok((pointer[..parent_len].to_string(), last))
+}
+
+/// 数组下标；`-` 表示末尾之后，仅在 `allow_end` 时接受
+fn array_index(key: &str, len: usize, allow_end: bool) -> Result<Option<usize>, PatchError> {
+    if key == "-" {
+        return Ok(allow_end.then_some(len));
+    }
+    if key.is_empty()
+        || !key.bytes().all(|b| b.is_ascii_digit())
+        || (key.len() > 1 && key.starts_with('0'))
+    {
+        return Err(PatchError::Invalid(format!(
+            "'{}' is not an array index",
+            key
+        )));
+    }
+    let index: usize = key
+        .parse()
+        .map_err(|_| PatchError::Invalid(format!("'{}' is not an array index", key)))?;
+    let in_bounds = if allow_end { index <= len } else { index < len };
+    Ok(in_bounds.then_some(index))
+}
+
+fn add(doc: &mut Value, pointer: &str, value: Value) -> Result<(), PatchError> {
+    let (parent, key) = split_parent(pointer)?;
+    match pointer_mut(doc, &parent)? {
+        Value::Object(map) => {
+            map.insert(key, value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            let index = array_index(&key, items.len(), true)?.ok_or_else(|| {
+                PatchError::Invalid(format!("Index in '{}' is out of bounds", pointer))
+            })?;
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(PatchError::Invalid(format!(
+            "Parent of '{}' is not an object or array",
+            pointer
+        ))),
+    }
+}
+
+fn remove(doc: &mut Value, pointer: &str) -> Result<Value, PatchError> {
+    let (parent, key) = split_parent(pointer)?;
+    let removed = match pointer_mut(doc, &parent)? {
+        Value::Object(map) => map.remove(&key),
+        Value::Array(items) => {
+            array_index(&key, items.len(), false)?.map(|index| items.remove(index))
+        }
+        _ => None,
+    };
+    removed.ok_or_else(|| PatchError::Invalid(format!("Path '{}' does not exist", pointer)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pointer_escapes() {
+        assert_eq!(
+            parse_pointer("/a~1b/c~0d").unwrap(),
+            vec!["a/b".to_string(), "c~d".to_string()]
+        );
+        assert!(parse_pointer("a").is_err());
+        assert!(parse_pointer("/a~2").is_err());
+        assert!(parse_pointer("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_array_operations() {
+        let mut doc = json!({ "tags": ["a", "b"] });
+        let ops: Vec<PatchOperation> = serde_json::from_value(json!([
+            { "op": "add", "path": "/tags/-", "value": "c" },
+            { "op": "add", "path": "/tags/0", "value": "z" },
+            { "op": "remove", "path": "/tags/1" },
+            { "op": "copy", "from": "/tags/0", "path": "/first" }
+        ]))
+        .unwrap();
+        apply_patch(&mut doc, &ops).unwrap();
+        assert_eq!(doc, json!({ "tags": ["z", "b", "c"], "first": "z" }));
+
+        let out_of_bounds = [PatchOperation::Remove {
+            path: "/tags/3".to_string(),
+        }];
+        assert!(apply_patch(&mut doc, &out_of_bounds).is_err());
+        let leading_zero = [PatchOperation::Remove {
+            path: "/tags/01".to_string(),
+        }];
+        assert!(apply_patch(&mut doc, &leading_zero).is_err());
+    }
+}
@@ -1,3 +1,12 @@
 pub mod asset_url;
+pub mod clock;
+pub mod csv;
+pub mod email_reply;
+pub mod ics;
+pub mod json_patch;
+pub mod nullable;
+pub mod object_storage;
+pub mod webhook_filter;
 
 pub use asset_url::AssetUrlHelper;
+pub use object_storage::ObjectStorage;
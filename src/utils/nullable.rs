@@ -0,0 +1,15 @@
+//! 区分“字段缺省”与“显式 null”的反序列化
+//!
+//! 用于可清空的更新字段：
+//! `#[serde(default, deserialize_with = "crate::utils::nullable::deserialize")]`
+//! 标注在 `Option<Option<T>>` 上时，缺省为 `None`（不修改），
+//! `null` 为 `Some(None)`（清空），有值为 `Some(Some(v))`。
+use serde::{Deserialize, Deserializer};
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
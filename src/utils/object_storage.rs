@@ -0,0 +1,315 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::config::StorageConfig;
+
+/// 请求超时时间
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// 上传分片的超时时间，分片可达数十 MiB
+const PART_UPLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// S3 兼容对象存储（AWS S3、MinIO 等）
+///
+/// 不依赖 SDK：通过 AWS Signature V4 生成预签名 URL，客户端直接上传/下载，
+/// 服务端只在确认上传和删除对象时发起请求。
+#[derive(Clone, Debug)]
+pub struct ObjectStorage {
+    scheme: String,
+    host: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    path_style: bool,
+    http: reqwest::Client,
+}
+
+impl ObjectStorage {
+    pub fn new(config: &StorageConfig) -> Result<Self, String> {
+        let endpoint = url::Url::parse(&config.endpoint)
+            .map_err(|e| format!("Invalid storage endpoint: {}", e))?;
+        let host = endpoint
+            .host_str()
+            .ok_or_else(|| "Storage endpoint has no host".to_string())?;
+        let host = match endpoint.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to build storage client: {}", e))?;
+
+        Ok(Self {
+            scheme: endpoint.scheme().to_string(),
+            host,
+            bucket: config.bucket.clone(),
+            region: config.region.clone(),
+            access_key: config.access_key.clone(),
+            secret_key: config.secret_key.clone(),
+            path_style: config.path_style,
+            http,
+        })
+    }
+
+    /// 生成预签名 URL，有效期 `expires_in` 秒
+    pub fn presign(&self, method: &str, key: &str, expires_in: u64) -> String {
+        self.presign_at(method, key, expires_in, Utc::now())
+    }
+
+    /// 以指定时间生成预签名 URL（query string 方式的 SigV4，payload 不参与签名）
+    pub fn presign_at(
+        &self,
+        method: &str,
+        key: &str,
+        expires_in: u64,
+        now: DateTime<Utc>,
+    ) -> String {
+        self.presign_query_at(method, key, &[], expires_in, now)
+    }
+
+    /// 带额外查询参数（如分片上传的 `uploadId`、`partNumber`）的预签名 URL
+    pub fn presign_query_at(
+        &self,
+        method: &str,
+        key: &str,
+        params: &[(&str, &str)],
+        expires_in: u64,
+        now: DateTime<Utc>,
+    ) -> String {
+        let (host, path) = if self.path_style {
+            (
+                self.host.clone(),
+                format!("/{}/{}", self.bucket, uri_encode(key, false)),
+            )
+        } else {
+            (
+                format!("{}.{}", self.bucket, self.host),
+                format!("/{}", uri_encode(key, false)),
+            )
+        };
+
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+
+        // 规范请求要求参数按编码后的名称字典序排列
+        let mut query = [
+            ("X-Amz-Algorithm", "AWS4-HMAC-SHA256".to_string()),
+            ("X-Amz-Credential", format!("{}/{}", self.access_key, scope)),
+            ("X-Amz-Date", amz_date.clone()),
+            ("X-Amz-Expires", expires_in.to_string()),
+            ("X-Amz-SignedHeaders", "host".to_string()),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), uri_encode(value, true)))
+        .chain(
+            params
+                .iter()
+                .map(|(name, value)| (uri_encode(name, true), uri_encode(value, true))),
+        )
+        .collect::<Vec<_>>();
+        query.sort();
+        let query = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\n\nhost\nUNSIGNED-PAYLOAD",
+            method, path, query, host
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = [date.as_str(), self.region.as_str(), "s3", "aws4_request"]
+            .iter()
+            .fold(
+                format!("AWS4{}", self.secret_key).into_bytes(),
+                |key, part| hmac_sha256(&key, part.as_bytes()),
+            );
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        format!(
+            "{}://{}{}?{}&X-Amz-Signature={}",
+            self.scheme, host, path, query, signature
+        )
+    }
+
+    /// 查询对象大小；对象不存在时返回 `None`
+    pub async fn head(&self, key: &str) -> Result<Option<i64>, String> {
+        let response = self
+            .http
+            .head(self.presign("HEAD", key, 60))
+            .send()
+            .await
+            .map_err(|e| format!("Storage request failed: {}", e))?;
+        match response.status().as_u16() {
+            404 => Ok(None),
+            status if (200..300).contains(&status) => Ok(Some(
+                response
+                    .headers()
+                    .get(reqwest::header::CONTENT_LENGTH)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+            )),
+            status => Err(format!("Storage returned HTTP {}", status)),
+        }
+    }
+
+    /// 删除对象；对象不存在也视为成功
+    pub async fn delete(&self, key: &str) -> Result<(), String> {
+        let response = self
+            .http
+            .delete(self.presign("DELETE", key, 60))
+            .send()
+            .await
+            .map_err(|e| format!("Storage request failed: {}", e))?;
+        match response.status().as_u16() {
+            status if (200..300).contains(&status) || status == 404 => Ok(()),
+            status => Err(format!("Storage returned HTTP {}", status)),
+        }
+    }
+
+    /// 发起分片上传，返回 upload id
+    pub async fn create_multipart(&self, key: &str, content_type: &str) -> Result<String, String> {
+        let url = self.presign_query_at("POST", key, &[("uploads", "")], 60, Utc::now());
+        let response = self
+            .http
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .send()
+            .await
+            .map_err(|e| format!("Storage request failed: {}", e))?;
+        let body = Self::success_body(response).await?;
+        xml_element(&body, "UploadId").ok_or_else(|| "Storage response has no UploadId".to_string())
+    }
+
+    /// 上传一个分片（编号从 1 开始），返回其 ETag
+    pub async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: i32,
+        body: axum::body::Bytes,
+    ) -> Result<String, String> {
+        let part_number = part_number.to_string();
+        let url = self.presign_query_at(
+            "PUT",
+            key,
+            &[("partNumber", &part_number), ("uploadId", upload_id)],
+            PART_UPLOAD_TIMEOUT.as_secs(),
+            Utc::now(),
+        );
+        let response = self
+            .http
+            .put(url)
+            .timeout(PART_UPLOAD_TIMEOUT)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Storage request failed: {}", e))?;
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        Self::success_body(response).await?;
+        etag.ok_or_else(|| "Storage response has no ETag".to_string())
+    }
+
+    /// 按 `(编号, ETag)` 合并分片为完整对象
+    pub async fn complete_multipart(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[(i32, &str)],
+    ) -> Result<(), String> {
+        let body = format!(
+            "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
+            parts
+                .iter()
+                .map(|(number, etag)| format!(
+                    "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                    number, etag
+                ))
+                .collect::<String>()
+        );
+        let url = self.presign_query_at("POST", key, &[("uploadId", upload_id)], 60, Utc::now());
+        let response = self
+            .http
+            .post(url)
+            .timeout(PART_UPLOAD_TIMEOUT)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Storage request failed: {}", e))?;
+        // 合并失败时 S3 也可能返回 200，错误写在响应体中
+        let body = Self::success_body(response).await?;
+        match xml_element(&body, "Code") {
+            Some(code) => Err(format!("Storage failed to complete upload: {}", code)),
+            None => Ok(()),
+        }
+    }
+
+    /// 放弃分片上传并释放已上传的分片；上传不存在也视为成功
+    pub async fn abort_multipart(&self, key: &str, upload_id: &str) -> Result<(), String> {
+        let url = self.presign_query_at("DELETE", key, &[("uploadId", upload_id)], 60, Utc::now());
+        let response = self
+            .http
+            .delete(url)
+            .send()
+            .await
+            .map_err(|e| format!("Storage request failed: {}", e))?;
+        match response.status().as_u16() {
+            status if (200..300).contains(&status) || status == 404 => Ok(()),
+            status => Err(format!("Storage returned HTTP {}", status)),
+        }
+    }
+
+    async fn success_body(response: reqwest::Response) -> Result<String, String> {
+        let status = response.status().as_u16();
+        if !(200..300).contains(&status) {
+            return Err(format!("Storage returned HTTP {}", status));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read storage response: {}", e))
+    }
+}
+
+/// 取 XML 响应中第一个 `<name>` 元素的文本
+fn xml_element(body: &str, name: &str) -> Option<String> {
+    let start = body.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = body[start..].find(&format!("</{}>", name))? + start;
+    Some(body[start..end].to_string())
+}
+
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// SigV4 的 URI 编码：只保留非保留字符，路径中的 `/` 可选择不编码
+fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
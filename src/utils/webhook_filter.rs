@@ -0,0 +1,514 @@
+//! Webhook 事件过滤表达式
+//!
+//! 语法示例：
+//! `event == "issue.updated" and team in (ENG, OPS) and not label == wontfix`
+//! `priority >= high or $.title contains "crash"`
+//!
+//! - 字段：`event`、`team`（团队 id 或 key）、`label`（标签 id 或名称）、
+//!   `priority`，以及以 `$` 开头的 JSONPath（作用于事件 payload，支持
+//!   `.key`、`[0]`、`[*]`）
+//! - 运算符：`==` `!=` `>` `>=` `<` `<=` `in (...)` `contains` `exists`
+//! - 逻辑：`and`、`or`、`not` 与括号；多值字段只要任一值满足即为真
+//! - 内置字段的字符串比较不区分大小写；`priority` 按
+//!   none < low < medium < high < urgent 排序
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// 表达式最大长度
+pub const MAX_FILTER_LENGTH: usize = 2000;
+
+const MAX_DEPTH: usize = 32;
+const PRIORITIES: [&str; 5] = ["none", "low", "medium", "high", "urgent"];
+
+/// 过滤表达式求值时可见的事件信息
+#[derive(Debug, Clone, Default)]
+pub struct FilterInput {
+    pub event: String,
+    pub teams: Vec<String>,
+    pub labels: Vec<String>,
+    pub priority: Option<String>,
+    pub payload: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Field {
+    Event,
+    Team,
+    Label,
+    Priority,
+    Path(Vec<Segment>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+    In(Vec<Value>),
+    Exists,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare { field: Field, op: Op, value: Value },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Path(String),
+    Str(String),
+    Num(f64),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+/// 解析后的过滤表达式
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookFilter {
+    expr: Expr,
+}
+
+impl WebhookFilter {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        if source.len() > MAX_FILTER_LENGTH {
+            return Err(format!(
+                "Filter is too long (max {} characters)",
+                MAX_FILTER_LENGTH
+            ));
+        }
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Err("Filter is empty".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or_expr(0)?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("Unexpected {}", describe(token)));
+        }
+        Ok(Self { expr })
+    }
+
+    pub fn matches(&self, input: &FilterInput) -> bool {
+        eval(&self.expr, input)
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '=' | '!' | '<' | '>' => {
+                let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+                let op = match two.as_str() {
+                    "==" => "==",
+                    "!=" => "!=",
+                    ">=" => ">=",
+                    "<=" => "<=",
+                    _ if c == '>' => ">",
+                    _ if c == '<' => "<",
+                    _ => return Err(format!("Unexpected '{}' at position {}", c, i)),
+                };
+                i += op.len();
+                tokens.push(Token::Op(op));
+            }
+            '"' | '\'' => {
+                let quote = c;
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err("Unterminated string".to_string()),
+                        Some('\\') => {
+                            if let Some(next) = chars.get(i + 1) {
+                                text.push(*next);
+                            }
+                            i += 2;
+                        }
+                        Some(ch) if *ch == quote => {
+                            i += 1;
+                            break;
+                        }
+                        Some(ch) => {
+                            text.push(*ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::Str(text));
+            }
+            '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || "$._-[]*".contains(chars[i]))
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Path(chars[start..i].iter().collect()));
+            }
+            c if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let num = text
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid number '{}'", text))?;
+                tokens.push(Token::Num(num));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || "_.-".contains(chars[i])) {
+                    i += 1;
+                }
+                tokens.push(Token::Word(chars[start..i].iter().collect()));
+            }
+            _ => return Err(format!("Unexpected '{}' at position {}", c, i)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(w) => format!("'{}'", w),
+        Token::Path(p) => format!("'{}'", p),
+        Token::Str(s) => format!("\"{}\"", s),
+        Token::Num(n) => n.to_string(),
+        Token::Op(op) => format!("'{}'", op),
+        Token::LParen => "'('".to_string(),
+        Token::RParen => "')'".to_string(),
+        Token::Comma => "','".to_string(),
+    }
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "Unexpected end of filter".to_string())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn or_expr(&mut self, depth: usize) -> Result<Expr, String> {
+        let mut left = self.and_expr(depth)?;
+        while is_keyword(self.peek(), "or") {
+            self.pos += 1;
+            let right = self.and_expr(depth)?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self, depth: usize) -> Result<Expr, String> {
+        let mut left = self.unary(depth)?;
+        while is_keyword(self.peek(), "and") {
+            self.pos += 1;
+            let right = self.unary(depth)?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self, depth: usize) -> Result<Expr, String> {
+        if depth > MAX_DEPTH {
+            return Err("Filter is nested too deeply".to_string());
+        }
+        if is_keyword(self.peek(), "not") {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.unary(depth + 1)?)));
+        }
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let expr = self.or_expr(depth + 1)?;
+            match self.next()? {
+                Token::RParen => return Ok(expr),
+                other => return Err(format!("Expected ')' but found {}", describe(&other))),
+            }
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let field = match self.next()? {
+            Token::Path(path) => Field::Path(parse_path(&path)?),
+            Token::Word(word) => match word.to_ascii_lowercase().as_str() {
+                "event" => Field::Event,
+                "team" => Field::Team,
+                "label" => Field::Label,
+                "priority" => Field::Priority,
+                _ => return Err(format!("Unknown field '{}'", word)),
+            },
+            other => return Err(format!("Expected a field but found {}", describe(&other))),
+        };
+
+        let (op, value) = match self.next()? {
+            Token::Op(op) => {
+                let op = match op {
+                    "==" => Op::Eq,
+                    "!=" => Op::Ne,
+                    ">" => Op::Gt,
+                    ">=" => Op::Ge,
+                    "<" => Op::Lt,
+                    _ => Op::Le,
+                };
+                (op, self.value()?)
+            }
+            Token::Word(w) if w.eq_ignore_ascii_case("contains") => (Op::Contains, self.value()?),
+            Token::Word(w) if w.eq_ignore_ascii_case("exists") => (Op::Exists, Value::Null),
+            Token::Word(w) if w.eq_ignore_ascii_case("in") => (Op::In(self.list()?), Value::Null),
+            other => {
+                return Err(format!(
+                    "Expected an operator but found {}",
+                    describe(&other)
+                ));
+            }
+        };
+        if matches!(field, Field::Priority)
+            && matches!(op, Op::Gt | Op::Ge | Op::Lt | Op::Le)
+            && priority_rank(&value).is_none()
+        {
+            return Err(format!(
+                "Unknown priority; expected one of {}",
+                PRIORITIES.join(", ")
+            ));
+        }
+        Ok(Expr::Compare { field, op, value })
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        match self.next()? {
+            Token::Str(s) => Ok(Value::String(s)),
+            Token::Num(n) => Ok(serde_json::Number::from_f64(n)
+                .map(Value::Number)
+                .unwrap_or(Value::Null)),
+            Token::Word(w) => Ok(match w.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" => Value::Null,
+                _ => Value::String(w),
+            }),
+            other => Err(format!("Expected a value but found {}", describe(&other))),
+        }
+    }
+
+    fn list(&mut self) -> Result<Vec<Value>, String> {
+        if self.next()? != Token::LParen {
+            return Err("Expected '(' after 'in'".to_string());
+        }
+        let mut values = vec![self.value()?];
+        loop {
+            match self.next()? {
+                Token::Comma => values.push(self.value()?),
+                Token::RParen => return Ok(values),
+                other => {
+                    return Err(format!(
+                        "Expected ',' or ')' but found {}",
+                        describe(&other)
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+    let invalid = || format!("Invalid JSONPath '{}'", path);
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let inner = &after[..end];
+            segments.push(if inner == "*" {
+                Segment::Wildcard
+            } else {
+                Segment::Index(inner.parse().map_err(|_| invalid())?)
+            });
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = &after[..end];
+            if key.is_empty() {
+                return Err(invalid());
+            }
+            segments.push(if key == "*" {
+                Segment::Wildcard
+            } else {
+                Segment::Key(key.to_string())
+            });
+            rest = &after[end..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(segments)
+}
+
+fn select<'a>(root: &'a Value, segments: &[Segment]) -> Vec<&'a Value> {
+    let mut current = vec![root];
+    for segment in segments {
+        current = current
+            .into_iter()
+            .flat_map(|value| -> Vec<&Value> {
+                match (segment, value) {
+                    (Segment::Key(key), Value::Object(map)) => map.get(key).into_iter().collect(),
+                    (Segment::Index(index), Value::Array(items)) => {
+                        items.get(*index).into_iter().collect()
+                    }
+                    (Segment::Wildcard, Value::Array(items)) => items.iter().collect(),
+                    (Segment::Wildcard, Value::Object(map)) => map.values().collect(),
+                    _ => Vec::new(),
+                }
+            })
+            .collect();
+    }
+    current
+}
+
+fn priority_rank(value: &Value) -> Option<usize> {
+    let name = value.as_str()?.to_ascii_lowercase();
+    PRIORITIES.iter().position(|p| *p == name)
+}
+
+fn eval(expr: &Expr, input: &FilterInput) -> bool {
+    match expr {
+        Expr::And(left, right) => eval(left, input) && eval(right, input),
+        Expr::Or(left, right) => eval(left, input) || eval(right, input),
+        Expr::Not(inner) => !eval(inner, input),
+        Expr::Compare { field, op, value } => {
+            let (values, builtin): (Vec<Value>, bool) = match field {
+                Field::Event => (vec![Value::String(input.event.clone())], true),
+                Field::Team => (
+                    input.teams.iter().cloned().map(Value::String).collect(),
+                    true,
+                ),
+                Field::Label => (
+                    input.labels.iter().cloned().map(Value::String).collect(),
+                    true,
+                ),
+                Field::Priority => (
+                    input.priority.iter().cloned().map(Value::String).collect(),
+                    true,
+                ),
+                Field::Path(segments) => (
+                    select(&input.payload, segments)
+                        .into_iter()
+                        .cloned()
+                        .collect(),
+                    false,
+                ),
+            };
+            let priority = matches!(field, Field::Priority);
+            match op {
+                Op::Exists => values.iter().any(|v| !v.is_null()),
+                Op::Ne => !values.iter().any(|v| equals(v, value, builtin)),
+                Op::Eq => values.iter().any(|v| equals(v, value, builtin)),
+                Op::In(options) => values
+                    .iter()
+                    .any(|v| options.iter().any(|o| equals(v, o, builtin))),
+                Op::Contains => values.iter().any(|v| contains(v, value, builtin)),
+                Op::Gt | Op::Ge | Op::Lt | Op::Le => values.iter().any(|v| {
+                    let ordering = if priority {
+                        priority_rank(v)
+                            .zip(priority_rank(value))
+                            .map(|(a, b)| a.cmp(&b))
+                    } else {
+                        compare(v, value)
+                    };
+                    ordering.is_some_and(|o| match op {
+                        Op::Gt => o == Ordering::Greater,
+                        Op::Ge => o != Ordering::Less,
+                        Op::Lt => o == Ordering::Less,
+                        _ => o != Ordering::Greater,
+                    })
+                }),
+            }
+        }
+    }
+}
+
+fn equals(actual: &Value, expected: &Value, ignore_case: bool) -> bool {
+    match (actual, expected) {
+        (Value::String(a), Value::String(b)) if ignore_case => a.eq_ignore_ascii_case(b),
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        // 未加引号的数字或布尔值也能匹配字符串形式
+        (Value::String(a), Value::Number(b)) => a.parse::<f64>().ok() == b.as_f64(),
+        (Value::String(a), Value::Bool(b)) => a.parse::<bool>().ok() == Some(*b),
+        _ => actual == expected,
+    }
+}
+
+fn contains(actual: &Value, needle: &Value, ignore_case: bool) -> bool {
+    match (actual, needle) {
+        (Value::String(haystack), Value::String(needle)) => {
+            if ignore_case {
+                haystack.to_lowercase().contains(&needle.to_lowercase())
+            } else {
+                haystack.contains(needle.as_str())
+            }
+        }
+        (Value::Array(items), _) => items.iter().any(|item| equals(item, needle, ignore_case)),
+        _ => false,
+    }
+}
+
+fn compare(actual: &Value, expected: &Value) -> Option<Ordering> {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
@@ -71,10 +71,10 @@ pub fn validate_update_profile(changes: &UpdateProfileChanges) -> Result<(), App
         return Err(AppError::validation("No update data provided"));
     }
 
-    if let Some(name) = changes.name {
-        if name.trim().is_empty() {
-            return Err(AppError::validation("Name cannot be empty"));
-        }
+    if let Some(name) = changes.name
+        && name.trim().is_empty()
+    {
+        return Err(AppError::validation("Name cannot be empty"));
     }
 
     if let Some(username) = changes.username {
@@ -28,6 +28,28 @@ pub fn validate_update_comment(content: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// A reaction is a single emoji, a short name such as `thumbs_up`, or a
+/// workspace custom emoji written as `:name:`
+pub fn validate_reaction(emoji: &str) -> Result<(), AppError> {
+    if emoji.is_empty() {
+        return Err(AppError::validation("Reaction emoji is required"));
+    }
+
+    if emoji.chars().count() > 32 {
+        return Err(AppError::validation(
+            "Reaction emoji is too long (max 32 characters)",
+        ));
+    }
+
+    if emoji.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(AppError::validation(
+            "Reaction emoji cannot contain whitespace",
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
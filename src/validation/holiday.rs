@@ -0,0 +1,51 @@
+use crate::error::AppError;
+
+pub fn validate_create_holiday(name: &str, region: &Option<String>) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::validation("Holiday name is required"));
+    }
+    if name.len() > 255 {
+        return Err(AppError::validation(
+            "Holiday name must be at most 255 characters",
+        ));
+    }
+    if let Some(r) = region {
+        validate_region(r)?;
+    }
+    Ok(())
+}
+
+pub struct UpdateHolidayChanges<'a> {
+    pub name: Option<&'a str>,
+    pub date_present: bool,
+    pub region: Option<&'a str>,
+}
+
+pub fn validate_update_holiday(changes: &UpdateHolidayChanges) -> Result<(), AppError> {
+    if changes.name.is_none() && !changes.date_present && changes.region.is_none() {
+        return Err(AppError::validation("No update data provided"));
+    }
+    if let Some(name) = changes.name
+        && name.trim().is_empty()
+    {
+        return Err(AppError::validation("Holiday name cannot be empty"));
+    }
+    if let Some(region) = changes.region {
+        validate_region(region)?;
+    }
+    Ok(())
+}
+
+pub fn validate_region(region: &str) -> Result<(), AppError> {
+    if region.is_empty()
+        || region.len() > 50
+        || !region
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(AppError::validation(
+            "Region must be a code like CN or US-CA",
+        ));
+    }
+    Ok(())
+}
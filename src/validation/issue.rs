@@ -1,4 +1,5 @@
 use crate::db::enums::IssuePriority;
+use crate::db::models::team::estimate_scales;
 use crate::error::AppError;
 
 pub fn validate_create_issue(
@@ -16,12 +17,12 @@ pub fn validate_create_issue(
         ));
     }
 
-    if let Some(desc) = description {
-        if desc.len() > 10000 {
-            return Err(AppError::validation(
-                "Issue description is too long (max 10000 characters)",
-            ));
-        }
+    if let Some(desc) = description
+        && desc.len() > 10000
+    {
+        return Err(AppError::validation(
+            "Issue description is too long (max 10000 characters)",
+        ));
     }
 
     Ok(())
@@ -47,12 +48,12 @@ pub fn validate_update_issue(
         }
     }
 
-    if let Some(description) = description {
-        if description.len() > 10000 {
-            return Err(AppError::validation(
-                "Issue description is too long (max 10000 characters)",
-            ));
-        }
+    if let Some(description) = description
+        && description.len() > 10000
+    {
+        return Err(AppError::validation(
+            "Issue description is too long (max 10000 characters)",
+        ));
     }
 
     Ok(())
@@ -63,6 +64,20 @@ pub fn validate_priority(_priority: &Option<IssuePriority>) -> Result<(), AppErr
     Ok(())
 }
 
+/// The estimate must be one of the points of the team's scale
+pub fn validate_estimate(scale: &str, estimate: i32) -> Result<(), AppError> {
+    let points = estimate_scales::points(scale).unwrap_or_default();
+    if !points.contains(&estimate) {
+        let allowed: Vec<String> = points.iter().map(|p| p.to_string()).collect();
+        return Err(AppError::validation(format!(
+            "Estimate must be one of {} on the team's {} scale",
+            allowed.join(", "),
+            scale
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -86,4 +101,15 @@ mod tests {
         assert!(validate_update_issue(&Some("".to_string()), &None).is_err());
         assert!(validate_update_issue(&Some("a".repeat(256)), &None).is_err());
     }
+
+    #[test]
+    fn test_estimate_validation() {
+        assert!(validate_estimate("fibonacci", 13).is_ok());
+        assert!(validate_estimate("fibonacci", 4).is_err());
+        assert!(validate_estimate("tshirt", 8).is_ok());
+        assert!(validate_estimate("tshirt", 0).is_err());
+        assert!(validate_estimate("linear", 7).is_ok());
+        assert!(validate_estimate("linear", 11).is_err());
+        assert!(validate_estimate("unknown", 1).is_err());
+    }
 }
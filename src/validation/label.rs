@@ -23,18 +23,17 @@ pub fn validate_update_label(changes: &UpdateLabelChanges) -> Result<(), AppErro
     if changes.name.is_none() && changes.color.is_none() && !changes.level_present {
         return Err(AppError::validation("No update data provided"));
     }
-    if let Some(name) = changes.name {
-        if name.trim().is_empty() {
-            return Err(AppError::validation("Label name cannot be empty"));
-        }
+    if let Some(name) = changes.name
+        && name.trim().is_empty()
+    {
+        return Err(AppError::validation("Label name cannot be empty"));
     }
-    if let Some(color) = changes.color {
-        if !color.starts_with('#')
+    if let Some(color) = changes.color
+        && (!color.starts_with('#')
             || color.len() != 7
-            || !color.chars().skip(1).all(|c| c.is_ascii_hexdigit())
-        {
-            return Err(AppError::validation("Color must be hex like #RRGGBB"));
-        }
+            || !color.chars().skip(1).all(|c| c.is_ascii_hexdigit()))
+    {
+        return Err(AppError::validation("Color must be hex like #RRGGBB"));
     }
     Ok(())
 }
@@ -0,0 +1,17 @@
+use crate::error::AppError;
+
+pub const MAX_MILESTONE_NAME_CHARS: usize = 255;
+
+pub fn validate_milestone_name(name: &str) -> Result<(), AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation("Milestone name is required"));
+    }
+    if name.chars().count() > MAX_MILESTONE_NAME_CHARS {
+        return Err(AppError::validation(format!(
+            "Milestone name must be at most {} characters",
+            MAX_MILESTONE_NAME_CHARS
+        )));
+    }
+    Ok(())
+}
@@ -1,9 +1,11 @@
 pub mod auth;
 pub mod comment;
 pub mod cycle;
+pub mod holiday;
 pub mod invitation;
 pub mod issue;
 pub mod label;
+pub mod milestone;
 pub mod project;
 pub mod project_status;
 pub mod workflow;
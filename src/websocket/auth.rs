@@ -1,6 +1,9 @@
 use crate::{
-    db::{DbPool, models::AuthUser},
+    cache::TokenRevocationList,
+    db::{DbExecutor, DbPool, models::AuthUser},
+    error::AppError,
     middleware::auth::{AuthConfig, AuthService},
+    utils::clock,
 };
 use axum::{extract::Query, http::StatusCode};
 use diesel::prelude::*;
@@ -32,15 +35,22 @@ pub struct AuthenticatedUser {
 #[derive(Debug, Deserialize)]
 pub struct WebSocketAuthQuery {
     pub token: Option<String>,
+    /// 客户端自报的名称（如 web、desktop、ios），用于会话统计
+    pub client: Option<String>,
+    /// 上次连接下发的恢复令牌，用于恢复订阅并重放断开期间的消息
+    pub recovery_token: Option<String>,
+    /// 客户端支持的出站消息压缩算法，逗号分隔、按偏好排序，目前支持 `deflate`
+    pub compression: Option<String>,
 }
 
 pub struct WebSocketAuth;
 
 impl WebSocketAuth {
-    /// 验证WebSocket连接的JWT token
+    /// 验证WebSocket连接的JWT token；提供吊销列表时同时检查token是否已被吊销
     pub async fn authenticate_websocket(
         pool: Arc<DbPool>,
         token: &str,
+        revocations: Option<&TokenRevocationList>,
     ) -> Result<AuthenticatedUser, WebSocketAuthError> {
         // 使用现有的认证服务
         let auth_service = AuthService::new(AuthConfig::default());
@@ -51,6 +61,14 @@ impl WebSocketAuth {
             WebSocketAuthError::InvalidToken
         })?;
 
+        if let Some(revocations) = revocations
+            && revocations
+                .is_revoked(claims.sub, &claims.jti, claims.iat)
+                .await
+        {
+            return Err(WebSocketAuthError::RevokedToken);
+        }
+
         let user_id = claims.sub;
 
         // 从数据库获取用户信息
@@ -75,13 +93,14 @@ impl WebSocketAuth {
     pub async fn extract_and_validate_token(
         pool: Arc<DbPool>,
         query: Query<WebSocketAuthQuery>,
+        revocations: Option<&TokenRevocationList>,
     ) -> Result<AuthenticatedUser, WebSocketAuthError> {
         let token = query
             .token
             .as_ref()
             .ok_or(WebSocketAuthError::MissingToken)?;
 
-        Self::authenticate_websocket(pool, token).await
+        Self::authenticate_websocket(pool, token, revocations).await
     }
 
     /// 从URL参数或Header中提取token
@@ -92,10 +111,10 @@ impl WebSocketAuth {
         }
 
         // 尝试从authorization参数获取
-        if let Some(auth) = query_params.get("authorization") {
-            if let Some(token) = auth.strip_prefix("Bearer ") {
-                return Some(token.to_string());
-            }
+        if let Some(auth) = query_params.get("authorization")
+            && let Some(token) = auth.strip_prefix("Bearer ")
+        {
+            return Some(token.to_string());
         }
 
         None
@@ -110,7 +129,7 @@ impl WebSocketAuth {
 
     /// 检查token是否过期
     pub fn is_token_expired(claims: &Claims) -> bool {
-        let now = chrono::Utc::now().timestamp() as u64;
+        let now = clock::now().timestamp() as u64;
         claims.exp < now
     }
 
@@ -124,6 +143,9 @@ impl WebSocketAuth {
                 (StatusCode::UNAUTHORIZED, "Invalid authentication token")
             }
             WebSocketAuthError::ExpiredToken => (StatusCode::UNAUTHORIZED, "Token has expired"),
+            WebSocketAuthError::RevokedToken => {
+                (StatusCode::UNAUTHORIZED, "Token has been revoked")
+            }
             WebSocketAuthError::UserNotFound => (StatusCode::NOT_FOUND, "User not found"),
             WebSocketAuthError::InvalidUserId => {
                 (StatusCode::BAD_REQUEST, "Invalid user ID format")
@@ -140,6 +162,7 @@ pub enum WebSocketAuthError {
     MissingToken,
     InvalidToken,
     ExpiredToken,
+    RevokedToken,
     UserNotFound,
     InvalidUserId,
     DatabaseError,
@@ -151,6 +174,7 @@ impl std::fmt::Display for WebSocketAuthError {
             WebSocketAuthError::MissingToken => write!(f, "Missing authentication token"),
             WebSocketAuthError::InvalidToken => write!(f, "Invalid authentication token"),
             WebSocketAuthError::ExpiredToken => write!(f, "Token has expired"),
+            WebSocketAuthError::RevokedToken => write!(f, "Token has been revoked"),
             WebSocketAuthError::UserNotFound => write!(f, "User not found"),
             WebSocketAuthError::InvalidUserId => write!(f, "Invalid user ID format"),
             WebSocketAuthError::DatabaseError => write!(f, "Database error"),
@@ -160,19 +184,19 @@ impl std::fmt::Display for WebSocketAuthError {
 
 impl std::error::Error for WebSocketAuthError {}
 
-async fn get_user_by_id(
-    pool: &Arc<DbPool>,
-    user_id: Uuid,
-) -> Result<AuthUser, diesel::result::Error> {
+async fn get_user_by_id(pool: &Arc<DbPool>, user_id: Uuid) -> Result<AuthUser, AppError> {
     use crate::schema::users::dsl::*;
 
-    let mut conn = pool.get().expect("Failed to get DB connection");
-
-    let user = users
-        .filter(id.eq(user_id))
-        .filter(is_active.eq(true))
-        .select(crate::db::models::User::as_select())
-        .first(&mut conn)?;
+    let user = DbExecutor::new(DbPool::clone(pool))
+        .read(move |conn| {
+            users
+                .filter(id.eq(user_id))
+                .filter(is_active.eq(true))
+                .select(crate::db::models::User::as_select())
+                .first(conn)
+                .map_err(AppError::from)
+        })
+        .await?;
 
     Ok(AuthUser {
         id: user.id,
@@ -183,22 +207,19 @@ async fn get_user_by_id(
     })
 }
 
-async fn get_user_current_workspace(
-    pool: &Arc<DbPool>,
-    user_id: Uuid,
-) -> Result<Uuid, diesel::result::Error> {
+async fn get_user_current_workspace(pool: &Arc<DbPool>, user_id: Uuid) -> Result<Uuid, AppError> {
     use crate::schema::users::dsl::*;
 
-    let mut conn = pool.get().expect("Failed to get DB connection");
-
-    let workspace_id = users
-        .filter(id.eq(user_id))
-        .filter(is_active.eq(true))
-        .select(current_workspace_id)
-        .first::<Option<Uuid>>(&mut conn)?
-        .ok_or_else(|| diesel::result::Error::NotFound)?;
-
-    Ok(workspace_id)
+    DbExecutor::new(DbPool::clone(pool))
+        .read(move |conn| {
+            users
+                .filter(id.eq(user_id))
+                .filter(is_active.eq(true))
+                .select(current_workspace_id)
+                .first::<Option<Uuid>>(conn)?
+                .ok_or_else(|| AppError::not_found("Current workspace"))
+        })
+        .await
 }
 
 #[cfg(test)]
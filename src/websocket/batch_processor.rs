@@ -2,6 +2,7 @@
 //!
 //! 提供事件和消息的批处理能力，优化系统吞吐量和性能
 
+use crate::utils::clock;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
@@ -534,7 +535,7 @@ where
 
     /// 内部批处理函数
     async fn process_batch_internal(&self, items: Vec<BatchItem<T>>) {
-        let batch_id = Uuid::new_v4().to_string();
+        let batch_id = clock::new_id().to_string();
         let batch_size = items.len();
         let start_time = Instant::now();
 
@@ -613,7 +614,7 @@ where
 
         metrics.queue_length = self.get_total_queue_length().await;
         metrics.max_queue_length = metrics.max_queue_length.max(metrics.queue_length);
-        metrics.last_updated = chrono::Utc::now();
+        metrics.last_updated = clock::now();
     }
 
     /// 启动指标收集任务
@@ -831,7 +832,7 @@ where
             "current_batch_size": adaptive_config.current_batch_size,
             "current_timeout_ms": adaptive_config.current_timeout_ms,
             "queue_by_priority": queue_by_priority,
-            "timestamp": chrono::Utc::now()
+            "timestamp": clock::now()
         })
     }
 }
@@ -873,7 +874,7 @@ impl BatchProcessor<GenericWebSocketEvent, EventResult> for WebSocketEventBatchP
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = BatchResult<EventResult>> + Send + 'a>>
     {
         Box::pin(async move {
-            let batch_id = Uuid::new_v4().to_string();
+            let batch_id = clock::new_id().to_string();
             let start_time = Instant::now();
             let total_items = items.len();
 
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// How long a drag hint lives without being refreshed
+pub const DRAG_LOCK_TTL_SECS: i64 = 10;
+
+#[derive(Debug, Clone, Copy)]
+struct DragHold {
+    user_id: Uuid,
+    acquired_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+/// Hint broadcast to board viewers while an issue is being dragged. More than
+/// one holder means users are dragging it at the same time; `owner_id` is the
+/// one whose drop will be accepted.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct IssueMoveLock {
+    pub issue_id: Uuid,
+    pub owner_id: Option<Uuid>,
+    pub holders: Vec<Uuid>,
+    pub conflict: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub ttl_secs: i64,
+}
+
+/// Short-lived, in-memory drag holds per issue. Holds expire on their own so
+/// a client that disconnects mid-drag does not block the issue.
+#[derive(Default)]
+pub struct BoardLocks {
+    holds: Mutex<HashMap<Uuid, Vec<DragHold>>>,
+}
+
+impl BoardLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start or refresh the user's drag of the issue
+    pub fn acquire(&self, issue_id: Uuid, user_id: Uuid, now: DateTime<Utc>) -> IssueMoveLock {
+        let mut holds = self.holds.lock().unwrap();
+        Self::drop_expired(&mut holds, now);
+        let entry = holds.entry(issue_id).or_default();
+        let expires_at = now + Duration::seconds(DRAG_LOCK_TTL_SECS);
+        match entry.iter_mut().find(|h| h.user_id == user_id) {
+            Some(hold) => hold.expires_at = expires_at,
+            None => entry.push(DragHold {
+                user_id,
+                acquired_at: now,
+                expires_at,
+            }),
+        }
+        Self::snapshot(issue_id, entry)
+    }
+
+    /// End the user's drag; returns the remaining state of the issue
+    pub fn release(&self, issue_id: Uuid, user_id: Uuid, now: DateTime<Utc>) -> IssueMoveLock {
+        let mut holds = self.holds.lock().unwrap();
+        let snapshot = match holds.get_mut(&issue_id) {
+            Some(entry) => {
+                entry.retain(|h| h.user_id != user_id && h.expires_at > now);
+                Self::snapshot(issue_id, entry)
+            }
+            None => Self::snapshot(issue_id, &[]),
+        };
+        if snapshot.holders.is_empty() {
+            holds.remove(&issue_id);
+        }
+        snapshot
+    }
+
+    /// Holder whose move wins: the earliest live hold, ties broken by the
+    /// smaller user id so every server instance picks the same one
+    pub fn owner(&self, issue_id: Uuid, now: DateTime<Utc>) -> Option<Uuid> {
+        let holds = self.holds.lock().unwrap();
+        let live: Vec<DragHold> = holds
+            .get(&issue_id)?
+            .iter()
+            .filter(|h| h.expires_at > now)
+            .copied()
+            .collect();
+        Self::winner(&live).map(|h| h.user_id)
+    }
+
+    fn drop_expired(holds: &mut HashMap<Uuid, Vec<DragHold>>, now: DateTime<Utc>) {
+        holds.retain(|_, entry| {
+            entry.retain(|h| h.expires_at > now);
+            !entry.is_empty()
+        });
+    }
+
+    fn winner(holds: &[DragHold]) -> Option<&DragHold> {
+        holds.iter().min_by_key(|h| (h.acquired_at, h.user_id))
+    }
+
+    fn snapshot(issue_id: Uuid, holds: &[DragHold]) -> IssueMoveLock {
+        let mut ordered: Vec<&DragHold> = holds.iter().collect();
+        ordered.sort_by_key(|h| (h.acquired_at, h.user_id));
+        IssueMoveLock {
+            issue_id,
+            owner_id: Self::winner(holds).map(|h| h.user_id),
+            holders: ordered.iter().map(|h| h.user_id).collect(),
+            conflict: ordered.len() > 1,
+            expires_at: ordered.iter().map(|h| h.expires_at).max(),
+            ttl_secs: DRAG_LOCK_TTL_SECS,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor, db::models::board::ReorderIssueRequest, error::AppError,
+    services::board_service::BoardService, services::context::RequestContext, utils::clock,
+    websocket::board_locks::BoardLocks,
+};
+
+pub struct BoardHandlers;
+
+impl BoardHandlers {
+    pub async fn handle_start_issue_drag(
+        db: &DbExecutor,
+        locks: &BoardLocks,
+        ctx: RequestContext,
+        issue_id: Uuid,
+    ) -> Result<serde_json::Value, AppError> {
+        let team_id = {
+            let ctx = ctx.clone();
+            db.read(move |conn| BoardService::issue_team(conn, &ctx, issue_id))
+                .await?
+        };
+        let lock = locks.acquire(issue_id, ctx.user_id, clock::now());
+        BoardService::publish_lock(&ctx, team_id, &lock);
+        Ok(serde_json::to_value(lock).unwrap())
+    }
+
+    pub async fn handle_end_issue_drag(
+        db: &DbExecutor,
+        locks: &BoardLocks,
+        ctx: RequestContext,
+        issue_id: Uuid,
+    ) -> Result<serde_json::Value, AppError> {
+        let team_id = {
+            let ctx = ctx.clone();
+            db.read(move |conn| BoardService::issue_team(conn, &ctx, issue_id))
+                .await?
+        };
+        let lock = locks.release(issue_id, ctx.user_id, clock::now());
+        BoardService::publish_lock(&ctx, team_id, &lock);
+        Ok(serde_json::to_value(lock).unwrap())
+    }
+
+    /// Drops from users who are not the winning drag holder are rejected, so
+    /// simultaneous drags resolve the same way regardless of arrival order
+    pub async fn handle_reorder_issue(
+        db: &DbExecutor,
+        locks: &BoardLocks,
+        ctx: RequestContext,
+        issue_id: Uuid,
+        data: ReorderIssueRequest,
+    ) -> Result<serde_json::Value, AppError> {
+        let now = clock::now();
+        if let Some(owner) = locks.owner(issue_id, now)
+            && owner != ctx.user_id
+        {
+            return Err(AppError::conflict_with_code(
+                "Another user is moving this issue",
+                None,
+                "ISSUE_LOCKED_FOR_MOVE",
+            ));
+        }
+        let result = {
+            let ctx = ctx.clone();
+            db.transaction(move |conn| BoardService::reorder(conn, &ctx, issue_id, &data))
+                .await?
+        };
+        let lock = locks.release(issue_id, ctx.user_id, now);
+        BoardService::publish_lock(&ctx, result.issue.team_id, &lock);
+        Ok(serde_json::to_value(result).unwrap())
+    }
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+
+use crate::{
+    db::DbExecutor, db::models::comment::CommentPageQuery, error::AppError,
+    services::comments_service::CommentsService, services::context::RequestContext,
+};
+
+pub struct CommentHandlers;
+
+impl CommentHandlers {
+    pub async fn handle_query_comments(
+        db: &DbExecutor,
+        ctx: RequestContext,
+        issue_id: Uuid,
+        query: CommentPageQuery,
+    ) -> Result<serde_json::Value, AppError> {
+        let page = db
+            .read(move |conn| CommentsService::page_by_issue(conn, &ctx, issue_id, &query))
+            .await?;
+        Ok(serde_json::to_value(page).unwrap())
+    }
+}
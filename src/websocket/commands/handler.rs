@@ -1,29 +1,39 @@
-use diesel::{Connection, RunQueryDsl};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    db::DbPool, error::AppError, services::context::RequestContext,
+    db::models::comment::CommentPageQuery,
+    db::models::websocket_dead_letter::NewWebSocketDeadLetter,
+    db::{DbExecutor, DbPool},
+    error::AppError,
+    services::context::{AuthChannel, RequestContext},
+    services::dead_letter_service::DeadLetterService,
+    services::permission_service::{Permission, PermissionService},
+    utils::clock,
+    utils::json_patch::PatchDocument,
     websocket::security::SecureMessage,
+    websocket::topic::Topic,
 };
 
 use super::types::*;
 
 #[derive(Clone)]
 pub struct WebSocketCommandHandler {
-    db: Arc<DbPool>,
+    db: DbExecutor,
     idempotency: IdempotencyControl,
     message_signer: Option<Arc<crate::websocket::MessageSigner>>,
     asset_helper: Arc<crate::utils::AssetUrlHelper>,
+    board_locks: Arc<crate::websocket::BoardLocks>,
 }
 
 impl WebSocketCommandHandler {
     pub fn new(db: Arc<DbPool>, asset_helper: Arc<crate::utils::AssetUrlHelper>) -> Self {
         Self {
-            db,
+            db: DbExecutor::new(DbPool::clone(&db)),
             idempotency: IdempotencyControl::new(300),
             message_signer: None,
             asset_helper,
+            board_locks: Arc::new(crate::websocket::BoardLocks::new()),
         }
     }
 
@@ -161,9 +171,14 @@ impl WebSocketCommandHandler {
                     team_key.hash(&mut hasher);
                 }
             }
-            WebSocketCommand::DeleteTeam { team_id, .. } => {
+            WebSocketCommand::DeleteTeam {
+                team_id,
+                confirmation_token,
+                ..
+            } => {
                 "delete_team".hash(&mut hasher);
                 team_id.hash(&mut hasher);
+                confirmation_token.hash(&mut hasher);
             }
             WebSocketCommand::QueryTeams { .. } => {
                 "query_teams".hash(&mut hasher);
@@ -222,6 +237,7 @@ impl WebSocketCommandHandler {
                 if let Some(ref search) = filters.search {
                     search.hash(&mut hasher);
                 }
+                filters.fields.hash(&mut hasher);
             }
             WebSocketCommand::CreateWorkspace { data, .. } => {
                 "create_workspace".hash(&mut hasher);
@@ -240,9 +256,14 @@ impl WebSocketCommandHandler {
                     url_key.hash(&mut hasher);
                 }
             }
-            WebSocketCommand::DeleteWorkspace { workspace_id, .. } => {
+            WebSocketCommand::DeleteWorkspace {
+                workspace_id,
+                confirmation_token,
+                ..
+            } => {
                 "delete_workspace".hash(&mut hasher);
                 workspace_id.hash(&mut hasher);
+                confirmation_token.hash(&mut hasher);
             }
             WebSocketCommand::GetCurrentWorkspace { .. } => {
                 "get_current_workspace".hash(&mut hasher);
@@ -326,6 +347,7 @@ impl WebSocketCommandHandler {
                 if let Some(owner_id) = filters.owner_id {
                     owner_id.hash(&mut hasher);
                 }
+                filters.fields.hash(&mut hasher);
             }
             WebSocketCommand::CreateIssue { data, .. } => {
                 "create_issue".hash(&mut hasher);
@@ -352,6 +374,15 @@ impl WebSocketCommandHandler {
                 "delete_issue".hash(&mut hasher);
                 issue_id.hash(&mut hasher);
             }
+            WebSocketCommand::ApplyIssuePatch {
+                issue_id, patch, ..
+            } => {
+                "apply_issue_patch".hash(&mut hasher);
+                issue_id.hash(&mut hasher);
+                serde_json::to_string(patch)
+                    .unwrap_or_default()
+                    .hash(&mut hasher);
+            }
             WebSocketCommand::QueryIssues { filters, .. } => {
                 "query_issues".hash(&mut hasher);
                 if let Some(team_id) = filters.team_id {
@@ -369,13 +400,42 @@ impl WebSocketCommandHandler {
                 if let Some(ref search) = filters.search {
                     search.hash(&mut hasher);
                 }
+                if let Some(limit) = filters.limit {
+                    limit.hash(&mut hasher);
+                }
+                filters.fields.hash(&mut hasher);
             }
             WebSocketCommand::GetIssue { issue_id, .. } => {
                 "get_issue".hash(&mut hasher);
                 issue_id.hash(&mut hasher);
             }
+            WebSocketCommand::QueryComments {
+                issue_id, query, ..
+            } => {
+                "query_comments".hash(&mut hasher);
+                issue_id.hash(&mut hasher);
+                serde_json::to_string(query)
+                    .unwrap_or_default()
+                    .hash(&mut hasher);
+            }
+            WebSocketCommand::StartIssueDrag { issue_id, .. } => {
+                "start_issue_drag".hash(&mut hasher);
+                issue_id.hash(&mut hasher);
+            }
+            WebSocketCommand::EndIssueDrag { issue_id, .. } => {
+                "end_issue_drag".hash(&mut hasher);
+                issue_id.hash(&mut hasher);
+            }
+            WebSocketCommand::ReorderIssue { issue_id, data, .. } => {
+                "reorder_issue".hash(&mut hasher);
+                issue_id.hash(&mut hasher);
+                data.position.to_bits().hash(&mut hasher);
+                if let Some(state_id) = data.workflow_state_id {
+                    state_id.hash(&mut hasher);
+                }
+            }
         }
-        let time_window = chrono::Utc::now().timestamp() / 300;
+        let time_window = clock::now().timestamp() / 300;
         time_window.hash(&mut hasher);
         format!("ws_cmd_{:x}", hasher.finish())
     }
@@ -416,6 +476,94 @@ impl WebSocketCommandHandler {
         command: WebSocketCommand,
         user: &crate::websocket::auth::AuthenticatedUser,
     ) -> WebSocketCommandResponse {
+        self.handle_connection_command(command, user, None).await
+    }
+
+    /// Handle a command received on a live connection; `connection` is the
+    /// manager's record of that connection, used by `get_connection_info`.
+    /// Commands that change data and fail with a server-side error are kept
+    /// as dead letters, and the error details carry the dead letter id.
+    pub async fn handle_connection_command(
+        &self,
+        command: WebSocketCommand,
+        user: &crate::websocket::auth::AuthenticatedUser,
+        connection: Option<ConnectionInfo>,
+    ) -> WebSocketCommandResponse {
+        let replayable = command.is_replayable().then(|| command.clone());
+        let (mut response, failure) = self.execute_command(command, user, connection).await;
+
+        if let (Some(command), Some(error), Some(workspace_id)) =
+            (replayable, failure, user.current_workspace_id)
+            && DeadLetterService::is_dead_letter(&error)
+            && let Some(dead_letter_id) = self
+                .record_dead_letter(workspace_id, user.user_id, command, &response, &error)
+                .await
+            && let Some(command_error) = response.error.as_mut()
+        {
+            command_error.details = Some(serde_json::json!({ "dead_letter_id": dead_letter_id }));
+        }
+        response
+    }
+
+    /// Run a dead-lettered command again as `user`; returns the response and
+    /// the error it failed with, without recording a new dead letter
+    pub async fn replay_command(
+        &self,
+        command: WebSocketCommand,
+        user: &crate::websocket::auth::AuthenticatedUser,
+    ) -> (WebSocketCommandResponse, Option<AppError>) {
+        self.execute_command(command, user, None).await
+    }
+
+    async fn record_dead_letter(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        command: WebSocketCommand,
+        response: &WebSocketCommandResponse,
+        error: &AppError,
+    ) -> Option<Uuid> {
+        let command = match serde_json::to_string(&command) {
+            Ok(command) => command,
+            Err(e) => {
+                tracing::warn!("Failed to serialize dead-lettered command: {}", e);
+                return None;
+            }
+        };
+        let new = NewWebSocketDeadLetter {
+            workspace_id,
+            user_id,
+            command_type: response.command_type.clone(),
+            request_id: response.request_id.clone(),
+            command,
+            error_code: error.code().as_str().to_string(),
+            error_message: error.to_string(),
+        };
+        match self
+            .db
+            .transaction(move |conn| DeadLetterService::record(conn, &new))
+            .await
+        {
+            Ok(dead_letter) => Some(dead_letter.id),
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to record dead letter for {} command: {}",
+                    response.command_type,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Run a command; the error is the one it failed with, if it failed
+    /// with an `AppError` rather than a rejected precondition
+    async fn execute_command(
+        &self,
+        command: WebSocketCommand,
+        user: &crate::websocket::auth::AuthenticatedUser,
+        connection: Option<ConnectionInfo>,
+    ) -> (WebSocketCommandResponse, Option<AppError>) {
         let request_id = match &command {
             WebSocketCommand::CreateLabel { request_id, .. }
             | WebSocketCommand::UpdateLabel { request_id, .. }
@@ -456,8 +604,13 @@ impl WebSocketCommandHandler {
             | WebSocketCommand::CreateIssue { request_id, .. }
             | WebSocketCommand::UpdateIssue { request_id, .. }
             | WebSocketCommand::DeleteIssue { request_id, .. }
+            | WebSocketCommand::ApplyIssuePatch { request_id, .. }
             | WebSocketCommand::QueryIssues { request_id, .. }
-            | WebSocketCommand::GetIssue { request_id, .. } => request_id.clone(),
+            | WebSocketCommand::GetIssue { request_id, .. }
+            | WebSocketCommand::QueryComments { request_id, .. }
+            | WebSocketCommand::StartIssueDrag { request_id, .. }
+            | WebSocketCommand::EndIssueDrag { request_id, .. }
+            | WebSocketCommand::ReorderIssue { request_id, .. } => request_id.clone(),
         };
 
         let idempotency_key = "disabled".to_string();
@@ -501,14 +654,19 @@ impl WebSocketCommandHandler {
             WebSocketCommand::CreateIssue { .. } => "create_issue",
             WebSocketCommand::UpdateIssue { .. } => "update_issue",
             WebSocketCommand::DeleteIssue { .. } => "delete_issue",
+            WebSocketCommand::ApplyIssuePatch { .. } => "apply_issue_patch",
             WebSocketCommand::QueryIssues { .. } => "query_issues",
             WebSocketCommand::GetIssue { .. } => "get_issue",
+            WebSocketCommand::QueryComments { .. } => "query_comments",
+            WebSocketCommand::StartIssueDrag { .. } => "start_issue_drag",
+            WebSocketCommand::EndIssueDrag { .. } => "end_issue_drag",
+            WebSocketCommand::ReorderIssue { .. } => "reorder_issue",
         };
 
         let workspace_id = match user.current_workspace_id {
             Some(ws) => ws,
             None => {
-                return WebSocketCommandResponse::error(
+                let response = WebSocketCommandResponse::error(
                     command_type,
                     &idempotency_key,
                     request_id,
@@ -517,6 +675,7 @@ impl WebSocketCommandHandler {
                         "No current workspace selected",
                     ),
                 );
+                return (response, None);
             }
         };
 
@@ -524,8 +683,21 @@ impl WebSocketCommandHandler {
             user_id: user.user_id,
             workspace_id,
             idempotency_key: Some(idempotency_key.clone()),
+            channel: AuthChannel::Session,
         };
 
+        if let Some(permission) = command.required_permission()
+            && let Err(err) = self.check_permission(&ctx, permission).await
+        {
+            let error = match &err {
+                AppError::Forbidden { message } => WebSocketCommandError::permission_error(message),
+                other => WebSocketCommandError::system_error(&other.to_string()),
+            };
+            let response =
+                WebSocketCommandResponse::error(command_type, &idempotency_key, request_id, error);
+            return (response, Some(err));
+        }
+
         let result = match command {
             WebSocketCommand::CreateLabel { data, .. } => self.handle_create_label(ctx, data).await,
             WebSocketCommand::UpdateLabel { label_id, data, .. } => {
@@ -551,15 +723,20 @@ impl WebSocketCommandHandler {
                 self.handle_unsubscribe(ctx, topics).await
             }
             WebSocketCommand::GetConnectionInfo { .. } => {
-                self.handle_get_connection_info(ctx, user).await
+                self.handle_get_connection_info(ctx, connection).await
             }
             WebSocketCommand::Ping { .. } => Ok(serde_json::json!({"message": "pong"})),
             WebSocketCommand::CreateTeam { data, .. } => self.handle_create_team(ctx, data).await,
             WebSocketCommand::UpdateTeam { team_id, data, .. } => {
                 self.handle_update_team(ctx, team_id, data).await
             }
-            WebSocketCommand::DeleteTeam { team_id, .. } => {
-                self.handle_delete_team(ctx, team_id).await
+            WebSocketCommand::DeleteTeam {
+                team_id,
+                confirmation_token,
+                ..
+            } => {
+                self.handle_delete_team(ctx, team_id, confirmation_token)
+                    .await
             }
             WebSocketCommand::QueryTeams { .. } => self.handle_query_teams(ctx).await,
             WebSocketCommand::AddTeamMember { team_id, data, .. } => {
@@ -618,8 +795,13 @@ impl WebSocketCommandHandler {
             WebSocketCommand::UpdateWorkspace {
                 workspace_id, data, ..
             } => self.handle_update_workspace(ctx, workspace_id, data).await,
-            WebSocketCommand::DeleteWorkspace { workspace_id, .. } => {
-                self.handle_delete_workspace(ctx, workspace_id).await
+            WebSocketCommand::DeleteWorkspace {
+                workspace_id,
+                confirmation_token,
+                ..
+            } => {
+                self.handle_delete_workspace(ctx, workspace_id, confirmation_token)
+                    .await
             }
             WebSocketCommand::GetCurrentWorkspace { .. } => {
                 self.handle_get_current_workspace(ctx).await
@@ -646,27 +828,57 @@ impl WebSocketCommandHandler {
             WebSocketCommand::DeleteIssue { issue_id, .. } => {
                 self.handle_delete_issue(ctx, issue_id).await
             }
+            WebSocketCommand::ApplyIssuePatch {
+                issue_id, patch, ..
+            } => self.handle_apply_issue_patch(ctx, issue_id, patch).await,
             WebSocketCommand::QueryIssues { filters, .. } => {
                 self.handle_query_issues(ctx, filters).await
             }
             WebSocketCommand::GetIssue { issue_id, .. } => {
                 self.handle_get_issue(ctx, issue_id).await
             }
+            WebSocketCommand::QueryComments {
+                issue_id, query, ..
+            } => self.handle_query_comments(ctx, issue_id, query).await,
+            WebSocketCommand::StartIssueDrag { issue_id, .. } => {
+                self.handle_start_issue_drag(ctx, issue_id).await
+            }
+            WebSocketCommand::EndIssueDrag { issue_id, .. } => {
+                self.handle_end_issue_drag(ctx, issue_id).await
+            }
+            WebSocketCommand::ReorderIssue { issue_id, data, .. } => {
+                self.handle_reorder_issue(ctx, issue_id, data).await
+            }
         };
 
         match result {
-            Ok(data) => {
-                WebSocketCommandResponse::success(command_type, &idempotency_key, request_id, data)
-            }
-            Err(app_error) => WebSocketCommandResponse::error(
-                command_type,
-                &idempotency_key,
-                request_id,
-                WebSocketCommandError::business_error("COMMAND_ERROR", &app_error.to_string()),
+            Ok(data) => (
+                WebSocketCommandResponse::success(command_type, &idempotency_key, request_id, data),
+                None,
+            ),
+            Err(app_error) => (
+                WebSocketCommandResponse::error(
+                    command_type,
+                    &idempotency_key,
+                    request_id,
+                    WebSocketCommandError::business_error("COMMAND_ERROR", &app_error.to_string()),
+                ),
+                Some(app_error),
             ),
         }
     }
 
+    async fn check_permission(
+        &self,
+        ctx: &RequestContext,
+        permission: Permission,
+    ) -> Result<(), AppError> {
+        let ctx = ctx.clone();
+        self.db
+            .read(move |conn| PermissionService::require(conn, &ctx, permission).map(|_| ()))
+            .await
+    }
+
     // Label handlers (delegate)
     async fn handle_create_label(
         &self,
@@ -755,11 +967,35 @@ impl WebSocketCommandHandler {
         )
     }
 
+    /// Validate and normalize topic subscriptions; the manager applies them
+    /// to the connection once the command succeeds
+    fn normalize_topics(topics: &[String]) -> Result<Vec<String>, AppError> {
+        if topics.is_empty() {
+            return Err(AppError::validation("At least one topic is required"));
+        }
+        let invalid: Vec<&str> = topics
+            .iter()
+            .filter(|t| Topic::normalize_subscription(t).is_none())
+            .map(String::as_str)
+            .collect();
+        if !invalid.is_empty() {
+            return Err(AppError::validation(format!(
+                "Invalid topics: {}. Use workspace, issue:<id>, project:<id>, team:<id> or <kind>:*",
+                invalid.join(", ")
+            )));
+        }
+        Ok(topics
+            .iter()
+            .filter_map(|t| Topic::normalize_subscription(t))
+            .collect())
+    }
+
     async fn handle_subscribe(
         &self,
         _ctx: RequestContext,
         topics: Vec<String>,
     ) -> Result<serde_json::Value, AppError> {
+        let topics = Self::normalize_topics(&topics)?;
         Ok(
             serde_json::json!({"subscribed_topics": topics, "message": "Successfully subscribed to topics"}),
         )
@@ -770,6 +1006,7 @@ impl WebSocketCommandHandler {
         _ctx: RequestContext,
         topics: Vec<String>,
     ) -> Result<serde_json::Value, AppError> {
+        let topics = Self::normalize_topics(&topics)?;
         Ok(
             serde_json::json!({"unsubscribed_topics": topics, "message": "Successfully unsubscribed from topics"}),
         )
@@ -778,17 +1015,9 @@ impl WebSocketCommandHandler {
     async fn handle_get_connection_info(
         &self,
         _ctx: RequestContext,
-        user: &crate::websocket::auth::AuthenticatedUser,
+        connection: Option<ConnectionInfo>,
     ) -> Result<serde_json::Value, AppError> {
-        let connection_info = ConnectionInfo {
-            user_id: user.user_id,
-            username: user.username.clone(),
-            connected_at: chrono::Utc::now(),
-            last_ping: chrono::Utc::now(),
-            subscriptions: vec![],
-            message_queue_size: 0,
-            state: "connected".to_string(),
-        };
+        let connection_info = connection.ok_or_else(|| AppError::not_found("Connection"))?;
         serde_json::to_value(connection_info).map_err(|e| AppError::Internal(e.to_string()))
     }
 
@@ -798,70 +1027,7 @@ impl WebSocketCommandHandler {
         ctx: RequestContext,
         data: CreateTeamCommand,
     ) -> Result<serde_json::Value, AppError> {
-        if data.name.trim().is_empty() {
-            return Err(AppError::validation("Team name is required"));
-        }
-        if data.team_key.trim().is_empty() {
-            return Err(AppError::validation("Team key is required"));
-        }
-        if !data
-            .team_key
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
-        {
-            return Err(AppError::validation(
-                "Team key can only contain letters, numbers, hyphens, and underscores",
-            ));
-        }
-        let mut conn = self
-            .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let req = crate::routes::teams::CreateTeamRequest {
-            name: data.name,
-            team_key: data.team_key,
-            description: data.description,
-            icon_url: data.icon_url,
-            is_private: data.is_private,
-        };
-        let user_id = ctx.user_id;
-        let current_workspace_id = ctx.workspace_id;
-        use crate::schema;
-        let result =
-            conn.transaction::<crate::db::models::team::Team, diesel::result::Error, _>(|conn| {
-                let new_team = crate::db::models::team::NewTeam {
-                    name: req.name.clone(),
-                    team_key: req.team_key.clone(),
-                    description: req.description.clone(),
-                    icon_url: req.icon_url.clone(),
-                    is_private: req.is_private,
-                    workspace_id: current_workspace_id,
-                };
-                let team: crate::db::models::team::Team = diesel::insert_into(schema::teams::table)
-                    .values(&new_team)
-                    .get_result::<crate::db::models::team::Team>(conn)?;
-                let new_team_member = crate::db::models::team::NewTeamMember {
-                    user_id,
-                    team_id: team.id,
-                    role: "admin".to_string(),
-                };
-                diesel::insert_into(schema::team_members::table)
-                    .values(&new_team_member)
-                    .execute(conn)?;
-                Ok(team)
-            });
-        match result {
-            Ok(team) => Ok(serde_json::to_value(team).unwrap()),
-            Err(e) => {
-                if e.to_string().contains("team_key") {
-                    Err(AppError::validation(
-                        "Team with this key already exists in this workspace",
-                    ))
-                } else {
-                    Err(AppError::Internal("Failed to create team".to_string()))
-                }
-            }
-        }
+        super::teams::TeamHandlers::handle_create_team(&self.db, ctx, data).await
     }
 
     async fn handle_update_team(
@@ -870,105 +1036,21 @@ impl WebSocketCommandHandler {
         team_id: Uuid,
         data: UpdateTeamCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = self
-            .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        use crate::schema;
-        use diesel::prelude::*;
-        let existing_team = match schema::teams::table
-            .filter(schema::teams::id.eq(team_id))
-            .filter(schema::teams::workspace_id.eq(ctx.workspace_id))
-            .select(crate::db::models::team::Team::as_select())
-            .first::<crate::db::models::team::Team>(&mut conn)
-        {
-            Ok(team) => team,
-            Err(_) => return Err(AppError::not_found("Team not found")),
-        };
-        if data.name.is_none()
-            && data.team_key.is_none()
-            && data.description.is_none()
-            && data.icon_url.is_none()
-            && data.is_private.is_none()
-        {
-            return Ok(serde_json::to_value(existing_team).unwrap());
-        }
-        let team_name = data.name.as_ref().unwrap_or(&existing_team.name);
-        let team_key_val = data.team_key.as_ref().unwrap_or(&existing_team.team_key);
-        let description_val = data
-            .description
-            .as_ref()
-            .or(existing_team.description.as_ref());
-        let icon_url_val = data.icon_url.as_ref().or(existing_team.icon_url.as_ref());
-        let is_private_val = data.is_private.unwrap_or(existing_team.is_private);
-        let updated = diesel::update(schema::teams::table.filter(schema::teams::id.eq(team_id)))
-            .set((
-                schema::teams::name.eq(team_name),
-                schema::teams::team_key.eq(team_key_val),
-                schema::teams::description.eq(description_val),
-                schema::teams::icon_url.eq(icon_url_val),
-                schema::teams::is_private.eq(is_private_val),
-            ))
-            .get_result::<crate::db::models::team::Team>(&mut conn);
-        match updated {
-            Ok(team) => Ok(serde_json::to_value(team).unwrap()),
-            Err(e) => {
-                if e.to_string().contains("team_key") {
-                    Err(AppError::validation(
-                        "Team with this key already exists in this workspace",
-                    ))
-                } else {
-                    Err(AppError::Internal("Failed to update team".to_string()))
-                }
-            }
-        }
+        super::teams::TeamHandlers::handle_update_team(&self.db, ctx, team_id, data).await
     }
 
     async fn handle_delete_team(
         &self,
         ctx: RequestContext,
         team_id: Uuid,
+        confirmation_token: Option<String>,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = self
-            .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        use crate::schema;
-        use diesel::prelude::*;
-        match schema::teams::table
-            .filter(schema::teams::id.eq(team_id))
-            .filter(schema::teams::workspace_id.eq(ctx.workspace_id))
-            .select(crate::db::models::team::Team::as_select())
-            .first::<crate::db::models::team::Team>(&mut conn)
-        {
-            Ok(_) => (),
-            Err(_) => return Err(AppError::not_found("Team not found")),
-        }
-        match diesel::delete(schema::teams::table.filter(schema::teams::id.eq(team_id)))
-            .execute(&mut conn)
-        {
-            Ok(_) => Ok(serde_json::json!({"deleted": true, "team_id": team_id})),
-            Err(_) => Err(AppError::Internal("Failed to delete team".to_string())),
-        }
+        super::teams::TeamHandlers::handle_delete_team(&self.db, ctx, team_id, confirmation_token)
+            .await
     }
 
     async fn handle_query_teams(&self, ctx: RequestContext) -> Result<serde_json::Value, AppError> {
-        let mut conn = self
-            .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        use crate::schema;
-        use diesel::prelude::*;
-        let list = match schema::teams::table
-            .filter(schema::teams::workspace_id.eq(ctx.workspace_id))
-            .select(crate::db::models::team::Team::as_select())
-            .order(schema::teams::created_at.desc())
-            .load::<crate::db::models::team::Team>(&mut conn)
-        {
-            Ok(list) => list,
-            Err(_) => return Err(AppError::Internal("Failed to retrieve teams".to_string())),
-        };
-        Ok(serde_json::to_value(list).unwrap())
+        super::teams::TeamHandlers::handle_query_teams(&self.db, ctx).await
     }
 
     // Team members via service
@@ -978,21 +1060,21 @@ impl WebSocketCommandHandler {
         team_id: Uuid,
         data: AddTeamMemberCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = self
-            .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let role_str = match data.role {
             TeamMemberRole::Admin => "admin",
             TeamMemberRole::Member => "member",
         };
-        crate::services::team_members_service::TeamMembersService::add(
-            &mut conn,
-            &ctx,
-            team_id,
-            data.user_id,
-            role_str,
-        )?;
+        self.db
+            .transaction(move |conn| {
+                crate::services::team_members_service::TeamMembersService::add(
+                    conn,
+                    &ctx,
+                    team_id,
+                    data.user_id,
+                    role_str,
+                )
+            })
+            .await?;
         Ok(serde_json::json!({"added": true, "team_id": team_id, "user_id": data.user_id}))
     }
 
@@ -1003,21 +1085,21 @@ impl WebSocketCommandHandler {
         member_user_id: Uuid,
         data: UpdateTeamMemberCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = self
-            .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let role_str = match data.role {
             TeamMemberRole::Admin => "admin",
             TeamMemberRole::Member => "member",
         };
-        crate::services::team_members_service::TeamMembersService::update(
-            &mut conn,
-            &ctx,
-            team_id,
-            member_user_id,
-            role_str,
-        )?;
+        self.db
+            .transaction(move |conn| {
+                crate::services::team_members_service::TeamMembersService::update(
+                    conn,
+                    &ctx,
+                    team_id,
+                    member_user_id,
+                    role_str,
+                )
+            })
+            .await?;
         Ok(serde_json::json!({"updated": true, "team_id": team_id, "user_id": member_user_id}))
     }
 
@@ -1027,16 +1109,16 @@ impl WebSocketCommandHandler {
         team_id: Uuid,
         member_user_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = self
-            .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        crate::services::team_members_service::TeamMembersService::remove(
-            &mut conn,
-            &ctx,
-            team_id,
-            member_user_id,
-        )?;
+        self.db
+            .transaction(move |conn| {
+                crate::services::team_members_service::TeamMembersService::remove(
+                    conn,
+                    &ctx,
+                    team_id,
+                    member_user_id,
+                )
+            })
+            .await?;
         Ok(serde_json::json!({"removed": true, "team_id": team_id, "user_id": member_user_id}))
     }
 
@@ -1045,13 +1127,12 @@ impl WebSocketCommandHandler {
         ctx: RequestContext,
         team_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = self
+        let members = self
             .db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let members = crate::services::team_members_service::TeamMembersService::list(
-            &mut conn, &ctx, team_id,
-        )?;
+            .read(move |conn| {
+                crate::services::team_members_service::TeamMembersService::list(conn, &ctx, team_id)
+            })
+            .await?;
         // Map to DTO-like structure for ws response
         let result: Vec<serde_json::Value> = members
             .into_iter()
@@ -1190,9 +1271,15 @@ impl WebSocketCommandHandler {
         &self,
         ctx: RequestContext,
         workspace_id: Uuid,
+        confirmation_token: Option<String>,
     ) -> Result<serde_json::Value, AppError> {
-        super::workspaces::WorkspaceHandlers::handle_delete_workspace(&self.db, ctx, workspace_id)
-            .await
+        super::workspaces::WorkspaceHandlers::handle_delete_workspace(
+            &self.db,
+            ctx,
+            workspace_id,
+            confirmation_token,
+        )
+        .await
     }
 
     async fn handle_get_current_workspace(
@@ -1296,6 +1383,15 @@ impl WebSocketCommandHandler {
         super::issues::IssueHandlers::handle_delete_issue(&self.db, ctx, issue_id).await
     }
 
+    async fn handle_apply_issue_patch(
+        &self,
+        ctx: RequestContext,
+        issue_id: Uuid,
+        patch: PatchDocument,
+    ) -> Result<serde_json::Value, AppError> {
+        super::issues::IssueHandlers::handle_apply_issue_patch(&self.db, ctx, issue_id, patch).await
+    }
+
     async fn handle_query_issues(
         &self,
         ctx: RequestContext,
@@ -1312,6 +1408,62 @@ impl WebSocketCommandHandler {
         super::issues::IssueHandlers::handle_get_issue(&self.db, ctx, issue_id).await
     }
 
+    // Comment handlers (delegate)
+    async fn handle_query_comments(
+        &self,
+        ctx: RequestContext,
+        issue_id: Uuid,
+        query: CommentPageQuery,
+    ) -> Result<serde_json::Value, AppError> {
+        super::comments::CommentHandlers::handle_query_comments(&self.db, ctx, issue_id, query)
+            .await
+    }
+
+    // Board handlers (delegate)
+    async fn handle_start_issue_drag(
+        &self,
+        ctx: RequestContext,
+        issue_id: Uuid,
+    ) -> Result<serde_json::Value, AppError> {
+        super::board::BoardHandlers::handle_start_issue_drag(
+            &self.db,
+            &self.board_locks,
+            ctx,
+            issue_id,
+        )
+        .await
+    }
+
+    async fn handle_end_issue_drag(
+        &self,
+        ctx: RequestContext,
+        issue_id: Uuid,
+    ) -> Result<serde_json::Value, AppError> {
+        super::board::BoardHandlers::handle_end_issue_drag(
+            &self.db,
+            &self.board_locks,
+            ctx,
+            issue_id,
+        )
+        .await
+    }
+
+    async fn handle_reorder_issue(
+        &self,
+        ctx: RequestContext,
+        issue_id: Uuid,
+        data: crate::db::models::board::ReorderIssueRequest,
+    ) -> Result<serde_json::Value, AppError> {
+        super::board::BoardHandlers::handle_reorder_issue(
+            &self.db,
+            &self.board_locks,
+            ctx,
+            issue_id,
+            data,
+        )
+        .await
+    }
+
     pub async fn start_cleanup_task(&self) {
         let idempotency = self.idempotency.clone();
         tokio::spawn(async move {
@@ -1,7 +1,10 @@
 use uuid::Uuid;
 
 use crate::{
-    error::AppError, services::context::RequestContext, services::issues_service::IssuesService,
+    db::DbExecutor, db::models::issue::IssueResponse, error::AppError,
+    services::context::RequestContext, services::field_selection::FieldSelection,
+    services::issue_patch_service::IssuePatchService, services::issues_service::IssuesService,
+    utils::json_patch::PatchDocument,
 };
 
 use super::types::*;
@@ -10,68 +13,81 @@ pub struct IssueHandlers;
 
 impl IssueHandlers {
     pub async fn handle_create_issue(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         data: CreateIssueCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
-        let issue = IssuesService::create_from_ws_command(&mut conn, &ctx, &data)?;
+        let issue = db
+            .transaction(move |conn| IssuesService::create_from_ws_command(conn, &ctx, &data))
+            .await?;
         Ok(serde_json::to_value(issue).unwrap())
     }
 
     pub async fn handle_update_issue(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         issue_id: Uuid,
         data: UpdateIssueCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
+        let issue = db
+            .transaction(move |conn| {
+                IssuesService::update_from_ws_command(conn, &ctx, issue_id, &data)
+            })
+            .await?;
+        Ok(serde_json::to_value(issue).unwrap())
+    }
 
-        let issue = IssuesService::update_from_ws_command(&mut conn, &ctx, issue_id, &data)?;
+    pub async fn handle_apply_issue_patch(
+        db: &DbExecutor,
+        ctx: RequestContext,
+        issue_id: Uuid,
+        patch: PatchDocument,
+    ) -> Result<serde_json::Value, AppError> {
+        let issue = db
+            .transaction(move |conn| IssuePatchService::apply(conn, &ctx, issue_id, &patch))
+            .await?;
         Ok(serde_json::to_value(issue).unwrap())
     }
 
     pub async fn handle_delete_issue(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         issue_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
-        IssuesService::delete(&mut conn, &ctx, issue_id)?;
+        db.transaction(move |conn| IssuesService::delete(conn, &ctx, issue_id))
+            .await?;
         Ok(serde_json::json!({"deleted": true, "issue_id": issue_id}))
     }
 
     pub async fn handle_query_issues(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         filters: IssueFilters,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
-        let issues = IssuesService::list_from_ws_command(&mut conn, &ctx, &filters)?;
-        Ok(serde_json::to_value(issues).unwrap())
+        let fields = FieldSelection::from_names(
+            filters.fields.iter().flatten().map(String::as_str),
+            IssueResponse::FIELDS,
+        )?;
+        let issues = db
+            .read(move |conn| IssuesService::list_from_ws_command(conn, &ctx, &filters))
+            .await?;
+        fields.project(&issues)
     }
 
     pub async fn handle_get_issue(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         issue_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
-        let issue = IssuesService::get_by_id(&mut conn, &ctx, issue_id)?;
+        let issue = db
+            .read(move |conn| {
+                let issue = IssuesService::get_by_id(conn, &ctx, issue_id)?;
+                if let Err(e) = IssuesService::mark_viewed(conn, &ctx, issue_id) {
+                    tracing::warn!("Failed to record issue view: {}", e);
+                }
+                Ok(issue)
+            })
+            .await?;
         Ok(serde_json::to_value(issue).unwrap())
     }
 }
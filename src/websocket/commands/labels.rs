@@ -1,6 +1,7 @@
 use uuid::Uuid;
 
 use crate::{
+    db::DbExecutor,
     error::AppError,
     services::context::RequestContext,
     validation::label::{UpdateLabelChanges, validate_create_label, validate_update_label},
@@ -12,25 +13,26 @@ pub struct LabelHandlers;
 
 impl LabelHandlers {
     pub async fn handle_create_label(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         data: CreateLabelCommand,
     ) -> Result<serde_json::Value, AppError> {
         validate_create_label(&data.name, &data.color)?;
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let req = crate::routes::labels::CreateLabelRequest {
             name: data.name,
             color: data.color,
             level: data.level,
         };
-        let label = crate::services::labels_service::LabelsService::create(&mut conn, &ctx, &req)?;
+        let label = db
+            .transaction(move |conn| {
+                crate::services::labels_service::LabelsService::create(conn, &ctx, &req)
+            })
+            .await?;
         Ok(serde_json::to_value(&label).unwrap())
     }
 
     pub async fn handle_update_label(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         label_id: Uuid,
         data: UpdateLabelCommand,
@@ -41,46 +43,43 @@ impl LabelHandlers {
             level_present: data.level.is_some(),
         };
         validate_update_label(&changes)?;
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let req = crate::routes::labels::UpdateLabelRequest {
             name: data.name,
             color: data.color,
             level: data.level,
         };
-        let updated = crate::services::labels_service::LabelsService::update(
-            &mut conn, &ctx, label_id, &req,
-        )?;
+        let updated = db
+            .transaction(move |conn| {
+                crate::services::labels_service::LabelsService::update(conn, &ctx, label_id, &req)
+            })
+            .await?;
         Ok(serde_json::to_value(&updated).unwrap())
     }
 
     pub async fn handle_delete_label(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         label_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        crate::services::labels_service::LabelsService::delete(&mut conn, &ctx, label_id)?;
+        db.transaction(move |conn| {
+            crate::services::labels_service::LabelsService::delete(conn, &ctx, label_id)
+        })
+        .await?;
         Ok(serde_json::json!({"deleted": true, "label_id": label_id}))
     }
 
     pub async fn handle_query_labels(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         filters: LabelFilters,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let labels = crate::services::labels_service::LabelsService::list(
-            &mut conn,
+        let labels = crate::services::labels_service::LabelsService::list_cached(
+            db,
             &ctx,
             filters.name_pattern,
             filters.level,
-        )?;
+        )
+        .await?;
         Ok(serde_json::to_value(labels).unwrap())
     }
 }
@@ -1,3 +1,5 @@
+pub mod board;
+pub mod comments;
 pub mod handler;
 pub mod issues;
 pub mod labels;
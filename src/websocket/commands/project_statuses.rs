@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use crate::{error::AppError, services::context::RequestContext};
+use crate::{db::DbExecutor, error::AppError, services::context::RequestContext};
 
 use super::types::*;
 
@@ -8,40 +8,37 @@ pub struct ProjectStatusesHandlers;
 
 impl ProjectStatusesHandlers {
     pub async fn handle_get_list(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let list = crate::services::project_statuses_service::ProjectStatusesService::list(
-            &mut conn, &ctx,
-        )?;
+        let list = db
+            .read(move |conn| {
+                crate::services::project_statuses_service::ProjectStatusesService::list(conn, &ctx)
+            })
+            .await?;
         Ok(serde_json::to_value(list).unwrap())
     }
 
     pub async fn handle_get_by_id(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         status_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let item = crate::services::project_statuses_service::ProjectStatusesService::get_by_id(
-            &mut conn, &ctx, status_id,
-        )?;
+        let item = db
+            .read(move |conn| {
+                crate::services::project_statuses_service::ProjectStatusesService::get_by_id(
+                    conn, &ctx, status_id,
+                )
+            })
+            .await?;
         Ok(serde_json::to_value(item).unwrap())
     }
 
     pub async fn handle_create(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         data: CreateProjectStatusCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let category_enum = match data.category.as_str() {
             "backlog" => crate::db::models::project_status::ProjectStatusCategory::Backlog,
             "planned" => crate::db::models::project_status::ProjectStatusCategory::Planned,
@@ -56,46 +53,51 @@ impl ProjectStatusesHandlers {
             color: Some(data.color),
             category: category_enum,
         };
-        let created = crate::services::project_statuses_service::ProjectStatusesService::create(
-            &mut conn,
-            &ctx,
-            &model_request,
-        )?;
+        let created = db
+            .transaction(move |conn| {
+                crate::services::project_statuses_service::ProjectStatusesService::create(
+                    conn,
+                    &ctx,
+                    &model_request,
+                )
+            })
+            .await?;
         Ok(serde_json::to_value(created).unwrap())
     }
 
     pub async fn handle_update(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         status_id: Uuid,
         data: UpdateProjectStatusCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let req = crate::routes::project_statuses::UpdateProjectStatusRequest {
             name: data.name,
             description: data.description,
             color: data.color,
             category: data.category,
         };
-        let updated = crate::services::project_statuses_service::ProjectStatusesService::update(
-            &mut conn, &ctx, status_id, &req,
-        )?;
+        let updated = db
+            .transaction(move |conn| {
+                crate::services::project_statuses_service::ProjectStatusesService::update(
+                    conn, &ctx, status_id, &req,
+                )
+            })
+            .await?;
         Ok(serde_json::to_value(updated).unwrap())
     }
 
     pub async fn handle_delete(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         status_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        crate::services::project_statuses_service::ProjectStatusesService::delete(
-            &mut conn, &ctx, status_id,
-        )?;
+        db.transaction(move |conn| {
+            crate::services::project_statuses_service::ProjectStatusesService::delete(
+                conn, &ctx, status_id,
+            )
+        })
+        .await?;
         Ok(serde_json::json!({"deleted": true, "status_id": status_id}))
     }
 }
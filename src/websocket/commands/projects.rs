@@ -1,6 +1,10 @@
 use uuid::Uuid;
 
-use crate::{error::AppError, services::context::RequestContext, utils::AssetUrlHelper};
+use crate::{
+    db::DbExecutor, db::models::project::ProjectInfo, error::AppError,
+    services::context::RequestContext, services::field_selection::FieldSelection,
+    utils::AssetUrlHelper,
+};
 
 use super::types::*;
 
@@ -8,7 +12,7 @@ pub struct ProjectHandlers;
 
 impl ProjectHandlers {
     pub async fn handle_create_project(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         data: CreateProjectCommand,
         _asset_helper: &AssetUrlHelper,
@@ -29,10 +33,6 @@ impl ProjectHandlers {
             ));
         }
 
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
         let create_req = crate::db::models::project::CreateProjectRequest {
             name: data.name,
             project_key: data.project_key,
@@ -43,25 +43,21 @@ impl ProjectHandlers {
             roadmap_id: None,
         };
 
-        let project = crate::services::projects_service::ProjectsService::create(
-            &mut conn,
-            &ctx,
-            &create_req,
-        )?;
+        let project = db
+            .transaction(move |conn| {
+                crate::services::projects_service::ProjectsService::create(conn, &ctx, &create_req)
+            })
+            .await?;
         Ok(serde_json::to_value(project).unwrap())
     }
 
     pub async fn handle_update_project(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         project_id: Uuid,
         data: UpdateProjectCommand,
         asset_helper: &AssetUrlHelper,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
         let update_req = crate::db::models::project::UpdateProjectRequest {
             name: data.name,
             description: data.description,
@@ -71,48 +67,57 @@ impl ProjectHandlers {
             priority: data.priority.map(|p| p.parse().unwrap_or_default()),
         };
 
-        let project_info = crate::services::projects_service::ProjectsService::update(
-            &mut conn,
-            &ctx,
-            asset_helper,
-            project_id,
-            &update_req,
-        )?;
+        let asset_helper = asset_helper.clone();
+        let project_info = db
+            .transaction(move |conn| {
+                crate::services::projects_service::ProjectsService::update(
+                    conn,
+                    &ctx,
+                    &asset_helper,
+                    project_id,
+                    &update_req,
+                )
+            })
+            .await?;
 
         Ok(serde_json::to_value(project_info).unwrap())
     }
 
     pub async fn handle_delete_project(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         project_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
-        crate::services::projects_service::ProjectsService::delete(&mut conn, &ctx, project_id)?;
+        db.transaction(move |conn| {
+            crate::services::projects_service::ProjectsService::delete(conn, &ctx, project_id)
+        })
+        .await?;
         Ok(serde_json::json!({"deleted": true, "project_id": project_id}))
     }
 
     pub async fn handle_query_projects(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         filters: ProjectFilters,
         asset_helper: &AssetUrlHelper,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
-        let projects = crate::services::projects_service::ProjectsService::list_infos(
-            &mut conn,
-            &ctx,
-            asset_helper,
-            filters.search,
-            filters.owner_id,
+        let fields = FieldSelection::from_names(
+            filters.fields.iter().flatten().map(String::as_str),
+            ProjectInfo::FIELDS,
         )?;
-
-        Ok(serde_json::to_value(projects).unwrap())
+        let asset_helper = asset_helper.clone();
+        let projects = db
+            .read(move |conn| {
+                crate::services::projects_service::ProjectsService::list_infos(
+                    conn,
+                    &ctx,
+                    &asset_helper,
+                    filters.search,
+                    filters.owner_id,
+                )
+            })
+            .await?;
+
+        fields.project(&projects)
     }
 }
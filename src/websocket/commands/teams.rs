@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use crate::{error::AppError, services::context::RequestContext};
+use crate::{db::DbExecutor, error::AppError, services::context::RequestContext};
 
 use super::types::*;
 
@@ -8,7 +8,7 @@ pub struct TeamHandlers;
 
 impl TeamHandlers {
     pub async fn handle_create_team(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         data: CreateTeamCommand,
     ) -> Result<serde_json::Value, AppError> {
@@ -27,61 +27,76 @@ impl TeamHandlers {
                 "Team key can only contain letters, numbers, hyphens, and underscores",
             ));
         }
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let req = crate::routes::teams::CreateTeamRequest {
             name: data.name,
             team_key: data.team_key,
             description: data.description,
             icon_url: data.icon_url,
             is_private: data.is_private,
+            parent_team_id: data.parent_team_id,
         };
-        let team = crate::services::teams_service::TeamsService::create(&mut conn, &ctx, &req)?;
+        let team = db
+            .transaction(move |conn| {
+                crate::services::teams_service::TeamsService::create(conn, &ctx, &req)
+            })
+            .await?;
         Ok(serde_json::to_value(team).unwrap())
     }
 
     pub async fn handle_update_team(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         team_id: Uuid,
         data: UpdateTeamCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let req = crate::routes::teams::UpdateTeamRequest {
             name: data.name,
             team_key: data.team_key,
             description: data.description,
             icon_url: data.icon_url,
             is_private: data.is_private,
+            estimate_scale: data.estimate_scale,
+            assignment_mode: data.assignment_mode,
+            default_assignee_id: data.default_assignee_id,
         };
-        let team =
-            crate::services::teams_service::TeamsService::update(&mut conn, &ctx, team_id, &req)?;
+        let team = db
+            .transaction(move |conn| {
+                crate::services::teams_service::TeamsService::update(conn, &ctx, team_id, &req)
+            })
+            .await?;
         Ok(serde_json::to_value(team).unwrap())
     }
 
     pub async fn handle_delete_team(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         team_id: Uuid,
+        confirmation_token: Option<String>,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        crate::services::teams_service::TeamsService::delete(&mut conn, &ctx, team_id)?;
+        let Some(token) = confirmation_token else {
+            let confirmation = db
+                .transaction(move |conn| {
+                    crate::services::teams_service::TeamsService::request_delete(
+                        conn, &ctx, team_id,
+                    )
+                })
+                .await?;
+            return Ok(serde_json::to_value(confirmation).unwrap());
+        };
+        db.transaction(move |conn| {
+            crate::services::teams_service::TeamsService::delete(conn, &ctx, team_id, &token)
+        })
+        .await?;
         Ok(serde_json::json!({"deleted": true, "team_id": team_id}))
     }
 
     pub async fn handle_query_teams(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let list = crate::services::teams_service::TeamsService::list(&mut conn, &ctx)?;
+        let list = db
+            .read(move |conn| crate::services::teams_service::TeamsService::list(conn, &ctx))
+            .await?;
         Ok(serde_json::to_value(list).unwrap())
     }
 }
@@ -195,6 +195,7 @@ mod command_tests {
                 role: Some(WorkspaceMemberRole::Admin),
                 user_id: None,
                 search: Some("john".to_string()),
+                fields: None,
             },
             request_id: Some("req-query-ws".to_string()),
         };
@@ -223,6 +224,7 @@ mod command_tests {
                 role: None,
                 user_id: None,
                 search: None,
+                fields: None,
             },
             request_id: Some("req-query-ws-no-search".to_string()),
         };
@@ -312,6 +314,7 @@ mod command_tests {
         let workspace_id = uuid::Uuid::new_v4();
         let command = WebSocketCommand::DeleteWorkspace {
             workspace_id,
+            confirmation_token: None,
             request_id: Some("req-delete-ws".to_string()),
         };
 
@@ -321,8 +324,10 @@ mod command_tests {
         match deserialized {
             WebSocketCommand::DeleteWorkspace {
                 workspace_id: ws_id,
+                confirmation_token,
                 request_id,
             } => {
+                assert_eq!(confirmation_token, None);
                 assert_eq!(request_id, Some("req-delete-ws".to_string()));
                 assert_eq!(ws_id, workspace_id);
             }
@@ -346,4 +351,28 @@ mod command_tests {
             _ => panic!("Expected GetCurrentWorkspace command"),
         }
     }
+
+    #[test]
+    fn test_command_required_permission() {
+        use crate::services::permission_service::Permission;
+
+        let delete = WebSocketCommand::DeleteWorkspace {
+            workspace_id: uuid::Uuid::new_v4(),
+            confirmation_token: None,
+            request_id: None,
+        };
+        assert_eq!(
+            delete.required_permission(),
+            Some(Permission::DeleteWorkspace)
+        );
+
+        let batch = WebSocketCommand::BatchDeleteLabels {
+            label_ids: vec![],
+            request_id: None,
+        };
+        assert_eq!(batch.required_permission(), Some(Permission::ManageLabels));
+
+        let ping = WebSocketCommand::Ping { request_id: None };
+        assert_eq!(ping.required_permission(), None);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::utils::clock;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -6,6 +7,10 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::db::enums::LabelLevel;
+use crate::db::models::board::ReorderIssueRequest;
+use crate::db::models::comment::CommentPageQuery;
+use crate::services::permission_service::Permission;
+use crate::utils::json_patch::PatchDocument;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -78,6 +83,9 @@ pub enum WebSocketCommand {
     },
     DeleteTeam {
         team_id: Uuid,
+        /// Omitted on the first call, which returns the token to send back
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        confirmation_token: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
     },
@@ -140,6 +148,9 @@ pub enum WebSocketCommand {
     },
     DeleteWorkspace {
         workspace_id: Uuid,
+        /// Omitted on the first call, which returns the token to send back
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        confirmation_token: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
     },
@@ -218,6 +229,13 @@ pub enum WebSocketCommand {
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
     },
+    /// 以 JSON Patch 操作数组或 Merge Patch 对象部分更新问题，与 `PATCH /issues/:id` 一致
+    ApplyIssuePatch {
+        issue_id: Uuid,
+        patch: PatchDocument,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
     QueryIssues {
         filters: IssueFilters,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -228,6 +246,114 @@ pub enum WebSocketCommand {
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
     },
+    // Comments
+    /// 分页查询问题的评论，与 `GET /issues/:id/comments/page` 一致
+    QueryComments {
+        issue_id: Uuid,
+        #[serde(default)]
+        query: CommentPageQuery,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
+    // Board
+    StartIssueDrag {
+        issue_id: Uuid,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
+    EndIssueDrag {
+        issue_id: Uuid,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
+    ReorderIssue {
+        issue_id: Uuid,
+        data: ReorderIssueRequest,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
+}
+
+impl WebSocketCommand {
+    /// 执行命令所需的工作空间权限，与对应 HTTP 路由保持一致；`None` 表示无需额外权限
+    pub fn required_permission(&self) -> Option<Permission> {
+        match self {
+            WebSocketCommand::CreateLabel { .. }
+            | WebSocketCommand::UpdateLabel { .. }
+            | WebSocketCommand::DeleteLabel { .. }
+            | WebSocketCommand::BatchCreateLabels { .. }
+            | WebSocketCommand::BatchUpdateLabels { .. }
+            | WebSocketCommand::BatchDeleteLabels { .. } => Some(Permission::ManageLabels),
+            WebSocketCommand::CreateTeam { .. }
+            | WebSocketCommand::UpdateTeam { .. }
+            | WebSocketCommand::DeleteTeam { .. } => Some(Permission::ManageTeams),
+            WebSocketCommand::AddTeamMember { .. }
+            | WebSocketCommand::UpdateTeamMember { .. }
+            | WebSocketCommand::RemoveTeamMember { .. } => Some(Permission::ManageTeamMembers),
+            WebSocketCommand::InviteWorkspaceMember { .. } => Some(Permission::InviteMembers),
+            WebSocketCommand::CreateProjectStatus { .. }
+            | WebSocketCommand::UpdateProjectStatus { .. }
+            | WebSocketCommand::DeleteProjectStatus { .. } => {
+                Some(Permission::ManageProjectStatuses)
+            }
+            WebSocketCommand::UpdateWorkspace { .. } => Some(Permission::UpdateWorkspace),
+            WebSocketCommand::DeleteWorkspace { .. } => Some(Permission::DeleteWorkspace),
+            WebSocketCommand::CreateProject { .. } => Some(Permission::CreateProject),
+            WebSocketCommand::UpdateProject { .. } => Some(Permission::UpdateProject),
+            WebSocketCommand::DeleteProject { .. } => Some(Permission::DeleteProject),
+            WebSocketCommand::CreateIssue { .. } => Some(Permission::CreateIssue),
+            WebSocketCommand::UpdateIssue { .. } | WebSocketCommand::ApplyIssuePatch { .. } => {
+                Some(Permission::UpdateIssue)
+            }
+            WebSocketCommand::DeleteIssue { .. } => Some(Permission::DeleteIssue),
+            WebSocketCommand::StartIssueDrag { .. }
+            | WebSocketCommand::EndIssueDrag { .. }
+            | WebSocketCommand::ReorderIssue { .. } => Some(Permission::UpdateIssue),
+            WebSocketCommand::QueryLabels { .. }
+            | WebSocketCommand::Subscribe { .. }
+            | WebSocketCommand::Unsubscribe { .. }
+            | WebSocketCommand::GetConnectionInfo { .. }
+            | WebSocketCommand::Ping { .. }
+            | WebSocketCommand::QueryTeams { .. }
+            | WebSocketCommand::ListTeamMembers { .. }
+            | WebSocketCommand::AcceptInvitation { .. }
+            | WebSocketCommand::QueryWorkspaceMembers { .. }
+            | WebSocketCommand::QueryProjectStatuses { .. }
+            | WebSocketCommand::GetProjectStatusById { .. }
+            | WebSocketCommand::CreateWorkspace { .. }
+            | WebSocketCommand::GetCurrentWorkspace { .. }
+            | WebSocketCommand::UpdateProfile { .. }
+            | WebSocketCommand::QueryProjects { .. }
+            | WebSocketCommand::QueryIssues { .. }
+            | WebSocketCommand::GetIssue { .. }
+            | WebSocketCommand::QueryComments { .. } => None,
+        }
+    }
+
+    /// 是否修改数据；只有这类命令因系统错误失败时才写入死信，修复后可由管理员重放。
+    /// 查询、订阅和拖拽锁只对当时的连接有意义，不重放
+    pub fn is_replayable(&self) -> bool {
+        !matches!(
+            self,
+            WebSocketCommand::QueryLabels { .. }
+                | WebSocketCommand::Subscribe { .. }
+                | WebSocketCommand::Unsubscribe { .. }
+                | WebSocketCommand::GetConnectionInfo { .. }
+                | WebSocketCommand::Ping { .. }
+                | WebSocketCommand::QueryTeams { .. }
+                | WebSocketCommand::ListTeamMembers { .. }
+                | WebSocketCommand::QueryWorkspaceMembers { .. }
+                | WebSocketCommand::QueryProjectStatuses { .. }
+                | WebSocketCommand::GetProjectStatusById { .. }
+                | WebSocketCommand::GetCurrentWorkspace { .. }
+                | WebSocketCommand::QueryProjects { .. }
+                | WebSocketCommand::QueryIssues { .. }
+                | WebSocketCommand::GetIssue { .. }
+                | WebSocketCommand::QueryComments { .. }
+                | WebSocketCommand::StartIssueDrag { .. }
+                | WebSocketCommand::EndIssueDrag { .. }
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -264,6 +390,7 @@ pub struct LabelUpdate {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionInfo {
+    pub connection_id: String,
     pub user_id: Uuid,
     pub username: String,
     pub connected_at: DateTime<Utc>,
@@ -271,6 +398,7 @@ pub struct ConnectionInfo {
     pub subscriptions: Vec<String>,
     pub message_queue_size: usize,
     pub state: String,
+    pub current_workspace_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -347,7 +475,7 @@ impl WebSocketCommandResponse {
             data: Some(data),
             error: None,
             meta: None,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
 
@@ -366,7 +494,7 @@ impl WebSocketCommandResponse {
             data: Some(data),
             error: None,
             meta: Some(meta),
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
 
@@ -384,7 +512,7 @@ impl WebSocketCommandResponse {
             data: None,
             error: Some(error),
             meta: None,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
 
@@ -402,7 +530,7 @@ impl WebSocketCommandResponse {
             data: Some(serde_json::json!({"message": message})),
             error: None,
             meta: None,
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
         }
     }
 }
@@ -488,7 +616,7 @@ impl IdempotencyControl {
     }
 
     pub async fn cleanup_expired(&self) {
-        let cutoff_time = Utc::now() - chrono::Duration::seconds(self.expiration_seconds as i64);
+        let cutoff_time = clock::now() - chrono::Duration::seconds(self.expiration_seconds as i64);
         let mut commands = self.processed_commands.write().await;
         commands.retain(|_, response| response.timestamp > cutoff_time);
     }
@@ -502,6 +630,8 @@ pub struct CreateTeamCommand {
     pub description: Option<String>,
     pub icon_url: Option<String>,
     pub is_private: bool,
+    #[serde(default)]
+    pub parent_team_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -511,6 +641,18 @@ pub struct UpdateTeamCommand {
     pub description: Option<String>,
     pub icon_url: Option<String>,
     pub is_private: Option<bool>,
+    #[serde(default)]
+    pub estimate_scale: Option<String>,
+    /// none、default_assignee 或 round_robin
+    #[serde(default)]
+    pub assignment_mode: Option<String>,
+    /// 传 null 清除默认负责人
+    #[serde(
+        default,
+        deserialize_with = "crate::utils::nullable::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_assignee_id: Option<Option<Uuid>>,
 }
 
 // Team member command payloads
@@ -552,6 +694,9 @@ pub struct WorkspaceMemberFilters {
     pub role: Option<WorkspaceMemberRole>,
     pub user_id: Option<Uuid>,
     pub search: Option<String>,
+    /// 只返回这些顶层字段（`id` 总会返回），与 REST 的 `?fields=` 一致
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
 }
 
 // Workspace command payloads
@@ -595,6 +740,8 @@ pub struct UpdateProfileCommand {
     pub username: Option<String>,
     pub email: Option<String>,
     pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub asset_region: Option<String>,
 }
 
 // Project command payloads
@@ -621,6 +768,9 @@ pub struct UpdateProjectCommand {
 pub struct ProjectFilters {
     pub search: Option<String>,
     pub owner_id: Option<Uuid>,
+    /// 只返回这些顶层字段（`id` 总会返回），与 REST 的 `?fields=` 一致
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
 }
 
 // Issue command payloads
@@ -637,6 +787,8 @@ pub struct CreateIssueCommand {
     pub label_ids: Option<Vec<Uuid>>,
     pub cycle_id: Option<Uuid>,
     pub parent_issue_id: Option<Uuid>,
+    #[serde(default)]
+    pub estimate: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -651,6 +803,13 @@ pub struct UpdateIssueCommand {
     pub workflow_state_id: Option<Uuid>,
     pub cycle_id: Option<Uuid>,
     pub label_ids: Option<Vec<Uuid>>,
+    /// `null` 清除估算
+    #[serde(
+        default,
+        deserialize_with = "crate::utils::nullable::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimate: Option<Option<i32>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -663,8 +822,18 @@ pub struct IssueFilters {
     pub assignee_id: Option<Uuid>,
     #[serde(default, deserialize_with = "deserialize_optional_string")]
     pub priority: Option<String>,
+    /// 非空时按全文搜索匹配标题和描述，结果按相关度排序
     #[serde(default, deserialize_with = "deserialize_optional_string")]
     pub search: Option<String>,
+    /// 全文搜索返回的最大条数，仅在 `search` 非空时生效
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// 为 true 时同时返回已归档的 Issue，与 REST 的 `?include_archived=` 一致；全文搜索不受影响
+    #[serde(default)]
+    pub include_archived: bool,
+    /// 只返回这些顶层字段（`id` 总会返回），与 REST 的 `?fields=` 一致
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
 }
 
 // 自定义反序列化函数：将空字符串转换为 None
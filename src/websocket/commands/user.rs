@@ -1,4 +1,6 @@
-use crate::{error::AppError, services::context::RequestContext, utils::AssetUrlHelper};
+use crate::{
+    db::DbExecutor, error::AppError, services::context::RequestContext, utils::AssetUrlHelper,
+};
 
 use super::types::*;
 
@@ -7,30 +9,32 @@ pub struct UserHandlers;
 impl UserHandlers {
     /// Handle profile update via websocket command
     pub async fn handle_update_profile(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         data: UpdateProfileCommand,
         asset_helper: &AssetUrlHelper,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
         // Convert websocket command to auth service request format
         let update_request = crate::routes::auth::UpdateProfileRequest {
             name: data.name,
             username: data.username,
             email: data.email,
             avatar_url: data.avatar_url,
+            asset_region: data.asset_region,
         };
 
         // Use the existing AuthService::update_profile method
-        let profile = crate::services::auth_service::AuthService::update_profile(
-            &mut conn,
-            &ctx,
-            &update_request,
-            asset_helper,
-        )?;
+        let asset_helper = asset_helper.clone();
+        let profile = db
+            .transaction(move |conn| {
+                crate::services::auth_service::AuthService::update_profile(
+                    conn,
+                    &ctx,
+                    &update_request,
+                    &asset_helper,
+                )
+            })
+            .await?;
 
         Ok(serde_json::to_value(profile).unwrap())
     }
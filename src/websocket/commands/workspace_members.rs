@@ -1,6 +1,9 @@
 use uuid::Uuid;
 
-use crate::{error::AppError, services::context::RequestContext};
+use crate::{
+    db::DbExecutor, error::AppError, routes::workspace_members::WorkspaceMemberInfo,
+    services::context::RequestContext, services::field_selection::FieldSelection,
+};
 
 use super::types::*;
 
@@ -8,14 +11,10 @@ pub struct WorkspaceMemberHandlers;
 
 impl WorkspaceMemberHandlers {
     pub async fn handle_invite_member(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         data: InviteWorkspaceMemberCommand,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
         let req = crate::routes::workspace_members::InviteMemberRequest {
             email: data.email,
             role: match data.role {
@@ -31,43 +30,39 @@ impl WorkspaceMemberHandlers {
             },
         };
 
-        let invitation =
-            crate::services::workspace_members_service::WorkspaceMembersService::invite_member(
-                &mut conn, &ctx, &req,
-            )?;
+        let invitation = db
+            .transaction(move |conn| {
+                crate::services::workspace_members_service::WorkspaceMembersService::invite_member(
+                    conn, &ctx, &req,
+                )
+            })
+            .await?;
 
         Ok(serde_json::to_value(invitation).unwrap())
     }
 
     pub async fn handle_accept_invitation(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         invitation_id: Uuid,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
-        let invitation =
+        let invitation = db.transaction(move |conn| {
             crate::services::workspace_members_service::WorkspaceMembersService::accept_invitation(
-                &mut conn,
+                conn,
                 &ctx,
                 invitation_id,
-            )?;
+            )
+        }).await?;
 
         Ok(serde_json::to_value(invitation).unwrap())
     }
 
     pub async fn handle_list_workspace_members(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         asset_helper: &crate::utils::AssetUrlHelper,
         ctx: RequestContext,
         filters: WorkspaceMemberFilters,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-
         let role_enum = filters.role.map(|r| match r {
             WorkspaceMemberRole::Owner => {
                 crate::db::models::workspace_member::WorkspaceMemberRole::Owner
@@ -80,15 +75,20 @@ impl WorkspaceMemberHandlers {
             }
         });
 
-        let members = crate::services::workspace_members_service::WorkspaceMembersService::get_current_workspace_members_with_search(
-            &mut conn,
-            &ctx,
+        let fields = FieldSelection::from_names(
+            filters.fields.iter().flatten().map(String::as_str),
+            WorkspaceMemberInfo::FIELDS,
+        )?;
+        let members = crate::services::workspace_members_service::WorkspaceMembersService::get_workspace_members_cached(
+            db,
             asset_helper,
+            ctx.workspace_id,
             role_enum,
             filters.user_id,
             filters.search,
-        )?;
+        )
+        .await?;
 
-        Ok(serde_json::to_value(members).unwrap())
+        fields.project(&members)
     }
 }
@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use crate::{error::AppError, services::context::RequestContext};
+use crate::{db::DbExecutor, error::AppError, services::context::RequestContext};
 
 use super::types::*;
 
@@ -8,7 +8,7 @@ pub struct WorkspaceHandlers;
 
 impl WorkspaceHandlers {
     pub async fn handle_create_workspace(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         _ctx: RequestContext,
         data: CreateWorkspaceCommand,
         asset_helper: &crate::utils::AssetUrlHelper,
@@ -28,15 +28,16 @@ impl WorkspaceHandlers {
                 "Workspace URL key can only contain letters, numbers, hyphens, and underscores",
             ));
         }
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let workspace = crate::services::workspaces_service::WorkspacesService::create(
-            &mut conn,
-            &data.name,
-            &data.url_key,
-            data.logo_url,
-        )?;
+        let workspace = db
+            .transaction(move |conn| {
+                crate::services::workspaces_service::WorkspacesService::create(
+                    conn,
+                    &data.name,
+                    &data.url_key,
+                    data.logo_url,
+                )
+            })
+            .await?;
 
         // Process logo_url with asset_helper
         let processed_logo_url = workspace
@@ -54,26 +55,27 @@ impl WorkspaceHandlers {
     }
 
     pub async fn handle_update_workspace(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         workspace_id: Uuid,
         data: UpdateWorkspaceCommand,
         asset_helper: &crate::utils::AssetUrlHelper,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
         let req = crate::routes::workspaces::UpdateWorkspaceRequest {
             name: data.name,
             url_key: data.url_key,
             logo_url: data.logo_url,
         };
-        let workspace = crate::services::workspaces_service::WorkspacesService::update(
-            &mut conn,
-            &ctx,
-            workspace_id,
-            &req,
-        )?;
+        let workspace = db
+            .transaction(move |conn| {
+                crate::services::workspaces_service::WorkspacesService::update(
+                    conn,
+                    &ctx,
+                    workspace_id,
+                    &req,
+                )
+            })
+            .await?;
 
         // Process logo_url with asset_helper
         let processed_logo_url = workspace
@@ -91,34 +93,50 @@ impl WorkspaceHandlers {
     }
 
     pub async fn handle_delete_workspace(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         workspace_id: Uuid,
+        confirmation_token: Option<String>,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        crate::services::workspaces_service::WorkspacesService::delete(
-            &mut conn,
-            &ctx,
-            workspace_id,
-        )?;
+        let Some(token) = confirmation_token else {
+            let confirmation = db
+                .transaction(move |conn| {
+                    crate::services::workspaces_service::WorkspacesService::request_delete(
+                        conn,
+                        &ctx,
+                        workspace_id,
+                    )
+                })
+                .await?;
+            return Ok(serde_json::to_value(confirmation).unwrap());
+        };
+        db.transaction(move |conn| {
+            crate::services::workspaces_service::WorkspacesService::delete(
+                conn,
+                &ctx,
+                workspace_id,
+                &token,
+            )
+        })
+        .await?;
         Ok(serde_json::json!({"deleted": true, "workspace_id": workspace_id}))
     }
 
     pub async fn handle_get_current_workspace(
-        db: &crate::db::DbPool,
+        db: &DbExecutor,
         ctx: RequestContext,
         asset_helper: &crate::utils::AssetUrlHelper,
     ) -> Result<serde_json::Value, AppError> {
-        let mut conn = db
-            .get()
-            .map_err(|_| AppError::Internal("Database connection failed".to_string()))?;
-        let workspace = crate::services::workspaces_service::WorkspacesService::get_current(
-            &mut conn,
-            &ctx,
-            asset_helper,
-        )?;
+        let asset_helper = asset_helper.clone();
+        let workspace = db
+            .read(move |conn| {
+                crate::services::workspaces_service::WorkspacesService::get_current(
+                    conn,
+                    &ctx,
+                    &asset_helper,
+                )
+            })
+            .await?;
         Ok(serde_json::to_value(workspace).unwrap())
     }
 }
@@ -0,0 +1,112 @@
+use axum::extract::ws::Message;
+use flate2::{Compression as Level, read::ZlibDecoder, write::ZlibEncoder};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+use crate::websocket::WebSocketMonitor;
+
+/// 握手时通过 `compression` 参数协商的出站消息压缩算法
+///
+/// 只压缩服务端下发的消息：超过阈值的负载以二进制帧发送，内容是压缩后的 JSON；
+/// 未超过阈值的消息仍是普通文本帧，客户端按帧类型区分即可。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// zlib 封装的 DEFLATE（RFC 1950），与浏览器 `DecompressionStream("deflate")` 一致
+    Deflate,
+}
+
+impl Compression {
+    /// 从客户端提供的候选列表（逗号分隔，按偏好排序）中选出第一个支持的算法
+    pub fn negotiate(offer: Option<&str>) -> Option<Self> {
+        offer?
+            .split(',')
+            .find_map(|name| match name.trim().to_ascii_lowercase().as_str() {
+                "deflate" => Some(Self::Deflate),
+                _ => None,
+            })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deflate => "deflate",
+        }
+    }
+
+    pub fn compress(&self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Level::fast());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    pub fn decompress(&self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Deflate => {
+                let mut decoded = Vec::new();
+                ZlibDecoder::new(bytes).read_to_end(&mut decoded)?;
+                Ok(decoded)
+            }
+        }
+    }
+}
+
+/// 按连接协商结果把序列化好的消息转换为 WebSocket 帧
+#[derive(Debug, Clone, Copy)]
+pub struct FrameCompressor {
+    codec: Option<Compression>,
+    threshold_bytes: usize,
+}
+
+impl FrameCompressor {
+    pub fn new(codec: Option<Compression>, threshold_bytes: usize) -> Self {
+        Self {
+            codec,
+            threshold_bytes,
+        }
+    }
+
+    pub fn codec(&self) -> Option<Compression> {
+        self.codec
+    }
+
+    pub fn threshold_bytes(&self) -> usize {
+        self.threshold_bytes
+    }
+
+    /// 未协商压缩或负载小于阈值时返回文本帧；压缩失败或压缩后没有变小时同样退回文本帧
+    pub fn to_message(&self, text: String, monitor: Option<&WebSocketMonitor>) -> Message {
+        let Some(codec) = self.codec else {
+            return Message::Text(text);
+        };
+        if text.len() < self.threshold_bytes {
+            return Message::Text(text);
+        }
+
+        let started = std::time::Instant::now();
+        match codec.compress(text.as_bytes()) {
+            Ok(compressed) if compressed.len() < text.len() => {
+                if let Some(monitor) = monitor {
+                    monitor.record_compression(text.len(), compressed.len(), started.elapsed());
+                }
+                Message::Binary(compressed)
+            }
+            Ok(_) => {
+                if let Some(monitor) = monitor {
+                    monitor.record_compression_skipped();
+                }
+                Message::Text(text)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to compress WebSocket frame: {}", e);
+                if let Some(monitor) = monitor {
+                    monitor.record_compression_skipped();
+                }
+                Message::Text(text)
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::utils::clock;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::manager::{MessageType, WebSocketMessage};
+
+/// 发生变更的实体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Issue,
+    Comment,
+    Label,
+    Project,
+    ProjectStatus,
+    Milestone,
+    Cycle,
+    Team,
+    Workflow,
+    WorkflowState,
+}
+
+/// 实体变更的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// `entity_changed` 消息的数据，只推送给实体所属工作区的连接。
+///
+/// 客户端按 `entity` + `id` 更新本地缓存：`created` / `updated` 时用
+/// `payload` 覆盖，`deleted` 时移除（此时 `payload` 为 `null`），无需整表重新拉取。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityChanged {
+    pub entity: EntityKind,
+    pub action: EntityAction,
+    pub id: Uuid,
+    pub payload: serde_json::Value,
+}
+
+impl EntityChanged {
+    pub fn new<T: Serialize>(
+        entity: EntityKind,
+        action: EntityAction,
+        id: Uuid,
+        payload: &T,
+    ) -> Result<Self, serde_json::Error> {
+        let payload = match action {
+            EntityAction::Deleted => serde_json::Value::Null,
+            _ => serde_json::to_value(payload)?,
+        };
+        Ok(Self {
+            entity,
+            action,
+            id,
+            payload,
+        })
+    }
+
+    /// 包装成推送给客户端的消息
+    pub fn to_message(&self) -> WebSocketMessage {
+        WebSocketMessage {
+            id: Some(clock::new_id().to_string()),
+            message_type: MessageType::EntityChanged,
+            data: serde_json::to_value(self).unwrap_or_default(),
+            timestamp: Some(clock::now()),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::AppError;
+use crate::utils::clock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -97,7 +98,7 @@ impl WebSocketError {
             retry_after: None,
             severity,
             category,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             request_id: None,
         }
     }
@@ -343,7 +344,7 @@ impl Default for WebSocketErrorMapper {
                 errors_by_severity: HashMap::new(),
                 errors_by_category: HashMap::new(),
                 recent_errors: Vec::new(),
-                last_updated: chrono::Utc::now(),
+                last_updated: clock::now(),
             })),
             max_recent_errors: 1000,
         }
@@ -377,7 +378,7 @@ impl WebSocketErrorMapper {
             stats.recent_errors.remove(0);
         }
 
-        stats.last_updated = chrono::Utc::now();
+        stats.last_updated = clock::now();
 
         // 记录日志
         match error.severity {
@@ -418,48 +419,29 @@ impl WebSocketErrorMapper {
     /// 清理过期的错误记录
     pub fn cleanup_old_errors(&self, max_age: Duration) {
         let mut stats = self.error_stats.write().unwrap();
-        let cutoff_time = chrono::Utc::now() - chrono::Duration::from_std(max_age).unwrap();
+        let cutoff_time = clock::now() - chrono::Duration::from_std(max_age).unwrap();
 
         stats
             .recent_errors
             .retain(|error| error.timestamp > cutoff_time);
-        stats.last_updated = chrono::Utc::now();
+        stats.last_updated = clock::now();
     }
 
     /// 从AppError映射到WebSocketError
     pub fn map_app_error(&self, error: &AppError) -> WebSocketError {
-        let (code, message) = match error {
-            AppError::Validation { message } => {
-                (WebSocketErrorCode::ValidationFailed, message.clone())
+        let code = match error {
+            AppError::NotFound { resource } if resource == "label" => {
+                WebSocketErrorCode::LabelNotFound
             }
-            AppError::NotFound { resource } => {
-                let code = match resource.as_str() {
-                    "label" => WebSocketErrorCode::LabelNotFound,
-                    "user" => WebSocketErrorCode::UserNotFound,
-                    _ => WebSocketErrorCode::CommandNotFound,
-                };
-                (code, format!("{} not found", resource))
+            AppError::NotFound { resource } if resource == "user" => {
+                WebSocketErrorCode::UserNotFound
             }
-            AppError::Conflict { message, .. } => {
-                if message.contains("already exists") {
-                    (WebSocketErrorCode::LabelExists, message.clone())
-                } else {
-                    (WebSocketErrorCode::CommandFailed, message.clone())
-                }
+            AppError::Conflict { message, .. } if message.contains("already exists") => {
+                WebSocketErrorCode::LabelExists
             }
-            AppError::Auth { message } => {
-                (WebSocketErrorCode::AuthenticationFailed, message.clone())
-            }
-            AppError::Database(_) => (
-                WebSocketErrorCode::DatabaseError,
-                "Database error".to_string(),
-            ),
-            AppError::Internal(message) => (WebSocketErrorCode::InternalError, message.clone()),
-            _ => (
-                WebSocketErrorCode::InternalError,
-                "Unknown error".to_string(),
-            ),
+            _ => error.code().ws_code(),
         };
+        let message = error.public_message();
 
         let mut ws_error = WebSocketError::new(code, message);
 
@@ -543,6 +525,10 @@ impl WebSocketErrorMapper {
                 WebSocketErrorCode::TokenExpired,
                 "Token has expired".to_string(),
             ),
+            crate::websocket::auth::WebSocketAuthError::RevokedToken => (
+                WebSocketErrorCode::TokenInvalid,
+                "Token has been revoked".to_string(),
+            ),
             crate::websocket::auth::WebSocketAuthError::UserNotFound => (
                 WebSocketErrorCode::UserNotFound,
                 "User not found".to_string(),
@@ -2,6 +2,7 @@
 //!
 //! 提供业务事件的统一抽象，实现业务逻辑与WebSocket传输层的解耦
 
+use crate::utils::clock;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -149,7 +150,7 @@ impl BusinessContext {
             user,
             business_params: HashMap::new(),
             transaction_id: None,
-            trace_id: Uuid::new_v4().to_string(),
+            trace_id: clock::new_id().to_string(),
             tenant_id: user.current_workspace_id,
         }
     }
@@ -765,15 +766,15 @@ impl BusinessEventBuilder {
         let payload = serde_json::to_value(event)?;
 
         Ok(GenericBusinessEvent {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             event_name: E::event_name().to_string(),
             version: E::version().to_string(),
-            user_id: Uuid::new_v4(), // 这里应该从具体事件中获取
+            user_id: clock::new_id(), // 这里应该从具体事件中获取
             workspace_id: None,
             connection_id: None,
             request_id: None,
             payload,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             tags: event.business_tags(),
             resource_ids: event.resource_ids(),
             required_permissions: event.required_permissions(),
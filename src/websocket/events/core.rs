@@ -2,6 +2,7 @@
 //!
 //! 提供事件分发、上下文管理和错误处理的核心功能
 
+use crate::utils::clock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error as StdError;
@@ -52,7 +53,7 @@ impl EventResult {
             execution_time_ms: execution_time.as_millis() as u64,
             handler_name,
             event_id,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             metadata: HashMap::new(),
         }
     }
@@ -71,7 +72,7 @@ impl EventResult {
             execution_time_ms: execution_time.as_millis() as u64,
             handler_name,
             event_id,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             metadata: HashMap::new(),
         }
     }
@@ -151,7 +152,7 @@ impl EventContext {
     /// 创建新的事件上下文
     pub fn new() -> Self {
         Self {
-            request_id: Uuid::new_v4().to_string(),
+            request_id: clock::new_id().to_string(),
             user: None,
             connection_id: None,
             db: None,
@@ -328,7 +329,7 @@ impl EventDispatcher {
         // 更新按类型统计
         *stats.events_by_type.entry(event_type).or_insert(0) += 1;
 
-        stats.last_updated = chrono::Utc::now();
+        stats.last_updated = clock::now();
     }
 }
 
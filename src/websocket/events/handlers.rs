@@ -2,6 +2,7 @@
 //!
 //! 提供简化的事件处理器注册、查找和执行机制
 
+use crate::utils::clock;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::error::Error as StdError;
@@ -153,7 +154,7 @@ impl EventHandler<crate::websocket::events::GenericWebSocketEvent> for Connectio
                         "action": format!("{:?}", action),
                         "user_id": user_id,
                         "connection_id": connection_id,
-                        "timestamp": chrono::Utc::now()
+                        "timestamp": clock::now()
                     }))
                 }
                 _ => Ok(serde_json::json!({
@@ -207,11 +208,11 @@ impl EventHandler<crate::websocket::events::GenericWebSocketEvent> for MessageEv
                     );
                     Ok(serde_json::json!({
                         "status": "processed",
-                        "message_id": Uuid::new_v4(),
+                        "message_id": clock::new_id(),
                         "from_user_id": from_user_id,
                         "to_user_id": to_user_id,
                         "message_type": format!("{:?}", message_type),
-                        "processed_at": chrono::Utc::now()
+                        "processed_at": clock::now()
                     }))
                 }
                 _ => Ok(serde_json::json!({
@@ -258,7 +259,7 @@ impl EventHandler<crate::websocket::events::GenericWebSocketEvent> for SystemEve
                         "status": "processed",
                         "system_event": format!("{:?}", system_event),
                         "payload": payload,
-                        "processed_at": chrono::Utc::now()
+                        "processed_at": clock::now()
                     }))
                 }
                 _ => Ok(serde_json::json!({
@@ -306,7 +307,7 @@ impl EventHandler<crate::websocket::events::GenericWebSocketEvent> for ErrorEven
                 "user_id": event.user_id,
                 "connection_id": event.connection_id,
                 "context_request_id": ctx.request_id,
-                "timestamp": chrono::Utc::now(),
+                "timestamp": clock::now(),
                 "error_type": "event_processing_error"
             });
 
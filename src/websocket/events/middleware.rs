@@ -2,6 +2,7 @@
 //!
 //! 提供事件处理的中间件机制，支持认证、授权、日志、监控、缓存等横切关注点
 
+use crate::utils::clock;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -226,7 +227,7 @@ impl MiddlewareChain {
             (current_avg * (middleware_stats.executions - 1) as f64 + current_duration_ms)
                 / middleware_stats.executions as f64;
 
-        middleware_stats.last_execution = Some(chrono::Utc::now());
+        middleware_stats.last_execution = Some(clock::now());
     }
 
     /// 获取统计信息
@@ -662,7 +663,7 @@ impl CacheMiddleware {
     async fn get_cached_result(&self, cache_key: &str) -> Option<EventResult> {
         let cache = self.cache.read().await;
         if let Some(entry) = cache.get(cache_key) {
-            if chrono::Utc::now() < entry.expires_at {
+            if clock::now() < entry.expires_at {
                 return Some(entry.value.clone());
             }
         }
@@ -670,7 +671,7 @@ impl CacheMiddleware {
     }
 
     async fn cache_result(&self, cache_key: String, result: EventResult) {
-        let expires_at = chrono::Utc::now() + chrono::Duration::seconds(self.ttl_seconds as i64);
+        let expires_at = clock::now() + chrono::Duration::seconds(self.ttl_seconds as i64);
         let entry = CacheEntry {
             value: result,
             expires_at,
@@ -681,7 +682,7 @@ impl CacheMiddleware {
     }
 
     async fn cleanup_expired(&self) {
-        let now = chrono::Utc::now();
+        let now = clock::now();
         let mut cache = self.cache.write().await;
         cache.retain(|_, entry| now < entry.expires_at);
     }
@@ -721,7 +722,7 @@ impl EventMiddleware for CacheMiddleware {
             let cache = self.cache.clone();
             async move {
                 let mut cache = cache.write().await;
-                let now = chrono::Utc::now();
+                let now = clock::now();
                 cache.retain(|_, entry| now < entry.expires_at);
             }
         });
@@ -779,13 +780,13 @@ impl RateLimit {
         Self {
             requests_per_second,
             burst_size,
-            last_refill: chrono::Utc::now(),
+            last_refill: clock::now(),
             tokens: burst_size,
         }
     }
 
     pub fn try_consume(&mut self) -> bool {
-        let now = chrono::Utc::now();
+        let now = clock::now();
         let time_passed = (now - self.last_refill).num_seconds() as u32;
 
         // 补充令牌
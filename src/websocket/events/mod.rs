@@ -13,6 +13,7 @@ pub mod handlers;
 pub mod middleware;
 pub mod types;
 
+use crate::utils::clock;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
@@ -51,12 +52,12 @@ pub trait Event: Send + Sync + fmt::Debug {
 
     /// 获取事件ID
     fn event_id(&self) -> String {
-        Uuid::new_v4().to_string()
+        clock::new_id().to_string()
     }
 
     /// 获取时间戳
     fn timestamp(&self) -> chrono::DateTime<chrono::Utc> {
-        chrono::Utc::now()
+        clock::now()
     }
 }
 
@@ -248,7 +249,7 @@ impl EventBuilder {
         connection_id: String,
     ) -> GenericWebSocketEvent {
         GenericWebSocketEvent {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             event_type: WebSocketEventType::Connection {
                 action,
                 user_id,
@@ -257,7 +258,7 @@ impl EventBuilder {
             user_id: Some(user_id),
             connection_id: Some(connection_id),
             workspace_id: None,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             metadata: EventMetadata::default(),
             should_broadcast: true,
             broadcast_targets: vec![],
@@ -280,7 +281,7 @@ impl EventBuilder {
         };
 
         GenericWebSocketEvent {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             event_type: WebSocketEventType::Message {
                 message_type,
                 from_user_id,
@@ -290,7 +291,7 @@ impl EventBuilder {
             user_id: Some(from_user_id),
             connection_id: None,
             workspace_id: None,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             metadata: EventMetadata::default(),
             should_broadcast,
             broadcast_targets,
@@ -306,7 +307,7 @@ impl EventBuilder {
         workspace_id: Option<Uuid>,
     ) -> GenericWebSocketEvent {
         GenericWebSocketEvent {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             event_type: WebSocketEventType::Business {
                 business_type,
                 payload,
@@ -315,7 +316,7 @@ impl EventBuilder {
             user_id,
             connection_id: None,
             workspace_id,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             metadata: EventMetadata::default(),
             should_broadcast: false,
             broadcast_targets: vec![],
@@ -329,7 +330,7 @@ impl EventBuilder {
         payload: serde_json::Value,
     ) -> GenericWebSocketEvent {
         GenericWebSocketEvent {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             event_type: WebSocketEventType::System {
                 system_event,
                 payload,
@@ -337,7 +338,7 @@ impl EventBuilder {
             user_id: None,
             connection_id: None,
             workspace_id: None,
-            timestamp: chrono::Utc::now(),
+            timestamp: clock::now(),
             metadata: EventMetadata::default(),
             should_broadcast: true,
             broadcast_targets: vec![],
@@ -2,6 +2,7 @@
 //!
 //! 定义事件系统中使用的核心类型、枚举和常量
 
+use crate::utils::clock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -181,7 +182,7 @@ impl EventMetadata {
     /// 检查是否过期
     pub fn is_expired(&self) -> bool {
         if let Some(expires_at) = self.expires_at {
-            chrono::Utc::now() > expires_at
+            clock::now() > expires_at
         } else {
             false
         }
@@ -613,7 +614,7 @@ impl EventMetrics {
             .or_insert(0) += 1;
 
         self.update_processing_time(processing_time_ms);
-        self.last_updated = chrono::Utc::now();
+        self.last_updated = clock::now();
     }
 
     /// 更新失败事件统计
@@ -627,7 +628,7 @@ impl EventMetrics {
             .or_insert(0) += 1;
 
         self.update_processing_time(processing_time_ms);
-        self.last_updated = chrono::Utc::now();
+        self.last_updated = clock::now();
     }
 
     /// 更新处理时间统计
@@ -0,0 +1,137 @@
+use crate::utils::clock;
+use futures_util::StreamExt;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::websocket::manager::{WebSocketManager, WebSocketMessage};
+
+/// 跨实例转发 WebSocket 消息的 Redis 频道
+pub const FANOUT_CHANNEL: &str = "ws:fanout";
+
+/// 消息的投递范围
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeliveryTarget {
+    /// 所有连接
+    All,
+    /// 当前工作区一致的连接
+    Workspace { workspace_id: Uuid },
+    /// 订阅了主题且当前工作区一致的连接；`topic` 为主题键
+    Topic { workspace_id: Uuid, topic: String },
+    /// 某个用户的所有连接
+    User { user_id: Uuid },
+}
+
+/// 通过 Redis 频道在实例之间转发的消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanoutEnvelope {
+    /// 发布方实例；实例忽略自己发布的消息，因为本机连接已直接投递
+    pub origin: String,
+    pub target: DeliveryTarget,
+    pub message: WebSocketMessage,
+}
+
+/// 基于 Redis pub/sub 的跨实例广播
+///
+/// 负载均衡后面的每个实例只持有自己的连接。广播时先投递给本机连接，
+/// 再发布到 `ws:fanout` 频道，由其他实例的监听任务投递给它们的本机连接。
+/// Redis 不可用时只记录警告，本机投递不受影响。
+#[derive(Clone)]
+pub struct RedisFanout {
+    redis_client: redis::Client,
+    instance_id: String,
+    connection: Arc<Mutex<Option<redis::aio::MultiplexedConnection>>>,
+}
+
+impl RedisFanout {
+    pub fn new(redis_client: redis::Client) -> Self {
+        Self {
+            redis_client,
+            instance_id: clock::new_id().to_string(),
+            connection: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// 本实例发布的消息信封
+    pub fn envelope(&self, target: DeliveryTarget, message: WebSocketMessage) -> FanoutEnvelope {
+        FanoutEnvelope {
+            origin: self.instance_id.clone(),
+            target,
+            message,
+        }
+    }
+
+    /// 解析收到的消息；格式错误或来自本实例时返回 `None`
+    pub fn accept(&self, payload: &str) -> Option<FanoutEnvelope> {
+        match serde_json::from_str::<FanoutEnvelope>(payload) {
+            Ok(envelope) if envelope.origin == self.instance_id => None,
+            Ok(envelope) => Some(envelope),
+            Err(e) => {
+                tracing::warn!("Ignoring malformed WebSocket fanout message: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 发布给其他实例；失败时丢弃连接，下次发布重新建立
+    pub async fn publish(&self, target: DeliveryTarget, message: WebSocketMessage) {
+        let payload = match serde_json::to_string(&self.envelope(target, message)) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!("Failed to serialize WebSocket fanout message: {}", e);
+                return;
+            }
+        };
+
+        let mut connection = self.connection.lock().await;
+        if connection.is_none() {
+            match self.redis_client.get_multiplexed_async_connection().await {
+                Ok(conn) => *connection = Some(conn),
+                Err(e) => {
+                    tracing::warn!("WebSocket fanout skipped, Redis unavailable: {}", e);
+                    return;
+                }
+            }
+        }
+        let Some(conn) = connection.as_mut() else {
+            return;
+        };
+        let published: redis::RedisResult<i64> = conn.publish(FANOUT_CHANNEL, payload).await;
+        if let Err(e) = published {
+            tracing::warn!("Failed to publish WebSocket fanout message: {}", e);
+            *connection = None;
+        }
+    }
+
+    /// 订阅频道，把其他实例发布的消息投递给本机连接；断开后重连
+    pub async fn run_listener(self, ws_manager: WebSocketManager) {
+        loop {
+            if let Err(e) = self.listen(&ws_manager).await {
+                tracing::warn!("WebSocket fanout listener disconnected: {}", e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+    }
+
+    async fn listen(&self, ws_manager: &WebSocketManager) -> redis::RedisResult<()> {
+        let mut pubsub = self.redis_client.get_async_pubsub().await?;
+        pubsub.subscribe(FANOUT_CHANNEL).await?;
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let payload: String = msg.get_payload()?;
+            if let Some(envelope) = self.accept(&payload) {
+                ws_manager
+                    .deliver_local(&envelope.target, envelope.message)
+                    .await;
+            }
+        }
+        Ok(())
+    }
+}
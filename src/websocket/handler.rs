@@ -9,9 +9,13 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    db::DbPool,
+    db::models::websocket_session::NewWebSocketSession,
+    db::{DbExecutor, DbPool},
+    services::session_analytics_service::SessionAnalyticsService,
+    utils::clock,
     websocket::{
         auth::{WebSocketAuth, WebSocketAuthQuery},
+        compression::Compression,
         manager::{ConnectedUser, WebSocketManager},
     },
 };
@@ -26,6 +30,7 @@ pub struct WebSocketState {
     pub retry_timeout_manager: crate::websocket::RetryTimeoutManager,
     pub monitor: crate::websocket::WebSocketMonitor,
     pub message_signer: crate::websocket::MessageSigner,
+    pub token_revocations: Option<crate::cache::TokenRevocationList>,
 }
 
 pub struct WebSocketHandler;
@@ -36,17 +41,32 @@ impl WebSocketHandler {
         ws: WebSocketUpgrade,
         Query(query): Query<WebSocketAuthQuery>,
         State(state): State<WebSocketState>,
+        headers: axum::http::HeaderMap,
     ) -> axum::response::Result<Response> {
+        let user_agent = headers
+            .get(axum::http::header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let client =
+            SessionAnalyticsService::client_name(query.client.as_deref(), user_agent.as_deref());
+        let recovery_token = query.recovery_token.clone();
+        let compression = Compression::negotiate(query.compression.as_deref());
+
         // 验证认证token
-        let authenticated_user =
-            match WebSocketAuth::extract_and_validate_token(state.db.clone(), Query(query)).await {
-                Ok(user) => user,
-                Err(error) => {
-                    tracing::warn!("WebSocket authentication failed: {}", error);
-                    let (status, message) = WebSocketAuth::error_response(error);
-                    return Err((status, message).into());
-                }
-            };
+        let authenticated_user = match WebSocketAuth::extract_and_validate_token(
+            state.db.clone(),
+            Query(query),
+            state.token_revocations.as_ref(),
+        )
+        .await
+        {
+            Ok(user) => user,
+            Err(error) => {
+                tracing::warn!("WebSocket authentication failed: {}", error);
+                let (status, message) = WebSocketAuth::error_response(error);
+                return Err((status, message).into());
+            }
+        };
 
         tracing::info!(
             "WebSocket upgrade request from user: {} ({})",
@@ -63,11 +83,16 @@ impl WebSocketHandler {
                 state.command_handler.clone(),
                 state.monitor.clone(),
                 state.db.clone(),
+                client,
+                user_agent,
+                recovery_token,
+                compression,
             )
         }))
     }
 
     /// 处理WebSocket连接
+    #[allow(clippy::too_many_arguments)]
     async fn handle_websocket_connection(
         socket: WebSocket,
         authenticated_user: crate::websocket::auth::AuthenticatedUser,
@@ -75,13 +100,17 @@ impl WebSocketHandler {
         command_handler: crate::websocket::WebSocketCommandHandler,
         monitor: crate::websocket::WebSocketMonitor,
         db: Arc<DbPool>,
+        client: String,
+        user_agent: Option<String>,
+        recovery_token: Option<String>,
+        compression: Option<Compression>,
     ) {
-        let connection_id = Uuid::new_v4().to_string();
+        let connection_id = clock::new_id().to_string();
         let connected_user = ConnectedUser {
             user_id: authenticated_user.user_id,
             username: authenticated_user.username.clone(),
-            connected_at: chrono::Utc::now(),
-            last_ping: chrono::Utc::now(),
+            connected_at: clock::now(),
+            last_ping: clock::now(),
             state: crate::websocket::manager::ConnectionState::Connected,
             subscriptions: std::collections::HashSet::new(),
             message_queue: std::collections::VecDeque::new(),
@@ -96,6 +125,26 @@ impl WebSocketHandler {
             connection_id
         );
 
+        // 记录会话开始（仅在有当前工作区时），统计失败不影响连接
+        let executor = DbExecutor::new((*db).clone());
+        let session = match authenticated_user.current_workspace_id {
+            Some(workspace_id) => {
+                let new_session = NewWebSocketSession {
+                    workspace_id,
+                    user_id: authenticated_user.user_id,
+                    connection_id: connection_id.clone(),
+                    client,
+                    user_agent,
+                };
+                executor
+                    .transaction(move |conn| SessionAnalyticsService::start(conn, new_session))
+                    .await
+                    .map_err(|e| tracing::warn!("Failed to record WebSocket session start: {}", e))
+                    .ok()
+            }
+            None => None,
+        };
+
         // 获取 asset_helper 从 command_handler
         let asset_helper = command_handler.get_asset_helper();
 
@@ -107,10 +156,21 @@ impl WebSocketHandler {
                 connected_user,
                 Some(command_handler),
                 Some(monitor),
-                Some(db),
+                Some(db.clone()),
                 Some(asset_helper),
+                recovery_token,
+                compression,
             )
             .await;
+
+        // 连接结束后记录会话时长
+        if let Some(session) = session
+            && let Err(e) = executor
+                .transaction(move |conn| SessionAnalyticsService::end(conn, session.id))
+                .await
+        {
+            tracing::warn!("Failed to record WebSocket session end: {}", e);
+        }
     }
 
     /// 获取在线用户列表
@@ -139,6 +199,7 @@ impl WebSocketHandler {
     ) -> axum::Json<WebSocketStats> {
         let connection_count = state.ws_manager.get_connection_count().await;
         let online_users = state.ws_manager.get_online_users().await;
+        let shards = state.ws_manager.get_shard_stats().await;
 
         axum::Json(WebSocketStats {
             total_connections: connection_count,
@@ -147,6 +208,7 @@ impl WebSocketHandler {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs(),
+            shards,
         })
     }
 
@@ -158,7 +220,7 @@ impl WebSocketHandler {
         use crate::websocket::manager::{MessageType, WebSocketMessage};
 
         let message = WebSocketMessage {
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(clock::new_id().to_string()),
             message_type: match payload.message_type.as_str() {
                 "text" => MessageType::Text,
                 "notification" => MessageType::Notification,
@@ -166,7 +228,7 @@ impl WebSocketHandler {
                 _ => MessageType::Text,
             },
             data: payload.data,
-            timestamp: Some(chrono::Utc::now()),
+            timestamp: Some(clock::now()),
         };
 
         state
@@ -188,7 +250,7 @@ impl WebSocketHandler {
         use crate::websocket::manager::{MessageType, WebSocketMessage};
 
         let message = WebSocketMessage {
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(clock::new_id().to_string()),
             message_type: match payload.message_type.as_str() {
                 "text" => MessageType::Text,
                 "notification" => MessageType::Notification,
@@ -196,7 +258,7 @@ impl WebSocketHandler {
                 _ => MessageType::SystemMessage,
             },
             data: payload.data,
-            timestamp: Some(chrono::Utc::now()),
+            timestamp: Some(clock::now()),
         };
 
         state.ws_manager.broadcast_message(message).await;
@@ -227,6 +289,49 @@ impl WebSocketHandler {
             remaining_connections: after_count,
         })
     }
+
+    /// 查询监控历史快照，默认返回最近 24 小时
+    pub async fn get_metrics_history(
+        State(state): State<WebSocketState>,
+        Query(params): Query<MetricsHistoryQuery>,
+    ) -> Result<axum::Json<MetricsHistoryResponse>, axum::http::StatusCode> {
+        let to = params.to.unwrap_or_else(chrono::Utc::now);
+        let from = params
+            .from
+            .unwrap_or_else(|| to - chrono::Duration::hours(24));
+        if from > to {
+            return Err(axum::http::StatusCode::BAD_REQUEST);
+        }
+        let limit = params.limit.unwrap_or(500).clamp(1, 5000);
+
+        let snapshots = state
+            .monitor
+            .get_metrics_history(from, to, limit)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load WebSocket metrics history: {}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+        Ok(axum::Json(MetricsHistoryResponse {
+            from,
+            to,
+            count: snapshots.len(),
+            snapshots,
+        }))
+    }
+
+    /// 按消息类型统计的出站帧大小和序列化耗时，总字节数最大的排在最前；附带压缩效果统计
+    pub async fn get_frame_stats(
+        State(state): State<WebSocketState>,
+    ) -> axum::Json<FrameStatsResponse> {
+        let kinds = state.monitor.get_frame_stats();
+        axum::Json(FrameStatsResponse {
+            count: kinds.len(),
+            kinds,
+            compression: state.monitor.get_compression_stats(),
+        })
+    }
 }
 
 // 响应结构体定义
@@ -248,6 +353,29 @@ pub struct WebSocketStats {
     pub total_connections: usize,
     pub unique_users: usize,
     pub server_uptime: u64,
+    pub shards: Vec<crate::websocket::monitoring::ShardStats>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct MetricsHistoryQuery {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<usize>,
+}
+
+#[derive(serde::Serialize)]
+pub struct MetricsHistoryResponse {
+    pub from: chrono::DateTime<chrono::Utc>,
+    pub to: chrono::DateTime<chrono::Utc>,
+    pub count: usize,
+    pub snapshots: Vec<crate::websocket::metrics_store::MetricsSnapshot>,
+}
+
+#[derive(serde::Serialize)]
+pub struct FrameStatsResponse {
+    pub count: usize,
+    pub kinds: Vec<crate::websocket::monitoring::FrameStats>,
+    pub compression: crate::websocket::monitoring::CompressionStats,
 }
 
 #[derive(serde::Deserialize)]
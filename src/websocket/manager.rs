@@ -1,3 +1,4 @@
+use crate::utils::clock;
 use axum::extract::ws::{Message, WebSocket};
 use futures_util::{sink::SinkExt, stream::StreamExt};
 use serde::{Deserialize, Serialize};
@@ -8,6 +9,14 @@ use tokio::sync::{RwLock, broadcast};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::services::redaction_service::{RedactionService, Viewer};
+use crate::websocket::compression::{Compression, FrameCompressor};
+use crate::websocket::entity_change::EntityChanged;
+use crate::websocket::fanout::{DeliveryTarget, RedisFanout};
+use crate::websocket::monitoring::ShardStats;
+use crate::websocket::shard::{DEFAULT_SHARD_COUNT, ShardedMap};
+use crate::websocket::topic::Topic;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebSocketMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -18,6 +27,48 @@ pub struct WebSocketMessage {
     pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl WebSocketMessage {
+    /// 用于帧统计的消息分类
+    ///
+    /// 主题事件按事件名、命令响应按命令类型细分，
+    /// 以便区分例如 `command_response:query_labels` 这类整表查询和 `entity_changed:label` 这类增量推送。
+    pub fn frame_kind(&self) -> String {
+        let base = match serde_json::to_value(&self.message_type) {
+            Ok(serde_json::Value::String(name)) => name,
+            _ => format!("{:?}", self.message_type),
+        };
+        let detail = match self.message_type {
+            MessageType::TopicEvent => self.data.get("event"),
+            MessageType::CommandResponse => self.data.get("command_type"),
+            MessageType::EntityChanged => self.data.get("entity"),
+            _ => None,
+        };
+        match detail.and_then(|value| value.as_str()) {
+            Some(detail) => format!("{}:{}", base, detail),
+            None => base,
+        }
+    }
+}
+
+/// 序列化一个出站消息，并在提供监控时记录帧大小和序列化耗时
+pub fn encode_frame(
+    message: &WebSocketMessage,
+    monitor: Option<&crate::websocket::WebSocketMonitor>,
+) -> Option<String> {
+    let started = std::time::Instant::now();
+    let text = match serde_json::to_string(message) {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Failed to serialize WebSocket message: {}", e);
+            return None;
+        }
+    };
+    if let Some(monitor) = monitor {
+        monitor.record_frame(&message.frame_kind(), text.len(), started.elapsed());
+    }
+    Some(text)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum MessageType {
@@ -32,6 +83,16 @@ pub enum MessageType {
     Command,         // 新增命令类型
     CommandResponse, // 新增命令响应类型
     InitialData,     // 连接后的初始化数据
+    TopicEvent,      // 订阅主题的事件
+    Reconnect,       // 客户端携带恢复令牌恢复断开前的连接
+    EntityChanged,   // 实体变更，客户端据此增量更新缓存
+}
+
+/// 定向消息，只投递到列出的连接
+#[derive(Debug, Clone)]
+pub struct RoutedMessage {
+    pub connection_ids: Arc<HashSet<String>>,
+    pub message: WebSocketMessage,
 }
 
 /// 连接状态
@@ -59,6 +120,32 @@ pub struct ConnectedUser {
     pub current_workspace_id: Option<Uuid>,
 }
 
+impl ConnectedUser {
+    /// 转换为 `get_connection_info` 命令返回的连接信息
+    pub fn connection_info(
+        &self,
+        connection_id: &str,
+    ) -> crate::websocket::commands::ConnectionInfo {
+        let mut subscriptions: Vec<String> = self.subscriptions.iter().cloned().collect();
+        subscriptions.sort();
+        let state = match serde_json::to_value(&self.state) {
+            Ok(serde_json::Value::String(state)) => state,
+            _ => format!("{:?}", self.state).to_lowercase(),
+        };
+        crate::websocket::commands::ConnectionInfo {
+            connection_id: connection_id.to_string(),
+            user_id: self.user_id,
+            username: self.username.clone(),
+            connected_at: self.connected_at,
+            last_ping: self.last_ping,
+            subscriptions,
+            message_queue_size: self.message_queue.len(),
+            state,
+            current_workspace_id: self.current_workspace_id,
+        }
+    }
+}
+
 /// 连接恢复信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionRecoveryInfo {
@@ -67,65 +154,107 @@ pub struct ConnectionRecoveryInfo {
     pub expires_at: chrono::DateTime<chrono::Utc>,
     pub subscriptions: HashSet<String>,
     pub pending_messages: VecDeque<WebSocketMessage>,
+    pub current_workspace_id: Option<Uuid>,
+}
+
+/// `Reconnect` 消息的数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconnectRequest {
+    pub recovery_token: String,
 }
 
 #[derive(Clone)]
 pub struct WebSocketManager {
-    // 存储所有活跃连接
-    connections: Arc<RwLock<HashMap<String, ConnectedUser>>>,
+    // 存储所有活跃连接（按 connection_id 分片）
+    connections: ShardedMap<String, ConnectedUser>,
     // 广播通道
     broadcast_tx: broadcast::Sender<WebSocketMessage>,
-    // 连接恢复信息
-    recovery_info: Arc<RwLock<HashMap<Uuid, ConnectionRecoveryInfo>>>,
-    // 订阅管理
-    subscriptions: Arc<RwLock<HashMap<String, HashSet<Uuid>>>>, // topic -> user_ids
+    // 定向消息通道（按 connection_id 过滤）
+    routed_tx: broadcast::Sender<RoutedMessage>,
+    // 连接恢复信息（按恢复令牌索引）
+    recovery_info: Arc<RwLock<HashMap<String, ConnectionRecoveryInfo>>>,
+    // 订阅管理（按 topic 分片）
+    subscriptions: ShardedMap<String, HashSet<String>>, // topic -> connection_ids
+    // 跨实例广播；未配置时只投递给本机连接
+    fanout: Option<RedisFanout>,
     // 配置
     max_queue_size: usize,
     recovery_token_ttl: Duration,
+    // 协商了压缩的连接上，超过该字节数的出站消息以压缩二进制帧发送
+    compression_threshold_bytes: usize,
 }
 
 impl WebSocketManager {
     pub fn new() -> Self {
+        Self::with_shards(DEFAULT_SHARD_COUNT)
+    }
+
+    /// 使用指定分片数创建管理器
+    pub fn with_shards(shard_count: usize) -> Self {
         let (broadcast_tx, _) = broadcast::channel(1000);
+        let (routed_tx, _) = broadcast::channel(1000);
         Self {
-            connections: Arc::new(RwLock::new(HashMap::new())),
+            connections: ShardedMap::new(shard_count),
             broadcast_tx,
+            routed_tx,
             recovery_info: Arc::new(RwLock::new(HashMap::new())),
-            subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            subscriptions: ShardedMap::new(shard_count),
+            fanout: None,
             max_queue_size: 100,
             recovery_token_ttl: Duration::from_secs(300), // 5分钟
+            compression_threshold_bytes: 8 * 1024,
         }
     }
 
+    /// 设置出站消息的压缩阈值
+    pub fn with_compression_threshold(mut self, threshold_bytes: usize) -> Self {
+        self.compression_threshold_bytes = threshold_bytes;
+        self
+    }
+
+    /// 启用跨实例广播：工作区、主题、用户和全局消息同时发布到 Redis，
+    /// 由其他实例投递给它们的本机连接
+    pub fn with_fanout(mut self, fanout: RedisFanout) -> Self {
+        self.fanout = Some(fanout);
+        self
+    }
+
+    pub fn fanout(&self) -> Option<&RedisFanout> {
+        self.fanout.as_ref()
+    }
+
     /// 完善消息 - 自动生成ID和timestamp
     fn complete_message(&self, mut message: WebSocketMessage) -> WebSocketMessage {
         if message.id.is_none() {
-            message.id = Some(Uuid::new_v4().to_string());
+            message.id = Some(clock::new_id().to_string());
         }
         if message.timestamp.is_none() {
-            message.timestamp = Some(chrono::Utc::now());
+            message.timestamp = Some(clock::now());
         }
         message
     }
 
-    // 添加新连接
+    // 添加新连接；没有恢复令牌的连接会分配一个，返回该令牌，断开后凭它恢复
     pub async fn add_connection(
         &self,
         connection_id: String,
-        user: ConnectedUser,
+        mut user: ConnectedUser,
         db: Option<&Arc<crate::db::DbPool>>,
         asset_helper: Option<&Arc<crate::utils::AssetUrlHelper>>,
-    ) {
-        let mut connections = self.connections.write().await;
-        connections.insert(connection_id.clone(), user.clone());
+    ) -> String {
+        let recovery_token = user
+            .recovery_token
+            .get_or_insert_with(|| clock::new_id().to_string())
+            .clone();
+        self.connections
+            .shard(&connection_id)
+            .write()
+            .await
+            .insert(connection_id.clone(), user.clone());
 
         // 更新订阅信息
-        let mut subscriptions = self.subscriptions.write().await;
         for topic in &user.subscriptions {
-            subscriptions
-                .entry(topic.clone())
-                .or_insert_with(HashSet::new)
-                .insert(user.user_id);
+            self.add_subscriber(topic, &connection_id).await;
         }
 
         info!(
@@ -142,7 +271,7 @@ impl WebSocketManager {
 
         // 发送用户加入消息
         let join_message = WebSocketMessage {
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(clock::new_id().to_string()),
             message_type: MessageType::UserJoined,
             data: if let Some(info) = user_info {
                 serde_json::json!({
@@ -156,31 +285,41 @@ impl WebSocketManager {
                     "connected_at": user.connected_at
                 })
             },
-            timestamp: Some(chrono::Utc::now()),
+            timestamp: Some(clock::now()),
         };
 
         let _ = self.broadcast_tx.send(join_message);
+        recovery_token
     }
 
     // 移除连接
     pub async fn remove_connection(&self, connection_id: &str) {
-        let mut connections = self.connections.write().await;
-        if let Some(user) = connections.remove(connection_id) {
+        let removed = self
+            .connections
+            .shard(connection_id)
+            .write()
+            .await
+            .remove(connection_id);
+        if let Some(user) = removed {
             info!(
                 "🔌 WebSocket User {} disconnected with connection_id: {}",
                 user.username, connection_id
             );
 
+            for topic in &user.subscriptions {
+                self.remove_subscriber(topic, connection_id).await;
+            }
+
             // 发送用户离开消息
             let leave_message = WebSocketMessage {
-                id: Some(Uuid::new_v4().to_string()),
+                id: Some(clock::new_id().to_string()),
                 message_type: MessageType::UserLeft,
                 data: serde_json::json!({
                     "user_id": user.user_id,
                     "username": user.username,
                     "message": format!("{} left the chat", user.username)
                 }),
-                timestamp: Some(chrono::Utc::now()),
+                timestamp: Some(clock::now()),
             };
 
             let _ = self.broadcast_tx.send(leave_message);
@@ -190,11 +329,13 @@ impl WebSocketManager {
         }
     }
 
-    /// 创建连接恢复信息
+    /// 创建连接恢复信息，沿用连接注册时下发的恢复令牌
     async fn create_recovery_info(&self, user: &ConnectedUser) {
-        let recovery_token = Uuid::new_v4().to_string();
-        let expires_at =
-            chrono::Utc::now() + chrono::Duration::from_std(self.recovery_token_ttl).unwrap();
+        let Some(recovery_token) = user.recovery_token.clone() else {
+            return;
+        };
+        let now = clock::now();
+        let expires_at = now + chrono::Duration::from_std(self.recovery_token_ttl).unwrap();
 
         let recovery_info = ConnectionRecoveryInfo {
             user_id: user.user_id,
@@ -202,61 +343,138 @@ impl WebSocketManager {
             expires_at,
             subscriptions: user.subscriptions.clone(),
             pending_messages: user.message_queue.clone(),
+            current_workspace_id: user.current_workspace_id,
         };
 
         let mut recovery_map = self.recovery_info.write().await;
-        recovery_map.insert(user.user_id, recovery_info);
+        recovery_map.retain(|_, info| info.expires_at > now);
+        recovery_map.insert(recovery_token, recovery_info);
 
         info!(
-            "🔄 WebSocket Created recovery info for user {} with token {}",
-            user.username, recovery_token
+            "🔄 WebSocket Created recovery info for user {}",
+            user.username
         );
     }
 
-    /// 恢复连接
+    /// 用恢复令牌恢复断开前的连接状态：把订阅恢复到新连接上，返回断开期间排队的消息。
+    /// 令牌只能使用一次；令牌无效、已过期或不属于该用户时返回 `None`
     pub async fn recover_connection(
         &self,
+        connection_id: &str,
         user_id: Uuid,
         recovery_token: &str,
-    ) -> Option<ConnectedUser> {
-        let recovery_map = self.recovery_info.write().await;
+    ) -> Option<ConnectionRecoveryInfo> {
+        let recovery_info = {
+            let mut recovery_map = self.recovery_info.write().await;
+            match recovery_map.get(recovery_token) {
+                Some(info) if info.expires_at <= clock::now() => {
+                    recovery_map.remove(recovery_token);
+                    return None;
+                }
+                Some(info) if info.user_id == user_id => recovery_map.remove(recovery_token)?,
+                _ => return None,
+            }
+        };
 
-        if let Some(recovery_info) = recovery_map.get(&user_id) {
-            if recovery_info.recovery_token == recovery_token
-                && recovery_info.expires_at > chrono::Utc::now()
-            {
-                // 恢复连接信息
-                let mut connections = self.connections.write().await;
-                if let Some(user) = connections.get_mut(&user_id.to_string()) {
-                    user.state = ConnectionState::Connected;
-                    user.subscriptions = recovery_info.subscriptions.clone();
-                    user.message_queue = recovery_info.pending_messages.clone();
-                    user.recovery_token = None;
-
-                    // 更新订阅信息
-                    let mut subscriptions = self.subscriptions.write().await;
-                    for topic in &user.subscriptions {
-                        subscriptions
-                            .entry(topic.clone())
-                            .or_insert_with(HashSet::new)
-                            .insert(user_id);
-                    }
+        let username = {
+            let mut connections = self.connections.shard(connection_id).write().await;
+            let user = connections.get_mut(connection_id)?;
+            user.state = ConnectionState::Connected;
+            user.username.clone()
+        };
+        for topic in &recovery_info.subscriptions {
+            self.subscribe_connection(connection_id, topic.clone())
+                .await;
+        }
 
-                    info!(
-                        "🔄 WebSocket Recovered connection for user {}",
-                        user.username
-                    );
-                    return Some(user.clone());
+        info!(
+            "🔄 WebSocket Recovered connection for user {} ({} subscriptions, {} queued messages)",
+            username,
+            recovery_info.subscriptions.len(),
+            recovery_info.pending_messages.len()
+        );
+        Some(recovery_info)
+    }
+
+    /// 处理恢复请求，返回依次发给该连接的消息：恢复结果，随后是断开期间排队的消息
+    pub async fn reconnect_messages(
+        &self,
+        connection_id: &str,
+        user_id: Uuid,
+        recovery_token: &str,
+    ) -> Vec<WebSocketMessage> {
+        let Some(recovery_info) = self
+            .recover_connection(connection_id, user_id, recovery_token)
+            .await
+        else {
+            return vec![self.complete_message(WebSocketMessage {
+                id: None,
+                message_type: MessageType::Error,
+                data: serde_json::json!({
+                    "code": "RECOVERY_FAILED",
+                    "message": "Recovery token is invalid or expired"
+                }),
+                timestamp: None,
+            })];
+        };
+
+        let mut subscriptions: Vec<String> = recovery_info.subscriptions.into_iter().collect();
+        subscriptions.sort();
+        let mut messages = vec![self.complete_message(WebSocketMessage {
+            id: None,
+            message_type: MessageType::SystemMessage,
+            data: serde_json::json!({
+                "message": "Connection recovered",
+                "connection_id": connection_id,
+                "subscriptions": subscriptions,
+                "replayed_messages": recovery_info.pending_messages.len()
+            }),
+            timestamp: None,
+        })];
+        messages.extend(recovery_info.pending_messages);
+        messages
+    }
+
+    /// 断开后等待恢复期间，把投递给该连接的消息放入离线队列
+    async fn queue_for_recovery(&self, target: &DeliveryTarget, message: &WebSocketMessage) {
+        let topic_keys = match target {
+            DeliveryTarget::All => return,
+            DeliveryTarget::Topic { topic, .. } => match Topic::parse(topic) {
+                Some(topic) => topic.matching_keys(),
+                None => return,
+            },
+            _ => Vec::new(),
+        };
+
+        let now = clock::now();
+        let mut recovery_map = self.recovery_info.write().await;
+        for info in recovery_map.values_mut() {
+            let matches = info.expires_at > now
+                && match target {
+                    DeliveryTarget::All => false,
+                    DeliveryTarget::Workspace { workspace_id } => {
+                        info.current_workspace_id == Some(*workspace_id)
+                    }
+                    DeliveryTarget::Topic { workspace_id, .. } => {
+                        info.current_workspace_id == Some(*workspace_id)
+                            && topic_keys
+                                .iter()
+                                .any(|key| info.subscriptions.contains(key))
+                    }
+                    DeliveryTarget::User { user_id } => info.user_id == *user_id,
+                };
+            if matches {
+                info.pending_messages.push_back(message.clone());
+                if info.pending_messages.len() > self.max_queue_size {
+                    info.pending_messages.pop_front();
                 }
             }
         }
-
-        None
     }
 
     /// 暂停连接（临时断开）
     pub async fn suspend_connection(&self, connection_id: &str) {
-        let mut connections = self.connections.write().await;
+        let mut connections = self.connections.shard(connection_id).write().await;
         if let Some(user) = connections.get_mut(connection_id) {
             user.state = ConnectionState::Suspended;
             info!(
@@ -268,7 +486,7 @@ impl WebSocketManager {
 
     /// 恢复暂停的连接
     pub async fn resume_connection(&self, connection_id: &str) {
-        let mut connections = self.connections.write().await;
+        let mut connections = self.connections.shard(connection_id).write().await;
         if let Some(user) = connections.get_mut(connection_id) {
             user.state = ConnectionState::Connected;
             info!("▶️ WebSocket Resumed connection for user {}", user.username);
@@ -277,8 +495,9 @@ impl WebSocketManager {
 
     /// 添加离线消息
     pub async fn add_offline_message(&self, user_id: Uuid, message: WebSocketMessage) {
-        let mut connections = self.connections.write().await;
-        if let Some(user) = connections.get_mut(&user_id.to_string()) {
+        let key = user_id.to_string();
+        let mut connections = self.connections.shard(&key).write().await;
+        if let Some(user) = connections.get_mut(&key) {
             if user.state == ConnectionState::Connected {
                 // 用户在线，直接发送
                 return;
@@ -299,8 +518,9 @@ impl WebSocketManager {
 
     /// 获取离线消息
     pub async fn get_offline_messages(&self, user_id: Uuid) -> VecDeque<WebSocketMessage> {
-        let mut connections = self.connections.write().await;
-        if let Some(user) = connections.get_mut(&user_id.to_string()) {
+        let key = user_id.to_string();
+        let mut connections = self.connections.shard(&key).write().await;
+        if let Some(user) = connections.get_mut(&key) {
             let messages = user.message_queue.clone();
             user.message_queue.clear();
             messages
@@ -309,140 +529,313 @@ impl WebSocketManager {
         }
     }
 
-    /// 订阅主题
+    /// 为用户的所有连接订阅主题
     pub async fn subscribe(&self, user_id: Uuid, topic: String) {
-        let mut connections = self.connections.write().await;
-        if let Some(user) = connections.get_mut(&user_id.to_string()) {
-            user.subscriptions.insert(topic.clone());
+        for connection_id in self.connection_ids(|user| user.user_id == user_id).await {
+            self.subscribe_connection(&connection_id, topic.clone())
+                .await;
+        }
+    }
+
+    /// 为单个连接订阅主题；连接不存在时忽略
+    pub async fn subscribe_connection(&self, connection_id: &str, topic: String) {
+        let added = match self
+            .connections
+            .shard(connection_id)
+            .write()
+            .await
+            .get_mut(connection_id)
+        {
+            Some(user) => user.subscriptions.insert(topic.clone()),
+            None => false,
+        };
+
+        if added {
+            self.add_subscriber(&topic, connection_id).await;
         }
+    }
 
-        let mut subscriptions = self.subscriptions.write().await;
-        subscriptions
-            .entry(topic)
+    async fn add_subscriber(&self, topic: &str, connection_id: &str) {
+        self.subscriptions
+            .shard(topic)
+            .write()
+            .await
+            .entry(topic.to_string())
             .or_insert_with(HashSet::new)
-            .insert(user_id);
+            .insert(connection_id.to_string());
     }
 
-    /// 取消订阅主题
+    /// 为用户的所有连接取消订阅主题
     pub async fn unsubscribe(&self, user_id: Uuid, topic: String) {
-        let mut connections = self.connections.write().await;
-        if let Some(user) = connections.get_mut(&user_id.to_string()) {
-            user.subscriptions.remove(&topic);
+        for connection_id in self.connection_ids(|user| user.user_id == user_id).await {
+            self.unsubscribe_connection(&connection_id, &topic).await;
         }
+    }
 
-        let mut subscriptions = self.subscriptions.write().await;
-        if let Some(subscribers) = subscriptions.get_mut(&topic) {
-            subscribers.remove(&user_id);
+    /// 为单个连接取消订阅主题
+    pub async fn unsubscribe_connection(&self, connection_id: &str, topic: &str) {
+        if let Some(user) = self
+            .connections
+            .shard(connection_id)
+            .write()
+            .await
+            .get_mut(connection_id)
+        {
+            user.subscriptions.remove(topic);
+        }
+
+        self.remove_subscriber(topic, connection_id).await;
+    }
+
+    async fn remove_subscriber(&self, topic: &str, connection_id: &str) {
+        let mut subscriptions = self.subscriptions.shard(topic).write().await;
+        if let Some(subscribers) = subscriptions.get_mut(topic) {
+            subscribers.remove(connection_id);
             if subscribers.is_empty() {
-                subscriptions.remove(&topic);
+                subscriptions.remove(topic);
             }
         }
     }
 
     // 获取连接用户信息
     pub async fn get_connection(&self, connection_id: &str) -> Option<ConnectedUser> {
-        let connections = self.connections.read().await;
+        let connections = self.connections.shard(connection_id).read().await;
         connections.get(connection_id).cloned()
     }
 
+    /// 获取连接的实际状态（订阅、队列长度等）
+    pub async fn get_connection_info(
+        &self,
+        connection_id: &str,
+    ) -> Option<crate::websocket::commands::ConnectionInfo> {
+        let connections = self.connections.shard(connection_id).read().await;
+        connections
+            .get(connection_id)
+            .map(|user| user.connection_info(connection_id))
+    }
+
     // 更新连接的最后ping时间
     pub async fn update_ping(&self, connection_id: &str) {
-        let mut connections = self.connections.write().await;
+        let mut connections = self.connections.shard(connection_id).write().await;
         if let Some(user) = connections.get_mut(connection_id) {
-            user.last_ping = chrono::Utc::now();
+            user.last_ping = clock::now();
         }
     }
 
     // 获取所有在线用户
     pub async fn get_online_users(&self) -> Vec<ConnectedUser> {
-        let connections = self.connections.read().await;
-        connections.values().cloned().collect()
+        let mut users = Vec::new();
+        for shard in self.connections.shards() {
+            users.extend(shard.read().await.values().cloned());
+        }
+        users
     }
 
     // 获取在线用户数量
     pub async fn get_connection_count(&self) -> usize {
-        let connections = self.connections.read().await;
-        connections.len()
+        self.connections.len().await
+    }
+
+    /// 获取每个分片的连接数与订阅主题数
+    pub async fn get_shard_stats(&self) -> Vec<ShardStats> {
+        let connections = self.connections.shard_lens().await;
+        let topics = self.subscriptions.shard_lens().await;
+        connections
+            .into_iter()
+            .zip(topics)
+            .enumerate()
+            .map(|(shard, (connections, topics))| ShardStats {
+                shard,
+                connections,
+                topics,
+            })
+            .collect()
+    }
+
+    /// 收集满足条件的连接 ID（逐个分片加读锁）
+    async fn connection_ids(&self, predicate: impl Fn(&ConnectedUser) -> bool) -> HashSet<String> {
+        let mut ids = HashSet::new();
+        for shard in self.connections.shards() {
+            ids.extend(
+                shard
+                    .read()
+                    .await
+                    .iter()
+                    .filter(|(_, user)| predicate(user))
+                    .map(|(id, _)| id.clone()),
+            );
+        }
+        ids
+    }
+
+    /// 把消息投递到指定连接，返回连接数
+    fn route(&self, connection_ids: HashSet<String>, message: WebSocketMessage) -> usize {
+        let count = connection_ids.len();
+        if count > 0
+            && let Err(e) = self.routed_tx.send(RoutedMessage {
+                connection_ids: Arc::new(connection_ids),
+                message,
+            })
+        {
+            error!("📤 WebSocket Failed to route message: {}", e);
+        }
+        count
+    }
+
+    /// 只投递给本机连接，返回收到消息的连接数；其他实例转发来的消息也由此投递
+    pub async fn deliver_local(&self, target: &DeliveryTarget, message: WebSocketMessage) -> usize {
+        self.queue_for_recovery(target, &message).await;
+        match target {
+            DeliveryTarget::All => match self.broadcast_tx.send(message) {
+                Ok(receivers) => receivers,
+                Err(e) => {
+                    error!("📢 WebSocket Failed to broadcast message: {}", e);
+                    0
+                }
+            },
+            DeliveryTarget::Workspace { workspace_id } => {
+                let connection_ids = self
+                    .connection_ids(|user| user.current_workspace_id == Some(*workspace_id))
+                    .await;
+                self.route(connection_ids, message)
+            }
+            DeliveryTarget::Topic {
+                workspace_id,
+                topic,
+            } => {
+                let Some(topic) = Topic::parse(topic) else {
+                    warn!("⚠️ WebSocket Ignoring message for unknown topic {}", topic);
+                    return 0;
+                };
+                let mut subscribers = HashSet::new();
+                for key in topic.matching_keys() {
+                    if let Some(ids) = self.subscriptions.shard(&key).read().await.get(&key) {
+                        subscribers.extend(ids.iter().cloned());
+                    }
+                }
+
+                let mut connection_ids = HashSet::new();
+                for connection_id in subscribers {
+                    let in_workspace = self
+                        .connections
+                        .shard(&connection_id)
+                        .read()
+                        .await
+                        .get(&connection_id)
+                        .is_some_and(|user| user.current_workspace_id == Some(*workspace_id));
+                    if in_workspace {
+                        connection_ids.insert(connection_id);
+                    }
+                }
+                self.route(connection_ids, message)
+            }
+            DeliveryTarget::User { user_id } => {
+                let connection_ids = self.connection_ids(|user| user.user_id == *user_id).await;
+                self.route(connection_ids, message)
+            }
+        }
+    }
+
+    /// 投递给本机连接，并在启用跨实例广播时发布给其他实例
+    async fn deliver(&self, target: DeliveryTarget, message: WebSocketMessage) -> usize {
+        let message = self.complete_message(message);
+        let delivered = self.deliver_local(&target, message.clone()).await;
+        if let Some(fanout) = &self.fanout {
+            fanout.publish(target, message).await;
+        }
+        delivered
     }
 
     // 广播消息给所有连接
     pub async fn broadcast_message(&self, message: WebSocketMessage) {
-        if let Err(e) = self.broadcast_tx.send(message) {
-            error!("📢 WebSocket Failed to broadcast message: {}", e);
-        }
+        self.deliver(DeliveryTarget::All, message).await;
     }
 
-    // 基于workspace广播消息
+    // 基于workspace广播消息 - 只发送给当前工作区一致的连接
     pub async fn broadcast_to_workspace(&self, workspace_id: Uuid, message: WebSocketMessage) {
-        let connections = self.connections.read().await;
-        let workspace_users: Vec<_> = connections
-            .iter()
-            .filter(|(_, user)| user.current_workspace_id == Some(workspace_id))
-            .map(|(id, _)| id.clone())
-            .collect();
-
-        if !workspace_users.is_empty() {
+        let workspace_connections = self
+            .deliver(DeliveryTarget::Workspace { workspace_id }, message)
+            .await;
+        if workspace_connections > 0 {
             info!(
-                "📢 WebSocket Broadcasting to workspace {} ({} users)",
-                workspace_id,
-                workspace_users.len()
+                "📢 WebSocket Broadcasting to workspace {} ({} connections)",
+                workspace_id, workspace_connections
             );
-            if let Err(e) = self.broadcast_tx.send(message) {
-                error!(
-                    "📢 WebSocket Failed to broadcast to workspace {}: {}",
-                    workspace_id, e
-                );
-            }
-        } else {
+        } else if self.fanout.is_none() {
             warn!("⚠️ WebSocket No users found in workspace {}", workspace_id);
         }
     }
 
+    /// 推送实体变更 - 只发送给当前工作区与实体所属工作区一致的连接
+    pub async fn entity_changed(&self, workspace_id: Uuid, change: &EntityChanged) {
+        self.deliver(
+            DeliveryTarget::Workspace { workspace_id },
+            change.to_message(),
+        )
+        .await;
+    }
+
+    /// 发布主题事件 - 只发送给订阅了该主题且当前工作区一致的连接
+    pub async fn publish(&self, workspace_id: Uuid, topic: &Topic, message: WebSocketMessage) {
+        let target = DeliveryTarget::Topic {
+            workspace_id,
+            topic: topic.key(),
+        };
+        let subscribed = self.deliver(target, message).await;
+        if subscribed > 0 {
+            info!(
+                "📢 WebSocket Published {} to {} connections",
+                topic, subscribed
+            );
+        }
+    }
+
     // 发送消息给特定用户
     pub async fn send_to_user(&self, user_id: Uuid, message: WebSocketMessage) {
-        let connections = self.connections.read().await;
-        let user_connections: Vec<_> = connections
-            .iter()
-            .filter(|(_, user)| user.user_id == user_id)
-            .map(|(id, _)| id.clone())
-            .collect();
-
-        if !user_connections.is_empty() {
-            if let Err(e) = self.broadcast_tx.send(message) {
-                error!(
-                    "📤 WebSocket Failed to send message to user {}: {}",
-                    user_id, e
-                );
-            }
-        } else {
+        let delivered = self
+            .deliver(DeliveryTarget::User { user_id }, message)
+            .await;
+        if delivered == 0 && self.fanout.is_none() {
             warn!("⚠️ WebSocket User {} is not connected", user_id);
         }
     }
 
+    /// 发送消息给单个连接，用于命令响应等只属于发起方的消息
+    pub async fn send_to_connection(&self, connection_id: &str, message: WebSocketMessage) {
+        let connection_ids = HashSet::from([connection_id.to_string()]);
+        self.route(connection_ids, message);
+    }
+
     // 获取广播接收器
     pub fn get_broadcast_receiver(&self) -> broadcast::Receiver<WebSocketMessage> {
         self.broadcast_tx.subscribe()
     }
 
+    // 获取定向消息接收器
+    pub fn get_routed_receiver(&self) -> broadcast::Receiver<RoutedMessage> {
+        self.routed_tx.subscribe()
+    }
+
     // 清理超时连接
-    pub async fn cleanup_stale_connections(&self, timeout_minutes: i64) {
-        let mut connections = self.connections.write().await;
-        let cutoff_time = chrono::Utc::now() - chrono::Duration::minutes(timeout_minutes);
-
-        let stale_connections: Vec<String> = connections
-            .iter()
-            .filter(|(_, user)| user.last_ping < cutoff_time)
-            .map(|(id, _)| id.clone())
-            .collect();
-
-        for connection_id in stale_connections {
-            if let Some(user) = connections.remove(&connection_id) {
-                warn!(
-                    "🧹 WebSocket Removed stale connection for user: {}",
-                    user.username
-                );
-            }
+    pub async fn cleanup_stale_connections(&self, timeout_minutes: i64) -> usize {
+        let cutoff_time = clock::now() - chrono::Duration::minutes(timeout_minutes);
+
+        let mut removed = 0;
+        for shard in self.connections.shards() {
+            let mut connections = shard.write().await;
+            connections.retain(|_, user| {
+                let stale = user.last_ping < cutoff_time;
+                if stale {
+                    warn!(
+                        "🧹 WebSocket Removed stale connection for user: {}",
+                        user.username
+                    );
+                    removed += 1;
+                }
+                !stale
+            });
         }
+        removed
     }
 
     // 处理WebSocket连接
@@ -456,20 +849,26 @@ impl WebSocketManager {
         monitor: Option<crate::websocket::WebSocketMonitor>,
         db: Option<Arc<crate::db::DbPool>>,
         asset_helper: Option<Arc<crate::utils::AssetUrlHelper>>,
+        reconnect_token: Option<String>,
+        compression: Option<Compression>,
     ) {
+        let compressor = FrameCompressor::new(compression, self.compression_threshold_bytes);
+
         // 订阅广播消息
         let mut rx = self.get_broadcast_receiver();
+        let mut routed_rx = self.get_routed_receiver();
         let user_id = user.user_id;
         let username = user.username.clone();
 
         // 添加连接
-        self.add_connection(
-            connection_id.clone(),
-            user.clone(),
-            db.as_ref(),
-            asset_helper.as_ref(),
-        )
-        .await;
+        let recovery_token = self
+            .add_connection(
+                connection_id.clone(),
+                user.clone(),
+                db.as_ref(),
+                asset_helper.as_ref(),
+            )
+            .await;
 
         // 记录连接监控
         if let Some(ref monitor) = monitor {
@@ -478,20 +877,48 @@ impl WebSocketManager {
                 .await;
         }
 
+        // 按连接用户在当前工作区的角色脱敏下发的消息；无法确定角色时按非成员处理
+        let viewer = Viewer {
+            user_id,
+            email: None,
+            role: match (user.current_workspace_id, db.as_ref()) {
+                (Some(workspace_id), Some(db_pool)) => {
+                    let ctx = crate::services::context::RequestContext {
+                        user_id,
+                        workspace_id,
+                        idempotency_key: None,
+                        channel: crate::services::context::AuthChannel::Session,
+                    };
+                    crate::db::DbExecutor::new((**db_pool).clone())
+                        .read(move |conn| RedactionService::viewer(conn, &ctx, None))
+                        .await
+                        .ok()
+                        .and_then(|viewer| viewer.role)
+                }
+                _ => None,
+            },
+        };
+
         // 发送连接成功消息和初始化数据
         let welcome_message = WebSocketMessage {
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(clock::new_id().to_string()),
             message_type: MessageType::SystemMessage,
             data: serde_json::json!({
                 "message": "Connected successfully",
                 "connection_id": connection_id,
-                "online_users": self.get_online_users().await.len()
+                "online_users": self.get_online_users().await.len(),
+                "recovery_token": recovery_token,
+                "recovery_token_ttl": self.recovery_token_ttl.as_secs(),
+                "compression": compressor.codec(),
+                "compression_threshold_bytes": compressor.threshold_bytes()
             }),
-            timestamp: Some(chrono::Utc::now()),
+            timestamp: Some(clock::now()),
         };
 
-        if let Ok(msg_text) = serde_json::to_string(&welcome_message) {
-            let _ = socket.send(Message::Text(msg_text)).await;
+        if let Some(msg_text) = encode_frame(&welcome_message, monitor.as_ref()) {
+            let _ = socket
+                .send(compressor.to_message(msg_text, monitor.as_ref()))
+                .await;
         }
 
         // 发送初始化数据（workspace members 和 teams）
@@ -499,13 +926,29 @@ impl WebSocketManager {
             user.current_workspace_id,
             db.as_ref(),
             asset_helper.as_ref(),
-        ) {
-            if let Some(init_data_message) = self
-                .get_initial_data_message(user_id, workspace_id, db_pool, asset_helper_ref)
+        ) && let Some(mut init_data_message) = self
+            .get_initial_data_message(user_id, workspace_id, db_pool, asset_helper_ref)
+            .await
+        {
+            RedactionService::redact_value(&mut init_data_message.data, &viewer);
+            if let Some(msg_text) = encode_frame(&init_data_message, monitor.as_ref()) {
+                let _ = socket
+                    .send(compressor.to_message(msg_text, monitor.as_ref()))
+                    .await;
+            }
+        }
+
+        // 握手时携带恢复令牌：恢复订阅并重放断开期间排队的消息
+        if let Some(token) = reconnect_token {
+            for mut message in self
+                .reconnect_messages(&connection_id, user_id, &token)
                 .await
             {
-                if let Ok(msg_text) = serde_json::to_string(&init_data_message) {
-                    let _ = socket.send(Message::Text(msg_text)).await;
+                RedactionService::redact_value(&mut message.data, &viewer);
+                if let Some(msg_text) = encode_frame(&message, monitor.as_ref()) {
+                    let _ = socket
+                        .send(compressor.to_message(msg_text, monitor.as_ref()))
+                        .await;
                 }
             }
         }
@@ -552,12 +995,12 @@ impl WebSocketManager {
                                         );
                                         manager.update_ping(&connection_id).await;
                                         let pong = WebSocketMessage {
-                                            id: Some(Uuid::new_v4().to_string()),
+                                            id: Some(clock::new_id().to_string()),
                                             message_type: MessageType::Pong,
-                                            data: serde_json::json!({"timestamp": chrono::Utc::now()}),
-                                            timestamp: Some(chrono::Utc::now()),
+                                            data: serde_json::json!({"timestamp": clock::now()}),
+                                            timestamp: Some(clock::now()),
                                         };
-                                        manager.broadcast_message(pong).await;
+                                        manager.send_to_connection(&connection_id, pong).await;
                                     }
                                     MessageType::Command => {
                                         // 处理命令
@@ -583,17 +1026,6 @@ impl WebSocketManager {
                                                                 .current_workspace_id, // 来自握手时的认证信息
                                                         };
 
-                                                    // 标记此次命令是否会影响标签数据（在移动 command 之前计算）
-                                                    let affects_labels = matches!(
-                                                        &command,
-                                                        crate::websocket::WebSocketCommand::CreateLabel { .. }
-                                                            | crate::websocket::WebSocketCommand::UpdateLabel { .. }
-                                                            | crate::websocket::WebSocketCommand::DeleteLabel { .. }
-                                                            | crate::websocket::WebSocketCommand::BatchCreateLabels { .. }
-                                                            | crate::websocket::WebSocketCommand::BatchUpdateLabels { .. }
-                                                            | crate::websocket::WebSocketCommand::BatchDeleteLabels { .. }
-                                                    );
-
                                                     // 标记此次命令是否会影响 workspace 数据
                                                     let affects_workspace = matches!(
                                                         &command,
@@ -601,11 +1033,34 @@ impl WebSocketManager {
                                                             | crate::websocket::WebSocketCommand::CreateWorkspace { .. }
                                                     );
 
+                                                    // 订阅变更在命令成功后写入连接状态
+                                                    let subscription_change = match &command {
+                                                        crate::websocket::WebSocketCommand::Subscribe { topics, .. } => {
+                                                            Some((true, topics.clone()))
+                                                        }
+                                                        crate::websocket::WebSocketCommand::Unsubscribe { topics, .. } => {
+                                                            Some((false, topics.clone()))
+                                                        }
+                                                        _ => None,
+                                                    };
+
+                                                    let connection_info = if matches!(
+                                                        &command,
+                                                        crate::websocket::WebSocketCommand::GetConnectionInfo { .. }
+                                                    ) {
+                                                        manager
+                                                            .get_connection_info(&connection_id)
+                                                            .await
+                                                    } else {
+                                                        None
+                                                    };
+
                                                     let start_time = std::time::Instant::now();
                                                     let response = handler
-                                                        .handle_command(
+                                                        .handle_connection_command(
                                                             command,
                                                             &authenticated_user,
+                                                            connection_info,
                                                         )
                                                         .await;
                                                     let response_time = start_time.elapsed();
@@ -620,56 +1075,46 @@ impl WebSocketManager {
                                                             .await;
                                                     }
 
+                                                    if response.success
+                                                        && let Some((subscribe, topics)) =
+                                                            subscription_change
+                                                    {
+                                                        for topic in topics.iter().filter_map(|t| {
+                                                            Topic::normalize_subscription(t)
+                                                        }) {
+                                                            if subscribe {
+                                                                manager
+                                                                    .subscribe_connection(
+                                                                        &connection_id,
+                                                                        topic,
+                                                                    )
+                                                                    .await;
+                                                            } else {
+                                                                manager
+                                                                    .unsubscribe_connection(
+                                                                        &connection_id,
+                                                                        &topic,
+                                                                    )
+                                                                    .await;
+                                                            }
+                                                        }
+                                                    }
+
                                                     let response_message = WebSocketMessage {
-                                                        id: Some(Uuid::new_v4().to_string()),
+                                                        id: Some(clock::new_id().to_string()),
                                                         message_type: MessageType::CommandResponse,
                                                         data: serde_json::to_value(&response)
                                                             .unwrap(),
-                                                        timestamp: Some(chrono::Utc::now()),
+                                                        timestamp: Some(clock::now()),
                                                     };
+                                                    // 命令响应只返回给发起命令的连接
                                                     manager
-                                                        .broadcast_message(response_message)
+                                                        .send_to_connection(
+                                                            &connection_id,
+                                                            response_message,
+                                                        )
                                                         .await;
 
-                                                    // 如果是影响标签数据的命令，追加一次 query_labels 的推送
-                                                    if affects_labels {
-                                                        // 构造一个 QueryLabels 命令（使用当前上下文工作区；filters 默认）
-                                                        let refresh_cmd = crate::websocket::WebSocketCommand::QueryLabels {
-                                                                filters: crate::websocket::commands::LabelFilters {
-                                                                    workspace_id: None,
-                                                                    level: None,
-                                                                    name_pattern: None,
-                                                                    color: None,
-                                                                    created_after: None,
-                                                                    created_before: None,
-                                                                    limit: None,
-                                                                    offset: None,
-                                                                },
-                                                                request_id: None,
-                                                            };
-
-                                                        let refresh_response = handler
-                                                            .handle_command(
-                                                                refresh_cmd,
-                                                                &authenticated_user,
-                                                            )
-                                                            .await;
-
-                                                        let refresh_message = WebSocketMessage {
-                                                            id: Some(Uuid::new_v4().to_string()),
-                                                            message_type:
-                                                                MessageType::CommandResponse,
-                                                            data: serde_json::to_value(
-                                                                &refresh_response,
-                                                            )
-                                                            .unwrap(),
-                                                            timestamp: Some(chrono::Utc::now()),
-                                                        };
-                                                        manager
-                                                            .broadcast_message(refresh_message)
-                                                            .await;
-                                                    }
-
                                                     // 如果是影响 workspace 的命令，广播 get_current_workspace
                                                     if affects_workspace {
                                                         // 构造一个 GetCurrentWorkspace 命令
@@ -685,14 +1130,14 @@ impl WebSocketManager {
                                                             .await;
 
                                                         let refresh_message = WebSocketMessage {
-                                                            id: Some(Uuid::new_v4().to_string()),
+                                                            id: Some(clock::new_id().to_string()),
                                                             message_type:
                                                                 MessageType::CommandResponse,
                                                             data: serde_json::to_value(
                                                                 &refresh_response,
                                                             )
                                                             .unwrap(),
-                                                            timestamp: Some(chrono::Utc::now()),
+                                                            timestamp: Some(clock::now()),
                                                         };
 
                                                         // 广播到同一workspace的所有用户
@@ -722,17 +1167,41 @@ impl WebSocketManager {
                                                     );
 
                                                     let error_message = WebSocketMessage {
-                                                        id: Some(Uuid::new_v4().to_string()),
+                                                        id: Some(clock::new_id().to_string()),
                                                         message_type: MessageType::CommandResponse,
                                                         data: serde_json::to_value(&error_response)
                                                             .unwrap(),
-                                                        timestamp: Some(chrono::Utc::now()),
+                                                        timestamp: Some(clock::now()),
                                                     };
-                                                    manager.broadcast_message(error_message).await;
+                                                    manager
+                                                        .send_to_connection(
+                                                            &connection_id,
+                                                            error_message,
+                                                        )
+                                                        .await;
                                                 }
                                             }
                                         }
                                     }
+                                    MessageType::Reconnect => {
+                                        info!(
+                                            "🔄 WebSocket Reconnect received from connection_id: {}",
+                                            connection_id
+                                        );
+                                        let token = serde_json::from_value::<ReconnectRequest>(
+                                            complete_message.data,
+                                        )
+                                        .map(|request| request.recovery_token)
+                                        .unwrap_or_default();
+                                        for message in manager
+                                            .reconnect_messages(&connection_id, user_id, &token)
+                                            .await
+                                        {
+                                            manager
+                                                .send_to_connection(&connection_id, message)
+                                                .await;
+                                        }
+                                    }
                                     MessageType::Text => {
                                         // 广播文本消息
                                         info!(
@@ -781,28 +1250,41 @@ impl WebSocketManager {
         let send_task = {
             let monitor = monitor.clone();
             tokio::spawn(async move {
-                while let Ok(message) = rx.recv().await {
-                    // 基于workspace广播 - 发送给同一workspace的所有用户
-                    let should_send = true; // 所有广播消息都发送给当前连接
-
-                    if should_send {
-                        if let Ok(msg_text) = serde_json::to_string(&message) {
-                            // 记录消息发送
-                            info!(
-                                "📤 WebSocket sending message to connection_id: {}, length: {}, type: {:?}",
-                                connection_id_clone,
-                                msg_text.len(),
-                                message.message_type
-                            );
-                            if let Some(ref monitor) = monitor {
-                                monitor
-                                    .record_message_sent(&connection_id_clone, msg_text.len())
-                                    .await;
+                loop {
+                    let mut message = tokio::select! {
+                        message = rx.recv() => match message {
+                            Ok(message) => message,
+                            Err(_) => break,
+                        },
+                        routed = routed_rx.recv() => match routed {
+                            Ok(routed) if routed.connection_ids.contains(&connection_id_clone) => {
+                                routed.message
                             }
+                            Ok(_) => continue,
+                            Err(_) => break,
+                        },
+                    };
+                    RedactionService::redact_value(&mut message.data, &viewer);
+
+                    if let Some(msg_text) = encode_frame(&message, monitor.as_ref()) {
+                        let frame = compressor.to_message(msg_text, monitor.as_ref());
+                        let wire_bytes = match &frame {
+                            Message::Binary(bytes) => bytes.len(),
+                            other => other.to_text().map(str::len).unwrap_or_default(),
+                        };
+                        // 记录消息发送（按实际发送的字节数）
+                        info!(
+                            "📤 WebSocket sending message to connection_id: {}, length: {}, type: {:?}",
+                            connection_id_clone, wire_bytes, message.message_type
+                        );
+                        if let Some(ref monitor) = monitor {
+                            monitor
+                                .record_message_sent(&connection_id_clone, wire_bytes)
+                                .await;
+                        }
 
-                            if sender.send(Message::Text(msg_text)).await.is_err() {
-                                break;
-                            }
+                        if sender.send(frame).await.is_err() {
+                            break;
                         }
                     }
                 }
@@ -834,61 +1316,57 @@ impl WebSocketManager {
         db: &Arc<crate::db::DbPool>,
         asset_helper: &Arc<crate::utils::AssetUrlHelper>,
     ) -> Option<WebSocketMessage> {
-        let mut conn = match db.get() {
-            Ok(conn) => conn,
-            Err(e) => {
-                error!("Failed to get database connection for initial data: {}", e);
-                return None;
-            }
-        };
-
         let ctx = crate::services::context::RequestContext {
             user_id,
             workspace_id,
             idempotency_key: None,
+            channel: crate::services::context::AuthChannel::Session,
         };
-
-        // 获取用户完整 profile（参考 GET /auth/profile API）
-        let user_profile = match crate::services::auth_service::AuthService::get_profile(
-            &mut conn,
-            &ctx,
-            asset_helper,
-        ) {
-            Ok(p) => p,
+        let asset_helper = asset_helper.clone();
+        let loaded = crate::db::DbExecutor::new((**db).clone())
+            .read(move |conn| {
+                // 获取用户完整 profile（参考 GET /auth/profile API）
+                let user_profile = crate::services::auth_service::AuthService::get_profile(
+                    conn,
+                    &ctx,
+                    &asset_helper,
+                )?;
+
+                // 获取当前 workspace 的 members
+                let workspace_members = crate::services::workspace_members_service::WorkspaceMembersService::get_current_workspace_members(
+                    conn,
+                    &ctx,
+                    &asset_helper,
+                    None,
+                    None,
+                )
+                .unwrap_or_else(|e| {
+                    warn!("Failed to get workspace members: {}", e);
+                    vec![]
+                });
+
+                // 获取当前 workspace 的 teams（不是用户所有的 teams）
+                let current_workspace_teams =
+                    crate::services::teams_service::TeamsService::list(conn, &ctx).unwrap_or_else(
+                        |e| {
+                            warn!("Failed to get current workspace teams: {}", e);
+                            vec![]
+                        },
+                    );
+                Ok((user_profile, workspace_members, current_workspace_teams))
+            })
+            .await;
+        let (user_profile, workspace_members, current_workspace_teams) = match loaded {
+            Ok(loaded) => loaded,
             Err(e) => {
                 warn!("Failed to get user profile: {}", e);
                 return None;
             }
         };
 
-        // 获取当前 workspace 的 members
-        let workspace_members = match crate::services::workspace_members_service::WorkspaceMembersService::get_current_workspace_members(
-            &mut conn,
-            &ctx,
-            asset_helper,
-            None,
-            None,
-        ) {
-            Ok(m) => m,
-            Err(e) => {
-                warn!("Failed to get workspace members: {}", e);
-                vec![]
-            }
-        };
-
-        // 获取当前 workspace 的 teams（不是用户所有的 teams）
-        let current_workspace_teams =
-            match crate::services::teams_service::TeamsService::list(&mut conn, &ctx) {
-                Ok(t) => t,
-                Err(e) => {
-                    warn!("Failed to get current workspace teams: {}", e);
-                    vec![]
-                }
-            };
-
         // 构建返回数据：user 对象（不含 workspaces 和 teams），然后 workspaces 和 teams 提到同级
         Some(WebSocketMessage {
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(clock::new_id().to_string()),
             message_type: MessageType::InitialData,
             data: serde_json::json!({
                 "user": {
@@ -903,7 +1381,7 @@ impl WebSocketManager {
                 "teams": current_workspace_teams,        // 当前工作空间的团队（修正）
                 "workspace_members": workspace_members,  // 当前工作空间的成员
             }),
-            timestamp: Some(chrono::Utc::now()),
+            timestamp: Some(clock::now()),
         })
     }
 
@@ -913,22 +1391,18 @@ impl WebSocketManager {
         user_id: Uuid,
         asset_helper: &crate::utils::AssetUrlHelper,
     ) -> Option<crate::db::models::auth::UserBasicInfo> {
-        let mut conn = match db.get() {
-            Ok(conn) => conn,
-            Err(e) => {
-                error!("Failed to get database connection for user info: {}", e);
-                return None;
-            }
-        };
-
         use crate::schema::users;
         use diesel::prelude::*;
 
-        match users::table
-            .filter(users::id.eq(user_id))
-            .select(crate::db::models::auth::User::as_select())
-            .first::<crate::db::models::auth::User>(&mut conn)
-        {
+        let user = crate::db::DbExecutor::new((**db).clone())
+            .read(move |conn| {
+                Ok(users::table
+                    .filter(users::id.eq(user_id))
+                    .select(crate::db::models::auth::User::as_select())
+                    .first::<crate::db::models::auth::User>(conn)?)
+            })
+            .await;
+        match user {
             Ok(user) => {
                 let processed_avatar_url = user
                     .avatar_url
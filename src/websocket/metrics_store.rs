@@ -0,0 +1,251 @@
+use crate::utils::clock;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::error::AppResult;
+use crate::websocket::monitoring::PerformanceMetrics;
+
+/// 某一时刻的监控指标快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub total_connections: u64,
+    pub active_connections: u64,
+    pub total_messages_sent: u64,
+    pub total_messages_received: u64,
+    pub total_commands_processed: u64,
+    pub average_response_time_ms: f64,
+    pub error_rate: f64,
+}
+
+impl From<&PerformanceMetrics> for MetricsSnapshot {
+    fn from(metrics: &PerformanceMetrics) -> Self {
+        Self {
+            timestamp: clock::now(),
+            total_connections: metrics.total_connections,
+            active_connections: metrics.active_connections,
+            total_messages_sent: metrics.total_messages_sent,
+            total_messages_received: metrics.total_messages_received,
+            total_commands_processed: metrics.total_commands_processed,
+            average_response_time_ms: metrics.average_response_time_ms,
+            error_rate: metrics.error_rate,
+        }
+    }
+}
+
+/// 监控数据的持久化存储
+#[async_trait]
+pub trait MetricsSink: Send + Sync {
+    /// 保存一个快照
+    async fn store(&self, snapshot: &MetricsSnapshot) -> AppResult<()>;
+
+    /// 按时间升序返回 `[from, to]` 区间内最多 `limit` 个快照
+    async fn history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> AppResult<Vec<MetricsSnapshot>>;
+
+    /// 最近一次保存的快照
+    async fn latest(&self) -> AppResult<Option<MetricsSnapshot>>;
+}
+
+/// 进程内存储，重启后数据丢失；用于测试或未配置持久化时
+pub struct InMemoryMetricsSink {
+    snapshots: RwLock<VecDeque<MetricsSnapshot>>,
+    capacity: usize,
+}
+
+impl InMemoryMetricsSink {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: RwLock::new(VecDeque::new()),
+            capacity: capacity.max(1),
+        }
+    }
+}
+
+#[async_trait]
+impl MetricsSink for InMemoryMetricsSink {
+    async fn store(&self, snapshot: &MetricsSnapshot) -> AppResult<()> {
+        let mut snapshots = self.snapshots.write().unwrap();
+        snapshots.push_back(snapshot.clone());
+        while snapshots.len() > self.capacity {
+            snapshots.pop_front();
+        }
+        Ok(())
+    }
+
+    async fn history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> AppResult<Vec<MetricsSnapshot>> {
+        let snapshots = self.snapshots.read().unwrap();
+        Ok(snapshots
+            .iter()
+            .filter(|s| s.timestamp >= from && s.timestamp <= to)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn latest(&self) -> AppResult<Option<MetricsSnapshot>> {
+        Ok(self.snapshots.read().unwrap().back().cloned())
+    }
+}
+
+/// 基于 Redis 有序集合的存储（score 为毫秒时间戳），超过保留期的数据会被裁剪
+pub struct RedisMetricsSink {
+    client: redis::Client,
+    key: String,
+    retention: Duration,
+}
+
+impl RedisMetricsSink {
+    pub const DEFAULT_KEY: &'static str = "ws:metrics:history";
+
+    pub fn new(client: redis::Client, retention: Duration) -> Self {
+        Self {
+            client,
+            key: Self::DEFAULT_KEY.to_string(),
+            retention,
+        }
+    }
+
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    fn decode(members: Vec<String>) -> Vec<MetricsSnapshot> {
+        members
+            .iter()
+            .filter_map(|m| serde_json::from_str(m).ok())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MetricsSink for RedisMetricsSink {
+    async fn store(&self, snapshot: &MetricsSnapshot) -> AppResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let member = serde_json::to_string(snapshot)
+            .map_err(|e| crate::error::AppError::internal(e.to_string()))?;
+        let score = snapshot.timestamp.timestamp_millis();
+        let _: () = conn.zadd(&self.key, member, score).await?;
+
+        let cutoff = score - self.retention.as_millis() as i64;
+        let _: () = conn.zrembyscore(&self.key, "-inf", cutoff).await?;
+        Ok(())
+    }
+
+    async fn history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> AppResult<Vec<MetricsSnapshot>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let members: Vec<String> = conn
+            .zrangebyscore_limit(
+                &self.key,
+                from.timestamp_millis(),
+                to.timestamp_millis(),
+                0,
+                limit as isize,
+            )
+            .await?;
+        Ok(Self::decode(members))
+    }
+
+    async fn latest(&self) -> AppResult<Option<MetricsSnapshot>> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let members: Vec<String> = conn.zrevrange(&self.key, 0, 0).await?;
+        Ok(Self::decode(members).into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot_at(timestamp: DateTime<Utc>, active: u64) -> MetricsSnapshot {
+        MetricsSnapshot {
+            timestamp,
+            total_connections: active,
+            active_connections: active,
+            total_messages_sent: 0,
+            total_messages_received: 0,
+            total_commands_processed: 0,
+            average_response_time_ms: 0.0,
+            error_rate: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_sink_history_window() {
+        let sink = InMemoryMetricsSink::new(3);
+        let now = Utc::now();
+        for i in 0..4 {
+            sink.store(&snapshot_at(now + chrono::Duration::minutes(i), i as u64))
+                .await
+                .unwrap();
+        }
+
+        // 容量为 3，最早的快照被淘汰
+        let all = sink
+            .history(
+                now - chrono::Duration::hours(1),
+                now + chrono::Duration::hours(1),
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].active_connections, 1);
+
+        let window = sink
+            .history(
+                now + chrono::Duration::minutes(2),
+                now + chrono::Duration::minutes(3),
+                1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(window.len(), 1);
+        assert_eq!(window[0].active_connections, 2);
+
+        assert_eq!(sink.latest().await.unwrap().unwrap().active_connections, 3);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_redis_sink_roundtrip() {
+        let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+        let key = format!("test:ws:metrics:{}", uuid::Uuid::new_v4());
+        let sink = RedisMetricsSink::new(client, Duration::from_secs(3600)).with_key(&key);
+        let now = Utc::now();
+
+        // 超出保留期的快照会被裁剪
+        sink.store(&snapshot_at(now - chrono::Duration::hours(2), 1))
+            .await
+            .unwrap();
+        sink.store(&snapshot_at(now, 2)).await.unwrap();
+
+        let history = sink
+            .history(now - chrono::Duration::hours(3), now, 10)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].active_connections, 2);
+        assert_eq!(sink.latest().await.unwrap(), Some(history[0].clone()));
+    }
+}
@@ -1,14 +1,21 @@
 // Legacy modules (kept for backward compatibility)
 pub mod auth;
+pub mod board_locks;
 pub mod commands;
+pub mod compression;
+pub mod entity_change;
 pub mod error_mapper;
+pub mod fanout;
 pub mod handler;
 pub mod manager;
+pub mod metrics_store;
 pub mod monitoring;
 pub mod rate_limiter;
 pub mod retry_timeout;
 pub mod security;
+pub mod shard;
 pub mod tests;
+pub mod topic;
 
 // New unified event system modules (temporarily commented out due to compilation issues)
 // pub mod batch_processor;
@@ -17,12 +24,16 @@ pub mod tests;
 
 // Re-export commonly used types for convenience
 pub use auth::{AuthenticatedUser, WebSocketAuth, WebSocketAuthError, WebSocketAuthQuery};
+pub use board_locks::{BoardLocks, IssueMoveLock};
 pub use commands::{
     WebSocketCommand, WebSocketCommandError, WebSocketCommandHandler, WebSocketCommandResponse,
 };
+pub use compression::{Compression, FrameCompressor};
+pub use entity_change::{EntityAction, EntityChanged, EntityKind};
 pub use error_mapper::{
     WebSocketError, WebSocketErrorCode, WebSocketErrorHandler, WebSocketErrorMapper,
 };
+pub use fanout::{DeliveryTarget, FanoutEnvelope, RedisFanout};
 
 // Legacy handler exports (backward compatibility)
 pub use handler::{
@@ -31,15 +42,17 @@ pub use handler::{
     WebSocketStats,
 };
 pub use manager::{ConnectedUser, MessageType, WebSocketManager, WebSocketMessage};
+pub use metrics_store::{InMemoryMetricsSink, MetricsSink, MetricsSnapshot, RedisMetricsSink};
 pub use monitoring::{
-    ConnectionQuality, HealthCheck, HealthStatus, MonitoringConfig, MonitoringData,
-    PerformanceMetrics, WebSocketMonitor,
+    CompressionStats, ConnectionQuality, FrameStats, HealthCheck, HealthStatus, MonitoringConfig,
+    MonitoringData, PerformanceMetrics, ShardStats, WebSocketMonitor,
 };
 pub use rate_limiter::{RateLimitConfig, RateLimitError, WebSocketRateLimiter};
 pub use retry_timeout::{
     ConnectionHealthChecker, RetryConfig, RetryTimeoutError, RetryTimeoutManager, TimeoutConfig,
 };
 pub use security::{MessageSigner, SecureMessage, SecureMessageBuilder, SecurityError};
+pub use topic::Topic;
 
 // New unified system exports (temporarily commented out due to compilation issues)
 // pub use batch_processor::{
@@ -58,8 +71,12 @@ pub use security::{MessageSigner, SecureMessage, SecureMessageBuilder, SecurityE
 // };
 
 use crate::db::DbPool;
+use crate::scheduler::{Schedule, ScheduledJob};
 use std::sync::Arc;
 
+/// How often the legacy state's cleanup jobs run by default
+const CLEANUP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
 // Temporarily commented out due to compilation issues
 // /// Unified WebSocket state for the new system
 // #[derive(Clone)]
@@ -79,9 +96,15 @@ use std::sync::Arc;
 //     }
 // }
 
-/// Legacy WebSocket state (backward compatibility)
-pub fn create_websocket_state(db: Arc<DbPool>, config: &crate::config::Config) -> WebSocketState {
-    let ws_manager = WebSocketManager::new();
+/// Legacy WebSocket state (backward compatibility); listeners are registered
+/// with `supervisor` and periodic cleanups with `scheduler`
+pub fn create_websocket_state(
+    db: Arc<DbPool>,
+    config: &crate::config::Config,
+    supervisor: &crate::supervisor::TaskSupervisor,
+    scheduler: &crate::scheduler::Scheduler,
+) -> WebSocketState {
+    let ws_manager = create_manager(config);
     let message_signer = Arc::new(MessageSigner::new(config));
     let asset_helper = Arc::new(crate::utils::AssetUrlHelper::new(&config.assets()));
     let command_handler = WebSocketCommandHandler::new(db.clone(), asset_helper)
@@ -90,15 +113,54 @@ pub fn create_websocket_state(db: Arc<DbPool>, config: &crate::config::Config) -
     let error_handler = WebSocketErrorHandler::new();
     let retry_timeout_manager =
         RetryTimeoutManager::new(RetryConfig::default(), TimeoutConfig::default());
-    let monitor = WebSocketMonitor::new(MonitoringConfig::default());
-
-    // 启动清理任务
-    tokio::spawn({
-        let signer = message_signer.clone();
-        async move {
-            signer.start_cleanup_task().await;
+    let monitor = create_monitor(config);
+    let token_revocations = match redis::Client::open(config.redis_url.clone()) {
+        Ok(client) => Some(crate::cache::TokenRevocationList::new(client)),
+        Err(e) => {
+            tracing::warn!(
+                "Invalid Redis URL, WebSocket token revocation checks disabled: {}",
+                e
+            );
+            None
         }
-    });
+    };
+
+    // 定期清理任务；只涉及本进程状态，每个实例各自运行
+    scheduler.register(ScheduledJob::new(
+        "ws_message_signer_cleanup",
+        Schedule::every(CLEANUP_INTERVAL),
+        {
+            let signer = message_signer.clone();
+            move || {
+                let signer = signer.clone();
+                async move {
+                    let removed = signer.cleanup_expired_cache().await;
+                    Ok(format!("removed {} message ids", removed))
+                }
+            }
+        },
+    ));
+    if let Some(fanout) = ws_manager.fanout().cloned() {
+        supervisor.spawn("ws_fanout_listener", {
+            let ws_manager = ws_manager.clone();
+            move || fanout.clone().run_listener(ws_manager.clone())
+        });
+    }
+    scheduler.register(ScheduledJob::new(
+        "ws_connection_cleanup",
+        Schedule::every(CLEANUP_INTERVAL),
+        {
+            let ws_manager = ws_manager.clone();
+            move || {
+                let ws_manager = ws_manager.clone();
+                async move {
+                    // Connections that haven't pinged in the last 10 minutes
+                    let removed = ws_manager.cleanup_stale_connections(10).await;
+                    Ok(format!("removed {} stale connections", removed))
+                }
+            }
+        },
+    ));
 
     WebSocketState {
         db,
@@ -109,9 +171,65 @@ pub fn create_websocket_state(db: Arc<DbPool>, config: &crate::config::Config) -
         retry_timeout_manager,
         monitor,
         message_signer: (*message_signer).clone(),
+        token_revocations,
     }
 }
 
+/// Build the manager; with `WS_FANOUT=redis` broadcasts also reach clients
+/// connected to other instances
+fn create_manager(config: &crate::config::Config) -> WebSocketManager {
+    let ws_manager =
+        WebSocketManager::new().with_compression_threshold(config.ws_compression_threshold_bytes);
+    if config.ws_fanout != "redis" {
+        return ws_manager;
+    }
+    match redis::Client::open(config.redis_url.clone()) {
+        Ok(client) => ws_manager.with_fanout(RedisFanout::new(client)),
+        Err(e) => {
+            tracing::warn!(
+                "Invalid Redis URL, WebSocket broadcasts stay on this instance: {}",
+                e
+            );
+            ws_manager
+        }
+    }
+}
+
+/// Build the monitor with the history sink selected by `WS_METRICS_SINK`
+fn create_monitor(config: &crate::config::Config) -> WebSocketMonitor {
+    let monitoring_config = MonitoringConfig::default();
+    let sink: Option<Arc<dyn MetricsSink>> = match config.ws_metrics_sink.as_str() {
+        "redis" => match redis::Client::open(config.redis_url.clone()) {
+            Ok(client) => Some(Arc::new(RedisMetricsSink::new(
+                client,
+                std::time::Duration::from_secs(config.ws_metrics_retention_hours * 3600),
+            ))),
+            Err(e) => {
+                tracing::warn!(
+                    "Invalid Redis URL for WebSocket metrics, using memory: {}",
+                    e
+                );
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let monitor = match sink {
+        Some(sink) => WebSocketMonitor::with_sink(monitoring_config, sink),
+        None => WebSocketMonitor::new(monitoring_config),
+    };
+
+    tokio::spawn({
+        let monitor = monitor.clone();
+        async move {
+            monitor.restore_from_sink().await;
+        }
+    });
+
+    monitor
+}
+
 // Temporarily commented out due to compilation issues
 // /// Create unified WebSocket state with new event system
 // pub async fn create_unified_websocket_state(
@@ -129,6 +247,11 @@ pub fn create_websocket_routes() -> axum::Router<WebSocketState> {
         .route("/ws", get(WebSocketHandler::websocket_handler))
         .route("/ws/online", get(WebSocketHandler::get_online_users))
         .route("/ws/stats", get(WebSocketHandler::get_websocket_stats))
+        .route(
+            "/ws/metrics/history",
+            get(WebSocketHandler::get_metrics_history),
+        )
+        .route("/ws/metrics/frames", get(WebSocketHandler::get_frame_stats))
         .route("/ws/send", post(WebSocketHandler::send_message_to_user))
         .route("/ws/broadcast", post(WebSocketHandler::broadcast_message))
         .route("/ws/cleanup", post(WebSocketHandler::cleanup_connections))
@@ -141,17 +264,4 @@ pub fn create_websocket_routes() -> axum::Router<WebSocketState> {
 //     axum::Router::new()
 // }
 
-/// Background task to periodically clean up stale connections (legacy)
-pub async fn start_connection_cleanup_task(ws_manager: WebSocketManager) {
-    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300)); // 5 minutes
-
-    loop {
-        interval.tick().await;
-        tracing::debug!("Running WebSocket connection cleanup");
-
-        // Clean up connections that haven't pinged in the last 10 minutes
-        ws_manager.cleanup_stale_connections(10).await;
-    }
-}
-
 // tests are in separate module `tests.rs`
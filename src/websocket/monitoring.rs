@@ -1,3 +1,4 @@
+use crate::utils::clock;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -6,6 +7,9 @@ use std::time::Duration;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::error::AppResult;
+use crate::websocket::metrics_store::{InMemoryMetricsSink, MetricsSink, MetricsSnapshot};
+
 /// 性能指标
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
@@ -57,6 +61,71 @@ pub struct MonitoringData {
     pub health_checks: Vec<HealthCheck>,
     pub error_summary: HashMap<String, u64>,
     pub resource_usage: ResourceUsage,
+    /// 出站帧统计，按总字节数降序
+    pub frame_stats: Vec<FrameStats>,
+    /// 出站帧压缩统计
+    pub compression: CompressionStats,
+}
+
+/// 按消息类型统计的出站帧大小和 JSON 序列化耗时
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameStats {
+    /// 消息分类，见 `WebSocketMessage::frame_kind`
+    pub kind: String,
+    pub frames: u64,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+    pub average_bytes: f64,
+    pub total_serialize_us: u64,
+    pub max_serialize_us: u64,
+    pub average_serialize_us: f64,
+}
+
+impl FrameStats {
+    fn record(&mut self, bytes: usize, serialize_time: Duration) {
+        let micros = serialize_time.as_micros() as u64;
+        self.frames += 1;
+        self.total_bytes += bytes as u64;
+        self.max_bytes = self.max_bytes.max(bytes as u64);
+        self.total_serialize_us += micros;
+        self.max_serialize_us = self.max_serialize_us.max(micros);
+        self.average_bytes = self.total_bytes as f64 / self.frames as f64;
+        self.average_serialize_us = self.total_serialize_us as f64 / self.frames as f64;
+    }
+}
+
+/// 协商了压缩的连接上超过阈值的出站帧的压缩效果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompressionStats {
+    /// 以压缩二进制帧发送的帧数
+    pub compressed_frames: u64,
+    /// 超过阈值但压缩后没有变小（或压缩失败）而按原文发送的帧数
+    pub skipped_frames: u64,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    /// 原始字节数 / 压缩后字节数，越大越好
+    pub compression_ratio: f64,
+    pub total_compress_us: u64,
+    pub average_compress_us: f64,
+}
+
+impl CompressionStats {
+    fn record(&mut self, original: usize, compressed: usize, compress_time: Duration) {
+        self.compressed_frames += 1;
+        self.original_bytes += original as u64;
+        self.compressed_bytes += compressed as u64;
+        self.total_compress_us += compress_time.as_micros() as u64;
+        self.compression_ratio = self.original_bytes as f64 / self.compressed_bytes.max(1) as f64;
+        self.average_compress_us = self.total_compress_us as f64 / self.compressed_frames as f64;
+    }
+}
+
+/// 连接分片统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardStats {
+    pub shard: usize,
+    pub connections: usize,
+    pub topics: usize,
 }
 
 /// 资源使用情况
@@ -81,6 +150,12 @@ pub struct WebSocketMonitor {
     error_summary: Arc<RwLock<HashMap<String, u64>>>,
     /// 响应时间记录
     response_times: Arc<RwLock<Vec<Duration>>>,
+    /// 按消息类型的出站帧统计
+    frame_stats: Arc<RwLock<HashMap<String, FrameStats>>>,
+    /// 出站帧压缩统计
+    compression_stats: Arc<RwLock<CompressionStats>>,
+    /// 历史快照存储
+    sink: Arc<dyn MetricsSink>,
     /// 监控配置
     config: MonitoringConfig,
 }
@@ -93,6 +168,10 @@ pub struct MonitoringConfig {
     pub metrics_collection_interval: Duration,
     pub connection_quality_threshold_ms: f64,
     pub error_rate_threshold: f64,
+    /// 写入历史存储的间隔
+    pub snapshot_interval: Duration,
+    /// 超过该字节数的出站帧记录警告日志，便于发现需要改为增量推送的消息
+    pub large_frame_bytes: usize,
 }
 
 impl Default for MonitoringConfig {
@@ -103,12 +182,21 @@ impl Default for MonitoringConfig {
             metrics_collection_interval: Duration::from_secs(10),
             connection_quality_threshold_ms: 100.0,
             error_rate_threshold: 0.05, // 5%
+            snapshot_interval: Duration::from_secs(60),
+            large_frame_bytes: 64 * 1024,
         }
     }
 }
 
 impl WebSocketMonitor {
     pub fn new(config: MonitoringConfig) -> Self {
+        // 默认只在内存中保留 24 小时的快照
+        let capacity = (86_400 / config.snapshot_interval.as_secs().max(1)) as usize;
+        Self::with_sink(config, Arc::new(InMemoryMetricsSink::new(capacity)))
+    }
+
+    /// 使用指定的历史存储创建监控器
+    pub fn with_sink(config: MonitoringConfig, sink: Arc<dyn MetricsSink>) -> Self {
         let monitor = Self {
             metrics: Arc::new(RwLock::new(PerformanceMetrics {
                 total_connections: 0,
@@ -118,12 +206,15 @@ impl WebSocketMonitor {
                 total_commands_processed: 0,
                 average_response_time_ms: 0.0,
                 error_rate: 0.0,
-                last_updated: Utc::now(),
+                last_updated: clock::now(),
             })),
             connection_quality: Arc::new(RwLock::new(HashMap::new())),
             health_checks: Arc::new(RwLock::new(Vec::new())),
             error_summary: Arc::new(RwLock::new(HashMap::new())),
             response_times: Arc::new(RwLock::new(Vec::new())),
+            frame_stats: Arc::new(RwLock::new(HashMap::new())),
+            compression_stats: Arc::new(RwLock::new(CompressionStats::default())),
+            sink,
             config,
         };
 
@@ -137,7 +228,7 @@ impl WebSocketMonitor {
         let mut metrics = self.metrics.write().unwrap();
         metrics.total_connections += 1;
         metrics.active_connections += 1;
-        metrics.last_updated = Utc::now();
+        metrics.last_updated = clock::now();
 
         // 初始化连接质量数据
         let mut quality_map = self.connection_quality.write().unwrap();
@@ -149,7 +240,7 @@ impl WebSocketMonitor {
                 connection_id: connection_id_clone.clone(),
                 latency_ms: 0.0,
                 packet_loss_rate: 0.0,
-                last_ping_time: Utc::now(),
+                last_ping_time: clock::now(),
                 connection_stability: 1.0,
                 bandwidth_usage: 0,
             },
@@ -165,7 +256,7 @@ impl WebSocketMonitor {
     pub async fn record_disconnection(&self, connection_id: &str) {
         let mut metrics = self.metrics.write().unwrap();
         metrics.active_connections = metrics.active_connections.saturating_sub(1);
-        metrics.last_updated = Utc::now();
+        metrics.last_updated = clock::now();
 
         let mut quality_map = self.connection_quality.write().unwrap();
         quality_map.remove(connection_id);
@@ -180,7 +271,7 @@ impl WebSocketMonitor {
     pub async fn record_message_sent(&self, connection_id: &str, message_size: usize) {
         let mut metrics = self.metrics.write().unwrap();
         metrics.total_messages_sent += 1;
-        metrics.last_updated = Utc::now();
+        metrics.last_updated = clock::now();
 
         // 更新带宽使用
         let mut quality_map = self.connection_quality.write().unwrap();
@@ -194,11 +285,74 @@ impl WebSocketMonitor {
         );
     }
 
+    /// 记录一个出站帧的大小和序列化耗时
+    pub fn record_frame(&self, kind: &str, bytes: usize, serialize_time: Duration) {
+        let mut frame_stats = self.frame_stats.write().unwrap();
+        frame_stats
+            .entry(kind.to_string())
+            .or_insert_with(|| FrameStats {
+                kind: kind.to_string(),
+                ..FrameStats::default()
+            })
+            .record(bytes, serialize_time);
+
+        if bytes >= self.config.large_frame_bytes {
+            warn!(
+                "Large WebSocket frame: kind={}, size={}, serialize_us={}",
+                kind,
+                bytes,
+                serialize_time.as_micros()
+            );
+        } else {
+            debug!(
+                "WebSocket frame: kind={}, size={}, serialize_us={}",
+                kind,
+                bytes,
+                serialize_time.as_micros()
+            );
+        }
+    }
+
+    /// 出站帧统计，按总字节数降序，最大的负载排在最前
+    pub fn get_frame_stats(&self) -> Vec<FrameStats> {
+        let mut stats: Vec<FrameStats> =
+            self.frame_stats.read().unwrap().values().cloned().collect();
+        stats.sort_by(|a, b| {
+            b.total_bytes
+                .cmp(&a.total_bytes)
+                .then_with(|| a.kind.cmp(&b.kind))
+        });
+        stats
+    }
+
+    /// 记录一个压缩后发送的出站帧
+    pub fn record_compression(&self, original: usize, compressed: usize, compress_time: Duration) {
+        self.compression_stats
+            .write()
+            .unwrap()
+            .record(original, compressed, compress_time);
+        debug!(
+            "WebSocket frame compressed: original={}, compressed={}, compress_us={}",
+            original,
+            compressed,
+            compress_time.as_micros()
+        );
+    }
+
+    /// 记录一个超过阈值但按原文发送的出站帧
+    pub fn record_compression_skipped(&self) {
+        self.compression_stats.write().unwrap().skipped_frames += 1;
+    }
+
+    pub fn get_compression_stats(&self) -> CompressionStats {
+        self.compression_stats.read().unwrap().clone()
+    }
+
     /// 记录消息接收
     pub async fn record_message_received(&self, connection_id: &str, message_size: usize) {
         let mut metrics = self.metrics.write().unwrap();
         metrics.total_messages_received += 1;
-        metrics.last_updated = Utc::now();
+        metrics.last_updated = clock::now();
 
         // 更新带宽使用
         let mut quality_map = self.connection_quality.write().unwrap();
@@ -216,7 +370,7 @@ impl WebSocketMonitor {
     pub async fn record_command_processed(&self, response_time: Duration, success: bool) {
         let mut metrics = self.metrics.write().unwrap();
         metrics.total_commands_processed += 1;
-        metrics.last_updated = Utc::now();
+        metrics.last_updated = clock::now();
 
         // 记录响应时间
         let mut response_times = self.response_times.write().unwrap();
@@ -263,7 +417,7 @@ impl WebSocketMonitor {
         if let Some(quality) = quality_map.get_mut(connection_id) {
             quality.latency_ms = latency_ms;
             quality.packet_loss_rate = packet_loss_rate;
-            quality.last_ping_time = Utc::now();
+            quality.last_ping_time = clock::now();
 
             // 计算连接稳定性（基于延迟和丢包率）
             let latency_score = if latency_ms <= self.config.connection_quality_threshold_ms {
@@ -314,6 +468,8 @@ impl WebSocketMonitor {
             health_checks,
             error_summary,
             resource_usage: self.get_resource_usage().await,
+            frame_stats: self.get_frame_stats(),
+            compression: self.get_compression_stats(),
         }
     }
 
@@ -339,7 +495,7 @@ impl WebSocketMonitor {
             health_checks.push(HealthCheck {
                 status: HealthStatus::Warning,
                 message: "High number of active connections".to_string(),
-                timestamp: Utc::now(),
+                timestamp: clock::now(),
                 details: HashMap::new(),
             });
         }
@@ -349,7 +505,7 @@ impl WebSocketMonitor {
             health_checks.push(HealthCheck {
                 status: HealthStatus::Critical,
                 message: format!("High error rate: {:.2}%", metrics.error_rate * 100.0),
-                timestamp: Utc::now(),
+                timestamp: clock::now(),
                 details: HashMap::new(),
             });
         }
@@ -362,7 +518,7 @@ impl WebSocketMonitor {
                     "High average response time: {:.2}ms",
                     metrics.average_response_time_ms
                 ),
-                timestamp: Utc::now(),
+                timestamp: clock::now(),
                 details: HashMap::new(),
             });
         }
@@ -378,7 +534,7 @@ impl WebSocketMonitor {
             health_checks.push(HealthCheck {
                 status: HealthStatus::Warning,
                 message: format!("{} connections with poor quality", poor_connections),
-                timestamp: Utc::now(),
+                timestamp: clock::now(),
                 details: HashMap::new(),
             });
         }
@@ -393,6 +549,42 @@ impl WebSocketMonitor {
         );
     }
 
+    /// 从历史存储恢复累计计数（活跃连接数不恢复）
+    pub async fn restore_from_sink(&self) {
+        match self.sink.latest().await {
+            Ok(Some(snapshot)) => {
+                let mut metrics = self.metrics.write().unwrap();
+                metrics.total_connections = snapshot.total_connections;
+                metrics.total_messages_sent = snapshot.total_messages_sent;
+                metrics.total_messages_received = snapshot.total_messages_received;
+                metrics.total_commands_processed = snapshot.total_commands_processed;
+                metrics.last_updated = clock::now();
+                info!(
+                    "Restored WebSocket metrics from snapshot at {}",
+                    snapshot.timestamp
+                );
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to restore WebSocket metrics: {}", e),
+        }
+    }
+
+    /// 将当前指标写入历史存储
+    pub async fn persist_snapshot(&self) -> AppResult<()> {
+        let snapshot = MetricsSnapshot::from(&*self.metrics.read().unwrap());
+        self.sink.store(&snapshot).await
+    }
+
+    /// 查询历史快照
+    pub async fn get_metrics_history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> AppResult<Vec<MetricsSnapshot>> {
+        self.sink.history(from, to, limit).await
+    }
+
     /// 启动后台监控任务
     fn start_background_tasks(&self) {
         let monitor = self.clone();
@@ -417,13 +609,28 @@ impl WebSocketMonitor {
                 monitor.collect_metrics().await;
             }
         });
+
+        // 快照持久化任务
+        let monitor = self.clone();
+        let config = self.config.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.snapshot_interval);
+            // 第一次 tick 立即返回，跳过以免在恢复前写入空快照
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = monitor.persist_snapshot().await {
+                    warn!("Failed to persist WebSocket metrics snapshot: {}", e);
+                }
+            }
+        });
     }
 
     /// 收集指标
     async fn collect_metrics(&self) {
         // 清理过期的连接质量数据
         let mut quality_map = self.connection_quality.write().unwrap();
-        let cutoff_time = Utc::now() - chrono::Duration::minutes(5);
+        let cutoff_time = clock::now() - chrono::Duration::minutes(5);
         quality_map.retain(|_, quality| quality.last_ping_time > cutoff_time);
 
         debug!("Metrics collection completed");
@@ -460,7 +667,7 @@ impl WebSocketMonitor {
             total_commands_processed: 0,
             average_response_time_ms: 0.0,
             error_rate: 0.0,
-            last_updated: Utc::now(),
+            last_updated: clock::now(),
         };
 
         let mut response_times = self.response_times.write().unwrap();
@@ -469,6 +676,9 @@ impl WebSocketMonitor {
         let mut error_summary = self.error_summary.write().unwrap();
         error_summary.clear();
 
+        self.frame_stats.write().unwrap().clear();
+        *self.compression_stats.write().unwrap() = CompressionStats::default();
+
         info!("Metrics reset completed");
     }
 }
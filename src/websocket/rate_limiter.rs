@@ -81,15 +81,14 @@ impl UserRequestRecord {
         }
 
         // 检查命令特定限制
-        if let Some(cmd_type) = command_type {
-            if let Some(command_limit) = config.command_limits.get(cmd_type) {
-                if let Some(command_times) = self.command_counts.get(cmd_type) {
-                    let recent_command_requests =
-                        command_times.iter().filter(|&&time| time > cutoff).count();
-                    if recent_command_requests >= *command_limit as usize {
-                        return true;
-                    }
-                }
+        if let Some(cmd_type) = command_type
+            && let Some(command_limit) = config.command_limits.get(cmd_type)
+            && let Some(command_times) = self.command_counts.get(cmd_type)
+        {
+            let recent_command_requests =
+                command_times.iter().filter(|&&time| time > cutoff).count();
+            if recent_command_requests >= *command_limit as usize {
+                return true;
             }
         }
 
@@ -1,5 +1,5 @@
 use crate::config::Config;
-use chrono::Utc;
+use crate::utils::clock;
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
@@ -51,9 +51,9 @@ impl MessageSigner {
 
     /// 对消息进行签名
     pub fn sign_message(&self, payload: &serde_json::Value, user_id: Uuid) -> SecureMessage {
-        let message_id = Uuid::new_v4().to_string();
-        let timestamp = Utc::now().timestamp();
-        let nonce = Uuid::new_v4().to_string();
+        let message_id = clock::new_id().to_string();
+        let timestamp = clock::now().timestamp();
+        let nonce = clock::new_id().to_string();
 
         // 创建签名数据
         let signature_data =
@@ -91,7 +91,7 @@ impl MessageSigner {
 
     /// 验证时间戳是否在允许的时间窗口内
     fn verify_timestamp(&self, timestamp: i64) -> Result<(), SecurityError> {
-        let now = Utc::now().timestamp();
+        let now = clock::now().timestamp();
         let time_diff = (now - timestamp).abs();
 
         if time_diff > self.time_window {
@@ -174,8 +174,8 @@ impl MessageSigner {
         processed.insert(message_id.to_string());
     }
 
-    /// 清理过期的消息ID缓存
-    pub async fn cleanup_expired_cache(&self) {
+    /// 清理过期的消息ID缓存，返回清理的数量；由调度器定期调用
+    pub async fn cleanup_expired_cache(&self) -> usize {
         // 这里可以添加基于时间戳的清理逻辑
         // 由于我们使用的是HashSet，这里暂时保留所有记录
         // 在实际生产环境中，可能需要实现基于时间戳的清理
@@ -187,22 +187,12 @@ impl MessageSigner {
                 .take(processed.len() / 2)
                 .cloned()
                 .collect();
-            for id in to_remove {
-                processed.remove(&id);
+            for id in &to_remove {
+                processed.remove(id);
             }
+            return to_remove.len();
         }
-    }
-
-    /// 启动定期清理任务
-    pub async fn start_cleanup_task(&self) {
-        let signer = self.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300)); // 5分钟清理一次
-            loop {
-                interval.tick().await;
-                signer.cleanup_expired_cache().await;
-            }
-        });
+        0
     }
 }
 
@@ -303,13 +293,79 @@ mod tests {
             server_host: "localhost".to_string(),
             server_port: 8000,
             cors_origins: vec!["*".to_string()],
+            cors_allow_credentials: false,
+            cors_max_age_secs: 3600,
+            app_env: "development".to_string(),
+            security_headers_enabled: true,
+            hsts_max_age_secs: None,
+            hsts_include_subdomains: false,
+            referrer_policy: "strict-origin-when-cross-origin".to_string(),
+            content_security_policy: "default-src 'self'".to_string(),
             jwt_secret: "test-secret-key-for-signing".to_string(),
             jwt_access_token_expires_in: 3600,
             jwt_refresh_token_expires_in: 604800,
             log_level: "info".to_string(),
             log_format: "json".to_string(),
             assets_url: "http://localhost:8000/assets".to_string(),
+            assets_region_urls: vec![],
+            assets_region_fallbacks: vec![],
+            assets_region_probe_secs: 30,
             bcrypt_cost: 4,
+            admin_emails: vec![],
+            metrics_token: None,
+            ws_metrics_sink: "memory".to_string(),
+            ws_metrics_retention_hours: 168,
+            ws_fanout: "none".to_string(),
+            ws_compression_threshold_bytes: 8 * 1024,
+            notification_batch_windows: vec![],
+            notification_email_windows: vec![],
+            email_reply_domain: None,
+            inbound_email_secret: None,
+            storage_endpoint: None,
+            storage_bucket: None,
+            storage_region: "us-east-1".to_string(),
+            storage_access_key: None,
+            storage_secret_key: None,
+            storage_path_style: true,
+            attachment_max_bytes: 25 * 1024 * 1024,
+            upload_session_max_bytes: 5 * 1024 * 1024 * 1024,
+            upload_session_ttl_secs: 86400,
+            rate_limit_enabled: true,
+            rate_limit_window_secs: 60,
+            rate_limit_per_user: 600,
+            rate_limit_per_ip: 300,
+            rate_limit_trust_proxy: false,
+            oauth_redirect_base_url: "http://localhost:8000".to_string(),
+            oauth_google_client_id: None,
+            oauth_google_client_secret: None,
+            oauth_github_client_id: None,
+            oauth_github_client_secret: None,
+            email_backend: "log".to_string(),
+            email_from: "Momentum <no-reply@localhost>".to_string(),
+            app_url: "http://localhost:3000".to_string(),
+            smtp_host: None,
+            smtp_port: 587,
+            smtp_username: None,
+            smtp_password: None,
+            smtp_implicit_tls: false,
+            email_domain_dns_url: "https://cloudflare-dns.com/dns-query".to_string(),
+            ses_region: "us-east-1".to_string(),
+            ses_access_key: None,
+            ses_secret_key: None,
+            push_vapid_subject: None,
+            push_vapid_public_key: None,
+            push_vapid_private_key: None,
+            apns_key_id: None,
+            apns_team_id: None,
+            apns_private_key: None,
+            apns_topic: None,
+            apns_sandbox: false,
+            fcm_project_id: None,
+            fcm_client_email: None,
+            fcm_private_key: None,
+            scheduler_jobs: String::new(),
+            telemetry_enabled: false,
+            telemetry_endpoint: None,
         }
     }
 
@@ -360,7 +416,7 @@ mod tests {
 
         // 创建一个过期的消息（时间戳设置为很久以前）
         let mut expired_message = signer.sign_message(&payload, user_id);
-        expired_message.timestamp = Utc::now().timestamp() - 1000; // 1000秒前
+        expired_message.timestamp = chrono::Utc::now().timestamp() - 1000; // 1000秒前
 
         let result = signer.verify_message(&expired_message).await;
         assert!(matches!(result, Err(SecurityError::MessageExpired { .. })));
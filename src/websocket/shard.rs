@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// 默认分片数量
+pub const DEFAULT_SHARD_COUNT: usize = 32;
+
+/// 按 key 哈希分片的并发 HashMap
+///
+/// 每个分片有独立的读写锁，不同 key 的写操作大多落在不同分片上，
+/// 避免单一 `RwLock<HashMap>` 在大量连接时成为竞争点。
+/// 调用方不应同时持有两个分片的锁。
+pub struct ShardedMap<K, V> {
+    shards: Arc<[RwLock<HashMap<K, V>>]>,
+}
+
+impl<K, V> Clone for ShardedMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            shards: self.shards.clone(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> ShardedMap<K, V> {
+    pub fn new(shard_count: usize) -> Self {
+        let shard_count = shard_count.max(1);
+        let shards: Vec<_> = (0..shard_count)
+            .map(|_| RwLock::new(HashMap::new()))
+            .collect();
+        Self {
+            shards: shards.into(),
+        }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// 计算 key 所在分片的下标
+    pub fn shard_index<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) % self.shards.len()
+    }
+
+    /// 获取 key 所在的分片
+    pub fn shard<Q: Hash + ?Sized>(&self, key: &Q) -> &RwLock<HashMap<K, V>> {
+        &self.shards[self.shard_index(key)]
+    }
+
+    /// 遍历所有分片
+    pub fn shards(&self) -> impl Iterator<Item = &RwLock<HashMap<K, V>>> {
+        self.shards.iter()
+    }
+
+    /// 所有分片的元素总数
+    pub async fn len(&self) -> usize {
+        let mut total = 0;
+        for shard in self.shards.iter() {
+            total += shard.read().await.len();
+        }
+        total
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// 每个分片的元素数量
+    pub async fn shard_lens(&self) -> Vec<usize> {
+        let mut lens = Vec::with_capacity(self.shards.len());
+        for shard in self.shards.iter() {
+            lens.push(shard.read().await.len());
+        }
+        lens
+    }
+}
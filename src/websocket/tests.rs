@@ -467,4 +467,333 @@ mod websocket_tests {
         assert_eq!(deserialized.id, Some("test-id".to_string()));
         assert_eq!(deserialized.message_type, MessageType::Command);
     }
+
+    /// 测试分片映射按 key 稳定分布且统计正确
+    #[tokio::test]
+    async fn test_sharded_map_distribution() {
+        use crate::websocket::shard::ShardedMap;
+
+        let map: ShardedMap<String, usize> = ShardedMap::new(8);
+        for i in 0..100 {
+            let key = format!("conn_{}", i);
+            map.shard(&key).write().await.insert(key.clone(), i);
+        }
+
+        assert_eq!(map.len().await, 100);
+        assert_eq!(map.shard_lens().await.iter().sum::<usize>(), 100);
+        assert_eq!(map.shard_index("conn_42"), map.shard_index("conn_42"));
+        assert_eq!(
+            map.shard("conn_42").read().await.get("conn_42").copied(),
+            Some(42)
+        );
+    }
+
+    /// 测试分片管理器的连接数与分片统计
+    #[tokio::test]
+    async fn test_sharded_manager_shard_stats() {
+        use crate::websocket::manager::{ConnectedUser, ConnectionState, WebSocketManager};
+        use std::collections::{HashMap, HashSet, VecDeque};
+
+        let manager = WebSocketManager::with_shards(4);
+        for i in 0..20 {
+            let user = ConnectedUser {
+                user_id: Uuid::new_v4(),
+                username: format!("user_{}", i),
+                connected_at: chrono::Utc::now(),
+                last_ping: chrono::Utc::now(),
+                state: ConnectionState::Connected,
+                subscriptions: HashSet::from(["workspace".to_string()]),
+                message_queue: VecDeque::new(),
+                recovery_token: None,
+                metadata: HashMap::new(),
+                current_workspace_id: None,
+            };
+            manager
+                .add_connection(format!("conn_{}", i), user, None, None)
+                .await;
+        }
+
+        let stats = manager.get_shard_stats().await;
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats.iter().map(|s| s.connections).sum::<usize>(), 20);
+        assert_eq!(stats.iter().map(|s| s.topics).sum::<usize>(), 1);
+
+        manager.remove_connection("conn_0").await;
+        assert_eq!(manager.get_connection_count().await, 19);
+    }
+
+    /// 测试主题键与主题互相转换，通配符不是主题
+    #[test]
+    fn test_topic_parse_round_trip() {
+        use crate::websocket::Topic;
+
+        let issue = Topic::Issue(Uuid::new_v4());
+        assert_eq!(Topic::parse(&issue.key()), Some(issue));
+        assert_eq!(Topic::parse("workspace"), Some(Topic::Workspace));
+        assert_eq!(Topic::parse("issue:*"), None);
+        assert_eq!(Topic::parse("cycle:1"), None);
+    }
+
+    /// 测试跨实例消息忽略本实例发布的和格式错误的消息
+    #[test]
+    fn test_fanout_envelope_origin_filtering() {
+        use crate::websocket::{DeliveryTarget, MessageType, RedisFanout, WebSocketMessage};
+
+        let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+        let local = RedisFanout::new(client.clone());
+        let remote = RedisFanout::new(client);
+        assert_ne!(local.instance_id(), remote.instance_id());
+
+        let message = WebSocketMessage {
+            id: Some("msg-1".to_string()),
+            message_type: MessageType::Notification,
+            data: serde_json::json!({ "title": "Assigned" }),
+            timestamp: Some(chrono::Utc::now()),
+        };
+        let target = DeliveryTarget::User {
+            user_id: Uuid::new_v4(),
+        };
+        let payload =
+            serde_json::to_string(&remote.envelope(target.clone(), message.clone())).unwrap();
+
+        let accepted = local.accept(&payload).unwrap();
+        assert_eq!(accepted.target, target);
+        assert_eq!(accepted.message.id.as_deref(), Some("msg-1"));
+        assert!(remote.accept(&payload).is_none());
+        assert!(local.accept("not json").is_none());
+    }
+
+    /// 测试本机投递只发给当前工作区一致且订阅了主题的连接
+    #[tokio::test]
+    async fn test_deliver_local_topic_targets_workspace_subscribers() {
+        use crate::websocket::manager::{ConnectedUser, ConnectionState, WebSocketManager};
+        use crate::websocket::{DeliveryTarget, MessageType, Topic, WebSocketMessage};
+        use std::collections::{HashMap, HashSet, VecDeque};
+
+        let manager = WebSocketManager::new();
+        let workspace_id = Uuid::new_v4();
+        let issue = Topic::Issue(Uuid::new_v4());
+        for (connection_id, workspace, topic) in [
+            ("same_ws", workspace_id, issue.key()),
+            ("other_ws", Uuid::new_v4(), issue.key()),
+            ("unsubscribed", workspace_id, "project:*".to_string()),
+        ] {
+            let user = ConnectedUser {
+                user_id: Uuid::new_v4(),
+                username: connection_id.to_string(),
+                connected_at: chrono::Utc::now(),
+                last_ping: chrono::Utc::now(),
+                state: ConnectionState::Connected,
+                subscriptions: HashSet::new(),
+                message_queue: VecDeque::new(),
+                recovery_token: None,
+                metadata: HashMap::new(),
+                current_workspace_id: Some(workspace),
+            };
+            manager
+                .add_connection(connection_id.to_string(), user, None, None)
+                .await;
+            manager.subscribe_connection(connection_id, topic).await;
+        }
+
+        let mut routed = manager.get_routed_receiver();
+        let message = WebSocketMessage {
+            id: None,
+            message_type: MessageType::TopicEvent,
+            data: serde_json::json!({ "event": "issue.updated" }),
+            timestamp: None,
+        };
+        let target = DeliveryTarget::Topic {
+            workspace_id,
+            topic: issue.key(),
+        };
+        assert_eq!(manager.deliver_local(&target, message).await, 1);
+        let delivered = routed.recv().await.unwrap();
+        assert_eq!(
+            *delivered.connection_ids,
+            HashSet::from(["same_ws".to_string()])
+        );
+    }
+
+    /// 测试断开后凭恢复令牌恢复订阅并重放断开期间的消息，令牌只能使用一次
+    #[tokio::test]
+    async fn test_reconnect_restores_subscriptions_and_replays_queue() {
+        use crate::websocket::manager::{ConnectedUser, ConnectionState, WebSocketManager};
+        use crate::websocket::{DeliveryTarget, MessageType, Topic, WebSocketMessage};
+        use std::collections::{HashMap, HashSet, VecDeque};
+
+        let manager = WebSocketManager::new();
+        let (user_id, workspace_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let issue = Topic::Issue(Uuid::new_v4());
+        let user = ConnectedUser {
+            user_id,
+            username: "reconnecting".to_string(),
+            connected_at: chrono::Utc::now(),
+            last_ping: chrono::Utc::now(),
+            state: ConnectionState::Connected,
+            subscriptions: HashSet::new(),
+            message_queue: VecDeque::new(),
+            recovery_token: None,
+            metadata: HashMap::new(),
+            current_workspace_id: Some(workspace_id),
+        };
+
+        let token = manager
+            .add_connection("old".to_string(), user.clone(), None, None)
+            .await;
+        manager.subscribe_connection("old", issue.key()).await;
+        manager.remove_connection("old").await;
+
+        let event = WebSocketMessage {
+            id: Some("missed".to_string()),
+            message_type: MessageType::TopicEvent,
+            data: serde_json::json!({ "event": "issue.updated" }),
+            timestamp: None,
+        };
+        let target = DeliveryTarget::Topic {
+            workspace_id,
+            topic: issue.key(),
+        };
+        assert_eq!(manager.deliver_local(&target, event).await, 0);
+
+        let new_token = manager
+            .add_connection("new".to_string(), user, None, None)
+            .await;
+        assert_ne!(new_token, token);
+
+        let messages = manager.reconnect_messages("new", user_id, &token).await;
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].message_type, MessageType::SystemMessage);
+        assert_eq!(messages[0].data["replayed_messages"], 1);
+        assert_eq!(messages[1].id.as_deref(), Some("missed"));
+        assert_eq!(
+            manager.get_connection("new").await.unwrap().subscriptions,
+            HashSet::from([issue.key()])
+        );
+
+        let reused = manager.reconnect_messages("new", user_id, &token).await;
+        assert_eq!(reused.len(), 1);
+        assert_eq!(reused[0].message_type, MessageType::Error);
+    }
+
+    /// 测试恢复令牌不能被其他用户使用
+    #[tokio::test]
+    async fn test_reconnect_rejects_other_users_token() {
+        use crate::websocket::manager::{ConnectedUser, ConnectionState, WebSocketManager};
+        use std::collections::{HashMap, HashSet, VecDeque};
+
+        let manager = WebSocketManager::new();
+        let owner = Uuid::new_v4();
+        let user = ConnectedUser {
+            user_id: owner,
+            username: "owner".to_string(),
+            connected_at: chrono::Utc::now(),
+            last_ping: chrono::Utc::now(),
+            state: ConnectionState::Connected,
+            subscriptions: HashSet::from(["workspace".to_string()]),
+            message_queue: VecDeque::new(),
+            recovery_token: None,
+            metadata: HashMap::new(),
+            current_workspace_id: None,
+        };
+        let token = manager
+            .add_connection("conn".to_string(), user.clone(), None, None)
+            .await;
+        manager.remove_connection("conn").await;
+        manager
+            .add_connection("other".to_string(), user, None, None)
+            .await;
+
+        assert!(
+            manager
+                .recover_connection("other", Uuid::new_v4(), &token)
+                .await
+                .is_none()
+        );
+        assert!(
+            manager
+                .recover_connection("other", owner, &token)
+                .await
+                .is_some()
+        );
+    }
+
+    /// 测试实体变更消息的结构：删除时不携带实体内容
+    #[test]
+    fn test_entity_changed_envelope() {
+        use crate::websocket::{EntityAction, EntityChanged, EntityKind, MessageType};
+
+        let id = Uuid::new_v4();
+        let label = serde_json::json!({ "id": id, "name": "Bug", "color": "#FF0000" });
+        let updated =
+            EntityChanged::new(EntityKind::Label, EntityAction::Updated, id, &label).unwrap();
+        let message = updated.to_message();
+        assert_eq!(message.message_type, MessageType::EntityChanged);
+        assert_eq!(message.frame_kind(), "entity_changed:label");
+        assert_eq!(
+            message.data,
+            serde_json::json!({
+                "entity": "label",
+                "action": "updated",
+                "id": id,
+                "payload": label,
+            })
+        );
+
+        let deleted =
+            EntityChanged::new(EntityKind::WorkflowState, EntityAction::Deleted, id, &label)
+                .unwrap();
+        assert_eq!(deleted.to_message().data["entity"], "workflow_state");
+        assert!(deleted.payload.is_null());
+    }
+
+    /// 测试实体变更只推送给实体所属工作区的连接
+    #[tokio::test]
+    async fn test_entity_changed_targets_workspace() {
+        use crate::websocket::manager::{ConnectedUser, ConnectionState, WebSocketManager};
+        use crate::websocket::{EntityAction, EntityChanged, EntityKind, MessageType};
+        use std::collections::{HashMap, HashSet, VecDeque};
+
+        let manager = WebSocketManager::new();
+        let workspace_id = Uuid::new_v4();
+        for (connection_id, workspace) in [
+            ("same_ws", Some(workspace_id)),
+            ("other_ws", Some(Uuid::new_v4())),
+            ("no_ws", None),
+        ] {
+            let user = ConnectedUser {
+                user_id: Uuid::new_v4(),
+                username: connection_id.to_string(),
+                connected_at: chrono::Utc::now(),
+                last_ping: chrono::Utc::now(),
+                state: ConnectionState::Connected,
+                subscriptions: HashSet::new(),
+                message_queue: VecDeque::new(),
+                recovery_token: None,
+                metadata: HashMap::new(),
+                current_workspace_id: workspace,
+            };
+            manager
+                .add_connection(connection_id.to_string(), user, None, None)
+                .await;
+        }
+
+        let mut routed = manager.get_routed_receiver();
+        let change = EntityChanged::new(
+            EntityKind::Issue,
+            EntityAction::Deleted,
+            Uuid::new_v4(),
+            &(),
+        )
+        .unwrap();
+        manager.entity_changed(workspace_id, &change).await;
+
+        let delivered = routed.recv().await.unwrap();
+        assert_eq!(
+            *delivered.connection_ids,
+            HashSet::from(["same_ws".to_string()])
+        );
+        assert_eq!(delivered.message.message_type, MessageType::EntityChanged);
+        assert_eq!(delivered.message.data["action"], "deleted");
+    }
 }
@@ -0,0 +1,87 @@
+use uuid::Uuid;
+
+/// 可订阅的主题
+///
+/// 订阅字符串的格式：
+/// - `workspace`：当前工作区的所有主题事件
+/// - `issue:{id}` / `project:{id}` / `team:{id}`：单个实体的事件
+/// - `issue:*` / `project:*` / `team:*`：某类实体的全部事件
+///
+/// 主题事件只投递给当前工作区与事件所属工作区一致的连接。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Workspace,
+    Issue(Uuid),
+    Project(Uuid),
+    Team(Uuid),
+}
+
+pub const WORKSPACE_TOPIC: &str = "workspace";
+
+const ENTITY_KINDS: [&str; 3] = ["issue", "project", "team"];
+
+impl Topic {
+    /// 订阅表中的主题键，例如 `issue:5f0c...`
+    pub fn key(&self) -> String {
+        match self {
+            Topic::Workspace => WORKSPACE_TOPIC.to_string(),
+            Topic::Issue(id) => format!("issue:{}", id),
+            Topic::Project(id) => format!("project:{}", id),
+            Topic::Team(id) => format!("team:{}", id),
+        }
+    }
+
+    /// 从主题键还原主题，与 `key` 互逆；通配符不是主题，返回 `None`
+    pub fn parse(key: &str) -> Option<Topic> {
+        if key == WORKSPACE_TOPIC {
+            return Some(Topic::Workspace);
+        }
+        let (kind, id) = key.split_once(':')?;
+        let id: Uuid = id.parse().ok()?;
+        match kind {
+            "issue" => Some(Topic::Issue(id)),
+            "project" => Some(Topic::Project(id)),
+            "team" => Some(Topic::Team(id)),
+            _ => None,
+        }
+    }
+
+    /// 所有会收到该主题事件的订阅键（精确、通配符以及 `workspace`）
+    pub fn matching_keys(&self) -> Vec<String> {
+        let wildcard = match self {
+            Topic::Workspace => return vec![WORKSPACE_TOPIC.to_string()],
+            Topic::Issue(_) => "issue:*",
+            Topic::Project(_) => "project:*",
+            Topic::Team(_) => "team:*",
+        };
+        vec![
+            self.key(),
+            wildcard.to_string(),
+            WORKSPACE_TOPIC.to_string(),
+        ]
+    }
+
+    /// 校验并规范化订阅字符串；无效时返回 `None`
+    pub fn normalize_subscription(subscription: &str) -> Option<String> {
+        let subscription = subscription.trim();
+        if subscription.eq_ignore_ascii_case(WORKSPACE_TOPIC) {
+            return Some(WORKSPACE_TOPIC.to_string());
+        }
+        let (kind, id) = subscription.split_once(':')?;
+        let kind = kind.to_ascii_lowercase();
+        if !ENTITY_KINDS.contains(&kind.as_str()) {
+            return None;
+        }
+        if id == "*" {
+            return Some(format!("{}:*", kind));
+        }
+        let id: Uuid = id.parse().ok()?;
+        Some(format!("{}:{}", kind, id))
+    }
+}
+
+impl std::fmt::Display for Topic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.key())
+    }
+}
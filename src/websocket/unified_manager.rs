@@ -2,6 +2,7 @@
 //!
 //! 集成新的事件系统，提供统一的WebSocket连接管理、事件处理和消息分发功能
 
+use crate::utils::clock;
 use axum::extract::ws::{Message, WebSocket};
 use base64::Engine;
 use futures_util::{SinkExt, StreamExt};
@@ -257,7 +258,7 @@ impl UnifiedWebSocketManager {
                 // 更新发送统计
                 let mut stats = stats_clone.write().await;
                 stats.total_messages_sent += 1;
-                stats.last_updated = chrono::Utc::now();
+                stats.last_updated = clock::now();
             }
         });
 
@@ -343,7 +344,7 @@ impl UnifiedWebSocketManager {
         {
             let mut stats = self.stats.write().await;
             stats.total_messages_received += 1;
-            stats.last_updated = chrono::Utc::now();
+            stats.last_updated = clock::now();
         }
 
         // 尝试解析为事件
@@ -510,7 +511,7 @@ impl UnifiedWebSocketManager {
         result: &EventResult,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let broadcast_message = BroadcastMessage {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             message_type: BroadcastType::EventUpdate,
             content: serde_json::to_value(result)?,
             target: if event.broadcast_targets().is_empty() {
@@ -518,7 +519,7 @@ impl UnifiedWebSocketManager {
             } else {
                 BroadcastTarget::Users(event.broadcast_targets())
             },
-            created_at: chrono::Utc::now(),
+            created_at: clock::now(),
             metadata: HashMap::new(),
         };
 
@@ -541,8 +542,8 @@ impl UnifiedWebSocketManager {
         let connection = Arc::new(Connection {
             id: connection_id,
             user,
-            connected_at: chrono::Utc::now(),
-            last_activity: chrono::Utc::now(),
+            connected_at: clock::now(),
+            last_activity: clock::now(),
             state: ConnectionState::Connected,
             subscriptions: HashSet::new(),
             message_queue: Arc::new(RwLock::new(VecDeque::new())),
@@ -602,7 +603,7 @@ impl UnifiedWebSocketManager {
                     .or_insert(0) += 1;
             }
 
-            stats.last_updated = chrono::Utc::now();
+            stats.last_updated = clock::now();
         }
 
         info!("Added connection {} for user {}", connection_id, user_id);
@@ -660,7 +661,7 @@ impl UnifiedWebSocketManager {
                 }
 
                 // 计算连接持续时间
-                let duration = (chrono::Utc::now() - connection.connected_at).num_seconds() as f64;
+                let duration = (clock::now() - connection.connected_at).num_seconds() as f64;
                 let current_avg = stats.average_connection_duration_seconds;
                 let total_completed = stats.total_connections - stats.active_connections as u64;
 
@@ -670,7 +671,7 @@ impl UnifiedWebSocketManager {
                             / total_completed as f64;
                 }
 
-                stats.last_updated = chrono::Utc::now();
+                stats.last_updated = clock::now();
             }
 
             info!("Removed connection {} for user {}", connection_id, user_id);
@@ -804,7 +805,7 @@ impl UnifiedWebSocketManager {
         if !success {
             stats.failed_events += 1;
         }
-        stats.last_updated = chrono::Utc::now();
+        stats.last_updated = clock::now();
     }
 
     /// 启动后台任务
@@ -831,7 +832,7 @@ impl UnifiedWebSocketManager {
             loop {
                 interval.tick().await;
                 let _timeout = Duration::from_secs(config.connection_timeout_seconds);
-                let cutoff_time = chrono::Utc::now()
+                let cutoff_time = clock::now()
                     - chrono::Duration::seconds(config.connection_timeout_seconds as i64);
 
                 let mut stale_connections = Vec::new();
@@ -894,7 +895,7 @@ impl UnifiedWebSocketManager {
                     while let Some(queued_msg) = queue.front() {
                         // 检查消息是否过期
                         if let Some(expires_at) = queued_msg.expires_at {
-                            if chrono::Utc::now() > expires_at {
+                            if clock::now() > expires_at {
                                 queue.pop_front();
                                 continue;
                             }
@@ -938,6 +939,33 @@ impl UnifiedWebSocketManager {
         user_connections.keys().cloned().collect()
     }
 
+    /// 获取连接的实际状态，供 `get_connection_info` 命令返回
+    pub async fn get_connection_info(
+        &self,
+        connection_id: &str,
+    ) -> Option<crate::websocket::commands::ConnectionInfo> {
+        let connection = self.get_connection(connection_id).await?;
+        let mut subscriptions: Vec<String> = connection.subscriptions.iter().cloned().collect();
+        subscriptions.sort();
+        let state = match connection.state {
+            ConnectionState::Connected => "connected",
+            ConnectionState::Disconnected => "disconnected",
+            ConnectionState::Suspended => "suspended",
+            ConnectionState::Reconnecting => "reconnecting",
+        };
+        Some(crate::websocket::commands::ConnectionInfo {
+            connection_id: connection.id.clone(),
+            user_id: connection.user.user_id,
+            username: connection.user.username.clone(),
+            connected_at: connection.connected_at,
+            last_ping: connection.last_activity,
+            subscriptions,
+            message_queue_size: connection.message_queue.read().await.len(),
+            state: state.to_string(),
+            current_workspace_id: connection.user.current_workspace_id,
+        })
+    }
+
     /// 获取连接总数
     pub async fn get_connection_count(&self) -> usize {
         let connections = self.connections.read().await;
@@ -983,11 +1011,11 @@ impl UnifiedWebSocketManager {
         message: serde_json::Value,
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
         let broadcast_message = BroadcastMessage {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             message_type: BroadcastType::UserMessage,
             content: message,
             target: BroadcastTarget::User(user_id),
-            created_at: chrono::Utc::now(),
+            created_at: clock::now(),
             metadata: HashMap::new(),
         };
 
@@ -1001,11 +1029,11 @@ impl UnifiedWebSocketManager {
         message: serde_json::Value,
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
         let broadcast_message = BroadcastMessage {
-            id: Uuid::new_v4().to_string(),
+            id: clock::new_id().to_string(),
             message_type: BroadcastType::UserMessage,
             content: message,
             target: BroadcastTarget::Workspace(workspace_id),
-            created_at: chrono::Utc::now(),
+            created_at: clock::now(),
             metadata: HashMap::new(),
         };
 
@@ -1046,7 +1074,7 @@ impl UnifiedWebSocketManager {
                 "average_connection_duration": stats.average_connection_duration_seconds,
                 "average_event_processing_time": metrics.average_processing_time_ms
             },
-            "timestamp": chrono::Utc::now()
+            "timestamp": clock::now()
         })
     }
 }
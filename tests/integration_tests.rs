@@ -388,10 +388,10 @@ mod performance_tests {
 
         let mut successful_connections = 0;
         for handle in handles {
-            if let Ok(success) = handle.await {
-                if success {
-                    successful_connections += 1;
-                }
+            if let Ok(success) = handle.await
+                && success
+            {
+                successful_connections += 1;
             }
         }
 
@@ -504,6 +504,7 @@ mod user_tests {
             name: "Test User".to_string(),
             avatar_url: None,
             current_workspace_id: Some(workspace_id),
+            asset_region: None,
             workspaces: vec![mock_workspace],
             teams: vec![mock_team],
         };
@@ -0,0 +1,82 @@
+// Workspace API key tests
+
+use chrono::{Duration, Utc};
+use rust_backend::db::models::api_key::{ApiKey, ApiKeyRecord};
+use rust_backend::db::models::oauth_app::oauth_scopes;
+use rust_backend::services::api_keys_service::ApiKeysService;
+use rust_backend::services::api_tokens_service::ApiTokensService;
+use rust_backend::services::context::AuthChannel;
+use rust_backend::services::oauth_service::OAuthService;
+use uuid::Uuid;
+
+fn record(scopes: &str) -> ApiKeyRecord {
+    let now = Utc::now();
+    ApiKeyRecord {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        name: "CI".to_string(),
+        key_prefix: "mk_01234567".to_string(),
+        key_hash: ApiKeysService::hash_key("mk_secret"),
+        scopes: scopes.to_string(),
+        tier: "free".to_string(),
+        created_by: Uuid::new_v4(),
+        last_used_at: None,
+        expires_at: None,
+        revoked_at: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[test]
+fn api_key_detection_and_hashing() {
+    assert!(ApiKeysService::is_api_key("mk_abc"));
+    assert!(!ApiKeysService::is_api_key("mtm_abc"));
+    // Each bearer prefix belongs to exactly one kind of credential
+    assert!(!ApiTokensService::is_api_token("mk_abc"));
+    assert!(!OAuthService::is_access_token("mk_abc"));
+
+    let hash = ApiKeysService::hash_key("mk_abc");
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, ApiKeysService::hash_key("mk_abc"));
+    assert_ne!(hash, ApiKeysService::hash_key("mk_abd"));
+}
+
+#[test]
+fn api_key_scopes_are_validated_and_deduplicated() {
+    let scopes = ApiKeysService::validate_scopes(&[
+        oauth_scopes::READ_ISSUES.to_string(),
+        oauth_scopes::WRITE_ISSUES.to_string(),
+        oauth_scopes::READ_ISSUES.to_string(),
+    ])
+    .unwrap();
+    assert_eq!(scopes, vec!["read:issues", "write:issues"]);
+
+    assert!(ApiKeysService::validate_scopes(&[]).is_err());
+    assert!(ApiKeysService::validate_scopes(&["admin:everything".to_string()]).is_err());
+}
+
+#[test]
+fn api_key_activity_and_response_shape() {
+    let now = Utc::now();
+    let mut key = record("read:issues write:comments");
+    assert!(key.is_active(now));
+
+    key.expires_at = Some(now - Duration::minutes(1));
+    assert!(!key.is_active(now));
+    key.expires_at = Some(now + Duration::days(1));
+    key.revoked_at = Some(now);
+    assert!(!key.is_active(now));
+
+    let api_key = ApiKey::from(key);
+    assert_eq!(api_key.scopes, vec!["read:issues", "write:comments"]);
+    let json = serde_json::to_value(&api_key).unwrap();
+    assert!(json.get("key_hash").is_none());
+}
+
+#[test]
+fn api_key_channel_is_restrictable() {
+    assert_eq!(AuthChannel::parse("api_key"), Some(AuthChannel::ApiKey));
+    assert!(AuthChannel::ApiKey.is_api());
+    assert!(AuthChannel::API.contains(&AuthChannel::ApiKey));
+}
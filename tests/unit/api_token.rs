@@ -0,0 +1,131 @@
+// Personal access token and API usage rollup tests
+
+use chrono::NaiveDate;
+use rust_backend::db::models::api_token::{ApiUsageDaily, api_client_types, api_tiers};
+use rust_backend::services::api_tokens_service::ApiTokensService;
+use uuid::Uuid;
+
+fn row(day: NaiveDate, endpoint: &str, requests: i64, errors: i64) -> ApiUsageDaily {
+    ApiUsageDaily {
+        client_type: api_client_types::PERSONAL_TOKEN.to_string(),
+        client_id: Uuid::nil(),
+        day,
+        endpoint: endpoint.to_string(),
+        request_count: requests,
+        error_count: errors,
+    }
+}
+
+#[test]
+fn api_token_detection_and_hashing() {
+    assert!(ApiTokensService::is_api_token("mtm_abc"));
+    assert!(!ApiTokensService::is_api_token(
+        "eyJhbGciOiJIUzI1NiJ9.e30.x"
+    ));
+
+    let hash = ApiTokensService::hash_token("mtm_abc");
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, ApiTokensService::hash_token("mtm_abc"));
+    assert_ne!(hash, ApiTokensService::hash_token("mtm_abd"));
+}
+
+#[test]
+fn api_tier_limits() {
+    assert_eq!(api_tiers::daily_request_limit(api_tiers::FREE), Some(1_000));
+    assert_eq!(api_tiers::daily_request_limit(api_tiers::PRO), Some(50_000));
+    assert_eq!(api_tiers::daily_request_limit(api_tiers::UNLIMITED), None);
+    // Unknown tiers fall back to the free quota
+    assert_eq!(api_tiers::daily_request_limit("legacy"), Some(1_000));
+}
+
+#[test]
+fn usage_rolls_up_by_endpoint_and_day() {
+    let yesterday = NaiveDate::from_ymd_opt(2025, 9, 24).unwrap();
+    let today = NaiveDate::from_ymd_opt(2025, 9, 25).unwrap();
+    let rows = vec![
+        row(yesterday, "GET /issues", 10, 1),
+        row(yesterday, "POST /issues", 2, 2),
+        row(today, "GET /issues", 5, 0),
+    ];
+
+    let report = ApiTokensService::summarize_usage(
+        api_client_types::PERSONAL_TOKEN,
+        Uuid::nil(),
+        api_tiers::FREE,
+        yesterday,
+        today,
+        &rows,
+    );
+
+    assert_eq!(report.total_requests, 17);
+    assert_eq!(report.total_errors, 3);
+    assert_eq!(report.requests_today, 5);
+    assert_eq!(report.daily_limit, Some(1_000));
+
+    assert_eq!(report.endpoints[0].endpoint, "GET /issues");
+    assert_eq!(report.endpoints[0].requests, 15);
+    assert_eq!(report.endpoints[1].error_rate, 1.0);
+
+    assert_eq!(report.daily.len(), 2);
+    assert_eq!(report.daily[0].day, yesterday);
+    assert_eq!(report.daily[0].requests, 12);
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn personal_tokens_share_the_user_quota() {
+    use super::test_db;
+    use rust_backend::db::models::api_token::NewApiToken;
+    use rust_backend::db::repositories::api_tokens::ApiTokensRepo;
+
+    let mut conn = test_db::connect();
+    let token = |conn: &mut diesel::PgConnection, user_id: Uuid| {
+        let key = Uuid::new_v4().simple().to_string();
+        ApiTokensRepo::insert(
+            conn,
+            &NewApiToken {
+                user_id,
+                name: "Test".to_string(),
+                token_prefix: key[..12].to_string(),
+                token_hash: ApiTokensService::hash_token(&key),
+                expires_at: None,
+                scopes: None,
+            },
+        )
+        .unwrap()
+        .id
+    };
+    let user = test_db::user(&mut conn);
+    let other_user = test_db::user(&mut conn);
+    let first = token(&mut conn, user);
+    let second = token(&mut conn, user);
+    let foreign = token(&mut conn, other_user);
+
+    for (token_id, requests) in [(first, 2), (second, 3), (foreign, 7)] {
+        for _ in 0..requests {
+            ApiTokensService::record_usage(
+                &mut conn,
+                api_client_types::PERSONAL_TOKEN,
+                token_id,
+                "GET /issues",
+                false,
+            )
+            .unwrap();
+        }
+    }
+
+    let remaining = |conn: &mut diesel::PgConnection, token_id, user_id| {
+        ApiTokensService::quota_remaining(
+            conn,
+            api_client_types::PERSONAL_TOKEN,
+            token_id,
+            user_id,
+            api_tiers::FREE,
+        )
+        .unwrap()
+    };
+    // Both tokens of the user draw from one quota; other users do not count
+    assert_eq!(remaining(&mut conn, first, user), Some(995));
+    assert_eq!(remaining(&mut conn, second, user), Some(995));
+    assert_eq!(remaining(&mut conn, foreign, other_user), Some(993));
+}
@@ -0,0 +1,120 @@
+// AppState dependency injection tests: handlers run against test doubles
+
+use axum::body::HttpBody;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use chrono::{NaiveDate, TimeZone, Utc, Weekday};
+use rust_backend::cache::{CacheStore, MemoryCacheStore};
+use rust_backend::db::models::auth::AuthUser;
+use rust_backend::db::models::email::EmailMessage;
+use rust_backend::middleware::auth::AuthUserInfo;
+use rust_backend::routes::dashboard::get_dashboard_summary;
+use rust_backend::services::context::AuthChannel;
+use rust_backend::services::dashboard_service::DashboardService;
+use rust_backend::services::email_service::Mailer;
+use rust_backend::testing::{RecordingMailer, StubDashboardSource, test_state};
+use rust_backend::utils::clock::{self, FixedClock, SequentialIds};
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn auth_info(workspace_id: Option<Uuid>) -> AuthUserInfo {
+    AuthUserInfo {
+        user: AuthUser {
+            id: Uuid::new_v4(),
+            email: "dev@example.com".to_string(),
+            username: "dev".to_string(),
+            name: "Dev".to_string(),
+            avatar_url: None,
+        },
+        current_workspace_id: workspace_id,
+        channel: AuthChannel::Session,
+        asset_region: None,
+    }
+}
+
+async fn json_body(response: Response) -> serde_json::Value {
+    let mut body = response.into_body();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk.unwrap());
+    }
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn app_state_dashboard_handler_runs_on_stubs_and_caches() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap();
+    let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+    let dashboards = Arc::new(StubDashboardSource::new(DashboardService::assemble(
+        vec![],
+        vec![],
+        vec![],
+        today,
+        Weekday::Mon,
+        now,
+    )));
+    let cache = Arc::new(MemoryCacheStore::new());
+    let mut state = test_state()
+        .with_cache(cache.clone())
+        .with_dashboards(dashboards.clone());
+    // Set on the state only; installing it would leak into parallel tests
+    state.clock = Arc::new(FixedClock::new(now));
+    let state = Arc::new(state);
+    let workspace_id = Uuid::new_v4();
+    let info = auth_info(Some(workspace_id));
+
+    let first = get_dashboard_summary(State(state.clone()), info.clone())
+        .await
+        .into_response();
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(json_body(first).await["data"]["cached"], false);
+    assert_eq!(dashboards.loads().len(), 1);
+    assert_eq!(dashboards.loads()[0].0.workspace_id, workspace_id);
+    assert_eq!(dashboards.loads()[0].1, today);
+    assert_eq!(cache.len(), 1);
+
+    let second = get_dashboard_summary(State(state.clone()), info)
+        .await
+        .into_response();
+    assert_eq!(json_body(second).await["data"]["cached"], true);
+    assert_eq!(dashboards.loads().len(), 1);
+
+    let no_workspace = get_dashboard_summary(State(state), auth_info(None))
+        .await
+        .into_response();
+    assert_eq!(no_workspace.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn app_state_memory_cache_expires_with_the_clock() {
+    let start = Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap();
+    let fixed = Arc::new(FixedClock::new(start));
+    let _guard = clock::freeze(fixed.clone(), Arc::new(SequentialIds::new(1)));
+    let cache = MemoryCacheStore::new();
+
+    cache.set_ex("k", "v".to_string(), 30).await.unwrap();
+    assert_eq!(cache.get("k").await.unwrap().as_deref(), Some("v"));
+    fixed.advance(chrono::Duration::seconds(31));
+    assert_eq!(cache.get("k").await.unwrap(), None);
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn app_state_recording_mailer_keeps_queued_emails() {
+    let mailer = RecordingMailer::new();
+    mailer
+        .enqueue_quietly(EmailMessage {
+            to: "new@example.com".to_string(),
+            subject: "Hello".to_string(),
+            text: "Hi".to_string(),
+            html: None,
+            reply_to: None,
+            from: None,
+        })
+        .await;
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "new@example.com");
+    assert_eq!(mailer.platform_from(), "Momentum <no-reply@localhost>");
+}
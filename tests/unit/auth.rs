@@ -1,4 +1,5 @@
-// Unit tests focus on pure validation and DTO shaping
+// Unit tests focus on pure validation, DTO shaping, token revocation and
+// registration side effects
 
 #[test]
 fn validate_register_and_login_inputs() {
@@ -38,3 +39,130 @@ fn validate_register_and_login_inputs() {
     };
     assert!(validate_update_profile(&empty_changes).is_err());
 }
+
+#[test]
+fn change_password_request_requires_strong_new_password() {
+    use rust_backend::db::models::auth::ChangePasswordRequest;
+    use validator::Validate;
+
+    let request = |current: &str, new: &str| ChangePasswordRequest {
+        current_password: current.to_string(),
+        new_password: new.to_string(),
+    };
+    assert!(request("OldP4ss!word", "StrongP4ss!").validate().is_ok());
+    assert!(request("", "StrongP4ss!").validate().is_err());
+    assert!(request("OldP4ss!word", "weak").validate().is_err());
+}
+
+#[test]
+fn token_revocation_cutoff_only_affects_older_tokens() {
+    use rust_backend::cache::TokenRevocationList;
+
+    assert!(!TokenRevocationList::issued_before_cutoff(100, None));
+    assert!(TokenRevocationList::issued_before_cutoff(99, Some(100)));
+    // Tokens issued in the same second as the cutoff, e.g. right after a
+    // password change, stay valid
+    assert!(!TokenRevocationList::issued_before_cutoff(100, Some(100)));
+    assert!(!TokenRevocationList::issued_before_cutoff(101, Some(100)));
+}
+
+#[tokio::test]
+#[ignore = "requires running Redis"]
+async fn token_revocation_list_round_trip() {
+    use rust_backend::cache::TokenRevocationList;
+
+    let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    let revocations = TokenRevocationList::new(client);
+    let user_id = uuid::Uuid::new_v4();
+    let jti = uuid::Uuid::new_v4().to_string();
+    let other_jti = uuid::Uuid::new_v4().to_string();
+    let now = TokenRevocationList::now();
+
+    assert!(!revocations.is_revoked(user_id, &jti, now).await);
+    revocations.revoke_token(&jti, now + 60).await.unwrap();
+    assert!(revocations.is_revoked(user_id, &jti, now).await);
+    assert!(!revocations.is_revoked(user_id, &other_jti, now).await);
+
+    revocations
+        .revoke_issued_before(user_id, now)
+        .await
+        .unwrap();
+    assert!(revocations.is_revoked(user_id, &other_jti, now - 10).await);
+    assert!(!revocations.is_revoked(user_id, &other_jti, now).await);
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn password_registration_does_not_auto_join_until_email_is_verified() {
+    use super::test_db;
+    use rust_backend::config::AssetsConfig;
+    use rust_backend::db::models::auth::RegisterRequest;
+    use rust_backend::db::models::user_identity::{ExternalProfile, LoginProvider};
+    use rust_backend::db::models::workspace_settings::UpdateWorkspaceSettingsRequest;
+    use rust_backend::db::repositories::workspace_members::WorkspaceMembersRepo;
+    use rust_backend::services::auth_service::AuthService;
+    use rust_backend::services::oauth_login_service::OAuthLoginService;
+    use rust_backend::services::workspace_settings_service::WorkspaceSettingsService;
+    use rust_backend::utils::AssetUrlHelper;
+
+    let mut conn = test_db::connect();
+    let assets = AssetUrlHelper::new(&AssetsConfig {
+        base_url: "http://localhost".to_string(),
+        regions: Vec::new(),
+        fallbacks: Default::default(),
+    });
+    let owner = test_db::user(&mut conn);
+    let workspace_id = test_db::workspace(&mut conn, owner);
+    let key = uuid::Uuid::new_v4().simple().to_string();
+    let domain = format!("d{}.example", &key[..12]);
+    WorkspaceSettingsService::update(
+        &mut conn,
+        &test_db::ctx(owner, workspace_id),
+        &UpdateWorkspaceSettingsRequest {
+            auto_join_domains: Some(vec![domain.clone()]),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // Anyone can type an address at the domain, so this proves nothing
+    let registered = AuthService::register(
+        &mut conn,
+        &RegisterRequest {
+            email: format!("someone@{}", domain),
+            username: format!("u{}", &key[..12]),
+            name: "Someone".to_string(),
+            password: "StrongP4ss!".to_string(),
+        },
+        &assets,
+    )
+    .unwrap();
+    assert!(registered.current_workspace_url_key.is_none());
+    let user_id = registered.user.id;
+    assert!(
+        WorkspaceMembersRepo::find(&mut conn, workspace_id, user_id)
+            .unwrap()
+            .is_none()
+    );
+
+    // A provider-verified email for the same address joins the workspace
+    OAuthLoginService::login(
+        &mut conn,
+        LoginProvider::Github,
+        &ExternalProfile {
+            provider_user_id: key.clone(),
+            email: Some(format!("someone@{}", domain)),
+            email_verified: true,
+            name: None,
+            username_hint: None,
+            avatar_url: None,
+        },
+        &assets,
+    )
+    .unwrap();
+    assert!(
+        WorkspaceMembersRepo::find(&mut conn, workspace_id, user_id)
+            .unwrap()
+            .is_some()
+    );
+}
@@ -0,0 +1,79 @@
+use chrono::{Duration, Utc};
+use rust_backend::db::models::auto_close::{AutoCloseAction, TeamAutoClosePolicy};
+use rust_backend::services::auto_close_service::AutoCloseService;
+use uuid::Uuid;
+
+fn policy(inactive_days: i32, warning_days: i32) -> TeamAutoClosePolicy {
+    TeamAutoClosePolicy {
+        team_id: Uuid::new_v4(),
+        enabled: true,
+        state_ids: format!("{} not-a-uuid", Uuid::new_v4()),
+        inactive_days,
+        warning_days,
+        close_state_id: Uuid::new_v4(),
+        exempt_label_id: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn auto_close_warns_before_closing() {
+    let policy = policy(14, 3);
+    let now = Utc::now();
+
+    // Not yet in the warning window
+    let active = now - Duration::days(10);
+    assert_eq!(
+        AutoCloseService::decide(&policy, active, None, now),
+        AutoCloseAction::None
+    );
+
+    // Inactive for 11 days: warn
+    let idle = now - Duration::days(11);
+    assert_eq!(
+        AutoCloseService::decide(&policy, idle, None, now),
+        AutoCloseAction::Warn
+    );
+
+    // Long inactive but never warned: warn first instead of closing
+    let stale = now - Duration::days(30);
+    assert_eq!(
+        AutoCloseService::decide(&policy, stale, None, now),
+        AutoCloseAction::Warn
+    );
+
+    // Warned, but the warning has not stood long enough
+    assert_eq!(
+        AutoCloseService::decide(&policy, stale, Some(now - Duration::days(1)), now),
+        AutoCloseAction::None
+    );
+
+    // Warned three days ago and still inactive: close
+    assert_eq!(
+        AutoCloseService::decide(&policy, stale, Some(now - Duration::days(3)), now),
+        AutoCloseAction::Close
+    );
+}
+
+#[test]
+fn auto_close_activity_voids_warning() {
+    let policy = policy(14, 3);
+    let now = Utc::now();
+    let warned = now - Duration::days(5);
+    let commented = now - Duration::days(4);
+    assert_eq!(
+        AutoCloseService::decide(&policy, commented, Some(warned), now),
+        AutoCloseAction::None
+    );
+    assert_eq!(policy.state_id_list().len(), 1);
+}
+
+#[test]
+fn auto_close_policy_windows() {
+    assert!(AutoCloseService::validate_windows(14, 3).is_ok());
+    assert!(AutoCloseService::validate_windows(0, 0).is_err());
+    assert!(AutoCloseService::validate_windows(14, 0).is_err());
+    assert!(AutoCloseService::validate_windows(14, 14).is_err());
+    assert!(AutoCloseService::validate_windows(400, 3).is_err());
+}
@@ -0,0 +1,191 @@
+// Automation rule tests
+
+use rust_backend::db::models::automation::{
+    AutomationAction, AutomationConditions, AutomationEvent, automation_triggers,
+};
+use rust_backend::db::models::issue::Issue;
+use rust_backend::services::automations_service::AutomationsService;
+use uuid::Uuid;
+
+fn issue() -> Issue {
+    Issue {
+        id: Uuid::new_v4(),
+        project_id: None,
+        cycle_id: None,
+        creator_id: Uuid::new_v4(),
+        assignee_id: None,
+        parent_issue_id: None,
+        issue_number: 7,
+        title: "Checkout crashes on Safari".to_string(),
+        description: None,
+        priority: "high".to_string(),
+        is_changelog_candidate: false,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        team_id: Uuid::new_v4(),
+        workflow_id: None,
+        workflow_state_id: None,
+        archived_at: None,
+        archive_batch_id: None,
+        estimate: None,
+    }
+}
+
+#[test]
+fn every_condition_must_hold() {
+    let issue = issue();
+    let bug = Uuid::new_v4();
+    let event = AutomationEvent::IssueCreated;
+    let conditions = AutomationConditions {
+        priorities: vec!["high".to_string(), "urgent".to_string()],
+        label_ids: vec![bug],
+        assigned: Some(false),
+        title_contains: Some("CRASH".to_string()),
+        ..Default::default()
+    };
+
+    assert!(AutomationsService::matches(
+        &conditions,
+        &issue,
+        &[bug],
+        &event
+    ));
+    assert!(!AutomationsService::matches(
+        &conditions,
+        &issue,
+        &[],
+        &event
+    ));
+    assert!(!AutomationsService::matches(
+        &conditions,
+        &Issue {
+            priority: "low".to_string(),
+            ..issue.clone()
+        },
+        &[bug],
+        &event
+    ));
+    assert!(!AutomationsService::matches(
+        &conditions,
+        &Issue {
+            assignee_id: Some(Uuid::new_v4()),
+            ..issue.clone()
+        },
+        &[bug],
+        &event
+    ));
+    assert!(!AutomationsService::matches(
+        &AutomationConditions {
+            project_id: Some(Uuid::new_v4()),
+            ..conditions.clone()
+        },
+        &issue,
+        &[bug],
+        &event
+    ));
+    assert!(AutomationsService::matches(
+        &AutomationConditions::default(),
+        &issue,
+        &[],
+        &event
+    ));
+}
+
+#[test]
+fn event_conditions_match_the_triggering_change() {
+    let (todo, done) = (Uuid::new_v4(), Uuid::new_v4());
+    let into_done = AutomationConditions {
+        to_state_id: Some(done),
+        ..Default::default()
+    };
+    assert!(AutomationsService::event_matches(
+        &into_done,
+        &AutomationEvent::IssueStateChanged {
+            from_state_id: Some(todo),
+            to_state_id: Some(done),
+        }
+    ));
+    assert!(!AutomationsService::event_matches(
+        &into_done,
+        &AutomationEvent::IssueStateChanged {
+            from_state_id: Some(done),
+            to_state_id: Some(todo),
+        }
+    ));
+    assert!(!AutomationsService::event_matches(
+        &AutomationConditions {
+            from_state_id: Some(todo),
+            ..Default::default()
+        },
+        &AutomationEvent::IssueStateChanged {
+            from_state_id: None,
+            to_state_id: Some(done),
+        }
+    ));
+
+    let label = Uuid::new_v4();
+    let label_added = AutomationConditions {
+        added_label_id: Some(label),
+        ..Default::default()
+    };
+    assert!(AutomationsService::event_matches(
+        &label_added,
+        &AutomationEvent::IssueLabelAdded { label_id: label }
+    ));
+    assert!(!AutomationsService::event_matches(
+        &label_added,
+        &AutomationEvent::IssueLabelAdded {
+            label_id: Uuid::new_v4()
+        }
+    ));
+}
+
+#[test]
+fn rules_round_trip_through_json() {
+    let cycle_id = Uuid::new_v4();
+    let actions: Vec<AutomationAction> = serde_json::from_value(serde_json::json!([
+        { "type": "set_assignee", "assignee_id": null },
+        { "type": "move_to_cycle", "cycle_id": cycle_id },
+        { "type": "post_comment", "content": "Triaged automatically" }
+    ]))
+    .unwrap();
+    assert_eq!(
+        actions,
+        vec![
+            AutomationAction::SetAssignee { assignee_id: None },
+            AutomationAction::MoveToCycle { cycle_id },
+            AutomationAction::PostComment {
+                content: "Triaged automatically".to_string()
+            },
+        ]
+    );
+    assert!(
+        serde_json::from_value::<AutomationAction>(serde_json::json!({ "type": "delete_issue" }))
+            .is_err()
+    );
+
+    assert_eq!(
+        serde_json::to_value(AutomationConditions::default()).unwrap(),
+        serde_json::json!({})
+    );
+    assert!(
+        serde_json::from_value::<AutomationConditions>(serde_json::json!({ "status": "done" }))
+            .is_err()
+    );
+
+    let event = AutomationEvent::IssueLabelAdded {
+        label_id: Uuid::new_v4(),
+    };
+    assert_eq!(event.trigger(), automation_triggers::ISSUE_LABEL_ADDED);
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(
+        serde_json::from_str::<AutomationEvent>(&json).unwrap(),
+        event
+    );
+}
+
+#[test]
+fn failed_runs_back_off_exponentially() {
+    assert_eq!(AutomationsService::retry_delay(1).num_seconds(), 120);
+    assert_eq!(AutomationsService::retry_delay(2).num_seconds(), 240);
+}
@@ -0,0 +1,45 @@
+use chrono::{TimeZone, Utc};
+use rust_backend::utils::clock::{self, FixedClock, IdGenerator, RandomIds, SequentialIds};
+use std::sync::Arc;
+
+#[test]
+fn frozen_clock_and_ids_are_deterministic() {
+    let start = Utc.with_ymd_and_hms(2026, 10, 17, 9, 30, 0).unwrap();
+    let fixed = Arc::new(FixedClock::new(start));
+    let guard = clock::freeze(fixed.clone(), Arc::new(SequentialIds::new(7)));
+
+    assert_eq!(clock::now(), start);
+    assert_eq!(clock::now(), start);
+    fixed.advance(chrono::Duration::minutes(5));
+    assert_eq!(clock::now(), start + chrono::Duration::minutes(5));
+
+    let first = clock::new_id();
+    let second = clock::new_id();
+    assert_eq!(first.to_string(), "00000000-0000-0007-0000-000000000001");
+    assert_eq!(second.to_string(), "00000000-0000-0007-0000-000000000002");
+
+    // The same seed replays the same ids
+    let replay = SequentialIds::new(7);
+    assert_eq!(replay.new_id(), first);
+    assert_eq!(replay.new_id(), second);
+
+    drop(guard);
+    assert_ne!(clock::now(), start + chrono::Duration::minutes(5));
+}
+
+#[test]
+fn frozen_providers_nest_and_restore() {
+    let outer = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let inner = Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+    let _outer = clock::freeze(Arc::new(FixedClock::new(outer)), Arc::new(RandomIds));
+    {
+        let _inner = clock::freeze(
+            Arc::new(FixedClock::new(inner)),
+            Arc::new(SequentialIds::new(1)),
+        );
+        assert_eq!(clock::now(), inner);
+        assert_eq!(clock::new_id().as_u128(), (1u128 << 64) | 1);
+    }
+    assert_eq!(clock::now(), outer);
+    assert_eq!(clock::new_id().get_version_num(), 4);
+}
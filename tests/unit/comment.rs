@@ -10,3 +10,147 @@ fn validate_comment_create_and_update() {
     assert!(validate_update_comment("edit").is_ok());
     assert!(validate_update_comment(" ").is_err());
 }
+
+#[test]
+fn validate_reaction_emoji() {
+    use rust_backend::validation::comment::validate_reaction;
+    assert!(validate_reaction("👍").is_ok());
+    assert!(validate_reaction("thumbs_up").is_ok());
+    assert!(validate_reaction("").is_err());
+    assert!(validate_reaction("thumbs up").is_err());
+    assert!(validate_reaction(&"a".repeat(33)).is_err());
+}
+
+#[test]
+fn reactions_are_summarized_per_emoji() {
+    use rust_backend::db::models::comment::CommentReaction;
+    use rust_backend::services::comments_service::CommentsService;
+    use uuid::Uuid;
+
+    let (me, other, comment_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let reaction = |user_id: Uuid, emoji: &str| CommentReaction {
+        id: Uuid::new_v4(),
+        comment_id,
+        user_id,
+        reaction_type: emoji.to_string(),
+        created_at: None,
+    };
+    let reactions = vec![
+        reaction(other, "🎉"),
+        reaction(other, "👍"),
+        reaction(me, "👍"),
+        reaction(other, "👀"),
+    ];
+
+    let summary = CommentsService::summarize_reactions(&reactions, me);
+    let emojis: Vec<&str> = summary.iter().map(|s| s.emoji.as_str()).collect();
+    assert_eq!(emojis, vec!["👍", "🎉", "👀"]);
+    assert_eq!(summary[0].count, 2);
+    assert!(summary[0].reacted);
+    assert_eq!(summary[1].count, 1);
+    assert!(!summary[1].reacted);
+    assert!(CommentsService::summarize_reactions(&[], me).is_empty());
+}
+
+#[test]
+fn spam_heuristics_flag_link_floods_and_repeats() {
+    use rust_backend::db::models::comment::spam_signals;
+    use rust_backend::services::comment_moderation_service::{
+        AuthorActivity, CommentModerationService,
+    };
+
+    let quiet = AuthorActivity {
+        recent_comments: 1,
+        recent_duplicates: 1,
+    };
+    let normal = CommentModerationService::evaluate("Looks good, see https://example.com", &quiet);
+    assert!(!normal.is_suspect());
+    assert!(normal.signals.is_empty());
+
+    let links = "buy https://a.example http://b.example https://c.example https://d.example";
+    let verdict = CommentModerationService::evaluate(links, &quiet);
+    assert_eq!(verdict.signals, vec![spam_signals::LINKS]);
+    assert!(!verdict.is_suspect());
+
+    let burst = AuthorActivity {
+        recent_comments: 12,
+        recent_duplicates: 3,
+    };
+    let verdict = CommentModerationService::evaluate(links, &burst);
+    assert_eq!(
+        verdict.signals,
+        vec![
+            spam_signals::LINKS,
+            spam_signals::DUPLICATE,
+            spam_signals::BURST
+        ]
+    );
+    assert_eq!(verdict.score, 7);
+    assert!(verdict.is_suspect());
+
+    let shouting = CommentModerationService::evaluate(
+        "THIS IS THE BEST OFFER YOU WILL EVER SEE, CLICK NOW",
+        &quiet,
+    );
+    assert_eq!(shouting.signals, vec![spam_signals::SHOUTING]);
+    assert!(
+        !CommentModerationService::evaluate("OK", &quiet)
+            .signals
+            .contains(&spam_signals::SHOUTING)
+    );
+}
+
+#[test]
+fn moderation_patterns_become_escaped_ilike_patterns() {
+    use rust_backend::services::comment_moderation_service::CommentModerationService;
+
+    assert_eq!(
+        CommentModerationService::pattern_to_like("cheap pills"),
+        "%cheap pills%"
+    );
+    assert_eq!(
+        CommentModerationService::pattern_to_like("win*prize"),
+        "%win%prize%"
+    );
+    assert_eq!(
+        CommentModerationService::pattern_to_like("100%_off\\"),
+        "%100\\%\\_off\\\\%"
+    );
+}
+
+#[test]
+fn anchored_window_centers_the_anchor_and_fills_from_the_longer_side() {
+    use rust_backend::services::comments_service::CommentsService;
+
+    // 9 slots around the anchor: 4 on each side when both have enough
+    assert_eq!(CommentsService::split_window(100, 100, 9), (4, 4));
+    // An odd share goes below the anchor
+    assert_eq!(CommentsService::split_window(100, 100, 10), (4, 5));
+    // Near the top of the list the remaining room goes below, and vice versa
+    assert_eq!(CommentsService::split_window(1, 100, 9), (1, 7));
+    assert_eq!(CommentsService::split_window(100, 2, 9), (6, 2));
+    assert_eq!(CommentsService::split_window(2, 3, 9), (2, 3));
+    assert_eq!(CommentsService::split_window(5, 5, 1), (0, 0));
+}
+
+#[test]
+fn comment_page_query_defaults_to_newest_first() {
+    use rust_backend::db::models::comment::{CommentOrder, CommentPageQuery};
+    use rust_backend::services::comments_service::CommentsService;
+
+    let query: CommentPageQuery = serde_json::from_str("{}").unwrap();
+    assert_eq!(query.order, CommentOrder::NewestFirst);
+    assert!(query.around.is_none());
+
+    let query: CommentPageQuery = serde_json::from_value(serde_json::json!({
+        "order": "oldest_first",
+        "around": "6f1c2a8e-3b8f-4c4e-9a55-1d6f0a3b7c21",
+    }))
+    .unwrap();
+    assert_eq!(query.order, CommentOrder::OldestFirst);
+    assert!(query.around.is_some());
+
+    assert_eq!(CommentsService::clamp_page_limit(None), 50);
+    assert_eq!(CommentsService::clamp_page_limit(Some(0)), 1);
+    assert_eq!(CommentsService::clamp_page_limit(Some(10_000)), 200);
+}
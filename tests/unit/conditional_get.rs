@@ -0,0 +1,127 @@
+// Weak ETags and If-None-Match matching for list endpoints
+
+use chrono::{Duration, TimeZone, Utc};
+use rust_backend::db::repositories::collection_versions::CollectionVersion;
+use rust_backend::middleware::conditional_get::{Collection, EtagVariant, etag_matches, weak_etag};
+use uuid::Uuid;
+
+fn version(count: i64, minutes: i64) -> CollectionVersion {
+    CollectionVersion {
+        count,
+        last_updated_at: Some(
+            Utc.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap() + Duration::minutes(minutes),
+        ),
+    }
+}
+
+#[test]
+fn only_list_endpoints_are_conditional() {
+    assert_eq!(Collection::for_path("/issues"), Some(Collection::Issues));
+    assert_eq!(
+        Collection::for_path("/projects/"),
+        Some(Collection::Projects)
+    );
+    assert_eq!(
+        Collection::for_path("/project-statuses"),
+        Some(Collection::ProjectStatuses)
+    );
+    assert_eq!(Collection::for_path("/issues/by-key/ENG-1"), None);
+    assert_eq!(Collection::for_path("/projects/board"), None);
+    assert_eq!(Collection::for_path("/notifications"), None);
+}
+
+#[test]
+fn etags_change_with_the_data_and_the_request() {
+    let variant = EtagVariant {
+        user_id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        query: Some("team_id=1&fields=title"),
+        asset_region: None,
+    };
+    let etag = weak_etag(Collection::Issues, &version(3, 0), &variant);
+    assert!(etag.starts_with("W/\"") && etag.ends_with('"'));
+    assert_eq!(
+        etag,
+        weak_etag(Collection::Issues, &version(3, 0), &variant)
+    );
+
+    // A deletion lowers the count, an edit moves the latest update
+    assert_ne!(
+        etag,
+        weak_etag(Collection::Issues, &version(2, 0), &variant)
+    );
+    assert_ne!(
+        etag,
+        weak_etag(Collection::Issues, &version(3, 1), &variant)
+    );
+    assert_ne!(
+        etag,
+        weak_etag(Collection::Labels, &version(3, 0), &variant)
+    );
+    let other_user = EtagVariant {
+        user_id: Uuid::new_v4(),
+        ..variant.clone()
+    };
+    assert_ne!(
+        etag,
+        weak_etag(Collection::Issues, &version(3, 0), &other_user)
+    );
+    let other_query = EtagVariant {
+        query: Some("team_id=2&fields=title"),
+        ..variant.clone()
+    };
+    assert_ne!(
+        etag,
+        weak_etag(Collection::Issues, &version(3, 0), &other_query)
+    );
+}
+
+#[test]
+fn combined_versions_keep_the_latest_change() {
+    let empty = CollectionVersion::default();
+    assert_eq!(version(3, 5).combine(empty), version(3, 5));
+    assert_eq!(version(3, 5).combine(version(2, 9)), version(5, 9));
+}
+
+#[test]
+fn if_none_match_compares_weakly() {
+    let etag = "W/\"abc\"";
+    assert!(etag_matches("W/\"abc\"", etag));
+    assert!(etag_matches("\"abc\"", etag));
+    assert!(etag_matches("\"old\", W/\"abc\"", etag));
+    assert!(etag_matches("*", etag));
+    assert!(!etag_matches("W/\"old\"", etag));
+    assert!(!etag_matches("", etag));
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn issue_list_version_covers_embedded_teams_and_members() {
+    use super::test_db;
+    use diesel::prelude::*;
+    use rust_backend::schema::workspace_members;
+
+    let mut conn = test_db::connect();
+    let owner = test_db::user(&mut conn);
+    let workspace = test_db::workspace(&mut conn, owner);
+    let initial = Collection::Issues.version(&mut conn, workspace).unwrap();
+
+    test_db::team(&mut conn, workspace);
+    let with_team = Collection::Issues.version(&mut conn, workspace).unwrap();
+    assert_ne!(with_team, initial);
+
+    let member = test_db::user(&mut conn);
+    diesel::insert_into(workspace_members::table)
+        .values((
+            workspace_members::user_id.eq(member),
+            workspace_members::workspace_id.eq(workspace),
+            workspace_members::role
+                .eq(rust_backend::db::models::workspace_member::WorkspaceMemberRole::Member),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    assert_ne!(
+        Collection::Issues.version(&mut conn, workspace).unwrap(),
+        with_team
+    );
+}
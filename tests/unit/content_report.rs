@@ -0,0 +1,70 @@
+// Validation and resolution tests for content reports
+
+use rust_backend::db::models::content_report::{
+    CreateReportRequest, ReportResolution, ResolveReportRequest, report_status, report_targets,
+};
+use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+use rust_backend::services::content_reports_service::ContentReportsService;
+use rust_backend::services::permission_service::{Permission, PermissionService};
+
+fn request(reason: &str, details: Option<&str>) -> CreateReportRequest {
+    CreateReportRequest {
+        reason: reason.to_string(),
+        details: details.map(str::to_string),
+    }
+}
+
+#[test]
+fn reports_need_a_known_reason_and_details_for_other() {
+    let validate = ContentReportsService::validate_report;
+    assert_eq!(
+        validate(&request("spam", Some("  buy now  "))).unwrap(),
+        ("spam".to_string(), Some("buy now".to_string()))
+    );
+    assert_eq!(
+        validate(&request("harassment", Some("   "))).unwrap(),
+        ("harassment".to_string(), None)
+    );
+    assert!(validate(&request("boring", None)).is_err());
+    assert!(validate(&request("other", None)).is_err());
+    assert!(validate(&request("other", Some(" "))).is_err());
+    assert!(validate(&request("other", Some(&"x".repeat(1001)))).is_err());
+    assert!(validate(&request("other", Some("wrong team"))).is_ok());
+}
+
+#[test]
+fn resolutions_map_to_statuses_and_outcomes() {
+    let req: ResolveReportRequest =
+        serde_json::from_str(r#"{"action":"hide","note":"rude"}"#).unwrap();
+    assert_eq!(req.action, ReportResolution::Hide);
+    assert!(serde_json::from_str::<ResolveReportRequest>(r#"{"action":"ban"}"#).is_err());
+
+    assert_eq!(ReportResolution::Dismiss.status(), report_status::DISMISSED);
+    assert_eq!(ReportResolution::Hide.status(), report_status::HIDDEN);
+    assert_eq!(ReportResolution::Remove.status(), report_status::REMOVED);
+
+    assert_eq!(
+        ContentReportsService::outcome(report_targets::ISSUE, ReportResolution::Hide),
+        "A moderator archived the reported issue."
+    );
+    assert_eq!(
+        ContentReportsService::outcome(report_targets::COMMENT, ReportResolution::Remove),
+        "A moderator removed the reported comment."
+    );
+}
+
+#[test]
+fn guests_report_and_admins_moderate() {
+    assert!(PermissionService::role_allows(
+        &WorkspaceMemberRole::Guest,
+        Permission::ReportContent
+    ));
+    assert!(!PermissionService::role_allows(
+        &WorkspaceMemberRole::Member,
+        Permission::ModerateContent
+    ));
+    assert!(PermissionService::role_allows(
+        &WorkspaceMemberRole::Admin,
+        Permission::ModerateContent
+    ));
+}
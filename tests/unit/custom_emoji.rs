@@ -0,0 +1,50 @@
+// Workspace custom emoji tests
+
+use rust_backend::services::custom_emojis_service::CustomEmojisService;
+
+#[test]
+fn emoji_names_are_normalized_shortcode_names() {
+    assert_eq!(
+        CustomEmojisService::normalize_name("party").unwrap(),
+        "party"
+    );
+    assert_eq!(
+        CustomEmojisService::normalize_name(" :Party_Parrot: ").unwrap(),
+        "party_parrot"
+    );
+    assert_eq!(
+        CustomEmojisService::normalize_name("+1-ok").unwrap(),
+        "+1-ok"
+    );
+
+    assert!(CustomEmojisService::normalize_name("x").is_err());
+    assert!(CustomEmojisService::normalize_name("party parrot").is_err());
+    assert!(CustomEmojisService::normalize_name("ünicode").is_err());
+    assert!(CustomEmojisService::normalize_name(&"a".repeat(31)).is_err());
+}
+
+#[test]
+fn only_colon_wrapped_reactions_refer_to_custom_emoji() {
+    assert_eq!(CustomEmojisService::reaction_name(":party:"), Some("party"));
+    assert_eq!(CustomEmojisService::reaction_name("👍"), None);
+    assert_eq!(CustomEmojisService::reaction_name("thumbs_up"), None);
+    assert_eq!(CustomEmojisService::reaction_name("::"), None);
+    assert_eq!(CustomEmojisService::reaction_name(":Party:"), None);
+}
+
+#[test]
+fn shortcodes_are_found_in_markdown() {
+    assert_eq!(
+        CustomEmojisService::shortcodes("Shipped :party: :rocket::party: done"),
+        vec!["party", "rocket"]
+    );
+    assert_eq!(
+        CustomEmojisService::shortcodes("(:ok_hand:) and **:fire:**"),
+        vec!["ok_hand", "fire"]
+    );
+    // Times, URLs and lone colons are not shortcodes
+    assert!(CustomEmojisService::shortcodes("Meet at 10:30:45").is_empty());
+    assert!(CustomEmojisService::shortcodes("see https://example.com:8080/").is_empty());
+    assert!(CustomEmojisService::shortcodes("a: b: c").is_empty());
+    assert!(CustomEmojisService::shortcodes("").is_empty());
+}
@@ -4,3 +4,196 @@ fn validate_create_cycle_rules() {
     assert!(validate_create_cycle("Sprint 1").is_ok());
     assert!(validate_create_cycle(" ").is_err());
 }
+
+#[test]
+fn cycle_points_derive_commitment_from_scope_changes() {
+    use rust_backend::db::models::cycle::{CycleIssuePoints, CyclePoints, CycleScopeChange};
+    use rust_backend::db::models::workflow::WorkflowStateCategory;
+
+    let change = |kind: &str, delta: i32| CycleScopeChange {
+        id: 0,
+        cycle_id: uuid::Uuid::nil(),
+        issue_id: uuid::Uuid::new_v4(),
+        change: kind.to_string(),
+        points_delta: delta,
+        created_at: chrono::Utc::now(),
+    };
+    let issues = [
+        CycleIssuePoints {
+            estimate: Some(5),
+            category: Some(WorkflowStateCategory::Completed),
+        },
+        CycleIssuePoints {
+            estimate: Some(3),
+            category: Some(WorkflowStateCategory::Started),
+        },
+        CycleIssuePoints {
+            estimate: Some(8),
+            category: Some(WorkflowStateCategory::Unstarted),
+        },
+        CycleIssuePoints {
+            estimate: None,
+            category: None,
+        },
+    ];
+    // After the start an 8-point issue was added, a 2-point one removed and
+    // the 3-point one re-estimated down from 5
+    let changes = [
+        change("added", 8),
+        change("removed", -2),
+        change("estimated", -2),
+    ];
+
+    let points = CyclePoints::compute(&issues, &changes);
+    assert_eq!(points.total_points, 16);
+    assert_eq!(points.completed_points, 5);
+    assert_eq!(points.scope_added_points, 8);
+    assert_eq!(points.scope_removed_points, 4);
+    assert_eq!(points.scope_change_points, 4);
+    assert_eq!(points.committed_points, 12);
+    assert_eq!(points.issues_added, 1);
+    assert_eq!(points.issues_removed, 1);
+    assert_eq!(points.unestimated_issues, 1);
+
+    let untouched = CyclePoints::compute(&issues, &[]);
+    assert_eq!(untouched.committed_points, untouched.total_points);
+}
+
+#[test]
+fn cycle_burndown_replays_scope_and_completions_per_day() {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use rust_backend::db::models::cycle::{CycleBurndown, CyclePoints, CycleScopeChange};
+    use uuid::Uuid;
+
+    let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let day = |d: u32, h: u32| Utc.with_ymd_and_hms(2025, 3, d, h, 0, 0).unwrap();
+    let change = |id: i64, issue_id: Uuid, kind: &str, delta: i32, at| CycleScopeChange {
+        id,
+        cycle_id: Uuid::nil(),
+        issue_id,
+        change: kind.to_string(),
+        points_delta: delta,
+        created_at: at,
+    };
+    let changes = [
+        // Committed before the start: 5 + 3 points
+        change(1, a, "added", 5, day(1, 9)),
+        change(2, b, "added", 3, day(1, 10)),
+        // Day 3: a is completed and c (2 points) joins
+        change(3, a, "completed", 5, day(3, 12)),
+        change(4, c, "added", 2, day(3, 15)),
+        // Day 4: b is re-estimated up, a is reopened and completed again
+        change(5, b, "estimated", 2, day(4, 8)),
+        change(6, a, "reopened", -5, day(4, 9)),
+        change(7, a, "completed", 5, day(4, 11)),
+        // Day 5: c leaves the cycle
+        change(8, c, "removed", -2, day(5, 9)),
+    ];
+    let start = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+    let end = NaiveDate::from_ymd_opt(2025, 3, 6).unwrap();
+    let today = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap();
+
+    let burndown = CycleBurndown::compute(Uuid::nil(), start, end, today, &changes);
+    assert_eq!(burndown.committed_issues, 2);
+    assert_eq!(burndown.committed_points, 8);
+    // Days after today are not reported
+    assert_eq!(burndown.days.len(), 4);
+
+    let remaining: Vec<i64> = burndown.days.iter().map(|d| d.remaining_points).collect();
+    assert_eq!(remaining, vec![8, 5, 7, 5]);
+    let scope: Vec<i64> = burndown.days.iter().map(|d| d.scope_points).collect();
+    assert_eq!(scope, vec![8, 10, 12, 10]);
+    let completed: Vec<i64> = burndown.days.iter().map(|d| d.completed_points).collect();
+    assert_eq!(completed, vec![0, 5, 5, 5]);
+    let added: Vec<i64> = burndown.days.iter().map(|d| d.scope_added_points).collect();
+    assert_eq!(added, vec![0, 2, 4, 4]);
+    assert_eq!(burndown.days[1].scope_added_issues, 1);
+    assert_eq!(burndown.days[3].remaining_issues, 1);
+    let ideal: Vec<f64> = burndown.days.iter().map(|d| d.ideal_points).collect();
+    assert_eq!(ideal, vec![8.0, 6.0, 4.0, 2.0]);
+
+    // Completions are progress, not scope changes
+    let points = CyclePoints::compute(&[], &changes[2..]);
+    assert_eq!(points.issues_added, 1);
+    assert_eq!(points.scope_added_points, 4);
+    assert_eq!(points.scope_removed_points, 2);
+
+    let not_started =
+        CycleBurndown::compute(Uuid::nil(), start, end, day(1, 0).date_naive(), &changes);
+    assert!(not_started.days.is_empty());
+}
+
+#[test]
+fn next_cycle_window_follows_cadence() {
+    use chrono::NaiveDate;
+    use rust_backend::db::models::cycle::TeamCycleSettings;
+    use rust_backend::services::cycles_service::CyclesService;
+
+    let date = |m: u32, d: u32| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+    // Two-week cycles starting on Mondays with a two-day cooldown
+    let settings = TeamCycleSettings {
+        team_id: uuid::Uuid::nil(),
+        auto_create: true,
+        length_days: 14,
+        start_weekday: 1,
+        cooldown_days: 2,
+        roll_over_unfinished: false,
+        last_rolled_over_cycle_id: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    };
+    // 2025-03-02 is a Sunday and 2025-03-05 a Wednesday
+    let today = date(3, 5);
+
+    // First cycle: the next Monday
+    assert_eq!(
+        CyclesService::next_cycle_window(&settings, None, today),
+        (date(3, 10), date(3, 23))
+    );
+    // A cycle ending Friday 3/14 leaves the weekend free, then the next
+    // Monday after the cooldown
+    assert_eq!(
+        CyclesService::next_cycle_window(&settings, Some(date(3, 14)), today),
+        (date(3, 17), date(3, 30))
+    );
+    // Ending on a Sunday: the cooldown pushes past Monday to the week after
+    assert_eq!(
+        CyclesService::next_cycle_window(&settings, Some(date(3, 16)), today),
+        (date(3, 24), date(4, 6))
+    );
+    // A lapsed team starts from today, not right after its old cycle
+    assert_eq!(
+        CyclesService::next_cycle_window(&settings, Some(date(1, 5)), today),
+        (date(3, 10), date(3, 23))
+    );
+
+    let no_cooldown = TeamCycleSettings {
+        cooldown_days: 0,
+        start_weekday: 3,
+        length_days: 7,
+        ..settings
+    };
+    // Starting on today's weekday starts today
+    assert_eq!(
+        CyclesService::next_cycle_window(&no_cooldown, None, today),
+        (date(3, 5), date(3, 11))
+    );
+    assert_eq!(
+        CyclesService::next_cycle_window(&no_cooldown, Some(date(3, 11)), today),
+        (date(3, 12), date(3, 18))
+    );
+}
+
+#[test]
+fn cycle_cadence_validation() {
+    use rust_backend::services::cycles_service::CyclesService;
+
+    assert!(CyclesService::validate_cadence(14, 1, 0).is_ok());
+    assert!(CyclesService::validate_cadence(84, 7, 28).is_ok());
+    assert!(CyclesService::validate_cadence(0, 1, 0).is_err());
+    assert!(CyclesService::validate_cadence(85, 1, 0).is_err());
+    assert!(CyclesService::validate_cadence(14, 0, 0).is_err());
+    assert!(CyclesService::validate_cadence(14, 8, 0).is_err());
+    assert!(CyclesService::validate_cadence(14, 1, -1).is_err());
+    assert!(CyclesService::validate_cadence(14, 1, 29).is_err());
+}
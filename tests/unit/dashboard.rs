@@ -0,0 +1,158 @@
+use chrono::{NaiveDate, Weekday};
+use rust_backend::db::models::dashboard::{
+    DashboardActivity, DashboardCycleRollup, DashboardIssue,
+};
+use rust_backend::services::dashboard_service::{
+    ACTIVITY_LIMIT, ASSEMBLY_BUDGET, DashboardService, OPEN_ISSUE_LIMIT,
+};
+use std::time::Instant;
+use uuid::Uuid;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+}
+
+fn open_issue(number: i32, cycle_end_date: Option<NaiveDate>) -> DashboardIssue {
+    DashboardIssue {
+        id: Uuid::new_v4(),
+        issue_number: number,
+        title: format!("Issue {}", number),
+        priority: "medium".to_string(),
+        team_id: Uuid::new_v4(),
+        state_category: None,
+        cycle_id: cycle_end_date.map(|_| Uuid::new_v4()),
+        cycle_end_date,
+        updated_at: chrono::Utc::now(),
+    }
+}
+
+fn cycle(total_issues: i64, completed_issues: i64, end_date: NaiveDate) -> DashboardCycleRollup {
+    DashboardCycleRollup {
+        cycle_id: Uuid::new_v4(),
+        team_id: Uuid::new_v4(),
+        name: "Sprint".to_string(),
+        start_date: date(5),
+        end_date,
+        total_issues,
+        completed_issues,
+        total_points: total_issues * 2,
+        completed_points: completed_issues * 2,
+    }
+}
+
+fn activity() -> DashboardActivity {
+    DashboardActivity {
+        notification_id: Uuid::new_v4(),
+        event_type: "issue_assigned".to_string(),
+        entity_type: "issue".to_string(),
+        entity_id: Uuid::new_v4(),
+        title: "You were assigned an issue".to_string(),
+        read: false,
+        occurred_at: chrono::Utc::now(),
+    }
+}
+
+#[test]
+fn dashboard_weeks_run_monday_to_sunday() {
+    // 2026-10-17 is a Saturday
+    assert_eq!(
+        DashboardService::week_bounds(date(17), Weekday::Mon),
+        (date(12), date(18))
+    );
+    assert_eq!(
+        DashboardService::week_bounds(date(12), Weekday::Mon),
+        (date(12), date(18))
+    );
+}
+
+#[test]
+fn dashboard_weeks_follow_the_workspace_week_start() {
+    assert_eq!(
+        DashboardService::week_bounds(date(17), Weekday::Sun),
+        (date(11), date(17))
+    );
+    assert_eq!(
+        DashboardService::week_bounds(date(17), Weekday::Sat),
+        (date(17), date(23))
+    );
+}
+
+#[test]
+fn dashboard_summary_is_assembled_without_counting() {
+    let issues = vec![
+        open_issue(1, Some(date(9))),
+        open_issue(2, Some(date(14))),
+        open_issue(3, Some(date(18))),
+        open_issue(4, Some(date(25))),
+        open_issue(5, None),
+    ];
+    let summary = DashboardService::assemble(
+        issues,
+        vec![cycle(4, 1, date(20)), cycle(0, 0, date(16))],
+        vec![activity()],
+        date(17),
+        Weekday::Mon,
+        chrono::Utc::now(),
+    );
+    assert!(!summary.has_more_open_issues);
+    assert_eq!(summary.my_open_issues.len(), 5);
+    let due: Vec<i32> = summary
+        .due_this_week
+        .iter()
+        .map(|issue| issue.issue_number)
+        .collect();
+    assert_eq!(due, vec![2, 3]);
+    assert_eq!(summary.active_cycles[0].completion_rate, 25.0);
+    assert_eq!(summary.active_cycles[0].days_remaining, 3);
+    // Empty and overdue cycles neither divide by zero nor go negative
+    assert_eq!(summary.active_cycles[1].completion_rate, 0.0);
+    assert_eq!(summary.active_cycles[1].days_remaining, 0);
+    assert!(!summary.cached);
+
+    // The extra row fetched past the limit only marks that more exist
+    let issues = (0..=OPEN_ISSUE_LIMIT as i32)
+        .map(|n| open_issue(n, None))
+        .collect();
+    let activity = (0..ACTIVITY_LIMIT + 3).map(|_| activity()).collect();
+    let summary = DashboardService::assemble(
+        issues,
+        Vec::new(),
+        activity,
+        date(17),
+        Weekday::Mon,
+        chrono::Utc::now(),
+    );
+    assert!(summary.has_more_open_issues);
+    assert_eq!(summary.my_open_issues.len(), OPEN_ISSUE_LIMIT);
+    assert_eq!(summary.recent_activity.len(), ACTIVITY_LIMIT);
+}
+
+#[test]
+fn dashboard_assembly_stays_within_its_latency_budget() {
+    const RUNS: u32 = 200;
+    let issues: Vec<DashboardIssue> = (0..=OPEN_ISSUE_LIMIT as i32)
+        .map(|n| open_issue(n, Some(date(10 + (n as u32 % 15)))))
+        .collect();
+    let cycles: Vec<DashboardCycleRollup> = (0..10).map(|n| cycle(40, n, date(24))).collect();
+    let activity: Vec<DashboardActivity> = (0..ACTIVITY_LIMIT).map(|_| activity()).collect();
+
+    let started = Instant::now();
+    for _ in 0..RUNS {
+        let summary = DashboardService::assemble(
+            issues.clone(),
+            cycles.clone(),
+            activity.clone(),
+            date(17),
+            Weekday::Mon,
+            chrono::Utc::now(),
+        );
+        assert!(summary.has_more_open_issues);
+    }
+    let per_summary = started.elapsed() / RUNS;
+    assert!(
+        per_summary < ASSEMBLY_BUDGET,
+        "assembling a summary took {:?}, budget is {:?}",
+        per_summary,
+        ASSEMBLY_BUDGET
+    );
+}
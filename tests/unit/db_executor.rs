@@ -0,0 +1,24 @@
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use rust_backend::db::DbExecutor;
+use rust_backend::error::AppError;
+use std::time::Duration;
+
+fn unreachable_pool() -> Pool<ConnectionManager<PgConnection>> {
+    Pool::builder()
+        .connection_timeout(Duration::from_millis(50))
+        .build_unchecked(ConnectionManager::new(
+            "postgres://nobody@127.0.0.1:1/momentum",
+        ))
+}
+
+#[tokio::test]
+async fn executor_reports_unavailable_database_as_internal_error() {
+    let executor = DbExecutor::new(unreachable_pool());
+
+    let read = executor.read(|_| Ok(1)).await;
+    assert!(matches!(read, Err(AppError::Internal(_))));
+
+    let written = executor.transaction(|_| Ok(())).await;
+    assert!(matches!(written, Err(AppError::Internal(_))));
+}
@@ -0,0 +1,111 @@
+// Tests for WebSocket command dead letters
+
+use rust_backend::db::models::websocket_dead_letter::dead_letter_status;
+use rust_backend::error::AppError;
+use rust_backend::services::dead_letter_service::DeadLetterService;
+use rust_backend::websocket::{WebSocketCommand, WebSocketCommandError, WebSocketCommandResponse};
+
+fn command(json: serde_json::Value) -> WebSocketCommand {
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn only_server_side_and_transient_failures_are_dead_lettered() {
+    assert!(DeadLetterService::is_dead_letter(&AppError::internal(
+        "boom"
+    )));
+    assert!(DeadLetterService::is_dead_letter(&AppError::Database(
+        diesel::result::Error::BrokenTransactionManager
+    )));
+    assert!(DeadLetterService::is_dead_letter(&AppError::Redis(
+        redis::RedisError::from((redis::ErrorKind::IoError, "connection refused"))
+    )));
+
+    assert!(!DeadLetterService::is_dead_letter(&AppError::validation(
+        "name is required"
+    )));
+    assert!(!DeadLetterService::is_dead_letter(&AppError::not_found(
+        "label"
+    )));
+    assert!(!DeadLetterService::is_dead_letter(&AppError::forbidden(
+        "no"
+    )));
+    assert!(!DeadLetterService::is_dead_letter(
+        &AppError::conflict_with_code("taken", None, "LABEL_EXISTS")
+    ));
+}
+
+#[test]
+fn only_commands_that_change_data_are_replayable() {
+    let create = command(serde_json::json!({
+        "type": "create_label",
+        "data": {"name": "Bug", "color": "#ff0000", "level": "Issue"},
+        "request_id": "req-1"
+    }));
+    assert!(create.is_replayable());
+
+    let ping = command(serde_json::json!({"type": "ping"}));
+    assert!(!ping.is_replayable());
+    let subscribe = command(serde_json::json!({"type": "subscribe", "topics": ["team:1"]}));
+    assert!(!subscribe.is_replayable());
+}
+
+#[test]
+fn recorded_commands_round_trip() {
+    let original = command(serde_json::json!({
+        "type": "create_label",
+        "data": {"name": "Bug", "color": "#ff0000", "level": "Issue"},
+        "request_id": "req-1"
+    }));
+    let stored = serde_json::to_string(&original).unwrap();
+    let replayed: WebSocketCommand = serde_json::from_str(&stored).unwrap();
+    assert_eq!(serde_json::to_string(&replayed).unwrap(), stored);
+}
+
+#[test]
+fn replay_outcome_follows_the_failure() {
+    let ok = WebSocketCommandResponse::success(
+        "create_label",
+        "key",
+        None,
+        serde_json::json!({"id": 1}),
+    );
+    assert_eq!(
+        DeadLetterService::replay_outcome(&ok, None),
+        (dead_letter_status::REPLAYED, None)
+    );
+
+    let failed = WebSocketCommandResponse::error(
+        "create_label",
+        "key",
+        None,
+        WebSocketCommandError::business_error("COMMAND_ERROR", "failed"),
+    );
+    let (status, error) =
+        DeadLetterService::replay_outcome(&failed, Some(&AppError::internal("still broken")));
+    assert_eq!(status, dead_letter_status::PENDING);
+    assert_eq!(error.unwrap().0, "INTERNAL_ERROR");
+
+    let (status, error) =
+        DeadLetterService::replay_outcome(&failed, Some(&AppError::validation("bad color")));
+    assert_eq!(status, dead_letter_status::REJECTED);
+    assert_eq!(error.unwrap().0, "VALIDATION_FAILED");
+
+    // Rejected before running without raising an error
+    let denied = WebSocketCommandResponse::error(
+        "create_label",
+        "key",
+        None,
+        WebSocketCommandError::permission_error("Missing permission"),
+    );
+    assert_eq!(
+        DeadLetterService::replay_outcome(&denied, None),
+        (
+            dead_letter_status::REJECTED,
+            Some((
+                "PERMISSION_ERROR".to_string(),
+                "Missing permission".to_string()
+            ))
+        )
+    );
+}
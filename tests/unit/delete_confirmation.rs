@@ -0,0 +1,106 @@
+use rust_backend::db::models::delete_confirmation::{
+    DeleteConfirmationRecord, DeleteImpact, delete_targets,
+};
+use rust_backend::error::AppError;
+use rust_backend::services::context::{AuthChannel, RequestContext};
+use rust_backend::services::delete_confirmations_service::DeleteConfirmationsService;
+use uuid::Uuid;
+
+fn context() -> RequestContext {
+    RequestContext {
+        user_id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        idempotency_key: None,
+        channel: AuthChannel::Session,
+    }
+}
+
+fn record(ctx: &RequestContext, target_id: Uuid) -> DeleteConfirmationRecord {
+    let now = chrono::Utc::now();
+    DeleteConfirmationRecord {
+        id: Uuid::new_v4(),
+        workspace_id: ctx.workspace_id,
+        target_type: delete_targets::TEAM.to_string(),
+        target_id,
+        target_name: "Platform".to_string(),
+        token_hash: DeleteConfirmationsService::hash_token("del_token"),
+        impact: serde_json::to_string(&DeleteImpact {
+            issues: 12,
+            members: 3,
+            teams: 1,
+            ..Default::default()
+        })
+        .unwrap(),
+        requested_by: Some(ctx.user_id),
+        expires_at: now + chrono::Duration::minutes(5),
+        confirmed_at: None,
+        created_at: now,
+    }
+}
+
+#[test]
+fn delete_confirmation_only_fits_its_own_target_and_user() {
+    let ctx = context();
+    let team_id = Uuid::new_v4();
+    let record = record(&ctx, team_id);
+    let now = chrono::Utc::now();
+
+    assert!(
+        DeleteConfirmationsService::check(&record, &ctx, delete_targets::TEAM, team_id, now)
+            .is_ok()
+    );
+    assert!(matches!(
+        DeleteConfirmationsService::check(&record, &ctx, delete_targets::TEAM, Uuid::new_v4(), now),
+        Err(AppError::Validation { .. })
+    ));
+    assert!(matches!(
+        DeleteConfirmationsService::check(&record, &ctx, delete_targets::WORKSPACE, team_id, now),
+        Err(AppError::Validation { .. })
+    ));
+
+    let other_user = RequestContext {
+        user_id: Uuid::new_v4(),
+        ..ctx.clone()
+    };
+    assert!(matches!(
+        DeleteConfirmationsService::check(&record, &other_user, delete_targets::TEAM, team_id, now),
+        Err(AppError::Forbidden { .. })
+    ));
+    let other_workspace = RequestContext {
+        workspace_id: Uuid::new_v4(),
+        ..ctx.clone()
+    };
+    assert!(
+        DeleteConfirmationsService::check(
+            &record,
+            &other_workspace,
+            delete_targets::TEAM,
+            team_id,
+            now
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn delete_confirmation_expires() {
+    let ctx = context();
+    let team_id = Uuid::new_v4();
+    let record = record(&ctx, team_id);
+    let later = record.expires_at + chrono::Duration::seconds(1);
+    match DeleteConfirmationsService::check(&record, &ctx, delete_targets::TEAM, team_id, later) {
+        Err(AppError::Conflict { code, .. }) => {
+            assert_eq!(code.as_deref(), Some("DELETE_CONFIRMATION_EXPIRED"))
+        }
+        other => panic!("expected an expired token, got {:?}", other),
+    }
+
+    assert_eq!(
+        DeleteConfirmationsService::hash_token("del_token"),
+        record.token_hash
+    );
+    assert_ne!(
+        DeleteConfirmationsService::hash_token("del_other"),
+        record.token_hash
+    );
+}
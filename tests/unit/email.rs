@@ -0,0 +1,91 @@
+use chrono::{TimeZone, Utc};
+use rust_backend::config::SesConfig;
+use rust_backend::db::models::notification::DigestEmail;
+use rust_backend::services::email_service::{EmailService, SesTransport};
+use uuid::Uuid;
+
+#[test]
+fn email_invitation_links_to_invitation_and_escapes_html() {
+    let invitation_id = Uuid::new_v4();
+    let expires_at = Utc.with_ymd_and_hms(2025, 10, 14, 9, 0, 0).unwrap();
+    let email = EmailService::invitation_email(
+        "https://app.example.com",
+        "new@example.com",
+        "Ada",
+        "R&D <core>",
+        invitation_id,
+        expires_at,
+    );
+
+    let link = format!("https://app.example.com/invitations/{}", invitation_id);
+    assert_eq!(email.to, "new@example.com");
+    assert_eq!(email.subject, "Ada invited you to R&D <core>");
+    assert!(email.text.contains(&link));
+    assert!(email.text.contains("2025-10-14"));
+    let html = email.html.unwrap();
+    assert!(html.contains("R&amp;D &lt;core&gt;"));
+    assert!(html.contains(&format!("href=\"{}\"", link)));
+}
+
+#[test]
+fn email_digest_mime_has_plain_and_html_parts() {
+    let email = EmailService::digest_email(DigestEmail {
+        to: "dev@example.com".to_string(),
+        subject: "2 new notifications".to_string(),
+        text: "You were mentioned on \"Fix login\"\n@dev take a look\n\nUpdated \"Docs\"\n"
+            .to_string(),
+        reply_to: Some("reply+abc@mail.example.com".to_string()),
+    });
+    assert_eq!(
+        email.html.as_deref(),
+        Some(
+            "<p>You were mentioned on &quot;Fix login&quot;<br>\n@dev take a look</p>\n<p>Updated &quot;Docs&quot;<br>\n</p>"
+        )
+    );
+
+    let mime = EmailService::build_mime("Momentum <no-reply@example.com>", &email).unwrap();
+    let raw = String::from_utf8(mime.formatted()).unwrap();
+    assert!(raw.contains("multipart/alternative"));
+    assert!(raw.contains("Reply-To: reply+abc@mail.example.com"));
+
+    let invalid = rust_backend::db::models::email::EmailMessage {
+        to: "not an address".to_string(),
+        ..email
+    };
+    assert!(EmailService::build_mime("no-reply@example.com", &invalid).is_err());
+}
+
+#[test]
+fn email_ses_requests_are_signed_for_the_region() {
+    let ses = SesTransport::new(&SesConfig {
+        region: "eu-west-1".to_string(),
+        access_key: "AKIDEXAMPLE".to_string(),
+        secret_key: "secret".to_string(),
+    })
+    .unwrap();
+    assert_eq!(ses.host(), "email.eu-west-1.amazonaws.com");
+
+    let message = EmailService::password_reset_email(
+        "https://app.example.com",
+        "dev@example.com",
+        "Dev",
+        "tok123",
+        30,
+    );
+    let body = SesTransport::request_body("no-reply@example.com", &message);
+    assert_eq!(body["Destination"]["ToAddresses"][0], "dev@example.com");
+    assert!(
+        body["Content"]["Simple"]["Body"]["Html"]["Data"]
+            .as_str()
+            .unwrap()
+            .contains("reset-password?token=tok123")
+    );
+
+    let now = Utc.with_ymd_and_hms(2025, 10, 8, 12, 0, 0).unwrap();
+    let auth = ses.authorization(b"{}", now);
+    assert!(auth.starts_with(
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20251008/eu-west-1/ses/aws4_request, SignedHeaders=content-type;host;x-amz-date, Signature="
+    ));
+    // Signature covers the body
+    assert_ne!(auth, ses.authorization(b"{\"a\":1}", now));
+}
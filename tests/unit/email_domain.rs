@@ -0,0 +1,151 @@
+// Workspace custom email domain tests
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_backend::db::models::workspace_email_domain::{WorkspaceEmailDomain, email_domain_status};
+use rust_backend::error::AppError;
+use rust_backend::services::workspace_email_domains_service::{
+    DnsResolver, DohResolver, WorkspaceEmailDomainsService,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn domain(status: &str) -> WorkspaceEmailDomain {
+    let now = Utc::now();
+    WorkspaceEmailDomain {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        domain: "mail.acme.com".to_string(),
+        from_name: Some("Acme".to_string()),
+        from_local_part: "notifications".to_string(),
+        dkim_selector: "momentum".to_string(),
+        verification_token: "tok123".to_string(),
+        status: status.to_string(),
+        last_error: None,
+        verified_at: None,
+        last_checked_at: None,
+        created_by: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+struct StaticResolver(HashMap<String, Vec<String>>);
+
+#[async_trait]
+impl DnsResolver for StaticResolver {
+    async fn txt_records(&self, name: &str) -> Result<Vec<String>, AppError> {
+        Ok(self.0.get(name).cloned().unwrap_or_default())
+    }
+}
+
+#[test]
+fn email_domain_validation_normalizes_and_rejects_bad_names() {
+    assert_eq!(
+        WorkspaceEmailDomainsService::validate_domain(" Mail.Acme.COM. ").unwrap(),
+        "mail.acme.com"
+    );
+    for bad in [
+        "localhost",
+        "acme..com",
+        "-acme.com",
+        "acme.com/x",
+        "a b.com",
+    ] {
+        assert!(
+            WorkspaceEmailDomainsService::validate_domain(bad).is_err(),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn email_domain_sender_is_used_only_once_verified() {
+    let pending = domain(email_domain_status::PENDING);
+    assert_eq!(pending.sender(), "Acme <notifications@mail.acme.com>");
+    let response =
+        WorkspaceEmailDomainsService::to_response(pending, "Momentum <no-reply@example.com>");
+    assert_eq!(response.effective_from, "Momentum <no-reply@example.com>");
+    assert_eq!(
+        response.dns_records[0].name,
+        "_momentum-verification.mail.acme.com"
+    );
+    assert_eq!(
+        response.dns_records[0].value,
+        "momentum-verification=tok123"
+    );
+    assert_eq!(
+        response.dns_records[1].name,
+        "momentum._domainkey.mail.acme.com"
+    );
+
+    let verified = WorkspaceEmailDomain {
+        from_name: None,
+        ..domain(email_domain_status::VERIFIED)
+    };
+    let response = WorkspaceEmailDomainsService::to_response(verified, "no-reply@example.com");
+    assert_eq!(response.effective_from, "notifications@mail.acme.com");
+}
+
+#[test]
+fn email_domain_check_needs_token_and_dkim_key() {
+    let d = domain(email_domain_status::PENDING);
+    let token = vec!["momentum-verification=tok123".to_string()];
+    let dkim = vec!["v=DKIM1; k=rsa; p=MIIBIjANBg".to_string()];
+
+    assert!(WorkspaceEmailDomainsService::evaluate(&d, &token, &dkim).passed());
+
+    let wrong_token = vec!["momentum-verification=other".to_string()];
+    let check = WorkspaceEmailDomainsService::evaluate(&d, &wrong_token, &dkim);
+    assert!(!check.ownership_verified);
+    assert!(check.error.unwrap().contains("_momentum-verification"));
+
+    let revoked = vec!["v=DKIM1; k=rsa; p=".to_string()];
+    let check = WorkspaceEmailDomainsService::evaluate(&d, &token, &revoked);
+    assert!(check.ownership_verified);
+    assert!(!check.dkim_verified);
+    assert!(!check.passed());
+}
+
+#[test]
+fn email_domain_doh_answers_join_split_txt_strings() {
+    let body = json!({
+        "Status": 0,
+        "Answer": [
+            { "name": "momentum._domainkey.mail.acme.com.", "type": 5, "data": "dkim.provider.net." },
+            { "name": "dkim.provider.net.", "type": 16, "data": "\"v=DKIM1; k=rsa; \" \"p=MIIB\"" },
+            { "name": "dkim.provider.net.", "type": 16, "data": "unquoted" }
+        ]
+    });
+    assert_eq!(
+        DohResolver::parse_txt_answers(&body),
+        vec!["v=DKIM1; k=rsa; p=MIIB".to_string(), "unquoted".to_string()]
+    );
+    assert!(DohResolver::parse_txt_answers(&json!({ "Status": 3 })).is_empty());
+}
+
+#[tokio::test]
+async fn email_domain_check_resolves_both_records() {
+    let d = domain(email_domain_status::PENDING);
+    let resolver = StaticResolver(HashMap::from([
+        (
+            "_momentum-verification.mail.acme.com".to_string(),
+            vec!["momentum-verification=tok123".to_string()],
+        ),
+        (
+            "momentum._domainkey.mail.acme.com".to_string(),
+            vec!["v=DKIM1; p=MIIB".to_string()],
+        ),
+    ]));
+    assert!(
+        WorkspaceEmailDomainsService::check(&resolver, &d)
+            .await
+            .passed()
+    );
+
+    let empty = StaticResolver(HashMap::new());
+    let check = WorkspaceEmailDomainsService::check(&empty, &d).await;
+    assert!(!check.passed());
+}
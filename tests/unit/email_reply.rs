@@ -0,0 +1,79 @@
+// Reply-by-email token, address and body cleanup tests
+
+use rust_backend::utils::email_reply::{
+    extract_email_address, extract_reply_token, reply_address, reply_token, strip_reply_text,
+    verify_reply_token,
+};
+use uuid::Uuid;
+
+const SECRET: &str = "test-secret";
+
+#[test]
+fn reply_token_roundtrip_and_tamper() {
+    let issue_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let token = reply_token(issue_id, user_id, SECRET);
+
+    assert_eq!(
+        verify_reply_token(&token, SECRET),
+        Some((issue_id, user_id))
+    );
+    assert_eq!(verify_reply_token(&token, "other-secret"), None);
+    assert_eq!(verify_reply_token("not-a-token", SECRET), None);
+
+    // Swapping the user part must invalidate the signature
+    let other = reply_token(issue_id, Uuid::new_v4(), SECRET);
+    let forged = format!("{}{}", &other[..42], &token[42..]);
+    assert_eq!(verify_reply_token(&forged, SECRET), None);
+}
+
+#[test]
+fn reply_token_is_found_among_recipients() {
+    let token = reply_token(Uuid::new_v4(), Uuid::new_v4(), SECRET);
+    let address = reply_address(&token, "reply.example.com");
+    let to = format!("team@example.com, Momentum <{}>", address);
+
+    assert_eq!(extract_reply_token(&to), Some(token));
+    assert_eq!(extract_reply_token("team@example.com"), None);
+    assert_eq!(
+        extract_email_address("Alice Smith <Alice@Example.com>"),
+        "Alice@Example.com"
+    );
+    assert_eq!(
+        extract_email_address(" bob@example.com "),
+        "bob@example.com"
+    );
+}
+
+#[test]
+fn strip_reply_text_drops_quotes_and_signatures() {
+    let gmail = "Sounds good, shipping today.\n\nOn Mon, Jan 1, 2024 at 10:00 AM Momentum <reply+abc@example.com> wrote:\n> New comment on \"Fix login\"\n";
+    assert_eq!(strip_reply_text(gmail), "Sounds good, shipping today.");
+
+    let signed = "Looks fixed.\r\n\r\n-- \r\nAlice\r\nStaff Engineer\r\n";
+    assert_eq!(strip_reply_text(signed), "Looks fixed.");
+
+    let mobile = "Will check tomorrow\n\nSent from my iPhone\n";
+    assert_eq!(strip_reply_text(mobile), "Will check tomorrow");
+
+    let outlook = "Agreed\n\nFrom: Momentum <notifications@example.com>\nSent: Monday\n";
+    assert_eq!(strip_reply_text(outlook), "Agreed");
+
+    let chinese = "收到\n\n在 2024年1月1日 10:00，Momentum 写道：\n> 原文\n";
+    assert_eq!(strip_reply_text(chinese), "收到");
+
+    let inline = "> quoted question\nMy answer\n> another quote\nMore detail";
+    assert_eq!(strip_reply_text(inline), "My answer\nMore detail");
+
+    assert_eq!(strip_reply_text("> only a quote\n"), "");
+}
+
+#[test]
+fn inbound_secret_must_match_exactly() {
+    use rust_backend::services::inbound_email_service::InboundEmailService;
+
+    assert!(InboundEmailService::secret_matches("s3cret", "s3cret"));
+    assert!(!InboundEmailService::secret_matches("s3creT", "s3cret"));
+    assert!(!InboundEmailService::secret_matches("s3cre", "s3cret"));
+    assert!(!InboundEmailService::secret_matches("", "s3cret"));
+}
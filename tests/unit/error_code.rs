@@ -0,0 +1,125 @@
+// Error code catalog and structured error bodies
+
+use axum::{
+    Router,
+    body::{Body, HttpBody},
+    http::{Request, StatusCode},
+    middleware::from_fn,
+    routing::get,
+};
+use rust_backend::db::models::api::ApiResponse;
+use rust_backend::error::{AppError, ErrorCode};
+use rust_backend::middleware::request_tracking::request_tracking_middleware;
+use rust_backend::websocket::error_mapper::{WebSocketErrorCode, WebSocketErrorMapper};
+use std::collections::HashSet;
+use tower::ServiceExt;
+
+fn serialization_failure() -> AppError {
+    AppError::Database(diesel::result::Error::DatabaseError(
+        diesel::result::DatabaseErrorKind::SerializationFailure,
+        Box::new("could not serialize access".to_string()),
+    ))
+}
+
+#[test]
+fn catalog_codes_are_unique_and_stable() {
+    let names: HashSet<&str> = ErrorCode::ALL.iter().map(|code| code.as_str()).collect();
+    assert_eq!(names.len(), ErrorCode::ALL.len());
+    for code in ErrorCode::ALL {
+        assert_eq!(
+            serde_json::to_value(code).unwrap(),
+            serde_json::json!(code.as_str())
+        );
+        assert!(!code.description().is_empty());
+    }
+    assert_eq!(
+        ErrorCode::TransactionConflict.as_str(),
+        "TRANSACTION_CONFLICT"
+    );
+}
+
+#[test]
+fn app_errors_map_to_status_and_ws_code_in_one_place() {
+    let cases = [
+        (AppError::validation("bad"), StatusCode::BAD_REQUEST, false),
+        (AppError::auth("no"), StatusCode::UNAUTHORIZED, false),
+        (AppError::forbidden("no"), StatusCode::FORBIDDEN, false),
+        (AppError::not_found("team"), StatusCode::NOT_FOUND, false),
+        (
+            AppError::conflict_with_code("taken", None, "USER_001"),
+            StatusCode::CONFLICT,
+            false,
+        ),
+        (serialization_failure(), StatusCode::CONFLICT, true),
+        (
+            AppError::Database(diesel::result::Error::NotFound),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            false,
+        ),
+        (
+            AppError::internal(format!("step {} failed", 3)),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            false,
+        ),
+    ];
+    for (error, status, retryable) in cases {
+        assert_eq!(error.code().status(), status, "{}", error);
+        assert_eq!(error.code().retryable(), retryable, "{}", error);
+    }
+
+    let mapper = WebSocketErrorMapper::default();
+    let conflict = mapper.map_app_error(&serialization_failure());
+    assert_eq!(conflict.code, ErrorCode::TransactionConflict.ws_code());
+    assert_eq!(conflict.message, "Transaction conflict, please retry");
+    assert_eq!(
+        mapper.map_app_error(&AppError::forbidden("no")).code,
+        WebSocketErrorCode::PermissionDenied
+    );
+}
+
+#[test]
+fn status_only_errors_get_a_code_and_retry_hint() {
+    let limited = ApiResponse::<()>::error(429, "Too many requests", vec![]).with_retry_after(7);
+    let body = serde_json::to_value(&limited).unwrap();
+    assert_eq!(body["error_code"], "RATE_LIMITED");
+    assert_eq!(body["retryable"], true);
+    assert_eq!(body["retry_after_secs"], 7);
+    assert!(body.get("trace_id").is_none());
+
+    let ok = serde_json::to_value(ApiResponse::success(1, "ok")).unwrap();
+    assert!(ok.get("error_code").is_none());
+    assert!(ok.get("retryable").is_none());
+}
+
+#[tokio::test]
+async fn error_bodies_carry_the_request_id() {
+    let app = Router::new()
+        .route(
+            "/conflict",
+            get(|| async { Err::<(), _>(serialization_failure()) }),
+        )
+        .layer(from_fn(request_tracking_middleware));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/conflict")
+                .header("x-request-id", "req-42")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let mut response_body = response.into_body();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response_body.data().await {
+        bytes.extend_from_slice(&chunk.unwrap());
+    }
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["code"], 409);
+    assert_eq!(body["error_code"], "TRANSACTION_CONFLICT");
+    assert_eq!(body["trace_id"], "req-42");
+    assert_eq!(body["retryable"], true);
+    assert_eq!(body["errors"][0]["code"], "TRANSACTION_CONFLICT");
+}
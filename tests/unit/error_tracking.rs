@@ -0,0 +1,166 @@
+use rust_backend::db::models::error_tracking::{ErrorEvent, error_levels};
+use rust_backend::db::models::workflow::{WorkflowState, WorkflowStateCategory};
+use rust_backend::services::error_tracking_service::ErrorTrackingService;
+use uuid::Uuid;
+
+fn event(value: serde_json::Value) -> ErrorEvent {
+    serde_json::from_value(value).unwrap()
+}
+
+fn state(category: WorkflowStateCategory, is_default: bool) -> WorkflowState {
+    WorkflowState {
+        id: Uuid::new_v4(),
+        workflow_id: Uuid::new_v4(),
+        name: category.as_str().to_string(),
+        description: None,
+        color: None,
+        category,
+        position: 0,
+        is_default,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    }
+}
+
+#[test]
+fn exceptions_group_by_type_and_stack_not_message() {
+    let first = event(serde_json::json!({
+        "exception": { "values": [{
+            "type": "TypeError",
+            "value": "Cannot read properties of undefined (reading 'id')",
+            "stacktrace": { "frames": [
+                { "filename": "node_modules/react.js", "function": "render", "lineno": 10 },
+                { "filename": "src/app.js", "function": "loadUser", "lineno": 42, "in_app": true }
+            ]}
+        }]}
+    }));
+    let moved = event(serde_json::json!({
+        "exception": { "values": [{
+            "type": "TypeError",
+            "value": "Cannot read properties of null (reading 'id')",
+            "stacktrace": { "frames": [
+                { "filename": "node_modules/react.js", "function": "render", "lineno": 12 },
+                { "filename": "src/app.js", "function": "loadUser", "lineno": 57, "in_app": true }
+            ]}
+        }]}
+    }));
+    let other = event(serde_json::json!({
+        "exception": { "values": [{ "type": "RangeError", "value": "Invalid array length" }] }
+    }));
+
+    let fingerprint = ErrorTrackingService::fingerprint(&first).unwrap();
+    assert_eq!(fingerprint.len(), 64);
+    assert_eq!(
+        ErrorTrackingService::fingerprint(&moved).unwrap(),
+        fingerprint
+    );
+    assert_ne!(
+        ErrorTrackingService::fingerprint(&other).unwrap(),
+        fingerprint
+    );
+    assert!(ErrorTrackingService::fingerprint(&ErrorEvent::default()).is_none());
+}
+
+#[test]
+fn client_fingerprints_override_and_extend_the_default() {
+    let base = serde_json::json!({ "message": "Payment provider timed out" });
+    let default = ErrorTrackingService::fingerprint(&event(base.clone())).unwrap();
+
+    let mut custom = base.clone();
+    custom["fingerprint"] = serde_json::json!(["payments", "timeout"]);
+    let custom = event(custom);
+    assert_ne!(ErrorTrackingService::fingerprint(&custom).unwrap(), default);
+    let same_custom = event(serde_json::json!({
+        "message": "Something else",
+        "fingerprint": ["payments", "timeout"]
+    }));
+    assert_eq!(
+        ErrorTrackingService::fingerprint(&same_custom).unwrap(),
+        ErrorTrackingService::fingerprint(&custom).unwrap()
+    );
+
+    let mut extended = base;
+    extended["fingerprint"] = serde_json::json!(["{{ default }}"]);
+    assert_eq!(
+        ErrorTrackingService::fingerprint(&event(extended)).unwrap(),
+        default
+    );
+}
+
+#[test]
+fn titles_levels_and_priorities() {
+    let raised = event(serde_json::json!({
+        "exception": { "values": [{ "type": "KeyError", "value": "'user_id'\nmore" }] }
+    }));
+    assert_eq!(
+        ErrorTrackingService::title(&raised).as_deref(),
+        Some("KeyError: 'user_id'")
+    );
+    let logged = event(serde_json::json!({ "message": "\nDisk almost full\nsda1" }));
+    assert_eq!(
+        ErrorTrackingService::title(&logged).as_deref(),
+        Some("Disk almost full")
+    );
+    let long = event(serde_json::json!({ "message": "x".repeat(400) }));
+    assert_eq!(
+        ErrorTrackingService::title(&long).unwrap().chars().count(),
+        255
+    );
+
+    assert_eq!(
+        ErrorTrackingService::normalize_level(Some("FATAL")),
+        error_levels::FATAL
+    );
+    assert_eq!(
+        ErrorTrackingService::normalize_level(Some("warn")),
+        error_levels::WARNING
+    );
+    assert_eq!(
+        ErrorTrackingService::normalize_level(None),
+        error_levels::ERROR
+    );
+    assert_eq!(
+        ErrorTrackingService::priority_for_level(error_levels::FATAL),
+        "urgent"
+    );
+    assert_eq!(
+        ErrorTrackingService::priority_for_level(error_levels::DEBUG),
+        "low"
+    );
+}
+
+#[test]
+fn sentry_auth_header_carries_the_key() {
+    assert_eq!(
+        ErrorTrackingService::sentry_key(
+            "Sentry sentry_version=7, sentry_client=sentry.python/1.0, sentry_key=errk_abc"
+        ),
+        Some("errk_abc")
+    );
+    assert_eq!(
+        ErrorTrackingService::sentry_key("Sentry sentry_version=7"),
+        None
+    );
+}
+
+#[test]
+fn resolved_issues_reopen_into_an_open_state() {
+    let done = state(WorkflowStateCategory::Completed, true);
+    let backlog = state(WorkflowStateCategory::Backlog, false);
+    let todo = state(WorkflowStateCategory::Unstarted, false);
+    let states = vec![done.clone(), backlog.clone(), todo.clone()];
+
+    // A closed default state is skipped; unstarted comes before backlog
+    assert_eq!(
+        ErrorTrackingService::open_state(&states).unwrap().id,
+        todo.id
+    );
+    let default_backlog = state(WorkflowStateCategory::Backlog, true);
+    assert_eq!(
+        ErrorTrackingService::open_state(&[todo, default_backlog.clone()])
+            .unwrap()
+            .id,
+        default_backlog.id
+    );
+    assert!(ErrorTrackingService::open_state(&[done]).is_none());
+}
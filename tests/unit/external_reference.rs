@@ -0,0 +1,45 @@
+use rust_backend::db::models::app_installation::webhook_events;
+use rust_backend::services::external_references_service::ExternalReferencesService;
+use rust_backend::services::webhook_service::WebhookService;
+
+#[test]
+fn sources_are_lower_case_slugs() {
+    assert_eq!(
+        ExternalReferencesService::normalize_source(" Zendesk ").unwrap(),
+        "zendesk"
+    );
+    assert_eq!(
+        ExternalReferencesService::normalize_source("sentry_eu-1").unwrap(),
+        "sentry_eu-1"
+    );
+    assert!(ExternalReferencesService::normalize_source("").is_err());
+    assert!(ExternalReferencesService::normalize_source("sales force").is_err());
+    assert!(ExternalReferencesService::normalize_source(&"a".repeat(33)).is_err());
+}
+
+#[test]
+fn external_ids_and_urls_are_validated() {
+    assert_eq!(
+        ExternalReferencesService::normalize_external_id("  4711 ").unwrap(),
+        "4711"
+    );
+    assert!(ExternalReferencesService::normalize_external_id("   ").is_err());
+    assert!(ExternalReferencesService::normalize_external_id(&"x".repeat(256)).is_err());
+
+    assert!(
+        ExternalReferencesService::validate_url("https://acme.zendesk.com/agent/tickets/4711")
+            .is_ok()
+    );
+    assert!(ExternalReferencesService::validate_url("http://sentry.internal/issues/9").is_ok());
+    assert!(ExternalReferencesService::validate_url("javascript:alert(1)").is_err());
+    assert!(ExternalReferencesService::validate_url("/tickets/4711").is_err());
+}
+
+#[test]
+fn issue_webhook_samples_include_external_references() {
+    let sample =
+        WebhookService::sample_data(webhook_events::ISSUE_UPDATED, uuid::Uuid::new_v4()).unwrap();
+    let references = sample["external_references"].as_array().unwrap();
+    assert_eq!(references[0]["source"], "zendesk");
+    assert_eq!(references[0]["issue_id"], sample["id"]);
+}
@@ -0,0 +1,102 @@
+// Sparse fieldset tests for list responses
+
+use rust_backend::db::models::auth::UserBasicInfo;
+use rust_backend::db::models::issue::IssueResponse;
+use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+use rust_backend::routes::workspace_members::WorkspaceMemberInfo;
+use rust_backend::services::field_selection::FieldSelection;
+use rust_backend::websocket::commands::types::IssueFilters;
+use uuid::Uuid;
+
+fn member() -> WorkspaceMemberInfo {
+    let user_id = Uuid::new_v4();
+    let now = chrono::Utc::now().naive_utc();
+    WorkspaceMemberInfo {
+        id: Uuid::new_v4(),
+        user_id,
+        workspace_id: Uuid::new_v4(),
+        role: WorkspaceMemberRole::Member,
+        user: UserBasicInfo {
+            id: user_id,
+            name: "Ada".to_string(),
+            username: "ada".to_string(),
+            email: "ada@example.com".to_string(),
+            avatar_url: None,
+        },
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[test]
+fn fields_are_parsed_from_the_query_and_checked() {
+    let allowed = IssueResponse::FIELDS;
+    assert!(FieldSelection::from_query(None, allowed).unwrap().is_all());
+    assert!(
+        FieldSelection::from_query(Some(" , "), allowed)
+            .unwrap()
+            .is_all()
+    );
+
+    let fields = FieldSelection::from_query(Some("title, identifier,title"), allowed).unwrap();
+    assert!(!fields.is_all());
+    assert!(fields.includes("id"));
+    assert!(fields.includes("title"));
+    assert!(fields.includes("identifier"));
+    assert!(!fields.includes("description"));
+
+    let only_id = FieldSelection::from_query(Some("id"), allowed).unwrap();
+    assert!(!only_id.is_all());
+    assert!(!only_id.includes("title"));
+
+    assert!(FieldSelection::from_query(Some("title,secret"), allowed).is_err());
+}
+
+#[test]
+fn projection_keeps_only_the_selected_fields() {
+    let members = vec![member(), member()];
+    let all = FieldSelection::default().project(&members).unwrap();
+    assert_eq!(all, serde_json::to_value(&members).unwrap());
+
+    let fields = FieldSelection::from_names(["role", "user"], WorkspaceMemberInfo::FIELDS).unwrap();
+    let projected = fields.project(&members).unwrap();
+    for (item, member) in projected.as_array().unwrap().iter().zip(&members) {
+        let keys: Vec<&str> = item
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(keys.len(), 3);
+        assert_eq!(item["id"], serde_json::json!(member.id));
+        assert_eq!(item["role"], serde_json::json!(member.role));
+        assert_eq!(item["user"]["username"], serde_json::json!("ada"));
+    }
+}
+
+#[test]
+fn member_fields_list_every_serialized_field() {
+    let value = serde_json::to_value(member()).unwrap();
+    let mut keys: Vec<&str> = value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    let mut fields = WorkspaceMemberInfo::FIELDS.to_vec();
+    keys.sort_unstable();
+    fields.sort_unstable();
+    assert_eq!(keys, fields);
+}
+
+#[test]
+fn ws_issue_queries_accept_a_field_list() {
+    let filters: IssueFilters =
+        serde_json::from_value(serde_json::json!({ "fields": ["title", "priority"] })).unwrap();
+    assert_eq!(
+        filters.fields,
+        Some(vec!["title".to_string(), "priority".to_string()])
+    );
+    let filters: IssueFilters = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert!(filters.fields.is_none());
+}
@@ -0,0 +1,85 @@
+// GitHub integration issue key parsing, payload and signature tests
+
+use rust_backend::db::models::github_integration::{GithubPullRequestEvent, GithubPushEvent};
+use rust_backend::services::github_integration_service::GithubIntegrationService;
+use rust_backend::services::webhook_service::WebhookService;
+
+#[test]
+fn issue_keys_are_found_in_messages_and_branches() {
+    let teams = ["ENG", "DES"];
+    assert_eq!(
+        GithubIntegrationService::find_issue_keys("Fix login redirect (ENG-123)", &teams),
+        vec![(0, 123)]
+    );
+    assert_eq!(
+        GithubIntegrationService::find_issue_keys("eng-42-fix-login\nDES-7, ENG-42", &teams),
+        vec![(0, 42), (1, 7)]
+    );
+    assert_eq!(
+        GithubIntegrationService::find_issue_keys("feature/des-9", &teams),
+        vec![(1, 9)]
+    );
+}
+
+#[test]
+fn issue_keys_need_a_boundary_and_a_number() {
+    let teams = ["ENG"];
+    assert!(GithubIntegrationService::find_issue_keys("BENG-12 and ENG-", &teams).is_empty());
+    assert!(GithubIntegrationService::find_issue_keys("ENG-x", &teams).is_empty());
+    assert!(GithubIntegrationService::find_issue_keys("ENG-123", &[]).is_empty());
+}
+
+#[test]
+fn github_signatures_verify_with_the_shared_secret() {
+    // Example from GitHub's webhook documentation
+    let secret = "It's a Secret to Everybody";
+    let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+    assert!(WebhookService::verify(secret, "Hello, World!", signature));
+    assert!(!WebhookService::verify(secret, "Hello, World?", signature));
+}
+
+#[test]
+fn webhook_url_names_the_workspace() {
+    let workspace_id = uuid::Uuid::new_v4();
+    assert_eq!(
+        GithubIntegrationService::webhook_url("https://api.example.com/", workspace_id),
+        format!(
+            "https://api.example.com/integrations/github/webhook?workspace_id={}",
+            workspace_id
+        )
+    );
+}
+
+#[test]
+fn github_payloads_parse() {
+    let push: GithubPushEvent = serde_json::from_value(serde_json::json!({
+        "ref": "refs/heads/main",
+        "repository": { "full_name": "acme/app", "private": true },
+        "commits": [{
+            "id": "0d1a26e67d8f5eaf1f6ba5c57fc3c7d91ac0fd1c",
+            "message": "ENG-1 Fix crash\n\nDetails",
+            "url": "https://github.com/acme/app/commit/0d1a26e",
+            "author": { "name": "Ada", "email": "ada@example.com", "username": "ada" }
+        }]
+    }))
+    .unwrap();
+    assert_eq!(push.commits[0].author.username.as_deref(), Some("ada"));
+
+    let pull: GithubPullRequestEvent = serde_json::from_value(serde_json::json!({
+        "action": "closed",
+        "number": 5,
+        "repository": { "full_name": "acme/app" },
+        "pull_request": {
+            "number": 5,
+            "title": "Fix crash",
+            "html_url": "https://github.com/acme/app/pull/5",
+            "state": "closed",
+            "merged": true,
+            "user": { "login": "ada" },
+            "head": { "ref": "eng-1-fix-crash", "sha": "0d1a26e" }
+        }
+    }))
+    .unwrap();
+    assert!(pull.pull_request.merged);
+    assert_eq!(pull.pull_request.head.ref_name, "eng-1-fix-crash");
+}
@@ -0,0 +1,42 @@
+use rust_backend::error::AppError;
+use rust_backend::graphql::{MAX_QUERY_DEPTH, build_schema, graphql_error};
+
+#[test]
+fn graphql_schema_exposes_issue_relations() {
+    let sdl = build_schema().sdl();
+    for field in [
+        "issues(",
+        "assignee: User",
+        "team: Team",
+        "comments: [Comment!]!",
+        "owner: User",
+    ] {
+        assert!(sdl.contains(field), "schema is missing `{}`", field);
+    }
+    // Read-only facade
+    assert!(!sdl.contains("type Mutation"));
+}
+
+#[tokio::test]
+async fn graphql_rejects_queries_nested_too_deeply() {
+    let mut query = String::from("id");
+    for _ in 0..MAX_QUERY_DEPTH {
+        query = format!("team {{ issues {{ {} }} }}", query);
+    }
+    let response = build_schema()
+        .execute(format!("{{ issues {{ {} }} }}", query))
+        .await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(response.errors[0].message.contains("nested too deep"));
+}
+
+#[test]
+fn graphql_errors_keep_api_error_codes_and_hide_internals() {
+    let err = graphql_error(AppError::conflict_with_code("Taken", None, "NAME_TAKEN"));
+    assert_eq!(err.message, "Taken");
+    let code = err.extensions.as_ref().and_then(|e| e.get("code")).cloned();
+    assert_eq!(code, Some(async_graphql::Value::from("NAME_TAKEN")));
+
+    let err = graphql_error(AppError::internal("connection refused on 10.0.0.3"));
+    assert_eq!(err.message, "Internal server error");
+}
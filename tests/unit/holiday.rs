@@ -0,0 +1,137 @@
+// Validation and calendar math tests for workspace holidays
+
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn validate_holiday_rules() {
+    use rust_backend::validation::holiday::{
+        UpdateHolidayChanges, validate_create_holiday, validate_update_holiday,
+    };
+    assert!(validate_create_holiday("New Year", &Some("CN".to_string())).is_ok());
+    assert!(validate_create_holiday(" ", &None).is_err());
+    assert!(validate_create_holiday("New Year", &Some("C N".to_string())).is_err());
+
+    let c = UpdateHolidayChanges {
+        name: None,
+        date_present: false,
+        region: None,
+    };
+    assert!(validate_update_holiday(&c).is_err());
+
+    let c = UpdateHolidayChanges {
+        name: None,
+        date_present: true,
+        region: Some("US-CA"),
+    };
+    assert!(validate_update_holiday(&c).is_ok());
+}
+
+#[test]
+fn parse_ics_holiday_calendar() {
+    use rust_backend::utils::ics::parse_ics_holidays;
+
+    let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+BEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20250101\r\nDTEND;VALUE=DATE:20250102\r\nSUMMARY:New Year's Day\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20251001\r\nDTEND;VALUE=DATE:20251004\r\nSUMMARY:National\r\n  Day\\, Golden Week\r\nEND:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let holidays = parse_ics_holidays(ics).unwrap();
+    assert_eq!(holidays.len(), 4);
+    assert_eq!(holidays[0].name, "New Year's Day");
+    assert_eq!(holidays[0].date, date(2025, 1, 1));
+    assert_eq!(holidays[1].name, "National Day, Golden Week");
+    assert_eq!(holidays[3].date, date(2025, 10, 3));
+
+    assert!(parse_ics_holidays("not a calendar").is_err());
+    assert!(
+        parse_ics_holidays("BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:x\nEND:VEVENT\nEND:VCALENDAR")
+            .is_err()
+    );
+}
+
+#[test]
+fn count_working_days_skips_weekends_and_holidays() {
+    use rust_backend::services::holidays_service::HolidaysService;
+
+    // 2025-09-01 is a Monday; two full weeks contain 10 weekdays
+    let start = date(2025, 9, 1);
+    let end = date(2025, 9, 14);
+    assert_eq!(
+        HolidaysService::count_working_days(start, end, &HashSet::new()),
+        10
+    );
+
+    let holidays: HashSet<NaiveDate> = [date(2025, 9, 3), date(2025, 9, 6)].into_iter().collect();
+    // Only the Wednesday counts; the Saturday holiday is already a weekend day
+    assert_eq!(
+        HolidaysService::count_working_days(start, end, &holidays),
+        9
+    );
+
+    assert_eq!(
+        HolidaysService::count_working_days(end, start, &HashSet::new()),
+        0
+    );
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn holiday_names_are_trimmed_before_the_duplicate_check() {
+    use super::test_db;
+    use rust_backend::error::AppError;
+    use rust_backend::routes::holidays::{
+        CreateHolidayRequest, ImportHolidaysRequest, UpdateHolidayRequest,
+    };
+    use rust_backend::services::holidays_service::HolidaysService;
+
+    let mut conn = test_db::connect();
+    let owner = test_db::user(&mut conn);
+    let workspace = test_db::workspace(&mut conn, owner);
+    let ctx = test_db::ctx(owner, workspace);
+    let create = |name: &str, holiday_date| CreateHolidayRequest {
+        name: name.to_string(),
+        holiday_date,
+        region: None,
+    };
+
+    let new_year =
+        HolidaysService::create(&mut conn, &ctx, &create("New Year", date(2025, 1, 1))).unwrap();
+    assert!(matches!(
+        HolidaysService::create(&mut conn, &ctx, &create("New Year ", date(2025, 1, 1))),
+        Err(AppError::Conflict { .. })
+    ));
+
+    let other =
+        HolidaysService::create(&mut conn, &ctx, &create("Other", date(2025, 1, 1))).unwrap();
+    let renamed = HolidaysService::update(
+        &mut conn,
+        &ctx,
+        other.id,
+        &UpdateHolidayRequest {
+            name: Some(" New Year".to_string()),
+            holiday_date: None,
+            region: None,
+        },
+    );
+    assert!(matches!(renamed, Err(AppError::Conflict { .. })));
+
+    let imported = HolidaysService::import_ics(
+        &mut conn,
+        &ctx,
+        &ImportHolidaysRequest {
+            ics: "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20250101\r\n\
+SUMMARY:New Year \r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"
+                .to_string(),
+            region: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(imported.imported, 0);
+    assert_eq!(imported.skipped, 1);
+    assert_eq!(new_year.name, "New Year");
+}
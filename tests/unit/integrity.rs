@@ -0,0 +1,15 @@
+// Sanity tests for the integrity check catalogue
+
+use std::collections::HashSet;
+
+#[test]
+fn integrity_checks_are_well_formed() {
+    use rust_backend::services::integrity_service::INTEGRITY_CHECKS;
+
+    let mut names = HashSet::new();
+    for check in INTEGRITY_CHECKS {
+        assert!(names.insert(check.name), "duplicate check {}", check.name);
+        assert!(check.count_sql.contains("AS count"), "{}", check.name);
+        assert!(!check.description.is_empty());
+    }
+}
@@ -5,3 +5,20 @@ fn validate_invite_email_rules() {
     assert!(validate_invite_email("").is_err());
     assert!(validate_invite_email("no-at.com").is_err());
 }
+
+#[test]
+fn bulk_invite_entries_default_to_no_role() {
+    use rust_backend::db::models::invitation::BulkInviteRequest;
+    use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+
+    let req: BulkInviteRequest = serde_json::from_value(serde_json::json!({
+        "invitations": [
+            {"email": "ada@example.com"},
+            {"email": "bob@example.com", "role": "Admin"}
+        ]
+    }))
+    .unwrap();
+    assert_eq!(req.invitations.len(), 2);
+    assert!(req.invitations[0].role.is_none());
+    assert_eq!(req.invitations[1].role, Some(WorkspaceMemberRole::Admin));
+}
@@ -1,4 +1,4 @@
-// Validation-only tests for issues
+// Validation, read receipt and search tests for issues
 
 #[test]
 fn validate_issue_create_and_update() {
@@ -13,3 +13,116 @@ fn validate_issue_create_and_update() {
     assert!(validate_update_issue(&None, &Some("Desc".to_string())).is_ok());
     assert!(validate_update_issue(&None, &None).is_err());
 }
+
+#[test]
+fn issue_unread_tracks_last_view() {
+    use rust_backend::db::models::issue::{Issue, IssueResponse};
+    let updated_at = chrono::Utc::now();
+    let issue = Issue {
+        id: uuid::Uuid::new_v4(),
+        project_id: None,
+        cycle_id: None,
+        creator_id: uuid::Uuid::new_v4(),
+        assignee_id: None,
+        parent_issue_id: None,
+        issue_number: 1,
+        title: "Title".to_string(),
+        description: None,
+        priority: "none".to_string(),
+        is_changelog_candidate: false,
+        created_at: updated_at,
+        updated_at,
+        team_id: uuid::Uuid::new_v4(),
+        workflow_id: None,
+        workflow_state_id: None,
+        archived_at: None,
+        archive_batch_id: None,
+        estimate: None,
+    };
+
+    let mut resp = IssueResponse::from(issue);
+    resp.set_last_viewed(None);
+    assert_eq!(resp.unread, Some(true));
+
+    resp.set_last_viewed(Some(updated_at - chrono::Duration::minutes(5)));
+    assert_eq!(resp.unread, Some(true));
+
+    resp.set_last_viewed(Some(updated_at + chrono::Duration::minutes(5)));
+    assert_eq!(resp.unread, Some(false));
+    assert!(resp.last_viewed_at.is_some());
+}
+
+#[test]
+fn issue_search_query_and_limit_are_normalized() {
+    use rust_backend::services::search_service::{
+        DEFAULT_SEARCH_LIMIT, MAX_QUERY_LENGTH, MAX_SEARCH_LIMIT, SearchService,
+    };
+
+    assert_eq!(
+        SearchService::normalize_query("  login bug ").unwrap(),
+        "login bug"
+    );
+    assert!(SearchService::normalize_query("   ").is_err());
+    assert!(SearchService::normalize_query(&"a".repeat(MAX_QUERY_LENGTH + 1)).is_err());
+
+    assert_eq!(SearchService::clamp_limit(None), DEFAULT_SEARCH_LIMIT);
+    assert_eq!(SearchService::clamp_limit(Some(0)), 1);
+    assert_eq!(SearchService::clamp_limit(Some(1000)), MAX_SEARCH_LIMIT);
+}
+
+#[test]
+fn issue_search_ws_filters_accept_limit() {
+    use rust_backend::websocket::commands::IssueFilters;
+
+    let filters: IssueFilters =
+        serde_json::from_str(r#"{"search": "crash on save", "limit": 5}"#).unwrap();
+    assert_eq!(filters.search.as_deref(), Some("crash on save"));
+    assert_eq!(filters.limit, Some(5));
+
+    let filters: IssueFilters = serde_json::from_str(r#"{"search": ""}"#).unwrap();
+    assert_eq!(filters.search, None);
+    assert_eq!(filters.limit, None);
+    assert!(!filters.include_archived);
+
+    let filters: IssueFilters = serde_json::from_str(r#"{"include_archived": true}"#).unwrap();
+    assert!(filters.include_archived);
+}
+
+#[test]
+fn new_issues_without_a_state_go_to_a_triage_state() {
+    use rust_backend::db::models::workflow::{WorkflowState, WorkflowStateCategory};
+    use rust_backend::services::issues_service::IssuesService;
+
+    let state = |workflow_id: uuid::Uuid, category, position, is_default| WorkflowState {
+        id: uuid::Uuid::new_v4(),
+        workflow_id,
+        name: format!("{:?}", category),
+        description: None,
+        color: None,
+        category,
+        position,
+        is_default,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    };
+    let (main, other) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let states = [
+        state(main, WorkflowStateCategory::Backlog, 0, true),
+        state(other, WorkflowStateCategory::Triage, 0, false),
+        state(main, WorkflowStateCategory::Triage, 1, true),
+        state(main, WorkflowStateCategory::Started, 2, true),
+    ];
+
+    // Default states are preferred when the issue has no workflow
+    assert_eq!(
+        IssuesService::triage_state(&states, None).map(|s| s.id),
+        Some(states[2].id)
+    );
+    // An issue with a workflow stays in it
+    assert_eq!(
+        IssuesService::triage_state(&states, Some(other)).map(|s| s.id),
+        Some(states[1].id)
+    );
+    assert!(IssuesService::triage_state(&states[..1], None).is_none());
+    assert!(IssuesService::triage_state(&states, Some(uuid::Uuid::new_v4())).is_none());
+}
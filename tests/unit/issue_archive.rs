@@ -0,0 +1,76 @@
+use chrono::{Duration, Utc};
+use rust_backend::db::models::issue_archive::{
+    BulkArchiveRequest, IssueArchiveBatch, IssueArchiveFilter, issue_archive_status,
+};
+use rust_backend::services::issue_archive_service::{IssueArchiveService, UNDO_WINDOW_HOURS};
+use uuid::Uuid;
+
+#[test]
+fn bulk_archive_is_a_dry_run_unless_confirmed() {
+    let team_id = Uuid::new_v4();
+    let request: BulkArchiveRequest =
+        serde_json::from_value(serde_json::json!({ "filter": { "team_id": team_id } })).unwrap();
+    assert!(request.dry_run);
+    assert_eq!(request.filter.team_id, Some(team_id));
+
+    let request: BulkArchiveRequest = serde_json::from_value(serde_json::json!({
+        "filter": { "priority": "low" },
+        "dry_run": false,
+    }))
+    .unwrap();
+    assert!(!request.dry_run);
+}
+
+#[test]
+fn archive_filter_needs_a_valid_condition() {
+    assert!(IssueArchiveService::validate_filter(&IssueArchiveFilter::default()).is_err());
+
+    let filter = IssueArchiveFilter {
+        priority: Some("whenever".to_string()),
+        ..Default::default()
+    };
+    assert!(IssueArchiveService::validate_filter(&filter).is_err());
+
+    let filter = IssueArchiveFilter {
+        priority: Some("low".to_string()),
+        updated_before: Some(Utc::now() - Duration::days(90)),
+        ..Default::default()
+    };
+    assert!(IssueArchiveService::validate_filter(&filter).is_ok());
+}
+
+#[test]
+fn undo_window_starts_when_the_batch_completes() {
+    let completed_at = Utc::now();
+    let mut batch = IssueArchiveBatch {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        requested_by: Uuid::new_v4(),
+        filter: "{}".to_string(),
+        status: issue_archive_status::RUNNING.to_string(),
+        issue_count: 0,
+        created_at: completed_at,
+        completed_at: None,
+        undone_at: None,
+    };
+    assert_eq!(IssueArchiveService::undo_until(&batch), None);
+
+    batch.completed_at = Some(completed_at);
+    assert_eq!(
+        IssueArchiveService::undo_until(&batch),
+        Some(completed_at + Duration::hours(UNDO_WINDOW_HOURS))
+    );
+}
+
+#[test]
+fn archive_jobs_are_told_apart_from_other_tasks() {
+    let batch_id = Uuid::new_v4();
+    assert_eq!(
+        IssueArchiveService::parse_job(&format!("issue_archive:{}", batch_id)),
+        Some(batch_id)
+    );
+    assert_eq!(
+        IssueArchiveService::parse_job(&format!("member_import:{}", batch_id)),
+        None
+    );
+}
@@ -0,0 +1,191 @@
+// Batched enrichment of issue list responses
+
+use rust_backend::db::enums::LabelLevel;
+use rust_backend::db::models::auth::UserBasicInfo;
+use rust_backend::db::models::issue::Issue;
+use rust_backend::db::models::label::Label;
+use rust_backend::db::models::team::TeamBasicInfo;
+use rust_backend::db::models::workflow::{WorkflowState, WorkflowStateCategory};
+use rust_backend::services::issue_enrichment::{EnrichmentKeys, IssueEnrichment};
+use uuid::Uuid;
+
+fn issue(number: i32, team_id: Uuid) -> Issue {
+    Issue {
+        id: Uuid::new_v4(),
+        project_id: None,
+        cycle_id: None,
+        creator_id: Uuid::new_v4(),
+        assignee_id: None,
+        parent_issue_id: None,
+        issue_number: number,
+        title: format!("Issue {}", number),
+        description: None,
+        priority: "none".to_string(),
+        is_changelog_candidate: false,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        team_id,
+        workflow_id: None,
+        workflow_state_id: None,
+        archived_at: None,
+        archive_batch_id: None,
+        estimate: None,
+    }
+}
+
+fn state(workflow_id: Uuid, name: &str, position: i32) -> WorkflowState {
+    WorkflowState {
+        id: Uuid::new_v4(),
+        workflow_id,
+        name: name.to_string(),
+        description: None,
+        color: None,
+        category: WorkflowStateCategory::Unstarted,
+        position,
+        is_default: true,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    }
+}
+
+/// A page of `count` issues spread over a few teams, workflows, assignees
+/// and projects, like a real workspace
+fn page(count: usize) -> Vec<Issue> {
+    let teams: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+    let workflows: Vec<Uuid> = (0..2).map(|_| Uuid::new_v4()).collect();
+    let users: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+    let projects: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+    (0..count)
+        .map(|n| {
+            let mut issue = issue(n as i32, teams[n % teams.len()]);
+            issue.workflow_id = (n % 3 != 0).then(|| workflows[n % workflows.len()]);
+            issue.assignee_id = (n % 2 == 0).then(|| users[n % users.len()]);
+            issue.project_id = (n % 4 != 0).then(|| projects[n % projects.len()]);
+            issue
+        })
+        .collect()
+}
+
+#[test]
+fn keys_are_distinct_per_kind() {
+    let issues = page(60);
+    let keys = EnrichmentKeys::collect(&issues);
+    assert_eq!(keys.issue_ids.len(), 60);
+    assert_eq!(keys.team_ids.len(), 4);
+    assert_eq!(keys.workflow_ids.len(), 2);
+    assert_eq!(keys.default_state_team_ids.len(), 4);
+    assert_eq!(keys.assignee_ids.len(), 5);
+    assert_eq!(keys.project_ids.len(), 3);
+
+    assert_eq!(EnrichmentKeys::collect(&[]).query_count(), 0);
+}
+
+#[test]
+fn query_count_stays_flat_as_pages_grow() {
+    // Per-row lookups ran the last-view query plus a team and a states query
+    // for every issue, and the single-issue view adds assignee, labels and
+    // project (with status, owner and statuses) on top
+    let per_row = |issues: usize| 1 + issues * 8;
+
+    let mut previous = None;
+    for size in [10, 100, 1000] {
+        let issues = page(size);
+        let batched = EnrichmentKeys::collect(&issues).query_count();
+        assert_eq!(batched, 8, "{} issues", size);
+        assert!(batched * 10 <= per_row(size), "{} issues", size);
+        if let Some(previous) = previous {
+            assert_eq!(batched, previous);
+        }
+        previous = Some(batched);
+    }
+
+    // Kinds without any ids are skipped
+    let team = Uuid::new_v4();
+    assert_eq!(
+        EnrichmentKeys::collect(&[issue(1, team), issue(2, team)]).query_count(),
+        4
+    );
+}
+
+#[test]
+fn related_rows_are_joined_in_memory() {
+    let team_id = Uuid::new_v4();
+    let workflow_id = Uuid::new_v4();
+    let default_workflow = Uuid::new_v4();
+    let assignee = Uuid::new_v4();
+
+    let mut with_workflow = issue(42, team_id);
+    with_workflow.workflow_id = Some(workflow_id);
+    with_workflow.assignee_id = Some(assignee);
+    let plain = issue(43, team_id);
+    let mut missing_assignee = issue(44, Uuid::new_v4());
+    missing_assignee.assignee_id = Some(Uuid::new_v4());
+
+    let mut enrichment = IssueEnrichment::default();
+    enrichment.teams.insert(
+        team_id,
+        TeamBasicInfo {
+            id: team_id,
+            name: "Engineering".to_string(),
+            team_key: "ENG".to_string(),
+            description: None,
+            icon_url: None,
+            is_private: false,
+        },
+    );
+    enrichment.workflow_states.insert(
+        workflow_id,
+        vec![state(workflow_id, "Todo", 1), state(workflow_id, "Done", 2)],
+    );
+    enrichment
+        .default_states
+        .insert(team_id, vec![state(default_workflow, "Backlog", 0)]);
+    enrichment.users.insert(
+        assignee,
+        UserBasicInfo {
+            id: assignee,
+            name: "Ada".to_string(),
+            username: "ada".to_string(),
+            email: "ada@example.com".to_string(),
+            avatar_url: None,
+        },
+    );
+    enrichment.labels.insert(
+        with_workflow.id,
+        vec![Label {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            name: "bug".to_string(),
+            color: "#ff0000".to_string(),
+            level: LabelLevel::Issue,
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }],
+    );
+
+    let first = enrichment.apply(with_workflow);
+    assert_eq!(first.identifier.as_deref(), Some("ENG-42"));
+    assert_eq!(
+        first.team.as_ref().map(|t| t.name.as_str()),
+        Some("Engineering")
+    );
+    let states: Vec<&str> = first
+        .workflow_states
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(states, vec!["Todo", "Done"]);
+    assert_eq!(first.assignee.map(|a| a.username), Some("ada".to_string()));
+    assert_eq!(first.labels.len(), 1);
+    assert_eq!(first.unread, Some(true));
+
+    let second = enrichment.apply(plain);
+    assert_eq!(second.workflow_states.len(), 1);
+    assert_eq!(second.workflow_states[0].name, "Backlog");
+    assert!(second.labels.is_empty());
+
+    let third = enrichment.apply(missing_assignee);
+    assert!(third.team.is_none());
+    assert!(third.assignee.is_none());
+    assert!(third.workflow_states.is_empty());
+}
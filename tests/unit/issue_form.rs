@@ -0,0 +1,149 @@
+use rust_backend::db::models::issue_form::FormField;
+use rust_backend::services::issue_forms_service::IssueFormsService;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+fn fields(value: serde_json::Value) -> Vec<FormField> {
+    serde_json::from_value(value).unwrap()
+}
+
+fn answers(value: serde_json::Value) -> BTreeMap<String, serde_json::Value> {
+    serde_json::from_value(value).unwrap()
+}
+
+fn bug_report(bug: Uuid, crash: Uuid) -> Vec<FormField> {
+    fields(serde_json::json!([
+        { "key": "summary", "label": "Summary", "type": "text", "required": true, "maps_to": "title" },
+        { "key": "details", "label": "What happened", "type": "textarea", "maps_to": "description" },
+        { "key": "severity", "label": "Severity", "type": "select", "required": true, "maps_to": "priority",
+          "options": [{ "value": "low", "label": "Minor" }, { "value": "urgent", "label": "Blocking" }] },
+        { "key": "kind", "label": "Kind", "type": "multi_select", "maps_to": "labels",
+          "options": [
+              { "value": "bug", "label": "Bug", "label_id": bug },
+              { "value": "crash", "label": "Crash", "label_id": crash }
+          ] },
+        { "key": "browser", "label": "Browser", "type": "select",
+          "options": [{ "value": "ff", "label": "Firefox" }, { "value": "chrome", "label": "Chrome" }] },
+        { "key": "users", "label": "Users affected", "type": "number" },
+        { "key": "regression", "label": "Worked before", "type": "checkbox" }
+    ]))
+}
+
+#[test]
+fn form_needs_one_required_title_field() {
+    assert!(
+        IssueFormsService::validate_fields(&bug_report(Uuid::new_v4(), Uuid::new_v4())).is_ok()
+    );
+
+    let no_title = fields(serde_json::json!([
+        { "key": "details", "label": "Details", "type": "textarea", "maps_to": "description" }
+    ]));
+    assert!(IssueFormsService::validate_fields(&no_title).is_err());
+
+    let optional_title = fields(serde_json::json!([
+        { "key": "summary", "label": "Summary", "type": "text", "maps_to": "title" }
+    ]));
+    assert!(IssueFormsService::validate_fields(&optional_title).is_err());
+
+    let two_titles = fields(serde_json::json!([
+        { "key": "a", "label": "A", "type": "text", "required": true, "maps_to": "title" },
+        { "key": "b", "label": "B", "type": "text", "required": true, "maps_to": "title" }
+    ]));
+    assert!(IssueFormsService::validate_fields(&two_titles).is_err());
+}
+
+#[test]
+fn form_rejects_bad_keys_types_and_mappings() {
+    let title = serde_json::json!({ "key": "summary", "label": "Summary", "type": "text", "required": true, "maps_to": "title" });
+    let cases = [
+        serde_json::json!({ "key": "Bad Key", "label": "X", "type": "text" }),
+        serde_json::json!({ "key": "summary", "label": "Again", "type": "text" }),
+        serde_json::json!({ "key": "x", "label": "X", "type": "date" }),
+        serde_json::json!({ "key": "x", "label": "X", "type": "select", "options": [] }),
+        serde_json::json!({ "key": "x", "label": "X", "type": "select",
+            "options": [{ "value": "a", "label": "A" }, { "value": "a", "label": "A again" }] }),
+        serde_json::json!({ "key": "x", "label": "X", "type": "select", "maps_to": "priority",
+            "options": [{ "value": "critical", "label": "Critical" }] }),
+        serde_json::json!({ "key": "x", "label": "X", "type": "select", "maps_to": "labels",
+            "options": [{ "value": "a", "label": "A" }] }),
+        serde_json::json!({ "key": "x", "label": "X", "type": "number", "maps_to": "description" }),
+        serde_json::json!({ "key": "x", "label": "X", "type": "text", "maps_to": "assignee" }),
+    ];
+    for case in cases {
+        let form = fields(serde_json::json!([title.clone(), case.clone()]));
+        assert!(
+            IssueFormsService::validate_fields(&form).is_err(),
+            "accepted {}",
+            case
+        );
+    }
+}
+
+#[test]
+fn submission_maps_answers_onto_the_issue() {
+    let (bug, crash) = (Uuid::new_v4(), Uuid::new_v4());
+    let issue = IssueFormsService::validate_submission(
+        &bug_report(bug, crash),
+        &answers(serde_json::json!({
+            "summary": "  Checkout button does nothing ",
+            "details": "Clicking it shows a spinner forever.",
+            "severity": "urgent",
+            "kind": ["crash", "bug", "crash"],
+            "browser": "ff",
+            "users": 12,
+            "regression": true
+        })),
+    )
+    .unwrap();
+
+    assert_eq!(issue.title, "Checkout button does nothing");
+    assert_eq!(issue.priority.as_deref(), Some("urgent"));
+    assert_eq!(issue.label_ids, vec![crash, bug]);
+    assert_eq!(
+        issue.description.as_deref(),
+        Some(
+            "Clicking it shows a spinner forever.\n\n**Browser**\nFirefox\n\n**Users affected**\n12\n\n**Worked before**\nYes"
+        )
+    );
+}
+
+#[test]
+fn submission_reports_every_problem() {
+    let err = IssueFormsService::validate_submission(
+        &bug_report(Uuid::new_v4(), Uuid::new_v4()),
+        &answers(serde_json::json!({
+            "summary": "   ",
+            "kind": ["feature"],
+            "users": "many",
+            "regression": "yes",
+            "os": "linux"
+        })),
+    )
+    .unwrap_err()
+    .to_string();
+
+    for problem in [
+        "'os' is not a field of this form",
+        "Summary is required",
+        "Severity is required",
+        "Kind must only contain options of the field",
+        "Users affected must be a number",
+        "Worked before must be true or false",
+    ] {
+        assert!(err.contains(problem), "{} missing from {}", problem, err);
+    }
+}
+
+#[test]
+fn optional_fields_can_be_left_out() {
+    let issue = IssueFormsService::validate_submission(
+        &bug_report(Uuid::new_v4(), Uuid::new_v4()),
+        &answers(
+            serde_json::json!({ "summary": "Typo on pricing page", "severity": "low", "kind": [] }),
+        ),
+    )
+    .unwrap();
+    assert_eq!(issue.title, "Typo on pricing page");
+    assert_eq!(issue.description, None);
+    assert!(issue.label_ids.is_empty());
+}
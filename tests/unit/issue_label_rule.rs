@@ -0,0 +1,134 @@
+use rust_backend::db::models::issue::Issue;
+use rust_backend::db::models::issue_label_rule::IssueLabelRule;
+use rust_backend::services::issue_label_rules_service::IssueLabelRulesService;
+use uuid::Uuid;
+
+fn new_issue(title: &str, description: Option<&str>, priority: &str) -> Issue {
+    let now = chrono::Utc::now();
+    Issue {
+        id: Uuid::new_v4(),
+        project_id: None,
+        cycle_id: None,
+        creator_id: Uuid::new_v4(),
+        assignee_id: None,
+        parent_issue_id: None,
+        issue_number: 1,
+        title: title.to_string(),
+        description: description.map(str::to_string),
+        priority: priority.to_string(),
+        is_changelog_candidate: false,
+        created_at: now,
+        updated_at: now,
+        team_id: Uuid::new_v4(),
+        workflow_id: None,
+        workflow_state_id: None,
+        archived_at: None,
+        archive_batch_id: None,
+        estimate: None,
+    }
+}
+
+fn rule(
+    name: &str,
+    match_type: &str,
+    pattern: &str,
+    label_id: Option<Uuid>,
+    priority: Option<&str>,
+) -> IssueLabelRule {
+    let now = chrono::Utc::now();
+    IssueLabelRule {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        team_id: Uuid::new_v4(),
+        name: name.to_string(),
+        match_type: match_type.to_string(),
+        pattern: pattern.to_string(),
+        label_id,
+        priority: priority.map(str::to_string),
+        position: 0,
+        enabled: true,
+        created_by: Uuid::new_v4(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[test]
+fn label_rules_match_keywords_and_regexes() {
+    let crash = Uuid::new_v4();
+    let billing = Uuid::new_v4();
+    let rules = vec![
+        rule("Crashes", "keyword", "crash", Some(crash), Some("urgent")),
+        rule(
+            "Invoices",
+            "regex",
+            r"(?i)\binv-\d+\b",
+            Some(billing),
+            Some("low"),
+        ),
+        rule("Security", "keyword", "CVE", None, Some("high")),
+    ];
+
+    let issue = new_issue(
+        "App CRASHES on launch",
+        Some("Started after paying inv-1042"),
+        "none",
+    );
+    let outcome = IssueLabelRulesService::evaluate(&issue, &rules, &[]);
+    assert_eq!(outcome.label_ids, vec![crash, billing]);
+    // The first matching rule with a priority wins
+    assert_eq!(outcome.priority.as_deref(), Some("urgent"));
+    assert_eq!(outcome.applied.len(), 2);
+    assert_eq!(outcome.applied[0].rule_name, "Crashes");
+    assert_eq!(outcome.applied[0].priority.as_deref(), Some("urgent"));
+    assert_eq!(outcome.applied[1].label_id, Some(billing));
+    assert_eq!(outcome.applied[1].priority, None);
+    assert!(outcome.applied.iter().all(|a| a.issue_id == issue.id));
+
+    let quiet = new_issue("Update the onboarding copy", None, "none");
+    assert!(
+        IssueLabelRulesService::evaluate(&quiet, &rules, &[])
+            .applied
+            .is_empty()
+    );
+}
+
+#[test]
+fn label_rules_keep_what_the_issue_already_has() {
+    let crash = Uuid::new_v4();
+    let mut disabled = rule("Disabled", "keyword", "crash", Some(Uuid::new_v4()), None);
+    disabled.enabled = false;
+    let rules = vec![
+        rule("Crashes", "keyword", "crash", Some(crash), Some("urgent")),
+        rule("Also crashes", "keyword", "crash", Some(crash), None),
+        disabled,
+        rule("Broken", "regex", "([", Some(Uuid::new_v4()), None),
+    ];
+
+    // A priority chosen at creation is not overridden and labels already on
+    // the issue are not logged again
+    let issue = new_issue("Crash in settings", None, "medium");
+    let outcome = IssueLabelRulesService::evaluate(&issue, &rules, &[crash]);
+    assert!(outcome.label_ids.is_empty());
+    assert_eq!(outcome.priority, None);
+    assert!(outcome.applied.is_empty());
+
+    // Rules adding a label the issue gets from an earlier rule are left out
+    let issue = new_issue("Crash in settings", None, "none");
+    let outcome = IssueLabelRulesService::evaluate(&issue, &rules, &[]);
+    assert_eq!(outcome.label_ids, vec![crash]);
+    assert_eq!(outcome.applied.len(), 1);
+}
+
+#[test]
+fn label_rule_patterns_are_validated() {
+    assert_eq!(
+        IssueLabelRulesService::validate_pattern("keyword", "  crash ").unwrap(),
+        "crash"
+    );
+    assert!(IssueLabelRulesService::validate_pattern("keyword", "   ").is_err());
+    assert!(IssueLabelRulesService::validate_pattern("regex", r"^\[bug\]").is_ok());
+    assert!(IssueLabelRulesService::validate_pattern("regex", "([").is_err());
+    assert!(IssueLabelRulesService::validate_pattern("glob", "crash*").is_err());
+    assert!(IssueLabelRulesService::validate_pattern("keyword", &"a".repeat(501)).is_err());
+}
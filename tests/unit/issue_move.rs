@@ -0,0 +1,156 @@
+use chrono::Utc;
+use rust_backend::db::models::workflow::{WorkflowState, WorkflowStateCategory};
+use rust_backend::services::issue_moves_service::IssueMovesService;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn state(name: &str, category: WorkflowStateCategory, is_default: bool) -> WorkflowState {
+    WorkflowState {
+        id: Uuid::new_v4(),
+        workflow_id: Uuid::new_v4(),
+        name: name.to_string(),
+        description: None,
+        color: None,
+        category,
+        position: 0,
+        is_default,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn issue_key_uses_team_key_and_number() {
+    assert_eq!(IssueMovesService::issue_key("ENG", 42), "ENG-42");
+}
+
+#[test]
+fn issue_keys_parse_back_to_team_key_and_number() {
+    let parse = IssueMovesService::parse_issue_key;
+    assert_eq!(parse("ENG-42"), Some(("ENG", 42)));
+    assert_eq!(parse(" eng-7 "), Some(("eng", 7)));
+    assert_eq!(parse("team-1_ok-3"), Some(("team-1_ok", 3)));
+    assert_eq!(parse("ENG-0"), None);
+    assert_eq!(parse("ENG-+4"), None);
+    assert_eq!(parse("ENG-"), None);
+    assert_eq!(parse("-42"), None);
+    assert_eq!(parse("ENG42"), None);
+}
+
+#[test]
+fn remap_state_prefers_explicit_mapping() {
+    let current = state("In Progress", WorkflowStateCategory::Started, false);
+    let same_name = state("In Progress", WorkflowStateCategory::Started, false);
+    let mapped = state("Doing", WorkflowStateCategory::Started, false);
+    let targets = vec![same_name.clone(), mapped.clone()];
+
+    let mapping = HashMap::from([(current.id, mapped.id)]);
+    assert_eq!(
+        IssueMovesService::remap_state(&current, &targets, &mapping),
+        Some(mapped.id)
+    );
+
+    // A mapping to a state outside the target team is not honoured
+    let mapping = HashMap::from([(current.id, Uuid::new_v4())]);
+    assert_eq!(
+        IssueMovesService::remap_state(&current, &targets, &mapping),
+        None
+    );
+}
+
+#[test]
+fn remap_state_falls_back_by_name_then_category() {
+    let current = state("Review", WorkflowStateCategory::Started, false);
+    let other = state("Doing", WorkflowStateCategory::Started, false);
+    let default = state("Active", WorkflowStateCategory::Started, true);
+    let review = state("review", WorkflowStateCategory::Started, false);
+    let done = state("Review", WorkflowStateCategory::Completed, false);
+    let none = HashMap::new();
+
+    let targets = vec![other.clone(), default.clone(), review.clone(), done.clone()];
+    assert_eq!(
+        IssueMovesService::remap_state(&current, &targets, &none),
+        Some(review.id)
+    );
+
+    let targets = vec![other.clone(), default.clone(), done.clone()];
+    assert_eq!(
+        IssueMovesService::remap_state(&current, &targets, &none),
+        Some(default.id)
+    );
+
+    let targets = vec![done.clone(), other.clone()];
+    assert_eq!(
+        IssueMovesService::remap_state(&current, &targets, &none),
+        Some(other.id)
+    );
+
+    assert_eq!(
+        IssueMovesService::remap_state(&current, &[done], &none),
+        None
+    );
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn issue_numbers_are_unique_per_team() {
+    use super::test_db;
+    use diesel::prelude::*;
+    use rust_backend::schema::issues;
+
+    let mut conn = test_db::connect();
+    let owner = test_db::user(&mut conn);
+    let workspace = test_db::workspace(&mut conn, owner);
+    let team = test_db::team(&mut conn, workspace);
+    let other_team = test_db::team(&mut conn, workspace);
+    test_db::issue(&mut conn, team, owner, 1, "");
+    // Another team can reuse the number
+    test_db::issue(&mut conn, other_team, owner, 1, "");
+
+    let duplicate = conn.transaction(|conn| {
+        diesel::insert_into(issues::table)
+            .values((
+                issues::team_id.eq(team),
+                issues::creator_id.eq(owner),
+                issues::issue_number.eq(1),
+                issues::title.eq("Duplicate"),
+            ))
+            .execute(conn)
+    });
+    assert!(matches!(
+        duplicate,
+        Err(diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::UniqueViolation,
+            _
+        ))
+    ));
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn issues_looked_up_by_key_carry_their_identifier() {
+    use super::test_db;
+    use diesel::prelude::*;
+    use rust_backend::schema::teams;
+    use rust_backend::services::issues_service::IssuesService;
+
+    let mut conn = test_db::connect();
+    let owner = test_db::user(&mut conn);
+    let workspace = test_db::workspace(&mut conn, owner);
+    let team = test_db::team(&mut conn, workspace);
+    let issue = test_db::issue(&mut conn, team, owner, 7, "");
+    let team_key: String = teams::table
+        .find(team)
+        .select(teams::team_key)
+        .first(&mut conn)
+        .unwrap();
+    let key = IssueMovesService::issue_key(&team_key, 7);
+    let ctx = test_db::ctx(owner, workspace);
+
+    let by_key = IssuesService::get_by_key(&mut conn, &ctx, &key.to_lowercase()).unwrap();
+    assert_eq!(by_key.id, issue);
+    assert_eq!(by_key.identifier.as_deref(), Some(key.as_str()));
+
+    let by_id = IssuesService::get_by_id(&mut conn, &ctx, issue).unwrap();
+    assert_eq!(by_id.identifier.as_deref(), Some(key.as_str()));
+}
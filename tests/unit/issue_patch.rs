@@ -0,0 +1,171 @@
+use rust_backend::db::enums::IssuePriority;
+use rust_backend::services::issue_patch_service::{IssuePatchService, IssuePatchTarget};
+use rust_backend::utils::json_patch::{self, PatchDocument};
+use serde_json::json;
+use uuid::Uuid;
+
+fn target() -> IssuePatchTarget {
+    IssuePatchTarget {
+        title: "Login fails".to_string(),
+        description: Some("Steps to reproduce".to_string()),
+        priority: "low".to_string(),
+        team_id: Uuid::new_v4(),
+        project_id: None,
+        cycle_id: None,
+        assignee_id: Some(Uuid::new_v4()),
+        workflow_id: Some(Uuid::new_v4()),
+        workflow_state_id: Some(Uuid::new_v4()),
+        estimate: Some(3),
+        label_ids: vec![Uuid::new_v4()],
+    }
+}
+
+fn document(content_type: &str, body: serde_json::Value) -> PatchDocument {
+    IssuePatchService::parse_document(Some(content_type), body).unwrap()
+}
+
+#[test]
+fn patch_documents_follow_the_content_type() {
+    assert!(matches!(
+        document(
+            "application/json-patch+json",
+            json!([{ "op": "remove", "path": "/estimate" }])
+        ),
+        PatchDocument::JsonPatch(_)
+    ));
+    assert!(matches!(
+        document(
+            "application/merge-patch+json; charset=utf-8",
+            json!({ "title": "New" })
+        ),
+        PatchDocument::MergePatch(_)
+    ));
+    assert!(matches!(
+        document("application/json", json!([])),
+        PatchDocument::JsonPatch(_)
+    ));
+
+    assert!(
+        IssuePatchService::parse_document(
+            Some("application/json-patch+json"),
+            json!({ "title": "New" })
+        )
+        .is_err()
+    );
+    assert!(
+        IssuePatchService::parse_document(Some("application/merge-patch+json"), json!([])).is_err()
+    );
+    assert!(
+        IssuePatchService::parse_document(None, json!([{ "op": "shuffle", "path": "/title" }]))
+            .is_err()
+    );
+}
+
+#[test]
+fn patch_paths_are_restricted_to_editable_fields() {
+    let allowed = document(
+        "application/json-patch+json",
+        json!([
+            { "op": "test", "path": "/title", "value": "Login fails" },
+            { "op": "add", "path": "/label_ids/-", "value": Uuid::new_v4() },
+            { "op": "copy", "from": "/assignee_id", "path": "/project_id" }
+        ]),
+    );
+    assert!(IssuePatchService::validate_paths(&allowed).is_ok());
+
+    for body in [
+        json!([{ "op": "replace", "path": "/issue_number", "value": 7 }]),
+        json!([{ "op": "replace", "path": "", "value": {} }]),
+        json!([{ "op": "replace", "path": "/title/0", "value": "x" }]),
+        json!([{ "op": "move", "from": "/creator_id", "path": "/assignee_id" }]),
+        json!([]),
+    ] {
+        let patch = document("application/json-patch+json", body);
+        assert!(IssuePatchService::validate_paths(&patch).is_err());
+    }
+    let merge = document(
+        "application/merge-patch+json",
+        json!({ "created_at": null }),
+    );
+    assert!(IssuePatchService::validate_paths(&merge).is_err());
+}
+
+#[test]
+fn json_patch_changes_become_an_issue_update() {
+    let before = target();
+    let added = Uuid::new_v4();
+    let patch = document(
+        "application/json-patch+json",
+        json!([
+            { "op": "test", "path": "/priority", "value": "low" },
+            { "op": "replace", "path": "/priority", "value": "urgent" },
+            { "op": "remove", "path": "/assignee_id" },
+            { "op": "add", "path": "/label_ids/-", "value": added },
+            { "op": "replace", "path": "/estimate", "value": null }
+        ]),
+    );
+    let after = IssuePatchService::patch_target(&before, &patch).unwrap();
+    assert_eq!(after.assignee_id, None);
+    assert_eq!(after.label_ids, vec![before.label_ids[0], added]);
+
+    let (req, clears) = IssuePatchService::changes(&before, &after).unwrap();
+    assert!(matches!(req.priority, Some(IssuePriority::Urgent)));
+    assert!(clears.assignee_id && !clears.description);
+    assert_eq!(req.assignee_id, None);
+    assert_eq!(req.estimate, Some(None));
+    assert_eq!(req.label_ids, Some(vec![before.label_ids[0], added]));
+    assert!(req.title.is_none() && req.team_id.is_none() && req.description.is_none());
+
+    // A failed test leaves the issue alone and reports a conflict
+    let stale = document(
+        "application/json-patch+json",
+        json!([
+            { "op": "test", "path": "/title", "value": "Something else" },
+            { "op": "replace", "path": "/title", "value": "Renamed" }
+        ]),
+    );
+    assert!(IssuePatchService::patch_target(&before, &stale).is_err());
+}
+
+#[test]
+fn merge_patch_changes_become_an_issue_update() {
+    let before = target();
+    let patch = document(
+        "application/merge-patch+json",
+        json!({ "title": "Login fails on Safari", "description": null, "label_ids": [] }),
+    );
+    let after = IssuePatchService::patch_target(&before, &patch).unwrap();
+    let (req, clears) = IssuePatchService::changes(&before, &after).unwrap();
+    assert_eq!(req.title.as_deref(), Some("Login fails on Safari"));
+    assert!(clears.description);
+    assert_eq!(req.label_ids, Some(Vec::new()));
+    assert!(req.estimate.is_none() && req.priority.is_none());
+
+    // Required fields cannot be removed, nor can the workflow unless the
+    // issue changes team
+    let no_title = document("application/merge-patch+json", json!({ "title": null }));
+    assert!(IssuePatchService::patch_target(&before, &no_title).is_err());
+    let no_state = document(
+        "application/merge-patch+json",
+        json!({ "workflow_state_id": null }),
+    );
+    let after = IssuePatchService::patch_target(&before, &no_state).unwrap();
+    assert!(IssuePatchService::changes(&before, &after).is_err());
+    let bad_priority = document(
+        "application/merge-patch+json",
+        json!({ "priority": "asap" }),
+    );
+    let after = IssuePatchService::patch_target(&before, &bad_priority).unwrap();
+    assert!(IssuePatchService::changes(&before, &after).is_err());
+}
+
+#[test]
+fn merge_patch_follows_rfc_7396() {
+    let mut doc = json!({ "a": "b", "c": { "d": "e", "f": "g" } });
+    json_patch::apply_merge_patch(&mut doc, &json!({ "a": "z", "c": { "f": null } }));
+    assert_eq!(doc, json!({ "a": "z", "c": { "d": "e" } }));
+
+    let mut doc = json!({ "tags": ["x", "y"] });
+    json_patch::apply_merge_patch(&mut doc, &json!({ "tags": ["z"] }));
+    assert_eq!(doc, json!({ "tags": ["z"] }));
+}
@@ -0,0 +1,83 @@
+use rust_backend::db::models::issue_relation::IssueRelationType;
+use rust_backend::services::issue_relations_service::IssueRelationsService;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use uuid::Uuid;
+
+fn successors(
+    edges: &HashMap<Uuid, Vec<Uuid>>,
+) -> impl FnMut(&[Uuid]) -> Result<Vec<(Uuid, Uuid)>, Infallible> + '_ {
+    move |frontier| {
+        Ok(frontier
+            .iter()
+            .flat_map(|from| {
+                edges
+                    .get(from)
+                    .into_iter()
+                    .flatten()
+                    .map(move |to| (*from, *to))
+            })
+            .collect())
+    }
+}
+
+#[test]
+fn relation_types_round_trip_and_invert() {
+    for kind in [
+        IssueRelationType::Blocks,
+        IssueRelationType::BlockedBy,
+        IssueRelationType::Duplicates,
+        IssueRelationType::DuplicatedBy,
+        IssueRelationType::RelatesTo,
+    ] {
+        assert_eq!(IssueRelationType::parse(kind.as_str()), Some(kind));
+        assert_eq!(kind.inverse().inverse(), kind);
+    }
+    assert_eq!(IssueRelationType::parse("parent_of"), None);
+    assert_eq!(
+        IssueRelationType::RelatesTo.inverse(),
+        IssueRelationType::RelatesTo
+    );
+}
+
+#[test]
+fn passive_relations_are_stored_flipped() {
+    let a = Uuid::new_v4();
+    let b = Uuid::new_v4();
+    assert_eq!(
+        IssueRelationsService::canonical(a, b, IssueRelationType::BlockedBy),
+        (b, a, IssueRelationType::Blocks)
+    );
+    assert_eq!(
+        IssueRelationsService::canonical(a, b, IssueRelationType::DuplicatedBy),
+        (b, a, IssueRelationType::Duplicates)
+    );
+    assert_eq!(
+        IssueRelationsService::canonical(a, b, IssueRelationType::RelatesTo),
+        (a, b, IssueRelationType::RelatesTo)
+    );
+}
+
+#[test]
+fn blocking_cycles_are_detected() {
+    let [a, b, c, d] = [
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+    ];
+    // a blocks b, b blocks c, b blocks d
+    let edges = HashMap::from([(a, vec![b]), (b, vec![c, d])]);
+
+    // c blocks a closes the loop c -> a -> b -> c
+    let path = IssueRelationsService::find_blocking_cycle(c, a, successors(&edges)).unwrap();
+    assert_eq!(path, Some(vec![a, b, c]));
+
+    // d blocks c is fine: nothing reachable from c leads back to d
+    let path = IssueRelationsService::find_blocking_cycle(d, c, successors(&edges)).unwrap();
+    assert_eq!(path, None);
+
+    // a blocks d again is redundant but not a cycle
+    let path = IssueRelationsService::find_blocking_cycle(a, d, successors(&edges)).unwrap();
+    assert_eq!(path, None);
+}
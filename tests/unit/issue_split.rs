@@ -0,0 +1,96 @@
+use rust_backend::db::models::issue_split::SplitCandidate;
+use rust_backend::services::issue_split_service::{IssueSplitService, MAX_SPLIT_ITEMS};
+
+#[test]
+fn checklist_items_are_parsed_from_task_lists() {
+    let description = "Intro\n- [ ] Write migration\n  * [x] Add model\n+ [X]   \n- plain bullet\n-[ ] no space\n1. [ ] numbered";
+    let items = IssueSplitService::checklist_items(description);
+    let titles: Vec<&str> = items.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, vec!["Write migration", "Add model"]);
+    assert!(items.iter().all(|c| c.description.is_none()));
+}
+
+#[test]
+fn description_sections_split_at_top_level_headings() {
+    let description = "Preamble\n\n## API\nAdd endpoint\n### Details\nMore\n## Worker\n\n## Docs\nWrite them\n#hashtag";
+    let sections = IssueSplitService::description_sections(description);
+    assert_eq!(
+        sections,
+        vec![
+            SplitCandidate {
+                title: "API".to_string(),
+                description: Some("Add endpoint\n### Details\nMore".to_string()),
+            },
+            SplitCandidate {
+                title: "Worker".to_string(),
+                description: None,
+            },
+            SplitCandidate {
+                title: "Docs".to_string(),
+                description: Some("Write them\n#hashtag".to_string()),
+            },
+        ]
+    );
+    assert!(IssueSplitService::description_sections("no headings").is_empty());
+}
+
+#[test]
+fn select_validates_indexes() {
+    let candidates = IssueSplitService::checklist_items("- [ ] a\n- [ ] b\n- [ ] c");
+
+    let selected = IssueSplitService::select(&candidates, &[2, 0]).unwrap();
+    let titles: Vec<&str> = selected.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, vec!["c", "a"]);
+
+    assert!(IssueSplitService::select(&candidates, &[]).is_err());
+    assert!(IssueSplitService::select(&candidates, &[1, 1]).is_err());
+    assert!(IssueSplitService::select(&candidates, &[3]).is_err());
+
+    let too_many: Vec<usize> = (0..=MAX_SPLIT_ITEMS).collect();
+    assert!(IssueSplitService::select(&candidates, &too_many).is_err());
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn issues_of_other_workspaces_cannot_be_split() {
+    use super::test_db;
+    use rust_backend::db::models::issue_split::{SplitIssueRequest, SplitSource};
+    use rust_backend::error::AppError;
+
+    let mut conn = test_db::connect();
+    let owner = test_db::user(&mut conn);
+    let own_workspace = test_db::workspace(&mut conn, owner);
+    let outsider = test_db::user(&mut conn);
+    let other_workspace = test_db::workspace(&mut conn, outsider);
+    let other_team = test_db::team(&mut conn, other_workspace);
+    let issue = test_db::issue(
+        &mut conn,
+        other_team,
+        outsider,
+        1,
+        "- [ ] First\n- [ ] Second",
+    );
+
+    let req = SplitIssueRequest {
+        source: SplitSource::Checklist,
+        items: vec![0, 1],
+        close_original: true,
+        close_state_id: None,
+    };
+    let result =
+        IssueSplitService::split(&mut conn, &test_db::ctx(owner, own_workspace), issue, &req);
+    assert!(matches!(result, Err(AppError::NotFound { .. })));
+
+    // The owner of the issue's workspace can split it
+    let split = IssueSplitService::split(
+        &mut conn,
+        &test_db::ctx(outsider, other_workspace),
+        issue,
+        &SplitIssueRequest {
+            close_original: false,
+            ..req
+        },
+    )
+    .unwrap();
+    assert_eq!(split.created.len(), 2);
+}
@@ -0,0 +1,51 @@
+use rust_backend::cache::LockManager;
+use std::time::Duration;
+
+#[test]
+fn lock_renewal_runs_three_times_per_ttl() {
+    assert_eq!(
+        LockManager::renew_interval(Duration::from_secs(30)),
+        Duration::from_secs(10)
+    );
+    // Very short leases are still renewed at a sane rate
+    assert_eq!(
+        LockManager::renew_interval(Duration::from_millis(90)),
+        Duration::from_millis(100)
+    );
+    assert_ne!(
+        LockManager::lock_key("rollup"),
+        LockManager::fence_key("rollup")
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires running Redis"]
+async fn lock_is_exclusive_and_fencing_tokens_increase() {
+    let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    let locks = LockManager::new(client);
+    let name = format!("test:{}", uuid::Uuid::new_v4());
+    let ttl = Duration::from_secs(5);
+
+    let first = locks.try_acquire(&name, ttl).await.unwrap().unwrap();
+    assert!(locks.try_acquire(&name, ttl).await.unwrap().is_none());
+    assert!(
+        locks
+            .acquire(&name, ttl, Duration::from_millis(200))
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let token = first.fencing_token();
+    assert!(first.release().await.unwrap());
+
+    let second = locks.try_acquire(&name, ttl).await.unwrap().unwrap();
+    assert!(second.fencing_token() > token);
+    assert!(second.is_held());
+    drop(second);
+
+    let stats = locks.stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].acquired, 2);
+    assert_eq!(stats[0].timed_out, 1);
+    assert!(stats[0].contended >= 2);
+}
@@ -0,0 +1,58 @@
+use axum::http::Method;
+use rust_backend::cache::maintenance::{
+    MAINTENANCE_EVENT, MaintenanceEvent, MaintenanceMode, MaintenanceScope,
+};
+use rust_backend::middleware::maintenance::is_mutation;
+use rust_backend::websocket::MessageType;
+use uuid::Uuid;
+
+#[test]
+fn maintenance_keys_are_scoped_per_workspace() {
+    let workspace = Uuid::new_v4();
+    assert_eq!(MaintenanceMode::key(None), "maintenance:global");
+    assert_eq!(
+        MaintenanceMode::key(Some(workspace)),
+        format!("maintenance:workspace:{}", workspace)
+    );
+}
+
+#[test]
+fn maintenance_blocks_only_writes_outside_exempt_paths() {
+    assert!(!is_mutation(&Method::GET, "/issues"));
+    assert!(!is_mutation(&Method::HEAD, "/issues"));
+    assert!(!is_mutation(&Method::OPTIONS, "/issues"));
+    assert!(is_mutation(&Method::POST, "/issues"));
+    assert!(is_mutation(&Method::PUT, "/labels/1"));
+    assert!(is_mutation(&Method::DELETE, "/projects/1"));
+    assert!(is_mutation(&Method::POST, "/auth/tokens"));
+
+    // Admins can still lift maintenance, users can still sign in and out
+    assert!(!is_mutation(&Method::PUT, "/admin/maintenance"));
+    assert!(!is_mutation(&Method::POST, "/auth/login"));
+    assert!(!is_mutation(&Method::POST, "/auth/logout"));
+    assert!(!is_mutation(&Method::POST, "/graphql"));
+}
+
+#[test]
+fn maintenance_event_becomes_system_message() {
+    let workspace = Uuid::new_v4();
+    let event = MaintenanceEvent {
+        enabled: true,
+        scope: MaintenanceScope::Workspace,
+        workspace_id: Some(workspace),
+        message: Some("Upgrading".to_string()),
+    };
+    let payload = serde_json::to_string(&event).unwrap();
+    assert_eq!(
+        serde_json::from_str::<MaintenanceEvent>(&payload).unwrap(),
+        event
+    );
+
+    let message = event.to_ws_message();
+    assert_eq!(message.message_type, MessageType::SystemMessage);
+    assert_eq!(message.data["event"], MAINTENANCE_EVENT);
+    assert_eq!(message.data["enabled"], true);
+    assert_eq!(message.data["scope"], "workspace");
+    assert_eq!(message.data["workspace_id"], workspace.to_string());
+    assert_eq!(message.data["message"], "Upgrading");
+}
@@ -0,0 +1,147 @@
+// Rendering, sanitization and mention extraction tests for markdown
+
+use rust_backend::cache::{CacheStore, MemoryCacheStore};
+use rust_backend::services::markdown_service::{MAX_CODE_BLOCK_LINES, MarkdownService};
+
+#[test]
+fn rendering_strips_unsafe_html() {
+    let rendered = MarkdownService::render(
+        "# Title\n\n<script>alert(1)</script>\n\n[click](javascript:alert(1)) [docs](https://example.com)",
+    );
+    assert!(rendered.html.contains("<h1>Title</h1>"));
+    assert!(!rendered.html.contains("<script"));
+    assert!(!rendered.html.contains("javascript:"));
+    assert!(
+        rendered
+            .html
+            .contains(r#"<a href="https://example.com" rel="noopener noreferrer">docs</a>"#)
+    );
+}
+
+#[test]
+fn rendering_supports_tables_and_strikethrough() {
+    let rendered = MarkdownService::render("| a | b |\n|---|---|\n| 1 | 2 |\n\n~~old~~");
+    assert!(rendered.html.contains("<table>"));
+    assert!(rendered.html.contains("<del>old</del>"));
+}
+
+#[test]
+fn mentions_skip_code_and_email_addresses() {
+    let rendered = MarkdownService::render(
+        "Thanks @alice and @bob.\n\nMail carol@example.com or ping @alice again.\n\n`@dave` and\n\n```\n@erin\n```",
+    );
+    assert_eq!(rendered.mentions, vec!["alice", "bob"]);
+    assert!(!rendered.text.contains("@erin"));
+}
+
+#[test]
+fn cache_keys_depend_only_on_the_markdown() {
+    assert_eq!(
+        MarkdownService::cache_key("**bold**"),
+        MarkdownService::cache_key("**bold**")
+    );
+    assert_ne!(
+        MarkdownService::cache_key("**bold**"),
+        MarkdownService::cache_key("*bold*")
+    );
+    assert!(MarkdownService::cache_key("").starts_with("markdown:v2:"));
+}
+
+#[tokio::test]
+async fn cached_rendering_reuses_stored_output() {
+    let cache = MemoryCacheStore::new();
+    let key = MarkdownService::cache_key("hello @alice");
+
+    let rendered = MarkdownService::render_cached(&cache, "hello @alice").await;
+    assert_eq!(rendered.mentions, vec!["alice"]);
+    assert!(cache.get(&key).await.unwrap().is_some());
+
+    let stale = r#"{"html":"<p>cached</p>","text":"cached","mentions":[],"code_blocks":[]}"#;
+    cache.set_ex(&key, stale.to_string(), 60).await.unwrap();
+    let cached = MarkdownService::render_cached(&cache, "hello @alice").await;
+    assert_eq!(cached.html, "<p>cached</p>");
+}
+
+#[test]
+fn code_blocks_are_labelled_with_their_language() {
+    let rendered = MarkdownService::render(
+        "```rs,ignore\nfn main() {}\n```\n\n```\n{\"ok\": true}\n```\n\n```mermaid\ngraph TD\n```",
+    );
+    assert!(
+        rendered
+            .html
+            .contains(r#"<code class="language-rust">fn main() {}"#)
+    );
+    assert!(rendered.html.contains(r#"<code class="language-json">"#));
+
+    let languages: Vec<(Option<&str>, bool)> = rendered
+        .code_blocks
+        .iter()
+        .map(|block| (block.language.as_deref(), block.language_detected))
+        .collect();
+    assert_eq!(
+        languages,
+        [(Some("rust"), false), (Some("json"), true), (None, false)]
+    );
+}
+
+#[test]
+fn languages_are_detected_from_common_code() {
+    let detect = MarkdownService::detect_language;
+    assert_eq!(detect("#!/bin/bash\nset -e\n"), Some("shell"));
+    assert_eq!(
+        detect("def run(self):\n    import os\n    print(os.name)\n"),
+        Some("python")
+    );
+    assert_eq!(
+        detect("SELECT id FROM issues WHERE team_id = 1"),
+        Some("sql")
+    );
+    assert_eq!(
+        detect("2025-01-01 10:00:00 ERROR request failed\n2025-01-01 10:00:01 INFO retrying\n"),
+        Some("log")
+    );
+    assert_eq!(detect("[WARN] disk almost full\n[INFO] ok\n"), Some("log"));
+    assert_eq!(detect("some notes about the release"), None);
+    assert_eq!(MarkdownService::fence_language("YML"), Some("yaml"));
+    assert_eq!(
+        MarkdownService::fence_language("python title=x"),
+        Some("python")
+    );
+}
+
+#[test]
+fn long_code_blocks_are_truncated_and_kept_in_full() {
+    let log: String = (0..MAX_CODE_BLOCK_LINES + 50)
+        .map(|i| format!("line {}\n", i))
+        .collect();
+    let markdown = format!("Crash:\n\n```text\n{}```\n\n```\nshort\n```", log);
+    let rendered = MarkdownService::render(&markdown);
+
+    let block = &rendered.code_blocks[0];
+    assert!(block.truncated);
+    assert_eq!(block.lines, MAX_CODE_BLOCK_LINES + 50);
+    assert!(!rendered.code_blocks[1].truncated);
+    assert!(
+        rendered
+            .html
+            .contains(&format!("line {}\n", MAX_CODE_BLOCK_LINES - 1))
+    );
+    assert!(
+        !rendered
+            .html
+            .contains(&format!("line {}\n", MAX_CODE_BLOCK_LINES))
+    );
+    assert!(rendered.html.contains(&format!(
+        r#"<p class="code-truncated">Truncated: {} of {} lines shown</p>"#,
+        MAX_CODE_BLOCK_LINES,
+        MAX_CODE_BLOCK_LINES + 50
+    )));
+
+    assert_eq!(MarkdownService::code_block(&markdown, 0), Some(log));
+    assert_eq!(
+        MarkdownService::code_block(&markdown, 1).as_deref(),
+        Some("short\n")
+    );
+    assert_eq!(MarkdownService::code_block(&markdown, 2), None);
+}
@@ -0,0 +1,94 @@
+use rust_backend::db::models::member_import::{MemberImportLine, member_import_row_status};
+use rust_backend::db::models::team::Team;
+use rust_backend::services::member_imports_service::MemberImportsService;
+use rust_backend::utils::csv::parse_csv;
+use uuid::Uuid;
+
+fn team(name: &str, key: &str) -> Team {
+    Team {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        name: name.to_string(),
+        team_key: key.to_string(),
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        description: None,
+        icon_url: None,
+        is_private: false,
+        parent_team_id: None,
+        estimate_scale: "fibonacci".to_string(),
+        assignment_mode: "none".to_string(),
+        default_assignee_id: None,
+        last_auto_assignee_id: None,
+    }
+}
+
+fn line(row_number: i32, email: &str, role: &str, teams: &[&str]) -> MemberImportLine {
+    MemberImportLine {
+        row_number,
+        email: email.to_string(),
+        role: role.to_string(),
+        teams: teams.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+#[test]
+fn parse_csv_handles_quotes_and_line_endings() {
+    let records = parse_csv("a,b\r\n\"x, y\",\"say \"\"hi\"\"\"\n\n").unwrap();
+    assert_eq!(records, vec![vec!["a", "b"], vec!["x, y", "say \"hi\""]]);
+    assert!(parse_csv("a,\"unterminated\n").is_err());
+}
+
+#[test]
+fn parse_lines_reads_header_columns() {
+    let csv = "Teams,Email,Role\n\"ENG; Design\",ann@example.com,Admin\n,bob@example.com,\n";
+    let lines = MemberImportsService::parse_lines(csv).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].row_number, 1);
+    assert_eq!(lines[0].email, "ann@example.com");
+    assert_eq!(lines[0].role, "admin");
+    assert_eq!(lines[0].teams, vec!["ENG", "Design"]);
+    assert!(lines[1].teams.is_empty());
+
+    assert!(MemberImportsService::parse_lines("name,role\nann,member\n").is_err());
+    assert!(MemberImportsService::parse_lines("email\n").is_err());
+}
+
+#[test]
+fn validate_lines_reports_per_row_results() {
+    let eng = team("Engineering", "ENG");
+    let teams = vec![eng.clone(), team("Design", "DES")];
+    let lines = vec![
+        line(1, "ann@example.com", "", &["eng", "Engineering"]),
+        line(2, "not-an-email", "member", &[]),
+        line(3, "ANN@example.com", "member", &[]),
+        line(4, "bob@example.com", "owner", &[]),
+        line(5, "cat@example.com", "guest", &["Marketing"]),
+    ];
+    let rows = MemberImportsService::validate_lines(&lines, &teams);
+
+    assert_eq!(rows[0].status, member_import_row_status::PENDING);
+    assert_eq!(rows[0].role, "member");
+    assert_eq!(rows[0].team_ids, eng.id.to_string());
+    for row in &rows[1..] {
+        assert_eq!(row.status, member_import_row_status::INVALID);
+        assert!(row.message.is_some());
+    }
+    assert_eq!(rows[2].message.as_deref(), Some("Duplicate of row 1"));
+}
+
+#[test]
+fn member_import_jobs_round_trip() {
+    let id = Uuid::new_v4();
+    let task = format!(
+        "{}{}",
+        rust_backend::services::member_imports_service::JOB_PREFIX,
+        id
+    );
+    assert_eq!(MemberImportsService::parse_job(&task), Some(id));
+    assert_eq!(MemberImportsService::parse_job("other:task"), None);
+    assert_eq!(
+        MemberImportsService::content_hash("email\n"),
+        MemberImportsService::content_hash("email\n")
+    );
+}
@@ -0,0 +1,56 @@
+use rust_backend::metrics::{HttpMetrics, PrometheusText};
+use std::time::Duration;
+
+#[test]
+fn http_latency_histogram_is_cumulative() {
+    let metrics = HttpMetrics::default();
+    metrics.observe("GET", "/issues/:issue_id", 200, Duration::from_millis(3));
+    metrics.observe("GET", "/issues/:issue_id", 200, Duration::from_millis(40));
+    metrics.observe("GET", "/issues/:issue_id", 200, Duration::from_secs(30));
+
+    let mut out = PrometheusText::new();
+    metrics.render(&mut out);
+    let text = out.finish();
+
+    assert!(text.contains("# TYPE http_request_duration_seconds histogram"));
+    let labels = r#"method="GET",route="/issues/:issue_id",status="200""#;
+    assert!(text.contains(&format!(
+        "http_request_duration_seconds_bucket{{{},le=\"0.005\"}} 1",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "http_request_duration_seconds_bucket{{{},le=\"0.05\"}} 2",
+        labels
+    )));
+    // Slower than the largest bucket: only counted in +Inf
+    assert!(text.contains(&format!(
+        "http_request_duration_seconds_bucket{{{},le=\"10\"}} 2",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 3",
+        labels
+    )));
+    assert!(text.contains(&format!(
+        "http_request_duration_seconds_count{{{}}} 3",
+        labels
+    )));
+}
+
+#[test]
+fn prometheus_text_escapes_label_values() {
+    let mut out = PrometheusText::new();
+    out.family("background_task_up", "gauge", "Task state");
+    out.sample("background_task_up", &[("task", "say \"hi\"\\\n")], 1.0);
+    out.gauge("websocket_connections_active", "Open connections", 4.0);
+
+    assert_eq!(
+        out.finish(),
+        "# HELP background_task_up Task state\n\
+         # TYPE background_task_up gauge\n\
+         background_task_up{task=\"say \\\"hi\\\"\\\\\\n\"} 1\n\
+         # HELP websocket_connections_active Open connections\n\
+         # TYPE websocket_connections_active gauge\n\
+         websocket_connections_active 4\n"
+    );
+}
@@ -0,0 +1,107 @@
+// Tests for project milestones
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_backend::db::models::milestone::{Milestone, MilestoneIssueProgress};
+use rust_backend::db::models::workflow::WorkflowStateCategory;
+use rust_backend::services::milestones_service::MilestonesService;
+use uuid::Uuid;
+
+fn at(day: u32) -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 10, day, 12, 0, 0).unwrap()
+}
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 10, day).unwrap()
+}
+
+fn milestone(target_date: Option<NaiveDate>) -> Milestone {
+    Milestone {
+        id: Uuid::new_v4(),
+        project_id: Uuid::new_v4(),
+        name: "Beta".to_string(),
+        description: None,
+        target_date,
+        created_by: None,
+        created_at: at(1),
+        updated_at: at(1),
+    }
+}
+
+fn issue(
+    category: Option<WorkflowStateCategory>,
+    added: u32,
+    updated: u32,
+) -> MilestoneIssueProgress {
+    MilestoneIssueProgress {
+        issue_id: Uuid::new_v4(),
+        category,
+        added_at: at(added),
+        updated_at: at(updated),
+    }
+}
+
+#[test]
+fn validate_milestone_name_rules() {
+    use rust_backend::validation::milestone::validate_milestone_name;
+    assert!(validate_milestone_name("Beta launch").is_ok());
+    assert!(validate_milestone_name("  ").is_err());
+    assert!(validate_milestone_name(&"m".repeat(256)).is_err());
+}
+
+#[test]
+fn stats_count_issues_by_category_and_skip_canceled() {
+    let issues = vec![
+        issue(Some(WorkflowStateCategory::Completed), 1, 3),
+        issue(Some(WorkflowStateCategory::Started), 1, 2),
+        issue(Some(WorkflowStateCategory::Backlog), 2, 2),
+        issue(None, 2, 2),
+        issue(Some(WorkflowStateCategory::Canceled), 1, 4),
+    ];
+    let stats = MilestonesService::summarize(&milestone(Some(date(10))), date(5), &issues);
+
+    assert_eq!(stats.total_issues, 4);
+    assert_eq!(stats.completed_issues, 1);
+    assert_eq!(stats.in_progress_issues, 1);
+    assert_eq!(stats.todo_issues, 2);
+    assert_eq!(stats.canceled_issues, 1);
+    assert_eq!(stats.completion_rate, 0.25);
+    assert_eq!(stats.days_remaining, Some(5));
+    assert!(!stats.is_overdue);
+}
+
+#[test]
+fn burnup_tracks_scope_and_completion_per_day() {
+    let issues = vec![
+        issue(Some(WorkflowStateCategory::Completed), 1, 3),
+        issue(Some(WorkflowStateCategory::Completed), 2, 1),
+        issue(Some(WorkflowStateCategory::Started), 4, 4),
+    ];
+    let stats = MilestonesService::summarize(&milestone(None), date(5), &issues);
+
+    let points: Vec<(NaiveDate, i64, i64)> = stats
+        .burnup
+        .iter()
+        .map(|p| (p.day, p.scope, p.completed))
+        .collect();
+    assert_eq!(
+        points,
+        vec![
+            (date(1), 1, 0),
+            (date(2), 2, 1),
+            (date(3), 2, 2),
+            (date(4), 3, 2),
+            (date(5), 3, 2),
+        ]
+    );
+}
+
+#[test]
+fn burnup_stops_at_target_date_and_flags_overdue() {
+    let issues = vec![issue(Some(WorkflowStateCategory::Unstarted), 1, 1)];
+    let stats = MilestonesService::summarize(&milestone(Some(date(3))), date(6), &issues);
+
+    assert_eq!(stats.burnup.len(), 3);
+    assert_eq!(stats.burnup.last().unwrap().day, date(3));
+    assert_eq!(stats.days_remaining, Some(0));
+    assert!(stats.is_overdue);
+}
@@ -1,13 +1,82 @@
+pub mod api_key;
+pub mod api_token;
+pub mod app_state;
+pub mod attachment;
 pub mod auth;
+pub mod auto_close;
+pub mod automation;
 pub mod cache;
+pub mod clock;
 pub mod comment;
+pub mod conditional_get;
+pub mod config;
+pub mod content_report;
+pub mod custom_emoji;
 pub mod cycle;
+pub mod dashboard;
+pub mod db_executor;
+pub mod dead_letter;
+pub mod delete_confirmation;
+pub mod email;
+pub mod email_domain;
+pub mod email_reply;
+pub mod error_code;
+pub mod error_tracking;
+pub mod external_reference;
+pub mod field_selection;
+pub mod github_integration;
+pub mod graphql;
+pub mod holiday;
+pub mod integrity;
 pub mod invitation;
 pub mod issue;
+pub mod issue_archive;
+pub mod issue_enrichment;
+pub mod issue_form;
+pub mod issue_label_rule;
+pub mod issue_move;
+pub mod issue_patch;
+pub mod issue_relation;
+pub mod issue_split;
 pub mod labels;
+pub mod locks;
+pub mod maintenance;
+pub mod markdown;
+pub mod member_import;
+pub mod metrics;
+pub mod milestone;
+pub mod notification;
+pub mod oauth;
+pub mod oauth_login;
+pub mod openapi;
+pub mod organization;
+pub mod permission;
 pub mod project;
+pub mod project_cost;
 pub mod project_statuses;
+pub mod push;
+pub mod rate_limit;
+pub mod redaction;
+pub mod sandbox;
+pub mod scheduler;
+pub mod security_headers;
+pub mod service_account;
+pub mod session_analytics;
+pub mod settings_history;
+pub mod supervisor;
+pub mod sync;
 pub mod team;
+pub mod team_hierarchy;
+pub mod telemetry;
+pub mod test_db;
+pub mod transaction;
+pub mod typed_cache;
+pub mod upload_session;
+pub mod user_preference;
+pub mod webhook;
+pub mod webhook_filter;
 pub mod workflow;
 pub mod workspace;
+pub mod workspace_import;
 pub mod workspace_member;
+pub mod workspace_settings;
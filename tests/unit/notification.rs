@@ -0,0 +1,108 @@
+// Batching policy and digest grouping tests for notifications
+
+use rust_backend::db::models::Notification;
+use rust_backend::services::notifications_service::{
+    EmailReplySettings, NotificationBatchPolicy, NotificationsService,
+};
+use rust_backend::utils::email_reply;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn notification(recipient_id: Uuid, event_count: i32) -> Notification {
+    let now = chrono::Utc::now();
+    Notification {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        recipient_id,
+        actor_id: None,
+        event_type: "issue_updated".to_string(),
+        entity_type: "issue".to_string(),
+        entity_id: Uuid::new_v4(),
+        title: "Fix login".to_string(),
+        body: None,
+        event_count,
+        read_at: None,
+        email_pending: true,
+        emailed_at: None,
+        created_at: now,
+        updated_at: now,
+        in_app: true,
+    }
+}
+
+#[test]
+fn batch_windows_parse_and_fall_back() {
+    let windows = NotificationBatchPolicy::parse_windows(&[
+        "issue_updated=600".to_string(),
+        " comment_created = 30 ".to_string(),
+        "".to_string(),
+    ])
+    .unwrap();
+    assert_eq!(windows["issue_updated"], Duration::from_secs(600));
+    assert_eq!(windows["comment_created"], Duration::from_secs(30));
+
+    assert!(NotificationBatchPolicy::parse_windows(&["issue_updated".to_string()]).is_err());
+    assert!(NotificationBatchPolicy::parse_windows(&["issue_updated=soon".to_string()]).is_err());
+
+    let policy = NotificationBatchPolicy {
+        in_app: windows,
+        ..NotificationBatchPolicy::default()
+    };
+    assert_eq!(
+        policy.in_app_window("issue_updated"),
+        Duration::from_secs(600)
+    );
+    assert_eq!(policy.in_app_window("unknown"), policy.in_app_default);
+    assert_eq!(policy.email_window("issue_updated"), policy.email_default);
+}
+
+#[test]
+fn digests_group_by_recipient_and_sum_events() {
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+    let digests = NotificationsService::group_digests(vec![
+        notification(alice, 20),
+        notification(bob, 1),
+        notification(alice, 2),
+    ]);
+
+    assert_eq!(digests.len(), 2);
+    assert_eq!(digests[0].recipient_id, alice);
+    assert_eq!(digests[0].notifications.len(), 2);
+    assert_eq!(digests[0].total_events, 22);
+    assert_eq!(digests[1].total_events, 1);
+}
+
+#[test]
+fn comment_digest_carries_reply_address() {
+    let alice = Uuid::new_v4();
+    let mut comment = notification(alice, 1);
+    comment.event_type = "comment_created".to_string();
+    comment.body = Some("Can you take a look?".to_string());
+    let issue_id = comment.entity_id;
+
+    let digest = NotificationsService::group_digests(vec![comment, notification(alice, 3)])
+        .pop()
+        .unwrap();
+    let settings = EmailReplySettings {
+        domain: "reply.example.com".to_string(),
+        secret: "secret".to_string(),
+    };
+
+    let email =
+        NotificationsService::render_digest_email(&digest, "alice@example.com", Some(&settings));
+    let reply_to = email
+        .reply_to
+        .expect("single commented issue gets a reply-to");
+    let token = email_reply::extract_reply_token(&reply_to).unwrap();
+    assert_eq!(
+        email_reply::verify_reply_token(&token, "secret"),
+        Some((issue_id, alice))
+    );
+    assert!(email.text.contains("Can you take a look?"));
+    assert!(email.text.contains(&reply_to));
+    assert_eq!(email.subject, "4 new notifications");
+
+    let plain = NotificationsService::render_digest_email(&digest, "alice@example.com", None);
+    assert_eq!(plain.reply_to, None);
+}
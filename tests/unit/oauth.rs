@@ -0,0 +1,72 @@
+// OAuth provider scope and PKCE tests
+
+use rust_backend::db::models::oauth_app::oauth_scopes;
+use rust_backend::services::oauth_service::OAuthService;
+
+#[test]
+fn oauth_scope_parse_dedups_and_validates() {
+    let scopes = oauth_scopes::parse("read:issues  write:comments read:issues");
+    assert_eq!(scopes, vec!["read:issues", "write:comments"]);
+    assert_eq!(oauth_scopes::join(&scopes), "read:issues write:comments");
+    assert!(oauth_scopes::is_valid("write:projects"));
+    assert!(!oauth_scopes::is_valid("admin:everything"));
+}
+
+#[test]
+fn oauth_write_scope_implies_read() {
+    let granted = vec!["write:issues".to_string()];
+    assert!(oauth_scopes::allows(&granted, "read:issues"));
+    assert!(oauth_scopes::allows(&granted, "write:issues"));
+    assert!(!oauth_scopes::allows(&granted, "read:projects"));
+
+    let read_only = vec!["read:issues".to_string()];
+    assert!(!oauth_scopes::allows(&read_only, "write:issues"));
+}
+
+#[test]
+fn oauth_required_scope_by_route() {
+    assert_eq!(
+        oauth_scopes::required_for("GET", "/issues/:issue_id"),
+        Some(oauth_scopes::READ_ISSUES)
+    );
+    assert_eq!(
+        oauth_scopes::required_for("POST", "/issues/:issue_id/comments"),
+        Some(oauth_scopes::WRITE_COMMENTS)
+    );
+    assert_eq!(
+        oauth_scopes::required_for("PUT", "/projects/:project_id"),
+        Some(oauth_scopes::WRITE_PROJECTS)
+    );
+    assert_eq!(
+        oauth_scopes::required_for("GET", "/auth/profile"),
+        Some(oauth_scopes::READ_USER)
+    );
+    assert_eq!(
+        oauth_scopes::required_for("GET", "/search/issues"),
+        Some(oauth_scopes::READ_ISSUES)
+    );
+    // Account management stays off-limits to third-party apps
+    assert_eq!(oauth_scopes::required_for("POST", "/auth/tokens"), None);
+    assert_eq!(oauth_scopes::required_for("GET", "/oauth/apps"), None);
+}
+
+#[test]
+fn oauth_redirect_uri_validation() {
+    assert!(OAuthService::validate_redirect_uri("https://app.example.com/callback").is_ok());
+    assert!(OAuthService::validate_redirect_uri("http://localhost:3000/callback").is_ok());
+    assert!(OAuthService::validate_redirect_uri("http://app.example.com/callback").is_err());
+    assert!(OAuthService::validate_redirect_uri("https://app.example.com/cb#frag").is_err());
+    assert!(OAuthService::validate_redirect_uri("not a url").is_err());
+}
+
+#[test]
+fn oauth_pkce_s256_challenge() {
+    // BASE64URL(SHA256(verifier)) without padding
+    let verifier = "dBjftJeZ4CVP-mJ0kz2-w3nTJ7jRHzC4g0Ia4J1OiRs";
+    let challenge = "HiPkiCi59h_sr_JH2DUVzfnrq9iOnI_7kXniQ4xt3Qo";
+    assert!(OAuthService::verify_code_challenge(challenge, verifier));
+    assert!(!OAuthService::verify_code_challenge(
+        challenge,
+        "wrong-verifier"
+    ));
+}
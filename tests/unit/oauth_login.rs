@@ -0,0 +1,88 @@
+use rust_backend::config::LoginProviderConfig;
+use rust_backend::db::models::user_identity::{ExternalProfile, LoginProvider};
+use rust_backend::services::oauth_login_service::OAuthLoginService;
+use serde_json::json;
+
+#[test]
+fn oauth_login_authorize_url_carries_state_and_redirect() {
+    let settings = LoginProviderConfig {
+        client_id: "client-123".to_string(),
+        client_secret: "secret".to_string(),
+        redirect_uri: "https://api.example.com/auth/oauth/github/callback".to_string(),
+    };
+    let url = url::Url::parse(&OAuthLoginService::authorize_url(
+        LoginProvider::Github,
+        &settings,
+        "state-abc",
+    ))
+    .unwrap();
+    assert_eq!(url.host_str(), Some("github.com"));
+    let params: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+    assert_eq!(params["client_id"], "client-123");
+    assert_eq!(params["redirect_uri"], settings.redirect_uri);
+    assert_eq!(params["state"], "state-abc");
+    assert_eq!(params["scope"], "read:user user:email");
+    assert!(!params.contains_key("client_secret"));
+
+    assert_eq!(LoginProvider::parse("google"), Some(LoginProvider::Google));
+    assert_eq!(LoginProvider::parse("gitlab"), None);
+}
+
+#[test]
+fn oauth_login_parses_provider_profiles() {
+    let google = OAuthLoginService::google_profile(&json!({
+        "sub": "1098",
+        "email": "ada@example.com",
+        "email_verified": true,
+        "name": "Ada Lovelace",
+        "picture": "https://example.com/a.png"
+    }))
+    .unwrap();
+    assert_eq!(google.provider_user_id, "1098");
+    assert!(google.email_verified);
+
+    let user = json!({"id": 42, "login": "ada-l", "name": null, "email": "public@example.com"});
+    let emails = json!([
+        {"email": "old@example.com", "primary": false, "verified": true},
+        {"email": "ada@example.com", "primary": true, "verified": true},
+        {"email": "unverified@example.com", "primary": false, "verified": false}
+    ]);
+    let github = OAuthLoginService::github_profile(&user, &emails).unwrap();
+    assert_eq!(github.provider_user_id, "42");
+    assert_eq!(github.email.as_deref(), Some("ada@example.com"));
+    assert!(github.email_verified);
+
+    // Without the emails scope only the public, unverified address is known
+    let github = OAuthLoginService::github_profile(&user, &serde_json::Value::Null).unwrap();
+    assert_eq!(github.email.as_deref(), Some("public@example.com"));
+    assert!(!github.email_verified);
+    assert!(OAuthLoginService::github_profile(&json!({}), &emails).is_none());
+}
+
+#[test]
+fn oauth_login_derives_valid_usernames() {
+    let profile = |hint: Option<&str>, email: Option<&str>| ExternalProfile {
+        provider_user_id: "1".to_string(),
+        email: email.map(str::to_string),
+        email_verified: true,
+        name: None,
+        username_hint: hint.map(str::to_string),
+        avatar_url: None,
+    };
+    assert_eq!(
+        OAuthLoginService::base_username(&profile(Some("Ada-L"), None)),
+        "ada_l"
+    );
+    assert_eq!(
+        OAuthLoginService::base_username(&profile(None, Some("grace.hopper@example.com"))),
+        "grace_hopper"
+    );
+    assert_eq!(
+        OAuthLoginService::base_username(&profile(None, Some("x@example.com"))),
+        "xuser"
+    );
+    assert_eq!(
+        OAuthLoginService::base_username(&profile(None, None)),
+        "user"
+    );
+}
@@ -0,0 +1,143 @@
+use rust_backend::db::models::issue::Issue;
+use rust_backend::db::models::issue_relation::ExternalIssueRef;
+use rust_backend::db::models::organization::Organization;
+use rust_backend::db::models::workspace::Workspace;
+use rust_backend::services::cross_workspace_relations_service::CrossWorkspaceRelationsService;
+use rust_backend::services::organizations_service::OrganizationsService;
+use uuid::Uuid;
+
+fn organization(allow_cross_workspace_links: bool) -> Organization {
+    Organization {
+        id: Uuid::new_v4(),
+        name: "Acme".to_string(),
+        allow_cross_workspace_links,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    }
+}
+
+fn workspace(url_key: &str, organization_id: Option<Uuid>) -> Workspace {
+    Workspace {
+        id: Uuid::new_v4(),
+        name: url_key.to_string(),
+        url_key: url_key.to_string(),
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        logo_url: None,
+        organization_id,
+    }
+}
+
+fn issue(issue_number: i32, title: &str) -> Issue {
+    Issue {
+        id: Uuid::new_v4(),
+        project_id: None,
+        cycle_id: None,
+        creator_id: Uuid::new_v4(),
+        assignee_id: None,
+        parent_issue_id: None,
+        issue_number,
+        title: title.to_string(),
+        description: Some("Internal details".to_string()),
+        priority: "high".to_string(),
+        is_changelog_candidate: false,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        team_id: Uuid::new_v4(),
+        workflow_id: None,
+        workflow_state_id: None,
+        archived_at: None,
+        archive_batch_id: None,
+        estimate: None,
+    }
+}
+
+#[test]
+fn external_references_name_workspace_and_issue_key() {
+    assert_eq!(
+        ExternalIssueRef::parse(" acme-mobile/ENG-42 "),
+        Some(ExternalIssueRef {
+            workspace_url_key: "acme-mobile".to_string(),
+            team_key: "ENG".to_string(),
+            issue_number: 42,
+        })
+    );
+    // Team keys may contain hyphens; the number follows the last one
+    assert_eq!(
+        ExternalIssueRef::parse("acme/web-app-7").map(|r| r.team_key),
+        Some("web-app".to_string())
+    );
+    assert!(ExternalIssueRef::parse("ENG-42").is_none());
+    assert!(ExternalIssueRef::parse("/ENG-42").is_none());
+    assert!(ExternalIssueRef::parse("acme/-42").is_none());
+    assert!(ExternalIssueRef::parse("acme/ENG-0").is_none());
+    assert!(ExternalIssueRef::parse("acme/ENG-x").is_none());
+}
+
+#[test]
+fn read_through_needs_the_policy_and_a_sibling_workspace() {
+    let open = organization(true);
+    let closed = organization(false);
+    let sibling = workspace("sibling", Some(open.id));
+    let stranger = workspace("stranger", Some(Uuid::new_v4()));
+
+    assert!(CrossWorkspaceRelationsService::read_through_allowed(
+        Some(&open),
+        &sibling,
+        true
+    ));
+    assert!(!CrossWorkspaceRelationsService::read_through_allowed(
+        Some(&open),
+        &sibling,
+        false
+    ));
+    assert!(!CrossWorkspaceRelationsService::read_through_allowed(
+        Some(&open),
+        &stranger,
+        true
+    ));
+    let closed_sibling = workspace("closed", Some(closed.id));
+    assert!(!CrossWorkspaceRelationsService::read_through_allowed(
+        Some(&closed),
+        &closed_sibling,
+        true
+    ));
+    assert!(!CrossWorkspaceRelationsService::read_through_allowed(
+        None, &sibling, true
+    ));
+}
+
+#[test]
+fn summaries_only_expose_key_title_and_state() {
+    let other = workspace("acme-mobile", None);
+    let issue = issue(42, "Crash on login");
+
+    let readable =
+        CrossWorkspaceRelationsService::summarize(&issue, "ENG", &other, Some("In Progress"), true);
+    assert_eq!(readable.key, "ENG-42");
+    assert_eq!(readable.workspace_url_key, "acme-mobile");
+    assert_eq!(readable.title.as_deref(), Some("Crash on login"));
+    assert_eq!(readable.state.as_deref(), Some("In Progress"));
+    assert!(!readable.restricted);
+    let json = serde_json::to_value(&readable).unwrap();
+    assert!(json.get("description").is_none());
+    assert!(json.get("priority").is_none());
+
+    let restricted = CrossWorkspaceRelationsService::summarize(
+        &issue,
+        "ENG",
+        &other,
+        Some("In Progress"),
+        false,
+    );
+    assert_eq!(restricted.key, "ENG-42");
+    assert!(restricted.title.is_none());
+    assert!(restricted.state.is_none());
+    assert!(restricted.restricted);
+}
+
+#[test]
+fn organization_names_are_required() {
+    assert!(OrganizationsService::validate_name("Acme").is_ok());
+    assert!(OrganizationsService::validate_name("  ").is_err());
+}
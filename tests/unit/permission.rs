@@ -0,0 +1,147 @@
+// Workspace RBAC permission matrix tests
+
+use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+use rust_backend::services::channel_permissions_service::ChannelPermissionsService;
+use rust_backend::services::context::AuthChannel;
+use rust_backend::services::permission_service::{Permission, PermissionService};
+
+#[test]
+fn owner_holds_every_permission() {
+    for permission in [
+        Permission::DeleteWorkspace,
+        Permission::UpdateWorkspace,
+        Permission::ManageTeams,
+        Permission::DeleteIssue,
+        Permission::CreateComment,
+    ] {
+        assert!(PermissionService::role_allows(
+            &WorkspaceMemberRole::Owner,
+            permission
+        ));
+    }
+}
+
+#[test]
+fn admin_cannot_delete_workspace() {
+    let admin = WorkspaceMemberRole::Admin;
+    assert!(!PermissionService::role_allows(
+        &admin,
+        Permission::DeleteWorkspace
+    ));
+    assert!(PermissionService::role_allows(
+        &admin,
+        Permission::UpdateWorkspace
+    ));
+    assert!(PermissionService::role_allows(
+        &admin,
+        Permission::InviteMembers
+    ));
+    assert!(PermissionService::role_allows(
+        &admin,
+        Permission::DeleteProject
+    ));
+}
+
+#[test]
+fn member_edits_content_but_not_settings() {
+    let member = WorkspaceMemberRole::Member;
+    assert!(PermissionService::role_allows(
+        &member,
+        Permission::CreateIssue
+    ));
+    assert!(PermissionService::role_allows(
+        &member,
+        Permission::UpdateProject
+    ));
+    assert!(PermissionService::role_allows(
+        &member,
+        Permission::ManageLabels
+    ));
+    assert!(!PermissionService::role_allows(
+        &member,
+        Permission::DeleteProject
+    ));
+    assert!(!PermissionService::role_allows(
+        &member,
+        Permission::ManageTeams
+    ));
+    assert!(!PermissionService::role_allows(
+        &member,
+        Permission::InviteMembers
+    ));
+    assert!(!PermissionService::role_allows(
+        &member,
+        Permission::ViewIssueViewers
+    ));
+}
+
+#[test]
+fn guest_can_only_comment() {
+    let guest = WorkspaceMemberRole::Guest;
+    assert!(PermissionService::role_allows(
+        &guest,
+        Permission::CreateComment
+    ));
+    assert!(!PermissionService::role_allows(
+        &guest,
+        Permission::CreateIssue
+    ));
+    assert!(!PermissionService::role_allows(
+        &guest,
+        Permission::UpdateIssue
+    ));
+    assert!(!PermissionService::role_allows(
+        &guest,
+        Permission::ManageCycles
+    ));
+}
+
+#[test]
+fn permission_keys_round_trip() {
+    for permission in Permission::ALL {
+        assert_eq!(Permission::parse(permission.key()), Some(permission));
+    }
+    assert_eq!(Permission::parse("delete_everything"), None);
+}
+
+#[test]
+fn api_channels_never_delete_workspace_or_lift_restrictions() {
+    assert!(Permission::DeleteWorkspace.always_denied_to_api());
+    assert!(Permission::ManageApiAccess.always_denied_to_api());
+    assert!(!Permission::CreateIssue.always_denied_to_api());
+
+    assert!(!AuthChannel::Session.is_api());
+    for channel in AuthChannel::API {
+        assert!(channel.is_api());
+        assert_eq!(AuthChannel::parse(channel.as_str()), Some(channel));
+    }
+}
+
+#[test]
+fn channel_restrictions_validate_and_dedupe_permissions() {
+    let requested = vec![
+        "delete_issue".to_string(),
+        " delete_issue ".to_string(),
+        "delete_workspace".to_string(),
+        "manage_webhooks".to_string(),
+    ];
+    assert_eq!(
+        ChannelPermissionsService::restrictable(&requested).unwrap(),
+        vec![Permission::DeleteIssue, Permission::ManageWebhooks]
+    );
+    assert!(ChannelPermissionsService::restrictable(&["nope".to_string()]).is_err());
+}
+
+#[test]
+fn managing_members_needs_admin() {
+    let admin = WorkspaceMemberRole::Admin;
+    let member = WorkspaceMemberRole::Member;
+    assert!(PermissionService::role_allows(
+        &admin,
+        Permission::ManageMembers
+    ));
+    assert!(!PermissionService::role_allows(
+        &member,
+        Permission::ManageMembers
+    ));
+}
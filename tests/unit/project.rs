@@ -7,3 +7,67 @@ fn validate_create_project_rules() {
     assert!(validate_create_project("Alpha", "TOO_LONG_KEY_123").is_err());
     assert!(validate_create_project("Alpha", "BAD KEY").is_err());
 }
+
+#[test]
+fn board_categories_are_parsed_in_column_order() {
+    use rust_backend::db::models::project_status::ProjectStatusCategory;
+    use rust_backend::services::projects_service::ProjectsService;
+
+    assert_eq!(
+        ProjectsService::parse_board_categories("in_progress, planned,planned").unwrap(),
+        vec![
+            ProjectStatusCategory::Planned,
+            ProjectStatusCategory::InProgress
+        ]
+    );
+    assert!(
+        ProjectsService::parse_board_categories("")
+            .unwrap()
+            .is_empty()
+    );
+    assert!(ProjectsService::parse_board_categories("planned,shipped").is_err());
+}
+
+#[test]
+fn project_health_compares_progress_with_time_elapsed() {
+    use chrono::NaiveDate;
+    use rust_backend::db::models::project::ProjectHealth;
+    use rust_backend::db::models::project_status::ProjectStatusCategory;
+    use rust_backend::services::projects_service::ProjectsService;
+
+    let day = |d: u32| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+    let health = |category, target, today, total, completed| {
+        ProjectsService::project_health(category, day(1), target, today, total, completed)
+    };
+    let in_progress = ProjectStatusCategory::InProgress;
+
+    // Halfway through with half the issues done
+    assert_eq!(
+        health(in_progress, Some(day(21)), day(11), 10, 5),
+        Some(ProjectHealth::OnTrack)
+    );
+    // Halfway through with nothing done
+    assert_eq!(
+        health(in_progress, Some(day(21)), day(11), 10, 0),
+        Some(ProjectHealth::AtRisk)
+    );
+    assert_eq!(
+        health(in_progress, Some(day(21)), day(25), 10, 9),
+        Some(ProjectHealth::OffTrack)
+    );
+    assert_eq!(
+        health(in_progress, Some(day(21)), day(25), 10, 10),
+        Some(ProjectHealth::OnTrack)
+    );
+    assert_eq!(health(in_progress, None, day(11), 10, 0), None);
+    assert_eq!(
+        health(
+            ProjectStatusCategory::Completed,
+            Some(day(21)),
+            day(25),
+            10,
+            2
+        ),
+        None
+    );
+}
@@ -0,0 +1,135 @@
+use rust_backend::db::enums::ProjectPriority;
+use rust_backend::db::models::project::Project;
+use rust_backend::db::models::project_cost::{BudgetStatus, ProjectCostEntry};
+use rust_backend::services::project_costs_service::ProjectCostsService;
+use uuid::Uuid;
+
+fn project(budget_amount_cents: Option<i64>) -> Project {
+    Project {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        roadmap_id: None,
+        owner_id: Uuid::new_v4(),
+        name: "Website".to_string(),
+        project_key: "WEB".to_string(),
+        description: None,
+        target_date: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        project_status_id: Uuid::new_v4(),
+        priority: ProjectPriority::Medium,
+        budget_currency: Some("EUR".to_string()),
+        budget_amount_cents,
+        budget_alert_percent: 80,
+        budget_alert_level: "ok".to_string(),
+    }
+}
+
+fn entry(
+    project_id: Uuid,
+    user_id: Uuid,
+    minutes: i32,
+    hourly_rate_cents: i64,
+) -> ProjectCostEntry {
+    ProjectCostEntry {
+        id: Uuid::new_v4(),
+        project_id,
+        user_id,
+        issue_id: None,
+        minutes,
+        hourly_rate_cents,
+        amount_cents: ProjectCostsService::cost_cents(minutes, hourly_rate_cents),
+        currency: "EUR".to_string(),
+        spent_on: chrono::NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(),
+        description: None,
+        created_by: Some(user_id),
+        created_at: chrono::Utc::now(),
+    }
+}
+
+#[test]
+fn currencies_are_three_letter_codes() {
+    assert_eq!(
+        ProjectCostsService::normalize_currency(" usd ").unwrap(),
+        "USD"
+    );
+    assert!(ProjectCostsService::normalize_currency("US").is_err());
+    assert!(ProjectCostsService::normalize_currency("EURO").is_err());
+    assert!(ProjectCostsService::normalize_currency("U$D").is_err());
+}
+
+#[test]
+fn time_is_priced_to_the_nearest_cent() {
+    assert_eq!(ProjectCostsService::cost_cents(60, 10_000), 10_000);
+    assert_eq!(ProjectCostsService::cost_cents(90, 10_000), 15_000);
+    // 1 minute at 1.00/h is 1.67 cents
+    assert_eq!(ProjectCostsService::cost_cents(1, 100), 2);
+    assert_eq!(ProjectCostsService::cost_cents(1, 20), 0);
+}
+
+#[test]
+fn budget_status_follows_the_alert_threshold() {
+    assert_eq!(
+        ProjectCostsService::budget_status(5_000, None, 80),
+        BudgetStatus::NoBudget
+    );
+    assert_eq!(
+        ProjectCostsService::budget_status(7_999, Some(10_000), 80),
+        BudgetStatus::Ok
+    );
+    assert_eq!(
+        ProjectCostsService::budget_status(8_000, Some(10_000), 80),
+        BudgetStatus::Approaching
+    );
+    assert_eq!(
+        ProjectCostsService::budget_status(10_000, Some(10_000), 80),
+        BudgetStatus::Approaching
+    );
+    assert_eq!(
+        ProjectCostsService::budget_status(10_001, Some(10_000), 80),
+        BudgetStatus::Exceeded
+    );
+    assert!(BudgetStatus::Exceeded > BudgetStatus::Approaching);
+    assert_eq!(BudgetStatus::NoBudget.as_str(), "ok");
+    assert_eq!(
+        BudgetStatus::parse("approaching"),
+        Some(BudgetStatus::Approaching)
+    );
+}
+
+#[test]
+fn summary_totals_spend_per_member() {
+    let project = project(Some(50_000));
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+    let entries = vec![
+        entry(project.id, alice, 120, 6_000),
+        entry(project.id, bob, 60, 9_000),
+        entry(project.id, alice, 30, 6_000),
+    ];
+
+    let summary = ProjectCostsService::summarize(&project, &entries);
+    assert_eq!(summary.spent_cents, 24_000);
+    assert_eq!(summary.remaining_cents, Some(26_000));
+    assert_eq!(summary.total_minutes, 210);
+    assert_eq!(summary.percent_used, Some(48.0));
+    assert_eq!(summary.status, BudgetStatus::Ok);
+    assert_eq!(summary.currency.as_deref(), Some("EUR"));
+    assert_eq!(summary.by_member.len(), 2);
+    assert_eq!(summary.by_member[0].user_id, alice);
+    assert_eq!(summary.by_member[0].amount_cents, 15_000);
+    assert_eq!(summary.by_member[0].minutes, 150);
+    assert_eq!(summary.by_member[1].amount_cents, 9_000);
+}
+
+#[test]
+fn summary_without_budget_still_reports_spend() {
+    let project = project(None);
+    let entries = vec![entry(project.id, Uuid::new_v4(), 60, 5_000)];
+
+    let summary = ProjectCostsService::summarize(&project, &entries);
+    assert_eq!(summary.spent_cents, 5_000);
+    assert_eq!(summary.remaining_cents, None);
+    assert_eq!(summary.percent_used, None);
+    assert_eq!(summary.status, BudgetStatus::NoBudget);
+}
@@ -0,0 +1,134 @@
+// Push subscription and delivery tests
+
+use chrono::Utc;
+use rust_backend::db::models::notification::{Notification, notification_events};
+use rust_backend::db::models::push::{
+    PushPreferenceRecord, PushPreferences, RegisterPushSubscriptionRequest, push_platforms,
+};
+use rust_backend::services::push_service::{PushOutcome, PushService};
+use uuid::Uuid;
+
+fn register(platform: &str, token: &str) -> RegisterPushSubscriptionRequest {
+    RegisterPushSubscriptionRequest {
+        platform: platform.to_string(),
+        token: token.to_string(),
+        device_name: Some("  Pixel 8  ".to_string()),
+    }
+}
+
+#[test]
+fn push_registration_validates_platform_and_token() {
+    let user = Uuid::new_v4();
+    let web = PushService::validate_registration(
+        user,
+        &register("web", " https://fcm.googleapis.com/fcm/send/abc "),
+    )
+    .unwrap();
+    assert_eq!(web.platform, push_platforms::WEB);
+    assert_eq!(web.token, "https://fcm.googleapis.com/fcm/send/abc");
+    assert_eq!(web.device_name.as_deref(), Some("Pixel 8"));
+
+    assert!(PushService::validate_registration(user, &register("apns", "a1B2c3")).is_ok());
+    assert!(PushService::validate_registration(user, &register("apns", "not-hex")).is_err());
+    assert!(PushService::validate_registration(user, &register("web", "http://x")).is_err());
+    assert!(PushService::validate_registration(user, &register("fcm", "")).is_err());
+    assert!(PushService::validate_registration(user, &register("sms", "123")).is_err());
+}
+
+#[test]
+fn push_responses_are_classified_per_platform() {
+    assert_eq!(
+        PushService::classify("web", 201, ""),
+        PushOutcome::Delivered
+    );
+    assert_eq!(PushService::classify("web", 410, ""), PushOutcome::Gone);
+    assert_eq!(PushService::classify("web", 404, ""), PushOutcome::Gone);
+    assert_eq!(
+        PushService::classify("apns", 400, r#"{"reason":"BadDeviceToken"}"#),
+        PushOutcome::Gone
+    );
+    assert!(matches!(
+        PushService::classify("apns", 400, r#"{"reason":"PayloadTooLarge"}"#),
+        PushOutcome::Rejected(_)
+    ));
+    assert!(matches!(
+        PushService::classify("apns", 403, r#"{"reason":"ExpiredProviderToken"}"#),
+        PushOutcome::Retry(_)
+    ));
+    assert_eq!(
+        PushService::classify("fcm", 400, r#"{"error":{"status":"UNREGISTERED"}}"#),
+        PushOutcome::Gone
+    );
+    assert!(matches!(
+        PushService::classify("fcm", 401, ""),
+        PushOutcome::Retry(_)
+    ));
+    assert!(matches!(
+        PushService::classify("web", 429, ""),
+        PushOutcome::Retry(_)
+    ));
+    assert!(matches!(
+        PushService::classify("web", 503, ""),
+        PushOutcome::Retry(_)
+    ));
+}
+
+#[test]
+fn push_retry_delay_backs_off() {
+    assert_eq!(PushService::retry_delay(1).num_seconds(), 15);
+    assert_eq!(PushService::retry_delay(2).num_seconds(), 30);
+    assert_eq!(PushService::retry_delay(4).num_seconds(), 120);
+}
+
+#[test]
+fn push_preferences_mute_events() {
+    assert!(PushPreferences::default().allows(notification_events::MENTIONED));
+
+    let preferences = PushPreferences::from(PushPreferenceRecord {
+        user_id: Uuid::new_v4(),
+        enabled: true,
+        muted_events: "issue_updated budget_alert".to_string(),
+        updated_at: Utc::now(),
+    });
+    assert!(!preferences.allows(notification_events::ISSUE_UPDATED));
+    assert!(preferences.allows(notification_events::MENTIONED));
+
+    let disabled = PushPreferences {
+        enabled: false,
+        muted_events: Vec::new(),
+    };
+    assert!(!disabled.allows(notification_events::MENTIONED));
+
+    let muted =
+        PushService::normalize_muted_events(&["mentioned".to_string(), " mentioned ".to_string()])
+            .unwrap();
+    assert_eq!(muted, vec!["mentioned"]);
+    assert!(PushService::normalize_muted_events(&["nope".to_string()]).is_err());
+}
+
+#[test]
+fn push_payload_carries_notification_target() {
+    let now = Utc::now();
+    let notification = Notification {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        recipient_id: Uuid::new_v4(),
+        actor_id: None,
+        event_type: notification_events::ISSUE_ASSIGNED.to_string(),
+        entity_type: "issue".to_string(),
+        entity_id: Uuid::new_v4(),
+        title: "MOM-12 assigned to you".to_string(),
+        body: None,
+        event_count: 1,
+        read_at: None,
+        email_pending: true,
+        emailed_at: None,
+        created_at: now,
+        updated_at: now,
+        in_app: true,
+    };
+    let payload = PushService::payload(&notification);
+    assert_eq!(payload.notification_id, notification.id);
+    assert_eq!(payload.entity_id, notification.entity_id);
+    assert_eq!(payload.title, notification.title);
+}
@@ -0,0 +1,42 @@
+use axum::http::{HeaderMap, HeaderValue};
+use rust_backend::middleware::rate_limit::{HttpRateLimiter, client_ip};
+use std::net::IpAddr;
+
+#[test]
+fn rate_limit_retry_after_rounds_up_to_whole_seconds() {
+    let now = 1_000_000;
+    // Oldest request 59.5s ago in a 60s window: 0.5s left, reported as 1s
+    assert_eq!(HttpRateLimiter::retry_after_secs(now - 59_500, now, 60), 1);
+    assert_eq!(HttpRateLimiter::retry_after_secs(now - 10_000, now, 60), 50);
+    assert_eq!(HttpRateLimiter::retry_after_secs(now - 10_001, now, 60), 50);
+    // Already outside the window
+    assert_eq!(HttpRateLimiter::retry_after_secs(now - 90_000, now, 60), 1);
+}
+
+#[test]
+fn rate_limit_client_ip_only_trusts_proxy_headers_when_configured() {
+    let peer: IpAddr = "10.0.0.5".parse().unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "x-forwarded-for",
+        HeaderValue::from_static("203.0.113.7, 10.0.0.1"),
+    );
+
+    assert_eq!(client_ip(&headers, Some(peer), false), Some(peer));
+    assert_eq!(
+        client_ip(&headers, Some(peer), true),
+        Some("203.0.113.7".parse().unwrap())
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-real-ip", HeaderValue::from_static("2001:db8::1"));
+    assert_eq!(
+        client_ip(&headers, Some(peer), true),
+        Some("2001:db8::1".parse().unwrap())
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-forwarded-for", HeaderValue::from_static("not-an-ip"));
+    assert_eq!(client_ip(&headers, Some(peer), true), Some(peer));
+    assert_eq!(client_ip(&HeaderMap::new(), None, true), None);
+}
@@ -0,0 +1,117 @@
+// Tests for role-aware field redaction
+
+use rust_backend::db::models::auth::UserBasicInfo;
+use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+use rust_backend::services::redaction_service::{RedactionService, Viewer};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+fn viewer(role: Option<WorkspaceMemberRole>) -> Viewer {
+    Viewer {
+        user_id: Uuid::new_v4(),
+        email: Some("me@example.com".to_string()),
+        role,
+    }
+}
+
+fn payload() -> Value {
+    json!({
+        "members": [
+            { "id": Uuid::new_v4(), "name": "Jane", "email": "jane@example.com" }
+        ],
+        "invitation": { "email": "new@example.com", "invitation_token": "inv_secret" },
+        "billing": {
+            "billing_email": "billing@example.com",
+            "billing_address": "1 Main St",
+            "tax_id": "DE123",
+            "card_last4": "4242"
+        }
+    })
+}
+
+fn redacted_for(role: Option<WorkspaceMemberRole>) -> Value {
+    let mut value = payload();
+    RedactionService::redact_value(&mut value, &viewer(role));
+    value
+}
+
+#[test]
+fn guest_sees_masked_emails_and_no_secrets() {
+    let value = redacted_for(Some(WorkspaceMemberRole::Guest));
+    assert_eq!(value["members"][0]["email"], "j***@example.com");
+    assert_eq!(value["members"][0]["name"], "Jane");
+    assert_eq!(value["invitation"]["email"], "n***@example.com");
+    assert_eq!(value["invitation"]["invitation_token"], Value::Null);
+    assert_eq!(value["billing"]["billing_email"], "b***@example.com");
+    assert_eq!(value["billing"]["card_last4"], Value::Null);
+}
+
+#[test]
+fn member_sees_emails_but_not_invitation_tokens_or_billing() {
+    let value = redacted_for(Some(WorkspaceMemberRole::Member));
+    assert_eq!(value["members"][0]["email"], "jane@example.com");
+    assert_eq!(value["invitation"]["invitation_token"], Value::Null);
+    assert_eq!(value["billing"]["billing_address"], Value::Null);
+    assert_eq!(value["billing"]["tax_id"], Value::Null);
+}
+
+#[test]
+fn admin_sees_invitation_tokens_but_not_billing() {
+    let value = redacted_for(Some(WorkspaceMemberRole::Admin));
+    assert_eq!(value["invitation"]["invitation_token"], "inv_secret");
+    assert_eq!(value["billing"]["billing_email"], "b***@example.com");
+    assert_eq!(value["billing"]["billing_address"], Value::Null);
+}
+
+#[test]
+fn owner_sees_everything() {
+    let original = payload();
+    let mut value = original.clone();
+    RedactionService::redact_value(&mut value, &viewer(Some(WorkspaceMemberRole::Owner)));
+    assert_eq!(value, original);
+}
+
+#[test]
+fn non_member_sees_nothing_sensitive() {
+    let value = redacted_for(None);
+    assert_eq!(value["members"][0]["email"], "j***@example.com");
+    assert_eq!(value["invitation"]["invitation_token"], Value::Null);
+}
+
+#[test]
+fn viewer_keeps_their_own_email() {
+    let me = viewer(Some(WorkspaceMemberRole::Guest));
+    let users = vec![
+        UserBasicInfo {
+            id: me.user_id,
+            name: "Me".to_string(),
+            username: "me".to_string(),
+            email: "me+work@example.com".to_string(),
+            avatar_url: None,
+        },
+        UserBasicInfo {
+            id: Uuid::new_v4(),
+            name: "Other".to_string(),
+            username: "other".to_string(),
+            email: "other@example.com".to_string(),
+            avatar_url: None,
+        },
+    ];
+    let value = RedactionService::redact(&users, &me).unwrap();
+    assert_eq!(value[0]["email"], "me+work@example.com");
+    assert_eq!(value[1]["email"], "o***@example.com");
+
+    let mut invitation = json!({ "id": Uuid::new_v4(), "email": "me@example.com" });
+    RedactionService::redact_value(&mut invitation, &me);
+    assert_eq!(invitation["email"], "me@example.com");
+}
+
+#[test]
+fn sensitive_field_detection() {
+    assert!(RedactionService::may_contain_sensitive(
+        br#"{"data":{"email":"a@b.c"}}"#
+    ));
+    assert!(!RedactionService::may_contain_sensitive(
+        br#"{"data":{"title":"email me"}}"#
+    ));
+}
@@ -0,0 +1,56 @@
+use rust_backend::error::AppError;
+use rust_backend::services::sandbox_service::SandboxService;
+use uuid::Uuid;
+
+#[test]
+fn every_profile_parses_into_records() {
+    for name in SandboxService::profile_names() {
+        let bundle = SandboxService::load_profile(name).unwrap();
+        assert!(!bundle.teams.is_empty(), "{} has no teams", name);
+        assert!(!bundle.issues.is_empty(), "{} has no issues", name);
+        for issue in &bundle.issues {
+            assert!(
+                bundle
+                    .teams
+                    .iter()
+                    .any(|t| t.external_id == issue.team_external_id),
+                "{}: {} references an unknown team",
+                name,
+                issue.identifier
+            );
+            for label in &issue.label_external_ids {
+                assert!(
+                    bundle.labels.iter().any(|l| &l.external_id == label),
+                    "{}: {} references an unknown label",
+                    name,
+                    issue.identifier
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn unknown_profiles_are_rejected_with_the_available_ones() {
+    match SandboxService::load_profile("enterprise") {
+        Err(AppError::Validation { message }) => {
+            assert!(message.contains("demo"));
+            assert!(message.contains("starter"));
+        }
+        other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn parse_job_reads_only_reset_tasks() {
+    let id = Uuid::new_v4();
+    assert_eq!(
+        SandboxService::parse_job(&format!("workspace_reset:{}", id)),
+        Some(id)
+    );
+    assert_eq!(
+        SandboxService::parse_job(&format!("workspace_import:{}", id)),
+        None
+    );
+    assert_eq!(SandboxService::parse_job("workspace_reset:nope"), None);
+}
@@ -0,0 +1,135 @@
+use chrono::{TimeZone, Utc};
+use rust_backend::db::models::scheduled_job::ScheduledJobRecord;
+use rust_backend::scheduler::{Schedule, ScheduledJob, Scheduler};
+use std::time::Duration;
+
+fn at(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+}
+
+fn schedule(value: &str) -> Schedule {
+    value.parse().unwrap()
+}
+
+#[test]
+fn cron_schedules_find_the_next_matching_minute() {
+    // 2025-01-01 is a Wednesday
+    assert_eq!(
+        schedule("*/15 * * * *").next_after(at(2025, 1, 1, 10, 7, 30)),
+        Some(at(2025, 1, 1, 10, 15, 0))
+    );
+    assert_eq!(
+        schedule("*/15 * * * *").next_after(at(2025, 1, 1, 10, 15, 0)),
+        Some(at(2025, 1, 1, 10, 30, 0))
+    );
+    assert_eq!(
+        schedule("@daily").next_after(at(2025, 12, 31, 23, 59, 0)),
+        Some(at(2026, 1, 1, 0, 0, 0))
+    );
+    // 7 is Sunday
+    assert_eq!(
+        schedule("0 0 * * 7").next_after(at(2025, 1, 1, 0, 0, 0)),
+        Some(at(2025, 1, 5, 0, 0, 0))
+    );
+    assert_eq!(
+        schedule("0 0 31 2 *").next_after(at(2025, 1, 1, 0, 0, 0)),
+        None
+    );
+}
+
+#[test]
+fn cron_runs_on_either_restricted_day_field() {
+    let first_or_monday = schedule("0 9 1 * 1");
+    assert_eq!(
+        first_or_monday.next_after(at(2025, 1, 1, 10, 0, 0)),
+        Some(at(2025, 1, 6, 9, 0, 0))
+    );
+    assert_eq!(
+        first_or_monday.next_after(at(2025, 1, 27, 10, 0, 0)),
+        Some(at(2025, 2, 1, 9, 0, 0))
+    );
+}
+
+#[test]
+fn invalid_schedules_are_rejected() {
+    for value in [
+        "",
+        "* * * *",
+        "60 * * * *",
+        "* * 0 * *",
+        "*/0 * * * *",
+        "5-1 * * * *",
+        "@every",
+        "@every 0s",
+        "@every 5d",
+    ] {
+        assert!(
+            value.parse::<Schedule>().is_err(),
+            "{value} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn interval_schedules_run_after_the_interval() {
+    let every = schedule("@every 5m");
+    assert_eq!(
+        every.next_after(at(2025, 1, 1, 10, 7, 30)),
+        Some(at(2025, 1, 1, 10, 12, 30))
+    );
+    assert_eq!(every.to_string(), "@every 5m");
+    assert_eq!(
+        Schedule::every(Duration::from_secs(300)).next_after(at(2025, 1, 1, 10, 7, 30)),
+        Some(at(2025, 1, 1, 10, 12, 30))
+    );
+}
+
+#[test]
+fn overrides_replace_or_disable_job_schedules() {
+    let overrides =
+        Scheduler::parse_overrides("cycle_status = */5 * * * *; ws_connection_cleanup=off;")
+            .unwrap();
+    assert_eq!(overrides["cycle_status"], Some(schedule("*/5 * * * *")));
+    assert_eq!(overrides["ws_connection_cleanup"], None);
+    assert!(Scheduler::parse_overrides("cycle_status").is_err());
+    assert!(Scheduler::parse_overrides("cycle_status=often").is_err());
+}
+
+#[test]
+fn status_lists_registered_jobs_with_their_last_run() {
+    let scheduler = Scheduler::with_overrides("b_cleanup=off;a_sync=@hourly");
+    let job = |name: &str| {
+        ScheduledJob::new(name, Schedule::every(Duration::from_secs(60)), || async {
+            Ok(String::new())
+        })
+    };
+    scheduler.register(job("b_cleanup"));
+    scheduler.register(job("a_sync").exclusive());
+    scheduler.register(job("a_sync"));
+
+    let record = ScheduledJobRecord {
+        name: "a_sync".to_string(),
+        schedule: "@every 60s".to_string(),
+        last_status: Some("succeeded".to_string()),
+        last_started_at: Some(at(2025, 1, 1, 10, 0, 0)),
+        last_finished_at: Some(at(2025, 1, 1, 10, 0, 1)),
+        last_duration_ms: Some(1000),
+        last_result: Some("synced 3".to_string()),
+        last_error: None,
+        run_count: 4,
+        failure_count: 1,
+        updated_at: at(2025, 1, 1, 10, 0, 1),
+    };
+    let status = scheduler.status(vec![record]);
+
+    assert_eq!(status.len(), 2);
+    assert_eq!(status[0].name, "a_sync");
+    assert_eq!(status[0].schedule, "@hourly");
+    assert!(status[0].enabled && status[0].exclusive);
+    assert_eq!(status[0].last_result.as_deref(), Some("synced 3"));
+    assert_eq!(status[0].run_count, 4);
+    assert_eq!(status[1].name, "b_cleanup");
+    assert!(!status[1].enabled);
+    assert_eq!(status[1].last_status, None);
+    assert_eq!(status[1].run_count, 0);
+}
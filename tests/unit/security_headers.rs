@@ -0,0 +1,154 @@
+use axum::{
+    Json, Router,
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+    response::{Html, IntoResponse},
+    routing::get,
+};
+use rust_backend::config::{CorsConfig, SecurityHeadersConfig};
+use rust_backend::middleware::security_headers::{
+    SecurityHeaders, cors_layer, security_headers_middleware,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn headers_config(hsts_max_age_secs: u64) -> SecurityHeadersConfig {
+    SecurityHeadersConfig {
+        enabled: true,
+        hsts_max_age_secs,
+        hsts_include_subdomains: true,
+        referrer_policy: "no-referrer".to_string(),
+        content_security_policy: "default-src 'self'".to_string(),
+    }
+}
+
+fn app(config: &SecurityHeadersConfig, cors: &CorsConfig) -> Router {
+    Router::new()
+        .route(
+            "/issues",
+            get(|| async { Json(serde_json::json!({ "data": [] })) }),
+        )
+        .route("/docs", get(|| async { Html("<h1>API</h1>") }))
+        .route(
+            "/framed",
+            get(|| async { ([(header::X_FRAME_OPTIONS, "SAMEORIGIN")], "ok").into_response() }),
+        )
+        .layer(cors_layer(cors).unwrap())
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(SecurityHeaders::new(config).unwrap()),
+            security_headers_middleware,
+        ))
+}
+
+fn any_origin() -> CorsConfig {
+    CorsConfig {
+        origins: vec!["*".to_string()],
+        allow_credentials: false,
+        max_age_secs: 600,
+    }
+}
+
+async fn get_path(app: Router, path: &str) -> axum::response::Response {
+    app.oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn security_headers_on_json_routes() {
+    let response = get_path(app(&headers_config(31_536_000), &any_origin()), "/issues").await;
+    let headers = response.headers();
+    assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+    assert_eq!(headers[header::X_FRAME_OPTIONS], "DENY");
+    assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+    assert_eq!(
+        headers[header::STRICT_TRANSPORT_SECURITY],
+        "max-age=31536000; includeSubDomains"
+    );
+    // CSP only guards served HTML
+    assert!(!headers.contains_key(header::CONTENT_SECURITY_POLICY));
+}
+
+#[tokio::test]
+async fn security_headers_add_csp_to_html_and_keep_handler_values() {
+    let config = headers_config(0);
+    let response = get_path(app(&config, &any_origin()), "/docs").await;
+    assert_eq!(
+        response.headers()[header::CONTENT_SECURITY_POLICY],
+        "default-src 'self'"
+    );
+    assert!(
+        !response
+            .headers()
+            .contains_key(header::STRICT_TRANSPORT_SECURITY)
+    );
+
+    let response = get_path(app(&config, &any_origin()), "/framed").await;
+    assert_eq!(response.headers()[header::X_FRAME_OPTIONS], "SAMEORIGIN");
+
+    let mut disabled = config.clone();
+    disabled.enabled = false;
+    let response = get_path(app(&disabled, &any_origin()), "/issues").await;
+    assert!(
+        !response
+            .headers()
+            .contains_key(header::X_CONTENT_TYPE_OPTIONS)
+    );
+}
+
+#[tokio::test]
+async fn cors_preflight_honors_configured_origins() {
+    let cors = CorsConfig {
+        origins: vec!["https://app.example.com".to_string()],
+        allow_credentials: true,
+        max_age_secs: 600,
+    };
+    let preflight = |origin: &str| {
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/issues")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app(&headers_config(0), &cors)
+        .oneshot(preflight("https://app.example.com"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://app.example.com"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_HEADERS],
+        "authorization"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+    assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+
+    let response = app(&headers_config(0), &cors)
+        .oneshot(preflight("https://evil.example.com"))
+        .await
+        .unwrap();
+    assert!(
+        !response
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+    );
+}
+
+#[test]
+fn cors_rejects_invalid_origins() {
+    let cors = CorsConfig {
+        origins: vec!["https://bad\norigin".to_string()],
+        allow_credentials: false,
+        max_age_secs: 600,
+    };
+    assert!(cors_layer(&cors).is_err());
+}
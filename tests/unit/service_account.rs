@@ -0,0 +1,56 @@
+// Service account naming and scoped token tests
+
+use chrono::Utc;
+use rust_backend::db::models::api_token::{ApiToken, api_tiers};
+use rust_backend::services::service_accounts_service::ServiceAccountsService;
+use uuid::Uuid;
+
+#[test]
+fn usernames_are_derived_from_the_account_name() {
+    let base = ServiceAccountsService::base_username;
+    assert_eq!(base("Deploy Pipeline"), "deploy_pipeline_bot");
+    assert_eq!(base("CI Bot"), "ci_bot");
+    assert_eq!(base("  Sentry -> Issues  "), "sentry_issues_bot");
+    assert_eq!(base("!!!"), "bot");
+    assert_eq!(
+        base("Nightly Build Of All The Services"),
+        "nightly_build_of_all_the_s_bot"
+    );
+    assert!(base(&"x".repeat(200)).chars().count() <= 30);
+}
+
+#[test]
+fn explicit_usernames_are_validated_and_lowercased() {
+    let validate = ServiceAccountsService::validate_username;
+    assert_eq!(validate(" Release_Bot ").unwrap(), "release_bot");
+    assert!(validate("ab").is_err());
+    assert!(validate("has space").is_err());
+    assert!(validate("dash-bot").is_err());
+    assert!(validate(&"a".repeat(31)).is_err());
+}
+
+#[test]
+fn token_scopes_serialize_as_a_list() {
+    let mut token = ApiToken {
+        id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        name: "ci".to_string(),
+        token_prefix: "mtm_abcd".to_string(),
+        token_hash: "hash".to_string(),
+        tier: api_tiers::FREE.to_string(),
+        last_used_at: None,
+        expires_at: None,
+        revoked_at: None,
+        created_at: Utc::now(),
+        scopes: Some("read:issues write:issues".to_string()),
+    };
+    let json = serde_json::to_value(&token).unwrap();
+    assert_eq!(
+        json["scopes"],
+        serde_json::json!(["read:issues", "write:issues"])
+    );
+    assert!(json.get("token_hash").is_none());
+
+    token.scopes = None;
+    assert!(serde_json::to_value(&token).unwrap()["scopes"].is_null());
+}
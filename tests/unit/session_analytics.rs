@@ -0,0 +1,112 @@
+// Tests for WebSocket session analytics
+
+use chrono::{Duration, TimeZone, Utc};
+use rust_backend::db::models::websocket_session::WebSocketSession;
+use rust_backend::services::session_analytics_service::SessionAnalyticsService;
+use uuid::Uuid;
+
+fn session(
+    user_id: Uuid,
+    client: &str,
+    connected_at: chrono::DateTime<Utc>,
+    disconnected_at: Option<chrono::DateTime<Utc>>,
+) -> WebSocketSession {
+    WebSocketSession {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::nil(),
+        user_id,
+        connection_id: Uuid::new_v4().to_string(),
+        client: client.to_string(),
+        user_agent: None,
+        connected_at,
+        disconnected_at,
+    }
+}
+
+#[test]
+fn client_name_prefers_declared_name_then_user_agent() {
+    let name = SessionAnalyticsService::client_name;
+    assert_eq!(name(Some(" iOS-App "), None), "ios-app");
+    assert_eq!(
+        name(Some("bad name!"), Some("Mozilla/5.0 (X11; Linux x86_64)")),
+        "web"
+    );
+    assert_eq!(
+        name(
+            None,
+            Some("Mozilla/5.0 (Macintosh) Momentum/1.2 Electron/30.0")
+        ),
+        "desktop"
+    );
+    assert_eq!(
+        name(None, Some("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0)")),
+        "mobile"
+    );
+    assert_eq!(name(None, Some("curl/8.0")), "unknown");
+    assert_eq!(name(None, None), "unknown");
+}
+
+#[test]
+fn summarize_rolls_up_sessions() {
+    let now = Utc.with_ymd_and_hms(2025, 10, 8, 12, 0, 0).unwrap();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let day1 = Utc.with_ymd_and_hms(2025, 10, 7, 9, 0, 0).unwrap();
+    let sessions = vec![
+        session(alice, "web", day1, Some(day1 + Duration::minutes(30))),
+        session(bob, "desktop", day1, Some(day1 + Duration::minutes(10))),
+        // Still connected
+        session(alice, "web", now - Duration::minutes(5), None),
+        // Open for longer than the cut-off: counted as ended, capped at 24h
+        session(bob, "web", now - Duration::hours(30), None),
+    ];
+
+    let report = SessionAnalyticsService::summarize(
+        Uuid::nil(),
+        day1.date_naive(),
+        now.date_naive(),
+        now,
+        &sessions,
+    );
+
+    assert_eq!(report.total_sessions, 4);
+    assert_eq!(report.unique_users, 2);
+    assert_eq!(report.active_sessions, 1);
+    assert_eq!(report.total_duration_seconds, 1800 + 600 + 300 + 24 * 3600);
+    assert_eq!(report.sessions_by_hour[9], 2);
+    assert_eq!(report.sessions_by_hour[11], 1);
+    assert_eq!(report.sessions_by_hour[6], 1);
+
+    assert_eq!(report.clients[0].client, "web");
+    assert_eq!(report.clients[0].sessions, 3);
+    assert_eq!(report.clients[0].users, 2);
+    assert_eq!(report.clients[1].client, "desktop");
+
+    assert_eq!(report.daily.len(), 2);
+    assert_eq!(report.daily[0].day, day1.date_naive());
+    assert_eq!(report.daily[0].sessions, 3);
+    assert_eq!(report.daily[0].active_users, 2);
+    assert_eq!(report.daily[1].sessions, 1);
+
+    assert_eq!(report.users[0].user_id, bob);
+    assert_eq!(report.users[0].sessions, 2);
+    assert_eq!(
+        report.users[1].last_connected_at,
+        now - Duration::minutes(5)
+    );
+}
+
+#[test]
+fn summarize_empty_window() {
+    let now = Utc::now();
+    let report = SessionAnalyticsService::summarize(
+        Uuid::nil(),
+        now.date_naive(),
+        now.date_naive(),
+        now,
+        &[],
+    );
+    assert_eq!(report.total_sessions, 0);
+    assert_eq!(report.average_duration_seconds, 0.0);
+    assert_eq!(report.sessions_by_hour, vec![0; 24]);
+    assert!(report.users.is_empty());
+}
@@ -0,0 +1,73 @@
+use rust_backend::db::models::audit::SettingChange;
+use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+use rust_backend::services::permission_service::{Permission, PermissionService};
+use rust_backend::services::settings_history_service::SettingsHistoryService;
+use serde_json::json;
+
+#[test]
+fn settings_diff_lists_changed_fields_only() {
+    let before = json!({
+        "id": "6c1f6f06-8a4e-4bd8-9a55-0ad8f0b3c3b1",
+        "name": "Platform",
+        "team_key": "PLT",
+        "is_private": false,
+        "description": null,
+        "updated_at": "2025-01-01T00:00:00Z",
+    });
+    let after = json!({
+        "id": "6c1f6f06-8a4e-4bd8-9a55-0ad8f0b3c3b1",
+        "name": "Platform",
+        "team_key": "INF",
+        "is_private": true,
+        "description": null,
+        "updated_at": "2025-01-02T00:00:00Z",
+    });
+
+    assert_eq!(
+        SettingsHistoryService::diff(Some(&before), Some(&after)),
+        vec![
+            SettingChange {
+                field: "is_private".to_string(),
+                from: json!(false),
+                to: json!(true),
+            },
+            SettingChange {
+                field: "team_key".to_string(),
+                from: json!("PLT"),
+                to: json!("INF"),
+            },
+        ]
+    );
+    assert!(SettingsHistoryService::diff(Some(&before), Some(&before)).is_empty());
+}
+
+#[test]
+fn created_and_deleted_settings_diff_against_nothing() {
+    let workflow = json!({ "id": "x", "name": "Default", "is_default": true, "description": null });
+
+    let created = SettingsHistoryService::diff(None, Some(&workflow));
+    assert_eq!(
+        created
+            .iter()
+            .map(|change| change.field.as_str())
+            .collect::<Vec<_>>(),
+        vec!["is_default", "name"]
+    );
+    assert!(created.iter().all(|change| change.from.is_null()));
+
+    let deleted = SettingsHistoryService::diff(Some(&workflow), None);
+    assert_eq!(deleted.len(), 2);
+    assert!(deleted.iter().all(|change| change.to.is_null()));
+}
+
+#[test]
+fn only_admins_view_settings_history() {
+    assert!(PermissionService::role_allows(
+        &WorkspaceMemberRole::Admin,
+        Permission::ViewAuditLog
+    ));
+    assert!(!PermissionService::role_allows(
+        &WorkspaceMemberRole::Member,
+        Permission::ViewAuditLog
+    ));
+}
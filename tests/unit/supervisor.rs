@@ -0,0 +1,95 @@
+// Background task supervision tests
+
+use rust_backend::supervisor::{BackoffPolicy, TaskState, TaskSupervisor};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+fn fast_backoff() -> BackoffPolicy {
+    BackoffPolicy {
+        initial: Duration::from_millis(10),
+        max: Duration::from_millis(40),
+        reset_after: Duration::from_secs(60),
+    }
+}
+
+#[test]
+fn supervisor_backoff_doubles_up_to_max() {
+    let policy = BackoffPolicy {
+        initial: Duration::from_secs(1),
+        max: Duration::from_secs(10),
+        reset_after: Duration::from_secs(60),
+    };
+    assert_eq!(policy.delay(1), Duration::from_secs(1));
+    assert_eq!(policy.delay(2), Duration::from_secs(2));
+    assert_eq!(policy.delay(4), Duration::from_secs(8));
+    assert_eq!(policy.delay(5), Duration::from_secs(10));
+    assert_eq!(policy.delay(64), Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn supervisor_restarts_panicked_task() {
+    let supervisor = TaskSupervisor::with_backoff(fast_backoff());
+    let runs = Arc::new(AtomicU32::new(0));
+
+    supervisor.spawn("flaky", {
+        let runs = runs.clone();
+        move || {
+            let runs = runs.clone();
+            async move {
+                if runs.fetch_add(1, Ordering::SeqCst) < 2 {
+                    panic!("boom");
+                }
+                std::future::pending::<()>().await
+            }
+        }
+    });
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while runs.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let health = supervisor.health();
+    assert_eq!(health.len(), 1);
+    assert_eq!(health[0].state, TaskState::Running);
+    assert_eq!(health[0].restarts, 2);
+    assert_eq!(health[0].last_panic.as_deref(), Some("boom"));
+    assert!(supervisor.is_ready());
+
+    assert!(supervisor.shutdown(Duration::from_secs(1)).await);
+    assert_eq!(supervisor.health()[0].state, TaskState::Stopped);
+    assert!(!supervisor.is_ready());
+}
+
+#[tokio::test]
+async fn supervisor_reports_backoff_as_not_ready() {
+    let supervisor = TaskSupervisor::with_backoff(BackoffPolicy {
+        initial: Duration::from_secs(60),
+        ..fast_backoff()
+    });
+    supervisor.spawn("broken", || async { panic!("always") });
+    supervisor.spawn("idle", std::future::pending::<()>);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while supervisor.health()[0].state != TaskState::Backoff {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert!(!supervisor.is_ready());
+
+    // Shutdown interrupts the backoff sleep
+    assert!(supervisor.shutdown(Duration::from_secs(1)).await);
+    assert!(
+        supervisor
+            .health()
+            .iter()
+            .all(|task| task.state == TaskState::Stopped)
+    );
+}
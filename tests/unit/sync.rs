@@ -0,0 +1,96 @@
+// Tests for the offline sync snapshot
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use rust_backend::db::enums::CycleStatus;
+use rust_backend::db::models::cycle::Cycle;
+use rust_backend::db::models::issue::Issue;
+use rust_backend::error::AppError;
+use rust_backend::services::sync_service::{MAX_CHANGES_AGE_DAYS, SyncService};
+use uuid::Uuid;
+
+fn issue(id: Uuid) -> Issue {
+    Issue {
+        id,
+        project_id: None,
+        cycle_id: None,
+        creator_id: Uuid::new_v4(),
+        assignee_id: None,
+        parent_issue_id: None,
+        issue_number: 7,
+        title: "Offline mode".to_string(),
+        description: None,
+        priority: "high".to_string(),
+        is_changelog_candidate: false,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        team_id: Uuid::new_v4(),
+        workflow_id: None,
+        workflow_state_id: None,
+        archived_at: None,
+        archive_batch_id: None,
+        estimate: None,
+    }
+}
+
+fn cycle(start_date: NaiveDate, updated_at: chrono::DateTime<Utc>) -> Cycle {
+    Cycle {
+        id: Uuid::new_v4(),
+        team_id: Uuid::new_v4(),
+        name: "Sprint".to_string(),
+        start_date,
+        end_date: start_date + Duration::days(14),
+        status: CycleStatus::Active,
+        created_at: updated_at,
+        description: None,
+        goal: None,
+        updated_at,
+    }
+}
+
+#[test]
+fn since_must_be_recent_and_not_in_the_future() {
+    let now = Utc::now();
+    assert!(SyncService::check_since(now - Duration::hours(1), now).is_ok());
+    assert!(matches!(
+        SyncService::check_since(now + Duration::minutes(1), now),
+        Err(AppError::Validation { .. })
+    ));
+    assert!(matches!(
+        SyncService::check_since(now - Duration::days(MAX_CHANGES_AGE_DAYS + 1), now),
+        Err(AppError::Conflict { .. })
+    ));
+}
+
+#[test]
+fn issues_carry_their_label_ids() {
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+    let (bug, ui) = (Uuid::new_v4(), Uuid::new_v4());
+    let issues = SyncService::attach_labels(
+        vec![issue(first), issue(second)],
+        &[(first, bug), (first, ui), (Uuid::new_v4(), bug)],
+    );
+
+    let mut expected = vec![bug, ui];
+    expected.sort();
+    assert_eq!(issues[0].label_ids, expected);
+    assert!(issues[1].label_ids.is_empty());
+    assert_eq!(issues[0].priority, "high");
+}
+
+#[test]
+fn changed_cycles_include_updated_and_newly_started() {
+    let since = Utc.with_ymd_and_hms(2025, 10, 8, 9, 0, 0).unwrap();
+    let old_day = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+    let cycles = vec![
+        cycle(old_day, since - Duration::days(3)),
+        cycle(old_day, since + Duration::hours(1)),
+        cycle(since.date_naive(), since - Duration::days(3)),
+    ];
+    let ids: Vec<Uuid> = cycles.iter().map(|c| c.id).collect();
+
+    let changed: Vec<Uuid> = SyncService::changed_cycles(cycles, since)
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(changed, vec![ids[1], ids[2]]);
+}
@@ -2,6 +2,7 @@ use rust_backend::{
     routes::teams::{CreateTeamRequest, UpdateTeamRequest},
     services::{team_members_service::TeamMembersService, teams_service::TeamsService},
 };
+use uuid::Uuid;
 
 #[test]
 fn test_teams_service_validate_name() {
@@ -47,6 +48,7 @@ fn test_create_team_request_validation_logic() {
         description: None,
         icon_url: None,
         is_private: false,
+        parent_team_id: None,
     };
     assert!(TeamsService::validate_name(&req.name).is_err());
 
@@ -57,6 +59,7 @@ fn test_create_team_request_validation_logic() {
         description: None,
         icon_url: None,
         is_private: false,
+        parent_team_id: None,
     };
     assert!(TeamsService::validate_team_key(&req.team_key).is_err());
 
@@ -67,6 +70,7 @@ fn test_create_team_request_validation_logic() {
         description: None,
         icon_url: None,
         is_private: false,
+        parent_team_id: None,
     };
     assert!(TeamsService::validate_team_key(&req.team_key).is_err());
 
@@ -77,6 +81,7 @@ fn test_create_team_request_validation_logic() {
         description: Some("Test desc".to_string()),
         icon_url: None,
         is_private: false,
+        parent_team_id: None,
     };
     assert!(TeamsService::validate_name(&req.name).is_ok());
     assert!(TeamsService::validate_team_key(&req.team_key).is_ok());
@@ -90,6 +95,9 @@ fn test_update_team_request_no_changes_logic() {
         description: None,
         icon_url: None,
         is_private: None,
+        estimate_scale: None,
+        assignment_mode: None,
+        default_assignee_id: None,
     };
     // Should have all fields None
     assert!(req.name.is_none());
@@ -119,3 +127,30 @@ fn test_team_member_role_mapping() {
     assert_eq!(admin_str, "admin");
     assert_eq!(member_str, "member");
 }
+
+#[test]
+fn round_robin_takes_turns_in_join_order() {
+    let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let members = [a, b, c];
+    assert_eq!(TeamsService::next_round_robin(&members, None), Some(a));
+    assert_eq!(TeamsService::next_round_robin(&members, Some(a)), Some(b));
+    assert_eq!(TeamsService::next_round_robin(&members, Some(c)), Some(a));
+    // The last assignee left the team: start over
+    assert_eq!(
+        TeamsService::next_round_robin(&members, Some(Uuid::new_v4())),
+        Some(a)
+    );
+    assert_eq!(TeamsService::next_round_robin(&[], Some(a)), None);
+}
+
+#[test]
+fn update_team_request_can_clear_the_default_assignee() {
+    let req: UpdateTeamRequest =
+        serde_json::from_value(serde_json::json!({ "assignment_mode": "round_robin" })).unwrap();
+    assert_eq!(req.assignment_mode.as_deref(), Some("round_robin"));
+    assert_eq!(req.default_assignee_id, None);
+
+    let req: UpdateTeamRequest =
+        serde_json::from_value(serde_json::json!({ "default_assignee_id": null })).unwrap();
+    assert_eq!(req.default_assignee_id, Some(None));
+}
@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+
+use rust_backend::db::models::issue::Issue;
+use rust_backend::db::models::team::{MAX_TEAM_DEPTH, Team, TeamHierarchy};
+use rust_backend::db::models::workflow::WorkflowStateCategory;
+use rust_backend::services::team_hierarchy_service::TeamHierarchyService;
+use uuid::Uuid;
+
+fn team(name: &str, parent_team_id: Option<Uuid>, is_private: bool) -> Team {
+    Team {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::nil(),
+        name: name.to_string(),
+        team_key: name.to_uppercase(),
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        description: None,
+        icon_url: None,
+        is_private,
+        parent_team_id,
+        estimate_scale: "fibonacci".to_string(),
+        assignment_mode: "none".to_string(),
+        default_assignee_id: None,
+        last_auto_assignee_id: None,
+    }
+}
+
+fn issue(team_id: Uuid, workflow_state_id: Option<Uuid>, minutes_ago: i64) -> Issue {
+    let created_at = chrono::Utc::now() - chrono::Duration::minutes(minutes_ago);
+    Issue {
+        id: Uuid::new_v4(),
+        project_id: None,
+        cycle_id: None,
+        creator_id: Uuid::new_v4(),
+        assignee_id: None,
+        parent_issue_id: None,
+        issue_number: 1,
+        title: "Title".to_string(),
+        description: None,
+        priority: "none".to_string(),
+        is_changelog_candidate: false,
+        created_at,
+        updated_at: created_at,
+        team_id,
+        workflow_id: None,
+        workflow_state_id,
+        archived_at: None,
+        archive_batch_id: None,
+        estimate: None,
+    }
+}
+
+#[test]
+fn ancestors_and_descendants_follow_parent_links() {
+    let root = Uuid::new_v4();
+    let child = Uuid::new_v4();
+    let grandchild = Uuid::new_v4();
+    let sibling = Uuid::new_v4();
+    let hierarchy = TeamHierarchy::new([
+        (root, None),
+        (child, Some(root)),
+        (grandchild, Some(child)),
+        (sibling, Some(root)),
+    ]);
+
+    assert_eq!(hierarchy.ancestors(grandchild), vec![child, root]);
+    assert!(hierarchy.ancestors(root).is_empty());
+    let descendants: HashSet<Uuid> = hierarchy.descendants(root).into_iter().collect();
+    assert_eq!(descendants, HashSet::from([child, grandchild, sibling]));
+    assert_eq!(hierarchy.subtree(child), vec![child, grandchild]);
+    assert_eq!(hierarchy.depth(grandchild), 3);
+    assert_eq!(hierarchy.height(root), 3);
+    assert_eq!(hierarchy.height(sibling), 1);
+}
+
+#[test]
+fn walks_stop_at_cycles() {
+    let a = Uuid::new_v4();
+    let b = Uuid::new_v4();
+    let hierarchy = TeamHierarchy::new([(a, Some(b)), (b, Some(a))]);
+
+    assert_eq!(hierarchy.ancestors(a), vec![b]);
+    assert_eq!(hierarchy.descendants(a), vec![b]);
+    assert_eq!(hierarchy.height(a), 2);
+}
+
+#[test]
+fn moving_a_team_below_itself_is_rejected() {
+    let root = Uuid::new_v4();
+    let child = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    let hierarchy = TeamHierarchy::new([(root, None), (child, Some(root)), (other, None)]);
+
+    assert!(hierarchy.would_cycle(root, root));
+    assert!(hierarchy.would_cycle(root, child));
+    assert!(!hierarchy.would_cycle(child, other));
+    assert!(TeamHierarchyService::validate_parent(&hierarchy, root, child).is_err());
+    assert!(TeamHierarchyService::validate_parent(&hierarchy, root, other).is_ok());
+}
+
+#[test]
+fn nesting_is_limited() {
+    let ids: Vec<Uuid> = (0..MAX_TEAM_DEPTH).map(|_| Uuid::new_v4()).collect();
+    let mut links = vec![(ids[0], None)];
+    links.extend(ids.windows(2).map(|w| (w[1], Some(w[0]))));
+    let leaf = Uuid::new_v4();
+    links.push((leaf, None));
+    let hierarchy = TeamHierarchy::new(links);
+
+    let deepest = ids[MAX_TEAM_DEPTH - 1];
+    assert!(TeamHierarchyService::validate_parent(&hierarchy, leaf, deepest).is_err());
+    assert!(
+        TeamHierarchyService::validate_parent(&hierarchy, leaf, ids[MAX_TEAM_DEPTH - 2]).is_ok()
+    );
+    // Moving a whole chain counts its own levels
+    assert!(TeamHierarchyService::validate_parent(&hierarchy, ids[0], leaf).is_err());
+}
+
+#[test]
+fn private_parents_hide_sub_teams_from_non_members() {
+    let private_root = team("private", None, true);
+    let sub_team = team("sub", Some(private_root.id), false);
+    let public_root = team("public", None, false);
+    let private_child = team("secret", Some(public_root.id), true);
+    let teams = vec![
+        private_root.clone(),
+        sub_team.clone(),
+        public_root.clone(),
+        private_child.clone(),
+    ];
+    let hierarchy = TeamHierarchy::from_teams(&teams);
+
+    let outsider = TeamHierarchyService::visible_team_ids(&teams, &hierarchy, &HashSet::new());
+    assert_eq!(outsider, HashSet::from([public_root.id]));
+
+    // Members of a parent team inherit access to every sub-team
+    let member = TeamHierarchyService::visible_team_ids(
+        &teams,
+        &hierarchy,
+        &HashSet::from([private_root.id, public_root.id]),
+    );
+    assert_eq!(
+        member,
+        HashSet::from([
+            private_root.id,
+            sub_team.id,
+            public_root.id,
+            private_child.id
+        ])
+    );
+}
+
+#[test]
+fn issue_counts_roll_up_through_visible_teams() {
+    let root = team("root", None, false);
+    let child = team("child", Some(root.id), false);
+    let grandchild = team("grandchild", Some(child.id), false);
+    let hidden = team("hidden", Some(root.id), true);
+    let teams: HashMap<Uuid, Team> = [&root, &child, &grandchild, &hidden]
+        .into_iter()
+        .map(|t| (t.id, t.clone()))
+        .collect();
+    let hierarchy = TeamHierarchy::from_teams(&teams.values().cloned().collect::<Vec<_>>());
+    let visible = HashSet::from([root.id, child.id, grandchild.id]);
+    let counts = HashMap::from([
+        (root.id, 1),
+        (child.id, 2),
+        (grandchild.id, 4),
+        (hidden.id, 8),
+    ]);
+
+    let tree = TeamHierarchyService::build_node(root.id, &teams, &hierarchy, &visible, &counts);
+    assert_eq!(tree.issue_count, 1);
+    assert_eq!(tree.total_issue_count, 7);
+    assert_eq!(tree.children.len(), 1);
+    assert_eq!(tree.children[0].total_issue_count, 6);
+    assert_eq!(tree.children[0].children[0].id, grandchild.id);
+}
+
+#[test]
+fn board_groups_issues_by_state_category() {
+    let team_id = Uuid::new_v4();
+    let started = Uuid::new_v4();
+    let done = Uuid::new_v4();
+    let categories = HashMap::from([
+        (started, WorkflowStateCategory::Started),
+        (done, WorkflowStateCategory::Completed),
+    ]);
+    let first = issue(team_id, Some(started), 10);
+    let second = issue(team_id, Some(started), 5);
+    let unpositioned = issue(team_id, Some(started), 1);
+    let stateless = issue(team_id, None, 1);
+    let finished = issue(team_id, Some(done), 1);
+    let positions = HashMap::from([(first.id, 1.0), (second.id, 0.5)]);
+
+    let columns = TeamHierarchyService::board_columns(
+        vec![
+            first.clone(),
+            second.clone(),
+            unpositioned.clone(),
+            stateless.clone(),
+            finished.clone(),
+        ],
+        &categories,
+        &positions,
+    );
+
+    let names: Vec<&str> = columns.iter().map(|c| c.category.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "triage",
+            "backlog",
+            "unstarted",
+            "started",
+            "completed",
+            "canceled"
+        ]
+    );
+    let started_ids: Vec<Uuid> = columns[3].issues.iter().map(|i| i.id).collect();
+    assert_eq!(started_ids, vec![second.id, first.id, unpositioned.id]);
+    assert_eq!(columns[1].issues[0].id, stateless.id);
+    assert_eq!(columns[4].issues[0].id, finished.id);
+}
@@ -0,0 +1,73 @@
+// Usage telemetry opt-in and report contents tests
+
+use chrono::Utc;
+use rust_backend::db::models::telemetry::{
+    FeatureAdoption, TELEMETRY_SCHEMA_VERSION, TelemetryReport, UsageCounts,
+};
+use rust_backend::testing::test_config;
+use uuid::Uuid;
+
+#[test]
+fn telemetry_is_off_unless_opted_in() {
+    let mut config = test_config();
+    assert!(config.telemetry().is_none());
+
+    config.telemetry_endpoint = Some("https://telemetry.example.com/v1/reports".to_string());
+    assert!(config.telemetry().is_none());
+
+    config.telemetry_enabled = true;
+    assert_eq!(
+        config.telemetry().unwrap().endpoint,
+        "https://telemetry.example.com/v1/reports"
+    );
+}
+
+#[test]
+fn reports_hold_only_aggregate_counts() {
+    let report = TelemetryReport {
+        schema_version: TELEMETRY_SCHEMA_VERSION,
+        instance_id: Uuid::new_v4(),
+        version: "0.1.0".to_string(),
+        generated_at: Utc::now(),
+        counts: UsageCounts {
+            workspaces: 3,
+            users: 12,
+            websocket_connections: 4,
+            ..UsageCounts::default()
+        },
+        feature_adoption: FeatureAdoption {
+            automations: 1,
+            ..FeatureAdoption::default()
+        },
+    };
+    let body = serde_json::to_value(&report).unwrap();
+
+    let keys: Vec<&str> = body
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            "counts",
+            "feature_adoption",
+            "generated_at",
+            "instance_id",
+            "schema_version",
+            "version"
+        ]
+    );
+    for section in ["counts", "feature_adoption"] {
+        assert!(
+            body[section]
+                .as_object()
+                .unwrap()
+                .values()
+                .all(|v| v.is_i64())
+        );
+    }
+    assert_eq!(body["counts"]["websocket_connections"], 4);
+    assert_eq!(body["feature_adoption"]["automations"], 1);
+}
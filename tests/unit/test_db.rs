@@ -0,0 +1,115 @@
+// Fixtures for tests that need a migrated database. Such tests are ignored by
+// default; run them with DATABASE_URL set and `cargo test -- --ignored`.
+// Everything runs in a test transaction that is never committed.
+
+use diesel::prelude::*;
+use rust_backend::schema::{
+    issues, team_issue_counters, teams, users, workspace_members, workspaces,
+};
+use rust_backend::services::context::{AuthChannel, RequestContext};
+use uuid::Uuid;
+
+pub fn connect() -> PgConnection {
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let mut conn = PgConnection::establish(&url).expect("Failed to connect to the database");
+    conn.begin_test_transaction().unwrap();
+    conn
+}
+
+pub fn user(conn: &mut PgConnection) -> Uuid {
+    let key = Uuid::new_v4().simple().to_string();
+    diesel::insert_into(users::table)
+        .values((
+            users::name.eq("Test User"),
+            users::email.eq(format!("{}@example.com", key)),
+            users::username.eq(format!("u{}", &key[..20])),
+        ))
+        .returning(users::id)
+        .get_result(conn)
+        .unwrap()
+}
+
+/// A workspace with `owner` as its owner
+pub fn workspace(conn: &mut PgConnection, owner: Uuid) -> Uuid {
+    let key = Uuid::new_v4().simple().to_string();
+    let workspace_id = diesel::insert_into(workspaces::table)
+        .values((
+            workspaces::name.eq("Test Workspace"),
+            workspaces::url_key.eq(format!("ws-{}", &key[..20])),
+        ))
+        .returning(workspaces::id)
+        .get_result(conn)
+        .unwrap();
+    diesel::insert_into(workspace_members::table)
+        .values((
+            workspace_members::user_id.eq(owner),
+            workspace_members::workspace_id.eq(workspace_id),
+            workspace_members::role
+                .eq(rust_backend::db::models::workspace_member::WorkspaceMemberRole::Owner),
+        ))
+        .execute(conn)
+        .unwrap();
+    workspace_id
+}
+
+pub fn team(conn: &mut PgConnection, workspace_id: Uuid) -> Uuid {
+    let key = Uuid::new_v4().simple().to_string();
+    diesel::insert_into(teams::table)
+        .values((
+            teams::workspace_id.eq(workspace_id),
+            teams::name.eq("Test Team"),
+            teams::team_key.eq(format!("T{}", &key[..6]).to_uppercase()),
+            teams::is_private.eq(false),
+        ))
+        .returning(teams::id)
+        .get_result(conn)
+        .unwrap()
+}
+
+pub fn issue(
+    conn: &mut PgConnection,
+    team_id: Uuid,
+    creator_id: Uuid,
+    number: i32,
+    description: &str,
+) -> Uuid {
+    let id = diesel::insert_into(issues::table)
+        .values((
+            issues::team_id.eq(team_id),
+            issues::creator_id.eq(creator_id),
+            issues::issue_number.eq(number),
+            issues::title.eq(format!("Issue {}", number)),
+            issues::description.eq(description),
+        ))
+        .returning(issues::id)
+        .get_result(conn)
+        .unwrap();
+    // Keep the team counter past explicit numbers so issues created later
+    // by services do not reuse them
+    diesel::insert_into(team_issue_counters::table)
+        .values((
+            team_issue_counters::team_id.eq(team_id),
+            team_issue_counters::last_issue_number.eq(number),
+        ))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .unwrap();
+    diesel::update(
+        team_issue_counters::table
+            .filter(team_issue_counters::team_id.eq(team_id))
+            .filter(team_issue_counters::last_issue_number.lt(number)),
+    )
+    .set(team_issue_counters::last_issue_number.eq(number))
+    .execute(conn)
+    .unwrap();
+    id
+}
+
+pub fn ctx(user_id: Uuid, workspace_id: Uuid) -> RequestContext {
+    RequestContext {
+        user_id,
+        workspace_id,
+        idempotency_key: None,
+        channel: AuthChannel::Session,
+    }
+}
@@ -0,0 +1,16 @@
+use rust_backend::db::after_commit;
+use rust_backend::db::transaction::in_transaction;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn after_commit_runs_immediately_outside_a_transaction() {
+    assert!(!in_transaction());
+
+    let ran = Rc::new(Cell::new(false));
+    let flag = ran.clone();
+    after_commit(move || flag.set(true));
+
+    assert!(ran.get());
+    assert!(!in_transaction());
+}
@@ -0,0 +1,88 @@
+// Typed cache entries with versioned invalidation
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_backend::cache::{CacheEntry, CacheStore, MemoryCacheStore};
+use rust_backend::error::AppError;
+use uuid::Uuid;
+
+static NAMES: CacheEntry<Vec<String>> = CacheEntry::new("test_names", 60);
+
+async fn load_counted(loads: &AtomicUsize, value: &str) -> Result<Vec<String>, AppError> {
+    loads.fetch_add(1, Ordering::SeqCst);
+    Ok(vec![value.to_string()])
+}
+
+#[tokio::test]
+async fn cached_values_are_loaded_once_per_version() {
+    let cache = MemoryCacheStore::new();
+    let store: Option<&dyn CacheStore> = Some(&cache);
+    let scope = Uuid::new_v4();
+    let loads = AtomicUsize::new(0);
+
+    let first = NAMES
+        .get_or_load(store, scope, "all", || load_counted(&loads, "a"))
+        .await
+        .unwrap();
+    let second = NAMES
+        .get_or_load(store, scope, "all", || load_counted(&loads, "b"))
+        .await
+        .unwrap();
+    assert_eq!(first, vec!["a"]);
+    assert_eq!(second, vec!["a"]);
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+    NAMES.invalidate(&cache, scope).await.unwrap();
+    let reloaded = NAMES
+        .get_or_load(store, scope, "all", || load_counted(&loads, "b"))
+        .await
+        .unwrap();
+    assert_eq!(reloaded, vec!["b"]);
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn invalidation_covers_every_variant_of_its_scope_only() {
+    let cache = MemoryCacheStore::new();
+    let (scope, other) = (Uuid::new_v4(), Uuid::new_v4());
+    for (s, variant) in [(scope, "eu"), (scope, "us"), (other, "eu")] {
+        NAMES
+            .set(&cache, s, variant, &vec![variant.to_string()])
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        NAMES.get(&cache, scope, "us").await,
+        Some(vec!["us".to_string()])
+    );
+
+    NAMES.invalidate(&cache, scope).await.unwrap();
+    assert_eq!(NAMES.get(&cache, scope, "eu").await, None);
+    assert_eq!(NAMES.get(&cache, scope, "us").await, None);
+    assert_eq!(
+        NAMES.get(&cache, other, "eu").await,
+        Some(vec!["eu".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn loads_without_a_cache_and_skips_failed_loads() {
+    let loads = AtomicUsize::new(0);
+    let scope = Uuid::new_v4();
+    for _ in 0..2 {
+        NAMES
+            .get_or_load(None, scope, "all", || load_counted(&loads, "a"))
+            .await
+            .unwrap();
+    }
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+    let cache = MemoryCacheStore::new();
+    let failed = NAMES
+        .get_or_load(Some(&cache), scope, "all", || async {
+            Err(AppError::not_found("workflow"))
+        })
+        .await;
+    assert!(failed.is_err());
+    assert_eq!(NAMES.get(&cache, scope, "all").await, None);
+}
@@ -0,0 +1,107 @@
+// Resumable upload chunk rules and multipart signing tests
+
+use chrono::TimeZone;
+use rust_backend::config::StorageConfig;
+use rust_backend::error::AppError;
+use rust_backend::services::upload_sessions_service::{
+    MAX_CHUNK_BYTES, MIN_CHUNK_BYTES, UploadSessionsService,
+};
+use rust_backend::utils::ObjectStorage;
+
+fn storage() -> ObjectStorage {
+    ObjectStorage::new(&StorageConfig {
+        endpoint: "http://localhost:9000".to_string(),
+        bucket: "momentum".to_string(),
+        region: "us-east-1".to_string(),
+        access_key: "minioadmin".to_string(),
+        secret_key: "minioadmin".to_string(),
+        path_style: true,
+        max_attachment_bytes: 1024,
+    })
+    .unwrap()
+}
+
+fn is_validation(result: Result<(), AppError>) -> bool {
+    matches!(result, Err(AppError::Validation { .. }))
+}
+
+#[test]
+fn chunks_must_fill_parts_until_the_last() {
+    let size = 2 * MIN_CHUNK_BYTES + 10;
+    assert!(UploadSessionsService::check_chunk(size, 0, MIN_CHUNK_BYTES, 1).is_ok());
+    assert!(is_validation(UploadSessionsService::check_chunk(
+        size,
+        0,
+        MIN_CHUNK_BYTES - 1,
+        1
+    )));
+    // The last chunk may be small
+    assert!(UploadSessionsService::check_chunk(size, 2 * MIN_CHUNK_BYTES, 10, 3).is_ok());
+    assert!(UploadSessionsService::check_chunk(10, 0, 10, 1).is_ok());
+}
+
+#[test]
+fn chunks_are_bounded_by_size_and_limits() {
+    let size = MIN_CHUNK_BYTES * 100;
+    assert!(is_validation(UploadSessionsService::check_chunk(
+        size, 0, 0, 1
+    )));
+    assert!(is_validation(UploadSessionsService::check_chunk(
+        size,
+        0,
+        MAX_CHUNK_BYTES as i64 + 1,
+        1
+    )));
+    assert!(is_validation(UploadSessionsService::check_chunk(
+        size,
+        size - 5,
+        10,
+        2
+    )));
+    // Storage allows 10,000 parts, so part 10,000 must finish the upload
+    assert!(is_validation(UploadSessionsService::check_chunk(
+        size,
+        0,
+        MIN_CHUNK_BYTES,
+        10_000
+    )));
+    assert!(UploadSessionsService::check_chunk(size, size - 1, 1, 10_000).is_ok());
+}
+
+#[test]
+fn multipart_presign_sorts_subresources_into_the_query() {
+    let now = chrono::Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let storage = storage();
+
+    assert_eq!(
+        storage.presign_at("GET", "attachments/a.bin", 60, now),
+        storage.presign_query_at("GET", "attachments/a.bin", &[], 60, now)
+    );
+
+    let url = storage.presign_query_at(
+        "PUT",
+        "attachments/a.bin",
+        &[("uploadId", "abc/def"), ("partNumber", "3")],
+        60,
+        now,
+    );
+    let query = url.split_once('?').unwrap().1;
+    let names: Vec<&str> = query
+        .split('&')
+        .map(|pair| pair.split_once('=').unwrap().0)
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "X-Amz-Algorithm",
+            "X-Amz-Credential",
+            "X-Amz-Date",
+            "X-Amz-Expires",
+            "X-Amz-SignedHeaders",
+            "partNumber",
+            "uploadId",
+            "X-Amz-Signature",
+        ]
+    );
+    assert!(query.contains("uploadId=abc%2Fdef"));
+}
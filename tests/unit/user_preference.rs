@@ -0,0 +1,103 @@
+// User preference defaults, stored channel parsing and validation tests
+
+use chrono::Utc;
+use rust_backend::db::models::notification::notification_events;
+use rust_backend::db::models::oauth_app::oauth_scopes;
+use rust_backend::db::models::user_preference::{
+    NotificationChannels, UserPreferenceRecord, UserPreferences, themes,
+};
+use rust_backend::services::user_preferences_service::UserPreferencesService;
+use uuid::Uuid;
+
+#[test]
+fn users_without_preferences_get_every_channel() {
+    let preferences = UserPreferences::default();
+    assert_eq!(preferences.timezone, "UTC");
+    assert_eq!(preferences.locale, "en");
+    assert_eq!(preferences.theme, themes::SYSTEM);
+    assert_eq!(
+        preferences.notifications.len(),
+        notification_events::ALL.len()
+    );
+    assert!(
+        preferences
+            .notifications
+            .values()
+            .all(|channels| *channels == NotificationChannels::default())
+    );
+    assert!(preferences.channels("added_later").email);
+}
+
+#[test]
+fn muted_channels_are_read_per_event_type() {
+    let preferences = UserPreferences::from(UserPreferenceRecord {
+        user_id: Uuid::new_v4(),
+        timezone: "Europe/Berlin".to_string(),
+        locale: "de".to_string(),
+        theme: themes::DARK.to_string(),
+        muted_channels: "issue_updated:email issue_updated:websocket comment_created:in_app \
+                         retired_event:email mentioned:carrier_pigeon"
+            .to_string(),
+        updated_at: Utc::now(),
+    });
+
+    let updated = preferences.channels(notification_events::ISSUE_UPDATED);
+    assert!(updated.in_app && !updated.email && !updated.websocket);
+    let commented = preferences.channels(notification_events::COMMENT_CREATED);
+    assert!(!commented.in_app && commented.email && commented.websocket);
+    assert_eq!(
+        preferences.channels(notification_events::MENTIONED),
+        NotificationChannels::default()
+    );
+    assert!(!preferences.notifications.contains_key("retired_event"));
+}
+
+#[test]
+fn time_zones_and_locales_are_validated() {
+    for timezone in [
+        "Europe/Berlin",
+        "America/Argentina/Buenos_Aires",
+        "America/Port-au-Prince",
+        "Etc/GMT+5",
+    ] {
+        assert_eq!(
+            UserPreferencesService::validate_timezone(timezone).unwrap(),
+            timezone
+        );
+    }
+    assert_eq!(
+        UserPreferencesService::validate_timezone(" utc ").unwrap(),
+        "UTC"
+    );
+    for timezone in [
+        "Berlin",
+        "europe/berlin",
+        "Europe/",
+        "Europe/Ber lin",
+        "../etc/passwd",
+    ] {
+        assert!(UserPreferencesService::validate_timezone(timezone).is_err());
+    }
+
+    assert_eq!(UserPreferencesService::validate_locale("EN").unwrap(), "en");
+    assert_eq!(
+        UserPreferencesService::validate_locale("pt_BR").unwrap(),
+        "pt-BR"
+    );
+    assert_eq!(
+        UserPreferencesService::validate_locale("zh-Hans-CN").unwrap(),
+        "zh-Hans-CN"
+    );
+    for locale in ["", "e", "english-language", "en-", "en-US!"] {
+        assert!(UserPreferencesService::validate_locale(locale).is_err());
+    }
+}
+
+#[test]
+fn third_party_apps_cannot_change_preferences() {
+    assert_eq!(
+        oauth_scopes::required_for("PUT", "/users/preferences"),
+        None
+    );
+    assert!(oauth_scopes::required_for("PUT", "/users/profile").is_some());
+}
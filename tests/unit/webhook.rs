@@ -0,0 +1,288 @@
+// App and workspace webhook filtering, signing and retry tests
+
+use rust_backend::db::models::app_installation::{WebhookEnvelope, webhook_events};
+use rust_backend::db::models::oauth_app::oauth_scopes;
+use rust_backend::db::models::webhook::{Webhook, WebhookConfig};
+use rust_backend::services::webhook_service::WebhookService;
+use rust_backend::services::webhooks_service::WebhooksService;
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn lifecycle_events_bypass_subscriptions() {
+    for event in [
+        webhook_events::INSTALLATION_CREATED,
+        webhook_events::INSTALLATION_DELETED,
+        webhook_events::INSTALLATION_PERMISSIONS_CHANGED,
+    ] {
+        assert!(webhook_events::is_lifecycle(event));
+        assert!(!webhook_events::is_subscribable(event));
+        assert!(WebhookService::should_deliver(&[], &[], event));
+    }
+}
+
+#[test]
+fn domain_events_need_subscription_and_scope() {
+    let subscribed = strings(&[
+        webhook_events::ISSUE_CREATED,
+        webhook_events::COMMENT_CREATED,
+    ]);
+    let scopes = strings(&[oauth_scopes::WRITE_ISSUES]);
+
+    // write:issues implies read:issues
+    assert!(WebhookService::should_deliver(
+        &subscribed,
+        &scopes,
+        webhook_events::ISSUE_CREATED
+    ));
+    // Not subscribed
+    assert!(!WebhookService::should_deliver(
+        &subscribed,
+        &scopes,
+        webhook_events::ISSUE_UPDATED
+    ));
+    // Subscribed, but the installation lacks read:comments
+    assert!(!WebhookService::should_deliver(
+        &subscribed,
+        &scopes,
+        webhook_events::COMMENT_CREATED
+    ));
+    assert_eq!(
+        webhook_events::required_scope(webhook_events::PROJECT_DELETED),
+        Some(oauth_scopes::READ_PROJECTS)
+    );
+}
+
+#[test]
+fn webhook_signature_is_hmac_sha256() {
+    assert_eq!(
+        WebhookService::sign("whsec_test", r#"{"event":"issue.created"}"#),
+        "sha256=b44581cf2f0060e7968a7231aef664802c719d8bbb5907fbf9cf00e3f8c628ae"
+    );
+}
+
+#[test]
+fn webhook_retry_backs_off_exponentially() {
+    assert_eq!(WebhookService::retry_delay(1).num_seconds(), 30);
+    assert_eq!(WebhookService::retry_delay(2).num_seconds(), 60);
+    assert_eq!(WebhookService::retry_delay(5).num_seconds(), 480);
+}
+
+#[test]
+fn webhook_signature_header_lists_every_active_key() {
+    let body = r#"{"event":"issue.created"}"#;
+    let header = WebhookService::signature_header(
+        &[
+            (Some("whk_new"), "whsec_new"),
+            (Some("whk_old"), "whsec_test"),
+        ],
+        body,
+    );
+    assert_eq!(
+        header,
+        format!(
+            "kid=whk_new;{}, kid=whk_old;sha256=b44581cf2f0060e7968a7231aef664802c719d8bbb5907fbf9cf00e3f8c628ae",
+            WebhookService::sign("whsec_new", body)
+        )
+    );
+
+    let entries = WebhookService::parse_signature_header(&header);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].key_id.as_deref(), Some("whk_old"));
+    assert_eq!(
+        entries[1].signature,
+        WebhookService::sign("whsec_test", body)
+    );
+}
+
+#[test]
+fn webhook_signature_verification_matches_key_ids() {
+    let body = r#"{"event":"issue.created"}"#;
+    let keys = [
+        (Some("whk_new"), "whsec_new"),
+        (Some("whk_old"), "whsec_test"),
+    ];
+    let old_only = WebhookService::signature_header(&[(Some("whk_old"), "whsec_test")], body);
+    assert_eq!(
+        WebhookService::verify_header(&keys, body, &old_only),
+        Some(Some("whk_old"))
+    );
+    // A bare signature is checked against every key
+    assert_eq!(
+        WebhookService::verify_header(&keys, body, &WebhookService::sign("whsec_new", body)),
+        Some(Some("whk_new"))
+    );
+    // Right signature, wrong key id
+    let mislabeled = format!("kid=whk_new;{}", WebhookService::sign("whsec_test", body));
+    assert_eq!(
+        WebhookService::verify_header(&keys, body, &mislabeled),
+        None
+    );
+    assert!(!WebhookService::verify(
+        "whsec_test",
+        body,
+        "sha256=not-hex"
+    ));
+    assert!(!WebhookService::verify(
+        "whsec_test",
+        "{}",
+        &WebhookService::sign("whsec_test", body)
+    ));
+}
+
+#[test]
+fn webhook_samples_cover_every_event_type() {
+    let app_id = uuid::Uuid::new_v4();
+    for event in webhook_events::LIFECYCLE
+        .iter()
+        .chain(webhook_events::SUBSCRIBABLE)
+    {
+        assert!(
+            WebhookService::sample_data(event, app_id).is_some(),
+            "no sample for {}",
+            event
+        );
+    }
+    let moved = WebhookService::sample_data(webhook_events::ISSUE_MOVED, app_id).unwrap();
+    assert!(moved["move"]["to_key"].is_string());
+    assert_eq!(
+        WebhookService::sample_data(webhook_events::INSTALLATION_CREATED, app_id).unwrap()["app_id"],
+        serde_json::json!(app_id)
+    );
+    assert!(WebhookService::sample_data("issue.archived", app_id).is_none());
+}
+
+#[test]
+fn webhook_envelope_only_marks_test_deliveries() {
+    let mut envelope = WebhookEnvelope {
+        id: uuid::Uuid::nil(),
+        event: webhook_events::ISSUE_CREATED.to_string(),
+        workspace_id: uuid::Uuid::nil(),
+        installation_id: uuid::Uuid::nil(),
+        created_at: chrono::Utc::now(),
+        data: serde_json::json!({}),
+        test: false,
+    };
+    assert!(
+        serde_json::to_value(&envelope)
+            .unwrap()
+            .get("test")
+            .is_none()
+    );
+    envelope.test = true;
+    assert_eq!(serde_json::to_value(&envelope).unwrap()["test"], true);
+}
+
+#[test]
+fn webhook_urls_must_be_https_except_on_loopback() {
+    assert!(WebhookService::validate_url("https://example.com/hooks").is_ok());
+    assert!(WebhookService::validate_url("http://localhost:8080/hooks").is_ok());
+    assert!(WebhookService::validate_url("http://example.com/hooks").is_err());
+    assert!(WebhookService::validate_url("not a url").is_err());
+}
+
+#[test]
+fn workspace_webhooks_need_known_events() {
+    assert_eq!(
+        WebhooksService::subscriptions(&strings(&[
+            webhook_events::ISSUE_CREATED,
+            webhook_events::COMMENT_CREATED,
+            webhook_events::ISSUE_CREATED,
+        ]))
+        .unwrap(),
+        "issue.created comment.created"
+    );
+    assert!(WebhooksService::subscriptions(&[]).is_err());
+    assert!(WebhooksService::subscriptions(&strings(&["issue.archived"])).is_err());
+    // Lifecycle events only exist for app installations
+    assert!(
+        WebhooksService::subscriptions(&strings(&[webhook_events::INSTALLATION_CREATED])).is_err()
+    );
+}
+
+#[test]
+fn workspace_webhook_secret_is_only_shown_when_generated() {
+    let now = chrono::Utc::now();
+    let webhook = Webhook {
+        id: uuid::Uuid::new_v4(),
+        workspace_id: uuid::Uuid::new_v4(),
+        url: "https://example.com/hooks".to_string(),
+        secret: "whsec_abc".to_string(),
+        events: "issue.created project.updated".to_string(),
+        is_active: true,
+        created_by: None,
+        created_at: now,
+        updated_at: now,
+    };
+    assert!(webhook.subscribes_to(webhook_events::PROJECT_UPDATED));
+    assert!(!webhook.subscribes_to(webhook_events::PROJECT_CREATED));
+
+    let listed = serde_json::to_value(WebhookConfig::new(webhook.clone(), None)).unwrap();
+    assert!(listed.get("secret").is_none());
+    assert_eq!(
+        listed["events"],
+        serde_json::json!(["issue.created", "project.updated"])
+    );
+    assert_eq!(listed["url"], "https://example.com/hooks");
+
+    let created =
+        serde_json::to_value(WebhookConfig::new(webhook, Some("whsec_abc".to_string()))).unwrap();
+    assert_eq!(created["secret"], "whsec_abc");
+}
+
+#[test]
+#[ignore = "requires a migrated database in DATABASE_URL"]
+fn deliveries_are_redelivered_under_the_workspace_webhook() {
+    use super::test_db;
+    use rust_backend::db::models::app_installation::TestWebhookRequest;
+    use rust_backend::db::models::webhook::CreateWebhookRequest;
+
+    let mut conn = test_db::connect();
+    let owner = test_db::user(&mut conn);
+    let workspace = test_db::workspace(&mut conn, owner);
+    let ctx = test_db::ctx(owner, workspace);
+    let webhook = WebhooksService::create(
+        &mut conn,
+        &ctx,
+        &CreateWebhookRequest {
+            url: "https://example.com/hooks".to_string(),
+            events: strings(&[webhook_events::ISSUE_CREATED]),
+        },
+    )
+    .unwrap()
+    .webhook;
+    let delivery = WebhooksService::queue_test_deliveries(
+        &mut conn,
+        &ctx,
+        webhook.id,
+        &TestWebhookRequest {
+            events: strings(&[webhook_events::ISSUE_CREATED]),
+        },
+    )
+    .unwrap()
+    .remove(0);
+
+    let replay =
+        WebhooksService::queue_redelivery(&mut conn, &ctx, webhook.id, delivery.id).unwrap();
+    assert_ne!(replay.id, delivery.id);
+    assert_eq!(replay.webhook_id, Some(webhook.id));
+    assert_eq!(replay.payload, delivery.payload);
+
+    // Another workspace can neither see nor replay it
+    let outsider = test_db::user(&mut conn);
+    let other = test_db::workspace(&mut conn, outsider);
+    assert!(
+        WebhooksService::get_by_id(&mut conn, &test_db::ctx(outsider, other), webhook.id).is_err()
+    );
+    assert!(
+        WebhooksService::queue_redelivery(
+            &mut conn,
+            &test_db::ctx(outsider, other),
+            webhook.id,
+            delivery.id
+        )
+        .is_err()
+    );
+}
@@ -0,0 +1,97 @@
+use rust_backend::utils::webhook_filter::{FilterInput, WebhookFilter};
+use serde_json::json;
+
+fn input() -> FilterInput {
+    FilterInput {
+        event: "issue.updated".to_string(),
+        teams: vec![
+            "8d0c7b8e-0000-4000-8000-000000000001".to_string(),
+            "ENG".to_string(),
+        ],
+        labels: vec!["bug".to_string(), "backend".to_string()],
+        priority: Some("high".to_string()),
+        payload: json!({
+            "title": "Crash on login",
+            "issue_number": 42,
+            "assignee_id": null,
+            "comments": [{"author": "ann"}, {"author": "bob"}],
+        }),
+    }
+}
+
+fn matches(source: &str) -> bool {
+    WebhookFilter::parse(source)
+        .unwrap_or_else(|e| panic!("{}: {}", source, e))
+        .matches(&input())
+}
+
+#[test]
+fn builtin_fields_match_case_insensitively() {
+    assert!(matches("event == \"issue.updated\""));
+    assert!(matches("event == issue.updated and team == eng"));
+    assert!(matches("team in (OPS, ENG)"));
+    assert!(!matches("team in (OPS, DESIGN)"));
+    assert!(matches("label == BUG"));
+    assert!(matches("label != wontfix"));
+    assert!(!matches("label != bug"));
+}
+
+#[test]
+fn priority_compares_by_rank() {
+    assert!(matches("priority >= medium"));
+    assert!(matches("priority > medium"));
+    assert!(!matches("priority >= urgent"));
+    assert!(matches("priority == high"));
+    assert!(WebhookFilter::parse("priority > extreme").is_err());
+}
+
+#[test]
+fn json_paths_select_payload_values() {
+    assert!(matches("$.title contains Crash"));
+    // Payload strings compare case-sensitively
+    assert!(!matches("$.title contains crash"));
+    assert!(!matches("$.title contains 'logout'"));
+    assert!(matches("$.issue_number >= 40 and $.issue_number < 50"));
+    assert!(matches("$.issue_number == 42"));
+    assert!(matches("$.comments[*].author == bob"));
+    assert!(matches("$.comments[0].author == ann"));
+    assert!(!matches("$.comments[2].author exists"));
+    assert!(!matches("$.assignee_id exists"));
+    assert!(matches("$.title exists"));
+}
+
+#[test]
+fn boolean_logic_and_precedence() {
+    // `and` binds tighter than `or`
+    assert!(matches("team == OPS and label == bug or priority == high"));
+    assert!(!matches(
+        "team == OPS and (label == bug or priority == high)"
+    ));
+    assert!(matches("not (team == OPS) and not label == wontfix"));
+}
+
+#[test]
+fn invalid_filters_are_rejected() {
+    for source in [
+        "",
+        "   ",
+        "team ==",
+        "team = ENG",
+        "owner == ann",
+        "(team == ENG",
+        "team == ENG)",
+        "team in ENG",
+        "$.title[x] exists",
+        "$..title exists",
+        "$.title == \"unterminated",
+        "team == ENG and",
+    ] {
+        assert!(
+            WebhookFilter::parse(source).is_err(),
+            "expected '{}' to be rejected",
+            source
+        );
+    }
+    let deep = format!("{}team == ENG{}", "(".repeat(40), ")".repeat(40));
+    assert!(WebhookFilter::parse(&deep).is_err());
+}
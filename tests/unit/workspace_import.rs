@@ -0,0 +1,138 @@
+use chrono::Utc;
+use rust_backend::db::models::workflow::{WorkflowState, WorkflowStateCategory};
+use rust_backend::services::import_service::ImportService;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+fn state(name: &str, category: WorkflowStateCategory) -> WorkflowState {
+    WorkflowState {
+        id: Uuid::new_v4(),
+        workflow_id: Uuid::new_v4(),
+        name: name.to_string(),
+        description: None,
+        color: None,
+        category,
+        position: 0,
+        is_default: false,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn linear_export_maps_issues_comments_and_priorities() {
+    let export = r##"{
+        "teams": [{"id": "t1", "key": "ENG", "name": "Engineering"}],
+        "projects": [{"id": "p1", "name": "Launch"}],
+        "labels": [{"id": "l1", "name": "Bug", "color": "#eb5757"}],
+        "issues": [{
+            "id": "i1",
+            "identifier": "ENG-1",
+            "title": "Crash on start",
+            "description": "",
+            "priority": 2,
+            "team": {"id": "t1"},
+            "project": {"id": "p1"},
+            "state": {"name": "In Progress", "type": "started"},
+            "assignee": {"email": "ada@example.com"},
+            "labels": [{"id": "l1"}],
+            "comments": [{"id": "c1", "body": "Seen on iOS", "user": {"email": "bob@example.com"}}]
+        }]
+    }"##;
+
+    let bundle = ImportService::parse("linear", export).unwrap();
+    assert_eq!(bundle.record_count(), 5);
+    let issue = &bundle.issues[0];
+    assert_eq!(issue.identifier, "ENG-1");
+    assert_eq!(issue.priority, "high");
+    assert_eq!(issue.description, None);
+    assert_eq!(issue.project_external_id.as_deref(), Some("p1"));
+    assert_eq!(issue.state_category.as_deref(), Some("started"));
+    assert_eq!(issue.label_external_ids, vec!["l1".to_string()]);
+    assert_eq!(
+        issue.comments[0].author_email.as_deref(),
+        Some("bob@example.com")
+    );
+}
+
+#[test]
+fn jira_export_maps_projects_to_teams_and_epics_to_projects() {
+    let export = r#"{
+        "issues": [
+            {"id": "100", "key": "WEB-1", "fields": {
+                "summary": "Checkout revamp",
+                "issuetype": {"name": "Epic"},
+                "project": {"id": "10", "key": "WEB", "name": "Website"}
+            }},
+            {"id": "101", "key": "WEB-2", "fields": {
+                "summary": "Card form",
+                "description": {"type": "doc", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "First"}]},
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Second"}]}
+                ]},
+                "issuetype": {"name": "Story"},
+                "project": {"id": "10", "key": "WEB", "name": "Website"},
+                "status": {"name": "Won't Do", "statusCategory": {"key": "done"}},
+                "priority": {"name": "Highest"},
+                "labels": ["frontend", "frontend"],
+                "parent": {"id": "100", "key": "WEB-1"},
+                "comment": {"comments": [{"id": "9", "body": "Agreed", "author": {"emailAddress": "ada@example.com"}}]}
+            }}
+        ]
+    }"#;
+
+    let bundle = ImportService::parse("jira", export).unwrap();
+    assert_eq!(bundle.teams.len(), 1);
+    assert_eq!(bundle.teams[0].key, "WEB");
+    assert_eq!(bundle.projects.len(), 1);
+    assert_eq!(bundle.projects[0].name, "Checkout revamp");
+    assert_eq!(bundle.labels.len(), 1);
+    assert_eq!(bundle.issues.len(), 1);
+
+    let issue = &bundle.issues[0];
+    assert_eq!(issue.team_external_id, "10");
+    assert_eq!(issue.project_external_id.as_deref(), Some("100"));
+    assert_eq!(issue.description.as_deref(), Some("First\nSecond"));
+    assert_eq!(issue.priority, "urgent");
+    assert_eq!(issue.state_category.as_deref(), Some("canceled"));
+    assert_eq!(issue.comments[0].body, "Agreed");
+}
+
+#[test]
+fn parse_rejects_unknown_sources_and_empty_exports() {
+    assert!(ImportService::parse("asana", "{}").is_err());
+    assert!(ImportService::parse("linear", "not json").is_err());
+    assert!(ImportService::parse("linear", "{}").is_err());
+    assert!(ImportService::parse("jira", r#"{"issues": []}"#).is_err());
+}
+
+#[test]
+fn project_keys_are_short_and_unique() {
+    let taken: HashSet<String> = ["MA", "MA2"].iter().map(|k| k.to_string()).collect();
+    assert_eq!(ImportService::project_key("Mobile App", &taken), "MA3");
+    assert_eq!(
+        ImportService::project_key("Infrastructure", &HashSet::new()),
+        "INFRASTRUC"
+    );
+    assert_eq!(ImportService::project_key("— ✓ —", &HashSet::new()), "PRJ");
+
+    let taken: HashSet<String> = ["INFRASTRUC".to_string()].into_iter().collect();
+    assert_eq!(
+        ImportService::project_key("Infrastructure", &taken),
+        "INFRASTRU2"
+    );
+}
+
+#[test]
+fn states_match_by_name_then_category() {
+    let states = vec![
+        state("Todo", WorkflowStateCategory::Unstarted),
+        state("Doing", WorkflowStateCategory::Started),
+    ];
+    let by_name = ImportService::match_state(&states, Some("todo"), Some("started")).unwrap();
+    assert_eq!(by_name.name, "Todo");
+    let by_category =
+        ImportService::match_state(&states, Some("In Progress"), Some("started")).unwrap();
+    assert_eq!(by_category.name, "Doing");
+    assert!(ImportService::match_state(&states, Some("Review"), None).is_none());
+}
@@ -3,3 +3,113 @@ fn placeholder_role_change_validation() {
     use rust_backend::validation::workspace_member::validate_role_change;
     assert!(validate_role_change().is_ok());
 }
+
+#[test]
+fn directory_page_size_is_clamped() {
+    use rust_backend::services::workspace_members_service::{
+        DEFAULT_DIRECTORY_PAGE_SIZE, MAX_DIRECTORY_PAGE_SIZE, WorkspaceMembersService,
+    };
+    assert_eq!(
+        WorkspaceMembersService::page_size(None),
+        DEFAULT_DIRECTORY_PAGE_SIZE
+    );
+    assert_eq!(WorkspaceMembersService::page_size(Some(0)), 1);
+    assert_eq!(WorkspaceMembersService::page_size(Some(50)), 50);
+    assert_eq!(
+        WorkspaceMembersService::page_size(Some(10_000)),
+        MAX_DIRECTORY_PAGE_SIZE
+    );
+}
+
+#[test]
+fn member_changes_are_folded_per_user() {
+    use rust_backend::db::models::workspace_member::{WorkspaceMemberChange, member_change};
+    use rust_backend::services::workspace_members_service::WorkspaceMembersService;
+
+    let workspace_id = uuid::Uuid::new_v4();
+    let (alice, bob) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let change = |id, user_id, change: &str| WorkspaceMemberChange {
+        id,
+        workspace_id,
+        user_id,
+        change: change.to_string(),
+        created_at: chrono::Utc::now(),
+    };
+    let changes = vec![
+        change(7, bob, member_change::MEMBER_ADDED),
+        change(8, alice, member_change::ROLE_CHANGED),
+        change(9, bob, member_change::MEMBER_REMOVED),
+    ];
+    assert_eq!(
+        WorkspaceMembersService::touched_users(&changes),
+        vec![bob, alice]
+    );
+}
+
+#[test]
+fn cached_member_lists_are_filtered_like_the_query() {
+    use rust_backend::db::models::auth::UserBasicInfo;
+    use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+    use rust_backend::routes::workspace_members::WorkspaceMemberInfo;
+    use rust_backend::services::workspace_members_service::WorkspaceMembersService;
+
+    let now = chrono::Utc::now().naive_utc();
+    let member = |name: &str, role: WorkspaceMemberRole| {
+        let id = uuid::Uuid::new_v4();
+        WorkspaceMemberInfo {
+            id,
+            user_id: id,
+            workspace_id: uuid::Uuid::nil(),
+            role,
+            user: UserBasicInfo {
+                id,
+                name: name.to_string(),
+                username: name.to_lowercase(),
+                email: format!("{}@example.com", name.to_lowercase()),
+                avatar_url: None,
+            },
+            created_at: now,
+            updated_at: now,
+        }
+    };
+    let members = || {
+        vec![
+            member("Ada", WorkspaceMemberRole::Owner),
+            member("Grace", WorkspaceMemberRole::Member),
+            member("Linus", WorkspaceMemberRole::Member),
+        ]
+    };
+
+    let names = |members: Vec<WorkspaceMemberInfo>| -> Vec<String> {
+        members.into_iter().map(|m| m.user.name).collect()
+    };
+    assert_eq!(
+        names(WorkspaceMembersService::filter_members(
+            members(),
+            Some(WorkspaceMemberRole::Member),
+            None,
+            None
+        )),
+        vec!["Grace", "Linus"]
+    );
+    assert_eq!(
+        names(WorkspaceMembersService::filter_members(
+            members(),
+            None,
+            None,
+            Some("GRACE@")
+        )),
+        vec!["Grace"]
+    );
+    let all = members();
+    let ada = all[0].user_id;
+    assert_eq!(
+        names(WorkspaceMembersService::filter_members(
+            all,
+            Some(WorkspaceMemberRole::Member),
+            Some(ada),
+            None
+        )),
+        Vec::<String>::new()
+    );
+}
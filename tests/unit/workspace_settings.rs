@@ -0,0 +1,122 @@
+// Workspace settings defaults, storage round trip and validation tests
+
+use chrono::{Utc, Weekday};
+use rust_backend::db::models::workspace_member::WorkspaceMemberRole;
+use rust_backend::db::models::workspace_settings::{
+    WorkspaceSettings, WorkspaceSettingsRecord, week_start_days, workspace_features,
+};
+use rust_backend::services::workspace_settings_service::WorkspaceSettingsService;
+use uuid::Uuid;
+
+fn record(domains: &str, features: &str) -> WorkspaceSettingsRecord {
+    WorkspaceSettingsRecord {
+        workspace_id: Uuid::new_v4(),
+        default_workflow_id: None,
+        week_start_day: week_start_days::SUNDAY.to_string(),
+        estimate_scale: "tshirt".to_string(),
+        allowed_email_domains: domains.to_string(),
+        features: features.to_string(),
+        updated_by: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        archive_completed_after_days: Some(90),
+        auto_join_domains: "acme.com".to_string(),
+        auto_join_role: WorkspaceMemberRole::Guest,
+    }
+}
+
+#[test]
+fn workspaces_without_settings_get_the_defaults() {
+    let settings = WorkspaceSettings::defaults(Uuid::new_v4());
+    assert_eq!(settings.week_starts_on(), Weekday::Mon);
+    assert_eq!(settings.estimate_scale, "fibonacci");
+    assert!(settings.updated_at.is_none());
+    assert_eq!(settings.archive_completed_after_days, None);
+    for feature in workspace_features::ALL {
+        assert!(settings.feature_enabled(feature));
+    }
+    assert!(settings.allows_email("anyone@anywhere.example"));
+    assert!(!settings.auto_joins_email("anyone@anywhere.example"));
+    assert_eq!(settings.auto_join_role, WorkspaceMemberRole::Member);
+}
+
+#[test]
+fn stored_settings_fill_in_unset_toggles() {
+    let settings = WorkspaceSettingsService::from_record(record(
+        "acme.com contractors.acme.com",
+        r#"{"automations":false,"retired_feature":false}"#,
+    ));
+    assert_eq!(settings.week_starts_on(), Weekday::Sun);
+    assert_eq!(settings.estimate_scale, "tshirt");
+    assert_eq!(settings.archive_completed_after_days, Some(90));
+    assert!(!settings.feature_enabled(workspace_features::AUTOMATIONS));
+    assert!(settings.feature_enabled(workspace_features::CONTENT_REPORTS));
+    assert!(!settings.features.contains_key("retired_feature"));
+    assert_eq!(
+        settings.allowed_email_domains,
+        vec!["acme.com", "contractors.acme.com"]
+    );
+    assert!(settings.allows_email("ada@ACME.com"));
+    assert!(settings.allows_email("bob@contractors.acme.com"));
+    assert!(!settings.allows_email("eve@evil-acme.com"));
+    assert!(!settings.allows_email("not-an-email"));
+    assert!(settings.auto_joins_email("ada@Acme.com"));
+    assert!(!settings.auto_joins_email("bob@contractors.acme.com"));
+    assert_eq!(settings.auto_join_role, WorkspaceMemberRole::Guest);
+
+    // Unreadable toggles fall back to every feature on
+    let settings = WorkspaceSettingsService::from_record(record("", "not json"));
+    assert!(settings.feature_enabled(workspace_features::AUTOMATIONS));
+    assert!(settings.allows_email("anyone@anywhere.example"));
+}
+
+#[test]
+fn allowed_email_domains_are_normalized() {
+    let domains = WorkspaceSettingsService::validate_email_domains(&[
+        " @Acme.com ".to_string(),
+        "acme.com.".to_string(),
+        "mail.acme.com".to_string(),
+    ])
+    .unwrap();
+    assert_eq!(domains, vec!["acme.com", "mail.acme.com"]);
+
+    assert!(WorkspaceSettingsService::validate_email_domains(&["localhost".to_string()]).is_err());
+    let too_many: Vec<String> = (0..51).map(|n| format!("d{}.example.com", n)).collect();
+    assert!(WorkspaceSettingsService::validate_email_domains(&too_many).is_err());
+}
+
+#[test]
+fn auto_archive_age_can_be_left_set_or_cleared() {
+    use rust_backend::db::models::workspace_settings::UpdateWorkspaceSettingsRequest;
+
+    let unchanged: UpdateWorkspaceSettingsRequest =
+        serde_json::from_value(serde_json::json!({ "week_start_day": "sunday" })).unwrap();
+    assert_eq!(unchanged.archive_completed_after_days, None);
+
+    let set: UpdateWorkspaceSettingsRequest =
+        serde_json::from_value(serde_json::json!({ "archive_completed_after_days": 30 })).unwrap();
+    assert_eq!(set.archive_completed_after_days, Some(Some(30)));
+
+    let cleared: UpdateWorkspaceSettingsRequest =
+        serde_json::from_value(serde_json::json!({ "archive_completed_after_days": null }))
+            .unwrap();
+    assert_eq!(cleared.archive_completed_after_days, Some(None));
+}
+
+#[test]
+fn auto_join_settings_are_optional_in_updates() {
+    use rust_backend::db::models::workspace_settings::UpdateWorkspaceSettingsRequest;
+
+    let unchanged: UpdateWorkspaceSettingsRequest =
+        serde_json::from_value(serde_json::json!({ "week_start_day": "sunday" })).unwrap();
+    assert!(unchanged.auto_join_domains.is_none());
+    assert!(unchanged.auto_join_role.is_none());
+
+    let set: UpdateWorkspaceSettingsRequest = serde_json::from_value(serde_json::json!({
+        "auto_join_domains": ["acme.com"],
+        "auto_join_role": "Guest"
+    }))
+    .unwrap();
+    assert_eq!(set.auto_join_domains, Some(vec!["acme.com".to_string()]));
+    assert_eq!(set.auto_join_role, Some(WorkspaceMemberRole::Guest));
+}
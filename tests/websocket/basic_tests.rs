@@ -7,7 +7,9 @@ use url::Url;
 use uuid::Uuid;
 
 use rust_backend::websocket::{
+    BoardLocks, WebSocketCommand,
     auth::WebSocketAuth,
+    board_locks::DRAG_LOCK_TTL_SECS,
     manager::{ConnectionState, MessageType, WebSocketManager, WebSocketMessage},
 };
 
@@ -237,6 +239,263 @@ async fn test_websocket_manager_send_to_user() {
     manager.send_to_user(user_id, test_message).await;
 }
 
+#[tokio::test]
+async fn test_websocket_manager_send_to_user_is_not_broadcast() {
+    use rust_backend::websocket::manager::ConnectedUser;
+
+    let manager = WebSocketManager::new();
+    let mut rx = manager.get_broadcast_receiver();
+    let mut routed_rx = manager.get_routed_receiver();
+    let user_id = Uuid::new_v4();
+
+    let user = ConnectedUser {
+        user_id,
+        username: "target_user".to_string(),
+        connected_at: chrono::Utc::now(),
+        last_ping: chrono::Utc::now(),
+        state: ConnectionState::Connected,
+        subscriptions: std::collections::HashSet::new(),
+        message_queue: std::collections::VecDeque::new(),
+        recovery_token: None,
+        metadata: std::collections::HashMap::new(),
+        current_workspace_id: None,
+    };
+    manager
+        .add_connection("direct_connection".to_string(), user, None, None)
+        .await;
+    // Drain the join announcement
+    let _ = timeout(Duration::from_millis(100), rx.recv()).await;
+
+    let test_message = WebSocketMessage {
+        id: Some(Uuid::new_v4().to_string()),
+        message_type: MessageType::Notification,
+        data: json!({"title": "Assigned to you"}),
+        timestamp: Some(chrono::Utc::now()),
+    };
+    manager.send_to_user(user_id, test_message.clone()).await;
+
+    let routed = timeout(Duration::from_millis(100), routed_rx.recv())
+        .await
+        .expect("routed message")
+        .unwrap();
+    assert!(routed.connection_ids.contains("direct_connection"));
+    assert_eq!(routed.message.id, test_message.id);
+    assert!(
+        timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+        "direct messages must not go out on the broadcast channel"
+    );
+}
+
+fn workspace_user(workspace_id: Uuid) -> rust_backend::websocket::manager::ConnectedUser {
+    rust_backend::websocket::manager::ConnectedUser {
+        user_id: Uuid::new_v4(),
+        username: "workspace_user".to_string(),
+        connected_at: chrono::Utc::now(),
+        last_ping: chrono::Utc::now(),
+        state: ConnectionState::Connected,
+        subscriptions: std::collections::HashSet::new(),
+        message_queue: std::collections::VecDeque::new(),
+        recovery_token: None,
+        metadata: std::collections::HashMap::new(),
+        current_workspace_id: Some(workspace_id),
+    }
+}
+
+fn notification(text: &str) -> WebSocketMessage {
+    WebSocketMessage {
+        id: Some(Uuid::new_v4().to_string()),
+        message_type: MessageType::Notification,
+        data: json!({ "text": text }),
+        timestamp: None,
+    }
+}
+
+#[tokio::test]
+async fn test_websocket_manager_broadcast_to_workspace_is_routed() {
+    let manager = WebSocketManager::new();
+    let (workspace, other) = (Uuid::new_v4(), Uuid::new_v4());
+    manager
+        .add_connection("in".to_string(), workspace_user(workspace), None, None)
+        .await;
+    manager
+        .add_connection("out".to_string(), workspace_user(other), None, None)
+        .await;
+    let mut rx = manager.get_broadcast_receiver();
+    let mut routed_rx = manager.get_routed_receiver();
+
+    manager
+        .broadcast_to_workspace(workspace, notification("hello"))
+        .await;
+
+    let routed = timeout(Duration::from_millis(100), routed_rx.recv())
+        .await
+        .expect("routed message")
+        .unwrap();
+    assert_eq!(
+        *routed.connection_ids,
+        std::collections::HashSet::from(["in".to_string()])
+    );
+    assert!(timeout(Duration::from_millis(50), rx.recv()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_websocket_manager_send_to_connection_reaches_only_that_connection() {
+    let manager = WebSocketManager::new();
+    let workspace = Uuid::new_v4();
+    manager
+        .add_connection("caller".to_string(), workspace_user(workspace), None, None)
+        .await;
+    manager
+        .add_connection("peer".to_string(), workspace_user(workspace), None, None)
+        .await;
+    let mut rx = manager.get_broadcast_receiver();
+    let mut routed_rx = manager.get_routed_receiver();
+
+    let response = WebSocketMessage {
+        id: Some(Uuid::new_v4().to_string()),
+        message_type: MessageType::CommandResponse,
+        data: json!({"command_type": "query_issues", "success": true}),
+        timestamp: None,
+    };
+    manager.send_to_connection("caller", response.clone()).await;
+
+    let routed = timeout(Duration::from_millis(100), routed_rx.recv())
+        .await
+        .expect("routed message")
+        .unwrap();
+    assert_eq!(
+        *routed.connection_ids,
+        std::collections::HashSet::from(["caller".to_string()])
+    );
+    assert_eq!(routed.message.id, response.id);
+    assert!(
+        timeout(Duration::from_millis(50), rx.recv()).await.is_err(),
+        "command responses must not go out on the broadcast channel"
+    );
+}
+
+#[tokio::test]
+async fn test_websocket_manager_connection_info_reflects_connection_state() {
+    let manager = WebSocketManager::new();
+    let workspace = Uuid::new_v4();
+    let user = workspace_user(workspace);
+    let (user_id, connected_at) = (user.user_id, user.connected_at);
+    // The offline queue is keyed by the user id as connection id
+    let connection_id = user_id.to_string();
+    manager
+        .add_connection(connection_id.clone(), user, None, None)
+        .await;
+    manager
+        .subscribe_connection(&connection_id, "team:b".to_string())
+        .await;
+    manager
+        .subscribe_connection(&connection_id, "team:a".to_string())
+        .await;
+    manager.suspend_connection(&connection_id).await;
+    manager
+        .add_offline_message(user_id, notification("queued"))
+        .await;
+
+    let info = manager.get_connection_info(&connection_id).await.unwrap();
+    assert_eq!(info.connection_id, connection_id);
+    assert_eq!(info.user_id, user_id);
+    assert_eq!(info.connected_at, connected_at);
+    assert_eq!(info.subscriptions, vec!["team:a", "team:b"]);
+    assert_eq!(info.message_queue_size, 1);
+    assert_eq!(info.state, "suspended");
+    assert_eq!(info.current_workspace_id, Some(workspace));
+
+    assert!(manager.get_connection_info("missing").await.is_none());
+}
+
+#[tokio::test]
+async fn test_websocket_manager_publish_to_topic_subscribers() {
+    use rust_backend::websocket::Topic;
+
+    let manager = WebSocketManager::new();
+    let (workspace, other) = (Uuid::new_v4(), Uuid::new_v4());
+    let issue_id = Uuid::new_v4();
+    for (connection_id, workspace_id) in [
+        ("exact", workspace),
+        ("wildcard", workspace),
+        ("unsubscribed", workspace),
+        ("other_workspace", other),
+    ] {
+        manager
+            .add_connection(
+                connection_id.to_string(),
+                workspace_user(workspace_id),
+                None,
+                None,
+            )
+            .await;
+    }
+    manager
+        .subscribe_connection("exact", format!("issue:{}", issue_id))
+        .await;
+    manager
+        .subscribe_connection("wildcard", "issue:*".to_string())
+        .await;
+    manager
+        .subscribe_connection("other_workspace", "issue:*".to_string())
+        .await;
+    let mut routed_rx = manager.get_routed_receiver();
+
+    let topic = Topic::Issue(issue_id);
+    manager
+        .publish(workspace, &topic, notification("updated"))
+        .await;
+    let routed = timeout(Duration::from_millis(100), routed_rx.recv())
+        .await
+        .expect("routed message")
+        .unwrap();
+    assert_eq!(
+        *routed.connection_ids,
+        std::collections::HashSet::from(["exact".to_string(), "wildcard".to_string()])
+    );
+
+    // Unsubscribed and disconnected connections stop receiving the topic
+    manager.unsubscribe_connection("wildcard", "issue:*").await;
+    manager.remove_connection("exact").await;
+    manager
+        .publish(workspace, &topic, notification("updated again"))
+        .await;
+    assert!(
+        timeout(Duration::from_millis(50), routed_rx.recv())
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+fn test_topic_subscription_normalization() {
+    use rust_backend::websocket::Topic;
+
+    let id = Uuid::new_v4();
+    assert_eq!(
+        Topic::normalize_subscription(&format!("Issue:{}", id.to_string().to_uppercase())),
+        Some(format!("issue:{}", id))
+    );
+    assert_eq!(
+        Topic::normalize_subscription("project:*"),
+        Some("project:*".to_string())
+    );
+    assert_eq!(
+        Topic::normalize_subscription(" workspace "),
+        Some("workspace".to_string())
+    );
+    assert_eq!(Topic::normalize_subscription("issue:not-a-uuid"), None);
+    assert_eq!(Topic::normalize_subscription("cycle:*"), None);
+    assert_eq!(
+        Topic::Team(id).matching_keys(),
+        vec![
+            format!("team:{}", id),
+            "team:*".to_string(),
+            "workspace".to_string()
+        ]
+    );
+}
+
 #[tokio::test]
 async fn test_websocket_manager_multiple_users() {
     use rust_backend::websocket::manager::ConnectedUser;
@@ -295,6 +554,81 @@ async fn test_websocket_error_handling() {
     manager.update_ping("non_existent").await;
 }
 
+#[test]
+fn test_board_drag_locks_resolve_conflicts() {
+    let locks = BoardLocks::new();
+    let issue_id = Uuid::new_v4();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let now = chrono::Utc::now();
+
+    let lock = locks.acquire(issue_id, alice, now);
+    assert_eq!(lock.owner_id, Some(alice));
+    assert!(!lock.conflict);
+
+    // A second drag flags the conflict; the earlier holder keeps the move
+    let lock = locks.acquire(issue_id, bob, now + chrono::Duration::seconds(1));
+    assert!(lock.conflict);
+    assert_eq!(lock.holders, vec![alice, bob]);
+    assert_eq!(locks.owner(issue_id, now), Some(alice));
+
+    // Refreshing does not change who acquired first
+    locks.acquire(issue_id, alice, now + chrono::Duration::seconds(2));
+    assert_eq!(
+        locks.owner(issue_id, now + chrono::Duration::seconds(2)),
+        Some(alice)
+    );
+
+    let lock = locks.release(issue_id, alice, now + chrono::Duration::seconds(3));
+    assert_eq!(lock.owner_id, Some(bob));
+    assert!(!lock.conflict);
+
+    // Holds expire without a release
+    let later = now + chrono::Duration::seconds(DRAG_LOCK_TTL_SECS + 2);
+    assert_eq!(locks.owner(issue_id, later), None);
+    assert!(locks.release(issue_id, bob, later).holders.is_empty());
+}
+
+#[test]
+fn test_board_drag_lock_ties_break_by_user_id() {
+    let locks = BoardLocks::new();
+    let issue_id = Uuid::new_v4();
+    let now = chrono::Utc::now();
+    let mut users = [Uuid::new_v4(), Uuid::new_v4()];
+    locks.acquire(issue_id, users[1], now);
+    locks.acquire(issue_id, users[0], now);
+    users.sort();
+    assert_eq!(locks.owner(issue_id, now), Some(users[0]));
+}
+
+#[test]
+fn test_reorder_issue_command_parsing() {
+    let issue_id = Uuid::new_v4();
+    let command: WebSocketCommand = serde_json::from_value(json!({
+        "type": "reorder_issue",
+        "issue_id": issue_id,
+        "data": {"position": 1.5, "expected_version": 3},
+        "request_id": "r1"
+    }))
+    .unwrap();
+    match command {
+        WebSocketCommand::ReorderIssue {
+            issue_id: id, data, ..
+        } => {
+            assert_eq!(id, issue_id);
+            assert_eq!(data.position, 1.5);
+            assert_eq!(data.expected_version, Some(3));
+            assert!(data.workflow_state_id.is_none());
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(
+        serde_json::from_value::<WebSocketCommand>(
+            json!({"type": "start_issue_drag", "issue_id": issue_id})
+        )
+        .is_ok()
+    );
+}
+
 // Integration test helper functions
 fn create_test_jwt(user_id: Uuid, username: &str, secret: &str) -> String {
     use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
@@ -340,6 +674,114 @@ async fn test_jwt_token_creation() {
     assert!(!parts[2].is_empty()); // signature
 }
 
+#[tokio::test]
+async fn test_frame_stats_by_message_kind() {
+    use rust_backend::websocket::{WebSocketMonitor, manager::encode_frame};
+
+    let label_refresh = WebSocketMessage {
+        id: None,
+        message_type: MessageType::CommandResponse,
+        data: json!({"command_type": "query_labels", "data": {"labels": ["a", "b", "c"]}}),
+        timestamp: None,
+    };
+    let topic_event = WebSocketMessage {
+        id: None,
+        message_type: MessageType::TopicEvent,
+        data: json!({"topic": "team:1", "event": "issue_updated", "data": {}}),
+        timestamp: None,
+    };
+    let ping = WebSocketMessage {
+        id: None,
+        message_type: MessageType::Ping,
+        data: json!({}),
+        timestamp: None,
+    };
+    assert_eq!(label_refresh.frame_kind(), "command_response:query_labels");
+    assert_eq!(topic_event.frame_kind(), "topic_event:issue_updated");
+    assert_eq!(ping.frame_kind(), "ping");
+
+    let monitor = WebSocketMonitor::default();
+    let label_text = encode_frame(&label_refresh, Some(&monitor)).unwrap();
+    encode_frame(&label_refresh, Some(&monitor)).unwrap();
+    let ping_text = encode_frame(&ping, Some(&monitor)).unwrap();
+
+    let stats = monitor.get_frame_stats();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].kind, "command_response:query_labels");
+    assert_eq!(stats[0].frames, 2);
+    assert_eq!(stats[0].total_bytes, 2 * label_text.len() as u64);
+    assert_eq!(stats[0].max_bytes, label_text.len() as u64);
+    assert_eq!(stats[1].kind, "ping");
+    assert_eq!(stats[1].total_bytes, ping_text.len() as u64);
+
+    monitor.reset_metrics().await;
+    assert!(monitor.get_frame_stats().is_empty());
+}
+
+#[tokio::test]
+async fn test_frame_compression_above_threshold() {
+    use axum::extract::ws::Message;
+    use rust_backend::websocket::{
+        Compression, FrameCompressor, WebSocketMonitor, manager::encode_frame,
+    };
+
+    assert_eq!(
+        Compression::negotiate(Some("zstd, Deflate")),
+        Some(Compression::Deflate)
+    );
+    assert_eq!(Compression::negotiate(Some("br")), None);
+    assert_eq!(Compression::negotiate(None), None);
+
+    let issues: Vec<_> = (0..200)
+        .map(|i| json!({"id": i, "title": format!("Issue {}", i), "state": "todo"}))
+        .collect();
+    let query_issues = WebSocketMessage {
+        id: None,
+        message_type: MessageType::CommandResponse,
+        data: json!({"command_type": "query_issues", "data": {"issues": issues}}),
+        timestamp: None,
+    };
+    let text = encode_frame(&query_issues, None).unwrap();
+
+    let monitor = WebSocketMonitor::default();
+    let compressor = FrameCompressor::new(Some(Compression::Deflate), 1024);
+    let compressed = match compressor.to_message(text.clone(), Some(&monitor)) {
+        Message::Binary(bytes) => bytes,
+        other => panic!("expected a binary frame, got {:?}", other),
+    };
+    assert!(compressed.len() < text.len());
+    assert_eq!(
+        Compression::Deflate.decompress(&compressed).unwrap(),
+        text.as_bytes()
+    );
+
+    // 低于阈值、未协商压缩的连接都保持文本帧
+    assert!(matches!(
+        compressor.to_message("{}".to_string(), Some(&monitor)),
+        Message::Text(_)
+    ));
+    assert!(matches!(
+        FrameCompressor::new(None, 0).to_message(text.clone(), Some(&monitor)),
+        Message::Text(_)
+    ));
+    // 压缩后反而变大的负载按原文发送
+    assert!(matches!(
+        FrameCompressor::new(Some(Compression::Deflate), 0)
+            .to_message("{}".to_string(), Some(&monitor)),
+        Message::Text(_)
+    ));
+
+    let stats = monitor.get_compression_stats();
+    assert_eq!(stats.compressed_frames, 1);
+    assert_eq!(stats.skipped_frames, 1);
+    assert_eq!(stats.original_bytes, text.len() as u64);
+    assert_eq!(stats.compressed_bytes, compressed.len() as u64);
+    assert!(stats.compression_ratio > 1.0);
+
+    monitor.reset_metrics().await;
+    assert_eq!(monitor.get_compression_stats().compressed_frames, 0);
+}
+
 // Performance and stress tests
 #[tokio::test]
 async fn test_websocket_manager_performance() {
@@ -474,6 +916,7 @@ mod integration_tests {
                 username: Some("johndoe_updated".to_string()),
                 email: Some("john.updated@example.com".to_string()),
                 avatar_url: Some("https://example.com/avatar.jpg".to_string()),
+                asset_region: None,
             },
             request_id: Some("req_123".to_string()),
         };
@@ -502,6 +945,7 @@ mod integration_tests {
                 username: None,
                 email: None,
                 avatar_url: Some("https://example.com/new-avatar.jpg".to_string()),
+                asset_region: None,
             },
             request_id: Some("req_124".to_string()),
         };
@@ -546,6 +990,7 @@ mod integration_tests {
                 username: Some("updated_username".to_string()),
                 email: None,
                 avatar_url: Some("https://example.com/new-avatar.jpg".to_string()),
+                asset_region: None,
             },
             request_id: Some("profile_update_123".to_string()),
         };
@@ -649,6 +1094,7 @@ mod integration_tests {
             filters: ProjectFilters {
                 search: Some("test".to_string()),
                 owner_id: Some(Uuid::new_v4()),
+                fields: None,
             },
             request_id: Some("query_projects_123".to_string()),
         };
@@ -669,3 +1115,75 @@ mod integration_tests {
         }
     }
 }
+
+#[test]
+fn test_apply_issue_patch_command_parsing() {
+    use rust_backend::utils::json_patch::PatchDocument;
+
+    let issue_id = Uuid::new_v4();
+    let command: WebSocketCommand = serde_json::from_value(json!({
+        "type": "apply_issue_patch",
+        "issue_id": issue_id,
+        "patch": [
+            {"op": "test", "path": "/title", "value": "Old"},
+            {"op": "replace", "path": "/title", "value": "New"}
+        ],
+        "request_id": "r1"
+    }))
+    .unwrap();
+    match command {
+        WebSocketCommand::ApplyIssuePatch {
+            issue_id: id,
+            patch: PatchDocument::JsonPatch(operations),
+            ..
+        } => {
+            assert_eq!(id, issue_id);
+            assert_eq!(operations.len(), 2);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+
+    let merge: WebSocketCommand = serde_json::from_value(json!({
+        "type": "apply_issue_patch",
+        "issue_id": issue_id,
+        "patch": {"assignee_id": null}
+    }))
+    .unwrap();
+    assert!(matches!(
+        merge,
+        WebSocketCommand::ApplyIssuePatch {
+            patch: PatchDocument::MergePatch(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_delete_team_command_confirmation_token() {
+    let team_id = Uuid::new_v4();
+    let request: WebSocketCommand = serde_json::from_value(json!({
+        "type": "delete_team",
+        "team_id": team_id
+    }))
+    .unwrap();
+    assert!(matches!(
+        request,
+        WebSocketCommand::DeleteTeam {
+            confirmation_token: None,
+            ..
+        }
+    ));
+
+    let confirm: WebSocketCommand = serde_json::from_value(json!({
+        "type": "delete_team",
+        "team_id": team_id,
+        "confirmation_token": "del_abc"
+    }))
+    .unwrap();
+    match confirm {
+        WebSocketCommand::DeleteTeam {
+            confirmation_token, ..
+        } => assert_eq!(confirmation_token.as_deref(), Some("del_abc")),
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
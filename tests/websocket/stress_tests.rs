@@ -583,6 +583,91 @@ async fn stress_test_websocket_manager_subscription_performance() {
     assert!(duration < Duration::from_secs(10));
 }
 
+/// Compare single-lock vs sharded managers at high connection counts.
+/// Run with `cargo test stress_test_sharded_manager -- --ignored --nocapture`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+#[ignore = "benchmark: 50k connections"]
+async fn stress_test_sharded_manager_50k_connections() {
+    let num_connections = 50_000;
+    let workspaces: Vec<Uuid> = (0..64).map(|_| Uuid::new_v4()).collect();
+
+    for shard_count in [1usize, 32] {
+        let manager = WebSocketManager::with_shards(shard_count);
+
+        let start_time = Instant::now();
+        let mut handles = Vec::new();
+        for worker in 0..16 {
+            let manager = manager.clone();
+            let workspaces = workspaces.clone();
+            handles.push(tokio::spawn(async move {
+                for i in (worker..num_connections).step_by(16) {
+                    let user = rust_backend::websocket::manager::ConnectedUser {
+                        user_id: Uuid::new_v4(),
+                        username: format!("bench_user_{}", i),
+                        connected_at: chrono::Utc::now(),
+                        last_ping: chrono::Utc::now(),
+                        state: rust_backend::websocket::manager::ConnectionState::Connected,
+                        subscriptions: std::collections::HashSet::from([format!(
+                            "topic_{}",
+                            i % 1000
+                        )]),
+                        message_queue: std::collections::VecDeque::new(),
+                        recovery_token: None,
+                        metadata: std::collections::HashMap::new(),
+                        current_workspace_id: Some(workspaces[i % workspaces.len()]),
+                    };
+                    manager
+                        .add_connection(format!("bench_connection_{}", i), user, None, None)
+                        .await;
+                }
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let connect_duration = start_time.elapsed();
+        assert_eq!(manager.get_connection_count().await, num_connections);
+
+        // Concurrent pings and workspace broadcasts against the full connection set
+        let start_time = Instant::now();
+        let mut handles = Vec::new();
+        for worker in 0..16 {
+            let manager = manager.clone();
+            let workspaces = workspaces.clone();
+            handles.push(tokio::spawn(async move {
+                for i in 0..200 {
+                    manager
+                        .update_ping(&format!("bench_connection_{}", worker * 200 + i))
+                        .await;
+                    if i % 20 == 0 {
+                        let message = WebSocketMessage {
+                            id: None,
+                            message_type: MessageType::Notification,
+                            data: json!({ "sequence": i }),
+                            timestamp: None,
+                        };
+                        manager
+                            .broadcast_to_workspace(workspaces[i % workspaces.len()], message)
+                            .await;
+                    }
+                }
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let broadcast_duration = start_time.elapsed();
+
+        let shard_stats = manager.get_shard_stats().await;
+        let busiest = shard_stats.iter().map(|s| s.connections).max().unwrap();
+        println!(
+            "Sharded manager ({} shards): connect {:?}, ping+broadcast {:?}, busiest shard {} connections",
+            shard_count, connect_duration, broadcast_duration, busiest
+        );
+        assert_eq!(shard_stats.len(), shard_count);
+    }
+}
+
 // Integration stress tests (require running server)
 #[cfg(test)]
 mod integration_stress_tests {